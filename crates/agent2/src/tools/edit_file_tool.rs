@@ -931,7 +931,8 @@ mod tests {
                         .project
                         .all_languages
                         .defaults
-                        .remove_trailing_whitespace_on_save = Some(true);
+                        .remove_trailing_whitespace_on_save =
+                        Some(settings::RemoveTrailingWhitespaceOnSave::All);
                 });
             });
         });
@@ -988,7 +989,8 @@ mod tests {
                         .project
                         .all_languages
                         .defaults
-                        .remove_trailing_whitespace_on_save = Some(false);
+                        .remove_trailing_whitespace_on_save =
+                        Some(settings::RemoveTrailingWhitespaceOnSave::None);
                 });
             });
         });
@@ -2704,6 +2704,7 @@ impl FollowableItem for TextThreadEditor {
                                 scroll_top_anchor: editor_state.scroll_top_anchor,
                                 scroll_x: editor_state.scroll_y,
                                 scroll_y: editor_state.scroll_y,
+                                highlights: editor_state.highlights,
                                 ..Default::default()
                             }),
                             window,
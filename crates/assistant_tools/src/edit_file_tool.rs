@@ -1677,7 +1677,8 @@ mod tests {
                         .project
                         .all_languages
                         .defaults
-                        .remove_trailing_whitespace_on_save = Some(true);
+                        .remove_trailing_whitespace_on_save =
+                        Some(settings::RemoveTrailingWhitespaceOnSave::All);
                 });
             });
         });
@@ -1740,7 +1741,8 @@ mod tests {
                         .project
                         .all_languages
                         .defaults
-                        .remove_trailing_whitespace_on_save = Some(false);
+                        .remove_trailing_whitespace_on_save =
+                        Some(settings::RemoveTrailingWhitespaceOnSave::None);
                 });
             });
         });
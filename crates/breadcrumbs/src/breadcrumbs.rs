@@ -1,11 +1,11 @@
-use editor::Editor;
+use editor::{Anchor, Editor};
 use gpui::{
     Context, Element, EventEmitter, Focusable, FontWeight, IntoElement, ParentElement, Render,
     StyledText, Subscription, Window,
 };
 use itertools::Itertools;
 use settings::Settings;
-use std::cmp;
+use std::{cmp, ops::Range};
 use theme::ActiveTheme;
 use ui::{ButtonLike, ButtonStyle, Label, Tooltip, prelude::*};
 use workspace::{
@@ -51,10 +51,28 @@ impl Render for Breadcrumbs {
             return element;
         };
 
-        let Some(mut segments) = active_item.breadcrumbs(cx.theme(), cx) else {
+        let Some(segments) = active_item.breadcrumbs(cx.theme(), cx) else {
             return element;
         };
 
+        // An editor's breadcrumbs are its file followed by the symbols containing the cursor,
+        // which can be clicked to select them.
+        let editor = active_item.downcast::<Editor>();
+        let symbol_ranges = editor
+            .as_ref()
+            .map(|editor| editor.read(cx).breadcrumb_symbol_ranges(cx))
+            .unwrap_or_default();
+        let mut segments = segments
+            .into_iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                let symbol_range = index
+                    .checked_sub(1)
+                    .and_then(|index| symbol_ranges.get(index).cloned());
+                (symbol_range, segment)
+            })
+            .collect::<Vec<(Option<Range<Anchor>>, BreadcrumbText)>>();
+
         let prefix_end_ix = cmp::min(segments.len(), MAX_SEGMENTS / 2);
         let suffix_start_ix = cmp::max(
             prefix_end_ix,
@@ -64,15 +82,19 @@ impl Render for Breadcrumbs {
         if suffix_start_ix > prefix_end_ix {
             segments.splice(
                 prefix_end_ix..suffix_start_ix,
-                Some(BreadcrumbText {
-                    text: "⋯".into(),
-                    highlights: None,
-                    font: None,
-                }),
+                Some((
+                    None,
+                    BreadcrumbText {
+                        text: "⋯".into(),
+                        highlights: None,
+                        font: None,
+                    },
+                )),
             );
         }
 
         let highlighted_segments = segments.into_iter().enumerate().map(|(index, segment)| {
+            let (symbol_range, segment) = segment;
             let mut text_style = window.text_style();
             if let Some(ref font) = segment.font {
                 text_style.font_family = font.family.clone();
@@ -90,9 +112,35 @@ impl Render for Breadcrumbs {
                 return styled_element;
             }
 
-            StyledText::new(segment.text.replace('\n', "⏎"))
+            let text = StyledText::new(segment.text.replace('\n', "⏎"))
                 .with_default_highlights(&text_style, segment.highlights.unwrap_or_default())
-                .into_any()
+                .into_any();
+            match symbol_range.zip(editor.as_ref()) {
+                Some((symbol_range, editor)) => {
+                    let editor = editor.downgrade();
+                    div()
+                        .id(("breadcrumb-symbol", index))
+                        .rounded_xs()
+                        .cursor_pointer()
+                        .hover(|style| style.bg(cx.theme().colors().ghost_element_hover))
+                        .child(text)
+                        .tooltip(Tooltip::text("Select Symbol"))
+                        .on_click(move |_, window, cx| {
+                            cx.stop_propagation();
+                            editor
+                                .update(cx, |editor, cx| {
+                                    editor.select_breadcrumb_symbol(
+                                        symbol_range.clone(),
+                                        window,
+                                        cx,
+                                    )
+                                })
+                                .ok();
+                        })
+                        .into_any_element()
+                }
+                None => text,
+            }
         });
         let breadcrumbs = Itertools::intersperse_with(highlighted_segments, || {
             Label::new("›").color(Color::Placeholder).into_any_element()
@@ -16,6 +16,12 @@ use util::ResultExt;
 
 pub static CALCULATE_DIFF_TASK: LazyLock<TaskLabel> = LazyLock::new(TaskLabel::new);
 
+/// Modified hunks spanning more rows than this on either side aren't diffed word by word.
+const MAX_WORD_DIFF_ROWS: usize = 8;
+
+/// Modified hunks with more bytes than this on either side aren't diffed word by word.
+const MAX_WORD_DIFF_LEN: usize = 4096;
+
 pub struct BufferDiff {
     pub buffer_id: BufferId,
     inner: BufferDiffInner,
@@ -68,6 +74,11 @@ pub struct DiffHunk {
     /// The range in the buffer's diff base text to which this hunk corresponds.
     pub diff_base_byte_range: Range<usize>,
     pub secondary_status: DiffHunkSecondaryStatus,
+    /// The ranges of the words in the buffer that differ from the diff base, for modified hunks
+    /// that are small enough to be diffed word by word.
+    pub buffer_word_diffs: Vec<Range<Anchor>>,
+    /// The ranges of the words in the diff base that differ from the buffer.
+    pub base_word_diffs: Vec<Range<usize>>,
 }
 
 /// We store [`InternalDiffHunk`]s internally so we don't need to store the additional row range.
@@ -75,6 +86,8 @@ pub struct DiffHunk {
 struct InternalDiffHunk {
     buffer_range: Range<Anchor>,
     diff_base_byte_range: Range<usize>,
+    buffer_word_diffs: Vec<Range<Anchor>>,
+    base_word_diffs: Vec<Range<usize>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -534,11 +547,15 @@ impl BufferDiffInner {
             [
                 (
                     &hunk.buffer_range.start,
-                    (hunk.buffer_range.start, hunk.diff_base_byte_range.start),
+                    (
+                        hunk.buffer_range.start,
+                        hunk.diff_base_byte_range.start,
+                        hunk,
+                    ),
                 ),
                 (
                     &hunk.buffer_range.end,
-                    (hunk.buffer_range.end, hunk.diff_base_byte_range.end),
+                    (hunk.buffer_range.end, hunk.diff_base_byte_range.end, hunk),
                 ),
             ]
         });
@@ -557,8 +574,8 @@ impl BufferDiffInner {
         let mut summaries = buffer.summaries_for_anchors_with_payload::<Point, _, _>(anchor_iter);
         iter::from_fn(move || {
             loop {
-                let (start_point, (start_anchor, start_base)) = summaries.next()?;
-                let (mut end_point, (mut end_anchor, end_base)) = summaries.next()?;
+                let (start_point, (start_anchor, start_base, hunk)) = summaries.next()?;
+                let (mut end_point, (mut end_anchor, end_base, _)) = summaries.next()?;
 
                 if !start_anchor.is_valid(buffer) {
                     continue;
@@ -629,6 +646,8 @@ impl BufferDiffInner {
                     diff_base_byte_range: start_base..end_base,
                     buffer_range: start_anchor..end_anchor,
                     secondary_status,
+                    buffer_word_diffs: hunk.buffer_word_diffs.clone(),
+                    base_word_diffs: hunk.base_word_diffs.clone(),
                 });
             }
         })
@@ -659,6 +678,8 @@ impl BufferDiffInner {
                 buffer_range: hunk.buffer_range.clone(),
                 // The secondary status is not used by callers of this method.
                 secondary_status: DiffHunkSecondaryStatus::NoSecondaryHunk,
+                buffer_word_diffs: hunk.buffer_word_diffs.clone(),
+                base_word_diffs: hunk.base_word_diffs.clone(),
             })
         })
     }
@@ -755,6 +776,8 @@ fn compute_hunks(
                 InternalDiffHunk {
                     buffer_range: buffer.anchor_before(0)..buffer.anchor_before(0),
                     diff_base_byte_range: 0..diff_base.len() - 1,
+                    buffer_word_diffs: Vec::new(),
+                    base_word_diffs: Vec::new(),
                 },
                 &buffer,
             );
@@ -779,6 +802,8 @@ fn compute_hunks(
             InternalDiffHunk {
                 buffer_range: Anchor::MIN..Anchor::MAX,
                 diff_base_byte_range: 0..0,
+                buffer_word_diffs: Vec::new(),
+                base_word_diffs: Vec::new(),
             },
             &buffer,
         );
@@ -857,12 +882,62 @@ fn process_patch_hunk(
     let start = Point::new(buffer_row_range.start, 0);
     let end = Point::new(buffer_row_range.end, 0);
     let buffer_range = buffer.anchor_before(start)..buffer.anchor_before(end);
+    let (buffer_word_diffs, base_word_diffs) =
+        compute_word_diffs(diff_base, &diff_base_byte_range, buffer, start..end);
     InternalDiffHunk {
         buffer_range,
         diff_base_byte_range,
+        buffer_word_diffs,
+        base_word_diffs,
     }
 }
 
+/// Diffs the words of a modified hunk, returning the ranges of the words that changed in the
+/// buffer and in the diff base. Hunks that add or delete whole lines, or that are too big for the
+/// word diff to be useful, aren't diffed.
+fn compute_word_diffs(
+    diff_base: &Rope,
+    diff_base_byte_range: &Range<usize>,
+    buffer: &text::BufferSnapshot,
+    buffer_point_range: Range<Point>,
+) -> (Vec<Range<Anchor>>, Vec<Range<usize>>) {
+    let buffer_byte_range = buffer_point_range.to_offset(buffer);
+    let base_rows = diff_base.offset_to_point(diff_base_byte_range.end).row
+        - diff_base.offset_to_point(diff_base_byte_range.start).row;
+    let buffer_rows = buffer_point_range.end.row - buffer_point_range.start.row;
+    if diff_base_byte_range.is_empty()
+        || buffer_byte_range.is_empty()
+        || diff_base_byte_range.len() > MAX_WORD_DIFF_LEN
+        || buffer_byte_range.len() > MAX_WORD_DIFF_LEN
+        || base_rows as usize > MAX_WORD_DIFF_ROWS
+        || buffer_rows as usize > MAX_WORD_DIFF_ROWS
+    {
+        return (Vec::new(), Vec::new());
+    }
+
+    let base_text = diff_base
+        .chunks_in_range(diff_base_byte_range.clone())
+        .collect::<String>();
+    let buffer_text = buffer
+        .text_for_range(buffer_byte_range.clone())
+        .collect::<String>();
+    let (base_changes, buffer_changes) = language::word_diff(&base_text, &buffer_text);
+    let buffer_word_diffs = buffer_changes
+        .into_iter()
+        .map(|range| {
+            buffer.anchor_after(buffer_byte_range.start + range.start)
+                ..buffer.anchor_before(buffer_byte_range.start + range.end)
+        })
+        .collect();
+    let base_word_diffs = base_changes
+        .into_iter()
+        .map(|range| {
+            diff_base_byte_range.start + range.start..diff_base_byte_range.start + range.end
+        })
+        .collect();
+    (buffer_word_diffs, base_word_diffs)
+}
+
 impl std::fmt::Debug for BufferDiff {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BufferChangeSet")
@@ -1423,6 +1498,36 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_buffer_diff_word_diffs(cx: &mut gpui::TestAppContext) {
+        let diff_base = "one\ntwo three\nfour\n";
+        let buffer_text = "one\ntwo THREE\nfour\nfive\n";
+        let buffer = Buffer::new(0, BufferId::new(1).unwrap(), buffer_text.to_string());
+        let diff = BufferDiffSnapshot::new_sync(buffer.clone(), diff_base.to_string(), cx);
+        let word_diffs = diff
+            .hunks_intersecting_range(Anchor::MIN..Anchor::MAX, &buffer)
+            .map(|hunk| {
+                (
+                    hunk.base_word_diffs
+                        .iter()
+                        .map(|range| &diff_base[range.clone()])
+                        .collect::<Vec<_>>(),
+                    hunk.buffer_word_diffs
+                        .iter()
+                        .map(|range| buffer.text_for_range(range.clone()).collect::<String>())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            word_diffs,
+            [
+                (vec!["three"], vec!["THREE".to_string()]),
+                (Vec::new(), Vec::new()),
+            ]
+        );
+    }
+
     #[gpui::test]
     async fn test_buffer_diff_with_secondary(cx: &mut gpui::TestAppContext) {
         let head_text = "
@@ -0,0 +1,327 @@
+use editor::{ClipboardHistoryEntry, Editor, actions::PasteFromHistory, clipboard_history};
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity,
+    Window,
+};
+use picker::{Picker, PickerDelegate};
+use std::sync::Arc;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::ModalView;
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(ClipboardHistoryView::register).detach();
+}
+
+pub struct ClipboardHistoryView {
+    picker: Entity<Picker<ClipboardHistoryDelegate>>,
+}
+
+impl ClipboardHistoryView {
+    fn register(editor: &mut Editor, _window: Option<&mut Window>, cx: &mut Context<Editor>) {
+        if !editor.mode().is_full() {
+            return;
+        }
+        let editor_handle = cx.weak_entity();
+        editor
+            .register_action(move |_: &PasteFromHistory, window, cx| {
+                Self::toggle(&editor_handle, window, cx);
+            })
+            .detach();
+    }
+
+    fn toggle(editor: &WeakEntity<Editor>, window: &mut Window, cx: &mut App) {
+        let entries = clipboard_history(cx);
+        if entries.is_empty() {
+            return;
+        }
+        let Some(workspace) = editor
+            .read_with(cx, |editor, _| editor.workspace())
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+
+        let editor = editor.clone();
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                ClipboardHistoryView::new(editor, entries, window, cx)
+            });
+        })
+    }
+
+    fn new(
+        editor: WeakEntity<Editor>,
+        entries: Vec<ClipboardHistoryEntry>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = ClipboardHistoryDelegate::new(cx.entity().downgrade(), editor, entries);
+        let picker = cx.new(|cx| {
+            Picker::uniform_list(delegate, window, cx).max_height(Some(vh(0.75, window)))
+        });
+        Self { picker }
+    }
+}
+
+impl Render for ClipboardHistoryView {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for ClipboardHistoryView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for ClipboardHistoryView {}
+impl ModalView for ClipboardHistoryView {}
+
+pub struct ClipboardHistoryDelegate {
+    clipboard_history_view: WeakEntity<ClipboardHistoryView>,
+    editor: WeakEntity<Editor>,
+    entries: Vec<ClipboardHistoryEntry>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl ClipboardHistoryDelegate {
+    fn new(
+        clipboard_history_view: WeakEntity<ClipboardHistoryView>,
+        editor: WeakEntity<Editor>,
+        entries: Vec<ClipboardHistoryEntry>,
+    ) -> Self {
+        let candidates = entries
+            .iter()
+            .enumerate()
+            .map(|(id, entry)| StringMatchCandidate::new(id, &preview(&entry.text)))
+            .collect();
+        Self {
+            clipboard_history_view,
+            editor,
+            entries,
+            candidates,
+            matches: Vec::new(),
+            selected_index: 0,
+        }
+    }
+}
+
+/// Renders an entry on a single line, keeping line breaks visible.
+fn preview(text: &str) -> String {
+    text.trim_end_matches('\n').replace('\n', "⏎")
+}
+
+impl PickerDelegate for ClipboardHistoryDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search clipboard history…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(entry) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|mat| self.entries.get(mat.candidate_id))
+        {
+            self.editor
+                .update(cx, |editor, cx| {
+                    window.focus(&editor.focus_handle(cx));
+                    editor.paste_clipboard_history_entry(entry, window, cx);
+                })
+                .log_err();
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.clipboard_history_view
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let background = cx.background_executor().clone();
+        let candidates = self.candidates.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, candidate)| StringMatch {
+                        candidate_id: index,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                let mut matches = match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await;
+                // Keep the most recent entries first among the matches.
+                matches.sort_unstable_by_key(|mat| mat.candidate_id);
+                matches
+            };
+
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let entry = self.entries.get(mat.candidate_id)?;
+        let selection_count = entry.selection_count();
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(HighlightedLabel::new(mat.string.clone(), mat.positions.clone()).truncate())
+                .when(selection_count > 1, |item| {
+                    item.end_slot(
+                        Label::new(format!("{selection_count} selections"))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::{
+        SelectionEffects,
+        actions::{Copy, SelectAll},
+    };
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::Project;
+    use workspace::{AppState, Workspace};
+
+    #[gpui::test]
+    async fn test_paste_from_history(cx: &mut TestAppContext) {
+        init_test(cx);
+        let project = Project::test(project::FakeFs::new(cx.executor()), [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let editor = workspace.update_in(cx, |workspace, window, cx| {
+            let editor = cx.new(|cx| Editor::multi_line(window, cx));
+            workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+            editor
+        });
+        editor.update_in(cx, |editor, window, cx| {
+            editor.set_text("first\nsecond\n", window, cx);
+            for range in [0..5, 6..12] {
+                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.select_ranges([range])
+                });
+                editor.copy(&Copy, window, cx);
+            }
+        });
+
+        cx.dispatch_action(PasteFromHistory);
+        let picker = open_picker(&workspace, cx);
+        assert_eq!(entry_labels(&picker, cx), vec!["second", "first"]);
+
+        cx.dispatch_action(menu::SelectNext);
+        editor.update_in(cx, |editor, window, cx| {
+            editor.select_all(&SelectAll, window, cx)
+        });
+        cx.dispatch_action(menu::Confirm);
+        cx.run_until_parked();
+        assert_eq!(editor.update(cx, |editor, cx| editor.text(cx)), "first");
+    }
+
+    fn open_picker(
+        workspace: &Entity<Workspace>,
+        cx: &mut VisualTestContext,
+    ) -> Entity<Picker<ClipboardHistoryDelegate>> {
+        cx.run_until_parked();
+        workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_modal::<ClipboardHistoryView>(cx)
+                .unwrap()
+                .read(cx)
+                .picker
+                .clone()
+        })
+    }
+
+    fn entry_labels(
+        picker: &Entity<Picker<ClipboardHistoryDelegate>>,
+        cx: &mut VisualTestContext,
+    ) -> Vec<String> {
+        picker.read_with(cx, |picker, _| {
+            picker
+                .delegate
+                .matches
+                .iter()
+                .map(|mat| mat.string.clone())
+                .collect()
+        })
+    }
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            language::init(cx);
+            crate::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            state
+        })
+    }
+}
@@ -17,12 +17,12 @@ use editor::{
 use fs::Fs;
 use futures::{SinkExt, StreamExt, channel::mpsc, lock::Mutex};
 use git::repository::repo_path;
-use gpui::{App, Rgba, TestAppContext, UpdateGlobal, VisualContext, VisualTestContext};
+use gpui::{App, Entity, Rgba, TestAppContext, UpdateGlobal, VisualContext, VisualTestContext};
 use indoc::indoc;
 use language::FakeLspAdapter;
 use lsp::LSP_REQUEST_TIMEOUT;
 use project::{
-    ProjectPath, SERVER_PROGRESS_THROTTLE_TIMEOUT,
+    Project, ProjectPath, SERVER_PROGRESS_THROTTLE_TIMEOUT,
     lsp_store::lsp_ext_command::{ExpandedMacro, LspExtExpandMacro},
 };
 use recent_projects::disconnected_overlay::DisconnectedOverlay;
@@ -3985,6 +3985,138 @@ async fn test_add_breakpoints(cx_a: &mut TestAppContext, cx_b: &mut TestAppConte
     assert_eq!(breakpoints_a, breakpoints_b);
 }
 
+#[gpui::test]
+async fn test_share_bookmarks(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
+    let executor = cx_a.executor();
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+    let active_call_b = cx_b.read(ActiveCall::global);
+    cx_a.update(editor::init);
+    cx_b.update(editor::init);
+    client_a
+        .fs()
+        .insert_tree(
+            "/a",
+            json!({
+                "test.txt": "one\ntwo\nthree\nfour\nfive",
+            }),
+        )
+        .await;
+    let (project_a, worktree_id) = client_a.build_local_project("/a", cx_a).await;
+    let project_path = ProjectPath {
+        worktree_id,
+        path: rel_path(&"test.txt").into(),
+    };
+
+    active_call_a
+        .update(cx_a, |call, cx| call.set_location(Some(&project_a), cx))
+        .await
+        .unwrap();
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    active_call_b
+        .update(cx_b, |call, cx| call.set_location(Some(&project_b), cx))
+        .await
+        .unwrap();
+    let (workspace_a, cx_a) = client_a.build_workspace(&project_a, cx_a);
+    let (workspace_b, cx_b) = client_b.build_workspace(&project_b, cx_b);
+
+    let editor_a = workspace_a
+        .update_in(cx_a, |workspace, window, cx| {
+            workspace.open_path(project_path.clone(), None, true, window, cx)
+        })
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    let editor_b = workspace_b
+        .update_in(cx_b, |workspace, window, cx| {
+            workspace.open_path(project_path.clone(), None, true, window, cx)
+        })
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    let bookmarks = |project: &Entity<Project>, cx: &mut VisualTestContext| {
+        project.read_with(cx, |project, cx| {
+            project
+                .bookmark_store()
+                .read(cx)
+                .all_bookmarks()
+                .flat_map(|(buffer, bookmarks)| {
+                    let snapshot = buffer.read(cx).snapshot();
+                    bookmarks
+                        .iter()
+                        .map(|bookmark| {
+                            (
+                                bookmark.position.summary::<Point>(&snapshot).row,
+                                bookmark.author,
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // Client A bookmarks the first line.
+    editor_a.update_in(cx_a, |editor, window, cx| {
+        editor.toggle_bookmark(&editor::actions::ToggleBookmark, window, cx);
+    });
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    let user_a = Some(client_a.user_id().unwrap());
+    assert_eq!(bookmarks(&project_a, cx_a), vec![(0, user_a)]);
+    assert_eq!(bookmarks(&project_b, cx_b), vec![(0, user_a)]);
+
+    // Client B bookmarks the third line.
+    editor_b.update_in(cx_b, |editor, window, cx| {
+        editor.move_down(&editor::actions::MoveDown, window, cx);
+        editor.move_down(&editor::actions::MoveDown, window, cx);
+        editor.toggle_bookmark(&editor::actions::ToggleBookmark, window, cx);
+    });
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    let user_b = Some(client_b.user_id().unwrap());
+    assert_eq!(bookmarks(&project_a, cx_a), vec![(0, user_a), (2, user_b)]);
+    assert_eq!(bookmarks(&project_b, cx_b), vec![(0, user_a), (2, user_b)]);
+
+    // Client B removes the bookmark added by client A.
+    editor_b.update_in(cx_b, |editor, window, cx| {
+        editor.move_up(&editor::actions::MoveUp, window, cx);
+        editor.move_up(&editor::actions::MoveUp, window, cx);
+        editor.toggle_bookmark(&editor::actions::ToggleBookmark, window, cx);
+    });
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    assert_eq!(bookmarks(&project_a, cx_a), vec![(2, user_b)]);
+    assert_eq!(bookmarks(&project_b, cx_b), vec![(2, user_b)]);
+
+    // Client A clears all bookmarks.
+    editor_a.update_in(cx_a, |editor, window, cx| {
+        editor.clear_bookmarks(&editor::actions::ClearBookmarks, window, cx);
+    });
+    cx_a.run_until_parked();
+    cx_b.run_until_parked();
+
+    assert_eq!(bookmarks(&project_a, cx_a), Vec::new());
+    assert_eq!(bookmarks(&project_b, cx_b), Vec::new());
+}
+
 #[gpui::test]
 async fn test_client_can_query_lsp_ext(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext) {
     let mut server = TestServer::start(cx_a.executor()).await;
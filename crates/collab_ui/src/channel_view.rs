@@ -608,6 +608,7 @@ impl FollowableItem for ChannelView {
                                 scroll_top_anchor: state.scroll_top_anchor,
                                 scroll_x: state.scroll_x,
                                 scroll_y: state.scroll_y,
+                                highlights: state.highlights,
                                 ..Default::default()
                             }),
                             window,
@@ -14,7 +14,7 @@ use diagnostic_renderer::DiagnosticBlock;
 use editor::{
     Editor, EditorEvent, ExcerptRange, MultiBuffer, PathKey,
     display_map::{BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
-    multibuffer_context_lines,
+    is_minified_buffer, multibuffer_context_lines,
 };
 use gpui::{
     AnyElement, AnyView, App, AsyncApp, Context, Entity, EventEmitter, FocusHandle, Focusable,
@@ -57,7 +57,12 @@ actions!(
         /// Toggles the display of warning-level diagnostics.
         ToggleWarnings,
         /// Toggles automatic refresh of diagnostics.
-        ToggleDiagnosticsRefresh
+        ToggleDiagnosticsRefresh,
+        /// Shows the diagnostics of the next batch of files, when too many files have
+        /// diagnostics to show them all at once.
+        LoadMoreDiagnostics,
+        /// Shows the diagnostics in minified files, which are skipped by default.
+        IncludeMinifiedDiagnostics
     ]
 );
 
@@ -84,6 +89,15 @@ pub(crate) struct ProjectDiagnosticsEditor {
     include_warnings: bool,
     update_excerpts_task: Option<Task<Result<()>>>,
     diagnostic_summary_update: Task<()>,
+    /// The paths whose diagnostics are shown.
+    shown_paths: BTreeSet<ProjectPath>,
+    /// How many files' diagnostics are shown before the rest are deferred.
+    shown_file_limit: usize,
+    /// The paths with diagnostics that aren't shown yet, because too many files have diagnostics.
+    deferred_paths: BTreeSet<ProjectPath>,
+    /// The minified files with diagnostics, which aren't shown unless asked for.
+    minified_paths: BTreeSet<ProjectPath>,
+    include_minified: bool,
     _subscription: Subscription,
 }
 
@@ -92,6 +106,10 @@ impl EventEmitter<EditorEvent> for ProjectDiagnosticsEditor {}
 const DIAGNOSTICS_UPDATE_DELAY: Duration = Duration::from_millis(50);
 const DIAGNOSTICS_SUMMARY_UPDATE_DELAY: Duration = Duration::from_millis(30);
 
+/// How many files' diagnostics are shown at once. Opening the buffers of thousands of files at
+/// once freezes the UI, so the rest are shown in batches of this size when asked for.
+const DIAGNOSTICS_BATCH_FILE_COUNT: usize = 200;
+
 impl Render for ProjectDiagnosticsEditor {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let warning_count = if self.include_warnings {
@@ -136,7 +154,12 @@ impl Render for ProjectDiagnosticsEditor {
                         )
                     })
             } else {
-                div().size_full().child(self.editor.clone())
+                v_flex()
+                    .size_full()
+                    .child(div().flex_1().min_h_0().child(self.editor.clone()))
+                    .when_some(self.render_deferred_files(cx), |this, deferred_files| {
+                        this.child(deferred_files)
+                    })
             };
 
         div()
@@ -145,6 +168,8 @@ impl Render for ProjectDiagnosticsEditor {
             .size_full()
             .on_action(cx.listener(Self::toggle_warnings))
             .on_action(cx.listener(Self::toggle_diagnostics_refresh))
+            .on_action(cx.listener(Self::load_more_diagnostics))
+            .on_action(cx.listener(Self::include_minified_diagnostics))
             .child(child)
     }
 }
@@ -277,6 +302,11 @@ impl ProjectDiagnosticsEditor {
             paths_to_update: Default::default(),
             update_excerpts_task: None,
             diagnostic_summary_update: Task::ready(()),
+            shown_paths: Default::default(),
+            shown_file_limit: DIAGNOSTICS_BATCH_FILE_COUNT,
+            deferred_paths: Default::default(),
+            minified_paths: Default::default(),
+            include_minified: false,
             _subscription: project_event_subscription,
         };
         this.update_all_excerpts(window, cx);
@@ -295,12 +325,21 @@ impl ProjectDiagnosticsEditor {
                 .await;
             loop {
                 let Some(path) = this.update(cx, |this, cx| {
-                    let Some(path) = this.paths_to_update.pop_first() else {
-                        this.update_excerpts_task = None;
-                        cx.notify();
-                        return None;
-                    };
-                    Some(path)
+                    loop {
+                        let Some(path) = this.paths_to_update.pop_first() else {
+                            this.update_excerpts_task = None;
+                            cx.notify();
+                            return None;
+                        };
+                        // Files that aren't shown yet are left unopened once enough are shown.
+                        if !this.shown_paths.contains(&path)
+                            && this.shown_paths.len() >= this.shown_file_limit
+                        {
+                            this.deferred_paths.insert(path);
+                            continue;
+                        }
+                        return Some(path);
+                    }
                 })?
                 else {
                     break;
@@ -311,10 +350,34 @@ impl ProjectDiagnosticsEditor {
                     .await
                     .log_err()
                 {
+                    let skip = this.update(cx, |this, cx| {
+                        let skip = !this.include_minified
+                            && !this.shown_paths.contains(&path)
+                            && is_minified_buffer(buffer.read(cx));
+                        if skip {
+                            this.minified_paths.insert(path.clone());
+                        }
+                        skip
+                    })?;
+                    if skip {
+                        continue;
+                    }
+
+                    let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id())?;
                     this.update_in(cx, |this, window, cx| {
+                        this.shown_paths.insert(path.clone());
                         this.update_excerpts(buffer, window, cx)
                     })?
                     .await?;
+                    this.update(cx, |this, _| {
+                        if this
+                            .diagnostics
+                            .get(&buffer_id)
+                            .is_none_or(|diagnostics| diagnostics.is_empty())
+                        {
+                            this.shown_paths.remove(&path);
+                        }
+                    })?;
                 }
             }
             Ok(())
@@ -407,10 +470,98 @@ impl ProjectDiagnosticsEditor {
 
             self.paths_to_update = project_paths;
         });
+        self.deferred_paths.clear();
+        self.minified_paths.clear();
 
         self.update_stale_excerpts(window, cx);
     }
 
+    /// Shows the diagnostics of the next batch of files whose diagnostics were deferred.
+    fn load_more_diagnostics(
+        &mut self,
+        _: &LoadMoreDiagnostics,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.deferred_paths.is_empty() {
+            return;
+        }
+        self.shown_file_limit = self.shown_paths.len() + DIAGNOSTICS_BATCH_FILE_COUNT;
+        self.paths_to_update.append(&mut self.deferred_paths);
+        self.update_stale_excerpts(window, cx);
+        cx.notify();
+    }
+
+    /// Shows the diagnostics in the minified files that were skipped.
+    fn include_minified_diagnostics(
+        &mut self,
+        _: &IncludeMinifiedDiagnostics,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.minified_paths.is_empty() {
+            return;
+        }
+        self.include_minified = true;
+        self.shown_file_limit = self
+            .shown_file_limit
+            .saturating_add(self.minified_paths.len());
+        self.paths_to_update.append(&mut self.minified_paths);
+        self.update_stale_excerpts(window, cx);
+        cx.notify();
+    }
+
+    fn render_deferred_files(&self, cx: &mut Context<Self>) -> Option<AnyElement> {
+        let deferred_file_count = self.deferred_paths.len();
+        let minified_file_count = self.minified_paths.len();
+        if deferred_file_count == 0 && minified_file_count == 0 {
+            return None;
+        }
+
+        Some(
+            h_flex()
+                .w_full()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .bg(cx.theme().colors().editor_background)
+                .when(deferred_file_count > 0, |this| {
+                    let label = if deferred_file_count == 1 {
+                        "Show 1 more file".to_string()
+                    } else {
+                        format!("Show {deferred_file_count} more files")
+                    };
+                    this.child(
+                        Button::new("diagnostics-load-more", label)
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.load_more_diagnostics(&LoadMoreDiagnostics, window, cx);
+                            })),
+                    )
+                })
+                .when(minified_file_count > 0, |this| {
+                    this.child(
+                        Button::new(
+                            "diagnostics-include-minified",
+                            format!("{minified_file_count} minified skipped"),
+                        )
+                        .label_size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.include_minified_diagnostics(
+                                &IncludeMinifiedDiagnostics,
+                                window,
+                                cx,
+                            );
+                        })),
+                    )
+                })
+                .into_any_element(),
+        )
+    }
+
     fn diagnostics_are_unchanged(
         &self,
         existing: &[DiagnosticEntry<text::Anchor>],
@@ -2,7 +2,7 @@ use super::*;
 use collections::{HashMap, HashSet};
 use editor::{
     DisplayPoint, EditorSettings,
-    actions::{GoToDiagnostic, GoToPreviousDiagnostic, Hover, MoveToBeginning},
+    actions::{GoToDiagnostic, GoToNextError, GoToPreviousDiagnostic, Hover, MoveToBeginning},
     display_map::{DisplayRow, Inlay},
     test::{
         editor_content_with_blocks, editor_lsp_test_context::EditorLspTestContext,
@@ -317,6 +317,98 @@ async fn test_diagnostics(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_diagnostics_in_batches(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let file_count = DIAGNOSTICS_BATCH_FILE_COUNT + 1;
+    let mut files = serde_json::Map::new();
+    for ix in 0..file_count {
+        files.insert(format!("file{ix}.rs"), json!("let x = 1;"));
+    }
+    files.insert("bundle.min.js".to_string(), json!("let x = 1;"));
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/test"), serde_json::Value::Object(files))
+        .await;
+
+    let language_server_id = LanguageServerId(0);
+    let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+    let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*window, cx);
+    let workspace = window.root(cx).unwrap();
+
+    lsp_store.update(cx, |lsp_store, cx| {
+        let file_names = (0..file_count)
+            .map(|ix| format!("file{ix}.rs"))
+            .chain(["bundle.min.js".to_string()]);
+        for file_name in file_names {
+            let uri = lsp::Uri::from_file_path(Path::new(path!("/test")).join(file_name)).unwrap();
+            lsp_store
+                .update_diagnostics(
+                    language_server_id,
+                    lsp::PublishDiagnosticsParams {
+                        uri,
+                        diagnostics: vec![lsp::Diagnostic {
+                            range: lsp::Range::new(
+                                lsp::Position::new(0, 4),
+                                lsp::Position::new(0, 5),
+                            ),
+                            severity: Some(lsp::DiagnosticSeverity::ERROR),
+                            message: "unused variable".to_string(),
+                            ..Default::default()
+                        }],
+                        version: None,
+                    },
+                    None,
+                    DiagnosticSourceKind::Pushed,
+                    &[],
+                    cx,
+                )
+                .unwrap();
+        }
+    });
+
+    let diagnostics = window.build_entity(cx, |window, cx| {
+        ProjectDiagnosticsEditor::new(true, project.clone(), workspace.downgrade(), window, cx)
+    });
+    let shown_counts = |cx: &mut VisualTestContext| {
+        diagnostics.read_with(cx, |diagnostics, cx| {
+            assert_eq!(
+                diagnostics.multibuffer.read(cx).all_buffers().len(),
+                diagnostics.shown_paths.len()
+            );
+            (
+                diagnostics.shown_paths.len(),
+                diagnostics.deferred_paths.len(),
+                diagnostics.minified_paths.len(),
+            )
+        })
+    };
+
+    // Only the first batch of files is shown, and the minified file is skipped.
+    cx.executor()
+        .advance_clock(DIAGNOSTICS_UPDATE_DELAY + Duration::from_millis(10));
+    cx.executor().run_until_parked();
+    assert_eq!(shown_counts(cx), (DIAGNOSTICS_BATCH_FILE_COUNT, 1, 1));
+
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        diagnostics.load_more_diagnostics(&LoadMoreDiagnostics, window, cx)
+    });
+    cx.executor()
+        .advance_clock(DIAGNOSTICS_UPDATE_DELAY + Duration::from_millis(10));
+    cx.executor().run_until_parked();
+    assert_eq!(shown_counts(cx), (file_count, 0, 1));
+
+    diagnostics.update_in(cx, |diagnostics, window, cx| {
+        diagnostics.include_minified_diagnostics(&IncludeMinifiedDiagnostics, window, cx)
+    });
+    cx.executor()
+        .advance_clock(DIAGNOSTICS_UPDATE_DELAY + Duration::from_millis(10));
+    cx.executor().run_until_parked();
+    assert_eq!(shown_counts(cx), (file_count + 1, 0, 0));
+}
+
 #[gpui::test]
 async fn test_diagnostics_with_folds(cx: &mut TestAppContext) {
     init_test(cx);
@@ -1514,6 +1606,7 @@ async fn go_to_diagnostic_with_severity(cx: &mut TestAppContext) {
                 editor.go_to_diagnostic(
                     &GoToDiagnostic {
                         severity: $severity,
+                        ..GoToDiagnostic::default()
                     },
                     window,
                     cx,
@@ -1567,6 +1660,97 @@ async fn go_to_diagnostic_with_severity(cx: &mut TestAppContext) {
     cx.assert_editor_state(indoc! {"error ˇwarning info hint"});
 }
 
+#[gpui::test]
+async fn go_to_diagnostic_with_source(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let lsp_store =
+        cx.update_editor(|editor, _, cx| editor.project().unwrap().read(cx).lsp_store());
+
+    cx.set_state(indoc! {"ˇwarning error lint"});
+
+    cx.update(|_, cx| {
+        lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store
+                .update_diagnostics(
+                    LanguageServerId(0),
+                    lsp::PublishDiagnosticsParams {
+                        uri: lsp::Uri::from_file_path(path!("/root/file")).unwrap(),
+                        version: None,
+                        diagnostics: vec![
+                            lsp::Diagnostic {
+                                range: lsp::Range::new(
+                                    lsp::Position::new(0, 0),
+                                    lsp::Position::new(0, 7),
+                                ),
+                                severity: Some(lsp::DiagnosticSeverity::WARNING),
+                                source: Some("rustc".to_string()),
+                                ..Default::default()
+                            },
+                            lsp::Diagnostic {
+                                range: lsp::Range::new(
+                                    lsp::Position::new(0, 8),
+                                    lsp::Position::new(0, 13),
+                                ),
+                                severity: Some(lsp::DiagnosticSeverity::ERROR),
+                                source: Some("rustc".to_string()),
+                                ..Default::default()
+                            },
+                            lsp::Diagnostic {
+                                range: lsp::Range::new(
+                                    lsp::Position::new(0, 14),
+                                    lsp::Position::new(0, 18),
+                                ),
+                                severity: Some(lsp::DiagnosticSeverity::WARNING),
+                                source: Some("clippy".to_string()),
+                                ..Default::default()
+                            },
+                        ],
+                    },
+                    None,
+                    DiagnosticSourceKind::Pushed,
+                    &[],
+                    cx,
+                )
+                .unwrap()
+        });
+    });
+    cx.run_until_parked();
+
+    let clippy = GoToDiagnostic {
+        source: Some("Clippy".to_string()),
+        ..GoToDiagnostic::default()
+    };
+    cx.update_editor(|editor, window, cx| editor.go_to_diagnostic(&clippy, window, cx));
+    cx.assert_editor_state(indoc! {"warning error ˇlint"});
+    cx.update_editor(|editor, window, cx| editor.go_to_diagnostic(&clippy, window, cx));
+    cx.assert_editor_state(indoc! {"warning error ˇlint"});
+
+    cx.update_editor(|editor, window, cx| editor.go_to_next_error(&GoToNextError, window, cx));
+    cx.assert_editor_state(indoc! {"warning ˇerror lint"});
+    cx.update_editor(|editor, window, cx| editor.go_to_next_error(&GoToNextError, window, cx));
+    cx.assert_editor_state(indoc! {"warning ˇerror lint"});
+
+    // While there are errors, warnings are skipped if so configured.
+    cx.update(|_, cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings
+                    .diagnostics
+                    .get_or_insert_default()
+                    .skip_warnings_when_errors_exist = Some(true);
+            });
+        });
+    });
+    cx.update_editor(|editor, window, cx| {
+        editor.go_to_diagnostic(&GoToDiagnostic::default(), window, cx)
+    });
+    cx.assert_editor_state(indoc! {"warning ˇerror lint"});
+    cx.update_editor(|editor, window, cx| editor.go_to_diagnostic(&clippy, window, cx));
+    cx.assert_editor_state(indoc! {"warning error ˇlint"});
+}
+
 #[gpui::test]
 async fn test_buffer_diagnostics(cx: &mut TestAppContext) {
     init_test(cx);
@@ -160,6 +160,7 @@ impl DiagnosticIndicator {
                 editor.go_to_diagnostic_impl(
                     editor::Direction::Next,
                     GoToDiagnosticSeverityFilter::default(),
+                    None,
                     window,
                     cx,
                 );
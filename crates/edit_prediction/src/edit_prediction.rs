@@ -1,9 +1,13 @@
+mod inline_completion_source;
+
 use std::ops::Range;
 
 use client::EditPredictionUsage;
 use gpui::{App, Context, Entity, SharedString};
 use language::{Anchor, Buffer, BufferSnapshot, OffsetRangeExt};
 
+pub use inline_completion_source::*;
+
 // TODO: Find a better home for `Direction`.
 //
 // This should live in an ancestor crate of `editor` and `edit_prediction`,
@@ -0,0 +1,222 @@
+use std::{collections::BTreeMap, ops::Range, rc::Rc, sync::Arc, time::Duration};
+
+use anyhow::Result;
+use gpui::{App, Context, Entity, Global, SharedString, Task};
+use language::{Anchor, Buffer, BufferSnapshot, EditPreview};
+
+use crate::{Direction, EditPrediction, EditPredictionProvider, interpolate_edits};
+
+pub const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(75);
+
+/// A source of ghost-text completions that can be supplied by other crates or extensions.
+///
+/// Sources only need to produce the text to insert at the cursor. Debouncing, cancellation of
+/// outdated requests, and interpolation of the completion as the user types are handled by
+/// [`InlineCompletionSourceProvider`].
+pub trait InlineCompletionSource: 'static {
+    /// The name shown to the user for this source.
+    fn display_name(&self) -> SharedString;
+
+    fn is_enabled(&self, _buffer: &Entity<Buffer>, _cursor_position: Anchor, _cx: &App) -> bool {
+        true
+    }
+
+    /// Requests the text to insert at `cursor_position`. The returned task is dropped when the
+    /// request becomes outdated, so sources should stop any in-flight work when that happens.
+    fn request_completion(
+        &self,
+        buffer: &Entity<Buffer>,
+        cursor_position: Anchor,
+        cx: &mut App,
+    ) -> Task<Result<Option<String>>>;
+}
+
+/// The inline completion sources registered by name, selectable with the
+/// `edit_predictions.custom_provider` setting.
+#[derive(Default)]
+pub struct InlineCompletionRegistry {
+    sources: BTreeMap<SharedString, Rc<dyn InlineCompletionSource>>,
+}
+
+impl Global for InlineCompletionRegistry {}
+
+impl InlineCompletionRegistry {
+    /// Registers `source` under `name`, replacing any source previously registered with it.
+    pub fn register(
+        name: impl Into<SharedString>,
+        source: Rc<dyn InlineCompletionSource>,
+        cx: &mut App,
+    ) {
+        cx.default_global::<Self>()
+            .sources
+            .insert(name.into(), source);
+    }
+
+    pub fn unregister(name: &str, cx: &mut App) {
+        if cx.has_global::<Self>() {
+            cx.global_mut::<Self>().sources.remove(name);
+        }
+    }
+
+    pub fn source(name: &str, cx: &App) -> Option<Rc<dyn InlineCompletionSource>> {
+        cx.try_global::<Self>()?.sources.get(name).cloned()
+    }
+
+    pub fn names(cx: &App) -> Vec<SharedString> {
+        cx.try_global::<Self>()
+            .map(|registry| registry.sources.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Clone)]
+struct CurrentCompletion {
+    snapshot: BufferSnapshot,
+    edits: Arc<[(Range<Anchor>, String)]>,
+    edit_preview: EditPreview,
+}
+
+/// An [`EditPredictionProvider`] that shows the completions of an [`InlineCompletionSource`].
+pub struct InlineCompletionSourceProvider {
+    source: Rc<dyn InlineCompletionSource>,
+    pending_request: Option<Task<Result<()>>>,
+    current_completion: Option<CurrentCompletion>,
+}
+
+impl InlineCompletionSourceProvider {
+    pub fn new(source: Rc<dyn InlineCompletionSource>) -> Self {
+        Self {
+            source,
+            pending_request: None,
+            current_completion: None,
+        }
+    }
+
+    pub fn display_name(&self) -> SharedString {
+        self.source.display_name()
+    }
+}
+
+impl EditPredictionProvider for InlineCompletionSourceProvider {
+    fn name() -> &'static str {
+        "custom"
+    }
+
+    fn display_name() -> &'static str {
+        "Custom"
+    }
+
+    fn show_completions_in_menu() -> bool {
+        true
+    }
+
+    fn supports_jump_to_edit() -> bool {
+        false
+    }
+
+    fn is_enabled(&self, buffer: &Entity<Buffer>, cursor_position: Anchor, cx: &App) -> bool {
+        self.source.is_enabled(buffer, cursor_position, cx)
+    }
+
+    fn is_refreshing(&self) -> bool {
+        self.pending_request.is_some()
+    }
+
+    fn refresh(
+        &mut self,
+        buffer: Entity<Buffer>,
+        cursor_position: Anchor,
+        debounce: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = buffer.read(cx).snapshot();
+        if let Some(current_completion) = self.current_completion.as_ref()
+            && interpolate_edits(
+                &current_completion.snapshot,
+                &snapshot,
+                &current_completion.edits,
+            )
+            .is_some()
+        {
+            return;
+        }
+
+        // Replacing the pending request drops it, cancelling the outdated request.
+        self.pending_request = Some(cx.spawn(async move |this, cx| {
+            if debounce {
+                cx.background_executor().timer(DEBOUNCE_TIMEOUT).await;
+            }
+
+            let request = this.update(cx, |this, cx| {
+                this.source.request_completion(&buffer, cursor_position, cx)
+            })?;
+            let completion_text = match request.await {
+                Ok(Some(text)) if !text.is_empty() => text,
+                result => {
+                    if let Err(error) = result {
+                        log::error!("failed to request inline completion: {error:#}");
+                    }
+                    this.update(cx, |this, cx| {
+                        this.pending_request = None;
+                        cx.notify();
+                    })?;
+                    return Ok(());
+                }
+            };
+
+            let edits: Arc<[(Range<Anchor>, String)]> =
+                vec![(cursor_position..cursor_position, completion_text)].into();
+            let edit_preview = buffer
+                .read_with(cx, |buffer, cx| buffer.preview_edits(edits.clone(), cx))?
+                .await;
+
+            this.update(cx, |this, cx| {
+                this.current_completion = Some(CurrentCompletion {
+                    snapshot,
+                    edits,
+                    edit_preview,
+                });
+                this.pending_request = None;
+                cx.notify();
+            })
+        }));
+    }
+
+    fn cycle(
+        &mut self,
+        _buffer: Entity<Buffer>,
+        _cursor_position: Anchor,
+        _direction: Direction,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
+    fn accept(&mut self, _cx: &mut Context<Self>) {
+        self.pending_request = None;
+        self.current_completion = None;
+    }
+
+    fn discard(&mut self, _cx: &mut Context<Self>) {
+        self.pending_request = None;
+        self.current_completion = None;
+    }
+
+    fn suggest(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        _cursor_position: Anchor,
+        cx: &mut Context<Self>,
+    ) -> Option<EditPrediction> {
+        let current_completion = self.current_completion.as_ref()?;
+        let edits = interpolate_edits(
+            &current_completion.snapshot,
+            &buffer.read(cx).snapshot(),
+            &current_completion.edits,
+        )?;
+        Some(EditPrediction::Local {
+            id: None,
+            edits,
+            edit_preview: Some(current_completion.edit_preview.clone()),
+        })
+    }
+}
@@ -3,6 +3,7 @@ use client::{UserStore, zed_urls};
 use cloud_llm_client::UsageLimit;
 use codestral::CodestralCompletionProvider;
 use copilot::{Copilot, Status};
+use edit_prediction::InlineCompletionRegistry;
 use editor::{Editor, SelectionEffects, actions::ShowEditPrediction, scroll::Autoscroll};
 use feature_flags::{FeatureFlagAppExt, PredictEditsRateCompletionsFeatureFlag};
 use fs::Fs;
@@ -296,6 +297,43 @@ impl Render for EditPredictionButton {
                 )
             }
 
+            EditPredictionProvider::Custom => {
+                let Some(source) = all_language_settings
+                    .edit_predictions
+                    .custom_provider
+                    .as_deref()
+                    .and_then(|name| InlineCompletionRegistry::source(name, cx))
+                else {
+                    return div();
+                };
+                let display_name = source.display_name();
+                let enabled = self.editor_enabled.unwrap_or(true);
+                let icon = if enabled {
+                    IconName::ZedPredict
+                } else {
+                    IconName::ZedPredictDisabled
+                };
+                let this = cx.entity();
+
+                div().child(
+                    PopoverMenu::new("custom-edit-prediction")
+                        .menu(move |window, cx| {
+                            Some(this.update(cx, |this, cx| {
+                                this.build_custom_provider_context_menu(window, cx)
+                            }))
+                        })
+                        .anchor(Corner::BottomRight)
+                        .trigger_with_tooltip(
+                            IconButton::new("custom-edit-prediction-icon", icon)
+                                .shape(IconButtonShape::Square),
+                            move |window, cx| {
+                                Tooltip::for_action(display_name.clone(), &ToggleMenu, window, cx)
+                            },
+                        )
+                        .with_handle(self.popover_menu_handle.clone()),
+                )
+            }
+
             EditPredictionProvider::Zed => {
                 let enabled = self.editor_enabled.unwrap_or(true);
 
@@ -556,6 +594,7 @@ impl EditPredictionButton {
                 | EditPredictionProvider::Copilot
                 | EditPredictionProvider::Supermaven
                 | EditPredictionProvider::Codestral
+                | EditPredictionProvider::Custom
         ) {
             menu = menu
                 .separator()
@@ -801,6 +840,16 @@ impl EditPredictionButton {
         })
     }
 
+    fn build_custom_provider_context_menu(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<ContextMenu> {
+        ContextMenu::build(window, cx, |menu, window, cx| {
+            self.build_language_settings_menu(menu, window, cx)
+        })
+    }
+
     fn build_zeta_context_menu(
         &self,
         window: &mut Window,
@@ -267,6 +267,11 @@ pub struct CutToEndOfLine {
 #[action(namespace = editor)]
 pub struct FoldAtLevel(pub u32);
 
+/// Unfolds all code blocks at the specified indentation level.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema, Action)]
+#[action(namespace = editor)]
+pub struct UnfoldAtLevel(pub u32);
+
 /// Spawns the nearest available task from the current cursor position.
 #[derive(PartialEq, Clone, Deserialize, Default, JsonSchema, Action)]
 #[action(namespace = editor)]
@@ -276,6 +281,65 @@ pub struct SpawnNearestTask {
     pub reveal: task::RevealStrategy,
 }
 
+/// Starts recording a keyboard macro from the actions and text typed afterwards.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct StartRecordingMacro {
+    /// When set, the macro is also saved under this name so it can be replayed in later sessions.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Replays a recorded keyboard macro.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayMacro {
+    /// The name of a saved macro to replay. Defaults to the most recently recorded macro.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// How many times to replay the macro.
+    #[serde(default)]
+    pub times: Option<usize>,
+    /// Replay the macro once for every cursor, as if each cursor were the only one.
+    #[serde(default)]
+    pub per_cursor: bool,
+}
+
+/// Saves the current selections under a name, so that they can be restored later, including
+/// after the file is reopened.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct SaveSelectionSet {
+    /// The name to save the selections under. Defaults to an unnamed set.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Restores the selections that were saved under a name.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct RestoreSelectionSet {
+    /// The name the selections were saved under. Defaults to the unnamed set.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Keeps the scroll positions of the editors in the other panes in sync with this one, or stops
+/// keeping them in sync.
+#[derive(PartialEq, Clone, Deserialize, Default, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct ToggleScrollLock {
+    /// Whether to scroll the editors to the same fraction of their length, rather than to the
+    /// same row. Useful for related files of different lengths, like a test and its source.
+    #[serde(default)]
+    pub proportional: bool,
+}
+
 #[derive(Clone, PartialEq, Action)]
 #[action(no_json, no_register)]
 pub struct DiffClipboardWithSelectionData {
@@ -290,6 +354,33 @@ pub enum UuidVersion {
     V7,
 }
 
+/// Inserts the current local time at each cursor.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct InsertTimestamp {
+    /// A `strftime` format, such as `%Y-%m-%d %H:%M`. Defaults to RFC 3339, as in
+    /// `2024-05-01T10:00:00+02:00`.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// Inserts an increasing number at each cursor, such as `1`, `2` and `3` for three cursors.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct InsertSequence {
+    /// The number inserted at the first cursor. Defaults to 1.
+    #[serde(default)]
+    pub start: Option<i64>,
+    /// The amount added at each further cursor. Defaults to 1.
+    #[serde(default)]
+    pub step: Option<i64>,
+    /// The minimum number of digits, padding with leading zeros.
+    #[serde(default)]
+    pub width: Option<usize>,
+}
+
 /// Splits selection into individual lines.
 #[derive(PartialEq, Clone, Deserialize, Default, JsonSchema, Action)]
 #[action(namespace = editor)]
@@ -300,6 +391,82 @@ pub struct SplitSelectionIntoLines {
     pub keep_selections: bool,
 }
 
+/// Aligns the cursors or selections on different lines at the same column by inserting
+/// spaces before them.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct AlignSelections {
+    /// Align the next occurrence of this text after each cursor, such as `=` or `:`,
+    /// instead of the cursors themselves.
+    #[serde(default)]
+    pub align_on: Option<String>,
+}
+
+/// Joins the current line with the next line, or the lines spanned by the selection.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct JoinLines {
+    /// The text put between the joined lines, such as `", "`. Defaults to a space.
+    #[serde(default)]
+    pub separator: Option<String>,
+}
+
+/// Evaluates the arithmetic or bitwise expression in each selection, such as `(1 + 2) * 0x10`,
+/// replacing it with the result.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct EvaluateSelection {
+    /// Whether to keep the expression and append ` = result` after it. Expressions ending with
+    /// `=` always get the result appended.
+    #[serde(default)]
+    pub append: bool,
+}
+
+/// Increments the number under each cursor, or the first number in each selection.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct IncrementNumber {
+    /// The amount to add. Defaults to 1.
+    #[serde(default)]
+    pub count: Option<u32>,
+    /// Whether each further cursor adds `count` more than the previous one, turning a column of
+    /// equal numbers into a sequence.
+    #[serde(default)]
+    pub sequential: bool,
+    /// Whether to increment the year, month or day under the cursor in `YYYY-MM-DD` dates.
+    #[serde(default)]
+    pub dates: bool,
+    /// Whether to increment the component under the cursor in `1.2.3` version numbers,
+    /// resetting the components after it.
+    #[serde(default)]
+    pub versions: bool,
+}
+
+/// Decrements the number under each cursor, or the first number in each selection.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct DecrementNumber {
+    /// The amount to subtract. Defaults to 1.
+    #[serde(default)]
+    pub count: Option<u32>,
+    /// Whether each further cursor subtracts `count` more than the previous one, turning a
+    /// column of equal numbers into a sequence.
+    #[serde(default)]
+    pub sequential: bool,
+    /// Whether to decrement the year, month or day under the cursor in `YYYY-MM-DD` dates.
+    #[serde(default)]
+    pub dates: bool,
+    /// Whether to decrement the component under the cursor in `1.2.3` version numbers,
+    /// resetting the components after it.
+    #[serde(default)]
+    pub versions: bool,
+}
+
 /// Goes to the next diagnostic in the file.
 #[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema, Action)]
 #[action(namespace = editor)]
@@ -307,6 +474,9 @@ pub struct SplitSelectionIntoLines {
 pub struct GoToDiagnostic {
     #[serde(default)]
     pub severity: GoToDiagnosticSeverityFilter,
+    /// Only go to the diagnostics reported by this source, such as `clippy` or `eslint`.
+    #[serde(default)]
+    pub source: Option<String>,
 }
 
 /// Goes to the previous diagnostic in the file.
@@ -316,6 +486,41 @@ pub struct GoToDiagnostic {
 pub struct GoToPreviousDiagnostic {
     #[serde(default)]
     pub severity: GoToDiagnosticSeverityFilter,
+    /// Only go to the diagnostics reported by this source, such as `clippy` or `eslint`.
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// Copies the selected code to the clipboard as a PNG image, rendered with the editor's font and theme.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct CopyAsImage {
+    /// The space around the code, in pixels. Defaults to 32.
+    #[serde(default)]
+    pub padding: Option<f32>,
+    /// Whether to draw a window title bar with controls above the code.
+    #[serde(default = "default_true")]
+    pub window_chrome: bool,
+}
+
+/// Saves the buffer as a standalone HTML file highlighted with the theme's syntax colors.
+#[derive(PartialEq, Clone, Default, Debug, Deserialize, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct ExportAsHtml {
+    /// Whether to number the lines of the exported file.
+    #[serde(default)]
+    pub line_numbers: bool,
+}
+
+/// Shows or hides the lines of a log file that have the given level, folding away hidden lines.
+#[derive(PartialEq, Clone, Debug, Deserialize, JsonSchema, Action)]
+#[action(namespace = editor)]
+#[serde(deny_unknown_fields)]
+pub struct ToggleLogLevel {
+    /// One of `error`, `warn`, `info`, `debug` or `trace`.
+    pub level: LogLevel,
 }
 
 actions!(
@@ -328,6 +533,32 @@ actions!(
     ]
 );
 
+actions!(
+    call_hierarchy,
+    [
+        /// Expands the selected call, loading the calls to or from it.
+        ExpandSelectedCall,
+        /// Collapses the selected call, or selects its parent if it is collapsed.
+        CollapseSelectedCall,
+        /// Switches between showing incoming and outgoing calls.
+        #[action(name = "ToggleDirection")]
+        ToggleCallDirection
+    ]
+);
+
+actions!(
+    type_hierarchy,
+    [
+        /// Expands the selected type, loading its supertypes or subtypes.
+        ExpandSelectedType,
+        /// Collapses the selected type, or selects its parent if it is collapsed.
+        CollapseSelectedType,
+        /// Switches between showing supertypes and subtypes.
+        #[action(name = "ToggleDirection")]
+        ToggleTypeDirection
+    ]
+);
+
 actions!(
     go_to_line,
     [
@@ -345,10 +576,15 @@ actions!(
         /// Accepts a partial edit prediction.
         #[action(deprecated_aliases = ["editor::AcceptPartialCopilotSuggestion"])]
         AcceptPartialEditPrediction,
+        /// Accepts the current edit prediction up to the end of the line.
+        AcceptNextLineEditPrediction,
         /// Adds a cursor above the current selection.
         AddSelectionAbove,
         /// Adds a cursor below the current selection.
         AddSelectionBelow,
+        /// Prompts for text, such as `=` or `:`, and aligns its next occurrence after each
+        /// cursor at the same column.
+        AlignSelectionsOn,
         /// Applies all diff hunks in the editor.
         ApplyAllDiffHunks,
         /// Applies the diff hunk at the current position.
@@ -357,16 +593,24 @@ actions!(
         Backspace,
         /// Shows git blame information for the current line.
         BlameHover,
+        /// Places a cursor at the same column on every selected line, padding short
+        /// lines with spaces, so that typed text is inserted on all of them.
+        BlockInsert,
         /// Cancels the current operation.
         Cancel,
         /// Cancels the running flycheck operation.
         CancelFlycheck,
         /// Cancels pending language server work.
         CancelLanguageServerWork,
+        /// Removes all bookmarks in the project.
+        ClearBookmarks,
         /// Clears flycheck results.
         ClearFlycheck,
         /// Confirms the rename operation.
         ConfirmRename,
+        /// Confirms the rename operation, opening the resulting edits in a preview
+        /// where they can be reviewed before being applied.
+        ConfirmRenameWithPreview,
         /// Confirms completion by inserting at cursor.
         ConfirmCompletionInsert,
         /// Confirms completion by replacing existing text.
@@ -411,6 +655,11 @@ actions!(
         CopyAndTrim,
         /// Copies the current file location to the clipboard.
         CopyFileLocation,
+        /// Copies the path of the symbols containing the cursor, such as `module::Type::method`,
+        /// to the clipboard.
+        CopyBreadcrumbPath,
+        /// Copies the selected text along with its syntax highlighting, as HTML and RTF.
+        CopyWithSyntaxHighlighting,
         /// Copies the highlighted text as JSON.
         CopyHighlightJson,
         /// Copies the current file name to the clipboard.
@@ -421,6 +670,9 @@ actions!(
         CopyPermalinkToLine,
         /// Cuts selected text to the clipboard.
         Cut,
+        /// Converts the identifier under the cursor, or the selection, to the next case of
+        /// camelCase, PascalCase, snake_case, SCREAMING_SNAKE_CASE and kebab-case.
+        CycleCase,
         /// Deletes the character after the cursor.
         Delete,
         /// Deletes the current line.
@@ -435,6 +687,8 @@ actions!(
         DiffClipboardWithSelection,
         /// Displays names of all active cursors.
         DisplayCursorNames,
+        /// Duplicates the current line below and comments out the original line.
+        DuplicateLineAndComment,
         /// Duplicates the current line below.
         DuplicateLineDown,
         /// Duplicates the current line above.
@@ -444,6 +698,8 @@ actions!(
         /// Expands all diff hunks in the editor.
         #[action(deprecated_aliases = ["editor::ExpandAllHunkDiffs"])]
         ExpandAllDiffHunks,
+        /// Expands the excerpts containing the selections to show their enclosing functions.
+        ExpandExcerptsToFunction,
         /// Expands macros recursively at cursor position.
         ExpandMacroRecursively,
         /// Finds all references to the symbol at cursor.
@@ -456,6 +712,8 @@ actions!(
         Fold,
         /// Folds all foldable regions in the editor.
         FoldAll,
+        /// Folds all regions delimited by region markers, such as `// #region` and `// #endregion`.
+        FoldAllRegions,
         /// Folds all code blocks at indentation level 1.
         #[action(name = "FoldAtLevel_1")]
         FoldAtLevel1,
@@ -485,22 +743,37 @@ actions!(
         FoldAtLevel9,
         /// Folds all function bodies in the editor.
         FoldFunctionBodies,
+        /// Folds the imports at the top of each file, such as Rust's `use` declarations.
+        FoldImports,
         /// Folds the current code block and all its children.
         FoldRecursive,
         /// Folds the selected ranges.
         FoldSelectedRanges,
+        /// Moves the keyboard focus into the hover popover, so that its text can be selected
+        /// and copied.
+        FocusHover,
         /// Toggles focus back to the last active buffer.
         ToggleFocus,
+        /// Toggles dimming all text outside the function enclosing the cursor.
+        ToggleFocusMode,
         /// Toggles folding at the current position.
         ToggleFold,
         /// Toggles recursive folding at the current position.
         ToggleFoldRecursive,
         /// Toggles all folds in a buffer or all excerpts in multibuffer.
         ToggleFoldAll,
+        /// Folds the excerpts containing the selections down to their first line, or unfolds them.
+        ToggleFoldExcerpts,
         /// Formats the entire document.
         Format,
         /// Formats only the selected text.
         FormatSelections,
+        /// Pretty-prints the JSON in the selections, or in the whole buffer if nothing is selected.
+        FormatSelectionAsJson,
+        /// Pretty-prints the XML in the selections, or in the whole buffer if nothing is selected.
+        FormatSelectionAsXml,
+        /// Realigns the columns of the Markdown table at the cursor.
+        FormatTable,
         /// Goes to the declaration of the symbol at cursor.
         GoToDeclaration,
         /// Goes to declaration in a split pane.
@@ -517,12 +790,30 @@ actions!(
         GoToImplementation,
         /// Goes to implementation in a split pane.
         GoToImplementationSplit,
+        /// Goes back to the location of the most recent edit.
+        GoToLastEditLocation,
+        /// Goes to the next bookmark in the editor.
+        GoToNextBookmark,
         /// Goes to the next change in the file.
         GoToNextChange,
+        /// Goes to the next region that was edited since the file was opened.
+        GoToNextChangedRegion,
+        /// Goes to the next error in the file, skipping other diagnostics.
+        GoToNextError,
+        /// Goes to the next syntax error that the parser found in the file.
+        GoToNextSyntaxError,
         /// Goes to the parent module of the current file.
         GoToParentModule,
+        /// Goes to the previous bookmark in the editor.
+        GoToPreviousBookmark,
         /// Goes to the previous change in the file.
         GoToPreviousChange,
+        /// Goes to the previous region that was edited since the file was opened.
+        GoToPreviousChangedRegion,
+        /// Goes to the previous error in the file, skipping other diagnostics.
+        GoToPreviousError,
+        /// Goes to the previous syntax error that the parser found in the file.
+        GoToPreviousSyntaxError,
         /// Goes to the type definition of the symbol at cursor.
         GoToTypeDefinition,
         /// Goes to type definition in a split pane.
@@ -539,20 +830,34 @@ actions!(
         Hover,
         /// Increases indentation of selected lines.
         Indent,
+        /// Inserts a column to the left of the cursor in a Markdown table.
+        InsertTableColumnLeft,
+        /// Inserts a column to the right of the cursor in a Markdown table.
+        InsertTableColumnRight,
+        /// Inserts a row above the cursor in a Markdown table.
+        InsertTableRowAbove,
+        /// Inserts a row below the cursor in a Markdown table.
+        InsertTableRowBelow,
         /// Inserts a UUID v4 at cursor position.
         InsertUuidV4,
         /// Inserts a UUID v7 at cursor position.
         InsertUuidV7,
-        /// Joins the current line with the next line.
-        JoinLines,
         /// Cuts to kill ring (Emacs-style).
         KillRingCut,
         /// Yanks from kill ring (Emacs-style).
         KillRingYank,
+        /// Replaces the text just yanked with the previous entry of the kill ring (Emacs-style).
+        KillRingYankPop,
         /// Moves cursor down one line.
         LineDown,
         /// Moves cursor up one line.
         LineUp,
+        /// Removes the whitespace outside of strings from the JSON in the selections, or in the
+        /// whole buffer if nothing is selected.
+        MinifySelectionAsJson,
+        /// Removes the whitespace between tags from the XML in the selections, or in the whole
+        /// buffer if nothing is selected.
+        MinifySelectionAsXml,
         /// Moves cursor down.
         MoveDown,
         /// Moves cursor left.
@@ -561,6 +866,10 @@ actions!(
         MoveLineDown,
         /// Moves the current line up.
         MoveLineUp,
+        /// Moves the syntax node under the cursor past its next sibling.
+        MoveNodeDown,
+        /// Moves the syntax node under the cursor before its previous sibling.
+        MoveNodeUp,
         /// Moves cursor right.
         MoveRight,
         /// Moves cursor to the beginning of the document.
@@ -632,8 +941,39 @@ actions!(
         PageUp,
         /// Pastes from clipboard.
         Paste,
+        /// Shows recent copies and cuts and pastes the chosen one.
+        PasteFromHistory,
+        /// Pastes the copy made before the last one with the same number of cursors, giving each
+        /// cursor the text copied at the matching cursor.
+        PastePreviousKillForEachCursor,
+        /// Pastes the clipboard as a rectangle, inserting each of its lines at the
+        /// cursor's column on successive lines and padding short lines with spaces.
+        PasteRectangular,
+        /// Pastes from clipboard, keeping the indentation of the copied text instead of
+        /// adjusting it to the surrounding code.
+        PasteWithoutReindent,
+        /// Shows the callers of the function under the cursor in a call hierarchy.
+        ShowIncomingCalls,
+        /// Shows the functions called by the function under the cursor in a call hierarchy.
+        ShowOutgoingCalls,
+        /// Shows the types that derive from or implement the type under the cursor in a type
+        /// hierarchy.
+        ShowSubtypes,
+        /// Shows the types that the type under the cursor derives from or implements in a type
+        /// hierarchy.
+        ShowSupertypes,
+        /// Shows the definition of the symbol under the cursor in an editor embedded
+        /// below the current line.
+        PeekDefinition,
+        /// Shows the next definition in the open peek.
+        PeekNextDefinition,
+        /// Shows the previous definition in the open peek.
+        PeekPreviousDefinition,
         /// Navigates to the previous edit prediction.
         PreviousEditPrediction,
+        /// Shows the fixes for the diagnostic under the cursor, applying the fix right away when
+        /// there's only one, or when one of them is preferred.
+        QuickFix,
         /// Redoes the last undone edit.
         Redo,
         /// Redoes the last selection change.
@@ -646,8 +986,16 @@ actions!(
         RevealInFileManager,
         /// Reverses the order of selected lines.
         ReverseLines,
+        /// Reverts the last edit that a language server applied across files, such as a rename,
+        /// unless one of the files changed since.
+        RevertLastWorkspaceEdit,
         /// Reloads the file from disk.
         ReloadFile,
+        /// Reloads the file from disk, merging the changes on disk into the unsaved edits.
+        ReloadFileWithMerge,
+        /// Restores the most recent selection that had multiple cursors or selected text, such as
+        /// after a click collapsed it.
+        ReselectLastSelection,
         /// Rewraps text to fit within the preferred line length.
         Rewrap,
         /// Runs flycheck diagnostics.
@@ -664,6 +1012,9 @@ actions!(
         SelectAll,
         /// Selects all matches of the current selection.
         SelectAllMatches,
+        /// Selects the string literal containing the cursor, including its quotes. Repeating it
+        /// selects the enclosing string literal.
+        SelectAroundString,
         /// Selects to the start of the current excerpt.
         SelectToStartOfExcerpt,
         /// Selects to the start of the next excerpt.
@@ -676,12 +1027,21 @@ actions!(
         SelectDown,
         /// Selects the enclosing symbol.
         SelectEnclosingSymbol,
+        /// Selects the contents of the string literal containing the cursor, without its quotes.
+        /// Repeating it selects the contents of the enclosing string literal.
+        SelectInsideString,
         /// Selects the next larger syntax node.
         SelectLargerSyntaxNode,
         /// Selects the next syntax node sibling.
         SelectNextSyntaxNode,
         /// Selects the previous syntax node sibling.
         SelectPreviousSyntaxNode,
+        /// Selects the next named sibling of the syntax node under the cursor, without leaving its
+        /// parent.
+        SelectNextSyntaxSibling,
+        /// Selects the previous named sibling of the syntax node under the cursor, without leaving
+        /// its parent.
+        SelectPreviousSyntaxSibling,
         /// Extends selection left.
         SelectLeft,
         /// Selects the current line.
@@ -716,6 +1076,8 @@ actions!(
         ShowCharacterPalette,
         /// Shows edit prediction at cursor.
         ShowEditPrediction,
+        /// Shows the lines of every level in a log file.
+        ShowAllLogLevels,
         /// Shows signature help for the current function.
         ShowSignatureHelp,
         /// Shows word completions.
@@ -732,14 +1094,24 @@ actions!(
         SortLinesCaseInsensitive,
         /// Sorts selected lines case-sensitively.
         SortLinesCaseSensitive,
+        /// Sorts selected lines by the number they start with.
+        SortLinesNumeric,
+        /// Stages the diff hunks at the cursors, without moving to the next hunk.
+        StageHunkAtCursor,
         /// Stops the language server for the current file.
         StopLanguageServer,
+        /// Stops recording the current keyboard macro.
+        StopRecordingMacro,
+        /// Swaps the syntax node under the cursor with its next sibling, keeping the cursor in place.
+        SwapWithNextSibling,
         /// Switches between source and header files.
         SwitchSourceHeader,
         /// Inserts a tab character or indents.
         Tab,
         /// Removes a tab character or outdents.
         Backtab,
+        /// Toggles a bookmark at the current line.
+        ToggleBookmark,
         /// Toggles a breakpoint at the current line.
         ToggleBreakpoint,
         /// Toggles the case of selected text.
@@ -754,14 +1126,26 @@ actions!(
         ToggleAutoSignatureHelp,
         /// Toggles inline git blame display.
         ToggleGitBlameInline,
+        /// Toggles showing the uncommitted changes inline, with every diff hunk in the editor
+        /// expanded to show its deleted lines.
+        ToggleInlineDiff,
         /// Opens the git commit for the blame at cursor.
         OpenGitBlameCommit,
         /// Toggles the diagnostics panel.
         ToggleDiagnostics,
+        /// Pins a highlight with its own color on every occurrence of the word under the cursor,
+        /// or removes it if the word is already highlighted.
+        ToggleHighlightWordUnderCursor,
         /// Toggles indent guides display.
         ToggleIndentGuides,
         /// Toggles inlay hints display.
         ToggleInlayHints,
+        /// Toggles the display of inlay hints without a kind, such as chaining hints.
+        ToggleOtherInlayHints,
+        /// Toggles the display of parameter name inlay hints.
+        ToggleParameterInlayHints,
+        /// Toggles the display of type inlay hints.
+        ToggleTypeInlayHints,
         /// Toggles inline values display.
         ToggleInlineValues,
         /// Toggles inline diagnostics display.
@@ -772,10 +1156,20 @@ actions!(
         ToggleLineNumbers,
         /// Toggles the minimap display.
         ToggleMinimap,
+        /// Pins the hover popover so that it stays open while editing, or unpins it.
+        TogglePinnedHover,
+        /// Toggles typewriter scrolling, which keeps the cursor line at a fixed row of the viewport.
+        ToggleTypewriterScrolling,
+        /// Toggles between rendering ANSI escape sequences as text styles and showing them raw.
+        ToggleAnsiEscapes,
+        /// Toggles padding the columns of CSV and TSV files so that they line up, without editing the file.
+        ToggleCsvAlignment,
         /// Swaps the start and end of the current selection.
         SwapSelectionEnds,
         /// Sets a mark at the current position.
         SetMark,
+        /// Locks the file against edits in every editor that shows it, or unlocks it.
+        ToggleReadOnly,
         /// Toggles relative line numbers display.
         ToggleRelativeLineNumbers,
         /// Toggles diff display for selected hunks.
@@ -785,16 +1179,26 @@ actions!(
         ToggleSelectionMenu,
         /// Toggles soft wrap mode.
         ToggleSoftWrap,
+        /// Toggles underlining the syntax errors that the parser found in the file.
+        ToggleSyntaxErrors,
         /// Toggles the tab bar display.
         ToggleTabBar,
         /// Transposes characters around cursor.
         Transpose,
+        /// Swaps the line of each cursor with the line above it.
+        TransposeLines,
+        /// Swaps the word before each cursor with the word after it.
+        TransposeWords,
         /// Undoes the last edit.
         Undo,
         /// Undoes the last selection change.
         UndoSelection,
         /// Unfolds all folded regions.
         UnfoldAll,
+        /// Unfolds all regions delimited by region markers, such as `// #region` and `// #endregion`.
+        UnfoldAllRegions,
+        /// Unfolds the imports at the top of each file.
+        UnfoldImports,
         /// Unfolds lines at cursor.
         UnfoldLines,
         /// Unfolds recursively at cursor.
@@ -803,10 +1207,20 @@ actions!(
         UniqueLinesCaseInsensitive,
         /// Removes duplicate lines (case-sensitive).
         UniqueLinesCaseSensitive,
+        /// Unstages the diff hunks at the cursors, without moving to the next hunk.
+        UnstageHunkAtCursor,
         /// Removes the surrounding syntax node (for example brackets, or closures)
         /// from the current selections.
         UnwrapSyntaxNode,
+        /// Opens all bookmarks in the project in a multibuffer.
+        ViewBookmarks,
         /// Wraps selections in tag specified by language.
-        WrapSelectionsInTag
+        WrapSelectionsInTag,
+        /// Increases the font size of this editor only.
+        ZoomIn,
+        /// Decreases the font size of this editor only.
+        ZoomOut,
+        /// Resets the font size of this editor to the buffer font size.
+        ZoomReset
     ]
 );
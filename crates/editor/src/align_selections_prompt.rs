@@ -0,0 +1,103 @@
+//! The prompt opened by `editor::AlignSelectionsOn`, which asks for the text to align the
+//! cursors on before running `editor::AlignSelections` with it.
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription,
+    WeakEntity, Window, prelude::*,
+};
+use ui::prelude::*;
+use workspace::ModalView;
+
+use crate::{
+    Editor, EditorEvent,
+    actions::{AlignSelections, AlignSelectionsOn},
+};
+
+pub struct AlignSelectionsPrompt {
+    text_editor: Entity<Editor>,
+    active_editor: WeakEntity<Editor>,
+    _subscription: Subscription,
+}
+
+impl ModalView for AlignSelectionsPrompt {}
+
+impl EventEmitter<DismissEvent> for AlignSelectionsPrompt {}
+
+impl Focusable for AlignSelectionsPrompt {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.text_editor.focus_handle(cx)
+    }
+}
+
+impl AlignSelectionsPrompt {
+    fn new(active_editor: WeakEntity<Editor>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let text_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Text to align on, such as = or :", window, cx);
+            editor
+        });
+        let subscription = cx.subscribe_in(&text_editor, window, |_, _, event, _, cx| {
+            if let EditorEvent::Blurred = event {
+                cx.emit(DismissEvent);
+            }
+        });
+        Self {
+            text_editor,
+            active_editor,
+            _subscription: subscription,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let align_on = self.text_editor.read(cx).text(cx);
+        self.active_editor
+            .update(cx, |editor, cx| {
+                if !align_on.is_empty() {
+                    let action = AlignSelections {
+                        align_on: Some(align_on),
+                    };
+                    editor.align_selections(&action, window, cx);
+                }
+                editor.focus_handle(cx).focus(window);
+            })
+            .ok();
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for AlignSelectionsPrompt {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .w(rems(24.))
+            .elevation_2(cx)
+            .key_context("AlignSelectionsPrompt")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(div().px_2().py_1().child(self.text_editor.clone()))
+    }
+}
+
+impl Editor {
+    pub fn align_selections_on(
+        &mut self,
+        _: &AlignSelectionsOn,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.read_only(cx) {
+            return;
+        }
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        let active_editor = cx.entity().downgrade();
+        workspace.update(cx, |workspace, cx| {
+            workspace.toggle_modal(window, cx, move |window, cx| {
+                AlignSelectionsPrompt::new(active_editor, window, cx)
+            });
+        });
+    }
+}
@@ -0,0 +1,348 @@
+//! Renders ANSI escape sequences in read-only buffers, such as logs and captured terminal output,
+//! as text styles: the escape sequences are folded away and the text they style is highlighted.
+use std::{any::TypeId, ops::Range, sync::Arc};
+
+use collections::HashMap;
+use gpui::{
+    App, Context, Empty, FontStyle, FontWeight, HighlightStyle, Hsla, IntoElement as _, Rgba,
+    UnderlineStyle, Window, px,
+};
+use multi_buffer::Anchor;
+use settings::Settings as _;
+use theme::{ActiveTheme as _, Theme};
+
+use crate::{
+    Editor, EditorSettings, ToggleAnsiEscapes,
+    display_map::{Crease, FoldPlaceholder},
+};
+
+const ESCAPE: u8 = 0x1b;
+
+struct AnsiEscapeFold;
+struct AnsiEscapeHighlight;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnsiColor {
+    /// One of the 256 colors of the xterm palette, the first 16 of which come from the theme.
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AnsiStyle {
+    pub foreground: Option<AnsiColor>,
+    pub background: Option<AnsiColor>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ParsedAnsi {
+    /// The byte ranges of every control sequence, including those that don't set a style.
+    pub escapes: Vec<Range<usize>>,
+    /// The byte ranges of styled text, excluding escape sequences.
+    pub styles: Vec<(Range<usize>, AnsiStyle)>,
+}
+
+/// Finds the control sequences (`ESC [ ... <final byte>`) in `text` and the styles that their
+/// "select graphic rendition" (`m`) sequences apply to the text following them.
+pub fn parse_ansi(text: &str) -> ParsedAnsi {
+    let bytes = text.as_bytes();
+    let mut parsed = ParsedAnsi::default();
+    let mut style = AnsiStyle::default();
+    let mut run_start = 0;
+    let mut ix = 0;
+    while ix < bytes.len() {
+        if bytes[ix] != ESCAPE || bytes.get(ix + 1) != Some(&b'[') {
+            ix += 1;
+            continue;
+        }
+        let params_start = ix + 2;
+        let Some(final_ix) = bytes[params_start..]
+            .iter()
+            .position(|byte| !(0x20..=0x3f).contains(byte))
+            .map(|offset| params_start + offset)
+            .filter(|final_ix| (0x40..=0x7e).contains(&bytes[*final_ix]))
+        else {
+            ix += 1;
+            continue;
+        };
+
+        if run_start < ix && style != AnsiStyle::default() {
+            parsed.styles.push((run_start..ix, style));
+        }
+        if bytes[final_ix] == b'm' {
+            apply_sgr(&mut style, &text[params_start..final_ix]);
+        }
+        parsed.escapes.push(ix..final_ix + 1);
+        ix = final_ix + 1;
+        run_start = ix;
+    }
+    if run_start < bytes.len() && style != AnsiStyle::default() {
+        parsed.styles.push((run_start..bytes.len(), style));
+    }
+    parsed
+}
+
+fn apply_sgr(style: &mut AnsiStyle, params: &str) {
+    let mut params = params
+        .split([';', ':'])
+        .map(|param| param.parse::<u16>().unwrap_or(0));
+    while let Some(param) = params.next() {
+        match param {
+            0 => *style = AnsiStyle::default(),
+            1 => style.bold = true,
+            3 => style.italic = true,
+            4 => style.underline = true,
+            22 => style.bold = false,
+            23 => style.italic = false,
+            24 => style.underline = false,
+            30..=37 => style.foreground = Some(AnsiColor::Indexed(param as u8 - 30)),
+            38 => style.foreground = extended_color(&mut params).or(style.foreground),
+            39 => style.foreground = None,
+            40..=47 => style.background = Some(AnsiColor::Indexed(param as u8 - 40)),
+            48 => style.background = extended_color(&mut params).or(style.background),
+            49 => style.background = None,
+            90..=97 => style.foreground = Some(AnsiColor::Indexed(param as u8 - 90 + 8)),
+            100..=107 => style.background = Some(AnsiColor::Indexed(param as u8 - 100 + 8)),
+            _ => {}
+        }
+    }
+}
+
+/// Parses the `5;<index>` or `2;<r>;<g>;<b>` parameters that follow a 38 or 48.
+fn extended_color(params: &mut impl Iterator<Item = u16>) -> Option<AnsiColor> {
+    let mut component = || params.next().map(|param| param.min(255) as u8);
+    match component()? {
+        5 => Some(AnsiColor::Indexed(component()?)),
+        2 => Some(AnsiColor::Rgb(component()?, component()?, component()?)),
+        _ => None,
+    }
+}
+
+fn ansi_color_to_hsla(color: AnsiColor, theme: &Theme) -> Hsla {
+    let colors = theme.colors();
+    match color {
+        AnsiColor::Indexed(index) => match index {
+            0 => colors.terminal_ansi_black,
+            1 => colors.terminal_ansi_red,
+            2 => colors.terminal_ansi_green,
+            3 => colors.terminal_ansi_yellow,
+            4 => colors.terminal_ansi_blue,
+            5 => colors.terminal_ansi_magenta,
+            6 => colors.terminal_ansi_cyan,
+            7 => colors.terminal_ansi_white,
+            8 => colors.terminal_ansi_bright_black,
+            9 => colors.terminal_ansi_bright_red,
+            10 => colors.terminal_ansi_bright_green,
+            11 => colors.terminal_ansi_bright_yellow,
+            12 => colors.terminal_ansi_bright_blue,
+            13 => colors.terminal_ansi_bright_magenta,
+            14 => colors.terminal_ansi_bright_cyan,
+            15 => colors.terminal_ansi_bright_white,
+            // A 6x6x6 color cube.
+            16..=231 => {
+                let index = index - 16;
+                let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+                rgb_to_hsla(level(index / 36), level(index / 6 % 6), level(index % 6))
+            }
+            // A grayscale ramp.
+            232..=255 => {
+                let level = 8 + (index - 232) * 10;
+                rgb_to_hsla(level, level, level)
+            }
+        },
+        AnsiColor::Rgb(r, g, b) => rgb_to_hsla(r, g, b),
+    }
+}
+
+fn rgb_to_hsla(r: u8, g: u8, b: u8) -> Hsla {
+    Rgba {
+        r: r as f32 / 255.,
+        g: g as f32 / 255.,
+        b: b as f32 / 255.,
+        a: 1.,
+    }
+    .into()
+}
+
+fn highlight_style(style: &AnsiStyle, theme: &Theme) -> HighlightStyle {
+    HighlightStyle {
+        color: style
+            .foreground
+            .map(|color| ansi_color_to_hsla(color, theme)),
+        background_color: style
+            .background
+            .map(|color| ansi_color_to_hsla(color, theme)),
+        font_weight: style.bold.then_some(FontWeight::BOLD),
+        font_style: style.italic.then_some(FontStyle::Italic),
+        underline: style.underline.then(|| UnderlineStyle {
+            thickness: px(1.),
+            ..UnderlineStyle::default()
+        }),
+        ..HighlightStyle::default()
+    }
+}
+
+impl Editor {
+    /// Whether ANSI escape sequences are rendered as text styles, which by default happens in
+    /// read-only editors when the `render_ansi_escapes` setting is enabled.
+    pub fn ansi_escapes_rendered(&self, cx: &App) -> bool {
+        self.mode.is_full()
+            && self.render_ansi_escapes.unwrap_or_else(|| {
+                self.read_only(cx) && EditorSettings::get_global(cx).render_ansi_escapes
+            })
+    }
+
+    pub fn toggle_ansi_escapes(
+        &mut self,
+        _: &ToggleAnsiEscapes,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.render_ansi_escapes = Some(!self.ansi_escapes_rendered(cx));
+        self.refresh_ansi_escapes(window, cx);
+    }
+
+    pub(crate) fn refresh_ansi_escapes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let type_id = TypeId::of::<AnsiEscapeFold>();
+        let render = self.ansi_escapes_rendered(cx);
+        if !render && !self.ansi_escapes_applied {
+            return;
+        }
+        self.ansi_escapes_applied = render;
+
+        let snapshot = self.snapshot(window, cx);
+        let task = cx.background_spawn(async move {
+            let buffer = snapshot.buffer_snapshot();
+            let parsed = if render {
+                parse_ansi(&buffer.text())
+            } else {
+                ParsedAnsi::default()
+            };
+            let anchor_range = |range: Range<usize>| {
+                buffer.anchor_after(range.start)..buffer.anchor_before(range.end)
+            };
+            let new_escapes = parsed
+                .escapes
+                .into_iter()
+                .map(anchor_range)
+                .collect::<Vec<_>>();
+            let existing_escapes = snapshot
+                .folds_in_range(0..buffer.len())
+                .filter(|fold| fold.placeholder.type_tag == Some(type_id))
+                .map(|fold| fold.range.start..fold.range.end)
+                .collect::<Vec<_>>();
+            let mut styles = HashMap::<AnsiStyle, Vec<Range<Anchor>>>::default();
+            for (range, style) in parsed.styles {
+                styles.entry(style).or_default().push(anchor_range(range));
+            }
+            (new_escapes, existing_escapes, styles)
+        });
+
+        self.ansi_escapes_task = cx.spawn(async move |this, cx| {
+            let (new_escapes, existing_escapes, styles) = task.await;
+            this.update(cx, |this, cx| {
+                if new_escapes != existing_escapes {
+                    let placeholder = FoldPlaceholder {
+                        render: Arc::new(|_, _, _| Empty.into_any_element()),
+                        constrain_width: false,
+                        merge_adjacent: false,
+                        type_tag: Some(type_id),
+                    };
+                    let creases = new_escapes
+                        .into_iter()
+                        .map(|range| Crease::simple(range, placeholder.clone()))
+                        .collect();
+                    this.display_map.update(cx, |display_map, cx| {
+                        display_map.remove_folds_with_type(existing_escapes, type_id, cx);
+                        display_map.fold(creases, cx);
+                    });
+                }
+
+                this.display_map.update(cx, |display_map, _| {
+                    display_map.clear_keyed_highlights(TypeId::of::<AnsiEscapeHighlight>());
+                });
+                let theme = cx.theme().clone();
+                for (key, (style, ranges)) in styles.into_iter().enumerate() {
+                    this.highlight_text_key::<AnsiEscapeHighlight>(
+                        key,
+                        ranges,
+                        highlight_style(&style, &theme),
+                        cx,
+                    );
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ansi() {
+        let text = "plain \x1b[1;31mbold red\x1b[0m \x1b[2K\x1b[38;5;208morange\x1b[39;4m_\x1b[m";
+        let parsed = parse_ansi(text);
+        let escaped = |range: &Range<usize>| &text[range.clone()];
+        assert_eq!(
+            parsed.escapes.iter().map(escaped).collect::<Vec<_>>(),
+            [
+                "\x1b[1;31m",
+                "\x1b[0m",
+                "\x1b[2K",
+                "\x1b[38;5;208m",
+                "\x1b[39;4m",
+                "\x1b[m"
+            ]
+        );
+        assert_eq!(
+            parsed
+                .styles
+                .iter()
+                .map(|(range, style)| (escaped(range), *style))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    "bold red",
+                    AnsiStyle {
+                        foreground: Some(AnsiColor::Indexed(1)),
+                        bold: true,
+                        ..AnsiStyle::default()
+                    }
+                ),
+                (
+                    "orange",
+                    AnsiStyle {
+                        foreground: Some(AnsiColor::Indexed(208)),
+                        ..AnsiStyle::default()
+                    }
+                ),
+                (
+                    "_",
+                    AnsiStyle {
+                        underline: true,
+                        ..AnsiStyle::default()
+                    }
+                ),
+            ]
+        );
+
+        let parsed = parse_ansi("\x1b[48;2;10;20;30mbg\x1b[12");
+        assert_eq!(parsed.escapes, [0..16]);
+        assert_eq!(
+            parsed.styles,
+            [(
+                16..22,
+                AnsiStyle {
+                    background: Some(AnsiColor::Rgb(10, 20, 30)),
+                    ..AnsiStyle::default()
+                }
+            )]
+        );
+    }
+}
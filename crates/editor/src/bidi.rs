@@ -0,0 +1,225 @@
+//! Bidirectional text support. Lines are stored in logical order, but lines that mix
+//! left-to-right text with Arabic or Hebrew are shaped in visual order, so positions in the line
+//! need to be mapped between the two orders when placing carets, selections and mouse clicks.
+use std::ops::Range;
+
+use gpui::{Pixels, ShapedLine};
+use unicode_bidi::{BidiClass, BidiInfo, bidi_class};
+
+/// Whether `text` contains any right-to-left characters, outside of which no reordering happens.
+pub(crate) fn contains_rtl(text: &str) -> bool {
+    text.chars()
+        .any(|ch| matches!(bidi_class(ch), BidiClass::R | BidiClass::AL))
+}
+
+/// Returns the caret offsets of `text` from left to right on screen. Like in [`BidiLayout`], the
+/// caret at an offset is on the leading edge of the character starting there, which is its right
+/// edge for right-to-left characters, and on the trailing edge of the last character at the end.
+pub(crate) fn visual_carets(text: &str) -> Vec<usize> {
+    let bidi_info = BidiInfo::new(text, None);
+    let Some(paragraph) = bidi_info.paragraphs.first() else {
+        return vec![0];
+    };
+    let (levels, runs) = bidi_info.visual_runs(paragraph, paragraph.range.clone());
+
+    // Every character has two edges on screen, with the left edge of the character at visual
+    // position `ix` sorting as `2 * ix`.
+    let mut carets = Vec::with_capacity(text.len() + 1);
+    let mut visual_ix = 0;
+    for run in runs {
+        let rtl = levels[run.start].is_rtl();
+        let mut chars = text[run.clone()]
+            .char_indices()
+            .map(|(ix, ch)| run.start + ix..run.start + ix + ch.len_utf8())
+            .collect::<Vec<_>>();
+        if rtl {
+            chars.reverse();
+        }
+        for char_range in chars {
+            let (left, right) = (2 * visual_ix, 2 * visual_ix + 1);
+            carets.push((if rtl { right } else { left }, char_range.start));
+            if char_range.end == text.len() {
+                carets.push((if rtl { left } else { right }, char_range.end));
+            }
+            visual_ix += 1;
+        }
+    }
+    carets.sort_unstable();
+    carets.into_iter().map(|(_, offset)| offset).collect()
+}
+
+/// A grapheme cluster of a shaped line, with its logical byte range and its horizontal extent.
+#[derive(Clone, Debug, PartialEq)]
+struct Cluster {
+    range: Range<usize>,
+    x: Range<Pixels>,
+    rtl: bool,
+}
+
+/// The caret positions of a shaped line that contains right-to-left text.
+#[derive(Clone, Debug)]
+pub(crate) struct BidiLayout {
+    /// Clusters in visual order, from left to right.
+    clusters: Vec<Cluster>,
+    len: usize,
+    width: Pixels,
+}
+
+impl BidiLayout {
+    /// Returns the layout of `line` if it has right-to-left text, as the shaper lays its glyphs
+    /// out in visual order.
+    pub fn for_line(line: &ShapedLine) -> Option<Self> {
+        if !contains_rtl(&line.text) {
+            return None;
+        }
+        let glyphs = line
+            .runs
+            .iter()
+            .flat_map(|run| &run.glyphs)
+            .map(|glyph| (glyph.index, glyph.position.x));
+        Some(Self::new(&line.text, glyphs, line.width))
+    }
+
+    fn new(text: &str, glyphs: impl IntoIterator<Item = (usize, Pixels)>, width: Pixels) -> Self {
+        let bidi_info = BidiInfo::new(text, None);
+
+        let mut glyphs = glyphs.into_iter().collect::<Vec<_>>();
+        glyphs.sort_by_key(|(_, x)| *x);
+        // Ligatures and combining marks produce several glyphs for one cluster.
+        glyphs.dedup_by_key(|(index, _)| *index);
+
+        let mut cluster_starts = glyphs.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+        cluster_starts.sort_unstable();
+        cluster_starts.dedup();
+
+        let clusters = glyphs
+            .iter()
+            .enumerate()
+            .map(|(ix, &(start, left))| {
+                let next_start = cluster_starts.partition_point(|&index| index <= start);
+                let end = cluster_starts
+                    .get(next_start)
+                    .copied()
+                    .unwrap_or(text.len());
+                let right = glyphs.get(ix + 1).map_or(width, |(_, x)| *x);
+                Cluster {
+                    range: start..end,
+                    x: left..right,
+                    rtl: bidi_info
+                        .levels
+                        .get(start)
+                        .is_some_and(|level| level.is_rtl()),
+                }
+            })
+            .collect();
+
+        Self {
+            clusters,
+            len: text.len(),
+            width,
+        }
+    }
+
+    /// The x position of the caret before the character at `index`, which is the right edge of
+    /// right-to-left characters.
+    pub fn x_for_index(&self, index: usize) -> Pixels {
+        if index >= self.len {
+            return self
+                .clusters
+                .iter()
+                .find(|cluster| cluster.range.end == self.len)
+                .map_or(self.width, |cluster| {
+                    if cluster.rtl {
+                        cluster.x.start
+                    } else {
+                        cluster.x.end
+                    }
+                });
+        }
+        self.clusters
+            .iter()
+            .find(|cluster| cluster.range.contains(&index))
+            .map_or(self.width, |cluster| {
+                if cluster.rtl {
+                    cluster.x.end
+                } else {
+                    cluster.x.start
+                }
+            })
+    }
+
+    /// The index of the character drawn at `x`, if any.
+    pub fn index_for_x(&self, x: Pixels) -> Option<usize> {
+        if x >= self.width {
+            return None;
+        }
+        let ix = self
+            .clusters
+            .partition_point(|cluster| cluster.x.start <= x)
+            .saturating_sub(1);
+        Some(
+            self.clusters
+                .get(ix)
+                .map_or(0, |cluster| cluster.range.start),
+        )
+    }
+
+    /// The horizontal extents that cover the characters in `range`, from left to right. A range
+    /// crossing a change of direction is drawn as several segments.
+    pub fn x_ranges_for_range(&self, range: Range<usize>) -> Vec<Range<Pixels>> {
+        let mut x_ranges = Vec::<Range<Pixels>>::new();
+        for cluster in &self.clusters {
+            if cluster.range.start < range.start || cluster.range.start >= range.end {
+                continue;
+            }
+            match x_ranges.last_mut() {
+                Some(last) if last.end == cluster.x.start => last.end = cluster.x.end,
+                _ => x_ranges.push(cluster.x.clone()),
+            }
+        }
+        x_ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::px;
+
+    #[test]
+    fn test_visual_carets() {
+        assert!(!contains_rtl("let x = 1;"));
+        assert!(contains_rtl("let x = \"שלום\";"));
+
+        assert_eq!(visual_carets("abc"), [0, 1, 2, 3]);
+        assert_eq!(visual_carets(""), [0]);
+        // Hebrew letters are two bytes long, and are shown from right to left.
+        assert_eq!(visual_carets("ab אב"), [0, 1, 2, 7, 5, 3]);
+        assert_eq!(visual_carets("אב c"), [5, 6, 4, 2, 0]);
+    }
+
+    #[test]
+    fn test_bidi_layout() {
+        // "ab אב" is shown as "ab בא", with every character 10 pixels wide.
+        let text = "ab אב";
+        let glyphs = [(0, 0.), (1, 10.), (2, 20.), (5, 30.), (3, 40.)];
+        let layout = BidiLayout::new(text, glyphs.map(|(ix, x)| (ix, px(x))), px(50.));
+
+        assert_eq!(layout.x_for_index(0), px(0.));
+        assert_eq!(layout.x_for_index(3), px(50.));
+        assert_eq!(layout.x_for_index(5), px(40.));
+        assert_eq!(layout.x_for_index(7), px(30.));
+
+        assert_eq!(layout.index_for_x(px(5.)), Some(0));
+        assert_eq!(layout.index_for_x(px(35.)), Some(5));
+        assert_eq!(layout.index_for_x(px(45.)), Some(3));
+        assert_eq!(layout.index_for_x(px(55.)), None);
+
+        // Selecting "b א" covers "b " and "א", which aren't next to each other on screen.
+        assert_eq!(
+            layout.x_ranges_for_range(1..5),
+            [px(10.)..px(30.), px(40.)..px(50.)]
+        );
+        assert_eq!(layout.x_ranges_for_range(3..7), [px(30.)..px(50.)]);
+    }
+}
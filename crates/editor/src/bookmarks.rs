@@ -0,0 +1,277 @@
+use std::ops::Range;
+
+use collections::HashMap;
+use gpui::{ClickEvent, Context, Window};
+use language::{Bias, Point};
+use multi_buffer::{Anchor, MultiBufferSnapshot, ToPoint as _};
+use project::bookmark_store::BookmarkStore;
+use ui::{Tooltip, prelude::*};
+use workspace::Workspace;
+
+use crate::{
+    Autoscroll, DisplayPoint, DisplayRow, Editor, MultibufferSelectionMode, SelectionEffects,
+    ToDisplayPoint as _,
+    actions::{
+        ClearBookmarks, GoToNextBookmark, GoToPreviousBookmark, ToggleBookmark, ViewBookmarks,
+    },
+};
+
+impl Editor {
+    pub fn toggle_bookmark(
+        &mut self,
+        _: &ToggleBookmark,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut rows = self
+            .selections
+            .all::<Point>(cx)
+            .into_iter()
+            .map(|selection| selection.head().row)
+            .collect::<Vec<_>>();
+        rows.dedup();
+        for row in rows {
+            let position = snapshot.anchor_after(Point::new(row, 0));
+            self.toggle_bookmark_at_anchor(position, cx);
+        }
+    }
+
+    pub fn toggle_bookmark_at_anchor(&mut self, position: Anchor, cx: &mut Context<Self>) {
+        let Some(bookmark_store) = self.bookmark_store.clone() else {
+            return;
+        };
+        let Some(buffer) = self.buffer.read(cx).buffer_for_anchor(position, cx) else {
+            return;
+        };
+
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.toggle_bookmark(buffer, position.text_anchor, cx);
+        });
+        cx.notify();
+    }
+
+    pub fn clear_bookmarks(
+        &mut self,
+        _: &ClearBookmarks,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(bookmark_store) = self.bookmark_store.clone() {
+            bookmark_store.update(cx, |bookmark_store, cx| bookmark_store.clear_bookmarks(cx));
+        }
+    }
+
+    pub fn go_to_next_bookmark(
+        &mut self,
+        _: &GoToNextBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let rows = self.bookmarked_rows(cx);
+        let cursor_row = self.selections.newest::<Point>(cx).head().row;
+        let row = rows
+            .iter()
+            .find(|row| **row > cursor_row)
+            .or(rows.first())
+            .copied();
+        self.go_to_bookmarked_row(row, window, cx);
+    }
+
+    pub fn go_to_previous_bookmark(
+        &mut self,
+        _: &GoToPreviousBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let rows = self.bookmarked_rows(cx);
+        let cursor_row = self.selections.newest::<Point>(cx).head().row;
+        let row = rows
+            .iter()
+            .rev()
+            .find(|row| **row < cursor_row)
+            .or(rows.last())
+            .copied();
+        self.go_to_bookmarked_row(row, window, cx);
+    }
+
+    fn go_to_bookmarked_row(
+        &mut self,
+        row: Option<u32>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(row) = row else {
+            return;
+        };
+        let destination = Point::new(row, 0);
+        self.unfold_ranges(&[destination..destination], false, false, cx);
+        self.change_selections(
+            SelectionEffects::scroll(Autoscroll::center()),
+            window,
+            cx,
+            |s| {
+                s.select_ranges([destination..destination]);
+            },
+        );
+    }
+
+    /// Whether the editor's singleton buffer has any bookmarks, which need room in the gutter.
+    pub(crate) fn has_bookmarks(&self, cx: &App) -> bool {
+        let Some(bookmark_store) = self.bookmark_store.as_ref() else {
+            return false;
+        };
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return false;
+        };
+        bookmark_store.read(cx).has_bookmarks(&buffer, cx)
+    }
+
+    /// Returns the sorted multibuffer rows that have a bookmark on them.
+    fn bookmarked_rows(&self, cx: &App) -> Vec<u32> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut rows = self
+            .bookmarks_in_range(Point::zero()..snapshot.max_point(), &snapshot, cx)
+            .into_iter()
+            .map(|anchor| anchor.to_point(&snapshot).row)
+            .collect::<Vec<_>>();
+        rows.sort_unstable();
+        rows.dedup();
+        rows
+    }
+
+    fn bookmarks_in_range(
+        &self,
+        range: Range<Point>,
+        snapshot: &MultiBufferSnapshot,
+        cx: &App,
+    ) -> Vec<Anchor> {
+        let Some(bookmark_store) = self.bookmark_store.as_ref() else {
+            return Vec::new();
+        };
+        let bookmark_store = bookmark_store.read(cx);
+        let multi_buffer = self.buffer.read(cx);
+
+        let mut bookmarks = Vec::new();
+        for (buffer_snapshot, range, excerpt_id) in snapshot.range_to_buffer_ranges(range) {
+            let Some(buffer) = multi_buffer.buffer(buffer_snapshot.remote_id()) else {
+                continue;
+            };
+            let range =
+                buffer_snapshot.anchor_before(range.start)..buffer_snapshot.anchor_after(range.end);
+            bookmarks.extend(
+                bookmark_store
+                    .bookmarks(&buffer, Some(range), buffer_snapshot, cx)
+                    .map(|bookmark| {
+                        Anchor::in_buffer(excerpt_id, buffer_snapshot.remote_id(), *bookmark)
+                    }),
+            );
+        }
+        bookmarks
+    }
+
+    /// Get all display rows with a bookmark that will be rendered within the editor.
+    pub(crate) fn active_bookmarks(
+        &self,
+        range: Range<DisplayRow>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> HashMap<DisplayRow, Anchor> {
+        if self.bookmark_store.is_none() {
+            return HashMap::default();
+        }
+
+        let snapshot = self.snapshot(window, cx);
+        let multi_buffer_snapshot = snapshot.display_snapshot.buffer_snapshot();
+        let range = snapshot.display_point_to_point(DisplayPoint::new(range.start, 0), Bias::Left)
+            ..snapshot.display_point_to_point(DisplayPoint::new(range.end, 0), Bias::Right);
+
+        self.bookmarks_in_range(range, multi_buffer_snapshot, cx)
+            .into_iter()
+            .map(|anchor| {
+                let row = anchor
+                    .to_point(multi_buffer_snapshot)
+                    .to_display_point(&snapshot)
+                    .row();
+                (row, anchor)
+            })
+            .collect()
+    }
+
+    /// Get the login of the collaborator who added the bookmark at `position` in a shared project.
+    fn bookmark_author(&self, position: Anchor, cx: &App) -> Option<SharedString> {
+        let bookmark_store = self.bookmark_store.as_ref()?.read(cx);
+        let buffer = self.buffer.read(cx).buffer_for_anchor(position, cx)?;
+        let abs_path = BookmarkStore::abs_path_from_buffer(&buffer, cx)?;
+        let row = position.text_anchor.summary::<Point>(buffer.read(cx)).row;
+        let author = bookmark_store.bookmark_at_row(&abs_path, row, cx)?.author?;
+        let user = self
+            .project
+            .as_ref()?
+            .read(cx)
+            .user_store()
+            .read(cx)
+            .get_cached_user(author)?;
+        Some(format!("@{}", user.github_login).into())
+    }
+
+    pub(crate) fn render_bookmark(
+        &self,
+        position: Anchor,
+        row: DisplayRow,
+        cx: &mut Context<Self>,
+    ) -> IconButton {
+        let focus_handle = self.focus_handle.clone();
+        let tooltip: SharedString = match self.bookmark_author(position, cx) {
+            Some(author) => format!("Remove bookmark added by {author}").into(),
+            None => "Remove bookmark".into(),
+        };
+        IconButton::new(("bookmark_indicator", row.0 as usize), IconName::Bookmark)
+            .icon_size(IconSize::XSmall)
+            .size(ButtonSize::None)
+            .icon_color(Color::Accent)
+            .style(ButtonStyle::Transparent)
+            .on_click(cx.listener(move |editor, _: &ClickEvent, window, cx| {
+                window.focus(&editor.focus_handle);
+                editor.toggle_bookmark_at_anchor(position, cx);
+            }))
+            .tooltip(move |window, cx| {
+                Tooltip::for_action_in(tooltip.clone(), &ToggleBookmark, &focus_handle, window, cx)
+            })
+    }
+
+    pub fn view_bookmarks(
+        workspace: &mut Workspace,
+        _: &ViewBookmarks,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let bookmark_store = workspace.project().read(cx).bookmark_store();
+        let mut locations = std::collections::HashMap::default();
+        for (buffer, bookmarks) in bookmark_store.read(cx).all_bookmarks() {
+            let snapshot = buffer.read(cx).snapshot();
+            let ranges = bookmarks
+                .iter()
+                .map(|bookmark| {
+                    let row = bookmark.position.summary::<Point>(&snapshot).row;
+                    Point::new(row, 0)..Point::new(row, snapshot.line_len(row))
+                })
+                .collect::<Vec<_>>();
+            locations.insert(buffer.clone(), ranges);
+        }
+
+        if locations.is_empty() {
+            return;
+        }
+
+        Self::open_locations_in_multibuffer(
+            workspace,
+            locations,
+            "Bookmarks".to_string(),
+            false,
+            MultibufferSelectionMode::First,
+            window,
+            cx,
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Navigating between the regions of the buffers that were edited since they were opened, as
+//! recorded in each buffer's edit history. Unlike diff hunks, these regions don't depend on git,
+//! and unlike the change list, they're visited in the order they appear in rather than the order
+//! they were made in.
+use gpui::{App, Context, Window};
+use language::Point;
+use multi_buffer::ToPoint as _;
+
+use crate::{
+    Autoscroll, Editor, SelectionEffects,
+    actions::{GoToLastEditLocation, GoToNextChangedRegion, GoToPreviousChangedRegion},
+};
+
+impl Editor {
+    pub fn go_to_next_changed_region(
+        &mut self,
+        _: &GoToNextChangedRegion,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let starts = self.changed_region_starts(cx);
+        let head = self.selections.newest::<Point>(cx).head();
+        let destination = starts
+            .iter()
+            .find(|start| **start > head)
+            .or(starts.first())
+            .copied();
+        self.go_to_changed_position(destination, window, cx);
+    }
+
+    pub fn go_to_previous_changed_region(
+        &mut self,
+        _: &GoToPreviousChangedRegion,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let starts = self.changed_region_starts(cx);
+        let head = self.selections.newest::<Point>(cx).head();
+        let destination = starts
+            .iter()
+            .rev()
+            .find(|start| **start < head)
+            .or(starts.last())
+            .copied();
+        self.go_to_changed_position(destination, window, cx);
+    }
+
+    pub fn go_to_last_edit_location(
+        &mut self,
+        _: &GoToLastEditLocation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(locations) = self.change_list.last().map(|locations| locations.to_vec()) else {
+            return;
+        };
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let locations = locations
+            .iter()
+            .map(|location| location.to_point(&snapshot))
+            .map(|point| point..point)
+            .collect::<Vec<_>>();
+        self.unfold_ranges(&locations, false, false, cx);
+        self.change_selections(
+            SelectionEffects::scroll(Autoscroll::center()).nav_history(true),
+            window,
+            cx,
+            |s| s.select_ranges(locations),
+        );
+    }
+
+    fn go_to_changed_position(
+        &mut self,
+        position: Option<Point>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(position) = position else {
+            return;
+        };
+        self.unfold_ranges(&[position..position], false, false, cx);
+        self.change_selections(
+            SelectionEffects::scroll(Autoscroll::center()),
+            window,
+            cx,
+            |s| s.select_ranges([position..position]),
+        );
+    }
+
+    /// Returns the sorted multibuffer positions at which the regions that were edited since
+    /// their buffers were opened start.
+    fn changed_region_starts(&self, cx: &App) -> Vec<Point> {
+        let multibuffer = self.buffer.read(cx);
+        let snapshot = multibuffer.snapshot(cx);
+        let mut starts = Vec::new();
+        for (excerpt_id, buffer_snapshot, excerpt_range) in snapshot.excerpts() {
+            let Some(buffer) = multibuffer.buffer(buffer_snapshot.remote_id()) else {
+                continue;
+            };
+            let since = buffer.read(cx).base_text_version();
+            starts.extend(
+                buffer_snapshot
+                    .anchored_edits_since_in_range::<usize>(&since, excerpt_range.context)
+                    .filter_map(|(_, range)| snapshot.anchor_in_excerpt(excerpt_id, range.start))
+                    .map(|start| start.to_point(&snapshot)),
+            );
+        }
+        starts.sort();
+        starts.dedup();
+        starts
+    }
+}
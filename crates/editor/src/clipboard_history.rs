@@ -0,0 +1,94 @@
+//! A ring of the most recent copies and cuts made in editors, for `editor::PasteFromHistory`.
+//!
+//! Copies made with several cursors keep each cursor's text apart, so that
+//! `editor::PastePreviousKillForEachCursor` can paste each cursor's earlier copy back at it.
+use std::collections::VecDeque;
+
+use gpui::{App, ClipboardEntry, ClipboardItem, Context, Global, Window};
+
+use crate::{
+    ClipboardSelection, Editor, HideMouseCursorOrigin, actions::PastePreviousKillForEachCursor,
+};
+
+const MAX_CLIPBOARD_HISTORY_LEN: usize = 32;
+
+#[derive(Clone, Debug)]
+pub struct ClipboardHistoryEntry {
+    pub text: String,
+    /// Per-selection metadata, used to split the text back across cursors when pasting.
+    pub selections: Option<Vec<ClipboardSelection>>,
+}
+
+impl ClipboardHistoryEntry {
+    /// The number of selections this entry was copied from.
+    pub fn selection_count(&self) -> usize {
+        self.selections
+            .as_ref()
+            .map_or(1, |selections| selections.len())
+    }
+}
+
+#[derive(Default)]
+struct ClipboardHistory {
+    entries: VecDeque<ClipboardHistoryEntry>,
+}
+
+impl Global for ClipboardHistory {}
+
+/// Writes `item` to the system clipboard and records it in the clipboard history.
+pub(crate) fn write_to_clipboard(item: ClipboardItem, cx: &mut App) {
+    if let Some(ClipboardEntry::String(string)) = item.entries().first()
+        && item.entries().len() == 1
+    {
+        let entry = ClipboardHistoryEntry {
+            text: string.text().clone(),
+            selections: string.metadata_json::<Vec<ClipboardSelection>>(),
+        };
+        let history = cx.default_global::<ClipboardHistory>();
+        history
+            .entries
+            .retain(|existing| existing.text != entry.text);
+        history.entries.push_front(entry);
+        history.entries.truncate(MAX_CLIPBOARD_HISTORY_LEN);
+    }
+    cx.write_to_clipboard(item);
+}
+
+/// Returns the recorded clipboard entries, most recent first.
+pub fn clipboard_history(cx: &App) -> Vec<ClipboardHistoryEntry> {
+    cx.try_global::<ClipboardHistory>()
+        .map(|history| history.entries.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+impl Editor {
+    pub fn paste_clipboard_history_entry(
+        &mut self,
+        entry: &ClipboardHistoryEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+        self.do_paste(&entry.text, entry.selections.clone(), true, window, cx);
+    }
+
+    pub fn paste_previous_kill_for_each_cursor(
+        &mut self,
+        _: &PastePreviousKillForEachCursor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let cursor_count = self.selections.count();
+        let Some(entry) = cx.try_global::<ClipboardHistory>().and_then(|history| {
+            history
+                .entries
+                .iter()
+                .filter(|entry| entry.selection_count() == cursor_count)
+                .nth(1)
+                .cloned()
+        }) else {
+            return;
+        };
+        self.paste_clipboard_history_entry(&entry, window, cx);
+    }
+}
@@ -1,7 +1,8 @@
-use crate::code_context_menus::CompletionsMenu;
+use crate::code_context_menus::{CompletionRanking, CompletionsMenu};
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::TestAppContext;
 use language::CodeLabel;
+use language::language_settings::LspSortTextMode;
 use lsp::{CompletionItem, CompletionItemKind, LanguageServerId};
 use project::{Completion, CompletionSource};
 use settings::SnippetSortOrder;
@@ -239,6 +240,83 @@ async fn test_fuzzy_over_sort_positions(cx: &mut TestAppContext) {
     assert_eq!(matches[2].string, "fetch_code_lens");
 }
 
+#[gpui::test]
+async fn test_sort_text_before_match_score(cx: &mut TestAppContext) {
+    let completions = vec![
+        CompletionBuilder::variable("code_lens", None, "2"),
+        CompletionBuilder::function("fetch_code_lens", None, "1"),
+    ];
+
+    let matches =
+        filter_and_sort_matches("lens", &completions, SnippetSortOrder::default(), cx).await;
+    assert_eq!(matches[0].string, "code_lens");
+
+    let ranking = CompletionRanking {
+        lsp_sort_text: LspSortTextMode::BeforeMatchScore,
+        ..CompletionRanking::default()
+    };
+    let matches = filter_and_sort_matches_with_ranking(
+        "lens",
+        &completions,
+        SnippetSortOrder::default(),
+        ranking,
+        cx,
+    )
+    .await;
+    assert_eq!(matches[0].string, "fetch_code_lens");
+    assert_eq!(matches[1].string, "code_lens");
+}
+
+#[gpui::test]
+async fn test_deprioritize_deprecated(cx: &mut TestAppContext) {
+    let completions = vec![
+        CompletionBuilder::deprecated(CompletionBuilder::function("foo_bar", None, "7fffffff")),
+        CompletionBuilder::function("foo_baz", None, "7fffffff"),
+    ];
+
+    let matches =
+        filter_and_sort_matches("foo", &completions, SnippetSortOrder::default(), cx).await;
+    assert_eq!(matches[0].string, "foo_bar");
+
+    let ranking = CompletionRanking {
+        deprioritize_deprecated: true,
+        ..CompletionRanking::default()
+    };
+    let matches = filter_and_sort_matches_with_ranking(
+        "foo",
+        &completions,
+        SnippetSortOrder::default(),
+        ranking,
+        cx,
+    )
+    .await;
+    assert_eq!(matches[0].string, "foo_baz");
+    assert_eq!(matches[1].string, "foo_bar");
+}
+
+#[gpui::test]
+async fn test_prefer_prefix_matches(cx: &mut TestAppContext) {
+    let completions = vec![
+        CompletionBuilder::variable("my_set", None, "0"),
+        CompletionBuilder::function("setter_for_everything", None, "1"),
+    ];
+
+    let ranking = CompletionRanking {
+        prefer_prefix_matches: true,
+        ..CompletionRanking::default()
+    };
+    let matches = filter_and_sort_matches_with_ranking(
+        "set",
+        &completions,
+        SnippetSortOrder::default(),
+        ranking,
+        cx,
+    )
+    .await;
+    assert_eq!(matches[0].string, "setter_for_everything");
+    assert_eq!(matches[1].string, "my_set");
+}
+
 async fn test_for_each_prefix<F>(
     target: &str,
     completions: &Vec<Completion>,
@@ -278,6 +356,13 @@ impl CompletionBuilder {
         Self::new(label, filter_text, sort_text, CompletionItemKind::SNIPPET)
     }
 
+    fn deprecated(mut completion: Completion) -> Completion {
+        if let CompletionSource::Lsp { lsp_completion, .. } = &mut completion.source {
+            lsp_completion.deprecated = Some(true);
+        }
+        completion
+    }
+
     fn new(
         label: &str,
         filter_text: Option<&str>,
@@ -314,6 +399,23 @@ async fn filter_and_sort_matches(
     completions: &Vec<Completion>,
     snippet_sort_order: SnippetSortOrder,
     cx: &mut TestAppContext,
+) -> Vec<StringMatch> {
+    filter_and_sort_matches_with_ranking(
+        query,
+        completions,
+        snippet_sort_order,
+        CompletionRanking::default(),
+        cx,
+    )
+    .await
+}
+
+async fn filter_and_sort_matches_with_ranking(
+    query: &str,
+    completions: &Vec<Completion>,
+    snippet_sort_order: SnippetSortOrder,
+    ranking: CompletionRanking,
+    cx: &mut TestAppContext,
 ) -> Vec<StringMatch> {
     let candidates: Arc<[StringMatchCandidate]> = completions
         .iter()
@@ -332,5 +434,11 @@ async fn filter_and_sort_matches(
         background_executor,
     )
     .await;
-    CompletionsMenu::sort_string_matches(matches, Some(query), snippet_sort_order, completions)
+    CompletionsMenu::sort_string_matches(
+        matches,
+        Some(query),
+        snippet_sort_order,
+        ranking,
+        completions,
+    )
 }
@@ -7,6 +7,7 @@ use gpui::{
 };
 use itertools::Itertools;
 use language::CodeLabel;
+use language::language_settings::{CompletionSettings, LspSortTextMode};
 use language::{Buffer, LanguageName, LanguageRegistry};
 use markdown::{Markdown, MarkdownElement};
 use multi_buffer::{Anchor, ExcerptId};
@@ -234,6 +235,25 @@ pub struct CompletionsMenu {
     language: Option<LanguageName>,
     display_options: CompletionDisplayOptions,
     snippet_sort_order: SnippetSortOrder,
+    ranking: CompletionRanking,
+}
+
+/// The `completions` language settings that control how completions are ranked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CompletionRanking {
+    pub lsp_sort_text: LspSortTextMode,
+    pub deprioritize_deprecated: bool,
+    pub prefer_prefix_matches: bool,
+}
+
+impl From<&CompletionSettings> for CompletionRanking {
+    fn from(settings: &CompletionSettings) -> Self {
+        Self {
+            lsp_sort_text: settings.lsp_sort_text,
+            deprioritize_deprecated: settings.deprioritize_deprecated,
+            prefer_prefix_matches: settings.prefer_prefix_matches,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -274,6 +294,7 @@ impl CompletionsMenu {
         completions: Box<[Completion]>,
         display_options: CompletionDisplayOptions,
         snippet_sort_order: SnippetSortOrder,
+        ranking: CompletionRanking,
         language_registry: Option<Arc<LanguageRegistry>>,
         language: Option<LanguageName>,
         cx: &mut Context<Editor>,
@@ -308,6 +329,7 @@ impl CompletionsMenu {
             language,
             display_options,
             snippet_sort_order,
+            ranking,
         };
 
         completions_menu.start_markdown_parse_for_nearby_entries(cx);
@@ -376,6 +398,7 @@ impl CompletionsMenu {
             language: None,
             display_options: CompletionDisplayOptions::default(),
             snippet_sort_order,
+            ranking: CompletionRanking::default(),
         }
     }
 
@@ -481,6 +504,13 @@ impl CompletionsMenu {
             };
             provider.selection_changed(entry, window, cx);
         }
+        if let Some(entry) = self.entries.borrow().get(self.selected_item) {
+            let label = self.completions.borrow()[entry.candidate_id]
+                .label
+                .text
+                .clone();
+            crate::screen_reader::announce(&label, window, cx);
+        }
         self.resolve_visible_completions(provider, cx);
         self.start_markdown_parse_for_nearby_entries(cx);
         cx.notify();
@@ -1046,6 +1076,7 @@ impl CompletionsMenu {
         let completions = self.completions.clone();
         let sort_completions = self.sort_completions;
         let snippet_sort_order = self.snippet_sort_order;
+        let ranking = self.ranking;
         cx.foreground_executor().spawn(async move {
             let mut matches = matches_task.await;
 
@@ -1054,6 +1085,7 @@ impl CompletionsMenu {
                     matches,
                     Some(&query),
                     snippet_sort_order,
+                    ranking,
                     completions.borrow().as_ref(),
                 );
             }
@@ -1081,6 +1113,7 @@ impl CompletionsMenu {
                 matches,
                 None,
                 self.snippet_sort_order,
+                self.ranking,
                 self.completions.borrow().as_ref(),
             );
         }
@@ -1104,6 +1137,7 @@ impl CompletionsMenu {
         matches: Vec<StringMatch>,
         query: Option<&str>,
         snippet_sort_order: SnippetSortOrder,
+        ranking: CompletionRanking,
         completions: &[Completion],
     ) -> Vec<StringMatch> {
         let mut matches = matches;
@@ -1112,7 +1146,10 @@ impl CompletionsMenu {
         enum MatchTier<'a> {
             WordStartMatch {
                 sort_exact: Reverse<i32>,
+                sort_prefix: Reverse<bool>,
                 sort_snippet: Reverse<i32>,
+                sort_deprecated: bool,
+                sort_text_before_score: Option<&'a str>,
                 sort_score: Reverse<OrderedFloat<f64>>,
                 sort_positions: Vec<usize>,
                 sort_text: Option<&'a str>,
@@ -1120,6 +1157,7 @@ impl CompletionsMenu {
                 sort_label: &'a str,
             },
             OtherMatch {
+                sort_deprecated: bool,
                 sort_score: Reverse<OrderedFloat<f64>>,
             },
         }
@@ -1128,6 +1166,7 @@ impl CompletionsMenu {
             .as_ref()
             .and_then(|q| q.chars().next())
             .and_then(|c| c.to_lowercase().next());
+        let query_lower = query.map(|query| query.to_lowercase());
 
         if snippet_sort_order == SnippetSortOrder::None {
             matches.retain(|string_match| {
@@ -1157,6 +1196,21 @@ impl CompletionsMenu {
                 CompletionSource::Dap { sort_text } => Some(sort_text.as_str()),
                 _ => None,
             };
+            let (sort_text_before_score, sort_text) = match ranking.lsp_sort_text {
+                LspSortTextMode::AfterMatchScore => (None, sort_text),
+                LspSortTextMode::BeforeMatchScore => (sort_text, None),
+                LspSortTextMode::Ignore => (None, None),
+            };
+            let sort_deprecated = ranking.deprioritize_deprecated
+                && completion
+                    .source
+                    .lsp_completion(false)
+                    .is_some_and(|lsp_completion| {
+                        lsp_completion.deprecated == Some(true)
+                            || lsp_completion.tags.as_ref().is_some_and(|tags| {
+                                tags.contains(&lsp::CompletionItemTag::DEPRECATED)
+                            })
+                    });
 
             let (sort_kind, sort_label) = completion.sort_key();
 
@@ -1173,7 +1227,10 @@ impl CompletionsMenu {
                 .unwrap_or(false);
 
             if query_start_doesnt_match_split_words {
-                MatchTier::OtherMatch { sort_score }
+                MatchTier::OtherMatch {
+                    sort_deprecated,
+                    sort_score,
+                }
             } else {
                 let sort_snippet = match snippet_sort_order {
                     SnippetSortOrder::Top => Reverse(if is_snippet { 1 } else { 0 }),
@@ -1187,10 +1244,23 @@ impl CompletionsMenu {
                 } else {
                     0
                 });
+                let sort_prefix = Reverse(
+                    ranking.prefer_prefix_matches
+                        && query_lower.as_ref().is_some_and(|query_lower| {
+                            completion
+                                .label
+                                .filter_text()
+                                .to_lowercase()
+                                .starts_with(query_lower.as_str())
+                        }),
+                );
 
                 MatchTier::WordStartMatch {
                     sort_exact,
+                    sort_prefix,
                     sort_snippet,
+                    sort_deprecated,
+                    sort_text_before_score,
                     sort_score,
                     sort_positions,
                     sort_text,
@@ -1293,7 +1363,7 @@ impl CodeActionContents {
         self.len() == 0
     }
 
-    fn iter(&self) -> impl Iterator<Item = CodeActionsItem> + '_ {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = CodeActionsItem> + '_ {
         self.tasks
             .iter()
             .flat_map(|tasks| {
@@ -0,0 +1,211 @@
+//! Renders the selected code as a PNG image for `editor::CopyAsImage`.
+use std::io::Cursor;
+
+use anyhow::Result;
+use gpui::{
+    ClipboardItem, Context, FontStyle, Image, ImageFormat, Pixels, Rgba, TextRun, Window, px,
+};
+use image::RgbaImage;
+use settings::Settings as _;
+use theme::{ActiveTheme as _, ThemeSettings};
+
+use crate::{
+    CopyAsImage, Editor, clipboard_history,
+    highlighted_export::{StyledRun, styled_runs},
+};
+
+const DEFAULT_PADDING: f32 = 32.;
+const WINDOW_CHROME_HEIGHT: f32 = 32.;
+const WINDOW_CONTROL_RADIUS: f32 = 6.;
+const WINDOW_CONTROL_SPACING: f32 = 8.;
+const WINDOW_CONTROL_COLORS: [u32; 3] = [0xff5f56ff, 0xffbd2eff, 0x27c93fff];
+
+/// Splits `runs` at newlines, expanding tabs to spaces so that glyphs line up as in the editor.
+fn runs_by_line(runs: Vec<StyledRun>, tab_size: usize) -> Vec<Vec<StyledRun>> {
+    let mut lines = vec![Vec::<StyledRun>::new()];
+    let mut column = 0;
+    for run in runs {
+        for (ix, text) in run.text.split('\n').enumerate() {
+            if ix > 0 {
+                lines.push(Vec::new());
+                column = 0;
+            }
+            if text.is_empty() {
+                continue;
+            }
+            let mut expanded = String::with_capacity(text.len());
+            for ch in text.chars() {
+                if ch == '\t' {
+                    let width = tab_size - column % tab_size;
+                    expanded.extend(std::iter::repeat_n(' ', width));
+                    column += width;
+                } else {
+                    expanded.push(ch);
+                    column += 1;
+                }
+            }
+            lines.last_mut().unwrap().push(StyledRun {
+                text: expanded,
+                ..run.clone()
+            });
+        }
+    }
+    if lines.len() > 1 && lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+fn to_image_pixel(color: impl Into<Rgba>) -> image::Rgba<u8> {
+    let color = color.into();
+    image::Rgba([color.r, color.g, color.b, color.a].map(|channel| (channel * 255.).round() as u8))
+}
+
+/// Fills an antialiased circle, assuming the pixels beneath it are opaque.
+fn fill_circle(image: &mut RgbaImage, center: (f32, f32), radius: f32, color: image::Rgba<u8>) {
+    let left = (center.0 - radius).floor().max(0.) as u32;
+    let top = (center.1 - radius).floor().max(0.) as u32;
+    let right = ((center.0 + radius).ceil() as u32).min(image.width());
+    let bottom = ((center.1 + radius).ceil() as u32).min(image.height());
+    for y in top..bottom {
+        for x in left..right {
+            let distance = (x as f32 + 0.5 - center.0).hypot(y as f32 + 0.5 - center.1);
+            let coverage = (radius - distance + 0.5).clamp(0., 1.);
+            if coverage == 0. {
+                continue;
+            }
+            let pixel = image.get_pixel_mut(x, y);
+            for channel in 0..3 {
+                pixel[channel] = (color[channel] as f32 * coverage
+                    + pixel[channel] as f32 * (1. - coverage))
+                    .round() as u8;
+            }
+        }
+    }
+}
+
+impl Editor {
+    pub fn copy_as_image(
+        &mut self,
+        action: &CopyAsImage,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match self.selection_as_image(action, window, cx) {
+            Ok(Some(image)) => {
+                clipboard_history::write_to_clipboard(ClipboardItem::new_image(&image), cx)
+            }
+            Ok(None) => {}
+            Err(error) => log::error!("failed to render selection as an image: {error:#}"),
+        }
+    }
+
+    /// Rasterizes the text of the non-empty selections with the editor's font and syntax theme.
+    pub(crate) fn selection_as_image(
+        &self,
+        action: &CopyAsImage,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Result<Option<Image>> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let syntax = cx.theme().syntax().clone();
+        let mut runs = Vec::new();
+        for selection in self.selections.all::<usize>(cx) {
+            if selection.is_empty() {
+                continue;
+            }
+            if !runs.is_empty() {
+                runs.push(StyledRun::plain("\n"));
+            }
+            runs.extend(styled_runs(&snapshot, selection.range(), &syntax));
+        }
+        if runs.is_empty() {
+            return Ok(None);
+        }
+
+        let settings = ThemeSettings::get_global(cx);
+        let font = settings.buffer_font.clone();
+        let font_size = self.buffer_font_size(cx);
+        let line_height = font_size * settings.line_height();
+        let foreground = cx.theme().colors().editor_foreground;
+        let background = cx.theme().colors().editor_background;
+        let tab_size = self.buffer.read(cx).language_settings(cx).tab_size.get() as usize;
+
+        let lines = runs_by_line(runs, tab_size)
+            .into_iter()
+            .map(|line| {
+                let text = line.iter().map(|run| run.text.as_str()).collect::<String>();
+                let text_runs = line
+                    .iter()
+                    .map(|run| {
+                        let mut font = font.clone();
+                        if run.bold {
+                            font.weight = gpui::FontWeight::BOLD;
+                        }
+                        if run.italic {
+                            font.style = FontStyle::Italic;
+                        }
+                        TextRun {
+                            len: run.text.len(),
+                            font,
+                            color: run.color.unwrap_or(foreground),
+                            background_color: None,
+                            underline: None,
+                            strikethrough: None,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                window
+                    .text_system()
+                    .shape_line(text.into(), font_size, &text_runs, None)
+            })
+            .collect::<Vec<_>>();
+
+        let padding = px(action.padding.unwrap_or(DEFAULT_PADDING).max(0.));
+        let chrome_height = if action.window_chrome {
+            px(WINDOW_CHROME_HEIGHT)
+        } else {
+            Pixels::ZERO
+        };
+        let content_width = lines
+            .iter()
+            .map(|line| line.width)
+            .max()
+            .unwrap_or_default();
+        let width = content_width + padding * 2.;
+        let height = chrome_height + line_height * lines.len() as f32 + padding * 2.;
+
+        let scale_factor = window.scale_factor();
+        let mut image = RgbaImage::from_pixel(
+            (f32::from(width) * scale_factor).ceil() as u32,
+            (f32::from(height) * scale_factor).ceil() as u32,
+            to_image_pixel(background),
+        );
+        if action.window_chrome {
+            let radius = WINDOW_CONTROL_RADIUS * scale_factor;
+            let center_y = f32::from(padding + chrome_height) / 2. * scale_factor;
+            for (ix, color) in WINDOW_CONTROL_COLORS.into_iter().enumerate() {
+                let center_x = (f32::from(padding)
+                    + WINDOW_CONTROL_RADIUS
+                    + ix as f32 * (WINDOW_CONTROL_RADIUS * 2. + WINDOW_CONTROL_SPACING))
+                    * scale_factor;
+                fill_circle(
+                    &mut image,
+                    (center_x, center_y),
+                    radius,
+                    to_image_pixel(gpui::rgba(color)),
+                );
+            }
+        }
+
+        let text_system = cx.text_system().clone();
+        for (row, line) in lines.iter().enumerate() {
+            let origin = gpui::point(padding, padding + chrome_height + line_height * row as f32);
+            line.rasterize(origin, line_height, scale_factor, &text_system, &mut image)?;
+        }
+
+        let mut bytes = Vec::new();
+        image.write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)?;
+        Ok(Some(Image::from_bytes(ImageFormat::Png, bytes)))
+    }
+}
@@ -0,0 +1,331 @@
+//! Rainbow columns for CSV and TSV files: every column is colored with its own accent color, and
+//! the columns can be aligned with padding inlays, without editing the file.
+use std::{any::TypeId, mem, ops::Range};
+
+use gpui::{
+    App, Context, Entity, HighlightStyle, IntoElement, ParentElement, Render, Subscription, Task,
+    WeakEntity, Window, div,
+};
+use language::Point;
+use multi_buffer::{Anchor, MultiBufferRow, MultiBufferSnapshot, ToPoint as _};
+use settings::Settings as _;
+use theme::ActiveTheme as _;
+use ui::{Button, ButtonCommon, Clickable, FluentBuilder, LabelSize, Tooltip};
+use util::post_inc;
+use workspace::{StatusItemView, item::ItemHandle};
+
+use crate::{Editor, EditorSettings, ToggleCsvAlignment, display_map::Inlay};
+
+/// Files with more rows than this are colored but not aligned, as every cell needs an inlay.
+const MAX_ALIGNED_ROWS: usize = 10_000;
+
+struct CsvColumnHighlight;
+
+/// Returns the byte ranges of the fields of a CSV `line`, keeping delimiters inside of quoted
+/// fields in the field.
+pub fn csv_fields(line: &str, delimiter: char) -> Vec<Range<usize>> {
+    if line.is_empty() {
+        return Vec::new();
+    }
+    let mut fields = Vec::new();
+    let mut field_start = 0;
+    let mut in_quotes = false;
+    for (ix, ch) in line.char_indices() {
+        if ch == '"' {
+            in_quotes = !in_quotes;
+        } else if ch == delimiter && !in_quotes {
+            fields.push(field_start..ix);
+            field_start = ix + ch.len_utf8();
+        }
+    }
+    fields.push(field_start..line.len());
+    fields
+}
+
+fn csv_delimiter(file_name: Option<&str>, language_name: Option<&str>) -> Option<char> {
+    let extension = file_name
+        .and_then(|file_name| file_name.rsplit_once('.'))
+        .map(|(_, extension)| extension.to_ascii_lowercase());
+    match (extension.as_deref(), language_name) {
+        (Some("csv"), _) | (_, Some("CSV")) => Some(','),
+        (Some("tsv"), _) | (_, Some("TSV")) => Some('\t'),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct CsvColumns {
+    column_ranges: Vec<Vec<Range<Anchor>>>,
+    paddings: Vec<(Anchor, usize)>,
+}
+
+fn csv_columns(buffer: &MultiBufferSnapshot, delimiter: char, align: bool) -> CsvColumns {
+    let text = buffer.text();
+    let lines = text.split('\n').collect::<Vec<_>>();
+    let mut line_start = 0;
+    let mut rows = Vec::with_capacity(lines.len());
+    for line in &lines {
+        rows.push((line_start, csv_fields(line, delimiter)));
+        line_start += line.len() + 1;
+    }
+
+    let mut columns = CsvColumns::default();
+    for (line_start, fields) in &rows {
+        for (column, field) in fields.iter().enumerate() {
+            if field.is_empty() {
+                continue;
+            }
+            if columns.column_ranges.len() <= column {
+                columns.column_ranges.resize_with(column + 1, Vec::new);
+            }
+            columns.column_ranges[column].push(
+                buffer.anchor_after(line_start + field.start)
+                    ..buffer.anchor_before(line_start + field.end),
+            );
+        }
+    }
+
+    if align && rows.len() <= MAX_ALIGNED_ROWS {
+        let field_width = |line: &str, field: &Range<usize>| line[field.clone()].chars().count();
+        let mut widths = Vec::<usize>::new();
+        for (line, (_, fields)) in lines.iter().zip(&rows) {
+            for (column, field) in fields.iter().enumerate() {
+                if widths.len() <= column {
+                    widths.push(0);
+                }
+                widths[column] = widths[column].max(field_width(line, field));
+            }
+        }
+        for (line, (line_start, fields)) in lines.iter().zip(&rows) {
+            // The last field of a line is not followed by anything that needs aligning.
+            for (column, field) in fields
+                .iter()
+                .enumerate()
+                .take(fields.len().saturating_sub(1))
+            {
+                let padding = widths[column] - field_width(line, field);
+                if padding > 0 {
+                    columns
+                        .paddings
+                        .push((buffer.anchor_after(line_start + field.end), padding));
+                }
+            }
+        }
+    }
+    columns
+}
+
+impl Editor {
+    /// The delimiter of the CSV or TSV file shown in the editor, if any.
+    pub fn csv_delimiter(&self) -> Option<char> {
+        self.csv_delimiter
+    }
+
+    pub fn csv_alignment_enabled(&self, cx: &App) -> bool {
+        self.csv_delimiter.is_some()
+            && self
+                .csv_alignment
+                .unwrap_or(EditorSettings::get_global(cx).rainbow_csv.align_columns)
+    }
+
+    pub fn toggle_csv_alignment(
+        &mut self,
+        _: &ToggleCsvAlignment,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.csv_delimiter.is_none() {
+            cx.propagate();
+            return;
+        }
+        self.csv_alignment = Some(!self.csv_alignment_enabled(cx));
+        self.refresh_csv_columns(window, cx);
+    }
+
+    /// Returns the zero-based column of the newest cursor in a CSV file, along with the header
+    /// of that column on the first line.
+    pub fn csv_column_at_cursor(&self, cx: &App) -> Option<(usize, String)> {
+        let delimiter = self.csv_delimiter?;
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let line = |row: u32| {
+            buffer
+                .text_for_range(
+                    Point::new(row, 0)..Point::new(row, buffer.line_len(MultiBufferRow(row))),
+                )
+                .collect::<String>()
+        };
+        let cursor = self.selections.newest_anchor().head().to_point(&buffer);
+        let column = csv_fields(&line(cursor.row), delimiter)
+            .iter()
+            .position(|field| cursor.column as usize <= field.end)?;
+        let header_line = line(0);
+        let header = csv_fields(&header_line, delimiter)
+            .get(column)
+            .map(|field| {
+                header_line[field.clone()]
+                    .trim()
+                    .trim_matches('"')
+                    .to_string()
+            })
+            .unwrap_or_default();
+        Some((column, header))
+    }
+
+    pub(crate) fn refresh_csv_columns(&mut self, _: &mut Window, cx: &mut Context<Self>) {
+        let settings = EditorSettings::get_global(cx).rainbow_csv;
+        self.csv_delimiter = self
+            .buffer
+            .read(cx)
+            .as_singleton()
+            .filter(|_| self.mode.is_full() && settings.enabled)
+            .and_then(|buffer| {
+                let buffer = buffer.read(cx);
+                let language_name = buffer.language().map(|language| language.name());
+                csv_delimiter(
+                    buffer.file().map(|file| file.file_name(cx)),
+                    language_name.as_ref().map(|name| name.as_ref()),
+                )
+            });
+        let Some(delimiter) = self.csv_delimiter else {
+            if !self.csv_columns_applied {
+                return;
+            }
+            self.csv_columns_applied = false;
+            self.csv_columns_task = Task::ready(());
+            let padding_inlays = mem::take(&mut self.csv_padding_inlays);
+            self.splice_inlays(&padding_inlays, Vec::new(), cx);
+            self.display_map.update(cx, |display_map, _| {
+                display_map.clear_keyed_highlights(TypeId::of::<CsvColumnHighlight>());
+            });
+            cx.notify();
+            return;
+        };
+
+        self.csv_columns_applied = true;
+        let align = self.csv_alignment_enabled(cx);
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let task = cx.background_spawn(async move { csv_columns(&buffer, delimiter, align) });
+        self.csv_columns_task = cx.spawn(async move |this, cx| {
+            let columns = task.await;
+            this.update(cx, |this, cx| {
+                this.display_map.update(cx, |display_map, _| {
+                    display_map.clear_keyed_highlights(TypeId::of::<CsvColumnHighlight>());
+                });
+                let accents = cx.theme().accents().clone();
+                for (column, ranges) in columns.column_ranges.into_iter().enumerate() {
+                    this.highlight_text_key::<CsvColumnHighlight>(
+                        column,
+                        ranges,
+                        HighlightStyle::color(accents.color_for_index(column as u32)),
+                        cx,
+                    );
+                }
+
+                let new_inlays = columns
+                    .paddings
+                    .into_iter()
+                    .map(|(position, width)| {
+                        Inlay::padding(post_inc(&mut this.next_inlay_id), position, width)
+                    })
+                    .collect::<Vec<_>>();
+                let mut padding_inlays = new_inlays.iter().map(|inlay| inlay.id).collect();
+                mem::swap(&mut this.csv_padding_inlays, &mut padding_inlays);
+                this.splice_inlays(&padding_inlays, new_inlays, cx);
+            })
+            .ok();
+        });
+    }
+}
+
+/// Shows the header of the CSV column that the cursor is in.
+#[derive(Default)]
+pub struct CsvColumnIndicator {
+    column: Option<(usize, String)>,
+    editor: Option<WeakEntity<Editor>>,
+    _observe_active_editor: Option<Subscription>,
+}
+
+impl CsvColumnIndicator {
+    fn update_column(&mut self, editor: Entity<Editor>, _: &mut Window, cx: &mut Context<Self>) {
+        let column = editor.read(cx).csv_column_at_cursor(cx);
+        if column != self.column {
+            self.column = column;
+            cx.notify();
+        }
+    }
+}
+
+impl Render for CsvColumnIndicator {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div().when_some(self.column.clone(), |el, (column, header)| {
+            let label = if header.is_empty() {
+                format!("Column {}", column + 1)
+            } else {
+                format!("{header} (Column {})", column + 1)
+            };
+            el.child(
+                Button::new("csv-column", label)
+                    .label_size(LabelSize::Small)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        if let Some(editor) = this.editor.as_ref().and_then(|e| e.upgrade()) {
+                            editor.update(cx, |editor, cx| {
+                                editor.toggle_csv_alignment(&ToggleCsvAlignment, window, cx)
+                            });
+                        }
+                    }))
+                    .tooltip(|window, cx| {
+                        Tooltip::for_action(
+                            "Toggle Column Alignment",
+                            &ToggleCsvAlignment,
+                            window,
+                            cx,
+                        )
+                    }),
+            )
+        })
+    }
+}
+
+impl StatusItemView for CsvColumnIndicator {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(editor) = active_pane_item.and_then(|item| item.downcast::<Editor>()) {
+            self._observe_active_editor = Some(cx.observe_in(&editor, window, Self::update_column));
+            self.editor = Some(editor.downgrade());
+            self.update_column(editor, window, cx);
+        } else {
+            self.column = None;
+            self.editor = None;
+            self._observe_active_editor = None;
+        }
+        cx.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_fields() {
+        let line = r#"id,"name, with comma",,"quoted ""word""""#;
+        assert_eq!(
+            csv_fields(line, ',')
+                .into_iter()
+                .map(|field| &line[field])
+                .collect::<Vec<_>>(),
+            ["id", "\"name, with comma\"", "", "\"quoted \"\"word\"\"\""]
+        );
+        assert_eq!(csv_fields("a\tb,c", '\t'), [0..1, 2..5]);
+        assert!(csv_fields("", ',').is_empty());
+
+        assert_eq!(csv_delimiter(Some("data.CSV"), None), Some(','));
+        assert_eq!(csv_delimiter(Some("data.tsv"), None), Some('\t'));
+        assert_eq!(csv_delimiter(None, Some("CSV")), Some(','));
+        assert_eq!(csv_delimiter(Some("data.json"), Some("JSON")), None);
+    }
+}
@@ -0,0 +1,102 @@
+//! Highlighting the text that matches the `highlight_rules` of a language with a syntax theme
+//! style, such as domain keywords, deprecated APIs, or internal markers. Only the visible lines
+//! are searched, so the highlights are refreshed when the editor is scrolled or edited.
+use std::{any::TypeId, ops::Range};
+
+use collections::HashMap;
+use gpui::{App, Context, SharedString};
+use language::{Bias, Point, language_settings::language_settings};
+use multi_buffer::{Anchor, MultiBufferRow, ToPoint as _};
+use theme::ActiveTheme as _;
+
+use crate::Editor;
+
+struct CustomHighlight;
+
+impl Editor {
+    pub(crate) fn refresh_custom_highlights(&mut self, cx: &mut Context<Self>) {
+        if !self.mode.is_full() {
+            return;
+        }
+        let highlights = self.custom_highlight_ranges(cx);
+        if highlights.is_empty() && !self.custom_highlights_applied {
+            return;
+        }
+        self.custom_highlights_applied = !highlights.is_empty();
+
+        self.display_map.update(cx, |display_map, _| {
+            display_map.clear_keyed_highlights(TypeId::of::<CustomHighlight>());
+        });
+        let syntax = cx.theme().syntax().clone();
+        for (key, (style, ranges)) in highlights.into_iter().enumerate() {
+            self.highlight_text_key::<CustomHighlight>(key, ranges, syntax.get(&style), cx);
+        }
+        cx.notify();
+    }
+
+    /// Returns the ranges of the visible lines that match each language's highlight rules,
+    /// grouped by the style to highlight them with.
+    fn custom_highlight_ranges(&self, cx: &App) -> Vec<(SharedString, Vec<Range<Anchor>>)> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let visible_start = self.scroll_manager.anchor().anchor.to_point(&snapshot);
+        let visible_end = snapshot.clip_point(
+            visible_start + Point::new(self.visible_line_count().unwrap_or(0.).ceil() as u32, 0),
+            Bias::Left,
+        );
+        let visible_end = Point::new(
+            visible_end.row,
+            snapshot.line_len(MultiBufferRow(visible_end.row)),
+        );
+
+        let mut highlights = HashMap::<SharedString, Vec<Range<Anchor>>>::default();
+        for (buffer, range, excerpt_id) in
+            snapshot.range_to_buffer_ranges(Point::new(visible_start.row, 0)..visible_end)
+        {
+            let settings = language_settings(
+                buffer.language().map(|language| language.name()),
+                buffer.file(),
+                cx,
+            );
+            let rules = settings
+                .highlight_rules
+                .iter()
+                .filter(|rule| {
+                    buffer
+                        .file()
+                        .is_none_or(|file| rule.applies_to(file.path().as_std_path()))
+                })
+                .collect::<Vec<_>>();
+            if rules.is_empty() {
+                continue;
+            }
+
+            let text = buffer.text_for_range(range.clone()).collect::<String>();
+            for rule in rules {
+                let ranges = highlights.entry(rule.style.clone()).or_default();
+                for found in rule
+                    .regex
+                    .find_iter(&text)
+                    .filter(|found| !found.is_empty())
+                {
+                    let start = buffer.anchor_after(range.start + found.start());
+                    let end = buffer.anchor_before(range.start + found.end());
+                    if let Some(start) = snapshot.anchor_in_excerpt(excerpt_id, start)
+                        && let Some(end) = snapshot.anchor_in_excerpt(excerpt_id, end)
+                    {
+                        ranges.push(start..end);
+                    }
+                }
+            }
+        }
+
+        let mut highlights = highlights
+            .into_iter()
+            .filter(|(_, ranges)| !ranges.is_empty())
+            .collect::<Vec<_>>();
+        for (_, ranges) in &mut highlights {
+            ranges.sort_by(|a, b| a.start.cmp(&b.start, &snapshot));
+        }
+        highlights.sort_by(|(a, _), (b, _)| a.cmp(b));
+        highlights
+    }
+}
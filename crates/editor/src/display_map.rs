@@ -45,14 +45,16 @@ use gpui::{App, Context, Entity, Font, HighlightStyle, LineLayout, Pixels, Under
 pub use inlay_map::Inlay;
 use inlay_map::InlaySnapshot;
 pub use inlay_map::{InlayOffset, InlayPoint};
-pub use invisibles::{is_invisible, replacement};
+pub use invisibles::{is_invisible, placeholder, replacement};
 use language::{
-    OffsetUtf16, Point, Subscription as BufferSubscription, language_settings::language_settings,
+    FoldRegionMarkers, OffsetUtf16, Point, Subscription as BufferSubscription,
+    language_settings::language_settings,
 };
 use multi_buffer::{
     Anchor, AnchorRangeExt, ExcerptId, MultiBuffer, MultiBufferPoint, MultiBufferRow,
     MultiBufferSnapshot, RowInfo, ToOffset, ToPoint,
 };
+use parking_lot::Mutex;
 use project::project_settings::DiagnosticSeverity;
 use serde::Deserialize;
 
@@ -66,10 +68,11 @@ use std::{
     sync::Arc,
 };
 use sum_tree::{Bias, TreeMap};
-use tab_map::TabSnapshot;
+use tab_map::{CharWidths, TabSnapshot};
 use text::{BufferId, LineIndent};
 use ui::{SharedString, px};
 use unicode_segmentation::UnicodeSegmentation;
+pub use wrap_map::WrapStyle;
 use wrap_map::{WrapMap, WrapSnapshot};
 
 pub use crate::display_map::{fold_map::FoldMap, inlay_map::InlayMap, tab_map::TabMap};
@@ -86,6 +89,9 @@ pub enum HighlightKey {
     TypePlus(TypeId, usize),
 }
 
+/// Tags folds created from region markers, so that they can be unfolded on their own.
+pub(crate) enum RegionFold {}
+
 pub trait ToDisplayPoint {
     fn to_display_point(&self, map: &DisplaySnapshot) -> DisplayPoint;
 }
@@ -93,6 +99,15 @@ pub trait ToDisplayPoint {
 type TextHighlights = TreeMap<HighlightKey, Arc<(HighlightStyle, Vec<Range<Anchor>>)>>;
 type InlayHighlights = TreeMap<TypeId, TreeMap<InlayId, (HighlightStyle, InlayHighlight)>>;
 
+/// The fold ranges found in the buffer, by the row they start on.
+#[derive(Default)]
+struct FoldRanges {
+    /// Regions delimited by region markers.
+    regions: HashMap<MultiBufferRow, Option<Range<Point>>>,
+    /// Ranges of the language's `folds.scm` captures.
+    syntax_folds: HashMap<MultiBufferRow, Option<Range<Point>>>,
+}
+
 /// Decides how text in a [`MultiBuffer`] should be displayed in a buffer, handling inlay hints,
 /// folding, hard tabs, soft wrapping, custom blocks (like diagnostics), and highlighting.
 ///
@@ -107,6 +122,8 @@ pub struct DisplayMap {
     fold_map: FoldMap,
     /// Keeps track of hard tabs in a buffer.
     tab_map: TabMap,
+    /// The font that elastic tabstops measure cells in, when they're enabled.
+    elastic_tabstops_font: Option<(Font, Pixels)>,
     /// Handles soft wrapping.
     wrap_map: Entity<WrapMap>,
     /// Tracks custom blocks such as diagnostics that should be displayed within buffer.
@@ -117,6 +134,9 @@ pub struct DisplayMap {
     inlay_highlights: InlayHighlights,
     /// A container for explicitly foldable ranges, which supersede indentation based fold range suggestions.
     crease_map: CreaseMap,
+    /// The fold ranges found in snapshots of the buffer, and the buffer's edit and non-text
+    /// state update counts they were found at.
+    fold_ranges: ((usize, usize), Arc<Mutex<FoldRanges>>),
     pub(crate) fold_placeholder: FoldPlaceholder,
     pub clip_at_line_ends: bool,
     pub(crate) masked: bool,
@@ -154,9 +174,11 @@ impl DisplayMap {
             fold_map,
             inlay_map,
             tab_map,
+            elastic_tabstops_font: None,
             wrap_map,
             block_map,
             crease_map,
+            fold_ranges: Default::default(),
             fold_placeholder,
             diagnostics_max_severity,
             text_highlights: Default::default(),
@@ -168,10 +190,26 @@ impl DisplayMap {
 
     pub fn snapshot(&mut self, cx: &mut Context<Self>) -> DisplaySnapshot {
         let buffer_snapshot = self.buffer.read(cx).snapshot(cx);
+        let buffer_version = (
+            buffer_snapshot.edit_count(),
+            buffer_snapshot.non_text_state_update_count(),
+        );
+        if self.fold_ranges.0 != buffer_version {
+            self.fold_ranges = (buffer_version, Default::default());
+        }
         let edits = self.buffer_subscription.consume().into_inner();
         let (inlay_snapshot, edits) = self.inlay_map.sync(buffer_snapshot, edits);
         let (fold_snapshot, edits) = self.fold_map.read(inlay_snapshot, edits);
         let tab_size = Self::tab_size(&self.buffer, cx);
+        let elastic_tabstops_font = Self::elastic_tabstops(&self.buffer, cx)
+            .then(|| self.wrap_map.read(cx).font_with_size().clone());
+        if elastic_tabstops_font != self.elastic_tabstops_font {
+            self.tab_map
+                .set_elastic_tabstops(elastic_tabstops_font.as_ref().map(|(font, font_size)| {
+                    CharWidths::new(cx.text_system().clone(), font, *font_size)
+                }));
+            self.elastic_tabstops_font = elastic_tabstops_font;
+        }
         let (tab_snapshot, edits) = self.tab_map.sync(fold_snapshot, edits, tab_size);
         let (wrap_snapshot, edits) = self
             .wrap_map
@@ -182,6 +220,7 @@ impl DisplayMap {
             block_snapshot,
             diagnostics_max_severity: self.diagnostics_max_severity,
             crease_snapshot: self.crease_map.snapshot(),
+            fold_ranges: self.fold_ranges.1.clone(),
             text_highlights: self.text_highlights.clone(),
             inlay_highlights: self.inlay_highlights.clone(),
             clip_at_line_ends: self.clip_at_line_ends,
@@ -475,13 +514,23 @@ impl DisplayMap {
         Some(DisplayRow(block_row.0))
     }
 
+    /// Highlights `ranges` with `style`, returning whether the highlights changed. Highlights that
+    /// are refreshed without changing, like those of the matching brackets when the cursor moves
+    /// within a block, are left in place.
     pub fn highlight_text(
         &mut self,
         key: HighlightKey,
         ranges: Vec<Range<Anchor>>,
         style: HighlightStyle,
-    ) {
+    ) -> bool {
+        if let Some(existing) = self.text_highlights.get(&key)
+            && existing.0 == style
+            && existing.1 == ranges
+        {
+            return false;
+        }
         self.text_highlights.insert(key, Arc::new((style, ranges)));
+        true
     }
 
     pub(crate) fn highlight_inlays(
@@ -524,11 +573,23 @@ impl DisplayMap {
         cleared
     }
 
+    /// Removes the text highlights that were added with a key for `type_id`.
+    pub fn clear_keyed_highlights(&mut self, type_id: TypeId) {
+        self.text_highlights.retain(
+            |key, _| !matches!(key, HighlightKey::TypePlus(key_type_id, _) if *key_type_id == type_id),
+        );
+    }
+
     pub fn set_font(&self, font: Font, font_size: Pixels, cx: &mut Context<Self>) -> bool {
         self.wrap_map
             .update(cx, |map, cx| map.set_font_with_size(font, font_size, cx))
     }
 
+    pub fn set_wrap_style(&self, wrap_style: WrapStyle, cx: &mut Context<Self>) -> bool {
+        self.wrap_map
+            .update(cx, |map, cx| map.set_wrap_style(wrap_style, cx))
+    }
+
     pub fn set_wrap_width(&self, width: Option<Pixels>, cx: &mut Context<Self>) -> bool {
         self.wrap_map
             .update(cx, |map, cx| map.set_wrap_width(width, cx))
@@ -615,7 +676,20 @@ impl DisplayMap {
             .and_then(|buffer| buffer.language())
             .map(|l| l.name());
         let file = buffer.and_then(|buffer| buffer.file());
-        language_settings(language, file, cx).tab_size
+        let settings = language_settings(language, file, cx);
+        match buffer {
+            Some(buffer) => buffer.indent_override().apply(settings).tab_size,
+            None => settings.tab_size,
+        }
+    }
+
+    fn elastic_tabstops(buffer: &Entity<MultiBuffer>, cx: &App) -> bool {
+        let buffer = buffer.read(cx).as_singleton().map(|buffer| buffer.read(cx));
+        let language = buffer
+            .and_then(|buffer| buffer.language())
+            .map(|l| l.name());
+        let file = buffer.and_then(|buffer| buffer.file());
+        language_settings(language, file, cx).elastic_tabstops
     }
 
     #[cfg(test)]
@@ -690,7 +764,12 @@ impl<'a> HighlightedChunk<'a> {
                 chars.next();
                 let (prefix, suffix) = text.split_at(ch.len_utf8());
                 text = suffix;
-                if let Some(replacement) = replacement(ch) {
+                let glyph = if editor_style.show_invisible_placeholders {
+                    placeholder(ch).or_else(|| replacement(ch))
+                } else {
+                    replacement(ch)
+                };
+                if let Some(replacement) = glyph {
                     let invisible_highlight = HighlightStyle {
                         background_color: Some(editor_style.status.hint_background),
                         underline: Some(UnderlineStyle {
@@ -758,6 +837,9 @@ impl<'a> HighlightedChunk<'a> {
 #[derive(Clone)]
 pub struct DisplaySnapshot {
     pub crease_snapshot: CreaseSnapshot,
+    /// Shared by the snapshots of the same buffer contents and syntax tree, so that each fold is
+    /// only scanned or queried for once after an edit, rather than on every frame showing it.
+    fold_ranges: Arc<Mutex<FoldRanges>>,
     block_snapshot: BlockSnapshot,
     text_highlights: TextHighlights,
     inlay_highlights: InlayHighlights,
@@ -1290,6 +1372,113 @@ impl DisplaySnapshot {
             .unwrap_or(false)
     }
 
+    /// Returns the range of the region delimited by region markers (e.g. `// #region` and
+    /// `// #endregion`) that starts on the given row, including the row that ends it.
+    pub fn fold_region_for_buffer_row(&self, buffer_row: MultiBufferRow) -> Option<Range<Point>> {
+        if let Some(range) = self.fold_ranges.lock().regions.get(&buffer_row) {
+            return range.clone();
+        }
+
+        let buffer = self.buffer_snapshot();
+        let range = self.fold_region_markers(buffer_row).and_then(|markers| {
+            let mut depth = 0;
+            for row in buffer_row.0 + 1..=buffer.max_row().0 {
+                if self.line_starts_with(MultiBufferRow(row), &markers.end) {
+                    if depth == 0 {
+                        return Some(
+                            Point::new(buffer_row.0, buffer.line_len(buffer_row))
+                                ..Point::new(row, buffer.line_len(MultiBufferRow(row))),
+                        );
+                    }
+                    depth -= 1;
+                } else if self.line_starts_with(MultiBufferRow(row), &markers.start) {
+                    depth += 1;
+                }
+            }
+            None
+        });
+        self.fold_ranges
+            .lock()
+            .regions
+            .insert(buffer_row, range.clone());
+        range
+    }
+
+    /// Returns the range to fold for the outermost `@fold` capture of the language's `folds.scm`
+    /// query that starts on the given row.
+    fn syntax_fold_range(&self, buffer_row: MultiBufferRow) -> Option<Range<Point>> {
+        if let Some(range) = self.fold_ranges.lock().syntax_folds.get(&buffer_row) {
+            return range.clone();
+        }
+        let range = self.buffer_snapshot().syntax_fold_range(buffer_row);
+        self.fold_ranges
+            .lock()
+            .syntax_folds
+            .insert(buffer_row, range.clone());
+        range
+    }
+
+    /// Returns the ranges of all the regions delimited by region markers, found in a single pass
+    /// over the buffer, as when folding all of them.
+    pub fn fold_regions(&self) -> Vec<Range<Point>> {
+        let buffer = self.buffer_snapshot();
+        let mut regions = Vec::new();
+        let mut open_regions = Vec::<(MultiBufferRow, FoldRegionMarkers)>::new();
+        for row in 0..=buffer.max_row().0 {
+            let row = MultiBufferRow(row);
+            if let Some(position) = open_regions
+                .iter()
+                .rposition(|(_, markers)| self.line_starts_with(row, &markers.end))
+            {
+                // The innermost open region with these markers ends here.
+                let (start_row, _) = open_regions.remove(position);
+                regions.push(
+                    Point::new(start_row.0, buffer.line_len(start_row))
+                        ..Point::new(row.0, buffer.line_len(row)),
+                );
+            } else if let Some(markers) = self.fold_region_markers(row) {
+                open_regions.push((row, markers));
+            }
+        }
+        regions.sort_by_key(|range| range.start);
+        regions
+    }
+
+    /// Returns the markers of the region that starts on the given row, if any.
+    fn fold_region_markers(&self, buffer_row: MultiBufferRow) -> Option<FoldRegionMarkers> {
+        let buffer = self.buffer_snapshot();
+        let indent = buffer.indent_size_for_line(buffer_row).len;
+        if indent == buffer.line_len(buffer_row) {
+            return None;
+        }
+        buffer
+            .language_scope_at(Point::new(buffer_row.0, indent))?
+            .fold_region_markers()
+            .iter()
+            .find(|markers| self.line_starts_with(buffer_row, &markers.start))
+            .cloned()
+    }
+
+    /// Whether the text of the row after its indentation starts with `prefix`.
+    fn line_starts_with(&self, buffer_row: MultiBufferRow, prefix: &str) -> bool {
+        let buffer = self.buffer_snapshot();
+        let indent = buffer.indent_size_for_line(buffer_row).len;
+        let mut chars = buffer.chars_at(Point::new(buffer_row.0, indent));
+        prefix.chars().all(|c| chars.next() == Some(c))
+    }
+
+    /// Returns whether a fold can be created on the given row, either from region markers, the
+    /// language's `folds.scm` query or, when the language doesn't have one, from indentation.
+    pub fn starts_fold(&self, buffer_row: MultiBufferRow) -> bool {
+        if self.fold_region_for_buffer_row(buffer_row).is_some() {
+            true
+        } else if self.buffer_snapshot().uses_syntax_folds(buffer_row) {
+            self.syntax_fold_range(buffer_row).is_some()
+        } else {
+            self.starts_indent(buffer_row)
+        }
+    }
+
     pub fn crease_for_buffer_row(&self, buffer_row: MultiBufferRow) -> Option<Crease<Point>> {
         let start =
             MultiBufferPoint::new(buffer_row.0, self.buffer_snapshot().line_len(buffer_row));
@@ -1327,6 +1516,32 @@ impl DisplaySnapshot {
                     render_toggle: render_toggle.clone(),
                 }),
             }
+        } else if let Some(range) = self.fold_region_for_buffer_row(buffer_row) {
+            if self.is_line_folded(buffer_row) {
+                return None;
+            }
+            Some(Crease::Inline {
+                range,
+                placeholder: FoldPlaceholder {
+                    type_tag: Some(TypeId::of::<RegionFold>()),
+                    ..self.fold_placeholder.clone()
+                },
+                render_toggle: None,
+                render_trailer: None,
+                metadata: None,
+            })
+        } else if self.buffer_snapshot().uses_syntax_folds(buffer_row) {
+            if self.is_line_folded(buffer_row) {
+                return None;
+            }
+            let range = self.syntax_fold_range(buffer_row)?;
+            Some(Crease::Inline {
+                range,
+                placeholder: self.fold_placeholder.clone(),
+                render_toggle: None,
+                render_trailer: None,
+                metadata: None,
+            })
         } else if self.starts_indent(MultiBufferRow(start.row))
             && !self.is_line_folded(MultiBufferRow(start.row))
         {
@@ -1554,7 +1769,7 @@ pub mod tests {
     use lsp::LanguageServerId;
     use project::Project;
     use rand::{Rng, prelude::*};
-    use settings::{SettingsContent, SettingsStore};
+    use settings::{SettingsContent, SettingsStore, WrapIndent};
     use smol::stream::StreamExt;
     use std::{env, sync::Arc};
     use text::PointUtf16;
@@ -1953,6 +2168,53 @@ pub mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_soft_wrap_indicator(cx: &mut gpui::TestAppContext) {
+        cx.background_executor
+            .set_block_on_ticks(usize::MAX..=usize::MAX);
+        cx.update(|cx| init_test(cx, |_| {}));
+
+        let text = "one two three four five six seven eight\nnine";
+        let buffer = cx.update(|cx| MultiBuffer::build_simple(text, cx));
+        let map = cx.new(|cx| {
+            DisplayMap::new(
+                buffer,
+                font("Helvetica"),
+                px(12.0),
+                Some(px(96.)),
+                1,
+                1,
+                FoldPlaceholder::test(),
+                DiagnosticSeverity::Warning,
+                cx,
+            )
+        });
+        map.update(cx, |map, cx| {
+            map.set_wrap_style(
+                WrapStyle {
+                    indent: WrapIndent::None,
+                    indicator: Some('↪'),
+                },
+                cx,
+            )
+        });
+
+        // Continuation lines start with the indicator, followed by a space.
+        let snapshot = map.update(cx, |map, cx| map.snapshot(cx));
+        let display_text = snapshot.text();
+        let lines = display_text.lines().collect::<Vec<_>>();
+        assert!(lines.len() > 3, "{display_text:?} should be wrapped");
+        assert!(!lines[0].starts_with('↪'));
+        assert!(
+            lines[1..lines.len() - 1]
+                .iter()
+                .all(|line| line.starts_with("↪ ")),
+            "{display_text:?}"
+        );
+        assert_eq!(lines.last(), Some(&"nine"));
+        assert_eq!(display_text.replace("\n↪ ", ""), text);
+    }
+
     #[gpui::test]
     fn test_text_chunks(cx: &mut gpui::App) {
         init_test(cx, |_| {});
@@ -1,4 +1,4 @@
-use crate::{ChunkRenderer, HighlightStyles, InlayId};
+use crate::{ChunkRenderer, HighlightStyles, InlayId, virtual_text::VirtualTextInlay};
 use collections::BTreeSet;
 use gpui::{Hsla, Rgba};
 use language::{Chunk, Edit, Point, TextSummary};
@@ -48,6 +48,7 @@ pub struct Inlay {
 pub enum InlayContent {
     Text(text::Rope),
     Color(Hsla),
+    VirtualText(VirtualTextInlay),
 }
 
 impl Inlay {
@@ -99,11 +100,29 @@ impl Inlay {
         }
     }
 
+    /// Whitespace that aligns the text after it, such as the columns of a CSV file.
+    pub fn padding(id: u32, position: Anchor, width: usize) -> Self {
+        Self {
+            id: InlayId::Padding(id),
+            position,
+            content: InlayContent::Text(" ".repeat(width).into()),
+        }
+    }
+
+    pub fn virtual_text(id: u32, position: Anchor, content: VirtualTextInlay) -> Self {
+        Self {
+            id: InlayId::VirtualText(id),
+            position,
+            content: InlayContent::VirtualText(content),
+        }
+    }
+
     pub fn text(&self) -> &Rope {
         static COLOR_TEXT: OnceLock<Rope> = OnceLock::new();
         match &self.content {
             InlayContent::Text(text) => text,
             InlayContent::Color(_) => COLOR_TEXT.get_or_init(|| Rope::from("◼")),
+            InlayContent::VirtualText(content) => &content.text,
         }
     }
 
@@ -373,6 +392,7 @@ impl<'a> Iterator for InlayChunks<'a> {
                     }),
                     InlayId::Hint(_) => self.highlight_styles.inlay_hint,
                     InlayId::DebuggerValue(_) => self.highlight_styles.inlay_hint,
+                    InlayId::Padding(_) => None,
                     InlayId::Color(_) => {
                         if let InlayContent::Color(color) = inlay.content {
                             renderer = Some(ChunkRenderer {
@@ -404,6 +424,21 @@ impl<'a> Iterator for InlayChunks<'a> {
                         }
                         self.highlight_styles.inlay_hint
                     }
+                    InlayId::VirtualText(_) => {
+                        if let InlayContent::VirtualText(content) = &inlay.content {
+                            if let Some(render) = content.render.clone() {
+                                renderer = Some(ChunkRenderer {
+                                    id: ChunkRendererId::Inlay(inlay.id),
+                                    render: Arc::new(move |cx| render(cx.window, cx.context)),
+                                    constrain_width: false,
+                                    measured_width: None,
+                                });
+                            }
+                            Some(content.style)
+                        } else {
+                            None
+                        }
+                    }
                 };
                 let next_inlay_highlight_endpoint;
                 let offset_in_inlay = self.output_offset - self.transforms.start().0;
@@ -57,7 +57,60 @@ pub fn replacement(c: char) -> Option<&'static str> {
     }
 }
 
+// The invisible characters that are most often pasted into code by accident can instead be
+// shown as a glyph, so that they can't be mistaken for a space or for nothing at all.
+pub fn placeholder(c: char) -> Option<&'static str> {
+    match c {
+        '\u{a0}' | '\u{202f}' => Some(NO_BREAK_SPACE_SYMBOL),
+        '\u{200b}' | '\u{200c}' | '\u{2060}' | '\u{feff}' => Some(ZERO_WIDTH_SYMBOL),
+        c if is_bidi_control(c) => Some(BIDI_CONTROL_SYMBOL),
+        _ => None,
+    }
+}
+
+// Marks, embeddings, overrides and isolates, which change the direction that the text around
+// them is displayed in.
+pub fn is_bidi_control(c: char) -> bool {
+    matches!(
+        c,
+        '\u{61c}' | '\u{200e}' | '\u{200f}' | '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+// The names of the invisible characters that have a placeholder, and of a few others that are
+// commonly found in text, to show when hovering them.
+pub fn name(c: char) -> Option<&'static str> {
+    Some(match c {
+        '\u{a0}' => "NO-BREAK SPACE",
+        '\u{ad}' => "SOFT HYPHEN",
+        '\u{61c}' => "ARABIC LETTER MARK",
+        '\u{200b}' => "ZERO WIDTH SPACE",
+        '\u{200c}' => "ZERO WIDTH NON-JOINER",
+        '\u{200d}' => "ZERO WIDTH JOINER",
+        '\u{200e}' => "LEFT-TO-RIGHT MARK",
+        '\u{200f}' => "RIGHT-TO-LEFT MARK",
+        '\u{2028}' => "LINE SEPARATOR",
+        '\u{2029}' => "PARAGRAPH SEPARATOR",
+        '\u{202a}' => "LEFT-TO-RIGHT EMBEDDING",
+        '\u{202b}' => "RIGHT-TO-LEFT EMBEDDING",
+        '\u{202c}' => "POP DIRECTIONAL FORMATTING",
+        '\u{202d}' => "LEFT-TO-RIGHT OVERRIDE",
+        '\u{202e}' => "RIGHT-TO-LEFT OVERRIDE",
+        '\u{202f}' => "NARROW NO-BREAK SPACE",
+        '\u{2060}' => "WORD JOINER",
+        '\u{2066}' => "LEFT-TO-RIGHT ISOLATE",
+        '\u{2067}' => "RIGHT-TO-LEFT ISOLATE",
+        '\u{2068}' => "FIRST STRONG ISOLATE",
+        '\u{2069}' => "POP DIRECTIONAL ISOLATE",
+        '\u{feff}' => "ZERO WIDTH NO-BREAK SPACE (BYTE ORDER MARK)",
+        _ => return None,
+    })
+}
+
 const FIXED_WIDTH_SPACE: &str = "\u{2007}";
+const NO_BREAK_SPACE_SYMBOL: &str = "⍽";
+const ZERO_WIDTH_SYMBOL: &str = "◌";
+const BIDI_CONTROL_SYMBOL: &str = "⇄";
 
 // IDEOGRAPHIC SPACE is common alongside Chinese and other wide character sets.
 // We don't highlight this for now (as it already shows up wide in the editor),
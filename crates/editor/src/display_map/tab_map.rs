@@ -1,15 +1,19 @@
 use super::{
     Highlights,
-    fold_map::{self, Chunk, FoldChunks, FoldEdit, FoldPoint, FoldSnapshot},
+    fold_map::{self, Chunk, FoldChunks, FoldEdit, FoldOffset, FoldPoint, FoldSnapshot},
 };
 
+use gpui::{Font, Pixels, TextSystem};
 use language::Point;
 use multi_buffer::MultiBufferSnapshot;
-use std::{cmp, mem, num::NonZeroU32, ops::Range};
-use sum_tree::Bias;
+use std::{cmp, mem, num::NonZeroU32, ops::Range, sync::Arc};
+use sum_tree::{Bias, SumTree};
 
 const MAX_EXPANSION_COLUMN: u32 = 256;
 
+/// The number of spaces between the widest cell of an elastic tabstop column and the next column.
+const ELASTIC_TABSTOP_PADDING: u32 = 1;
+
 // Handles a tab width <= 128
 const SPACES: &[u8; u128::BITS as usize] = &[b' '; _];
 const MAX_TABS: NonZeroU32 = NonZeroU32::new(SPACES.len() as u32).unwrap();
@@ -17,7 +21,11 @@ const MAX_TABS: NonZeroU32 = NonZeroU32::new(SPACES.len() as u32).unwrap();
 /// Keeps track of hard tabs in a text buffer.
 ///
 /// See the [`display_map` module documentation](crate::display_map) for more information.
-pub struct TabMap(TabSnapshot);
+pub struct TabMap {
+    snapshot: TabSnapshot,
+    elastic_tabstops: Option<CharWidths>,
+    elastic_tabstops_changed: bool,
+}
 
 impl TabMap {
     pub fn new(fold_snapshot: FoldSnapshot, tab_size: NonZeroU32) -> (Self, TabSnapshot) {
@@ -25,15 +33,31 @@ impl TabMap {
             fold_snapshot,
             tab_size: tab_size.min(MAX_TABS),
             max_expansion_column: MAX_EXPANSION_COLUMN,
+            elastic_tabstops: None,
             version: 0,
         };
-        (Self(snapshot.clone()), snapshot)
+        (
+            Self {
+                snapshot: snapshot.clone(),
+                elastic_tabstops: None,
+                elastic_tabstops_changed: false,
+            },
+            snapshot,
+        )
     }
 
     #[cfg(test)]
     pub fn set_max_expansion_column(&mut self, column: u32) -> TabSnapshot {
-        self.0.max_expansion_column = column;
-        self.0.clone()
+        self.snapshot.max_expansion_column = column;
+        self.snapshot.clone()
+    }
+
+    /// Enables elastic tabstops, which lay out the cells that tabs terminate on adjacent lines
+    /// to equal widths as measured by `char_widths`, or disables them when given `None`. Takes
+    /// effect on the next sync.
+    pub fn set_elastic_tabstops(&mut self, char_widths: Option<CharWidths>) {
+        self.elastic_tabstops = char_widths;
+        self.elastic_tabstops_changed = true;
     }
 
     pub fn sync(
@@ -42,19 +66,66 @@ impl TabMap {
         mut fold_edits: Vec<FoldEdit>,
         tab_size: NonZeroU32,
     ) -> (TabSnapshot, Vec<TabEdit>) {
-        let old_snapshot = &mut self.0;
+        let old_snapshot = &mut self.snapshot;
         let mut new_snapshot = TabSnapshot {
             fold_snapshot,
             tab_size: tab_size.min(MAX_TABS),
             max_expansion_column: old_snapshot.max_expansion_column,
+            elastic_tabstops: None,
             version: old_snapshot.version,
         };
 
-        if old_snapshot.fold_snapshot.version != new_snapshot.fold_snapshot.version {
+        let text_changed = old_snapshot.fold_snapshot.version != new_snapshot.fold_snapshot.version;
+        if text_changed {
             new_snapshot.version += 1;
         }
 
-        let tab_edits = if old_snapshot.tab_size == new_snapshot.tab_size {
+        let layout_changed = old_snapshot.tab_size != new_snapshot.tab_size
+            || mem::take(&mut self.elastic_tabstops_changed);
+        let mut elastic_row_edits = None;
+        if let Some(char_widths) = &self.elastic_tabstops {
+            new_snapshot.elastic_tabstops = Some(match &old_snapshot.elastic_tabstops {
+                Some(elastic_tabstops) if !layout_changed && fold_edits.is_empty() => {
+                    elastic_tabstops.clone()
+                }
+                Some(elastic_tabstops) if !layout_changed => {
+                    let (elastic_tabstops, row_edits) = elastic_tabstops.edit(
+                        &old_snapshot.fold_snapshot,
+                        &new_snapshot.fold_snapshot,
+                        &fold_edits,
+                        new_snapshot.tab_size,
+                        char_widths,
+                    );
+                    elastic_row_edits = Some(row_edits);
+                    Arc::new(elastic_tabstops)
+                }
+                _ => Arc::new(ElasticTabstops::new(
+                    &new_snapshot.fold_snapshot,
+                    new_snapshot.tab_size,
+                    char_widths,
+                )),
+            });
+        }
+
+        let tab_edits = if layout_changed {
+            new_snapshot.version += 1;
+            vec![TabEdit {
+                old: TabPoint::zero()..old_snapshot.max_point(),
+                new: TabPoint::zero()..new_snapshot.max_point(),
+            }]
+        } else if let Some(row_edits) = elastic_row_edits {
+            // An edit can change the width of the cells on the rows around it, so the edits cover
+            // every row whose tabstops were laid out again.
+            row_edits
+                .into_iter()
+                .map(|(old_rows, new_rows)| TabEdit {
+                    old: TabPoint::new(old_rows.start, 0)
+                        ..TabPoint::new(old_rows.end - 1, old_snapshot.line_len(old_rows.end - 1)),
+                    new: TabPoint::new(new_rows.start, 0)
+                        ..TabPoint::new(new_rows.end - 1, new_snapshot.line_len(new_rows.end - 1)),
+                })
+                .collect()
+        } else {
             // Expand each edit to include the next tab on the same line as the edit,
             // and any subsequent tabs on that line that moved across the tab expansion
             // boundary.
@@ -147,23 +218,276 @@ impl TabMap {
             } else {
                 vec![]
             }
-        } else {
-            new_snapshot.version += 1;
-            vec![TabEdit {
-                old: TabPoint::zero()..old_snapshot.max_point(),
-                new: TabPoint::zero()..new_snapshot.max_point(),
-            }]
         };
         *old_snapshot = new_snapshot;
         (old_snapshot.clone(), tab_edits)
     }
 }
 
+/// Measures the width of characters in spaces, which is what elastic tabstops lay out cells in.
+#[derive(Clone)]
+pub struct CharWidths(Arc<dyn Fn(char) -> f32 + Send + Sync>);
+
+impl CharWidths {
+    /// Measures characters as they're rendered in the given font, so that cells of proportional
+    /// text line up too.
+    pub fn new(text_system: Arc<TextSystem>, font: &Font, font_size: Pixels) -> Self {
+        let font_id = text_system.resolve_font(font);
+        let advance = move |ch| {
+            text_system
+                .advance(font_id, font_size, ch)
+                .map_or(Pixels::ZERO, |size| size.width)
+        };
+        let space_width = advance(' ');
+        if space_width <= Pixels::ZERO {
+            return Self::monospace();
+        }
+        let ascii_widths: [f32; 128] =
+            std::array::from_fn(|ch| advance(ch as u8 as char) / space_width);
+        Self(Arc::new(move |ch| match ascii_widths.get(ch as usize) {
+            Some(width) => *width,
+            None => advance(ch) / space_width,
+        }))
+    }
+
+    /// Measures every character as one space wide, as in a monospace font.
+    pub fn monospace() -> Self {
+        Self(Arc::new(|_| 1.))
+    }
+
+    fn width(&self, ch: char) -> f32 {
+        (self.0)(ch)
+    }
+}
+
+/// The columns that the tabs on each row expand to with elastic tabstops, where every tab
+/// terminates a cell, and the cells in the same column on adjacent lines are laid out to the width
+/// of the widest of them. Each tab expands to the number of spaces that brings the next cell to
+/// its tabstop, as measured by [`CharWidths`].
+#[derive(Clone)]
+pub struct ElasticTabstops {
+    rows: SumTree<ElasticRow>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ElasticRow {
+    /// The length in characters and the width in spaces of each cell that a tab terminates.
+    cells: Vec<(u32, f32)>,
+    /// The expanded column that each tab ends at.
+    tabstops: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ElasticRowSummary {
+    rows: u32,
+}
+
+impl sum_tree::Item for ElasticRow {
+    type Summary = ElasticRowSummary;
+
+    fn summary(&self, _cx: ()) -> Self::Summary {
+        ElasticRowSummary { rows: 1 }
+    }
+}
+
+impl sum_tree::ContextLessSummary for ElasticRowSummary {
+    fn zero() -> Self {
+        Default::default()
+    }
+
+    fn add_summary(&mut self, summary: &Self) {
+        self.rows += summary.rows;
+    }
+}
+
+impl<'a> sum_tree::Dimension<'a, ElasticRowSummary> for u32 {
+    fn zero(_cx: ()) -> Self {
+        Default::default()
+    }
+
+    fn add_summary(&mut self, summary: &'a ElasticRowSummary, _cx: ()) {
+        *self += summary.rows;
+    }
+}
+
+impl ElasticTabstops {
+    fn new(fold_snapshot: &FoldSnapshot, tab_size: NonZeroU32, char_widths: &CharWidths) -> Self {
+        let cells = Self::measure_rows(
+            fold_snapshot,
+            0..fold_snapshot.max_point().row() + 1,
+            char_widths,
+        );
+        Self {
+            rows: SumTree::from_iter(Self::layout(cells, tab_size), ()),
+        }
+    }
+
+    /// Lays out the rows around the edits again, returning the new tabstops along with the old and
+    /// new rows that were laid out.
+    ///
+    /// The cells in a column line up across a block of adjacent rows that have tabs, which rows
+    /// without tabs end, so only the blocks that contain the edited rows need to be measured.
+    fn edit(
+        &self,
+        old_fold_snapshot: &FoldSnapshot,
+        new_fold_snapshot: &FoldSnapshot,
+        fold_edits: &[FoldEdit],
+        tab_size: NonZeroU32,
+        char_widths: &CharWidths,
+    ) -> (Self, Vec<(Range<u32>, Range<u32>)>) {
+        let mut row_edits: Vec<(Range<u32>, Range<u32>)> = Vec::new();
+        let mut cursor = self.rows.cursor::<u32>(());
+        for fold_edit in fold_edits {
+            let mut old_rows = fold_edit.old.start.to_point(old_fold_snapshot).row()
+                ..fold_edit.old.end.to_point(old_fold_snapshot).row() + 1;
+            let mut new_rows = fold_edit.new.start.to_point(new_fold_snapshot).row()
+                ..fold_edit.new.end.to_point(new_fold_snapshot).row() + 1;
+
+            cursor.seek(&old_rows.start, Bias::Right);
+            let mut rows_above = 0;
+            while rows_above < old_rows.start {
+                cursor.prev();
+                if cursor.item().is_none_or(|row| row.cells.is_empty()) {
+                    break;
+                }
+                rows_above += 1;
+            }
+            old_rows.start -= rows_above;
+            new_rows.start = new_rows.start.saturating_sub(rows_above);
+
+            cursor.seek(&old_rows.end, Bias::Right);
+            while let Some(row) = cursor.item()
+                && !row.cells.is_empty()
+            {
+                old_rows.end += 1;
+                new_rows.end += 1;
+                cursor.next();
+            }
+
+            if let Some((last_old_rows, last_new_rows)) = row_edits.last_mut()
+                && last_old_rows.end >= old_rows.start
+            {
+                last_old_rows.end = old_rows.end;
+                last_new_rows.end = new_rows.end;
+            } else {
+                row_edits.push((old_rows, new_rows));
+            }
+        }
+
+        let mut cursor = self.rows.cursor::<u32>(());
+        let mut rows = SumTree::new(());
+        for (old_rows, new_rows) in &row_edits {
+            rows.append(cursor.slice(&old_rows.start, Bias::Right), ());
+            let cells = Self::measure_rows(new_fold_snapshot, new_rows.clone(), char_widths);
+            rows.extend(Self::layout(cells, tab_size), ());
+            cursor.seek(&old_rows.end, Bias::Right);
+        }
+        rows.append(cursor.suffix(), ());
+
+        (Self { rows }, row_edits)
+    }
+
+    /// The length and width of the cells on each of the given rows.
+    fn measure_rows(
+        fold_snapshot: &FoldSnapshot,
+        rows: Range<u32>,
+        char_widths: &CharWidths,
+    ) -> Vec<Vec<(u32, f32)>> {
+        let end_row = rows.end - 1;
+        let range = FoldPoint::new(rows.start, 0).to_offset(fold_snapshot)
+            ..FoldPoint::new(end_row, fold_snapshot.line_len(end_row)).to_offset(fold_snapshot);
+        let mut cells = vec![Vec::new()];
+        let mut cell = (0, 0.);
+        for chunk in fold_snapshot.chunks(range, false, Highlights::default()) {
+            for ch in chunk.text.chars() {
+                match ch {
+                    '\t' => {
+                        if let Some(row) = cells.last_mut() {
+                            row.push(mem::take(&mut cell));
+                        }
+                    }
+                    '\n' => {
+                        cells.push(Vec::new());
+                        cell = (0, 0.);
+                    }
+                    _ => {
+                        cell.0 += 1;
+                        cell.1 += char_widths.width(ch);
+                    }
+                }
+            }
+        }
+        cells
+    }
+
+    /// Lays out a block of rows, which rows without tabs bound on either side.
+    fn layout(cells: Vec<Vec<(u32, f32)>>, tab_size: NonZeroU32) -> Vec<ElasticRow> {
+        // The position of each tabstop in spaces from the start of its row.
+        let mut positions = cells
+            .iter()
+            .map(|cells| Vec::with_capacity(cells.len()))
+            .collect::<Vec<Vec<u32>>>();
+        let column_count = cells.iter().map(Vec::len).max().unwrap_or(0);
+        for column in 0..column_count {
+            let mut row = 0;
+            while row < cells.len() {
+                if cells[row].len() <= column {
+                    row += 1;
+                    continue;
+                }
+
+                // A block of adjacent rows that all have a cell in this column.
+                let block_start = row;
+                let mut width = tab_size.get();
+                while row < cells.len() && cells[row].len() > column {
+                    let cell_width = cells[row][column].1.ceil() as u32;
+                    width = width.max(cell_width + ELASTIC_TABSTOP_PADDING);
+                    row += 1;
+                }
+                for positions in &mut positions[block_start..row] {
+                    let cell_start = positions.last().copied().unwrap_or(0);
+                    positions.push(cell_start + width);
+                }
+            }
+        }
+
+        cells
+            .into_iter()
+            .zip(positions)
+            .map(|(cells, positions)| {
+                // Tabs are expanded to spaces, so a tab that follows proportional text expands to
+                // however many spaces come closest to its tabstop.
+                let mut x = 0.;
+                let mut column = 0;
+                let tabstops = cells
+                    .iter()
+                    .zip(positions)
+                    .map(|((len, width), position)| {
+                        x += width;
+                        let tab_len = (position as f32 - x).round().max(1.) as u32;
+                        x += tab_len as f32;
+                        column += len + tab_len;
+                        column
+                    })
+                    .collect();
+                ElasticRow { cells, tabstops }
+            })
+            .collect()
+    }
+
+    fn row(&self, row: u32) -> &[u32] {
+        let mut cursor = self.rows.cursor::<u32>(());
+        cursor.seek(&row, Bias::Right);
+        cursor.item().map_or(&[], |row| row.tabstops.as_slice())
+    }
+}
+
 #[derive(Clone)]
 pub struct TabSnapshot {
     pub fold_snapshot: FoldSnapshot,
     pub tab_size: NonZeroU32,
     pub max_expansion_column: u32,
+    pub elastic_tabstops: Option<Arc<ElasticTabstops>>,
     pub version: usize,
 }
 
@@ -258,7 +582,6 @@ impl TabSnapshot {
             max_expansion_column: self.max_expansion_column,
             output_position: range.start.0,
             max_output_position: range.end.0,
-            tab_size: self.tab_size,
             chunk: Chunk {
                 text: unsafe { std::str::from_utf8_unchecked(&SPACES[..to_next_stop as usize]) },
                 is_tab: true,
@@ -297,7 +620,7 @@ impl TabSnapshot {
     pub fn to_tab_point(&self, input: FoldPoint) -> TabPoint {
         let chunks = self.fold_snapshot.chunks_at(FoldPoint::new(input.row(), 0));
         let tab_cursor = TabStopCursor::new(chunks);
-        let expanded = self.expand_tabs(tab_cursor, input.column());
+        let expanded = self.expand_tabs(input.row(), tab_cursor, input.column());
         TabPoint::new(input.row(), expanded)
     }
 
@@ -309,7 +632,7 @@ impl TabSnapshot {
         let tab_cursor = TabStopCursor::new(chunks);
         let expanded = output.column();
         let (collapsed, expanded_char_column, to_next_stop) =
-            self.collapse_tabs(tab_cursor, expanded, bias);
+            self.collapse_tabs(output.row(), tab_cursor, expanded, bias);
 
         (
             FoldPoint::new(output.row(), collapsed),
@@ -332,12 +655,24 @@ impl TabSnapshot {
             .to_buffer_point(inlay_point)
     }
 
-    fn expand_tabs<'a, I>(&self, mut cursor: TabStopCursor<'a, I>, column: u32) -> u32
+    /// The number of columns that a tab starting at the given expanded column expands to.
+    fn tab_len(&self, row: u32, column: u32) -> u32 {
+        if let Some(elastic_tabstops) = &self.elastic_tabstops
+            && let Some(tabstop) = elastic_tabstops
+                .row(row)
+                .iter()
+                .find(|tabstop| **tabstop > column)
+        {
+            return tabstop - column;
+        }
+        let tab_size = self.tab_size.get();
+        tab_size - column % tab_size
+    }
+
+    fn expand_tabs<'a, I>(&self, row: u32, mut cursor: TabStopCursor<'a, I>, column: u32) -> u32
     where
         I: Iterator<Item = Chunk<'a>>,
     {
-        let tab_size = self.tab_size.get();
-
         let end_column = column.min(self.max_expansion_column);
         let mut seek_target = end_column;
         let mut tab_count = 0;
@@ -345,7 +680,7 @@ impl TabSnapshot {
 
         while let Some(tab_stop) = cursor.seek(seek_target) {
             let expanded_chars_old = tab_stop.char_offset + expanded_tab_len - tab_count;
-            let tab_len = tab_size - ((expanded_chars_old - 1) % tab_size);
+            let tab_len = self.tab_len(row, expanded_chars_old - 1);
             tab_count += 1;
             expanded_tab_len += tab_len;
 
@@ -367,6 +702,7 @@ impl TabSnapshot {
 
     fn collapse_tabs<'a, I>(
         &self,
+        row: u32,
         mut cursor: TabStopCursor<'a, I>,
         column: u32,
         bias: Bias,
@@ -374,7 +710,6 @@ impl TabSnapshot {
     where
         I: Iterator<Item = Chunk<'a>>,
     {
-        let tab_size = self.tab_size.get();
         let mut collapsed_column = column;
         let mut seek_target = column.min(self.max_expansion_column);
         let mut tab_count = 0;
@@ -383,7 +718,7 @@ impl TabSnapshot {
         while let Some(tab_stop) = cursor.seek(seek_target) {
             // Calculate how much we want to expand this tab stop (into spaces)
             let expanded_chars_old = tab_stop.char_offset + expanded_tab_len - tab_count;
-            let tab_len = tab_size - ((expanded_chars_old - 1) % tab_size);
+            let tab_len = self.tab_len(row, expanded_chars_old - 1);
             // Increment tab count
             tab_count += 1;
             // The count of how many spaces we've added to this line in place of tab bytes
@@ -506,7 +841,6 @@ pub struct TabChunks<'a> {
     snapshot: &'a TabSnapshot,
     max_expansion_column: u32,
     max_output_position: Point,
-    tab_size: NonZeroU32,
     // region: iteration state
     fold_chunks: FoldChunks<'a>,
     chunk: Chunk<'a>,
@@ -597,12 +931,11 @@ impl<'a> Iterator for TabChunks<'a> {
                         self.chunk.text = &self.chunk.text[1..];
                         self.chunk.tabs >>= 1;
                         self.chunk.chars >>= 1;
-                        let tab_size = if self.input_column < self.max_expansion_column {
-                            self.tab_size.get()
+                        let mut len = if self.input_column < self.max_expansion_column {
+                            self.snapshot.tab_len(self.output_position.row, self.column)
                         } else {
                             1
                         };
-                        let mut len = tab_size - self.column % tab_size;
                         let next_output_position = cmp::min(
                             self.output_position + Point::new(0, len),
                             self.max_output_position,
@@ -784,7 +1117,7 @@ mod tests {
 
             assert_eq!(
                 tab_snapshot.expected_expand_tabs(text.chars(), column),
-                tab_snapshot.expand_tabs(cursor, column)
+                tab_snapshot.expand_tabs(0, cursor, column)
             );
         }
     }
@@ -910,6 +1243,159 @@ mod tests {
         }
     }
 
+    #[gpui::test]
+    fn test_elastic_tabstops(cx: &mut gpui::App) {
+        let tab_size = NonZeroU32::new(4).unwrap();
+        let buffer = MultiBuffer::build_simple("a\tbb\tc\nlonger\td\te\n\tx\n\nq\tr", cx);
+        let subscription = buffer.update(cx, |buffer, _| buffer.subscribe());
+        let buffer_snapshot = buffer.read(cx).snapshot(cx);
+        let (mut inlay_map, inlay_snapshot) = InlayMap::new(buffer_snapshot);
+        let (mut fold_map, fold_snapshot) = FoldMap::new(inlay_snapshot);
+        let (mut tab_map, _) = TabMap::new(fold_snapshot.clone(), tab_size);
+
+        // Cells in the same column on adjacent lines have the width of the widest of them, and
+        // an empty line ends the block of lines that are aligned.
+        tab_map.set_elastic_tabstops(Some(CharWidths::monospace()));
+        let (tab_snapshot, _) = tab_map.sync(fold_snapshot, Vec::new(), tab_size);
+        assert_eq!(
+            tab_snapshot.text(),
+            "a      bb  c\nlonger d   e\n       x\n\nq   r"
+        );
+        assert_eq!(
+            tab_snapshot.to_tab_point(FoldPoint::new(1, 9)),
+            TabPoint::new(1, 11)
+        );
+        assert_eq!(
+            tab_snapshot
+                .to_fold_point(TabPoint::new(0, 9), Bias::Left)
+                .0,
+            FoldPoint::new(0, 4)
+        );
+
+        // Widening a cell realigns the lines above it.
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit(
+                [(Point::new(2, 0)..Point::new(2, 0), "widest cell")],
+                None,
+                cx,
+            )
+        });
+        let buffer_snapshot = buffer.read(cx).snapshot(cx);
+        let (inlay_snapshot, edits) =
+            inlay_map.sync(buffer_snapshot, subscription.consume().into_inner());
+        let (fold_snapshot, edits) = fold_map.read(inlay_snapshot, edits);
+        let (tab_snapshot, tab_edits) = tab_map.sync(fold_snapshot, edits, tab_size);
+        assert_eq!(
+            tab_snapshot.text(),
+            "a           bb  c\nlonger      d   e\nwidest cell x\n\nq   r"
+        );
+        assert_eq!(
+            tab_edits,
+            [TabEdit {
+                old: TabPoint::zero()..TabPoint::new(2, 8),
+                new: TabPoint::zero()..TabPoint::new(2, 13),
+            }]
+        );
+
+        tab_map.set_elastic_tabstops(None);
+        let (tab_snapshot, _) =
+            tab_map.sync(tab_snapshot.fold_snapshot.clone(), Vec::new(), tab_size);
+        assert_eq!(
+            tab_snapshot.text(),
+            "a   bb  c\nlonger  d   e\nwidest cell x\n\nq   r"
+        );
+    }
+
+    #[gpui::test]
+    fn test_elastic_tabstops_relayout_edited_block(cx: &mut gpui::App) {
+        let tab_size = NonZeroU32::new(4).unwrap();
+        let buffer = MultiBuffer::build_simple("a\tb\nc\td\n\ne\tf\ng\th\n\ni\tj", cx);
+        let subscription = buffer.update(cx, |buffer, _| buffer.subscribe());
+        let buffer_snapshot = buffer.read(cx).snapshot(cx);
+        let (mut inlay_map, inlay_snapshot) = InlayMap::new(buffer_snapshot);
+        let (mut fold_map, fold_snapshot) = FoldMap::new(inlay_snapshot);
+        let (mut tab_map, _) = TabMap::new(fold_snapshot.clone(), tab_size);
+        tab_map.set_elastic_tabstops(Some(CharWidths::monospace()));
+        let (tab_snapshot, _) = tab_map.sync(fold_snapshot, Vec::new(), tab_size);
+        assert_eq!(tab_snapshot.text(), "a   b\nc   d\n\ne   f\ng   h\n\ni   j");
+
+        // Only the block of rows with tabs around the edit is laid out again.
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(Point::new(4, 0)..Point::new(4, 0), "ggggg")], None, cx)
+        });
+        let buffer_snapshot = buffer.read(cx).snapshot(cx);
+        let (inlay_snapshot, edits) =
+            inlay_map.sync(buffer_snapshot, subscription.consume().into_inner());
+        let (fold_snapshot, edits) = fold_map.read(inlay_snapshot, edits);
+        let (tab_snapshot, tab_edits) = tab_map.sync(fold_snapshot, edits, tab_size);
+        assert_eq!(
+            tab_snapshot.text(),
+            "a   b\nc   d\n\ne      f\ngggggg h\n\ni   j"
+        );
+        assert_eq!(
+            tab_edits,
+            [TabEdit {
+                old: TabPoint::new(3, 0)..TabPoint::new(4, 5),
+                new: TabPoint::new(3, 0)..TabPoint::new(4, 8),
+            }]
+        );
+
+        // Edits in separate blocks are laid out separately, and a row that gains a tab joins the
+        // blocks around it.
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit(
+                [
+                    (Point::new(0, 0)..Point::new(0, 0), "aaaa"),
+                    (Point::new(5, 0)..Point::new(5, 0), "k\tl"),
+                ],
+                None,
+                cx,
+            )
+        });
+        let buffer_snapshot = buffer.read(cx).snapshot(cx);
+        let (inlay_snapshot, edits) =
+            inlay_map.sync(buffer_snapshot, subscription.consume().into_inner());
+        let (fold_snapshot, edits) = fold_map.read(inlay_snapshot, edits);
+        let (tab_snapshot, tab_edits) = tab_map.sync(fold_snapshot, edits, tab_size);
+        assert_eq!(
+            tab_snapshot.text(),
+            "aaaaa b\nc     d\n\ne      f\ngggggg h\nk      l\ni      j"
+        );
+        assert_eq!(
+            tab_edits,
+            [
+                TabEdit {
+                    old: TabPoint::new(0, 0)..TabPoint::new(1, 5),
+                    new: TabPoint::new(0, 0)..TabPoint::new(1, 7),
+                },
+                TabEdit {
+                    old: TabPoint::new(3, 0)..TabPoint::new(6, 5),
+                    new: TabPoint::new(3, 0)..TabPoint::new(6, 8),
+                },
+            ]
+        );
+    }
+
+    #[gpui::test]
+    fn test_elastic_tabstops_with_proportional_widths(cx: &mut gpui::App) {
+        let tab_size = NonZeroU32::new(4).unwrap();
+        let buffer = MultiBuffer::build_simple("ww\tx\nab\ty", cx);
+        let buffer_snapshot = buffer.read(cx).snapshot(cx);
+        let (_, inlay_snapshot) = InlayMap::new(buffer_snapshot);
+        let (_, fold_snapshot) = FoldMap::new(inlay_snapshot);
+        let (mut tab_map, _) = TabMap::new(fold_snapshot.clone(), tab_size);
+
+        // A `w` is two spaces wide, so the tab after `ww` only needs one space to reach the same
+        // position as the tab after `ab`.
+        tab_map.set_elastic_tabstops(Some(CharWidths(Arc::new(
+            |ch| {
+                if ch == 'w' { 2. } else { 1. }
+            },
+        ))));
+        let (tab_snapshot, _) = tab_map.sync(fold_snapshot, Vec::new(), tab_size);
+        assert_eq!(tab_snapshot.text(), "ww x\nab   y");
+    }
+
     #[gpui::test]
     fn test_long_lines(cx: &mut gpui::App) {
         let max_expansion_column = 12;
@@ -3,18 +3,64 @@ use super::{
     fold_map::{Chunk, FoldRows},
     tab_map::{self, TabEdit, TabPoint, TabSnapshot},
 };
-use gpui::{App, AppContext as _, Context, Entity, Font, LineWrapper, Pixels, Task};
+use gpui::{
+    App, AppContext as _, Context, Entity, Font, HighlightStyle, LineWrapper, Pixels, Task,
+};
 use language::Point;
 use multi_buffer::{MultiBufferSnapshot, RowInfo};
+use settings::WrapIndent;
 use smol::future::yield_now;
-use std::sync::LazyLock;
-use std::{cmp, collections::VecDeque, mem, ops::Range, time::Duration};
+use std::{cmp, collections::VecDeque, mem, ops::Range, sync::Arc, time::Duration};
 use sum_tree::{Bias, Cursor, Dimensions, SumTree};
 use text::Patch;
 
 pub use super::tab_map::TextSummary;
 pub type WrapEdit = text::Edit<u32>;
 
+/// Lines longer than this many bytes, such as minified code, are wrapped before
+/// the first character that doesn't fit instead of at word boundaries, and
+/// their wrapped lines aren't indented.
+const LONG_LINE_LEN: usize = 16 * 1024;
+
+/// How much further than the line itself its continuation lines are indented with
+/// [`WrapIndent::Extra`].
+const EXTRA_WRAP_INDENT: u32 = 2;
+
+/// How the continuation lines of soft-wrapped lines are displayed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WrapStyle {
+    pub indent: WrapIndent,
+    /// A character shown at the start of continuation lines, in place of their indentation.
+    pub indicator: Option<char>,
+}
+
+impl WrapStyle {
+    /// The indentation of the continuation lines of a line indented by `indent`.
+    fn continuation_indent(&self, indent: u32) -> u32 {
+        let indent = match self.indent {
+            WrapIndent::Same => indent,
+            WrapIndent::Extra => indent + EXTRA_WRAP_INDENT,
+            WrapIndent::None => 0,
+        };
+        indent.max(self.min_indent())
+    }
+
+    /// Continuation lines with an indicator leave a space between it and the text.
+    fn min_indent(&self) -> u32 {
+        if self.indicator.is_some() { 2 } else { 0 }
+    }
+
+    /// The text that the wrap transforms are sliced from: a newline followed by the indicator
+    /// and as much indentation as continuation lines can have.
+    fn wrap_text(&self) -> Arc<str> {
+        let mut wrap_text = String::new();
+        wrap_text.push('\n');
+        wrap_text.extend(self.indicator);
+        wrap_text.extend((0..LineWrapper::MAX_INDENT as usize).map(|_| ' '));
+        wrap_text.into()
+    }
+}
+
 /// Handles soft wrapping of text.
 ///
 /// See the [`display_map` module documentation](crate::display_map) for more information.
@@ -26,6 +72,9 @@ pub struct WrapMap {
     wrap_width: Option<Pixels>,
     background_task: Option<Task<()>>,
     font_with_size: (Font, Pixels),
+    wrap_style: WrapStyle,
+    /// Shared by all the wrap transforms, which only differ in how much of it they show.
+    wrap_text: Arc<str>,
 }
 
 #[derive(Clone)]
@@ -38,7 +87,9 @@ pub struct WrapSnapshot {
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 struct Transform {
     summary: TransformSummary,
-    display_text: Option<&'static str>,
+    /// For wrap transforms, the text starting with their newline. Only as much of it as the
+    /// transform's output summary covers is shown.
+    display_text: Option<Arc<str>>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -95,6 +146,8 @@ impl WrapMap {
         let handle = cx.new(|cx| {
             let mut this = Self {
                 font_with_size: (font, font_size),
+                wrap_style: WrapStyle::default(),
+                wrap_text: WrapStyle::default().wrap_text(),
                 wrap_width: None,
                 pending_edits: Default::default(),
                 interpolated_edits: Default::default(),
@@ -134,6 +187,10 @@ impl WrapMap {
         (self.snapshot.clone(), mem::take(&mut self.edits_since_sync))
     }
 
+    pub fn font_with_size(&self) -> &(Font, Pixels) {
+        &self.font_with_size
+    }
+
     pub fn set_font_with_size(
         &mut self,
         font: Font,
@@ -151,6 +208,19 @@ impl WrapMap {
         }
     }
 
+    pub fn set_wrap_style(&mut self, wrap_style: WrapStyle, cx: &mut Context<Self>) -> bool {
+        if wrap_style == self.wrap_style {
+            return false;
+        }
+
+        if wrap_style.indicator != self.wrap_style.indicator {
+            self.wrap_text = wrap_style.wrap_text();
+        }
+        self.wrap_style = wrap_style;
+        self.rewrap(cx);
+        true
+    }
+
     pub fn set_wrap_width(&mut self, wrap_width: Option<Pixels>, cx: &mut Context<Self>) -> bool {
         if wrap_width == self.wrap_width {
             return false;
@@ -171,6 +241,8 @@ impl WrapMap {
 
             let text_system = cx.text_system().clone();
             let (font, font_size) = self.font_with_size.clone();
+            let wrap_style = self.wrap_style;
+            let wrap_text = self.wrap_text.clone();
             let task = cx.background_spawn(async move {
                 let mut line_wrapper = text_system.line_wrapper(font, font_size);
                 let tab_snapshot = new_snapshot.tab_snapshot.clone();
@@ -183,6 +255,8 @@ impl WrapMap {
                             new: range.clone(),
                         }],
                         wrap_width,
+                        wrap_style,
+                        &wrap_text,
                         &mut line_wrapper,
                     )
                     .await;
@@ -256,12 +330,21 @@ impl WrapMap {
             let mut snapshot = self.snapshot.clone();
             let text_system = cx.text_system().clone();
             let (font, font_size) = self.font_with_size.clone();
+            let wrap_style = self.wrap_style;
+            let wrap_text = self.wrap_text.clone();
             let update_task = cx.background_spawn(async move {
                 let mut edits = Patch::default();
                 let mut line_wrapper = text_system.line_wrapper(font, font_size);
                 for (tab_snapshot, tab_edits) in pending_edits {
                     let wrap_edits = snapshot
-                        .update(tab_snapshot, &tab_edits, wrap_width, &mut line_wrapper)
+                        .update(
+                            tab_snapshot,
+                            &tab_edits,
+                            wrap_width,
+                            wrap_style,
+                            &wrap_text,
+                            &mut line_wrapper,
+                        )
                         .await;
                     edits = edits.compose(&wrap_edits);
                 }
@@ -400,6 +483,8 @@ impl WrapSnapshot {
         new_tab_snapshot: TabSnapshot,
         tab_edits: &[TabEdit],
         wrap_width: Pixels,
+        wrap_style: WrapStyle,
+        wrap_text: &Arc<str>,
         line_wrapper: &mut LineWrapper,
     ) -> Patch<u32> {
         #[derive(Debug)]
@@ -488,10 +573,24 @@ impl WrapSnapshot {
                     }
 
                     let mut prev_boundary_ix = 0;
-                    for boundary in line_wrapper.wrap_line(&line_fragments, wrap_width) {
+                    let boundaries: Box<dyn Iterator<Item = gpui::Boundary>> =
+                        if line.len() > LONG_LINE_LEN {
+                            Box::new(line_wrapper.wrap_long_line(&line_fragments, wrap_width))
+                        } else {
+                            Box::new(line_wrapper.wrap_line_with_indent(
+                                &line_fragments,
+                                wrap_width,
+                                |indent| wrap_style.continuation_indent(indent),
+                            ))
+                        };
+                    for boundary in boundaries {
                         let wrapped = &line[prev_boundary_ix..boundary.ix];
                         push_isomorphic(&mut edit_transforms, TextSummary::from(wrapped));
-                        edit_transforms.push(Transform::wrap(boundary.next_indent));
+                        edit_transforms.push(Transform::wrap(
+                            boundary.next_indent.max(wrap_style.min_indent()),
+                            wrap_style.indicator,
+                            wrap_text,
+                        ));
                         prev_boundary_ix = boundary.ix;
                     }
 
@@ -924,9 +1023,9 @@ impl<'a> Iterator for WrapChunks<'a> {
         }
 
         let transform = self.transforms.item()?;
-        if let Some(display_text) = transform.display_text {
+        if let Some(display_text) = &transform.display_text {
             let mut start_ix = 0;
-            let mut end_ix = display_text.len();
+            let mut end_ix = 1 + transform.summary.output.lines.column as usize;
             let mut summary = transform.summary.output.lines;
 
             if self.output_position > self.transforms.start().0 {
@@ -941,8 +1040,15 @@ impl<'a> Iterator for WrapChunks<'a> {
 
             self.output_position.0 += summary;
             self.transforms.next();
+            let text = &display_text[start_ix..end_ix];
+            // Fade the wrap indicator, if any, so that it isn't mistaken for text.
+            let has_indicator = text.trim_start_matches('\n').starts_with(|c| c != ' ');
             return Some(Chunk {
-                text: &display_text[start_ix..end_ix],
+                text,
+                highlight_style: has_indicator.then(|| HighlightStyle {
+                    fade_out: Some(0.5),
+                    ..HighlightStyle::default()
+                }),
                 ..Default::default()
             });
         }
@@ -1044,26 +1150,25 @@ impl Transform {
         }
     }
 
-    fn wrap(indent: u32) -> Self {
-        static WRAP_TEXT: LazyLock<String> = LazyLock::new(|| {
-            let mut wrap_text = String::new();
-            wrap_text.push('\n');
-            wrap_text.extend((0..LineWrapper::MAX_INDENT as usize).map(|_| ' '));
-            wrap_text
-        });
+    fn wrap(indent: u32, indicator: Option<char>, wrap_text: &Arc<str>) -> Self {
+        // The indicator takes the place of the first column of indentation.
+        let len = match indicator {
+            Some(indicator) if indent > 0 => indicator.len_utf8() + indent as usize - 1,
+            _ => indent as usize,
+        };
 
         Self {
             summary: TransformSummary {
                 input: TextSummary::default(),
                 output: TextSummary {
-                    lines: Point::new(1, indent),
+                    lines: Point::new(1, len as u32),
                     first_line_chars: 0,
                     last_line_chars: indent,
                     longest_row: 1,
                     longest_row_chars: indent,
                 },
             },
-            display_text: Some(&WRAP_TEXT[..1 + indent as usize]),
+            display_text: Some(wrap_text.clone()),
         }
     }
 
@@ -1454,6 +1559,63 @@ mod tests {
         wrap_map.read_with(cx, |map, _| assert!(map.pending_edits.is_empty()));
     }
 
+    #[gpui::test]
+    async fn test_wrap_long_lines(cx: &mut gpui::TestAppContext) {
+        init_test(cx);
+
+        let text_system = cx.read(|cx| cx.text_system().clone());
+        let font = test_font();
+        let font_size = px(14.0);
+        let wrap_width = Some(px(100.));
+        let tab_size = NonZeroU32::new(4).unwrap();
+
+        let long_line = "abc ".repeat(LONG_LINE_LEN / 2);
+        let buffer = cx.update(|cx| MultiBuffer::build_simple(&format!("{long_line}\nshort"), cx));
+        let buffer_snapshot = buffer.read_with(cx, |buffer, cx| buffer.snapshot(cx));
+        let (_, inlay_snapshot) = InlayMap::new(buffer_snapshot);
+        let (_, fold_snapshot) = FoldMap::new(inlay_snapshot);
+        let (_, tabs_snapshot) = TabMap::new(fold_snapshot, tab_size);
+
+        let (wrap_map, _) = cx.update(|cx| {
+            WrapMap::new(
+                tabs_snapshot.clone(),
+                font.clone(),
+                font_size,
+                wrap_width,
+                cx,
+            )
+        });
+        let mut notifications = observe(&wrap_map, cx);
+        while wrap_map.read_with(cx, |map, _| map.is_rewrapping()) {
+            notifications.next().await.unwrap();
+        }
+        let (snapshot, _) = wrap_map.update(cx, |map, cx| map.sync(tabs_snapshot, Vec::new(), cx));
+
+        // Each wrapped line of the long line is unindented, and holds as many characters as fit,
+        // measured with the font's advances, even when that splits a word. The short line is left
+        // alone.
+        let font_id = text_system.resolve_font(&font);
+        let advance = |c: char| text_system.advance(font_id, font_size, c).unwrap().width;
+        let text = snapshot.text();
+        let mut wrapped_lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(wrapped_lines.pop(), Some("short"));
+        assert!(wrapped_lines.len() > 1);
+        assert_eq!(wrapped_lines.concat(), long_line);
+        let mut rest = long_line.as_str();
+        for wrapped_line in &wrapped_lines[..wrapped_lines.len() - 1] {
+            rest = &rest[wrapped_line.len()..];
+            let width = wrapped_line
+                .chars()
+                .fold(px(0.), |width, c| width + advance(c));
+            let next_char = rest.chars().next().unwrap();
+            assert!(width <= wrap_width.unwrap(), "{wrapped_line:?} overflows");
+            assert!(
+                width + advance(next_char) > wrap_width.unwrap(),
+                "{wrapped_line:?} could fit {next_char:?}"
+            );
+        }
+    }
+
     fn init_test(cx: &mut gpui::TestAppContext) {
         cx.update(|cx| {
             let settings = SettingsStore::test(cx);
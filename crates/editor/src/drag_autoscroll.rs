@@ -0,0 +1,155 @@
+//! Scrolling the editor while a selection or a dragged selection's drop cursor is dragged past
+//! the edge of the text. The editor keeps scrolling, and the selection keeps extending, while the
+//! mouse is held still outside of the text, faster the further outside of it the mouse is.
+use std::time::Duration;
+
+use gpui::{Context, Pixels, Task, Window};
+use language::Bias;
+
+use crate::{
+    Editor, SelectionDragState,
+    display_map::{DisplayPoint, DisplayRow, ToDisplayPoint as _},
+};
+
+/// How often the editor scrolls while the mouse is held outside of the text.
+const DRAG_AUTOSCROLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How many lines or columns per second the editor scrolls for each line or column that the
+/// mouse is outside of the text.
+const DRAG_AUTOSCROLL_SPEED: f32 = 12.;
+
+/// The fastest the editor scrolls vertically, in lines per second.
+const MAX_VERTICAL_DRAG_AUTOSCROLL_SPEED: f32 = 240.;
+
+pub(crate) struct DragAutoscroll {
+    /// The scroll speed, in columns and lines per second.
+    velocity: gpui::Point<f32>,
+    /// The column of the mouse, for columnar selections.
+    goal_column: u32,
+    _task: Task<()>,
+}
+
+/// The vertical scroll speed, in lines per second, when the mouse is `overshoot` above or below
+/// the text.
+pub(crate) fn vertical_drag_autoscroll_speed(overshoot: Pixels, line_height: Pixels) -> f32 {
+    (overshoot / line_height * DRAG_AUTOSCROLL_SPEED).min(MAX_VERTICAL_DRAG_AUTOSCROLL_SPEED)
+}
+
+/// The horizontal scroll speed, in columns per second, when the mouse is `overshoot` to the left
+/// or right of the text.
+pub(crate) fn horizontal_drag_autoscroll_speed(overshoot: Pixels, em_width: Pixels) -> f32 {
+    overshoot / em_width * DRAG_AUTOSCROLL_SPEED
+}
+
+impl Editor {
+    /// Scrolls the editor at `velocity`, in columns and lines per second, until the drag ends or
+    /// the velocity is set back to zero.
+    pub(crate) fn set_drag_autoscroll(
+        &mut self,
+        velocity: gpui::Point<f32>,
+        goal_column: u32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if velocity == gpui::Point::default() {
+            self.drag_autoscroll = None;
+            return;
+        }
+        if let Some(autoscroll) = self.drag_autoscroll.as_mut() {
+            autoscroll.velocity = velocity;
+            autoscroll.goal_column = goal_column;
+            return;
+        }
+
+        let task = cx.spawn_in(window, async move |editor, cx| {
+            loop {
+                cx.background_executor()
+                    .timer(DRAG_AUTOSCROLL_INTERVAL)
+                    .await;
+                let scrolled = editor
+                    .update_in(cx, |editor, window, cx| {
+                        editor.drag_autoscroll_tick(window, cx)
+                    })
+                    .unwrap_or(false);
+                if !scrolled {
+                    break;
+                }
+            }
+        });
+        self.drag_autoscroll = Some(DragAutoscroll {
+            velocity,
+            goal_column,
+            _task: task,
+        });
+    }
+
+    /// Scrolls by one interval's worth, and moves the dragged selection head or drop cursor to
+    /// the edge of the text that is being scrolled towards.
+    fn drag_autoscroll_tick(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let Some(autoscroll) = self.drag_autoscroll.as_ref() else {
+            return false;
+        };
+        let velocity = autoscroll.velocity;
+        let goal_column = autoscroll.goal_column;
+        let selecting =
+            self.columnar_selection_state.is_some() || self.selections.pending_anchor().is_some();
+        let dragging = matches!(
+            self.selection_drag_state,
+            SelectionDragState::Dragging { .. }
+        );
+        if !selecting && !dragging {
+            self.drag_autoscroll = None;
+            return false;
+        }
+
+        let interval = DRAG_AUTOSCROLL_INTERVAL.as_secs_f32();
+        self.apply_scroll_delta(velocity.map(|speed| speed * interval), window, cx);
+
+        let snapshot = self.snapshot(window, cx);
+        let current = match &self.selection_drag_state {
+            SelectionDragState::Dragging { drop_cursor, .. } if !selecting => drop_cursor.start,
+            _ => self.selections.newest_anchor().head(),
+        }
+        .to_display_point(&snapshot);
+
+        // Rows are counted in display rows, so folds, wrapped lines and excerpt boundaries are
+        // stepped over like any other line.
+        let scroll_position = snapshot.scroll_position();
+        let visible_lines = self.visible_line_count().unwrap_or(0.);
+        let visible_columns = self.visible_column_count().unwrap_or(0.);
+        let row = if velocity.y > 0. {
+            (scroll_position.y + visible_lines).floor() - 1.
+        } else if velocity.y < 0. {
+            scroll_position.y.ceil()
+        } else {
+            current.row().0 as f64
+        };
+        let column = if velocity.x > 0. {
+            (scroll_position.x + visible_columns).floor() as u32
+        } else if velocity.x < 0. {
+            scroll_position.x.ceil() as u32
+        } else {
+            goal_column
+        };
+        let max_point = snapshot.max_point();
+        let row = DisplayRow((row.max(0.) as u32).min(max_point.row().0));
+        let position = snapshot.clip_point(DisplayPoint::new(row, column), Bias::Left);
+
+        if selecting {
+            let goal_column = if velocity.x == 0. {
+                goal_column
+            } else {
+                column
+            };
+            self.update_selection(position, goal_column, gpui::Point::default(), window, cx);
+        } else if let SelectionDragState::Dragging { drop_cursor, .. } =
+            &mut self.selection_drag_state
+        {
+            let anchor = snapshot.display_point_to_anchor(position, Bias::Left);
+            drop_cursor.start = anchor;
+            drop_cursor.end = anchor;
+            cx.notify();
+        }
+        true
+    }
+}
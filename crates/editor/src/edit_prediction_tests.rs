@@ -1,8 +1,14 @@
-use edit_prediction::EditPredictionProvider;
-use gpui::{Entity, prelude::*};
+use edit_prediction::{
+    EditPredictionProvider, InlineCompletionSource, InlineCompletionSourceProvider,
+};
+use gpui::{Entity, Task, prelude::*};
 use indoc::indoc;
 use multi_buffer::{Anchor, MultiBufferSnapshot, ToPoint};
-use std::ops::Range;
+use std::{
+    cell::{Cell, RefCell},
+    ops::Range,
+    rc::Rc,
+};
 use text::{Point, ToOffset};
 
 use crate::{
@@ -270,6 +276,51 @@ async fn test_edit_prediction_jump_disabled_for_non_zed_providers(cx: &mut gpui:
     });
 }
 
+#[gpui::test]
+async fn test_inline_completion_source(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let source = Rc::new(FakeInlineCompletionSource::default());
+    let provider = cx.new(|_| InlineCompletionSourceProvider::new(source.clone()));
+    cx.update_editor(|editor, window, cx| {
+        editor.set_edit_prediction_provider(Some(provider), window, cx);
+    });
+    cx.set_state("let x = ˇ");
+    cx.run_until_parked();
+    source.completion.replace(Some("foo();\nbar();".into()));
+    source.requests.set(0);
+
+    // Debounced requests replace each other and are only sent once the timeout elapses.
+    for _ in 0..2 {
+        cx.update_editor(|editor, window, cx| {
+            editor.refresh_edit_prediction(true, true, window, cx);
+        });
+    }
+    cx.run_until_parked();
+    assert_eq!(source.requests.get(), 0);
+    cx.executor()
+        .advance_clock(edit_prediction::DEBOUNCE_TIMEOUT);
+    cx.run_until_parked();
+    assert_eq!(source.requests.get(), 1);
+
+    cx.update_editor(|editor, window, cx| editor.update_visible_edit_prediction(window, cx));
+    assert_editor_active_edit_completion(&mut cx, |_, edits| {
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].1.as_str(), "foo();\nbar();");
+    });
+
+    cx.update_editor(|editor, window, cx| {
+        editor.accept_next_line_edit_prediction(&crate::AcceptNextLineEditPrediction, window, cx)
+    });
+    cx.assert_editor_state("let x = foo();\nˇ");
+    assert_eq!(source.requests.get(), 1);
+
+    cx.update_editor(|editor, window, cx| editor.update_visible_edit_prediction(window, cx));
+    accept_completion(&mut cx);
+    cx.assert_editor_state("let x = foo();\nbar();ˇ");
+}
+
 fn assert_editor_active_edit_completion(
     cx: &mut EditorTestContext,
     assert: impl FnOnce(MultiBufferSnapshot, &Vec<(Range<Anchor>, String)>),
@@ -519,3 +570,25 @@ impl EditPredictionProvider for FakeNonZedEditPredictionProvider {
         self.completion.clone()
     }
 }
+
+#[derive(Default)]
+struct FakeInlineCompletionSource {
+    completion: RefCell<Option<String>>,
+    requests: Cell<usize>,
+}
+
+impl InlineCompletionSource for FakeInlineCompletionSource {
+    fn display_name(&self) -> gpui::SharedString {
+        "Fake Inline Completion Source".into()
+    }
+
+    fn request_completion(
+        &self,
+        _buffer: &gpui::Entity<language::Buffer>,
+        _cursor_position: language::Anchor,
+        _cx: &mut gpui::App,
+    ) -> Task<anyhow::Result<Option<String>>> {
+        self.requests.set(self.requests.get() + 1);
+        Task::ready(Ok(self.completion.borrow().clone()))
+    }
+}
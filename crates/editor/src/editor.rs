@@ -13,31 +13,61 @@
 //!
 //! If you're looking to improve Vim mode, you should check out Vim crate that wraps Editor and overrides its behavior.
 pub mod actions;
+mod align_selections_prompt;
+mod ansi_escapes;
+mod bidi;
 mod blink_manager;
+mod bookmarks;
+mod call_hierarchy;
+mod changed_regions;
 mod clangd_ext;
+mod clipboard_history;
 pub mod code_context_menus;
+mod code_image;
+mod csv_columns;
+mod custom_highlights;
 pub mod display_map;
+mod drag_autoscroll;
 mod editor_settings;
 mod element;
+mod evaluate;
+mod fold_imports;
+mod fold_reveal;
 mod git;
+mod gutter_decorations;
 mod highlight_matching_bracket;
+mod highlighted_export;
 mod hover_links;
 pub mod hover_popover;
+mod increment;
 mod indent_guides;
 mod inlay_hint_cache;
 pub mod items;
 mod jsx_tag_auto_close;
+mod keyboard_macros;
+mod kill_ring;
+mod large_file_banner;
 mod linked_editing_ranges;
+mod log_files;
 mod lsp_colors;
 mod lsp_ext;
+mod markdown_tables;
 mod mouse_context_menu;
 pub mod movement;
+mod peek_definition;
 mod persistence;
+mod postfix_completions;
+mod profiler;
 mod proposed_changes_editor;
+mod quick_fix;
+mod read_only_indicator;
 mod rust_analyzer_ext;
+mod screen_reader;
 pub mod scroll;
+mod selection_sets;
 mod selections_collection;
 pub mod tasks;
+pub mod todo_comments;
 
 #[cfg(test)]
 mod code_completion_tests;
@@ -46,22 +76,37 @@ mod edit_prediction_tests;
 #[cfg(test)]
 mod editor_tests;
 mod signature_help;
+mod snippet_variables;
+mod string_literals;
+mod structured_text;
+mod syntax_errors;
 #[cfg(any(test, feature = "test-support"))]
 pub mod test;
+mod test_results;
+mod type_hierarchy;
+mod unconfirmed_edits;
+mod unicode_highlights;
+mod virtual_text;
 
 pub(crate) use actions::*;
+pub use call_hierarchy::{CallHierarchyDirection, CallHierarchyView};
+pub use clipboard_history::{ClipboardHistoryEntry, clipboard_history};
+pub use csv_columns::CsvColumnIndicator;
 pub use display_map::{ChunkRenderer, ChunkRendererContext, DisplayPoint, FoldPlaceholder};
 pub use edit_prediction::Direction;
 pub use editor_settings::{
-    CurrentLineHighlight, DocumentColorsRenderMode, EditorSettings, HideMouseMode,
+    CurrentLineHighlight, DocumentColorsRenderMode, EditorSettings, HideMouseMode, LineNumbers,
     ScrollBeyondLastLine, ScrollbarAxes, SearchSettings, ShowMinimap,
 };
 pub use element::{
     CursorLayout, EditorElement, HighlightedRange, HighlightedRangeLine, PointForPosition,
 };
 pub use git::blame::BlameRenderer;
+pub use gutter_decorations::GutterDecoration;
 pub use hover_popover::hover_markdown_style;
 pub use items::MAX_TAB_TITLE_LEN;
+pub use large_file_banner::LargeFileBanner;
+pub use log_files::LogLevel;
 pub use lsp::CompletionContext;
 pub use lsp_ext::lsp_tasks;
 pub use multi_buffer::{
@@ -71,7 +116,10 @@ pub use multi_buffer::{
 pub use proposed_changes_editor::{
     ProposedChangeLocation, ProposedChangesEditor, ProposedChangesEditorToolbar,
 };
+pub use read_only_indicator::ReadOnlyIndicator;
 pub use text::Bias;
+pub use type_hierarchy::{TypeHierarchyDirection, TypeHierarchyView};
+pub use virtual_text::{RenderVirtualText, VirtualText, VirtualTextId, VirtualTextPlacement};
 
 use ::git::{
     Restore,
@@ -85,15 +133,20 @@ use client::{Collaborator, ParticipantIndex, parse_zed_link};
 use clock::{AGENT_REPLICA_ID, ReplicaId};
 use code_context_menus::{
     AvailableCodeAction, CodeActionContents, CodeActionsItem, CodeActionsMenu, CodeContextMenu,
-    CompletionsMenu, ContextMenuOrigin,
+    CompletionRanking, CompletionsMenu, ContextMenuOrigin,
 };
-use collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque, hash_map};
 use convert_case::{Case, Casing};
 use dap::TelemetrySpawnLocation;
 use display_map::*;
 use edit_prediction::{EditPredictionProvider, EditPredictionProviderHandle};
-use editor_settings::{GoToDefinitionFallback, Minimap as MinimapSettings};
-use element::{AcceptEditPredictionBinding, LineWithInvisibles, PositionMap, layout_line};
+use editor_settings::{
+    BidiCursorMovement, DocumentHighlightStyle, GoToDefinitionFallback, Minimap as MinimapSettings,
+    MultiCursorPaste,
+};
+use element::{
+    AcceptEditPredictionBinding, CursorAnimation, LineWithInvisibles, PositionMap, layout_line,
+};
 use futures::{
     FutureExt, StreamExt as _,
     future::{self, Shared, join},
@@ -103,13 +156,14 @@ use fuzzy::{StringMatch, StringMatchCandidate};
 use git::blame::{GitBlame, GlobalBlameRenderer};
 use gpui::{
     Action, Animation, AnimationExt, AnyElement, App, AppContext, AsyncWindowContext,
-    AvailableSpace, Background, Bounds, ClickEvent, ClipboardEntry, ClipboardItem, Context,
-    DispatchPhase, Edges, Entity, EntityInputHandler, EventEmitter, FocusHandle, FocusOutEvent,
-    Focusable, FontId, FontWeight, Global, HighlightStyle, Hsla, KeyContext, Modifiers,
-    MouseButton, MouseDownEvent, PaintQuad, ParentElement, Pixels, Render, ScrollHandle,
-    SharedString, Size, Stateful, Styled, Subscription, Task, TextStyle, TextStyleRefinement,
-    UTF16Selection, UnderlineStyle, UniformListScrollHandle, WeakEntity, WeakFocusHandle, Window,
-    div, point, prelude::*, pulsating_between, px, relative, size,
+    AvailableSpace, Background, Bounds, ClickEvent, ClipboardEntry, ClipboardItem,
+    CompositionClause, Context, DispatchPhase, Edges, Entity, EntityId, EntityInputHandler,
+    EventEmitter, FocusHandle, FocusOutEvent, Focusable, Font, FontId, FontWeight, Global,
+    HighlightStyle, Hsla, KeyContext, Modifiers, MouseButton, MouseDownEvent, PaintQuad,
+    ParentElement, Pixels, Render, ScrollHandle, SharedString, Size, Stateful, Styled,
+    Subscription, Task, TextStyle, TextStyleRefinement, UTF16Selection, UnderlineStyle,
+    UniformListScrollHandle, WeakEntity, WeakFocusHandle, Window, div, point, prelude::*,
+    pulsating_between, px, relative, size,
 };
 use highlight_matching_bracket::refresh_matching_bracket_highlights;
 use hover_links::{HoverLink, HoveredLinkState, InlayHighlight, find_file};
@@ -117,15 +171,16 @@ use hover_popover::{HoverState, hide_hover};
 use indent_guides::ActiveIndentGuidesState;
 use inlay_hint_cache::{InlayHintCache, InlaySplice, InvalidationStrategy};
 use itertools::{Either, Itertools};
+use kill_ring::LastKillRingEdit;
 use language::{
     AutoindentMode, BlockCommentConfig, BracketMatch, BracketPair, Buffer, BufferRow,
     BufferSnapshot, Capability, CharClassifier, CharKind, CharScopeContext, CodeLabel, CursorShape,
-    DiagnosticEntryRef, DiffOptions, EditPredictionsMode, EditPreview, HighlightedText, IndentKind,
-    IndentSize, Language, OffsetRangeExt, Point, Runnable, RunnableRange, Selection, SelectionGoal,
-    TextObject, TransactionId, TreeSitterOptions, WordsQuery,
+    Diagnostic, DiagnosticEntryRef, DiffOptions, EditPredictionsMode, EditPreview, HighlightedText,
+    IndentKind, IndentSize, Language, OffsetRangeExt, Point, Runnable, RunnableRange, Selection,
+    SelectionGoal, TextObject, TransactionId, TreeSitterOptions, WordsQuery,
     language_settings::{
-        self, InlayHintSettings, LspInsertMode, RewrapBehavior, WordsCompletionMode,
-        all_language_settings, language_settings,
+        self, InlayHintKind, InlayHintSettings, LanguageSettings, LspInsertMode, RewrapBehavior,
+        WordsCompletionMode, all_language_settings, language_settings,
     },
     point_from_lsp, point_to_lsp, text_diff_with_options,
 };
@@ -144,11 +199,13 @@ use multi_buffer::{
 };
 use parking_lot::Mutex;
 use persistence::DB;
+use profiler::ProfiledSubsystem;
 use project::{
     BreakpointWithPosition, CodeAction, Completion, CompletionDisplayOptions, CompletionIntent,
     CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, InlayHint,
     Location, LocationLink, PrepareRenameResponse, Project, ProjectItem, ProjectPath,
     ProjectTransaction, TaskSourceKind,
+    bookmark_store::BookmarkStore,
     debugger::{
         breakpoint_store::{
             Breakpoint, BreakpointEditAction, BreakpointSessionState, BreakpointState,
@@ -158,7 +215,9 @@ use project::{
     },
     git_store::{GitStoreEvent, RepositoryEvent},
     lsp_store::{CompletionDocumentation, FormatTrigger, LspFormatTarget, OpenLspBufferHandle},
-    project_settings::{DiagnosticSeverity, GoToDiagnosticSeverityFilter, ProjectSettings},
+    project_settings::{
+        DiagnosticSeverity, GoToDiagnosticSeverity, GoToDiagnosticSeverityFilter, ProjectSettings,
+    },
 };
 use rand::seq::SliceRandom;
 use rpc::{ErrorCode, ErrorExt, proto::PeerId};
@@ -207,9 +266,11 @@ use workspace::{
 use crate::{
     code_context_menus::CompletionsMenuSource,
     editor_settings::MultiCursorModifier,
-    hover_links::{find_url, find_url_from_range},
+    hover_links::{find_link_rule_url, find_url, find_url_from_range, open_file_link},
     scroll::{ScrollOffset, ScrollPixelOffset},
     signature_help::{SignatureHelpHiddenBy, SignatureHelpState},
+    test_results::TestRun,
+    virtual_text::VirtualTextDisplay,
 };
 
 pub const FILE_HEADER_HEIGHT: u32 = 2;
@@ -284,6 +345,8 @@ pub enum InlayId {
     // LSP
     Hint(u32),
     Color(u32),
+    Padding(u32),
+    VirtualText(u32),
 }
 
 impl InlayId {
@@ -293,6 +356,8 @@ impl InlayId {
             Self::DebuggerValue(id) => *id,
             Self::Hint(id) => *id,
             Self::Color(id) => *id,
+            Self::Padding(id) => *id,
+            Self::VirtualText(id) => *id,
         }
     }
 }
@@ -302,8 +367,31 @@ pub enum DebugStackFrameLine {}
 enum DocumentHighlightRead {}
 enum DocumentHighlightWrite {}
 enum InputComposition {}
+enum InputCompositionClause {}
 pub enum PendingInput {}
 enum SelectedTextHighlight {}
+enum PinnedWordHighlight {}
+enum FocusModeHighlight {}
+enum MixedLineEndingHighlight {}
+
+/// The colors of pinned words, taken from the collaborator colors after the local player's,
+/// which is the color of the editor's own selections.
+const PINNED_WORD_HIGHLIGHT_COLORS: [fn(&Theme) -> Hsla; 6] = [
+    |theme| theme.players().color_for_participant(1).selection,
+    |theme| theme.players().color_for_participant(2).selection,
+    |theme| theme.players().color_for_participant(3).selection,
+    |theme| theme.players().color_for_participant(4).selection,
+    |theme| theme.players().color_for_participant(5).selection,
+    |theme| theme.players().color_for_participant(6).selection,
+];
+
+/// A word highlighted everywhere in the editor by [`ToggleHighlightWordUnderCursor`].
+struct PinnedWord {
+    /// Keys the word's highlights, which stay separate when several words share a color.
+    id: usize,
+    text: String,
+    color_index: usize,
+}
 
 pub enum ConflictsOuter {}
 pub enum ConflictsOurs {}
@@ -348,6 +436,7 @@ pub fn init_settings(cx: &mut App) {
 
 pub fn init(cx: &mut App) {
     init_settings(cx);
+    keyboard_macros::init(cx);
 
     cx.set_global(GlobalBlameRenderer(Arc::new(())));
 
@@ -362,6 +451,7 @@ pub fn init(cx: &mut App) {
             workspace.register_action(Editor::new_file_horizontal);
             workspace.register_action(Editor::cancel_language_server_work);
             workspace.register_action(Editor::toggle_focus);
+            workspace.register_action(Editor::view_bookmarks);
         },
     )
     .detach();
@@ -442,6 +532,9 @@ pub fn set_diagnostic_renderer(renderer: impl DiagnosticRenderer + 'static, cx:
 }
 
 pub struct SearchWithinRange;
+/// The parts of the buffer outside the [`SearchWithinRange`] ranges, shaded while searching
+/// within a selection.
+pub struct SearchOutsideRange;
 
 trait InvalidationRegion {
     fn ranges(&self) -> &[Range<Anchor>];
@@ -561,6 +654,9 @@ pub struct EditorStyle {
     pub edit_prediction_styles: EditPredictionStyles,
     pub unnecessary_code_fade: f32,
     pub show_underlines: bool,
+    /// Whether invisible characters that have a placeholder glyph are shown as it, instead of
+    /// as blank space.
+    pub show_invisible_placeholders: bool,
 }
 
 impl Default for EditorStyle {
@@ -583,6 +679,7 @@ impl Default for EditorStyle {
             },
             unnecessary_code_fade: Default::default(),
             show_underlines: true,
+            show_invisible_placeholders: false,
         }
     }
 }
@@ -987,8 +1084,19 @@ enum SelectionDragState {
         drop_cursor: Selection<Anchor>,
         hide_drop_cursor: bool,
     },
+    /// State when a selection dragged out of another editor is hovering over this editor.
+    DraggingFromOtherEditor {
+        source: WeakEntity<Editor>,
+        selection: Selection<Anchor>,
+        drop_cursor: Selection<Anchor>,
+    },
 }
 
+/// The editor whose selection is currently being dragged, so that other
+/// visible editors can show a drop cursor and accept the selection.
+struct DraggedSelectionSource(WeakEntity<Editor>);
+impl Global for DraggedSelectionSource {}
+
 enum ColumnarSelectionState {
     FromMouse {
         selection_tail: Anchor,
@@ -1065,6 +1173,28 @@ pub struct Editor {
     disable_expand_excerpt_buttons: bool,
     show_line_numbers: Option<bool>,
     use_relative_line_numbers: Option<bool>,
+    typewriter_scrolling: Option<bool>,
+    render_ansi_escapes: Option<bool>,
+    ansi_escapes_applied: bool,
+    ansi_escapes_task: Task<()>,
+    unicode_highlights_applied: bool,
+    custom_highlights_applied: bool,
+    revealed_folds: Vec<fold_reveal::RevealedFold>,
+    unicode_highlights_task: Task<()>,
+    log_file: bool,
+    hidden_log_levels: BTreeSet<LogLevel>,
+    following_log: bool,
+    log_file_task: Task<()>,
+    unconfirmed_edits_task: Option<Task<()>>,
+    drag_autoscroll: Option<drag_autoscroll::DragAutoscroll>,
+    imports_auto_folded: bool,
+    show_syntax_errors: Option<bool>,
+    selection_sets: HashMap<String, Vec<Range<Anchor>>>,
+    csv_delimiter: Option<char>,
+    csv_alignment: Option<bool>,
+    csv_padding_inlays: Vec<InlayId>,
+    csv_columns_applied: bool,
+    csv_columns_task: Task<()>,
     show_git_diff_gutter: Option<bool>,
     show_code_actions: Option<bool>,
     show_runnables: Option<bool>,
@@ -1075,6 +1205,13 @@ pub struct Editor {
     highlighted_rows: HashMap<TypeId, Vec<RowHighlight>>,
     background_highlights: HashMap<HighlightKey, BackgroundHighlight>,
     gutter_highlights: HashMap<TypeId, GutterHighlight>,
+    gutter_badges: HashMap<TypeId, Vec<(Anchor, SharedString)>>,
+    gutter_decorations: HashMap<TypeId, Vec<GutterDecoration>>,
+    virtual_texts: HashMap<VirtualTextId, VirtualTextDisplay>,
+    next_virtual_text_id: usize,
+    test_runs: Vec<TestRun>,
+    next_test_run_id: usize,
+    last_kill_ring_edit: Option<LastKillRingEdit>,
     scrollbar_marker_state: ScrollbarMarkerState,
     active_indent_guides_state: ActiveIndentGuidesState,
     nav_history: Option<ItemNavHistory>,
@@ -1092,10 +1229,16 @@ pub struct Editor {
     code_actions_task: Option<Task<Result<()>>>,
     quick_selection_highlight_task: Option<(Range<Anchor>, Task<()>)>,
     debounced_selection_highlight_task: Option<(Range<Anchor>, Task<()>)>,
+    pinned_words: Vec<PinnedWord>,
+    next_pinned_word_id: usize,
+    pinned_word_highlights_task: Option<Task<()>>,
+    focus_mode: bool,
+    font_size_adjustment: Pixels,
     document_highlights_task: Option<Task<()>>,
     linked_editing_range_task: Option<Task<Option<()>>>,
     linked_edit_ranges: linked_editing_ranges::LinkedEditingRanges,
     pending_rename: Option<RenameState>,
+    peek_definition: Option<peek_definition::PeekDefinitionState>,
     searchable: bool,
     cursor_shape: CursorShape,
     current_line_highlight: Option<CurrentLineHighlight>,
@@ -1143,6 +1286,7 @@ pub struct Editor {
     show_git_blame_inline: bool,
     show_git_blame_inline_delay_task: Option<Task<()>>,
     git_blame_inline_enabled: bool,
+    inline_diff_enabled: bool,
     render_diff_hunk_controls: RenderDiffHunkControlsFn,
     serialize_dirty_buffers: bool,
     show_selection_menu: Option<bool>,
@@ -1161,10 +1305,13 @@ pub struct Editor {
     >,
     last_bounds: Option<Bounds<Pixels>>,
     last_position_map: Option<Rc<PositionMap>>,
+    /// The in-flight animations of local cursors, keyed by their index, when the smooth caret is enabled.
+    cursor_animations: HashMap<usize, CursorAnimation>,
     expect_bounds_change: Option<Bounds<Pixels>>,
     tasks: BTreeMap<(BufferId, BufferRow), RunnableTasks>,
     tasks_update_task: Option<Task<()>>,
     breakpoint_store: Option<Entity<BreakpointStore>>,
+    bookmark_store: Option<Entity<BookmarkStore>>,
     gutter_breakpoint_indicator: (Option<PhantomBreakpointIndicator>, Option<Task<()>>),
     hovered_diff_hunk_row: Option<DisplayRow>,
     pull_diagnostics_task: Task<()>,
@@ -1182,7 +1329,14 @@ pub struct Editor {
     toggle_fold_multiple_buffers: Task<()>,
     _scroll_cursor_center_top_bottom_task: Task<()>,
     serialize_selections: Task<()>,
+    announce_cursor: Task<()>,
+    matching_brackets_task: Task<()>,
+    /// The buffers that were told which of their ranges this editor shows.
+    visible_buffers: HashSet<BufferId>,
+    profiler: Option<profiler::EditorProfiler>,
     serialize_folds: Task<()>,
+    serialize_file_state: Task<()>,
+    restore_file_state_from_db: bool,
     mouse_cursor_hidden: bool,
     minimap: Option<Entity<Self>>,
     hide_mouse_mode: HideMouseMode,
@@ -1222,6 +1376,7 @@ pub struct EditorSnapshot {
     show_code_actions: Option<bool>,
     show_runnables: Option<bool>,
     show_breakpoints: Option<bool>,
+    has_bookmarks: bool,
     git_blame_gutter_max_author_length: Option<usize>,
     pub display_snapshot: DisplaySnapshot,
     pub placeholder_display_snapshot: Option<DisplaySnapshot>,
@@ -1536,6 +1691,12 @@ pub struct RenameState {
 
 struct InvalidationStack<T>(Vec<T>);
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PartialEditPredictionGranularity {
+    Word,
+    Line,
+}
+
 struct RegisteredEditPredictionProvider {
     provider: Arc<dyn EditPredictionProviderHandle>,
     _subscription: Subscription,
@@ -1603,7 +1764,7 @@ enum SelectSyntaxNodeScrollBehavior {
     CursorBottom,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct NavigationData {
     cursor_anchor: Anchor,
     cursor_position: Point,
@@ -1882,6 +2043,9 @@ impl Editor {
                     project::Event::RefreshInlayHints => {
                         editor.refresh_inlay_hints(InlayHintRefreshReason::RefreshRequested, cx);
                     }
+                    project::Event::UnconfirmedEditsChanged(buffer_id) => {
+                        editor.handle_unconfirmed_edits_changed(*buffer_id, cx);
+                    }
                     project::Event::LanguageServerAdded(..)
                     | project::Event::LanguageServerRemoved(..) => {
                         if editor.tasks_update_task.is_none() {
@@ -2045,6 +2209,9 @@ impl Editor {
                 .detach();
             cx.observe_pending_input(window, Self::observe_pending_input)
                 .detach();
+            let editor_id = cx.entity_id();
+            cx.on_release(move |editor, cx| editor.clear_visible_buffer_ranges(editor_id, cx))
+                .detach();
         }
 
         let show_indent_guides =
@@ -2058,6 +2225,10 @@ impl Editor {
             (EditorMode::Full { .. }, Some(project)) => Some(project.read(cx).breakpoint_store()),
             _ => None,
         };
+        let bookmark_store = match (&mode, project.as_ref()) {
+            (EditorMode::Full { .. }, Some(project)) => Some(project.read(cx).bookmark_store()),
+            _ => None,
+        };
 
         let mut code_action_providers = Vec::new();
         let mut load_uncommitted_diff = None;
@@ -2118,6 +2289,28 @@ impl Editor {
             show_gutter: full_mode,
             show_line_numbers: (!full_mode).then_some(false),
             use_relative_line_numbers: None,
+            typewriter_scrolling: None,
+            render_ansi_escapes: None,
+            ansi_escapes_applied: false,
+            ansi_escapes_task: Task::ready(()),
+            unicode_highlights_applied: false,
+            custom_highlights_applied: false,
+            revealed_folds: Vec::new(),
+            unicode_highlights_task: Task::ready(()),
+            log_file: false,
+            hidden_log_levels: BTreeSet::new(),
+            following_log: false,
+            log_file_task: Task::ready(()),
+            unconfirmed_edits_task: None,
+            drag_autoscroll: None,
+            imports_auto_folded: false,
+            show_syntax_errors: None,
+            selection_sets: HashMap::default(),
+            csv_delimiter: None,
+            csv_alignment: None,
+            csv_padding_inlays: Vec::new(),
+            csv_columns_applied: false,
+            csv_columns_task: Task::ready(()),
             disable_expand_excerpt_buttons: !full_mode,
             show_git_diff_gutter: None,
             show_code_actions: None,
@@ -2129,6 +2322,13 @@ impl Editor {
             highlighted_rows: HashMap::default(),
             background_highlights: HashMap::default(),
             gutter_highlights: HashMap::default(),
+            gutter_badges: HashMap::default(),
+            gutter_decorations: HashMap::default(),
+            virtual_texts: HashMap::default(),
+            next_virtual_text_id: 0,
+            test_runs: Vec::new(),
+            next_test_run_id: 0,
+            last_kill_ring_edit: None,
             scrollbar_marker_state: ScrollbarMarkerState::default(),
             active_indent_guides_state: ActiveIndentGuidesState::default(),
             nav_history: None,
@@ -2148,9 +2348,15 @@ impl Editor {
             code_actions_task: None,
             quick_selection_highlight_task: None,
             debounced_selection_highlight_task: None,
+            pinned_words: Vec::new(),
+            next_pinned_word_id: 0,
+            pinned_word_highlights_task: None,
+            focus_mode: false,
+            font_size_adjustment: px(0.),
             document_highlights_task: None,
             linked_editing_range_task: None,
             pending_rename: None,
+            peek_definition: None,
             searchable: !is_minimap,
             cursor_shape: EditorSettings::get_global(cx)
                 .cursor_shape
@@ -2186,6 +2392,7 @@ impl Editor {
             pixel_position_of_newest_cursor: None,
             last_bounds: None,
             last_position_map: None,
+            cursor_animations: HashMap::default(),
             expect_bounds_change: None,
             gutter_dimensions: GutterDimensions::default(),
             style: None,
@@ -2206,6 +2413,7 @@ impl Editor {
             show_git_blame_inline_delay_task: None,
             git_blame_inline_enabled: full_mode
                 && ProjectSettings::get_global(cx).git.inline_blame.enabled,
+            inline_diff_enabled: false,
             render_diff_hunk_controls: Arc::new(render_diff_hunk_controls),
             serialize_dirty_buffers: !is_minimap
                 && ProjectSettings::get_global(cx)
@@ -2216,6 +2424,7 @@ impl Editor {
             tasks: BTreeMap::default(),
 
             breakpoint_store,
+            bookmark_store,
             gutter_breakpoint_indicator: (None, None),
             hovered_diff_hunk_row: None,
             _subscriptions: (!is_minimap)
@@ -2260,7 +2469,13 @@ impl Editor {
             selection_mark_mode: false,
             toggle_fold_multiple_buffers: Task::ready(()),
             serialize_selections: Task::ready(()),
+            announce_cursor: Task::ready(()),
+            matching_brackets_task: Task::ready(()),
+            visible_buffers: HashSet::default(),
+            profiler: None,
             serialize_folds: Task::ready(()),
+            serialize_file_state: Task::ready(()),
+            restore_file_state_from_db: false,
             text_style_refinement: None,
             load_diff_task: load_uncommitted_diff,
             temporary_diff_override: false,
@@ -2287,6 +2502,13 @@ impl Editor {
                     cx.notify();
                 }));
         }
+        if let Some(bookmarks) = editor.bookmark_store.as_ref() {
+            editor
+                ._subscriptions
+                .push(cx.observe(bookmarks, |_, _, cx| {
+                    cx.notify();
+                }));
+        }
         editor.tasks_update_task = Some(editor.refresh_runnables(window, cx));
         editor._subscriptions.extend(project_subscriptions);
 
@@ -2397,6 +2619,15 @@ impl Editor {
                 editor.create_minimap(EditorSettings::get_global(cx).minimap, window, cx);
             editor.colors = Some(LspColorData::new(cx));
             editor.update_lsp_data(false, None, window, cx);
+            editor.refresh_wrap_style(cx);
+            editor.refresh_ansi_escapes(window, cx);
+            editor.refresh_unicode_highlights(window, cx);
+            editor.refresh_custom_highlights(cx);
+            editor.refresh_log_file(window, cx);
+            editor.refresh_csv_columns(window, cx);
+            editor.refresh_mixed_line_endings(cx);
+            editor.auto_fold_imports(window, cx);
+            editor.refresh_syntax_errors(cx);
         }
 
         if editor.mode.is_full() {
@@ -2479,6 +2710,9 @@ impl Editor {
         if self.pending_rename.is_some() {
             key_context.add("renaming");
         }
+        if self.is_peeking_definition() {
+            key_context.add("peeking_definition");
+        }
 
         match self.context_menu.borrow().as_ref() {
             Some(CodeContextMenu::Completions(menu)) => {
@@ -2743,6 +2977,7 @@ impl Editor {
             show_code_actions: self.show_code_actions,
             show_runnables: self.show_runnables,
             show_breakpoints: self.show_breakpoints,
+            has_bookmarks: self.has_bookmarks(cx),
             git_blame_gutter_max_author_length,
             display_snapshot: self.display_map.update(cx, |map, cx| map.snapshot(cx)),
             placeholder_display_snapshot: self
@@ -2960,13 +3195,65 @@ impl Editor {
     }
 
     pub fn read_only(&self, cx: &App) -> bool {
-        self.read_only || self.buffer.read(cx).read_only()
+        self.read_only || self.buffer.read(cx).read_only() || self.edits_locked(cx)
     }
 
     pub fn set_read_only(&mut self, read_only: bool) {
         self.read_only = read_only;
     }
 
+    /// Whether the edited buffer was locked with [`ToggleReadOnly`], which unlike `read_only` can
+    /// be undone by the user. The lock is on the buffer, so it applies to every editor showing it.
+    pub fn edits_locked(&self, cx: &App) -> bool {
+        self.buffer
+            .read(cx)
+            .as_singleton()
+            .is_some_and(|buffer| buffer.read(cx).edits_locked())
+    }
+
+    pub fn toggle_read_only(&mut self, _: &ToggleReadOnly, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+        let edits_locked = self.edits_locked(cx);
+        // Editors that are read-only for other reasons can't be unlocked.
+        if !edits_locked && self.read_only(cx) {
+            return;
+        }
+        buffer.update(cx, |buffer, cx| buffer.set_edits_locked(!edits_locked, cx));
+    }
+
+    /// Shows a hint when an edit is ignored because of [`ToggleReadOnly`], so that typing into a
+    /// locked editor doesn't silently do nothing.
+    fn notify_edits_locked(&self, cx: &mut Context<Self>) {
+        if let Some(buffer) = self.buffer.read(cx).as_singleton()
+            && buffer.read(cx).edits_locked()
+        {
+            self.notify_buffer_edits_locked(buffer, cx);
+        }
+    }
+
+    /// Shows the hint for edits that the multibuffer dropped because the given buffer is locked,
+    /// such as those from pasting, replacing or other actions that edit the buffer directly.
+    fn notify_buffer_edits_locked(&self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<ToggleReadOnly>(),
+                    "This editor is read-only",
+                )
+                .on_click("Unlock", move |_, cx| {
+                    buffer.update(cx, |buffer, cx| buffer.set_edits_locked(false, cx));
+                })
+                .autohide(),
+                cx,
+            )
+        });
+    }
+
     pub fn set_use_autoclose(&mut self, autoclose: bool) {
         self.use_autoclose = autoclose;
     }
@@ -3112,6 +3399,8 @@ impl Editor {
         }
 
         if local {
+            self.update_log_following(buffer);
+            self.schedule_cursor_announcement(window, cx);
             if let Some(buffer_id) = new_cursor_position.buffer_id
                 && !self.registered_buffers.contains_key(&buffer_id)
                 && let Some(project) = self.project.as_ref()
@@ -3175,7 +3464,11 @@ impl Editor {
             self.refresh_code_actions(window, cx);
             self.refresh_document_highlights(cx);
             self.refresh_selected_text_highlights(false, window, cx);
-            refresh_matching_bracket_highlights(self, cx);
+            self.refresh_focus_mode(cx);
+            self.refold_revealed_regions(window, cx);
+            self.profile(ProfiledSubsystem::MatchingBrackets, |editor| {
+                refresh_matching_bracket_highlights(editor, cx)
+            });
             self.update_visible_edit_prediction(window, cx);
             self.edit_prediction_requires_modifier_in_indent_conflict = true;
             linked_editing_ranges::refresh_linked_ranges(self, window, cx);
@@ -3417,8 +3710,10 @@ impl Editor {
 
             self.selections_did_change(true, old_cursor_position, state.effects, window, cx);
 
-            if self.should_open_signature_help_automatically(old_cursor_position, cx) {
-                self.show_signature_help(&ShowSignatureHelp, window, cx);
+            if let Some(trigger) =
+                self.should_open_signature_help_automatically(old_cursor_position, cx)
+            {
+                self.request_signature_help(trigger, window, cx);
             }
         }
     }
@@ -3430,6 +3725,7 @@ impl Editor {
         T: Into<Arc<str>>,
     {
         if self.read_only(cx) {
+            self.notify_edits_locked(cx);
             return;
         }
 
@@ -3444,6 +3740,7 @@ impl Editor {
         T: Into<Arc<str>>,
     {
         if self.read_only(cx) {
+            self.notify_edits_locked(cx);
             return;
         }
 
@@ -3463,6 +3760,7 @@ impl Editor {
         T: Into<Arc<str>>,
     {
         if self.read_only(cx) {
+            self.notify_edits_locked(cx);
             return;
         }
 
@@ -3834,6 +4132,7 @@ impl Editor {
 
     fn end_selection(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.columnar_selection_state.take();
+        self.drag_autoscroll = None;
         if let Some(pending_mode) = self.selections.pending_mode() {
             let selections = self.selections.all::<usize>(cx);
             self.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
@@ -3954,6 +4253,9 @@ impl Editor {
         if self.dismiss_menus_and_popups(true, window, cx) {
             return;
         }
+        if self.hide_peek_definition(cx) {
+            return;
+        }
 
         if self.mode.is_full()
             && self.change_selections(Default::default(), window, cx, |s| s.try_cancel())
@@ -3974,7 +4276,12 @@ impl Editor {
             return true;
         }
 
-        if hide_hover(self, cx) {
+        let hid_hover = if is_user_requested {
+            hover_popover::dismiss_hover(self, window, cx)
+        } else {
+            hide_hover(self, cx)
+        };
+        if hid_hover {
             return true;
         }
 
@@ -4067,6 +4374,7 @@ impl Editor {
         let text: Arc<str> = text.into();
 
         if self.read_only(cx) {
+            self.notify_edits_locked(cx);
             return;
         }
 
@@ -4421,7 +4729,7 @@ impl Editor {
 
             let trigger_in_words =
                 this.show_edit_predictions_in_menu() || !had_active_edit_prediction;
-            if this.hard_wrap.is_some() {
+            if this.hard_wrap.is_some() || this.auto_wrap_on_type(cx) {
                 let latest: Range<Point> = this.selections.newest(cx).range();
                 if latest.is_empty()
                     && this
@@ -4444,6 +4752,9 @@ impl Editor {
             linked_editing_ranges::refresh_linked_ranges(this, window, cx);
             this.refresh_edit_prediction(true, false, window, cx);
             jsx_tag_auto_close::handle_from(this, initial_buffer_versions, window, cx);
+            if &*text == "|" && this.markdown_table_editing_enabled(cx) {
+                this.realign_markdown_table(window, cx);
+            }
         });
     }
 
@@ -5196,10 +5507,59 @@ impl Editor {
         );
     }
 
+    pub fn toggle_type_inlay_hints(
+        &mut self,
+        _: &ToggleTypeInlayHints,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_inlay_hint_kind(Some(InlayHintKind::Type), cx);
+    }
+
+    pub fn toggle_parameter_inlay_hints(
+        &mut self,
+        _: &ToggleParameterInlayHints,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_inlay_hint_kind(Some(InlayHintKind::Parameter), cx);
+    }
+
+    pub fn toggle_other_inlay_hints(
+        &mut self,
+        _: &ToggleOtherInlayHints,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_inlay_hint_kind(None, cx);
+    }
+
+    fn toggle_inlay_hint_kind(&mut self, kind: Option<InlayHintKind>, cx: &mut Context<Self>) {
+        if self.semantics_provider.is_none() || !self.mode.is_full() {
+            return;
+        }
+        if let Some(InlaySplice {
+            to_remove,
+            to_insert,
+        }) = self.inlay_hint_cache.toggle_hint_kind(
+            kind,
+            &self.buffer,
+            self.visible_inlay_hints(cx).cloned().collect::<Vec<_>>(),
+            cx,
+        ) {
+            self.splice_inlays(&to_remove, to_insert, cx);
+        }
+    }
+
     pub fn inlay_hints_enabled(&self) -> bool {
         self.inlay_hint_cache.enabled
     }
 
+    /// Whether inlay hints of the given kind are shown, `None` standing for hints without a kind, such as chaining hints.
+    pub fn inlay_hint_kind_enabled(&self, kind: Option<InlayHintKind>) -> bool {
+        self.inlay_hint_cache.hint_kind_allowed(kind)
+    }
+
     pub fn inline_values_enabled(&self) -> bool {
         self.inline_value_cache.enabled
     }
@@ -5224,6 +5584,16 @@ impl Editor {
     }
 
     fn refresh_inlay_hints(&mut self, reason: InlayHintRefreshReason, cx: &mut Context<Self>) {
+        self.profile(ProfiledSubsystem::InlayHints, |editor| {
+            editor.refresh_inlay_hints_unprofiled(reason, cx)
+        });
+    }
+
+    fn refresh_inlay_hints_unprofiled(
+        &mut self,
+        reason: InlayHintRefreshReason,
+        cx: &mut Context<Self>,
+    ) {
         if self.semantics_provider.is_none() || !self.mode.is_full() {
             return;
         }
@@ -5238,47 +5608,40 @@ impl Editor {
         );
         let (invalidate_cache, required_languages) = match reason {
             InlayHintRefreshReason::ModifiersChanged(enabled) => {
-                match self.inlay_hint_cache.modifiers_override(enabled) {
-                    Some(enabled) => {
-                        if enabled {
-                            (InvalidationStrategy::RefreshRequested, None)
-                        } else {
-                            self.clear_inlay_hints(cx);
-                            return;
-                        }
-                    }
-                    None => return,
+                let state_change = self.inlay_hint_cache.modifiers_override(
+                    enabled,
+                    &self.buffer,
+                    self.visible_inlay_hints(cx).cloned().collect::<Vec<_>>(),
+                    cx,
+                );
+                if !self.apply_inlay_hints_state_change(state_change, cx) {
+                    return;
                 }
+                (InvalidationStrategy::RefreshRequested, None)
             }
             InlayHintRefreshReason::Toggle(enabled) => {
-                if self.inlay_hint_cache.toggle(enabled) {
-                    if enabled {
-                        (InvalidationStrategy::RefreshRequested, None)
-                    } else {
-                        self.clear_inlay_hints(cx);
-                        return;
-                    }
-                } else {
+                let state_change = self.inlay_hint_cache.toggle(
+                    enabled,
+                    &self.buffer,
+                    self.visible_inlay_hints(cx).cloned().collect::<Vec<_>>(),
+                    cx,
+                );
+                if !self.apply_inlay_hints_state_change(state_change, cx) {
                     return;
                 }
+                (InvalidationStrategy::RefreshRequested, None)
             }
             InlayHintRefreshReason::SettingsChange(new_settings) => {
-                match self.inlay_hint_cache.update_settings(
+                let state_change = self.inlay_hint_cache.update_settings(
                     &self.buffer,
                     new_settings,
                     self.visible_inlay_hints(cx).cloned().collect::<Vec<_>>(),
                     cx,
-                ) {
-                    ControlFlow::Break(Some(InlaySplice {
-                        to_remove,
-                        to_insert,
-                    })) => {
-                        self.splice_inlays(&to_remove, to_insert, cx);
-                        return;
-                    }
-                    ControlFlow::Break(None) => return,
-                    ControlFlow::Continue(()) => (InvalidationStrategy::RefreshRequested, None),
+                );
+                if !self.apply_inlay_hints_state_change(state_change, cx) {
+                    return;
                 }
+                (InvalidationStrategy::RefreshRequested, None)
             }
             InlayHintRefreshReason::ExcerptsRemoved(excerpts_removed) => {
                 if let Some(InlaySplice {
@@ -5316,6 +5679,25 @@ impl Editor {
         }
     }
 
+    /// Splices the inlays changed by a cache state change, returning whether new hints have to be queried.
+    fn apply_inlay_hints_state_change(
+        &self,
+        state_change: ControlFlow<Option<InlaySplice>>,
+        cx: &mut Context<Editor>,
+    ) -> bool {
+        match state_change {
+            ControlFlow::Break(Some(InlaySplice {
+                to_remove,
+                to_insert,
+            })) => {
+                self.splice_inlays(&to_remove, to_insert, cx);
+                false
+            }
+            ControlFlow::Break(None) => false,
+            ControlFlow::Continue(()) => true,
+        }
+    }
+
     pub fn clear_inlay_hints(&self, cx: &mut Context<Editor>) {
         self.splice_inlays(
             &self
@@ -5337,6 +5719,23 @@ impl Editor {
             .filter(move |inlay| matches!(inlay.id, InlayId::Hint(_)))
     }
 
+    /// The range of the multibuffer that is scrolled into view, approximated by the lines below
+    /// the scroll anchor.
+    pub fn multi_buffer_visible_range(&self, cx: &App) -> Range<Point> {
+        let multi_buffer_snapshot = self.buffer().read(cx).snapshot(cx);
+        let multi_buffer_visible_start = self
+            .scroll_manager
+            .anchor()
+            .anchor
+            .to_point(&multi_buffer_snapshot);
+        let multi_buffer_visible_end = multi_buffer_snapshot.clip_point(
+            multi_buffer_visible_start
+                + Point::new(self.visible_line_count().unwrap_or(0.).ceil() as u32, 0),
+            Bias::Left,
+        );
+        multi_buffer_visible_start..multi_buffer_visible_end
+    }
+
     pub fn visible_excerpts(
         &self,
         restrict_to_languages: Option<&HashSet<Arc<Language>>>,
@@ -5348,17 +5747,7 @@ impl Editor {
         let project = project.read(cx);
         let multi_buffer = self.buffer().read(cx);
         let multi_buffer_snapshot = multi_buffer.snapshot(cx);
-        let multi_buffer_visible_start = self
-            .scroll_manager
-            .anchor()
-            .anchor
-            .to_point(&multi_buffer_snapshot);
-        let multi_buffer_visible_end = multi_buffer_snapshot.clip_point(
-            multi_buffer_visible_start
-                + Point::new(self.visible_line_count().unwrap_or(0.).ceil() as u32, 0),
-            Bias::Left,
-        );
-        let multi_buffer_visible_range = multi_buffer_visible_start..multi_buffer_visible_end;
+        let multi_buffer_visible_range = self.multi_buffer_visible_range(cx);
         multi_buffer_snapshot
             .range_to_buffer_ranges(multi_buffer_visible_range)
             .into_iter()
@@ -5391,6 +5780,62 @@ impl Editor {
             .collect()
     }
 
+    /// Tells the buffers in the viewport which of their ranges are visible, so that their syntax is
+    /// reparsed before the rest of the buffer after an edit.
+    pub(crate) fn report_visible_buffer_ranges(&mut self, cx: &mut Context<Editor>) {
+        if !self.mode.is_full() {
+            return;
+        }
+        let editor_id = cx.entity_id();
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        let visible_start = self.scroll_manager.anchor().anchor.to_point(&snapshot);
+        let visible_end = snapshot.clip_point(
+            visible_start + Point::new(self.visible_line_count().unwrap_or(0.).ceil() as u32, 0),
+            Bias::Left,
+        );
+
+        let mut visible_ranges = HashMap::<BufferId, Range<usize>>::default();
+        for (buffer, range, _) in snapshot.range_to_buffer_ranges(visible_start..visible_end) {
+            visible_ranges
+                .entry(buffer.remote_id())
+                .and_modify(|visible_range| {
+                    visible_range.start = visible_range.start.min(range.start);
+                    visible_range.end = visible_range.end.max(range.end);
+                })
+                .or_insert(range);
+        }
+
+        let buffers = mem::take(&mut self.visible_buffers)
+            .into_iter()
+            .filter(|buffer_id| !visible_ranges.contains_key(buffer_id))
+            .map(|buffer_id| (buffer_id, None))
+            .chain(
+                visible_ranges
+                    .iter()
+                    .map(|(buffer_id, range)| (*buffer_id, Some(range.clone()))),
+            )
+            .filter_map(|(buffer_id, range)| Some((multi_buffer.buffer(buffer_id)?, range)))
+            .collect::<Vec<_>>();
+        self.visible_buffers = visible_ranges.into_keys().collect();
+
+        for (buffer, visible_range) in buffers {
+            buffer.update(cx, |buffer, _| {
+                let visible_range = visible_range
+                    .map(|range| buffer.anchor_before(range.start)..buffer.anchor_after(range.end));
+                buffer.set_visible_range(editor_id, visible_range)
+            });
+        }
+    }
+
+    fn clear_visible_buffer_ranges(&mut self, editor_id: EntityId, cx: &mut App) {
+        for buffer_id in self.visible_buffers.drain() {
+            if let Some(buffer) = self.buffer.read(cx).buffer(buffer_id) {
+                buffer.update(cx, |buffer, _| buffer.set_visible_range(editor_id, None));
+            }
+        }
+    }
+
     pub fn text_layout_details(&self, window: &mut Window) -> TextLayoutDetails {
         TextLayoutDetails {
             text_system: window.text_system().clone(),
@@ -5723,6 +6168,7 @@ impl Editor {
         };
 
         let snippet_sort_order = EditorSettings::get_global(cx).snippet_sort_order;
+        let ranking = CompletionRanking::from(&completion_settings);
 
         let id = post_inc(&mut self.next_completion_id);
         let task = cx.spawn_in(window, async move |editor, cx| {
@@ -5985,6 +6431,13 @@ impl Editor {
         cx.stop_propagation();
 
         let buffer_handle = completions_menu.buffer.clone();
+        let selected_text = {
+            let snapshot = self.buffer.read(cx).snapshot(cx);
+            let selection = self.selections.newest::<usize>(cx);
+            snapshot
+                .text_for_range(selection.range())
+                .collect::<String>()
+        };
 
         let CompletionEdit {
             new_text,
@@ -5995,6 +6448,7 @@ impl Editor {
             intent,
             &buffer_handle,
             &completions_menu.initial_position.text_anchor,
+            &selected_text,
             cx,
         );
 
@@ -6130,8 +6584,18 @@ impl Editor {
 
         let provider = self.completion_provider.as_ref()?;
         drop(completion);
+        let auto_import_notification = {
+            let buffer = buffer_handle.read(cx);
+            language_settings(
+                buffer.language().map(|language| language.name()),
+                buffer.file(),
+                cx,
+            )
+            .completions
+            .auto_import_notification
+        };
         let apply_edits = provider.apply_additional_edits_for_completion(
-            buffer_handle,
+            buffer_handle.clone(),
             completions_menu.completions.clone(),
             candidate_id,
             true,
@@ -6145,12 +6609,59 @@ impl Editor {
             self.show_signature_help(&ShowSignatureHelp, window, cx);
         }
 
-        Some(cx.foreground_executor().spawn(async move {
-            apply_edits.await?;
+        Some(cx.spawn(async move |editor, cx| {
+            let transaction = apply_edits.await?;
+            if auto_import_notification && let Some(transaction) = transaction {
+                editor.update(cx, |editor, cx| {
+                    editor.show_auto_import_notification(buffer_handle, transaction, cx)
+                })?;
+            }
             Ok(())
         }))
     }
 
+    /// Tells the user about the import that accepting a completion added, offering to undo it.
+    fn show_auto_import_notification(
+        &self,
+        buffer: Entity<Buffer>,
+        transaction: language::Transaction,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        let added_line = {
+            let buffer = buffer.read(cx);
+            buffer
+                .edited_ranges_for_transaction::<usize>(&transaction)
+                .find_map(|range| {
+                    let text = buffer.text_for_range(range).collect::<String>();
+                    text.lines()
+                        .map(str::trim)
+                        .find(|line| !line.is_empty())
+                        .map(ToOwned::to_owned)
+                })
+        };
+        let message = match added_line {
+            Some(line) => format!("Added `{line}`"),
+            None => "Added an import".to_string(),
+        };
+
+        struct AutoImportNotification;
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(NotificationId::unique::<AutoImportNotification>(), message)
+                    .on_click("Undo", move |_, cx| {
+                        buffer.update(cx, |buffer, cx| {
+                            buffer.undo_transaction(transaction.id, cx);
+                        });
+                    })
+                    .autohide(),
+                cx,
+            )
+        });
+    }
+
     pub fn toggle_code_actions(
         &mut self,
         action: &ToggleCodeActions,
@@ -6402,17 +6913,9 @@ impl Editor {
 
         match action {
             CodeActionsItem::Task(task_source_kind, resolved_task) => {
-                workspace.update(cx, |workspace, cx| {
-                    workspace.schedule_resolved_task(
-                        task_source_kind,
-                        resolved_task,
-                        false,
-                        window,
-                        cx,
-                    );
-
-                    Some(Task::ready(Ok(())))
-                })
+                let position = actions_menu.actions.tasks().map(|tasks| tasks.position);
+                self.schedule_runnable_task(task_source_kind, resolved_task, position, window, cx);
+                Some(Task::ready(Ok(())))
             }
             CodeActionsItem::CodeAction {
                 excerpt_id,
@@ -6537,6 +7040,84 @@ impl Editor {
         Ok(())
     }
 
+    /// Reverts the edits of `transaction` and opens them in a [`ProposedChangesEditor`] instead,
+    /// so that they can be reviewed, partially restored and then applied.
+    pub async fn open_project_transaction_preview(
+        workspace: WeakEntity<Workspace>,
+        transaction: ProjectTransaction,
+        title: String,
+        cx: &mut AsyncWindowContext,
+    ) -> Result<()> {
+        let mut entries = transaction.0.into_iter().collect::<Vec<_>>();
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            entries.sort_unstable_by_key(|(buffer, _)| {
+                buffer.read(cx).file().map(|f| f.path().clone())
+            });
+
+            let mut locations = Vec::new();
+            let mut edits_by_buffer = Vec::new();
+            for (buffer, transaction) in entries {
+                let edits = buffer.update(cx, |buffer, cx| {
+                    let snapshot = buffer.snapshot();
+                    let edits = snapshot
+                        .edits_since::<usize>(&transaction.start)
+                        .map(|edit| {
+                            let new_text = snapshot.text_for_range(edit.new).collect::<String>();
+                            (edit.old, new_text)
+                        })
+                        .collect::<Vec<_>>();
+                    buffer.undo_transaction(transaction.id, cx);
+                    buffer.forget_transaction(transaction.id);
+                    edits
+                });
+                if edits.is_empty() {
+                    continue;
+                }
+
+                let snapshot = buffer.read(cx).snapshot();
+                let mut ranges = Vec::<Range<Point>>::new();
+                for (range, _) in &edits {
+                    let mut line_range =
+                        snapshot.offset_to_point(range.start)..snapshot.offset_to_point(range.end);
+                    line_range.start.column = 0;
+                    line_range.end.column = snapshot.line_len(line_range.end.row);
+                    match ranges.last_mut() {
+                        Some(last) if last.end.row + 1 >= line_range.start.row => {
+                            last.end = last.end.max(line_range.end);
+                        }
+                        _ => ranges.push(line_range),
+                    }
+                }
+                locations.push(ProposedChangeLocation {
+                    buffer: buffer.clone(),
+                    ranges,
+                });
+                edits_by_buffer.push((buffer, edits));
+            }
+            if locations.is_empty() {
+                return;
+            }
+
+            let project = workspace.project().clone();
+            let preview = cx
+                .new(|cx| ProposedChangesEditor::new(title, locations, Some(project), window, cx));
+            preview.update(cx, |preview, cx| {
+                for (buffer, edits) in edits_by_buffer {
+                    if let Some(branch) = preview.branch_buffer_for_base(&buffer) {
+                        branch.update(cx, |branch, cx| branch.edit(edits, None, cx));
+                    }
+                }
+            });
+            workspace.add_item_to_active_pane(Box::new(preview), None, true, window, cx);
+        })?;
+
+        Ok(())
+    }
+
     pub fn clear_code_action_providers(&mut self) {
         self.code_action_providers.clear();
         self.available_code_actions.take();
@@ -6847,16 +7428,48 @@ impl Editor {
         }
 
         let snapshot = cursor_buffer.read(cx).snapshot();
-        let (start_word_range, _) = snapshot.surrounding_word(cursor_buffer_position, None);
+        let (start_word_range, start_word_kind) =
+            snapshot.surrounding_word(cursor_buffer_position, None);
         let (end_word_range, _) = snapshot.surrounding_word(tail_buffer_position, None);
         if start_word_range != end_word_range {
             self.document_highlights_task.take();
-            self.clear_background_highlights::<DocumentHighlightRead>(cx);
-            self.clear_background_highlights::<DocumentHighlightWrite>(cx);
+            self.clear_document_highlights(cx);
             return None;
         }
 
         let debounce = EditorSettings::get_global(cx).lsp_highlight_debounce;
+        if !provider.supports_document_highlights(&cursor_buffer, cx) {
+            if !self.mode.is_full()
+                || start_word_kind != Some(CharKind::Word)
+                || start_word_range.is_empty()
+            {
+                self.document_highlights_task.take();
+                self.clear_document_highlights(cx);
+                return None;
+            }
+
+            let word = snapshot
+                .text_for_range(start_word_range)
+                .collect::<String>();
+            self.document_highlights_task = Some(cx.spawn(async move |this, cx| {
+                cx.background_executor()
+                    .timer(Duration::from_millis(debounce))
+                    .await;
+
+                this.update(cx, |this, cx| {
+                    if this.pending_rename.is_some() {
+                        return;
+                    }
+
+                    let ranges = this.visible_word_occurrences(&word, cx);
+                    this.highlight_document_occurrences(&ranges, &[], cx);
+                    cx.notify();
+                })
+                .log_err();
+            }));
+            return None;
+        }
+
         self.document_highlights_task = Some(cx.spawn(async move |this, cx| {
             cx.background_executor()
                 .timer(Duration::from_millis(debounce))
@@ -6916,16 +7529,7 @@ impl Editor {
                         }
                     }
 
-                    this.highlight_background::<DocumentHighlightRead>(
-                        &read_ranges,
-                        |theme| theme.colors().editor_document_highlight_read_background,
-                        cx,
-                    );
-                    this.highlight_background::<DocumentHighlightWrite>(
-                        &write_ranges,
-                        |theme| theme.colors().editor_document_highlight_write_background,
-                        cx,
-                    );
+                    this.highlight_document_occurrences(&read_ranges, &write_ranges, cx);
                     cx.notify();
                 })
                 .log_err();
@@ -6934,6 +7538,125 @@ impl Editor {
         None
     }
 
+    /// Highlights the occurrences of the symbol under the cursor, in the styles configured for
+    /// reading and for writing it.
+    fn highlight_document_occurrences(
+        &mut self,
+        read_ranges: &[Range<Anchor>],
+        write_ranges: &[Range<Anchor>],
+        cx: &mut Context<Self>,
+    ) {
+        let styles = EditorSettings::get_global(cx).document_highlights;
+        self.highlight_document_occurrences_of_kind::<DocumentHighlightRead>(
+            read_ranges,
+            styles.read,
+            |theme| theme.colors().editor_document_highlight_read_background,
+            cx.theme().colors().editor_document_highlight_read_underline,
+            cx,
+        );
+        self.highlight_document_occurrences_of_kind::<DocumentHighlightWrite>(
+            write_ranges,
+            styles.write,
+            |theme| theme.colors().editor_document_highlight_write_background,
+            cx.theme()
+                .colors()
+                .editor_document_highlight_write_underline,
+            cx,
+        );
+    }
+
+    fn highlight_document_occurrences_of_kind<T: 'static>(
+        &mut self,
+        ranges: &[Range<Anchor>],
+        style: DocumentHighlightStyle,
+        background_color: fn(&Theme) -> Hsla,
+        underline_color: Hsla,
+        cx: &mut Context<Self>,
+    ) {
+        if ranges.is_empty() {
+            self.clear_background_highlights::<T>(cx);
+            self.clear_highlights::<T>(cx);
+            return;
+        }
+        match style {
+            DocumentHighlightStyle::Background => {
+                self.highlight_background::<T>(ranges, background_color, cx);
+                self.clear_highlights::<T>(cx);
+            }
+            DocumentHighlightStyle::Underline => {
+                // The occurrences are still background highlights, only without a color, so that
+                // they are shown in the scrollbar and can be navigated between.
+                self.highlight_background::<T>(ranges, |_| Hsla::transparent_black(), cx);
+                self.highlight_text::<T>(
+                    ranges.to_vec(),
+                    HighlightStyle {
+                        underline: Some(UnderlineStyle {
+                            color: Some(underline_color),
+                            thickness: px(1.),
+                            wavy: false,
+                        }),
+                        ..HighlightStyle::default()
+                    },
+                    cx,
+                );
+            }
+        }
+    }
+
+    fn clear_document_highlights(&mut self, cx: &mut Context<Self>) {
+        self.clear_background_highlights::<DocumentHighlightRead>(cx);
+        self.clear_background_highlights::<DocumentHighlightWrite>(cx);
+        self.clear_highlights::<DocumentHighlightRead>(cx);
+        self.clear_highlights::<DocumentHighlightWrite>(cx);
+    }
+
+    /// Returns the case-sensitive, whole-word occurrences of `word` in the visible rows, used to
+    /// highlight occurrences when no language server provides document highlights.
+    fn visible_word_occurrences(&self, word: &str, cx: &App) -> Vec<Range<Anchor>> {
+        let multi_buffer_snapshot = self.buffer().read(cx).snapshot(cx);
+        let multi_buffer_visible_start = self
+            .scroll_manager
+            .anchor()
+            .anchor
+            .to_point(&multi_buffer_snapshot);
+        let multi_buffer_visible_end = multi_buffer_snapshot.clip_point(
+            multi_buffer_visible_start
+                + Point::new(self.visible_line_count().unwrap_or(0.).ceil() as u32, 0),
+            Bias::Left,
+        );
+
+        let mut occurrences = Vec::new();
+        for (buffer_snapshot, search_range, excerpt_id) in multi_buffer_snapshot
+            .range_to_buffer_ranges(multi_buffer_visible_start..multi_buffer_visible_end)
+        {
+            let text = buffer_snapshot
+                .text_for_range(search_range.clone())
+                .collect::<String>();
+            let classifier = buffer_snapshot.char_classifier_at(search_range.start);
+            for (offset, _) in text.match_indices(word) {
+                let match_end = offset + word.len();
+                let is_word_start = text[..offset]
+                    .chars()
+                    .next_back()
+                    .is_none_or(|c| !classifier.is_word(c));
+                let is_word_end = text[match_end..]
+                    .chars()
+                    .next()
+                    .is_none_or(|c| !classifier.is_word(c));
+                if is_word_start && is_word_end {
+                    let start = buffer_snapshot.anchor_after(search_range.start + offset);
+                    let end = buffer_snapshot.anchor_before(search_range.start + match_end);
+                    occurrences.push(Anchor::range_in_buffer(
+                        excerpt_id,
+                        buffer_snapshot.remote_id(),
+                        start..end,
+                    ));
+                }
+            }
+        }
+        occurrences
+    }
+
     fn prepare_highlight_query_from_selection(
         &mut self,
         cx: &mut Context<Editor>,
@@ -7040,6 +7763,38 @@ impl Editor {
         })
     }
 
+    /// Highlights the lines of a file whose line break differs from the rest of the file, until the
+    /// file is saved with a single line ending.
+    fn refresh_mixed_line_endings(&mut self, cx: &mut Context<Editor>) {
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+        let multi_buffer_snapshot = self.buffer.read(cx).snapshot(cx);
+        let Some((excerpt_id, _, _)) = multi_buffer_snapshot.as_singleton() else {
+            return;
+        };
+        let ranges = buffer
+            .read(cx)
+            .mixed_line_endings()
+            .iter()
+            .filter_map(|range| {
+                Some(
+                    multi_buffer_snapshot.anchor_in_excerpt(*excerpt_id, range.start)?
+                        ..multi_buffer_snapshot.anchor_in_excerpt(*excerpt_id, range.end)?,
+                )
+            })
+            .collect::<Vec<_>>();
+        if ranges.is_empty() {
+            self.clear_background_highlights::<MixedLineEndingHighlight>(cx);
+        } else {
+            self.highlight_background::<MixedLineEndingHighlight>(
+                &ranges,
+                |theme| theme.status().warning_background,
+                cx,
+            );
+        }
+    }
+
     fn refresh_single_line_folds(&mut self, window: &mut Window, cx: &mut Context<Editor>) {
         struct NewlineFold;
         let type_id = std::any::TypeId::of::<NewlineFold>();
@@ -7181,6 +7936,252 @@ impl Editor {
         }
     }
 
+    pub fn zoom_in(&mut self, _: &ZoomIn, _: &mut Window, cx: &mut Context<Self>) {
+        self.adjust_font_size(px(1.), cx);
+    }
+
+    pub fn zoom_out(&mut self, _: &ZoomOut, _: &mut Window, cx: &mut Context<Self>) {
+        self.adjust_font_size(px(-1.), cx);
+    }
+
+    pub fn zoom_reset(&mut self, _: &ZoomReset, _: &mut Window, cx: &mut Context<Self>) {
+        self.font_size_adjustment = px(0.);
+        cx.notify();
+    }
+
+    /// Changes the font size of this editor relative to the global buffer font size, leaving
+    /// other editors untouched.
+    pub fn adjust_font_size(&mut self, delta: Pixels, cx: &mut Context<Self>) {
+        let font_size = theme::clamp_font_size(self.buffer_font_size(cx) + delta);
+        self.font_size_adjustment = font_size - self.unadjusted_buffer_font_size(cx);
+        cx.notify();
+    }
+
+    /// The font size of this editor's buffer text, including its zoom level.
+    pub fn buffer_font_size(&self, cx: &App) -> Pixels {
+        theme::clamp_font_size(self.unadjusted_buffer_font_size(cx) + self.font_size_adjustment)
+    }
+
+    /// The font size of this editor's buffer text before it's zoomed, which the buffer's language
+    /// can set instead of the global buffer font size.
+    fn unadjusted_buffer_font_size(&self, cx: &App) -> Pixels {
+        match self
+            .font_language_settings(cx)
+            .and_then(|settings| settings.font_size)
+        {
+            Some(font_size) => theme::adjusted_font_size(font_size, cx),
+            None => ThemeSettings::get_global(cx).buffer_font_size(cx),
+        }
+    }
+
+    /// The font of this editor's buffer text, with the family and features that the buffer's
+    /// language overrides.
+    pub fn buffer_font(&self, cx: &App) -> Font {
+        let mut font = ThemeSettings::get_global(cx).buffer_font.clone();
+        if let Some(settings) = self.font_language_settings(cx) {
+            if let Some(family) = &settings.font_family {
+                font.family = family.0.clone().into();
+            }
+            if let Some(features) = &settings.font_features {
+                font.features = features.clone();
+            }
+        }
+        font
+    }
+
+    /// Fonts are only chosen by language for editors of a single buffer, as excerpts of different
+    /// languages share the same line height in multibuffers.
+    fn font_language_settings<'a>(&'a self, cx: &'a App) -> Option<Cow<'a, LanguageSettings>> {
+        let buffer = self.buffer.read(cx);
+        buffer.is_singleton().then(|| buffer.language_settings(cx))
+    }
+
+    pub fn toggle_focus_mode(
+        &mut self,
+        _: &ToggleFocusMode,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.focus_mode = !self.focus_mode;
+        if self.focus_mode {
+            self.refresh_focus_mode(cx);
+        } else {
+            self.clear_highlights::<FocusModeHighlight>(cx);
+        }
+    }
+
+    pub fn focus_mode(&self) -> bool {
+        self.focus_mode
+    }
+
+    /// Dims the text outside the innermost function enclosing the newest cursor.
+    fn refresh_focus_mode(&mut self, cx: &mut Context<Self>) {
+        if !self.focus_mode {
+            return;
+        }
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let cursor = self.selections.newest::<usize>(cx).head();
+        let function_range = snapshot
+            .text_object_ranges(cursor..cursor, TreeSitterOptions::default())
+            .filter(|(range, object)| {
+                *object == TextObject::AroundFunction
+                    && range.start <= cursor
+                    && cursor <= range.end
+            })
+            .map(|(range, _)| range)
+            .min_by_key(|range| range.len());
+        let Some(function_range) = function_range else {
+            self.clear_highlights::<FocusModeHighlight>(cx);
+            return;
+        };
+
+        let dimmed_ranges = [0..function_range.start, function_range.end..snapshot.len()]
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| snapshot.anchor_after(range.start)..snapshot.anchor_before(range.end))
+            .collect();
+        self.highlight_text::<FocusModeHighlight>(
+            dimmed_ranges,
+            HighlightStyle {
+                fade_out: Some(0.6),
+                ..Default::default()
+            },
+            cx,
+        );
+    }
+
+    pub fn toggle_highlight_word_under_cursor(
+        &mut self,
+        _: &ToggleHighlightWordUnderCursor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let selection = self.selections.newest::<usize>(cx);
+        let range = if selection.is_empty() {
+            let (range, kind) = snapshot.surrounding_word(selection.head(), None);
+            if kind != Some(CharKind::Word) {
+                return;
+            }
+            range
+        } else {
+            selection.range()
+        };
+        let text = snapshot.text_for_range(range).collect::<String>();
+        if text.trim().is_empty() {
+            return;
+        }
+
+        if let Some(ix) = self.pinned_words.iter().position(|word| word.text == text) {
+            let word = self.pinned_words.remove(ix);
+            self.background_highlights.remove(&HighlightKey::TypePlus(
+                TypeId::of::<PinnedWordHighlight>(),
+                word.id,
+            ));
+            self.scrollbar_marker_state.dirty = true;
+            cx.emit(EditorEvent::TransientHighlightsChanged);
+            cx.notify();
+            return;
+        }
+
+        let color_index = (0..PINNED_WORD_HIGHLIGHT_COLORS.len())
+            .find(|ix| self.pinned_words.iter().all(|word| word.color_index != *ix))
+            .unwrap_or(self.pinned_words.len() % PINNED_WORD_HIGHLIGHT_COLORS.len());
+        let id = post_inc(&mut self.next_pinned_word_id);
+        self.pinned_words.push(PinnedWord {
+            id,
+            text,
+            color_index,
+        });
+        self.refresh_pinned_word_highlights(false, window, cx);
+    }
+
+    fn refresh_pinned_word_highlights(
+        &mut self,
+        debounce: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.pinned_words.is_empty() {
+            return;
+        }
+
+        let words = self
+            .pinned_words
+            .iter()
+            .map(|word| (word.id, word.text.clone(), word.color_index))
+            .collect::<Vec<_>>();
+        let multi_buffer_snapshot = self.buffer.read(cx).snapshot(cx);
+        self.pinned_word_highlights_task = Some(cx.spawn_in(window, async move |editor, cx| {
+            if debounce {
+                cx.background_executor()
+                    .timer(SELECTION_HIGHLIGHT_DEBOUNCE_TIMEOUT)
+                    .await;
+            }
+            let highlights = cx
+                .background_spawn(async move {
+                    let mut highlights = Vec::new();
+                    for (id, text, color_index) in words {
+                        let Ok(query) = project::search::SearchQuery::text(
+                            text,
+                            true,
+                            true,
+                            false,
+                            Default::default(),
+                            Default::default(),
+                            false,
+                            None,
+                        ) else {
+                            continue;
+                        };
+                        let mut match_ranges = Vec::new();
+                        for (buffer_snapshot, search_range, excerpt_id) in multi_buffer_snapshot
+                            .range_to_buffer_ranges(0..multi_buffer_snapshot.len())
+                        {
+                            match_ranges.extend(
+                                query
+                                    .search(buffer_snapshot, Some(search_range.clone()))
+                                    .await
+                                    .into_iter()
+                                    .map(|match_range| {
+                                        Anchor::range_in_buffer(
+                                            excerpt_id,
+                                            buffer_snapshot.remote_id(),
+                                            buffer_snapshot.anchor_after(
+                                                search_range.start + match_range.start,
+                                            )
+                                                ..buffer_snapshot.anchor_before(
+                                                    search_range.start + match_range.end,
+                                                ),
+                                        )
+                                    }),
+                            );
+                        }
+                        highlights.push((id, color_index, match_ranges));
+                    }
+                    highlights
+                })
+                .await;
+            editor
+                .update(cx, |editor, cx| {
+                    for (id, color_index, match_ranges) in highlights {
+                        // The word may have been unpinned while searching.
+                        if editor.pinned_words.iter().any(|word| word.id == id) {
+                            editor.highlight_background_key::<PinnedWordHighlight>(
+                                id,
+                                &match_ranges,
+                                PINNED_WORD_HIGHLIGHT_COLORS[color_index],
+                                cx,
+                            );
+                        }
+                    }
+                    cx.emit(EditorEvent::TransientHighlightsChanged);
+                })
+                .log_err();
+        }));
+    }
+
     pub fn refresh_edit_prediction(
         &mut self,
         debounce: bool,
@@ -7556,6 +8557,24 @@ impl Editor {
         _: &AcceptPartialEditPrediction,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        self.accept_edit_prediction_prefix(PartialEditPredictionGranularity::Word, window, cx);
+    }
+
+    pub fn accept_next_line_edit_prediction(
+        &mut self,
+        _: &AcceptNextLineEditPrediction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.accept_edit_prediction_prefix(PartialEditPredictionGranularity::Line, window, cx);
+    }
+
+    fn accept_edit_prediction_prefix(
+        &mut self,
+        granularity: PartialEditPredictionGranularity,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         let Some(active_edit_prediction) = self.active_edit_prediction.as_ref() else {
             return;
@@ -7602,18 +8621,27 @@ impl Editor {
                 });
 
                 if let Some(text) = insertion {
-                    let mut partial_completion = text
-                        .chars()
-                        .by_ref()
-                        .take_while(|c| c.is_alphabetic())
-                        .collect::<String>();
-                    if partial_completion.is_empty() {
-                        partial_completion = text
-                            .chars()
-                            .by_ref()
-                            .take_while(|c| c.is_whitespace() || !c.is_alphabetic())
-                            .collect::<String>();
-                    }
+                    let partial_completion = match granularity {
+                        PartialEditPredictionGranularity::Word => {
+                            let mut partial_completion = text
+                                .chars()
+                                .by_ref()
+                                .take_while(|c| c.is_alphabetic())
+                                .collect::<String>();
+                            if partial_completion.is_empty() {
+                                partial_completion = text
+                                    .chars()
+                                    .by_ref()
+                                    .take_while(|c| c.is_whitespace() || !c.is_alphabetic())
+                                    .collect::<String>();
+                            }
+                            partial_completion
+                        }
+                        PartialEditPredictionGranularity::Line => match text.find('\n') {
+                            Some(newline_ix) => text[..=newline_ix].to_string(),
+                            None => text.clone(),
+                        },
+                    };
 
                     cx.emit(EditorEvent::InputHandled {
                         utf16_range_to_replace: None,
@@ -8502,9 +9530,9 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let Some((workspace, _)) = self.workspace.clone() else {
+        if self.workspace.is_none() {
             return;
-        };
+        }
         let Some(project) = self.project.clone() else {
             return;
         };
@@ -8520,19 +9548,19 @@ impl Editor {
 
         let reveal_strategy = action.reveal;
         let task_context = Self::build_tasks_context(&project, &buffer, buffer_row, &tasks, cx);
-        cx.spawn_in(window, async move |_, cx| {
+        cx.spawn_in(window, async move |editor, cx| {
             let context = task_context.await?;
             let (task_source_kind, mut resolved_task) = tasks.resolve(&context).next()?;
 
             let resolved = &mut resolved_task.resolved;
             resolved.reveal = reveal_strategy;
 
-            workspace
-                .update_in(cx, |workspace, window, cx| {
-                    workspace.schedule_resolved_task(
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    editor.schedule_runnable_task(
                         task_source_kind,
                         resolved_task,
-                        false,
+                        Some(tasks.offset),
                         window,
                         cx,
                     );
@@ -10014,6 +11042,7 @@ impl Editor {
 
     pub fn backspace(&mut self, _: &Backspace, window: &mut Window, cx: &mut Context<Self>) {
         if self.read_only(cx) {
+            self.notify_edits_locked(cx);
             return;
         }
         self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
@@ -10110,6 +11139,7 @@ impl Editor {
 
     pub fn delete(&mut self, _: &Delete, window: &mut Window, cx: &mut Context<Self>) {
         if self.read_only(cx) {
+            self.notify_edits_locked(cx);
             return;
         }
         self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
@@ -10139,6 +11169,11 @@ impl Editor {
         if self.move_to_prev_snippet_tabstop(window, cx) {
             return;
         }
+        if self.markdown_table_editing_enabled(cx)
+            && self.move_to_markdown_table_cell(false, window, cx)
+        {
+            return;
+        }
         self.outdent(&Outdent, window, cx);
     }
 
@@ -10156,6 +11191,11 @@ impl Editor {
             return;
         }
         self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+        if self.markdown_table_editing_enabled(cx)
+            && self.move_to_markdown_table_cell(true, window, cx)
+        {
+            return;
+        }
         let mut selections = self.selections.all_adjusted(cx);
         let buffer = self.buffer.read(cx);
         let snapshot = buffer.snapshot(cx);
@@ -10559,9 +11599,15 @@ impl Editor {
         });
     }
 
+    /// Joins the lines spanned by each selection, or each cursor's line with the next one,
+    /// putting `separator` between the joined lines.
+    ///
+    /// Leading indentation and, when both lines are line comments, the next line's comment
+    /// prefix are removed. A non-empty separator also drops trailing whitespace, and is left
+    /// out inside brackets that the language keeps on one line, such as parentheses.
     pub fn join_lines_impl(
         &mut self,
-        insert_whitespace: bool,
+        separator: &str,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
@@ -10601,17 +11647,46 @@ impl Editor {
         self.transact(window, cx, |this, window, cx| {
             for row_range in row_ranges.into_iter().rev() {
                 for row in row_range.iter_rows().rev() {
-                    let end_of_line = Point::new(row.0, snapshot.line_len(row));
+                    let start_of_line = Point::new(row.0, snapshot.indent_size_for_line(row).len);
+                    let mut end_of_line = Point::new(row.0, snapshot.line_len(row));
                     let next_line_row = row.next_row();
                     let indent = snapshot.indent_size_for_line(next_line_row);
-                    let start_of_next_line = Point::new(next_line_row.0, indent.len);
+                    let mut start_of_next_line = Point::new(next_line_row.0, indent.len);
+                    let end_of_next_line =
+                        Point::new(next_line_row.0, snapshot.line_len(next_line_row));
 
-                    let replace =
-                        if snapshot.line_len(next_line_row) > indent.len && insert_whitespace {
-                            " "
-                        } else {
-                            ""
-                        };
+                    let line = snapshot
+                        .text_for_range(start_of_line..end_of_line)
+                        .collect::<String>();
+                    let mut next_line = snapshot
+                        .text_for_range(start_of_next_line..end_of_next_line)
+                        .collect::<String>();
+                    let scope = snapshot.language_scope_at(start_of_line);
+                    if let Some(scope) = &scope
+                        && let Some(prefix) = joined_comment_prefix(scope, &line, &next_line)
+                    {
+                        let rest = next_line[prefix.len()..].trim_start();
+                        start_of_next_line.column += (next_line.len() - rest.len()) as u32;
+                        next_line = rest.to_string();
+                    }
+
+                    let mut replace = "";
+                    if !separator.is_empty() {
+                        let line = line.trim_end();
+                        end_of_line.column = start_of_line.column + line.len() as u32;
+                        let hugs_bracket = scope.as_ref().is_some_and(|scope| {
+                            scope.brackets().any(|(pair, enabled)| {
+                                enabled
+                                    && !pair.newline
+                                    && pair.start != pair.end
+                                    && (line.ends_with(pair.start.as_str())
+                                        || next_line.starts_with(pair.end.as_str()))
+                            })
+                        });
+                        if !line.is_empty() && !next_line.is_empty() && !hugs_bracket {
+                            replace = separator;
+                        }
+                    }
 
                     this.buffer.update(cx, |buffer, cx| {
                         buffer.edit([(end_of_line..start_of_next_line, replace)], None, cx)
@@ -10625,9 +11700,9 @@ impl Editor {
         });
     }
 
-    pub fn join_lines(&mut self, _: &JoinLines, window: &mut Window, cx: &mut Context<Self>) {
+    pub fn join_lines(&mut self, action: &JoinLines, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
-        self.join_lines_impl(true, window, cx);
+        self.join_lines_impl(action.separator.as_deref().unwrap_or(" "), window, cx);
     }
 
     pub fn sort_lines_case_sensitive(
@@ -10661,9 +11736,33 @@ impl Editor {
         })
     }
 
-    pub fn unique_lines_case_insensitive(
+    pub fn sort_lines_numeric(
         &mut self,
-        _: &UniqueLinesCaseInsensitive,
+        _: &SortLinesNumeric,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Lines that don't start with a number are kept first, in their original order.
+        fn leading_number(line: &str) -> Option<f64> {
+            let line = line.trim_start();
+            let len = line
+                .char_indices()
+                .find(|&(ix, c)| !(c.is_ascii_digit() || c == '.' || (ix == 0 && c == '-')))
+                .map_or(line.len(), |(ix, _)| ix);
+            line[..len].parse().ok()
+        }
+
+        self.manipulate_immutable_lines(window, cx, |lines| {
+            lines.sort_by(|a, b| match (leading_number(a), leading_number(b)) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (a, b) => a.is_some().cmp(&b.is_some()),
+            })
+        })
+    }
+
+    pub fn unique_lines_case_insensitive(
+        &mut self,
+        _: &UniqueLinesCaseInsensitive,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
@@ -10773,6 +11872,22 @@ impl Editor {
             .detach_and_notify_err(window, cx);
     }
 
+    pub fn reload_file_with_merge(
+        &mut self,
+        _: &ReloadFileWithMerge,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        if !self.can_reload_with_merge(cx) {
+            return;
+        }
+        self.reload_with_merge(project, window, cx)
+            .detach_and_notify_err(window, cx);
+    }
+
     pub fn restore_file(
         &mut self,
         _: &::git::RestoreFile,
@@ -11563,6 +12678,24 @@ impl Editor {
         })
     }
 
+    pub fn cycle_case(&mut self, _: &CycleCase, window: &mut Window, cx: &mut Context<Self>) {
+        self.manipulate_text(window, cx, |text| {
+            let has_lowercase = text.chars().any(|c| c.is_lowercase());
+            let next_case = if text.contains('-') {
+                Case::Camel
+            } else if !has_lowercase {
+                Case::Kebab
+            } else if text.contains('_') {
+                Case::UpperSnake
+            } else if text.starts_with(|c: char| c.is_uppercase()) {
+                Case::Snake
+            } else {
+                Case::UpperCamel
+            };
+            text.to_case(next_case)
+        })
+    }
+
     pub fn convert_to_rot13(
         &mut self,
         _: &ConvertToRot13,
@@ -11686,6 +12819,117 @@ impl Editor {
         });
     }
 
+    /// Inserts the text of a selection dragged out of another editor at `target`,
+    /// also removing it from that editor when `is_cut` is true.
+    pub fn drop_selection_from_other_editor(
+        &mut self,
+        source: &WeakEntity<Editor>,
+        selection: &Selection<Anchor>,
+        target: DisplayPoint,
+        is_cut: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(source) = source.upgrade() else {
+            return;
+        };
+        if self.read_only(cx) {
+            return;
+        }
+
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let insert_point = display_map
+            .clip_point(target, Bias::Left)
+            .to_point(&display_map);
+        let insert_anchor = display_map.buffer_snapshot().anchor_before(insert_point);
+
+        // Text moved within the same buffer, as between two editors for the same file, is
+        // deleted and inserted in a single transaction, so that it's undone in one step.
+        let moved_range = if is_cut {
+            self.source_range_in_buffer(&source, selection, &display_map, cx)
+        } else {
+            None
+        };
+        if let Some(moved_range) = &moved_range {
+            let buffer = display_map.buffer_snapshot();
+            let moved_range =
+                moved_range.start.to_offset(buffer)..moved_range.end.to_offset(buffer);
+            if moved_range.contains(&insert_point.to_offset(buffer)) {
+                return;
+            }
+        }
+
+        let text = source.update(cx, |source, cx| {
+            let text = source
+                .buffer
+                .read(cx)
+                .snapshot(cx)
+                .text_for_range(selection.start..selection.end)
+                .collect::<String>();
+            if is_cut && moved_range.is_none() && !source.read_only(cx) {
+                source.transact(window, cx, |source, window, cx| {
+                    source.buffer.update(cx, |buffer, cx| {
+                        buffer.edit([(selection.start..selection.end, "")], None, cx);
+                    });
+                    source.change_selections(Default::default(), window, cx, |s| {
+                        s.select_anchor_ranges([selection.start..selection.start]);
+                    });
+                });
+            }
+            text
+        });
+
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let last_edit_start = insert_anchor.bias_left(&buffer);
+        let last_edit_end = insert_anchor.bias_right(&buffer);
+        let moved = moved_range.is_some();
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer.update(cx, |buffer, cx| {
+                let deletion = moved_range.map(|range| (range, String::new()));
+                buffer.edit(
+                    deletion
+                        .into_iter()
+                        .chain([(insert_anchor..insert_anchor, text)]),
+                    None,
+                    cx,
+                );
+            });
+            this.change_selections(Default::default(), window, cx, |s| {
+                s.select_anchor_ranges([last_edit_start..last_edit_end]);
+            });
+        });
+        if moved {
+            source.update(cx, |source, cx| {
+                source.change_selections(Default::default(), window, cx, |s| {
+                    s.select_anchor_ranges([selection.start..selection.start]);
+                });
+            });
+        }
+        window.focus(&self.focus_handle);
+    }
+
+    /// Returns the range of a selection in another editor in this editor's buffer, when both
+    /// editors show the same buffer.
+    fn source_range_in_buffer(
+        &self,
+        source: &Entity<Editor>,
+        selection: &Selection<Anchor>,
+        display_map: &DisplaySnapshot,
+        cx: &App,
+    ) -> Option<Range<Anchor>> {
+        if source.read(cx).buffer == self.buffer {
+            return Some(selection.start..selection.end);
+        }
+        let buffer = display_map.buffer_snapshot();
+        let (excerpt_id, buffer_id, _) = buffer.as_singleton()?;
+        if selection.start.buffer_id != Some(buffer_id) {
+            return None;
+        }
+        let start = buffer.anchor_in_excerpt(*excerpt_id, selection.start.text_anchor)?;
+        let end = buffer.anchor_in_excerpt(*excerpt_id, selection.end.text_anchor)?;
+        Some(start..end)
+    }
+
     pub fn clear_selection_drag_state(&mut self) {
         self.selection_drag_state = SelectionDragState::None;
     }
@@ -11795,6 +13039,99 @@ impl Editor {
         self.duplicate(false, false, window, cx);
     }
 
+    /// Duplicates the lines of each selection below them and comments out the original lines,
+    /// moving the selections to the copy.
+    pub fn duplicate_line_and_comment(
+        &mut self,
+        _: &DuplicateLineAndComment,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.read_only(cx) {
+            return;
+        }
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let buffer = display_map.buffer_snapshot();
+        let selections = self.selections.all::<Point>(cx);
+
+        let mut row_ranges = Vec::<Range<MultiBufferRow>>::new();
+        for selection in &selections {
+            let rows = selection.spanned_rows(false, &display_map);
+            if let Some(last_rows) = row_ranges.last_mut()
+                && rows.start < last_rows.end
+            {
+                last_rows.end = last_rows.end.max(rows.end);
+                continue;
+            }
+            row_ranges.push(rows);
+        }
+
+        let mut edits = Vec::<(Range<Point>, String)>::new();
+        for rows in &row_ranges {
+            let last_row = rows.end.previous_row();
+            let start = Point::new(rows.start.0, 0);
+            let end = Point::new(last_row.0, buffer.line_len(last_row));
+            let text = buffer.text_for_range(start..end).collect::<String>();
+
+            let commented_rows = (rows.start.0..rows.end.0)
+                .map(MultiBufferRow)
+                .filter(|row| !buffer.is_line_blank(*row))
+                .collect::<Vec<_>>();
+            let indent_column = commented_rows
+                .iter()
+                .map(|row| buffer.indent_size_for_line(*row).len)
+                .min();
+            let scope = buffer.language_scope_at(Point::new(
+                rows.start.0,
+                buffer.indent_size_for_line(rows.start).len,
+            ));
+            let mut comment_suffix = String::new();
+            if let Some(scope) = scope
+                && let Some(indent_column) = indent_column
+            {
+                if let Some(prefix) = scope.line_comment_prefixes().first() {
+                    edits.extend(commented_rows.iter().map(|row| {
+                        let position = Point::new(row.0, indent_column);
+                        (position..position, prefix.to_string())
+                    }));
+                } else if let Some(block_comment) = scope.block_comment() {
+                    let position = Point::new(rows.start.0, indent_column);
+                    edits.push((position..position, block_comment.start.to_string()));
+                    comment_suffix = block_comment.end.to_string();
+                }
+            }
+            edits.push((end..end, format!("{comment_suffix}\n{text}")));
+        }
+
+        // Each selection moves down by the length of the copies inserted above it and of its own.
+        let mut new_selections = Vec::with_capacity(selections.len());
+        let mut rows_iter = row_ranges.iter().peekable();
+        let mut row_delta = 0;
+        for selection in selections {
+            while let Some(rows) = rows_iter.next_if(|rows| rows.end.0 <= selection.start.row) {
+                row_delta += rows.end.0 - rows.start.0;
+            }
+            let own_rows = rows_iter.peek().map_or(0, |rows| rows.end.0 - rows.start.0);
+            let offset = row_delta + own_rows;
+            new_selections.push(Selection {
+                start: Point::new(selection.start.row + offset, selection.start.column),
+                end: Point::new(selection.end.row + offset, selection.end.column),
+                ..selection
+            });
+        }
+
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer.update(cx, |buffer, cx| {
+                buffer.edit(edits, None, cx);
+            });
+            this.change_selections(Default::default(), window, cx, |s| {
+                s.select(new_selections);
+            });
+        });
+    }
+
     pub fn move_line_up(&mut self, _: &MoveLineUp, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
         if self.mode.is_single_line() {
@@ -11806,6 +13143,7 @@ impl Editor {
         let buffer = self.buffer.read(cx).snapshot(cx);
 
         let mut edits = Vec::new();
+        let mut moved_line_indents = HashMap::default();
         let mut unfold_ranges = Vec::new();
         let mut refold_creases = Vec::new();
 
@@ -11849,15 +13187,25 @@ impl Editor {
                         .chain(['\n'])
                         .collect::<String>();
 
+                    let reindent = buffer
+                        .language_settings_at(insertion_point, cx)
+                        .auto_indent_on_move_lines;
                     edits.push((
                         buffer.anchor_after(range_to_move.start)
                             ..buffer.anchor_before(range_to_move.end),
                         String::new(),
+                        false,
                     ));
                     let insertion_anchor = buffer.anchor_after(insertion_point);
-                    edits.push((insertion_anchor..insertion_anchor, text));
+                    edits.push((insertion_anchor..insertion_anchor, text, reindent));
 
                     let row_delta = range_to_move.start.row - insertion_point.row + 1;
+                    if reindent {
+                        for row in start_row.0..end_row.0 {
+                            let indent = buffer.indent_size_for_line(MultiBufferRow(row)).len;
+                            moved_line_indents.insert(row - row_delta, indent);
+                        }
+                    }
 
                     // Move selections up
                     new_selections.extend(contiguous_row_selections.drain(..).map(
@@ -11890,10 +13238,18 @@ impl Editor {
         self.transact(window, cx, |this, window, cx| {
             this.unfold_ranges(&unfold_ranges, true, true, cx);
             this.buffer.update(cx, |buffer, cx| {
-                for (range, text) in edits {
-                    buffer.edit([(range, text)], None, cx);
+                for (range, text, reindent) in edits {
+                    let autoindent_mode = reindent.then(|| AutoindentMode::Block {
+                        original_indent_columns: Vec::new(),
+                    });
+                    buffer.edit([(range, text)], autoindent_mode, cx);
                 }
             });
+            shift_selections_by_indent_change(
+                &mut new_selections,
+                &moved_line_indents,
+                &this.buffer.read(cx).snapshot(cx),
+            );
             this.fold_creases(refold_creases, true, window, cx);
             this.change_selections(Default::default(), window, cx, |s| {
                 s.select(new_selections);
@@ -11917,6 +13273,7 @@ impl Editor {
         let buffer = self.buffer.read(cx).snapshot(cx);
 
         let mut edits = Vec::new();
+        let mut moved_line_indents = HashMap::default();
         let mut unfold_ranges = Vec::new();
         let mut refold_creases = Vec::new();
 
@@ -11950,15 +13307,25 @@ impl Editor {
                     let mut text = String::from("\n");
                     text.extend(buffer.text_for_range(range_to_move.clone()));
                     text.pop(); // Drop trailing newline
+                    let reindent = buffer
+                        .language_settings_at(insertion_point, cx)
+                        .auto_indent_on_move_lines;
                     edits.push((
                         buffer.anchor_after(range_to_move.start)
                             ..buffer.anchor_before(range_to_move.end),
                         String::new(),
+                        false,
                     ));
                     let insertion_anchor = buffer.anchor_after(insertion_point);
-                    edits.push((insertion_anchor..insertion_anchor, text));
+                    edits.push((insertion_anchor..insertion_anchor, text, reindent));
 
                     let row_delta = insertion_point.row - range_to_move.end.row + 1;
+                    if reindent {
+                        for row in start_row.0..end_row.0 {
+                            let indent = buffer.indent_size_for_line(MultiBufferRow(row)).len;
+                            moved_line_indents.insert(row + row_delta, indent);
+                        }
+                    }
 
                     // Move selections down
                     new_selections.extend(contiguous_row_selections.drain(..).map(
@@ -11991,10 +13358,18 @@ impl Editor {
         self.transact(window, cx, |this, window, cx| {
             this.unfold_ranges(&unfold_ranges, true, true, cx);
             this.buffer.update(cx, |buffer, cx| {
-                for (range, text) in edits {
-                    buffer.edit([(range, text)], None, cx);
+                for (range, text, reindent) in edits {
+                    let autoindent_mode = reindent.then(|| AutoindentMode::Block {
+                        original_indent_columns: Vec::new(),
+                    });
+                    buffer.edit([(range, text)], autoindent_mode, cx);
                 }
             });
+            shift_selections_by_indent_change(
+                &mut new_selections,
+                &moved_line_indents,
+                &this.buffer.read(cx).snapshot(cx),
+            );
             this.fold_creases(refold_creases, true, window, cx);
             this.change_selections(Default::default(), window, cx, |s| s.select(new_selections));
         });
@@ -12060,6 +13435,104 @@ impl Editor {
         });
     }
 
+    /// Swaps the word before each cursor with the word after it, leaving the cursor after both.
+    /// A cursor inside a word swaps that word with the next one.
+    pub fn transpose_words(
+        &mut self,
+        _: &TransposeWords,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut edits = Vec::<(Range<usize>, String)>::new();
+        let mut cursors = Vec::new();
+        for selection in self.selections.all::<usize>(cx) {
+            if !selection.is_empty() {
+                cursors.push(selection.range());
+                continue;
+            }
+            let Some((first, second)) = words_to_transpose(&snapshot, selection.head()) else {
+                cursors.push(selection.range());
+                continue;
+            };
+            cursors.push(second.end..second.end);
+            if edits
+                .last()
+                .is_some_and(|(range, _)| range.end > first.start)
+            {
+                continue;
+            }
+            let first_text = snapshot.text_for_range(first.clone()).collect::<String>();
+            let second_text = snapshot.text_for_range(second.clone()).collect::<String>();
+            edits.push((first, second_text));
+            edits.push((second, first_text));
+        }
+
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            this.change_selections(Default::default(), window, cx, |s| {
+                s.select_ranges(cursors);
+            });
+        });
+    }
+
+    /// Swaps the line of each cursor with the line above it, moving the cursor down a line.
+    pub fn transpose_lines(
+        &mut self,
+        _: &TransposeLines,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let max_row = snapshot.max_point().row;
+        let mut edits = Vec::<(Range<Point>, String)>::new();
+        let mut cursors = Vec::new();
+        let mut last_swapped_row = None;
+        for selection in self.selections.all::<Point>(cx) {
+            let head = selection.head();
+            let row = head.row;
+            if row == 0 || last_swapped_row.is_some_and(|last_row| last_row >= row - 1) {
+                cursors.push(head..head);
+                continue;
+            }
+            last_swapped_row = Some(row);
+
+            let above = Point::new(row - 1, 0)
+                ..Point::new(row - 1, snapshot.line_len(MultiBufferRow(row - 1)));
+            let current =
+                Point::new(row, 0)..Point::new(row, snapshot.line_len(MultiBufferRow(row)));
+            let above_text = snapshot.text_for_range(above.clone()).collect::<String>();
+            let current_text = snapshot.text_for_range(current.clone()).collect::<String>();
+            edits.push((
+                above.start..current.end,
+                format!("{current_text}\n{above_text}"),
+            ));
+
+            let next_row = (row + 1).min(max_row);
+            cursors.push(if next_row == row {
+                let end = Point::new(row, above_text.len() as u32);
+                end..end
+            } else {
+                let position = Point::new(
+                    next_row,
+                    head.column.min(snapshot.line_len(MultiBufferRow(next_row))),
+                );
+                position..position
+            });
+        }
+
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            this.change_selections(Default::default(), window, cx, |s| {
+                s.select_ranges(cursors);
+            });
+        });
+    }
+
     pub fn rewrap(&mut self, _: &Rewrap, _: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
         if self.mode.is_single_line() {
@@ -12497,42 +13970,7 @@ impl Editor {
     pub fn cut(&mut self, _: &Cut, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
         let item = self.cut_common(true, window, cx);
-        cx.write_to_clipboard(item);
-    }
-
-    pub fn kill_ring_cut(&mut self, _: &KillRingCut, window: &mut Window, cx: &mut Context<Self>) {
-        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
-        self.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
-            s.move_with(|snapshot, sel| {
-                if sel.is_empty() {
-                    sel.end = DisplayPoint::new(sel.end.row(), snapshot.line_len(sel.end.row()));
-                }
-                if sel.is_empty() {
-                    sel.end = DisplayPoint::new(sel.end.row() + 1_u32, 0);
-                }
-            });
-        });
-        let item = self.cut_common(false, window, cx);
-        cx.set_global(KillRing(item))
-    }
-
-    pub fn kill_ring_yank(
-        &mut self,
-        _: &KillRingYank,
-        window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
-        let (text, metadata) = if let Some(KillRing(item)) = cx.try_global() {
-            if let Some(ClipboardEntry::String(kill_ring)) = item.entries().first() {
-                (kill_ring.text().to_string(), kill_ring.metadata_json())
-            } else {
-                return;
-            }
-        } else {
-            return;
-        };
-        self.do_paste(&text, metadata, false, window, cx);
+        clipboard_history::write_to_clipboard(item, cx);
     }
 
     pub fn copy_and_trim(&mut self, _: &CopyAndTrim, _: &mut Window, cx: &mut Context<Self>) {
@@ -12633,17 +14071,39 @@ impl Editor {
             }
         }
 
-        cx.write_to_clipboard(ClipboardItem::new_string_with_json_metadata(
+        clipboard_history::write_to_clipboard(
+            ClipboardItem::new_string_with_json_metadata(text, clipboard_selections),
+            cx,
+        );
+    }
+
+    pub fn do_paste(
+        &mut self,
+        text: &String,
+        clipboard_selections: Option<Vec<ClipboardSelection>>,
+        handle_entire_lines: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.do_paste_with_reindent(
             text,
             clipboard_selections,
-        ));
+            handle_entire_lines,
+            true,
+            window,
+            cx,
+        );
     }
 
-    pub fn do_paste(
+    /// Pastes `text`, re-indenting multi-line content to fit the destination when `reindent` is
+    /// true and the language's `auto_indent_on_paste` setting is enabled. When `reindent` is true,
+    /// the pasted text is also formatted if `format_on_paste` is enabled.
+    fn do_paste_with_reindent(
         &mut self,
         text: &String,
         clipboard_selections: Option<Vec<ClipboardSelection>>,
         handle_entire_lines: bool,
+        reindent: bool,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
@@ -12652,11 +14112,25 @@ impl Editor {
         }
 
         let clipboard_text = Cow::Borrowed(text.as_str());
+        let format_on_paste = reindent
+            && self
+                .buffer
+                .read(cx)
+                .read(cx)
+                .language_settings_at(self.selections.last::<usize>(cx).head(), cx)
+                .format_on_paste;
 
-        self.transact(window, cx, |this, window, cx| {
+        let transaction_id = self.transact(window, cx, |this, window, cx| {
             let had_active_edit_prediction = this.has_active_edit_prediction();
             let old_selections = this.selections.all::<usize>(cx);
             let cursor_offset = this.selections.last::<usize>(cx).head();
+            let auto_indent_on_paste = reindent
+                && this
+                    .buffer
+                    .read(cx)
+                    .read(cx)
+                    .language_settings_at(cursor_offset, cx)
+                    .auto_indent_on_paste;
 
             if let Some(mut clipboard_selections) = clipboard_selections {
                 let all_selections_were_entire_line =
@@ -12666,14 +14140,9 @@ impl Editor {
                 if clipboard_selections.len() != old_selections.len() {
                     clipboard_selections.drain(..);
                 }
-                let mut auto_indent_on_paste = true;
 
                 this.buffer.update(cx, |buffer, cx| {
                     let snapshot = buffer.read(cx);
-                    auto_indent_on_paste = snapshot
-                        .language_settings_at(cursor_offset, cx)
-                        .auto_indent_on_paste;
-
                     let mut start_offset = 0;
                     let mut edits = Vec::new();
                     let mut original_indent_columns = Vec::new();
@@ -12742,7 +14211,7 @@ impl Editor {
             } else {
                 let url = url::Url::parse(&clipboard_text).ok();
 
-                let auto_indent_mode = if !clipboard_text.is_empty() {
+                let auto_indent_mode = if auto_indent_on_paste && !clipboard_text.is_empty() {
                     Some(AutoindentMode::Block {
                         original_indent_columns: Vec::new(),
                     })
@@ -12794,6 +14263,54 @@ impl Editor {
 
             this.trigger_completion_on_input(text, trigger_in_words, window, cx);
         });
+
+        if let Some(transaction_id) = transaction_id
+            && format_on_paste
+        {
+            self.format_pasted_text(transaction_id, cx);
+        }
+    }
+
+    /// Formats the ranges edited by the paste in `transaction_id` with the language server,
+    /// merging the formatting edits into that transaction so they are undone together.
+    fn format_pasted_text(&mut self, transaction_id: TransactionId, cx: &mut Context<Self>) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let multi_buffer = self.buffer.clone();
+        let ranges = multi_buffer
+            .read(cx)
+            .edited_ranges_for_transaction::<usize>(transaction_id, cx);
+        if ranges.is_empty() {
+            return;
+        }
+        let (buffers, target) = self.lsp_format_target_for_ranges(ranges, cx);
+        let mut timeout = cx.background_executor().timer(FORMAT_TIMEOUT).fuse();
+        let format = project.update(cx, |project, cx| {
+            project.format(buffers, target, true, FormatTrigger::Manual, cx)
+        });
+        cx.spawn(async move |_, cx| {
+            let project_transaction = futures::select_biased! {
+                transaction = format.fuse() => transaction?,
+                () = timeout => {
+                    log::warn!("timed out waiting for formatting pasted text");
+                    return Ok(());
+                }
+            };
+            multi_buffer.update(cx, |multi_buffer, cx| {
+                if let Some(buffer) = multi_buffer.as_singleton() {
+                    if let Some(transaction) = project_transaction.0.get(&buffer) {
+                        multi_buffer.merge_transactions(transaction.id, transaction_id, cx);
+                    }
+                } else if !project_transaction.0.is_empty() {
+                    multi_buffer.push_transaction(&project_transaction.0, cx);
+                    if let Some(format_transaction_id) = multi_buffer.last_transaction_id(cx) {
+                        multi_buffer.merge_transactions(format_transaction_id, transaction_id, cx);
+                    }
+                }
+            })
+        })
+        .detach_and_log_err(cx);
     }
 
     pub fn diff_clipboard_with_selection(
@@ -12832,28 +14349,207 @@ impl Editor {
     }
 
     pub fn paste(&mut self, _: &Paste, window: &mut Window, cx: &mut Context<Self>) {
+        self.paste_from_clipboard(true, window, cx);
+    }
+
+    pub fn paste_without_reindent(
+        &mut self,
+        _: &PasteWithoutReindent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.paste_from_clipboard(false, window, cx);
+    }
+
+    fn paste_from_clipboard(
+        &mut self,
+        reindent: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
         if let Some(item) = cx.read_from_clipboard() {
             let entries = item.entries();
 
             match entries.first() {
+                Some(ClipboardEntry::String(clipboard_string))
+                    if entries.len() == 1
+                        && self.selections.count() == 1
+                        && EditorSettings::get_global(cx).multi_cursor_paste
+                            == MultiCursorPaste::Split
+                        && clipboard_string
+                            .metadata_json::<Vec<ClipboardSelection>>()
+                            .is_some_and(|selections| selections.len() > 1) =>
+                {
+                    self.paste_lines_rectangular(
+                        clipboard_string.text(),
+                        clipboard_string.metadata_json::<Vec<ClipboardSelection>>(),
+                        window,
+                        cx,
+                    )
+                }
                 // For now, we only support applying metadata if there's one string. In the future, we can incorporate all the selections
                 // of all the pasted entries.
                 Some(ClipboardEntry::String(clipboard_string)) if entries.len() == 1 => self
-                    .do_paste(
+                    .do_paste_with_reindent(
                         clipboard_string.text(),
                         clipboard_string.metadata_json::<Vec<ClipboardSelection>>(),
                         true,
+                        reindent,
                         window,
                         cx,
                     ),
-                _ => self.do_paste(&item.text().unwrap_or_default(), None, true, window, cx),
+                _ => self.do_paste_with_reindent(
+                    &item.text().unwrap_or_default(),
+                    None,
+                    true,
+                    reindent,
+                    window,
+                    cx,
+                ),
             }
         }
     }
 
+    pub fn paste_rectangular(
+        &mut self,
+        _: &PasteRectangular,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(item) = cx.read_from_clipboard() else {
+            return;
+        };
+        let entries = item.entries();
+        let (text, clipboard_selections) = match entries.first() {
+            Some(ClipboardEntry::String(clipboard_string)) if entries.len() == 1 => (
+                clipboard_string.text().clone(),
+                clipboard_string.metadata_json::<Vec<ClipboardSelection>>(),
+            ),
+            _ => (item.text().unwrap_or_default(), None),
+        };
+        self.paste_lines_rectangular(&text, clipboard_selections, window, cx);
+    }
+
+    fn paste_lines_rectangular(
+        &mut self,
+        text: &str,
+        clipboard_selections: Option<Vec<ClipboardSelection>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.read_only(cx) {
+            return;
+        }
+
+        // A copied column selection is pasted one selection per line,
+        // and any other text one line per line.
+        let mut lines = Vec::new();
+        if let Some(clipboard_selections) = clipboard_selections.filter(|s| s.len() > 1) {
+            let mut start_offset = 0;
+            for clipboard_selection in &clipboard_selections {
+                let end_offset = start_offset + clipboard_selection.len;
+                let Some(line) = text.get(start_offset..end_offset) else {
+                    break;
+                };
+                lines.push(line.trim_end_matches('\n'));
+                start_offset = end_offset + 1;
+            }
+        } else {
+            lines.extend(text.lines());
+        }
+        if lines.is_empty() {
+            return;
+        }
+
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+        let display_snapshot = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let buffer = display_snapshot.buffer_snapshot();
+        let cursor = self.selections.newest::<Point>(cx).head();
+        let column = display_column(&display_snapshot, cursor);
+
+        let mut edits = Vec::<(Range<Point>, String)>::new();
+        let mut cursors = Vec::new();
+        for (ix, line) in lines.iter().enumerate() {
+            let row = cursor.row + ix as u32;
+            if row > buffer.max_point().row {
+                // Add the lines that don't fit in the buffer after its last line.
+                let mut new_text = String::new();
+                for (ix, line) in lines.iter().enumerate().skip(ix) {
+                    new_text.push('\n');
+                    new_text.extend(iter::repeat_n(' ', column as usize));
+                    new_text.push_str(line);
+                    let end = Point::new(cursor.row + ix as u32, column + line.len() as u32);
+                    cursors.push(end..end);
+                }
+                let max_point = buffer.max_point();
+                match edits.last_mut() {
+                    Some((range, text)) if range.start == max_point => text.push_str(&new_text),
+                    _ => edits.push((max_point..max_point, new_text)),
+                }
+                break;
+            }
+            let (position, padding) = insertion_point_for_column(&display_snapshot, row, column);
+            let mut new_text = " ".repeat(padding as usize);
+            new_text.push_str(line);
+            let end = Point::new(row, position.column + new_text.len() as u32);
+            edits.push((position..position, new_text));
+            cursors.push(end..end);
+        }
+
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            this.change_selections(Default::default(), window, cx, |s| s.select_ranges(cursors));
+        });
+    }
+
+    pub fn block_insert(&mut self, _: &BlockInsert, window: &mut Window, cx: &mut Context<Self>) {
+        if self.read_only(cx) {
+            return;
+        }
+
+        let display_snapshot = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let mut rows_and_columns = Vec::new();
+        for selection in self.selections.all::<Point>(cx) {
+            let column = display_column(&display_snapshot, selection.start);
+            let mut end_row = selection.end.row;
+            // Like splitting a selection into lines, skip the last line of a
+            // multi-line selection that ends at the start of a line.
+            if end_row > selection.start.row && selection.end.column == 0 {
+                end_row -= 1;
+            }
+            for row in selection.start.row..=end_row {
+                if rows_and_columns
+                    .last()
+                    .is_none_or(|(last_row, _)| *last_row < row)
+                {
+                    rows_and_columns.push((row, column));
+                }
+            }
+        }
+
+        let mut edits = Vec::new();
+        let mut cursors = Vec::new();
+        for (row, column) in rows_and_columns {
+            let (position, padding) = insertion_point_for_column(&display_snapshot, row, column);
+            if padding > 0 {
+                edits.push((position..position, " ".repeat(padding as usize)));
+            }
+            let cursor = Point::new(row, position.column + padding);
+            cursors.push(cursor..cursor);
+        }
+
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            this.change_selections(Default::default(), window, cx, |s| s.select_ranges(cursors));
+        });
+    }
+
     pub fn undo(&mut self, _: &Undo, window: &mut Window, cx: &mut Context<Self>) {
         if self.read_only(cx) {
+            self.notify_edits_locked(cx);
             return;
         }
 
@@ -12884,6 +14580,7 @@ impl Editor {
 
     pub fn redo(&mut self, _: &Redo, window: &mut Window, cx: &mut Context<Self>) {
         if self.read_only(cx) {
+            self.notify_edits_locked(cx);
             return;
         }
 
@@ -12921,12 +14618,27 @@ impl Editor {
             .update(cx, |buffer, cx| buffer.group_until_transaction(tx_id, cx));
     }
 
+    /// The movements of the left and right arrow keys, which follow either the logical or the
+    /// visual order of bidirectional text.
+    fn horizontal_movements(
+        cx: &App,
+    ) -> (
+        fn(&DisplaySnapshot, DisplayPoint) -> DisplayPoint,
+        fn(&DisplaySnapshot, DisplayPoint) -> DisplayPoint,
+    ) {
+        match EditorSettings::get_global(cx).bidi_cursor_movement {
+            BidiCursorMovement::Logical => (movement::left, movement::right),
+            BidiCursorMovement::Visual => (movement::visual_left, movement::visual_right),
+        }
+    }
+
     pub fn move_left(&mut self, _: &MoveLeft, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let (left, _) = Self::horizontal_movements(cx);
         self.change_selections(Default::default(), window, cx, |s| {
             s.move_with(|map, selection| {
                 let cursor = if selection.is_empty() {
-                    movement::left(map, selection.start)
+                    left(map, selection.start)
                 } else {
                     selection.start
                 };
@@ -12937,17 +14649,19 @@ impl Editor {
 
     pub fn select_left(&mut self, _: &SelectLeft, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let (left, _) = Self::horizontal_movements(cx);
         self.change_selections(Default::default(), window, cx, |s| {
-            s.move_heads_with(|map, head, _| (movement::left(map, head), SelectionGoal::None));
+            s.move_heads_with(|map, head, _| (left(map, head), SelectionGoal::None));
         })
     }
 
     pub fn move_right(&mut self, _: &MoveRight, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let (_, right) = Self::horizontal_movements(cx);
         self.change_selections(Default::default(), window, cx, |s| {
             s.move_with(|map, selection| {
                 let cursor = if selection.is_empty() {
-                    movement::right(map, selection.end)
+                    right(map, selection.end)
                 } else {
                     selection.end
                 };
@@ -12958,8 +14672,9 @@ impl Editor {
 
     pub fn select_right(&mut self, _: &SelectRight, window: &mut Window, cx: &mut Context<Self>) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let (_, right) = Self::horizontal_movements(cx);
         self.change_selections(Default::default(), window, cx, |s| {
-            s.move_heads_with(|map, head, _| (movement::right(map, head), SelectionGoal::None));
+            s.move_heads_with(|map, head, _| (right(map, head), SelectionGoal::None));
         });
     }
 
@@ -13699,16 +15414,27 @@ impl Editor {
         cx: &mut Context<Self>,
     ) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let goal = Self::end_of_line_goal(cx);
         self.change_selections(Default::default(), window, cx, |s| {
             s.move_cursors_with(|map, head, _| {
                 (
                     movement::line_end(map, head, action.stop_at_soft_wraps),
-                    SelectionGoal::None,
+                    goal,
                 )
             });
         })
     }
 
+    /// The goal of the selections moved to the end of a line. With `sticky_end_of_line`, it
+    /// keeps them at the end of the lines they're moved up or down to.
+    fn end_of_line_goal(cx: &App) -> SelectionGoal {
+        if EditorSettings::get_global(cx).sticky_end_of_line {
+            SelectionGoal::HorizontalPosition(f64::INFINITY)
+        } else {
+            SelectionGoal::None
+        }
+    }
+
     pub fn select_to_end_of_line(
         &mut self,
         action: &SelectToEndOfLine,
@@ -13716,11 +15442,12 @@ impl Editor {
         cx: &mut Context<Self>,
     ) {
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let goal = Self::end_of_line_goal(cx);
         self.change_selections(Default::default(), window, cx, |s| {
             s.move_heads_with(|map, head, _| {
                 (
                     movement::line_end(map, head, action.stop_at_soft_wraps),
-                    SelectionGoal::None,
+                    goal,
                 )
             });
         })
@@ -13771,7 +15498,7 @@ impl Editor {
             }
             this.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
             let item = this.cut_common(false, window, cx);
-            cx.write_to_clipboard(item);
+            clipboard_history::write_to_clipboard(item, cx);
         });
     }
 
@@ -14103,6 +15830,24 @@ impl Editor {
         );
     }
 
+    fn navigation_data(&self, cursor_anchor: Anchor, cx: &App) -> NavigationData {
+        let buffer = self.buffer.read(cx).read(cx);
+        let scroll_anchor = self.scroll_manager.anchor();
+        NavigationData {
+            cursor_anchor,
+            cursor_position: cursor_anchor.to_point(&buffer),
+            scroll_top_row: scroll_anchor.top_row(&buffer),
+            scroll_anchor,
+        }
+    }
+
+    pub(crate) fn push_jump_location(&mut self, cx: &App) {
+        let data = self.navigation_data(self.selections.newest_anchor().head(), cx);
+        if let Some(nav_history) = self.nav_history.as_mut() {
+            nav_history.push_jump(data);
+        }
+    }
+
     fn push_to_nav_history(
         &mut self,
         cursor_anchor: Anchor,
@@ -14111,29 +15856,21 @@ impl Editor {
         always: bool,
         cx: &mut Context<Self>,
     ) {
-        if let Some(nav_history) = self.nav_history.as_mut() {
-            let buffer = self.buffer.read(cx).read(cx);
-            let cursor_position = cursor_anchor.to_point(&buffer);
-            let scroll_state = self.scroll_manager.anchor();
-            let scroll_top_row = scroll_state.top_row(&buffer);
-            drop(buffer);
+        if self.nav_history.is_some() {
+            let data = self.navigation_data(cursor_anchor, cx);
 
             if let Some(new_position) = new_position {
-                let row_delta = (new_position.row as i64 - cursor_position.row as i64).abs();
+                let row_delta = (new_position.row as i64 - data.cursor_position.row as i64).abs();
                 if row_delta == 0 || (row_delta < MIN_NAVIGATION_HISTORY_ROW_DELTA && !always) {
                     return;
                 }
             }
 
-            nav_history.push(
-                Some(NavigationData {
-                    cursor_anchor,
-                    cursor_position,
-                    scroll_anchor: scroll_state,
-                    scroll_top_row,
-                }),
-                cx,
-            );
+            let Some(nav_history) = self.nav_history.as_mut() else {
+                return;
+            };
+            nav_history.push_jump(data.clone());
+            nav_history.push(Some(data), cx);
             cx.emit(EditorEvent::PushedToNavHistory {
                 anchor: cursor_anchor,
                 is_deactivate,
@@ -14234,6 +15971,92 @@ impl Editor {
         });
     }
 
+    pub fn align_selections(
+        &mut self,
+        action: &AlignSelections,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.read_only(cx) {
+            return;
+        }
+
+        let display_snapshot = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let buffer = display_snapshot.buffer_snapshot();
+        let text_layout_details = self.text_layout_details(window);
+        let em_advance = self.character_dimensions(window).em_advance;
+        let selections = self.selections.all::<Point>(cx);
+        let align_on = action.align_on.as_deref().filter(|text| !text.is_empty());
+
+        // Only the first selection on each line is aligned.
+        let mut alignment_points = Vec::new();
+        for selection in &selections {
+            let row = selection.start.row;
+            if alignment_points
+                .last()
+                .is_some_and(|(point, _): &(Point, usize)| point.row == row)
+            {
+                continue;
+            }
+            let point = if let Some(align_on) = align_on {
+                let line_end = Point::new(row, buffer.line_len(MultiBufferRow(row)));
+                let rest_of_line = buffer
+                    .text_for_range(selection.start..line_end)
+                    .collect::<String>();
+                let Some(ix) = rest_of_line.find(align_on) else {
+                    continue;
+                };
+                Point::new(row, selection.start.column + ix as u32)
+            } else {
+                selection.start
+            };
+            // Measure columns by where they're drawn, so that tabs and wide characters count
+            // for as many columns as they take up.
+            let x = display_snapshot.x_for_display_point(
+                point.to_display_point(&display_snapshot),
+                &text_layout_details,
+            );
+            let column = (x / em_advance).round() as usize;
+            alignment_points.push((point, column));
+        }
+
+        let Some(max_column) = alignment_points.iter().map(|(_, column)| *column).max() else {
+            return;
+        };
+        let edits = alignment_points
+            .iter()
+            .filter(|(_, column)| *column < max_column)
+            .map(|(point, column)| (*point..*point, " ".repeat(max_column - column)))
+            .collect::<Vec<_>>();
+        if edits.is_empty() {
+            return;
+        }
+
+        let new_selections = selections
+            .into_iter()
+            .map(|selection| {
+                selection.map(|point| {
+                    match alignment_points.iter().find(|(alignment_point, _)| {
+                        alignment_point.row == point.row && alignment_point.column <= point.column
+                    }) {
+                        Some((_, column)) => {
+                            Point::new(point.row, point.column + (max_column - column) as u32)
+                        }
+                        None => point,
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            this.change_selections(Default::default(), window, cx, |s| {
+                s.select(new_selections);
+            });
+        });
+    }
+
     pub fn add_selection_above(
         &mut self,
         _: &AddSelectionAbove,
@@ -15545,6 +17368,259 @@ impl Editor {
         }
     }
 
+    pub fn select_next_syntax_sibling(
+        &mut self,
+        _: &SelectNextSyntaxSibling,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_syntax_sibling(Direction::Next, window, cx);
+    }
+
+    pub fn select_prev_syntax_sibling(
+        &mut self,
+        _: &SelectPreviousSyntaxSibling,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_syntax_sibling(Direction::Prev, window, cx);
+    }
+
+    /// Unlike `select_next_syntax_node`, this only considers named siblings and never moves up
+    /// to an ancestor's siblings, so repeating it steps through e.g. the arguments of a call.
+    fn select_syntax_sibling(
+        &mut self,
+        direction: Direction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let mut selected_sibling = false;
+        let new_selections = self
+            .selections
+            .all::<usize>(cx)
+            .into_iter()
+            .map(|selection| {
+                let old_range = selection.start..selection.end;
+                if let Some((_, sibling)) =
+                    syntax_node_and_named_sibling(&buffer, old_range, direction)
+                {
+                    selected_sibling = true;
+                    Selection {
+                        id: selection.id,
+                        start: sibling.start,
+                        end: sibling.end,
+                        goal: SelectionGoal::None,
+                        reversed: selection.reversed,
+                    }
+                } else {
+                    selection
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if selected_sibling {
+            self.change_selections(
+                SelectionEffects::scroll(Autoscroll::fit()),
+                window,
+                cx,
+                |s| {
+                    s.select(new_selections);
+                },
+            );
+        }
+    }
+
+    pub fn move_node_up(&mut self, _: &MoveNodeUp, window: &mut Window, cx: &mut Context<Self>) {
+        self.swap_syntax_node_with_sibling(Direction::Prev, true, window, cx);
+    }
+
+    pub fn move_node_down(
+        &mut self,
+        _: &MoveNodeDown,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.swap_syntax_node_with_sibling(Direction::Next, true, window, cx);
+    }
+
+    pub fn swap_with_next_sibling(
+        &mut self,
+        _: &SwapWithNextSibling,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.swap_syntax_node_with_sibling(Direction::Next, false, window, cx);
+    }
+
+    /// Exchanges the text of the syntax node under each selection with that of its named sibling
+    /// in `direction`. Lines after the first are reindented to match their new location.
+    ///
+    /// When `follow_node` is true, selections move along with their node, otherwise they stay
+    /// where the node used to be.
+    fn swap_syntax_node_with_sibling(
+        &mut self,
+        direction: Direction,
+        follow_node: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.read_only(cx) {
+            return;
+        }
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let selections = self.selections.all::<usize>(cx);
+
+        struct Swap {
+            first: Range<usize>,
+            second: Range<usize>,
+            new_first_text: String,
+            new_second_text: String,
+        }
+
+        let mut swaps = Vec::<Swap>::new();
+        let mut swap_ix_by_selection = Vec::with_capacity(selections.len());
+        for selection in &selections {
+            let range = selection.start..selection.end;
+            let existing_swap = swaps
+                .iter()
+                .position(|swap| swap.first.start <= range.start && range.end <= swap.second.end);
+            if existing_swap.is_some() {
+                swap_ix_by_selection.push(existing_swap);
+                continue;
+            }
+
+            let Some((node, sibling)) =
+                syntax_node_and_named_sibling(&buffer, range.clone(), direction)
+            else {
+                swap_ix_by_selection.push(None);
+                continue;
+            };
+            let (first, second) = match direction {
+                Direction::Prev => (sibling, node),
+                Direction::Next => (node, sibling),
+            };
+            if swaps
+                .last()
+                .is_some_and(|swap| first.start < swap.second.end)
+            {
+                swap_ix_by_selection.push(None);
+                continue;
+            }
+
+            let indent_for_offset = |offset: usize| {
+                buffer.indent_size_for_line(MultiBufferRow(offset.to_point(&buffer).row))
+            };
+            let first_indent = indent_for_offset(first.start);
+            let second_indent = indent_for_offset(second.start);
+            let first_text = buffer.text_for_range(first.clone()).collect::<String>();
+            let second_text = buffer.text_for_range(second.clone()).collect::<String>();
+            swaps.push(Swap {
+                new_first_text: reindent_subsequent_lines(
+                    &second_text,
+                    second_indent,
+                    first_indent,
+                ),
+                new_second_text: reindent_subsequent_lines(
+                    &first_text,
+                    first_indent,
+                    second_indent,
+                ),
+                first,
+                second,
+            });
+            swap_ix_by_selection.push(Some(swaps.len() - 1));
+        }
+        if swaps.is_empty() {
+            return;
+        }
+
+        // Compute where each swapped range starts once all of the edits have been applied.
+        let mut new_starts = Vec::with_capacity(swaps.len());
+        let mut delta = 0isize;
+        for swap in &swaps {
+            let new_first_start = (swap.first.start as isize + delta) as usize;
+            let new_second_start =
+                new_first_start + swap.new_first_text.len() + (swap.second.start - swap.first.end);
+            new_starts.push((new_first_start, new_second_start));
+            delta += swap.new_first_text.len() as isize - swap.first.len() as isize;
+            delta += swap.new_second_text.len() as isize - swap.second.len() as isize;
+        }
+
+        let new_selections = selections
+            .iter()
+            .zip(swap_ix_by_selection)
+            .map(|(selection, swap_ix)| {
+                let mut selection = selection.clone();
+                let Some(swap_ix) = swap_ix else {
+                    let shift = |offset: usize| {
+                        let delta = swaps
+                            .iter()
+                            .filter(|swap| swap.second.end <= offset)
+                            .map(|swap| {
+                                swap.new_first_text.len() as isize
+                                    + swap.new_second_text.len() as isize
+                                    - swap.first.len() as isize
+                                    - swap.second.len() as isize
+                            })
+                            .sum::<isize>();
+                        (offset as isize + delta) as usize
+                    };
+                    selection.start = shift(selection.start);
+                    selection.end = shift(selection.end);
+                    return selection;
+                };
+
+                let swap = &swaps[swap_ix];
+                let (new_first_start, new_second_start) = new_starts[swap_ix];
+                let in_first = swap.first.contains(&selection.start)
+                    || (swap.first.start == selection.start && swap.first.is_empty());
+                let (old_start, new_start, new_len) = match (in_first, follow_node) {
+                    (true, true) => (
+                        swap.first.start,
+                        new_second_start,
+                        swap.new_second_text.len(),
+                    ),
+                    (true, false) => (swap.first.start, new_first_start, swap.new_first_text.len()),
+                    (false, true) => (
+                        swap.second.start,
+                        new_first_start,
+                        swap.new_first_text.len(),
+                    ),
+                    (false, false) => (
+                        swap.second.start,
+                        new_second_start,
+                        swap.new_second_text.len(),
+                    ),
+                };
+                let map = |offset: usize| new_start + offset.saturating_sub(old_start).min(new_len);
+                selection.start = map(selection.start);
+                selection.end = map(selection.end);
+                selection.goal = SelectionGoal::None;
+                selection
+            })
+            .collect::<Vec<_>>();
+
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer.update(cx, |buffer, cx| {
+                let edits = swaps.into_iter().flat_map(|swap| {
+                    [
+                        (swap.first, swap.new_first_text),
+                        (swap.second, swap.new_second_text),
+                    ]
+                });
+                buffer.edit(edits, None, cx);
+            });
+            this.change_selections(Default::default(), window, cx, |s| {
+                s.select(new_selections);
+            });
+        });
+    }
+
     fn refresh_runnables(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Task<()> {
         if !EditorSettings::get_global(cx).gutter.runnables {
             self.clear_tasks();
@@ -15893,6 +17969,49 @@ impl Editor {
         }
     }
 
+    pub fn reselect_last_selection(
+        &mut self,
+        _: &ReselectLastSelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let current_selections = self.selections.disjoint_anchors_arc();
+        let Some(entry) =
+            self.selection_history
+                .undo_stack
+                .iter()
+                .rev()
+                .find(|entry| {
+                    entry.selections != current_selections
+                        && (entry.selections.len() > 1
+                            || entry.selections.iter().any(|selection| {
+                                selection.start.cmp(&selection.end, &buffer).is_ne()
+                            }))
+                })
+                .cloned()
+        else {
+            return;
+        };
+
+        // Unlike undoing, reselecting is recorded as a regular selection change so it can be
+        // undone in turn.
+        self.with_selection_effects_deferred(window, cx, |this, window, cx| {
+            this.end_selection(window, cx);
+            this.change_selections(
+                SelectionEffects::scroll(Autoscroll::newest()),
+                window,
+                cx,
+                |s| s.select_anchors(entry.selections.to_vec()),
+            );
+        });
+
+        self.select_next_state = entry.select_next_state;
+        self.select_prev_state = entry.select_prev_state;
+        self.add_selections_state = entry.add_selections_state;
+    }
+
     pub fn expand_excerpts(
         &mut self,
         action: &ExpandExcerpts,
@@ -15920,6 +18039,58 @@ impl Editor {
         self.expand_excerpts_for_direction(action.lines, ExpandExcerptDirection::Up, cx)
     }
 
+    pub fn expand_excerpts_to_function(
+        &mut self,
+        _: &ExpandExcerptsToFunction,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut expansions = HashMap::<ExcerptId, (u32, u32)>::default();
+        for selection in self.selections.all::<usize>(cx) {
+            let head = selection.head();
+            let Some(mut excerpt) = snapshot.excerpt_containing(head..head) else {
+                continue;
+            };
+            let Some(context_range) = snapshot.context_range_for_excerpt(excerpt.id()) else {
+                continue;
+            };
+            let offset = excerpt.map_offset_to_buffer(head);
+            let buffer = excerpt.buffer();
+            // The function usually extends past the excerpt, so look it up in the whole buffer.
+            let Some(function_range) = buffer
+                .text_object_ranges(offset..offset, TreeSitterOptions::default())
+                .filter(|(range, object)| {
+                    *object == TextObject::AroundFunction
+                        && range.start <= offset
+                        && offset <= range.end
+                })
+                .map(|(range, _)| range)
+                .min_by_key(|range| range.len())
+            else {
+                continue;
+            };
+
+            let lines_up = buffer
+                .summary_for_anchor::<Point>(&context_range.start)
+                .row
+                .saturating_sub(buffer.offset_to_point(function_range.start).row);
+            let lines_down = buffer
+                .offset_to_point(function_range.end)
+                .row
+                .saturating_sub(buffer.summary_for_anchor::<Point>(&context_range.end).row);
+            let expansion = expansions.entry(excerpt.id()).or_default();
+            expansion.0 = expansion.0.max(lines_up);
+            expansion.1 = expansion.1.max(lines_down);
+        }
+
+        self.buffer.update(cx, |buffer, cx| {
+            for (excerpt_id, (lines_up, lines_down)) in expansions {
+                buffer.expand_excerpts_by([excerpt_id], lines_up, lines_down, cx);
+            }
+        })
+    }
+
     pub fn expand_excerpts_for_direction(
         &mut self,
         lines: u32,
@@ -16009,6 +18180,7 @@ impl Editor {
         let Some(end) = multibuffer.buffer_point_to_anchor(&buffer, range.end, cx) else {
             return;
         };
+        self.reveal_folded_ranges(&[start..end], cx);
         self.change_selections(
             SelectionEffects::default().nav_history(true),
             window,
@@ -16027,7 +18199,13 @@ impl Editor {
             return;
         }
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
-        self.go_to_diagnostic_impl(Direction::Next, action.severity, window, cx)
+        self.go_to_diagnostic_impl(
+            Direction::Next,
+            action.severity,
+            action.source.as_deref(),
+            window,
+            cx,
+        )
     }
 
     pub fn go_to_prev_diagnostic(
@@ -16040,18 +18218,90 @@ impl Editor {
             return;
         }
         self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
-        self.go_to_diagnostic_impl(Direction::Prev, action.severity, window, cx)
+        self.go_to_diagnostic_impl(
+            Direction::Prev,
+            action.severity,
+            action.source.as_deref(),
+            window,
+            cx,
+        )
+    }
+
+    pub fn go_to_next_error(
+        &mut self,
+        _: &GoToNextError,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.diagnostics_enabled() {
+            return;
+        }
+        self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        self.go_to_diagnostic_impl(
+            Direction::Next,
+            GoToDiagnosticSeverityFilter::Only(GoToDiagnosticSeverity::Error),
+            None,
+            window,
+            cx,
+        )
+    }
+
+    pub fn go_to_previous_error(
+        &mut self,
+        _: &GoToPreviousError,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.diagnostics_enabled() {
+            return;
+        }
+        self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        self.go_to_diagnostic_impl(
+            Direction::Prev,
+            GoToDiagnosticSeverityFilter::Only(GoToDiagnosticSeverity::Error),
+            None,
+            window,
+            cx,
+        )
     }
 
+    /// Moves to the next or previous diagnostic that matches `severity`, and that was reported
+    /// by `source` if given.
     pub fn go_to_diagnostic_impl(
         &mut self,
         direction: Direction,
         severity: GoToDiagnosticSeverityFilter,
+        source: Option<&str>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
         let buffer = self.buffer.read(cx).snapshot(cx);
         let selection = self.selections.newest::<usize>(cx);
+        let from_source = move |diagnostic: &Diagnostic| {
+            source.is_none_or(|source| {
+                diagnostic
+                    .source
+                    .as_deref()
+                    .is_some_and(|diagnostic_source| diagnostic_source.eq_ignore_ascii_case(source))
+            })
+        };
+
+        // Warnings and lesser diagnostics are skipped while there are errors, if so configured.
+        let severity = if ProjectSettings::get_global(cx)
+            .diagnostics
+            .skip_warnings_when_errors_exist
+            && severity.matches(lsp::DiagnosticSeverity::ERROR)
+            && buffer
+                .diagnostics_in_range::<usize>(0..buffer.len())
+                .any(|entry| {
+                    entry.diagnostic.severity == lsp::DiagnosticSeverity::ERROR
+                        && !entry.diagnostic.is_unnecessary
+                        && from_source(entry.diagnostic)
+                }) {
+            GoToDiagnosticSeverityFilter::Only(GoToDiagnosticSeverity::Error)
+        } else {
+            severity
+        };
 
         let mut active_group_id = None;
         if let ActiveDiagnostic::Group(active_group) = &self.active_diagnostics
@@ -16061,28 +18311,27 @@ impl Editor {
         }
 
         fn filtered<'a>(
-            snapshot: EditorSnapshot,
             severity: GoToDiagnosticSeverityFilter,
+            from_source: impl Fn(&Diagnostic) -> bool + 'a,
             diagnostics: impl Iterator<Item = DiagnosticEntryRef<'a, usize>>,
         ) -> impl Iterator<Item = DiagnosticEntryRef<'a, usize>> {
             diagnostics
                 .filter(move |entry| severity.matches(entry.diagnostic.severity))
+                .filter(move |entry| from_source(entry.diagnostic))
                 .filter(|entry| entry.range.start != entry.range.end)
                 .filter(|entry| !entry.diagnostic.is_unnecessary)
-                .filter(move |entry| !snapshot.intersects_fold(entry.range.start))
         }
 
-        let snapshot = self.snapshot(window, cx);
         let before = filtered(
-            snapshot.clone(),
             severity,
+            from_source,
             buffer
                 .diagnostics_in_range(0..selection.start)
                 .filter(|entry| entry.range.start <= selection.start),
         );
         let after = filtered(
-            snapshot,
             severity,
+            from_source,
             buffer
                 .diagnostics_in_range(selection.start..buffer.len())
                 .filter(|entry| entry.range.start >= selection.start),
@@ -16120,6 +18369,7 @@ impl Editor {
         let Some(buffer_id) = buffer.buffer_id_for_anchor(next_diagnostic_start) else {
             return;
         };
+        self.reveal_folded_ranges(&[next_diagnostic.range.clone()], cx);
         self.change_selections(Default::default(), window, cx, |s| {
             s.select_ranges(vec![
                 next_diagnostic.range.start..next_diagnostic.range.start,
@@ -16524,7 +18774,9 @@ impl Editor {
             let url = if let Some(end_pos) = end_position {
                 find_url_from_range(&buffer, start_position..end_pos, cx.clone())
             } else {
-                find_url(&buffer, start_position, cx.clone()).map(|(_, url)| url)
+                find_url(&buffer, start_position, cx.clone())
+                    .or_else(|| find_link_rule_url(&buffer, start_position, cx.clone()))
+                    .map(|(_, url)| url)
             };
 
             if let Some(url) = url {
@@ -16566,12 +18818,8 @@ impl Editor {
         cx.spawn_in(window, async move |_, cx| {
             let result = find_file(&buffer, project, buffer_position, cx).await;
 
-            if let Some((_, path)) = result {
-                workspace
-                    .update_in(cx, |workspace, window, cx| {
-                        workspace.open_resolved_path(path, window, cx)
-                    })?
-                    .await?;
+            if let Some((_, path, position)) = result {
+                open_file_link(workspace, path, position, cx).await?;
             }
             anyhow::Ok(())
         })
@@ -16601,8 +18849,8 @@ impl Editor {
                     first_url_or_file = Some(Either::Left(url));
                     None
                 }
-                HoverLink::File(path) => {
-                    first_url_or_file = Some(Either::Right(path));
+                HoverLink::File(path, position) => {
+                    first_url_or_file = Some(Either::Right((path, position)));
                     None
                 }
             })
@@ -16689,16 +18937,12 @@ impl Editor {
                         cx.update(|_, cx| cx.open_url(&url))?;
                         Ok(Navigated::Yes)
                     }
-                    Some(Either::Right(path)) => {
+                    Some(Either::Right((path, position))) => {
                         let Some(workspace) = workspace else {
                             return Ok(Navigated::No);
                         };
 
-                        workspace
-                            .update_in(cx, |workspace, window, cx| {
-                                workspace.open_resolved_path(path, window, cx)
-                            })?
-                            .await?;
+                        open_file_link(workspace, path, position, cx).await?;
                         Ok(Navigated::Yes)
                     }
                     None => Ok(Navigated::No),
@@ -17138,6 +19382,8 @@ impl Editor {
                         this.clear_background_highlights::<DocumentHighlightWrite>(cx);
                     let read_highlights =
                         this.clear_background_highlights::<DocumentHighlightRead>(cx);
+                    this.clear_highlights::<DocumentHighlightWrite>(cx);
+                    this.clear_highlights::<DocumentHighlightRead>(cx);
                     let ranges = write_highlights
                         .iter()
                         .flat_map(|(_, ranges)| ranges.iter())
@@ -17212,9 +19458,27 @@ impl Editor {
         }))
     }
 
-    pub fn confirm_rename(
+    pub fn confirm_rename(
+        &mut self,
+        _: &ConfirmRename,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        self.confirm_rename_internal(false, window, cx)
+    }
+
+    pub fn confirm_rename_with_preview(
+        &mut self,
+        _: &ConfirmRenameWithPreview,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        self.confirm_rename_internal(true, window, cx)
+    }
+
+    fn confirm_rename_internal(
         &mut self,
-        _: &ConfirmRename,
+        preview: bool,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Option<Task<Result<()>>> {
@@ -17244,19 +19508,41 @@ impl Editor {
 
         Some(cx.spawn_in(window, async move |editor, cx| {
             let project_transaction = rename.await?;
+            let title = format!("Rename: {} → {}", old_name, new_name);
+            if preview {
+                Self::open_project_transaction_preview(workspace, project_transaction, title, cx)
+                    .await?;
+            } else {
+                Self::open_project_transaction(&editor, workspace, project_transaction, title, cx)
+                    .await?;
+            }
+
+            editor.update(cx, |editor, cx| {
+                editor.refresh_document_highlights(cx);
+            })?;
+            Ok(())
+        }))
+    }
+
+    pub fn revert_last_workspace_edit(
+        &mut self,
+        _: &RevertLastWorkspaceEdit,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        let project = self.project.clone()?;
+        let workspace = self.workspace()?.downgrade();
+        let revert = project.update(cx, |project, cx| project.revert_last_workspace_edit(cx));
+        Some(cx.spawn_in(window, async move |editor, cx| {
+            let project_transaction = revert.await?;
             Self::open_project_transaction(
                 &editor,
                 workspace,
                 project_transaction,
-                format!("Rename: {} → {}", old_name, new_name),
+                "Revert Workspace Edit".to_string(),
                 cx,
             )
-            .await?;
-
-            editor.update(cx, |editor, cx| {
-                editor.refresh_document_highlights(cx);
-            })?;
-            Ok(())
+            .await
         }))
     }
 
@@ -17370,26 +19656,7 @@ impl Editor {
         let (buffers, target) = match target {
             FormatTarget::Buffers(buffers) => (buffers, LspFormatTarget::Buffers),
             FormatTarget::Ranges(selection_ranges) => {
-                let multi_buffer = buffer.read(cx);
-                let snapshot = multi_buffer.read(cx);
-                let mut buffers = HashSet::default();
-                let mut buffer_id_to_ranges: BTreeMap<BufferId, Vec<Range<text::Anchor>>> =
-                    BTreeMap::new();
-                for selection_range in selection_ranges {
-                    for (buffer, buffer_range, _) in
-                        snapshot.range_to_buffer_ranges(selection_range)
-                    {
-                        let buffer_id = buffer.remote_id();
-                        let start = buffer.anchor_before(buffer_range.start);
-                        let end = buffer.anchor_after(buffer_range.end);
-                        buffers.insert(multi_buffer.buffer(buffer_id).unwrap());
-                        buffer_id_to_ranges
-                            .entry(buffer_id)
-                            .and_modify(|buffer_ranges| buffer_ranges.push(start..end))
-                            .or_insert_with(|| vec![start..end]);
-                    }
-                }
-                (buffers, LspFormatTarget::Ranges(buffer_id_to_ranges))
+                self.lsp_format_target_for_ranges(selection_ranges, cx)
             }
         };
 
@@ -17448,6 +19715,30 @@ impl Editor {
         })
     }
 
+    fn lsp_format_target_for_ranges<T: ToOffset>(
+        &self,
+        ranges: Vec<Range<T>>,
+        cx: &App,
+    ) -> (HashSet<Entity<Buffer>>, LspFormatTarget) {
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.read(cx);
+        let mut buffers = HashSet::default();
+        let mut buffer_id_to_ranges: BTreeMap<BufferId, Vec<Range<text::Anchor>>> = BTreeMap::new();
+        for range in ranges {
+            for (buffer, buffer_range, _) in snapshot.range_to_buffer_ranges(range) {
+                let buffer_id = buffer.remote_id();
+                let start = buffer.anchor_before(buffer_range.start);
+                let end = buffer.anchor_after(buffer_range.end);
+                buffers.insert(multi_buffer.buffer(buffer_id).unwrap());
+                buffer_id_to_ranges
+                    .entry(buffer_id)
+                    .and_modify(|buffer_ranges| buffer_ranges.push(start..end))
+                    .or_insert_with(|| vec![start..end]);
+            }
+        }
+        (buffers, LspFormatTarget::Ranges(buffer_id_to_ranges))
+    }
+
     fn organize_imports(
         &mut self,
         _: &OrganizeImports,
@@ -18330,6 +20621,59 @@ impl Editor {
         self.fold_at_level(&actions::FoldAtLevel(9), window, cx);
     }
 
+    fn unfold_at_level(
+        &mut self,
+        unfold_at: &UnfoldAtLevel,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.buffer.read(cx).is_singleton() {
+            return;
+        }
+
+        let unfold_at_level = unfold_at.0;
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let buffer_snapshot = display_map.buffer_snapshot();
+        let mut to_unfold = Vec::new();
+        let mut stack = vec![(0, buffer_snapshot.max_row().0, 1)];
+
+        while let Some((mut start_row, end_row, current_level)) = stack.pop() {
+            while start_row < end_row {
+                let row = MultiBufferRow(start_row);
+                // Folded rows don't produce creases, so look for a fold starting on the row first.
+                let fold_range = display_map
+                    .folds_in_range(Point::new(start_row, 0)..Point::new(start_row + 1, 0))
+                    .map(|fold| fold.range.to_point(buffer_snapshot))
+                    .find(|range| range.start.row == start_row);
+                let range = fold_range.clone().or_else(|| {
+                    display_map
+                        .crease_for_buffer_row(row)
+                        .map(|crease| crease.range().clone())
+                });
+
+                match range {
+                    Some(range) => {
+                        let nested_start_row = range.start.row + 1;
+                        let nested_end_row = range.end.row;
+
+                        if current_level < unfold_at_level {
+                            stack.push((nested_start_row, nested_end_row, current_level + 1));
+                        } else if current_level == unfold_at_level
+                            && let Some(fold_range) = fold_range
+                        {
+                            to_unfold.push(fold_range.start..fold_range.start);
+                        }
+
+                        start_row = nested_end_row + 1;
+                    }
+                    None => start_row += 1,
+                }
+            }
+        }
+
+        self.unfold_ranges(&to_unfold, true, true, cx);
+    }
+
     pub fn fold_all(&mut self, _: &actions::FoldAll, window: &mut Window, cx: &mut Context<Self>) {
         if self.buffer.read(cx).is_singleton() {
             let mut fold_ranges = Vec::new();
@@ -18538,6 +20882,36 @@ impl Editor {
         }
     }
 
+    pub fn fold_all_regions(
+        &mut self,
+        _: &actions::FoldAllRegions,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let placeholder = FoldPlaceholder {
+            type_tag: Some(TypeId::of::<RegionFold>()),
+            ..display_map.fold_placeholder.clone()
+        };
+        let creases = display_map
+            .fold_regions()
+            .into_iter()
+            .map(|range| Crease::simple(range, placeholder.clone()))
+            .collect();
+        self.fold_creases(creases, true, window, cx);
+    }
+
+    pub fn unfold_all_regions(
+        &mut self,
+        _: &actions::UnfoldAllRegions,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let ranges = display_map.fold_regions();
+        self.remove_folds_with_type(&ranges, TypeId::of::<RegionFold>(), true, cx);
+    }
+
     pub fn fold_selected_ranges(
         &mut self,
         _: &FoldSelectedRanges,
@@ -18553,6 +20927,49 @@ impl Editor {
         self.fold_creases(ranges, true, window, cx);
     }
 
+    pub fn toggle_fold_excerpts(
+        &mut self,
+        _: &ToggleFoldExcerpts,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.buffer.read(cx).is_singleton() {
+            return;
+        }
+
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let buffer = display_map.buffer_snapshot();
+        let mut ranges = Vec::<Range<Point>>::new();
+        for selection in self.selections.all::<usize>(cx) {
+            let head = selection.head();
+            let Some(excerpt) = buffer.excerpt_containing(head..head) else {
+                continue;
+            };
+            let start = excerpt.start_anchor().to_point(buffer);
+            let end = excerpt.end_anchor().to_point(buffer);
+            // The first line stays visible to tell the folded excerpts apart.
+            if start.row < end.row {
+                let range = Point::new(start.row, buffer.line_len(MultiBufferRow(start.row)))..end;
+                if !ranges.contains(&range) {
+                    ranges.push(range);
+                }
+            }
+        }
+
+        if ranges
+            .iter()
+            .any(|range| display_map.is_line_folded(MultiBufferRow(range.start.row)))
+        {
+            self.unfold_ranges(&ranges, true, true, cx);
+        } else {
+            let creases = ranges
+                .into_iter()
+                .map(|range| Crease::simple(range, display_map.fold_placeholder.clone()))
+                .collect();
+            self.fold_creases(creases, true, window, cx);
+        }
+    }
+
     pub fn fold_ranges<T: ToOffset + Clone>(
         &mut self,
         ranges: Vec<Range<T>>,
@@ -18863,6 +21280,122 @@ impl Editor {
         }
     }
 
+    pub fn stage_hunk_at_cursor(
+        &mut self,
+        _: &StageHunkAtCursor,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ranges = self.selections.disjoint_anchor_ranges().collect();
+        self.stage_or_unstage_diff_hunks(true, ranges, cx);
+    }
+
+    pub fn unstage_hunk_at_cursor(
+        &mut self,
+        _: &UnstageHunkAtCursor,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ranges = self.selections.disjoint_anchor_ranges().collect();
+        self.stage_or_unstage_diff_hunks(false, ranges, cx);
+    }
+
+    /// Opens a menu to stage, unstage, restore or expand the diff hunk whose gutter indicator was
+    /// right-clicked.
+    fn deploy_diff_hunk_context_menu(
+        &mut self,
+        hunk_range: Range<Anchor>,
+        clicked_point: gpui::Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let Some(hunk) = self
+            .diff_hunks_in_ranges(&[hunk_range.clone()], &snapshot)
+            .next()
+        else {
+            return;
+        };
+        let stage = hunk.status().has_secondary_hunk();
+        let is_created_file = hunk.is_created_file();
+        let is_expanded = self
+            .buffer
+            .read(cx)
+            .has_expanded_diff_hunks_in_ranges(&[hunk_range.clone()], cx);
+        let hunk_start = hunk_range.start;
+        let weak_editor = cx.weak_entity();
+        let focus_handle = self.focus_handle(cx);
+
+        let context_menu = ui::ContextMenu::build(window, cx, |menu, _, _| {
+            let (stage_label, stage_action): (_, Box<dyn Action>) = if stage {
+                ("Stage Hunk", Box::new(StageHunkAtCursor))
+            } else {
+                ("Unstage Hunk", Box::new(UnstageHunkAtCursor))
+            };
+            menu.context(focus_handle)
+                .entry(stage_label, Some(stage_action), {
+                    let weak_editor = weak_editor.clone();
+                    move |_, cx| {
+                        weak_editor
+                            .update(cx, |editor, cx| {
+                                editor.stage_or_unstage_diff_hunks(
+                                    stage,
+                                    vec![hunk_start..hunk_start],
+                                    cx,
+                                );
+                            })
+                            .ok();
+                    }
+                })
+                .item(
+                    ui::ContextMenuEntry::new("Restore Hunk")
+                        .action(Box::new(::git::Restore))
+                        .disabled(is_created_file)
+                        .handler({
+                            let weak_editor = weak_editor.clone();
+                            move |window, cx| {
+                                weak_editor
+                                    .update(cx, |editor, cx| {
+                                        let snapshot = editor.buffer.read(cx).snapshot(cx);
+                                        let point = hunk_start.to_point(&snapshot);
+                                        editor.restore_hunks_in_ranges(
+                                            vec![point..point],
+                                            window,
+                                            cx,
+                                        );
+                                    })
+                                    .ok();
+                            }
+                        }),
+                )
+                .separator()
+                .entry(
+                    if is_expanded {
+                        "Hide Diff"
+                    } else {
+                        "Show Diff"
+                    },
+                    Some(Box::new(ToggleSelectedDiffHunks)),
+                    move |_, cx| {
+                        weak_editor
+                            .update(cx, |editor, cx| {
+                                editor.toggle_single_diff_hunk(hunk_range.clone(), cx);
+                            })
+                            .ok();
+                    },
+                )
+        });
+
+        self.mouse_context_menu = MouseContextMenu::pinned_to_editor(
+            self,
+            hunk_start,
+            clicked_point,
+            context_menu,
+            window,
+            cx,
+        );
+    }
+
     fn do_stage_or_unstage_and_next(
         &mut self,
         stage: bool,
@@ -18931,6 +21464,8 @@ impl Editor {
                         diff_base_byte_range: hunk.diff_base_byte_range,
                         secondary_status: hunk.secondary_status,
                         range: Point::zero()..Point::zero(), // unused
+                        buffer_word_diffs: hunk.buffer_word_diffs,
+                        base_word_diffs: hunk.base_word_diffs,
                     })
                     .collect::<Vec<_>>(),
                 &buffer_snapshot,
@@ -19290,9 +21825,25 @@ impl Editor {
         wrap_guides
     }
 
+    /// The column after which the text of longer lines is highlighted, when
+    /// `highlight_line_length_overflow` is enabled.
+    pub fn line_length_overflow_column(&self, cx: &App) -> Option<usize> {
+        if !self.mode.is_full() {
+            return None;
+        }
+        let settings = self.buffer.read(cx).language_settings(cx);
+        settings
+            .highlight_line_length_overflow
+            .then_some(settings.preferred_line_length as usize)
+    }
+
     pub fn soft_wrap_mode(&self, cx: &App) -> SoftWrap {
         let settings = self.buffer.read(cx).language_settings(cx);
-        let mode = self.soft_wrap_mode_override.unwrap_or(settings.soft_wrap);
+        let mode = match self.soft_wrap_mode_override {
+            Some(mode) => mode,
+            None if self.large_file_mode(cx) => language_settings::SoftWrap::None,
+            None => settings.soft_wrap,
+        };
         match mode {
             language_settings::SoftWrap::PreferLine | language_settings::SoftWrap::None => {
                 SoftWrap::None
@@ -19307,6 +21858,22 @@ impl Editor {
         }
     }
 
+    /// Whether this editor's buffer is in large file mode, in which
+    /// expensive features such as syntax highlighting are disabled.
+    pub fn large_file_mode(&self, cx: &App) -> bool {
+        self.buffer
+            .read(cx)
+            .as_singleton()
+            .is_some_and(|buffer| buffer.read(cx).large_file_mode())
+    }
+
+    /// Leaves large file mode, re-enabling all features for this editor's buffer.
+    pub fn disable_large_file_mode(&mut self, cx: &mut Context<Self>) {
+        if let Some(buffer) = self.buffer.read(cx).as_singleton() {
+            buffer.update(cx, |buffer, cx| buffer.set_large_file_mode(false, cx));
+        }
+    }
+
     pub fn set_soft_wrap_mode(
         &mut self,
         mode: language_settings::SoftWrap,
@@ -19322,6 +21889,34 @@ impl Editor {
         cx.notify();
     }
 
+    /// Whether the line of the newest cursor has grown past the preferred line length in a
+    /// language that is set to `auto_wrap_at_preferred_line_length`, and so should be rewrapped.
+    fn auto_wrap_on_type(&self, cx: &mut Context<Self>) -> bool {
+        if self.mode.is_single_line() {
+            return false;
+        }
+        let head = self.selections.newest::<Point>(cx).head();
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let settings = snapshot.language_settings_at(head, cx);
+        settings.auto_wrap_at_preferred_line_length
+            && snapshot.line_len(MultiBufferRow(head.row)) > settings.preferred_line_length
+    }
+
+    /// Applies the wrap indicator and indentation settings to the continuation lines of
+    /// soft-wrapped lines.
+    fn refresh_wrap_style(&mut self, cx: &mut Context<Self>) {
+        if !self.mode.is_full() {
+            return;
+        }
+        let settings = EditorSettings::get_global(cx);
+        let wrap_style = WrapStyle {
+            indent: settings.wrap_indent,
+            indicator: settings.wrap_indicator,
+        };
+        self.display_map
+            .update(cx, |map, cx| map.set_wrap_style(wrap_style, cx));
+    }
+
     pub fn set_text_style_refinement(&mut self, style: TextStyleRefinement) {
         self.text_style_refinement = Some(style);
     }
@@ -19436,7 +22031,7 @@ impl Editor {
 
     pub fn should_use_relative_line_numbers(&self, cx: &mut App) -> bool {
         self.use_relative_line_numbers
-            .unwrap_or(EditorSettings::get_global(cx).relative_line_numbers)
+            .unwrap_or(EditorSettings::get_global(cx).line_numbers != LineNumbers::Absolute)
     }
 
     pub fn toggle_relative_line_numbers(
@@ -19454,6 +22049,31 @@ impl Editor {
         cx.notify();
     }
 
+    pub fn typewriter_scrolling_enabled(&self, cx: &App) -> bool {
+        self.mode.is_full()
+            && self
+                .typewriter_scrolling
+                .unwrap_or(EditorSettings::get_global(cx).typewriter_scrolling.enabled)
+    }
+
+    pub fn toggle_typewriter_scrolling(
+        &mut self,
+        _: &ToggleTypewriterScrolling,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let enabled = self.typewriter_scrolling_enabled(cx);
+        self.set_typewriter_scrolling(Some(!enabled), cx)
+    }
+
+    pub fn set_typewriter_scrolling(&mut self, enabled: Option<bool>, cx: &mut Context<Self>) {
+        self.typewriter_scrolling = enabled;
+        if self.typewriter_scrolling_enabled(cx) {
+            self.request_autoscroll(Autoscroll::newest(), cx);
+        }
+        cx.notify();
+    }
+
     pub fn set_show_gutter(&mut self, show_gutter: bool, cx: &mut Context<Self>) {
         self.show_gutter = show_gutter;
         cx.notify();
@@ -19789,6 +22409,34 @@ impl Editor {
         self.git_blame_inline_enabled
     }
 
+    pub fn toggle_inline_diff(
+        &mut self,
+        _: &ToggleInlineDiff,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Diff views expand all of their hunks on their own, and always show them.
+        if !self.inline_diff_enabled && self.buffer.read(cx).all_diff_hunks_expanded() {
+            return;
+        }
+        self.inline_diff_enabled = !self.inline_diff_enabled;
+        let enabled = self.inline_diff_enabled;
+        self.buffer.update(cx, |buffer, cx| {
+            if enabled {
+                buffer.set_all_diff_hunks_expanded(cx);
+            } else {
+                buffer.set_all_diff_hunks_collapsed(cx);
+            }
+        });
+        cx.notify();
+    }
+
+    /// Whether every diff hunk is expanded, with hunks that appear as the buffer is edited or
+    /// its diff base changes expanded too.
+    pub fn inline_diff_enabled(&self) -> bool {
+        self.inline_diff_enabled
+    }
+
     pub fn toggle_selection_menu(
         &mut self,
         _: &ToggleSelectionMenu,
@@ -19976,17 +22624,69 @@ impl Editor {
         .detach();
     }
 
-    pub fn copy_file_location(
+    pub fn copy_file_location(
+        &mut self,
+        _: &CopyFileLocation,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let selection = self.selections.newest::<Point>(cx).start.row + 1;
+        if let Some(file) = self.target_file(cx) {
+            let path = file.path().display(file.path_style(cx));
+            cx.write_to_clipboard(ClipboardItem::new_string(format!("{path}:{selection}")));
+        }
+    }
+
+    /// Copies the names of the symbols containing the cursor, such as `module::Type::method`.
+    pub fn copy_breadcrumb_path(
+        &mut self,
+        _: &CopyBreadcrumbPath,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let cursor = self.selections.newest_anchor().head();
+        let Some((_, symbols)) = self.buffer.read(cx).symbols_containing(cursor, None, cx) else {
+            return;
+        };
+        let path = symbols
+            .iter()
+            .map(|symbol| {
+                // For items with several names, like `impl Trait for Type`, the last one is the
+                // one the nested symbols belong to.
+                symbol
+                    .name_ranges
+                    .last()
+                    .map_or(symbol.text.as_str(), |range| &symbol.text[range.clone()])
+            })
+            .join("::");
+        if !path.is_empty() {
+            cx.write_to_clipboard(ClipboardItem::new_string(path));
+        }
+    }
+
+    /// Get the ranges of the symbols shown in the breadcrumbs, from the outermost to the innermost.
+    pub fn breadcrumb_symbol_ranges(&self, cx: &App) -> Vec<Range<Anchor>> {
+        let cursor = self.selections.newest_anchor().head();
+        self.buffer
+            .read(cx)
+            .symbols_containing(cursor, None, cx)
+            .map(|(_, symbols)| symbols.into_iter().map(|symbol| symbol.range).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn select_breadcrumb_symbol(
         &mut self,
-        _: &CopyFileLocation,
-        _: &mut Window,
+        range: Range<Anchor>,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let selection = self.selections.newest::<Point>(cx).start.row + 1;
-        if let Some(file) = self.target_file(cx) {
-            let path = file.path().display(file.path_style(cx));
-            cx.write_to_clipboard(ClipboardItem::new_string(format!("{path}:{selection}")));
-        }
+        self.change_selections(
+            SelectionEffects::scroll(Autoscroll::center()),
+            window,
+            cx,
+            |selections| selections.select_anchor_ranges([range]),
+        );
+        window.focus(&self.focus_handle);
     }
 
     pub fn open_permalink_to_line(
@@ -20068,6 +22768,70 @@ impl Editor {
         });
     }
 
+    pub fn insert_timestamp(
+        &mut self,
+        action: &InsertTimestamp,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        use std::fmt::Write as _;
+
+        let now = chrono::Local::now();
+        let timestamp = match &action.format {
+            Some(format) => {
+                let mut timestamp = String::new();
+                // Formatting fails for invalid formats, where `to_string` would panic.
+                if write!(timestamp, "{}", now.format(format)).is_err() {
+                    log::error!("invalid timestamp format {format:?}");
+                    return;
+                }
+                timestamp
+            }
+            None => now.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        };
+
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+        self.transact(window, cx, |this, window, cx| {
+            let edits = this
+                .selections
+                .all::<Point>(cx)
+                .into_iter()
+                .map(|selection| (selection.range(), timestamp.clone()));
+            this.edit(edits, cx);
+            this.refresh_edit_prediction(true, false, window, cx);
+        });
+    }
+
+    pub fn insert_sequence(
+        &mut self,
+        action: &InsertSequence,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let start = action.start.unwrap_or(1);
+        let step = action.step.unwrap_or(1);
+        let width = action.width.unwrap_or(0);
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+        self.transact(window, cx, |this, window, cx| {
+            let edits =
+                this.selections
+                    .all::<Point>(cx)
+                    .into_iter()
+                    .zip(0..)
+                    .map(|(selection, ix)| {
+                        let value = start.saturating_add(step.saturating_mul(ix));
+                        let number = if value < 0 {
+                            format!("-{:0width$}", value.unsigned_abs())
+                        } else {
+                            format!("{value:0width$}")
+                        };
+                        (selection.range(), number)
+                    });
+            this.edit(edits, cx);
+            this.refresh_edit_prediction(true, false, window, cx);
+        });
+    }
+
     pub fn open_selections_in_multibuffer(
         &mut self,
         _: &OpenSelectionsInMultibuffer,
@@ -20311,7 +23075,29 @@ impl Editor {
             ranges,
             |colors| colors.colors().editor_document_highlight_read_background,
             cx,
-        )
+        );
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut ranges = ranges.to_vec();
+        ranges.sort_by(|a, b| a.start.cmp(&b.start, &snapshot));
+        let mut outside_ranges = Vec::with_capacity(ranges.len() + 1);
+        let mut start = Anchor::min();
+        for range in ranges {
+            if start.cmp(&range.start, &snapshot).is_lt() {
+                outside_ranges.push(start..range.start);
+            }
+            if start.cmp(&range.end, &snapshot).is_lt() {
+                start = range.end;
+            }
+        }
+        if start.cmp(&Anchor::max(), &snapshot).is_lt() {
+            outside_ranges.push(start..Anchor::max());
+        }
+        self.highlight_background::<SearchOutsideRange>(
+            &outside_ranges,
+            |colors| colors.colors().editor_subheader_background,
+            cx,
+        );
     }
 
     pub fn set_breadcrumb_header(&mut self, new_header: String) {
@@ -20320,6 +23106,7 @@ impl Editor {
 
     pub fn clear_search_within_ranges(&mut self, cx: &mut Context<Self>) {
         self.clear_background_highlights::<SearchWithinRange>(cx);
+        self.clear_background_highlights::<SearchOutsideRange>(cx);
     }
 
     pub fn highlight_background<T: 'static>(
@@ -20328,12 +23115,12 @@ impl Editor {
         color_fetcher: fn(&Theme) -> Hsla,
         cx: &mut Context<Self>,
     ) {
-        self.background_highlights.insert(
+        self.set_background_highlight(
             HighlightKey::Type(TypeId::of::<T>()),
-            (color_fetcher, Arc::from(ranges)),
+            ranges,
+            color_fetcher,
+            cx,
         );
-        self.scrollbar_marker_state.dirty = true;
-        cx.notify();
     }
 
     pub fn highlight_background_key<T: 'static>(
@@ -20343,10 +23130,45 @@ impl Editor {
         color_fetcher: fn(&Theme) -> Hsla,
         cx: &mut Context<Self>,
     ) {
-        self.background_highlights.insert(
+        self.set_background_highlight(
             HighlightKey::TypePlus(TypeId::of::<T>(), key),
-            (color_fetcher, Arc::from(ranges)),
+            ranges,
+            color_fetcher,
+            cx,
         );
+    }
+
+    /// Replaces the background highlights for `key`. Unchanged ranges are left alone, and the
+    /// existing allocation is reused when the number of ranges stays the same, as these are
+    /// refreshed on every keystroke for document highlights and search results.
+    ///
+    /// Whether the highlights changed is decided from the key and the ranges alone: function
+    /// pointers have no stable identity, so the color fetcher is replaced without comparing it.
+    fn set_background_highlight(
+        &mut self,
+        key: HighlightKey,
+        ranges: &[Range<Anchor>],
+        color_fetcher: fn(&Theme) -> Hsla,
+        cx: &mut Context<Self>,
+    ) {
+        match self.background_highlights.entry(key) {
+            hash_map::Entry::Occupied(mut entry) => {
+                let (existing_color_fetcher, existing_ranges) = entry.get_mut();
+                *existing_color_fetcher = color_fetcher;
+                if **existing_ranges == *ranges {
+                    return;
+                }
+                match Arc::get_mut(existing_ranges) {
+                    Some(existing_ranges) if existing_ranges.len() == ranges.len() => {
+                        existing_ranges.copy_from_slice(ranges)
+                    }
+                    _ => *existing_ranges = Arc::from(ranges),
+                }
+            }
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert((color_fetcher, Arc::from(ranges)));
+            }
+        }
         self.scrollbar_marker_state.dirty = true;
         cx.notify();
     }
@@ -20365,6 +23187,61 @@ impl Editor {
         Some(text_highlights)
     }
 
+    /// Shows a short label in the gutter next to each of the given positions, replacing the
+    /// badges previously set for `T`.
+    pub fn set_gutter_badges<T: 'static>(
+        &mut self,
+        badges: impl Into<Vec<(Anchor, SharedString)>>,
+        cx: &mut Context<Self>,
+    ) {
+        self.gutter_badges.insert(TypeId::of::<T>(), badges.into());
+        cx.notify();
+    }
+
+    pub fn clear_gutter_badges<T: 'static>(&mut self, cx: &mut Context<Self>) {
+        if self.gutter_badges.remove(&TypeId::of::<T>()).is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Get the gutter badges on the display rows in `range`, with the labels of badges sharing a
+    /// row joined together.
+    pub(crate) fn active_gutter_badges(
+        &self,
+        range: Range<DisplayRow>,
+        snapshot: &EditorSnapshot,
+    ) -> HashMap<DisplayRow, SharedString> {
+        let mut badges = BTreeMap::<DisplayRow, Vec<&SharedString>>::new();
+        for (anchor, label) in self.gutter_badges.values().flatten() {
+            let row = anchor.to_display_point(&snapshot.display_snapshot).row();
+            if range.contains(&row) {
+                badges.entry(row).or_default().push(label);
+            }
+        }
+        badges
+            .into_iter()
+            .map(|(row, mut labels)| {
+                labels.sort();
+                labels.dedup();
+                (row, labels.into_iter().join("").into())
+            })
+            .collect()
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn all_gutter_badges(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<(DisplayRow, SharedString)> {
+        let snapshot = self.snapshot(window, cx);
+        let max_row = snapshot.max_point().row().next_row();
+        self.active_gutter_badges(DisplayRow(0)..max_row, &snapshot)
+            .into_iter()
+            .sorted()
+            .collect()
+    }
+
     pub fn highlight_gutter<T: 'static>(
         &mut self,
         ranges: impl Into<Vec<Range<Anchor>>>,
@@ -20497,6 +23374,19 @@ impl Editor {
         res
     }
 
+    #[cfg(feature = "test-support")]
+    pub fn search_outside_range_highlights(&mut self, cx: &mut Context<Self>) -> Vec<Range<Point>> {
+        let snapshot = self.buffer().read(cx).snapshot(cx);
+        self.background_highlights
+            .get(&HighlightKey::Type(TypeId::of::<SearchOutsideRange>()))
+            .map_or(Vec::new(), |(_color, ranges)| {
+                ranges
+                    .iter()
+                    .map(|range| range.start.to_point(&snapshot)..range.end.to_point(&snapshot))
+                    .collect()
+            })
+    }
+
     #[cfg(feature = "test-support")]
     pub fn search_background_highlights(&mut self, cx: &mut Context<Self>) -> Vec<Range<Point>> {
         let snapshot = self.buffer().read(cx).snapshot(cx);
@@ -20676,14 +23566,16 @@ impl Editor {
         style: HighlightStyle,
         cx: &mut Context<Self>,
     ) {
-        self.display_map.update(cx, |map, _| {
+        let changed = self.display_map.update(cx, |map, _| {
             map.highlight_text(
                 HighlightKey::TypePlus(TypeId::of::<T>(), key),
                 ranges,
                 style,
-            );
+            )
         });
-        cx.notify();
+        if changed {
+            cx.notify();
+        }
     }
 
     pub fn highlight_text<T: 'static>(
@@ -20692,10 +23584,12 @@ impl Editor {
         style: HighlightStyle,
         cx: &mut Context<Self>,
     ) {
-        self.display_map.update(cx, |map, _| {
+        let changed = self.display_map.update(cx, |map, _| {
             map.highlight_text(HighlightKey::Type(TypeId::of::<T>()), ranges, style)
         });
-        cx.notify();
+        if changed {
+            cx.notify();
+        }
     }
 
     pub(crate) fn highlight_inlays<T: 'static>(
@@ -20857,8 +23751,17 @@ impl Editor {
                 self.refresh_active_diagnostics(cx);
                 self.refresh_code_actions(window, cx);
                 self.refresh_selected_text_highlights(true, window, cx);
+                self.refresh_pinned_word_highlights(true, window, cx);
                 self.refresh_single_line_folds(window, cx);
-                refresh_matching_bracket_highlights(self, cx);
+                self.refresh_ansi_escapes(window, cx);
+                self.refresh_unicode_highlights(window, cx);
+                self.refresh_custom_highlights(cx);
+                self.refresh_log_file(window, cx);
+                self.refresh_csv_columns(window, cx);
+                self.schedule_unconfirmed_edits_refresh(cx);
+                self.profile(ProfiledSubsystem::MatchingBrackets, |editor| {
+                    refresh_matching_bracket_highlights(editor, cx)
+                });
                 if self.has_active_edit_prediction() {
                     self.update_visible_edit_prediction(window, cx);
                 }
@@ -20951,6 +23854,7 @@ impl Editor {
                     excerpts: excerpts.clone(),
                 });
                 self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
+                self.report_visible_buffer_ranges(cx);
             }
             multi_buffer::Event::ExcerptsRemoved {
                 ids,
@@ -20983,6 +23887,8 @@ impl Editor {
             }
             multi_buffer::Event::Reparsed(buffer_id) => {
                 self.tasks_update_task = Some(self.refresh_runnables(window, cx));
+                self.auto_fold_imports(window, cx);
+                self.refresh_syntax_errors(cx);
                 jsx_tag_auto_close::refresh_enabled_in_any_buffer(self, multibuffer, cx);
 
                 cx.emit(EditorEvent::Reparsed(*buffer_id));
@@ -20993,14 +23899,37 @@ impl Editor {
             multi_buffer::Event::LanguageChanged(buffer_id) => {
                 linked_editing_ranges::refresh_linked_ranges(self, window, cx);
                 jsx_tag_auto_close::refresh_enabled_in_any_buffer(self, multibuffer, cx);
+                self.refresh_custom_highlights(cx);
+                cx.emit(EditorEvent::Reparsed(*buffer_id));
+                cx.notify();
+            }
+            multi_buffer::Event::LargeFileModeChanged(buffer_id) => {
+                self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
                 cx.emit(EditorEvent::Reparsed(*buffer_id));
                 cx.notify();
             }
+            multi_buffer::Event::EditsLockChanged(_) => {
+                cx.emit(EditorEvent::TitleChanged);
+                cx.notify();
+            }
+            multi_buffer::Event::LockedEditsDropped(buffer_id) => {
+                if let Some(buffer) = multibuffer.read(cx).buffer(*buffer_id) {
+                    self.notify_buffer_edits_locked(buffer, cx);
+                }
+            }
             multi_buffer::Event::DirtyChanged => cx.emit(EditorEvent::DirtyChanged),
-            multi_buffer::Event::Saved => cx.emit(EditorEvent::Saved),
-            multi_buffer::Event::FileHandleChanged
-            | multi_buffer::Event::Reloaded
-            | multi_buffer::Event::BufferDiffChanged => cx.emit(EditorEvent::TitleChanged),
+            multi_buffer::Event::Saved => {
+                self.refresh_mixed_line_endings(cx);
+                cx.emit(EditorEvent::Saved);
+            }
+            multi_buffer::Event::Reloaded => {
+                self.follow_log_file(window, cx);
+                self.refresh_mixed_line_endings(cx);
+                cx.emit(EditorEvent::TitleChanged);
+            }
+            multi_buffer::Event::FileHandleChanged | multi_buffer::Event::BufferDiffChanged => {
+                cx.emit(EditorEvent::TitleChanged)
+            }
             multi_buffer::Event::DiagnosticsUpdated => {
                 self.update_diagnostics_state(window, cx);
             }
@@ -21134,6 +24063,13 @@ impl Editor {
             }
             self.refresh_colors(false, None, window, cx);
         }
+        self.refresh_wrap_style(cx);
+        self.refresh_ansi_escapes(window, cx);
+        self.refresh_unicode_highlights(window, cx);
+        self.refresh_custom_highlights(cx);
+        self.refresh_log_file(window, cx);
+        self.refresh_csv_columns(window, cx);
+        self.refresh_syntax_errors(cx);
 
         cx.notify();
     }
@@ -21401,6 +24337,12 @@ impl Editor {
         file.is_none_or(|file| project::File::from_dyn(Some(file)).is_some())
     }
 
+    fn clear_composition_clauses(&mut self, cx: &mut Context<Self>) {
+        self.display_map.update(cx, |display_map, _| {
+            display_map.clear_keyed_highlights(TypeId::of::<InputCompositionClause>())
+        });
+    }
+
     fn marked_text_ranges(&self, cx: &App) -> Option<Vec<Range<OffsetUtf16>>> {
         let snapshot = self.buffer.read(cx).read(cx);
         let (_, ranges) = self.text_highlights::<InputComposition>(cx)?;
@@ -22085,6 +25027,7 @@ fn process_completion_for_edit(
     intent: CompletionIntent,
     buffer: &Entity<Buffer>,
     cursor_position: &text::Anchor,
+    selected_text: &str,
     cx: &mut Context<Editor>,
 ) -> CompletionEdit {
     let buffer = buffer.read(cx);
@@ -22110,7 +25053,17 @@ fn process_completion_for_edit(
         {
             snippet_source = label;
         }
-        match Snippet::parse(&snippet_source).log_err() {
+        let position = cursor_position.to_offset(&buffer_snapshot);
+        let resolve_variable = |name: &str| {
+            snippet_variables::resolve_snippet_variable(
+                name,
+                &buffer_snapshot,
+                position,
+                selected_text,
+                cx,
+            )
+        };
+        match Snippet::parse_with_variables(&snippet_source, &resolve_variable).log_err() {
             Some(parsed_snippet) => (Some(parsed_snippet.clone()), parsed_snippet.text),
             None => (None, completion.new_text.clone()),
         }
@@ -22294,6 +25247,79 @@ fn insert_extra_newline_tree_sitter(buffer: &MultiBufferSnapshot, range: Range<u
             .all(|c| c.is_whitespace() && c != '\n')
 }
 
+/// Finds the named syntax node under `range` and its named sibling in `direction`, returning the
+/// ranges of both. Returns `None` if the node is already the first or last of its siblings.
+fn syntax_node_and_named_sibling(
+    buffer: &MultiBufferSnapshot,
+    range: Range<usize>,
+    direction: Direction,
+) -> Option<(Range<usize>, Range<usize>)> {
+    let (buffer, buffer_range) = match buffer.range_to_buffer_ranges(range.clone()).as_slice() {
+        [(buffer, buffer_range, _)] => (*buffer, buffer_range.clone()),
+        _ => return None,
+    };
+    let buffer_start = range.start - buffer_range.start;
+    let ancestor = buffer.syntax_ancestor(buffer_range.clone())?;
+    let mut node = if buffer_range.is_empty() {
+        // Expand to the outermost node starting at the same position, so that a cursor on a
+        // function name moves the whole call or statement rather than just the name.
+        let mut node = ancestor;
+        while let Some(parent) = node.parent()
+            && parent.parent().is_some()
+            && parent.start_byte() == node.start_byte()
+        {
+            node = parent;
+        }
+        node
+    } else {
+        ancestor
+            .descendant_for_byte_range(buffer_range.start, buffer_range.end)
+            .unwrap_or(ancestor)
+    };
+
+    while !node.is_named() {
+        node = node.parent()?;
+    }
+    let sibling = match direction {
+        Direction::Prev => node.prev_named_sibling(),
+        Direction::Next => node.next_named_sibling(),
+    }?;
+
+    let to_multibuffer = |range: Range<usize>| range.start + buffer_start..range.end + buffer_start;
+    Some((
+        to_multibuffer(node.byte_range()),
+        to_multibuffer(sibling.byte_range()),
+    ))
+}
+
+/// Shifts the indentation of every line of `text` after the first from `from` to `to`, so that
+/// a multi-line node keeps its shape when moved to a line with a different indentation.
+fn reindent_subsequent_lines(text: &str, from: IndentSize, to: IndentSize) -> String {
+    if from.len == to.len || !text.contains('\n') {
+        return text.to_string();
+    }
+
+    let indent_char = match to.kind {
+        IndentKind::Space => ' ',
+        IndentKind::Tab => '\t',
+    };
+    let mut lines = text.split('\n');
+    let mut result = lines.next().unwrap_or_default().to_string();
+    for line in lines {
+        result.push('\n');
+        if line.trim().is_empty() {
+            result.push_str(line);
+        } else if to.len > from.len {
+            result.extend(iter::repeat_n(indent_char, (to.len - from.len) as usize));
+            result.push_str(line);
+        } else {
+            let removable = line.len() - line.trim_start().len();
+            result.push_str(&line[removable.min((from.len - to.len) as usize)..]);
+        }
+    }
+    result
+}
+
 fn update_uncommitted_diff_for_buffer(
     editor: Entity<Editor>,
     project: &Entity<Project>,
@@ -22769,6 +25795,8 @@ pub trait SemanticsProvider {
 
     fn supports_inlay_hints(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool;
 
+    fn supports_document_highlights(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool;
+
     fn document_highlights(
         &self,
         buffer: &Entity<Buffer>,
@@ -23112,6 +26140,8 @@ impl CompletionProvider for Entity<Project> {
     ) -> Task<Result<Vec<CompletionResponse>>> {
         self.update(cx, |project, cx| {
             let snippets = snippet_completions(project, buffer, buffer_position, cx);
+            let postfix_completions =
+                postfix_completions::postfix_completions(buffer, buffer_position, cx);
             let project_completions = project.completions(buffer, buffer_position, options, cx);
             cx.background_spawn(async move {
                 let mut responses = project_completions.await?;
@@ -23119,6 +26149,13 @@ impl CompletionProvider for Entity<Project> {
                 if !snippets.completions.is_empty() {
                     responses.push(snippets);
                 }
+                if !postfix_completions.is_empty() {
+                    responses.push(CompletionResponse {
+                        completions: postfix_completions,
+                        display_options: CompletionDisplayOptions::default(),
+                        is_incomplete: false,
+                    });
+                }
                 Ok(responses)
             })
         })
@@ -23245,6 +26282,14 @@ impl SemanticsProvider for Entity<Project> {
         })
     }
 
+    fn supports_document_highlights(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
+        self.update(cx, |project, cx| {
+            buffer.update(cx, |buffer, cx| {
+                project.any_language_server_supports_document_highlights(buffer, cx)
+            })
+        })
+    }
+
     fn inline_values(
         &self,
         buffer_handle: Entity<Buffer>,
@@ -23561,6 +26606,7 @@ impl EditorSnapshot {
 
         let show_runnables = self.show_runnables.unwrap_or(gutter_settings.runnables);
         let show_breakpoints = self.show_breakpoints.unwrap_or(gutter_settings.breakpoints);
+        let show_bookmarks = gutter_settings.bookmarks && self.has_bookmarks;
 
         let git_blame_entries_width =
             self.git_blame_gutter_max_author_length
@@ -23584,7 +26630,7 @@ impl EditorSnapshot {
         let mut left_padding = git_blame_entries_width.unwrap_or(Pixels::ZERO);
         left_padding += if !is_singleton {
             ch_width * 4.0
-        } else if show_runnables || show_breakpoints {
+        } else if show_runnables || show_breakpoints || show_bookmarks {
             ch_width * 3.0
         } else if show_git_gutter && show_line_numbers {
             ch_width * 2.0
@@ -23658,7 +26704,7 @@ impl EditorSnapshot {
             }
         }
 
-        is_foldable |= self.starts_indent(buffer_row);
+        is_foldable |= self.starts_fold(buffer_row);
 
         if folded || (is_foldable && (row_contains_cursor || self.gutter_hovered)) {
             Some(
@@ -23759,6 +26805,8 @@ pub enum EditorEvent {
     },
     CursorShapeChanged,
     BreadcrumbsChanged,
+    /// The search matches, matching brackets or pinned words highlighted in the editor changed.
+    TransientHighlightsChanged,
     PushedToNavHistory {
         anchor: Anchor,
         is_deactivate: bool,
@@ -23788,16 +26836,19 @@ impl Render for Editor {
                 line_height: relative(settings.buffer_line_height.value()),
                 ..Default::default()
             },
-            EditorMode::Full { .. } | EditorMode::Minimap { .. } => TextStyle {
-                color: cx.theme().colors().editor_foreground,
-                font_family: settings.buffer_font.family.clone(),
-                font_features: settings.buffer_font.features.clone(),
-                font_fallbacks: settings.buffer_font.fallbacks.clone(),
-                font_size: settings.buffer_font_size(cx).into(),
-                font_weight: settings.buffer_font.weight,
-                line_height: relative(settings.buffer_line_height.value()),
-                ..Default::default()
-            },
+            EditorMode::Full { .. } | EditorMode::Minimap { .. } => {
+                let buffer_font = self.buffer_font(cx);
+                TextStyle {
+                    color: cx.theme().colors().editor_foreground,
+                    font_family: buffer_font.family,
+                    font_features: buffer_font.features,
+                    font_fallbacks: buffer_font.fallbacks,
+                    font_size: self.buffer_font_size(cx).into(),
+                    font_weight: buffer_font.weight,
+                    line_height: relative(settings.buffer_line_height.value()),
+                    ..Default::default()
+                }
+            }
         };
         if let Some(text_style_refinement) = &self.text_style_refinement {
             text_style.refine(text_style_refinement)
@@ -23824,6 +26875,9 @@ impl Render for Editor {
                 edit_prediction_styles: make_suggestion_styles(cx),
                 unnecessary_code_fade: ThemeSettings::get_global(cx).unnecessary_code_fade,
                 show_underlines: self.diagnostics_enabled(),
+                show_invisible_placeholders: EditorSettings::get_global(cx)
+                    .unicode_highlights
+                    .invisible_characters,
             },
         )
     }
@@ -23875,6 +26929,7 @@ impl EntityInputHandler for Editor {
 
     fn unmark_text(&mut self, _: &mut Window, cx: &mut Context<Self>) {
         self.clear_highlights::<InputComposition>(cx);
+        self.clear_composition_clauses(cx);
         self.ime_transaction.take();
     }
 
@@ -23916,6 +26971,7 @@ impl EntityInputHandler for Editor {
                     })
             });
 
+            keyboard_macros::observe_insertion(text, range_to_replace.clone(), cx);
             cx.emit(EditorEvent::InputHandled {
                 utf16_range_to_replace: range_to_replace,
                 text: text.into(),
@@ -24013,6 +27069,8 @@ impl EntityInputHandler for Editor {
                     .collect::<Vec<_>>()
             };
 
+            // Clauses are set again by the platform after the marked text changes.
+            this.clear_composition_clauses(cx);
             if text.is_empty() {
                 this.unmark_text(window, cx);
             } else {
@@ -24071,6 +27129,51 @@ impl EntityInputHandler for Editor {
         }
     }
 
+    fn set_marked_text_clauses(
+        &mut self,
+        clauses: Vec<CompositionClause>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((_, composition_ranges)) = self.text_highlights::<InputComposition>(cx) else {
+            return;
+        };
+        let composition_ranges = composition_ranges.to_vec();
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+
+        // The clause being converted has a thicker underline than the rest of the composition.
+        let mut clause_ranges = [Vec::new(), Vec::new()];
+        for composition_range in &composition_ranges {
+            let composition_start = composition_range.start.to_offset_utf16(&snapshot);
+            let composition_end = composition_range.end.to_offset_utf16(&snapshot);
+            for clause in &clauses {
+                let start = OffsetUtf16(composition_start.0 + clause.range_utf16.start);
+                let end = OffsetUtf16(composition_start.0 + clause.range_utf16.end);
+                let start = snapshot.clip_offset_utf16(start.min(composition_end), Bias::Left);
+                let end = snapshot.clip_offset_utf16(end.min(composition_end), Bias::Right);
+                clause_ranges[clause.is_target as usize]
+                    .push(snapshot.anchor_after(start)..snapshot.anchor_before(end));
+            }
+        }
+
+        self.highlight_text::<InputComposition>(composition_ranges, HighlightStyle::default(), cx);
+        for (key, ranges) in clause_ranges.into_iter().enumerate() {
+            self.highlight_text_key::<InputCompositionClause>(
+                key,
+                ranges,
+                HighlightStyle {
+                    underline: Some(UnderlineStyle {
+                        thickness: px(1. + key as f32),
+                        color: None,
+                        wavy: false,
+                    }),
+                    ..Default::default()
+                },
+                cx,
+            );
+        }
+    }
+
     fn bounds_for_range(
         &mut self,
         range_utf16: Range<usize>,
@@ -24449,8 +27552,125 @@ fn collapse_multiline_range(range: Range<Point>) -> Range<Point> {
         range.start..range.start
     }
 }
-pub struct KillRing(ClipboardItem);
-impl Global for KillRing {}
+
+/// Returns the column that `point` is displayed at, with tabs expanded.
+fn display_column(display_snapshot: &DisplaySnapshot, point: Point) -> u32 {
+    display_snapshot
+        .point_to_display_point(point, Bias::Left)
+        .column()
+}
+
+/// Returns the point displayed at the given column of a row, along with the number of spaces
+/// that need to be appended to the row for it to reach that column.
+fn insertion_point_for_column(
+    display_snapshot: &DisplaySnapshot,
+    row: u32,
+    column: u32,
+) -> (Point, u32) {
+    let buffer = display_snapshot.buffer_snapshot();
+    let line_start = display_snapshot.point_to_display_point(Point::new(row, 0), Bias::Left);
+    let line_end = Point::new(row, buffer.line_len(MultiBufferRow(row)));
+    let display_line_end = display_snapshot.point_to_display_point(line_end, Bias::Left);
+    if display_line_end.row() == line_start.row() && column >= display_line_end.column() {
+        return (line_end, column - display_line_end.column());
+    }
+    let position =
+        display_snapshot.clip_point(DisplayPoint::new(line_start.row(), column), Bias::Left);
+    (
+        display_snapshot.display_point_to_point(position, Bias::Left),
+        0,
+    )
+}
+
+/// Moves the ends of selections on reindented lines by as much as the line's indentation changed
+/// from its entry in `old_indents`, keeping them on the same text.
+fn shift_selections_by_indent_change(
+    selections: &mut [Selection<Point>],
+    old_indents: &HashMap<u32, u32>,
+    buffer: &MultiBufferSnapshot,
+) {
+    for selection in selections {
+        for point in [&mut selection.start, &mut selection.end] {
+            if let Some(old_indent) = old_indents.get(&point.row) {
+                let new_indent = buffer.indent_size_for_line(MultiBufferRow(point.row)).len;
+                point.column = (point.column + new_indent).saturating_sub(*old_indent);
+            }
+        }
+    }
+}
+
+/// Returns the line comment prefix to remove from `next_line` when joining it onto `line`,
+/// if both are comments starting with the same prefix. Neither line includes its indentation.
+fn joined_comment_prefix<'a>(
+    scope: &'a language::LanguageScope,
+    line: &str,
+    next_line: &str,
+) -> Option<&'a str> {
+    let longest_prefix = |text: &str| {
+        scope
+            .line_comment_prefixes()
+            .iter()
+            .map(|prefix| prefix.trim_end())
+            .filter(|prefix| !prefix.is_empty() && text.starts_with(prefix))
+            .max_by_key(|prefix| prefix.len())
+    };
+    let prefix = longest_prefix(line)?;
+    (longest_prefix(next_line) == Some(prefix)).then_some(prefix)
+}
+
+/// Returns the ranges of the words swapped by transposing words at `offset`: the word ending at
+/// or before it, or the word it's in, and the word after that.
+fn words_to_transpose(
+    buffer: &MultiBufferSnapshot,
+    offset: usize,
+) -> Option<(Range<usize>, Range<usize>)> {
+    let classifier = buffer.char_classifier_at(offset);
+    let is_word = |ch: char| classifier.kind(ch) == CharKind::Word;
+
+    // Inside a word, transpose that word with the next one.
+    let mut offset = offset;
+    if buffer.reversed_chars_at(offset).next().is_some_and(is_word) {
+        for ch in buffer.chars_at(offset) {
+            if !is_word(ch) {
+                break;
+            }
+            offset += ch.len_utf8();
+        }
+    }
+
+    let mut first_end = offset;
+    for ch in buffer.reversed_chars_at(offset) {
+        if is_word(ch) {
+            break;
+        }
+        first_end -= ch.len_utf8();
+    }
+    let mut first_start = first_end;
+    for ch in buffer.reversed_chars_at(first_end) {
+        if !is_word(ch) {
+            break;
+        }
+        first_start -= ch.len_utf8();
+    }
+
+    let mut second_start = offset;
+    for ch in buffer.chars_at(offset) {
+        if is_word(ch) {
+            break;
+        }
+        second_start += ch.len_utf8();
+    }
+    let mut second_end = second_start;
+    for ch in buffer.chars_at(second_start) {
+        if !is_word(ch) {
+            break;
+        }
+        second_end += ch.len_utf8();
+    }
+
+    (first_start < first_end && second_start < second_end)
+        .then_some((first_start..first_end, second_start..second_end))
+}
 
 const UPDATE_DEBOUNCE: Duration = Duration::from_millis(50);
 
@@ -24899,3 +28119,26 @@ pub fn multibuffer_context_lines(cx: &App) -> u32 {
         .unwrap_or(2)
         .min(32)
 }
+
+/// Scripts and stylesheets with lines longer than this are treated as minified, and are left out
+/// of multibuffers that gather many files unless asked for, since they're slow to show and rarely
+/// what's looked for.
+pub const MINIFIED_LINE_LEN: u32 = 1_000;
+
+/// The extensions of the files that are minified for shipping. Data files such as JSON and
+/// lockfiles have long lines too, but are often what's looked for.
+const MINIFIED_EXTENSIONS: &[&str] = &["js", "mjs", "cjs", "css"];
+
+/// Whether a buffer holds a minified script or stylesheet.
+pub fn is_minified_buffer(buffer: &Buffer) -> bool {
+    let Some(file_name) = buffer.file().and_then(|file| file.path().file_name()) else {
+        return false;
+    };
+    if file_name.contains(".min.") {
+        return true;
+    }
+    let is_script_or_style = file_name
+        .rsplit_once('.')
+        .is_some_and(|(_, extension)| MINIFIED_EXTENSIONS.contains(&extension));
+    is_script_or_style && buffer.text_summary().longest_row_chars > MINIFIED_LINE_LEN
+}
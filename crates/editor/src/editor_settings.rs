@@ -4,14 +4,17 @@ use std::num::NonZeroU32;
 use gpui::App;
 use language::CursorShape;
 use project::project_settings::DiagnosticSeverity;
+use regex::Regex;
 pub use settings::{
-    CurrentLineHighlight, DisplayIn, DocumentColorsRenderMode, DoubleClickInMultibuffer,
-    GoToDefinitionFallback, HideMouseMode, MinimapThumb, MinimapThumbBorder, MultiCursorModifier,
+    BidiCursorMovement, CurrentLineHighlight, DisplayIn, DocumentColorsRenderMode,
+    DocumentHighlightStyle, DoubleClickInMultibuffer, GoToDefinitionFallback, HideMouseMode,
+    LineNumbers, MinimapThumb, MinimapThumbBorder, MultiCursorModifier, MultiCursorPaste,
     ScrollBeyondLastLine, ScrollbarDiagnostics, SeedQuerySetting, ShowMinimap, SnippetSortOrder,
-    VsCodeSettings,
+    VsCodeSettings, WrapIndent,
 };
 use settings::{Settings, SettingsContent};
 use ui::scrollbars::{ScrollbarVisibility, ShowScrollbar};
+use util::ResultExt as _;
 
 /// Imports from the VSCode settings at
 /// https://code.visualstudio.com/docs/reference/default-settings
@@ -22,6 +25,16 @@ pub struct EditorSettings {
     pub current_line_highlight: CurrentLineHighlight,
     pub selection_highlight: bool,
     pub rounded_selection: bool,
+    pub cursor_ruler: CursorRuler,
+    pub smooth_caret: SmoothCaret,
+    pub todo_highlights: TodoHighlights,
+    pub link_rules: Vec<LinkRule>,
+    pub render_ansi_escapes: bool,
+    pub log_files: LogFiles,
+    pub rainbow_csv: RainbowCsv,
+    pub markdown_tables: MarkdownTables,
+    pub unicode_highlights: UnicodeHighlights,
+    pub document_highlights: DocumentHighlights,
     pub lsp_highlight_debounce: u64,
     pub hover_popover_enabled: bool,
     pub hover_popover_delay: u64,
@@ -29,20 +42,31 @@ pub struct EditorSettings {
     pub scrollbar: Scrollbar,
     pub minimap: Minimap,
     pub gutter: Gutter,
+    pub auto_fold_imports: bool,
+    pub refold_revealed_regions: bool,
+    pub show_syntax_errors: bool,
     pub scroll_beyond_last_line: ScrollBeyondLastLine,
+    /// The character shown at the start of the continuation lines of soft-wrapped lines.
+    pub wrap_indicator: Option<char>,
+    pub wrap_indent: WrapIndent,
     pub vertical_scroll_margin: f64,
     pub autoscroll_on_clicks: bool,
+    pub typewriter_scrolling: TypewriterScrolling,
+    pub smooth_scroll: SmoothScroll,
     pub horizontal_scroll_margin: f32,
     pub scroll_sensitivity: f32,
     pub fast_scroll_sensitivity: f32,
-    pub relative_line_numbers: bool,
+    pub line_numbers: LineNumbers,
     pub seed_search_query_from_cursor: SeedQuerySetting,
     pub use_smartcase_search: bool,
     pub multi_cursor_modifier: MultiCursorModifier,
+    pub bidi_cursor_movement: BidiCursorMovement,
+    pub sticky_end_of_line: bool,
     pub redact_private_values: bool,
     pub expand_excerpt_lines: u32,
     pub excerpt_context_lines: u32,
     pub middle_click_paste: bool,
+    pub multi_cursor_paste: MultiCursorPaste,
     pub double_click_in_multibuffer: DoubleClickInMultibuffer,
     pub search_wrap: bool,
     pub search: SearchSettings,
@@ -57,6 +81,7 @@ pub struct EditorSettings {
     pub drag_and_drop_selection: DragAndDropSelection,
     pub lsp_document_colors: DocumentColorsRenderMode,
     pub minimum_contrast_for_highlights: f32,
+    pub screen_reader_announcements: bool,
 }
 #[derive(Debug, Clone)]
 pub struct Jupyter {
@@ -84,6 +109,7 @@ pub struct Scrollbar {
     pub search_results: bool,
     pub diagnostics: ScrollbarDiagnostics,
     pub cursors: bool,
+    pub overflow_indicators: bool,
     pub axes: ScrollbarAxes,
 }
 
@@ -115,12 +141,40 @@ impl Minimap {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SmoothCaret {
+    /// Whether to animate the cursor between its old and new positions.
+    pub enabled: bool,
+    /// The duration of the cursor animation, in milliseconds.
+    pub duration: u64,
+    /// The number of cursors above which the animation is disabled.
+    pub max_cursors: usize,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TypewriterScrolling {
+    /// Whether to keep the cursor line at a fixed row of the viewport.
+    pub enabled: bool,
+    /// Where in the viewport to keep the cursor line, as a fraction of its height.
+    pub position: f64,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SmoothScroll {
+    /// Whether to animate programmatic and keyboard scrolling.
+    pub enabled: bool,
+    /// The duration of the scroll animation, in milliseconds.
+    pub duration: u64,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Gutter {
     pub min_line_number_digits: usize,
     pub line_numbers: bool,
+    pub bold_active_line_numbers: bool,
     pub runnables: bool,
     pub breakpoints: bool,
+    pub bookmarks: bool,
     pub folds: bool,
 }
 
@@ -152,6 +206,79 @@ pub struct DragAndDropSelection {
     pub delay: u64,
 }
 
+/// Rulers that follow the newest cursor in the editor.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct CursorRuler {
+    /// Whether to show a vertical ruler at the cursor's column.
+    pub column: bool,
+    /// Whether to show a horizontal ruler on the cursor's row.
+    pub row: bool,
+}
+
+/// Highlighting of tags such as `TODO` and `FIXME` in comments.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct TodoHighlights {
+    /// Whether to highlight the tags in comments.
+    pub enabled: bool,
+    /// The tags to highlight, and to list in the project TODOs view.
+    pub tags: Vec<String>,
+}
+
+/// A rule turning text that matches `regex` in comments into a link to `url`.
+#[derive(Clone, Debug)]
+pub struct LinkRule {
+    pub regex: Regex,
+    /// The URL template, in which `$0` and `${1}`, `${2}`, and so on refer to the match.
+    pub url: String,
+}
+
+/// Log file mode, which colors lines by level and follows files that are being appended to.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct LogFiles {
+    /// Whether to recognize log files and color their lines by level.
+    pub enabled: bool,
+    /// Whether to keep the cursor at the end of a log file that is being appended to, when it
+    /// was on the last line.
+    pub follow: bool,
+}
+
+/// Coloring and alignment of the columns of CSV and TSV files.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct RainbowCsv {
+    /// Whether to color every column of CSV and TSV files with its own accent color.
+    pub enabled: bool,
+    /// Whether to pad the columns so that they line up, without editing the file.
+    pub align_columns: bool,
+}
+
+/// Editing of Markdown tables.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct MarkdownTables {
+    /// Whether tab and shift-tab move between table cells, and tables are realigned as they're
+    /// edited.
+    pub enabled: bool,
+}
+
+/// How the occurrences of the symbol under the cursor are highlighted.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct DocumentHighlights {
+    /// How the occurrences where the symbol is read are highlighted.
+    pub read: DocumentHighlightStyle,
+    /// How the occurrences where the symbol is written are highlighted.
+    pub write: DocumentHighlightStyle,
+}
+
+/// Highlighting of invisible, confusable and bidirectional control characters.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct UnicodeHighlights {
+    /// Whether invisible characters are shown as placeholder glyphs.
+    pub invisible_characters: bool,
+    /// Whether characters that look like ASCII letters are highlighted in ASCII identifiers.
+    pub confusables: bool,
+    /// Whether bidirectional control characters that reorder the text are warned about.
+    pub bidi_controls: bool,
+}
+
 /// Default options for buffer and project search items.
 #[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 pub struct SearchSettings {
@@ -185,12 +312,67 @@ impl Settings for EditorSettings {
         let toolbar = editor.toolbar.unwrap();
         let search = editor.search.unwrap();
         let drag_and_drop_selection = editor.drag_and_drop_selection.unwrap();
+        let cursor_ruler = editor.cursor_ruler.unwrap();
+        let smooth_caret = editor.smooth_caret.unwrap();
+        let todo_highlights = editor.todo_highlights.unwrap();
+        let log_files = editor.log_files.unwrap();
+        let rainbow_csv = editor.rainbow_csv.unwrap();
+        let markdown_tables = editor.markdown_tables.unwrap();
+        let unicode_highlights = editor.unicode_highlights.unwrap();
+        let document_highlights = editor.document_highlights.unwrap();
+        let typewriter_scrolling = editor.typewriter_scrolling.unwrap();
+        let smooth_scroll = editor.smooth_scroll.unwrap();
         Self {
             cursor_blink: editor.cursor_blink.unwrap(),
             cursor_shape: editor.cursor_shape.map(Into::into),
             current_line_highlight: editor.current_line_highlight.unwrap(),
             selection_highlight: editor.selection_highlight.unwrap(),
             rounded_selection: editor.rounded_selection.unwrap(),
+            cursor_ruler: CursorRuler {
+                column: cursor_ruler.column.unwrap(),
+                row: cursor_ruler.row.unwrap(),
+            },
+            smooth_caret: SmoothCaret {
+                enabled: smooth_caret.enabled.unwrap(),
+                duration: smooth_caret.duration.unwrap(),
+                max_cursors: smooth_caret.max_cursors.unwrap() as usize,
+            },
+            todo_highlights: TodoHighlights {
+                enabled: todo_highlights.enabled.unwrap(),
+                tags: todo_highlights.tags.unwrap(),
+            },
+            link_rules: editor
+                .link_rules
+                .unwrap()
+                .into_iter()
+                .filter_map(|rule| {
+                    Some(LinkRule {
+                        regex: Regex::new(&rule.pattern).log_err()?,
+                        url: rule.url,
+                    })
+                })
+                .collect(),
+            render_ansi_escapes: editor.render_ansi_escapes.unwrap(),
+            log_files: LogFiles {
+                enabled: log_files.enabled.unwrap(),
+                follow: log_files.follow.unwrap(),
+            },
+            rainbow_csv: RainbowCsv {
+                enabled: rainbow_csv.enabled.unwrap(),
+                align_columns: rainbow_csv.align_columns.unwrap(),
+            },
+            markdown_tables: MarkdownTables {
+                enabled: markdown_tables.enabled.unwrap(),
+            },
+            unicode_highlights: UnicodeHighlights {
+                invisible_characters: unicode_highlights.invisible_characters.unwrap(),
+                confusables: unicode_highlights.confusables.unwrap(),
+                bidi_controls: unicode_highlights.bidi_controls.unwrap(),
+            },
+            document_highlights: DocumentHighlights {
+                read: document_highlights.read.unwrap(),
+                write: document_highlights.write.unwrap(),
+            },
             lsp_highlight_debounce: editor.lsp_highlight_debounce.unwrap(),
             hover_popover_enabled: editor.hover_popover_enabled.unwrap(),
             hover_popover_delay: editor.hover_popover_delay.unwrap(),
@@ -209,6 +391,7 @@ impl Settings for EditorSettings {
                 search_results: scrollbar.search_results.unwrap(),
                 diagnostics: scrollbar.diagnostics.unwrap(),
                 cursors: scrollbar.cursors.unwrap(),
+                overflow_indicators: scrollbar.overflow_indicators.unwrap(),
                 axes: ScrollbarAxes {
                     horizontal: axes.horizontal.unwrap(),
                     vertical: axes.vertical.unwrap(),
@@ -222,27 +405,50 @@ impl Settings for EditorSettings {
                 current_line_highlight: minimap.current_line_highlight,
                 max_width_columns: minimap.max_width_columns.unwrap(),
             },
+            auto_fold_imports: editor.auto_fold_imports.unwrap(),
+            refold_revealed_regions: editor.refold_revealed_regions.unwrap(),
+            show_syntax_errors: editor.show_syntax_errors.unwrap(),
             gutter: Gutter {
                 min_line_number_digits: gutter.min_line_number_digits.unwrap(),
                 line_numbers: gutter.line_numbers.unwrap(),
+                bold_active_line_numbers: gutter.bold_active_line_numbers.unwrap(),
                 runnables: gutter.runnables.unwrap(),
                 breakpoints: gutter.breakpoints.unwrap(),
+                bookmarks: gutter.bookmarks.unwrap(),
                 folds: gutter.folds.unwrap(),
             },
             scroll_beyond_last_line: editor.scroll_beyond_last_line.unwrap(),
+            wrap_indicator: editor
+                .wrap_indicator
+                .and_then(|indicator| indicator.chars().next()),
+            wrap_indent: editor.wrap_indent.unwrap(),
             vertical_scroll_margin: editor.vertical_scroll_margin.unwrap() as f64,
             autoscroll_on_clicks: editor.autoscroll_on_clicks.unwrap(),
+            typewriter_scrolling: TypewriterScrolling {
+                enabled: typewriter_scrolling.enabled.unwrap(),
+                position: (typewriter_scrolling.position.unwrap() as f64).clamp(0., 1.),
+            },
+            smooth_scroll: SmoothScroll {
+                enabled: smooth_scroll.enabled.unwrap(),
+                duration: smooth_scroll.duration.unwrap(),
+            },
             horizontal_scroll_margin: editor.horizontal_scroll_margin.unwrap(),
             scroll_sensitivity: editor.scroll_sensitivity.unwrap(),
             fast_scroll_sensitivity: editor.fast_scroll_sensitivity.unwrap(),
-            relative_line_numbers: editor.relative_line_numbers.unwrap(),
+            line_numbers: match editor.line_numbers.unwrap() {
+                LineNumbers::Absolute if editor.relative_line_numbers.unwrap() => LineNumbers::Both,
+                line_numbers => line_numbers,
+            },
             seed_search_query_from_cursor: editor.seed_search_query_from_cursor.unwrap(),
             use_smartcase_search: editor.use_smartcase_search.unwrap(),
             multi_cursor_modifier: editor.multi_cursor_modifier.unwrap(),
+            bidi_cursor_movement: editor.bidi_cursor_movement.unwrap(),
+            sticky_end_of_line: editor.sticky_end_of_line.unwrap(),
             redact_private_values: editor.redact_private_values.unwrap(),
             expand_excerpt_lines: editor.expand_excerpt_lines.unwrap(),
             excerpt_context_lines: editor.excerpt_context_lines.unwrap(),
             middle_click_paste: editor.middle_click_paste.unwrap(),
+            multi_cursor_paste: editor.multi_cursor_paste.unwrap(),
             double_click_in_multibuffer: editor.double_click_in_multibuffer.unwrap(),
             search_wrap: editor.search_wrap.unwrap(),
             search: SearchSettings {
@@ -268,6 +474,7 @@ impl Settings for EditorSettings {
             },
             lsp_document_colors: editor.lsp_document_colors.unwrap(),
             minimum_contrast_for_highlights: editor.minimum_contrast_for_highlights.unwrap().0,
+            screen_reader_announcements: editor.screen_reader_announcements.unwrap(),
         }
     }
 
@@ -357,6 +564,16 @@ impl Settings for EditorSettings {
                 ScrollBeyondLastLine::Off
             })
         }
+        vscode.enum_setting(
+            "editor.wrappingIndent",
+            &mut current.editor.wrap_indent,
+            |s| match s {
+                "same" => Some(WrapIndent::Same),
+                "indent" | "deepIndent" => Some(WrapIndent::Extra),
+                "none" => Some(WrapIndent::None),
+                _ => None,
+            },
+        );
 
         let mut scrollbar_axes = settings::ScrollbarAxesContent::default();
         vscode.enum_setting(
@@ -403,9 +620,16 @@ impl Settings for EditorSettings {
             "editor.fastScrollSensitivity",
             &mut current.editor.fast_scroll_sensitivity,
         );
-        if Some("relative") == vscode.read_string("editor.lineNumbers") {
-            current.editor.relative_line_numbers = Some(true);
-        }
+        vscode.enum_setting(
+            "editor.lineNumbers",
+            &mut current.editor.line_numbers,
+            |s| match s {
+                "on" => Some(LineNumbers::Absolute),
+                // VS Code shows the line number of the cursor's line in relative mode.
+                "relative" => Some(LineNumbers::Both),
+                _ => None,
+            },
+        );
 
         vscode.enum_setting(
             "editor.find.seedSearchStringFromSelection",
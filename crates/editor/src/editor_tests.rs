@@ -1,6 +1,7 @@
 use super::*;
 use crate::{
     JoinLines,
+    align_selections_prompt::AlignSelectionsPrompt,
     code_context_menus::CodeContextMenu,
     edit_prediction_tests::FakeEditPredictionProvider,
     linked_editing_ranges::LinkedEditingRanges,
@@ -13,7 +14,7 @@ use crate::{
     },
 };
 use buffer_diff::{BufferDiff, DiffHunkSecondaryStatus, DiffHunkStatus, DiffHunkStatusKind};
-use collections::HashMap;
+use collections::{HashMap, IndexMap};
 use futures::{StreamExt, channel::oneshot};
 use gpui::{
     BackgroundExecutor, DismissEvent, Rgba, SemanticVersion, TestAppContext, UpdateGlobal,
@@ -42,8 +43,9 @@ use project::{
 };
 use serde_json::{self, json};
 use settings::{
-    AllLanguageSettingsContent, IndentGuideBackgroundColoring, IndentGuideColoring,
-    ProjectSettingsContent,
+    AllLanguageSettingsContent, DocumentHighlightsContent, FormatOnSave, HighlightRuleContent,
+    IndentGuideBackgroundColoring, IndentGuideColoring, ProjectSettingsContent,
+    SmoothScrollContent, UnicodeHighlightsContent,
 };
 use std::{cell::RefCell, future::Future, rc::Rc, sync::atomic::AtomicBool, time::Instant};
 use std::{
@@ -61,7 +63,7 @@ use util::{
 };
 use workspace::{
     CloseActiveItem, CloseAllItems, CloseOtherItems, MoveItemToPaneInDirection, NavigationEntry,
-    OpenOptions, ViewId,
+    NavigationMode, OpenOptions, ViewId,
     invalid_buffer_view::InvalidBufferView,
     item::{FollowEvent, FollowableItem, Item, ItemHandle, SaveOptions},
     register_project_item,
@@ -380,6 +382,78 @@ fn test_ime_composition(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+fn test_ime_composition_clauses(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let editor = cx.add_window(|window, cx| {
+        let buffer = MultiBuffer::build_simple("", cx);
+        build_editor(buffer, window, cx)
+    });
+
+    fn underlines(
+        editor: &mut Editor,
+        window: &mut Window,
+        cx: &mut Context<Editor>,
+    ) -> Vec<(Pixels, Vec<Range<DisplayPoint>>)> {
+        let mut underlines = editor
+            .all_text_highlights(window, cx)
+            .into_iter()
+            .filter_map(|(style, ranges)| Some((style.underline?.thickness, ranges)))
+            .filter(|(_, ranges)| !ranges.is_empty())
+            .collect::<Vec<_>>();
+        underlines.sort_by_key(|(thickness, _)| *thickness);
+        underlines
+    }
+    let point = |column| DisplayPoint::new(DisplayRow(0), column);
+
+    _ = editor.update(cx, |editor, window, cx| {
+        editor.replace_and_mark_text_in_range(None, "kanji", None, window, cx);
+        assert_eq!(
+            underlines(editor, window, cx),
+            [(px(1.), vec![point(0)..point(5)])]
+        );
+
+        // The clause being converted is underlined more thickly.
+        editor.set_marked_text_clauses(
+            vec![
+                gpui::CompositionClause {
+                    range_utf16: 0..3,
+                    is_target: true,
+                },
+                gpui::CompositionClause {
+                    range_utf16: 3..5,
+                    is_target: false,
+                },
+            ],
+            window,
+            cx,
+        );
+        assert_eq!(
+            underlines(editor, window, cx),
+            [
+                (px(1.), vec![point(3)..point(5)]),
+                (px(2.), vec![point(0)..point(3)])
+            ]
+        );
+        assert_eq!(
+            editor.marked_text_ranges(cx),
+            Some(vec![OffsetUtf16(0)..OffsetUtf16(5)])
+        );
+
+        // New marked text is underlined as a whole until its clauses are set.
+        editor.replace_and_mark_text_in_range(None, "漢字", None, window, cx);
+        assert_eq!(editor.text(cx), "漢字");
+        assert_eq!(
+            underlines(editor, window, cx),
+            [(px(1.), vec![point(0)..point(6)])]
+        );
+
+        editor.unmark_text(window, cx);
+        assert_eq!(underlines(editor, window, cx), []);
+    });
+}
+
 #[gpui::test]
 fn test_selection_with_mouse(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -911,6 +985,56 @@ async fn test_navigation_history(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_jump_list_navigation(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    let project = Project::test(fs, [], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project, window, cx));
+    let editor = workspace
+        .update(cx, |workspace, window, cx| {
+            let buffer = MultiBuffer::build_simple(&sample_text(300, 5, 'a'), cx);
+            let editor = cx.new(|cx| build_editor(buffer, window, cx));
+            workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+            editor
+        })
+        .unwrap();
+    let cursor_row = |cx: &mut TestAppContext| {
+        editor.update(cx, |editor, cx| {
+            editor.selections.newest_display(cx).head().row()
+        })
+    };
+
+    // A small motion is not recorded, but a large one is.
+    _ = workspace.update(cx, |_, window, cx| {
+        editor.update(cx, |editor, cx| {
+            for row in [2, 20] {
+                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.select_display_ranges([DisplayPoint::new(DisplayRow(row), 0)
+                        ..DisplayPoint::new(DisplayRow(row), 0)])
+                });
+            }
+        })
+    });
+
+    for (mode, expected_row) in [
+        (NavigationMode::GoingBack, 2),
+        (NavigationMode::GoingBack, 2),
+        (NavigationMode::GoingForward, 20),
+        (NavigationMode::GoingForward, 20),
+    ] {
+        workspace
+            .update(cx, |workspace, window, cx| {
+                workspace.navigate_jump_list(mode, window, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(cursor_row(cx), DisplayRow(expected_row));
+    }
+}
+
 #[gpui::test]
 fn test_cancel(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -1402,6 +1526,268 @@ fn test_fold_at_level(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+fn test_unfold_at_level(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let text = "
+        class Foo:
+            def a():
+                print(1)
+
+            def b():
+                print(2)
+
+        class Bar:
+            def a():
+                print(1)
+    "
+    .unindent();
+    let editor = cx.add_window(|window, cx| {
+        let buffer = MultiBuffer::build_simple(&text, cx);
+        build_editor(buffer, window, cx)
+    });
+
+    _ = editor.update(cx, |editor, window, cx| {
+        editor.fold_at_level(&FoldAtLevel(2), window, cx);
+        editor.fold_at_level(&FoldAtLevel(1), window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            "
+                class Foo:⋯
+
+                class Bar:⋯
+            "
+            .unindent(),
+        );
+
+        editor.unfold_at_level(&UnfoldAtLevel(1), window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            "
+                class Foo:
+                    def a():⋯
+
+                    def b():⋯
+
+                class Bar:
+                    def a():⋯
+            "
+            .unindent(),
+        );
+
+        editor.unfold_at_level(&UnfoldAtLevel(2), window, cx);
+        assert_eq!(editor.display_text(cx), text);
+    });
+}
+
+#[gpui::test]
+fn test_reveal_folded_ranges(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let text = "
+        class Foo:
+            def a():
+                print(1)
+
+            def b():
+                print(2)
+
+        class Bar:
+            def a():
+                print(1)
+    "
+    .unindent();
+    let editor = cx.add_window(|window, cx| {
+        let buffer = MultiBuffer::build_simple(&text, cx);
+        build_editor(buffer, window, cx)
+    });
+    let folded = "
+        class Foo:⋯
+
+        class Bar:⋯
+    "
+    .unindent();
+    let revealed = "
+        class Foo:
+            def a():
+                print(1)
+
+            def b():⋯
+
+        class Bar:⋯
+    "
+    .unindent();
+
+    // Only the folds around the target are unfolded, and they stay unfolded by default.
+    _ = editor.update(cx, |editor, window, cx| {
+        editor.fold_at_level(&FoldAtLevel(2), window, cx);
+        editor.fold_at_level(&FoldAtLevel(1), window, cx);
+        assert_eq!(editor.display_text(cx), folded);
+
+        editor.go_to_singleton_buffer_point(Point::new(2, 8), window, cx);
+        assert_eq!(editor.display_text(cx), revealed);
+
+        editor.go_to_singleton_buffer_point(Point::new(0, 0), window, cx);
+        assert_eq!(editor.display_text(cx), revealed);
+    });
+
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings.editor.refold_revealed_regions = Some(true);
+            });
+        });
+    });
+
+    // With `refold_revealed_regions`, each fold is folded again once the cursor leaves it.
+    _ = editor.update(cx, |editor, window, cx| {
+        editor.unfold_all(&UnfoldAll, window, cx);
+        editor.fold_at_level(&FoldAtLevel(2), window, cx);
+        editor.fold_at_level(&FoldAtLevel(1), window, cx);
+        editor.go_to_singleton_buffer_point(Point::new(2, 8), window, cx);
+        assert_eq!(editor.display_text(cx), revealed);
+
+        editor.go_to_singleton_buffer_point(Point::new(1, 4), window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            "
+                class Foo:
+                    def a():⋯
+
+                    def b():⋯
+
+                class Bar:⋯
+            "
+            .unindent(),
+        );
+
+        editor.go_to_singleton_buffer_point(Point::new(0, 0), window, cx);
+        assert_eq!(editor.display_text(cx), folded);
+    });
+}
+
+#[gpui::test]
+async fn test_fold_regions(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+    let language = Arc::new(Language::new(
+        LanguageConfig {
+            line_comments: vec!["// ".into()],
+            ..Default::default()
+        },
+        Some(tree_sitter_rust::LANGUAGE.into()),
+    ));
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+    let text = indoc! {"
+        // #region Setup
+        fn a() {}
+        //#region Nested
+        fn b() {}
+        //#endregion
+        // #endregion
+        fn c() {}
+    "};
+    cx.set_state(&format!("{text}ˇ"));
+
+    cx.update_editor(|editor, window, cx| {
+        editor.fold_all_regions(&FoldAllRegions, window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            indoc! {"
+                // #region Setup⋯
+                fn c() {}
+            "}
+        );
+
+        editor.unfold_all_regions(&UnfoldAllRegions, window, cx);
+        assert_eq!(editor.display_text(cx), text);
+
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(2, 0)..Point::new(2, 0)])
+        });
+        editor.fold(&Fold, window, cx);
+        assert_eq!(
+            editor.display_text(cx),
+            indoc! {"
+                // #region Setup
+                fn a() {}
+                //#region Nested⋯
+                // #endregion
+                fn c() {}
+            "}
+        );
+
+        editor.unfold_all_regions(&UnfoldAllRegions, window, cx);
+        assert_eq!(editor.display_text(cx), text);
+
+        // The regions found on each row match those found in a single pass, and are found again
+        // after an edit.
+        let region_rows = |editor: &mut Editor, cx: &mut Context<Editor>| {
+            let snapshot = editor.display_map.update(cx, |map, cx| map.snapshot(cx));
+            let regions = (0..=snapshot.buffer_snapshot().max_row().0)
+                .filter_map(|row| snapshot.fold_region_for_buffer_row(MultiBufferRow(row)))
+                .collect::<Vec<_>>();
+            assert_eq!(regions, snapshot.fold_regions());
+            regions
+                .into_iter()
+                .map(|range| range.start.row..range.end.row)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(region_rows(editor, cx), [0..5, 2..4]);
+        editor.buffer.update(cx, |buffer, cx| {
+            buffer.edit([(Point::new(5, 0)..Point::new(6, 0), "")], None, cx)
+        });
+        assert_eq!(region_rows(editor, cx), [2..4]);
+    });
+}
+
+#[gpui::test]
+async fn test_syntax_folds_after_edits(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+    let language = Arc::new(
+        Language::new(
+            LanguageConfig::default(),
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        )
+        .with_folds_query("(block) @fold")
+        .unwrap(),
+    );
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+    let fold_range = |cx: &mut EditorTestContext| {
+        cx.update_editor(|editor, _, cx| {
+            let snapshot = editor.display_map.update(cx, |map, cx| map.snapshot(cx));
+            snapshot
+                .crease_for_buffer_row(MultiBufferRow(0))
+                .map(|crease| crease.range().clone())
+        })
+    };
+
+    cx.set_state(indoc! {"
+        fn a() {
+            b();ˇ
+        }
+    "});
+    cx.run_until_parked();
+    assert_eq!(
+        fold_range(&mut cx),
+        Some(Point::new(0, 8)..Point::new(1, 8))
+    );
+    // Folds found for the previous text and syntax tree aren't reused.
+    cx.set_state(indoc! {"
+        fn a() {
+            b();
+            c();ˇ
+        }
+    "});
+    cx.run_until_parked();
+    assert_eq!(
+        fold_range(&mut cx),
+        Some(Point::new(0, 8)..Point::new(2, 8))
+    );
+}
+
 #[gpui::test]
 fn test_move_cursor(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -1675,6 +2061,48 @@ fn test_move_cursor_different_line_lengths(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_sticky_end_of_line(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+    let move_to_end = MoveToEndOfLine {
+        stop_at_soft_wraps: true,
+    };
+
+    // Without the setting, moving to the end of a line keeps its column.
+    cx.set_state("abˇc\nabcdef\na\nabcdefgh");
+    cx.update_editor(|e, window, cx| e.move_to_end_of_line(&move_to_end, window, cx));
+    cx.update_editor(|e, window, cx| e.move_down(&MoveDown, window, cx));
+    cx.assert_editor_state("abc\nabcˇdef\na\nabcdefgh");
+
+    cx.update(|_, cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings.editor.sticky_end_of_line = Some(true);
+            });
+        });
+    });
+
+    // With it, the cursor stays at the end of longer and shorter lines.
+    cx.set_state("abˇc\nabcdef\na\nabcdefgh");
+    cx.update_editor(|e, window, cx| e.move_to_end_of_line(&move_to_end, window, cx));
+    cx.update_editor(|e, window, cx| e.move_down(&MoveDown, window, cx));
+    cx.assert_editor_state("abc\nabcdefˇ\na\nabcdefgh");
+    cx.update_editor(|e, window, cx| e.move_down(&MoveDown, window, cx));
+    cx.assert_editor_state("abc\nabcdef\naˇ\nabcdefgh");
+    cx.update_editor(|e, window, cx| e.move_down(&MoveDown, window, cx));
+    cx.assert_editor_state("abc\nabcdef\na\nabcdefghˇ");
+    cx.update_editor(|e, window, cx| e.move_up(&MoveUp, window, cx));
+    cx.update_editor(|e, window, cx| e.move_up(&MoveUp, window, cx));
+    cx.assert_editor_state("abc\nabcdefˇ\na\nabcdefgh");
+
+    // Moving horizontally drops the goal again.
+    cx.update_editor(|e, window, cx| e.move_left(&MoveLeft, window, cx));
+    cx.update_editor(|e, window, cx| e.move_down(&MoveDown, window, cx));
+    cx.update_editor(|e, window, cx| e.move_down(&MoveDown, window, cx));
+    cx.assert_editor_state("abc\nabcdef\na\nabcdeˇfgh");
+}
+
 #[gpui::test]
 fn test_beginning_end_of_line(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -2400,12 +2828,10 @@ async fn test_scroll_page_up_page_down(cx: &mut TestAppContext) {
 }
 
 #[gpui::test]
-async fn test_autoscroll(cx: &mut TestAppContext) {
+async fn test_drag_autoscroll(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
     let mut cx = EditorTestContext::new(cx).await;
-
-    let line_height = cx.update_editor(|editor, window, cx| {
-        editor.set_vertical_scroll_margin(2, cx);
+    let line_height = cx.editor(|editor, window, _| {
         editor
             .style()
             .unwrap()
@@ -2413,7 +2839,134 @@ async fn test_autoscroll(cx: &mut TestAppContext) {
             .line_height_in_pixels(window.rem_size())
     });
     let window = cx.window;
-    cx.simulate_window_resize(window, size(px(1000.), 6. * line_height));
+    cx.simulate_window_resize(window, size(px(1000.), 4. * line_height + px(0.5)));
+    cx.set_state(&format!(
+        "ˇ{}",
+        (0..50).map(|ix| format!("line {ix}\n")).collect::<String>()
+    ));
+
+    cx.update_editor(|editor, window, cx| {
+        editor.select(
+            SelectPhase::Begin {
+                position: DisplayPoint::new(DisplayRow(1), 0),
+                add: false,
+                click_count: 1,
+            },
+            window,
+            cx,
+        );
+        // The mouse is held still below the text.
+        editor.set_drag_autoscroll(gpui::Point::new(0., 30.), 0, window, cx);
+    });
+    for _ in 0..16 {
+        cx.executor().advance_clock(Duration::from_millis(16));
+        cx.run_until_parked();
+    }
+    let scroll_top = cx.update_editor(|editor, window, cx| {
+        let scroll_top = editor.snapshot(window, cx).scroll_position().y;
+        let selection = editor.selections.newest::<Point>(cx);
+        assert!(scroll_top > 5., "scrolled to {scroll_top}");
+        assert_eq!(selection.tail(), Point::new(1, 0));
+        assert!(
+            selection.head().row as f64 > scroll_top,
+            "selection head {:?} is above the scroll top {scroll_top}",
+            selection.head()
+        );
+        scroll_top
+    });
+
+    // Releasing the mouse stops the scrolling.
+    cx.update_editor(|editor, window, cx| editor.select(SelectPhase::End, window, cx));
+    for _ in 0..16 {
+        cx.executor().advance_clock(Duration::from_millis(16));
+        cx.run_until_parked();
+    }
+    cx.update_editor(|editor, window, cx| {
+        assert_eq!(editor.snapshot(window, cx).scroll_position().y, scroll_top);
+    });
+}
+
+#[gpui::test]
+async fn test_smooth_scroll(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings.editor.smooth_scroll = Some(SmoothScrollContent {
+                    enabled: Some(true),
+                    // Long enough for the animation to still be running when asserting.
+                    duration: Some(60_000),
+                });
+            });
+        });
+    });
+    let mut cx = EditorTestContext::new(cx).await;
+    let line_height = cx.editor(|editor, window, _| {
+        editor
+            .style()
+            .unwrap()
+            .text
+            .line_height_in_pixels(window.rem_size())
+    });
+    let window = cx.window;
+    cx.simulate_window_resize(window, size(px(1000.), 4. * line_height + px(0.5)));
+
+    cx.set_state(
+        r#"ˇone
+        two
+        three
+        four
+        five
+        six
+        seven
+        eight
+        nine
+        ten
+        "#,
+    );
+
+    // Paging moves the scroll anchor immediately, while the rendered
+    // position catches up with it from where it was.
+    cx.update_editor(|editor, window, cx| {
+        assert_eq!(editor.scroll_manager.animation_offset(), None);
+        editor.scroll_screen(&ScrollAmount::Page(1.), window, cx);
+        assert_eq!(
+            editor.snapshot(window, cx).scroll_position(),
+            gpui::Point::new(0., 3.)
+        );
+        let animation_offset = editor.scroll_manager.animation_offset().unwrap();
+        assert!(
+            (-3.0..0.).contains(&animation_offset),
+            "unexpected animation offset {animation_offset}"
+        );
+    });
+
+    // Scrolling with the mouse wheel is never animated.
+    cx.update_editor(|editor, window, cx| {
+        editor.apply_scroll_delta(gpui::Point::new(0., 1.), window, cx);
+        assert_eq!(
+            editor.snapshot(window, cx).scroll_position(),
+            gpui::Point::new(0., 4.)
+        );
+        assert_eq!(editor.scroll_manager.animation_offset(), None);
+    });
+}
+
+#[gpui::test]
+async fn test_autoscroll(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    let line_height = cx.update_editor(|editor, window, cx| {
+        editor.set_vertical_scroll_margin(2, cx);
+        editor
+            .style()
+            .unwrap()
+            .text
+            .line_height_in_pixels(window.rem_size())
+    });
+    let window = cx.window;
+    cx.simulate_window_resize(window, size(px(1000.), 6. * line_height));
 
     cx.set_state(
         r#"ˇone
@@ -2482,6 +3035,504 @@ async fn test_autoscroll(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+fn test_scroll_lock(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let short_text = (0..11)
+        .map(|row| row.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let long_text = (0..21)
+        .map(|row| row.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let editor = cx.add_window(|window, cx| {
+        let buffer = MultiBuffer::build_simple(&short_text, cx);
+        build_editor(buffer, window, cx)
+    });
+    let cx = &mut VisualTestContext::from_window(*editor, cx);
+    let editor = editor.root(cx).unwrap();
+    let other_editor = cx.new_window_entity(|window, cx| {
+        let buffer = MultiBuffer::build_simple(&long_text, cx);
+        build_editor(buffer, window, cx)
+    });
+
+    // Locked editors are scrolled to the same row.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.lock_scroll_with(vec![other_editor.clone()], false, window, cx);
+        editor.set_scroll_position(gpui::Point::new(0., 4.), window, cx);
+    });
+    other_editor.update(cx, |other_editor, cx| {
+        assert!(other_editor.is_scroll_locked());
+        assert_eq!(other_editor.scroll_position(cx).y, 4.);
+    });
+
+    // Either editor scrolls the other.
+    other_editor.update_in(cx, |other_editor, window, cx| {
+        other_editor.set_scroll_position(gpui::Point::new(0., 6.), window, cx);
+    });
+    editor.update(cx, |editor, cx| {
+        assert_eq!(editor.scroll_position(cx).y, 6.)
+    });
+
+    // Proportionally locked editors are scrolled to the same fraction of their length.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.lock_scroll_with(vec![other_editor.clone()], true, window, cx);
+        editor.set_scroll_position(gpui::Point::new(0., 5.), window, cx);
+    });
+    other_editor.update(cx, |other_editor, cx| {
+        assert_eq!(other_editor.scroll_position(cx).y, 10.);
+    });
+
+    // Toggling the lock off unlocks both editors.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_scroll_lock(&ToggleScrollLock::default(), window, cx);
+        assert!(!editor.is_scroll_locked());
+        editor.set_scroll_position(gpui::Point::new(0., 1.), window, cx);
+    });
+    other_editor.update(cx, |other_editor, cx| {
+        assert!(!other_editor.is_scroll_locked());
+        assert_eq!(other_editor.scroll_position(cx).y, 10.);
+    });
+}
+
+#[gpui::test]
+async fn test_typewriter_scrolling(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    let line_height = cx.update_editor(|editor, window, cx| {
+        editor.set_vertical_scroll_margin(2, cx);
+        editor
+            .style()
+            .unwrap()
+            .text
+            .line_height_in_pixels(window.rem_size())
+    });
+    let window = cx.window;
+    cx.simulate_window_resize(window, size(px(1000.), 6. * line_height));
+
+    cx.set_state(
+        r#"ˇone
+            two
+            three
+            four
+            five
+            six
+            seven
+            eight
+            nine
+            ten
+        "#,
+    );
+    cx.update_editor(|editor, _, cx| {
+        editor.set_typewriter_scrolling(Some(true), cx);
+    });
+
+    // The cursor cannot be centered at the start of the buffer.
+    cx.update_editor(|editor, window, cx| {
+        editor.move_down(&Default::default(), window, cx);
+        editor.move_down(&Default::default(), window, cx);
+    });
+    cx.update_editor(|editor, window, cx| {
+        assert_eq!(
+            editor.snapshot(window, cx).scroll_position(),
+            gpui::Point::new(0., 0.)
+        );
+    });
+
+    // Once it can, the buffer scrolls underneath the cursor on every move.
+    for expected_scroll_top in [1., 2., 3.] {
+        cx.update_editor(|editor, window, cx| {
+            editor.move_down(&Default::default(), window, cx);
+        });
+        cx.update_editor(|editor, window, cx| {
+            assert_eq!(
+                editor.snapshot(window, cx).scroll_position(),
+                gpui::Point::new(0., expected_scroll_top)
+            );
+        });
+    }
+    cx.update_editor(|editor, window, cx| {
+        editor.move_up(&Default::default(), window, cx);
+    });
+    cx.update_editor(|editor, window, cx| {
+        assert_eq!(
+            editor.snapshot(window, cx).scroll_position(),
+            gpui::Point::new(0., 2.)
+        );
+    });
+
+    // Toggling it off restores the regular, minimal autoscroll.
+    cx.update_editor(|editor, window, cx| {
+        editor.toggle_typewriter_scrolling(&ToggleTypewriterScrolling, window, cx);
+        editor.move_down(&Default::default(), window, cx);
+    });
+    cx.update_editor(|editor, window, cx| {
+        assert!(!editor.typewriter_scrolling_enabled(cx));
+        assert_eq!(
+            editor.snapshot(window, cx).scroll_position(),
+            gpui::Point::new(0., 2.)
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_highlight_rules(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.highlight_rules = Some(vec![
+            HighlightRuleContent {
+                pattern: r"\bdeprecated_\w+".into(),
+                style: "keyword".into(),
+                paths: Vec::new(),
+            },
+            HighlightRuleContent {
+                pattern: "HACK".into(),
+                style: "comment".into(),
+                paths: vec!["docs/**".into()],
+            },
+        ]);
+    });
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("ˇdeprecated_call(); // HACK: not_deprecated_call");
+    cx.run_until_parked();
+
+    // The rule scoped to other paths doesn't apply.
+    cx.update_editor(|editor, window, cx| {
+        let highlights = editor.all_text_highlights(window, cx);
+        assert_eq!(highlights.len(), 1);
+        let (style, ranges) = &highlights[0];
+        assert_eq!(*style, cx.theme().syntax().get("keyword"));
+        assert_eq!(
+            ranges,
+            &[DisplayPoint::new(DisplayRow(0), 0)..DisplayPoint::new(DisplayRow(0), 15)]
+        );
+    });
+
+    // Matches are found again after an edit.
+    cx.simulate_input("deprecated_");
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| {
+        let highlights = editor.all_text_highlights(window, cx);
+        assert_eq!(highlights.len(), 1);
+        assert_eq!(
+            highlights[0].1,
+            &[DisplayPoint::new(DisplayRow(0), 0)..DisplayPoint::new(DisplayRow(0), 26)]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_toggle_ansi_escapes(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("\x1b[1;31mˇerror\x1b[0m: failed");
+
+    // Editable buffers show the raw escape sequences by default.
+    cx.update_editor(|editor, _, cx| {
+        assert!(!editor.ansi_escapes_rendered(cx));
+        assert_eq!(editor.display_text(cx), "\x1b[1;31merror\x1b[0m: failed");
+    });
+
+    cx.update_editor(|editor, window, cx| {
+        editor.toggle_ansi_escapes(&ToggleAnsiEscapes, window, cx);
+    });
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| {
+        assert!(editor.ansi_escapes_rendered(cx));
+        assert_eq!(editor.display_text(cx), "⋯error⋯: failed");
+        let highlights = editor.all_text_highlights(window, cx);
+        assert_eq!(highlights.len(), 1);
+        let (style, ranges) = &highlights[0];
+        assert_eq!(style.color, Some(cx.theme().colors().terminal_ansi_red));
+        assert_eq!(style.font_weight, Some(FontWeight::BOLD));
+        assert_eq!(
+            ranges,
+            &[DisplayPoint::new(DisplayRow(0), 1)..DisplayPoint::new(DisplayRow(0), 6)]
+        );
+    });
+
+    cx.update_editor(|editor, window, cx| {
+        editor.toggle_ansi_escapes(&ToggleAnsiEscapes, window, cx);
+    });
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| {
+        assert_eq!(editor.display_text(cx), "\x1b[1;31merror\x1b[0m: failed");
+        assert!(editor.all_text_highlights(window, cx).is_empty());
+    });
+}
+
+#[gpui::test]
+async fn test_unicode_highlights(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings.editor.unicode_highlights = Some(UnicodeHighlightsContent {
+                    confusables: Some(true),
+                    ..UnicodeHighlightsContent::default()
+                });
+            });
+        });
+    });
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("ˇlet admin = \"\u{202e}x\u{202c}\";\nlet pаss\u{a0}= 1;");
+    cx.run_until_parked();
+
+    cx.update_editor(|editor, _, cx| {
+        // Placeholder glyphs are rendered in place of the text, which is left unchanged.
+        assert_eq!(
+            editor.display_text(cx),
+            "let admin = \"\u{202e}x\u{202c}\";\nlet pаss\u{a0}= 1;"
+        );
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let text = |ranges: Vec<Range<Anchor>>| {
+            ranges
+                .into_iter()
+                .map(|range| snapshot.text_for_range(range).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            text(editor.bidi_control_highlights(cx)),
+            ["\u{202e}x\u{202c}"]
+        );
+        assert_eq!(text(editor.confusable_highlights(cx)), ["а"]);
+    });
+
+    cx.set_state("ˇlet admin = true;");
+    cx.run_until_parked();
+    cx.update_editor(|editor, _, cx| {
+        assert!(editor.bidi_control_highlights(cx).is_empty());
+        assert!(editor.confusable_highlights(cx).is_empty());
+    });
+}
+
+#[gpui::test]
+async fn test_csv_columns(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_file(
+        path!("/data.csv"),
+        "id,name,score\n7,Alice,90\n1234,Bo,8".into(),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/data.csv").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/data.csv"), cx)
+        })
+        .await
+        .unwrap();
+    let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        build_editor_with_project(project.clone(), buffer, window, cx)
+    });
+    cx.run_until_parked();
+
+    editor.update_in(cx, |editor, window, cx| {
+        assert_eq!(editor.csv_delimiter(), Some(','));
+        assert_eq!(editor.all_text_highlights(window, cx).len(), 3);
+        assert_eq!(
+            editor.display_text(cx),
+            "id,name,score\n7,Alice,90\n1234,Bo,8"
+        );
+        editor.toggle_csv_alignment(&ToggleCsvAlignment, window, cx);
+    });
+    cx.run_until_parked();
+
+    editor.update_in(cx, |editor, window, cx| {
+        assert_eq!(
+            editor.display_text(cx),
+            "id  ,name ,score\n7   ,Alice,90\n1234,Bo   ,8"
+        );
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(2, 6)..Point::new(2, 6)])
+        });
+        assert_eq!(
+            editor.csv_column_at_cursor(cx),
+            Some((1, "name".to_string()))
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_markdown_tables(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+    let markdown = languages::language("markdown", tree_sitter_md::LANGUAGE.into());
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(markdown), cx));
+    cx.set_state(indoc! {"
+        Scores:
+
+        | Name | Score |
+        |-|-:|
+        | Alice | 9ˇ |
+
+        Done.
+    "});
+    cx.run_until_parked();
+
+    cx.update_editor(|editor, window, cx| editor.format_table(&FormatTable, window, cx));
+    cx.assert_editor_state(indoc! {"
+        Scores:
+
+        | Name  | Score |
+        | ----- | ----: |
+        | Alice |     9ˇ |
+
+        Done.
+    "});
+
+    // Tab in the last cell adds a row.
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| editor.tab(&Tab, window, cx));
+    cx.assert_editor_state(indoc! {"
+        Scores:
+
+        | Name  | Score |
+        | ----- | ----: |
+        | Alice |     9 |
+        | ˇ      |       |
+
+        Done.
+    "});
+
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| {
+        editor.handle_input("Bob", window, cx);
+        editor.tab(&Tab, window, cx);
+    });
+    cx.assert_editor_state(indoc! {"
+        Scores:
+
+        | Name  | Score |
+        | ----- | ----: |
+        | Alice |     9 |
+        | Bob   |      ˇ |
+
+        Done.
+    "});
+
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| editor.backtab(&Backtab, window, cx));
+    cx.assert_editor_state(indoc! {"
+        Scores:
+
+        | Name  | Score |
+        | ----- | ----: |
+        | Alice |     9 |
+        | «Bobˇ»   |       |
+
+        Done.
+    "});
+
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| {
+        editor.insert_table_column_right(&InsertTableColumnRight, window, cx)
+    });
+    cx.assert_editor_state(indoc! {"
+        Scores:
+
+        | Name  |     | Score |
+        | ----- | --- | ----: |
+        | Alice |     |     9 |
+        | Bob   | ˇ    |       |
+
+        Done.
+    "});
+
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| {
+        editor.insert_table_row_above(&InsertTableRowAbove, window, cx)
+    });
+    cx.assert_editor_state(indoc! {"
+        Scores:
+
+        | Name  |     | Score |
+        | ----- | --- | ----: |
+        | Alice |     |     9 |
+        |       | ˇ    |       |
+        | Bob   |     |       |
+
+        Done.
+    "});
+
+    // Typing a pipe realigns the table.
+    cx.set_state(indoc! {"
+        | a | b |
+        |---|---|
+        | long valueˇ | x |
+    "});
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| editor.handle_input("|", window, cx));
+    cx.assert_editor_state(indoc! {"
+        | a          | b   |     |
+        | ---------- | --- | --- |
+        | long value | ˇ    | x   |
+    "});
+
+    // Outside of tables, tab indents.
+    cx.set_state("Doneˇ");
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| editor.tab(&Tab, window, cx));
+    cx.assert_editor_state("Done  ˇ");
+}
+
+#[gpui::test]
+async fn test_log_level_filters(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+    let text = indoc! {"
+        10:00:00 INFO starting
+        10:00:01 DEBUG config loaded
+        10:00:02 ERROR connection refused
+            at connect (net.rs:10)
+        10:00:03 DEBUG retrying
+        10:00:04 INFO connected"};
+    cx.set_state(&format!("ˇ{text}"));
+    cx.run_until_parked();
+    cx.update_editor(|editor, window, cx| {
+        assert!(editor.is_log_file());
+        let error_color = cx.theme().status().error;
+        assert!(
+            editor
+                .all_text_highlights(window, cx)
+                .iter()
+                .any(|(style, ranges)| style.color == Some(error_color) && ranges.len() == 1),
+            "error lines, including the stack trace, should be highlighted as one range"
+        );
+
+        editor.toggle_log_level(
+            &ToggleLogLevel {
+                level: LogLevel::Debug,
+            },
+            window,
+            cx,
+        );
+    });
+    cx.run_until_parked();
+    cx.update_editor(|editor, _, cx| {
+        assert_eq!(
+            editor.display_text(cx),
+            indoc! {"
+                10:00:00 INFO starting⋯
+                10:00:02 ERROR connection refused
+                    at connect (net.rs:10)⋯
+                10:00:04 INFO connected"}
+        );
+    });
+
+    cx.update_editor(|editor, window, cx| {
+        editor.show_all_log_levels(&ShowAllLogLevels, window, cx);
+    });
+    cx.run_until_parked();
+    cx.update_editor(|editor, _, cx| {
+        assert!(editor.hidden_log_levels().is_empty());
+        assert_eq!(editor.display_text(cx), text);
+    });
+}
+
 #[gpui::test]
 async fn test_move_page_up_page_down(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -4365,6 +5416,20 @@ async fn test_delete(cx: &mut TestAppContext) {
     "});
 }
 
+#[gpui::test]
+async fn test_backspace_and_delete_grapheme_clusters(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("a🇺🇸ˇ b👍🏽ˇ cकिˇ");
+    cx.update_editor(|e, window, cx| e.backspace(&Backspace, window, cx));
+    cx.assert_editor_state("aˇ bˇ cˇ");
+
+    cx.set_state("ˇ👨\u{200d}👩\u{200d}👧a ˇe\u{301}b");
+    cx.update_editor(|e, window, cx| e.delete(&Delete, window, cx));
+    cx.assert_editor_state("ˇa ˇb");
+}
+
 #[gpui::test]
 fn test_delete_line(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -4444,7 +5509,7 @@ fn test_join_lines_with_single_selection(cx: &mut TestAppContext) {
         );
 
         // When on single line, replace newline at end by space
-        editor.join_lines(&JoinLines, window, cx);
+        editor.join_lines(&JoinLines::default(), window, cx);
         assert_eq!(buffer.read(cx).text(), "aaa bbb\nccc\nddd\n\n");
         assert_eq!(
             editor.selections.ranges::<Point>(cx),
@@ -4455,7 +5520,7 @@ fn test_join_lines_with_single_selection(cx: &mut TestAppContext) {
         editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
             s.select_ranges([Point::new(0, 5)..Point::new(2, 2)])
         });
-        editor.join_lines(&JoinLines, window, cx);
+        editor.join_lines(&JoinLines::default(), window, cx);
         assert_eq!(buffer.read(cx).text(), "aaa bbb ccc ddd\n\n");
         assert_eq!(
             editor.selections.ranges::<Point>(cx),
@@ -4474,7 +5539,7 @@ fn test_join_lines_with_single_selection(cx: &mut TestAppContext) {
         editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
             s.select_ranges([Point::new(2, 1)..Point::new(2, 2)])
         });
-        editor.join_lines(&JoinLines, window, cx);
+        editor.join_lines(&JoinLines::default(), window, cx);
         assert_eq!(buffer.read(cx).text(), "aaa bbb\nccc\nddd\n");
         assert_eq!(
             editor.selections.ranges::<Point>(cx),
@@ -4482,7 +5547,7 @@ fn test_join_lines_with_single_selection(cx: &mut TestAppContext) {
         );
 
         // We can remove trailing newlines
-        editor.join_lines(&JoinLines, window, cx);
+        editor.join_lines(&JoinLines::default(), window, cx);
         assert_eq!(buffer.read(cx).text(), "aaa bbb\nccc\nddd");
         assert_eq!(
             editor.selections.ranges::<Point>(cx),
@@ -4490,7 +5555,7 @@ fn test_join_lines_with_single_selection(cx: &mut TestAppContext) {
         );
 
         // We don't blow up on the last line
-        editor.join_lines(&JoinLines, window, cx);
+        editor.join_lines(&JoinLines::default(), window, cx);
         assert_eq!(buffer.read(cx).text(), "aaa bbb\nccc\nddd");
         assert_eq!(
             editor.selections.ranges::<Point>(cx),
@@ -4514,15 +5579,15 @@ fn test_join_lines_with_single_selection(cx: &mut TestAppContext) {
         editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
             s.select_ranges([Point::new(0, 1)..Point::new(0, 1)])
         });
-        editor.join_lines(&JoinLines, window, cx);
+        editor.join_lines(&JoinLines::default(), window, cx);
         assert_eq!(buffer.read(cx).text(), "aaa bbb c\n  \n\td");
 
         // We don't insert a space for a line containing only spaces
-        editor.join_lines(&JoinLines, window, cx);
+        editor.join_lines(&JoinLines::default(), window, cx);
         assert_eq!(buffer.read(cx).text(), "aaa bbb c\n\td");
 
         // We ignore any leading tabs
-        editor.join_lines(&JoinLines, window, cx);
+        editor.join_lines(&JoinLines::default(), window, cx);
         assert_eq!(buffer.read(cx).text(), "aaa bbb c d");
 
         editor
@@ -4546,7 +5611,7 @@ fn test_join_lines_with_multi_selection(cx: &mut TestAppContext) {
             ])
         });
 
-        editor.join_lines(&JoinLines, window, cx);
+        editor.join_lines(&JoinLines::default(), window, cx);
         assert_eq!(buffer.read(cx).text(), "aaa bbb ccc\nddd\n");
 
         assert_eq!(
@@ -4589,7 +5654,7 @@ async fn test_join_lines_with_git_diff_base(executor: BackgroundExecutor, cx: &m
 
     // Join lines
     cx.update_editor(|editor, window, cx| {
-        editor.join_lines(&JoinLines, window, cx);
+        editor.join_lines(&JoinLines::default(), window, cx);
     });
     executor.run_until_parked();
 
@@ -4603,7 +5668,7 @@ async fn test_join_lines_with_git_diff_base(executor: BackgroundExecutor, cx: &m
     );
     // Join again
     cx.update_editor(|editor, window, cx| {
-        editor.join_lines(&JoinLines, window, cx);
+        editor.join_lines(&JoinLines::default(), window, cx);
     });
     executor.run_until_parked();
 
@@ -4616,6 +5681,88 @@ async fn test_join_lines_with_git_diff_base(executor: BackgroundExecutor, cx: &m
     );
 }
 
+#[gpui::test]
+async fn test_join_lines_with_comments_and_brackets(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let language = Arc::new(Language::new(
+        LanguageConfig {
+            line_comments: vec!["// ".into(), "/// ".into()],
+            brackets: BracketPairConfig {
+                pairs: vec![
+                    BracketPair {
+                        start: "{".to_string(),
+                        end: "}".to_string(),
+                        close: true,
+                        surround: true,
+                        newline: true,
+                    },
+                    BracketPair {
+                        start: "(".to_string(),
+                        end: ")".to_string(),
+                        close: true,
+                        surround: true,
+                        newline: false,
+                    },
+                ],
+                ..BracketPairConfig::default()
+            },
+            ..LanguageConfig::default()
+        },
+        Some(tree_sitter_rust::LANGUAGE.into()),
+    ));
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+
+    // The comment prefix of the joined line is removed, unless it's a different kind of comment.
+    cx.set_state(indoc! {"
+        // oneˇ
+        //   two
+        /// three
+    "});
+    cx.update_editor(|e, window, cx| e.join_lines(&JoinLines::default(), window, cx));
+    cx.assert_editor_state(indoc! {"
+        // oneˇ two
+        /// three
+    "});
+    cx.update_editor(|e, window, cx| e.join_lines(&JoinLines::default(), window, cx));
+    cx.assert_editor_state(indoc! {"
+        // one twoˇ /// three
+    "});
+
+    // No space is put inside parentheses, but braces keep theirs.
+    cx.set_state(indoc! {"
+        «foo(
+            a,  
+            b
+        ) {
+            bar();
+        }ˇ»
+    "});
+    cx.update_editor(|e, window, cx| e.join_lines(&JoinLines::default(), window, cx));
+    cx.assert_editor_state(indoc! {"
+        foo(a, b) { bar();ˇ }
+    "});
+
+    cx.set_state(indoc! {"
+        «one  
+            two
+        threeˇ»
+    "});
+    cx.update_editor(|e, window, cx| {
+        e.join_lines(
+            &JoinLines {
+                separator: Some(", ".into()),
+            },
+            window,
+            cx,
+        )
+    });
+    cx.assert_editor_state(indoc! {"
+        one, twoˇ, three
+    "});
+}
+
 #[gpui::test]
 async fn test_custom_newlines_cause_no_false_positive_diffs(
     executor: BackgroundExecutor,
@@ -4690,6 +5837,27 @@ async fn test_manipulate_immutable_lines_with_single_selection(cx: &mut TestAppC
         123ˇ»
     "});
 
+    // Test sort_lines_numeric()
+    cx.set_state(indoc! {"
+        «10 apples
+        9
+        b
+        -2.5
+          3
+        a
+        1.5ˇ»
+    "});
+    cx.update_editor(|e, window, cx| e.sort_lines_numeric(&SortLinesNumeric, window, cx));
+    cx.assert_editor_state(indoc! {"
+        «b
+        a
+        -2.5
+        1.5
+          3
+        9
+        10 applesˇ»
+    "});
+
     // Test reverse_lines()
     cx.set_state(indoc! {"
         «5
@@ -5531,19 +6699,394 @@ async fn test_manipulate_text(cx: &mut TestAppContext) {
         «HeLlO, wOrLD!ˇ»
     "});
 
-    // Test selections with `line_mode() = true`.
-    cx.update_editor(|editor, _window, _cx| editor.selections.set_line_mode(true));
-    cx.set_state(indoc! {"
-        «The quick brown
-        fox jumps over
-        tˇ»he lazy dog
-    "});
-    cx.update_editor(|e, window, cx| e.convert_to_upper_case(&ConvertToUpperCase, window, cx));
-    cx.assert_editor_state(indoc! {"
-        «THE QUICK BROWN
-        FOX JUMPS OVER
-        THE LAZY DOGˇ»
-    "});
+    // Test selections with `line_mode() = true`.
+    cx.update_editor(|editor, _window, _cx| editor.selections.set_line_mode(true));
+    cx.set_state(indoc! {"
+        «The quick brown
+        fox jumps over
+        tˇ»he lazy dog
+    "});
+    cx.update_editor(|e, window, cx| e.convert_to_upper_case(&ConvertToUpperCase, window, cx));
+    cx.assert_editor_state(indoc! {"
+        «THE QUICK BROWN
+        FOX JUMPS OVER
+        THE LAZY DOGˇ»
+    "});
+}
+
+#[gpui::test]
+async fn test_format_selection_as_json(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.tab_size = NonZeroU32::new(2);
+    });
+    let mut cx = EditorTestContext::new(cx).await;
+
+    // Nested lines keep the indentation of the line the selection starts on.
+    cx.set_state(indoc! {r#"
+        fn main() {
+            let payload = «{"id": 7, "tags": ["a", "b"]}ˇ»;
+        }
+    "#});
+    cx.update_editor(|editor, window, cx| {
+        editor.format_selection_as_json(&FormatSelectionAsJson, window, cx)
+    });
+    cx.assert_editor_state(indoc! {r#"
+        fn main() {
+            let payload = «{
+              "id": 7,
+              "tags": [
+                "a",
+                "b"
+              ]
+            }ˇ»;
+        }
+    "#});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.minify_selection_as_json(&MinifySelectionAsJson, window, cx)
+    });
+    cx.assert_editor_state(indoc! {r#"
+        fn main() {
+            let payload = «{"id":7,"tags":["a","b"]}ˇ»;
+        }
+    "#});
+
+    // Without a selection, the whole buffer is reformatted, and invalid input is left unchanged.
+    cx.set_state("ˇ<a><b>text</b>\n</a>");
+    cx.update_editor(|editor, window, cx| {
+        editor.format_selection_as_xml(&FormatSelectionAsXml, window, cx)
+    });
+    cx.assert_editor_state("«<a>\n  <b>text</b>\n</a>ˇ»");
+    cx.set_state("ˇ{\"a\": }");
+    cx.update_editor(|editor, window, cx| {
+        editor.format_selection_as_json(&FormatSelectionAsJson, window, cx)
+    });
+    cx.assert_editor_state("ˇ{\"a\": }");
+}
+
+#[gpui::test]
+async fn test_cycle_case(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state("let ˇfooBar = «bazQuxˇ»;");
+    let cycle = |cx: &mut EditorTestContext| {
+        cx.update_editor(|e, window, cx| e.cycle_case(&CycleCase, window, cx));
+    };
+    cycle(&mut cx);
+    cx.assert_editor_state("let «FooBarˇ» = «BazQuxˇ»;");
+    cycle(&mut cx);
+    cx.assert_editor_state("let «foo_barˇ» = «baz_quxˇ»;");
+    cycle(&mut cx);
+    cx.assert_editor_state("let «FOO_BARˇ» = «BAZ_QUXˇ»;");
+    cycle(&mut cx);
+    cx.assert_editor_state("let «foo-barˇ» = «baz-quxˇ»;");
+    cycle(&mut cx);
+    cx.assert_editor_state("let «fooBarˇ» = «bazQuxˇ»;");
+}
+
+#[gpui::test]
+async fn test_select_inside_and_around_string(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
+    cx.set_state(indoc! {r#"
+        fn main() {
+            let s = "aˇbc";
+            let t = ˇ1;
+        }
+    "#});
+    cx.run_until_parked();
+    cx.update_editor(|e, window, cx| e.select_inside_string(&SelectInsideString, window, cx));
+    cx.assert_editor_state(indoc! {r#"
+        fn main() {
+            let s = "«abcˇ»";
+            let t = ˇ1;
+        }
+    "#});
+    cx.update_editor(|e, window, cx| e.select_around_string(&SelectAroundString, window, cx));
+    cx.assert_editor_state(indoc! {r#"
+        fn main() {
+            let s = «"abc"ˇ»;
+            let t = ˇ1;
+        }
+    "#});
+    cx.update_editor(|e, window, cx| e.select_around_string(&SelectAroundString, window, cx));
+    cx.assert_editor_state(indoc! {r#"
+        fn main() {
+            let s = «"abc"ˇ»;
+            let t = ˇ1;
+        }
+    "#});
+
+    // Without a syntax tree, quotes are matched on the cursor's line.
+    cx.update_buffer(|buffer, cx| buffer.set_language(None, cx));
+    cx.set_state(r#"say 'hˇi' and "it\'s""#);
+    cx.update_editor(|e, window, cx| e.select_inside_string(&SelectInsideString, window, cx));
+    cx.assert_editor_state(r#"say '«hiˇ»' and "it\'s""#);
+    cx.update_editor(|e, window, cx| e.select_around_string(&SelectAroundString, window, cx));
+    cx.assert_editor_state(r#"say «'hi'ˇ» and "it\'s""#);
+    cx.set_state(r#"say 'hi' and "iˇt\'s""#);
+    cx.update_editor(|e, window, cx| e.select_around_string(&SelectAroundString, window, cx));
+    cx.assert_editor_state(r#"say 'hi' and «"it\'s"ˇ»"#);
+}
+
+#[gpui::test]
+async fn test_gutter_decorations(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    struct TestStatuses;
+    struct Coverage;
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("ˇone\ntwo\nthree");
+    cx.update_editor(|editor, window, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let row_range = |row: u32| {
+            snapshot.anchor_before(Point::new(row, 0))..snapshot.anchor_after(Point::new(row, 3))
+        };
+        editor.set_gutter_decorations::<TestStatuses>(
+            [
+                GutterDecoration::new(row_range(0), IconName::Check).tooltip("Passed"),
+                GutterDecoration::new(row_range(2), IconName::XCircle)
+                    .color(Color::Error)
+                    .on_click(|_, _, _| {}),
+            ],
+            cx,
+        );
+        editor.set_gutter_decorations::<Coverage>(
+            [GutterDecoration::new(row_range(1), IconName::Circle)],
+            cx,
+        );
+        assert_eq!(
+            editor.all_gutter_decorations(window, cx),
+            [
+                (DisplayRow(0), IconName::Check),
+                (DisplayRow(1), IconName::Circle),
+                (DisplayRow(2), IconName::XCircle),
+            ]
+        );
+
+        editor.clear_gutter_decorations::<TestStatuses>(cx);
+        assert_eq!(
+            editor.all_gutter_decorations(window, cx),
+            [(DisplayRow(1), IconName::Circle)]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_virtual_text(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("ˇfn test() {}\nfn other() {}");
+    cx.update_editor(|editor, _, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let ids = editor.insert_virtual_text(
+            [
+                VirtualText::text(
+                    snapshot.anchor_before(Point::new(0, 3)),
+                    VirtualTextPlacement::Inline,
+                    "async ",
+                ),
+                VirtualText::text(
+                    snapshot.anchor_before(Point::new(0, 0)),
+                    VirtualTextPlacement::EndOfLine,
+                    "// passed",
+                ),
+                VirtualText::widget(
+                    snapshot.anchor_before(Point::new(1, 0)),
+                    VirtualTextPlacement::AboveLine,
+                    |_, _| div().into_any_element(),
+                ),
+            ],
+            cx,
+        );
+        assert_eq!(
+            editor.display_text(cx),
+            "fn async test() {} // passed\n\nfn other() {}"
+        );
+
+        editor.remove_virtual_text(ids, cx);
+        assert_eq!(editor.display_text(cx), "fn test() {}\nfn other() {}");
+    });
+}
+
+#[gpui::test]
+async fn test_test_run_annotations(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("ˇfn test() {}\nfn other() {}");
+    let task = TaskTemplate {
+        label: "test".into(),
+        command: "cargo".into(),
+        tags: vec!["rust-test".into()],
+        ..TaskTemplate::default()
+    }
+    .resolve_task("test", &task::TaskContext::default())
+    .unwrap();
+
+    let start_test_run = |cx: &mut EditorTestContext| {
+        let (tx, rx) = oneshot::channel::<bool>();
+        cx.update_editor(|editor, window, cx| {
+            let position = editor
+                .buffer()
+                .read(cx)
+                .snapshot(cx)
+                .anchor_before(Point::zero());
+            let status = cx.background_spawn(async move { rx.await.ok() });
+            editor.start_test_run(
+                TaskSourceKind::UserInput,
+                task.clone(),
+                position,
+                status,
+                window,
+                cx,
+            );
+        });
+        tx
+    };
+
+    let tx = start_test_run(&mut cx);
+    cx.update_editor(|editor, _, cx| {
+        assert_eq!(editor.test_run_results(cx), [(0, None)]);
+        assert_eq!(editor.display_text(cx), "fn test() {} ◼\nfn other() {}");
+    });
+
+    tx.send(true).unwrap();
+    cx.run_until_parked();
+    cx.update_editor(|editor, _, cx| {
+        assert_eq!(editor.test_run_results(cx), [(0, Some(true))]);
+        assert_eq!(editor.display_text(cx), "fn test() {} ◼\nfn other() {}");
+    });
+
+    // Rerunning the test replaces its result, which is removed when the run is cancelled.
+    let tx = start_test_run(&mut cx);
+    cx.update_editor(|editor, _, cx| {
+        assert_eq!(editor.test_run_results(cx), [(0, None)]);
+        assert_eq!(editor.display_text(cx), "fn test() {} ◼\nfn other() {}");
+    });
+    drop(tx);
+    cx.run_until_parked();
+    cx.update_editor(|editor, _, cx| {
+        assert_eq!(editor.test_run_results(cx), []);
+        assert_eq!(editor.display_text(cx), "fn test() {}\nfn other() {}");
+    });
+}
+
+#[gpui::test]
+fn test_drop_selection_from_other_editor(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let source = cx.add_window(|window, cx| {
+        let buffer = MultiBuffer::build_simple("one two three", cx);
+        build_editor(buffer, window, cx)
+    });
+    let target = source
+        .update(cx, |_, window, cx| {
+            cx.new(|cx| build_editor(MultiBuffer::build_simple("four five", cx), window, cx))
+        })
+        .unwrap();
+    let source_editor = source.root(cx).unwrap().downgrade();
+    let selection = source
+        .update(cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges([4..8])
+            });
+            editor.selections.newest_anchor().clone()
+        })
+        .unwrap();
+
+    // Dropping without the copy modifier moves the text between editors.
+    cx.update_window(*source, |_, window, cx| {
+        target.update(cx, |editor, cx| {
+            editor.drop_selection_from_other_editor(
+                &source_editor,
+                &selection,
+                DisplayPoint::new(DisplayRow(0), 5),
+                true,
+                window,
+                cx,
+            );
+        });
+    })
+    .unwrap();
+    assert_eq!(
+        source.update(cx, |editor, _, cx| editor.text(cx)).unwrap(),
+        "one three"
+    );
+    assert_eq!(
+        target.read_with(cx, |editor, cx| editor.text(cx)),
+        "four two five"
+    );
+
+    // Undoing the drop in the target editor only removes the inserted text.
+    cx.update_window(*source, |_, window, cx| {
+        target.update(cx, |editor, cx| editor.undo(&Undo, window, cx));
+    })
+    .unwrap();
+    assert_eq!(
+        target.read_with(cx, |editor, cx| editor.text(cx)),
+        "four five"
+    );
+}
+
+#[gpui::test]
+fn test_drop_selection_from_editor_for_same_buffer(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let buffer = cx.new(|cx| language::Buffer::local("one two three", cx));
+    let source = cx.add_window(|window, cx| {
+        let multibuffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
+        build_editor(multibuffer, window, cx)
+    });
+    let target = source
+        .update(cx, |_, window, cx| {
+            let multibuffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
+            cx.new(|cx| build_editor(multibuffer, window, cx))
+        })
+        .unwrap();
+    let source_editor = source.root(cx).unwrap().downgrade();
+    let selection = source
+        .update(cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges([4..8])
+            });
+            editor.selections.newest_anchor().clone()
+        })
+        .unwrap();
+
+    cx.update_window(*source, |_, window, cx| {
+        target.update(cx, |editor, cx| {
+            editor.drop_selection_from_other_editor(
+                &source_editor,
+                &selection,
+                DisplayPoint::new(DisplayRow(0), 13),
+                true,
+                window,
+                cx,
+            );
+        });
+    })
+    .unwrap();
+    assert_eq!(
+        buffer.read_with(cx, |buffer, _| buffer.text()),
+        "one three two "
+    );
+
+    // Moving text within the buffer is undone in a single step.
+    cx.update_window(*source, |_, window, cx| {
+        target.update(cx, |editor, cx| editor.undo(&Undo, window, cx));
+    })
+    .unwrap();
+    assert_eq!(
+        buffer.read_with(cx, |buffer, _| buffer.text()),
+        "one two three"
+    );
 }
 
 #[gpui::test]
@@ -5671,6 +7214,437 @@ fn test_duplicate_line(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_duplicate_line_and_comment(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
+    cx.set_state(indoc! {"
+        fn main() {
+            let a = ˇ1;
+            «let b = 2;
+
+            let c = 3;ˇ»
+        }
+    "});
+    cx.update_editor(|e, window, cx| {
+        e.duplicate_line_and_comment(&DuplicateLineAndComment, window, cx)
+    });
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            // let a = 1;
+            let a = ˇ1;
+            // let b = 2;
+
+            // let c = 3;
+            «let b = 2;
+
+            let c = 3;ˇ»
+        }
+    "});
+
+    // Duplicating and commenting is undone in one step.
+    cx.update_editor(|e, window, cx| e.undo(&Undo, window, cx));
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let a = ˇ1;
+            «let b = 2;
+
+            let c = 3;ˇ»
+        }
+    "});
+}
+
+#[gpui::test]
+async fn test_copy_with_syntax_highlighting(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
+    cx.set_state("fn main() {\n    «let a = b < {c};ˇ»\n}\n");
+    cx.update_editor(|e, window, cx| {
+        e.copy_with_syntax_highlighting(&CopyWithSyntaxHighlighting, window, cx)
+    });
+
+    let item = cx.read_from_clipboard().unwrap();
+    let Some(ClipboardEntry::String(string)) = item.entries().first() else {
+        panic!("expected a string clipboard entry");
+    };
+    assert_eq!(string.text(), "let a = b < {c};");
+    let html = string.html().unwrap();
+    assert!(html.starts_with("<pre style="));
+    assert!(html.contains("&lt;") && !html.contains(" < "));
+    let rtf = string.rtf().unwrap();
+    assert!(rtf.starts_with("{\\rtf1"));
+    assert!(rtf.contains("\\{") && rtf.contains("\\}"));
+}
+
+#[gpui::test]
+async fn test_copy_as_image(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("fn main() {\n«    let a = 1;\n}ˇ»\n");
+    let action = CopyAsImage {
+        padding: Some(10.),
+        window_chrome: false,
+    };
+    cx.update_editor(|e, window, cx| e.copy_as_image(&action, window, cx));
+
+    let item = cx.read_from_clipboard().unwrap();
+    let Some(ClipboardEntry::Image(png)) = item.entries().first() else {
+        panic!("expected an image clipboard entry");
+    };
+    assert_eq!(png.format, gpui::ImageFormat::Png);
+    let (line_height, scale_factor) = cx.update_editor(|e, window, cx| {
+        (
+            e.buffer_font_size(cx) * ThemeSettings::get_global(cx).line_height(),
+            window.scale_factor(),
+        )
+    });
+    let image = image::load_from_memory(&png.bytes).unwrap();
+    assert_eq!(
+        image.height(),
+        ((f32::from(line_height) * 2. + 20.) * scale_factor).ceil() as u32
+    );
+
+    // Empty selections produce no image.
+    cx.set_state("fn main() {ˇ}");
+    cx.write_to_clipboard(ClipboardItem::new_string("text".into()));
+    cx.update_editor(|e, window, cx| e.copy_as_image(&action, window, cx));
+    assert_eq!(
+        cx.read_from_clipboard().and_then(|item| item.text()),
+        Some("text".into())
+    );
+}
+
+#[gpui::test]
+async fn test_breadcrumb_path(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let language = Arc::new(
+        Language::new(
+            LanguageConfig::default(),
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        )
+        .with_outline_query(
+            r#"
+            (mod_item
+                "mod" @context
+                name: (_) @name) @item
+            (impl_item
+                "impl" @context
+                trait: (_)? @name
+                "for"? @context
+                type: (_) @name) @item
+            (function_item
+                "fn" @context
+                name: (_) @name) @item
+            "#,
+        )
+        .unwrap(),
+    );
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+    cx.set_state(indoc! {"
+        mod module {
+            impl Display for Type {
+                fn method() {
+                    ˇ1
+                }
+            }
+        }
+    "});
+    cx.update_editor(|e, window, cx| e.copy_breadcrumb_path(&CopyBreadcrumbPath, window, cx));
+    assert_eq!(
+        cx.read_from_clipboard().and_then(|item| item.text()),
+        Some("module::Type::method".into())
+    );
+
+    // Clicking a breadcrumb selects its symbol.
+    cx.update_editor(|e, window, cx| {
+        let ranges = e.breadcrumb_symbol_ranges(cx);
+        assert_eq!(ranges.len(), 3);
+        e.select_breadcrumb_symbol(ranges[2].clone(), window, cx);
+    });
+    cx.assert_editor_state(indoc! {"
+        mod module {
+            impl Display for Type {
+                «fn method() {
+                    1
+                }ˇ»
+            }
+        }
+    "});
+}
+
+#[gpui::test]
+async fn test_buffer_as_html_document(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
+    cx.set_state("fn main() {\n    let a = b < c;ˇ\n}");
+
+    let html = cx.update_editor(|e, _, cx| e.buffer_as_html_document(false, cx));
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("b &lt; c"));
+    assert!(!html.contains("class=\"line-number\">"));
+
+    let html = cx.update_editor(|e, _, cx| e.buffer_as_html_document(true, cx));
+    for row in 1..=3 {
+        assert!(html.contains(&format!("<span class=\"line-number\">{row}</span>")));
+    }
+    assert!(!html.contains("<span class=\"line-number\">4</span>"));
+}
+
+#[gpui::test]
+fn test_zoom_editor_font_size(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let buffer = cx.update(|cx| MultiBuffer::build_simple("abc", cx));
+    let editor = cx.add_window(|window, cx| build_editor(buffer.clone(), window, cx));
+    let other_editor = cx.add_window(|window, cx| build_editor(buffer, window, cx));
+    let buffer_font_size = cx.update(|cx| ThemeSettings::get_global(cx).buffer_font_size(cx));
+
+    _ = editor.update(cx, |editor, window, cx| {
+        editor.zoom_in(&ZoomIn, window, cx);
+        editor.zoom_in(&ZoomIn, window, cx);
+        assert_eq!(editor.buffer_font_size(cx), buffer_font_size + px(2.));
+        editor.zoom_out(&ZoomOut, window, cx);
+        assert_eq!(editor.buffer_font_size(cx), buffer_font_size + px(1.));
+    });
+    _ = other_editor.update(cx, |editor, _, cx| {
+        assert_eq!(editor.buffer_font_size(cx), buffer_font_size);
+    });
+
+    // The zoom level follows changes to the global font size.
+    cx.update(|cx| theme::adjust_buffer_font_size(cx, |size| size + px(2.)));
+    _ = editor.update(cx, |editor, window, cx| {
+        assert_eq!(editor.buffer_font_size(cx), buffer_font_size + px(3.));
+        editor.zoom_reset(&ZoomReset, window, cx);
+        assert_eq!(editor.buffer_font_size(cx), buffer_font_size + px(2.));
+    });
+}
+
+#[gpui::test]
+fn test_language_font_overrides(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.languages.0.insert(
+            "Rust".into(),
+            LanguageSettingsContent {
+                font_family: Some(settings::FontFamilyName("Courier".into())),
+                font_features: Some(gpui::FontFeatures::disable_ligatures()),
+                font_size: Some(20.),
+                ..Default::default()
+            },
+        );
+    });
+
+    let rust_buffer = cx.new(|cx| Buffer::local("fn main() {}", cx).with_language(rust_lang(), cx));
+    let rust_editor = cx.add_window(|window, cx| {
+        build_editor(
+            cx.new(|cx| MultiBuffer::singleton(rust_buffer, cx)),
+            window,
+            cx,
+        )
+    });
+    let plain_editor =
+        cx.add_window(|window, cx| build_editor(MultiBuffer::build_simple("abc", cx), window, cx));
+    let buffer_font = cx.update(|cx| ThemeSettings::get_global(cx).buffer_font.clone());
+    let buffer_font_size = cx.update(|cx| ThemeSettings::get_global(cx).buffer_font_size(cx));
+
+    _ = rust_editor.update(cx, |editor, window, cx| {
+        let font = editor.buffer_font(cx);
+        assert_eq!(&*font.family, "Courier");
+        assert_eq!(font.features.is_calt_enabled(), Some(false));
+        assert_eq!(font.weight, buffer_font.weight);
+        assert_eq!(editor.buffer_font_size(cx), px(20.));
+
+        // Zooming is relative to the language's font size.
+        editor.zoom_in(&ZoomIn, window, cx);
+        assert_eq!(editor.buffer_font_size(cx), px(21.));
+        editor.zoom_reset(&ZoomReset, window, cx);
+        assert_eq!(editor.buffer_font_size(cx), px(20.));
+    });
+    _ = plain_editor.update(cx, |editor, _, cx| {
+        assert_eq!(editor.buffer_font(cx), buffer_font);
+        assert_eq!(editor.buffer_font_size(cx), buffer_font_size);
+    });
+}
+
+#[gpui::test]
+async fn test_focus_mode(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
+    cx.set_state(indoc! {"
+        fn a() {
+            ˇone();
+        }
+
+        fn b() {
+            two();
+        }
+    "});
+
+    cx.update_editor(|e, window, cx| e.toggle_focus_mode(&ToggleFocusMode, window, cx));
+    cx.assert_editor_text_highlights::<FocusModeHighlight>(
+        "fn a() {\n    one();\n}«\n\nfn b() {\n    two();\n}\n»",
+    );
+
+    cx.set_selections_state(indoc! {"
+        fn a() {
+            one();
+        }
+
+        fn b() {
+            ˇtwo();
+        }
+    "});
+    cx.assert_editor_text_highlights::<FocusModeHighlight>(
+        "«fn a() {\n    one();\n}\n\n»fn b() {\n    two();\n}«\n»",
+    );
+
+    cx.update_editor(|e, window, cx| e.toggle_focus_mode(&ToggleFocusMode, window, cx));
+    cx.assert_editor_text_highlights::<FocusModeHighlight>(
+        "fn a() {\n    one();\n}\n\nfn b() {\n    two();\n}\n",
+    );
+}
+
+#[gpui::test]
+async fn test_select_syntax_siblings(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
+
+    cx.set_state(indoc! {"
+        fn main() {
+            call(fiˇrst, second.field, third);
+        }
+    "});
+    cx.update_editor(|e, window, cx| {
+        e.select_next_syntax_sibling(&SelectNextSyntaxSibling, window, cx)
+    });
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            call(first, «second.fieldˇ», third);
+        }
+    "});
+    cx.update_editor(|e, window, cx| {
+        e.select_next_syntax_sibling(&SelectNextSyntaxSibling, window, cx)
+    });
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            call(first, second.field, «thirdˇ»);
+        }
+    "});
+
+    // The selection doesn't leave the argument list.
+    cx.update_editor(|e, window, cx| {
+        e.select_next_syntax_sibling(&SelectNextSyntaxSibling, window, cx)
+    });
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            call(first, second.field, «thirdˇ»);
+        }
+    "});
+
+    cx.update_editor(|e, window, cx| {
+        e.select_prev_syntax_sibling(&SelectPreviousSyntaxSibling, window, cx)
+    });
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            call(first, «second.fieldˇ», third);
+        }
+    "});
+}
+
+#[gpui::test]
+async fn test_move_and_swap_syntax_nodes(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
+
+    cx.set_state(indoc! {"
+        fn main() {
+            call(fiˇrst, second);
+        }
+    "});
+    cx.update_editor(|e, window, cx| e.move_node_down(&MoveNodeDown, window, cx));
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            call(second, fiˇrst);
+        }
+    "});
+
+    // The last sibling can't move any further.
+    cx.update_editor(|e, window, cx| e.move_node_down(&MoveNodeDown, window, cx));
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            call(second, fiˇrst);
+        }
+    "});
+
+    cx.update_editor(|e, window, cx| e.move_node_up(&MoveNodeUp, window, cx));
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            call(fiˇrst, second);
+        }
+    "});
+
+    // Swapping leaves the cursor where it was.
+    cx.update_editor(|e, window, cx| e.swap_with_next_sibling(&SwapWithNextSibling, window, cx));
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            call(seˇcond, first);
+        }
+    "});
+
+    // Multi-line statements move as a whole.
+    cx.set_state(indoc! {"
+        fn main() {
+            ˇlet a = foo(
+                1,
+            );
+            let b = 2;
+        }
+    "});
+    cx.update_editor(|e, window, cx| e.move_node_down(&MoveNodeDown, window, cx));
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let b = 2;
+            ˇlet a = foo(
+                1,
+            );
+        }
+    "});
+
+    // Lines after the first are reindented to match the new location.
+    cx.set_state(indoc! {"
+        fn main() {
+            let a = [ˇfoo(
+                1,
+            ),
+                bar];
+        }
+    "});
+    cx.update_editor(|e, window, cx| e.move_node_down(&MoveNodeDown, window, cx));
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            let a = [bar,
+                ˇfoo(
+                    1,
+                )];
+        }
+    "});
+}
+
 #[gpui::test]
 fn test_move_line_up_down(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -5826,6 +7800,64 @@ fn test_move_line_up_down_with_blocks(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_move_line_up_down_reindents(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
+    cx.set_state(indoc! {"
+        fn main() {
+            a();
+        }
+        «b();
+        if c {
+            d();
+        }ˇ»
+    "});
+    cx.update_editor(|e, window, cx| e.move_line_up(&MoveLineUp, window, cx));
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            a();
+            «b();
+            if c {
+                d();
+            }ˇ»
+        }
+    "});
+
+    cx.update_editor(|e, window, cx| e.move_line_down(&MoveLineDown, window, cx));
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            a();
+        }
+        «b();
+        if c {
+            d();
+        }ˇ»
+    "});
+
+    update_test_language_settings(&mut cx, |settings| {
+        settings.defaults.auto_indent_on_move_lines = Some(false);
+    });
+    cx.set_state(indoc! {"
+        fn main() {
+            a();
+        }
+        bˇ();
+    "});
+    cx.update_editor(|e, window, cx| e.move_line_up(&MoveLineUp, window, cx));
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            a();
+        bˇ();
+        }
+    "});
+}
+
 #[gpui::test]
 async fn test_selections_and_replace_blocks(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -6000,6 +8032,148 @@ fn test_transpose(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_transpose_words_and_lines(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    // Between words, the words on either side are swapped.
+    cx.set_state("one ˇtwo, three");
+    cx.update_editor(|e, window, cx| e.transpose_words(&TransposeWords, window, cx));
+    cx.assert_editor_state("two oneˇ, three");
+
+    // Repeating moves the word forward.
+    cx.update_editor(|e, window, cx| e.transpose_words(&TransposeWords, window, cx));
+    cx.assert_editor_state("two three, oneˇ");
+
+    // Inside a word, that word is swapped with the next one, at each cursor.
+    cx.set_state("foˇo_bar baz\nqˇux quux");
+    cx.update_editor(|e, window, cx| e.transpose_words(&TransposeWords, window, cx));
+    cx.assert_editor_state("baz foo_barˇ\nquux quxˇ");
+
+    // Without a word after the cursor, nothing changes.
+    cx.set_state("one twoˇ");
+    cx.update_editor(|e, window, cx| e.transpose_words(&TransposeWords, window, cx));
+    cx.assert_editor_state("one twoˇ");
+
+    cx.set_state("one\ntwˇo\nthree");
+    cx.update_editor(|e, window, cx| e.transpose_lines(&TransposeLines, window, cx));
+    cx.assert_editor_state("two\none\nthrˇee");
+    cx.update_editor(|e, window, cx| e.transpose_lines(&TransposeLines, window, cx));
+    cx.assert_editor_state("two\nthree\noneˇ");
+
+    // Transposing is undone in one step.
+    cx.update_editor(|e, window, cx| e.undo(&Undo, window, cx));
+    cx.assert_editor_state("two\none\nthrˇee");
+}
+
+#[gpui::test]
+async fn test_increment_and_decrement_number(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state("let a = ˇ9, b = 0x0ˇf;\nlet c = «2 + 3ˇ»;");
+    cx.update_editor(|e, window, cx| e.increment_number(&IncrementNumber::default(), window, cx));
+    cx.assert_editor_state("let a = ˇ10, b = 0x1ˇ0;\nlet c = «3ˇ» + 3;");
+
+    cx.update_editor(|e, window, cx| {
+        e.decrement_number(
+            &DecrementNumber {
+                count: Some(5),
+                ..Default::default()
+            },
+            window,
+            cx,
+        )
+    });
+    cx.assert_editor_state("let a = ˇ5, b = 0x0ˇb;\nlet c = «-2ˇ» + 3;");
+
+    // Sequential increments turn equal numbers into a sequence.
+    cx.set_state("ˇ0\nˇ0\nˇ0\nnone ˇ");
+    cx.update_editor(|e, window, cx| {
+        e.increment_number(
+            &IncrementNumber {
+                sequential: true,
+                ..Default::default()
+            },
+            window,
+            cx,
+        )
+    });
+    cx.assert_editor_state("ˇ1\nˇ2\nˇ3\nnone ˇ");
+}
+
+#[gpui::test]
+async fn test_evaluate_selection(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    // Each selection is evaluated on its own, and invalid expressions are left alone.
+    cx.set_state("«(1 + 2) * 3ˇ», «0xff & ~0x0fˇ», «a + 1ˇ», «2 ** 10 =ˇ»");
+    cx.update_editor(|e, window, cx| {
+        e.evaluate_selection(&EvaluateSelection::default(), window, cx)
+    });
+    cx.assert_editor_state("«9ˇ», «0xf0ˇ», «a + 1ˇ», 2 ** 10 = 1024ˇ");
+
+    cx.set_state("width: «7 / 2ˇ»;");
+    cx.update_editor(|e, window, cx| {
+        e.evaluate_selection(&EvaluateSelection { append: true }, window, cx)
+    });
+    cx.assert_editor_state("width: 7 / 2 = 3.5ˇ;");
+}
+
+#[gpui::test]
+async fn test_insert_sequence_and_timestamp(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state("id: ˇ\nid: «xˇ»\nid: ˇ");
+    cx.update_editor(|e, window, cx| e.insert_sequence(&InsertSequence::default(), window, cx));
+    cx.assert_editor_state("id: 1ˇ\nid: 2ˇ\nid: 3ˇ");
+
+    cx.set_state("id: ˇ\nid: ˇ\nid: ˇ");
+    cx.update_editor(|e, window, cx| {
+        e.insert_sequence(
+            &InsertSequence {
+                start: Some(5),
+                step: Some(-5),
+                width: Some(2),
+            },
+            window,
+            cx,
+        )
+    });
+    cx.assert_editor_state("id: 05ˇ\nid: 00ˇ\nid: -05ˇ");
+
+    cx.set_state("ˇ ˇ");
+    cx.update_editor(|e, window, cx| {
+        e.insert_timestamp(
+            &InsertTimestamp {
+                format: Some("[%%]".into()),
+            },
+            window,
+            cx,
+        )
+    });
+    cx.assert_editor_state("[%]ˇ [%]ˇ");
+
+    // Invalid formats insert nothing.
+    cx.update_editor(|e, window, cx| {
+        e.insert_timestamp(
+            &InsertTimestamp {
+                format: Some("%Q".into()),
+            },
+            window,
+            cx,
+        )
+    });
+    cx.assert_editor_state("[%]ˇ [%]ˇ");
+}
+
 #[gpui::test]
 async fn test_rewrap(cx: &mut TestAppContext) {
     init_test(cx, |settings| {
@@ -6897,6 +9071,75 @@ async fn test_hard_wrap(cx: &mut TestAppContext) {
     ));
 }
 
+#[gpui::test]
+async fn test_auto_wrap_at_preferred_line_length(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.languages.0.extend([(
+            "Markdown".into(),
+            LanguageSettingsContent {
+                auto_wrap_at_preferred_line_length: Some(true),
+                preferred_line_length: Some(16),
+                ..Default::default()
+            },
+        )]);
+    });
+    let mut cx = EditorTestContext::new(cx).await;
+
+    let markdown_language = Arc::new(Language::new(
+        LanguageConfig {
+            name: "Markdown".into(),
+            rewrap_prefixes: vec![regex::Regex::new("[-*+]\\s+").unwrap()],
+            ..LanguageConfig::default()
+        },
+        None,
+    ));
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(markdown_language), cx));
+
+    cx.set_state(indoc!(
+        "
+        - one two three ˇ
+        "
+    ));
+    cx.simulate_input("four");
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc!(
+        "
+        - one two three
+          fourˇ
+        "
+    ));
+
+    // Lines that fit aren't touched, even when the paragraph could be reflowed.
+    cx.set_state(indoc!(
+        "
+        one
+        two ˇ
+        "
+    ));
+    cx.simulate_input("three");
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc!(
+        "
+        one
+        two threeˇ
+        "
+    ));
+
+    cx.update_buffer(|buffer, cx| buffer.set_language(None, cx));
+    cx.set_state(indoc!(
+        "
+        one two three four ˇ
+        "
+    ));
+    cx.simulate_input("five");
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc!(
+        "
+        one two three four fiveˇ
+        "
+    ));
+}
+
 #[gpui::test]
 async fn test_cut_line_ends(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -6953,8 +9196,148 @@ async fn test_cut_line_ends(cx: &mut TestAppContext) {
         the lazy dog"});
     cx.update_editor(|e, window, cx| e.kill_ring_cut(&KillRingCut, window, cx));
     cx.assert_editor_state(indoc! {"
-        The quickˇ
-        fox jumps overˇthe lazy dog"});
+        The quickˇ
+        fox jumps overˇthe lazy dog"});
+}
+
+#[gpui::test]
+async fn test_kill_ring(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    // Consecutive kills are joined into one entry.
+    cx.set_state(indoc! {"
+        ˇone
+        two
+        three"});
+    cx.update_editor(|e, window, cx| e.kill_ring_cut(&KillRingCut, window, cx));
+    cx.update_editor(|e, window, cx| e.kill_ring_cut(&KillRingCut, window, cx));
+    cx.assert_editor_state(indoc! {"
+        ˇtwo
+        three"});
+    cx.update_editor(|e, window, cx| e.kill_ring_yank(&KillRingYank, window, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        ˇtwo
+        three"});
+
+    // A kill after moving the cursor starts a new entry.
+    cx.set_state(indoc! {"
+        one
+        two
+        ˇthree"});
+    cx.update_editor(|e, window, cx| e.kill_ring_cut(&KillRingCut, window, cx));
+    cx.update_editor(|e, window, cx| e.move_up(&MoveUp, window, cx));
+    cx.update_editor(|e, window, cx| e.kill_ring_yank(&KillRingYank, window, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        threeˇtwo
+        "});
+
+    // Yank-pop replaces the yanked text with the previous kills in turn.
+    cx.update_editor(|e, window, cx| e.kill_ring_yank_pop(&KillRingYankPop, window, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        one
+        ˇtwo
+        "});
+    cx.update_editor(|e, window, cx| e.kill_ring_yank_pop(&KillRingYankPop, window, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        threeˇtwo
+        "});
+
+    // Yank-pop does nothing once the cursor has moved.
+    cx.update_editor(|e, window, cx| e.move_left(&MoveLeft, window, cx));
+    cx.update_editor(|e, window, cx| e.kill_ring_yank_pop(&KillRingYankPop, window, cx));
+    cx.assert_editor_state(indoc! {"
+        one
+        threˇetwo
+        "});
+}
+
+#[gpui::test]
+async fn test_keyboard_macros(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    cx.update(|cx| {
+        cx.bind_keys([gpui::KeyBinding::new(
+            "ctrl-e",
+            MoveToEndOfLine::default(),
+            None,
+        )])
+    });
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state(indoc! {"
+        ˇone
+        two
+        three"});
+    cx.dispatch_action(StartRecordingMacro::default());
+    cx.simulate_input("x");
+    cx.simulate_keystrokes("ctrl-e");
+    cx.simulate_input("y");
+    cx.dispatch_action(StopRecordingMacro);
+    cx.assert_editor_state(indoc! {"
+        xoneyˇ
+        two
+        three"});
+
+    // Replaying applies the recorded steps to every cursor at once.
+    cx.set_state(indoc! {"
+        one
+        ˇtwo
+        ˇthree"});
+    cx.dispatch_action(ReplayMacro::default());
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        one
+        xtwoyˇ
+        xthreeyˇ"});
+
+    cx.set_state(indoc! {"
+        ˇone
+        two
+        three"});
+    cx.dispatch_action(ReplayMacro {
+        times: Some(2),
+        ..Default::default()
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        xoneyxyˇ
+        two
+        three"});
+
+    cx.set_state(indoc! {"
+        ˇone
+        ˇtwo
+        three"});
+    cx.dispatch_action(ReplayMacro {
+        per_cursor: true,
+        ..Default::default()
+    });
+    cx.run_until_parked();
+    cx.assert_editor_state(indoc! {"
+        xoneyˇ
+        xtwoyˇ
+        three"});
+
+    // Text typed into other editors while recording isn't part of the macro.
+    let other_editor = cx.update(|window, cx| {
+        cx.new(|cx| build_editor(MultiBuffer::build_simple("", cx), window, cx))
+    });
+    cx.set_state("ˇone");
+    cx.dispatch_action(StartRecordingMacro::default());
+    cx.simulate_input("a");
+    other_editor.update_in(&mut cx, |editor, window, cx| {
+        editor.replace_text_in_range(None, "z", window, cx);
+    });
+    cx.dispatch_action(StopRecordingMacro);
+    cx.set_state("ˇtwo");
+    cx.dispatch_action(ReplayMacro::default());
+    cx.run_until_parked();
+    cx.assert_editor_state("aˇtwo");
 }
 
 #[gpui::test]
@@ -7043,6 +9426,102 @@ async fn test_clipboard(cx: &mut TestAppContext) {
         tˇhe lazy dog"});
 }
 
+#[gpui::test]
+async fn test_paste_rectangular(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    // A copied column selection is pasted at the cursor's column on successive
+    // lines, padding short lines and adding lines past the end of the buffer.
+    cx.set_state(indoc! {"
+        «abˇ»c
+        «deˇ»f
+        «ghˇ»i"});
+    cx.update_editor(|e, window, cx| e.copy(&Copy, window, cx));
+    cx.set_state("12ˇ3\n");
+    cx.update_editor(|e, window, cx| e.paste_rectangular(&PasteRectangular, window, cx));
+    cx.assert_editor_state("12abˇ3\n  deˇ\n  ghˇ");
+
+    // Plain multi-line text is pasted one line per line.
+    cx.write_to_clipboard(ClipboardItem::new_string("x\ny".into()));
+    cx.set_state("1ˇ2\n3456\n");
+    cx.update_editor(|e, window, cx| e.paste_rectangular(&PasteRectangular, window, cx));
+    cx.assert_editor_state("1xˇ2\n3yˇ456\n");
+
+    // Columns are measured visually, with tabs expanded.
+    cx.set_state("\tˇa\n12345\nab\tc\n");
+    cx.write_to_clipboard(ClipboardItem::new_string("x\ny\nz".into()));
+    cx.update_editor(|e, window, cx| e.paste_rectangular(&PasteRectangular, window, cx));
+    cx.assert_editor_state("\txˇa\n1234yˇ5\nab\tzˇc\n");
+}
+
+#[gpui::test]
+async fn test_multi_cursor_paste_at_single_cursor(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state("«abˇ»c\n«deˇ»f");
+    cx.update_editor(|e, window, cx| e.copy(&Copy, window, cx));
+    cx.set_state("12ˇ3\n4");
+    cx.update_editor(|e, window, cx| e.paste(&Paste, window, cx));
+    cx.assert_editor_state("12ab\ndeˇ3\n4");
+
+    cx.update(|_, cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings.editor.multi_cursor_paste = Some(MultiCursorPaste::Split);
+            });
+        });
+    });
+    cx.set_state("12ˇ3\n4");
+    cx.update_editor(|e, window, cx| e.paste(&Paste, window, cx));
+    cx.assert_editor_state("12abˇ3\n4 deˇ");
+}
+
+#[gpui::test]
+async fn test_paste_previous_kill_for_each_cursor(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state("«oneˇ» «twoˇ»");
+    cx.update_editor(|e, window, cx| e.copy(&Copy, window, cx));
+    cx.set_state("«threeˇ»");
+    cx.update_editor(|e, window, cx| e.copy(&Copy, window, cx));
+    cx.set_state("«fourˇ» «fiveˇ»");
+    cx.update_editor(|e, window, cx| e.copy(&Copy, window, cx));
+
+    cx.set_state("ˇa\nˇb");
+    cx.update_editor(|e, window, cx| {
+        e.paste_previous_kill_for_each_cursor(&PastePreviousKillForEachCursor, window, cx)
+    });
+    cx.assert_editor_state("oneˇa\ntwoˇb");
+}
+
+#[gpui::test]
+async fn test_block_insert(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state(indoc! {"
+        ab«cd
+        x
+        efˇ»gh"});
+    cx.update_editor(|e, window, cx| e.block_insert(&BlockInsert, window, cx));
+    cx.assert_editor_state("abˇcd\nx ˇ\nefˇgh");
+
+    cx.update_editor(|e, window, cx| e.handle_input("-", window, cx));
+    cx.assert_editor_state("ab-ˇcd\nx -ˇ\nef-ˇgh");
+
+    // Columns are measured visually, with tabs expanded.
+    cx.set_state("\t«a\n12345\nabˇ»\tc");
+    cx.update_editor(|e, window, cx| e.block_insert(&BlockInsert, window, cx));
+    cx.assert_editor_state("\tˇa\n1234ˇ5\nab\tˇc");
+}
+
 #[gpui::test]
 async fn test_copy_trim(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -7247,6 +9726,61 @@ if is_entire_line {
     );
 }
 
+#[gpui::test]
+async fn test_paste_without_reindent(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
+    cx.write_to_clipboard(ClipboardItem::new_string("if a {\n    b();\n}".into()));
+
+    cx.set_state(indoc! {"
+        fn main() {
+            ˇ
+        }
+    "});
+    cx.update_editor(|e, window, cx| e.paste(&Paste, window, cx));
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            if a {
+                b();
+            }ˇ
+        }
+    "});
+
+    cx.set_state(indoc! {"
+        fn main() {
+            ˇ
+        }
+    "});
+    cx.update_editor(|e, window, cx| e.paste_without_reindent(&PasteWithoutReindent, window, cx));
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            if a {
+            b();
+        }ˇ
+        }
+    "});
+
+    // The `auto_indent_on_paste` setting also applies to text copied outside of Zed.
+    update_test_language_settings(&mut cx, |settings| {
+        settings.defaults.auto_indent_on_paste = Some(false);
+    });
+    cx.set_state(indoc! {"
+        fn main() {
+            ˇ
+        }
+    "});
+    cx.update_editor(|e, window, cx| e.paste(&Paste, window, cx));
+    cx.assert_editor_state(indoc! {"
+        fn main() {
+            if a {
+            b();
+        }ˇ
+        }
+    "});
+}
+
 #[gpui::test]
 async fn test_paste_multiline(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -7639,6 +10173,104 @@ async fn test_split_selection_into_lines_interacting_with_creases(cx: &mut TestA
         );
 }
 
+#[gpui::test]
+async fn test_align_selections(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state("aˇb\nabcˇd\nˇx");
+    cx.update_editor(|editor, window, cx| {
+        editor.align_selections(&AlignSelections::default(), window, cx);
+    });
+    cx.assert_editor_state("a  ˇb\nabcˇd\n   ˇx");
+
+    // Only the first selection on each line is aligned.
+    cx.set_state("«aˇ»b «cˇ»d\nabc«dˇ»");
+    cx.update_editor(|editor, window, cx| {
+        editor.align_selections(&AlignSelections::default(), window, cx);
+    });
+    cx.assert_editor_state("   «aˇ»b «cˇ»d\nabc«dˇ»");
+
+    cx.set_state("ˇa = 1,\nˇbcd = 2,\nˇef = 3,\nˇnone");
+    cx.update_editor(|editor, window, cx| {
+        editor.align_selections(
+            &AlignSelections {
+                align_on: Some("=".to_string()),
+            },
+            window,
+            cx,
+        );
+    });
+    cx.assert_editor_state("ˇa   = 1,\nˇbcd = 2,\nˇef  = 3,\nˇnone");
+
+    // Columns are measured visually, with tabs expanded.
+    cx.set_state("\tˇa\nabcdeˇf");
+    cx.update_editor(|editor, window, cx| {
+        editor.align_selections(&AlignSelections::default(), window, cx);
+    });
+    cx.assert_editor_state("\t ˇa\nabcdeˇf");
+}
+
+#[gpui::test]
+async fn test_align_selections_on_prompted_text(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    let project = Project::test(fs, [], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let buffer = cx.update(|cx| MultiBuffer::build_simple("a = 1,\nbcd = 2,", cx));
+    let cx = &mut VisualTestContext::from_window(*workspace.deref(), cx);
+    let editor = cx.new_window_entity(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            buffer,
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+        })
+        .unwrap();
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([
+                Point::new(0, 0)..Point::new(0, 0),
+                Point::new(1, 0)..Point::new(1, 0),
+            ])
+        });
+    });
+
+    cx.dispatch_action(AlignSelectionsOn);
+    workspace
+        .update(cx, |workspace, _, cx| {
+            assert!(
+                workspace
+                    .active_modal::<AlignSelectionsPrompt>(cx)
+                    .is_some()
+            );
+        })
+        .unwrap();
+    cx.simulate_input("=");
+    cx.dispatch_action(menu::Confirm);
+
+    assert_eq!(
+        editor.update(cx, |editor, cx| editor.text(cx)),
+        "a   = 1,\nbcd = 2,"
+    );
+    workspace
+        .update(cx, |workspace, _, cx| {
+            assert!(
+                workspace
+                    .active_modal::<AlignSelectionsPrompt>(cx)
+                    .is_none()
+            );
+        })
+        .unwrap();
+}
+
 #[gpui::test]
 async fn test_add_selection_above_below(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -8207,6 +10839,33 @@ async fn test_add_selection_above_below_multi_cursor_existing_state(cx: &mut Tes
     ));
 }
 
+#[gpui::test]
+async fn test_reselect_last_selection(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("«abcˇ» abc\nabc«defˇ»\nghi");
+
+    // Collapse the selections, as a click would, then move the cursor around.
+    cx.update_editor(|editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([1..1])
+        });
+        editor.move_down(&MoveDown, window, cx);
+        editor.move_right(&MoveRight, window, cx);
+    });
+    cx.assert_editor_state("abc abc\nabˇcdef\nghi");
+
+    cx.update_editor(|editor, window, cx| {
+        editor.reselect_last_selection(&ReselectLastSelection, window, cx)
+    });
+    cx.assert_editor_state("«abcˇ» abc\nabc«defˇ»\nghi");
+
+    // Reselecting is a regular selection change that can be undone.
+    cx.update_editor(|editor, window, cx| editor.undo_selection(&UndoSelection, window, cx));
+    cx.assert_editor_state("abc abc\nabˇcdef\nghi");
+}
+
 #[gpui::test]
 async fn test_select_next(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -9458,30 +12117,184 @@ async fn test_fold_function_bodies(cx: &mut TestAppContext) {
         .unindent(),
     );
 
-    let expected_display_text = "
-        impl A {
-            // this is an uncommitted comment
+    let expected_display_text = "
+        impl A {
+            // this is an uncommitted comment
+
+            fn b() {
+                ⋯
+            }
+
+            // this is another uncommitted comment
+
+            fn d() {
+                ⋯
+            }
+        }
+
+        fn g() {
+            ⋯
+        }
+        "
+    .unindent();
+
+    cx.update_editor(|editor, window, cx| {
+        editor.fold_function_bodies(&FoldFunctionBodies, window, cx);
+        assert_eq!(editor.display_text(cx), expected_display_text);
+    });
+}
+
+#[gpui::test]
+fn test_toggle_fold_excerpts(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let editor = cx.add_window(|window, cx| {
+        let multi_buffer = MultiBuffer::build_multi(
+            [(
+                "a0\na1\na2\na3\na4\na5\na6\na7\n",
+                vec![Point::row_range(0..3), Point::row_range(5..8)],
+            )],
+            cx,
+        );
+        Editor::new(EditorMode::full(), multi_buffer, None, window, cx)
+    });
+
+    _ = editor.update(cx, |editor, window, cx| {
+        editor.toggle_fold_excerpts(&ToggleFoldExcerpts, window, cx);
+        let display_text = editor.display_text(cx);
+        assert!(display_text.contains("a0⋯"), "{display_text:?}");
+        assert!(!display_text.contains("a1"), "{display_text:?}");
+        assert!(display_text.contains("a5\na6"), "{display_text:?}");
+
+        editor.toggle_fold_excerpts(&ToggleFoldExcerpts, window, cx);
+        let display_text = editor.display_text(cx);
+        assert!(display_text.contains("a0\na1\na2"), "{display_text:?}");
+    });
+}
+
+#[gpui::test]
+async fn test_toggle_read_only(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state("oneˇ");
+
+    cx.update_editor(|editor, window, cx| {
+        editor.toggle_read_only(&ToggleReadOnly, window, cx);
+        assert!(editor.edits_locked(cx));
+        assert!(editor.read_only(cx));
+        editor.handle_input(" two", window, cx);
+        editor.backspace(&Backspace, window, cx);
+    });
+    cx.assert_editor_state("oneˇ");
+
+    cx.update_editor(|editor, window, cx| {
+        editor.toggle_read_only(&ToggleReadOnly, window, cx);
+        assert!(!editor.read_only(cx));
+        editor.handle_input(" two", window, cx);
+    });
+    cx.assert_editor_state("one twoˇ");
+
+    // Editors that are read-only for other reasons can't be locked or unlocked.
+    cx.update_editor(|editor, window, cx| {
+        editor.set_read_only(true);
+        editor.toggle_read_only(&ToggleReadOnly, window, cx);
+        assert!(!editor.edits_locked(cx));
+    });
+}
+
+#[gpui::test]
+fn test_toggle_read_only_locks_every_editor(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let buffer = cx.new(|cx| language::Buffer::local("one", cx));
+    let first_editor = cx.add_window(|window, cx| {
+        let multibuffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
+        build_editor(multibuffer, window, cx)
+    });
+    let second_editor = cx.add_window(|window, cx| {
+        let multibuffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
+        build_editor(multibuffer, window, cx)
+    });
+    let excerpts_editor = cx.add_window(|window, cx| {
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::new(ReadWrite);
+            multibuffer.push_excerpts(
+                buffer.clone(),
+                [ExcerptRange::new(Point::new(0, 0)..Point::new(0, 3))],
+                cx,
+            );
+            multibuffer
+        });
+        build_editor(multibuffer, window, cx)
+    });
+
+    _ = first_editor.update(cx, |editor, window, cx| {
+        editor.toggle_read_only(&ToggleReadOnly, window, cx);
+    });
+    _ = second_editor.update(cx, |editor, window, cx| {
+        assert!(editor.edits_locked(cx));
+        assert!(editor.read_only(cx));
+        editor.move_to_end(&MoveToEnd, window, cx);
+        editor.handle_input(" two", window, cx);
+    });
+    _ = excerpts_editor.update(cx, |editor, window, cx| {
+        editor.move_to_end(&MoveToEnd, window, cx);
+        editor.handle_input(" three", window, cx);
+    });
+    assert_eq!(buffer.read_with(cx, |buffer, _| buffer.text()), "one");
 
-            fn b() {
-                ⋯
-            }
+    // Unlocking from another editor unlocks them all.
+    _ = second_editor.update(cx, |editor, window, cx| {
+        editor.toggle_read_only(&ToggleReadOnly, window, cx);
+    });
+    _ = first_editor.update(cx, |editor, window, cx| {
+        assert!(!editor.read_only(cx));
+        editor.move_to_end(&MoveToEnd, window, cx);
+        editor.handle_input(" two", window, cx);
+    });
+    assert_eq!(buffer.read_with(cx, |buffer, _| buffer.text()), "one two");
+}
 
-            // this is another uncommitted comment
+#[gpui::test]
+async fn test_cursor_announcement(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
 
-            fn d() {
-                ⋯
-            }
+    cx.set_state(indoc! {"
+        fn main() ˇ{
+            let é = 1;
         }
+    "});
+    cx.update_editor(|editor, _, cx| {
+        assert_eq!(
+            editor.cursor_announcement(cx),
+            "Line 1, column 11. Matching bracket on line 3, column 1"
+        );
+    });
 
-        fn g() {
-            ⋯
+    // Columns are counted in characters rather than bytes.
+    cx.set_state(indoc! {"
+        fn main() {
+            let «éˇ» = 1;
         }
-        "
-    .unindent();
+    "});
+    cx.update_editor(|editor, _, cx| {
+        assert_eq!(
+            editor.cursor_announcement(cx),
+            "Line 2, column 10. Selected: é"
+        );
+    });
 
-    cx.update_editor(|editor, window, cx| {
-        editor.fold_function_bodies(&FoldFunctionBodies, window, cx);
-        assert_eq!(editor.display_text(cx), expected_display_text);
+    cx.set_state(indoc! {"
+        fn ˇmain() {
+            let é = ˇ1;
+        }
+    "});
+    cx.update_editor(|editor, _, cx| {
+        assert_eq!(
+            editor.cursor_announcement(cx),
+            "Line 2, column 13. 2 cursors"
+        );
     });
 }
 
@@ -11904,6 +14717,61 @@ async fn test_range_format_respects_language_tab_size_override(cx: &mut TestAppC
     save.await;
 }
 
+#[gpui::test]
+async fn test_format_on_paste(cx: &mut TestAppContext) {
+    let (_, editor, cx, fake_server) = setup_range_format_test(cx).await;
+    update_test_language_settings(cx, |settings| {
+        settings.defaults.format_on_paste = Some(true);
+    });
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.set_text("fn main() {}\n", window, cx);
+        editor
+            .buffer()
+            .update(cx, |buffer, cx| buffer.finalize_last_transaction(cx));
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([11..11])
+        });
+    });
+    let mut format_requests = fake_server
+        .set_request_handler::<lsp::request::RangeFormatting, _, _>(move |params, _| async move {
+            assert_eq!(
+                params.range,
+                lsp::Range::new(lsp::Position::new(0, 11), lsp::Position::new(0, 15))
+            );
+            Ok(Some(vec![lsp::TextEdit::new(
+                lsp::Range::new(lsp::Position::new(0, 11), lsp::Position::new(0, 11)),
+                " ".to_string(),
+            )]))
+        });
+
+    cx.write_to_clipboard(ClipboardItem::new_string("a();".into()));
+    editor.update_in(cx, |editor, window, cx| editor.paste(&Paste, window, cx));
+    format_requests.next().await;
+    cx.run_until_parked();
+    assert_eq!(
+        editor.update(cx, |editor, cx| editor.text(cx)),
+        "fn main() { a();}\n"
+    );
+
+    // Undoing the paste also undoes the formatting.
+    editor.update_in(cx, |editor, window, cx| editor.undo(&Undo, window, cx));
+    assert_eq!(
+        editor.update(cx, |editor, cx| editor.text(cx)),
+        "fn main() {}\n"
+    );
+
+    // Pasting without reindenting skips formatting.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.paste_without_reindent(&PasteWithoutReindent, window, cx)
+    });
+    cx.run_until_parked();
+    assert_eq!(
+        editor.update(cx, |editor, cx| editor.text(cx)),
+        "fn main() {a();}\n"
+    );
+}
+
 #[gpui::test]
 async fn test_document_format_manual_trigger(cx: &mut TestAppContext) {
     init_test(cx, |settings| {
@@ -12032,7 +14900,8 @@ async fn test_document_format_manual_trigger(cx: &mut TestAppContext) {
 #[gpui::test]
 async fn test_multiple_formatters(cx: &mut TestAppContext) {
     init_test(cx, |settings| {
-        settings.defaults.remove_trailing_whitespace_on_save = Some(true);
+        settings.defaults.remove_trailing_whitespace_on_save =
+            Some(settings::RemoveTrailingWhitespaceOnSave::All);
         settings.defaults.formatter = Some(FormatterList::Vec(vec![
             Formatter::LanguageServer(settings::LanguageServerFormatterSpecifier::Current),
             Formatter::CodeAction("code-action-1".into()),
@@ -12289,6 +15158,122 @@ async fn test_multiple_formatters(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_code_actions_on_save(cx: &mut TestAppContext) {
+    init_test(cx, |settings| {
+        settings.defaults.format_on_save = Some(FormatOnSave::Off);
+        settings.defaults.code_actions_on_save = Some(IndexMap::from_iter([
+            ("source.organizeImports".to_string(), true),
+            ("source.fixAll".to_string(), false),
+            ("source.slow".to_string(), true),
+        ]));
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_file(path!("/file.rs"), "one\n".into()).await;
+
+    let project = Project::test(fs, [path!("/").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/file.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        build_editor_with_project(project.clone(), buffer, window, cx)
+    });
+
+    cx.executor().start_waiting();
+    let fake_server = fake_servers.next().await.unwrap();
+    let requested_kinds = Arc::new(Mutex::new(Vec::new()));
+    fake_server.set_request_handler::<lsp::request::CodeActionRequest, _, _>({
+        let requested_kinds = requested_kinds.clone();
+        move |params, _| {
+            let kind = params.context.only.unwrap()[0].clone();
+            requested_kinds.lock().push(kind.as_str().to_string());
+            async move {
+                if kind.as_str() == "source.slow" {
+                    futures::future::pending::<()>().await;
+                }
+                let uri = lsp::Uri::from_file_path(path!("/file.rs")).unwrap();
+                Ok(Some(vec![lsp::CodeActionOrCommand::CodeAction(
+                    lsp::CodeAction {
+                        kind: Some(kind),
+                        edit: Some(lsp::WorkspaceEdit::new(
+                            [(
+                                uri,
+                                vec![lsp::TextEdit::new(
+                                    lsp::Range::new(
+                                        lsp::Position::new(0, 0),
+                                        lsp::Position::new(0, 0),
+                                    ),
+                                    "imports organized\n".to_string(),
+                                )],
+                            )]
+                            .into_iter()
+                            .collect(),
+                        )),
+                        ..Default::default()
+                    },
+                )]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::CodeActionResolveRequest, _, _>(
+        move |params, _| async move { Ok(params) },
+    );
+
+    // Manual formatting doesn't run the code actions on save.
+    editor
+        .update_in(cx, |editor, window, cx| {
+            editor.perform_format(
+                project.clone(),
+                FormatTrigger::Manual,
+                FormatTarget::Buffers(editor.buffer().read(cx).all_buffers()),
+                window,
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    assert!(requested_kinds.lock().is_empty());
+
+    let format = editor.update_in(cx, |editor, window, cx| {
+        editor.perform_format(
+            project.clone(),
+            FormatTrigger::Save,
+            FormatTarget::Buffers(editor.buffer().read(cx).all_buffers()),
+            window,
+            cx,
+        )
+    });
+    cx.run_until_parked();
+    cx.executor().advance_clock(Duration::from_millis(2000));
+    format.await.unwrap();
+    assert_eq!(
+        *requested_kinds.lock(),
+        ["source.organizeImports", "source.slow"]
+    );
+    assert_eq!(
+        editor.update(cx, |editor, cx| editor.text(cx)),
+        "imports organized\none\n"
+    );
+}
+
 #[gpui::test]
 async fn test_organize_imports_manual_trigger(cx: &mut TestAppContext) {
     init_test(cx, |settings| {
@@ -13351,20 +16336,123 @@ async fn test_signature_help_multiple_signatures(cx: &mut TestAppContext) {
     cx.update_editor(|editor, window, cx| {
         editor.signature_help_next(&crate::SignatureHelpNext, window, cx);
     });
-
+
+    cx.editor(|editor, _, _| {
+        let popover = editor.signature_help_state.popover().cloned().unwrap();
+        assert_eq!(popover.current_signature, 0);
+    });
+
+    // Test previous navigation
+    cx.update_editor(|editor, window, cx| {
+        editor.signature_help_prev(&crate::SignatureHelpPrevious, window, cx);
+    });
+
+    cx.editor(|editor, _, _| {
+        let popover = editor.signature_help_state.popover().cloned().unwrap();
+        assert_eq!(popover.current_signature, 2);
+    });
+}
+
+#[gpui::test]
+async fn test_signature_help_persists_while_editing_arguments(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            signature_help_provider: Some(lsp::SignatureHelpOptions {
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    cx.set_state(indoc! {"
+        fn main() {
+            overloaded(ˇ);
+        }
+    "});
+
+    let signature_help = |active_parameter| lsp::SignatureHelp {
+        signatures: vec![
+            lsp::SignatureInformation {
+                label: "fn overloaded(x: i32)".to_string(),
+                documentation: None,
+                parameters: Some(vec![lsp::ParameterInformation {
+                    label: lsp::ParameterLabel::Simple("x: i32".to_string()),
+                    documentation: None,
+                }]),
+                active_parameter: None,
+            },
+            lsp::SignatureInformation {
+                label: "fn overloaded(x: i32, y: i32)".to_string(),
+                documentation: None,
+                parameters: Some(vec![
+                    lsp::ParameterInformation {
+                        label: lsp::ParameterLabel::Simple("x: i32".to_string()),
+                        documentation: None,
+                    },
+                    lsp::ParameterInformation {
+                        label: lsp::ParameterLabel::Simple("y: i32".to_string()),
+                        documentation: None,
+                    },
+                ]),
+                active_parameter: None,
+            },
+        ],
+        active_signature: Some(0),
+        active_parameter: Some(active_parameter),
+    };
+
+    cx.update_editor(|editor, window, cx| {
+        editor.show_signature_help(&ShowSignatureHelp, window, cx);
+    });
+    handle_signature_help_request(&mut cx, signature_help(0)).await;
+    cx.condition(|editor, _| editor.signature_help_state.is_shown())
+        .await;
+
+    cx.update_editor(|editor, window, cx| {
+        editor.signature_help_next(&crate::SignatureHelpNext, window, cx);
+    });
+
+    // Typing inside the argument list refreshes the popover, keeping the chosen overload.
+    cx.update_editor(|editor, window, cx| {
+        editor.handle_input("1, ", window, cx);
+    });
+    handle_signature_help_request(&mut cx, signature_help(1)).await;
+    cx.run_until_parked();
+    cx.editor(|editor, _, _| {
+        let popover = editor.signature_help_state.popover().cloned().unwrap();
+        assert_eq!(popover.current_signature, 1);
+        assert_eq!(popover.signatures[1].active_parameter, Some(1));
+    });
+
+    // An empty refresh while still editing the same arguments leaves the popover visible.
+    cx.update_editor(|editor, window, cx| {
+        editor.handle_input("2", window, cx);
+    });
+    handle_signature_help_request(
+        &mut cx,
+        lsp::SignatureHelp {
+            signatures: Vec::new(),
+            active_signature: None,
+            active_parameter: None,
+        },
+    )
+    .await;
+    cx.run_until_parked();
     cx.editor(|editor, _, _| {
         let popover = editor.signature_help_state.popover().cloned().unwrap();
-        assert_eq!(popover.current_signature, 0);
+        assert_eq!(popover.current_signature, 1);
     });
 
-    // Test previous navigation
+    // Leaving the argument list hides it.
     cx.update_editor(|editor, window, cx| {
-        editor.signature_help_prev(&crate::SignatureHelpPrevious, window, cx);
+        editor.move_to_end_of_line(&Default::default(), window, cx);
     });
-
     cx.editor(|editor, _, _| {
-        let popover = editor.signature_help_state.popover().cloned().unwrap();
-        assert_eq!(popover.current_signature, 2);
+        assert!(!editor.signature_help_state.is_shown());
     });
 }
 
@@ -14812,6 +17900,60 @@ async fn test_word_completions_disabled(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_postfix_completions(cx: &mut TestAppContext) {
+    init_test(cx, |language_settings| {
+        language_settings.defaults.completions = Some(CompletionSettingsContent {
+            words: Some(WordsCompletionMode::Disabled),
+            lsp: Some(false),
+            postfix_templates: Some(HashMap::from_iter([(
+                "dbg".to_string(),
+                "dbg!(${receiver})".to_string(),
+            )])),
+            ..Default::default()
+        });
+    });
+
+    let mut cx = EditorLspTestContext::new_rust(lsp::ServerCapabilities::default(), cx).await;
+    cx.set_state("fn main() { foo.bar().dbˇ }");
+    cx.executor().run_until_parked();
+    cx.update_editor(|editor, window, cx| {
+        editor.show_completions(&ShowCompletions::default(), window, cx);
+    });
+    cx.executor().run_until_parked();
+    cx.condition(|editor, _| editor.context_menu_visible())
+        .await;
+    cx.update_editor(|editor, _, _| {
+        if let Some(CodeContextMenu::Completions(menu)) = editor.context_menu.borrow_mut().as_ref()
+        {
+            assert_eq!(completion_menu_entries(menu), &["dbg"]);
+        } else {
+            panic!("expected completion menu to be open");
+        }
+    });
+    cx.update_editor(|editor, window, cx| {
+        editor
+            .confirm_completion(&ConfirmCompletion::default(), window, cx)
+            .unwrap()
+    })
+    .await
+    .unwrap();
+    cx.assert_editor_state("fn main() { dbg!(foo.bar())ˇ }");
+
+    cx.set_state("fn main() { foo .dbˇ }");
+    cx.executor().run_until_parked();
+    cx.update_editor(|editor, window, cx| {
+        editor.show_completions(&ShowCompletions::default(), window, cx);
+    });
+    cx.executor().run_until_parked();
+    cx.update_editor(|editor, _, _| {
+        assert!(
+            !editor.context_menu_visible(),
+            "postfix completions need an expression right before the dot"
+        );
+    });
+}
+
 fn gen_text_edit(params: &CompletionParams, text: &str) -> Option<lsp::CompletionTextEdit> {
     let position = || lsp::Position {
         line: params.text_document_position.position.line,
@@ -16348,6 +19490,76 @@ fn test_highlighted_ranges(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+fn test_refreshing_highlights_reuses_allocations(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let editor = cx.add_window(|window, cx| {
+        let buffer = MultiBuffer::build_simple(&sample_text(16, 8, 'a'), cx);
+        build_editor(buffer, window, cx)
+    });
+
+    _ = editor.update(cx, |editor, _, cx| {
+        struct Type1;
+        fn highlight_color(_: &Theme) -> Hsla {
+            Hsla::red()
+        }
+
+        let buffer = editor.buffer.read(cx).snapshot(cx);
+        let anchor_range =
+            |range: Range<Point>| buffer.anchor_after(range.start)..buffer.anchor_after(range.end);
+        let background_ranges = |editor: &Editor| {
+            editor.background_highlights[&HighlightKey::Type(TypeId::of::<Type1>())]
+                .1
+                .clone()
+        };
+
+        editor.highlight_background::<Type1>(
+            &[anchor_range(Point::new(2, 1)..Point::new(2, 3))],
+            highlight_color,
+            cx,
+        );
+        let allocation = Arc::as_ptr(&background_ranges(editor));
+
+        // Refreshing with the same ranges leaves the highlights untouched.
+        editor.scrollbar_marker_state.dirty = false;
+        editor.highlight_background::<Type1>(
+            &[anchor_range(Point::new(2, 1)..Point::new(2, 3))],
+            highlight_color,
+            cx,
+        );
+        assert!(!editor.scrollbar_marker_state.dirty);
+
+        // Moving the ranges reuses the existing allocation.
+        editor.highlight_background::<Type1>(
+            &[anchor_range(Point::new(4, 2)..Point::new(4, 4))],
+            highlight_color,
+            cx,
+        );
+        assert!(editor.scrollbar_marker_state.dirty);
+        let ranges = background_ranges(editor);
+        assert_eq!(Arc::as_ptr(&ranges), allocation);
+        assert_eq!(*ranges, [anchor_range(Point::new(4, 2)..Point::new(4, 4))]);
+
+        let style = HighlightStyle::color(Hsla::green());
+        editor.highlight_text::<Type1>(
+            vec![anchor_range(Point::new(3, 2)..Point::new(3, 5))],
+            style,
+            cx,
+        );
+        let text_ranges = editor.text_highlights::<Type1>(cx).unwrap().1.as_ptr();
+        editor.highlight_text::<Type1>(
+            vec![anchor_range(Point::new(3, 2)..Point::new(3, 5))],
+            style,
+            cx,
+        );
+        assert_eq!(
+            editor.text_highlights::<Type1>(cx).unwrap().1.as_ptr(),
+            text_ranges
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_following(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -16816,6 +20028,132 @@ async fn go_to_prev_overlapping_diagnostic(executor: BackgroundExecutor, cx: &mu
     "});
 }
 
+#[gpui::test]
+fn test_go_to_changed_region(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let editor = cx.add_window(|window, cx| {
+        let buffer = MultiBuffer::build_simple("one\ntwo\nthree\nfour\nfive\n", cx);
+        build_editor(buffer, window, cx)
+    });
+
+    for row in [3, 1] {
+        _ = editor.update(cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges([Point::new(row, 1)..Point::new(row, 1)])
+            });
+            editor.insert("X", window, cx);
+        });
+    }
+
+    _ = editor.update(cx, |editor, window, cx| {
+        let head = |editor: &Editor, cx: &mut Context<Editor>| {
+            editor.selections.newest::<Point>(cx).head()
+        };
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(0, 0)..Point::new(0, 0)])
+        });
+
+        editor.go_to_next_changed_region(&GoToNextChangedRegion, window, cx);
+        assert_eq!(head(editor, cx), Point::new(1, 1));
+        editor.go_to_next_changed_region(&GoToNextChangedRegion, window, cx);
+        assert_eq!(head(editor, cx), Point::new(3, 1));
+        // Wrap around the end of the buffer
+        editor.go_to_next_changed_region(&GoToNextChangedRegion, window, cx);
+        assert_eq!(head(editor, cx), Point::new(1, 1));
+        // Wrap around the start of the buffer
+        editor.go_to_previous_changed_region(&GoToPreviousChangedRegion, window, cx);
+        assert_eq!(head(editor, cx), Point::new(3, 1));
+        editor.go_to_previous_changed_region(&GoToPreviousChangedRegion, window, cx);
+        assert_eq!(head(editor, cx), Point::new(1, 1));
+
+        // The most recent edit was made on the second line
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(4, 0)..Point::new(4, 0)])
+        });
+        editor.go_to_last_edit_location(&GoToLastEditLocation, window, cx);
+        assert_eq!(head(editor, cx), Point::new(1, 2));
+    });
+}
+
+#[gpui::test]
+async fn test_selection_sets(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state("«oneˇ» two «threeˇ»\nfour ˇfive\n");
+    cx.update_editor(|editor, window, cx| {
+        editor.save_selection_set(
+            &SaveSelectionSet {
+                name: Some("words".into()),
+            },
+            window,
+            cx,
+        );
+    });
+    cx.update_editor(|editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(0, 3)..Point::new(0, 3)])
+        });
+        editor.save_selection_set(&SaveSelectionSet::default(), window, cx);
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([Point::new(0, 0)..Point::new(0, 0)])
+        });
+        editor.insert("zero ", window, cx);
+    });
+    cx.assert_editor_state("zero ˇone two three\nfour five\n");
+
+    // Saved selections follow the edits made since they were saved.
+    cx.update_editor(|editor, window, cx| {
+        editor.restore_selection_set(
+            &RestoreSelectionSet {
+                name: Some("words".into()),
+            },
+            window,
+            cx,
+        );
+    });
+    cx.assert_editor_state("zero «oneˇ» two «threeˇ»\nfour ˇfive\n");
+
+    cx.update_editor(|editor, window, cx| {
+        editor.restore_selection_set(&RestoreSelectionSet::default(), window, cx);
+    });
+    cx.assert_editor_state("zero oneˇ two three\nfour five\n");
+
+    // Restoring a set that was never saved leaves the selections alone.
+    cx.update_editor(|editor, window, cx| {
+        editor.restore_selection_set(
+            &RestoreSelectionSet {
+                name: Some("missing".into()),
+            },
+            window,
+            cx,
+        );
+    });
+    cx.assert_editor_state("zero oneˇ two three\nfour five\n");
+}
+
+#[gpui::test]
+async fn test_syntax_errors(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(rust_lang()), cx));
+    cx.set_state("ˇfn a() {}\nfn b() { let c = ; }\nfn d() {}\n");
+    cx.run_until_parked();
+
+    cx.update_editor(|editor, window, cx| {
+        editor.go_to_next_syntax_error(&GoToNextSyntaxError, window, cx);
+        assert_eq!(editor.selections.newest::<Point>(cx).head().row, 1);
+        assert!(editor.syntax_error_highlights(cx).is_empty());
+
+        editor.toggle_syntax_errors(&ToggleSyntaxErrors, window, cx);
+        assert!(!editor.syntax_error_highlights(cx).is_empty());
+        editor.toggle_syntax_errors(&ToggleSyntaxErrors, window, cx);
+        assert!(editor.syntax_error_highlights(cx).is_empty());
+    });
+}
+
 #[gpui::test]
 async fn test_go_to_hunk(executor: BackgroundExecutor, cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -17409,30 +20747,103 @@ async fn test_completions_with_additional_edits(cx: &mut TestAppContext) {
         filter_text: Some("some".to_string()),
         insert_text_format: Some(lsp::InsertTextFormat::SNIPPET),
         text_edit: Some(lsp::CompletionTextEdit::Edit(lsp::TextEdit {
-            range: lsp::Range {
-                start: lsp::Position {
-                    line: 0,
-                    character: 22,
-                },
-                end: lsp::Position {
-                    line: 0,
-                    character: 22,
-                },
-            },
+            range: lsp::Range {
+                start: lsp::Position {
+                    line: 0,
+                    character: 22,
+                },
+                end: lsp::Position {
+                    line: 0,
+                    character: 22,
+                },
+            },
+            new_text: "Some(2)".to_string(),
+        })),
+        additional_text_edits: Some(vec![lsp::TextEdit {
+            range: lsp::Range {
+                start: lsp::Position {
+                    line: 0,
+                    character: 20,
+                },
+                end: lsp::Position {
+                    line: 0,
+                    character: 22,
+                },
+            },
+            new_text: "".to_string(),
+        }]),
+        ..Default::default()
+    };
+
+    let closure_completion_item = completion_item.clone();
+    let mut request = cx.set_request_handler::<lsp::request::Completion, _, _>(move |_, _, _| {
+        let task_completion_item = closure_completion_item.clone();
+        async move {
+            Ok(Some(lsp::CompletionResponse::Array(vec![
+                task_completion_item,
+            ])))
+        }
+    });
+
+    request.next().await;
+
+    cx.condition(|editor, _| editor.context_menu_visible())
+        .await;
+    let apply_additional_edits = cx.update_editor(|editor, window, cx| {
+        editor
+            .confirm_completion(&ConfirmCompletion::default(), window, cx)
+            .unwrap()
+    });
+    cx.assert_editor_state("fn main() { let a = 2.Some(2)ˇ; }");
+
+    cx.set_request_handler::<lsp::request::ResolveCompletionItem, _, _>(move |_, _, _| {
+        let task_completion_item = completion_item.clone();
+        async move { Ok(task_completion_item) }
+    })
+    .next()
+    .await
+    .unwrap();
+    apply_additional_edits.await.unwrap();
+    cx.assert_editor_state("fn main() { let a = Some(2)ˇ; }");
+}
+
+#[gpui::test]
+async fn test_completions_with_auto_imports_disabled(cx: &mut TestAppContext) {
+    init_test(cx, |language_settings| {
+        language_settings.defaults.completions = Some(CompletionSettingsContent {
+            auto_imports: Some(false),
+            ..Default::default()
+        });
+    });
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            completion_provider: Some(lsp::CompletionOptions {
+                trigger_characters: Some(vec![".".to_string()]),
+                resolve_provider: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+
+    cx.set_state("fn main() { let a = 2ˇ; }");
+    cx.simulate_keystroke(".");
+    let completion_item = lsp::CompletionItem {
+        label: "some".into(),
+        kind: Some(lsp::CompletionItemKind::SNIPPET),
+        sort_text: Some("fffffff2".to_string()),
+        filter_text: Some("some".to_string()),
+        insert_text_format: Some(lsp::InsertTextFormat::SNIPPET),
+        text_edit: Some(lsp::CompletionTextEdit::Edit(lsp::TextEdit {
+            range: lsp::Range::new(lsp::Position::new(0, 22), lsp::Position::new(0, 22)),
             new_text: "Some(2)".to_string(),
         })),
         additional_text_edits: Some(vec![lsp::TextEdit {
-            range: lsp::Range {
-                start: lsp::Position {
-                    line: 0,
-                    character: 20,
-                },
-                end: lsp::Position {
-                    line: 0,
-                    character: 22,
-                },
-            },
-            new_text: "".to_string(),
+            range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, 0)),
+            new_text: "use std::option::Option;\n".to_string(),
         }]),
         ..Default::default()
     };
@@ -17446,7 +20857,6 @@ async fn test_completions_with_additional_edits(cx: &mut TestAppContext) {
             ])))
         }
     });
-
     request.next().await;
 
     cx.condition(|editor, _| editor.context_menu_visible())
@@ -17456,17 +20866,8 @@ async fn test_completions_with_additional_edits(cx: &mut TestAppContext) {
             .confirm_completion(&ConfirmCompletion::default(), window, cx)
             .unwrap()
     });
-    cx.assert_editor_state("fn main() { let a = 2.Some(2)ˇ; }");
-
-    cx.set_request_handler::<lsp::request::ResolveCompletionItem, _, _>(move |_, _, _| {
-        let task_completion_item = completion_item.clone();
-        async move { Ok(task_completion_item) }
-    })
-    .next()
-    .await
-    .unwrap();
     apply_additional_edits.await.unwrap();
-    cx.assert_editor_state("fn main() { let a = Some(2)ˇ; }");
+    cx.assert_editor_state("fn main() { let a = 2.Some(2)ˇ; }");
 }
 
 #[gpui::test]
@@ -19497,6 +22898,76 @@ async fn test_expand_diff_hunk_at_excerpt_boundary(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_toggle_inline_diff(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state(indoc! {"
+        one
+        ˇTWO
+        three
+        four
+    "});
+    cx.set_head_text(indoc! {"
+        one
+        two
+        three
+        four
+    "});
+    executor.run_until_parked();
+
+    cx.update_editor(|editor, window, cx| {
+        editor.toggle_inline_diff(&ToggleInlineDiff, window, cx);
+    });
+    executor.run_until_parked();
+    cx.assert_state_with_diff(
+        indoc! {"
+              one
+            - two
+            + ˇTWO
+              three
+              four
+        "}
+        .to_string(),
+    );
+
+    // Hunks created while the inline diff is shown are expanded, and canceling doesn't hide them.
+    cx.update_editor(|editor, window, cx| {
+        editor.move_down(&MoveDown, window, cx);
+        editor.move_down(&MoveDown, window, cx);
+        editor.handle_input("FOUR", window, cx);
+        editor.delete_to_end_of_line(&DeleteToEndOfLine, window, cx);
+        editor.cancel(&Cancel, window, cx);
+    });
+    executor.run_until_parked();
+    cx.assert_state_with_diff(
+        indoc! {"
+              one
+            - two
+            + TWO
+              three
+            - four
+            + FOURˇ
+        "}
+        .to_string(),
+    );
+
+    cx.update_editor(|editor, window, cx| {
+        editor.toggle_inline_diff(&ToggleInlineDiff, window, cx);
+    });
+    executor.run_until_parked();
+    cx.assert_state_with_diff(
+        indoc! {"
+            one
+            TWO
+            three
+            FOURˇ
+        "}
+        .to_string(),
+    );
+}
+
 #[gpui::test]
 async fn test_edits_around_expanded_insertion_hunks(
     executor: BackgroundExecutor,
@@ -21240,6 +24711,56 @@ async fn test_partially_staged_hunk(cx: &mut TestAppContext) {
     "}));
 }
 
+#[gpui::test]
+async fn test_stage_and_unstage_hunk_at_cursor(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let base_text = indoc! {"
+        one
+        two
+        three
+        four
+        five
+    "};
+    cx.set_head_text(base_text);
+    cx.set_index_text(base_text);
+    cx.set_state(indoc! {"
+        one
+        ˇTWO
+        three
+        FOUR
+        five
+    "});
+    cx.run_until_parked();
+
+    // Only the hunk at the cursor is staged, and the cursor stays in place.
+    cx.update_editor(|editor, window, cx| {
+        editor.stage_hunk_at_cursor(&StageHunkAtCursor, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_index_text(Some(indoc! {"
+        one
+        TWO
+        three
+        four
+        five
+    "}));
+    cx.assert_editor_state(indoc! {"
+        one
+        ˇTWO
+        three
+        FOUR
+        five
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        editor.unstage_hunk_at_cursor(&UnstageHunkAtCursor, window, cx);
+    });
+    cx.run_until_parked();
+    cx.assert_index_text(Some(base_text));
+}
+
 #[gpui::test]
 fn test_crease_insertion_and_rendering(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -21324,104 +24845,448 @@ async fn test_input_text(cx: &mut TestAppContext) {
         fourˇ
         five
 
-        siˇx"#
-            .unindent(),
-    );
+        siˇx"#
+            .unindent(),
+    );
+
+    cx.dispatch_action(HandleInput(String::new()));
+    cx.assert_editor_state(
+        &r#"ˇone
+        two
+
+        three
+        fourˇ
+        five
+
+        siˇx"#
+            .unindent(),
+    );
+
+    cx.dispatch_action(HandleInput("AAAA".to_string()));
+    cx.assert_editor_state(
+        &r#"AAAAˇone
+        two
+
+        three
+        fourAAAAˇ
+        five
+
+        siAAAAˇx"#
+            .unindent(),
+    );
+}
+
+#[gpui::test]
+async fn test_scroll_cursor_center_top_bottom(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.set_state(
+        r#"let foo = 1;
+let foo = 2;
+let foo = 3;
+let fooˇ = 4;
+let foo = 5;
+let foo = 6;
+let foo = 7;
+let foo = 8;
+let foo = 9;
+let foo = 10;
+let foo = 11;
+let foo = 12;
+let foo = 13;
+let foo = 14;
+let foo = 15;"#,
+    );
+
+    cx.update_editor(|e, window, cx| {
+        assert_eq!(
+            e.next_scroll_position,
+            NextScrollCursorCenterTopBottom::Center,
+            "Default next scroll direction is center",
+        );
+
+        e.scroll_cursor_center_top_bottom(&ScrollCursorCenterTopBottom, window, cx);
+        assert_eq!(
+            e.next_scroll_position,
+            NextScrollCursorCenterTopBottom::Top,
+            "After center, next scroll direction should be top",
+        );
+
+        e.scroll_cursor_center_top_bottom(&ScrollCursorCenterTopBottom, window, cx);
+        assert_eq!(
+            e.next_scroll_position,
+            NextScrollCursorCenterTopBottom::Bottom,
+            "After top, next scroll direction should be bottom",
+        );
+
+        e.scroll_cursor_center_top_bottom(&ScrollCursorCenterTopBottom, window, cx);
+        assert_eq!(
+            e.next_scroll_position,
+            NextScrollCursorCenterTopBottom::Center,
+            "After bottom, scrolling should start over",
+        );
+
+        e.scroll_cursor_center_top_bottom(&ScrollCursorCenterTopBottom, window, cx);
+        assert_eq!(
+            e.next_scroll_position,
+            NextScrollCursorCenterTopBottom::Top,
+            "Scrolling continues if retriggered fast enough"
+        );
+    });
+
+    cx.executor()
+        .advance_clock(SCROLL_CENTER_TOP_BOTTOM_DEBOUNCE_TIMEOUT + Duration::from_millis(200));
+    cx.executor().run_until_parked();
+    cx.update_editor(|e, _, _| {
+        assert_eq!(
+            e.next_scroll_position,
+            NextScrollCursorCenterTopBottom::Center,
+            "If scrolling is not triggered fast enough, it should reset"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_peek_definition(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            definition_provider: Some(lsp::OneOf::Left(true)),
+            ..lsp::ServerCapabilities::default()
+        },
+        cx,
+    )
+    .await;
+
+    cx.set_state(
+        &r#"fn one() {
+            let mut a = ˇtwo();
+        }
+
+        fn two() {}
+
+        fn three() {}"#
+            .unindent(),
+    );
+    let mut definitions = cx
+        .lsp
+        .set_request_handler::<lsp::request::GotoDefinition, _, _>(move |params, _| async move {
+            let uri = params.text_document_position_params.text_document.uri;
+            Ok(Some(lsp::GotoDefinitionResponse::Array(vec![
+                lsp::Location {
+                    uri: uri.clone(),
+                    range: lsp::Range::new(lsp::Position::new(4, 3), lsp::Position::new(4, 6)),
+                },
+                lsp::Location {
+                    uri,
+                    range: lsp::Range::new(lsp::Position::new(6, 3), lsp::Position::new(6, 8)),
+                },
+            ])))
+        });
+
+    cx.update_editor(|editor, window, cx| editor.peek_definition(&PeekDefinition, window, cx))
+        .expect("Peek definition was not started")
+        .await
+        .expect("Failed to peek definition");
+    definitions.next().await.unwrap();
+    cx.run_until_parked();
 
-    cx.dispatch_action(HandleInput(String::new()));
+    // The cursor stays where it was, while the peek shows the first definition.
     cx.assert_editor_state(
-        &r#"ˇone
-        two
+        &r#"fn one() {
+            let mut a = ˇtwo();
+        }
 
-        three
-        fourˇ
-        five
+        fn two() {}
 
-        siˇx"#
+        fn three() {}"#
             .unindent(),
     );
+    let peeked_cursor = |cx: &mut EditorLspTestContext| {
+        cx.update_editor(|editor, _, cx| {
+            let (peek_editor, index, count) = editor
+                .peeked_definition()
+                .expect("definition is not peeked");
+            let cursor = peek_editor.update(cx, |peek_editor, cx| {
+                peek_editor.selections.newest::<Point>(cx).head()
+            });
+            (cursor, index, count)
+        })
+    };
+    assert_eq!(peeked_cursor(&mut cx), (Point::new(4, 3), 0, 2));
 
-    cx.dispatch_action(HandleInput("AAAA".to_string()));
+    cx.update_editor(|editor, window, cx| {
+        editor.peek_next_definition(&PeekNextDefinition, window, cx)
+    });
+    assert_eq!(peeked_cursor(&mut cx), (Point::new(6, 3), 1, 2));
+    cx.update_editor(|editor, window, cx| {
+        editor.peek_next_definition(&PeekNextDefinition, window, cx)
+    });
+    assert_eq!(peeked_cursor(&mut cx), (Point::new(4, 3), 0, 2));
+    cx.update_editor(|editor, window, cx| {
+        editor.peek_previous_definition(&PeekPreviousDefinition, window, cx)
+    });
+    assert_eq!(peeked_cursor(&mut cx), (Point::new(6, 3), 1, 2));
+
+    // Edits in the peek are made to the underlying buffer.
+    cx.update_editor(|editor, window, cx| {
+        let (peek_editor, _, _) = editor.peeked_definition().unwrap();
+        peek_editor.update(cx, |peek_editor, cx| {
+            peek_editor.insert("renamed_", window, cx)
+        });
+    });
+    cx.update_editor(|editor, window, cx| editor.cancel(&Cancel, window, cx));
+    assert!(cx.update_editor(|editor, _, _| editor.peeked_definition().is_none()));
     cx.assert_editor_state(
-        &r#"AAAAˇone
-        two
+        &r#"fn one() {
+            let mut a = ˇtwo();
+        }
 
-        three
-        fourAAAAˇ
-        five
+        fn two() {}
 
-        siAAAAˇx"#
+        fn renamed_three() {}"#
             .unindent(),
     );
 }
 
 #[gpui::test]
-async fn test_scroll_cursor_center_top_bottom(cx: &mut TestAppContext) {
+async fn test_call_hierarchy(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+            ..lsp::ServerCapabilities::default()
+        },
+        cx,
+    )
+    .await;
 
-    let mut cx = EditorTestContext::new(cx).await;
     cx.set_state(
-        r#"let foo = 1;
-let foo = 2;
-let foo = 3;
-let fooˇ = 4;
-let foo = 5;
-let foo = 6;
-let foo = 7;
-let foo = 8;
-let foo = 9;
-let foo = 10;
-let foo = 11;
-let foo = 12;
-let foo = 13;
-let foo = 14;
-let foo = 15;"#,
+        &r#"fn one() {
+            two();
+        }
+
+        fn twˇo() {
+            three();
+        }
+
+        fn three() {}"#
+            .unindent(),
     );
 
-    cx.update_editor(|e, window, cx| {
+    let uri = cx.buffer_lsp_url.clone();
+    let item = move |name: &str, line: u32| lsp::CallHierarchyItem {
+        name: name.to_string(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: None,
+        detail: None,
+        uri: uri.clone(),
+        range: lsp::Range::new(lsp::Position::new(line, 0), lsp::Position::new(line + 2, 1)),
+        selection_range: lsp::Range::new(
+            lsp::Position::new(line, 3),
+            lsp::Position::new(line, 3 + name.len() as u32),
+        ),
+        data: None,
+    };
+    cx.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>({
+        let item = item.clone();
+        move |_, _, _| {
+            let item = item.clone();
+            async move { Ok(Some(vec![item("two", 4)])) }
+        }
+    });
+    cx.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>({
+        let item = item.clone();
+        move |_, params, _| {
+            let item = item.clone();
+            async move {
+                let calls = match params.item.name.as_str() {
+                    "two" => vec![lsp::CallHierarchyIncomingCall {
+                        from: item("one", 0),
+                        from_ranges: vec![lsp::Range::new(
+                            lsp::Position::new(1, 4),
+                            lsp::Position::new(1, 7),
+                        )],
+                    }],
+                    _ => Vec::new(),
+                };
+                Ok(Some(calls))
+            }
+        }
+    });
+    cx.set_request_handler::<lsp::request::CallHierarchyOutgoingCalls, _, _>({
+        let item = item.clone();
+        move |_, params, _| {
+            let item = item.clone();
+            async move {
+                let calls = match params.item.name.as_str() {
+                    "two" => vec![lsp::CallHierarchyOutgoingCall {
+                        to: item("three", 8),
+                        from_ranges: vec![lsp::Range::new(
+                            lsp::Position::new(5, 4),
+                            lsp::Position::new(5, 9),
+                        )],
+                    }],
+                    _ => Vec::new(),
+                };
+                Ok(Some(calls))
+            }
+        }
+    });
+
+    cx.update_editor(|editor, window, cx| {
+        editor.show_incoming_calls(&ShowIncomingCalls, window, cx)
+    })
+    .expect("Call hierarchy was not requested")
+    .await
+    .expect("Failed to show the call hierarchy");
+    cx.run_until_parked();
+
+    let view = cx.update_workspace(|workspace, _, cx| {
+        workspace
+            .active_item_as::<CallHierarchyView>(cx)
+            .expect("call hierarchy was not opened")
+    });
+    cx.update(|_, cx| {
+        assert_eq!(view.read(cx).direction(), CallHierarchyDirection::Incoming);
         assert_eq!(
-            e.next_scroll_position,
-            NextScrollCursorCenterTopBottom::Center,
-            "Default next scroll direction is center",
+            view.read(cx).visible_entries(),
+            [(0, "two".to_string()), (1, "one".to_string())]
         );
+    });
 
-        e.scroll_cursor_center_top_bottom(&ScrollCursorCenterTopBottom, window, cx);
+    // Callers are loaded level by level, as they are expanded.
+    cx.update(|window, cx| {
+        view.update(cx, |view, cx| view.expand_all_visible(window, cx));
+    });
+    cx.run_until_parked();
+    cx.update(|_, cx| {
         assert_eq!(
-            e.next_scroll_position,
-            NextScrollCursorCenterTopBottom::Top,
-            "After center, next scroll direction should be top",
+            view.read(cx).visible_entries(),
+            [(0, "two".to_string()), (1, "one".to_string())]
         );
+    });
 
-        e.scroll_cursor_center_top_bottom(&ScrollCursorCenterTopBottom, window, cx);
+    cx.update(|window, cx| {
+        view.update(cx, |view, cx| {
+            view.toggle_direction(&ToggleCallDirection, window, cx)
+        });
+    });
+    cx.run_until_parked();
+    cx.update(|_, cx| {
+        assert_eq!(view.read(cx).direction(), CallHierarchyDirection::Outgoing);
         assert_eq!(
-            e.next_scroll_position,
-            NextScrollCursorCenterTopBottom::Bottom,
-            "After top, next scroll direction should be bottom",
+            view.read(cx).visible_entries(),
+            [(0, "two".to_string()), (1, "three".to_string())]
         );
+    });
+}
 
-        e.scroll_cursor_center_top_bottom(&ScrollCursorCenterTopBottom, window, cx);
+#[gpui::test]
+async fn test_type_hierarchy(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorLspTestContext::new_rust(lsp::ServerCapabilities::default(), cx).await;
+
+    cx.set_state(
+        &r#"trait Shape {}
+
+        trait Polygˇon: Shape {}
+
+        struct Square;
+        impl Polygon for Square {}"#
+            .unindent(),
+    );
+
+    let uri = cx.buffer_lsp_url.clone();
+    let item =
+        move |name: &str, kind: lsp::SymbolKind, line: u32, column: u32| lsp::TypeHierarchyItem {
+            name: name.to_string(),
+            kind,
+            tags: None,
+            detail: None,
+            uri: uri.clone(),
+            range: lsp::Range::new(lsp::Position::new(line, 0), lsp::Position::new(line, 80)),
+            selection_range: lsp::Range::new(
+                lsp::Position::new(line, column),
+                lsp::Position::new(line, column + name.len() as u32),
+            ),
+            data: None,
+        };
+    cx.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>({
+        let item = item.clone();
+        move |_, _, _| {
+            let item = item.clone();
+            async move {
+                Ok(Some(vec![item(
+                    "Polygon",
+                    lsp::SymbolKind::INTERFACE,
+                    2,
+                    6,
+                )]))
+            }
+        }
+    });
+    cx.set_request_handler::<lsp::request::TypeHierarchySupertypes, _, _>({
+        let item = item.clone();
+        move |_, params, _| {
+            let item = item.clone();
+            async move {
+                let supertypes = match params.item.name.as_str() {
+                    "Polygon" => vec![item("Shape", lsp::SymbolKind::INTERFACE, 0, 6)],
+                    _ => Vec::new(),
+                };
+                Ok(Some(supertypes))
+            }
+        }
+    });
+    cx.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>({
+        let item = item.clone();
+        move |_, params, _| {
+            let item = item.clone();
+            async move {
+                let subtypes = match params.item.name.as_str() {
+                    "Polygon" => vec![item("Square", lsp::SymbolKind::STRUCT, 4, 7)],
+                    _ => Vec::new(),
+                };
+                Ok(Some(subtypes))
+            }
+        }
+    });
+
+    cx.update_editor(|editor, window, cx| editor.show_supertypes(&ShowSupertypes, window, cx))
+        .expect("Type hierarchy was not requested")
+        .await
+        .expect("Failed to show the type hierarchy");
+    cx.run_until_parked();
+
+    let view = cx.update_workspace(|workspace, _, cx| {
+        workspace
+            .active_item_as::<TypeHierarchyView>(cx)
+            .expect("type hierarchy was not opened")
+    });
+    cx.update(|_, cx| {
         assert_eq!(
-            e.next_scroll_position,
-            NextScrollCursorCenterTopBottom::Center,
-            "After bottom, scrolling should start over",
+            view.read(cx).direction(),
+            TypeHierarchyDirection::Supertypes
         );
-
-        e.scroll_cursor_center_top_bottom(&ScrollCursorCenterTopBottom, window, cx);
         assert_eq!(
-            e.next_scroll_position,
-            NextScrollCursorCenterTopBottom::Top,
-            "Scrolling continues if retriggered fast enough"
+            view.read(cx).visible_entries(),
+            [(0, "Polygon".to_string()), (1, "Shape".to_string())]
         );
     });
 
-    cx.executor()
-        .advance_clock(SCROLL_CENTER_TOP_BOTTOM_DEBOUNCE_TIMEOUT + Duration::from_millis(200));
-    cx.executor().run_until_parked();
-    cx.update_editor(|e, _, _| {
+    cx.update(|window, cx| {
+        view.update(cx, |view, cx| {
+            view.toggle_direction(&ToggleTypeDirection, window, cx)
+        });
+    });
+    cx.run_until_parked();
+    cx.update(|_, cx| {
+        assert_eq!(view.read(cx).direction(), TypeHierarchyDirection::Subtypes);
         assert_eq!(
-            e.next_scroll_position,
-            NextScrollCursorCenterTopBottom::Center,
-            "If scrolling is not triggered fast enough, it should reset"
+            view.read(cx).visible_entries(),
+            [(0, "Polygon".to_string()), (1, "Square".to_string())]
         );
     });
 }
@@ -22762,54 +26627,149 @@ async fn test_breakpoint_toggling(cx: &mut TestAppContext) {
             .read(cx)
             .all_source_breakpoints(cx)
     });
-
-    assert_eq!(1, breakpoints.len());
-    assert_breakpoint(
-        &breakpoints,
-        &abs_path,
-        vec![
-            (0, Breakpoint::new_standard()),
-            (3, Breakpoint::new_standard()),
-        ],
-    );
+
+    assert_eq!(1, breakpoints.len());
+    assert_breakpoint(
+        &breakpoints,
+        &abs_path,
+        vec![
+            (0, Breakpoint::new_standard()),
+            (3, Breakpoint::new_standard()),
+        ],
+    );
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.move_to_beginning(&MoveToBeginning, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    });
+
+    let breakpoints = editor.update(cx, |editor, cx| {
+        editor
+            .breakpoint_store()
+            .as_ref()
+            .unwrap()
+            .read(cx)
+            .all_source_breakpoints(cx)
+    });
+
+    assert_eq!(1, breakpoints.len());
+    assert_breakpoint(
+        &breakpoints,
+        &abs_path,
+        vec![(3, Breakpoint::new_standard())],
+    );
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.move_to_end(&MoveToEnd, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    });
+
+    let breakpoints = editor.update(cx, |editor, cx| {
+        editor
+            .breakpoint_store()
+            .as_ref()
+            .unwrap()
+            .read(cx)
+            .all_source_breakpoints(cx)
+    });
+
+    assert_eq!(0, breakpoints.len());
+    assert_breakpoint(&breakpoints, &abs_path, vec![]);
+}
+
+#[gpui::test]
+async fn test_bookmark_toggling_and_navigation(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/a/main.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    let bookmarked_rows = |cx: &mut VisualTestContext| {
+        project.read_with(cx, |project, cx| {
+            project
+                .bookmark_store()
+                .read(cx)
+                .all_source_bookmarks(cx)
+                .into_values()
+                .flatten()
+                .map(|bookmark| bookmark.row)
+                .collect::<Vec<_>>()
+        })
+    };
+    let cursor_row = |cx: &mut VisualTestContext| {
+        editor.update(cx, |editor, cx| {
+            editor.selections.newest::<Point>(cx).head().row
+        })
+    };
+
+    editor.update_in(cx, |editor, window, cx| {
+        assert!(!editor.has_bookmarks(cx));
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([
+                Point::new(1, 3)..Point::new(1, 3),
+                Point::new(3, 0)..Point::new(3, 0),
+            ])
+        });
+        editor.toggle_bookmark(&actions::ToggleBookmark, window, cx);
+        assert!(editor.has_bookmarks(cx));
+    });
+    assert_eq!(bookmarked_rows(cx), vec![1, 3]);
 
     editor.update_in(cx, |editor, window, cx| {
         editor.move_to_beginning(&MoveToBeginning, window, cx);
-        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
     });
-
-    let breakpoints = editor.update(cx, |editor, cx| {
-        editor
-            .breakpoint_store()
-            .as_ref()
-            .unwrap()
-            .read(cx)
-            .all_source_breakpoints(cx)
+    assert_eq!(cursor_row(cx), 1);
+    editor.update_in(cx, |editor, window, cx| {
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
     });
-
-    assert_eq!(1, breakpoints.len());
-    assert_breakpoint(
-        &breakpoints,
-        &abs_path,
-        vec![(3, Breakpoint::new_standard())],
-    );
-
+    assert_eq!(cursor_row(cx), 3);
     editor.update_in(cx, |editor, window, cx| {
-        editor.move_to_end(&MoveToEnd, window, cx);
-        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
+    });
+    assert_eq!(cursor_row(cx), 1, "navigation should wrap around");
+    editor.update_in(cx, |editor, window, cx| {
+        editor.go_to_previous_bookmark(&actions::GoToPreviousBookmark, window, cx);
+    });
+    assert_eq!(cursor_row(cx), 3, "navigation should wrap around");
+    editor.update_in(cx, |editor, window, cx| {
+        editor.go_to_previous_bookmark(&actions::GoToPreviousBookmark, window, cx);
     });
+    assert_eq!(cursor_row(cx), 1);
 
-    let breakpoints = editor.update(cx, |editor, cx| {
-        editor
-            .breakpoint_store()
-            .as_ref()
-            .unwrap()
-            .read(cx)
-            .all_source_breakpoints(cx)
+    // Toggling a bookmarked line removes its bookmark.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_bookmark(&actions::ToggleBookmark, window, cx);
     });
+    assert_eq!(bookmarked_rows(cx), vec![3]);
 
-    assert_eq!(0, breakpoints.len());
-    assert_breakpoint(&breakpoints, &abs_path, vec![]);
+    editor.update_in(cx, |editor, window, cx| {
+        editor.clear_bookmarks(&actions::ClearBookmarks, window, cx);
+        assert!(!editor.has_bookmarks(cx));
+    });
+    assert_eq!(bookmarked_rows(cx), Vec::<u32>::new());
 }
 
 #[gpui::test]
@@ -23268,6 +27228,79 @@ async fn test_rename_without_prepare(cx: &mut TestAppContext) {
     "});
 }
 
+#[gpui::test]
+async fn test_rename_with_preview(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let capabilities = lsp::ServerCapabilities {
+        rename_provider: Some(lsp::OneOf::Left(true)),
+        ..Default::default()
+    };
+    let mut cx = EditorLspTestContext::new_rust(capabilities, cx).await;
+
+    cx.set_state(indoc! {"
+        struct Fˇoo {}
+        fn f(_: Foo) {}
+    "});
+
+    cx.update_editor(|e, window, cx| e.rename(&Rename, window, cx))
+        .expect("Prepare rename was not started")
+        .await
+        .expect("Prepare rename failed");
+
+    let mut rename_handler =
+        cx.set_request_handler::<lsp::request::Rename, _, _>(move |url, _, _| async move {
+            let edits = [(0, 7, 10), (1, 8, 11)]
+                .into_iter()
+                .map(|(line, start, end)| lsp::TextEdit {
+                    range: lsp::Range {
+                        start: lsp::Position {
+                            line,
+                            character: start,
+                        },
+                        end: lsp::Position {
+                            line,
+                            character: end,
+                        },
+                    },
+                    new_text: "FooRenamed".to_string(),
+                })
+                .collect();
+            Ok(Some(lsp::WorkspaceEdit::new(
+                std::collections::HashMap::from_iter(Some((url, edits))),
+            )))
+        });
+    let rename_task = cx
+        .update_editor(|e, window, cx| {
+            e.confirm_rename_with_preview(&ConfirmRenameWithPreview, window, cx)
+        })
+        .expect("Confirm rename was not started");
+    rename_handler.next().await.unwrap();
+    rename_task.await.expect("Confirm rename failed");
+    cx.run_until_parked();
+
+    // The edits are not applied to the buffer until the preview is confirmed.
+    cx.assert_editor_state(indoc! {"
+        struct Fooˇ {}
+        fn f(_: Foo) {}
+    "});
+    let buffer = cx.update_editor(|editor, _, cx| editor.buffer().read(cx).as_singleton().unwrap());
+    let preview = cx.update_workspace(|workspace, _, cx| {
+        workspace
+            .active_item_as::<ProposedChangesEditor>(cx)
+            .expect("rename preview was not opened")
+    });
+    cx.update(|_, cx| {
+        let branch = preview
+            .read(cx)
+            .branch_buffer_for_base(&buffer)
+            .expect("renamed buffer is missing from the preview");
+        assert_eq!(
+            branch.read(cx).text(),
+            "struct FooRenamed {}\nfn f(_: FooRenamed) {}\n"
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_tree_sitter_brackets_newline_insertion(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -26149,6 +30182,104 @@ async fn test_select_next_prev_syntax_node(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_toggle_highlight_word_under_cursor(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+    let pinned_ranges = |cx: &mut EditorTestContext, id: usize| {
+        cx.update_editor(|editor, window, cx| {
+            let snapshot = editor.snapshot(window, cx);
+            editor
+                .background_highlights
+                .get(&HighlightKey::TypePlus(
+                    std::any::TypeId::of::<PinnedWordHighlight>(),
+                    id,
+                ))
+                .map(|(_, ranges)| {
+                    ranges
+                        .iter()
+                        .map(|range| range.to_offset(&snapshot.buffer_snapshot()))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        })
+    };
+
+    cx.set_state("let fˇoo = bar;\nfoo + bar + foobar;\n");
+    cx.update_editor(|editor, window, cx| {
+        editor.toggle_highlight_word_under_cursor(&ToggleHighlightWordUnderCursor, window, cx);
+    });
+    cx.run_until_parked();
+    assert_eq!(pinned_ranges(&mut cx, 0), vec![4..7, 15..18]);
+
+    cx.update_editor(|editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([11..11])
+        });
+        editor.toggle_highlight_word_under_cursor(&ToggleHighlightWordUnderCursor, window, cx);
+    });
+    cx.run_until_parked();
+    assert_eq!(pinned_ranges(&mut cx, 0), vec![4..7, 15..18]);
+    assert_eq!(pinned_ranges(&mut cx, 1), vec![10..13, 21..24]);
+
+    // Highlights follow edits to the buffer.
+    cx.update_editor(|editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([0..0])
+        });
+        editor.handle_input("foo ", window, cx);
+    });
+    cx.executor()
+        .advance_clock(SELECTION_HIGHLIGHT_DEBOUNCE_TIMEOUT);
+    cx.run_until_parked();
+    assert_eq!(pinned_ranges(&mut cx, 0), vec![0..3, 8..11, 19..22]);
+
+    // Toggling a highlighted word again removes its highlight.
+    cx.update_editor(|editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([1..1])
+        });
+        editor.toggle_highlight_word_under_cursor(&ToggleHighlightWordUnderCursor, window, cx);
+    });
+    cx.run_until_parked();
+    assert_eq!(pinned_ranges(&mut cx, 0), Vec::<Range<usize>>::new());
+    assert_eq!(pinned_ranges(&mut cx, 1), vec![14..17, 25..28]);
+
+    // Words pinned beyond the number of colors reuse a color, but keep their own highlights.
+    cx.set_state("ˇa b c d e f g a g\n");
+    for offset in [0, 2, 4, 6, 8, 10, 12] {
+        cx.update_editor(|editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges([offset..offset])
+            });
+            editor.toggle_highlight_word_under_cursor(&ToggleHighlightWordUnderCursor, window, cx);
+        });
+    }
+    cx.run_until_parked();
+    // "a" and "f" share the first color, as "bar" is still pinned with the second one.
+    assert_eq!(pinned_ranges(&mut cx, 2), vec![0..1, 14..15]);
+    assert_eq!(pinned_ranges(&mut cx, 7), vec![10..11]);
+
+    cx.update_editor(|editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([10..10])
+        });
+        editor.toggle_highlight_word_under_cursor(&ToggleHighlightWordUnderCursor, window, cx);
+    });
+    cx.run_until_parked();
+    assert_eq!(pinned_ranges(&mut cx, 7), Vec::<Range<usize>>::new());
+    assert_eq!(pinned_ranges(&mut cx, 2), vec![0..1, 14..15]);
+
+    // Pinned words aren't highlighted in the color of the editor's own selections.
+    cx.update_editor(|_, _, cx| {
+        let theme = cx.theme();
+        for color in PINNED_WORD_HIGHLIGHT_COLORS {
+            assert_ne!(color(theme), theme.players().local().selection);
+        }
+    });
+}
+
 #[gpui::test]
 async fn test_next_prev_document_highlight(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -26244,6 +30375,113 @@ let result = variable * 2;",
     );
 }
 
+#[gpui::test]
+async fn test_word_occurrence_highlights_without_lsp_support(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    // The language server doesn't support `textDocument/documentHighlight`.
+    let mut cx = EditorLspTestContext::new_rust(lsp::ServerCapabilities::default(), cx).await;
+
+    cx.set_state(indoc! {"
+        let fooˇ = 1;
+        let foobar = foo + Foo;
+        foo
+    "});
+    cx.executor().advance_clock(Duration::from_millis(500));
+    cx.run_until_parked();
+    cx.assert_editor_background_highlights::<DocumentHighlightRead>(indoc! {"
+        let «foo» = 1;
+        let foobar = «foo» + Foo;
+        «foo»
+    "});
+
+    // Non-word characters under the cursor clear the highlights.
+    cx.set_state(indoc! {"
+        let foo =ˇ 1;
+        let foobar = foo + Foo;
+        foo
+    "});
+    cx.executor().advance_clock(Duration::from_millis(500));
+    cx.run_until_parked();
+    cx.assert_editor_background_highlights::<DocumentHighlightRead>(indoc! {"
+        let foo = 1;
+        let foobar = foo + Foo;
+        foo
+    "});
+}
+
+#[gpui::test]
+async fn test_document_highlight_styles(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|settings, cx| {
+            settings.update_user_settings(cx, |settings| {
+                settings.editor.document_highlights = Some(DocumentHighlightsContent {
+                    read: Some(DocumentHighlightStyle::Background),
+                    write: Some(DocumentHighlightStyle::Underline),
+                });
+            });
+        });
+    });
+
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            document_highlight_provider: Some(lsp::OneOf::Left(true)),
+            ..lsp::ServerCapabilities::default()
+        },
+        cx,
+    )
+    .await;
+    let _highlight_requests = cx
+        .set_request_handler::<lsp::request::DocumentHighlightRequest, _, _>(
+            move |_, _, _| async move {
+                Ok(Some(vec![
+                    lsp::DocumentHighlight {
+                        range: lsp::Range::new(lsp::Position::new(0, 8), lsp::Position::new(0, 11)),
+                        kind: Some(lsp::DocumentHighlightKind::WRITE),
+                    },
+                    lsp::DocumentHighlight {
+                        range: lsp::Range::new(lsp::Position::new(1, 0), lsp::Position::new(1, 3)),
+                        kind: Some(lsp::DocumentHighlightKind::WRITE),
+                    },
+                    lsp::DocumentHighlight {
+                        range: lsp::Range::new(lsp::Position::new(2, 0), lsp::Position::new(2, 3)),
+                        kind: Some(lsp::DocumentHighlightKind::READ),
+                    },
+                ]))
+            },
+        );
+
+    cx.set_state(indoc! {"
+        let mut fooˇ = 1;
+        foo = 2;
+        foo
+    "});
+    cx.executor().advance_clock(Duration::from_millis(500));
+    cx.run_until_parked();
+    cx.assert_editor_background_highlights::<DocumentHighlightRead>(indoc! {"
+        let mut foo = 1;
+        foo = 2;
+        «foo»
+    "});
+    cx.assert_editor_text_highlights::<DocumentHighlightRead>(indoc! {"
+        let mut foo = 1;
+        foo = 2;
+        foo
+    "});
+    // Underlined occurrences can still be navigated between.
+    cx.assert_editor_background_highlights::<DocumentHighlightWrite>(indoc! {"
+        let mut «foo» = 1;
+        «foo» = 2;
+        foo
+    "});
+    cx.assert_editor_text_highlights::<DocumentHighlightWrite>(indoc! {"
+        let mut «foo» = 1;
+        «foo» = 2;
+        foo
+    "});
+}
+
 #[gpui::test]
 async fn test_paste_url_from_other_app_creates_markdown_link_over_selected_text(
     cx: &mut gpui::TestAppContext,
@@ -26637,3 +30875,117 @@ async fn test_copy_line_without_trailing_newline(cx: &mut TestAppContext) {
 
     cx.assert_editor_state("line1\nline2\nˇ");
 }
+
+#[gpui::test]
+async fn test_quick_fix(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+    let mut cx = EditorLspTestContext::new_rust(
+        lsp::ServerCapabilities {
+            code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(true)),
+            ..Default::default()
+        },
+        cx,
+    )
+    .await;
+    cx.set_state(indoc! {"
+        fn main() {
+            let ˇunused = 42;
+        }
+    "});
+    let diagnostic_range = lsp::Range::new(lsp::Position::new(1, 8), lsp::Position::new(1, 14));
+    cx.lsp
+        .notify::<lsp::notification::PublishDiagnostics>(lsp::PublishDiagnosticsParams {
+            uri: cx.buffer_lsp_url.clone(),
+            version: None,
+            diagnostics: vec![lsp::Diagnostic {
+                range: diagnostic_range,
+                severity: Some(lsp::DiagnosticSeverity::WARNING),
+                message: "unused variable".to_string(),
+                ..Default::default()
+            }],
+        });
+    cx.run_until_parked();
+
+    let url = cx.buffer_lsp_url.clone();
+    let code_action = move |title: &str, kind, is_preferred| {
+        lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+            title: title.to_string(),
+            kind: Some(kind),
+            is_preferred: Some(is_preferred),
+            edit: Some(lsp::WorkspaceEdit {
+                changes: Some(
+                    [(
+                        url.clone(),
+                        vec![lsp::TextEdit {
+                            range: diagnostic_range,
+                            new_text: "_unused".to_string(),
+                        }],
+                    )]
+                    .into_iter()
+                    .collect(),
+                ),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    };
+
+    // Several fixes, none of them preferred, are shown in a menu without the other actions.
+    let mut code_action_requests =
+        cx.set_request_handler::<lsp::request::CodeActionRequest, _, _>({
+            let code_action = code_action.clone();
+            move |_, _, _| {
+                let actions = vec![
+                    code_action(
+                        "Prefix with underscore",
+                        lsp::CodeActionKind::QUICKFIX,
+                        false,
+                    ),
+                    code_action("Remove the variable", lsp::CodeActionKind::QUICKFIX, false),
+                    code_action(
+                        "Extract into function",
+                        lsp::CodeActionKind::REFACTOR,
+                        false,
+                    ),
+                ];
+                async move { Ok(Some(actions)) }
+            }
+        });
+    cx.update_editor(|editor, window, cx| editor.quick_fix(&QuickFix, window, cx));
+    code_action_requests.next().await;
+    cx.run_until_parked();
+    cx.update_editor(|editor, _, _| {
+        let Some(CodeContextMenu::CodeActions(menu)) = editor.context_menu.borrow_mut().take()
+        else {
+            panic!("expected the code actions menu to be shown");
+        };
+        assert_eq!(
+            menu.actions
+                .iter()
+                .map(|item| item.label())
+                .collect::<Vec<_>>(),
+            ["Prefix with underscore", "Remove the variable"]
+        );
+    });
+
+    // A preferred fix is applied right away.
+    let mut code_action_requests =
+        cx.set_request_handler::<lsp::request::CodeActionRequest, _, _>(move |_, _, _| {
+            let actions = vec![
+                code_action(
+                    "Prefix with underscore",
+                    lsp::CodeActionKind::QUICKFIX,
+                    true,
+                ),
+                code_action("Remove the variable", lsp::CodeActionKind::QUICKFIX, false),
+            ];
+            async move { Ok(Some(actions)) }
+        });
+    cx.update_editor(|editor, window, cx| editor.quick_fix(&QuickFix, window, cx));
+    code_action_requests.next().await;
+    cx.run_until_parked();
+    cx.update_editor(|editor, _, cx| {
+        assert!(!editor.context_menu_visible());
+        assert_eq!(editor.text(cx), "fn main() {\n    let _unused = 42;\n}\n");
+    });
+}
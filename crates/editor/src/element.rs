@@ -3,21 +3,23 @@ use crate::{
     CodeActionSource, ColumnarMode, ConflictsOurs, ConflictsOursMarker, ConflictsOuter,
     ConflictsTheirs, ConflictsTheirsMarker, ContextMenuPlacement, CursorShape, CustomBlockId,
     DisplayDiffHunk, DisplayPoint, DisplayRow, DocumentHighlightRead, DocumentHighlightWrite,
-    EditDisplayMode, EditPrediction, Editor, EditorMode, EditorSettings, EditorSnapshot,
-    EditorStyle, FILE_HEADER_HEIGHT, FocusedBlock, GutterDimensions, HalfPageDown, HalfPageUp,
-    HandleInput, HoveredCursor, InlayHintRefreshReason, JumpData, LineDown, LineHighlight, LineUp,
-    MAX_LINE_LEN, MINIMAP_FONT_SIZE, MULTI_BUFFER_EXCERPT_HEADER_HEIGHT, OpenExcerpts, PageDown,
-    PageUp, PhantomBreakpointIndicator, Point, RowExt, RowRangeExt, SelectPhase,
-    SelectedTextHighlight, Selection, SelectionDragState, SoftWrap, StickyHeaderExcerpt, ToPoint,
-    ToggleFold, ToggleFoldAll,
+    DraggedSelectionSource, EditDisplayMode, EditPrediction, Editor, EditorMode, EditorSettings,
+    EditorSnapshot, EditorStyle, FILE_HEADER_HEIGHT, FocusedBlock, GutterDecoration,
+    GutterDimensions, HalfPageDown, HalfPageUp, HandleInput, HoveredCursor, InlayHintRefreshReason,
+    JumpData, LineDown, LineHighlight, LineUp, MAX_LINE_LEN, MINIMAP_FONT_SIZE,
+    MULTI_BUFFER_EXCERPT_HEADER_HEIGHT, OpenExcerpts, PageDown, PageUp, PhantomBreakpointIndicator,
+    Point, RowExt, RowRangeExt, SelectPhase, SelectedTextHighlight, Selection, SelectionDragState,
+    SoftWrap, StickyHeaderExcerpt, ToPoint, ToggleFold, ToggleFoldAll,
+    bidi::BidiLayout,
     code_context_menus::{CodeActionsMenu, MENU_ASIDE_MAX_WIDTH, MENU_ASIDE_MIN_WIDTH, MENU_GAP},
     display_map::{
         Block, BlockContext, BlockStyle, ChunkRendererId, DisplaySnapshot, EditorMargins,
         HighlightKey, HighlightedChunk, ToDisplayPoint,
     },
+    drag_autoscroll::{horizontal_drag_autoscroll_speed, vertical_drag_autoscroll_speed},
     editor_settings::{
-        CurrentLineHighlight, DocumentColorsRenderMode, DoubleClickInMultibuffer, Minimap,
-        MinimapThumb, MinimapThumbBorder, ScrollBeyondLastLine, ScrollbarAxes,
+        CurrentLineHighlight, DocumentColorsRenderMode, DoubleClickInMultibuffer, LineNumbers,
+        Minimap, MinimapThumb, MinimapThumbBorder, ScrollBeyondLastLine, ScrollbarAxes,
         ScrollbarDiagnostics, ShowMinimap,
     },
     git::blame::{BlameRenderer, GitBlame, GlobalBlameRenderer},
@@ -32,6 +34,7 @@ use crate::{
         ActiveScrollbarState, ScrollOffset, ScrollPixelOffset, ScrollbarThumbState,
         scroll_amount::ScrollAmount,
     },
+    todo_comments,
 };
 use buffer_diff::{DiffHunkStatus, DiffHunkStatusKind};
 use collections::{BTreeMap, HashMap};
@@ -44,7 +47,7 @@ use git::{
 use gpui::{
     Action, Along, AnyElement, App, AppContext, AvailableSpace, Axis as ScrollbarAxis, BorderStyle,
     Bounds, ClickEvent, ClipboardItem, ContentMask, Context, Corner, Corners, CursorStyle,
-    DispatchPhase, Edges, Element, ElementInputHandler, Entity, Focusable as _, FontId,
+    DispatchPhase, Edges, Element, ElementInputHandler, Entity, Focusable as _, FontId, FontWeight,
     GlobalElementId, Hitbox, HitboxBehavior, Hsla, InteractiveElement, IntoElement, IsZero,
     KeybindingKeystroke, Length, Modifiers, ModifiersChangedEvent, MouseButton, MouseClickEvent,
     MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad, ParentElement, Pixels, ScrollDelta,
@@ -119,6 +122,13 @@ struct SelectionLayout {
     user_name: Option<SharedString>,
 }
 
+/// The positions of the rulers that follow the newest cursor, if they are visible.
+#[derive(Default)]
+struct CursorRulerLayout {
+    column_x: Option<Pixels>,
+    row_y: Option<Pixels>,
+}
+
 struct InlineBlameLayout {
     element: AnyElement,
     bounds: Bounds<Pixels>,
@@ -241,6 +251,7 @@ impl EditorElement {
         register_action(editor, window, Editor::sort_lines_by_length);
         register_action(editor, window, Editor::sort_lines_case_sensitive);
         register_action(editor, window, Editor::sort_lines_case_insensitive);
+        register_action(editor, window, Editor::sort_lines_numeric);
         register_action(editor, window, Editor::reverse_lines);
         register_action(editor, window, Editor::shuffle_lines);
         register_action(editor, window, Editor::convert_indentation_to_spaces);
@@ -255,8 +266,13 @@ impl EditorElement {
         register_action(editor, window, Editor::convert_to_opposite_case);
         register_action(editor, window, Editor::convert_to_sentence_case);
         register_action(editor, window, Editor::toggle_case);
+        register_action(editor, window, Editor::cycle_case);
         register_action(editor, window, Editor::convert_to_rot13);
         register_action(editor, window, Editor::convert_to_rot47);
+        register_action(editor, window, Editor::format_selection_as_json);
+        register_action(editor, window, Editor::minify_selection_as_json);
+        register_action(editor, window, Editor::format_selection_as_xml);
+        register_action(editor, window, Editor::minify_selection_as_xml);
         register_action(editor, window, Editor::delete_to_previous_word_start);
         register_action(editor, window, Editor::delete_to_previous_subword_start);
         register_action(editor, window, Editor::delete_to_next_word_end);
@@ -267,17 +283,31 @@ impl EditorElement {
         register_action(editor, window, Editor::duplicate_line_up);
         register_action(editor, window, Editor::duplicate_line_down);
         register_action(editor, window, Editor::duplicate_selection);
+        register_action(editor, window, Editor::duplicate_line_and_comment);
         register_action(editor, window, Editor::move_line_up);
         register_action(editor, window, Editor::move_line_down);
         register_action(editor, window, Editor::transpose);
+        register_action(editor, window, Editor::transpose_words);
+        register_action(editor, window, Editor::transpose_lines);
+        register_action(editor, window, Editor::increment_number);
+        register_action(editor, window, Editor::decrement_number);
+        register_action(editor, window, Editor::evaluate_selection);
         register_action(editor, window, Editor::rewrap);
         register_action(editor, window, Editor::cut);
         register_action(editor, window, Editor::kill_ring_cut);
         register_action(editor, window, Editor::kill_ring_yank);
+        register_action(editor, window, Editor::kill_ring_yank_pop);
         register_action(editor, window, Editor::copy);
         register_action(editor, window, Editor::copy_and_trim);
+        register_action(editor, window, Editor::copy_with_syntax_highlighting);
+        register_action(editor, window, Editor::export_as_html);
+        register_action(editor, window, Editor::copy_as_image);
         register_action(editor, window, Editor::diff_clipboard_with_selection);
         register_action(editor, window, Editor::paste);
+        register_action(editor, window, Editor::paste_rectangular);
+        register_action(editor, window, Editor::paste_previous_kill_for_each_cursor);
+        register_action(editor, window, Editor::paste_without_reindent);
+        register_action(editor, window, Editor::block_insert);
         register_action(editor, window, Editor::undo);
         register_action(editor, window, Editor::redo);
         register_action(editor, window, Editor::move_page_up);
@@ -353,6 +383,8 @@ impl EditorElement {
         });
         register_action(editor, window, Editor::select_line);
         register_action(editor, window, Editor::split_selection_into_lines);
+        register_action(editor, window, Editor::align_selections);
+        register_action(editor, window, Editor::align_selections_on);
         register_action(editor, window, Editor::add_selection_above);
         register_action(editor, window, Editor::add_selection_below);
         register_action(editor, window, |editor, action, window, cx| {
@@ -372,18 +404,32 @@ impl EditorElement {
         register_action(editor, window, Editor::select_smaller_syntax_node);
         register_action(editor, window, Editor::select_next_syntax_node);
         register_action(editor, window, Editor::select_prev_syntax_node);
+        register_action(editor, window, Editor::select_next_syntax_sibling);
+        register_action(editor, window, Editor::select_prev_syntax_sibling);
+        register_action(editor, window, Editor::move_node_up);
+        register_action(editor, window, Editor::move_node_down);
+        register_action(editor, window, Editor::swap_with_next_sibling);
         register_action(editor, window, Editor::unwrap_syntax_node);
         register_action(editor, window, Editor::select_enclosing_symbol);
+        register_action(editor, window, Editor::select_inside_string);
+        register_action(editor, window, Editor::select_around_string);
         register_action(editor, window, Editor::move_to_enclosing_bracket);
         register_action(editor, window, Editor::undo_selection);
         register_action(editor, window, Editor::redo_selection);
+        register_action(editor, window, Editor::reselect_last_selection);
         if editor.read(cx).buffer_kind(cx) == ItemBufferKind::Multibuffer {
             register_action(editor, window, Editor::expand_excerpts);
             register_action(editor, window, Editor::expand_excerpts_up);
             register_action(editor, window, Editor::expand_excerpts_down);
+            register_action(editor, window, Editor::expand_excerpts_to_function);
+            register_action(editor, window, Editor::toggle_fold_excerpts);
         }
+        register_action(editor, window, Editor::toggle_read_only);
+        register_action(editor, window, Editor::toggle_editor_profiler);
         register_action(editor, window, Editor::go_to_diagnostic);
         register_action(editor, window, Editor::go_to_prev_diagnostic);
+        register_action(editor, window, Editor::go_to_next_error);
+        register_action(editor, window, Editor::go_to_previous_error);
         register_action(editor, window, Editor::go_to_next_hunk);
         register_action(editor, window, Editor::go_to_prev_hunk);
         register_action(editor, window, Editor::go_to_next_document_highlight);
@@ -398,6 +444,33 @@ impl EditorElement {
                 .go_to_definition_split(action, window, cx)
                 .detach_and_log_err(cx);
         });
+        register_action(editor, window, |editor, action, window, cx| {
+            if let Some(task) = editor.peek_definition(action, window, cx) {
+                task.detach_and_log_err(cx);
+            }
+        });
+        register_action(editor, window, Editor::peek_next_definition);
+        register_action(editor, window, |editor, action, window, cx| {
+            if let Some(task) = editor.show_incoming_calls(action, window, cx) {
+                task.detach_and_notify_err(window, cx);
+            }
+        });
+        register_action(editor, window, |editor, action, window, cx| {
+            if let Some(task) = editor.show_outgoing_calls(action, window, cx) {
+                task.detach_and_notify_err(window, cx);
+            }
+        });
+        register_action(editor, window, |editor, action, window, cx| {
+            if let Some(task) = editor.show_supertypes(action, window, cx) {
+                task.detach_and_notify_err(window, cx);
+            }
+        });
+        register_action(editor, window, |editor, action, window, cx| {
+            if let Some(task) = editor.show_subtypes(action, window, cx) {
+                task.detach_and_notify_err(window, cx);
+            }
+        });
+        register_action(editor, window, Editor::peek_previous_definition);
         register_action(editor, window, |editor, action, window, cx| {
             editor
                 .go_to_declaration(action, window, cx)
@@ -441,8 +514,12 @@ impl EditorElement {
         register_action(editor, window, Editor::fold_at_level_7);
         register_action(editor, window, Editor::fold_at_level_8);
         register_action(editor, window, Editor::fold_at_level_9);
+        register_action(editor, window, Editor::unfold_at_level);
+        register_action(editor, window, Editor::fold_all_regions);
+        register_action(editor, window, Editor::unfold_all_regions);
         register_action(editor, window, Editor::fold_all);
         register_action(editor, window, Editor::fold_function_bodies);
+        register_action(editor, window, Editor::fold_imports);
         register_action(editor, window, Editor::fold_recursive);
         register_action(editor, window, Editor::toggle_fold);
         register_action(editor, window, Editor::toggle_fold_recursive);
@@ -450,12 +527,14 @@ impl EditorElement {
         register_action(editor, window, Editor::unfold_lines);
         register_action(editor, window, Editor::unfold_recursive);
         register_action(editor, window, Editor::unfold_all);
+        register_action(editor, window, Editor::unfold_imports);
         register_action(editor, window, Editor::fold_selected_ranges);
         register_action(editor, window, Editor::set_mark);
         register_action(editor, window, Editor::swap_selection_ends);
         register_action(editor, window, Editor::show_completions);
         register_action(editor, window, Editor::show_word_completions);
         register_action(editor, window, Editor::toggle_code_actions);
+        register_action(editor, window, Editor::quick_fix);
         register_action(editor, window, Editor::open_excerpts);
         register_action(editor, window, Editor::open_excerpts_in_split);
         register_action(editor, window, Editor::open_proposed_changes_editor);
@@ -463,8 +542,26 @@ impl EditorElement {
         register_action(editor, window, Editor::toggle_tab_bar);
         register_action(editor, window, Editor::toggle_line_numbers);
         register_action(editor, window, Editor::toggle_relative_line_numbers);
+        register_action(editor, window, Editor::toggle_ansi_escapes);
+        register_action(editor, window, Editor::toggle_log_level);
+        register_action(editor, window, Editor::toggle_csv_alignment);
+        register_action(editor, window, Editor::show_all_log_levels);
+        register_action(editor, window, Editor::format_table);
+        register_action(editor, window, Editor::insert_table_row_above);
+        register_action(editor, window, Editor::insert_table_row_below);
+        register_action(editor, window, Editor::insert_table_column_left);
+        register_action(editor, window, Editor::insert_table_column_right);
+        register_action(editor, window, Editor::toggle_typewriter_scrolling);
+        register_action(editor, window, Editor::toggle_focus_mode);
+        register_action(editor, window, Editor::zoom_in);
+        register_action(editor, window, Editor::zoom_out);
+        register_action(editor, window, Editor::zoom_reset);
         register_action(editor, window, Editor::toggle_indent_guides);
+        register_action(editor, window, Editor::toggle_highlight_word_under_cursor);
         register_action(editor, window, Editor::toggle_inlay_hints);
+        register_action(editor, window, Editor::toggle_type_inlay_hints);
+        register_action(editor, window, Editor::toggle_parameter_inlay_hints);
+        register_action(editor, window, Editor::toggle_other_inlay_hints);
         register_action(editor, window, Editor::toggle_edit_predictions);
         if editor.read(cx).diagnostics_enabled() {
             register_action(editor, window, Editor::toggle_diagnostics);
@@ -476,6 +573,8 @@ impl EditorElement {
             register_action(editor, window, Editor::toggle_minimap);
         }
         register_action(editor, window, hover_popover::hover);
+        register_action(editor, window, hover_popover::toggle_pinned_hover);
+        register_action(editor, window, hover_popover::focus_hover);
         register_action(editor, window, Editor::reveal_in_finder);
         register_action(editor, window, Editor::copy_path);
         register_action(editor, window, Editor::copy_relative_path);
@@ -485,16 +584,29 @@ impl EditorElement {
         register_action(editor, window, Editor::copy_permalink_to_line);
         register_action(editor, window, Editor::open_permalink_to_line);
         register_action(editor, window, Editor::copy_file_location);
+        register_action(editor, window, Editor::copy_breadcrumb_path);
         register_action(editor, window, Editor::toggle_git_blame);
         register_action(editor, window, Editor::toggle_git_blame_inline);
+        register_action(editor, window, Editor::toggle_inline_diff);
         register_action(editor, window, Editor::open_git_blame_commit);
         register_action(editor, window, Editor::toggle_selected_diff_hunks);
         register_action(editor, window, Editor::toggle_staged_selected_diff_hunks);
         register_action(editor, window, Editor::stage_and_next);
         register_action(editor, window, Editor::unstage_and_next);
+        register_action(editor, window, Editor::stage_hunk_at_cursor);
+        register_action(editor, window, Editor::unstage_hunk_at_cursor);
         register_action(editor, window, Editor::expand_all_diff_hunks);
         register_action(editor, window, Editor::go_to_previous_change);
         register_action(editor, window, Editor::go_to_next_change);
+        register_action(editor, window, Editor::go_to_previous_changed_region);
+        register_action(editor, window, Editor::go_to_next_changed_region);
+        register_action(editor, window, Editor::go_to_last_edit_location);
+        register_action(editor, window, Editor::go_to_next_syntax_error);
+        register_action(editor, window, Editor::go_to_previous_syntax_error);
+        register_action(editor, window, Editor::toggle_syntax_errors);
+        register_action(editor, window, Editor::save_selection_set);
+        register_action(editor, window, Editor::restore_selection_set);
+        register_action(editor, window, Editor::toggle_scroll_lock);
 
         register_action(editor, window, |editor, action, window, cx| {
             if let Some(task) = editor.format(action, window, cx) {
@@ -569,6 +681,20 @@ impl EditorElement {
                 cx.propagate();
             }
         });
+        register_action(editor, window, |editor, action, window, cx| {
+            if let Some(task) = editor.confirm_rename_with_preview(action, window, cx) {
+                task.detach_and_notify_err(window, cx);
+            } else {
+                cx.propagate();
+            }
+        });
+        register_action(editor, window, |editor, action, window, cx| {
+            if let Some(task) = editor.revert_last_workspace_edit(action, window, cx) {
+                task.detach_and_notify_err(window, cx);
+            } else {
+                cx.propagate();
+            }
+        });
         register_action(editor, window, |editor, action, window, cx| {
             if let Some(task) = editor.find_all_references(action, window, cx) {
                 task.detach_and_log_err(cx);
@@ -590,6 +716,7 @@ impl EditorElement {
         register_action(editor, window, Editor::unique_lines_case_insensitive);
         register_action(editor, window, Editor::unique_lines_case_sensitive);
         register_action(editor, window, Editor::accept_partial_edit_prediction);
+        register_action(editor, window, Editor::accept_next_line_edit_prediction);
         register_action(editor, window, Editor::accept_edit_prediction);
         register_action(editor, window, Editor::restore_file);
         register_action(editor, window, Editor::git_restore);
@@ -597,14 +724,24 @@ impl EditorElement {
         register_action(editor, window, Editor::apply_selected_diff_hunks);
         register_action(editor, window, Editor::open_active_item_in_terminal);
         register_action(editor, window, Editor::reload_file);
+        register_action(editor, window, Editor::reload_file_with_merge);
         register_action(editor, window, Editor::spawn_nearest_task);
         register_action(editor, window, Editor::insert_uuid_v4);
         register_action(editor, window, Editor::insert_uuid_v7);
+        register_action(editor, window, Editor::insert_timestamp);
+        register_action(editor, window, Editor::insert_sequence);
         register_action(editor, window, Editor::open_selections_in_multibuffer);
         register_action(editor, window, Editor::toggle_breakpoint);
         register_action(editor, window, Editor::edit_log_breakpoint);
         register_action(editor, window, Editor::enable_breakpoint);
         register_action(editor, window, Editor::disable_breakpoint);
+        register_action(editor, window, Editor::toggle_bookmark);
+        register_action(editor, window, Editor::go_to_next_bookmark);
+        register_action(editor, window, Editor::go_to_previous_bookmark);
+        register_action(editor, window, Editor::clear_bookmarks);
+        register_action(editor, window, Editor::start_recording_macro);
+        register_action(editor, window, Editor::stop_recording_macro);
+        register_action(editor, window, Editor::replay_macro);
         if editor.read(cx).enable_wrap_selections_in_tag(cx) {
             register_action(editor, window, Editor::wrap_selections_in_tag);
         }
@@ -811,10 +948,17 @@ impl EditorElement {
     fn mouse_right_down(
         editor: &mut Editor,
         event: &MouseDownEvent,
+        hovered_hunk: Option<Range<Anchor>>,
         position_map: &PositionMap,
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) {
+        if let Some(hovered_hunk) = hovered_hunk {
+            editor.deploy_diff_hunk_context_menu(hovered_hunk, event.position, window, cx);
+            cx.stop_propagation();
+            return;
+        }
+
         if position_map.gutter_hitbox.is_hovered(window) {
             let gutter_right_padding = editor.gutter_dimensions.right_padding;
             let hitbox = &position_map.gutter_hitbox;
@@ -914,12 +1058,36 @@ impl EditorElement {
                 if !point_for_position.intersects_selection(&selection_display)
                     && text_hitbox.is_hovered(window)
                 {
-                    let is_cut = !(cfg!(target_os = "macos") && event.modifiers.alt
-                        || cfg!(not(target_os = "macos")) && event.modifiers.control);
                     editor.move_selection_on_drop(
                         &selection.clone(),
                         point_for_position.previous_valid,
-                        is_cut,
+                        is_cut_on_drop(&event.modifiers),
+                        window,
+                        cx,
+                    );
+                }
+                editor.selection_drag_state = SelectionDragState::None;
+                if cx.has_global::<DraggedSelectionSource>() {
+                    cx.remove_global::<DraggedSelectionSource>();
+                }
+                // Let another editor under the mouse accept the drop.
+                if text_hitbox.is_hovered(window) {
+                    cx.stop_propagation();
+                }
+                cx.notify();
+                return;
+            }
+            SelectionDragState::DraggingFromOtherEditor {
+                ref source,
+                ref selection,
+                ..
+            } => {
+                if text_hitbox.is_hovered(window) {
+                    editor.drop_selection_from_other_editor(
+                        &source.clone(),
+                        &selection.clone(),
+                        point_for_position.previous_valid,
+                        is_cut_on_drop(&event.modifiers),
                         window,
                         cx,
                     );
@@ -1005,25 +1173,30 @@ impl EditorElement {
         cx: &mut Context<Editor>,
     ) {
         if !editor.has_pending_selection()
-            && matches!(editor.selection_drag_state, SelectionDragState::None)
+            && matches!(
+                editor.selection_drag_state,
+                SelectionDragState::None | SelectionDragState::DraggingFromOtherEditor { .. }
+            )
         {
+            Self::selection_dragged_from_other_editor(editor, event, position_map, window, cx);
             return;
         }
 
         let point_for_position = position_map.point_for_position(event.position);
         let text_hitbox = &position_map.text_hitbox;
 
-        let scroll_delta = {
+        let autoscroll_velocity = {
             let text_bounds = text_hitbox.bounds;
-            let mut scroll_delta = gpui::Point::<f32>::default();
+            let mut velocity = gpui::Point::<f32>::default();
             let vertical_margin = position_map.line_height.min(text_bounds.size.height / 3.0);
             let top = text_bounds.origin.y + vertical_margin;
             let bottom = text_bounds.bottom_left().y - vertical_margin;
+            let line_height = position_map.line_height;
             if event.position.y < top {
-                scroll_delta.y = -scale_vertical_mouse_autoscroll_delta(top - event.position.y);
+                velocity.y = -vertical_drag_autoscroll_speed(top - event.position.y, line_height);
             }
             if event.position.y > bottom {
-                scroll_delta.y = scale_vertical_mouse_autoscroll_delta(event.position.y - bottom);
+                velocity.y = vertical_drag_autoscroll_speed(event.position.y - bottom, line_height);
             }
 
             // We need horizontal width of text
@@ -1040,13 +1213,14 @@ impl EditorElement {
             let right = text_bounds.top_right().x - scroll_space;
 
             if event.position.x < left {
-                scroll_delta.x = -scale_horizontal_mouse_autoscroll_delta(left - event.position.x);
+                velocity.x = -horizontal_drag_autoscroll_speed(left - event.position.x, em_width);
             }
             if event.position.x > right {
-                scroll_delta.x = scale_horizontal_mouse_autoscroll_delta(event.position.x - right);
+                velocity.x = horizontal_drag_autoscroll_speed(event.position.x - right, em_width);
             }
-            scroll_delta
+            velocity
         };
+        let goal_column = point_for_position.exact_unclipped.column();
 
         if !editor.has_pending_selection() {
             let drop_anchor = position_map
@@ -1061,7 +1235,7 @@ impl EditorElement {
                     drop_cursor.start = drop_anchor;
                     drop_cursor.end = drop_anchor;
                     *hide_drop_cursor = !text_hitbox.is_hovered(window);
-                    editor.apply_scroll_delta(scroll_delta, window, cx);
+                    editor.set_drag_autoscroll(autoscroll_velocity, goal_column, window, cx);
                     cx.notify();
                 }
                 SelectionDragState::ReadyToDrag {
@@ -1085,7 +1259,8 @@ impl EditorElement {
                             drop_cursor,
                             hide_drop_cursor: false,
                         };
-                        editor.apply_scroll_delta(scroll_delta, window, cx);
+                        cx.set_global(DraggedSelectionSource(cx.weak_entity()));
+                        editor.set_drag_autoscroll(autoscroll_velocity, goal_column, window, cx);
                         cx.notify();
                     } else {
                         let click_point = position_map.point_for_position(*click_position);
@@ -1102,12 +1277,13 @@ impl EditorElement {
                         editor.select(
                             SelectPhase::Update {
                                 position: point_for_position.previous_valid,
-                                goal_column: point_for_position.exact_unclipped.column(),
-                                scroll_delta,
+                                goal_column,
+                                scroll_delta: gpui::Point::default(),
                             },
                             window,
                             cx,
                         );
+                        editor.set_drag_autoscroll(autoscroll_velocity, goal_column, window, cx);
                     }
                 }
                 _ => {}
@@ -1116,15 +1292,72 @@ impl EditorElement {
             editor.select(
                 SelectPhase::Update {
                     position: point_for_position.previous_valid,
-                    goal_column: point_for_position.exact_unclipped.column(),
-                    scroll_delta,
+                    goal_column,
+                    scroll_delta: gpui::Point::default(),
                 },
                 window,
                 cx,
             );
+            editor.set_drag_autoscroll(autoscroll_velocity, goal_column, window, cx);
         }
     }
 
+    /// Shows a drop cursor while a selection dragged out of another editor hovers over this one.
+    fn selection_dragged_from_other_editor(
+        editor: &mut Editor,
+        event: &MouseMoveEvent,
+        position_map: &PositionMap,
+        window: &mut Window,
+        cx: &mut Context<Editor>,
+    ) {
+        let dragged_selection = cx
+            .try_global::<DraggedSelectionSource>()
+            .and_then(|source| source.0.upgrade())
+            .filter(|source| source.entity_id() != cx.entity_id())
+            .and_then(|source| match &source.read(cx).selection_drag_state {
+                SelectionDragState::Dragging { selection, .. } => {
+                    Some((source.downgrade(), selection.clone()))
+                }
+                _ => None,
+            });
+        let Some((source, selection)) =
+            dragged_selection.filter(|_| position_map.text_hitbox.is_hovered(window))
+        else {
+            if !matches!(editor.selection_drag_state, SelectionDragState::None) {
+                editor.selection_drag_state = SelectionDragState::None;
+                cx.notify();
+            }
+            return;
+        };
+
+        let point_for_position = position_map.point_for_position(event.position);
+        let drop_anchor = position_map
+            .snapshot
+            .display_point_to_anchor(point_for_position.previous_valid, Bias::Left);
+        if let SelectionDragState::DraggingFromOtherEditor {
+            ref mut drop_cursor,
+            ..
+        } = editor.selection_drag_state
+        {
+            drop_cursor.start = drop_anchor;
+            drop_cursor.end = drop_anchor;
+        } else {
+            let drop_cursor = Selection {
+                id: post_inc(&mut editor.selections.next_selection_id()),
+                start: drop_anchor,
+                end: drop_anchor,
+                reversed: false,
+                goal: SelectionGoal::None,
+            };
+            editor.selection_drag_state = SelectionDragState::DraggingFromOtherEditor {
+                source,
+                selection,
+                drop_cursor,
+            };
+        }
+        cx.notify();
+    }
+
     fn mouse_moved(
         editor: &mut Editor,
         event: &MouseMoveEvent,
@@ -1409,21 +1642,29 @@ impl EditorElement {
                 let player = editor.current_user_player_color(cx);
                 selections.push((player, layouts));
 
-                if let SelectionDragState::Dragging {
-                    ref selection,
-                    ref drop_cursor,
-                    ref hide_drop_cursor,
-                } = editor.selection_drag_state
-                    && !hide_drop_cursor
-                    && (drop_cursor
-                        .start
-                        .cmp(&selection.start, &snapshot.buffer_snapshot())
-                        .eq(&Ordering::Less)
-                        || drop_cursor
-                            .end
-                            .cmp(&selection.end, &snapshot.buffer_snapshot())
-                            .eq(&Ordering::Greater))
-                {
+                let drop_cursor = match editor.selection_drag_state {
+                    SelectionDragState::Dragging {
+                        ref selection,
+                        ref drop_cursor,
+                        hide_drop_cursor,
+                    } if !hide_drop_cursor
+                        && (drop_cursor
+                            .start
+                            .cmp(&selection.start, &snapshot.buffer_snapshot())
+                            .eq(&Ordering::Less)
+                            || drop_cursor
+                                .end
+                                .cmp(&selection.end, &snapshot.buffer_snapshot())
+                                .eq(&Ordering::Greater)) =>
+                    {
+                        Some(drop_cursor)
+                    }
+                    SelectionDragState::DraggingFromOtherEditor {
+                        ref drop_cursor, ..
+                    } => Some(drop_cursor),
+                    _ => None,
+                };
+                if let Some(drop_cursor) = drop_cursor {
                     let drag_cursor_layout = SelectionLayout::new(
                         drop_cursor.clone(),
                         false,
@@ -1599,9 +1840,22 @@ impl EditorElement {
 
             let show_local_cursors = editor.show_local_cursors(window, cx);
 
+            let smooth_caret = EditorSettings::get_global(cx).smooth_caret;
+            let previous_cursor_animations = mem::take(&mut editor.cursor_animations);
+            let animate_local_cursors = smooth_caret.enabled
+                && editor.mode.is_full()
+                && editor.selections.count() <= smooth_caret.max_cursors;
+            let cursor_animation_duration = Duration::from_millis(smooth_caret.duration);
+            let now = Instant::now();
+            let mut local_cursor_ix = 0;
+
             for (player_color, selections) in selections {
                 for selection in selections {
                     let cursor_position = selection.head;
+                    let cursor_ix = local_cursor_ix;
+                    if selection.is_local {
+                        local_cursor_ix += 1;
+                    }
 
                     let in_range = visible_display_row_range.contains(&cursor_position.row());
                     if (selection.is_local && !show_local_cursors)
@@ -1717,10 +1971,41 @@ impl EditorElement {
                         }
                     }
 
+                    let mut origin = point(x, y);
+                    if animate_local_cursors && selection.is_local {
+                        let target = point(
+                            ScrollPixelOffset::from(cursor_character_x),
+                            cursor_position.row().as_f64() * ScrollPixelOffset::from(line_height),
+                        );
+                        let animation = match previous_cursor_animations.get(&cursor_ix) {
+                            Some(animation) if animation.to == target => *animation,
+                            Some(animation) => CursorAnimation {
+                                from: animation
+                                    .position(now, cursor_animation_duration)
+                                    .unwrap_or(animation.to),
+                                to: target,
+                                started_at: now,
+                            },
+                            None => CursorAnimation {
+                                from: target,
+                                to: target,
+                                started_at: now,
+                            },
+                        };
+                        if let Some(position) = animation.position(now, cursor_animation_duration) {
+                            origin = point(
+                                Pixels::from(position.x - scroll_pixel_position.x),
+                                Pixels::from(position.y - scroll_pixel_position.y),
+                            );
+                            window.request_animation_frame();
+                        }
+                        editor.cursor_animations.insert(cursor_ix, animation);
+                    }
+
                     let mut cursor = CursorLayout {
                         color: player_color.cursor,
                         block_width,
-                        origin: point(x, y),
+                        origin,
                         line_height,
                         shape: selection.cursor_shape,
                         block_text,
@@ -2791,6 +3076,117 @@ impl EditorElement {
             .collect()
     }
 
+    /// The bounds of the text past the preferred line length, on the visible lines that are
+    /// longer than it.
+    fn layout_line_length_overflows(
+        &self,
+        snapshot: &EditorSnapshot,
+        start_row: DisplayRow,
+        line_layouts: &[LineWithInvisibles],
+        content_origin: gpui::Point<Pixels>,
+        scroll_pixel_position: gpui::Point<ScrollPixelOffset>,
+        line_height: Pixels,
+        text_hitbox: &Hitbox,
+        cx: &App,
+    ) -> Vec<Bounds<Pixels>> {
+        let Some(column) = self.editor.read(cx).line_length_overflow_column(cx) else {
+            return Vec::new();
+        };
+        let text_bounds = text_hitbox.bounds;
+        let buffer = snapshot.buffer_snapshot();
+        line_layouts
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, line)| {
+                let row = DisplayRow(start_row.0 + ix as u32);
+                // The preferred line length counts the characters of the buffer line, which soft
+                // wraps, folds, inlays and tabs lay out on other display rows and columns.
+                let buffer_row = snapshot
+                    .display_point_to_point(DisplayPoint::new(row, 0), Bias::Left)
+                    .row;
+                let overflow_column = buffer
+                    .chars_at(Point::new(buffer_row, 0))
+                    .take_while(|ch| *ch != '\n')
+                    .take(column)
+                    .map(char::len_utf8)
+                    .sum::<usize>() as u32;
+                if overflow_column >= buffer.line_len(MultiBufferRow(buffer_row)) {
+                    return None;
+                }
+                let overflow_start = snapshot
+                    .point_to_display_point(Point::new(buffer_row, overflow_column), Bias::Right);
+                let start_index = match overflow_start.row().cmp(&row) {
+                    Ordering::Less => 0,
+                    Ordering::Equal => overflow_start.column() as usize,
+                    Ordering::Greater => return None,
+                };
+
+                let scroll_x = Pixels::from(scroll_pixel_position.x);
+                let start_x = (content_origin.x + line.x_for_index(start_index) - scroll_x)
+                    .max(text_bounds.left());
+                let end_x = (content_origin.x + line.width - scroll_x).min(text_bounds.right());
+                let y = content_origin.y
+                    + Pixels::from(
+                        row.0 as f64 * ScrollPixelOffset::from(line_height)
+                            - scroll_pixel_position.y,
+                    );
+                (start_x < end_x).then(|| Bounds {
+                    origin: point(start_x, y),
+                    size: size(end_x - start_x, line_height),
+                })
+            })
+            .collect()
+    }
+
+    fn layout_cursor_ruler(
+        &self,
+        snapshot: &EditorSnapshot,
+        newest_selection_head: Option<DisplayPoint>,
+        start_row: DisplayRow,
+        line_layouts: &[LineWithInvisibles],
+        content_origin: gpui::Point<Pixels>,
+        scroll_pixel_position: gpui::Point<ScrollPixelOffset>,
+        line_height: Pixels,
+        text_hitbox: &Hitbox,
+        window: &Window,
+        cx: &App,
+    ) -> CursorRulerLayout {
+        let settings = EditorSettings::get_global(cx).cursor_ruler;
+        let Some(head) = newest_selection_head.filter(|_| snapshot.mode.is_full()) else {
+            return CursorRulerLayout::default();
+        };
+
+        let column_x = settings
+            .column
+            .then(|| {
+                let column = head.column() as usize;
+                // Rows outside of the visible range aren't laid out, so fall back to
+                // measuring the column in the buffer font.
+                let x = head
+                    .row()
+                    .0
+                    .checked_sub(start_row.0)
+                    .and_then(|ix| line_layouts.get(ix as usize))
+                    .map(|line| line.x_for_index(column))
+                    .unwrap_or_else(|| self.column_pixels(column, window));
+                content_origin.x + x - Pixels::from(scroll_pixel_position.x)
+            })
+            .filter(|x| *x >= content_origin.x && *x <= text_hitbox.bounds.right());
+        let row_y = settings
+            .row
+            .then(|| {
+                content_origin.y
+                    + Pixels::from(
+                        head.row().as_f64() * ScrollPixelOffset::from(line_height)
+                            - scroll_pixel_position.y,
+                    )
+                    + line_height
+            })
+            .filter(|y| *y >= text_hitbox.bounds.top() && *y <= text_hitbox.bounds.bottom());
+
+        CursorRulerLayout { column_x, row_y }
+    }
+
     fn calculate_indent_guide_bounds(
         row_range: Range<MultiBufferRow>,
         line_height: Pixels,
@@ -2918,6 +3314,154 @@ impl EditorElement {
         })
     }
 
+    fn layout_bookmarks(
+        &self,
+        line_height: Pixels,
+        range: Range<DisplayRow>,
+        scroll_position: gpui::Point<ScrollOffset>,
+        gutter_dimensions: &GutterDimensions,
+        gutter_hitbox: &Hitbox,
+        display_hunks: &[(DisplayDiffHunk, Option<Hitbox>)],
+        snapshot: &EditorSnapshot,
+        bookmarks: HashMap<DisplayRow, Anchor>,
+        row_infos: &[RowInfo],
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<AnyElement> {
+        self.editor.update(cx, |editor, cx| {
+            bookmarks
+                .into_iter()
+                .filter_map(|(display_row, position)| {
+                    if !range.contains(&display_row) {
+                        return None;
+                    }
+                    if row_infos
+                        .get((display_row - range.start).0 as usize)
+                        .is_some_and(|row_info| row_info.expand_info.is_some())
+                    {
+                        return None;
+                    }
+
+                    let row =
+                        MultiBufferRow(DisplayPoint::new(display_row, 0).to_point(snapshot).row);
+                    if snapshot.is_line_folded(row) {
+                        return None;
+                    }
+
+                    let button = editor.render_bookmark(position, display_row, cx);
+                    let button = prepaint_gutter_button(
+                        button,
+                        display_row,
+                        line_height,
+                        gutter_dimensions,
+                        scroll_position,
+                        gutter_hitbox,
+                        display_hunks,
+                        window,
+                        cx,
+                    );
+                    Some(button)
+                })
+                .collect_vec()
+        })
+    }
+
+    fn layout_gutter_badges(
+        &self,
+        line_height: Pixels,
+        range: Range<DisplayRow>,
+        scroll_position: gpui::Point<ScrollOffset>,
+        gutter_dimensions: &GutterDimensions,
+        gutter_hitbox: &Hitbox,
+        display_hunks: &[(DisplayDiffHunk, Option<Hitbox>)],
+        snapshot: &EditorSnapshot,
+        badges: HashMap<DisplayRow, SharedString>,
+        row_infos: &[RowInfo],
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<AnyElement> {
+        badges
+            .into_iter()
+            .filter_map(|(display_row, label)| {
+                if row_infos
+                    .get((display_row - range.start).0 as usize)
+                    .is_some_and(|row_info| row_info.expand_info.is_some())
+                {
+                    return None;
+                }
+
+                let row = MultiBufferRow(DisplayPoint::new(display_row, 0).to_point(snapshot).row);
+                if snapshot.is_line_folded(row) {
+                    return None;
+                }
+
+                let badge = Label::new(label)
+                    .size(LabelSize::XSmall)
+                    .color(Color::Accent)
+                    .buffer_font(cx);
+                Some(prepaint_gutter_button(
+                    badge,
+                    display_row,
+                    line_height,
+                    gutter_dimensions,
+                    scroll_position,
+                    gutter_hitbox,
+                    display_hunks,
+                    window,
+                    cx,
+                ))
+            })
+            .collect_vec()
+    }
+
+    fn layout_gutter_decorations(
+        &self,
+        line_height: Pixels,
+        range: Range<DisplayRow>,
+        scroll_position: gpui::Point<ScrollOffset>,
+        gutter_dimensions: &GutterDimensions,
+        gutter_hitbox: &Hitbox,
+        display_hunks: &[(DisplayDiffHunk, Option<Hitbox>)],
+        snapshot: &EditorSnapshot,
+        decorations: HashMap<DisplayRow, GutterDecoration>,
+        row_infos: &[RowInfo],
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<AnyElement> {
+        self.editor.update(cx, |editor, cx| {
+            decorations
+                .into_iter()
+                .filter_map(|(display_row, decoration)| {
+                    if row_infos
+                        .get((display_row - range.start).0 as usize)
+                        .is_some_and(|row_info| row_info.expand_info.is_some())
+                    {
+                        return None;
+                    }
+
+                    let row =
+                        MultiBufferRow(DisplayPoint::new(display_row, 0).to_point(snapshot).row);
+                    if snapshot.is_line_folded(row) {
+                        return None;
+                    }
+
+                    let button = editor.render_gutter_decoration(decoration, display_row, cx);
+                    Some(prepaint_gutter_button(
+                        button,
+                        display_row,
+                        line_height,
+                        gutter_dimensions,
+                        scroll_position,
+                        gutter_hitbox,
+                        display_hunks,
+                        window,
+                        cx,
+                    ))
+                })
+                .collect_vec()
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn layout_run_indicators(
         &self,
@@ -3216,7 +3760,18 @@ impl EditorElement {
         } else {
             None
         };
-        let relative_rows = self.calculate_relative_line_numbers(snapshot, &rows, relative_to);
+        let mut relative_rows = self.calculate_relative_line_numbers(snapshot, &rows, relative_to);
+        if is_relative && EditorSettings::get_global(cx).line_numbers == LineNumbers::Relative {
+            // The cursor may be on a soft-wrapped row, so number the start of its line instead.
+            let cursor_line_row = snapshot
+                .prev_line_boundary(newest_selection_head.to_point(snapshot))
+                .1
+                .row();
+            relative_rows.insert(cursor_line_row, 0);
+        }
+        let bold_active_line_numbers = EditorSettings::get_global(cx)
+            .gutter
+            .bold_active_line_numbers;
         let mut line_number = String::new();
         let line_numbers = buffer_rows
             .iter()
@@ -3246,8 +3801,9 @@ impl EditorElement {
                         }
                     })
                     .unwrap_or_else(|| cx.theme().colors().editor_line_number);
+                let bold = bold_active_line_numbers && active_rows.contains_key(&display_row);
                 let shaped_line =
-                    self.shape_line_number(SharedString::from(&line_number), color, window);
+                    self.shape_line_number(SharedString::from(&line_number), color, bold, window);
                 let scroll_top = scroll_position.y * ScrollPixelOffset::from(line_height);
                 let line_origin = gutter_hitbox.map(|hitbox| {
                     hitbox.origin
@@ -3275,6 +3831,7 @@ impl EditorElement {
                 let multi_buffer_row = MultiBufferRow(multi_buffer_row);
                 let line_number = LineNumberLayout {
                     shaped_line,
+                    bold,
                     hitbox,
                 };
                 Some((multi_buffer_row, line_number))
@@ -3337,6 +3894,140 @@ impl EditorElement {
             .collect()
     }
 
+    fn layout_trailing_whitespace(
+        row_infos: &[RowInfo],
+        snapshot: &EditorSnapshot,
+        cx: &App,
+    ) -> Vec<(Range<DisplayPoint>, Hsla)> {
+        let buffer_snapshot = snapshot.buffer_snapshot();
+        let background = cx.theme().colors().editor_trailing_whitespace_background;
+        row_infos
+            .iter()
+            .filter_map(|row_info| row_info.multibuffer_row)
+            .filter(|row| !snapshot.is_line_folded(*row))
+            .filter_map(|row| {
+                let line_end = Point::new(row.0, buffer_snapshot.line_len(row));
+                if !buffer_snapshot
+                    .language_settings_at(line_end, cx)
+                    .highlight_trailing_whitespace
+                {
+                    return None;
+                }
+                let whitespace_len = buffer_snapshot
+                    .reversed_chars_at(line_end)
+                    .take_while(|c| *c == ' ' || *c == '\t')
+                    .count() as u32;
+                if whitespace_len == 0 {
+                    return None;
+                }
+                let start = Point::new(row.0, line_end.column - whitespace_len);
+                Some((
+                    start.to_display_point(snapshot)..line_end.to_display_point(snapshot),
+                    background,
+                ))
+            })
+            .collect()
+    }
+
+    /// The words that changed within the visible, expanded diff hunks.
+    fn layout_word_diffs(
+        row_infos: &[RowInfo],
+        start_row: DisplayRow,
+        range: Range<Anchor>,
+        snapshot: &EditorSnapshot,
+        cx: &App,
+    ) -> Vec<(Range<DisplayPoint>, Hsla)> {
+        let buffer_snapshot = snapshot.buffer_snapshot();
+        let colors = cx.theme().colors();
+        let deleted_background = colors.version_control_deleted.opacity(0.3);
+        let added_background = colors.version_control_added.opacity(0.3);
+        // Words are only highlighted in expanded hunks, whose rows have a diff status.
+        let is_expanded = |range: &Range<DisplayPoint>| {
+            (range.start.row().0..=range.end.row().0).any(|row| {
+                row.checked_sub(start_row.0)
+                    .and_then(|ix| row_infos.get(ix as usize))
+                    .is_some_and(|row_info| row_info.diff_status.is_some())
+            })
+        };
+
+        let mut word_diffs = Vec::new();
+        for hunk in buffer_snapshot.diff_hunks_in_range(range) {
+            if hunk.base_word_diffs.is_empty() && hunk.buffer_word_diffs.is_empty() {
+                continue;
+            }
+            let Some(hunk_start) =
+                buffer_snapshot.anchor_in_excerpt(hunk.excerpt_id, hunk.buffer_range.start)
+            else {
+                continue;
+            };
+            if let Some(diff) = buffer_snapshot.diff_for_buffer_id(hunk.buffer_id) {
+                let base_text = diff.base_text();
+                for range in &hunk.base_word_diffs {
+                    let start =
+                        hunk_start.with_diff_base_anchor(base_text.anchor_after(range.start));
+                    let end = hunk_start.with_diff_base_anchor(base_text.anchor_before(range.end));
+                    word_diffs.push((
+                        start.to_display_point(snapshot)..end.to_display_point(snapshot),
+                        deleted_background,
+                    ));
+                }
+            }
+            for range in &hunk.buffer_word_diffs {
+                let (Some(start), Some(end)) = (
+                    buffer_snapshot.anchor_in_excerpt(hunk.excerpt_id, range.start),
+                    buffer_snapshot.anchor_in_excerpt(hunk.excerpt_id, range.end),
+                ) else {
+                    continue;
+                };
+                word_diffs.push((
+                    start.to_display_point(snapshot)..end.to_display_point(snapshot),
+                    added_background,
+                ));
+            }
+        }
+        word_diffs.retain(|(range, _)| !range.is_empty() && is_expanded(range));
+        word_diffs
+    }
+
+    fn layout_todo_highlights(
+        row_infos: &[RowInfo],
+        snapshot: &EditorSnapshot,
+        cx: &App,
+    ) -> Vec<(Range<DisplayPoint>, Hsla)> {
+        let settings = &EditorSettings::get_global(cx).todo_highlights;
+        if !settings.enabled || settings.tags.is_empty() {
+            return Vec::new();
+        }
+        let buffer_snapshot = snapshot.buffer_snapshot();
+        let background = cx.theme().colors().editor_todo_highlight_background;
+        let mut highlights = Vec::new();
+        for row in row_infos
+            .iter()
+            .filter_map(|row_info| row_info.multibuffer_row)
+            .filter(|row| !snapshot.is_line_folded(*row))
+        {
+            let line = buffer_snapshot
+                .text_for_range(
+                    Point::new(row.0, 0)..Point::new(row.0, buffer_snapshot.line_len(row)),
+                )
+                .collect::<String>();
+            for range in todo_comments::todo_tag_ranges(&line, &settings.tags) {
+                let start = Point::new(row.0, range.start as u32);
+                let end = Point::new(row.0, range.end as u32);
+                let is_todo = buffer_snapshot.point_to_buffer_offset(start).is_some_and(
+                    |(buffer, offset)| todo_comments::is_todo_position(buffer, offset),
+                );
+                if is_todo {
+                    highlights.push((
+                        start.to_display_point(snapshot)..end.to_display_point(snapshot),
+                        background,
+                    ));
+                }
+            }
+        }
+        highlights
+    }
+
     fn bg_segments_per_row(
         rows: Range<DisplayRow>,
         selections: &[(PlayerColor, Vec<SelectionLayout>)],
@@ -3494,6 +4185,7 @@ impl EditorElement {
         snapshot: &EditorSnapshot,
         style: &EditorStyle,
         editor_width: Pixels,
+        max_width: Pixels,
         is_row_soft_wrapped: impl Copy + Fn(usize) -> bool,
         bg_segments_per_row: &[Vec<(Range<DisplayPoint>, Hsla)>],
         window: &mut Window,
@@ -3538,9 +4230,11 @@ impl EditorElement {
                     LineWithInvisibles {
                         width: line.width,
                         len: line.len,
+                        bidi_layouts: smallvec![BidiLayout::for_line(&line)],
                         fragments: smallvec![LineFragment::Text(line)],
                         invisibles: Vec::new(),
                         font_size,
+                        truncated: false,
                     }
                 })
                 .collect()
@@ -3550,6 +4244,7 @@ impl EditorElement {
                 chunks,
                 style,
                 MAX_LINE_LEN,
+                max_width,
                 rows.len(),
                 &snapshot.mode,
                 editor_width,
@@ -5598,24 +6293,36 @@ impl EditorElement {
                     }
 
                     if show_active_line_background && !contains_non_empty_selection.selection {
-                        let highlight_h_range =
-                            match layout.position_map.snapshot.current_line_highlight {
-                                CurrentLineHighlight::Gutter => Some(Range {
-                                    start: layout.hitbox.left(),
-                                    end: layout.gutter_hitbox.right(),
-                                }),
-                                CurrentLineHighlight::Line => Some(Range {
-                                    start: layout.position_map.text_hitbox.bounds.left(),
-                                    end: layout.position_map.text_hitbox.bounds.right(),
-                                }),
-                                CurrentLineHighlight::All => Some(Range {
-                                    start: layout.hitbox.left(),
-                                    end: layout.hitbox.right(),
-                                }),
-                                CurrentLineHighlight::None => None,
+                        let current_line_highlight =
+                            layout.position_map.snapshot.current_line_highlight;
+                        let gutter_h_range = matches!(
+                            current_line_highlight,
+                            CurrentLineHighlight::Gutter | CurrentLineHighlight::All
+                        )
+                        .then(|| Range {
+                            start: layout.hitbox.left(),
+                            end: layout.gutter_hitbox.right(),
+                        });
+                        let text_h_range = match current_line_highlight {
+                            CurrentLineHighlight::Line => Some(Range {
+                                start: layout.position_map.text_hitbox.bounds.left(),
+                                end: layout.position_map.text_hitbox.bounds.right(),
+                            }),
+                            CurrentLineHighlight::All => Some(Range {
+                                start: layout.gutter_hitbox.right(),
+                                end: layout.hitbox.right(),
+                            }),
+                            CurrentLineHighlight::Gutter | CurrentLineHighlight::None => None,
+                        };
+                        let colors = cx.theme().colors();
+                        let highlights = [
+                            (gutter_h_range, colors.editor_active_line_gutter_background),
+                            (text_h_range, colors.editor_active_line_background),
+                        ];
+                        for (range, active_line_bg) in highlights {
+                            let Some(range) = range else {
+                                continue;
                             };
-                        if let Some(range) = highlight_h_range {
-                            let active_line_bg = cx.theme().colors().editor_active_line_background;
                             let bounds = Bounds {
                                 origin: point(
                                     range.start,
@@ -5716,6 +6423,11 @@ impl EditorElement {
                     paint_highlight(range.start, range.end, color, edges);
                 }
 
+                let overflow_background = cx.theme().status().warning_background;
+                for bounds in &layout.line_length_overflows {
+                    window.paint_quad(fill(*bounds, overflow_background));
+                }
+
                 for (guide_x, active) in layout.wrap_guides.iter() {
                     let color = if *active {
                         cx.theme().colors().editor_active_wrap_guide
@@ -5730,6 +6442,27 @@ impl EditorElement {
                         color,
                     ));
                 }
+
+                let ruler_color = cx.theme().colors().editor_active_wrap_guide;
+                let text_bounds = layout.position_map.text_hitbox.bounds;
+                if let Some(column_x) = layout.cursor_ruler.column_x {
+                    window.paint_quad(fill(
+                        Bounds {
+                            origin: point(column_x, text_bounds.origin.y),
+                            size: size(px(1.), text_bounds.size.height),
+                        },
+                        ruler_color,
+                    ));
+                }
+                if let Some(row_y) = layout.cursor_ruler.row_y {
+                    window.paint_quad(fill(
+                        Bounds {
+                            origin: point(text_bounds.origin.x, row_y - px(1.)),
+                            size: size(text_bounds.size.width, px(1.)),
+                        },
+                        ruler_color,
+                    ));
+                }
             }
         })
     }
@@ -5830,6 +6563,7 @@ impl EditorElement {
 
         for LineNumberLayout {
             shaped_line,
+            bold,
             hitbox,
         } in layout.line_numbers.values()
         {
@@ -5840,7 +6574,7 @@ impl EditorElement {
             let Some(()) = (if !is_singleton && hitbox.is_hovered(window) {
                 let color = cx.theme().colors().editor_hover_line_number;
 
-                let line = self.shape_line_number(shaped_line.text.clone(), color, window);
+                let line = self.shape_line_number(shaped_line.text.clone(), color, *bold, window);
                 line.paint(hitbox.origin, line_height, window, cx).log_err()
             } else {
                 shaped_line
@@ -6060,6 +6794,18 @@ impl EditorElement {
                 }
             });
 
+            for bookmark in layout.bookmarks.iter_mut() {
+                bookmark.paint(window, cx);
+            }
+
+            for badge in layout.gutter_badges.iter_mut() {
+                badge.paint(window, cx);
+            }
+
+            for decoration in layout.gutter_decorations.iter_mut() {
+                decoration.paint(window, cx);
+            }
+
             for breakpoint in layout.breakpoints.iter_mut() {
                 breakpoint.paint(window, cx);
             }
@@ -6181,6 +6927,7 @@ impl EditorElement {
                 } else if matches!(
                     editor.selection_drag_state,
                     SelectionDragState::Dragging { .. }
+                        | SelectionDragState::DraggingFromOtherEditor { .. }
                 ) {
                     window
                         .set_cursor_style(CursorStyle::DragCopy, &layout.position_map.text_hitbox);
@@ -6376,6 +7123,69 @@ impl EditorElement {
         }
     }
 
+    /// Fades the edges of the rows whose text extends beyond the viewport horizontally.
+    fn paint_overflow_indicators(&self, layout: &EditorLayout, window: &mut Window, cx: &mut App) {
+        const OVERFLOW_INDICATOR_WIDTH_IN_EMS: f32 = 2.;
+
+        if !layout.mode.is_full() || !EditorSettings::get_global(cx).scrollbar.overflow_indicators {
+            return;
+        }
+
+        let position_map = &layout.position_map;
+        let text_bounds = position_map.text_hitbox.bounds;
+        let width = (position_map.em_advance * OVERFLOW_INDICATOR_WIDTH_IN_EMS)
+            .min(text_bounds.size.width / 4.);
+        let line_height = position_map.line_height;
+        let scroll_x = Pixels::from(position_map.scroll_pixel_position.x);
+        let visible_width = text_bounds.right() - layout.content_origin.x;
+        let background = cx.theme().colors().editor_background;
+        let transparent = background.opacity(0.);
+
+        for (ix, line) in position_map.line_layouts.iter().enumerate() {
+            let (overflows_left, overflows_right) =
+                Self::overflowing_edges(line.width, scroll_x, visible_width);
+            let row = layout.visible_display_row_range.start.0 + ix as u32;
+            let line_y = layout.content_origin.y
+                + line_height * (row as f64 - position_map.scroll_position.y) as f32;
+
+            if overflows_left {
+                window.paint_quad(fill(
+                    Bounds::new(point(text_bounds.left(), line_y), size(width, line_height)),
+                    linear_gradient(
+                        90.,
+                        linear_color_stop(background, 0.),
+                        linear_color_stop(transparent, 1.),
+                    ),
+                ));
+            }
+            if overflows_right {
+                window.paint_quad(fill(
+                    Bounds::new(
+                        point(text_bounds.right() - width, line_y),
+                        size(width, line_height),
+                    ),
+                    linear_gradient(
+                        90.,
+                        linear_color_stop(transparent, 0.),
+                        linear_color_stop(background, 1.),
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// Whether a line of the given width has text hidden past the left and right edges of the
+    /// viewport, when scrolled horizontally by `scroll_x`.
+    fn overflowing_edges(
+        line_width: Pixels,
+        scroll_x: Pixels,
+        visible_width: Pixels,
+    ) -> (bool, bool) {
+        let overflows_left = scroll_x > Pixels::ZERO && line_width > Pixels::ZERO;
+        let overflows_right = line_width - scroll_x > visible_width;
+        (overflows_left, overflows_right)
+    }
+
     fn paint_cursors(&mut self, layout: &mut EditorLayout, window: &mut Window, cx: &mut App) {
         for cursor in &mut layout.visible_cursors {
             cursor.paint(layout.content_origin, window, cx);
@@ -6879,7 +7689,74 @@ impl EditorElement {
                     .collect(),
             };
 
-            highlighted_range.paint(fill, layout.position_map.text_hitbox.bounds, window);
+            let bidi_rows = row_range
+                .iter_rows()
+                .map(|row| {
+                    let line_layout =
+                        &layout.position_map.line_layouts[row.minus(start_row) as usize];
+                    let start_index = if row == range.start.row() {
+                        range.start.column() as usize
+                    } else {
+                        0
+                    };
+                    let end_index = if row == range.end.row() {
+                        range.end.column() as usize
+                    } else {
+                        line_layout.len
+                    };
+                    let mut x_ranges = line_layout.x_ranges_for_range(start_index..end_index)?;
+                    if row != range.end.row() {
+                        x_ranges.push(line_layout.width..line_layout.width + line_end_overshoot);
+                    }
+                    Some(x_ranges)
+                })
+                .collect::<Vec<_>>();
+
+            if bidi_rows.iter().all(Option::is_none) {
+                highlighted_range.paint(fill, layout.position_map.text_hitbox.bounds, window);
+                return;
+            }
+
+            // Ranges in lines with right-to-left text can cover several segments of a line, so
+            // every line is painted on its own.
+            let line_x = |x: Pixels| {
+                layout.content_origin.x
+                    + Pixels::from(
+                        ScrollPixelOffset::from(x) - layout.position_map.scroll_pixel_position.x,
+                    )
+            };
+            for (ix, (line, x_ranges)) in highlighted_range
+                .lines
+                .into_iter()
+                .zip(bidi_rows)
+                .enumerate()
+            {
+                let lines = match x_ranges {
+                    Some(x_ranges) => x_ranges
+                        .into_iter()
+                        .map(|x_range| HighlightedRangeLine {
+                            start_x: line_x(x_range.start),
+                            end_x: line_x(x_range.end),
+                        })
+                        .collect(),
+                    None => vec![line],
+                };
+                for line in lines {
+                    HighlightedRange {
+                        color,
+                        line_height: highlighted_range.line_height,
+                        corner_radius,
+                        start_y: highlighted_range.start_y
+                            + highlighted_range.line_height * ix as f32,
+                        lines: vec![line],
+                    }
+                    .paint(
+                        fill,
+                        layout.position_map.text_hitbox.bounds,
+                        window,
+                    );
+                }
+            }
         }
     }
 
@@ -7143,6 +8020,26 @@ impl EditorElement {
         }
     }
 
+    fn layout_profiler_overlay(
+        &self,
+        text_hitbox: &Hitbox,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        const MARGIN: Pixels = px(8.);
+
+        let mut element = self
+            .editor
+            .read(cx)
+            .profiler
+            .as_ref()?
+            .render(self.editor.clone(), cx);
+        let size = element.layout_as_root(AvailableSpace::min_size(), window, cx);
+        let origin = text_hitbox.top_right() + point(-size.width - MARGIN, MARGIN);
+        element.prepaint_as_root(origin, AvailableSpace::min_size(), window, cx);
+        Some(element)
+    }
+
     fn paint_mouse_context_menu(
         &mut self,
         layout: &mut EditorLayout,
@@ -7165,6 +8062,8 @@ impl EditorElement {
             let editor = self.editor.clone();
             let hitbox = layout.hitbox.clone();
             let mut delta = ScrollDelta::default();
+            let zoomable = layout.mode.is_full();
+            let mut zoom_lines = 0.;
 
             // Set a minimum scroll_sensitivity of 0.01 to make sure the user doesn't
             // accidentally turn off their scrolling.
@@ -7177,6 +8076,24 @@ impl EditorElement {
                 .max(0.01);
 
             move |event: &ScrollWheelEvent, phase, window, cx| {
+                if event.modifiers.secondary() && zoomable {
+                    if phase == DispatchPhase::Bubble && hitbox.should_handle_scroll(window) {
+                        zoom_lines += match event.delta {
+                            ScrollDelta::Lines(lines) => lines.y,
+                            ScrollDelta::Pixels(pixels) => pixels.y / position_map.line_height,
+                        };
+                        let steps = zoom_lines.trunc();
+                        if steps != 0. {
+                            zoom_lines -= steps;
+                            editor.update(cx, |editor, cx| {
+                                editor.adjust_font_size(px(steps), cx);
+                            });
+                        }
+                        cx.stop_propagation();
+                    }
+                    return;
+                }
+
                 let scroll_sensitivity = {
                     if event.modifiers.alt {
                         fast_scroll_sensitivity
@@ -7291,7 +8208,14 @@ impl EditorElement {
                             );
                         }),
                         MouseButton::Right => editor.update(cx, |editor, cx| {
-                            Self::mouse_right_down(editor, event, &position_map, window, cx);
+                            Self::mouse_right_down(
+                                editor,
+                                event,
+                                diff_hunk_range.clone(),
+                                &position_map,
+                                window,
+                                cx,
+                            );
                         }),
                         MouseButton::Middle => editor.update(cx, |editor, cx| {
                             Self::mouse_middle_down(editor, event, &position_map, window, cx);
@@ -7401,11 +8325,16 @@ impl EditorElement {
         &self,
         text: SharedString,
         color: Hsla,
+        bold: bool,
         window: &mut Window,
     ) -> ShapedLine {
+        let mut font = self.style.text.font();
+        if bold {
+            font.weight = FontWeight::BOLD;
+        }
         let run = TextRun {
             len: text.len(),
-            font: self.style.text.font(),
+            font,
             color,
             background_color: None,
             underline: None,
@@ -7529,7 +8458,7 @@ impl AcceptEditPredictionBinding {
 }
 
 fn prepaint_gutter_button(
-    button: IconButton,
+    button: impl IntoElement,
     row: DisplayRow,
     line_height: Pixels,
     gutter_dimensions: &GutterDimensions,
@@ -7667,10 +8596,15 @@ fn render_blame_entry(
 #[derive(Debug)]
 pub(crate) struct LineWithInvisibles {
     fragments: SmallVec<[LineFragment; 1]>,
+    /// The layout of each text fragment that has right-to-left text, by fragment index.
+    bidi_layouts: SmallVec<[Option<BidiLayout>; 1]>,
     invisibles: Vec<Invisible>,
     len: usize,
     pub(crate) width: Pixels,
     font_size: Pixels,
+    /// Whether the end of the line wasn't laid out, because the line is too long or the end is
+    /// far past the visible width. Positions in the end are approximated.
+    truncated: bool,
 }
 
 enum LineFragment {
@@ -7697,10 +8631,20 @@ impl fmt::Debug for LineFragment {
 }
 
 impl LineWithInvisibles {
+    /// Lines longer than this many bytes, which are rare outside of minified or generated files,
+    /// are laid out in slices, so that laying them out can stop once it's past the visible width.
+    /// Shorter lines are shaped whole, keeping ligatures and bidirectional text intact.
+    const LONG_LINE_LEN: usize = MAX_LINE_LEN / 2;
+    /// The length of the slices that long lines are laid out in, past the first [`Self::LONG_LINE_LEN`] bytes.
+    const LAYOUT_SLICE_LEN: usize = 256;
+    /// How far past the end of a slice to look for whitespace to end it after.
+    const LAYOUT_SLICE_WHITESPACE_SEARCH_LEN: usize = 64;
+
     fn from_chunks<'a>(
         chunks: impl Iterator<Item = HighlightedChunk<'a>>,
         editor_style: &EditorStyle,
         max_line_len: usize,
+        max_width: Pixels,
         max_line_count: usize,
         editor_mode: &EditorMode,
         text_width: Pixels,
@@ -7720,6 +8664,7 @@ impl LineWithInvisibles {
         let mut non_whitespace_added = false;
         let mut row = 0;
         let mut line_exceeded_max_len = false;
+        let mut slicing_line = false;
         let font_size = text_style.font_size.to_pixels(window.rem_size());
         let min_contrast = EditorSettings::get_global(cx).minimum_contrast_for_highlights;
 
@@ -7819,7 +8764,7 @@ impl LineWithInvisibles {
                     }
                 }
             } else {
-                for (ix, mut line_chunk) in highlighted_chunk.text.split('\n').enumerate() {
+                for (ix, line_text) in highlighted_chunk.text.split('\n').enumerate() {
                     if ix > 0 {
                         let segments = bg_segments_per_row.get(row).map(|v| &v[..]).unwrap_or(&[]);
                         let text_runs = if segments.is_empty() {
@@ -7839,30 +8784,47 @@ impl LineWithInvisibles {
                         layouts.push(Self {
                             width: mem::take(&mut width),
                             len: mem::take(&mut len),
+                            bidi_layouts: Self::bidi_layouts(&fragments),
                             fragments: mem::take(&mut fragments),
                             invisibles: std::mem::take(&mut invisibles),
                             font_size,
+                            truncated: mem::take(&mut line_exceeded_max_len),
                         });
 
                         line.clear();
                         styles.clear();
                         row += 1;
-                        line_exceeded_max_len = false;
                         non_whitespace_added = false;
+                        slicing_line = false;
                         if row == max_line_count {
                             return layouts;
                         }
                     }
 
-                    if !line_chunk.is_empty() && !line_exceeded_max_len {
+                    let mut rest = line_text;
+                    while !rest.is_empty() && !line_exceeded_max_len {
+                        // Split long chunks where the slice being laid out ends.
+                        let slice_end = if slicing_line {
+                            Self::LAYOUT_SLICE_LEN
+                        } else {
+                            Self::LONG_LINE_LEN
+                        };
+                        let slice_len = if highlighted_chunk.is_tab {
+                            rest.len()
+                        } else {
+                            Self::slice_len(rest, slice_end.saturating_sub(line.len()))
+                        };
+                        let (mut line_chunk, next_rest) = rest.split_at(slice_len);
+                        rest = next_rest;
+
                         let text_style = if let Some(style) = highlighted_chunk.style {
                             Cow::Owned(text_style.clone().highlight(style))
                         } else {
                             Cow::Borrowed(text_style)
                         };
 
-                        if line.len() + line_chunk.len() > max_line_len {
-                            let mut chunk_len = max_line_len - line.len();
+                        if len + line.len() + line_chunk.len() > max_line_len {
+                            let mut chunk_len = max_line_len.saturating_sub(len + line.len());
                             while !line_chunk.is_char_boundary(chunk_len) {
                                 chunk_len -= 1;
                             }
@@ -7886,8 +8848,8 @@ impl LineWithInvisibles {
                             if highlighted_chunk.is_tab {
                                 if non_whitespace_added || !is_soft_wrapped {
                                     invisibles.push(Invisible::Tab {
-                                        line_start_offset: line.len(),
-                                        line_end_offset: line.len() + line_chunk.len(),
+                                        line_start_offset: len + line.len(),
+                                        line_end_offset: len + line.len() + line_chunk.len(),
                                     });
                                 }
                             } else {
@@ -7899,7 +8861,7 @@ impl LineWithInvisibles {
                                             && (non_whitespace_added || !is_soft_wrapped)
                                         {
                                             Some(Invisible::Whitespace {
-                                                line_offset: line.len() + index,
+                                                line_offset: len + line.len() + index,
                                             })
                                         } else {
                                             None
@@ -7910,6 +8872,38 @@ impl LineWithInvisibles {
                         }
 
                         line.push_str(line_chunk);
+
+                        // Lay out long lines in slices, and stop once past `max_width`, so that
+                        // only the visible part of extremely long lines is shaped.
+                        if line.len() >= slice_end {
+                            slicing_line = true;
+                            let segments =
+                                bg_segments_per_row.get(row).map(|v| &v[..]).unwrap_or(&[]);
+                            let text_runs = if segments.is_empty() {
+                                &styles
+                            } else {
+                                &Self::split_runs_by_bg_segments(
+                                    &styles,
+                                    segments,
+                                    min_contrast,
+                                    len,
+                                )
+                            };
+                            let shaped_line = window.text_system().shape_line(
+                                line.clone().into(),
+                                font_size,
+                                text_runs,
+                                None,
+                            );
+                            width += shaped_line.width;
+                            len += shaped_line.len;
+                            fragments.push(LineFragment::Text(shaped_line));
+                            line.clear();
+                            styles.clear();
+                            if width > max_width {
+                                line_exceeded_max_len = true;
+                            }
+                        }
                     }
                 }
             }
@@ -7918,6 +8912,43 @@ impl LineWithInvisibles {
         layouts
     }
 
+    /// Returns how much of `text` to add to a slice that ends `len` bytes in. The slice is ended
+    /// after whitespace where there's some shortly past `len`, or otherwise on a grapheme
+    /// boundary, so that shaping isn't split within a word or a grapheme.
+    fn slice_len(text: &str, len: usize) -> usize {
+        if len >= text.len() {
+            return text.len();
+        }
+        let mut search_end = (len + Self::LAYOUT_SLICE_WHITESPACE_SEARCH_LEN).min(text.len());
+        while !text.is_char_boundary(search_end) {
+            search_end -= 1;
+        }
+        let mut search_start = len;
+        while !text.is_char_boundary(search_start) {
+            search_start -= 1;
+        }
+        if let Some((ix, whitespace)) = text[search_start..search_end]
+            .char_indices()
+            .find(|(_, c)| c.is_whitespace())
+        {
+            return search_start + ix + whitespace.len_utf8();
+        }
+        text.grapheme_indices(true)
+            .map(|(ix, _)| ix)
+            .find(|ix| *ix >= len.max(1))
+            .unwrap_or(text.len())
+    }
+
+    fn bidi_layouts(fragments: &[LineFragment]) -> SmallVec<[Option<BidiLayout>; 1]> {
+        fragments
+            .iter()
+            .map(|fragment| match fragment {
+                LineFragment::Text(shaped_line) => BidiLayout::for_line(shaped_line),
+                LineFragment::Element { .. } => None,
+            })
+            .collect()
+    }
+
     /// Takes text runs and non-overlapping left-to-right background ranges with color.
     /// Returns new text runs with adjusted contrast as per background ranges.
     fn split_runs_by_bg_segments(
@@ -8227,13 +9258,17 @@ impl LineWithInvisibles {
         let mut fragment_start_x = Pixels::ZERO;
         let mut fragment_start_index = 0;
 
-        for fragment in &self.fragments {
+        for (fragment, bidi_layout) in self.fragments.iter().zip(&self.bidi_layouts) {
             match fragment {
                 LineFragment::Text(shaped_line) => {
                     let fragment_end_index = fragment_start_index + shaped_line.len;
                     if index < fragment_end_index {
+                        let index = index - fragment_start_index;
                         return fragment_start_x
-                            + shaped_line.x_for_index(index - fragment_start_index);
+                            + match bidi_layout {
+                                Some(bidi_layout) => bidi_layout.x_for_index(index),
+                                None => shaped_line.x_for_index(index),
+                            };
                     }
                     fragment_start_x += shaped_line.width;
                     fragment_start_index = fragment_end_index;
@@ -8249,6 +9284,11 @@ impl LineWithInvisibles {
             }
         }
 
+        // Approximate the positions that weren't laid out from the average width of those that were.
+        if self.truncated && index > fragment_start_index && fragment_start_index > 0 {
+            let average_width = fragment_start_x / fragment_start_index as f32;
+            return fragment_start_x + average_width * (index - fragment_start_index) as f32;
+        }
         fragment_start_x
     }
 
@@ -8256,13 +9296,18 @@ impl LineWithInvisibles {
         let mut fragment_start_x = Pixels::ZERO;
         let mut fragment_start_index = 0;
 
-        for fragment in &self.fragments {
+        for (fragment, bidi_layout) in self.fragments.iter().zip(&self.bidi_layouts) {
             match fragment {
                 LineFragment::Text(shaped_line) => {
                     let fragment_end_x = fragment_start_x + shaped_line.width;
                     if x < fragment_end_x {
+                        let x = x - fragment_start_x;
                         return Some(
-                            fragment_start_index + shaped_line.index_for_x(x - fragment_start_x)?,
+                            fragment_start_index
+                                + match bidi_layout {
+                                    Some(bidi_layout) => bidi_layout.index_for_x(x)?,
+                                    None => shaped_line.index_for_x(x)?,
+                                },
                         );
                     }
                     fragment_start_x = fragment_end_x;
@@ -8282,6 +9327,52 @@ impl LineWithInvisibles {
         None
     }
 
+    /// The horizontal extents covering the characters in `range` when the line has right-to-left
+    /// text, where a range can be split into several segments. Returns `None` for lines that are
+    /// entirely left-to-right.
+    pub fn x_ranges_for_range(&self, range: Range<usize>) -> Option<Vec<Range<Pixels>>> {
+        if self.bidi_layouts.iter().all(Option::is_none) {
+            return None;
+        }
+
+        let mut x_ranges = Vec::<Range<Pixels>>::new();
+        let mut fragment_start_x = Pixels::ZERO;
+        let mut fragment_start_index = 0;
+        for (fragment, bidi_layout) in self.fragments.iter().zip(&self.bidi_layouts) {
+            let (fragment_len, fragment_width) = match fragment {
+                LineFragment::Text(shaped_line) => (shaped_line.len, shaped_line.width),
+                LineFragment::Element { len, size, .. } => (*len, size.width),
+            };
+            let fragment_end_index = fragment_start_index + fragment_len;
+            let start = range.start.max(fragment_start_index);
+            let end = range.end.min(fragment_end_index);
+            if start < end {
+                let fragment_x_ranges = match bidi_layout {
+                    Some(bidi_layout) => bidi_layout.x_ranges_for_range(
+                        start - fragment_start_index..end - fragment_start_index,
+                    ),
+                    None => vec![
+                        self.x_for_index(start) - fragment_start_x..if end == fragment_end_index {
+                            fragment_width
+                        } else {
+                            self.x_for_index(end) - fragment_start_x
+                        },
+                    ],
+                };
+                for x_range in fragment_x_ranges {
+                    let x_range = fragment_start_x + x_range.start..fragment_start_x + x_range.end;
+                    match x_ranges.last_mut() {
+                        Some(last) if last.end == x_range.start => last.end = x_range.end,
+                        _ => x_ranges.push(x_range),
+                    }
+                }
+            }
+            fragment_start_x += fragment_width;
+            fragment_start_index = fragment_end_index;
+        }
+        Some(x_ranges)
+    }
+
     pub fn font_id_for_index(&self, index: usize) -> Option<FontId> {
         let mut fragment_start_index = 0;
 
@@ -8474,6 +9565,7 @@ impl Element for EditorElement {
         window: &mut Window,
         cx: &mut App,
     ) -> Self::PrepaintState {
+        let prepaint_started = Instant::now();
         let text_style = TextStyleRefinement {
             font_size: Some(self.style.text.font_size),
             line_height: Some(self.style.text.line_height),
@@ -8657,6 +9749,12 @@ impl Element for EditorElement {
                     });
 
                     let mut scroll_position = snapshot.scroll_position();
+                    if let Some(animation_offset) =
+                        self.editor.read(cx).scroll_manager.animation_offset()
+                    {
+                        scroll_position.y = (scroll_position.y + animation_offset).max(0.);
+                        window.request_animation_frame();
+                    }
                     // The scroll position is a fractional point, the whole number of which represents
                     // the top of the window in terms of display rows.
                     let start_row = DisplayRow(scroll_position.y as u32);
@@ -8750,7 +9848,7 @@ impl Element for EditorElement {
                             .or_insert(background);
                     }
 
-                    let highlighted_ranges = self
+                    let mut highlighted_ranges = self
                         .editor_with_selections(cx)
                         .map(|editor| {
                             editor.read(cx).background_highlights_in_range(
@@ -8760,6 +9858,17 @@ impl Element for EditorElement {
                             )
                         })
                         .unwrap_or_default();
+                    highlighted_ranges
+                        .extend(Self::layout_trailing_whitespace(&row_infos, &snapshot, cx));
+                    highlighted_ranges
+                        .extend(Self::layout_todo_highlights(&row_infos, &snapshot, cx));
+                    highlighted_ranges.extend(Self::layout_word_diffs(
+                        &row_infos,
+                        start_row,
+                        start_anchor..end_anchor,
+                        &snapshot,
+                        cx,
+                    ));
                     let highlighted_gutter_ranges =
                         self.editor.read(cx).gutter_highlights_in_range(
                             start_anchor..end_anchor,
@@ -8837,6 +9946,19 @@ impl Element for EditorElement {
                             active_rows.entry(*display_row).or_default().breakpoint = true;
                         }
                     }
+                    let mut bookmark_rows = if EditorSettings::get_global(cx).gutter.bookmarks {
+                        self.editor.update(cx, |editor, cx| {
+                            editor.active_bookmarks(start_row..end_row, window, cx)
+                        })
+                    } else {
+                        HashMap::default()
+                    };
+                    let mut gutter_badge_rows = self.editor.update(cx, |editor, _| {
+                        editor.active_gutter_badges(start_row..end_row, &snapshot)
+                    });
+                    let mut gutter_decoration_rows = self.editor.update(cx, |editor, _| {
+                        editor.active_gutter_decorations(start_row..end_row, &snapshot)
+                    });
 
                     let line_numbers = self.layout_line_numbers(
                         Some(&gutter_hitbox),
@@ -8928,16 +10050,23 @@ impl Element for EditorElement {
                         self.style.background,
                     );
 
+                    // Lay out lines up to a viewport past the visible width, which leaves room
+                    // for scrolling horizontally to a selection before the next layout.
+                    let max_line_width =
+                        Pixels::from(scroll_position.x * f64::from(em_advance)) + editor_width * 2.;
+                    let layout_lines_started = Instant::now();
                     let mut line_layouts = Self::layout_lines(
                         start_row..end_row,
                         &snapshot,
                         &self.style,
                         editor_width,
+                        max_line_width,
                         is_row_soft_wrapped,
                         &bg_segments_per_row,
                         window,
                         cx,
                     );
+                    let layout_lines_time = layout_lines_started.elapsed();
                     let new_renderer_widths = (!is_minimap).then(|| {
                         line_layouts
                             .iter()
@@ -9105,7 +10234,7 @@ impl Element for EditorElement {
                                 cx,
                             )
                         {
-                            scroll_position = new_scroll_position;
+                            scroll_position.x = new_scroll_position.x;
                         }
                     });
 
@@ -9366,6 +10495,54 @@ impl Element for EditorElement {
                     let show_breakpoints = snapshot
                         .show_breakpoints
                         .unwrap_or(gutter_settings.breakpoints);
+                    if show_breakpoints {
+                        bookmark_rows.retain(|row, _| !breakpoint_rows.contains_key(row));
+                        gutter_badge_rows.retain(|row, _| !breakpoint_rows.contains_key(row));
+                        gutter_decoration_rows.retain(|row, _| !breakpoint_rows.contains_key(row));
+                    }
+                    gutter_badge_rows.retain(|row, _| !bookmark_rows.contains_key(row));
+                    gutter_decoration_rows.retain(|row, _| {
+                        !bookmark_rows.contains_key(row) && !gutter_badge_rows.contains_key(row)
+                    });
+                    let gutter_decorations = self.layout_gutter_decorations(
+                        line_height,
+                        start_row..end_row,
+                        scroll_position,
+                        &gutter_dimensions,
+                        &gutter_hitbox,
+                        &display_hunks,
+                        &snapshot,
+                        gutter_decoration_rows,
+                        &row_infos,
+                        window,
+                        cx,
+                    );
+                    let gutter_badges = self.layout_gutter_badges(
+                        line_height,
+                        start_row..end_row,
+                        scroll_position,
+                        &gutter_dimensions,
+                        &gutter_hitbox,
+                        &display_hunks,
+                        &snapshot,
+                        gutter_badge_rows,
+                        &row_infos,
+                        window,
+                        cx,
+                    );
+                    let bookmarks = self.layout_bookmarks(
+                        line_height,
+                        start_row..end_row,
+                        scroll_position,
+                        &gutter_dimensions,
+                        &gutter_hitbox,
+                        &display_hunks,
+                        &snapshot,
+                        bookmark_rows,
+                        &row_infos,
+                        window,
+                        cx,
+                    );
                     let breakpoints = if show_breakpoints {
                         self.layout_breakpoints(
                             line_height,
@@ -9450,6 +10627,30 @@ impl Element for EditorElement {
                         cx,
                     );
 
+                    let line_length_overflows = self.layout_line_length_overflows(
+                        &snapshot,
+                        start_row,
+                        &line_layouts,
+                        content_origin,
+                        scroll_pixel_position,
+                        line_height,
+                        &text_hitbox,
+                        cx,
+                    );
+
+                    let cursor_ruler = self.layout_cursor_ruler(
+                        &snapshot,
+                        newest_selection_head,
+                        start_row,
+                        &line_layouts,
+                        content_origin,
+                        scroll_pixel_position,
+                        line_height,
+                        &text_hitbox,
+                        window,
+                        cx,
+                    );
+
                     let minimap = window.with_element_namespace("minimap", |window| {
                         self.layout_minimap(
                             &snapshot,
@@ -9545,14 +10746,21 @@ impl Element for EditorElement {
                     });
 
                     self.editor.update(cx, |editor, _| {
-                        editor.last_position_map = Some(position_map.clone())
+                        editor.last_position_map = Some(position_map.clone());
+                        if let Some(profiler) = editor.profiler.as_mut() {
+                            profiler.record_layout(prepaint_started.elapsed(), layout_lines_time);
+                        }
                     });
 
+                    let profiler_overlay = self.layout_profiler_overlay(&text_hitbox, window, cx);
+
                     EditorLayout {
                         mode,
                         position_map,
                         visible_display_row_range: start_row..end_row,
                         wrap_guides,
+                        line_length_overflows,
+                        cursor_ruler,
                         indent_guides,
                         hitbox,
                         gutter_hitbox,
@@ -9581,12 +10789,16 @@ impl Element for EditorElement {
                         mouse_context_menu,
                         test_indicators,
                         breakpoints,
+                        bookmarks,
+                        gutter_badges,
+                        gutter_decorations,
                         crease_toggles,
                         crease_trailers,
                         tab_invisible,
                         space_invisible,
                         sticky_buffer_header,
                         expand_toggles,
+                        profiler_overlay,
                     }
                 })
             })
@@ -9603,6 +10815,7 @@ impl Element for EditorElement {
         window: &mut Window,
         cx: &mut App,
     ) {
+        let paint_started = Instant::now();
         if !layout.mode.is_minimap() {
             let focus_handle = self.editor.focus_handle(cx);
             let key_context = self
@@ -9617,6 +10830,9 @@ impl Element for EditorElement {
             );
             self.register_actions(window, cx);
             self.register_key_listeners(window, cx, layout);
+            if layout.profiler_overlay.is_some() {
+                crate::profiler::register_keystroke_listener(&self.editor, window);
+            }
         }
 
         let text_style = TextStyleRefinement {
@@ -9638,6 +10854,7 @@ impl Element for EditorElement {
                     }
 
                     self.paint_text(layout, window, cx);
+                    self.paint_overflow_indicators(layout, window, cx);
 
                     if layout.gutter_hitbox.size.width > Pixels::ZERO {
                         self.paint_gutter_highlights(layout, window, cx);
@@ -9660,9 +10877,18 @@ impl Element for EditorElement {
                     self.paint_scrollbars(layout, window, cx);
                     self.paint_edit_prediction_popover(layout, window, cx);
                     self.paint_mouse_context_menu(layout, window, cx);
+                    if let Some(mut profiler_overlay) = layout.profiler_overlay.take() {
+                        profiler_overlay.paint(window, cx);
+                    }
                 });
             })
-        })
+        });
+
+        self.editor.update(cx, |editor, _| {
+            if let Some(profiler) = editor.profiler.as_mut() {
+                profiler.record_paint(paint_started.elapsed());
+            }
+        });
     }
 }
 
@@ -9735,6 +10961,8 @@ pub struct EditorLayout {
     minimap: Option<MinimapLayout>,
     mode: EditorMode,
     wrap_guides: SmallVec<[(Pixels, bool); 2]>,
+    line_length_overflows: Vec<Bounds<Pixels>>,
+    cursor_ruler: CursorRulerLayout,
     indent_guides: Option<Vec<IndentGuideLayout>>,
     visible_display_row_range: Range<DisplayRow>,
     active_rows: BTreeMap<DisplayRow, LineHighlightSpec>,
@@ -9755,6 +10983,9 @@ pub struct EditorLayout {
     selections: Vec<(PlayerColor, Vec<SelectionLayout>)>,
     test_indicators: Vec<AnyElement>,
     breakpoints: Vec<AnyElement>,
+    bookmarks: Vec<AnyElement>,
+    gutter_badges: Vec<AnyElement>,
+    gutter_decorations: Vec<AnyElement>,
     crease_toggles: Vec<Option<AnyElement>>,
     expand_toggles: Vec<Option<(AnyElement, gpui::Point<Pixels>)>>,
     diff_hunk_controls: Vec<AnyElement>,
@@ -9764,6 +10995,7 @@ pub struct EditorLayout {
     tab_invisible: ShapedLine,
     space_invisible: ShapedLine,
     sticky_buffer_header: Option<AnyElement>,
+    profiler_overlay: Option<AnyElement>,
     document_colors: Option<(DocumentColorsRenderMode, Vec<(Range<DisplayPoint>, Hsla)>)>,
 }
 
@@ -9775,6 +11007,8 @@ impl EditorLayout {
 
 struct LineNumberLayout {
     shaped_line: ShapedLine,
+    /// Whether the line number is shown in bold, because its line has a cursor.
+    bold: bool,
     hitbox: Option<Hitbox>,
 }
 
@@ -10315,6 +11549,7 @@ pub fn layout_line(
         chunks,
         style,
         MAX_LINE_LEN,
+        Pixels::MAX,
         1,
         &snapshot.mode,
         text_width,
@@ -10337,6 +11572,29 @@ pub struct IndentGuideLayout {
     settings: IndentGuideSettings,
 }
 
+/// The movement of a local cursor between two positions, relative to the start of the buffer,
+/// used to animate it when the smooth caret is enabled.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CursorAnimation {
+    from: gpui::Point<ScrollPixelOffset>,
+    to: gpui::Point<ScrollPixelOffset>,
+    started_at: Instant,
+}
+
+impl CursorAnimation {
+    /// Returns the position of the cursor at `now`, or `None` once it has reached its target.
+    fn position(&self, now: Instant, duration: Duration) -> Option<gpui::Point<ScrollPixelOffset>> {
+        let elapsed = now.saturating_duration_since(self.started_at);
+        if self.from == self.to || elapsed >= duration {
+            return None;
+        }
+        let progress = elapsed.as_secs_f64() / duration.as_secs_f64();
+        // Ease out, so that the cursor starts fast and settles slowly.
+        let eased = 1. - (1. - progress).powi(3);
+        Some(self.from + (self.to - self.from) * eased)
+    }
+}
+
 pub struct CursorLayout {
     origin: gpui::Point<Pixels>,
     block_width: Pixels,
@@ -10618,12 +11876,11 @@ enum CursorPopoverType {
     EditPrediction,
 }
 
-pub fn scale_vertical_mouse_autoscroll_delta(delta: Pixels) -> f32 {
-    (delta.pow(1.2) / 100.0).min(px(3.0)).into()
-}
-
-fn scale_horizontal_mouse_autoscroll_delta(delta: Pixels) -> f32 {
-    (delta.pow(1.2) / 300.0).into()
+/// Whether dropping a dragged selection moves it instead of copying it,
+/// which happens unless alt (on macOS) or control (elsewhere) is held.
+fn is_cut_on_drop(modifiers: &Modifiers) -> bool {
+    !(cfg!(target_os = "macos") && modifiers.alt
+        || cfg!(not(target_os = "macos")) && modifiers.control)
 }
 
 pub fn register_action<T: Action>(
@@ -10884,6 +12141,100 @@ mod tests {
         assert_eq!(relative_rows[&DisplayRow(2)], 3);
     }
 
+    #[test]
+    fn test_cursor_animation_position() {
+        let started_at = Instant::now();
+        let duration = Duration::from_millis(100);
+        let animation = CursorAnimation {
+            from: point(0., 0.),
+            to: point(100., 40.),
+            started_at,
+        };
+
+        assert_eq!(
+            animation.position(started_at, duration),
+            Some(point(0., 0.))
+        );
+
+        let halfway = animation
+            .position(started_at + duration / 2, duration)
+            .unwrap();
+        // The animation eases out, so it covers most of the distance in its first half.
+        assert!(halfway.x > 50. && halfway.x < 100., "{halfway:?}");
+        assert!(halfway.y > 20. && halfway.y < 40., "{halfway:?}");
+
+        assert_eq!(animation.position(started_at + duration, duration), None);
+
+        let stationary = CursorAnimation {
+            from: point(100., 40.),
+            to: point(100., 40.),
+            started_at,
+        };
+        assert_eq!(stationary.position(started_at, duration), None);
+    }
+
+    #[gpui::test]
+    fn test_line_numbers_modes(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let window = cx.add_window(|window, cx| {
+            let buffer = MultiBuffer::build_simple(&sample_text(6, 6, 'a'), cx);
+            Editor::new(EditorMode::full(), buffer, None, window, cx)
+        });
+
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|cx| editor.read(cx).style().unwrap().clone());
+        let element = EditorElement::new(&editor, style);
+
+        for (line_numbers, expected) in [
+            (LineNumbers::Absolute, ["1", "2", "3", "4", "5", "6"]),
+            (LineNumbers::Relative, ["3", "2", "1", "0", "1", "2"]),
+            (LineNumbers::Both, ["3", "2", "1", "4", "1", "2"]),
+        ] {
+            cx.update(|cx| {
+                settings::SettingsStore::update_global(cx, |store, cx| {
+                    store.update_user_settings(cx, |settings| {
+                        settings.editor.line_numbers = Some(line_numbers);
+                    });
+                });
+            });
+            let snapshot = window
+                .update(cx, |editor, window, cx| editor.snapshot(window, cx))
+                .unwrap();
+            let layouts = cx
+                .update_window(*window, |_, window, cx| {
+                    element.layout_line_numbers(
+                        None,
+                        GutterDimensions {
+                            left_padding: Pixels::ZERO,
+                            right_padding: Pixels::ZERO,
+                            width: px(30.0),
+                            margin: Pixels::ZERO,
+                            git_blame_entries_width: None,
+                        },
+                        px(20.0),
+                        gpui::Point::default(),
+                        DisplayRow(0)..DisplayRow(6),
+                        &(0..6)
+                            .map(|row| RowInfo {
+                                buffer_row: Some(row),
+                                ..Default::default()
+                            })
+                            .collect::<Vec<_>>(),
+                        &BTreeMap::default(),
+                        Some(DisplayPoint::new(DisplayRow(3), 0)),
+                        &snapshot,
+                        window,
+                        cx,
+                    )
+                })
+                .unwrap();
+            let numbers = (0..6)
+                .map(|row| layouts[&MultiBufferRow(row)].shaped_line.text.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(numbers, expected, "line numbers: {line_numbers:?}");
+        }
+    }
+
     #[gpui::test]
     async fn test_vim_visual_selections(cx: &mut TestAppContext) {
         init_test(cx, |_| {});
@@ -11221,6 +12572,209 @@ mod tests {
             .collect()
     }
 
+    #[gpui::test]
+    fn test_line_length_overflows(cx: &mut TestAppContext) {
+        init_test(cx, |s| {
+            s.defaults.tab_size = NonZeroU32::new(4);
+            s.defaults.preferred_line_length = Some(4);
+            s.defaults.highlight_line_length_overflow = Some(true);
+        });
+
+        // The preferred line length counts characters, so the overflow of a line with multibyte
+        // characters or tabs starts at a later display column.
+        let (overflows, _) =
+            collect_line_length_overflows(cx, "ééééé\nab\tcdef\nabc", px(1000.), false);
+        assert_eq!(overflows, [(0, 8), (1, 5)]);
+
+        // A soft wrapped line overflows from the display row that the character past the preferred
+        // line length is wrapped to, and the rows after it overflow from their start.
+        update_test_language_settings(cx, |s| s.defaults.preferred_line_length = Some(100));
+        let (overflows, row_lens) =
+            collect_line_length_overflows(cx, &"a".repeat(200), px(300.), true);
+        let wrap_len = row_lens[0];
+        assert!(
+            wrap_len < 100,
+            "expected the line to wrap, got rows of {row_lens:?}"
+        );
+        let overflow_row = 100 / wrap_len;
+        let expected_overflows = std::iter::once((overflow_row as u32, 100 % wrap_len))
+            .chain((overflow_row + 1..row_lens.len()).map(|row| (row as u32, 0)))
+            .collect::<Vec<_>>();
+        assert_eq!(overflows, expected_overflows);
+    }
+
+    /// The display rows and the indices within them that the line length overflows start at,
+    /// along with the length of each laid out row.
+    fn collect_line_length_overflows(
+        cx: &mut TestAppContext,
+        input_text: &str,
+        editor_width: Pixels,
+        soft_wrap: bool,
+    ) -> (Vec<(u32, usize)>, Vec<usize>) {
+        let window = cx.add_window(|window, cx| {
+            let buffer = MultiBuffer::build_simple(input_text, cx);
+            let mut editor = Editor::new(EditorMode::full(), buffer, None, window, cx);
+            if soft_wrap {
+                editor.set_soft_wrap_mode(language_settings::SoftWrap::EditorWidth, cx);
+            }
+            editor
+        });
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|_, cx| editor.read(cx).style().unwrap().clone());
+
+        // The first draw sets the wrap width that the second one lays out with.
+        let mut draw = || {
+            cx.draw(
+                point(px(0.), px(0.)),
+                size(editor_width, px(500.)),
+                |_, _| EditorElement::new(&editor, style.clone()),
+            )
+            .1
+        };
+        draw();
+        let state = draw();
+
+        let position_map = &state.position_map;
+        let overflows = state
+            .line_length_overflows
+            .iter()
+            .map(|bounds| {
+                let row = ((bounds.origin.y - state.content_origin.y) / position_map.line_height)
+                    .round() as usize;
+                let line = &position_map.line_layouts[row];
+                let x = bounds.origin.x - state.content_origin.x;
+                let index = (0..=line.len)
+                    .find(|index| (line.x_for_index(*index) - x).abs() < px(0.01))
+                    .unwrap_or_else(|| panic!("no index of row {row} is at {x:?}"));
+                (row as u32, index)
+            })
+            .collect();
+        let row_lens = position_map
+            .line_layouts
+            .iter()
+            .map(|line| line.len)
+            .collect();
+        (overflows, row_lens)
+    }
+
+    #[gpui::test]
+    fn test_layout_long_line_up_to_max_width(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let line_len = 3 * LineWithInvisibles::LAYOUT_SLICE_LEN;
+        let window = cx.add_window(|window, cx| {
+            let buffer = MultiBuffer::build_simple(&"a".repeat(line_len), cx);
+            Editor::new(EditorMode::full(), buffer, None, window, cx)
+        });
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|cx| editor.read(cx).style().unwrap().clone());
+        let snapshot = window
+            .update(cx, |editor, window, cx| editor.snapshot(window, cx))
+            .unwrap();
+
+        let (full_line, visible_line) = cx
+            .update_window(*window, |_, window, cx| {
+                let mut layout = |max_width| {
+                    LineWithInvisibles::from_chunks(
+                        snapshot.highlighted_chunks(DisplayRow(0)..DisplayRow(1), true, &style),
+                        &style,
+                        MAX_LINE_LEN,
+                        max_width,
+                        1,
+                        &snapshot.mode,
+                        px(1000.),
+                        |_| false,
+                        &[],
+                        window,
+                        cx,
+                    )
+                    .pop()
+                    .unwrap()
+                };
+                (layout(Pixels::MAX), layout(px(1.)))
+            })
+            .unwrap();
+
+        assert_eq!(full_line.len, line_len);
+        assert!(!full_line.truncated);
+        // Only the first slice is laid out, and the positions past it are approximated.
+        assert_eq!(visible_line.len, LineWithInvisibles::LONG_LINE_LEN);
+        assert!(visible_line.truncated);
+        for index in [line_len / 2, line_len] {
+            let error = visible_line.x_for_index(index) - full_line.x_for_index(index);
+            assert!(error.abs() < px(1.), "{error:?} off at {index}");
+        }
+    }
+
+    #[gpui::test]
+    fn test_layout_short_line_whole(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        let text = "fn a() -> b { c != d }".repeat(LineWithInvisibles::LONG_LINE_LEN / 32);
+        let window = cx.add_window(|window, cx| {
+            let buffer = MultiBuffer::build_simple(&text, cx);
+            Editor::new(EditorMode::full(), buffer, None, window, cx)
+        });
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|cx| editor.read(cx).style().unwrap().clone());
+        let snapshot = window
+            .update(cx, |editor, window, cx| editor.snapshot(window, cx))
+            .unwrap();
+
+        let line = cx
+            .update_window(*window, |_, window, cx| {
+                LineWithInvisibles::from_chunks(
+                    snapshot.highlighted_chunks(DisplayRow(0)..DisplayRow(1), true, &style),
+                    &style,
+                    MAX_LINE_LEN,
+                    px(1.),
+                    1,
+                    &snapshot.mode,
+                    px(1000.),
+                    |_| false,
+                    &[],
+                    window,
+                    cx,
+                )
+                .pop()
+                .unwrap()
+            })
+            .unwrap();
+
+        assert_eq!(line.len, text.len());
+        assert_eq!(line.fragments.len(), 1);
+        assert!(!line.truncated);
+    }
+
+    #[test]
+    fn test_layout_slice_len() {
+        // Slices end after whitespace shortly past the end of the slice.
+        assert_eq!(LineWithInvisibles::slice_len("abcd efgh", 2), 5);
+        assert_eq!(LineWithInvisibles::slice_len("ab cd efgh", 2), 3);
+        assert_eq!(LineWithInvisibles::slice_len("abc", 5), 3);
+        // Without whitespace, slices end on a grapheme boundary.
+        let text = format!("{}e\u{301}{}", "a".repeat(4), "b".repeat(100));
+        assert_eq!(LineWithInvisibles::slice_len(&text, 5), 7);
+        assert_eq!(LineWithInvisibles::slice_len(&text, 6), 7);
+        assert_eq!(LineWithInvisibles::slice_len(&text, 8), 8);
+    }
+
+    #[test]
+    fn test_overflowing_edges() {
+        let edges = |line_width: f32, scroll_x: f32| {
+            EditorElement::overflowing_edges(px(line_width), px(scroll_x), px(100.))
+        };
+
+        // Lines that fit aren't faded, nor are empty lines scrolled past.
+        assert_eq!(edges(80., 0.), (false, false));
+        assert_eq!(edges(0., 50.), (false, false));
+        // A long line is faded on the right until its end is scrolled into view.
+        assert_eq!(edges(250., 0.), (false, true));
+        assert_eq!(edges(250., 100.), (true, true));
+        assert_eq!(edges(250., 150.), (true, false));
+        // A short line next to a long one is only faded where its text is hidden.
+        assert_eq!(edges(80., 50.), (true, false));
+    }
+
     #[gpui::test]
     fn test_merge_overlapping_ranges() {
         let base_bg = Hsla::white();
@@ -0,0 +1,360 @@
+//! Evaluating the arithmetic and bitwise expressions in selections, such as `(1 + 2) * 3` or
+//! `0xff & ~0x0f`.
+use std::{iter::Peekable, str::CharIndices};
+
+use gpui::{Context, Window};
+use multi_buffer::ToOffset as _;
+
+use crate::{Editor, EvaluateSelection, HideMouseCursorOrigin};
+
+impl Editor {
+    pub fn evaluate_selection(
+        &mut self,
+        action: &EvaluateSelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.read_only(cx) {
+            return;
+        }
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut edits = Vec::new();
+        let mut new_selections = Vec::new();
+        for selection in self.selections.all::<usize>(cx) {
+            let text = snapshot
+                .text_for_range(selection.range())
+                .collect::<String>();
+            let trimmed = text.trim_end();
+            let (expression, ends_with_equals) = match trimmed.strip_suffix('=') {
+                Some(expression) => (expression, true),
+                None => (trimmed, false),
+            };
+            // Each selection is evaluated on its own, so one invalid expression doesn't prevent
+            // evaluating the others.
+            let result = match evaluate(expression) {
+                Ok(result) => result,
+                Err(error) => {
+                    if error == EvaluateError::TooDeep {
+                        log::warn!(
+                            "not evaluating selection, as it nests deeper than {MAX_DEPTH} levels"
+                        );
+                    }
+                    new_selections.push(
+                        snapshot.anchor_before(selection.start)
+                            ..snapshot.anchor_after(selection.end),
+                    );
+                    continue;
+                }
+            };
+
+            if action.append || ends_with_equals {
+                let end = selection.start + trimmed.len();
+                let text = if ends_with_equals {
+                    format!(" {result}")
+                } else {
+                    format!(" = {result}")
+                };
+                let cursor = snapshot.anchor_after(end);
+                edits.push((end..end, text));
+                new_selections.push(cursor..cursor);
+            } else {
+                let start = snapshot.anchor_before(selection.start);
+                edits.push((selection.range(), result));
+                new_selections.push(start..snapshot.anchor_after(selection.end));
+            }
+        }
+        if edits.is_empty() {
+            return;
+        }
+
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            let snapshot = this.buffer.read(cx).snapshot(cx);
+            let ranges = new_selections
+                .into_iter()
+                .map(|range| range.start.to_offset(&snapshot)..range.end.to_offset(&snapshot))
+                .collect::<Vec<_>>();
+            this.change_selections(Default::default(), window, cx, |s| {
+                s.select_ranges(ranges);
+            });
+        });
+    }
+}
+
+/// How deeply parentheses and unary operators can nest. The parser is recursive, so deeper
+/// expressions would overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, PartialEq)]
+enum EvaluateError {
+    /// The expression isn't a valid arithmetic or bitwise expression, or its result isn't finite.
+    Invalid,
+    /// The expression nests deeper than `MAX_DEPTH` levels.
+    TooDeep,
+}
+
+/// Evaluates an arithmetic or bitwise expression.
+///
+/// Numbers can be written in decimal, as in `1.5e3`, or in hexadecimal or binary, as in `0xff`
+/// and `0b1010`. The result is written in hexadecimal when every number in the expression is.
+/// Operators follow the precedence of C, with `**` for exponentiation binding tightest.
+fn evaluate(expression: &str) -> Result<String, EvaluateError> {
+    let mut parser = Parser {
+        chars: expression.char_indices().peekable(),
+        text: expression,
+        all_hex: true,
+        has_number: false,
+        depth: 0,
+        too_deep: false,
+    };
+    let value = parser.parse_bitwise_or();
+    if parser.too_deep {
+        return Err(EvaluateError::TooDeep);
+    }
+    let value = value.ok_or(EvaluateError::Invalid)?;
+    parser.skip_whitespace();
+    if parser.chars.peek().is_some() || !parser.has_number || !value.is_finite() {
+        return Err(EvaluateError::Invalid);
+    }
+
+    if value.fract() == 0.0 && value.abs() < 2f64.powi(53) {
+        let value = value as i64;
+        if parser.all_hex && value >= 0 {
+            Ok(format!("{value:#x}"))
+        } else {
+            Ok(value.to_string())
+        }
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    text: &'a str,
+    all_hex: bool,
+    has_number: bool,
+    /// How many `parse_unary` calls are in progress.
+    depth: usize,
+    too_deep: bool,
+}
+
+impl Parser<'_> {
+    fn parse_bitwise_or(&mut self) -> Option<f64> {
+        let mut value = self.parse_bitwise_xor()?;
+        while self.eat("|") {
+            value = (integer(value)? | integer(self.parse_bitwise_xor()?)?) as f64;
+        }
+        Some(value)
+    }
+
+    fn parse_bitwise_xor(&mut self) -> Option<f64> {
+        let mut value = self.parse_bitwise_and()?;
+        while self.eat("^") {
+            value = (integer(value)? ^ integer(self.parse_bitwise_and()?)?) as f64;
+        }
+        Some(value)
+    }
+
+    fn parse_bitwise_and(&mut self) -> Option<f64> {
+        let mut value = self.parse_shift()?;
+        while self.eat("&") {
+            value = (integer(value)? & integer(self.parse_shift()?)?) as f64;
+        }
+        Some(value)
+    }
+
+    fn parse_shift(&mut self) -> Option<f64> {
+        let mut value = self.parse_sum()?;
+        loop {
+            if self.eat("<<") {
+                let shift = u32::try_from(integer(self.parse_sum()?)?).ok()?;
+                value = integer(value)?.checked_shl(shift)? as f64;
+            } else if self.eat(">>") {
+                let shift = u32::try_from(integer(self.parse_sum()?)?).ok()?;
+                value = integer(value)?.checked_shr(shift)? as f64;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn parse_sum(&mut self) -> Option<f64> {
+        let mut value = self.parse_product()?;
+        loop {
+            if self.eat("+") {
+                value += self.parse_product()?;
+            } else if self.eat("-") {
+                value -= self.parse_product()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn parse_product(&mut self) -> Option<f64> {
+        let mut value = self.parse_unary()?;
+        loop {
+            if self.eat("*") {
+                value *= self.parse_unary()?;
+            } else if self.eat("/") {
+                let divisor = self.parse_unary()?;
+                if divisor == 0.0 {
+                    return None;
+                }
+                value /= divisor;
+            } else if self.eat("%") {
+                let divisor = self.parse_unary()?;
+                if divisor == 0.0 {
+                    return None;
+                }
+                value %= divisor;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn parse_unary(&mut self) -> Option<f64> {
+        // Parentheses, unary operators and exponents all recurse through here.
+        if self.depth == MAX_DEPTH {
+            self.too_deep = true;
+            return None;
+        }
+        self.depth += 1;
+        let value = self.parse_unary_operand();
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_unary_operand(&mut self) -> Option<f64> {
+        if self.eat("-") {
+            Some(-self.parse_unary()?)
+        } else if self.eat("+") {
+            self.parse_unary()
+        } else if self.eat("~") {
+            Some(!integer(self.parse_unary()?)? as f64)
+        } else {
+            self.parse_power()
+        }
+    }
+
+    fn parse_power(&mut self) -> Option<f64> {
+        let base = self.parse_atom()?;
+        if self.eat("**") {
+            // Exponentiation is right-associative, and binds tighter than a unary minus on its
+            // left but not on its right, as in `-2 ** -1`.
+            let exponent = self.parse_unary()?;
+            Some(base.powf(exponent))
+        } else {
+            Some(base)
+        }
+    }
+
+    fn parse_atom(&mut self) -> Option<f64> {
+        if self.eat("(") {
+            let value = self.parse_bitwise_or()?;
+            return self.eat(")").then_some(value);
+        }
+
+        self.skip_whitespace();
+        let (start, first) = *self.chars.peek()?;
+        if !first.is_ascii_digit() && first != '.' {
+            return None;
+        }
+        let mut end = start;
+        while let Some(&(ix, ch)) = self.chars.peek() {
+            let is_exponent_sign = (ch == '-' || ch == '+')
+                && self.text[start..ix].ends_with(['e', 'E'])
+                && !self.text[start..ix].starts_with("0x");
+            if ch.is_ascii_alphanumeric() || ch == '.' || ch == '_' || is_exponent_sign {
+                end = ix + ch.len_utf8();
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+
+        let number = self.text[start..end].replace('_', "");
+        self.has_number = true;
+        if let Some(hex) = number.strip_prefix("0x") {
+            i64::from_str_radix(hex, 16).ok().map(|value| value as f64)
+        } else if let Some(binary) = number.strip_prefix("0b") {
+            self.all_hex = false;
+            i64::from_str_radix(binary, 2)
+                .ok()
+                .map(|value| value as f64)
+        } else {
+            self.all_hex = false;
+            number.parse::<f64>().ok()
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, ch)| ch.is_whitespace()).is_some() {}
+    }
+
+    /// Consumes `token` if the expression continues with it.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let Some(&(ix, _)) = self.chars.peek() else {
+            return false;
+        };
+        if !self.text[ix..].starts_with(token) {
+            return false;
+        }
+        for _ in token.chars() {
+            self.chars.next();
+        }
+        true
+    }
+}
+
+/// Converts an operand of a bitwise operator to an integer, failing for fractions.
+fn integer(value: f64) -> Option<i64> {
+    (value.fract() == 0.0 && value.abs() < 2f64.powi(63)).then_some(value as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate() {
+        assert_eq!(evaluate("1 + 2 * 3").as_deref(), Ok("7"));
+        assert_eq!(evaluate("(1 + 2) * 3").as_deref(), Ok("9"));
+        assert_eq!(evaluate("7 / 2").as_deref(), Ok("3.5"));
+        assert_eq!(evaluate("7 % 4 - -1").as_deref(), Ok("4"));
+        assert_eq!(evaluate("2 ** 3 ** 2").as_deref(), Ok("512"));
+        assert_eq!(evaluate("-2 ** 2").as_deref(), Ok("-4"));
+        assert_eq!(evaluate("1.5e3 + 1_000").as_deref(), Ok("2500"));
+        assert_eq!(evaluate("1 << 4 | 1").as_deref(), Ok("17"));
+        assert_eq!(evaluate("6 ^ 3 & 1").as_deref(), Ok("7"));
+        assert_eq!(evaluate("0xff & ~0x0f").as_deref(), Ok("0xf0"));
+        assert_eq!(evaluate("0x10 + 1").as_deref(), Ok("17"));
+        assert_eq!(evaluate("0b101 * 2").as_deref(), Ok("10"));
+
+        assert_eq!(evaluate("1 / 0"), Err(EvaluateError::Invalid));
+        assert_eq!(evaluate("1.5 | 1"), Err(EvaluateError::Invalid));
+        assert_eq!(evaluate("(1 + 2"), Err(EvaluateError::Invalid));
+        assert_eq!(evaluate("1 +"), Err(EvaluateError::Invalid));
+        assert_eq!(evaluate("x + 1"), Err(EvaluateError::Invalid));
+        assert_eq!(evaluate(""), Err(EvaluateError::Invalid));
+    }
+
+    #[test]
+    fn test_evaluate_nesting_limit() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(evaluate(&nested(MAX_DEPTH - 1)).as_deref(), Ok("1"));
+        assert_eq!(evaluate(&nested(MAX_DEPTH)), Err(EvaluateError::TooDeep));
+        assert_eq!(evaluate(&nested(100_000)), Err(EvaluateError::TooDeep));
+        assert_eq!(evaluate(&"-".repeat(100_000)), Err(EvaluateError::TooDeep));
+        assert_eq!(
+            evaluate(&"2 ** ".repeat(100_000)),
+            Err(EvaluateError::TooDeep)
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Folding the imports at the top of a file, such as Rust's `use` declarations, so that the start
+//! of the file shows its code. Imports are found with each language's `imports.scm` query.
+use std::{any::TypeId, ops::Range};
+
+use gpui::{App, Context, Window};
+use language::BufferSnapshot;
+use multi_buffer::Anchor;
+use settings::Settings as _;
+use text::ToOffset as _;
+
+use crate::{
+    Editor, EditorSettings, FoldImports, UnfoldImports,
+    display_map::{Crease, FoldPlaceholder},
+};
+
+enum ImportsFold {}
+
+/// Finds the first block of imports in `imports`, where imports that are only separated by
+/// whitespace belong to the same block. Blocks of a single line aren't worth folding.
+fn first_import_block(
+    buffer: &BufferSnapshot,
+    imports: impl IntoIterator<Item = Range<usize>>,
+) -> Option<Range<usize>> {
+    let mut imports = imports.into_iter();
+    let mut block = imports.next()?;
+    for import in imports {
+        if import.start < block.end {
+            block.end = block.end.max(import.end);
+        } else if buffer
+            .text_for_range(block.end..import.start)
+            .all(|chunk| chunk.trim().is_empty())
+        {
+            block.end = import.end;
+        } else {
+            break;
+        }
+    }
+    let rows = buffer.offset_to_point(block.start).row..buffer.offset_to_point(block.end).row;
+    (rows.start < rows.end).then_some(block)
+}
+
+impl Editor {
+    /// The ranges of the first block of imports in each excerpt.
+    fn import_blocks(&self, cx: &App) -> Vec<Range<Anchor>> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        snapshot
+            .excerpts()
+            .filter_map(|(excerpt_id, buffer, excerpt_range)| {
+                let context = excerpt_range.context.start.to_offset(buffer)
+                    ..excerpt_range.context.end.to_offset(buffer);
+                let block = first_import_block(buffer, buffer.import_ranges(context.clone()))?;
+                let block = block.start.max(context.start)..block.end.min(context.end);
+                let buffer_id = buffer.remote_id();
+                Some(
+                    Anchor::in_buffer(excerpt_id, buffer_id, buffer.anchor_after(block.start))
+                        ..Anchor::in_buffer(excerpt_id, buffer_id, buffer.anchor_before(block.end)),
+                )
+            })
+            .collect()
+    }
+
+    fn fold_import_blocks(
+        &mut self,
+        auto_scroll: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let placeholder = FoldPlaceholder {
+            type_tag: Some(TypeId::of::<ImportsFold>()),
+            ..self.display_map.read(cx).fold_placeholder.clone()
+        };
+        let creases = self
+            .import_blocks(cx)
+            .into_iter()
+            .map(|range| Crease::simple(range, placeholder.clone()))
+            .collect();
+        self.fold_creases(creases, auto_scroll, window, cx);
+    }
+
+    pub fn fold_imports(&mut self, _: &FoldImports, window: &mut Window, cx: &mut Context<Self>) {
+        self.fold_import_blocks(true, window, cx);
+    }
+
+    pub fn unfold_imports(
+        &mut self,
+        _: &UnfoldImports,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ranges = self.import_blocks(cx);
+        self.remove_folds_with_type(&ranges, TypeId::of::<ImportsFold>(), true, cx);
+    }
+
+    /// Folds the imports of a file the first time it's parsed after being opened, when
+    /// `auto_fold_imports` is enabled.
+    pub(crate) fn auto_fold_imports(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.imports_auto_folded
+            || !self.mode.is_full()
+            || !EditorSettings::get_global(cx).auto_fold_imports
+        {
+            return;
+        }
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+        let buffer = buffer.read(cx);
+        let has_imports_query = buffer
+            .language()
+            .and_then(|language| language.grammar())
+            .is_some_and(|grammar| grammar.imports_config().is_some());
+        // Wait for the language to be detected and the file to be parsed.
+        if !has_imports_query || buffer.is_parsing() {
+            return;
+        }
+        self.imports_auto_folded = true;
+        self.fold_import_blocks(false, window, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::AppContext as _;
+    use language::Buffer;
+
+    #[gpui::test]
+    fn test_first_import_block(cx: &mut gpui::App) {
+        let text = "use a;\n\nuse b::{\n    c,\n};\n// d\nuse e;\nuse f;\n";
+        let buffer = cx.new(|cx| Buffer::local(text, cx)).read(cx).snapshot();
+        let import = |import: &str| {
+            let start = text.find(import).unwrap();
+            start..start + import.len()
+        };
+
+        assert_eq!(
+            first_import_block(
+                &buffer,
+                [
+                    import("use a;"),
+                    import("use b::{\n    c,\n};"),
+                    import("use e;"),
+                    import("use f;"),
+                ],
+            ),
+            Some(import("use a;\n\nuse b::{\n    c,\n};"))
+        );
+        assert_eq!(
+            first_import_block(&buffer, [import("use e;"), import("use f;")]),
+            Some(import("use e;\nuse f;"))
+        );
+        assert_eq!(first_import_block(&buffer, [import("use a;")]), None);
+        assert_eq!(first_import_block(&buffer, Vec::new()), None);
+    }
+}
@@ -0,0 +1,78 @@
+//! Unfolding the folds that hide the target of a search, diagnostic, or go-to action, so that the
+//! cursor doesn't land on a folded line. With `refold_revealed_regions`, the revealed folds are
+//! folded again once no selection is inside them.
+use std::ops::Range;
+
+use gpui::{Context, Window};
+use multi_buffer::{Anchor, ToOffset};
+use settings::Settings as _;
+
+use crate::{
+    Editor, EditorSettings,
+    display_map::{Crease, FoldPlaceholder},
+};
+
+/// A fold that was removed to reveal a target, and that is folded again when it's left.
+pub(crate) struct RevealedFold {
+    range: Range<Anchor>,
+    placeholder: FoldPlaceholder,
+}
+
+impl Editor {
+    /// Removes the folds that intersect `ranges`, leaving the other folds in place.
+    pub(crate) fn reveal_folded_ranges<T: ToOffset + Clone>(
+        &mut self,
+        ranges: &[Range<T>],
+        cx: &mut Context<Self>,
+    ) {
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let revealed = ranges
+            .iter()
+            .flat_map(|range| display_map.folds_in_range(range.clone()))
+            .map(|fold| RevealedFold {
+                range: (*fold.range).clone(),
+                placeholder: fold.placeholder.clone(),
+            })
+            .collect::<Vec<_>>();
+        if revealed.is_empty() {
+            return;
+        }
+        if EditorSettings::get_global(cx).refold_revealed_regions {
+            self.revealed_folds.extend(revealed);
+        }
+        self.unfold_ranges(ranges, false, false, cx);
+    }
+
+    /// Folds the revealed folds that no selection is inside of anymore.
+    pub(crate) fn refold_revealed_regions(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.revealed_folds.is_empty() {
+            return;
+        }
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let selections = self.selections.disjoint_anchor_ranges().collect::<Vec<_>>();
+        let (left, kept): (Vec<_>, Vec<_>) = self.revealed_folds.drain(..).partition(|revealed| {
+            let start = revealed.range.start.to_offset(&buffer);
+            let end = revealed.range.end.to_offset(&buffer);
+            selections.iter().all(|selection| {
+                selection.end.to_offset(&buffer) < start || selection.start.to_offset(&buffer) > end
+            })
+        });
+        self.revealed_folds = kept;
+        if left.is_empty() {
+            return;
+        }
+
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let creases = left
+            .into_iter()
+            .filter(|revealed| {
+                display_map
+                    .folds_in_range(revealed.range.clone())
+                    .next()
+                    .is_none()
+            })
+            .map(|revealed| Crease::simple(revealed.range, revealed.placeholder))
+            .collect();
+        self.fold_creases(creases, false, window, cx);
+    }
+}
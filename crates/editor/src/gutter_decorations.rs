@@ -0,0 +1,144 @@
+//! Icons that other crates show in the editor's gutter, such as test statuses, coverage markers
+//! or review comments.
+use std::{any::TypeId, ops::Range, rc::Rc};
+
+use collections::{BTreeMap, HashMap};
+use gpui::{App, ClickEvent, Context, SharedString, Window};
+use itertools::Itertools as _;
+use multi_buffer::Anchor;
+use ui::{ButtonSize, ButtonStyle, IconButton, IconName, IconSize, Tooltip, prelude::*};
+
+use crate::{DisplayRow, Editor, EditorSnapshot, ToDisplayPoint as _};
+
+/// An icon shown in the gutter on the first row of `range`, set with
+/// [`Editor::set_gutter_decorations`].
+#[derive(Clone)]
+pub struct GutterDecoration {
+    pub range: Range<Anchor>,
+    pub icon: IconName,
+    pub color: Color,
+    pub tooltip: Option<SharedString>,
+    pub on_click: Option<Rc<dyn Fn(&Range<Anchor>, &mut Window, &mut App)>>,
+}
+
+impl GutterDecoration {
+    pub fn new(range: Range<Anchor>, icon: IconName) -> Self {
+        Self {
+            range,
+            icon,
+            color: Color::Muted,
+            tooltip: None,
+            on_click: None,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn tooltip(mut self, tooltip: impl Into<SharedString>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Calls `handler` with the decoration's range when its icon is clicked.
+    pub fn on_click(
+        mut self,
+        handler: impl Fn(&Range<Anchor>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl Editor {
+    /// Shows the given decorations in the gutter, replacing the decorations previously set for
+    /// `T`.
+    pub fn set_gutter_decorations<T: 'static>(
+        &mut self,
+        decorations: impl Into<Vec<GutterDecoration>>,
+        cx: &mut Context<Self>,
+    ) {
+        self.gutter_decorations
+            .insert(TypeId::of::<T>(), decorations.into());
+        cx.notify();
+    }
+
+    pub fn clear_gutter_decorations<T: 'static>(&mut self, cx: &mut Context<Self>) {
+        if self.gutter_decorations.remove(&TypeId::of::<T>()).is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Get the gutter decorations starting on the display rows in `range`. When several
+    /// decorations start on the same row, the first one set is shown.
+    pub(crate) fn active_gutter_decorations(
+        &self,
+        range: Range<DisplayRow>,
+        snapshot: &EditorSnapshot,
+    ) -> HashMap<DisplayRow, GutterDecoration> {
+        let mut decorations = BTreeMap::<DisplayRow, GutterDecoration>::new();
+        for (_, type_decorations) in self
+            .gutter_decorations
+            .iter()
+            .sorted_by_key(|(type_id, _)| **type_id)
+        {
+            for decoration in type_decorations {
+                let row = decoration
+                    .range
+                    .start
+                    .to_display_point(&snapshot.display_snapshot)
+                    .row();
+                if range.contains(&row) {
+                    decorations.entry(row).or_insert_with(|| decoration.clone());
+                }
+            }
+        }
+        decorations.into_iter().collect()
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn all_gutter_decorations(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<(DisplayRow, IconName)> {
+        let snapshot = self.snapshot(window, cx);
+        let max_row = snapshot.max_point().row().next_row();
+        self.active_gutter_decorations(DisplayRow(0)..max_row, &snapshot)
+            .into_iter()
+            .map(|(row, decoration)| (row, decoration.icon))
+            .sorted_by_key(|(row, _)| *row)
+            .collect()
+    }
+
+    pub(crate) fn render_gutter_decoration(
+        &self,
+        decoration: GutterDecoration,
+        row: DisplayRow,
+        cx: &mut Context<Self>,
+    ) -> IconButton {
+        let GutterDecoration {
+            range,
+            icon,
+            color,
+            tooltip,
+            on_click,
+        } = decoration;
+        IconButton::new(("gutter_decoration", row.0 as usize), icon)
+            .icon_size(IconSize::XSmall)
+            .size(ButtonSize::None)
+            .icon_color(color)
+            .style(ButtonStyle::Transparent)
+            .when_some(on_click, |button, on_click| {
+                button.on_click(cx.listener(move |editor, _: &ClickEvent, window, cx| {
+                    window.focus(&editor.focus_handle);
+                    on_click(&range, window, cx);
+                }))
+            })
+            .when_some(tooltip, |button, tooltip| {
+                button.tooltip(Tooltip::text(tooltip))
+            })
+    }
+}
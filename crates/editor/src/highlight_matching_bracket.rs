@@ -1,14 +1,15 @@
-use crate::{Editor, RangeToAnchorExt};
-use gpui::{Context, HighlightStyle};
+use crate::{Editor, EditorEvent, RangeToAnchorExt};
+use gpui::{Context, HighlightStyle, Task};
 use language::CursorShape;
 use multi_buffer::ToOffset;
 use theme::ActiveTheme;
 
-enum MatchingBracketHighlight {}
+pub(crate) enum MatchingBracketHighlight {}
 
+/// Highlights the brackets enclosing the cursor. Finding them can take a while in deeply nested
+/// files, so they're looked up in the background, and the highlights are updated once they're
+/// found unless the buffer has changed in the meantime.
 pub fn refresh_matching_bracket_highlights(editor: &mut Editor, cx: &mut Context<Editor>) {
-    editor.clear_highlights::<MatchingBracketHighlight>(cx);
-
     let buffer_snapshot = editor.buffer.read(cx).snapshot(cx);
     let newest_selection = editor
         .selections
@@ -16,12 +17,16 @@ pub fn refresh_matching_bracket_highlights(editor: &mut Editor, cx: &mut Context
         .map(|anchor| anchor.to_offset(&buffer_snapshot));
     // Don't highlight brackets if the selection isn't empty
     if !newest_selection.is_empty() {
+        editor.matching_brackets_task = Task::ready(());
+        clear_matching_bracket_highlights(editor, cx);
         return;
     }
 
     let head = newest_selection.head();
     if head > buffer_snapshot.len() {
         log::error!("bug: cursor offset is out of range while refreshing bracket highlights");
+        editor.matching_brackets_task = Task::ready(());
+        clear_matching_bracket_highlights(editor, cx);
         return;
     }
 
@@ -34,24 +39,53 @@ pub fn refresh_matching_bracket_highlights(editor: &mut Editor, cx: &mut Context
         }
     }
 
-    if let Some((opening_range, closing_range)) =
-        buffer_snapshot.innermost_enclosing_bracket_ranges(head..tail, None)
+    let edit_count = buffer_snapshot.edit_count();
+    let brackets = cx.background_spawn(async move {
+        let (opening_range, closing_range) =
+            buffer_snapshot.innermost_enclosing_bracket_ranges(head..tail, None)?;
+        Some(vec![
+            opening_range.to_anchors(&buffer_snapshot),
+            closing_range.to_anchors(&buffer_snapshot),
+        ])
+    });
+    // Replacing the task drops the lookup for an earlier cursor position.
+    editor.matching_brackets_task = cx.spawn(async move |editor, cx| {
+        let brackets = brackets.await;
+        editor
+            .update(cx, |editor, cx| {
+                if editor.buffer.read(cx).snapshot(cx).edit_count() != edit_count {
+                    return;
+                }
+                match brackets {
+                    Some(ranges) => {
+                        editor.highlight_text::<MatchingBracketHighlight>(
+                            ranges,
+                            HighlightStyle {
+                                background_color: Some(
+                                    cx.theme()
+                                        .colors()
+                                        .editor_document_highlight_bracket_background,
+                                ),
+                                ..Default::default()
+                            },
+                            cx,
+                        );
+                        cx.emit(EditorEvent::TransientHighlightsChanged);
+                    }
+                    None => clear_matching_bracket_highlights(editor, cx),
+                }
+            })
+            .ok();
+    });
+}
+
+fn clear_matching_bracket_highlights(editor: &mut Editor, cx: &mut Context<Editor>) {
+    if editor
+        .text_highlights::<MatchingBracketHighlight>(cx)
+        .is_some()
     {
-        editor.highlight_text::<MatchingBracketHighlight>(
-            vec![
-                opening_range.to_anchors(&buffer_snapshot),
-                closing_range.to_anchors(&buffer_snapshot),
-            ],
-            HighlightStyle {
-                background_color: Some(
-                    cx.theme()
-                        .colors()
-                        .editor_document_highlight_bracket_background,
-                ),
-                ..Default::default()
-            },
-            cx,
-        )
+        editor.clear_highlights::<MatchingBracketHighlight>(cx);
+        cx.emit(EditorEvent::TransientHighlightsChanged);
     }
 }
 
@@ -113,6 +147,7 @@ mod tests {
                 another_test(1, 2, 3);
             }
         "#});
+        cx.run_until_parked();
         cx.assert_editor_text_highlights::<MatchingBracketHighlight>(indoc! {r#"
             pub fn test«(»"Test argument"«)» {
                 another_test(1, 2, 3);
@@ -124,6 +159,7 @@ mod tests {
                 another_test(1, ˇ2, 3);
             }
         "#});
+        cx.run_until_parked();
         cx.assert_editor_text_highlights::<MatchingBracketHighlight>(indoc! {r#"
             pub fn test("Test argument") {
                 another_test«(»1, 2, 3«)»;
@@ -135,6 +171,7 @@ mod tests {
                 anotherˇ_test(1, 2, 3);
             }
         "#});
+        cx.run_until_parked();
         cx.assert_editor_text_highlights::<MatchingBracketHighlight>(indoc! {r#"
             pub fn test("Test argument") «{»
                 another_test(1, 2, 3);
@@ -147,6 +184,7 @@ mod tests {
                 another_test(1, 2, 3);
             }
         "#});
+        cx.run_until_parked();
         cx.assert_editor_text_highlights::<MatchingBracketHighlight>(indoc! {r#"
             pub fn test("Test argument") {
                 another_test(1, 2, 3);
@@ -159,6 +197,7 @@ mod tests {
                 another_test(1, 2, 3);
             }
         "#});
+        cx.run_until_parked();
         cx.assert_editor_text_highlights::<MatchingBracketHighlight>(indoc! {r#"
             pub fn test«("Test argument") {
                 another_test(1, 2, 3);
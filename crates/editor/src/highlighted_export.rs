@@ -0,0 +1,476 @@
+//! Renders buffer text with the theme's syntax colors as HTML and RTF, for
+//! `editor::CopyWithSyntaxHighlighting` and `editor::ExportAsHtml`.
+use std::{fmt::Write as _, ops::Range, sync::Arc};
+
+use gpui::{
+    App, AppContext as _, ClipboardEntry, ClipboardItem, ClipboardString, Context, FontStyle,
+    FontWeight, HighlightStyle, Hsla, Rgba, Window,
+};
+use multi_buffer::MultiBufferSnapshot;
+use project::DirectoryLister;
+use settings::Settings as _;
+use theme::{
+    ActiveTheme as _, Appearance, SyntaxTheme, Theme, ThemeRegistry, ThemeSelection, ThemeSettings,
+};
+use workspace::notifications::NotifyTaskExt as _;
+
+use crate::{CopyWithSyntaxHighlighting, Editor, ExportAsHtml, clipboard_history};
+
+/// A run of text sharing a single syntax highlight style.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct StyledRun {
+    pub text: String,
+    /// The name of the syntax theme entry that styles this run.
+    pub highlight: Option<String>,
+    pub color: Option<Hsla>,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+impl StyledRun {
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            highlight: None,
+            color: None,
+            bold: false,
+            italic: false,
+        }
+    }
+
+    fn has_same_style(&self, other: &Self) -> bool {
+        self.highlight == other.highlight
+            && self.color == other.color
+            && self.bold == other.bold
+            && self.italic == other.italic
+    }
+}
+
+/// The colors and font used to render runs outside of the editor.
+pub(crate) struct ExportStyle {
+    pub font_family: String,
+    pub font_size: f32,
+    pub foreground: Hsla,
+    pub background: Hsla,
+}
+
+impl ExportStyle {
+    pub fn from_theme(cx: &App) -> Self {
+        let settings = ThemeSettings::get_global(cx);
+        Self {
+            font_family: settings.buffer_font.family.to_string(),
+            font_size: settings.buffer_font_size(cx).into(),
+            foreground: cx.theme().colors().editor_foreground,
+            background: cx.theme().colors().editor_background,
+        }
+    }
+}
+
+/// Splits the text in `range` into runs of uniform syntax highlighting.
+pub(crate) fn styled_runs(
+    snapshot: &MultiBufferSnapshot,
+    range: Range<usize>,
+    syntax: &SyntaxTheme,
+) -> Vec<StyledRun> {
+    let mut runs = Vec::<StyledRun>::new();
+    for chunk in snapshot.chunks(range, true) {
+        let style = chunk
+            .syntax_highlight_id
+            .and_then(|id| id.style(syntax))
+            .unwrap_or_default();
+        let run = StyledRun {
+            text: chunk.text.to_string(),
+            highlight: chunk
+                .syntax_highlight_id
+                .and_then(|id| id.name(syntax))
+                .map(ToString::to_string),
+            color: style.color,
+            bold: is_bold(&style),
+            italic: style.font_style == Some(FontStyle::Italic),
+        };
+        match runs.last_mut() {
+            Some(last) if last.has_same_style(&run) => last.text.push_str(&run.text),
+            _ => runs.push(run),
+        }
+    }
+    runs
+}
+
+fn is_bold(style: &HighlightStyle) -> bool {
+    style
+        .font_weight
+        .is_some_and(|weight| weight >= FontWeight::BOLD)
+}
+
+fn css_color(color: Hsla) -> String {
+    let rgba = Rgba::from(color);
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        (rgba.r * 255.).round() as u8,
+        (rgba.g * 255.).round() as u8,
+        (rgba.b * 255.).round() as u8
+    )
+}
+
+fn escape_html(text: &str, output: &mut String) {
+    for ch in text.chars() {
+        match ch {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            _ => output.push(ch),
+        }
+    }
+}
+
+/// Renders `runs` as a `<pre>` element with inline styles, so that it keeps its colors when
+/// pasted into other applications.
+pub(crate) fn runs_to_html(runs: &[StyledRun], style: &ExportStyle) -> String {
+    let mut html = String::new();
+    write!(
+        html,
+        "<pre style=\"font-family: '{}', monospace; font-size: {}px; color: {}; background-color: {};\">",
+        style.font_family.replace(['\'', '"'], ""),
+        style.font_size,
+        css_color(style.foreground),
+        css_color(style.background),
+    )
+    .ok();
+    for run in runs {
+        let mut declarations = Vec::new();
+        if let Some(color) = run.color {
+            declarations.push(format!("color: {}", css_color(color)));
+        }
+        if run.bold {
+            declarations.push("font-weight: bold".to_string());
+        }
+        if run.italic {
+            declarations.push("font-style: italic".to_string());
+        }
+
+        if declarations.is_empty() {
+            escape_html(&run.text, &mut html);
+        } else {
+            write!(html, "<span style=\"{}\">", declarations.join("; ")).ok();
+            escape_html(&run.text, &mut html);
+            html.push_str("</span>");
+        }
+    }
+    html.push_str("</pre>");
+    html
+}
+
+/// Renders `runs` as an RTF document using a color table built from the runs' colors.
+pub(crate) fn runs_to_rtf(runs: &[StyledRun], style: &ExportStyle) -> String {
+    let mut colors = vec![css_color(style.foreground)];
+    let color_indices = runs
+        .iter()
+        .map(|run| {
+            let color = css_color(run.color.unwrap_or(style.foreground));
+            match colors.iter().position(|existing| *existing == color) {
+                Some(ix) => ix + 1,
+                None => {
+                    colors.push(color);
+                    colors.len()
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut rtf = String::new();
+    write!(
+        rtf,
+        "{{\\rtf1\\ansi\\deff0{{\\fonttbl{{\\f0\\fmodern {};}}}}{{\\colortbl;",
+        style.font_family.replace(['\\', '{', '}', ';'], "")
+    )
+    .ok();
+    for color in &colors {
+        let rgb = u32::from_str_radix(&color[1..], 16).unwrap_or_default();
+        write!(
+            rtf,
+            "\\red{}\\green{}\\blue{};",
+            (rgb >> 16) & 0xff,
+            (rgb >> 8) & 0xff,
+            rgb & 0xff
+        )
+        .ok();
+    }
+    write!(rtf, "}}\\f0\\fs{} ", (style.font_size * 2.).round() as u32).ok();
+
+    for (run, color_ix) in runs.iter().zip(color_indices) {
+        write!(
+            rtf,
+            "{{\\cf{color_ix}{}{} ",
+            if run.bold { "\\b" } else { "" },
+            if run.italic { "\\i" } else { "" }
+        )
+        .ok();
+        for ch in run.text.chars() {
+            match ch {
+                '\\' | '{' | '}' => {
+                    rtf.push('\\');
+                    rtf.push(ch);
+                }
+                '\n' => rtf.push_str("\\line "),
+                '\t' => rtf.push_str("\\tab "),
+                ch if ch.is_ascii() => rtf.push(ch),
+                ch => {
+                    let mut utf16 = [0; 2];
+                    for unit in ch.encode_utf16(&mut utf16) {
+                        write!(rtf, "\\u{}?", *unit as i16).ok();
+                    }
+                }
+            }
+        }
+        rtf.push('}');
+    }
+    rtf.push('}');
+    rtf
+}
+
+fn highlight_class(name: &str) -> String {
+    let mut class = String::from("hl");
+    for part in name.split('.') {
+        class.push('-');
+        class.extend(
+            part.chars()
+                .filter(|ch| ch.is_ascii_alphanumeric() || *ch == '_'),
+        );
+    }
+    class
+}
+
+fn appearance_class(appearance: Appearance) -> &'static str {
+    match appearance {
+        Appearance::Light => "light",
+        Appearance::Dark => "dark",
+    }
+}
+
+/// Returns the theme to offer as the alternative to the active one in exported documents,
+/// preferring the user's configured light or dark theme.
+fn theme_with_other_appearance(cx: &App) -> Option<Arc<Theme>> {
+    let appearance = match cx.theme().appearance() {
+        Appearance::Light => Appearance::Dark,
+        Appearance::Dark => Appearance::Light,
+    };
+    let name = match &ThemeSettings::get_global(cx).theme {
+        ThemeSelection::Dynamic { light, dark, .. } => match appearance {
+            Appearance::Light => light.0.clone(),
+            Appearance::Dark => dark.0.clone(),
+        },
+        ThemeSelection::Static(_) => theme::default_theme(appearance).into(),
+    };
+    ThemeRegistry::global(cx)
+        .get(&name)
+        .ok()
+        .filter(|theme| theme.appearance() == appearance)
+}
+
+/// Renders `runs` as a standalone HTML document styled with each of `themes`.
+///
+/// The first theme is applied initially. When more than one theme is given, the document
+/// contains a button that switches between the light and dark variants.
+pub(crate) fn runs_to_html_document(
+    runs: &[StyledRun],
+    title: &str,
+    style: &ExportStyle,
+    themes: &[Arc<Theme>],
+    line_numbers: bool,
+) -> String {
+    let mut highlights = Vec::<&str>::new();
+    for run in runs {
+        if let Some(highlight) = run.highlight.as_deref()
+            && !highlights.contains(&highlight)
+        {
+            highlights.push(highlight);
+        }
+    }
+    let line_count = runs
+        .iter()
+        .map(|run| run.text.matches('\n').count())
+        .sum::<usize>()
+        + 1;
+
+    let mut html =
+        String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>");
+    escape_html(title, &mut html);
+    html.push_str("</title>\n<style>\n");
+    write!(
+        html,
+        "body {{ margin: 0; }}\npre {{ margin: 0; padding: 16px; font-family: '{}', monospace; font-size: {}px; }}\n",
+        style.font_family.replace(['\'', '"', '<', '>'], ""),
+        style.font_size,
+    )
+    .ok();
+    write!(
+        html,
+        ".line-number {{ display: inline-block; min-width: {}ch; margin-right: 2ch; text-align: right; user-select: none; }}\n",
+        line_count.to_string().len()
+    )
+    .ok();
+    html.push_str("#theme-toggle { position: fixed; top: 8px; right: 8px; }\n");
+    for theme in themes {
+        let class = appearance_class(theme.appearance());
+        let colors = theme.colors();
+        write!(
+            html,
+            "body.{class} {{ color: {}; background-color: {}; }}\nbody.{class} .line-number {{ color: {}; }}\n",
+            css_color(colors.editor_foreground),
+            css_color(colors.editor_background),
+            css_color(colors.editor_line_number),
+        )
+        .ok();
+        for highlight in &highlights {
+            let highlight_style = theme.syntax().get(highlight);
+            let mut declarations = Vec::new();
+            if let Some(color) = highlight_style.color {
+                declarations.push(format!("color: {}", css_color(color)));
+            }
+            if is_bold(&highlight_style) {
+                declarations.push("font-weight: bold".to_string());
+            }
+            if highlight_style.font_style == Some(FontStyle::Italic) {
+                declarations.push("font-style: italic".to_string());
+            }
+            if !declarations.is_empty() {
+                writeln!(
+                    html,
+                    "body.{class} .{} {{ {}; }}",
+                    highlight_class(highlight),
+                    declarations.join("; ")
+                )
+                .ok();
+            }
+        }
+    }
+    html.push_str("</style>\n</head>\n");
+    write!(
+        html,
+        "<body class=\"{}\">\n",
+        themes
+            .first()
+            .map_or("dark", |theme| appearance_class(theme.appearance()))
+    )
+    .ok();
+    if themes.len() > 1 {
+        html.push_str(
+            "<button id=\"theme-toggle\" onclick=\"document.body.classList.toggle('light'); document.body.classList.toggle('dark');\">Toggle Light/Dark</button>\n",
+        );
+    }
+
+    html.push_str("<pre>");
+    let mut row = 1;
+    if line_numbers {
+        write!(html, "<span class=\"line-number\">{row}</span>").ok();
+    }
+    for run in runs {
+        let class = run.highlight.as_deref().map(highlight_class);
+        for (ix, line) in run.text.split('\n').enumerate() {
+            if ix > 0 {
+                html.push('\n');
+                row += 1;
+                if line_numbers {
+                    write!(html, "<span class=\"line-number\">{row}</span>").ok();
+                }
+            }
+            if line.is_empty() {
+                continue;
+            }
+            match &class {
+                Some(class) => {
+                    write!(html, "<span class=\"{class}\">").ok();
+                    escape_html(line, &mut html);
+                    html.push_str("</span>");
+                }
+                None => escape_html(line, &mut html),
+            }
+        }
+    }
+    html.push_str("</pre>\n</body>\n</html>\n");
+    html
+}
+
+impl Editor {
+    pub fn copy_with_syntax_highlighting(
+        &mut self,
+        _: &CopyWithSyntaxHighlighting,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let syntax = cx.theme().syntax().clone();
+        let mut runs = Vec::new();
+        for selection in self.selections.all::<usize>(cx) {
+            if selection.is_empty() {
+                continue;
+            }
+            if !runs.is_empty() {
+                runs.push(StyledRun::plain("\n"));
+            }
+            runs.extend(styled_runs(&snapshot, selection.range(), &syntax));
+        }
+        if runs.is_empty() {
+            return;
+        }
+
+        let style = ExportStyle::from_theme(cx);
+        let text = runs.iter().map(|run| run.text.as_str()).collect::<String>();
+        let string = ClipboardString::new(text)
+            .with_html(runs_to_html(&runs, &style))
+            .with_rtf(runs_to_rtf(&runs, &style));
+        clipboard_history::write_to_clipboard(
+            ClipboardItem::from(ClipboardEntry::String(string)),
+            cx,
+        );
+    }
+
+    pub(crate) fn buffer_as_html_document(&self, line_numbers: bool, cx: &App) -> String {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let runs = styled_runs(&snapshot, 0..snapshot.len(), cx.theme().syntax());
+        let mut themes = vec![cx.theme().clone()];
+        themes.extend(theme_with_other_appearance(cx));
+        runs_to_html_document(
+            &runs,
+            &self.buffer.read(cx).title(cx),
+            &ExportStyle::from_theme(cx),
+            &themes,
+            line_numbers,
+        )
+    }
+
+    pub fn export_as_html(
+        &mut self,
+        action: &ExportAsHtml,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        let html = self.buffer_as_html_document(action.line_numbers, cx);
+        let suggested_name = format!("{}.html", self.buffer.read(cx).title(cx));
+        let (fs, paths) = workspace.update(cx, |workspace, cx| {
+            let lister = if workspace.project().read(cx).is_local() {
+                DirectoryLister::Local(
+                    workspace.project().clone(),
+                    workspace.app_state().fs.clone(),
+                )
+            } else {
+                DirectoryLister::Project(workspace.project().clone())
+            };
+            (
+                workspace.app_state().fs.clone(),
+                workspace.prompt_for_new_path(lister, Some(suggested_name), window, cx),
+            )
+        });
+        cx.background_spawn(async move {
+            let Some(path) = paths.await.ok().flatten().into_iter().flatten().next() else {
+                return anyhow::Ok(());
+            };
+            fs.atomic_write(path, html).await
+        })
+        .detach_and_notify_err(window, cx);
+    }
+}
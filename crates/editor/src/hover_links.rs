@@ -7,7 +7,7 @@ use crate::{
     scroll::ScrollAmount,
 };
 use gpui::{App, AsyncWindowContext, Context, Entity, Modifiers, Task, Window, px};
-use language::{Bias, ToOffset};
+use language::{Bias, Point, ToOffset, ToPoint as _};
 use linkify::{LinkFinder, LinkKind};
 use lsp::LanguageServerId;
 use project::{
@@ -17,7 +17,8 @@ use project::{
 use settings::Settings;
 use std::ops::Range;
 use theme::ActiveTheme as _;
-use util::{ResultExt, TryFutureExt as _, maybe};
+use util::{ResultExt, TryFutureExt as _, maybe, paths::PathWithPosition};
+use workspace::Workspace;
 
 #[derive(Debug)]
 pub struct HoveredLinkState {
@@ -66,7 +67,8 @@ impl RangeInEditor {
 #[derive(Debug, Clone)]
 pub enum HoverLink {
     Url(String),
-    File(ResolvedPath),
+    /// A file, and the zero-based position to open it at, if the reference specified one.
+    File(ResolvedPath, Option<Point>),
     Text(LocationLink),
     InlayHint(lsp::Location, LanguageServerId),
 }
@@ -538,7 +540,9 @@ pub fn show_link_definition(
         async move {
             let result = match &trigger_point {
                 TriggerPoint::Text(_) => {
-                    if let Some((url_range, url)) = find_url(&buffer, buffer_position, cx.clone()) {
+                    if let Some((url_range, url)) = find_url(&buffer, buffer_position, cx.clone())
+                        .or_else(|| find_link_rule_url(&buffer, buffer_position, cx.clone()))
+                    {
                         this.read_with(cx, |_, _| {
                             let range = maybe!({
                                 let start =
@@ -549,7 +553,7 @@ pub fn show_link_definition(
                             (range, vec![HoverLink::Url(url)])
                         })
                         .ok()
-                    } else if let Some((filename_range, filename)) =
+                    } else if let Some((filename_range, filename, position)) =
                         find_file(&buffer, project.clone(), buffer_position, cx).await
                     {
                         let range = maybe!({
@@ -559,7 +563,7 @@ pub fn show_link_definition(
                             Some(RangeInEditor::Text(start..end))
                         });
 
-                        Some((range, vec![HoverLink::File(filename)]))
+                        Some((range, vec![HoverLink::File(filename, position)]))
                     } else if let Some(provider) = provider {
                         let task = cx.update(|_, cx| {
                             provider.definitions(&buffer, buffer_position, preferred_kind, cx)
@@ -718,6 +722,57 @@ pub(crate) fn find_url(
     None
 }
 
+/// Finds the text around `position` matching one of the `link_rules` setting's regexes, if it is
+/// inside a comment, and returns the URL the rule builds from it.
+pub(crate) fn find_link_rule_url(
+    buffer: &Entity<language::Buffer>,
+    position: text::Anchor,
+    cx: AsyncWindowContext,
+) -> Option<(Range<text::Anchor>, String)> {
+    let (snapshot, link_rules) = cx
+        .update(|_, cx| {
+            (
+                buffer.read(cx).snapshot(),
+                EditorSettings::get_global(cx).link_rules.clone(),
+            )
+        })
+        .ok()?;
+    if link_rules.is_empty() {
+        return None;
+    }
+
+    let offset = position.to_offset(&snapshot);
+    let is_in_comment = snapshot
+        .language_scope_at(offset)
+        .is_some_and(|scope| scope.override_name() == Some("comment"));
+    if !is_in_comment {
+        return None;
+    }
+
+    let row = position.to_point(&snapshot).row;
+    let line_start = Point::new(row, 0).to_offset(&snapshot);
+    let line = snapshot
+        .text_for_range(line_start..Point::new(row, snapshot.line_len(row)).to_offset(&snapshot))
+        .collect::<String>();
+    let column = offset - line_start;
+    for rule in &link_rules {
+        for captures in rule.regex.captures_iter(&line) {
+            let Some(link) = captures.get(0) else {
+                continue;
+            };
+            if link.is_empty() || link.start() > column || link.end() < column {
+                continue;
+            }
+            let mut url = String::new();
+            captures.expand(&rule.url, &mut url);
+            let range = snapshot.anchor_before(line_start + link.start())
+                ..snapshot.anchor_after(line_start + link.end());
+            return Some((range, url));
+        }
+    }
+    None
+}
+
 pub(crate) fn find_url_from_range(
     buffer: &Entity<language::Buffer>,
     range: Range<text::Anchor>,
@@ -778,12 +833,14 @@ pub(crate) fn find_url_from_range(
     None
 }
 
+/// Finds the path of an existing file around `position`, along with the position in that file if
+/// the path is followed by a `:row` or `:row:column` suffix, as in compiler output.
 pub(crate) async fn find_file(
     buffer: &Entity<language::Buffer>,
     project: Option<Entity<Project>>,
     position: text::Anchor,
     cx: &mut AsyncWindowContext,
-) -> Option<(Range<text::Anchor>, ResolvedPath)> {
+) -> Option<(Range<text::Anchor>, ResolvedPath, Option<Point>)> {
     let project = project?;
     let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot()).ok()?;
     let scope = snapshot.language_scope_at(position);
@@ -805,7 +862,17 @@ pub(crate) async fn find_file(
     }
 
     if let Some(existing_path) = check_path(&candidate_file_path, &project, buffer, cx).await {
-        return Some((range, existing_path));
+        return Some((range, existing_path, None));
+    }
+
+    let path_with_position = PathWithPosition::parse_str(&candidate_file_path);
+    if let Some(row) = path_with_position.row {
+        let path = path_with_position.path.to_string_lossy();
+        if let Some(existing_path) = check_path(&path, &project, buffer, cx).await {
+            let column = path_with_position.column.unwrap_or(1);
+            let position = Point::new(row.saturating_sub(1), column.saturating_sub(1));
+            return Some((range, existing_path, Some(position)));
+        }
     }
 
     if let Some(scope) = scope {
@@ -817,7 +884,7 @@ pub(crate) async fn find_file(
             let suffixed_candidate = format!("{candidate_file_path}.{suffix}");
             if let Some(existing_path) = check_path(&suffixed_candidate, &project, buffer, cx).await
             {
-                return Some((range, existing_path));
+                return Some((range, existing_path, None));
             }
         }
     }
@@ -825,6 +892,32 @@ pub(crate) async fn find_file(
     None
 }
 
+/// Opens the file of a [`HoverLink::File`], moving the cursor to `position` if there is one.
+pub(crate) async fn open_file_link(
+    workspace: Entity<Workspace>,
+    path: ResolvedPath,
+    position: Option<Point>,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<()> {
+    let item = workspace
+        .update_in(cx, |workspace, window, cx| {
+            workspace.open_resolved_path(path, window, cx)
+        })?
+        .await?;
+    if let Some(position) = position
+        && let Some(editor) = item.downcast::<Editor>()
+    {
+        editor.update_in(cx, |editor, window, cx| {
+            let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+                return;
+            };
+            let position = buffer.read(cx).clip_point(position, Bias::Left);
+            editor.go_to_singleton_buffer_point(position, window, cx);
+        })?;
+    }
+    Ok(())
+}
+
 fn surrounding_filename(
     snapshot: language::BufferSnapshot,
     position: text::Anchor,
@@ -929,8 +1022,9 @@ mod tests {
     use futures::StreamExt;
     use gpui::Modifiers;
     use indoc::indoc;
+    use language::{Language, LanguageConfig, LanguageMatcher};
     use lsp::request::{GotoDefinition, GotoTypeDefinition};
-    use settings::InlayHintSettingsContent;
+    use settings::{InlayHintSettingsContent, LinkRuleContent, SettingsStore};
     use util::{assert_set_eq, path};
     use workspace::item::Item;
 
@@ -1507,6 +1601,67 @@ mod tests {
         assert_eq!(cx.opened_url(), Some("https://zed.dev/releases".into()));
     }
 
+    #[gpui::test]
+    async fn test_link_rules(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|settings, cx| {
+                settings.update_user_settings(cx, |settings| {
+                    settings.editor.link_rules = Some(vec![LinkRuleContent {
+                        pattern: r"JIRA-(\d+)".to_string(),
+                        url: "https://jira.example.com/browse/${1}".to_string(),
+                    }]);
+                });
+            });
+        });
+        let language = Language::new(
+            LanguageConfig {
+                name: "Rust".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["rs".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        )
+        .with_override_query("[(line_comment) (block_comment)] @comment.inclusive")
+        .unwrap();
+        let mut cx =
+            EditorLspTestContext::new(language, lsp::ServerCapabilities::default(), cx).await;
+
+        cx.set_state(indoc! {"
+            // Fixes JIRA-123.
+            let jira = \"JIRA-456\";ˇ
+        "});
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            // Fixes JIRA-1ˇ23.
+            let jira = \"JIRA-456\";
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        cx.assert_editor_text_highlights::<HoveredLinkState>(indoc! {"
+            // Fixes «JIRA-123ˇ».
+            let jira = \"JIRA-456\";
+        "});
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        assert_eq!(
+            cx.opened_url(),
+            Some("https://jira.example.com/browse/123".into())
+        );
+
+        // Matches outside of comments are not links.
+        let screen_coord = cx.pixel_position(indoc! {"
+            // Fixes JIRA-123.
+            let jira = \"JIRA-4ˇ56\";
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        cx.assert_editor_text_highlights::<HoveredLinkState>(indoc! {"
+            // Fixes JIRA-123.
+            let jira = \"JIRA-456\";
+        "});
+    }
+
     #[gpui::test]
     async fn test_surrounding_filename(cx: &mut gpui::TestAppContext) {
         init_test(cx, |_| {});
@@ -1816,6 +1971,81 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_hover_filenames_with_position(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(lsp::ServerCapabilities::default(), cx).await;
+
+        let fs = cx.update_workspace(|workspace, _, cx| workspace.project().read(cx).fs().clone());
+        fs.as_fake()
+            .insert_file(
+                path!("/root/dir/file2.rs"),
+                "first line\nsecond line\n".as_bytes().to_vec(),
+            )
+            .await;
+
+        cx.set_state(indoc! {"
+            error[E0308]: mismatched types
+             --> file2.rs:2:8:
+            missing.rs:1:1ˇ
+        "});
+
+        // A path that does not exist is not a link, even with a position.
+        let screen_coord = cx.pixel_position(indoc! {"
+            error[E0308]: mismatched types
+             --> file2.rs:2:8:
+            missˇing.rs:1:1
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        cx.assert_editor_text_highlights::<HoveredLinkState>(indoc! {"
+            error[E0308]: mismatched types
+             --> file2.rs:2:8:
+            missing.rs:1:1
+        "});
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            error[E0308]: mismatched types
+             --> fiˇle2.rs:2:8:
+            missing.rs:1:1
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        cx.assert_editor_text_highlights::<HoveredLinkState>(indoc! {"
+            error[E0308]: mismatched types
+             --> «file2.rs:2:8:ˇ»
+            missing.rs:1:1
+        "});
+
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        cx.run_until_parked();
+        cx.update_workspace(|workspace, _, cx| {
+            let active_editor = workspace.active_item_as::<Editor>(cx).unwrap();
+            let buffer = active_editor
+                .read(cx)
+                .buffer()
+                .read(cx)
+                .as_singleton()
+                .unwrap();
+            let file_path = buffer
+                .read(cx)
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx);
+            assert_eq!(
+                file_path,
+                std::path::PathBuf::from(path!("/root/dir/file2.rs"))
+            );
+            assert_eq!(
+                active_editor.update(cx, |editor, cx| editor
+                    .selections
+                    .newest::<Point>(cx)
+                    .head()),
+                Point::new(1, 7)
+            );
+        });
+    }
+
     #[gpui::test]
     async fn test_hover_directories(cx: &mut gpui::TestAppContext) {
         init_test(cx, |_| {});
@@ -1,16 +1,19 @@
 use crate::{
     ActiveDiagnostic, Anchor, AnchorRangeExt, DisplayPoint, DisplayRow, Editor, EditorSettings,
-    EditorSnapshot, GlobalDiagnosticRenderer, Hover,
-    display_map::{InlayOffset, ToDisplayPoint, invisibles::is_invisible},
+    EditorSnapshot, FocusHover, GlobalDiagnosticRenderer, Hover, TogglePinnedHover,
+    display_map::{
+        InlayOffset, ToDisplayPoint,
+        invisibles::{self, is_invisible},
+    },
     hover_links::{InlayHighlight, RangeInEditor},
     scroll::ScrollAmount,
 };
 use anyhow::Context as _;
 use gpui::{
-    AnyElement, AsyncWindowContext, Context, Entity, Focusable as _, FontWeight, Hsla,
-    InteractiveElement, IntoElement, MouseButton, ParentElement, Pixels, ScrollHandle, Size,
-    StatefulInteractiveElement, StyleRefinement, Styled, Subscription, Task, TextStyleRefinement,
-    Window, div, px,
+    AnyElement, AsyncWindowContext, Context, CursorStyle, DragMoveEvent, Entity, Focusable as _,
+    FontWeight, Hsla, InteractiveElement, IntoElement, MouseButton, ParentElement, Pixels,
+    ScrollHandle, Size, StatefulInteractiveElement, StyleRefinement, Styled, Subscription, Task,
+    TextStyleRefinement, Window, div, px,
 };
 use itertools::Itertools;
 use language::{DiagnosticEntry, Language, LanguageRegistry};
@@ -23,7 +26,7 @@ use std::{borrow::Cow, cell::RefCell};
 use std::{ops::Range, sync::Arc, time::Duration};
 use std::{path::PathBuf, rc::Rc};
 use theme::ThemeSettings;
-use ui::{Scrollbars, WithScrollbar, prelude::*, theme_is_transparent};
+use ui::{Scrollbars, Tooltip, WithScrollbar, prelude::*, theme_is_transparent};
 use url::Url;
 use util::TryFutureExt;
 use workspace::{OpenOptions, OpenVisible, Workspace};
@@ -33,13 +36,59 @@ pub const MIN_POPOVER_CHARACTER_WIDTH: f32 = 20.;
 pub const MIN_POPOVER_LINE_HEIGHT: f32 = 4.;
 pub const POPOVER_RIGHT_OFFSET: Pixels = px(8.0);
 pub const HOVER_POPOVER_GAP: Pixels = px(10.);
+const MIN_RESIZED_POPOVER_SIZE: Size<Pixels> = Size {
+    width: px(120.),
+    height: px(40.),
+};
 
 /// Bindable action which uses the most recent selection head to trigger a hover
 pub fn hover(editor: &mut Editor, _: &Hover, window: &mut Window, cx: &mut Context<Editor>) {
     let head = editor.selections.newest_anchor().head();
+    editor.hover_state.pinned = false;
     show_hover(editor, head, true, window, cx);
 }
 
+/// Pins the visible hover popovers so that they stay open while editing, or unpins them.
+pub fn toggle_pinned_hover(
+    editor: &mut Editor,
+    _: &TogglePinnedHover,
+    _: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    if editor.hover_state.visible() {
+        editor.hover_state.pinned = !editor.hover_state.pinned;
+        cx.notify();
+    } else {
+        cx.propagate();
+    }
+}
+
+/// Moves the keyboard focus into the visible hover popover, so that its text can be selected and
+/// copied.
+pub fn focus_hover(
+    editor: &mut Editor,
+    _: &FocusHover,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    let markdown = editor
+        .hover_state
+        .info_popovers
+        .iter()
+        .find_map(|popover| popover.parsed_content.clone())
+        .or_else(|| {
+            editor
+                .hover_state
+                .diagnostic_popover
+                .as_ref()
+                .map(|popover| popover.markdown.clone())
+        });
+    match markdown {
+        Some(markdown) => window.focus(&markdown.focus_handle(cx)),
+        None => cx.propagate(),
+    }
+}
+
 /// The internal hover action dispatches between `show_hover` or `hide_hover`
 /// depending on whether a point to hover over is provided.
 pub fn hover_at(
@@ -48,7 +97,7 @@ pub fn hover_at(
     window: &mut Window,
     cx: &mut Context<Editor>,
 ) {
-    if EditorSettings::get_global(cx).hover_popover_enabled {
+    if EditorSettings::get_global(cx).hover_popover_enabled && !editor.hover_state.pinned {
         if show_keyboard_hover(editor, window, cx) {
             return;
         }
@@ -206,10 +255,15 @@ pub fn hover_at_inlay(
     }
 }
 
-/// Hides the type information popup.
+/// Hides the type information popup, unless it is pinned.
 /// Triggered by the `Hover` action when the cursor is not over a symbol or when the
 /// selections changed.
 pub fn hide_hover(editor: &mut Editor, cx: &mut Context<Editor>) -> bool {
+    if editor.hover_state.pinned {
+        return false;
+    }
+
+    editor.hover_state.resized_to = None;
     let info_popovers = editor.hover_state.info_popovers.drain(..);
     let diagnostics_popover = editor.hover_state.diagnostic_popover.take();
     let did_hide = info_popovers.count() > 0 || diagnostics_popover.is_some();
@@ -226,6 +280,16 @@ pub fn hide_hover(editor: &mut Editor, cx: &mut Context<Editor>) -> bool {
     did_hide
 }
 
+/// Hides the type information popup even if it is pinned, moving the focus back to the editor
+/// if it was within the popup.
+pub fn dismiss_hover(editor: &mut Editor, window: &mut Window, cx: &mut Context<Editor>) -> bool {
+    if editor.hover_state.focused(window, cx) {
+        window.focus(&editor.focus_handle);
+    }
+    editor.hover_state.pinned = false;
+    hide_hover(editor, cx)
+}
+
 /// Queries the LSP and shows type info and documentation
 /// about the symbol the mouse is currently hovering over.
 /// Triggered by the `Hover` action when the cursor may be over a symbol.
@@ -437,8 +501,19 @@ fn show_hover(
             );
 
             if let Some((invisible, range)) = invisible_char {
+                let mut text = format!("Unicode character U+{:02X}", invisible as u32);
+                if let Some(name) = invisibles::name(invisible) {
+                    text.push(' ');
+                    text.push_str(name);
+                }
+                if invisibles::is_bidi_control(invisible) {
+                    text.push_str(
+                        "\n\nThis character changes the direction that the text around it is \
+                        displayed in, which can make code read differently than it runs.",
+                    );
+                }
                 let blocks = vec![HoverBlock {
-                    text: format!("Unicode character U+{:02X}", invisible as u32),
+                    text,
                     kind: HoverBlockKind::PlainText,
                 }];
                 let parsed_content =
@@ -756,6 +831,19 @@ pub struct HoverState {
     pub diagnostic_popover: Option<DiagnosticPopover>,
     pub triggered_from: Option<Anchor>,
     pub info_task: Option<Task<Option<()>>>,
+    /// Whether the popovers stay open until they are explicitly dismissed.
+    pub pinned: bool,
+    /// The size that the user resized the info popovers to, if any.
+    pub resized_to: Option<Size<Pixels>>,
+}
+
+/// The state of a drag on the resize handle of an info popover.
+struct ResizeHover;
+
+impl Render for ResizeHover {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        gpui::Empty
+    }
 }
 
 impl HoverState {
@@ -806,7 +894,7 @@ impl HoverState {
             elements.push(diagnostic_popover.render(max_size, window, cx));
         }
         for info_popover in &mut self.info_popovers {
-            elements.push(info_popover.render(max_size, window, cx));
+            elements.push(info_popover.render(max_size, self.pinned, self.resized_to, window, cx));
         }
 
         Some((point, elements))
@@ -846,12 +934,16 @@ impl InfoPopover {
     pub(crate) fn render(
         &mut self,
         max_size: Size<Pixels>,
+        pinned: bool,
+        resized_to: Option<Size<Pixels>>,
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) -> AnyElement {
         let keyboard_grace = Rc::clone(&self.keyboard_grace);
+        let pin_tooltip = if pinned { "Unpin Hover" } else { "Pin Hover" };
         div()
             .id("info_popover")
+            .relative()
             .occlude()
             .elevation_2(cx)
             // Prevent a mouse down/move on the popover from being propagated to the editor,
@@ -862,12 +954,29 @@ impl InfoPopover {
                 *keyboard_grace = false;
                 cx.stop_propagation();
             })
+            .on_drag_move(
+                cx.listener(|editor, event: &DragMoveEvent<ResizeHover>, _, cx| {
+                    // The content is inset by the popover's padding, which leaves room for the pin
+                    // button on the right.
+                    let content_size =
+                        event.event.position - event.bounds.origin - gpui::point(px(32.), px(16.));
+                    editor.hover_state.resized_to = Some(Size {
+                        width: content_size.x.max(MIN_RESIZED_POPOVER_SIZE.width),
+                        height: content_size.y.max(MIN_RESIZED_POPOVER_SIZE.height),
+                    });
+                    cx.notify();
+                }),
+            )
             .p_2()
+            .pr_6()
             .when_some(self.parsed_content.clone(), |this, markdown| {
                 this.child(
                     div()
                         .id("info-md-container")
                         .overflow_y_scroll()
+                        .when_some(resized_to, |this, resized_to| {
+                            this.w(resized_to.width).h(resized_to.height)
+                        })
                         .max_w(max_size.width)
                         .max_h(max_size.height)
                         .track_scroll(&self.scroll_handle)
@@ -888,6 +997,38 @@ impl InfoPopover {
                     cx,
                 )
             })
+            .child(
+                div().absolute().top_1().right_1().child(
+                    IconButton::new(
+                        "pin-hover",
+                        if pinned {
+                            IconName::Unpin
+                        } else {
+                            IconName::Pin
+                        },
+                    )
+                    .icon_size(IconSize::XSmall)
+                    .icon_color(Color::Muted)
+                    .toggle_state(pinned)
+                    .tooltip(Tooltip::text(pin_tooltip))
+                    .on_click(cx.listener(|editor, _, window, cx| {
+                        toggle_pinned_hover(editor, &TogglePinnedHover, window, cx)
+                    })),
+                ),
+            )
+            .child(
+                div()
+                    .id("resize-hover")
+                    .absolute()
+                    .bottom_0()
+                    .right_0()
+                    .size_2()
+                    .cursor(CursorStyle::ResizeUpLeftDownRight)
+                    .on_drag(ResizeHover, |_, _, _, cx| {
+                        cx.stop_propagation();
+                        cx.new(|_| ResizeHover)
+                    }),
+            )
             .into_any_element()
     }
 
@@ -1361,6 +1502,57 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_pinned_hover_info_popover(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                hover_provider: Some(lsp::HoverProviderCapability::Simple(true)),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state(indoc! {"
+            fˇn test() { println!(); }
+        "});
+        let symbol_range = cx.lsp_range(indoc! {"
+            «fn» test() { println!(); }
+        "});
+        cx.set_request_handler::<lsp::request::HoverRequest, _, _>(move |_, _, _| async move {
+            Ok(Some(lsp::Hover {
+                contents: lsp::HoverContents::Markup(lsp::MarkupContent {
+                    kind: lsp::MarkupKind::Markdown,
+                    value: "pinned docs".to_string(),
+                }),
+                range: Some(symbol_range),
+            }))
+        });
+        cx.dispatch_action(Hover);
+        cx.condition(|editor, _| editor.hover_state.visible()).await;
+
+        // Pinned popovers stay open while moving the cursor and typing.
+        cx.dispatch_action(TogglePinnedHover);
+        cx.simulate_keystrokes("end enter a b c");
+        cx.run_until_parked();
+        cx.editor(|editor, _, cx| {
+            assert!(editor.hover_state.visible());
+            assert_eq!(
+                editor.hover_state.info_popovers[0].get_rendered_text(cx),
+                "pinned docs"
+            );
+        });
+
+        // Dismissing the popover unpins it.
+        cx.dispatch_action(crate::actions::Cancel);
+        cx.editor(|editor, _, _| {
+            assert!(!editor.hover_state.visible());
+            assert!(!editor.hover_state.pinned);
+        });
+    }
+
     #[gpui::test]
     async fn test_empty_hovers_filtered(cx: &mut gpui::TestAppContext) {
         init_test(cx, |_| {});
@@ -0,0 +1,328 @@
+//! Incrementing and decrementing the numbers under the cursors, including hexadecimal and binary
+//! numbers, and optionally the components of dates and version numbers.
+use std::{ops::Range, sync::LazyLock};
+
+use chrono::{Days, Months, NaiveDate};
+use gpui::{Context, Window};
+use language::Point;
+use multi_buffer::{MultiBufferRow, ToOffset as _};
+use regex::Regex;
+
+use crate::{DecrementNumber, Editor, HideMouseCursorOrigin, IncrementNumber};
+
+static DATE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(\d{4})-(\d{2})-(\d{2})\b").unwrap());
+static VERSION_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bv?(\d+)\.(\d+)\.(\d+)\b").unwrap());
+static NUMBER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"0[xX][0-9a-fA-F]+|0[bB][01]+|\d+").unwrap());
+
+impl Editor {
+    pub fn increment_number(
+        &mut self,
+        action: &IncrementNumber,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let count = action.count.unwrap_or(1) as i64;
+        self.increment_numbers(
+            count,
+            action.sequential,
+            action.dates,
+            action.versions,
+            window,
+            cx,
+        );
+    }
+
+    pub fn decrement_number(
+        &mut self,
+        action: &DecrementNumber,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let count = action.count.unwrap_or(1) as i64;
+        self.increment_numbers(
+            -count,
+            action.sequential,
+            action.dates,
+            action.versions,
+            window,
+            cx,
+        );
+    }
+
+    /// Adds `delta` to the number under each cursor, or to the first number in each selection.
+    /// When `sequential` is true, the amount added grows by `delta` at each further cursor.
+    fn increment_numbers(
+        &mut self,
+        delta: i64,
+        sequential: bool,
+        dates: bool,
+        versions: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.read_only(cx) {
+            return;
+        }
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let selections = self.selections.all::<Point>(cx);
+        let mut edits = Vec::<(Range<Point>, String)>::new();
+        // The selections after the edits, with the range of each incremented number's selection
+        // relative to the start of the number.
+        let mut new_selections = Vec::with_capacity(selections.len());
+        let mut amount = delta;
+        for selection in selections {
+            let row = selection.start.row;
+            let line_len = snapshot.line_len(MultiBufferRow(row));
+            let line = snapshot
+                .text_for_range(Point::new(row, 0)..Point::new(row, line_len))
+                .collect::<String>();
+            let columns = if selection.end.row == row {
+                selection.start.column as usize..selection.end.column as usize
+            } else {
+                selection.start.column as usize..line_len as usize
+            };
+
+            let Some((range, text)) = increment_in_line(&line, columns, amount, dates, versions)
+            else {
+                let range =
+                    snapshot.anchor_before(selection.start)..snapshot.anchor_after(selection.end);
+                new_selections.push((range, None));
+                continue;
+            };
+            if sequential {
+                amount += delta;
+            }
+            let start = Point::new(row, range.start as u32);
+            let end = Point::new(row, range.end as u32);
+            let relative_range = if selection.is_empty() {
+                let cursor = (selection.head().column - start.column).min(text.len() as u32);
+                cursor as usize..cursor as usize
+            } else {
+                0..text.len()
+            };
+            let anchor = snapshot.anchor_before(start);
+            new_selections.push((anchor..anchor, Some(relative_range)));
+            if edits
+                .last()
+                .is_none_or(|(last_range, _)| last_range.end <= start)
+            {
+                edits.push((start..end, text));
+            }
+        }
+        if edits.is_empty() {
+            return;
+        }
+
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer
+                .update(cx, |buffer, cx| buffer.edit(edits, None, cx));
+            let snapshot = this.buffer.read(cx).snapshot(cx);
+            let ranges = new_selections
+                .into_iter()
+                .map(|(range, relative_range)| match relative_range {
+                    Some(relative_range) => {
+                        let start = range.start.to_offset(&snapshot);
+                        start + relative_range.start..start + relative_range.end
+                    }
+                    None => range.start.to_offset(&snapshot)..range.end.to_offset(&snapshot),
+                })
+                .collect::<Vec<_>>();
+            this.change_selections(Default::default(), window, cx, |s| {
+                s.select_ranges(ranges);
+            });
+        });
+    }
+}
+
+/// Finds the number, date or version number in `line` under the cursor at `columns.start`, or
+/// the first one starting within `columns` if they aren't empty, and returns its byte range with
+/// the text that replaces it after adding `delta`.
+///
+/// Dates and version numbers are only recognized when `dates` or `versions` is true. Their
+/// component under the cursor is incremented: dates stay valid, and the components of a version
+/// number after the incremented one are reset to zero.
+fn increment_in_line(
+    line: &str,
+    columns: Range<usize>,
+    delta: i64,
+    dates: bool,
+    versions: bool,
+) -> Option<(Range<usize>, String)> {
+    let is_under_cursor = |range: &Range<usize>| {
+        if columns.is_empty() {
+            range.start <= columns.start && columns.start <= range.end
+        } else {
+            columns.contains(&range.start)
+        }
+    };
+
+    if dates {
+        for captures in DATE_REGEX.captures_iter(line) {
+            let range = captures.get(0)?.range();
+            if !is_under_cursor(&range) {
+                continue;
+            }
+            let date = NaiveDate::parse_from_str(&line[range.clone()], "%Y-%m-%d").ok()?;
+            let date = match component_under_cursor(&captures, columns.start) {
+                1 => add_months(date, delta.checked_mul(12)?),
+                2 => add_months(date, delta),
+                _ => {
+                    if delta < 0 {
+                        date.checked_sub_days(Days::new(delta.unsigned_abs()))
+                    } else {
+                        date.checked_add_days(Days::new(delta as u64))
+                    }
+                }
+            }?;
+            return Some((range, date.format("%Y-%m-%d").to_string()));
+        }
+    }
+
+    if versions {
+        for captures in VERSION_REGEX.captures_iter(line) {
+            let range = captures.get(0)?.range();
+            if !is_under_cursor(&range) {
+                continue;
+            }
+            let component = component_under_cursor(&captures, columns.start);
+            let mut text = line[range.start..captures.get(1)?.start()].to_string();
+            for ix in 1..=3 {
+                let value = captures.get(ix)?.as_str().parse::<u64>().ok()?;
+                let value = match ix.cmp(&component) {
+                    std::cmp::Ordering::Less => value,
+                    std::cmp::Ordering::Equal => value.saturating_add_signed(delta),
+                    std::cmp::Ordering::Greater => 0,
+                };
+                if ix > 1 {
+                    text.push('.');
+                }
+                text.push_str(&value.to_string());
+            }
+            return Some((range, text));
+        }
+    }
+
+    for number in NUMBER_REGEX.find_iter(line) {
+        let mut range = number.range();
+        let digits = number.as_str();
+        // A minus sign makes a decimal number negative, unless it follows a word, as in `a-1`.
+        let is_negative = digits.chars().all(|ch| ch.is_ascii_digit())
+            && line[..range.start].ends_with('-')
+            && !line[..range.start - 1]
+                .chars()
+                .next_back()
+                .is_some_and(|ch| ch.is_alphanumeric() || ch == '_');
+        if is_negative {
+            range.start -= 1;
+        }
+        if !is_under_cursor(&range) {
+            continue;
+        }
+
+        let text = if let Some(hex) = digits
+            .strip_prefix("0x")
+            .or_else(|| digits.strip_prefix("0X"))
+        {
+            let value = u64::from_str_radix(hex, 16)
+                .ok()?
+                .wrapping_add_signed(delta);
+            let hex = if hex.chars().any(|ch| ch.is_ascii_uppercase()) {
+                format!("{value:0width$X}", width = hex.len())
+            } else {
+                format!("{value:0width$x}", width = hex.len())
+            };
+            format!("{}{hex}", &digits[..2])
+        } else if let Some(binary) = digits
+            .strip_prefix("0b")
+            .or_else(|| digits.strip_prefix("0B"))
+        {
+            let value = u64::from_str_radix(binary, 2)
+                .ok()?
+                .wrapping_add_signed(delta);
+            format!("{}{value:0width$b}", &digits[..2], width = binary.len())
+        } else {
+            let value = line[range.clone()]
+                .parse::<i64>()
+                .ok()?
+                .saturating_add(delta);
+            // Keep zero padding, as in `007`.
+            let width = if digits.len() > 1 && digits.starts_with('0') {
+                digits.len()
+            } else {
+                0
+            };
+            if value < 0 {
+                format!("-{:0width$}", value.unsigned_abs())
+            } else {
+                format!("{value:0width$}")
+            }
+        };
+        return Some((range, text));
+    }
+    None
+}
+
+/// Returns the index of the capture group that contains `column`, or the first one after it.
+fn component_under_cursor(captures: &regex::Captures, column: usize) -> usize {
+    (1..captures.len())
+        .find(|&ix| captures.get(ix).is_some_and(|group| column <= group.end()))
+        .unwrap_or(captures.len() - 1)
+}
+
+fn add_months(date: NaiveDate, months: i64) -> Option<NaiveDate> {
+    let abs_months = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+    if months < 0 {
+        date.checked_sub_months(abs_months)
+    } else {
+        date.checked_add_months(abs_months)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn increment(line: &str, column: usize, delta: i64) -> Option<String> {
+        let (range, text) = increment_in_line(line, column..column, delta, true, true)?;
+        Some(format!(
+            "{}{text}{}",
+            &line[..range.start],
+            &line[range.end..]
+        ))
+    }
+
+    #[test]
+    fn test_increment_in_line() {
+        assert_eq!(increment("x = 9;", 4, 1).as_deref(), Some("x = 10;"));
+        assert_eq!(increment("x = 9;", 5, 1).as_deref(), Some("x = 10;"));
+        assert_eq!(increment("x = 9;", 0, 1), None);
+        assert_eq!(increment("x = -1;", 6, 3).as_deref(), Some("x = 2;"));
+        assert_eq!(increment("a-1", 2, 1).as_deref(), Some("a-2"));
+        assert_eq!(increment("007", 1, 3).as_deref(), Some("010"));
+        assert_eq!(increment("0xfF", 2, 1).as_deref(), Some("0x100"));
+        assert_eq!(increment("0x0f", 2, 1).as_deref(), Some("0x10"));
+        assert_eq!(increment("0b0111", 3, 1).as_deref(), Some("0b1000"));
+
+        assert_eq!(
+            increment("on 2024-01-31.", 12, 1).as_deref(),
+            Some("on 2024-02-01.")
+        );
+        assert_eq!(
+            increment("on 2024-01-31.", 8, 1).as_deref(),
+            Some("on 2024-02-29.")
+        );
+        assert_eq!(
+            increment("on 2024-02-29.", 3, -1).as_deref(),
+            Some("on 2023-02-28.")
+        );
+
+        assert_eq!(increment("v1.2.3", 3, 1).as_deref(), Some("v1.3.0"));
+        assert_eq!(increment("v1.2.3", 0, 1).as_deref(), Some("v2.0.0"));
+        assert_eq!(increment("v1.2.3", 5, -4).as_deref(), Some("v1.2.0"));
+    }
+}
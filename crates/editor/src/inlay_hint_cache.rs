@@ -36,9 +36,11 @@ use util::{ResultExt, post_inc};
 pub struct InlayHintCache {
     hints: HashMap<ExcerptId, Arc<RwLock<CachedExcerptHints>>>,
     allowed_hint_kinds: HashSet<Option<InlayHintKind>>,
+    allowed_hint_kinds_in_settings: HashSet<Option<InlayHintKind>>,
     version: usize,
     pub(super) enabled: bool,
     modifiers_override: bool,
+    toggle_on_modifiers_press: bool,
     enabled_in_settings: bool,
     update_tasks: HashMap<ExcerptId, TasksForRanges>,
     refresh_task: Task<()>,
@@ -47,6 +49,18 @@ pub struct InlayHintCache {
     lsp_request_limiter: Arc<Semaphore>,
 }
 
+/// Whether the hints are queried for and displayed.
+///
+/// Hints are displayed when they are enabled, unless the `toggle_on_modifiers_press` modifiers are held, which inverts that.
+/// When those modifiers are configured, hidden hints are still queried for and kept in the cache,
+/// so that pressing and releasing the modifiers only splices the cached hints in and out of the inlay map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HintsState {
+    Disabled,
+    Hidden,
+    Shown,
+}
+
 #[derive(Debug)]
 struct TasksForRanges {
     tasks: Vec<Task<()>>,
@@ -267,8 +281,12 @@ impl InlayHintCache {
     pub(super) fn new(inlay_hint_settings: InlayHintSettings) -> Self {
         Self {
             allowed_hint_kinds: inlay_hint_settings.enabled_inlay_hint_kinds(),
+            allowed_hint_kinds_in_settings: inlay_hint_settings.enabled_inlay_hint_kinds(),
             enabled: inlay_hint_settings.enabled,
             modifiers_override: false,
+            toggle_on_modifiers_press: inlay_hint_settings
+                .toggle_on_modifiers_press
+                .is_some_and(|modifiers| modifiers.modified()),
             enabled_in_settings: inlay_hint_settings.enabled,
             hints: HashMap::default(),
             update_tasks: HashMap::default(),
@@ -280,6 +298,25 @@ impl InlayHintCache {
         }
     }
 
+    fn state(&self) -> HintsState {
+        if self.enabled != self.modifiers_override {
+            HintsState::Shown
+        } else if self.toggle_on_modifiers_press {
+            HintsState::Hidden
+        } else {
+            HintsState::Disabled
+        }
+    }
+
+    /// Whether the hints from the cache are currently displayed in the inlay map.
+    pub(super) fn displayed(&self) -> bool {
+        self.state() == HintsState::Shown
+    }
+
+    pub(super) fn hint_kind_allowed(&self, kind: Option<InlayHintKind>) -> bool {
+        self.allowed_hint_kinds.contains(&kind)
+    }
+
     /// Checks inlay hint settings for enabled hint kinds and general enabled state.
     /// Generates corresponding inlay_map splice updates on settings changes.
     /// Does not update inlay hint cache state on disabling or inlay hint kinds change: only reenabling forces new LSP queries.
@@ -290,7 +327,7 @@ impl InlayHintCache {
         visible_hints: Vec<Inlay>,
         cx: &mut Context<Editor>,
     ) -> ControlFlow<Option<InlaySplice>> {
-        let old_enabled = self.enabled;
+        let old_state = self.state();
         // If the setting for inlay hints has changed, update `enabled`. This condition avoids inlay
         // hint visibility changes when other settings change (such as theme).
         //
@@ -302,15 +339,22 @@ impl InlayHintCache {
             self.enabled_in_settings = new_hint_settings.enabled;
             self.modifiers_override = false;
         };
+        self.toggle_on_modifiers_press = new_hint_settings
+            .toggle_on_modifiers_press
+            .is_some_and(|modifiers| modifiers.modified());
         self.invalidate_debounce = debounce_value(new_hint_settings.edit_debounce_ms);
         self.append_debounce = debounce_value(new_hint_settings.scroll_debounce_ms);
-        let new_allowed_hint_kinds = new_hint_settings.enabled_inlay_hint_kinds();
-        match (old_enabled, self.enabled) {
-            (false, false) => {
-                self.allowed_hint_kinds = new_allowed_hint_kinds;
-                ControlFlow::Break(None)
-            }
-            (true, true) => {
+        // Same as with `enabled`, hint kinds toggled in the editor are kept until the kinds in the settings change.
+        let hint_kinds_in_settings = new_hint_settings.enabled_inlay_hint_kinds();
+        let new_allowed_hint_kinds =
+            if hint_kinds_in_settings != self.allowed_hint_kinds_in_settings {
+                self.allowed_hint_kinds_in_settings = hint_kinds_in_settings.clone();
+                hint_kinds_in_settings
+            } else {
+                self.allowed_hint_kinds.clone()
+            };
+        match (old_state, self.state()) {
+            (HintsState::Shown, HintsState::Shown) => {
                 if new_allowed_hint_kinds == self.allowed_hint_kinds {
                     ControlFlow::Break(None)
                 } else {
@@ -327,51 +371,129 @@ impl InlayHintCache {
                     ControlFlow::Break(new_splice)
                 }
             }
-            (true, false) => {
-                self.modifiers_override = false;
+            (old_state, _) => {
                 self.allowed_hint_kinds = new_allowed_hint_kinds;
-                if self.hints.is_empty() {
-                    ControlFlow::Break(None)
-                } else {
-                    self.clear();
-                    ControlFlow::Break(Some(InlaySplice {
-                        to_remove: visible_hints.iter().map(|inlay| inlay.id).collect(),
-                        to_insert: Vec::new(),
-                    }))
-                }
-            }
-            (false, true) => {
-                self.modifiers_override = false;
-                self.allowed_hint_kinds = new_allowed_hint_kinds;
-                ControlFlow::Continue(())
+                self.switch_state(old_state, multi_buffer, &visible_hints, cx)
             }
         }
     }
 
-    pub(super) fn modifiers_override(&mut self, new_override: bool) -> Option<bool> {
+    pub(super) fn modifiers_override(
+        &mut self,
+        new_override: bool,
+        multi_buffer: &Entity<MultiBuffer>,
+        visible_hints: Vec<Inlay>,
+        cx: &mut Context<Editor>,
+    ) -> ControlFlow<Option<InlaySplice>> {
         if self.modifiers_override == new_override {
-            return None;
+            return ControlFlow::Break(None);
         }
+        let old_state = self.state();
         self.modifiers_override = new_override;
-        if (self.enabled && self.modifiers_override) || (!self.enabled && !self.modifiers_override)
-        {
-            self.clear();
-            Some(false)
-        } else {
-            Some(true)
-        }
+        self.switch_state(old_state, multi_buffer, &visible_hints, cx)
     }
 
-    pub(super) fn toggle(&mut self, enabled: bool) -> bool {
+    pub(super) fn toggle(
+        &mut self,
+        enabled: bool,
+        multi_buffer: &Entity<MultiBuffer>,
+        visible_hints: Vec<Inlay>,
+        cx: &mut Context<Editor>,
+    ) -> ControlFlow<Option<InlaySplice>> {
         if self.enabled == enabled {
-            return false;
+            return ControlFlow::Break(None);
         }
+        let old_state = self.state();
         self.enabled = enabled;
         self.modifiers_override = false;
-        if !enabled {
-            self.clear();
+        self.switch_state(old_state, multi_buffer, &visible_hints, cx)
+    }
+
+    /// Shows or hides the hints of a certain kind, reusing the cached hints instead of querying for new ones.
+    pub(super) fn toggle_hint_kind(
+        &mut self,
+        kind: Option<InlayHintKind>,
+        multi_buffer: &Entity<MultiBuffer>,
+        visible_hints: Vec<Inlay>,
+        cx: &mut Context<Editor>,
+    ) -> Option<InlaySplice> {
+        let mut new_allowed_hint_kinds = self.allowed_hint_kinds.clone();
+        if !new_allowed_hint_kinds.remove(&kind) {
+            new_allowed_hint_kinds.insert(kind);
+        }
+        let new_splice = if self.displayed() {
+            self.new_allowed_hint_kinds_splice(
+                multi_buffer,
+                &visible_hints,
+                &new_allowed_hint_kinds,
+                cx,
+            )
+        } else {
+            None
+        };
+        if new_splice.is_some() {
+            self.version += 1;
+        }
+        self.allowed_hint_kinds = new_allowed_hint_kinds;
+        new_splice
+    }
+
+    /// Generates the inlay_map splice for the transition from `old_state` to the current state.
+    /// Only leaving the disabled state requires new LSP queries, signalled with [`ControlFlow::Continue`].
+    fn switch_state(
+        &mut self,
+        old_state: HintsState,
+        multi_buffer: &Entity<MultiBuffer>,
+        visible_hints: &[Inlay],
+        cx: &mut Context<Editor>,
+    ) -> ControlFlow<Option<InlaySplice>> {
+        match (old_state, self.state()) {
+            (HintsState::Disabled, HintsState::Disabled)
+            | (HintsState::Hidden, HintsState::Hidden)
+            | (HintsState::Shown, HintsState::Shown) => ControlFlow::Break(None),
+            (HintsState::Disabled, HintsState::Hidden | HintsState::Shown) => {
+                ControlFlow::Continue(())
+            }
+            (HintsState::Hidden | HintsState::Shown, HintsState::Disabled) => {
+                self.clear();
+                ControlFlow::Break(hide_splice(visible_hints))
+            }
+            (HintsState::Shown, HintsState::Hidden) => {
+                ControlFlow::Break(hide_splice(visible_hints))
+            }
+            (HintsState::Hidden, HintsState::Shown) => {
+                ControlFlow::Break(self.cached_hints_splice(multi_buffer, cx))
+            }
+        }
+    }
+
+    fn cached_hints_splice(
+        &self,
+        multi_buffer: &Entity<MultiBuffer>,
+        cx: &mut Context<Editor>,
+    ) -> Option<InlaySplice> {
+        let multi_buffer_snapshot = multi_buffer.read(cx).snapshot(cx);
+        let mut to_insert = Vec::new();
+        for (excerpt_id, excerpt_cached_hints) in &self.hints {
+            let excerpt_cached_hints = excerpt_cached_hints.read();
+            for cached_hint_id in &excerpt_cached_hints.ordered_hints {
+                let cached_hint = &excerpt_cached_hints.hints_by_id[cached_hint_id];
+                if self.allowed_hint_kinds.contains(&cached_hint.kind)
+                    && let Some(anchor) =
+                        multi_buffer_snapshot.anchor_in_excerpt(*excerpt_id, cached_hint.position)
+                {
+                    to_insert.push(Inlay::hint(cached_hint_id.id(), anchor, cached_hint));
+                }
+            }
+        }
+        if to_insert.is_empty() {
+            None
+        } else {
+            Some(InlaySplice {
+                to_remove: Vec::new(),
+                to_insert,
+            })
         }
-        true
     }
 
     /// If needed, queries LSP for new inlay hints, using the invalidation strategy given.
@@ -386,8 +508,7 @@ impl InlayHintCache {
         ignore_debounce: bool,
         cx: &mut Context<Editor>,
     ) -> Option<InlaySplice> {
-        if (self.enabled && self.modifiers_override) || (!self.enabled && !self.modifiers_override)
-        {
+        if self.state() == HintsState::Disabled {
             return None;
         }
         let mut invalidated_hints = Vec::new();
@@ -660,6 +781,17 @@ impl InlayHintCache {
     }
 }
 
+fn hide_splice(visible_hints: &[Inlay]) -> Option<InlaySplice> {
+    if visible_hints.is_empty() {
+        None
+    } else {
+        Some(InlaySplice {
+            to_remove: visible_hints.iter().map(|inlay| inlay.id).collect(),
+            to_insert: Vec::new(),
+        })
+    }
+}
+
 fn debounce_value(debounce_ms: u64) -> Option<Duration> {
     if debounce_ms > 0 {
         Some(Duration::from_millis(debounce_ms))
@@ -1232,10 +1364,11 @@ fn apply_hint_update(
         };
 
         let new_inlay_id = post_inc(&mut editor.next_inlay_id);
-        if editor
-            .inlay_hint_cache
-            .allowed_hint_kinds
-            .contains(&new_hint.kind)
+        if editor.inlay_hint_cache.displayed()
+            && editor
+                .inlay_hint_cache
+                .allowed_hint_kinds
+                .contains(&new_hint.kind)
             && let Some(new_hint_position) =
                 multi_buffer_snapshot.anchor_in_excerpt(query.excerpt_id, new_hint.position)
         {
@@ -3317,6 +3450,156 @@ pub mod tests {
         }).unwrap();
     }
 
+    #[gpui::test]
+    async fn test_show_hints_while_modifiers_held(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |settings| {
+            settings.defaults.inlay_hints = Some(InlayHintSettingsContent {
+                show_value_hints: Some(true),
+                enabled: Some(false),
+                edit_debounce_ms: Some(0),
+                scroll_debounce_ms: Some(0),
+                show_type_hints: Some(true),
+                show_parameter_hints: Some(true),
+                show_other_hints: Some(true),
+                show_background: Some(false),
+                toggle_on_modifiers_press: Some(gpui::Modifiers {
+                    control: true,
+                    ..gpui::Modifiers::default()
+                }),
+            })
+        });
+
+        let lsp_request_count = Arc::new(AtomicUsize::new(0));
+        let (_, editor, _fake_server) = prepare_test_objects(cx, {
+            let lsp_request_count = lsp_request_count.clone();
+            move |fake_server, _| {
+                let lsp_request_count = lsp_request_count.clone();
+                fake_server.set_request_handler::<lsp::request::InlayHintRequest, _, _>(
+                    move |_, _| {
+                        lsp_request_count.fetch_add(1, Ordering::Release);
+                        async move {
+                            Ok(Some(vec![
+                                lsp::InlayHint {
+                                    position: lsp::Position::new(0, 1),
+                                    label: lsp::InlayHintLabel::String("type hint".to_string()),
+                                    kind: Some(lsp::InlayHintKind::TYPE),
+                                    text_edits: None,
+                                    tooltip: None,
+                                    padding_left: None,
+                                    padding_right: None,
+                                    data: None,
+                                },
+                                lsp::InlayHint {
+                                    position: lsp::Position::new(0, 2),
+                                    label: lsp::InlayHintLabel::String(
+                                        "parameter hint".to_string(),
+                                    ),
+                                    kind: Some(lsp::InlayHintKind::PARAMETER),
+                                    text_edits: None,
+                                    tooltip: None,
+                                    padding_left: None,
+                                    padding_right: None,
+                                    data: None,
+                                },
+                            ]))
+                        }
+                    },
+                );
+            }
+        })
+        .await;
+        cx.executor().run_until_parked();
+
+        let all_hints = vec!["type hint".to_string(), "parameter hint".to_string()];
+        editor
+            .update(cx, |editor, _, cx| {
+                assert_eq!(lsp_request_count.load(Ordering::Relaxed), 1);
+                assert_eq!(
+                    all_hints,
+                    cached_hint_labels(editor),
+                    "Should query hints in advance when they can be shown by holding modifiers"
+                );
+                assert!(visible_hint_labels(editor, cx).is_empty());
+            })
+            .unwrap();
+
+        editor
+            .update(cx, |editor, _, cx| {
+                editor
+                    .refresh_inlay_hints(crate::InlayHintRefreshReason::ModifiersChanged(true), cx);
+            })
+            .unwrap();
+        cx.executor().run_until_parked();
+        editor
+            .update(cx, |editor, _, cx| {
+                assert_eq!(
+                    lsp_request_count.load(Ordering::Relaxed),
+                    1,
+                    "Should show cached hints without querying when modifiers get held"
+                );
+                assert_eq!(all_hints, visible_hint_labels(editor, cx));
+            })
+            .unwrap();
+
+        editor
+            .update(cx, |editor, window, cx| {
+                editor.toggle_type_inlay_hints(&crate::ToggleTypeInlayHints, window, cx);
+            })
+            .unwrap();
+        cx.executor().run_until_parked();
+        editor
+            .update(cx, |editor, _, cx| {
+                assert_eq!(lsp_request_count.load(Ordering::Relaxed), 1);
+                assert_eq!(all_hints, cached_hint_labels(editor));
+                assert_eq!(
+                    vec!["parameter hint".to_string()],
+                    visible_hint_labels(editor, cx),
+                    "Should hide type hints after toggling them off"
+                );
+                assert!(!editor.inlay_hint_kind_enabled(Some(InlayHintKind::Type)));
+            })
+            .unwrap();
+
+        editor
+            .update(cx, |editor, _, cx| {
+                editor.refresh_inlay_hints(
+                    crate::InlayHintRefreshReason::ModifiersChanged(false),
+                    cx,
+                );
+            })
+            .unwrap();
+        cx.executor().run_until_parked();
+        editor
+            .update(cx, |editor, _, cx| {
+                assert_eq!(lsp_request_count.load(Ordering::Relaxed), 1);
+                assert_eq!(
+                    all_hints,
+                    cached_hint_labels(editor),
+                    "Should keep hints cached after releasing modifiers"
+                );
+                assert!(visible_hint_labels(editor, cx).is_empty());
+            })
+            .unwrap();
+
+        editor
+            .update(cx, |editor, _, cx| {
+                editor
+                    .refresh_inlay_hints(crate::InlayHintRefreshReason::ModifiersChanged(true), cx);
+            })
+            .unwrap();
+        cx.executor().run_until_parked();
+        editor
+            .update(cx, |editor, _, cx| {
+                assert_eq!(lsp_request_count.load(Ordering::Relaxed), 1);
+                assert_eq!(
+                    vec!["parameter hint".to_string()],
+                    visible_hint_labels(editor, cx),
+                    "Should keep toggled off hint kinds hidden when modifiers are held again"
+                );
+            })
+            .unwrap();
+    }
+
     #[gpui::test]
     async fn test_inlays_at_the_same_place(cx: &mut gpui::TestAppContext) {
         init_test(cx, |settings| {
@@ -1,10 +1,12 @@
 use crate::{
     Anchor, Autoscroll, Editor, EditorEvent, EditorSettings, ExcerptId, ExcerptRange, FormatTarget,
-    MultiBuffer, MultiBufferSnapshot, NavigationData, ReportEditorEvent, SearchWithinRange,
+    MultiBuffer, MultiBufferSnapshot, NavigationData, PINNED_WORD_HIGHLIGHT_COLORS,
+    PinnedWordHighlight, ReportEditorEvent, SearchOutsideRange, SearchWithinRange,
     SelectionEffects, ToPoint as _,
     display_map::HighlightKey,
     editor_settings::SeedQuerySetting,
-    persistence::{DB, SerializedEditor},
+    highlight_matching_bracket::MatchingBracketHighlight,
+    persistence::{DB, SerializedEditor, SerializedFileState},
     scroll::{ScrollAnchor, ScrollOffset},
 };
 use anyhow::{Context as _, Result, anyhow};
@@ -13,8 +15,8 @@ use file_icons::FileIcons;
 use futures::future::try_join_all;
 use git::status::GitSummary;
 use gpui::{
-    AnyElement, App, AsyncWindowContext, Context, Entity, EntityId, EventEmitter, IntoElement,
-    ParentElement, Pixels, SharedString, Styled, Task, WeakEntity, Window, point,
+    AnyElement, App, AsyncWindowContext, Context, Entity, EntityId, EventEmitter, HighlightStyle,
+    IntoElement, ParentElement, Pixels, SharedString, Styled, Task, WeakEntity, Window, point,
 };
 use language::{
     Bias, Buffer, BufferRow, CharKind, CharScopeContext, DiskState, LocalFile, Point,
@@ -53,7 +55,7 @@ use workspace::{
     item::{Dedup, ItemSettings, SerializableItem, TabContentParams},
 };
 use workspace::{
-    OpenVisible, Pane, WorkspaceSettings,
+    OpenVisible, Pane, SERIALIZATION_THROTTLE_TIME, WorkspaceSettings,
     item::{BreadcrumbText, FollowEvent, ProjectItemKind},
     searchable::SearchOptions,
 };
@@ -165,6 +167,7 @@ impl FollowableItem for Editor {
                     scroll_top_anchor: state.scroll_top_anchor,
                     scroll_x: state.scroll_x,
                     scroll_y: state.scroll_y,
+                    highlights: state.highlights,
                     ..Default::default()
                 },
                 cx,
@@ -186,7 +189,10 @@ impl FollowableItem for Editor {
             self.buffer.update(cx, |buffer, cx| {
                 buffer.remove_active_selections(cx);
             });
-        } else if self.focus_handle.is_focused(window) {
+        } else {
+            self.clear_leader_highlights(cx);
+        }
+        if self.leader_id.is_none() && self.focus_handle.is_focused(window) {
             self.buffer.update(cx, |buffer, cx| {
                 buffer.set_active_selections(
                     &self.selections.disjoint_anchors_arc(),
@@ -242,6 +248,7 @@ impl FollowableItem for Editor {
                 .pending_anchor()
                 .as_ref()
                 .map(|s| serialize_selection(s, &snapshot)),
+            highlights: Some(serialize_highlights(self, &snapshot, cx)),
         }))
     }
 
@@ -323,6 +330,11 @@ impl FollowableItem for Editor {
                         .map(|s| serialize_selection(s, &snapshot));
                     true
                 }
+                EditorEvent::TransientHighlightsChanged => {
+                    let snapshot = self.buffer.read(cx).snapshot(cx);
+                    update.highlights = Some(serialize_highlights(self, &snapshot, cx));
+                    true
+                }
                 _ => false,
             },
         }
@@ -455,21 +467,25 @@ async fn update_editor_from_message(
     })??;
 
     // Deserialize the editor state.
-    let (selections, pending_selection, scroll_top_anchor) = this.update(cx, |editor, cx| {
-        let buffer = editor.buffer.read(cx).read(cx);
-        let selections = message
-            .selections
-            .into_iter()
-            .filter_map(|selection| deserialize_selection(&buffer, selection))
-            .collect::<Vec<_>>();
-        let pending_selection = message
-            .pending_selection
-            .and_then(|selection| deserialize_selection(&buffer, selection));
-        let scroll_top_anchor = message
-            .scroll_top_anchor
-            .and_then(|anchor| deserialize_anchor(&buffer, anchor));
-        anyhow::Ok((selections, pending_selection, scroll_top_anchor))
-    })??;
+    let (selections, pending_selection, scroll_top_anchor, highlights) =
+        this.update(cx, |editor, cx| {
+            let buffer = editor.buffer.read(cx).read(cx);
+            let selections = message
+                .selections
+                .into_iter()
+                .filter_map(|selection| deserialize_selection(&buffer, selection))
+                .collect::<Vec<_>>();
+            let pending_selection = message
+                .pending_selection
+                .and_then(|selection| deserialize_selection(&buffer, selection));
+            let scroll_top_anchor = message
+                .scroll_top_anchor
+                .and_then(|anchor| deserialize_anchor(&buffer, anchor));
+            let highlights = message
+                .highlights
+                .map(|highlights| deserialize_highlights(&buffer, highlights));
+            anyhow::Ok((selections, pending_selection, scroll_top_anchor, highlights))
+        })??;
 
     // Wait until the buffer has received all of the operations referenced by
     // the editor's new state.
@@ -480,7 +496,8 @@ async fn update_editor_from_message(
                     .iter()
                     .chain(pending_selection.as_ref())
                     .flat_map(|selection| [selection.start, selection.end])
-                    .chain(scroll_top_anchor),
+                    .chain(scroll_top_anchor)
+                    .chain(highlights.iter().flat_map(LeaderHighlights::anchors)),
                 cx,
             )
         })
@@ -489,6 +506,9 @@ async fn update_editor_from_message(
 
     // Update the editor's state.
     this.update_in(cx, |editor, window, cx| {
+        if let Some(highlights) = highlights {
+            editor.set_leader_highlights(highlights, cx);
+        }
         if !selections.is_empty() || pending_selection.is_some() {
             editor.set_selections_from_remote(selections, pending_selection, window, cx);
             editor.request_autoscroll_remotely(Autoscroll::newest(), cx);
@@ -506,6 +526,135 @@ async fn update_editor_from_message(
     Ok(())
 }
 
+/// Highlights that a followed editor shares with its followers, which show them in addition to
+/// the highlights they make themselves.
+struct LeaderHighlights {
+    search_matches: Vec<Range<Anchor>>,
+    matching_brackets: Vec<Range<Anchor>>,
+    pinned_words: Vec<(usize, Vec<Range<Anchor>>)>,
+}
+
+enum LeaderSearchHighlights {}
+enum LeaderMatchingBracketHighlight {}
+enum LeaderPinnedWordHighlight {}
+
+impl LeaderHighlights {
+    fn anchors(&self) -> impl Iterator<Item = Anchor> + '_ {
+        self.search_matches
+            .iter()
+            .chain(&self.matching_brackets)
+            .chain(self.pinned_words.iter().flat_map(|(_, ranges)| ranges))
+            .flat_map(|range| [range.start, range.end])
+    }
+}
+
+impl Editor {
+    fn set_leader_highlights(&mut self, highlights: LeaderHighlights, cx: &mut Context<Self>) {
+        self.clear_leader_highlights(cx);
+        self.highlight_background::<LeaderSearchHighlights>(
+            &highlights.search_matches,
+            |theme| theme.colors().search_match_background,
+            cx,
+        );
+        self.highlight_text::<LeaderMatchingBracketHighlight>(
+            highlights.matching_brackets,
+            HighlightStyle {
+                background_color: Some(
+                    cx.theme()
+                        .colors()
+                        .editor_document_highlight_bracket_background,
+                ),
+                ..Default::default()
+            },
+            cx,
+        );
+        for (key, (color_index, ranges)) in highlights.pinned_words.into_iter().enumerate() {
+            if let Some(color) = PINNED_WORD_HIGHLIGHT_COLORS.get(color_index) {
+                self.highlight_background_key::<LeaderPinnedWordHighlight>(
+                    key, &ranges, *color, cx,
+                );
+            }
+        }
+    }
+
+    fn clear_leader_highlights(&mut self, cx: &mut Context<Self>) {
+        self.clear_background_highlights::<LeaderSearchHighlights>(cx);
+        self.clear_highlights::<LeaderMatchingBracketHighlight>(cx);
+        self.background_highlights.retain(|key, _| {
+            !matches!(key, HighlightKey::TypePlus(type_id, _) if *type_id == TypeId::of::<LeaderPinnedWordHighlight>())
+        });
+        cx.notify();
+    }
+}
+
+fn serialize_highlights(
+    editor: &Editor,
+    buffer: &MultiBufferSnapshot,
+    cx: &App,
+) -> proto::EditorHighlights {
+    let serialize_ranges = |ranges: &[Range<Anchor>]| {
+        ranges
+            .iter()
+            .map(|range| proto::EditorAnchorRange {
+                start: Some(serialize_anchor(&range.start, buffer)),
+                end: Some(serialize_anchor(&range.end, buffer)),
+            })
+            .collect::<Vec<_>>()
+    };
+    let background_ranges = |key: HighlightKey| {
+        editor
+            .background_highlights
+            .get(&key)
+            .map_or(Vec::new(), |(_, ranges)| serialize_ranges(ranges))
+    };
+
+    proto::EditorHighlights {
+        search_matches: background_ranges(HighlightKey::Type(
+            TypeId::of::<BufferSearchHighlights>(),
+        )),
+        matching_brackets: editor
+            .text_highlights::<MatchingBracketHighlight>(cx)
+            .map_or(Vec::new(), |(_, ranges)| serialize_ranges(ranges)),
+        pinned_words: editor
+            .pinned_words
+            .iter()
+            .map(|word| proto::editor_highlights::PinnedWordHighlight {
+                color_index: word.color_index as u32,
+                ranges: background_ranges(HighlightKey::TypePlus(
+                    TypeId::of::<PinnedWordHighlight>(),
+                    word.id,
+                )),
+            })
+            .collect(),
+    }
+}
+
+fn deserialize_highlights(
+    buffer: &MultiBufferSnapshot,
+    highlights: proto::EditorHighlights,
+) -> LeaderHighlights {
+    let deserialize_ranges = |ranges: Vec<proto::EditorAnchorRange>| {
+        ranges
+            .into_iter()
+            .filter_map(|range| {
+                Some(
+                    deserialize_anchor(buffer, range.start?)?
+                        ..deserialize_anchor(buffer, range.end?)?,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    LeaderHighlights {
+        search_matches: deserialize_ranges(highlights.search_matches),
+        matching_brackets: deserialize_ranges(highlights.matching_brackets),
+        pinned_words: highlights
+            .pinned_words
+            .into_iter()
+            .map(|word| (word.color_index as usize, deserialize_ranges(word.ranges)))
+            .collect(),
+    }
+}
+
 fn serialize_excerpt(
     buffer_id: BufferId,
     id: &ExcerptId,
@@ -629,6 +778,10 @@ impl Item for Editor {
         }
     }
 
+    fn push_jump_location(&mut self, cx: &mut Context<Self>) {
+        Editor::push_jump_location(self, cx);
+    }
+
     fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
         let file_path = self
             .buffer()
@@ -721,6 +874,13 @@ impl Item for Editor {
 
         h_flex()
             .gap_2()
+            .when(self.edits_locked(cx), |this| {
+                this.child(
+                    Icon::new(IconName::FileLock)
+                        .size(IconSize::XSmall)
+                        .color(Color::Muted),
+                )
+            })
             .child(
                 Label::new(self.title(cx).to_string())
                     .color(label_color)
@@ -920,6 +1080,48 @@ impl Item for Editor {
         })
     }
 
+    fn can_reload_with_merge(&self, cx: &App) -> bool {
+        self.buffer()
+            .read(cx)
+            .all_buffers()
+            .iter()
+            .any(|buffer| has_local_conflict(buffer, cx))
+    }
+
+    fn reload_with_merge(
+        &mut self,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let merges = self
+            .buffer()
+            .read(cx)
+            .all_buffers()
+            .into_iter()
+            .filter(|buffer| has_local_conflict(buffer, cx))
+            .map(|buffer| {
+                let merge = buffer.update(cx, |buffer, cx| buffer.reload_with_merge(cx));
+                async move { anyhow::Ok((buffer, merge.await?)) }
+            })
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |this, cx| {
+            for (buffer, has_conflicts) in try_join_all(merges).await? {
+                if has_conflicts {
+                    project.update(cx, |project, cx| {
+                        project.git_store().update(cx, |git_store, cx| {
+                            git_store.show_reload_conflicts(buffer, cx)
+                        })
+                    })?;
+                }
+            }
+            this.update(cx, |editor, cx| {
+                editor.request_autoscroll(Autoscroll::fit(), cx)
+            })?;
+            Ok(())
+        })
+    }
+
     fn as_searchable(&self, handle: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
         Some(Box::new(handle.clone()))
     }
@@ -982,10 +1184,15 @@ impl Item for Editor {
     fn added_to_workspace(
         &mut self,
         workspace: &mut Workspace,
-        _window: &mut Window,
+        window: &mut Window,
         cx: &mut Context<Self>,
     ) {
         self.workspace = Some((workspace.weak_handle(), workspace.database_id()));
+        if std::mem::take(&mut self.restore_file_state_from_db)
+            && let Some(workspace_id) = workspace.database_id()
+        {
+            self.restore_file_state(workspace_id, window, cx);
+        }
         if let Some(workspace) = &workspace.weak_handle().upgrade() {
             cx.subscribe(workspace, |editor, _, event: &workspace::Event, _cx| {
                 if let workspace::Event::ModalOpened = event {
@@ -1249,9 +1456,9 @@ impl SerializableItem for Editor {
         let mut serialize_dirty_buffers = self.serialize_dirty_buffers;
 
         let project = self.project.clone()?;
-        if project.read(cx).visible_worktrees(cx).next().is_none() {
-            // If we don't have a worktree, we don't serialize, because
-            // projects without worktrees aren't deserialized.
+        if !project.read(cx).is_local() && project.read(cx).visible_worktrees(cx).next().is_none() {
+            // Local projects without worktrees keep their scratch buffers across sessions,
+            // but remote projects without worktrees aren't deserialized.
             serialize_dirty_buffers = false;
         }
 
@@ -1344,35 +1551,21 @@ impl ProjectItem for Editor {
         cx: &mut Context<Self>,
     ) -> Self {
         let mut editor = Self::for_buffer(buffer.clone(), Some(project), window, cx);
-        if let Some((excerpt_id, buffer_id, snapshot)) =
-            editor.buffer().read(cx).snapshot(cx).as_singleton()
-            && WorkspaceSettings::get(None, cx).restore_on_file_reopen
-            && let Some(restoration_data) = Self::project_item_kind()
+        if WorkspaceSettings::get(None, cx).restore_on_file_reopen {
+            if let Some(restoration_data) = Self::project_item_kind()
                 .and_then(|kind| pane.as_ref()?.project_item_restoration_data.get(&kind))
                 .and_then(|data| data.downcast_ref::<EditorRestorationData>())
                 .and_then(|data| {
                     let file = project::File::from_dyn(buffer.read(cx).file())?;
                     data.entries.get(&file.abs_path(cx))
                 })
-        {
-            editor.fold_ranges(
-                clip_ranges(&restoration_data.folds, snapshot),
-                false,
-                window,
-                cx,
-            );
-            if !restoration_data.selections.is_empty() {
-                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
-                    s.select_ranges(clip_ranges(&restoration_data.selections, snapshot));
-                });
+            {
+                editor.apply_restoration_data(restoration_data, window, cx);
+            } else {
+                // Nothing was recorded for this file in this session, so fall back to the state
+                // persisted in the database once the editor knows its workspace.
+                editor.restore_file_state_from_db = true;
             }
-            let (top_row, offset) = restoration_data.scroll_position;
-            let anchor = Anchor::in_buffer(
-                *excerpt_id,
-                buffer_id,
-                snapshot.anchor_before(Point::new(top_row, 0)),
-            );
-            editor.set_scroll_anchor(ScrollAnchor { anchor, offset }, window, cx);
         }
 
         editor
@@ -1402,9 +1595,107 @@ fn clip_ranges<'a>(
         .collect()
 }
 
+impl From<&RestorationData> for SerializedFileState {
+    fn from(data: &RestorationData) -> Self {
+        let serialize_ranges = |ranges: &[Range<Point>]| {
+            ranges
+                .iter()
+                .map(|range| {
+                    (
+                        (range.start.row, range.start.column),
+                        (range.end.row, range.end.column),
+                    )
+                })
+                .collect()
+        };
+        let (scroll_top_row, offset) = data.scroll_position;
+        Self {
+            scroll_top_row,
+            scroll_offset: (offset.x, offset.y),
+            folds: serialize_ranges(&data.folds),
+            selections: serialize_ranges(&data.selections),
+        }
+    }
+}
+
+impl From<SerializedFileState> for RestorationData {
+    fn from(state: SerializedFileState) -> Self {
+        let deserialize_ranges = |ranges: Vec<((u32, u32), (u32, u32))>| {
+            ranges
+                .into_iter()
+                .map(|(start, end)| Point::new(start.0, start.1)..Point::new(end.0, end.1))
+                .collect()
+        };
+        let (x, y) = state.scroll_offset;
+        Self {
+            scroll_position: (state.scroll_top_row, gpui::point(x, y)),
+            folds: deserialize_ranges(state.folds),
+            selections: deserialize_ranges(state.selections),
+        }
+    }
+}
+
 impl EventEmitter<SearchEvent> for Editor {}
 
 impl Editor {
+    fn apply_restoration_data(
+        &mut self,
+        restoration_data: &RestorationData,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((excerpt_id, buffer_id, snapshot)) =
+            self.buffer().read(cx).snapshot(cx).as_singleton()
+        else {
+            return;
+        };
+        self.fold_ranges(
+            clip_ranges(&restoration_data.folds, snapshot),
+            false,
+            window,
+            cx,
+        );
+        if !restoration_data.selections.is_empty() {
+            self.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges(clip_ranges(&restoration_data.selections, snapshot));
+            });
+        }
+        let (top_row, offset) = restoration_data.scroll_position;
+        let anchor = Anchor::in_buffer(
+            *excerpt_id,
+            buffer_id,
+            snapshot.anchor_before(Point::new(top_row, 0)),
+        );
+        self.set_scroll_anchor(ScrollAnchor { anchor, offset }, window, cx);
+    }
+
+    fn restore_file_state(
+        &mut self,
+        workspace_id: WorkspaceId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(abs_path) =
+            self.buffer().read(cx).as_singleton().and_then(|buffer| {
+                Some(project::File::from_dyn(buffer.read(cx).file())?.abs_path(cx))
+            })
+        else {
+            return;
+        };
+        let state = cx.background_spawn(async move { DB.get_file_state(workspace_id, &abs_path) });
+        cx.spawn_in(window, async move |editor, cx| {
+            let Some(state) = state.await.log_err().flatten() else {
+                return;
+            };
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    editor.apply_restoration_data(&state.into(), window, cx)
+                })
+                .ok();
+        })
+        .detach();
+    }
+
     pub fn update_restoration_data(
         &self,
         cx: &mut Context<Self>,
@@ -1421,7 +1712,7 @@ impl Editor {
                 let pane = editor.workspace()?.read(cx).pane_for(&cx.entity())?;
                 let buffer = editor.buffer().read(cx).as_singleton()?;
                 let file_abs_path = project::File::from_dyn(buffer.read(cx).file())?.abs_path(cx);
-                pane.update(cx, |pane, _| {
+                let state = pane.update(cx, |pane, _| {
                     let data = pane
                         .project_item_restoration_data
                         .entry(kind)
@@ -1435,10 +1726,21 @@ impl Editor {
                         }
                     };
 
-                    let data = data.entries.entry(file_abs_path).or_default();
+                    let data = data.entries.entry(file_abs_path.clone()).or_default();
                     write(data);
-                    Some(())
-                })
+                    Some(SerializedFileState::from(&*data))
+                })?;
+
+                let workspace_id = editor.workspace.as_ref()?.1?;
+                let background_executor = cx.background_executor().clone();
+                editor.serialize_file_state = cx.background_spawn(async move {
+                    background_executor.timer(SERIALIZATION_THROTTLE_TIME).await;
+                    DB.save_file_state(workspace_id, file_abs_path.clone(), state)
+                        .await
+                        .with_context(|| format!("persisting editor state for {file_abs_path:?}"))
+                        .log_err();
+                });
+                Some(())
             });
         });
     }
@@ -1462,6 +1764,7 @@ impl SearchableItem for Editor {
             .is_some()
         {
             cx.emit(SearchEvent::MatchesInvalidated);
+            cx.emit(EditorEvent::TransientHighlightsChanged);
         }
     }
 
@@ -1483,6 +1786,7 @@ impl SearchableItem for Editor {
         );
         if updated {
             cx.emit(SearchEvent::MatchesInvalidated);
+            cx.emit(EditorEvent::TransientHighlightsChanged);
         }
     }
 
@@ -1499,7 +1803,8 @@ impl SearchableItem for Editor {
         if self.has_filtered_search_ranges() {
             self.previous_search_ranges = self
                 .clear_background_highlights::<SearchWithinRange>(cx)
-                .map(|(_, ranges)| ranges)
+                .map(|(_, ranges)| ranges);
+            self.clear_background_highlights::<SearchOutsideRange>(cx);
         }
 
         if let Some(range) = enabled {
@@ -1577,7 +1882,7 @@ impl SearchableItem for Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.unfold_ranges(&[matches[index].clone()], false, true, cx);
+        self.reveal_folded_ranges(&[matches[index].clone()], cx);
         let range = self.range_for_match(&matches[index]);
         self.change_selections(Default::default(), window, cx, |s| {
             s.select_ranges([range]);
@@ -1853,6 +2158,13 @@ pub fn entry_git_aware_label_color(git_status: GitSummary, ignored: bool, select
     }
 }
 
+/// Whether `buffer` and its file have both changed, in a way [`Buffer::reload_with_merge`] can
+/// combine.
+fn has_local_conflict(buffer: &Entity<Buffer>, cx: &App) -> bool {
+    let buffer = buffer.read(cx);
+    buffer.has_conflict() && buffer.file().is_some_and(|file| file.is_local())
+}
+
 fn path_for_buffer<'a>(
     buffer: &Entity<MultiBuffer>,
     height: usize,
@@ -1909,6 +2221,7 @@ mod tests {
     use fs::MTime;
     use gpui::{App, VisualTestContext};
     use language::{LanguageMatcher, TestFile};
+    use multi_buffer::ToOffset as _;
     use project::FakeFs;
     use std::path::{Path, PathBuf};
     use util::{path, rel_path::RelPath};
@@ -2141,4 +2454,145 @@ mod tests {
             });
         }
     }
+
+    #[gpui::test]
+    async fn test_restore_file_state_from_db(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            serde_json::json!({ "main.rs": "one\ntwo\nthree\nfour\nfive" }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let app_state = cx.update(workspace::AppState::test);
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        let (workspace, cx) = cx.add_window_view(|window, cx| {
+            Workspace::new(Some(workspace_id), project.clone(), app_state, window, cx)
+        });
+        let abs_path = Path::new(path!("/dir/main.rs"));
+        DB.save_file_state(
+            workspace_id,
+            abs_path.to_path_buf(),
+            SerializedFileState {
+                folds: vec![((3, 0), (4, 2))],
+                selections: vec![((1, 1), (1, 3))],
+                ..SerializedFileState::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_abs_path(
+                    abs_path.to_path_buf(),
+                    OpenOptions {
+                        visible: Some(OpenVisible::None),
+                        ..Default::default()
+                    },
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        cx.run_until_parked();
+        editor.update(cx, |editor, cx| {
+            assert_eq!(
+                editor
+                    .selections
+                    .all::<Point>(cx)
+                    .into_iter()
+                    .map(|selection| selection.range())
+                    .collect::<Vec<_>>(),
+                vec![Point::new(1, 1)..Point::new(1, 3)]
+            );
+            assert_eq!(editor.display_text(cx), "one\ntwo\nthree\n⋯ve");
+        });
+
+        // Changes to the view are persisted again for the next time the file is opened.
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges([Point::new(2, 0)..Point::new(2, 0)])
+            });
+        });
+        cx.executor().advance_clock(SERIALIZATION_THROTTLE_TIME);
+        cx.run_until_parked();
+        let state = DB.get_file_state(workspace_id, abs_path).unwrap().unwrap();
+        assert_eq!(state.selections, vec![((2, 0), (2, 0))]);
+        assert_eq!(state.folds, vec![((3, 0), (4, 2))]);
+    }
+
+    #[gpui::test]
+    async fn test_following_transient_highlights(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [path!("/file.rs").as_ref()], cx).await;
+        let buffer = project.update(cx, |project, cx| {
+            let buffer = project.create_local_buffer("one two one", None, false, cx);
+            cx.new(|cx| MultiBuffer::singleton(buffer, cx))
+        });
+        let build_editor = |window: &mut Window, cx: &mut Context<Editor>| {
+            Editor::for_multibuffer(buffer.clone(), Some(project.clone()), window, cx)
+        };
+        let leader = cx.add_window(build_editor);
+        let follower = cx.add_window(build_editor);
+
+        let update = leader
+            .update(cx, |leader, window, cx| {
+                let snapshot = leader.buffer().read(cx).snapshot(cx);
+                let matches = [
+                    snapshot.anchor_after(0)..snapshot.anchor_before(3),
+                    snapshot.anchor_after(8)..snapshot.anchor_before(11),
+                ];
+                leader.update_matches(&matches, window, cx);
+                let mut update = None;
+                assert!(leader.add_event_to_update_proto(
+                    &EditorEvent::TransientHighlightsChanged,
+                    &mut update,
+                    window,
+                    cx,
+                ));
+                update.unwrap()
+            })
+            .unwrap();
+
+        let leader_search_highlights = |follower: &Editor, cx: &App| {
+            let snapshot = follower.buffer().read(cx).snapshot(cx);
+            follower
+                .background_highlights
+                .get(&HighlightKey::Type(TypeId::of::<LeaderSearchHighlights>()))
+                .map_or(Vec::new(), |(_, ranges)| {
+                    ranges
+                        .iter()
+                        .map(|range| {
+                            range.start.to_offset(&snapshot)..range.end.to_offset(&snapshot)
+                        })
+                        .collect::<Vec<_>>()
+                })
+        };
+        follower
+            .update(cx, |follower, window, cx| {
+                follower.set_leader_id(Some(CollaboratorId::Agent), window, cx);
+                follower.apply_update_proto(&project, update, window, cx)
+            })
+            .unwrap()
+            .await
+            .unwrap();
+        follower
+            .update(cx, |follower, window, cx| {
+                // The leader's matches are shown without changing the follower's own search.
+                assert_eq!(leader_search_highlights(follower, cx), [0..3, 8..11]);
+                assert!(follower.get_matches(window, cx).is_empty());
+
+                follower.set_leader_id(None, window, cx);
+                assert!(leader_search_highlights(follower, cx).is_empty());
+            })
+            .unwrap();
+    }
 }
@@ -80,6 +80,21 @@ pub(crate) fn should_auto_close(
             }
         }
 
+        let is_void_tag = jsx_open_tag_node
+            .named_child(TS_NODE_TAG_NAME_CHILD_INDEX)
+            .is_some_and(|tag_name| {
+                let tag_name = buffer
+                    .text_for_range(tag_name.byte_range())
+                    .collect::<String>();
+                config
+                    .void_tag_names
+                    .iter()
+                    .any(|void_tag_name| void_tag_name.eq_ignore_ascii_case(&tag_name))
+            });
+        if is_void_tag {
+            continue;
+        }
+
         to_auto_edit.push(JsxTagCompletionState {
             edit_index: index,
             open_tag_range: jsx_open_tag_node.byte_range(),
@@ -303,6 +318,71 @@ pub(crate) fn generate_auto_close_edits(
     Ok(edits)
 }
 
+/// Returns the name of the tag at `offset` along with the name of the tag it pairs with, so that
+/// they can be edited together.
+pub(crate) fn linked_tag_name_ranges(
+    buffer: &BufferSnapshot,
+    offset: usize,
+    config: &JsxTagAutoCloseConfig,
+) -> Option<Vec<Range<usize>>> {
+    let is_tag_name = |node: &Node| {
+        node.kind() == config.tag_name_node_name
+            || config
+                .tag_name_node_name_alternates
+                .iter()
+                .any(|alternate| alternate == node.kind())
+    };
+    let layer = buffer.smallest_syntax_layer_containing(offset..offset)?;
+    // A cursor at the end of the tag name touches the node after it too.
+    let mut tag_name = [offset, offset.saturating_sub(1)]
+        .into_iter()
+        .filter_map(|offset| layer.node().named_descendant_for_byte_range(offset, offset))
+        .find(|node| {
+            is_tag_name(node) || node.parent().is_some_and(|parent| is_tag_name(&parent))
+        })?;
+    // Dot-separated tag names are made of several nodes.
+    while let Some(parent) = tag_name.parent()
+        && is_tag_name(&parent)
+    {
+        tag_name = parent;
+    }
+
+    let tag = tag_name.parent()?;
+    if tag.named_child(TS_NODE_TAG_NAME_CHILD_INDEX) != Some(tag_name) {
+        return None;
+    }
+    let element = tag.parent()?;
+    if element.kind() != config.jsx_element_node_name {
+        return None;
+    }
+    let paired_tag = if tag.kind() == config.open_tag_node_name {
+        element
+            .named_child(element.named_child_count().checked_sub(1)?)
+            .filter(|node| node.kind() == config.close_tag_node_name)?
+    } else if tag.kind() == config.close_tag_node_name {
+        element
+            .named_child(0)
+            .filter(|node| node.kind() == config.open_tag_node_name)?
+    } else {
+        return None;
+    };
+    let paired_tag_name = paired_tag
+        .named_child(TS_NODE_TAG_NAME_CHILD_INDEX)
+        .filter(is_tag_name)?;
+
+    // Tags with different names don't belong together, even if the syntax tree pairs them.
+    let name = buffer
+        .text_for_range(tag_name.byte_range())
+        .collect::<String>();
+    if !buffer
+        .text_for_range(paired_tag_name.byte_range())
+        .equals_str(&name)
+    {
+        return None;
+    }
+    Some(vec![tag_name.byte_range(), paired_tag_name.byte_range()])
+}
+
 pub(crate) fn refresh_enabled_in_any_buffer(
     editor: &mut Editor,
     multi_buffer: &Entity<MultiBuffer>,
@@ -789,6 +869,29 @@ mod jsx_tag_autoclose_tests {
         "<divˇfoobar" + ">" => "<div>ˇ</div>foobar"
     );
 
+    #[gpui::test]
+    async fn test_renames_paired_tag(cx: &mut TestAppContext) {
+        let mut cx = test_setup(cx).await;
+        cx.set_state("<div>\n    <spanˇ>text</span>\n</div>");
+        cx.run_until_parked();
+
+        cx.update_editor(|editor, window, cx| {
+            editor.handle_input("s", window, cx);
+        });
+        cx.run_until_parked();
+        cx.assert_editor_state("<div>\n    <spansˇ>text</spans>\n</div>");
+
+        // The opening tag is renamed along with the closing tag too.
+        cx.set_state("<div>\n    <span>text</ˇspan>\n</div>");
+        cx.run_until_parked();
+
+        cx.update_editor(|editor, window, cx| {
+            editor.handle_input("X", window, cx);
+        });
+        cx.run_until_parked();
+        cx.assert_editor_state("<div>\n    <Xspan>text</Xˇspan>\n</div>");
+    }
+
     #[gpui::test]
     async fn test_multibuffer(cx: &mut TestAppContext) {
         init_test(cx, |settings| {
@@ -0,0 +1,415 @@
+//! Recording and playback of keyboard macros.
+//!
+//! While a macro is being recorded, every action dispatched from a keystroke while the recording
+//! editor is focused and every text insertion it handles is captured. Replaying a macro dispatches
+//! the same steps to the editor it was replayed in, either once for the whole editor or once per
+//! cursor.
+use std::{cell::Cell, collections::VecDeque, ops::Range, rc::Rc, sync::Arc};
+
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use gpui::{Action, App, Context, Global, WeakEntity, Window};
+use multi_buffer::Anchor;
+use serde::{Deserialize, Serialize};
+use util::ResultExt as _;
+
+use crate::{
+    Editor, SelectionEffects,
+    actions::{ReplayMacro, StartRecordingMacro, StopRecordingMacro},
+};
+
+const NAMED_KEYBOARD_MACROS_KEY: &str = "editor-named-keyboard-macros";
+/// Protects against macros that end up replaying themselves.
+const MAX_REPLAYED_STEPS: usize = 10_000;
+
+#[derive(Debug)]
+enum MacroStep {
+    Action(Box<dyn Action>),
+    Insertion {
+        text: Arc<str>,
+        utf16_range_to_replace: Option<Range<isize>>,
+    },
+}
+
+impl Clone for MacroStep {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Action(action) => Self::Action(action.boxed_clone()),
+            Self::Insertion {
+                text,
+                utf16_range_to_replace,
+            } => Self::Insertion {
+                text: text.clone(),
+                utf16_range_to_replace: utf16_range_to_replace.clone(),
+            },
+        }
+    }
+}
+
+/// Actions are persisted by name, along with the arguments of the key binding that dispatched them.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SerializedMacroStep {
+    Action {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        arguments: Option<serde_json::Value>,
+    },
+    Insertion {
+        text: String,
+        utf16_range_to_replace: Option<Range<isize>>,
+    },
+}
+
+struct Recording {
+    name: Option<String>,
+    editor: WeakEntity<Editor>,
+    steps: Vec<MacroStep>,
+}
+
+#[derive(Default)]
+struct KeyboardMacros {
+    recording: Option<Recording>,
+    last_recorded: Vec<MacroStep>,
+    /// Loaded from the database the first time a named macro is saved or replayed.
+    named: Option<HashMap<String, Vec<MacroStep>>>,
+    replaying: Rc<Cell<bool>>,
+}
+
+/// Marks a replay as in progress for as long as it's alive, so that a replay that stops early
+/// (or whose window is closed) never leaves recording and replaying disabled.
+struct ReplayingGuard(Rc<Cell<bool>>);
+
+impl ReplayingGuard {
+    fn new(cx: &App) -> Self {
+        let replaying = cx.global::<KeyboardMacros>().replaying.clone();
+        replaying.set(true);
+        Self(replaying)
+    }
+}
+
+impl Drop for ReplayingGuard {
+    fn drop(&mut self) {
+        self.0.set(false);
+    }
+}
+
+impl Global for KeyboardMacros {}
+
+pub(crate) fn init(cx: &mut App) {
+    cx.set_global(KeyboardMacros::default());
+
+    cx.observe_keystrokes(|event, window, cx| {
+        let Some(action) = event.action.as_ref() else {
+            return;
+        };
+        if is_macro_action(action.as_ref()) {
+            return;
+        }
+        let macros = cx.global::<KeyboardMacros>();
+        if macros.replaying.get() {
+            return;
+        }
+        let Some(recording_editor) = macros
+            .recording
+            .as_ref()
+            .and_then(|recording| recording.editor.upgrade())
+        else {
+            return;
+        };
+        if !recording_editor
+            .read(cx)
+            .focus_handle
+            .contains_focused(window, cx)
+        {
+            return;
+        }
+        if let Some(recording) = cx.global_mut::<KeyboardMacros>().recording.as_mut() {
+            recording
+                .steps
+                .push(MacroStep::Action(action.boxed_clone()));
+        }
+    })
+    .detach();
+}
+
+fn is_macro_action(action: &dyn Action) -> bool {
+    let action = action.as_any();
+    action.is::<StartRecordingMacro>()
+        || action.is::<StopRecordingMacro>()
+        || action.is::<ReplayMacro>()
+}
+
+/// Records text typed into the editor that a macro is being recorded in.
+pub(crate) fn observe_insertion(
+    text: &str,
+    utf16_range_to_replace: Option<Range<isize>>,
+    cx: &mut Context<Editor>,
+) {
+    if !cx.has_global::<KeyboardMacros>() {
+        return;
+    }
+    let editor_id = cx.entity_id();
+    let macros = cx.global_mut::<KeyboardMacros>();
+    if macros.replaying.get() {
+        return;
+    }
+    if let Some(recording) = macros
+        .recording
+        .as_mut()
+        .filter(|recording| recording.editor.entity_id() == editor_id)
+    {
+        recording.steps.push(MacroStep::Insertion {
+            text: text.into(),
+            utf16_range_to_replace,
+        });
+    }
+}
+
+fn named_macros(cx: &mut App) -> &mut HashMap<String, Vec<MacroStep>> {
+    if cx.global::<KeyboardMacros>().named.is_none() {
+        let named = load_named_macros(cx);
+        cx.global_mut::<KeyboardMacros>().named = Some(named);
+    }
+    cx.global_mut::<KeyboardMacros>()
+        .named
+        .get_or_insert_default()
+}
+
+fn load_named_macros(cx: &App) -> HashMap<String, Vec<MacroStep>> {
+    if cfg!(any(test, feature = "test-support")) {
+        return HashMap::default();
+    }
+    let Some(json) = KEY_VALUE_STORE
+        .read_kvp(NAMED_KEYBOARD_MACROS_KEY)
+        .log_err()
+        .flatten()
+    else {
+        return HashMap::default();
+    };
+    let Some(serialized) =
+        serde_json::from_str::<HashMap<String, Vec<SerializedMacroStep>>>(&json).log_err()
+    else {
+        return HashMap::default();
+    };
+
+    serialized
+        .into_iter()
+        .map(|(name, steps)| {
+            let steps = steps
+                .into_iter()
+                .filter_map(|step| match step {
+                    SerializedMacroStep::Action { name, arguments } => cx
+                        .build_action(&name, arguments)
+                        .log_err()
+                        .map(MacroStep::Action),
+                    SerializedMacroStep::Insertion {
+                        text,
+                        utf16_range_to_replace,
+                    } => Some(MacroStep::Insertion {
+                        text: text.into(),
+                        utf16_range_to_replace,
+                    }),
+                })
+                .collect();
+            (name, steps)
+        })
+        .collect()
+}
+
+fn save_named_macros(cx: &mut App) {
+    if cfg!(any(test, feature = "test-support")) {
+        return;
+    }
+    let keymap = cx.key_bindings();
+    let keymap = keymap.borrow();
+    let serialized = cx
+        .global::<KeyboardMacros>()
+        .named
+        .iter()
+        .flatten()
+        .map(|(name, steps)| {
+            let steps = steps
+                .iter()
+                .map(|step| match step {
+                    MacroStep::Action(action) => SerializedMacroStep::Action {
+                        name: action.name().to_string(),
+                        // Bindings only match actions with equal arguments, so any binding for
+                        // the action carries its arguments.
+                        arguments: keymap
+                            .bindings_for_action(action.as_ref())
+                            .find_map(|binding| binding.action_input())
+                            .and_then(|input| serde_json::from_str(&input).log_err()),
+                    },
+                    MacroStep::Insertion {
+                        text,
+                        utf16_range_to_replace,
+                    } => SerializedMacroStep::Insertion {
+                        text: text.to_string(),
+                        utf16_range_to_replace: utf16_range_to_replace.clone(),
+                    },
+                })
+                .collect::<Vec<_>>();
+            (name.clone(), steps)
+        })
+        .collect::<HashMap<_, _>>();
+    drop(keymap);
+    let Some(json) = serde_json::to_string(&serialized).log_err() else {
+        return;
+    };
+    cx.background_spawn(async move {
+        KEY_VALUE_STORE
+            .write_kvp(NAMED_KEYBOARD_MACROS_KEY.to_string(), json)
+            .await
+    })
+    .detach_and_log_err(cx);
+}
+
+enum ReplayStep {
+    Macro(MacroStep),
+    SelectRange(Range<Anchor>),
+    CollectSelections,
+    RestoreCollectedSelections,
+}
+
+struct MacroReplayer {
+    editor: WeakEntity<Editor>,
+    steps: VecDeque<ReplayStep>,
+    collected_selections: Vec<Range<Anchor>>,
+    replayed: usize,
+    _replaying: ReplayingGuard,
+}
+
+impl MacroReplayer {
+    fn next(mut self, window: &mut Window, cx: &mut App) {
+        let step = if self.replayed < MAX_REPLAYED_STEPS {
+            self.steps.pop_front()
+        } else {
+            log::error!("Aborting keyboard macro replay after {MAX_REPLAYED_STEPS} steps");
+            None
+        };
+        self.replayed += 1;
+        let Some(step) = step else {
+            return;
+        };
+
+        let result = match step {
+            ReplayStep::Macro(MacroStep::Action(action)) => self
+                .editor
+                .read_with(cx, |editor, _| editor.focus_handle.clone())
+                .map(|focus_handle| focus_handle.dispatch_action(action.as_ref(), window, cx)),
+            ReplayStep::Macro(MacroStep::Insertion {
+                text,
+                utf16_range_to_replace,
+            }) => self.editor.update(cx, |editor, cx| {
+                editor.replay_insert_event(&text, utf16_range_to_replace, window, cx)
+            }),
+            ReplayStep::SelectRange(range) => self.editor.update(cx, |editor, cx| {
+                editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                    s.select_anchor_ranges([range])
+                })
+            }),
+            ReplayStep::CollectSelections => self.editor.read_with(cx, |editor, _| {
+                self.collected_selections
+                    .extend(editor.selections.disjoint_anchor_ranges());
+            }),
+            ReplayStep::RestoreCollectedSelections => {
+                let ranges = std::mem::take(&mut self.collected_selections);
+                self.editor.update(cx, |editor, cx| {
+                    editor.change_selections(SelectionEffects::default(), window, cx, |s| {
+                        s.select_anchor_ranges(ranges)
+                    })
+                })
+            }
+        };
+
+        if result.is_err() {
+            return;
+        }
+        window.defer(cx, move |window, cx| self.next(window, cx));
+    }
+}
+
+impl Editor {
+    pub fn start_recording_macro(
+        &mut self,
+        action: &StartRecordingMacro,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let editor = cx.entity().downgrade();
+        let macros = cx.global_mut::<KeyboardMacros>();
+        if macros.replaying.get() {
+            return;
+        }
+        macros.recording = Some(Recording {
+            name: action.name.clone(),
+            editor,
+            steps: Vec::new(),
+        });
+        cx.notify();
+    }
+
+    pub fn stop_recording_macro(
+        &mut self,
+        _: &StopRecordingMacro,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(recording) = cx.global_mut::<KeyboardMacros>().recording.take() else {
+            return;
+        };
+        if let Some(name) = recording.name {
+            named_macros(cx).insert(name, recording.steps.clone());
+            save_named_macros(cx);
+        }
+        cx.global_mut::<KeyboardMacros>().last_recorded = recording.steps;
+        cx.notify();
+    }
+
+    pub fn replay_macro(
+        &mut self,
+        action: &ReplayMacro,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let macros = cx.global::<KeyboardMacros>();
+        if macros.replaying.get() || macros.recording.is_some() {
+            return;
+        }
+        let steps = match &action.name {
+            Some(name) => named_macros(cx).get(name).cloned().unwrap_or_default(),
+            None => cx.global::<KeyboardMacros>().last_recorded.clone(),
+        };
+        if steps.is_empty() {
+            return;
+        }
+
+        let times = action.times.unwrap_or(1).max(1);
+        let repeated_steps = || {
+            (0..times)
+                .flat_map(|_| steps.iter().cloned())
+                .map(ReplayStep::Macro)
+        };
+        let mut replay_steps = VecDeque::new();
+        if action.per_cursor {
+            for range in self.selections.disjoint_anchor_ranges() {
+                replay_steps.push_back(ReplayStep::SelectRange(range));
+                replay_steps.extend(repeated_steps());
+                replay_steps.push_back(ReplayStep::CollectSelections);
+            }
+            replay_steps.push_back(ReplayStep::RestoreCollectedSelections);
+        } else {
+            replay_steps.extend(repeated_steps());
+        }
+
+        let replayer = MacroReplayer {
+            editor: cx.entity().downgrade(),
+            steps: replay_steps,
+            collected_selections: Vec::new(),
+            replayed: 0,
+            _replaying: ReplayingGuard::new(cx),
+        };
+        window.defer(cx, move |window, cx| replayer.next(window, cx));
+    }
+}
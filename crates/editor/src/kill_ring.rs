@@ -0,0 +1,194 @@
+//! An Emacs-style kill ring, shared by all editors.
+//!
+//! Consecutive kills at the same position are joined into one entry, so that `ctrl-k ctrl-k`
+//! yanks both lines back at once. Right after a yank, [`KillRingYankPop`] replaces the yanked
+//! text with the entry killed before it, cycling back through the ring.
+use std::{collections::VecDeque, ops::Range};
+
+use gpui::{App, ClipboardEntry, ClipboardItem, Context, Global, Window};
+use multi_buffer::{Anchor, ToOffset as _};
+
+use crate::{
+    DisplayPoint, Editor, HideMouseCursorOrigin, SelectionEffects,
+    actions::{KillRingCut, KillRingYank, KillRingYankPop},
+};
+
+/// The number of kills remembered, as in Emacs' default `kill-ring-max`.
+const MAX_KILL_RING_LEN: usize = 120;
+
+#[derive(Default)]
+struct KillRing {
+    /// Killed text, most recent first.
+    entries: VecDeque<ClipboardItem>,
+}
+
+impl Global for KillRing {}
+
+impl KillRing {
+    fn push(&mut self, item: ClipboardItem) {
+        if self.entries.len() == MAX_KILL_RING_LEN {
+            self.entries.pop_back();
+        }
+        self.entries.push_front(item);
+    }
+
+    /// Adds `item` to the end of the most recent kill.
+    fn append(&mut self, item: ClipboardItem) {
+        let Some(last) = self.entries.front_mut() else {
+            self.push(item);
+            return;
+        };
+        let mut text = last.text().unwrap_or_default();
+        text.push_str(&item.text().unwrap_or_default());
+        *last = ClipboardItem::new_string(text);
+    }
+}
+
+/// Where the editor's last kill or yank left its cursors, to tell whether the next kill
+/// continues it or whether a yank can still be replaced with [`KillRingYankPop`].
+pub(crate) struct LastKillRingEdit {
+    edit_count: usize,
+    cursors: Vec<usize>,
+    kind: KillRingEditKind,
+}
+
+enum KillRingEditKind {
+    Kill,
+    Yank {
+        ranges: Vec<Range<Anchor>>,
+        entry_ix: usize,
+    },
+}
+
+impl Editor {
+    pub fn kill_ring_cut(&mut self, _: &KillRingCut, window: &mut Window, cx: &mut Context<Self>) {
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+        let continues_kill = self.last_kill_ring_edit.take().is_some_and(|last| {
+            matches!(last.kind, KillRingEditKind::Kill) && self.is_after_kill_ring_edit(&last, cx)
+        });
+        self.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.move_with(|snapshot, sel| {
+                if sel.is_empty() {
+                    sel.end = DisplayPoint::new(sel.end.row(), snapshot.line_len(sel.end.row()));
+                }
+                if sel.is_empty() {
+                    sel.end = DisplayPoint::new(sel.end.row() + 1_u32, 0);
+                }
+            });
+        });
+        let item = self.cut_common(false, window, cx);
+        let kill_ring = cx.default_global::<KillRing>();
+        if continues_kill {
+            kill_ring.append(item);
+        } else {
+            kill_ring.push(item);
+        }
+        self.last_kill_ring_edit = Some(self.kill_ring_edit(KillRingEditKind::Kill, cx));
+    }
+
+    pub fn kill_ring_yank(
+        &mut self,
+        _: &KillRingYank,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+        self.yank_kill_ring_entry(0, window, cx);
+    }
+
+    pub fn kill_ring_yank_pop(
+        &mut self,
+        _: &KillRingYankPop,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(last) = self.last_kill_ring_edit.take() else {
+            return;
+        };
+        if !self.is_after_kill_ring_edit(&last, cx) {
+            return;
+        }
+        let KillRingEditKind::Yank { ranges, entry_ix } = last.kind else {
+            return;
+        };
+        let entry_count = cx
+            .try_global::<KillRing>()
+            .map_or(0, |kill_ring| kill_ring.entries.len());
+        if entry_count == 0 {
+            return;
+        }
+
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+        let entry_ix = (entry_ix + 1) % entry_count;
+        self.transact(window, cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_anchor_ranges(ranges);
+            });
+            editor.yank_kill_ring_entry(entry_ix, window, cx);
+        });
+    }
+
+    fn yank_kill_ring_entry(
+        &mut self,
+        entry_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ClipboardEntry::String(entry)) = cx
+            .try_global::<KillRing>()
+            .and_then(|kill_ring| kill_ring.entries.get(entry_ix))
+            .and_then(|item| item.entries().first())
+            .cloned()
+        else {
+            return;
+        };
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let starts = self
+            .selections
+            .all::<usize>(cx)
+            .into_iter()
+            .map(|selection| snapshot.anchor_before(selection.start))
+            .collect::<Vec<_>>();
+        self.do_paste(
+            &entry.text().to_string(),
+            entry.metadata_json(),
+            false,
+            window,
+            cx,
+        );
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let ends = self.selections.all::<usize>(cx);
+        let ranges = starts
+            .into_iter()
+            .zip(ends)
+            .map(|(start, end)| start..snapshot.anchor_after(end.head()))
+            .collect();
+        self.last_kill_ring_edit =
+            Some(self.kill_ring_edit(KillRingEditKind::Yank { ranges, entry_ix }, cx));
+    }
+
+    fn kill_ring_edit(&self, kind: KillRingEditKind, cx: &App) -> LastKillRingEdit {
+        LastKillRingEdit {
+            edit_count: self.buffer.read(cx).snapshot(cx).edit_count(),
+            cursors: self.kill_ring_cursors(cx),
+            kind,
+        }
+    }
+
+    /// Whether nothing was edited and no cursor moved since `last`.
+    fn is_after_kill_ring_edit(&self, last: &LastKillRingEdit, cx: &App) -> bool {
+        last.edit_count == self.buffer.read(cx).snapshot(cx).edit_count()
+            && last.cursors == self.kill_ring_cursors(cx)
+    }
+
+    fn kill_ring_cursors(&self, cx: &App) -> Vec<usize> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        self.selections
+            .disjoint_anchors()
+            .iter()
+            .map(|selection| selection.head().to_offset(&snapshot))
+            .collect()
+    }
+}
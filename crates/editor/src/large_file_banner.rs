@@ -0,0 +1,91 @@
+use crate::Editor;
+use gpui::{Entity, EventEmitter, Subscription};
+use ui::{Banner, prelude::*};
+use workspace::{ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
+
+/// A banner shown above editors whose buffer was opened in large file mode,
+/// offering to enable the features that were disabled for it.
+pub struct LargeFileBanner {
+    active_editor: Option<Entity<Editor>>,
+    large_file_mode: bool,
+    _observe_active_editor: Option<Subscription>,
+}
+
+impl LargeFileBanner {
+    pub fn new() -> Self {
+        Self {
+            active_editor: None,
+            large_file_mode: false,
+            _observe_active_editor: None,
+        }
+    }
+
+    fn toolbar_item_location(&self) -> ToolbarItemLocation {
+        if self.large_file_mode {
+            ToolbarItemLocation::Secondary
+        } else {
+            ToolbarItemLocation::Hidden
+        }
+    }
+}
+
+impl EventEmitter<ToolbarItemEvent> for LargeFileBanner {}
+
+impl Render for LargeFileBanner {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(editor) = self.active_editor.clone().filter(|_| self.large_file_mode) else {
+            return div().id("large-file-banner");
+        };
+
+        div().id("large-file-banner").child(
+            Banner::new()
+                .child(
+                    v_flex()
+                        .gap_0p5()
+                        .child(Label::new("This file was opened in large file mode").mt_0p5())
+                        .child(
+                            Label::new(
+                                "Syntax highlighting, language servers, inlay hints and soft wrap are disabled to keep the editor responsive.",
+                            )
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                        ),
+                )
+                .action_slot(
+                    Button::new("enable-all-features", "Enable All Features")
+                        .label_size(LabelSize::Small)
+                        .on_click(move |_, _, cx| {
+                            editor.update(cx, |editor, cx| editor.disable_large_file_mode(cx));
+                        }),
+                ),
+        )
+    }
+}
+
+impl ToolbarItemView for LargeFileBanner {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn workspace::ItemHandle>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> ToolbarItemLocation {
+        self.active_editor = active_pane_item.and_then(|item| item.act_as::<Editor>(cx));
+        self.large_file_mode = self
+            .active_editor
+            .as_ref()
+            .is_some_and(|editor| editor.read(cx).large_file_mode(cx));
+        self._observe_active_editor = self.active_editor.as_ref().map(|editor| {
+            cx.observe(editor, |this, editor, cx| {
+                let large_file_mode = editor.read(cx).large_file_mode(cx);
+                if this.large_file_mode != large_file_mode {
+                    this.large_file_mode = large_file_mode;
+                    cx.emit(ToolbarItemEvent::ChangeLocation(
+                        this.toolbar_item_location(),
+                    ));
+                    cx.notify();
+                }
+            })
+        });
+        self.toolbar_item_location()
+    }
+}
@@ -2,10 +2,10 @@ use collections::HashMap;
 use gpui::{Context, Window};
 use itertools::Itertools;
 use std::{ops::Range, time::Duration};
-use text::{AnchorRangeExt, BufferId, ToPoint};
+use text::{AnchorRangeExt, BufferId, ToOffset as _, ToPoint};
 use util::ResultExt;
 
-use crate::Editor;
+use crate::{Editor, jsx_tag_auto_close};
 
 #[derive(Clone, Default)]
 pub(super) struct LinkedEditingRanges(
@@ -96,8 +96,14 @@ pub(super) fn refresh_linked_ranges(
                     let buffer_id = buffer.read(cx).remote_id();
 
                     let linked_edits_task = project.linked_edits(buffer, *start, cx);
+                    let tag_edits = linked_tag_names(&snapshot, *start, cx);
                     let highlights = move || async move {
-                        let edits = linked_edits_task.await.log_err()?;
+                        // Without a language server providing the ranges, the closing tag is
+                        // still renamed along with the opening tag, and vice versa.
+                        let edits = match linked_edits_task.await.log_err() {
+                            Some(edits) if !edits.is_empty() => edits,
+                            _ => tag_edits?,
+                        };
                         // Find the range containing our current selection.
                         // We might not find one, because the selection contains both the start and end of the contained range
                         // (think of selecting <`html>foo`</html> - even though there's a matching closing tag, the selection goes beyond the range of the opening tag)
@@ -169,3 +175,23 @@ pub(super) fn refresh_linked_ranges(
     }));
     None
 }
+
+fn linked_tag_names(
+    snapshot: &language::BufferSnapshot,
+    position: text::Anchor,
+    cx: &gpui::App,
+) -> Option<Vec<Range<text::Anchor>>> {
+    let offset = position.to_offset(snapshot);
+    let language = snapshot.language_at(offset)?;
+    let config = language.config().jsx_tag_auto_close.as_ref()?;
+    if !snapshot.settings_at(offset, cx).linked_edits {
+        return None;
+    }
+    let ranges = jsx_tag_auto_close::linked_tag_name_ranges(snapshot, offset, config)?;
+    Some(
+        ranges
+            .into_iter()
+            .map(|range| snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end))
+            .collect(),
+    )
+}
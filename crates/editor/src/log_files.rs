@@ -0,0 +1,360 @@
+//! Log file mode: colors the lines of log files by level, folds away the lines of the levels that
+//! are filtered out, and follows log files that are being appended to.
+use std::{any::TypeId, ops::Range, sync::LazyLock};
+
+use collections::{BTreeSet, HashMap};
+use gpui::{Context, HighlightStyle, Hsla, Window};
+use language::{PLAIN_TEXT, Point};
+use multi_buffer::{Anchor, MultiBufferRow, MultiBufferSnapshot, ToPoint as _};
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use settings::Settings as _;
+use theme::ActiveTheme as _;
+
+use crate::{
+    Editor, EditorSettings, SelectionEffects, ShowAllLogLevels, ToggleLogLevel,
+    display_map::{Crease, FoldPlaceholder},
+    scroll::Autoscroll,
+};
+
+/// The number of non-blank lines at the start of a plain text file that are inspected to decide
+/// whether it is a log file.
+const SNIFFED_LINES: usize = 20;
+
+static LEVEL_FIELD_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\b(?:level|lvl|severity)"?\s*[=:]\s*"?([a-z]+)"#).unwrap());
+static LEVEL_WORD_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(FATAL|CRITICAL|ERROR|ERR|WARNING|WARN|INFO|NOTICE|DEBUG|TRACE)\b").unwrap()
+});
+
+struct LogLevelFold;
+struct LogLevelHighlight;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "fatal" | "critical" | "crit" | "panic" | "alert" | "emerg" | "error" | "err" => {
+                Some(Self::Error)
+            }
+            "warning" | "warn" => Some(Self::Warn),
+            "info" | "notice" | "information" => Some(Self::Info),
+            "debug" | "dbg" => Some(Self::Debug),
+            "trace" | "verbose" => Some(Self::Trace),
+            _ => None,
+        }
+    }
+}
+
+/// Recognizes the level of a log line, either from a `level=warn` style field, as written by
+/// structured loggers, or from an uppercase level such as `ERROR` or `[WARN]`.
+pub fn line_log_level(line: &str) -> Option<LogLevel> {
+    if let Some(captures) = LEVEL_FIELD_REGEX.captures(line)
+        && let Some(level) = LogLevel::from_name(&captures[1])
+    {
+        return Some(level);
+    }
+    LogLevel::from_name(LEVEL_WORD_REGEX.find(line)?.as_str())
+}
+
+/// Returns the level of every line of `text`. Lines without a level of their own, such as the
+/// lines of a stack trace, belong to the entry above them.
+pub fn log_levels(text: &str) -> Vec<Option<LogLevel>> {
+    let mut current_level = None;
+    text.split('\n')
+        .map(|line| {
+            if let Some(level) = line_log_level(line) {
+                current_level = Some(level);
+            }
+            current_level
+        })
+        .collect()
+}
+
+/// Whether most of the first lines of `text` have a log level.
+pub fn looks_like_log(text: &str) -> bool {
+    let lines = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(SNIFFED_LINES)
+        .collect::<Vec<_>>();
+    let leveled_lines = lines
+        .iter()
+        .filter(|line| line_log_level(line).is_some())
+        .count();
+    leveled_lines >= 3 && leveled_lines * 2 > lines.len()
+}
+
+fn is_log_file_name(file_name: &str) -> bool {
+    file_name.split('.').skip(1).any(|part| part == "log")
+}
+
+/// Groups consecutive rows with the same level.
+fn level_runs(levels: &[Option<LogLevel>]) -> Vec<(Range<u32>, LogLevel)> {
+    let mut runs = Vec::<(Range<u32>, LogLevel)>::new();
+    for (row, level) in levels.iter().enumerate() {
+        let row = row as u32;
+        let Some(level) = *level else {
+            continue;
+        };
+        match runs.last_mut() {
+            Some((rows, run_level)) if rows.end == row && *run_level == level => rows.end += 1,
+            _ => runs.push((row..row + 1, level)),
+        }
+    }
+    runs
+}
+
+/// The range to fold to hide `rows` entirely, including the newline that separates them from the
+/// rest of the buffer.
+fn hidden_rows_range(rows: Range<u32>, buffer: &MultiBufferSnapshot) -> Range<Point> {
+    let last_row = rows.end - 1;
+    if rows.start > 0 {
+        let start_row = rows.start - 1;
+        Point::new(start_row, buffer.line_len(MultiBufferRow(start_row)))
+            ..Point::new(last_row, buffer.line_len(MultiBufferRow(last_row)))
+    } else if last_row < buffer.max_row().0 {
+        Point::zero()..Point::new(rows.end, 0)
+    } else {
+        Point::zero()..buffer.max_point()
+    }
+}
+
+fn level_color(level: LogLevel, cx: &Context<Editor>) -> Option<Hsla> {
+    match level {
+        LogLevel::Error => Some(cx.theme().status().error),
+        LogLevel::Warn => Some(cx.theme().status().warning),
+        LogLevel::Info => None,
+        LogLevel::Debug | LogLevel::Trace => Some(cx.theme().colors().text_muted),
+    }
+}
+
+#[derive(Default)]
+struct LogFileState {
+    level_ranges: HashMap<LogLevel, Vec<Range<Anchor>>>,
+    hidden_ranges: Vec<Range<Anchor>>,
+}
+
+impl Editor {
+    /// Whether the editor shows a log file, which is either named like one (`server.log`,
+    /// `server.log.1`) or a plain text file whose lines mostly have a log level.
+    pub fn is_log_file(&self) -> bool {
+        self.log_file
+    }
+
+    pub fn toggle_log_level(
+        &mut self,
+        action: &ToggleLogLevel,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.log_file {
+            cx.propagate();
+            return;
+        }
+        if !self.hidden_log_levels.remove(&action.level) {
+            self.hidden_log_levels.insert(action.level);
+        }
+        self.refresh_log_file(window, cx);
+    }
+
+    pub fn show_all_log_levels(
+        &mut self,
+        _: &ShowAllLogLevels,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.log_file {
+            cx.propagate();
+            return;
+        }
+        self.hidden_log_levels.clear();
+        self.refresh_log_file(window, cx);
+    }
+
+    pub fn hidden_log_levels(&self) -> &BTreeSet<LogLevel> {
+        &self.hidden_log_levels
+    }
+
+    pub(crate) fn refresh_log_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let fold_type_id = TypeId::of::<LogLevelFold>();
+        // `Some(true)` when the file name marks a log file, and `Some(false)` when its contents
+        // have to be inspected.
+        let detection = self
+            .buffer
+            .read(cx)
+            .as_singleton()
+            .filter(|_| self.mode.is_full() && EditorSettings::get_global(cx).log_files.enabled)
+            .and_then(|buffer| {
+                let buffer = buffer.read(cx);
+                if buffer
+                    .file()
+                    .is_some_and(|file| is_log_file_name(file.file_name(cx)))
+                {
+                    Some(true)
+                } else if buffer
+                    .language()
+                    .is_none_or(|language| language.name() == PLAIN_TEXT.name())
+                {
+                    Some(false)
+                } else {
+                    None
+                }
+            });
+        if detection.is_none() && !self.log_file {
+            return;
+        }
+
+        let snapshot = self.snapshot(window, cx);
+        let hidden_levels = self.hidden_log_levels.clone();
+        let task = cx.background_spawn(async move {
+            let buffer = snapshot.buffer_snapshot();
+            let existing_folds = snapshot
+                .folds_in_range(0..buffer.len())
+                .filter(|fold| fold.placeholder.type_tag == Some(fold_type_id))
+                .map(|fold| fold.range.start..fold.range.end)
+                .collect::<Vec<_>>();
+            let text = buffer.text();
+            let is_log_file = match detection {
+                Some(true) => true,
+                Some(false) => looks_like_log(&text),
+                None => false,
+            };
+            if !is_log_file {
+                return (false, LogFileState::default(), existing_folds);
+            }
+
+            let mut state = LogFileState::default();
+            let mut hidden_rows = Vec::<Range<u32>>::new();
+            for (rows, level) in level_runs(&log_levels(&text)) {
+                let last_row = rows.end - 1;
+                let range = Point::new(rows.start, 0)
+                    ..Point::new(last_row, buffer.line_len(MultiBufferRow(last_row)));
+                state
+                    .level_ranges
+                    .entry(level)
+                    .or_default()
+                    .push(buffer.anchor_after(range.start)..buffer.anchor_before(range.end));
+                if hidden_levels.contains(&level) {
+                    match hidden_rows.last_mut() {
+                        Some(hidden) if hidden.end == rows.start => hidden.end = rows.end,
+                        _ => hidden_rows.push(rows),
+                    }
+                }
+            }
+            state.hidden_ranges = hidden_rows
+                .into_iter()
+                .map(|rows| {
+                    let range = hidden_rows_range(rows, buffer);
+                    buffer.anchor_after(range.start)..buffer.anchor_before(range.end)
+                })
+                .collect();
+            (true, state, existing_folds)
+        });
+
+        self.log_file_task = cx.spawn(async move |this, cx| {
+            let (is_log_file, state, existing_folds) = task.await;
+            this.update(cx, |this, cx| {
+                this.log_file = is_log_file;
+                if state.hidden_ranges != existing_folds {
+                    let placeholder = FoldPlaceholder {
+                        type_tag: Some(fold_type_id),
+                        ..this.display_map.read(cx).fold_placeholder.clone()
+                    };
+                    let creases = state
+                        .hidden_ranges
+                        .into_iter()
+                        .map(|range| Crease::simple(range, placeholder.clone()))
+                        .collect();
+                    this.display_map.update(cx, |display_map, cx| {
+                        display_map.remove_folds_with_type(existing_folds, fold_type_id, cx);
+                        display_map.fold(creases, cx);
+                    });
+                }
+
+                this.display_map.update(cx, |display_map, _| {
+                    display_map.clear_keyed_highlights(TypeId::of::<LogLevelHighlight>());
+                });
+                for (level, ranges) in state.level_ranges {
+                    if let Some(color) = level_color(level, cx) {
+                        this.highlight_text_key::<LogLevelHighlight>(
+                            level as usize,
+                            ranges,
+                            HighlightStyle::color(color),
+                            cx,
+                        );
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    /// Starts or stops following the end of a log file, depending on whether the cursor was
+    /// moved to its last line.
+    pub(crate) fn update_log_following(&mut self, buffer: &MultiBufferSnapshot) {
+        self.following_log = self.log_file
+            && self.selections.newest_anchor().head().to_point(buffer).row == buffer.max_row().0;
+    }
+
+    /// Moves the cursor to the end of a log file that was reloaded after being appended to, if
+    /// the cursor was on its last line.
+    pub(crate) fn follow_log_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.log_file || !self.following_log || !EditorSettings::get_global(cx).log_files.follow
+        {
+            return;
+        }
+        let end = self.buffer.read(cx).read(cx).len();
+        self.change_selections(
+            SelectionEffects::scroll(Autoscroll::newest()).nav_history(false),
+            window,
+            cx,
+            |selections| selections.select_ranges([end..end]),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_levels() {
+        assert_eq!(
+            log_levels(
+                "2024-05-01T10:00:00Z INFO server: listening on :8080\n\
+                 [2024-05-01 10:00:01] ERROR request failed\n\
+                 \x20   at handler (server.rs:10)\n\
+                 time=10:00:02 level=warn msg=\"slow request\"\n\
+                 {\"level\":\"debug\",\"msg\":\"retrying\"}\n\
+                 10:00:03 [TRACE] polling"
+            ),
+            [
+                Some(LogLevel::Info),
+                Some(LogLevel::Error),
+                Some(LogLevel::Error),
+                Some(LogLevel::Warn),
+                Some(LogLevel::Debug),
+                Some(LogLevel::Trace),
+            ]
+        );
+        assert_eq!(log_levels("no level\nan error in lowercase"), [None, None]);
+
+        assert!(looks_like_log("INFO a\nWARN b\n\nERROR c\nd"));
+        assert!(!looks_like_log("fn main() {\n    // TODO: handle ERROR\n}"));
+        assert!(is_log_file_name("server.log"));
+        assert!(is_log_file_name("server.log.1"));
+        assert!(!is_log_file_name("log.txt"));
+    }
+}
@@ -0,0 +1,514 @@
+//! Editing of Markdown pipe tables, which are found with tree-sitter: realigning their columns,
+//! moving between their cells with tab and shift-tab, and inserting rows and columns.
+use std::ops::Range;
+
+use gpui::{App, Context, Window};
+use language::{BufferSnapshot, Point, ToOffset as _};
+use settings::Settings as _;
+
+use crate::{
+    Editor, EditorSettings, FormatTable, InsertTableColumnLeft, InsertTableColumnRight,
+    InsertTableRowAbove, InsertTableRowBelow, SelectionEffects,
+};
+
+/// The minimum width of a column, so that its delimiter can hold both alignment colons.
+const MIN_COLUMN_WIDTH: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Alignment {
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+impl Alignment {
+    fn parse(cell: &str) -> Option<Self> {
+        let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+        if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+            return None;
+        }
+        Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+            (false, false) => Self::None,
+            (true, false) => Self::Left,
+            (true, true) => Self::Center,
+            (false, true) => Self::Right,
+        })
+    }
+
+    fn delimiter(self, width: usize) -> String {
+        match self {
+            Self::None => "-".repeat(width),
+            Self::Left => format!(":{}", "-".repeat(width - 1)),
+            Self::Center => format!(":{}:", "-".repeat(width - 2)),
+            Self::Right => format!("{}:", "-".repeat(width - 1)),
+        }
+    }
+}
+
+/// A position in a table: the line (counting the delimiter row) and the column of a cell, with an
+/// offset into the cell's trimmed contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct TableCursor {
+    line: usize,
+    /// `None` when the cursor is past the last pipe of the line.
+    column: Option<usize>,
+    offset: usize,
+    /// Whether to select the contents of the cell, rather than placing a cursor in it.
+    select_cell: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct MarkdownTable {
+    indent: String,
+    alignments: Vec<Alignment>,
+    /// The header followed by the body rows, without the delimiter row.
+    rows: Vec<Vec<String>>,
+}
+
+/// The byte ranges of the cells of a table line, between unescaped pipes. Leading and trailing
+/// pipes don't start or end a cell. The second element tells whether the line ends with a pipe.
+fn cell_ranges(line: &str) -> (Vec<Range<usize>>, bool) {
+    let mut pipes = Vec::new();
+    let mut escaped = false;
+    for (ix, ch) in line.char_indices() {
+        if ch == '|' && !escaped {
+            pipes.push(ix);
+        }
+        escaped = ch == '\\' && !escaped;
+    }
+
+    let content_start = line.len() - line.trim_start().len();
+    let content_end = line.trim_end().len().max(content_start);
+    let leading_pipe = pipes.first() == Some(&content_start);
+    let trailing_pipe = content_end > 0
+        && pipes.last() == Some(&(content_end - 1))
+        && !(leading_pipe && pipes.len() == 1);
+
+    let mut separators = pipes.as_slice();
+    let mut start = content_start;
+    let mut end = content_end;
+    if leading_pipe {
+        start = separators[0] + 1;
+        separators = &separators[1..];
+    }
+    if trailing_pipe {
+        end = separators[separators.len() - 1];
+        separators = &separators[..separators.len() - 1];
+    }
+    let mut ranges = Vec::new();
+    for &pipe in separators {
+        ranges.push(start..pipe);
+        start = pipe + 1;
+    }
+    ranges.push(start..end);
+    (ranges, trailing_pipe)
+}
+
+fn trimmed(line: &str, range: &Range<usize>) -> Range<usize> {
+    let cell = &line[range.clone()];
+    let start = range.start + (cell.len() - cell.trim_start().len());
+    start..start.max(range.start + cell.trim_end().len())
+}
+
+impl MarkdownTable {
+    fn parse(text: &str) -> Option<Self> {
+        let lines = text.split('\n').collect::<Vec<_>>();
+        let delimiter_line = lines.get(1)?;
+        let cells = |line: &str| {
+            cell_ranges(line)
+                .0
+                .iter()
+                .map(|range| line[trimmed(line, range)].to_string())
+                .collect::<Vec<_>>()
+        };
+        let alignments = cells(delimiter_line)
+            .iter()
+            .map(|cell| Alignment::parse(cell))
+            .collect::<Option<Vec<_>>>()?;
+        let mut table = Self {
+            indent: lines[0][..lines[0].len() - lines[0].trim_start().len()].to_string(),
+            alignments,
+            rows: lines
+                .iter()
+                .enumerate()
+                .filter(|(ix, _)| *ix != 1)
+                .map(|(_, line)| cells(line))
+                .collect(),
+        };
+        let columns = table
+            .rows
+            .iter()
+            .map(Vec::len)
+            .chain([table.alignments.len()])
+            .max()
+            .unwrap_or(0);
+        table.alignments.resize(columns, Alignment::None);
+        for row in &mut table.rows {
+            row.resize(columns, String::new());
+        }
+        Some(table)
+    }
+
+    fn line_count(&self) -> usize {
+        self.rows.len() + 1
+    }
+
+    fn row_index(line: usize) -> Option<usize> {
+        match line {
+            0 => Some(0),
+            1 => None,
+            line => Some(line - 1),
+        }
+    }
+
+    fn insert_row(&mut self, row: usize) {
+        self.rows
+            .insert(row, vec![String::new(); self.alignments.len()]);
+    }
+
+    fn insert_column(&mut self, column: usize) {
+        self.alignments.insert(column, Alignment::None);
+        for row in &mut self.rows {
+            row.insert(column, String::new());
+        }
+    }
+
+    /// Returns the formatted table and, for every line, the byte range of each cell's contents and
+    /// the offset of the end of the line.
+    fn format(&self) -> (String, Vec<(Vec<Range<usize>>, usize)>) {
+        let widths = (0..self.alignments.len())
+            .map(|column| {
+                self.rows
+                    .iter()
+                    .map(|row| row[column].chars().count())
+                    .max()
+                    .unwrap_or(0)
+                    .max(MIN_COLUMN_WIDTH)
+            })
+            .collect::<Vec<_>>();
+
+        let mut text = String::new();
+        let mut layout = Vec::new();
+        for line in 0..self.line_count() {
+            if line > 0 {
+                text.push('\n');
+            }
+            text.push_str(&self.indent);
+            text.push('|');
+            let mut cells = Vec::new();
+            for (column, (&width, &alignment)) in widths.iter().zip(&self.alignments).enumerate() {
+                text.push(' ');
+                let cell_start = text.len();
+                match Self::row_index(line) {
+                    Some(row) => {
+                        let content = &self.rows[row][column];
+                        let padding = width - content.chars().count();
+                        let left_padding = match alignment {
+                            Alignment::Right => padding,
+                            Alignment::Center => padding / 2,
+                            Alignment::None | Alignment::Left => 0,
+                        };
+                        text.extend(std::iter::repeat_n(' ', left_padding));
+                        let content_start = text.len();
+                        text.push_str(content);
+                        cells.push(content_start..text.len());
+                        text.extend(std::iter::repeat_n(' ', padding - left_padding));
+                    }
+                    None => {
+                        text.push_str(&alignment.delimiter(width));
+                        cells.push(cell_start..text.len());
+                    }
+                }
+                text.push_str(" |");
+            }
+            layout.push((cells, text.len()));
+        }
+        (text, layout)
+    }
+}
+
+/// Finds the lines of the pipe table containing `offset`.
+fn table_range(buffer: &BufferSnapshot, offset: usize) -> Option<Range<usize>> {
+    let table = buffer
+        .syntax_layers_for_range(offset..offset, true)
+        .find_map(|layer| {
+            let mut node = layer.node().descendant_for_byte_range(offset, offset)?;
+            while node.kind() != "pipe_table" {
+                node = node.parent()?;
+            }
+            Some(node)
+        })?;
+    let start_row = table.start_position().row as u32;
+    let end = table.end_position();
+    let end_row = if end.column == 0 && end.row as u32 > start_row {
+        end.row as u32 - 1
+    } else {
+        end.row as u32
+    };
+    Some(
+        Point::new(start_row, 0).to_offset(buffer)
+            ..Point::new(end_row, buffer.line_len(end_row)).to_offset(buffer),
+    )
+}
+
+/// Finds the cell of `text`, the text of a table, that contains `offset`.
+fn locate_cursor(text: &str, offset: usize) -> TableCursor {
+    let line = text[..offset].matches('\n').count();
+    let line_start = text[..offset].rfind('\n').map_or(0, |ix| ix + 1);
+    let line_text = text[line_start..].split('\n').next().unwrap_or_default();
+    let column_offset = offset - line_start;
+    let (cells, trailing_pipe) = cell_ranges(line_text);
+    let past_last_pipe = trailing_pipe && cells.last().is_none_or(|cell| column_offset > cell.end);
+    let column = if past_last_pipe {
+        None
+    } else {
+        cells
+            .iter()
+            .position(|cell| column_offset <= cell.end)
+            .or(cells.len().checked_sub(1))
+    };
+    let offset = column.map_or(0, |column| {
+        let content = trimmed(line_text, &cells[column]);
+        column_offset.clamp(content.start, content.end) - content.start
+    });
+    TableCursor {
+        line,
+        column,
+        offset,
+        select_cell: false,
+    }
+}
+
+impl TableCursor {
+    fn move_to_cell(&mut self, table: &MarkdownTable, forward: bool) {
+        let columns = table.alignments.len();
+        let mut line = self.line;
+        let mut column = match (self.column, forward) {
+            (Some(column), true) => column as isize + 1,
+            (Some(column), false) => column as isize - 1,
+            (None, true) => columns as isize,
+            (None, false) => columns as isize - 1,
+        };
+        loop {
+            if column < 0 {
+                if line == 0 {
+                    column = 0;
+                    break;
+                }
+                line -= 1;
+                column = columns as isize - 1;
+            } else if column as usize >= columns {
+                line += 1;
+                column = 0;
+            }
+            if line != 1 {
+                break;
+            }
+            column = if forward { columns as isize } else { -1 };
+        }
+        self.line = line;
+        self.column = Some(column as usize);
+        self.offset = 0;
+        self.select_cell = true;
+    }
+}
+
+impl Editor {
+    pub(crate) fn markdown_table_editing_enabled(&self, cx: &App) -> bool {
+        !self.read_only(cx) && EditorSettings::get_global(cx).markdown_tables.enabled
+    }
+
+    pub fn format_table(&mut self, _: &FormatTable, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.edit_markdown_table(window, cx, |_, _| {}) {
+            cx.propagate();
+        }
+    }
+
+    pub fn insert_table_row_above(
+        &mut self,
+        _: &InsertTableRowAbove,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let inserted = self.edit_markdown_table(window, cx, |table, cursor| {
+            // Rows can't be inserted above the header, so the row goes above the first body row.
+            let row = MarkdownTable::row_index(cursor.line).unwrap_or(0).max(1);
+            table.insert_row(row);
+            cursor.line = row + 1;
+            cursor.column = Some(cursor.column.unwrap_or(0));
+            cursor.offset = 0;
+        });
+        if !inserted {
+            cx.propagate();
+        }
+    }
+
+    pub fn insert_table_row_below(
+        &mut self,
+        _: &InsertTableRowBelow,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let inserted = self.edit_markdown_table(window, cx, |table, cursor| {
+            let row = MarkdownTable::row_index(cursor.line).unwrap_or(0) + 1;
+            table.insert_row(row);
+            cursor.line = row + 1;
+            cursor.column = Some(cursor.column.unwrap_or(0));
+            cursor.offset = 0;
+        });
+        if !inserted {
+            cx.propagate();
+        }
+    }
+
+    pub fn insert_table_column_left(
+        &mut self,
+        _: &InsertTableColumnLeft,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let inserted = self.edit_markdown_table(window, cx, |table, cursor| {
+            let column = cursor
+                .column
+                .unwrap_or(table.alignments.len().saturating_sub(1));
+            table.insert_column(column);
+            cursor.column = Some(column);
+            cursor.offset = 0;
+        });
+        if !inserted {
+            cx.propagate();
+        }
+    }
+
+    pub fn insert_table_column_right(
+        &mut self,
+        _: &InsertTableColumnRight,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let inserted = self.edit_markdown_table(window, cx, |table, cursor| {
+            let column = cursor
+                .column
+                .map_or(table.alignments.len(), |column| column + 1);
+            table.insert_column(column);
+            cursor.column = Some(column);
+            cursor.offset = 0;
+        });
+        if !inserted {
+            cx.propagate();
+        }
+    }
+
+    /// Moves to the next or previous cell of the Markdown table containing the cursor, realigning
+    /// the table. Moving past the last cell adds a row. Returns false outside of tables.
+    pub(crate) fn move_to_markdown_table_cell(
+        &mut self,
+        forward: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.edit_markdown_table(window, cx, |table, cursor| {
+            cursor.move_to_cell(table, forward);
+            if cursor.line >= table.line_count() {
+                table.insert_row(table.rows.len());
+            }
+        })
+    }
+
+    /// Realigns the Markdown table containing the cursor, keeping the cursor in its cell.
+    pub(crate) fn realign_markdown_table(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.edit_markdown_table(window, cx, |_, _| {});
+    }
+
+    /// Applies `edit` to the Markdown table containing the newest cursor, then rewrites the table
+    /// with aligned columns and moves the cursor to the cell it points to. Returns false when
+    /// there are several selections, or the newest cursor isn't in a table.
+    fn edit_markdown_table(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        edit: impl FnOnce(&mut MarkdownTable, &mut TableCursor),
+    ) -> bool {
+        if self.read_only(cx) || self.selections.count() > 1 {
+            return false;
+        }
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let Some((_, _, buffer)) = snapshot.as_singleton() else {
+            return false;
+        };
+        let head = self.selections.newest::<usize>(cx).head();
+        let Some(range) = table_range(buffer, head) else {
+            return false;
+        };
+        let old_text = buffer.text_for_range(range.clone()).collect::<String>();
+        let Some(mut table) = MarkdownTable::parse(&old_text) else {
+            return false;
+        };
+
+        let mut cursor = locate_cursor(&old_text, head - range.start);
+        edit(&mut table, &mut cursor);
+        let (new_text, layout) = table.format();
+        let (cells, line_end) = &layout[cursor.line.min(layout.len() - 1)];
+        let new_selection = match cursor.column.and_then(|column| cells.get(column)) {
+            Some(cell) if cursor.select_cell => cell.clone(),
+            Some(cell) => {
+                let offset = (cell.start + cursor.offset).min(cell.end);
+                offset..offset
+            }
+            None => *line_end..*line_end,
+        };
+        let new_selection = range.start + new_selection.start..range.start + new_selection.end;
+
+        self.transact(window, cx, |this, window, cx| {
+            if new_text != old_text {
+                this.buffer.update(cx, |buffer, cx| {
+                    buffer.edit([(range, new_text)], None, cx);
+                });
+            }
+            this.change_selections(SelectionEffects::default(), window, cx, |selections| {
+                selections.select_ranges([new_selection]);
+            });
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_markdown_table() {
+        let table = MarkdownTable::parse(
+            "  | Name | Score |\n  |:-|-:|\n  | Alice | 9 |\n  | Bob\n  | a \\| b | 10 | extra |",
+        )
+        .unwrap();
+        assert_eq!(
+            table.format().0,
+            [
+                "  | Name   | Score |       |",
+                "  | :----- | ----: | ----- |",
+                "  | Alice  |     9 |       |",
+                "  | Bob    |       |       |",
+                "  | a \\| b |    10 | extra |",
+            ]
+            .join("\n")
+        );
+        assert_eq!(MarkdownTable::parse("| a |\n| b |"), None);
+    }
+
+    #[test]
+    fn test_locate_table_cursor() {
+        let text = "| a | bc |\n|---|---|\n| d | e |";
+        let cursor = |offset| {
+            let cursor = locate_cursor(text, offset);
+            (cursor.line, cursor.column, cursor.offset)
+        };
+        assert_eq!(cursor(0), (0, Some(0), 0));
+        assert_eq!(cursor(3), (0, Some(0), 1));
+        assert_eq!(cursor(7), (0, Some(1), 1));
+        assert_eq!(cursor(10), (0, None, 0));
+        assert_eq!(cursor(text.len() - 2), (2, Some(1), 1));
+    }
+}
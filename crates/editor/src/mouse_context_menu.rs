@@ -3,7 +3,10 @@ use crate::{
     EvaluateSelectedText, FindAllReferences, GoToDeclaration, GoToDefinition, GoToImplementation,
     GoToTypeDefinition, Paste, Rename, RevealInFileManager, RunToCursor, SelectMode,
     SelectionEffects, SelectionExt, ToDisplayPoint, ToggleCodeActions,
-    actions::{Format, FormatSelections},
+    actions::{
+        ExpandExcerptsDown, ExpandExcerptsToFunction, ExpandExcerptsUp, Format, FormatSelections,
+        ToggleFoldExcerpts,
+    },
     selections_collection::SelectionsCollection,
 };
 use gpui::prelude::FluentBuilder;
@@ -199,6 +202,7 @@ pub fn deploy_context_menu(
                     .is_some()
             });
 
+        let is_multibuffer = !editor.buffer().read(cx).is_singleton();
         let evaluate_selection = window.is_action_available(&EvaluateSelectedText, cx);
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
 
@@ -234,6 +238,20 @@ pub fn deploy_context_menu(
                     }),
                 )
                 .separator()
+                .when(is_multibuffer, |builder| {
+                    builder
+                        .action("Expand Excerpt Up", Box::new(ExpandExcerptsUp::default()))
+                        .action(
+                            "Expand Excerpt Down",
+                            Box::new(ExpandExcerptsDown::default()),
+                        )
+                        .action(
+                            "Expand Excerpt to Function",
+                            Box::new(ExpandExcerptsToFunction),
+                        )
+                        .action("Toggle Excerpt Fold", Box::new(ToggleFoldExcerpts))
+                        .separator()
+                })
                 .action("Cut", Box::new(Cut))
                 .action("Copy", Box::new(Copy))
                 .action("Copy and Trim", Box::new(CopyAndTrim))
@@ -3,7 +3,7 @@
 
 use super::{Bias, DisplayPoint, DisplaySnapshot, SelectionGoal, ToDisplayPoint};
 use crate::{
-    DisplayRow, EditorStyle, ToOffset, ToPoint,
+    DisplayRow, EditorStyle, ToOffset, ToPoint, bidi,
     scroll::{ScrollAnchor, ScrollOffset},
 };
 use gpui::{Pixels, WindowTextSystem};
@@ -12,7 +12,8 @@ use multi_buffer::{MultiBufferRow, MultiBufferSnapshot};
 use serde::Deserialize;
 use workspace::searchable::Direction;
 
-use std::{ops::Range, sync::Arc};
+use std::{ops::Range, str, sync::Arc};
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation as _};
 
 /// Defines search strategy for items in `movement` module.
 /// `FindRange::SingeLine` only looks for a match on a single line at a time, whereas
@@ -38,7 +39,7 @@ pub struct TextLayoutDetails {
 /// to the previous line if that point is at the start of line.
 pub fn left(map: &DisplaySnapshot, mut point: DisplayPoint) -> DisplayPoint {
     if point.column() > 0 {
-        *point.column_mut() -= 1;
+        *point.column_mut() = previous_grapheme_boundary(map, point);
     } else if point.row().0 > 0 {
         *point.row_mut() -= 1;
         *point.column_mut() = map.line_len(point.row());
@@ -50,7 +51,7 @@ pub fn left(map: &DisplaySnapshot, mut point: DisplayPoint) -> DisplayPoint {
 /// that point is already at the start of line.
 pub fn saturating_left(map: &DisplaySnapshot, mut point: DisplayPoint) -> DisplayPoint {
     if point.column() > 0 {
-        *point.column_mut() -= 1;
+        *point.column_mut() = previous_grapheme_boundary(map, point);
     } else if point.column() == 0 {
         // If the current sofr_wrap mode is used, the column corresponding to the display is 0,
         //  which does not necessarily mean that the actual beginning of a paragraph
@@ -65,7 +66,7 @@ pub fn saturating_left(map: &DisplaySnapshot, mut point: DisplayPoint) -> Displa
 /// to the next line if that point is at the end of line.
 pub fn right(map: &DisplaySnapshot, mut point: DisplayPoint) -> DisplayPoint {
     if point.column() < map.line_len(point.row()) {
-        *point.column_mut() += 1;
+        *point.column_mut() = next_grapheme_boundary(map, point);
     } else if point.row() < map.max_point().row() {
         *point.row_mut() += 1;
         *point.column_mut() = 0;
@@ -76,10 +77,95 @@ pub fn right(map: &DisplaySnapshot, mut point: DisplayPoint) -> DisplayPoint {
 /// Returns a column to the right of the current point, not performing any wrapping
 /// if that point is already at the end of line.
 pub fn saturating_right(map: &DisplaySnapshot, mut point: DisplayPoint) -> DisplayPoint {
-    *point.column_mut() += 1;
+    *point.column_mut() = next_grapheme_boundary(map, point);
     map.clip_point(point, Bias::Right)
 }
 
+/// Returns the column of the extended grapheme cluster boundary before the given point, so that
+/// emoji sequences, flags and characters with combining marks are moved over as a whole.
+fn previous_grapheme_boundary(map: &DisplaySnapshot, point: DisplayPoint) -> u32 {
+    let column = point.column() as usize;
+    let line = map.line(point.row());
+    if column > line.len() || !line.is_char_boundary(column) {
+        return point.column().saturating_sub(1);
+    }
+    GraphemeCursor::new(column, line.len(), true)
+        .prev_boundary(&line, 0)
+        .ok()
+        .flatten()
+        .map_or(point.column().saturating_sub(1), |boundary| boundary as u32)
+}
+
+/// Returns the column of the extended grapheme cluster boundary after the given point.
+fn next_grapheme_boundary(map: &DisplaySnapshot, point: DisplayPoint) -> u32 {
+    let column = point.column() as usize;
+    let line = map.line(point.row());
+    if column >= line.len() || !line.is_char_boundary(column) {
+        return point.column() + 1;
+    }
+    GraphemeCursor::new(column, line.len(), true)
+        .next_boundary(&line, 0)
+        .ok()
+        .flatten()
+        .map_or(point.column() + 1, |boundary| boundary as u32)
+}
+
+/// Whether `ch` joins the extended grapheme cluster before it, like combining marks, emoji
+/// modifiers and zero-width joiners. Boundaries are never found before these characters, and the
+/// characters are classified like the start of their cluster.
+fn extends_grapheme(ch: char) -> bool {
+    if ch.is_ascii() {
+        return false;
+    }
+    let mut text = [b'a'; 5];
+    let len = 1 + ch.encode_utf8(&mut text[1..]).len();
+    str::from_utf8(&text[..len]).is_ok_and(|text| text.graphemes(true).nth(1).is_none())
+}
+
+/// Returns the point to the left of the current point on screen, which moves forwards through
+/// right-to-left text. Wraps like [`left`] at the visual start of the line.
+pub fn visual_left(map: &DisplaySnapshot, point: DisplayPoint) -> DisplayPoint {
+    visual_neighbor(map, point, false).unwrap_or_else(|| left(map, point))
+}
+
+/// Returns the point to the right of the current point on screen, which moves backwards through
+/// right-to-left text. Wraps like [`right`] at the visual end of the line.
+pub fn visual_right(map: &DisplaySnapshot, point: DisplayPoint) -> DisplayPoint {
+    visual_neighbor(map, point, true).unwrap_or_else(|| right(map, point))
+}
+
+fn visual_neighbor(
+    map: &DisplaySnapshot,
+    point: DisplayPoint,
+    rightwards: bool,
+) -> Option<DisplayPoint> {
+    let row = point.row();
+    let line = map.line(row);
+    if !bidi::contains_rtl(&line) {
+        return None;
+    }
+    let carets = bidi::visual_carets(&line);
+    let ix = carets
+        .iter()
+        .position(|caret| *caret == point.column() as usize)?;
+    let neighbor = if rightwards {
+        carets.get(ix + 1)
+    } else {
+        ix.checked_sub(1).and_then(|ix| carets.get(ix))
+    };
+    let Some(&column) = neighbor else {
+        // Wrap to the adjacent line from the visual edges of the line.
+        return Some(if rightwards {
+            right(map, DisplayPoint::new(row, map.line_len(row)))
+        } else {
+            left(map, DisplayPoint::new(row, 0))
+        });
+    };
+    let bias = if rightwards { Bias::Right } else { Bias::Left };
+    Some(map.clip_point(DisplayPoint::new(row, column as u32), bias))
+        .filter(|neighbor| *neighbor != point)
+}
+
 /// Returns a display point for the preceding displayed line (which might be a soft-wrapped line).
 pub fn up(
     map: &DisplaySnapshot,
@@ -619,18 +705,25 @@ pub fn find_preceding_boundary_point(
 ) -> Point {
     let mut prev_ch = None;
     let mut offset = from.to_offset(buffer_snapshot);
+    let mut cluster_end = offset;
 
     for ch in buffer_snapshot.reversed_chars_at(offset) {
         if find_range == FindRange::SingleLine && ch == '\n' {
             break;
         }
+        if extends_grapheme(ch) {
+            offset -= ch.len_utf8();
+            continue;
+        }
         if let Some(prev_ch) = prev_ch
             && is_boundary(ch, prev_ch)
         {
+            offset = cluster_end;
             break;
         }
 
         offset -= ch.len_utf8();
+        cluster_end = offset;
         prev_ch = Some(ch);
     }
 
@@ -676,7 +769,9 @@ pub fn find_boundary_point(
         if find_range == FindRange::SingleLine && ch == '\n' {
             break;
         }
+        let extends_grapheme = extends_grapheme(ch);
         if let Some(prev_ch) = prev_ch
+            && !extends_grapheme
             && is_boundary(prev_ch, ch)
         {
             if return_point_before_boundary {
@@ -687,7 +782,9 @@ pub fn find_boundary_point(
         }
         prev_offset = offset;
         offset += ch.len_utf8();
-        prev_ch = Some(ch);
+        if prev_ch.is_none() || !extends_grapheme {
+            prev_ch = Some(ch);
+        }
     }
     map.clip_point(offset.to_display_point(map), Bias::Right)
 }
@@ -718,6 +815,7 @@ pub fn find_preceding_boundary_trail(
     let start_offset = offset;
     for ch in forward {
         if let Some(prev_ch) = prev_ch
+            && !extends_grapheme(prev_ch)
             && is_boundary(prev_ch, ch)
         {
             if start_offset == offset {
@@ -766,6 +864,7 @@ pub fn find_boundary_trail(
     let start_offset = offset;
     for ch in forward {
         if let Some(prev_ch) = prev_ch
+            && !extends_grapheme(ch)
             && is_boundary(prev_ch, ch)
         {
             if start_offset == offset {
@@ -919,6 +1018,63 @@ mod tests {
         assert("oneˇ,;:!?ˇtwo", cx);
     }
 
+    #[gpui::test]
+    fn test_visual_left_and_right(cx: &mut gpui::App) {
+        init_test(cx);
+
+        // "ab אב" is shown as "ab בא", and its Hebrew letters are two bytes long.
+        let (snapshot, _) = marked_display_snapshot("ab אב\nc", cx);
+        let point = |row, column| DisplayPoint::new(DisplayRow(row), column);
+        assert_eq!(visual_right(&snapshot, point(0, 2)), point(0, 7));
+        assert_eq!(visual_right(&snapshot, point(0, 7)), point(0, 5));
+        assert_eq!(visual_right(&snapshot, point(0, 3)), point(1, 0));
+        assert_eq!(visual_left(&snapshot, point(0, 5)), point(0, 7));
+        assert_eq!(visual_left(&snapshot, point(0, 7)), point(0, 2));
+        assert_eq!(visual_left(&snapshot, point(1, 0)), point(0, 7));
+        assert_eq!(visual_right(&snapshot, point(1, 0)), point(1, 1));
+    }
+
+    #[gpui::test]
+    fn test_left_and_right_by_grapheme(cx: &mut gpui::App) {
+        init_test(cx);
+
+        fn assert(marked_text: &str, cx: &mut gpui::App) {
+            let (snapshot, display_points) = marked_display_snapshot(marked_text, cx);
+            assert_eq!(
+                left(&snapshot, display_points[1]),
+                display_points[0],
+                "{marked_text}"
+            );
+            assert_eq!(
+                right(&snapshot, display_points[0]),
+                display_points[1],
+                "{marked_text}"
+            );
+        }
+
+        // Flags are pairs of regional indicators.
+        assert("ˇ🇺🇸ˇ🇬🇧", cx);
+        assert("🇺🇸ˇ🇬🇧ˇ", cx);
+        // Skin tone modifiers and zero-width joiner sequences.
+        assert("aˇ👍🏽ˇb", cx);
+        assert("ˇ👨\u{200d}👩\u{200d}👧ˇ", cx);
+        // Combining marks, in Indic scripts and in decomposed Latin letters.
+        assert("ˇकिˇ", cx);
+        assert("नˇस्ˇ", cx);
+        assert("cafˇe\u{301}ˇ", cx);
+
+        // Word movement doesn't stop between a letter and its combining marks.
+        let (snapshot, display_points) = marked_display_snapshot("ˇनमस्कारˇ", cx);
+        assert_eq!(
+            next_word_end(&snapshot, display_points[0]),
+            display_points[1]
+        );
+        assert_eq!(
+            previous_word_start(&snapshot, display_points[1]),
+            display_points[0]
+        );
+    }
+
     #[gpui::test]
     fn test_previous_subword_start(cx: &mut gpui::App) {
         init_test(cx);
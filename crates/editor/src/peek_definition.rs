@@ -0,0 +1,420 @@
+//! Inline peeking of definitions for `editor::PeekDefinition`.
+//!
+//! The definition is shown in an editor embedded in a block below the cursor's line. That editor
+//! is backed by the definition's buffer, so it can be edited in place.
+use std::sync::Arc;
+
+use anyhow::Result;
+use collections::{HashMap, HashSet};
+use gpui::{Context, Entity, Focusable as _, Subscription, Task, WeakEntity, Window};
+use language::{Location, ToPoint as _};
+use project::LocationLink;
+use ui::{IconButton, IconButtonShape, IconName, IconSize, Tooltip, h_flex, prelude::*, v_flex};
+
+use crate::{
+    Editor, EditorEvent, GotoDefinitionKind, HoverLink, SelectionEffects,
+    actions::{PeekDefinition, PeekNextDefinition, PeekPreviousDefinition},
+    display_map::{
+        BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId, RenderBlock,
+    },
+    hover_links,
+    scroll::Autoscroll,
+};
+
+/// The number of lines of the peek block, including its header, when it is first opened.
+const DEFAULT_PEEK_HEIGHT: u32 = 14;
+const MIN_PEEK_HEIGHT: u32 = 4;
+const MAX_PEEK_HEIGHT: u32 = 60;
+/// The number of lines the peek block grows or shrinks by when resized.
+const PEEK_RESIZE_STEP: u32 = 4;
+
+pub(crate) struct PeekDefinitionState {
+    block_id: CustomBlockId,
+    editor: Entity<Editor>,
+    locations: Vec<Location>,
+    index: usize,
+    height: u32,
+    _subscription: Subscription,
+}
+
+impl Editor {
+    pub fn peek_definition(
+        &mut self,
+        _: &PeekDefinition,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        let provider = self.semantics_provider.clone()?;
+        let head = self.selections.newest_anchor().head();
+        let (buffer, position) = self.buffer.read(cx).text_anchor_for_position(head, cx)?;
+        let definitions =
+            provider.definitions(&buffer, position, GotoDefinitionKind::Symbol, cx)?;
+
+        Some(cx.spawn_in(window, async move |editor, cx| {
+            let Some(definitions) = definitions.await? else {
+                return Ok(());
+            };
+            editor.update_in(cx, |editor, window, cx| {
+                let locations = definitions
+                    .into_iter()
+                    .filter(|link| {
+                        hover_links::exclude_link_to_position(&buffer, &position, link, cx)
+                    })
+                    .map(|link| link.target)
+                    .collect::<Vec<_>>();
+                if locations.is_empty() {
+                    return;
+                }
+                editor.hide_peek_definition(cx);
+                editor.show_peek_definition(locations, head, window, cx);
+            })
+        }))
+    }
+
+    pub fn peek_next_definition(
+        &mut self,
+        _: &PeekNextDefinition,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.page_peek_definition(true, window, cx);
+    }
+
+    pub fn peek_previous_definition(
+        &mut self,
+        _: &PeekPreviousDefinition,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.page_peek_definition(false, window, cx);
+    }
+
+    pub(crate) fn is_peeking_definition(&self) -> bool {
+        self.peek_definition.is_some()
+    }
+
+    /// Returns the embedded editor of the open peek, with the index of the shown definition and
+    /// the number of definitions.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn peeked_definition(&self) -> Option<(Entity<Editor>, usize, usize)> {
+        let peek = self.peek_definition.as_ref()?;
+        Some((peek.editor.clone(), peek.index, peek.locations.len()))
+    }
+
+    /// Removes the peek block, returning whether one was shown.
+    pub(crate) fn hide_peek_definition(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(peek) = self.peek_definition.take() else {
+            return false;
+        };
+        self.remove_blocks(HashSet::from_iter([peek.block_id]), None, cx);
+        true
+    }
+
+    fn show_peek_definition(
+        &mut self,
+        locations: Vec<Location>,
+        position: multi_buffer::Anchor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let peek_editor = self.peek_editor_for_location(&locations[0], window, cx);
+        let render = render_peek_block(
+            cx.entity().downgrade(),
+            peek_editor.clone(),
+            &locations[0],
+            0,
+            locations.len(),
+            DEFAULT_PEEK_HEIGHT,
+        );
+        let block_id = self.insert_blocks(
+            [BlockProperties {
+                style: BlockStyle::Flex,
+                placement: BlockPlacement::Below(position),
+                height: Some(DEFAULT_PEEK_HEIGHT),
+                render,
+                priority: 0,
+            }],
+            Some(Autoscroll::fit()),
+            cx,
+        )[0];
+        self.peek_definition = Some(PeekDefinitionState {
+            block_id,
+            _subscription: self.subscribe_to_peek_editor(&peek_editor, cx),
+            editor: peek_editor,
+            locations,
+            index: 0,
+            height: DEFAULT_PEEK_HEIGHT,
+        });
+    }
+
+    fn page_peek_definition(&mut self, next: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(peek) = self.peek_definition.as_ref() else {
+            cx.propagate();
+            return;
+        };
+        let count = peek.locations.len();
+        let index = if next {
+            (peek.index + 1) % count
+        } else {
+            (peek.index + count - 1) % count
+        };
+        let peek_editor = self.peek_editor_for_location(&peek.locations[index], window, cx);
+        let subscription = self.subscribe_to_peek_editor(&peek_editor, cx);
+        if let Some(peek) = self.peek_definition.as_mut() {
+            peek.index = index;
+            peek.editor = peek_editor;
+            peek._subscription = subscription;
+        }
+        self.rerender_peek_definition(cx);
+    }
+
+    fn resize_peek_definition(&mut self, grow: bool, cx: &mut Context<Self>) {
+        let Some(peek) = self.peek_definition.as_mut() else {
+            return;
+        };
+        peek.height = if grow {
+            (peek.height + PEEK_RESIZE_STEP).min(MAX_PEEK_HEIGHT)
+        } else {
+            peek.height
+                .saturating_sub(PEEK_RESIZE_STEP)
+                .max(MIN_PEEK_HEIGHT)
+        };
+        let heights = HashMap::from_iter([(peek.block_id, peek.height)]);
+        self.resize_blocks(heights, Some(Autoscroll::fit()), cx);
+        self.rerender_peek_definition(cx);
+    }
+
+    fn rerender_peek_definition(&mut self, cx: &mut Context<Self>) {
+        let Some(peek) = self.peek_definition.as_ref() else {
+            return;
+        };
+        let render = render_peek_block(
+            cx.entity().downgrade(),
+            peek.editor.clone(),
+            &peek.locations[peek.index],
+            peek.index,
+            peek.locations.len(),
+            peek.height,
+        );
+        let renderers = HashMap::from_iter([(peek.block_id, render)]);
+        self.replace_blocks(renderers, None, cx);
+    }
+
+    fn open_peeked_definition(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(peek) = self.peek_definition.as_ref() else {
+            return;
+        };
+        let link = HoverLink::Text(LocationLink {
+            origin: None,
+            target: peek.locations[peek.index].clone(),
+        });
+        self.hide_peek_definition(cx);
+        self.navigate_to_hover_links(
+            Some(GotoDefinitionKind::Symbol),
+            vec![link],
+            false,
+            window,
+            cx,
+        )
+        .detach_and_log_err(cx);
+    }
+
+    fn peek_editor_for_location(
+        &self,
+        location: &Location,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Editor> {
+        let project = self.project.clone();
+        cx.new(|cx| {
+            let mut editor = Editor::for_buffer(location.buffer.clone(), project, window, cx);
+            editor.change_selections(
+                SelectionEffects::scroll(Autoscroll::top_relative(2)),
+                window,
+                cx,
+                |selections| {
+                    selections.select_anchor_ranges([location.range.start..location.range.start])
+                },
+            );
+            editor
+        })
+    }
+
+    fn subscribe_to_peek_editor(
+        &self,
+        peek_editor: &Entity<Editor>,
+        cx: &mut Context<Self>,
+    ) -> Subscription {
+        cx.subscribe(peek_editor, |_, _, event: &EditorEvent, cx| {
+            if event == &EditorEvent::Focused {
+                cx.emit(EditorEvent::FocusedIn)
+            }
+        })
+    }
+}
+
+fn render_peek_block(
+    editor: WeakEntity<Editor>,
+    peek_editor: Entity<Editor>,
+    location: &Location,
+    index: usize,
+    count: usize,
+    height: u32,
+) -> RenderBlock {
+    let location = location.clone();
+    Arc::new(move |cx: &mut BlockContext| {
+        let buffer = location.buffer.read(cx.app);
+        let row = location.range.start.to_point(buffer).row + 1;
+        let title = match buffer.file() {
+            Some(file) => format!("{}:{row}", file.path().display(file.path_style(cx.app))),
+            None => format!("untitled:{row}"),
+        };
+        let focus_handle = editor.upgrade().map(|editor| editor.focus_handle(cx.app));
+        let button = |id: &'static str, icon: IconName| {
+            IconButton::new(id, icon)
+                .shape(IconButtonShape::Square)
+                .icon_size(IconSize::Small)
+        };
+
+        v_flex()
+            .id(cx.block_id)
+            .block_mouse_except_scroll()
+            .w_full()
+            .h(cx.line_height * height as f32)
+            .border_y_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                h_flex()
+                    .h(cx.line_height)
+                    .flex_none()
+                    .pl(cx.margins.gutter.full_width())
+                    .pr_2()
+                    .gap_1()
+                    .bg(cx.theme().colors().editor_subheader_background)
+                    .child(
+                        div()
+                            .flex_1()
+                            .min_w_0()
+                            .child(Label::new(title).size(LabelSize::Small).truncate()),
+                    )
+                    .when(count > 1, |header| {
+                        header
+                            .child(
+                                Label::new(format!("{} of {count}", index + 1))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(
+                                button("peek-previous", IconName::ArrowUp)
+                                    .tooltip({
+                                        let focus_handle = focus_handle.clone();
+                                        move |window, cx| match &focus_handle {
+                                            Some(focus_handle) => Tooltip::for_action_in(
+                                                "Previous Definition",
+                                                &PeekPreviousDefinition,
+                                                focus_handle,
+                                                window,
+                                                cx,
+                                            ),
+                                            None => Tooltip::simple("Previous Definition", cx),
+                                        }
+                                    })
+                                    .on_click({
+                                        let editor = editor.clone();
+                                        move |_, window, cx| {
+                                            editor
+                                                .update(cx, |editor, cx| {
+                                                    editor.page_peek_definition(false, window, cx)
+                                                })
+                                                .ok();
+                                        }
+                                    }),
+                            )
+                            .child(
+                                button("peek-next", IconName::ArrowDown)
+                                    .tooltip({
+                                        let focus_handle = focus_handle.clone();
+                                        move |window, cx| match &focus_handle {
+                                            Some(focus_handle) => Tooltip::for_action_in(
+                                                "Next Definition",
+                                                &PeekNextDefinition,
+                                                focus_handle,
+                                                window,
+                                                cx,
+                                            ),
+                                            None => Tooltip::simple("Next Definition", cx),
+                                        }
+                                    })
+                                    .on_click({
+                                        let editor = editor.clone();
+                                        move |_, window, cx| {
+                                            editor
+                                                .update(cx, |editor, cx| {
+                                                    editor.page_peek_definition(true, window, cx)
+                                                })
+                                                .ok();
+                                        }
+                                    }),
+                            )
+                    })
+                    .child(
+                        button("peek-shrink", IconName::Dash)
+                            .tooltip(Tooltip::text("Shrink"))
+                            .disabled(height <= MIN_PEEK_HEIGHT)
+                            .on_click({
+                                let editor = editor.clone();
+                                move |_, _, cx| {
+                                    editor
+                                        .update(cx, |editor, cx| {
+                                            editor.resize_peek_definition(false, cx)
+                                        })
+                                        .ok();
+                                }
+                            }),
+                    )
+                    .child(
+                        button("peek-grow", IconName::Plus)
+                            .tooltip(Tooltip::text("Grow"))
+                            .disabled(height >= MAX_PEEK_HEIGHT)
+                            .on_click({
+                                let editor = editor.clone();
+                                move |_, _, cx| {
+                                    editor
+                                        .update(cx, |editor, cx| {
+                                            editor.resize_peek_definition(true, cx)
+                                        })
+                                        .ok();
+                                }
+                            }),
+                    )
+                    .child(
+                        button("peek-open", IconName::ArrowUpRight)
+                            .tooltip(Tooltip::text("Open Definition"))
+                            .on_click({
+                                let editor = editor.clone();
+                                move |_, window, cx| {
+                                    editor
+                                        .update(cx, |editor, cx| {
+                                            editor.open_peeked_definition(window, cx)
+                                        })
+                                        .ok();
+                                }
+                            }),
+                    )
+                    .child(
+                        button("peek-close", IconName::Close)
+                            .tooltip(Tooltip::text("Close"))
+                            .on_click({
+                                let editor = editor.clone();
+                                move |_, _, cx| {
+                                    editor
+                                        .update(cx, |editor, cx| editor.hide_peek_definition(cx))
+                                        .ok();
+                                }
+                            }),
+                    ),
+            )
+            .child(div().flex_1().min_h_0().child(peek_editor.clone()))
+            .into_any_element()
+    })
+}
@@ -10,7 +10,8 @@ use db::{
 };
 use fs::MTime;
 use itertools::Itertools as _;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 use workspace::{ItemId, WorkspaceDb, WorkspaceId};
 
@@ -87,6 +88,16 @@ impl Column for SerializedEditor {
     }
 }
 
+/// How a file was last viewed in a workspace, so that reopening it restores the same view.
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub(crate) struct SerializedFileState {
+    pub(crate) scroll_top_row: u32,
+    pub(crate) scroll_offset: (f64, f64),
+    /// Ranges as `((start_row, start_column), (end_row, end_column))`.
+    pub(crate) folds: Vec<((u32, u32), (u32, u32))>,
+    pub(crate) selections: Vec<((u32, u32), (u32, u32))>,
+}
+
 pub struct EditorDb(db::sqlez::thread_safe_connection::ThreadSafeConnection);
 
 impl Domain for EditorDb {
@@ -121,6 +132,19 @@ impl Domain for EditorDb {
     //   start: usize,
     //   end: usize,
     // )
+    //
+    // editor_file_states(
+    //   workspace_id: usize,
+    //   path: String,
+    //   state: String, // JSON-serialized `SerializedFileState`
+    // )
+    //
+    // editor_selection_sets(
+    //   workspace_id: usize,
+    //   path: String,
+    //   name: String,
+    //   selections: String, // JSON-serialized `Vec<((u32, u32), (u32, u32))>`
+    // )
 
     const MIGRATIONS: &[&str] = &[
         sql! (
@@ -197,6 +221,29 @@ impl Domain for EditorDb {
                 ON DELETE CASCADE
             ) STRICT;
         ),
+        sql! (
+            CREATE TABLE editor_file_states (
+                workspace_id INTEGER NOT NULL,
+                path TEXT NOT NULL,
+                state TEXT NOT NULL,
+                PRIMARY KEY(workspace_id, path),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            ) STRICT;
+        ),
+        sql! (
+            CREATE TABLE editor_selection_sets (
+                workspace_id INTEGER NOT NULL,
+                path TEXT NOT NULL,
+                name TEXT NOT NULL,
+                selections TEXT NOT NULL,
+                PRIMARY KEY(workspace_id, path, name),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            ) STRICT;
+        ),
     ];
 }
 
@@ -281,6 +328,93 @@ impl EditorDb {
         }
     }
 
+    query! {
+        fn get_file_state_json(workspace_id: WorkspaceId, path: String) -> Result<Option<String>> {
+            SELECT state
+            FROM editor_file_states
+            WHERE workspace_id = ?1 AND path = ?2
+        }
+    }
+
+    query! {
+        async fn save_file_state_json(workspace_id: WorkspaceId, path: String, state: String) -> Result<()> {
+            INSERT INTO editor_file_states
+                (workspace_id, path, state)
+            VALUES
+                (?1, ?2, ?3)
+            ON CONFLICT DO UPDATE SET
+                state = ?3
+        }
+    }
+
+    query! {
+        fn get_selection_set_json(workspace_id: WorkspaceId, path: String, name: String) -> Result<Option<String>> {
+            SELECT selections
+            FROM editor_selection_sets
+            WHERE workspace_id = ?1 AND path = ?2 AND name = ?3
+        }
+    }
+
+    query! {
+        async fn save_selection_set_json(workspace_id: WorkspaceId, path: String, name: String, selections: String) -> Result<()> {
+            INSERT INTO editor_selection_sets
+                (workspace_id, path, name, selections)
+            VALUES
+                (?1, ?2, ?3, ?4)
+            ON CONFLICT DO UPDATE SET
+                selections = ?4
+        }
+    }
+
+    pub(crate) fn get_file_state(
+        &self,
+        workspace_id: WorkspaceId,
+        abs_path: &Path,
+    ) -> Result<Option<SerializedFileState>> {
+        let path = abs_path.to_string_lossy().into_owned();
+        self.get_file_state_json(workspace_id, path)?
+            .map(|json| Ok(serde_json::from_str(&json)?))
+            .transpose()
+    }
+
+    pub(crate) async fn save_file_state(
+        &self,
+        workspace_id: WorkspaceId,
+        abs_path: PathBuf,
+        state: SerializedFileState,
+    ) -> Result<()> {
+        let path = abs_path.to_string_lossy().into_owned();
+        let state = serde_json::to_string(&state)?;
+        self.save_file_state_json(workspace_id, path, state).await
+    }
+
+    /// Returns the selections saved under `name` for a file, as
+    /// `((start_row, start_column), (end_row, end_column))`.
+    pub(crate) fn get_selection_set(
+        &self,
+        workspace_id: WorkspaceId,
+        abs_path: &Path,
+        name: &str,
+    ) -> Result<Option<Vec<((u32, u32), (u32, u32))>>> {
+        let path = abs_path.to_string_lossy().into_owned();
+        self.get_selection_set_json(workspace_id, path, name.to_string())?
+            .map(|json| Ok(serde_json::from_str(&json)?))
+            .transpose()
+    }
+
+    pub(crate) async fn save_selection_set(
+        &self,
+        workspace_id: WorkspaceId,
+        abs_path: PathBuf,
+        name: String,
+        selections: Vec<((u32, u32), (u32, u32))>,
+    ) -> Result<()> {
+        let path = abs_path.to_string_lossy().into_owned();
+        let selections = serde_json::to_string(&selections)?;
+        self.save_selection_set_json(workspace_id, path, name, selections)
+            .await
+    }
+
     pub async fn save_editor_selections(
         &self,
         editor_id: ItemId,
@@ -465,4 +599,67 @@ mod tests {
             .unwrap();
         assert_eq!(have, serialized_editor);
     }
+
+    #[gpui::test]
+    async fn test_save_and_get_file_state() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        let path = Path::new("/root/file.rs");
+        assert_eq!(DB.get_file_state(workspace_id, path).unwrap(), None);
+
+        let state = SerializedFileState {
+            scroll_top_row: 12,
+            scroll_offset: (0., 0.5),
+            folds: vec![((3, 10), (8, 1))],
+            selections: vec![((14, 2), (14, 6)), ((20, 0), (20, 0))],
+        };
+        DB.save_file_state(workspace_id, path.to_path_buf(), state.clone())
+            .await
+            .unwrap();
+        assert_eq!(DB.get_file_state(workspace_id, path).unwrap(), Some(state));
+
+        let state = SerializedFileState {
+            scroll_top_row: 3,
+            ..SerializedFileState::default()
+        };
+        DB.save_file_state(workspace_id, path.to_path_buf(), state.clone())
+            .await
+            .unwrap();
+        assert_eq!(DB.get_file_state(workspace_id, path).unwrap(), Some(state));
+    }
+
+    #[gpui::test]
+    async fn test_save_and_get_selection_set() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+        let path = Path::new("/root/file.rs");
+        assert_eq!(DB.get_selection_set(workspace_id, path, "a").unwrap(), None);
+
+        let selections = vec![((14, 2), (14, 6)), ((20, 0), (20, 0))];
+        DB.save_selection_set(
+            workspace_id,
+            path.to_path_buf(),
+            "a".to_string(),
+            selections.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            DB.get_selection_set(workspace_id, path, "a").unwrap(),
+            Some(selections)
+        );
+        assert_eq!(DB.get_selection_set(workspace_id, path, "b").unwrap(), None);
+
+        let selections = vec![((1, 0), (1, 0))];
+        DB.save_selection_set(
+            workspace_id,
+            path.to_path_buf(),
+            "a".to_string(),
+            selections.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            DB.get_selection_set(workspace_id, path, "a").unwrap(),
+            Some(selections)
+        );
+    }
 }
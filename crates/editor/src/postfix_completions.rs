@@ -0,0 +1,139 @@
+//! Postfix completions, such as `expr.if`, which rewrite the expression before the dot using one of
+//! the language's postfix templates.
+use std::ops::Range;
+
+use gpui::{App, Entity};
+use language::{Buffer, BufferSnapshot, CharScopeContext, CodeLabel, point_to_lsp};
+use lsp::{CompletionItemKind, InsertTextFormat, LanguageServerId};
+use project::{Completion, CompletionSource, lsp_store::CompletionDocumentation};
+use snippet::Snippet;
+use text::{ToOffset as _, ToPointUtf16 as _};
+
+const RECEIVER_PLACEHOLDER: &str = "${receiver}";
+
+/// Returns a completion for every postfix template of the language at `buffer_position`, if the
+/// word being typed follows a dot that directly follows an expression.
+pub(crate) fn postfix_completions(
+    buffer: &Entity<Buffer>,
+    buffer_position: text::Anchor,
+    cx: &App,
+) -> Vec<Completion> {
+    let snapshot = buffer.read(cx).snapshot();
+    let settings = snapshot.settings_at(buffer_position, cx);
+    if !settings.completions.postfix {
+        return Vec::new();
+    }
+    let Some(language) = snapshot.language_at(buffer_position) else {
+        return Vec::new();
+    };
+    let mut templates = language.config().postfix_templates.clone();
+    for (name, template) in &settings.completions.postfix_templates {
+        templates.insert(name.clone(), template.clone());
+    }
+    templates.retain(|_, template| !template.is_empty());
+    if templates.is_empty() {
+        return Vec::new();
+    }
+
+    let position = buffer_position.to_offset(&snapshot);
+    let classifier = snapshot
+        .char_classifier_at(position)
+        .scope_context(Some(CharScopeContext::Completion));
+    let word_len = snapshot
+        .reversed_chars_at(position)
+        .take_while(|c| classifier.is_word(*c))
+        .map(char::len_utf8)
+        .sum::<usize>();
+    let Some(dot) = (position - word_len).checked_sub(1) else {
+        return Vec::new();
+    };
+    if snapshot.chars_at(dot).next() != Some('.') {
+        return Vec::new();
+    }
+    let Some(receiver_range) = receiver_range(&snapshot, dot) else {
+        return Vec::new();
+    };
+
+    let receiver = snapshot
+        .text_for_range(receiver_range.clone())
+        .collect::<String>();
+    let escaped_receiver = receiver
+        .replace('\\', "\\\\")
+        .replace('$', "\\$")
+        .replace('}', "\\}");
+    let start = snapshot.anchor_before(receiver_range.start);
+    let lsp_range = lsp::Range {
+        start: point_to_lsp(receiver_range.start.to_point_utf16(&snapshot)),
+        end: point_to_lsp(position.to_point_utf16(&snapshot)),
+    };
+
+    templates
+        .into_iter()
+        .map(|(name, template)| {
+            let new_text = template.replace(RECEIVER_PLACEHOLDER, &escaped_receiver);
+            let preview = Snippet::parse(&new_text)
+                .map(|snippet| snippet.text)
+                .unwrap_or_else(|_| new_text.clone());
+            Completion {
+                replace_range: start..buffer_position,
+                new_text: new_text.clone(),
+                source: CompletionSource::Lsp {
+                    insert_range: None,
+                    server_id: LanguageServerId(usize::MAX),
+                    resolved: true,
+                    lsp_completion: Box::new(lsp::CompletionItem {
+                        label: name.clone(),
+                        kind: Some(CompletionItemKind::SNIPPET),
+                        insert_text_format: Some(InsertTextFormat::SNIPPET),
+                        text_edit: Some(lsp::CompletionTextEdit::InsertAndReplace(
+                            lsp::InsertReplaceEdit {
+                                new_text,
+                                insert: lsp_range,
+                                replace: lsp_range,
+                            },
+                        )),
+                        filter_text: Some(name.clone()),
+                        sort_text: Some(char::MAX.to_string()),
+                        ..lsp::CompletionItem::default()
+                    }),
+                    lsp_defaults: None,
+                },
+                label: CodeLabel::plain(name, None),
+                icon_path: None,
+                documentation: Some(CompletionDocumentation::SingleLineAndMultiLinePlainText {
+                    single_line: preview
+                        .lines()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string()
+                        .into(),
+                    plain_text: Some(preview.into()),
+                }),
+                insert_text_mode: None,
+                confirm: None,
+            }
+        })
+        .collect()
+}
+
+/// Finds the expression ending at the dot: the largest syntax node that ends right before it.
+fn receiver_range(snapshot: &BufferSnapshot, dot: usize) -> Option<Range<usize>> {
+    let last_char = snapshot.reversed_chars_at(dot).next()?;
+    if last_char.is_whitespace() {
+        return None;
+    }
+    let last_char_range = dot - last_char.len_utf8()..dot;
+    let layer = snapshot.smallest_syntax_layer_containing(last_char_range.clone())?;
+    let mut node = layer
+        .node()
+        .descendant_for_byte_range(last_char_range.start, last_char_range.end)?;
+    if node.end_byte() != dot {
+        return None;
+    }
+    while let Some(parent) = node.parent()
+        && parent.end_byte() == dot
+    {
+        node = parent;
+    }
+    Some(node.byte_range())
+}
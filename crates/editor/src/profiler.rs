@@ -0,0 +1,275 @@
+//! An overlay for investigating editor performance, toggled with `dev::ToggleEditorProfiler`. It
+//! measures the time from a keystroke to the next paint of the editor, how long each frame takes
+//! to lay out and paint, and the cost of subsystems that run on every edit or cursor movement.
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use gpui::{AnyElement, App, ClipboardItem, Context, DispatchPhase, Entity, KeyDownEvent, Window};
+use serde::Serialize;
+use ui::prelude::*;
+use zed_actions::dev::ToggleEditorProfiler;
+
+use crate::Editor;
+
+/// The number of recent measurements kept for each metric.
+const MAX_SAMPLES: usize = 240;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ProfiledSubsystem {
+    /// Shaping the visible lines, with their syntax and semantic highlights.
+    Highlighting,
+    InlayHints,
+    MatchingBrackets,
+}
+
+impl ProfiledSubsystem {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Highlighting => "Highlighting",
+            Self::InlayHints => "Inlay hints",
+            Self::MatchingBrackets => "Bracket refresh",
+        }
+    }
+}
+
+#[derive(Default)]
+struct Samples(VecDeque<Duration>);
+
+impl Samples {
+    fn record(&mut self, duration: Duration) {
+        if self.0.len() == MAX_SAMPLES {
+            self.0.pop_front();
+        }
+        self.0.push_back(duration);
+    }
+
+    fn summary(&self) -> Option<Summary> {
+        let last = *self.0.back()?;
+        let mut sorted = self.0.iter().copied().collect::<Vec<_>>();
+        sorted.sort_unstable();
+        let p95 = sorted[(sorted.len() * 95).div_ceil(100) - 1];
+        let total = sorted.iter().sum::<Duration>();
+        Some(Summary {
+            samples: sorted.len(),
+            last_ms: millis(last),
+            mean_ms: millis(total / sorted.len() as u32),
+            p95_ms: millis(p95),
+            max_ms: millis(*sorted.last()?),
+        })
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000_000.
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct Summary {
+    samples: usize,
+    last_ms: f64,
+    mean_ms: f64,
+    p95_ms: f64,
+    max_ms: f64,
+}
+
+impl Summary {
+    fn description(&self) -> String {
+        format!(
+            "{:.1} · p95 {:.1} · max {:.1} ms",
+            self.last_ms, self.p95_ms, self.max_ms
+        )
+    }
+}
+
+/// The report copied by the overlay's "Copy as JSON" button, for attaching to bug reports.
+#[derive(Serialize)]
+struct ProfileReport {
+    keystroke_to_paint: Option<Summary>,
+    layout: Option<Summary>,
+    paint: Option<Summary>,
+    subsystems: BTreeMap<ProfiledSubsystem, Summary>,
+}
+
+#[derive(Default)]
+pub(crate) struct EditorProfiler {
+    pending_keystroke: Option<Instant>,
+    keystroke_to_paint: Samples,
+    layout: Samples,
+    paint: Samples,
+    subsystems: BTreeMap<ProfiledSubsystem, Samples>,
+}
+
+impl EditorProfiler {
+    fn record_subsystem(&mut self, subsystem: ProfiledSubsystem, duration: Duration) {
+        self.subsystems
+            .entry(subsystem)
+            .or_default()
+            .record(duration);
+    }
+
+    pub(crate) fn record_layout(&mut self, layout: Duration, highlighting: Duration) {
+        self.layout.record(layout);
+        self.record_subsystem(ProfiledSubsystem::Highlighting, highlighting);
+    }
+
+    /// Records a paint, which completes the response to a keystroke received since the last one.
+    pub(crate) fn record_paint(&mut self, paint: Duration) {
+        self.paint.record(paint);
+        if let Some(keystroke) = self.pending_keystroke.take() {
+            self.keystroke_to_paint.record(keystroke.elapsed());
+        }
+    }
+
+    fn report(&self) -> ProfileReport {
+        ProfileReport {
+            keystroke_to_paint: self.keystroke_to_paint.summary(),
+            layout: self.layout.summary(),
+            paint: self.paint.summary(),
+            subsystems: self
+                .subsystems
+                .iter()
+                .filter_map(|(subsystem, samples)| Some((*subsystem, samples.summary()?)))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn render(&self, editor: Entity<Editor>, cx: &App) -> AnyElement {
+        let report = self.report();
+        let row = |label: &'static str, summary: Option<&Summary>| {
+            h_flex()
+                .justify_between()
+                .gap_4()
+                .child(
+                    Label::new(label)
+                        .size(LabelSize::XSmall)
+                        .color(Color::Muted),
+                )
+                .child(
+                    Label::new(summary.map_or("–".to_string(), Summary::description))
+                        .size(LabelSize::XSmall)
+                        .buffer_font(cx),
+                )
+        };
+
+        v_flex()
+            .id("editor-profiler")
+            .occlude()
+            .p_2()
+            .gap_0p5()
+            .bg(cx.theme().colors().elevated_surface_background)
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .rounded_sm()
+            .child(Label::new("Editor Profiler").size(LabelSize::Small))
+            .child(row(
+                "Keystroke to paint",
+                report.keystroke_to_paint.as_ref(),
+            ))
+            .child(row("Layout", report.layout.as_ref()))
+            .child(row("Paint", report.paint.as_ref()))
+            .children(
+                report
+                    .subsystems
+                    .iter()
+                    .map(|(subsystem, summary)| row(subsystem.label(), Some(summary))),
+            )
+            .child(
+                Button::new("copy-editor-profile", "Copy as JSON")
+                    .label_size(LabelSize::XSmall)
+                    .on_click(move |_, _, cx| {
+                        let Some(report) = editor
+                            .read(cx)
+                            .profiler
+                            .as_ref()
+                            .map(|profiler| profiler.report())
+                        else {
+                            return;
+                        };
+                        if let Ok(json) = serde_json::to_string_pretty(&report) {
+                            cx.write_to_clipboard(ClipboardItem::new_string(json));
+                        }
+                    }),
+            )
+            .into_any_element()
+    }
+}
+
+/// Notes the time of every keystroke dispatched while `editor` is being profiled.
+pub(crate) fn register_keystroke_listener(editor: &Entity<Editor>, window: &mut Window) {
+    let editor = editor.clone();
+    window.on_key_event(move |_: &KeyDownEvent, phase, _, cx| {
+        if phase == DispatchPhase::Capture {
+            editor.update(cx, |editor, _| {
+                if let Some(profiler) = editor.profiler.as_mut() {
+                    profiler.pending_keystroke.get_or_insert_with(Instant::now);
+                }
+            });
+        }
+    });
+}
+
+impl Editor {
+    pub fn toggle_editor_profiler(
+        &mut self,
+        _: &ToggleEditorProfiler,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.profiler = match self.profiler {
+            Some(_) => None,
+            None => Some(EditorProfiler::default()),
+        };
+        cx.notify();
+    }
+
+    /// Runs `f`, recording how long it took when the profiler is shown.
+    pub(crate) fn profile<R>(
+        &mut self,
+        subsystem: ProfiledSubsystem,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        if self.profiler.is_none() {
+            return f(self);
+        }
+        let started = Instant::now();
+        let result = f(self);
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.record_subsystem(subsystem, started.elapsed());
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samples_summary() {
+        let mut samples = Samples::default();
+        assert_eq!(samples.summary(), None);
+
+        for ms in (1..=20).rev() {
+            samples.record(Duration::from_millis(ms));
+        }
+        assert_eq!(
+            samples.summary(),
+            Some(Summary {
+                samples: 20,
+                last_ms: 1.,
+                mean_ms: 10.5,
+                p95_ms: 19.,
+                max_ms: 20.,
+            })
+        );
+
+        // Only the most recent samples are kept.
+        for _ in 0..MAX_SAMPLES {
+            samples.record(Duration::from_millis(2));
+        }
+        assert_eq!(samples.summary().map(|summary| summary.max_ms), Some(2.));
+    }
+}
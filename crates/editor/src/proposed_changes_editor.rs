@@ -474,6 +474,14 @@ impl SemanticsProvider for BranchBufferSemanticsProvider {
         }
     }
 
+    fn supports_document_highlights(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
+        if let Some(buffer) = self.to_base(buffer, &[], cx) {
+            self.0.supports_document_highlights(&buffer, cx)
+        } else {
+            false
+        }
+    }
+
     fn document_highlights(
         &self,
         buffer: &Entity<Buffer>,
@@ -0,0 +1,135 @@
+//! Fixing the diagnostic under the cursor. Unlike the code actions menu, which lists everything
+//! that applies to the cursor's line, only the fixes that language servers offer for the
+//! diagnostic are shown, and a fix that a server marks as preferred is applied right away.
+use std::rc::Rc;
+
+use futures::future;
+use gpui::{Context, Task, UniformListScrollHandle, Window};
+use itertools::Itertools as _;
+use multi_buffer::ToOffset as _;
+use project::{CodeAction, LspAction};
+use task::TaskContext;
+use util::ResultExt as _;
+
+use crate::{
+    ConfirmCodeAction, Editor, QuickFix,
+    code_context_menus::{
+        AvailableCodeAction, CodeActionContents, CodeActionsMenu, CodeContextMenu,
+    },
+};
+
+fn is_quick_fix(action: &CodeAction) -> bool {
+    match &action.lsp_action {
+        LspAction::Action(action) => {
+            action.kind.as_ref().is_some_and(|kind| {
+                kind.as_str()
+                    .starts_with(lsp::CodeActionKind::QUICKFIX.as_str())
+            }) || action
+                .diagnostics
+                .as_ref()
+                .is_some_and(|diagnostics| !diagnostics.is_empty())
+        }
+        LspAction::Command(_) | LspAction::CodeLens(_) => false,
+    }
+}
+
+fn is_preferred(action: &CodeAction) -> bool {
+    matches!(&action.lsp_action, LspAction::Action(action) if action.is_preferred == Some(true))
+}
+
+impl Editor {
+    pub fn quick_fix(&mut self, _: &QuickFix, window: &mut Window, cx: &mut Context<Self>) {
+        let head = self.selections.newest_anchor().head();
+        let excerpt_id = head.excerpt_id;
+        let (buffer, range) = {
+            let multibuffer = self.buffer.read(cx);
+            let snapshot = multibuffer.snapshot(cx);
+            let head = head.to_offset(&snapshot);
+            // Fix the most severe of the diagnostics under the cursor, and the narrowest of those.
+            let Some(diagnostic_range) = snapshot
+                .diagnostics_in_range::<usize>(head..head)
+                .min_by_key(|entry| (entry.diagnostic.severity, entry.range.len()))
+                .map(|entry| entry.range)
+            else {
+                return;
+            };
+            let Some((buffer, start)) =
+                multibuffer.text_anchor_for_position(diagnostic_range.start, cx)
+            else {
+                return;
+            };
+            let Some((end_buffer, end)) =
+                multibuffer.text_anchor_for_position(diagnostic_range.end, cx)
+            else {
+                return;
+            };
+            if buffer != end_buffer {
+                return;
+            }
+            (buffer, start..end)
+        };
+
+        let providers = self.code_action_providers.clone();
+        let tasks = providers
+            .iter()
+            .map(|provider| provider.code_actions(&buffer, range.clone(), window, cx))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |editor, cx| {
+            let mut fixes = Vec::new();
+            for (provider, actions) in providers.into_iter().zip(future::join_all(tasks).await) {
+                if let Some(actions) = actions.log_err() {
+                    fixes.extend(actions.into_iter().filter(is_quick_fix).map(|action| {
+                        AvailableCodeAction {
+                            excerpt_id,
+                            action,
+                            provider: provider.clone(),
+                        }
+                    }));
+                }
+            }
+
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    if fixes.is_empty() {
+                        return Task::ready(Ok(()));
+                    }
+                    // Apply the fix right away when there's no choice to make.
+                    let fix_ix = fixes
+                        .iter()
+                        .positions(|fix| is_preferred(&fix.action))
+                        .exactly_one()
+                        .ok()
+                        .or_else(|| (fixes.len() == 1).then_some(0));
+
+                    crate::hover_popover::hide_hover(editor, cx);
+                    *editor.context_menu.borrow_mut() =
+                        Some(CodeContextMenu::CodeActions(CodeActionsMenu {
+                            buffer,
+                            actions: CodeActionContents::new(
+                                None,
+                                Some(Rc::from(fixes)),
+                                Vec::new(),
+                                TaskContext::default(),
+                            ),
+                            selected_item: 0,
+                            scroll_handle: UniformListScrollHandle::default(),
+                            deployed_from: None,
+                        }));
+                    cx.notify();
+                    fix_ix
+                        .and_then(|item_ix| {
+                            editor.confirm_code_action(
+                                &ConfirmCodeAction {
+                                    item_ix: Some(item_ix),
+                                },
+                                window,
+                                cx,
+                            )
+                        })
+                        .unwrap_or_else(|| Task::ready(Ok(())))
+                })?
+                .await
+        })
+        .detach_and_log_err(cx);
+    }
+}
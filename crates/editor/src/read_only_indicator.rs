@@ -0,0 +1,66 @@
+use gpui::{
+    Context, Entity, IntoElement, ParentElement, Render, Subscription, WeakEntity, Window, div,
+};
+use ui::{ButtonCommon, Clickable, FluentBuilder, IconButton, IconName, IconSize, Tooltip};
+use workspace::{StatusItemView, item::ItemHandle};
+
+use crate::{Editor, ToggleReadOnly};
+
+/// Shows a lock in the status bar while the active editor is locked with [`ToggleReadOnly`].
+#[derive(Default)]
+pub struct ReadOnlyIndicator {
+    editor: Option<WeakEntity<Editor>>,
+    edits_locked: bool,
+    _observe_active_editor: Option<Subscription>,
+}
+
+impl ReadOnlyIndicator {
+    fn update_locked(&mut self, editor: Entity<Editor>, _: &mut Window, cx: &mut Context<Self>) {
+        let edits_locked = editor.read(cx).edits_locked(cx);
+        if edits_locked != self.edits_locked {
+            self.edits_locked = edits_locked;
+            cx.notify();
+        }
+    }
+}
+
+impl Render for ReadOnlyIndicator {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div().when(self.edits_locked, |el| {
+            el.child(
+                IconButton::new("read-only", IconName::FileLock)
+                    .icon_size(IconSize::Small)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        if let Some(editor) = this.editor.as_ref().and_then(|e| e.upgrade()) {
+                            editor.update(cx, |editor, cx| {
+                                editor.toggle_read_only(&ToggleReadOnly, window, cx)
+                            });
+                        }
+                    }))
+                    .tooltip(|window, cx| {
+                        Tooltip::for_action("Unlock Editor", &ToggleReadOnly, window, cx)
+                    }),
+            )
+        })
+    }
+}
+
+impl StatusItemView for ReadOnlyIndicator {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(editor) = active_pane_item.and_then(|item| item.downcast::<Editor>()) {
+            self._observe_active_editor = Some(cx.observe_in(&editor, window, Self::update_locked));
+            self.editor = Some(editor.downgrade());
+            self.update_locked(editor, window, cx);
+        } else {
+            self.edits_locked = false;
+            self.editor = None;
+            self._observe_active_editor = None;
+        }
+        cx.notify();
+    }
+}
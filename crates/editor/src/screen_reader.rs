@@ -0,0 +1,127 @@
+//! Announcements for screen readers. The editor's text isn't exposed to the platform's
+//! accessibility tree, so changes to the cursor, the selection, the highlighted completion and
+//! the diagnostics under the cursor are described in a sentence and read aloud instead.
+use std::time::Duration;
+
+use gpui::{App, Context, Window};
+use lsp::DiagnosticSeverity;
+use multi_buffer::{MultiBufferSnapshot, ToPoint as _};
+use settings::Settings as _;
+use text::Point;
+
+use crate::{Editor, EditorSettings};
+
+/// How long the cursor has to rest before it's announced, so that holding down an arrow key
+/// doesn't queue up an announcement for every line passed on the way.
+const ANNOUNCEMENT_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Selections longer than this are announced by their length rather than read out.
+const MAX_ANNOUNCED_SELECTION_CHARS: usize = 200;
+
+/// Reads `message` aloud, when screen reader announcements are enabled.
+pub(crate) fn announce(message: &str, window: &Window, cx: &App) {
+    if EditorSettings::get_global(cx).screen_reader_announcements {
+        window.announce(message);
+    }
+}
+
+impl Editor {
+    pub(crate) fn schedule_cursor_announcement(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !EditorSettings::get_global(cx).screen_reader_announcements
+            || !self.mode.is_full()
+            || !self.focus_handle.is_focused(window)
+        {
+            return;
+        }
+
+        self.announce_cursor = cx.spawn_in(window, async move |editor, cx| {
+            cx.background_executor().timer(ANNOUNCEMENT_DEBOUNCE).await;
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    window.announce(&editor.cursor_announcement(cx));
+                })
+                .ok();
+        });
+    }
+
+    /// Describes the newest selection: its position, the selected text, the diagnostic under the
+    /// cursor and where the bracket matching the one at the cursor is.
+    pub(crate) fn cursor_announcement(&self, cx: &mut App) -> String {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let selection = self.selections.newest::<usize>(cx);
+        let head = selection.head();
+        let position = head.to_point(&snapshot);
+
+        let mut parts = vec![format!(
+            "Line {}, column {}",
+            position.row + 1,
+            column_for_point(&snapshot, position) + 1
+        )];
+
+        if !selection.is_empty() {
+            let selected_text = snapshot
+                .text_for_range(selection.range())
+                .collect::<String>();
+            let char_count = selected_text.chars().count();
+            if char_count > MAX_ANNOUNCED_SELECTION_CHARS {
+                parts.push(format!("{char_count} characters selected"));
+            } else {
+                parts.push(format!("Selected: {selected_text}"));
+            }
+        }
+
+        let selection_count = self.selections.count();
+        if selection_count > 1 {
+            parts.push(format!("{selection_count} cursors"));
+        }
+
+        if let Some(diagnostic) = snapshot
+            .diagnostics_in_range::<usize>(head..head)
+            .filter(|entry| entry.range.start <= head && head <= entry.range.end)
+            .min_by_key(|entry| entry.diagnostic.severity)
+        {
+            let severity = match diagnostic.diagnostic.severity {
+                DiagnosticSeverity::ERROR => "Error",
+                DiagnosticSeverity::WARNING => "Warning",
+                DiagnosticSeverity::INFORMATION => "Info",
+                _ => "Hint",
+            };
+            parts.push(format!("{severity}: {}", diagnostic.diagnostic.message));
+        }
+
+        if selection.is_empty()
+            && let Some((open, close)) =
+                snapshot.innermost_enclosing_bracket_ranges(head..head, None)
+        {
+            let matching = if open.start == head || open.end == head {
+                Some(close.start)
+            } else if close.start == head || close.end == head {
+                Some(open.start)
+            } else {
+                None
+            };
+            if let Some(matching) = matching {
+                let matching = matching.to_point(&snapshot);
+                parts.push(format!(
+                    "Matching bracket on line {}, column {}",
+                    matching.row + 1,
+                    column_for_point(&snapshot, matching) + 1
+                ));
+            }
+        }
+
+        parts.join(". ")
+    }
+}
+
+/// The column of `point` in characters, as screen readers count them, rather than in bytes.
+fn column_for_point(snapshot: &MultiBufferSnapshot, point: Point) -> usize {
+    snapshot
+        .text_for_range(Point::new(point.row, 0)..point)
+        .map(|chunk| chunk.chars().count())
+        .sum()
+}
@@ -1,6 +1,7 @@
 mod actions;
 pub(crate) mod autoscroll;
 pub(crate) mod scroll_amount;
+mod scroll_lock;
 
 use crate::editor_settings::ScrollBeyondLastLine;
 use crate::{
@@ -16,9 +17,11 @@ use gpui::{Along, App, Axis, Context, Pixels, Task, Window, point, px};
 use language::language_settings::{AllLanguageSettings, SoftWrap};
 use language::{Bias, Point};
 pub use scroll_amount::ScrollAmount;
+use scroll_lock::ScrollLock;
 use settings::Settings;
 use std::{
     cmp::Ordering,
+    rc::Rc,
     time::{Duration, Instant},
 };
 use ui::scrollbars::ScrollbarAutoHide;
@@ -124,6 +127,28 @@ impl OngoingScroll {
     }
 }
 
+/// An eased transition of the rendered scroll position towards the scroll anchor.
+#[derive(Clone, Copy, Debug)]
+struct ScrollAnimation {
+    /// How many rows the rendered scroll position was away from the scroll anchor
+    /// when the animation started.
+    initial_offset: ScrollOffset,
+    started_at: Instant,
+    duration: Duration,
+}
+
+impl ScrollAnimation {
+    fn offset(&self, now: Instant) -> Option<ScrollOffset> {
+        let elapsed = now.saturating_duration_since(self.started_at);
+        if elapsed >= self.duration {
+            return None;
+        }
+        let remaining = 1. - elapsed.as_secs_f64() / self.duration.as_secs_f64();
+        // Ease out, so that the animation starts fast and settles slowly.
+        Some(self.initial_offset * remaining.powi(3))
+    }
+}
+
 #[derive(Copy, Clone, Default, PartialEq, Eq)]
 pub enum ScrollbarThumbState {
     #[default]
@@ -169,6 +194,9 @@ pub struct ScrollManager {
     visible_column_count: Option<f64>,
     forbid_vertical_scroll: bool,
     minimap_thumb_state: Option<ScrollbarThumbState>,
+    animation: Option<ScrollAnimation>,
+    /// The editors whose scroll positions are kept in sync with this one's, if any.
+    scroll_lock: Option<Rc<ScrollLock>>,
 }
 
 impl ScrollManager {
@@ -186,6 +214,8 @@ impl ScrollManager {
             visible_column_count: None,
             forbid_vertical_scroll: false,
             minimap_thumb_state: None,
+            animation: None,
+            scroll_lock: None,
         }
     }
 
@@ -211,6 +241,19 @@ impl ScrollManager {
         self.anchor.scroll_position(snapshot)
     }
 
+    /// How many rows the rendered scroll position currently lags behind the scroll anchor,
+    /// if a smooth scroll is in progress.
+    pub fn animation_offset(&self) -> Option<ScrollOffset> {
+        self.animation
+            .as_ref()
+            .and_then(|animation| animation.offset(Instant::now()))
+    }
+
+    /// The top of the viewport as it is currently rendered, taking any smooth scroll into account.
+    fn rendered_scroll_top(&self, snapshot: &DisplaySnapshot) -> ScrollOffset {
+        self.scroll_position(snapshot).y + self.animation_offset().unwrap_or_default()
+    }
+
     fn set_scroll_position(
         &mut self,
         scroll_position: gpui::Point<ScrollOffset>,
@@ -493,12 +536,14 @@ impl Editor {
     ) {
         let opened_first_time = self.scroll_manager.visible_line_count.is_none();
         self.scroll_manager.visible_line_count = Some(lines);
+        self.report_visible_buffer_ranges(cx);
         if opened_first_time {
             cx.spawn_in(window, async move |editor, cx| {
                 editor
                     .update_in(cx, |editor, window, cx| {
                         editor.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
                         editor.refresh_colors(false, None, window, cx);
+                        editor.refresh_custom_highlights(cx);
                     })
                     .ok()
             })
@@ -516,6 +561,7 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        self.scroll_manager.animation = None;
         let mut delta = scroll_delta;
         if self.scroll_manager.forbid_vertical_scroll {
             delta.y = 0.0;
@@ -532,6 +578,7 @@ impl Editor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> WasScrolled {
+        self.scroll_manager.animation = None;
         let mut position = scroll_position;
         if self.scroll_manager.forbid_vertical_scroll {
             let current_position = self.scroll_position(cx);
@@ -540,6 +587,29 @@ impl Editor {
         self.set_scroll_position_internal(position, true, false, window, cx)
     }
 
+    /// Animates the rendered scroll position from `previous_scroll_top` to the
+    /// current scroll anchor, when smooth scrolling is enabled.
+    pub(crate) fn animate_scroll_from(
+        &mut self,
+        previous_scroll_top: ScrollOffset,
+        cx: &mut Context<Self>,
+    ) {
+        let smooth_scroll = EditorSettings::get_global(cx).smooth_scroll;
+        if !smooth_scroll.enabled || !self.mode.is_full() {
+            return;
+        }
+        let initial_offset = previous_scroll_top - self.scroll_position(cx).y;
+        if initial_offset == 0. {
+            return;
+        }
+        self.scroll_manager.animation = Some(ScrollAnimation {
+            initial_offset,
+            started_at: Instant::now(),
+            duration: Duration::from_millis(smooth_scroll.duration),
+        });
+        cx.notify();
+    }
+
     /// Scrolls so that `row` is at the top of the editor view.
     pub fn set_scroll_top_row(
         &mut self,
@@ -613,8 +683,18 @@ impl Editor {
             cx,
         );
 
+        // Keep the input method's candidate window next to the text that's being composed.
+        if self.ime_transaction.is_some() {
+            window.invalidate_character_coordinates();
+        }
+
         self.refresh_inlay_hints(InlayHintRefreshReason::NewLinesShown, cx);
         self.refresh_colors(false, None, window, cx);
+        self.refresh_custom_highlights(cx);
+        self.report_visible_buffer_ranges(cx);
+        if editor_was_scrolled.0 && local {
+            self.sync_locked_scroll_positions(&display_map, window, cx);
+        }
         editor_was_scrolled
     }
 
@@ -630,12 +710,13 @@ impl Editor {
         cx: &mut Context<Self>,
     ) {
         hide_hover(self, cx);
+        self.scroll_manager.animation = None;
         let workspace_id = self.workspace.as_ref().and_then(|workspace| workspace.1);
         let top_row = scroll_anchor
             .anchor
             .to_point(&self.buffer().read(cx).snapshot(cx))
             .row;
-        self.scroll_manager.set_anchor(
+        let was_scrolled = self.scroll_manager.set_anchor(
             scroll_anchor,
             top_row,
             true,
@@ -644,6 +725,10 @@ impl Editor {
             window,
             cx,
         );
+        if was_scrolled.0 {
+            let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+            self.sync_locked_scroll_positions(&display_map, window, cx);
+        }
     }
 
     pub(crate) fn set_scroll_anchor_remote(
@@ -693,6 +778,8 @@ impl Editor {
         let Some(mut visible_column_count) = self.visible_column_count() else {
             return;
         };
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        let rendered_scroll_top = self.scroll_manager.rendered_scroll_top(&display_map);
 
         // If the user has a preferred line length, and has the editor
         // configured to wrap at the preferred line length, or bounded to it,
@@ -726,7 +813,9 @@ impl Editor {
                 amount.columns(visible_column_count),
                 amount.lines(visible_line_count),
             );
-        self.set_scroll_position(new_position, window, cx);
+        if self.set_scroll_position(new_position, window, cx).0 {
+            self.animate_scroll_from(rendered_scroll_top, cx);
+        }
     }
 
     /// Returns an ordering. The newest selection is:
@@ -1,11 +1,12 @@
 use crate::{
-    DisplayRow, Editor, EditorMode, LineWithInvisibles, RowExt, SelectionEffects,
+    DisplayRow, Editor, EditorMode, EditorSettings, LineWithInvisibles, RowExt, SelectionEffects,
     display_map::ToDisplayPoint,
     scroll::{ScrollOffset, WasScrolled},
 };
 use gpui::{Bounds, Context, Pixels, Window};
 use language::Point;
 use multi_buffer::Anchor;
+use settings::Settings as _;
 use std::cmp;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -88,6 +89,7 @@ pub enum AutoscrollStrategy {
     Bottom,
     TopRelative(usize),
     BottomRelative(usize),
+    Typewriter,
 }
 
 impl AutoscrollStrategy {
@@ -136,9 +138,17 @@ impl Editor {
             WasScrolled(false)
         };
 
-        let Some((autoscroll, local)) = autoscroll_request else {
+        let Some((mut autoscroll, local)) = autoscroll_request else {
             return (NeedsHorizontalAutoscroll(false), editor_was_scrolled);
         };
+        // With typewriter scrolling, the strategies that follow the cursor keep it pinned instead.
+        if let Autoscroll::Strategy(AutoscrollStrategy::Fit | AutoscrollStrategy::Newest, anchor) =
+            autoscroll
+            && self.typewriter_scrolling_enabled(cx)
+        {
+            autoscroll = Autoscroll::Strategy(AutoscrollStrategy::Typewriter, anchor);
+        }
+        let rendered_scroll_top = self.scroll_manager.rendered_scroll_top(&display_map);
 
         let mut target_top;
         let mut target_bottom;
@@ -169,7 +179,10 @@ impl Editor {
             let selections_fit = target_bottom - target_top <= visible_lines;
             if matches!(
                 autoscroll,
-                Autoscroll::Strategy(AutoscrollStrategy::Newest, _)
+                Autoscroll::Strategy(
+                    AutoscrollStrategy::Newest | AutoscrollStrategy::Typewriter,
+                    _
+                )
             ) || (matches!(autoscroll, Autoscroll::Strategy(AutoscrollStrategy::Fit, _))
                 && !selections_fit)
             {
@@ -263,8 +276,20 @@ impl Editor {
                 scroll_position.y = target_bottom + lines as ScrollOffset;
                 self.set_scroll_position_internal(scroll_position, local, true, window, cx)
             }
+            AutoscrollStrategy::Typewriter => {
+                let position = EditorSettings::get_global(cx).typewriter_scrolling.position;
+                let offset = ((visible_lines - (target_bottom - target_top)) * position)
+                    .floor()
+                    .max(0.);
+                scroll_position.y = (target_top - offset).max(0.0);
+                self.set_scroll_position_internal(scroll_position, local, true, window, cx)
+            }
         };
 
+        if was_autoscrolled.0 {
+            self.animate_scroll_from(rendered_scroll_top, cx);
+        }
+
         self.scroll_manager.last_autoscroll = Some((
             self.scroll_manager.anchor.offset,
             target_top,
@@ -0,0 +1,151 @@
+//! Keeping the scroll positions of the editors in several panes in sync, such as two views of
+//! the same buffer, or a test and its source. The locked editors share a `ScrollLock`, which
+//! lists all of them, and scrolling any of them scrolls the others.
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use gpui::{Context, Entity, WeakEntity, Window, point};
+use workspace::item::ItemHandle as _;
+
+use crate::{Editor, RowExt as _, actions::ToggleScrollLock, display_map::DisplaySnapshot};
+
+pub(crate) struct ScrollLock {
+    editors: RefCell<Vec<WeakEntity<Editor>>>,
+    /// Whether the editors are scrolled to the same fraction of their length, rather than to
+    /// the same row.
+    proportional: bool,
+    /// Set while the editors are being scrolled to the position of one of them, so that they
+    /// don't scroll each other in turn.
+    syncing: Cell<bool>,
+}
+
+impl Editor {
+    pub fn toggle_scroll_lock(
+        &mut self,
+        action: &ToggleScrollLock,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.scroll_manager.scroll_lock.is_some() {
+            self.unlock_scroll(cx);
+            return;
+        }
+
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        let this = cx.entity();
+        let editors = workspace
+            .read(cx)
+            .panes()
+            .iter()
+            .filter_map(|pane| pane.read(cx).active_item()?.act_as::<Editor>(cx))
+            .filter(|editor| *editor != this)
+            .collect::<Vec<_>>();
+        self.lock_scroll_with(editors, action.proportional, window, cx);
+    }
+
+    /// Keeps the scroll positions of `editors` in sync with this editor's, scrolling them to its
+    /// current position.
+    pub(crate) fn lock_scroll_with(
+        &mut self,
+        editors: Vec<Entity<Editor>>,
+        proportional: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if editors.is_empty() {
+            return;
+        }
+        self.unlock_scroll(cx);
+        for editor in &editors {
+            editor.update(cx, |editor, cx| editor.unlock_scroll(cx));
+        }
+
+        let lock = Rc::new(ScrollLock {
+            editors: RefCell::new(
+                editors
+                    .iter()
+                    .map(|editor| editor.downgrade())
+                    .chain([cx.entity().downgrade()])
+                    .collect(),
+            ),
+            proportional,
+            syncing: Cell::new(false),
+        });
+        for editor in &editors {
+            editor.update(cx, |editor, _| {
+                editor.scroll_manager.scroll_lock = Some(lock.clone());
+            });
+        }
+        self.scroll_manager.scroll_lock = Some(lock);
+
+        let display_map = self.display_map.update(cx, |map, cx| map.snapshot(cx));
+        self.sync_locked_scroll_positions(&display_map, window, cx);
+    }
+
+    /// Stops keeping the scroll positions of the editors locked with this one in sync.
+    fn unlock_scroll(&mut self, cx: &mut Context<Self>) {
+        let Some(lock) = self.scroll_manager.scroll_lock.take() else {
+            return;
+        };
+        let this = cx.entity().downgrade();
+        let editors = lock.editors.take();
+        for editor in editors.into_iter().filter(|editor| *editor != this) {
+            editor
+                .update(cx, |editor, _| editor.scroll_manager.scroll_lock = None)
+                .ok();
+        }
+    }
+
+    pub fn is_scroll_locked(&self) -> bool {
+        self.scroll_manager.scroll_lock.is_some()
+    }
+
+    /// Scrolls the editors locked with this one to its scroll position.
+    pub(super) fn sync_locked_scroll_positions(
+        &mut self,
+        display_map: &DisplaySnapshot,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(lock) = self.scroll_manager.scroll_lock.clone() else {
+            return;
+        };
+        if lock.syncing.replace(true) {
+            return;
+        }
+
+        let scroll_top = self.scroll_manager.anchor.scroll_position(display_map).y;
+        let fraction = scroll_top / display_map.max_point().row().as_f64().max(1.);
+        let this = cx.entity().downgrade();
+        lock.editors
+            .borrow_mut()
+            .retain(|editor| editor.upgrade().is_some());
+        let editors = lock.editors.borrow().clone();
+        for editor in editors.into_iter().filter(|editor| *editor != this) {
+            editor
+                .update(cx, |editor, cx| {
+                    let display_map = editor.display_map.update(cx, |map, cx| map.snapshot(cx));
+                    let scroll_top = if lock.proportional {
+                        fraction * display_map.max_point().row().as_f64()
+                    } else {
+                        scroll_top
+                    };
+                    let scroll_left = editor.scroll_manager.anchor.scroll_position(&display_map).x;
+                    editor.set_scroll_position_taking_display_map(
+                        point(scroll_left, scroll_top),
+                        true,
+                        false,
+                        display_map,
+                        window,
+                        cx,
+                    );
+                })
+                .ok();
+        }
+        lock.syncing.set(false);
+    }
+}
@@ -0,0 +1,108 @@
+//! Saving the current selections under a name and restoring them later. Saved selections follow
+//! the edits made while the editor is open, and are also persisted per file in the workspace
+//! database, so that they can be restored after the file is reopened.
+use std::path::PathBuf;
+
+use gpui::{App, Context, Window};
+use language::{Bias, Point};
+use multi_buffer::ToPoint as _;
+use util::ResultExt as _;
+use workspace::WorkspaceId;
+
+use crate::{
+    Autoscroll, Editor, SelectionEffects,
+    actions::{RestoreSelectionSet, SaveSelectionSet},
+    persistence::DB,
+};
+
+impl Editor {
+    pub fn save_selection_set(
+        &mut self,
+        action: &SaveSelectionSet,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let name = action.name.clone().unwrap_or_default();
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let ranges = self
+            .selections
+            .disjoint_anchors()
+            .iter()
+            .map(|selection| selection.start..selection.end)
+            .collect::<Vec<_>>();
+
+        if let Some((workspace_id, abs_path)) = self.selection_set_location(cx) {
+            let selections = ranges
+                .iter()
+                .map(|range| {
+                    let range = range.start.to_point(&snapshot)..range.end.to_point(&snapshot);
+                    (
+                        (range.start.row, range.start.column),
+                        (range.end.row, range.end.column),
+                    )
+                })
+                .collect();
+            let name = name.clone();
+            cx.background_spawn(async move {
+                DB.save_selection_set(workspace_id, abs_path, name, selections)
+                    .await
+                    .log_err();
+            })
+            .detach();
+        }
+        self.selection_sets.insert(name, ranges);
+    }
+
+    pub fn restore_selection_set(
+        &mut self,
+        action: &RestoreSelectionSet,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let name = action.name.clone().unwrap_or_default();
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let ranges = if let Some(ranges) = self.selection_sets.get(&name) {
+            ranges
+                .iter()
+                .map(|range| range.start.to_point(&snapshot)..range.end.to_point(&snapshot))
+                .collect::<Vec<_>>()
+        } else {
+            let Some((workspace_id, abs_path)) = self.selection_set_location(cx) else {
+                return;
+            };
+            let Some(selections) = DB
+                .get_selection_set(workspace_id, &abs_path, &name)
+                .log_err()
+                .flatten()
+            else {
+                return;
+            };
+            selections
+                .into_iter()
+                .map(|(start, end)| {
+                    snapshot.clip_point(Point::new(start.0, start.1), Bias::Left)
+                        ..snapshot.clip_point(Point::new(end.0, end.1), Bias::Right)
+                })
+                .collect()
+        };
+        if ranges.is_empty() {
+            return;
+        }
+
+        self.change_selections(
+            SelectionEffects::scroll(Autoscroll::newest()),
+            window,
+            cx,
+            |s| s.select_ranges(ranges),
+        );
+    }
+
+    /// The workspace and file that the selection sets of this editor are persisted for, if it
+    /// edits a single file.
+    fn selection_set_location(&self, cx: &App) -> Option<(WorkspaceId, PathBuf)> {
+        let workspace_id = self.workspace.as_ref()?.1?;
+        let buffer = self.buffer.read(cx).as_singleton()?;
+        let abs_path = project::File::from_dyn(buffer.read(cx).file())?.abs_path(cx);
+        Some((workspace_id, abs_path))
+    }
+}
@@ -29,6 +29,14 @@ pub enum SignatureHelpHiddenBy {
     Selection,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignatureHelpTrigger {
+    /// Signature help was requested explicitly, or the cursor entered a new argument list.
+    Invoked,
+    /// The cursor moved or the text changed inside the argument list of the shown popover.
+    Retrigger,
+}
+
 impl Editor {
     pub fn toggle_auto_signature_help_menu(
         &mut self,
@@ -42,7 +50,7 @@ impl Editor {
             .or_else(|| Some(!EditorSettings::get_global(cx).auto_signature_help));
         match self.auto_signature_help {
             Some(true) => {
-                self.show_signature_help(&ShowSignatureHelp, window, cx);
+                self.request_signature_help(SignatureHelpTrigger::Invoked, window, cx);
             }
             Some(false) => {
                 self.hide_signature_help(cx, SignatureHelpHiddenBy::AutoClose);
@@ -78,9 +86,9 @@ impl Editor {
         &mut self,
         old_cursor_position: &Anchor,
         cx: &mut Context<Self>,
-    ) -> bool {
+    ) -> Option<SignatureHelpTrigger> {
         if !(self.signature_help_state.is_shown() || self.auto_signature_help_enabled(cx)) {
-            return false;
+            return None;
         }
         let newest_selection = self.selections.newest::<usize>(cx);
         let head = newest_selection.head();
@@ -88,7 +96,7 @@ impl Editor {
         if !newest_selection.is_empty() && head != newest_selection.tail() {
             self.signature_help_state
                 .hide(SignatureHelpHiddenBy::Selection);
-            return false;
+            return None;
         }
 
         let buffer_snapshot = self.buffer().read(cx).snapshot(cx);
@@ -132,23 +140,24 @@ impl Editor {
             (None, None) => {
                 self.signature_help_state
                     .hide(SignatureHelpHiddenBy::AutoClose);
-                false
+                None
             }
             (Some(_), None) => {
                 self.signature_help_state
                     .hide(SignatureHelpHiddenBy::AutoClose);
-                false
+                None
             }
-            (None, Some(_)) => true,
+            (None, Some(_)) => Some(SignatureHelpTrigger::Invoked),
             (Some(previous), Some(current)) => {
-                let condition = self.signature_help_state.hidden_by_selection()
-                    || previous != current
-                    || (previous == current && self.signature_help_state.is_shown());
-                if !condition {
+                if previous == current && self.signature_help_state.is_shown() {
+                    Some(SignatureHelpTrigger::Retrigger)
+                } else if previous != current || self.signature_help_state.hidden_by_selection() {
+                    Some(SignatureHelpTrigger::Invoked)
+                } else {
                     self.signature_help_state
                         .hide(SignatureHelpHiddenBy::AutoClose);
+                    None
                 }
-                condition
             }
         }
     }
@@ -158,6 +167,19 @@ impl Editor {
         _: &ShowSignatureHelp,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) {
+        self.request_signature_help(SignatureHelpTrigger::Invoked, window, cx);
+    }
+
+    /// Requests signature help at the newest cursor.
+    ///
+    /// When retriggered while editing inside the same argument list, the popover stays visible
+    /// if the server momentarily returns nothing, and the overload picked by the user is kept.
+    pub(super) fn request_signature_help(
+        &mut self,
+        trigger: SignatureHelpTrigger,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         if self.pending_rename.is_some() || self.has_visible_completions_menu() {
             return;
@@ -185,9 +207,11 @@ impl Editor {
                         let Some(mut signature_help) =
                             signature_help.unwrap_or_default().into_iter().next()
                         else {
-                            editor
-                                .signature_help_state
-                                .hide(SignatureHelpHiddenBy::AutoClose);
+                            if trigger == SignatureHelpTrigger::Invoked {
+                                editor
+                                    .signature_help_state
+                                    .hide(SignatureHelpHiddenBy::AutoClose);
+                            }
                             return;
                         };
 
@@ -232,15 +256,32 @@ impl Editor {
                             .collect::<Vec<_>>();
 
                         if signatures.is_empty() {
-                            editor
-                                .signature_help_state
-                                .hide(SignatureHelpHiddenBy::AutoClose);
+                            if trigger == SignatureHelpTrigger::Invoked {
+                                editor
+                                    .signature_help_state
+                                    .hide(SignatureHelpHiddenBy::AutoClose);
+                            }
                             return;
                         }
 
-                        let current_signature = signature_help
-                            .active_signature
-                            .min(signatures.len().saturating_sub(1));
+                        let same_overloads = |popover: &SignatureHelpPopover| {
+                            popover
+                                .signatures
+                                .iter()
+                                .map(|signature| &signature.label)
+                                .eq(signatures.iter().map(|signature| &signature.label))
+                        };
+                        let current_signature = editor
+                            .signature_help_state
+                            .popover
+                            .as_ref()
+                            .filter(|popover| same_overloads(popover))
+                            .map(|popover| popover.current_signature)
+                            .unwrap_or_else(|| {
+                                signature_help
+                                    .active_signature
+                                    .min(signatures.len().saturating_sub(1))
+                            });
 
                         let signature_help_popover = SignatureHelpPopover {
                             style,
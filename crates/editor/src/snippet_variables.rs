@@ -0,0 +1,86 @@
+//! Resolves the TextMate variables, such as `$TM_FILENAME` or `$CURRENT_YEAR`, used in snippets.
+use std::path::Path;
+
+use chrono::Local;
+use gpui::App;
+use language::{BufferSnapshot, Point};
+use uuid::Uuid;
+
+/// Returns the value of the snippet variable `name` for a snippet inserted at `position`, or
+/// `None` if the variable is unknown or has no value here.
+pub(crate) fn resolve_snippet_variable(
+    name: &str,
+    buffer: &BufferSnapshot,
+    position: usize,
+    selected_text: &str,
+    cx: &App,
+) -> Option<String> {
+    let row = buffer.offset_to_point(position).row;
+    let date_format = match name {
+        "CURRENT_YEAR" => Some("%Y"),
+        "CURRENT_YEAR_SHORT" => Some("%y"),
+        "CURRENT_MONTH" => Some("%m"),
+        "CURRENT_MONTH_NAME" => Some("%B"),
+        "CURRENT_MONTH_NAME_SHORT" => Some("%b"),
+        "CURRENT_DATE" => Some("%d"),
+        "CURRENT_DAY_NAME" => Some("%A"),
+        "CURRENT_DAY_NAME_SHORT" => Some("%a"),
+        "CURRENT_HOUR" => Some("%H"),
+        "CURRENT_MINUTE" => Some("%M"),
+        "CURRENT_SECOND" => Some("%S"),
+        "CURRENT_SECONDS_UNIX" => Some("%s"),
+        "CURRENT_TIMEZONE_OFFSET" => Some("%:z"),
+        _ => None,
+    };
+    if let Some(date_format) = date_format {
+        return Some(Local::now().format(date_format).to_string());
+    }
+
+    let value = match name {
+        "TM_SELECTED_TEXT" => selected_text.to_string(),
+        "TM_CURRENT_LINE" => buffer
+            .text_for_range(Point::new(row, 0)..Point::new(row, buffer.line_len(row)))
+            .collect(),
+        "TM_CURRENT_WORD" => {
+            let (range, _) = buffer.surrounding_word(position, None);
+            buffer.text_for_range(range).collect()
+        }
+        "TM_LINE_INDEX" => row.to_string(),
+        "TM_LINE_NUMBER" => (row + 1).to_string(),
+        "TM_FILENAME" => buffer.file()?.file_name(cx).to_string(),
+        "TM_FILENAME_BASE" => Path::new(buffer.file()?.file_name(cx))
+            .file_stem()?
+            .to_string_lossy()
+            .into_owned(),
+        "TM_FILEPATH" => {
+            let file = buffer.file()?;
+            match file.as_local() {
+                Some(file) => file.abs_path(cx).to_string_lossy().into_owned(),
+                None => file.full_path(cx).to_string_lossy().into_owned(),
+            }
+        }
+        "TM_DIRECTORY" => buffer
+            .file()?
+            .as_local()?
+            .abs_path(cx)
+            .parent()?
+            .to_string_lossy()
+            .into_owned(),
+        "RELATIVE_FILEPATH" => {
+            let file = buffer.file()?;
+            file.path().display(file.path_style(cx)).into_owned()
+        }
+        "CLIPBOARD" => cx.read_from_clipboard()?.text()?,
+        "LINE_COMMENT" => buffer
+            .language_scope_at(position)?
+            .line_comment_prefixes()
+            .first()?
+            .trim_end()
+            .to_string(),
+        "RANDOM" => format!("{:06}", rand::random::<u32>() % 1_000_000),
+        "RANDOM_HEX" => format!("{:06x}", rand::random::<u32>() & 0xff_ffff),
+        "UUID" => Uuid::new_v4().to_string(),
+        _ => return None,
+    };
+    Some(value)
+}
@@ -0,0 +1,199 @@
+//! Selecting the contents of string literals, or whole string literals with their quotes.
+use std::ops::Range;
+
+use gpui::{Context, Window};
+use language::{Point, Selection, SelectionGoal};
+use multi_buffer::{MultiBufferRow, MultiBufferSnapshot, ToPoint as _};
+
+use crate::{Editor, HideMouseCursorOrigin, SelectAroundString, SelectInsideString};
+
+impl Editor {
+    pub fn select_inside_string(
+        &mut self,
+        _: &SelectInsideString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_string(true, window, cx);
+    }
+
+    pub fn select_around_string(
+        &mut self,
+        _: &SelectAroundString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_string(false, window, cx);
+    }
+
+    /// Selects the contents of the innermost string literal containing each selection, or the
+    /// whole literal when `inside` is false. When that's already selected, the next enclosing
+    /// string literal is selected, such as the template string around an interpolation.
+    fn select_string(&mut self, inside: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        let buffer = self.buffer.read(cx).snapshot(cx);
+        let mut selected_string = false;
+        let new_selections = self
+            .selections
+            .all::<usize>(cx)
+            .into_iter()
+            .map(|selection| {
+                let range = enclosing_string_literals(&buffer, selection.range())
+                    .into_iter()
+                    .map(|literal| {
+                        if inside {
+                            literal.contents
+                        } else {
+                            literal.range
+                        }
+                    })
+                    .find(|range| {
+                        range.start <= selection.start
+                            && selection.end <= range.end
+                            && *range != selection.range()
+                    });
+                match range {
+                    Some(range) => {
+                        selected_string = true;
+                        Selection {
+                            id: selection.id,
+                            start: range.start,
+                            end: range.end,
+                            reversed: false,
+                            goal: SelectionGoal::None,
+                        }
+                    }
+                    None => selection,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if selected_string {
+            self.change_selections(Default::default(), window, cx, |s| {
+                s.select(new_selections);
+            });
+        }
+    }
+}
+
+struct StringLiteral {
+    range: Range<usize>,
+    contents: Range<usize>,
+}
+
+/// Returns the string literals containing `range`, from the innermost one outwards.
+///
+/// Literals are found in the syntax tree, or by matching quotes on the line of `range` when the
+/// buffer has no syntax tree or no string nodes around `range`.
+fn enclosing_string_literals(
+    buffer: &MultiBufferSnapshot,
+    range: Range<usize>,
+) -> Vec<StringLiteral> {
+    let mut literals = Vec::new();
+    let mut node_range = range.clone();
+    while let Some((node, ancestor_range)) = buffer.syntax_ancestor(node_range.clone()) {
+        node_range = ancestor_range;
+        if is_string_node(node.kind()) {
+            let text = buffer
+                .text_for_range(node_range.clone())
+                .collect::<String>();
+            if let Some(contents) = string_contents(&text) {
+                literals.push(StringLiteral {
+                    range: node_range.clone(),
+                    contents: node_range.start + contents.start..node_range.start + contents.end,
+                });
+            }
+        }
+    }
+    if !literals.is_empty() {
+        return literals;
+    }
+
+    let row = range.start.to_point(buffer).row;
+    let line_start = buffer.point_to_offset(Point::new(row, 0));
+    let line_end = buffer.point_to_offset(Point::new(row, buffer.line_len(MultiBufferRow(row))));
+    let line = buffer
+        .text_for_range(line_start..line_end)
+        .collect::<String>();
+    let mut quoted = quoted_ranges(&line)
+        .into_iter()
+        .map(|quoted| StringLiteral {
+            range: line_start + quoted.start..line_start + quoted.end,
+            contents: line_start + quoted.start + 1..line_start + quoted.end - 1,
+        })
+        .filter(|literal| literal.range.start <= range.start && range.end <= literal.range.end)
+        .collect::<Vec<_>>();
+    quoted.sort_by_key(|literal| literal.range.len());
+    quoted
+}
+
+fn is_string_node(kind: &str) -> bool {
+    kind.contains("string")
+        && !["content", "fragment", "escape", "start", "end"]
+            .iter()
+            .any(|part| kind.contains(part))
+}
+
+/// Returns the range of the contents of a string literal's `text`, without its prefix, such as
+/// `r#` or `f`, and its quotes, which can be tripled.
+fn string_contents(text: &str) -> Option<Range<usize>> {
+    let quote_start = text.find(['"', '\'', '`'])?;
+    let quote = text[quote_start..].chars().next()?;
+    let rest = &text[quote_start..];
+    let quote_len = if rest.len() >= 6 && rest.starts_with(&quote.to_string().repeat(3)) {
+        3
+    } else {
+        1
+    };
+    let start = quote_start + quote_len;
+    let end = text.trim_end_matches('#').len().checked_sub(quote_len)?;
+    (start <= end && text[end..].starts_with(quote)).then_some(start..end)
+}
+
+/// Returns the ranges of the quoted strings in a line, including their quotes, skipping quotes
+/// escaped with a backslash.
+fn quoted_ranges(line: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut open_quote: Option<(char, usize)> = None;
+    let mut escaped = false;
+    for (ix, ch) in line.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if matches!(ch, '"' | '\'' | '`') {
+            match open_quote {
+                Some((quote, start)) if quote == ch => {
+                    ranges.push(start..ix + 1);
+                    open_quote = None;
+                }
+                Some(_) => {}
+                None => open_quote = Some((ch, ix)),
+            }
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_contents() {
+        assert_eq!(string_contents(r#""abc""#), Some(1..4));
+        assert_eq!(string_contents(r#""""#), Some(1..1));
+        assert_eq!(string_contents(r##"r#"a"b"#"##), Some(3..6));
+        assert_eq!(string_contents(r#"f'{x}'"#), Some(2..5));
+        assert_eq!(string_contents(r#""""doc""""#), Some(3..6));
+        assert_eq!(string_contents("abc"), None);
+    }
+
+    #[test]
+    fn test_quoted_ranges() {
+        assert_eq!(
+            quoted_ranges(r#"say "it's \"ok\"" and 'bye'"#),
+            [4..17, 22..27]
+        );
+    }
+}
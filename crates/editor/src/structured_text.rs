@@ -0,0 +1,370 @@
+//! Pretty-printing and minifying of JSON and XML in the selections, without a language server,
+//! such as for payloads pasted from logs into a scratch buffer.
+use std::ops::Range;
+
+use anyhow::{Context as _, Result, anyhow};
+use gpui::{Context, Window};
+use multi_buffer::{MultiBufferRow, ToPoint as _};
+use serde::Serialize as _;
+use workspace::{Toast, notifications::NotificationId};
+
+use crate::{
+    Autoscroll, Editor, FormatSelectionAsJson, FormatSelectionAsXml, MinifySelectionAsJson,
+    MinifySelectionAsXml, SelectionEffects,
+};
+
+/// Rewrites `text` as JSON with one value per line, indented by `indent` per level. Keys keep
+/// their order.
+pub fn pretty_print_json(text: &str, indent: &str) -> Result<String> {
+    let value = serde_json::from_str::<serde_json::Value>(text).context("Invalid JSON")?;
+    let mut output = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut output, formatter);
+    value.serialize(&mut serializer)?;
+    Ok(String::from_utf8(output)?)
+}
+
+/// Rewrites `text` as JSON without any whitespace outside of strings.
+pub fn minify_json(text: &str) -> Result<String> {
+    let value = serde_json::from_str::<serde_json::Value>(text).context("Invalid JSON")?;
+    Ok(serde_json::to_string(&value)?)
+}
+
+#[derive(Debug, PartialEq)]
+enum XmlToken<'a> {
+    Open {
+        name: &'a str,
+        text: &'a str,
+    },
+    Close {
+        name: &'a str,
+        text: &'a str,
+    },
+    /// Self-closing elements, comments, CDATA sections, processing instructions and doctypes.
+    Leaf(&'a str),
+    Text(&'a str),
+}
+
+/// Splits `text` into tags and the text between them, checking that every element is closed.
+fn tokenize_xml(text: &str) -> Result<Vec<XmlToken<'_>>> {
+    let mut tokens = Vec::new();
+    let mut open_elements = Vec::new();
+    let mut ix = 0;
+    while ix < text.len() {
+        let Some(tag_start) = text[ix..].find('<').map(|offset| ix + offset) else {
+            tokens.push(XmlToken::Text(&text[ix..]));
+            break;
+        };
+        if tag_start > ix {
+            tokens.push(XmlToken::Text(&text[ix..tag_start]));
+        }
+
+        let rest = &text[tag_start..];
+        let delimited = [
+            ("<!--", "-->"),
+            ("<![CDATA[", "]]>"),
+            ("<?", "?>"),
+            ("<!", ">"),
+        ]
+        .into_iter()
+        .find(|(start, _)| rest.starts_with(start));
+        if let Some((start, end)) = delimited {
+            let len = rest[start.len()..]
+                .find(end)
+                .map(|offset| start.len() + offset + end.len())
+                .with_context(|| format!("Unterminated `{start}`"))?;
+            tokens.push(XmlToken::Leaf(&rest[..len]));
+            ix = tag_start + len;
+            continue;
+        }
+
+        let mut quote = None;
+        let len = rest
+            .char_indices()
+            .find(|&(_, c)| {
+                match quote {
+                    Some(q) if c == q => quote = None,
+                    Some(_) => {}
+                    None if c == '"' || c == '\'' => quote = Some(c),
+                    None => return c == '>',
+                }
+                false
+            })
+            .map(|(offset, _)| offset + 1)
+            .context("Unterminated tag")?;
+        let tag = &rest[..len];
+        let name = |start: usize| {
+            tag[start..]
+                .split(|c: char| c.is_whitespace() || c == '/' || c == '>')
+                .next()
+                .unwrap_or_default()
+        };
+        if tag.starts_with("</") {
+            let name = name(2);
+            match open_elements.pop() {
+                Some(open) if open == name => {}
+                Some(open) => return Err(anyhow!("Expected `</{open}>`, found `{tag}`")),
+                None => return Err(anyhow!("Unexpected `{tag}`")),
+            }
+            tokens.push(XmlToken::Close { name, text: tag });
+        } else if tag.ends_with("/>") {
+            tokens.push(XmlToken::Leaf(tag));
+        } else {
+            let name = name(1);
+            open_elements.push(name);
+            tokens.push(XmlToken::Open { name, text: tag });
+        }
+        ix = tag_start + len;
+    }
+    if let Some(open) = open_elements.pop() {
+        return Err(anyhow!("`<{open}>` is never closed"));
+    }
+    Ok(tokens)
+}
+
+/// Rewrites `text` as XML with one tag per line, indented by `indent` per level. Elements that
+/// only contain text stay on one line, and the text between tags is trimmed.
+pub fn pretty_print_xml(text: &str, indent: &str) -> Result<String> {
+    let tokens = tokenize_xml(text)?;
+    let mut lines = Vec::new();
+    let mut depth = 0;
+    let mut ix = 0;
+    while ix < tokens.len() {
+        let line = match tokens[ix] {
+            XmlToken::Open { name, text } => match tokens.get(ix + 1..ix + 3) {
+                Some(
+                    [
+                        XmlToken::Text(content),
+                        XmlToken::Close {
+                            name: close,
+                            text: end,
+                        },
+                    ],
+                ) if *close == name => {
+                    ix += 2;
+                    format!("{text}{}{end}", content.trim())
+                }
+                _ => match tokens.get(ix + 1) {
+                    Some(XmlToken::Close { text: end, .. }) => {
+                        ix += 1;
+                        format!("{text}{end}")
+                    }
+                    _ => {
+                        lines.push(format!("{}{text}", indent.repeat(depth)));
+                        depth += 1;
+                        ix += 1;
+                        continue;
+                    }
+                },
+            },
+            XmlToken::Close { text, .. } => {
+                depth = depth.saturating_sub(1);
+                text.to_string()
+            }
+            XmlToken::Leaf(text) => text.to_string(),
+            XmlToken::Text(text) if text.trim().is_empty() => {
+                ix += 1;
+                continue;
+            }
+            XmlToken::Text(text) => text.trim().to_string(),
+        };
+        lines.push(format!("{}{line}", indent.repeat(depth)));
+        ix += 1;
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Rewrites `text` as XML without whitespace between tags.
+pub fn minify_xml(text: &str) -> Result<String> {
+    let tokens = tokenize_xml(text)?;
+    Ok(tokens
+        .into_iter()
+        .map(|token| match token {
+            XmlToken::Open { text, .. } | XmlToken::Close { text, .. } | XmlToken::Leaf(text) => {
+                text
+            }
+            XmlToken::Text(text) => text.trim(),
+        })
+        .collect())
+}
+
+impl Editor {
+    pub fn format_selection_as_json(
+        &mut self,
+        _: &FormatSelectionAsJson,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.reformat_selections(pretty_print_json, window, cx);
+    }
+
+    pub fn minify_selection_as_json(
+        &mut self,
+        _: &MinifySelectionAsJson,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.reformat_selections(|text, _| minify_json(text), window, cx);
+    }
+
+    pub fn format_selection_as_xml(
+        &mut self,
+        _: &FormatSelectionAsXml,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.reformat_selections(pretty_print_xml, window, cx);
+    }
+
+    pub fn minify_selection_as_xml(
+        &mut self,
+        _: &MinifySelectionAsXml,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.reformat_selections(|text, _| minify_xml(text), window, cx);
+    }
+
+    /// Replaces the text of every non-empty selection, or of the whole buffer if all selections
+    /// are empty, with `reformat(text, indent)`, where `indent` is one level of indentation in
+    /// the buffer's settings. Selections that fail to be reformatted are left unchanged.
+    fn reformat_selections(
+        &mut self,
+        reformat: impl Fn(&str, &str) -> Result<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.read_only(cx) {
+            return;
+        }
+        let mut ranges = self
+            .selections
+            .all::<usize>(cx)
+            .into_iter()
+            .filter(|selection| !selection.is_empty())
+            .map(|selection| selection.range())
+            .collect::<Vec<_>>();
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        if ranges.is_empty() {
+            ranges.push(0..snapshot.len());
+        }
+
+        let mut edits = Vec::<(Range<usize>, String)>::new();
+        let mut error = None;
+        for range in ranges {
+            let text = snapshot.text_for_range(range.clone()).collect::<String>();
+            let trimmed_start = text.len() - text.trim_start().len();
+            let trimmed_end = text.len() - text.trim_end().len();
+            if text.trim().is_empty() {
+                continue;
+            }
+            let range = range.start + trimmed_start..range.end - trimmed_end;
+
+            let settings = multi_buffer.language_settings_at(range.start, cx);
+            let indent = if settings.hard_tabs {
+                "\t".to_string()
+            } else {
+                " ".repeat(settings.tab_size.get() as usize)
+            };
+            match reformat(text.trim(), &indent) {
+                Ok(new_text) => {
+                    // Nested lines keep the indentation of the line that the selection starts on.
+                    let row = range.start.to_point(&snapshot).row;
+                    let base_indent = snapshot
+                        .indent_size_for_line(MultiBufferRow(row))
+                        .chars()
+                        .collect::<String>();
+                    let new_text = new_text.replace('\n', &format!("\n{base_indent}"));
+                    edits.push((range, new_text));
+                }
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+        }
+
+        if let Some(error) = error {
+            self.show_reformat_error(error, cx);
+        }
+        if edits.is_empty() {
+            return;
+        }
+
+        let mut delta = 0isize;
+        let new_ranges = edits
+            .iter()
+            .map(|(range, new_text)| {
+                let start = (range.start as isize + delta) as usize;
+                delta += new_text.len() as isize - range.len() as isize;
+                start..start + new_text.len()
+            })
+            .collect::<Vec<_>>();
+        self.transact(window, cx, |this, window, cx| {
+            this.buffer.update(cx, |buffer, cx| {
+                buffer.edit(edits, None, cx);
+            });
+            this.change_selections(
+                SelectionEffects::scroll(Autoscroll::fit()),
+                window,
+                cx,
+                |s| s.select_ranges(new_ranges),
+            );
+        });
+    }
+
+    fn show_reformat_error(&self, error: anyhow::Error, cx: &mut Context<Self>) {
+        struct ReformatError;
+        let message = format!("{error:#}");
+        let Some(workspace) = self.workspace() else {
+            log::warn!("failed to reformat selection: {message}");
+            return;
+        };
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(NotificationId::unique::<ReformatError>(), message).autohide(),
+                cx,
+            )
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json() {
+        let json = r#" {"b": [1, 2.5, {}], "a": {"nested": "x y"}, "c": []} "#;
+        assert_eq!(
+            pretty_print_json(json, "  ").unwrap(),
+            "{\n  \"b\": [\n    1,\n    2.5,\n    {}\n  ],\n  \"a\": {\n    \"nested\": \"x y\"\n  },\n  \"c\": []\n}"
+        );
+        assert_eq!(
+            minify_json(json).unwrap(),
+            r#"{"b":[1,2.5,{}],"a":{"nested":"x y"},"c":[]}"#
+        );
+        assert!(minify_json("{\"a\": }").is_err());
+    }
+
+    #[test]
+    fn test_xml() {
+        let xml = "<?xml version=\"1.0\"?><root a=\"1 > 0\"><item>one</item>\n  <item/><!-- note --><empty></empty><list><x>y</x></list></root>";
+        assert_eq!(
+            pretty_print_xml(xml, "\t").unwrap(),
+            "<?xml version=\"1.0\"?>\n<root a=\"1 > 0\">\n\t<item>one</item>\n\t<item/>\n\t<!-- note -->\n\t<empty></empty>\n\t<list>\n\t\t<x>y</x>\n\t</list>\n</root>"
+        );
+        assert_eq!(
+            minify_xml(&pretty_print_xml(xml, "  ").unwrap()).unwrap(),
+            "<?xml version=\"1.0\"?><root a=\"1 > 0\"><item>one</item><item/><!-- note --><empty></empty><list><x>y</x></list></root>"
+        );
+        assert_eq!(
+            minify_xml("<a><b></a>").unwrap_err().to_string(),
+            "Expected `</b>`, found `</a>`"
+        );
+        assert_eq!(
+            minify_xml("<a>").unwrap_err().to_string(),
+            "`<a>` is never closed"
+        );
+    }
+}
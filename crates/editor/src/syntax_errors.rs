@@ -0,0 +1,157 @@
+//! Underlining the syntax errors that tree-sitter finds when parsing a buffer, such as unclosed
+//! strings and stray tokens, so that they're visible in languages without a language server.
+use std::ops::Range;
+
+use gpui::{App, Context, HighlightStyle, UnderlineStyle, Window, px};
+use language::{Bias, Point};
+use multi_buffer::{Anchor, ToPoint as _};
+use settings::Settings as _;
+use text::ToOffset as _;
+use theme::ActiveTheme as _;
+
+use crate::{
+    Autoscroll, Editor, EditorSettings, SelectionEffects,
+    actions::{GoToNextSyntaxError, GoToPreviousSyntaxError, ToggleSyntaxErrors},
+};
+
+struct SyntaxErrorHighlight;
+
+impl Editor {
+    fn syntax_errors_enabled(&self, cx: &App) -> bool {
+        self.mode.is_full()
+            && self
+                .show_syntax_errors
+                .unwrap_or_else(|| EditorSettings::get_global(cx).show_syntax_errors)
+    }
+
+    pub fn toggle_syntax_errors(
+        &mut self,
+        _: &ToggleSyntaxErrors,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_syntax_errors = Some(!self.syntax_errors_enabled(cx));
+        self.refresh_syntax_errors(cx);
+    }
+
+    pub(crate) fn refresh_syntax_errors(&mut self, cx: &mut Context<Self>) {
+        if !self.syntax_errors_enabled(cx) {
+            self.clear_highlights::<SyntaxErrorHighlight>(cx);
+            return;
+        }
+
+        let ranges = self.syntax_error_ranges(cx);
+        if ranges.is_empty() {
+            self.clear_highlights::<SyntaxErrorHighlight>(cx);
+        } else {
+            let style = HighlightStyle {
+                underline: Some(UnderlineStyle {
+                    color: Some(cx.theme().status().error),
+                    thickness: px(1.),
+                    wavy: true,
+                }),
+                ..HighlightStyle::default()
+            };
+            self.highlight_text::<SyntaxErrorHighlight>(ranges, style, cx);
+        }
+    }
+
+    pub fn go_to_next_syntax_error(
+        &mut self,
+        _: &GoToNextSyntaxError,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let starts = self.syntax_error_starts(cx);
+        let head = self.selections.newest::<Point>(cx).head();
+        let destination = starts
+            .iter()
+            .find(|start| **start > head)
+            .or(starts.first())
+            .copied();
+        self.go_to_syntax_error(destination, window, cx);
+    }
+
+    pub fn go_to_previous_syntax_error(
+        &mut self,
+        _: &GoToPreviousSyntaxError,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let starts = self.syntax_error_starts(cx);
+        let head = self.selections.newest::<Point>(cx).head();
+        let destination = starts
+            .iter()
+            .rev()
+            .find(|start| **start < head)
+            .or(starts.last())
+            .copied();
+        self.go_to_syntax_error(destination, window, cx);
+    }
+
+    fn go_to_syntax_error(
+        &mut self,
+        position: Option<Point>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(position) = position else {
+            return;
+        };
+        self.unfold_ranges(&[position..position], false, false, cx);
+        self.change_selections(
+            SelectionEffects::scroll(Autoscroll::center()),
+            window,
+            cx,
+            |s| s.select_ranges([position..position]),
+        );
+    }
+
+    fn syntax_error_starts(&self, cx: &App) -> Vec<Point> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut starts = self
+            .syntax_error_ranges(cx)
+            .into_iter()
+            .map(|range| range.start.to_point(&snapshot))
+            .collect::<Vec<_>>();
+        starts.dedup();
+        starts
+    }
+
+    /// Returns the syntax errors in the excerpts of the editor, in order. Missing tokens, which
+    /// take up no space, are extended over the character before them so that they can be seen.
+    fn syntax_error_ranges(&self, cx: &App) -> Vec<Range<Anchor>> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut ranges = Vec::new();
+        for (excerpt_id, buffer, excerpt_range) in snapshot.excerpts() {
+            let context = excerpt_range.context.start.to_offset(buffer)
+                ..excerpt_range.context.end.to_offset(buffer);
+            ranges.extend(
+                buffer
+                    .syntax_error_ranges(context.clone())
+                    .into_iter()
+                    .filter_map(|mut range| {
+                        if range.is_empty() {
+                            range.start =
+                                buffer.clip_offset(range.start.saturating_sub(1), Bias::Left);
+                        }
+                        let start = range.start.max(context.start);
+                        let end = range.end.min(context.end);
+                        Some(
+                            snapshot.anchor_in_excerpt(excerpt_id, buffer.anchor_after(start))?
+                                ..snapshot
+                                    .anchor_in_excerpt(excerpt_id, buffer.anchor_before(end))?,
+                        )
+                    }),
+            );
+        }
+        ranges
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn syntax_error_highlights(&self, cx: &App) -> Vec<Range<Anchor>> {
+        self.text_highlights::<SyntaxErrorHighlight>(cx)
+            .map(|(_, ranges)| ranges.to_vec())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,262 @@
+//! Pass/fail badges shown next to tests that were run from the editor, such as from the gutter's
+//! run indicators, which rerun the test or open its output when clicked.
+use std::time::{Duration, Instant};
+
+use gpui::{Context, Task, WeakEntity, Window};
+use multi_buffer::{Anchor, ToPoint as _};
+use project::TaskSourceKind;
+use task::ResolvedTask;
+use ui::{Tooltip, h_flex, prelude::*};
+use util::post_inc;
+
+use crate::{Editor, VirtualText, VirtualTextId, VirtualTextPlacement};
+
+pub(crate) struct TestRun {
+    id: usize,
+    position: Anchor,
+    task_source_kind: TaskSourceKind,
+    task: ResolvedTask,
+    status: TestRunStatus,
+    virtual_text: Option<VirtualTextId>,
+    _run: Task<()>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TestRunStatus {
+    Running { started: Instant },
+    Finished { success: bool, duration: Duration },
+}
+
+/// Whether the task runs tests, judging by the tags of the runnable it was created for.
+fn is_test_task(task: &ResolvedTask) -> bool {
+    task.original_task()
+        .tags
+        .iter()
+        .any(|tag| tag.contains("test"))
+}
+
+impl Editor {
+    /// Runs a task for the runnable at `position`, annotating its definition with the result if
+    /// the task runs tests.
+    pub(crate) fn schedule_runnable_task(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        task: ResolvedTask,
+        position: Option<Anchor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        match position {
+            Some(position) if is_test_task(&task) => {
+                let status = workspace.update(cx, |workspace, cx| {
+                    workspace.schedule_resolved_task_with_status(
+                        task_source_kind.clone(),
+                        task.clone(),
+                        false,
+                        window,
+                        cx,
+                    )
+                });
+                self.start_test_run(task_source_kind, task, position, status, window, cx);
+            }
+            _ => workspace.update(cx, |workspace, cx| {
+                workspace.schedule_resolved_task(task_source_kind, task, false, window, cx)
+            }),
+        }
+    }
+
+    /// Shows the test at `position` as running until `status` resolves to whether it passed.
+    pub(crate) fn start_test_run(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        task: ResolvedTask,
+        position: Anchor,
+        status: Task<Option<bool>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // A test's previous result is replaced by its new run.
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let row = position.to_point(&snapshot).row;
+        let mut stale_annotations = Vec::new();
+        self.test_runs.retain(|run| {
+            let stale = run.position.to_point(&snapshot).row == row;
+            if stale {
+                stale_annotations.extend(run.virtual_text);
+            }
+            !stale
+        });
+        self.remove_virtual_text(stale_annotations, cx);
+
+        let id = post_inc(&mut self.next_test_run_id);
+        let started = Instant::now();
+        let run = cx.spawn_in(window, async move |editor, cx| {
+            // Keep the elapsed time of the running test up to date.
+            let _ticker = cx.spawn({
+                let editor = editor.clone();
+                async move |cx| {
+                    loop {
+                        cx.background_executor().timer(Duration::from_secs(1)).await;
+                        if editor.update(cx, |_, cx| cx.notify()).is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+            let success = status.await;
+            editor
+                .update(cx, |editor, cx| {
+                    editor.finish_test_run(id, success, started.elapsed(), cx)
+                })
+                .ok();
+        });
+        self.test_runs.push(TestRun {
+            id,
+            position,
+            task_source_kind,
+            task,
+            status: TestRunStatus::Running { started },
+            virtual_text: None,
+            _run: run,
+        });
+        self.refresh_test_run_annotation(id, cx);
+    }
+
+    fn finish_test_run(
+        &mut self,
+        id: usize,
+        success: Option<bool>,
+        duration: Duration,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(ix) = self.test_runs.iter().position(|run| run.id == id) else {
+            return;
+        };
+        match success {
+            Some(success) => {
+                self.test_runs[ix].status = TestRunStatus::Finished { success, duration };
+                self.refresh_test_run_annotation(id, cx);
+            }
+            // The task was cancelled or could not be spawned, so there's no result to show.
+            None => {
+                let run = self.test_runs.remove(ix);
+                self.remove_virtual_text(run.virtual_text, cx);
+            }
+        }
+    }
+
+    fn refresh_test_run_annotation(&mut self, id: usize, cx: &mut Context<Self>) {
+        let editor = cx.weak_entity();
+        let Some(run) = self.test_runs.iter_mut().find(|run| run.id == id) else {
+            return;
+        };
+        let stale_annotation = run.virtual_text.take();
+        let status = run.status;
+        let annotation = VirtualText::widget(
+            run.position,
+            VirtualTextPlacement::EndOfLine,
+            move |_, _| render_test_run_status(id, status, editor.clone()),
+        );
+        let virtual_text = self.insert_virtual_text([annotation], cx).pop();
+        if let Some(run) = self.test_runs.iter_mut().find(|run| run.id == id) {
+            run.virtual_text = virtual_text;
+        }
+        self.remove_virtual_text(stale_annotation, cx);
+    }
+
+    fn open_test_run(&mut self, id: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(run) = self.test_runs.iter().find(|run| run.id == id) else {
+            return;
+        };
+        let TestRunStatus::Finished { success, .. } = run.status else {
+            return;
+        };
+        let task_source_kind = run.task_source_kind.clone();
+        let task = run.task.clone();
+        let position = run.position;
+
+        if !success
+            && let Some(workspace) = self.workspace()
+            && workspace.update(cx, |workspace, cx| {
+                workspace.reveal_task_output(&task.resolved, window, cx)
+            })
+        {
+            return;
+        }
+        self.schedule_runnable_task(task_source_kind, task, Some(position), window, cx);
+    }
+
+    /// Get the rows of the tests run from this editor, and whether they passed, or `None` if
+    /// they are still running.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn test_run_results(&self, cx: &App) -> Vec<(u32, Option<bool>)> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        self.test_runs
+            .iter()
+            .map(|run| {
+                let success = match run.status {
+                    TestRunStatus::Running { .. } => None,
+                    TestRunStatus::Finished { success, .. } => Some(success),
+                };
+                (run.position.to_point(&snapshot).row, success)
+            })
+            .collect()
+    }
+}
+
+fn render_test_run_status(
+    id: usize,
+    status: TestRunStatus,
+    editor: WeakEntity<Editor>,
+) -> AnyElement {
+    let (icon, color, label, tooltip) = match status {
+        TestRunStatus::Running { started } => (
+            IconName::ArrowCircle,
+            Color::Muted,
+            format!("Running… {}", format_duration(started.elapsed())),
+            None,
+        ),
+        TestRunStatus::Finished {
+            success: true,
+            duration,
+        } => (
+            IconName::Check,
+            Color::Success,
+            format!("Passed in {}", format_duration(duration)),
+            Some("Rerun Test"),
+        ),
+        TestRunStatus::Finished {
+            success: false,
+            duration,
+        } => (
+            IconName::XCircle,
+            Color::Error,
+            format!("Failed in {}", format_duration(duration)),
+            Some("Open Test Output"),
+        ),
+    };
+
+    h_flex()
+        .id(("test_run", id))
+        .ml_2()
+        .gap_1()
+        .child(Icon::new(icon).size(IconSize::XSmall).color(color))
+        .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+        .when_some(tooltip, |this, tooltip| {
+            this.cursor_pointer()
+                .tooltip(Tooltip::text(tooltip))
+                .on_click(move |_, window, cx| {
+                    editor
+                        .update(cx, |editor, cx| editor.open_test_run(id, window, cx))
+                        .ok();
+                })
+        })
+        .into_any_element()
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1}s", duration.as_secs_f32())
+}
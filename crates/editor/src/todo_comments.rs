@@ -0,0 +1,53 @@
+//! Tags such as `TODO` and `FIXME` in comments, which are highlighted in the editor and listed by
+//! the project TODOs view.
+use std::ops::Range;
+
+use language::BufferSnapshot;
+
+/// Returns the byte ranges of every tag in `text` that appears as a whole word.
+pub fn todo_tag_ranges(text: &str, tags: &[String]) -> Vec<Range<usize>> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut ranges = Vec::new();
+    for tag in tags.iter().filter(|tag| !tag.is_empty()) {
+        for (start, _) in text.match_indices(tag.as_str()) {
+            let end = start + tag.len();
+            let is_word_start = !text[..start].chars().next_back().is_some_and(is_word_char);
+            let is_word_end = !text[end..].chars().next().is_some_and(is_word_char);
+            if is_word_start && is_word_end {
+                ranges.push(start..end);
+            }
+        }
+    }
+    ranges.sort_by_key(|range| range.start);
+    ranges
+}
+
+/// Whether a tag at `offset` should be treated as a TODO: it has to be inside a comment, unless the
+/// buffer has no language, in which case any occurrence counts.
+pub fn is_todo_position(buffer: &BufferSnapshot, offset: usize) -> bool {
+    if buffer.language().is_none() {
+        return true;
+    }
+    buffer
+        .language_scope_at(offset)
+        .is_some_and(|scope| scope.override_name() == Some("comment"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_todo_tag_ranges() {
+        let tags = vec!["TODO".to_string(), "FIXME".to_string()];
+        assert_eq!(
+            todo_tag_ranges("// TODO: fix, FIXME later", &tags),
+            vec![3..7, 14..19]
+        );
+        assert_eq!(
+            todo_tag_ranges("// TODOS, MY_TODO and FIXME2", &tags),
+            Vec::<Range<usize>>::new()
+        );
+        assert_eq!(todo_tag_ranges("TODO(nathan)", &tags), vec![0..4]);
+    }
+}
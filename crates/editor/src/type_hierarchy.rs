@@ -0,0 +1,473 @@
+//! A tree of the supertypes or subtypes of a type, for `editor::ShowSupertypes` and
+//! `editor::ShowSubtypes`.
+//!
+//! The tree is rooted at the type under the cursor and is loaded one level at a time, as its
+//! nodes are expanded.
+use std::ops::Range;
+
+use anyhow::Result;
+use gpui::{
+    App, Context, Entity, EventEmitter, FocusHandle, Focusable, ScrollStrategy, Task,
+    UniformListScrollHandle, WeakEntity, Window, uniform_list,
+};
+use language::ToPoint as _;
+use project::{Project, TypeHierarchyItem};
+use ui::{ListItem, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{Item, Workspace, item::ItemEvent};
+
+use crate::{
+    Editor,
+    actions::{
+        CollapseSelectedType, ExpandSelectedType, ShowSubtypes, ShowSupertypes, ToggleTypeDirection,
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeHierarchyDirection {
+    Supertypes,
+    Subtypes,
+}
+
+pub struct TypeHierarchyView {
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    direction: TypeHierarchyDirection,
+    /// The nodes of the tree, where the first node is its root.
+    nodes: Vec<TypeHierarchyNode>,
+    selected_node: usize,
+}
+
+struct TypeHierarchyNode {
+    item: TypeHierarchyItem,
+    depth: usize,
+    expanded: bool,
+    children: TypeHierarchyChildren,
+}
+
+enum TypeHierarchyChildren {
+    Unloaded,
+    Loading(Task<()>),
+    Loaded(Vec<usize>),
+}
+
+impl Editor {
+    pub fn show_supertypes(
+        &mut self,
+        _: &ShowSupertypes,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        self.show_type_hierarchy(TypeHierarchyDirection::Supertypes, window, cx)
+    }
+
+    pub fn show_subtypes(
+        &mut self,
+        _: &ShowSubtypes,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        self.show_type_hierarchy(TypeHierarchyDirection::Subtypes, window, cx)
+    }
+
+    fn show_type_hierarchy(
+        &mut self,
+        direction: TypeHierarchyDirection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Task<Result<()>>> {
+        let project = self.project.clone()?;
+        let workspace = self.workspace()?;
+        let head = self.selections.newest_anchor().head();
+        let (buffer, position) = self.buffer.read(cx).text_anchor_for_position(head, cx)?;
+        let items = project.update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, position, cx)
+        });
+
+        Some(cx.spawn_in(window, async move |_, cx| {
+            let Some(item) = items.await?.into_iter().next() else {
+                return Ok(());
+            };
+            workspace.update_in(cx, |workspace, window, cx| {
+                let weak_workspace = workspace.weak_handle();
+                let view = cx.new(|cx| {
+                    TypeHierarchyView::new(item, direction, project, weak_workspace, window, cx)
+                });
+                workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+            })
+        }))
+    }
+}
+
+impl TypeHierarchyView {
+    pub fn new(
+        root: TypeHierarchyItem,
+        direction: TypeHierarchyDirection,
+        project: Entity<Project>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            project,
+            workspace,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            direction,
+            nodes: Vec::new(),
+            selected_node: 0,
+        };
+        this.reset(root, window, cx);
+        this
+    }
+
+    pub fn direction(&self) -> TypeHierarchyDirection {
+        self.direction
+    }
+
+    /// Returns the depth and name of each visible node.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn visible_entries(&self) -> Vec<(usize, String)> {
+        self.visible_nodes()
+            .into_iter()
+            .map(|ix| (self.nodes[ix].depth, self.nodes[ix].item.name.clone()))
+            .collect()
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn expand_all_visible(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        for ix in self.visible_nodes() {
+            self.set_expanded(ix, true, window, cx);
+        }
+    }
+
+    fn reset(&mut self, root: TypeHierarchyItem, window: &mut Window, cx: &mut Context<Self>) {
+        self.nodes = vec![TypeHierarchyNode {
+            item: root,
+            depth: 0,
+            expanded: false,
+            children: TypeHierarchyChildren::Unloaded,
+        }];
+        self.selected_node = 0;
+        self.set_expanded(0, true, window, cx);
+    }
+
+    pub(crate) fn toggle_direction(
+        &mut self,
+        _: &ToggleTypeDirection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.direction = match self.direction {
+            TypeHierarchyDirection::Supertypes => TypeHierarchyDirection::Subtypes,
+            TypeHierarchyDirection::Subtypes => TypeHierarchyDirection::Supertypes,
+        };
+        let root = self.nodes[0].item.clone();
+        self.reset(root, window, cx);
+        cx.emit(ItemEvent::UpdateTab);
+    }
+
+    /// Returns the indices of the nodes that aren't hidden within collapsed nodes, in tree order.
+    fn visible_nodes(&self) -> Vec<usize> {
+        let mut visible_nodes = Vec::new();
+        let mut stack = vec![0];
+        while let Some(ix) = stack.pop() {
+            visible_nodes.push(ix);
+            let node = &self.nodes[ix];
+            if let (true, TypeHierarchyChildren::Loaded(children)) = (node.expanded, &node.children)
+            {
+                stack.extend(children.iter().rev());
+            }
+        }
+        visible_nodes
+    }
+
+    fn set_expanded(
+        &mut self,
+        ix: usize,
+        expanded: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let node = &mut self.nodes[ix];
+        node.expanded = expanded;
+        if expanded && matches!(node.children, TypeHierarchyChildren::Unloaded) {
+            let item = node.item.clone();
+            let types = self.project.update(cx, |project, cx| match self.direction {
+                TypeHierarchyDirection::Supertypes => project.supertypes(&item, cx),
+                TypeHierarchyDirection::Subtypes => project.subtypes(&item, cx),
+            });
+            self.nodes[ix].children =
+                TypeHierarchyChildren::Loading(cx.spawn_in(window, async move |this, cx| {
+                    let types = types.await.log_err().unwrap_or_default();
+                    this.update(cx, |this, cx| this.insert_children(ix, types, cx))
+                        .ok();
+                }));
+        }
+        cx.notify();
+    }
+
+    fn insert_children(
+        &mut self,
+        ix: usize,
+        types: Vec<TypeHierarchyItem>,
+        cx: &mut Context<Self>,
+    ) {
+        let depth = self.nodes[ix].depth + 1;
+        let mut children = Vec::with_capacity(types.len());
+        for item in types {
+            children.push(self.nodes.len());
+            self.nodes.push(TypeHierarchyNode {
+                item,
+                depth,
+                expanded: false,
+                children: TypeHierarchyChildren::Unloaded,
+            });
+        }
+        self.nodes[ix].children = TypeHierarchyChildren::Loaded(children);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let visible_nodes = self.visible_nodes();
+        if let Some(position) = visible_nodes
+            .iter()
+            .position(|ix| *ix == self.selected_node)
+            && let Some(next) = visible_nodes.get(position + 1)
+        {
+            self.select(*next, position + 1, cx);
+        }
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let visible_nodes = self.visible_nodes();
+        if let Some(position) = visible_nodes
+            .iter()
+            .position(|ix| *ix == self.selected_node)
+            && position > 0
+        {
+            self.select(visible_nodes[position - 1], position - 1, cx);
+        }
+    }
+
+    fn select(&mut self, ix: usize, position: usize, cx: &mut Context<Self>) {
+        self.selected_node = ix;
+        self.scroll_handle
+            .scroll_to_item(position, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn expand_selected_type(
+        &mut self,
+        _: &ExpandSelectedType,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_expanded(self.selected_node, true, window, cx);
+    }
+
+    fn collapse_selected_type(
+        &mut self,
+        _: &CollapseSelectedType,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.nodes[self.selected_node].expanded {
+            self.set_expanded(self.selected_node, false, window, cx);
+        } else if let Some(parent) = self.parent_of(self.selected_node) {
+            let position = self
+                .visible_nodes()
+                .iter()
+                .position(|ix| *ix == parent)
+                .unwrap_or_default();
+            self.select(parent, position, cx);
+        }
+    }
+
+    fn parent_of(&self, ix: usize) -> Option<usize> {
+        self.nodes.iter().position(|node| match &node.children {
+            TypeHierarchyChildren::Loaded(children) => children.contains(&ix),
+            _ => false,
+        })
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.open_node(self.selected_node, window, cx);
+    }
+
+    fn open_node(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let location = self.nodes[ix].item.location.clone();
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        window.defer(cx, move |window, cx| {
+            workspace.update(cx, |workspace, cx| {
+                let pane = workspace.adjacent_pane(window, cx);
+                let editor = workspace.open_project_item::<Editor>(
+                    pane,
+                    location.buffer.clone(),
+                    true,
+                    true,
+                    window,
+                    cx,
+                );
+                editor.update(cx, |editor, cx| {
+                    let buffer = location.buffer.read(cx);
+                    let range =
+                        location.range.start.to_point(buffer)..location.range.end.to_point(buffer);
+                    editor.go_to_singleton_buffer_range(range, window, cx);
+                });
+            });
+        });
+    }
+
+    fn render_node(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let node = &self.nodes[ix];
+        let toggle = match &node.children {
+            TypeHierarchyChildren::Loaded(children) if children.is_empty() => None,
+            _ => Some(node.expanded),
+        };
+        let location = &node.item.location;
+        let buffer = location.buffer.read(cx);
+        let row = location.range.start.to_point(buffer).row + 1;
+        let path = match buffer.file() {
+            Some(file) => format!("{}:{row}", file.path().display(file.path_style(cx))),
+            None => format!("untitled:{row}"),
+        };
+
+        ListItem::new(ix)
+            .indent_level(node.depth)
+            .indent_step_size(px(12.))
+            .toggle(toggle)
+            .toggle_state(ix == self.selected_node)
+            .on_toggle(cx.listener(move |this, _, window, cx| {
+                let expanded = this.nodes[ix].expanded;
+                this.set_expanded(ix, !expanded, window, cx);
+            }))
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.selected_node = ix;
+                this.open_node(ix, window, cx);
+            }))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(node.item.name.clone()))
+                    .children(node.item.detail.clone().map(|detail| {
+                        Label::new(detail)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate()
+                    }))
+                    .child(Label::new(path).size(LabelSize::Small).color(Color::Muted))
+                    .when(
+                        matches!(node.children, TypeHierarchyChildren::Loading(_)),
+                        |row| {
+                            row.child(
+                                Label::new("Loading…")
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        },
+                    ),
+            )
+            .into_any_element()
+    }
+}
+
+impl Render for TypeHierarchyView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let visible_nodes = self.visible_nodes();
+        let (title, toggle_label) = match self.direction {
+            TypeHierarchyDirection::Supertypes => ("Supertypes", "Show Subtypes"),
+            TypeHierarchyDirection::Subtypes => ("Subtypes", "Show Supertypes"),
+        };
+        let focus_handle = self.focus_handle.clone();
+
+        v_flex()
+            .key_context("TypeHierarchy")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::expand_selected_type))
+            .on_action(cx.listener(Self::collapse_selected_type))
+            .on_action(cx.listener(Self::toggle_direction))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new(title).color(Color::Muted))
+                    .child(
+                        Button::new("toggle-type-direction", toggle_label)
+                            .label_size(LabelSize::Small)
+                            .tooltip(move |window, cx| {
+                                Tooltip::for_action_in(
+                                    toggle_label,
+                                    &ToggleTypeDirection,
+                                    &focus_handle,
+                                    window,
+                                    cx,
+                                )
+                            })
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.toggle_direction(&ToggleTypeDirection, window, cx)
+                            })),
+                    ),
+            )
+            .child(
+                uniform_list(
+                    "type-hierarchy",
+                    visible_nodes.len(),
+                    cx.processor(move |this, range: Range<usize>, _, cx| {
+                        visible_nodes[range]
+                            .iter()
+                            .map(|ix| this.render_node(*ix, cx))
+                            .collect()
+                    }),
+                )
+                .track_scroll(self.scroll_handle.clone())
+                .flex_1()
+                .p_1(),
+            )
+    }
+}
+
+impl Focusable for TypeHierarchyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<ItemEvent> for TypeHierarchyView {}
+
+impl Item for TypeHierarchyView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::ListTree))
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let direction = match self.direction {
+            TypeHierarchyDirection::Supertypes => "Supertypes",
+            TypeHierarchyDirection::Subtypes => "Subtypes",
+        };
+        format!("{direction} of {}", self.nodes[0].item.name).into()
+    }
+}
@@ -0,0 +1,107 @@
+//! Marks text typed into the buffers of remote projects until the remote server acknowledges it,
+//! so that on a slow connection it's clear which edits haven't reached the server yet. Edits are
+//! always applied locally right away, and the server's own edits are merged in as they arrive.
+use std::time::Duration;
+
+use gpui::{Context, HighlightStyle, UnderlineStyle, px};
+use language::BufferId;
+use multi_buffer::Anchor;
+use theme::ActiveTheme as _;
+
+use crate::Editor;
+
+/// How long edits have to stay unconfirmed before they are marked, so that typing on a fast
+/// connection doesn't flicker.
+const UNCONFIRMED_EDITS_DELAY: Duration = Duration::from_millis(150);
+
+struct UnconfirmedEditHighlight;
+
+impl Editor {
+    fn shows_unconfirmed_edits(&self, cx: &Context<Self>) -> bool {
+        self.project
+            .as_ref()
+            .is_some_and(|project| project.read(cx).is_via_remote_server())
+    }
+
+    /// Marks the unconfirmed edits after a delay, unless they are confirmed by then.
+    pub(crate) fn schedule_unconfirmed_edits_refresh(&mut self, cx: &mut Context<Self>) {
+        if !self.shows_unconfirmed_edits(cx) || self.unconfirmed_edits_task.is_some() {
+            return;
+        }
+        self.unconfirmed_edits_task = Some(cx.spawn(async move |editor, cx| {
+            cx.background_executor()
+                .timer(UNCONFIRMED_EDITS_DELAY)
+                .await;
+            editor
+                .update(cx, |editor, cx| {
+                    editor.unconfirmed_edits_task = None;
+                    editor.refresh_unconfirmed_edits(cx);
+                })
+                .ok();
+        }));
+    }
+
+    pub(crate) fn handle_unconfirmed_edits_changed(
+        &mut self,
+        buffer_id: BufferId,
+        cx: &mut Context<Self>,
+    ) {
+        if self.buffer.read(cx).buffer(buffer_id).is_none() {
+            return;
+        }
+        // Edits that are already marked are unmarked right away, while the other edits still get
+        // a chance to be confirmed before they are marked.
+        if self
+            .text_highlights::<UnconfirmedEditHighlight>(cx)
+            .is_some()
+        {
+            self.refresh_unconfirmed_edits(cx);
+        }
+    }
+
+    fn refresh_unconfirmed_edits(&mut self, cx: &mut Context<Self>) {
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let multi_buffer = self.buffer.read(cx);
+        let snapshot = multi_buffer.snapshot(cx);
+        let project = project.read(cx);
+        let mut ranges = Vec::new();
+        for (excerpt_id, buffer_snapshot, _) in snapshot.excerpts() {
+            let buffer_id = buffer_snapshot.remote_id();
+            let Some(buffer) = multi_buffer.buffer(buffer_id) else {
+                continue;
+            };
+            ranges.extend(
+                project
+                    .unconfirmed_edits(&buffer, cx)
+                    .into_iter()
+                    .map(|range| {
+                        Anchor::in_buffer(excerpt_id, buffer_id, range.start)
+                            ..Anchor::in_buffer(excerpt_id, buffer_id, range.end)
+                    }),
+            );
+        }
+
+        if ranges.is_empty() {
+            self.clear_highlights::<UnconfirmedEditHighlight>(cx);
+        } else {
+            let style = HighlightStyle {
+                underline: Some(UnderlineStyle {
+                    color: Some(cx.theme().colors().text_muted),
+                    thickness: px(1.),
+                    wavy: false,
+                }),
+                ..HighlightStyle::default()
+            };
+            self.highlight_text::<UnconfirmedEditHighlight>(ranges, style, cx);
+        }
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn unconfirmed_edit_ranges(&self, cx: &gpui::App) -> Vec<std::ops::Range<Anchor>> {
+        self.text_highlights::<UnconfirmedEditHighlight>(cx)
+            .map(|(_, ranges)| ranges.to_vec())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,254 @@
+//! Warnings about characters that make text read differently than it is: bidirectional controls
+//! that reorder how code is displayed (as in "Trojan Source" attacks), and characters that look
+//! like ASCII letters in otherwise ASCII identifiers.
+use std::ops::Range;
+
+use gpui::{Context, HighlightStyle, UnderlineStyle, Window, px};
+use itertools::Itertools as _;
+use settings::Settings as _;
+use theme::ActiveTheme as _;
+
+use crate::{Editor, EditorSettings};
+
+struct BidiControlHighlight;
+struct ConfusableHighlight;
+
+/// Finds the embeddings, overrides and isolates in `text`, from the control character that
+/// starts them to the one that ends them, or to the end of the line if they are never ended.
+pub fn bidi_control_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (ix, c) in line.char_indices() {
+            match c {
+                '\u{202a}' | '\u{202b}' | '\u{202d}' | '\u{202e}' | '\u{2066}'..='\u{2068}' => {}
+                '\u{202c}' | '\u{2069}' => {
+                    if depth > 0 {
+                        depth -= 1;
+                        if depth == 0 {
+                            ranges.push(line_start + start..line_start + ix + c.len_utf8());
+                        }
+                    }
+                    continue;
+                }
+                _ => continue,
+            }
+            if depth == 0 {
+                start = ix;
+            }
+            depth += 1;
+        }
+        if depth > 0 {
+            let line_end = line.trim_end_matches(['\n', '\r']).len();
+            ranges.push(line_start + start..line_start + line_end);
+        }
+        line_start += line.len();
+    }
+    ranges
+}
+
+/// Finds the characters that look like ASCII letters or digits in words that otherwise only
+/// contain ASCII, such as the Cyrillic "а" in "pаssword".
+pub fn confusable_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let words = text
+        .char_indices()
+        .chunk_by(|(_, c)| c.is_alphanumeric() || *c == '_');
+    for (is_word, word) in &words {
+        if !is_word {
+            continue;
+        }
+        let word = word.collect::<Vec<_>>();
+        let mixed = word.iter().any(|(_, c)| c.is_ascii())
+            && word
+                .iter()
+                .all(|(_, c)| c.is_ascii() || ascii_lookalike(*c).is_some());
+        if mixed {
+            ranges.extend(
+                word.into_iter()
+                    .filter(|(_, c)| !c.is_ascii())
+                    .map(|(ix, c)| ix..ix + c.len_utf8()),
+            );
+        }
+    }
+    ranges
+}
+
+/// The ASCII character that `c` is easily mistaken for.
+pub fn ascii_lookalike(c: char) -> Option<char> {
+    Some(match c {
+        // Cyrillic
+        'а' => 'a',
+        'е' => 'e',
+        'о' => 'o',
+        'р' => 'p',
+        'с' => 'c',
+        'у' => 'y',
+        'х' => 'x',
+        'ѕ' => 's',
+        'і' => 'i',
+        'ј' => 'j',
+        'ԁ' => 'd',
+        'А' => 'A',
+        'В' => 'B',
+        'Е' => 'E',
+        'К' => 'K',
+        'М' => 'M',
+        'Н' => 'H',
+        'О' => 'O',
+        'Р' => 'P',
+        'С' => 'C',
+        'Т' => 'T',
+        'Х' => 'X',
+        'Ѕ' => 'S',
+        'І' => 'I',
+        'Ј' => 'J',
+        // Greek
+        'ο' => 'o',
+        'ν' => 'v',
+        'Α' => 'A',
+        'Β' => 'B',
+        'Ε' => 'E',
+        'Ζ' => 'Z',
+        'Η' => 'H',
+        'Ι' => 'I',
+        'Κ' => 'K',
+        'Μ' => 'M',
+        'Ν' => 'N',
+        'Ο' => 'O',
+        'Ρ' => 'P',
+        'Τ' => 'T',
+        'Υ' => 'Y',
+        'Χ' => 'X',
+        // Fullwidth forms
+        '\u{ff10}'..='\u{ff19}' => (b'0' + (c as u32 - 0xff10) as u8) as char,
+        '\u{ff21}'..='\u{ff3a}' => (b'A' + (c as u32 - 0xff21) as u8) as char,
+        '\u{ff41}'..='\u{ff5a}' => (b'a' + (c as u32 - 0xff41) as u8) as char,
+        _ => return None,
+    })
+}
+
+impl Editor {
+    pub(crate) fn refresh_unicode_highlights(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let settings = EditorSettings::get_global(cx).unicode_highlights;
+        let bidi_controls = self.mode.is_full() && settings.bidi_controls;
+        let confusables = self.mode.is_full() && settings.confusables;
+        if !bidi_controls && !confusables && !self.unicode_highlights_applied {
+            return;
+        }
+        self.unicode_highlights_applied = bidi_controls || confusables;
+
+        let snapshot = self.snapshot(window, cx);
+        let task = cx.background_spawn(async move {
+            let buffer = snapshot.buffer_snapshot();
+            let text = buffer.text();
+            let anchor_ranges = |ranges: Vec<Range<usize>>| {
+                ranges
+                    .into_iter()
+                    .map(|range| buffer.anchor_after(range.start)..buffer.anchor_before(range.end))
+                    .collect::<Vec<_>>()
+            };
+            let bidi_ranges = if bidi_controls {
+                anchor_ranges(bidi_control_ranges(&text))
+            } else {
+                Vec::new()
+            };
+            let confusable_ranges = if confusables {
+                anchor_ranges(confusable_ranges(&text))
+            } else {
+                Vec::new()
+            };
+            (bidi_ranges, confusable_ranges)
+        });
+
+        self.unicode_highlights_task = cx.spawn(async move |this, cx| {
+            let (bidi_ranges, confusable_ranges) = task.await;
+            this.update(cx, |this, cx| {
+                let status = cx.theme().status().clone();
+                let bidi_style = HighlightStyle {
+                    underline: Some(UnderlineStyle {
+                        color: Some(status.warning),
+                        thickness: px(1.),
+                        wavy: true,
+                    }),
+                    ..HighlightStyle::default()
+                };
+                let confusable_style = HighlightStyle {
+                    background_color: Some(status.warning_background),
+                    underline: Some(UnderlineStyle {
+                        color: Some(status.warning),
+                        thickness: px(1.),
+                        wavy: false,
+                    }),
+                    ..HighlightStyle::default()
+                };
+                if bidi_ranges.is_empty() {
+                    this.clear_highlights::<BidiControlHighlight>(cx);
+                } else {
+                    this.highlight_text::<BidiControlHighlight>(bidi_ranges, bidi_style, cx);
+                }
+                if confusable_ranges.is_empty() {
+                    this.clear_highlights::<ConfusableHighlight>(cx);
+                } else {
+                    this.highlight_text::<ConfusableHighlight>(
+                        confusable_ranges,
+                        confusable_style,
+                        cx,
+                    );
+                }
+            })
+            .ok();
+        });
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn bidi_control_highlights(&self, cx: &gpui::App) -> Vec<Range<multi_buffer::Anchor>> {
+        self.text_highlights::<BidiControlHighlight>(cx)
+            .map(|(_, ranges)| ranges.to_vec())
+            .unwrap_or_default()
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn confusable_highlights(&self, cx: &gpui::App) -> Vec<Range<multi_buffer::Anchor>> {
+        self.text_highlights::<ConfusableHighlight>(cx)
+            .map(|(_, ranges)| ranges.to_vec())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bidi_control_ranges() {
+        let text = "let a = \"\u{202e}x\u{202c}\";\nlet b = \"\u{2067}y\";\nlet c = \u{202c};\n";
+        let ranges = bidi_control_ranges(text);
+        assert_eq!(
+            ranges
+                .iter()
+                .map(|range| &text[range.clone()])
+                .collect::<Vec<_>>(),
+            ["\u{202e}x\u{202c}", "\u{2067}y\";"]
+        );
+    }
+
+    #[test]
+    fn test_confusable_ranges() {
+        let text = "let pаssword = сount + ｆoo; // привет, café, ｘ";
+        let ranges = confusable_ranges(text);
+        assert_eq!(
+            ranges
+                .iter()
+                .map(|range| &text[range.clone()])
+                .collect::<Vec<_>>(),
+            ["а", "с", "ｆ"]
+        );
+    }
+}
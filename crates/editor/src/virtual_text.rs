@@ -0,0 +1,226 @@
+//! Virtual text and small widgets that other crates show in the editor next to buffer
+//! positions, such as test results or annotations, without changing the buffer's text.
+//!
+//! Virtual text shown inline or at the end of a line is an inlay, so that the text after it
+//! moves aside, while virtual text shown above a line is a block.
+use std::{fmt, sync::Arc};
+
+use collections::HashSet;
+use gpui::{AnyElement, App, HighlightStyle, SharedString, StyledText, Window};
+use language::Point;
+use multi_buffer::{Anchor, MultiBufferRow, ToPoint as _};
+use text::Rope;
+use ui::{h_flex, prelude::*};
+use util::post_inc;
+
+use crate::{
+    Editor, InlayId,
+    display_map::{
+        BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId, Inlay,
+    },
+};
+
+pub type RenderVirtualText = Arc<dyn Send + Sync + Fn(&mut Window, &mut App) -> AnyElement>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VirtualTextPlacement {
+    /// Before the text at the virtual text's position.
+    Inline,
+    /// After the end of the line containing the virtual text's position.
+    EndOfLine,
+    /// On its own line, above the line containing the virtual text's position.
+    AboveLine,
+}
+
+/// Text or a widget shown next to a buffer position, added with [`Editor::insert_virtual_text`].
+#[derive(Clone)]
+pub struct VirtualText {
+    pub position: Anchor,
+    pub placement: VirtualTextPlacement,
+    pub text: SharedString,
+    pub style: HighlightStyle,
+    /// Renders a widget in place of the text.
+    pub render: Option<RenderVirtualText>,
+}
+
+impl VirtualText {
+    pub fn text(
+        position: Anchor,
+        placement: VirtualTextPlacement,
+        text: impl Into<SharedString>,
+    ) -> Self {
+        Self {
+            position,
+            placement,
+            text: text.into(),
+            style: HighlightStyle::default(),
+            render: None,
+        }
+    }
+
+    pub fn widget(
+        position: Anchor,
+        placement: VirtualTextPlacement,
+        render: impl Send + Sync + Fn(&mut Window, &mut App) -> AnyElement + 'static,
+    ) -> Self {
+        Self {
+            position,
+            placement,
+            // Inline widgets replace a single character of text.
+            text: "◼".into(),
+            style: HighlightStyle::default(),
+            render: Some(Arc::new(render)),
+        }
+    }
+
+    pub fn style(mut self, style: HighlightStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+/// The content of an inlay showing [`VirtualText`].
+#[derive(Clone)]
+pub struct VirtualTextInlay {
+    pub text: Rope,
+    pub style: HighlightStyle,
+    pub render: Option<RenderVirtualText>,
+}
+
+impl fmt::Debug for VirtualTextInlay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VirtualTextInlay")
+            .field("text", &self.text)
+            .field("style", &self.style)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VirtualTextId(usize);
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum VirtualTextDisplay {
+    Inlay(InlayId),
+    Block(CustomBlockId),
+}
+
+impl Editor {
+    /// Shows the given virtual text, returning ids to remove it with
+    /// [`Editor::remove_virtual_text`].
+    pub fn insert_virtual_text(
+        &mut self,
+        virtual_texts: impl IntoIterator<Item = VirtualText>,
+        cx: &mut Context<Self>,
+    ) -> Vec<VirtualTextId> {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let mut ids = Vec::new();
+        let mut inlays = Vec::new();
+        let mut blocks = Vec::new();
+        for virtual_text in virtual_texts {
+            let id = VirtualTextId(self.next_virtual_text_id);
+            self.next_virtual_text_id += 1;
+            ids.push(id);
+
+            match virtual_text.placement {
+                VirtualTextPlacement::Inline | VirtualTextPlacement::EndOfLine => {
+                    let (position, text) =
+                        if virtual_text.placement == VirtualTextPlacement::EndOfLine {
+                            let row = virtual_text.position.to_point(&snapshot).row;
+                            let line_end = Point::new(row, snapshot.line_len(MultiBufferRow(row)));
+                            // Separate the virtual text from the end of the line.
+                            (
+                                snapshot.anchor_after(line_end),
+                                Rope::from(format!(" {}", virtual_text.text)),
+                            )
+                        } else {
+                            (
+                                virtual_text.position,
+                                Rope::from(virtual_text.text.as_ref()),
+                            )
+                        };
+                    let inlay = Inlay::virtual_text(
+                        post_inc(&mut self.next_inlay_id),
+                        position,
+                        VirtualTextInlay {
+                            text,
+                            style: virtual_text.style,
+                            render: virtual_text.render,
+                        },
+                    );
+                    let inlay_id = inlay.id;
+                    inlays.push(inlay);
+                    self.virtual_texts
+                        .insert(id, VirtualTextDisplay::Inlay(inlay_id));
+                }
+                VirtualTextPlacement::AboveLine => {
+                    blocks.push((id, virtual_text));
+                }
+            }
+        }
+
+        if !inlays.is_empty() {
+            self.splice_inlays(&[], inlays, cx);
+        }
+        if !blocks.is_empty() {
+            let (virtual_text_ids, virtual_texts): (Vec<_>, Vec<_>) = blocks.into_iter().unzip();
+            let block_ids = self.insert_blocks(
+                virtual_texts
+                    .into_iter()
+                    .map(|virtual_text| BlockProperties {
+                        placement: BlockPlacement::Above(virtual_text.position),
+                        height: Some(1),
+                        style: BlockStyle::Flex,
+                        render: Arc::new(move |cx: &mut BlockContext| {
+                            let element = match &virtual_text.render {
+                                Some(render) => render(cx.window, cx.app),
+                                None => StyledText::new(virtual_text.text.clone())
+                                    .with_highlights([(
+                                        0..virtual_text.text.len(),
+                                        virtual_text.style,
+                                    )])
+                                    .into_any_element(),
+                            };
+                            h_flex()
+                                .h(cx.line_height)
+                                .pl(cx.anchor_x)
+                                .child(element)
+                                .into_any_element()
+                        }),
+                        priority: 0,
+                    }),
+                None,
+                cx,
+            );
+            for (id, block_id) in virtual_text_ids.into_iter().zip(block_ids) {
+                self.virtual_texts
+                    .insert(id, VirtualTextDisplay::Block(block_id));
+            }
+        }
+        ids
+    }
+
+    pub fn remove_virtual_text(
+        &mut self,
+        ids: impl IntoIterator<Item = VirtualTextId>,
+        cx: &mut Context<Self>,
+    ) {
+        let mut inlay_ids = Vec::new();
+        let mut block_ids = HashSet::default();
+        for id in ids {
+            match self.virtual_texts.remove(&id) {
+                Some(VirtualTextDisplay::Inlay(inlay_id)) => inlay_ids.push(inlay_id),
+                Some(VirtualTextDisplay::Block(block_id)) => {
+                    block_ids.insert(block_id);
+                }
+                None => {}
+            }
+        }
+        if !inlay_ids.is_empty() {
+            self.splice_inlays(&inlay_ids, Vec::new(), cx);
+        }
+        if !block_ids.is_empty() {
+            self.remove_blocks(block_ids, None, cx);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use editor::Editor;
+use gpui::{
+    Context, Entity, IntoElement, ParentElement, Render, Subscription, WeakEntity, Window, div,
+};
+use language::{Buffer, Encoding};
+use settings::Settings as _;
+use ui::{Button, ButtonCommon, Clickable, FluentBuilder, LabelSize, Tooltip};
+use workspace::{StatusBarSettings, StatusItemView, Workspace, item::ItemHandle};
+
+use crate::{EncodingSelector, SaveWithEncoding};
+
+/// Shows the encoding of the active buffer's file in the status bar, when it isn't UTF-8.
+pub struct ActiveBufferEncoding {
+    active_encoding: Option<(Entity<Buffer>, Encoding)>,
+    workspace: WeakEntity<Workspace>,
+    _observe_active_editor: Option<Subscription>,
+    _observe_active_buffer: Option<Subscription>,
+}
+
+impl ActiveBufferEncoding {
+    pub fn new(workspace: &Workspace) -> Self {
+        Self {
+            active_encoding: None,
+            workspace: workspace.weak_handle(),
+            _observe_active_editor: None,
+            _observe_active_buffer: None,
+        }
+    }
+
+    fn update_encoding(
+        &mut self,
+        editor: Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let previous_buffer = self
+            .active_encoding
+            .take()
+            .map(|(buffer, _)| buffer.entity_id());
+
+        if let Some((_, buffer, _)) = editor.read(cx).active_excerpt(cx) {
+            // The encoding changes on the buffer when it's saved with another encoding or reloaded.
+            if previous_buffer != Some(buffer.entity_id()) {
+                let editor = editor.clone();
+                self._observe_active_buffer =
+                    Some(cx.observe_in(&buffer, window, move |this, _, window, cx| {
+                        this.update_encoding(editor.clone(), window, cx)
+                    }));
+            }
+            let encoding = buffer.read(cx).encoding();
+            self.active_encoding = Some((buffer, encoding));
+        }
+
+        cx.notify();
+    }
+}
+
+impl Render for ActiveBufferEncoding {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if !StatusBarSettings::get_global(cx).encoding_button {
+            return div();
+        }
+
+        div().when_some(
+            self.active_encoding
+                .clone()
+                .filter(|(_, encoding)| *encoding != Encoding::Utf8),
+            |el, (buffer, encoding)| {
+                el.child(
+                    Button::new("change-encoding", encoding.name())
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            if let Some(workspace) = this.workspace.upgrade() {
+                                let buffer = buffer.clone();
+                                workspace.update(cx, |workspace, cx| {
+                                    EncodingSelector::toggle_for_buffer(
+                                        workspace, buffer, window, cx,
+                                    )
+                                });
+                            }
+                        }))
+                        .tooltip(|window, cx| {
+                            Tooltip::for_action("Save with Encoding", &SaveWithEncoding, window, cx)
+                        }),
+                )
+            },
+        )
+    }
+}
+
+impl StatusItemView for ActiveBufferEncoding {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(editor) = active_pane_item.and_then(|item| item.downcast::<Editor>()) {
+            self._observe_active_editor =
+                Some(cx.observe_in(&editor, window, Self::update_encoding));
+            self.update_encoding(editor, window, cx);
+        } else {
+            self.active_encoding = None;
+            self._observe_active_editor = None;
+            self._observe_active_buffer = None;
+        }
+
+        cx.notify();
+    }
+}
@@ -0,0 +1,217 @@
+mod active_buffer_encoding;
+
+use editor::Editor;
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity, actions};
+use language::{Buffer, Encoding};
+use picker::{Picker, PickerDelegate};
+use project::Project;
+use std::sync::Arc;
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, Workspace, notifications::NotifyTaskExt as _};
+
+pub use active_buffer_encoding::ActiveBufferEncoding;
+
+actions!(
+    encoding_selector,
+    [
+        /// Saves the active buffer's file in an encoding chosen from a modal.
+        SaveWithEncoding
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(EncodingSelector::register).detach();
+}
+
+pub struct EncodingSelector {
+    picker: Entity<Picker<EncodingSelectorDelegate>>,
+}
+
+impl EncodingSelector {
+    fn register(editor: &mut Editor, _window: Option<&mut Window>, cx: &mut Context<Editor>) {
+        let editor_handle = cx.weak_entity();
+        editor
+            .register_action(move |_: &SaveWithEncoding, window, cx| {
+                Self::toggle(&editor_handle, window, cx);
+            })
+            .detach();
+    }
+
+    fn toggle(editor: &WeakEntity<Editor>, window: &mut Window, cx: &mut App) {
+        let Some((workspace, buffer)) = editor
+            .update(cx, |editor, cx| {
+                Some((editor.workspace()?, editor.active_excerpt(cx)?.1))
+            })
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+
+        workspace.update(cx, |workspace, cx| {
+            Self::toggle_for_buffer(workspace, buffer, window, cx);
+        })
+    }
+
+    pub(crate) fn toggle_for_buffer(
+        workspace: &mut Workspace,
+        buffer: Entity<Buffer>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let project = workspace.project().clone();
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            EncodingSelector::new(buffer, project, window, cx)
+        });
+    }
+
+    fn new(
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let encoding = buffer.read(cx).encoding();
+        let delegate =
+            EncodingSelectorDelegate::new(cx.entity().downgrade(), buffer, project, encoding);
+        let picker = cx.new(|cx| Picker::nonsearchable_uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for EncodingSelector {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for EncodingSelector {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for EncodingSelector {}
+impl ModalView for EncodingSelector {}
+
+struct EncodingSelectorDelegate {
+    encoding_selector: WeakEntity<EncodingSelector>,
+    buffer: Entity<Buffer>,
+    project: Entity<Project>,
+    encoding: Encoding,
+    matches: Vec<Encoding>,
+    selected_index: usize,
+}
+
+impl EncodingSelectorDelegate {
+    fn new(
+        encoding_selector: WeakEntity<EncodingSelector>,
+        buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        encoding: Encoding,
+    ) -> Self {
+        let matches = Encoding::ALL.to_vec();
+        let selected_index = matches
+            .iter()
+            .position(|match_| *match_ == encoding)
+            .unwrap_or_default();
+        Self {
+            encoding_selector,
+            buffer,
+            project,
+            encoding,
+            matches,
+            selected_index,
+        }
+    }
+}
+
+impl PickerDelegate for EncodingSelectorDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Save with encoding…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(encoding) = self.matches.get(self.selected_index).copied() {
+            let buffer = self.buffer.clone();
+            let project = self.project.clone();
+            let previous_encoding = self.encoding;
+            window.defer(cx, move |window, cx| {
+                buffer.update(cx, |buffer, cx| buffer.set_encoding(encoding, cx));
+                let save =
+                    project.update(cx, |project, cx| project.save_buffer(buffer.clone(), cx));
+                window
+                    .spawn(cx, async move |cx| {
+                        let result = save.await;
+                        // Text that can't be represented in the encoding isn't saved, so the
+                        // buffer keeps its previous encoding.
+                        if result.is_err() {
+                            buffer.update(cx, |buffer, cx| {
+                                buffer.set_encoding(previous_encoding, cx)
+                            })?;
+                        }
+                        result
+                    })
+                    .detach_and_notify_err(window, cx);
+            });
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.encoding_selector
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        _query: String,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        Task::ready(())
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let encoding = self.matches.get(ix)?;
+
+        let mut list_item = ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected)
+            .child(Label::new(encoding.name()));
+
+        if &self.encoding == encoding {
+            list_item = list_item.end_slot(Icon::new(IconName::Check).color(Color::Muted));
+        }
+
+        Some(list_item)
+    }
+}
@@ -0,0 +1,475 @@
+//! Local history of edited files.
+//!
+//! Every local file is snapshotted when it is opened in an editor, whenever it is saved or reloaded
+//! from disk after that, and periodically while it has unsaved edits. Snapshots are plain copies of the file stored under [`paths::file_history_dir`], so they
+//! survive restarts and work without any version control.
+mod file_history_view;
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use collections::HashMap;
+use editor::Editor;
+use fs::{Fs, RemoveOptions};
+use futures::StreamExt as _;
+use gpui::{App, AppContext as _, Context, Entity, EntityId, Global, Subscription, Task, actions};
+use language::{Buffer, BufferEvent, LineEnding};
+use sha2::{Digest as _, Sha256};
+use util::ResultExt as _;
+
+pub use file_history_view::FileHistoryView;
+
+actions!(
+    file_history,
+    [
+        /// Shows the local history of the current file.
+        Toggle
+    ]
+);
+
+const MAX_SNAPSHOTS_PER_FILE: usize = 50;
+/// How long unsaved edits can go without being snapshotted.
+const EDIT_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// Files larger than this, in bytes, aren't snapshotted.
+const MAX_SNAPSHOT_LEN: usize = 1024 * 1024;
+
+pub fn init(fs: Arc<dyn Fs>, cx: &mut App) {
+    let file_history = cx.new(|_| FileHistory::new(fs));
+    cx.set_global(GlobalFileHistory(file_history));
+
+    cx.observe_new(|editor: &mut Editor, _, cx| {
+        if !editor.mode().is_full() {
+            return;
+        }
+        if let Some(buffer) = editor.buffer().read(cx).as_singleton() {
+            FileHistory::global(cx).update(cx, |file_history, cx| {
+                file_history.watch_buffer(&buffer, cx)
+            });
+        }
+    })
+    .detach();
+    cx.observe_new(FileHistoryView::register).detach();
+}
+
+struct GlobalFileHistory(Entity<FileHistory>);
+
+impl Global for GlobalFileHistory {}
+
+/// A snapshot of a file, as stored in the local history.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileSnapshot {
+    /// Where the snapshot contents are stored.
+    pub path: PathBuf,
+    /// When the snapshot was taken, in milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+}
+
+struct WatchedBuffer {
+    pending_edit_snapshot: Option<Task<()>>,
+    _subscriptions: [Subscription; 2],
+}
+
+pub struct FileHistory {
+    fs: Arc<dyn Fs>,
+    watched_buffers: HashMap<EntityId, WatchedBuffer>,
+}
+
+impl FileHistory {
+    fn new(fs: Arc<dyn Fs>) -> Self {
+        Self {
+            fs,
+            watched_buffers: HashMap::default(),
+        }
+    }
+
+    pub fn global(cx: &App) -> Entity<Self> {
+        cx.global::<GlobalFileHistory>().0.clone()
+    }
+
+    pub fn try_global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalFileHistory>()
+            .map(|file_history| file_history.0.clone())
+    }
+
+    /// Snapshots `buffer`, which was opened in an editor, and snapshots it again whenever it's
+    /// saved or reloaded, and at most every [`EDIT_SNAPSHOT_INTERVAL`] while it's being edited.
+    fn watch_buffer(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        let buffer_id = buffer.entity_id();
+        if self.watched_buffers.contains_key(&buffer_id) {
+            return;
+        }
+        if !buffer.read(cx).is_dirty() {
+            self.snapshot_buffer(buffer, cx);
+        }
+        let subscriptions = [
+            cx.subscribe(buffer, |this, buffer, event, cx| match event {
+                BufferEvent::Saved | BufferEvent::Reloaded => {
+                    if let Some(watched_buffer) = this.watched_buffers.get_mut(&buffer.entity_id())
+                    {
+                        watched_buffer.pending_edit_snapshot = None;
+                    }
+                    this.snapshot_buffer(&buffer, cx);
+                }
+                BufferEvent::Edited => this.schedule_edit_snapshot(buffer, cx),
+                _ => {}
+            }),
+            cx.observe_release(buffer, move |this, _, _| {
+                this.watched_buffers.remove(&buffer_id);
+            }),
+        ];
+        self.watched_buffers.insert(
+            buffer_id,
+            WatchedBuffer {
+                pending_edit_snapshot: None,
+                _subscriptions: subscriptions,
+            },
+        );
+    }
+
+    fn schedule_edit_snapshot(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
+        let buffer_id = buffer.entity_id();
+        let Some(watched_buffer) = self.watched_buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        if watched_buffer.pending_edit_snapshot.is_some() {
+            return;
+        }
+        let buffer = buffer.downgrade();
+        watched_buffer.pending_edit_snapshot = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(EDIT_SNAPSHOT_INTERVAL).await;
+            this.update(cx, |this, cx| {
+                if let Some(watched_buffer) = this.watched_buffers.get_mut(&buffer_id) {
+                    watched_buffer.pending_edit_snapshot = None;
+                }
+                if let Some(buffer) = buffer.upgrade() {
+                    this.snapshot_buffer(&buffer, cx);
+                }
+            })
+            .ok();
+        }));
+    }
+
+    /// Records the current contents of `buffer`, unless they match the latest snapshot, or the
+    /// buffer is too large to be worth snapshotting.
+    pub fn snapshot_buffer(&mut self, buffer: &Entity<Buffer>, cx: &mut Context<Self>) {
+        if buffer.read(cx).large_file_mode() || buffer.read(cx).len() > MAX_SNAPSHOT_LEN {
+            return;
+        }
+        let Some(abs_path) = local_abs_path(buffer, cx) else {
+            return;
+        };
+
+        let buffer = buffer.read(cx);
+        let text = buffer.as_rope().clone();
+        let line_ending = buffer.line_ending();
+        let fs = self.fs.clone();
+        cx.background_spawn(async move {
+            let snapshots = list_snapshots(fs.as_ref(), &abs_path).await?;
+            // Snapshots are saved with the file's line endings, while the buffer text uses `\n`.
+            if let Some(latest) = snapshots.first()
+                && let Ok(mut latest_text) = fs.load(&latest.path).await
+            {
+                LineEnding::normalize(&mut latest_text);
+                if latest_text == text.to_string() {
+                    return Ok(());
+                }
+            }
+
+            let dir = snapshots_dir(&abs_path);
+            fs.create_dir(&dir).await?;
+            let now_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64;
+            // Snapshot names must stay unique and ordered, even when taken within the same millisecond.
+            let timestamp_ms = snapshots
+                .first()
+                .map_or(now_ms, |latest| now_ms.max(latest.timestamp_ms + 1));
+            let file_name = abs_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            fs.save(
+                &dir.join(format!("{timestamp_ms}-{file_name}")),
+                &text,
+                line_ending,
+            )
+            .await?;
+
+            for stale in snapshots.iter().skip(MAX_SNAPSHOTS_PER_FILE - 1) {
+                fs.remove_file(&stale.path, RemoveOptions::default())
+                    .await
+                    .log_err();
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Returns the snapshots of the file at `abs_path`, most recent first.
+    pub fn snapshots(&self, abs_path: &Path, cx: &App) -> Task<Result<Vec<FileSnapshot>>> {
+        let fs = self.fs.clone();
+        let abs_path = abs_path.to_path_buf();
+        cx.background_spawn(async move { list_snapshots(fs.as_ref(), &abs_path).await })
+    }
+
+    /// Replaces the contents of `buffer` with those of `snapshot`, as a single undoable edit.
+    pub fn restore_snapshot(
+        &mut self,
+        buffer: Entity<Buffer>,
+        snapshot: &FileSnapshot,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        // Keep the current contents around, so restoring can itself be reverted.
+        self.snapshot_buffer(&buffer, cx);
+
+        let fs = self.fs.clone();
+        let snapshot_path = snapshot.path.clone();
+        cx.spawn(async move |_, cx| {
+            let text = fs.load(&snapshot_path).await?;
+            let diff = buffer.update(cx, |buffer, cx| buffer.diff(text, cx))?.await;
+            buffer.update(cx, |buffer, cx| buffer.apply_diff(diff, cx))?;
+            Ok(())
+        })
+    }
+}
+
+fn local_abs_path(buffer: &Entity<Buffer>, cx: &App) -> Option<PathBuf> {
+    let file = buffer.read(cx).file()?.as_local()?;
+    Some(file.abs_path(cx))
+}
+
+fn snapshots_dir(abs_path: &Path) -> PathBuf {
+    let digest = Sha256::digest(abs_path.to_string_lossy().as_bytes());
+    paths::file_history_dir().join(format!("{digest:x}"))
+}
+
+async fn list_snapshots(fs: &dyn Fs, abs_path: &Path) -> Result<Vec<FileSnapshot>> {
+    let dir = snapshots_dir(abs_path);
+    if !fs.is_dir(&dir).await {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots = Vec::new();
+    let mut entries = fs.read_dir(&dir).await?;
+    while let Some(path) = entries.next().await {
+        let path = path?;
+        let Some(timestamp_ms) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.split_once('-'))
+            .and_then(|(timestamp, _)| timestamp.parse().ok())
+        else {
+            continue;
+        };
+        snapshots.push(FileSnapshot { path, timestamp_ms });
+    }
+    snapshots.sort_by(|a, b| b.timestamp_ms.cmp(&a.timestamp_ms));
+    Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use project::Project;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_snapshots_and_restore(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/root"), json!({ "a.txt": "one" }))
+            .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        let file_history = cx.new(|_| FileHistory::new(fs.clone()));
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/a.txt"), cx)
+            })
+            .await
+            .unwrap();
+        file_history.update(cx, |file_history, cx| {
+            file_history.watch_buffer(&buffer, cx)
+        });
+        cx.run_until_parked();
+        let abs_path = Path::new(path!("/root/a.txt"));
+        let snapshots = file_history
+            .read_with(cx, |file_history, cx| file_history.snapshots(abs_path, cx))
+            .await
+            .unwrap();
+        assert_eq!(snapshots.len(), 1, "opening a file snapshots it");
+
+        buffer.update(cx, |buffer, cx| buffer.edit([(0..3, "two")], None, cx));
+        project
+            .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        let snapshots = file_history
+            .read_with(cx, |file_history, cx| file_history.snapshots(abs_path, cx))
+            .await
+            .unwrap();
+        assert_eq!(snapshots.len(), 2, "saving a file snapshots it");
+        assert_eq!(fs.load(&snapshots[0].path).await.unwrap(), "two");
+        assert_eq!(fs.load(&snapshots[1].path).await.unwrap(), "one");
+
+        // Saving unchanged contents doesn't add a snapshot.
+        project
+            .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        let snapshots = file_history
+            .read_with(cx, |file_history, cx| file_history.snapshots(abs_path, cx))
+            .await
+            .unwrap();
+        assert_eq!(snapshots.len(), 2);
+
+        file_history
+            .update(cx, |file_history, cx| {
+                file_history.restore_snapshot(buffer.clone(), &snapshots[1], cx)
+            })
+            .await
+            .unwrap();
+        assert_eq!(buffer.read_with(cx, |buffer, _| buffer.text()), "one");
+        buffer.update(cx, |buffer, cx| buffer.undo(cx));
+        assert_eq!(buffer.read_with(cx, |buffer, _| buffer.text()), "two");
+    }
+
+    #[gpui::test]
+    async fn test_snapshots_while_editing_and_on_reload(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/root"), json!({ "a.txt": "one" }))
+            .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        let file_history = cx.new(|_| FileHistory::new(fs.clone()));
+        let abs_path = Path::new(path!("/root/a.txt"));
+        let snapshot_texts = |cx: &mut TestAppContext| {
+            let snapshots =
+                file_history.read_with(cx, |file_history, cx| file_history.snapshots(abs_path, cx));
+            let fs = fs.clone();
+            async move {
+                let mut texts = Vec::new();
+                for snapshot in snapshots.await.unwrap() {
+                    texts.push(fs.load(&snapshot.path).await.unwrap());
+                }
+                texts
+            }
+        };
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/a.txt"), cx)
+            })
+            .await
+            .unwrap();
+        file_history.update(cx, |file_history, cx| {
+            file_history.watch_buffer(&buffer, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(snapshot_texts(cx).await, ["one"]);
+
+        // Unsaved edits are snapshotted once the interval has passed since the first of them.
+        buffer.update(cx, |buffer, cx| buffer.edit([(3..3, " two")], None, cx));
+        cx.executor().advance_clock(EDIT_SNAPSHOT_INTERVAL / 2);
+        buffer.update(cx, |buffer, cx| buffer.edit([(7..7, " three")], None, cx));
+        cx.run_until_parked();
+        assert_eq!(snapshot_texts(cx).await, ["one"]);
+        cx.executor().advance_clock(EDIT_SNAPSHOT_INTERVAL / 2);
+        cx.run_until_parked();
+        assert_eq!(snapshot_texts(cx).await, ["one two three", "one"]);
+
+        // Changes made outside of the editor are snapshotted once the buffer reloads them.
+        project
+            .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(snapshot_texts(cx).await, ["one two three", "one"]);
+        fs.save(
+            Path::new(path!("/root/a.txt")),
+            &"four".into(),
+            LineEnding::Unix,
+        )
+        .await
+        .unwrap();
+        cx.run_until_parked();
+        assert_eq!(buffer.read_with(cx, |buffer, _| buffer.text()), "four");
+        assert_eq!(snapshot_texts(cx).await, ["four", "one two three", "one"]);
+    }
+
+    #[gpui::test]
+    async fn test_snapshots_skip_unchanged_crlf_and_large_files(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "crlf.txt": "one\r\ntwo\r\n",
+                "large.txt": "a".repeat(MAX_SNAPSHOT_LEN + 1),
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+        let file_history = cx.new(|_| FileHistory::new(fs.clone()));
+        let snapshot_count = |abs_path: &'static str, cx: &mut TestAppContext| {
+            let snapshots = file_history.read_with(cx, |file_history, cx| {
+                file_history.snapshots(Path::new(abs_path), cx)
+            });
+            async move { snapshots.await.unwrap().len() }
+        };
+
+        let crlf_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/crlf.txt"), cx)
+            })
+            .await
+            .unwrap();
+        file_history.update(cx, |file_history, cx| {
+            file_history.watch_buffer(&crlf_buffer, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(snapshot_count(path!("/root/crlf.txt"), cx).await, 1);
+
+        // The snapshot keeps the file's CRLF line endings, which still match the buffer's text.
+        project
+            .update(cx, |project, cx| {
+                project.save_buffer(crlf_buffer.clone(), cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(snapshot_count(path!("/root/crlf.txt"), cx).await, 1);
+
+        let large_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/large.txt"), cx)
+            })
+            .await
+            .unwrap();
+        file_history.update(cx, |file_history, cx| {
+            file_history.watch_buffer(&large_buffer, cx)
+        });
+        cx.run_until_parked();
+        assert_eq!(snapshot_count(path!("/root/large.txt"), cx).await, 0);
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+        });
+    }
+}
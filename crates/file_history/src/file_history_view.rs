@@ -0,0 +1,292 @@
+use std::sync::Arc;
+
+use editor::Editor;
+use git_ui::file_diff_view::FileDiffView;
+use gpui::{
+    Action as _, AnyElement, App, Context, DismissEvent, Entity, EventEmitter, FocusHandle,
+    Focusable, Task, WeakEntity, Window,
+};
+use language::Buffer;
+use picker::{Picker, PickerDelegate};
+use time::{OffsetDateTime, UtcOffset};
+use ui::{KeyBinding, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+use crate::{FileHistory, FileSnapshot, Toggle, local_abs_path};
+
+pub struct FileHistoryView {
+    picker: Entity<Picker<FileHistoryDelegate>>,
+}
+
+impl FileHistoryView {
+    pub(crate) fn register(
+        editor: &mut Editor,
+        _window: Option<&mut Window>,
+        cx: &mut Context<Editor>,
+    ) {
+        if !editor.mode().is_full() {
+            return;
+        }
+        let editor_handle = cx.weak_entity();
+        editor
+            .register_action(move |_: &Toggle, window, cx| {
+                Self::toggle(&editor_handle, window, cx);
+            })
+            .detach();
+    }
+
+    fn toggle(editor: &WeakEntity<Editor>, window: &mut Window, cx: &mut App) {
+        let Some(file_history) = FileHistory::try_global(cx) else {
+            return;
+        };
+        let Some((workspace, buffer)) = editor
+            .update(cx, |editor, cx| {
+                Some((editor.workspace()?, editor.active_excerpt(cx)?.1))
+            })
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        let Some(abs_path) = local_abs_path(&buffer, cx) else {
+            return;
+        };
+
+        let snapshots = file_history.read(cx).snapshots(&abs_path, cx);
+        window
+            .spawn(cx, async move |cx| {
+                let snapshots = snapshots.await?;
+                workspace.update_in(cx, |workspace, window, cx| {
+                    let weak_workspace = workspace.weak_handle();
+                    workspace.toggle_modal(window, cx, move |window, cx| {
+                        FileHistoryView::new(weak_workspace, buffer, snapshots, window, cx)
+                    });
+                })
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        buffer: Entity<Buffer>,
+        snapshots: Vec<FileSnapshot>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate =
+            FileHistoryDelegate::new(cx.entity().downgrade(), workspace, buffer, snapshots, cx);
+        let picker = cx.new(|cx| {
+            Picker::nonsearchable_uniform_list(delegate, window, cx)
+                .max_height(Some(vh(0.75, window)))
+        });
+        let picker_focus_handle = picker.focus_handle(cx);
+        picker.update(cx, |picker, _| {
+            picker.delegate.focus_handle = picker_focus_handle;
+        });
+        Self { picker }
+    }
+}
+
+impl Render for FileHistoryView {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for FileHistoryView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for FileHistoryView {}
+impl ModalView for FileHistoryView {}
+
+pub struct FileHistoryDelegate {
+    file_history_view: WeakEntity<FileHistoryView>,
+    workspace: WeakEntity<Workspace>,
+    buffer: Entity<Buffer>,
+    snapshots: Vec<FileSnapshot>,
+    selected_index: usize,
+    timezone: UtcOffset,
+    focus_handle: FocusHandle,
+}
+
+impl FileHistoryDelegate {
+    fn new(
+        file_history_view: WeakEntity<FileHistoryView>,
+        workspace: WeakEntity<Workspace>,
+        buffer: Entity<Buffer>,
+        snapshots: Vec<FileSnapshot>,
+        cx: &mut Context<FileHistoryView>,
+    ) -> Self {
+        let timezone =
+            UtcOffset::from_whole_seconds(chrono::Local::now().offset().local_minus_utc())
+                .unwrap_or(UtcOffset::UTC);
+        Self {
+            file_history_view,
+            workspace,
+            buffer,
+            snapshots,
+            selected_index: 0,
+            timezone,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn format_timestamp(&self, snapshot: &FileSnapshot) -> String {
+        let timestamp =
+            OffsetDateTime::from_unix_timestamp_nanos(snapshot.timestamp_ms as i128 * 1_000_000)
+                .unwrap_or(OffsetDateTime::now_utc());
+        time_format::format_localized_timestamp(
+            timestamp,
+            OffsetDateTime::now_utc(),
+            self.timezone,
+            time_format::TimestampFormat::EnhancedAbsolute,
+        )
+    }
+
+    fn open_diff(&self, snapshot: &FileSnapshot, window: &mut Window, cx: &mut App) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let old_path = snapshot.path.clone();
+        let new_buffer = Task::ready(Ok(self.buffer.clone()));
+        workspace.update(cx, |workspace, cx| {
+            FileDiffView::open_against_buffer(old_path, new_buffer, workspace, window, cx)
+                .detach_and_log_err(cx);
+        });
+    }
+
+    fn restore(&self, snapshot: &FileSnapshot, cx: &mut App) {
+        let Some(file_history) = FileHistory::try_global(cx) else {
+            return;
+        };
+        file_history.update(cx, |file_history, cx| {
+            file_history
+                .restore_snapshot(self.buffer.clone(), snapshot, cx)
+                .detach_and_log_err(cx);
+        });
+    }
+}
+
+impl PickerDelegate for FileHistoryDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a snapshot…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No local history for this file".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(snapshot) = self.snapshots.get(self.selected_index) {
+            if secondary {
+                self.restore(snapshot, cx);
+            } else {
+                self.open_diff(snapshot, window, cx);
+            }
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.file_history_view
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        _query: String,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        Task::ready(())
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let snapshot = self.snapshots.get(ix)?;
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+                .child(Label::new(self.format_timestamp(snapshot))),
+        )
+    }
+
+    fn render_footer(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<AnyElement> {
+        if self.snapshots.is_empty() {
+            return None;
+        }
+        let focus_handle = self.focus_handle.clone();
+
+        Some(
+            h_flex()
+                .w_full()
+                .p_1p5()
+                .gap_0p5()
+                .justify_end()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    Button::new("restore-snapshot", "Restore")
+                        .key_binding(
+                            KeyBinding::for_action_in(
+                                &menu::SecondaryConfirm,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                            .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::SecondaryConfirm.boxed_clone(), cx)
+                        }),
+                )
+                .child(
+                    Button::new("open-snapshot-diff", "Open Diff")
+                        .key_binding(
+                            KeyBinding::for_action_in(&menu::Confirm, &focus_handle, window, cx)
+                                .map(|kb| kb.size(rems_from_px(12.))),
+                        )
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(menu::Confirm.boxed_clone(), cx)
+                        }),
+                )
+                .into_any(),
+        )
+    }
+}
@@ -173,6 +173,7 @@ fn conflicts_updated(
     cx: &mut Context<Editor>,
 ) {
     let buffer_id = conflict_set.read(cx).snapshot.buffer_id;
+    let is_reload_conflict = conflict_set.read(cx).has_reload_conflict;
     let conflict_set = conflict_set.read(cx).snapshot();
     let multibuffer = editor.buffer().read(cx);
     let snapshot = multibuffer.snapshot(cx);
@@ -294,7 +295,15 @@ fn conflicts_updated(
             style: BlockStyle::Fixed,
             render: Arc::new({
                 let conflict = conflict.clone();
-                move |cx| render_conflict_buttons(&conflict, excerpt_id, editor_handle.clone(), cx)
+                move |cx| {
+                    render_conflict_buttons(
+                        &conflict,
+                        excerpt_id,
+                        is_reload_conflict,
+                        editor_handle.clone(),
+                        cx,
+                    )
+                }
             }),
             priority: 0,
         })
@@ -383,9 +392,16 @@ fn update_conflict_highlighting(
 fn render_conflict_buttons(
     conflict: &ConflictRegion,
     excerpt_id: ExcerptId,
+    is_reload_conflict: bool,
     editor: WeakEntity<Editor>,
     cx: &mut BlockContext,
 ) -> AnyElement {
+    // Conflicts from merging changes on disk are between unsaved edits and the file on disk.
+    let (ours_label, theirs_label) = if is_reload_conflict {
+        ("Use Unsaved", "Use Disk")
+    } else {
+        ("Use HEAD", "Use Origin")
+    };
     h_flex()
         .id(cx.block_id)
         .h(cx.line_height)
@@ -394,7 +410,7 @@ fn render_conflict_buttons(
         .gap_1()
         .bg(cx.theme().colors().editor_background)
         .child(
-            Button::new("head", "Use HEAD")
+            Button::new("head", ours_label)
                 .label_size(LabelSize::Small)
                 .on_click({
                     let editor = editor.clone();
@@ -414,7 +430,7 @@ fn render_conflict_buttons(
                 }),
         )
         .child(
-            Button::new("origin", "Use Origin")
+            Button::new("origin", theirs_label)
                 .label_size(LabelSize::Small)
                 .on_click({
                     let editor = editor.clone();
@@ -42,6 +42,21 @@ impl FileDiffView {
         workspace: &Workspace,
         window: &mut Window,
         cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let new_buffer = workspace
+            .project()
+            .update(cx, |project, cx| project.open_local_buffer(&new_path, cx));
+        Self::open_against_buffer(old_path, new_buffer, workspace, window, cx)
+    }
+
+    /// Opens a diff between the file at `old_path` and the buffer that `new_buffer` resolves to,
+    /// including any of its unsaved changes.
+    pub fn open_against_buffer(
+        old_path: PathBuf,
+        new_buffer: impl Future<Output = Result<Entity<Buffer>>> + 'static,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
     ) -> Task<Result<Entity<Self>>> {
         let workspace = workspace.weak_handle();
         window.spawn(cx, async move |cx| {
@@ -49,9 +64,7 @@ impl FileDiffView {
             let old_buffer = project
                 .update(cx, |project, cx| project.open_local_buffer(&old_path, cx))?
                 .await?;
-            let new_buffer = project
-                .update(cx, |project, cx| project.open_local_buffer(&new_path, cx))?
-                .await?;
+            let new_buffer = new_buffer.await?;
 
             let buffer_diff = build_buffer_diff(&old_buffer, &new_buffer, cx).await?;
 
@@ -44,6 +44,7 @@ pub mod picker_prompt;
 pub mod project_diff;
 pub(crate) mod remote_output;
 pub mod repository_selector;
+pub mod split_diff_view;
 pub mod stash_picker;
 pub mod text_diff_view;
 
@@ -72,6 +73,7 @@ pub fn init(cx: &mut App) {
         repository_selector::register(workspace);
         branch_picker::register(workspace);
         stash_picker::register(workspace);
+        split_diff_view::register(workspace);
 
         let project = workspace.project().read(cx);
         if project.is_read_only(cx) {
@@ -0,0 +1,878 @@
+//! SplitDiffView shows two buffers side by side, with their differing lines aligned and
+//! highlighted, and lets hunks be copied from one side to the other.
+
+use anyhow::Result;
+use collections::HashSet;
+use editor::{
+    Editor, EditorEvent, RowHighlightOptions,
+    display_map::{BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+};
+use futures::{FutureExt, future::try_join_all, select_biased};
+use gpui::{
+    AnyElement, AnyView, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, IntoElement, PathPromptOptions, Render, Subscription, Task, Window, actions,
+};
+use language::{Buffer, Point};
+use project::{DirectoryLister, Project};
+use std::{
+    any::{Any, TypeId},
+    iter,
+    ops::Range,
+    pin::pin,
+    sync::Arc,
+    time::Duration,
+};
+use ui::prelude::*;
+use util::{ResultExt as _, paths::PathExt as _};
+use workspace::{
+    Item, ItemHandle as _, ItemNavHistory, ToolbarItemLocation, Workspace,
+    item::{BreadcrumbText, ItemEvent, SaveOptions, TabContentParams},
+    searchable::SearchableItemHandle,
+};
+
+actions!(
+    split_diff,
+    [
+        /// Compares the active file side by side with a file chosen from a prompt.
+        CompareActiveFileWith,
+        /// Compares the active file side by side with the clipboard contents.
+        CompareActiveFileWithClipboard,
+        /// Replaces the hunk under the cursor on the left side with the right side's lines.
+        CopyHunkLeft,
+        /// Replaces the hunk under the cursor on the right side with the left side's lines.
+        CopyHunkRight,
+    ]
+);
+
+const RECALCULATE_DIFF_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Hunks spanning more rows than this on either side are highlighted by line only.
+const MAX_INTRALINE_DIFF_ROWS: usize = 8;
+
+pub fn register(workspace: &mut Workspace) {
+    workspace.register_action(|workspace, _: &CompareActiveFileWith, window, cx| {
+        compare_active_file_with_path(workspace, window, cx);
+    });
+    workspace.register_action(
+        |workspace, _: &CompareActiveFileWithClipboard, window, cx| {
+            compare_active_file_with_clipboard(workspace, window, cx);
+        },
+    );
+}
+
+fn active_buffer(workspace: &Workspace, cx: &App) -> Option<Entity<Buffer>> {
+    workspace
+        .active_item_as::<Editor>(cx)?
+        .read(cx)
+        .buffer()
+        .read(cx)
+        .as_singleton()
+}
+
+fn compare_active_file_with_path(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(active_buffer) = active_buffer(workspace, cx) else {
+        return;
+    };
+    let project = workspace.project().clone();
+    let paths = workspace.prompt_for_open_path(
+        PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Compare".into()),
+        },
+        DirectoryLister::Project(project.clone()),
+        window,
+        cx,
+    );
+    cx.spawn_in(window, async move |workspace, cx| {
+        let Some(path) = paths
+            .await
+            .log_err()
+            .flatten()
+            .and_then(|paths| paths.into_iter().next())
+        else {
+            return Ok(());
+        };
+        let buffer = project
+            .update(cx, |project, cx| project.open_local_buffer(&path, cx))?
+            .await?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            SplitDiffView::deploy(buffer, active_buffer, workspace, window, cx);
+        })
+    })
+    .detach_and_log_err(cx);
+}
+
+fn compare_active_file_with_clipboard(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(active_buffer) = active_buffer(workspace, cx) else {
+        return;
+    };
+    let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+        return;
+    };
+    let language = active_buffer.read(cx).language().cloned();
+    let clipboard_buffer = cx.new(|cx| {
+        let mut buffer = Buffer::local(text, cx);
+        buffer.set_language(language, cx);
+        buffer
+    });
+    SplitDiffView::deploy(clipboard_buffer, active_buffer, workspace, window, cx);
+}
+
+/// A changed region, as rows of both texts along with the byte ranges of the words that differ
+/// within it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SplitDiffHunk {
+    old_rows: Range<u32>,
+    new_rows: Range<u32>,
+    old_changes: Vec<Range<usize>>,
+    new_changes: Vec<Range<usize>>,
+}
+
+fn line_starts(text: &str) -> Vec<usize> {
+    iter::once(0)
+        .chain(text.match_indices('\n').map(|(ix, _)| ix + 1))
+        .collect()
+}
+
+fn byte_range(line_starts: &[usize], len: usize, rows: Range<u32>) -> Range<usize> {
+    let offset = |row: u32| line_starts.get(row as usize).copied().unwrap_or(len);
+    offset(rows.start)..offset(rows.end)
+}
+
+fn compute_hunks(old_text: &str, new_text: &str) -> Vec<SplitDiffHunk> {
+    let old_line_starts = line_starts(old_text);
+    let new_line_starts = line_starts(new_text);
+    language::line_diff(old_text, new_text)
+        .into_iter()
+        .map(|(old_rows, new_rows)| {
+            let mut hunk = SplitDiffHunk {
+                old_rows,
+                new_rows,
+                old_changes: Vec::new(),
+                new_changes: Vec::new(),
+            };
+            let (old_len, new_len) = (hunk.old_rows.len(), hunk.new_rows.len());
+            if old_len == 0
+                || new_len == 0
+                || old_len > MAX_INTRALINE_DIFF_ROWS
+                || new_len > MAX_INTRALINE_DIFF_ROWS
+            {
+                return hunk;
+            }
+
+            let old_range = byte_range(&old_line_starts, old_text.len(), hunk.old_rows.clone());
+            let new_range = byte_range(&new_line_starts, new_text.len(), hunk.new_rows.clone());
+            let (old_changes, new_changes) =
+                language::word_diff(&old_text[old_range.clone()], &new_text[new_range.clone()]);
+            hunk.old_changes = old_changes
+                .into_iter()
+                .map(|range| old_range.start + range.start..old_range.start + range.end)
+                .collect();
+            hunk.new_changes = new_changes
+                .into_iter()
+                .map(|range| new_range.start + range.start..new_range.start + range.end)
+                .collect();
+            hunk
+        })
+        .collect()
+}
+
+struct DeletedRows;
+struct AddedRows;
+struct DeletedWords;
+struct AddedWords;
+
+pub struct SplitDiffView {
+    left_editor: Entity<Editor>,
+    right_editor: Entity<Editor>,
+    left_buffer: Entity<Buffer>,
+    right_buffer: Entity<Buffer>,
+    hunks: Vec<SplitDiffHunk>,
+    left_spacers: HashSet<CustomBlockId>,
+    right_spacers: HashSet<CustomBlockId>,
+    buffer_changes_tx: watch::Sender<()>,
+    _recalculate_diff_task: Task<Result<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl SplitDiffView {
+    /// Opens a view comparing `left_buffer` with `right_buffer` in the active pane.
+    pub fn deploy(
+        left_buffer: Entity<Buffer>,
+        right_buffer: Entity<Buffer>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let project = workspace.project().clone();
+        let diff_view =
+            cx.new(|cx| SplitDiffView::new(left_buffer, right_buffer, project, window, cx));
+        workspace.active_pane().update(cx, |pane, cx| {
+            pane.add_item(Box::new(diff_view.clone()), true, true, None, window, cx);
+        });
+        diff_view
+    }
+
+    pub fn new(
+        left_buffer: Entity<Buffer>,
+        right_buffer: Entity<Buffer>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let build_editor =
+            |buffer: &Entity<Buffer>, window: &mut Window, cx: &mut Context<Self>| {
+                cx.new(|cx| {
+                    let mut editor =
+                        Editor::for_buffer(buffer.clone(), Some(project.clone()), window, cx);
+                    editor.disable_diagnostics(cx);
+                    editor.set_show_git_diff_gutter(false, cx);
+                    // Spacer blocks keep the sides aligned by buffer row, which wrapping would break.
+                    editor.set_soft_wrap_mode(language::language_settings::SoftWrap::None, cx);
+                    editor
+                })
+            };
+        let left_editor = build_editor(&left_buffer, window, cx);
+        let right_editor = build_editor(&right_buffer, window, cx);
+
+        let mut subscriptions = Vec::new();
+        for editor in [&left_editor, &right_editor] {
+            subscriptions.push(cx.subscribe_in(editor, window, Self::handle_editor_event));
+        }
+        for buffer in [&left_buffer, &right_buffer] {
+            subscriptions.push(cx.subscribe(buffer, |this, _, event, _| match event {
+                language::BufferEvent::Edited
+                | language::BufferEvent::LanguageChanged
+                | language::BufferEvent::Reparsed => {
+                    this.buffer_changes_tx.send(()).ok();
+                }
+                _ => {}
+            }));
+        }
+
+        let (buffer_changes_tx, mut buffer_changes_rx) = watch::channel(());
+        let mut this = Self {
+            left_editor,
+            right_editor,
+            left_buffer,
+            right_buffer,
+            hunks: Vec::new(),
+            left_spacers: HashSet::default(),
+            right_spacers: HashSet::default(),
+            buffer_changes_tx,
+            _recalculate_diff_task: cx.spawn(async move |this, cx| {
+                while buffer_changes_rx.recv().await.is_ok() {
+                    loop {
+                        let mut timer = cx
+                            .background_executor()
+                            .timer(RECALCULATE_DIFF_DEBOUNCE)
+                            .fuse();
+                        let mut recv = pin!(buffer_changes_rx.recv().fuse());
+                        select_biased! {
+                            _ = timer => break,
+                            _ = recv => continue,
+                        }
+                    }
+
+                    log::trace!("start recalculating");
+                    let (old_text, new_text) = this.update(cx, |this, cx| {
+                        (
+                            this.left_buffer.read(cx).text(),
+                            this.right_buffer.read(cx).text(),
+                        )
+                    })?;
+                    let hunks = cx
+                        .background_spawn(async move { compute_hunks(&old_text, &new_text) })
+                        .await;
+                    this.update(cx, |this, cx| this.set_hunks(hunks, cx))?;
+                    log::trace!("finish recalculating");
+                }
+                Ok(())
+            }),
+            _subscriptions: subscriptions,
+        };
+        let hunks = compute_hunks(
+            &this.left_buffer.read(cx).text(),
+            &this.right_buffer.read(cx).text(),
+        );
+        this.set_hunks(hunks, cx);
+        this
+    }
+
+    fn handle_editor_event(
+        &mut self,
+        editor: &Entity<Editor>,
+        event: &EditorEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let EditorEvent::ScrollPositionChanged { .. } = event {
+            let other_editor = if *editor == self.left_editor {
+                self.right_editor.clone()
+            } else {
+                self.left_editor.clone()
+            };
+            let scroll_position = editor.update(cx, |editor, cx| editor.scroll_position(cx));
+            // Only scroll when the positions differ, so that the other editor's own scroll
+            // event doesn't bounce back.
+            other_editor.update(cx, |other_editor, cx| {
+                if other_editor.scroll_position(cx) != scroll_position {
+                    other_editor.set_scroll_position(scroll_position, window, cx);
+                }
+            });
+        }
+        cx.emit(event.clone());
+    }
+
+    fn set_hunks(&mut self, hunks: Vec<SplitDiffHunk>, cx: &mut Context<Self>) {
+        let left_spacers = hunks.iter().filter_map(|hunk| {
+            let height = hunk.new_rows.len().checked_sub(hunk.old_rows.len())?;
+            (height > 0).then_some((hunk.old_rows.end, height as u32))
+        });
+        let right_spacers = hunks.iter().filter_map(|hunk| {
+            let height = hunk.old_rows.len().checked_sub(hunk.new_rows.len())?;
+            (height > 0).then_some((hunk.new_rows.end, height as u32))
+        });
+        self.left_spacers = update_editor(
+            &self.left_editor,
+            std::mem::take(&mut self.left_spacers),
+            left_spacers.collect(),
+            hunks.iter().map(|hunk| hunk.old_rows.clone()),
+            hunks
+                .iter()
+                .flat_map(|hunk| hunk.old_changes.iter().cloned()),
+            Side::Left,
+            cx,
+        );
+        self.right_spacers = update_editor(
+            &self.right_editor,
+            std::mem::take(&mut self.right_spacers),
+            right_spacers.collect(),
+            hunks.iter().map(|hunk| hunk.new_rows.clone()),
+            hunks
+                .iter()
+                .flat_map(|hunk| hunk.new_changes.iter().cloned()),
+            Side::Right,
+            cx,
+        );
+        self.hunks = hunks;
+        cx.notify();
+    }
+
+    fn copy_hunk_left(&mut self, _: &CopyHunkLeft, window: &mut Window, cx: &mut Context<Self>) {
+        self.copy_hunk(Side::Left, window, cx);
+    }
+
+    fn copy_hunk_right(&mut self, _: &CopyHunkRight, window: &mut Window, cx: &mut Context<Self>) {
+        self.copy_hunk(Side::Right, window, cx);
+    }
+
+    /// Replaces the lines of the hunk under the cursor on the `target` side with the other
+    /// side's lines.
+    fn copy_hunk(&mut self, target: Side, window: &mut Window, cx: &mut Context<Self>) {
+        let left_focused = self.left_editor.focus_handle(cx).is_focused(window);
+        let (cursor_editor, cursor_side) = if left_focused {
+            (&self.left_editor, Side::Left)
+        } else {
+            (&self.right_editor, Side::Right)
+        };
+        let cursor_row = cursor_editor.update(cx, |editor, cx| {
+            editor.selections.newest::<Point>(cx).head().row
+        });
+
+        // The hunks may be out of date while a recalculation is pending, so diff the current
+        // contents.
+        let left_text = self.left_buffer.read(cx).text();
+        let right_text = self.right_buffer.read(cx).text();
+        let Some((left_rows, right_rows)) = language::line_diff(&left_text, &right_text)
+            .into_iter()
+            .find(|(left_rows, right_rows)| {
+                let rows = match cursor_side {
+                    Side::Left => left_rows,
+                    Side::Right => right_rows,
+                };
+                rows.contains(&cursor_row) || (rows.is_empty() && rows.start == cursor_row)
+            })
+        else {
+            return;
+        };
+
+        let left_range = byte_range(&line_starts(&left_text), left_text.len(), left_rows);
+        let right_range = byte_range(&line_starts(&right_text), right_text.len(), right_rows);
+        let (target_buffer, target_range, replacement) = match target {
+            Side::Left => (&self.left_buffer, left_range, &right_text[right_range]),
+            Side::Right => (&self.right_buffer, right_range, &left_text[left_range]),
+        };
+        target_buffer.update(cx, |buffer, cx| {
+            buffer.edit([(target_range, replacement)], None, cx);
+        });
+    }
+
+    fn title(&self, side: Side, cx: &App) -> SharedString {
+        let (buffer, fallback) = match side {
+            Side::Left => (&self.left_buffer, "Clipboard"),
+            Side::Right => (&self.right_buffer, "untitled"),
+        };
+        buffer
+            .read(cx)
+            .file()
+            .and_then(|file| {
+                Some(
+                    file.full_path(cx)
+                        .file_name()?
+                        .to_string_lossy()
+                        .to_string(),
+                )
+            })
+            .unwrap_or_else(|| fallback.into())
+            .into()
+    }
+
+    fn path(&self, side: Side, cx: &App) -> String {
+        let (buffer, fallback) = match side {
+            Side::Left => (&self.left_buffer, "Clipboard"),
+            Side::Right => (&self.right_buffer, "untitled"),
+        };
+        buffer
+            .read(cx)
+            .file()
+            .map(|file| file.full_path(cx).compact().to_string_lossy().into_owned())
+            .unwrap_or_else(|| fallback.into())
+    }
+
+    fn file_backed_editors(&self, cx: &App) -> Vec<Entity<Editor>> {
+        [
+            (&self.left_buffer, &self.left_editor),
+            (&self.right_buffer, &self.right_editor),
+        ]
+        .into_iter()
+        .filter(|(buffer, _)| buffer.read(cx).file().is_some())
+        .map(|(_, editor)| editor.clone())
+        .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// Replaces the spacers and highlights of one side's editor, returning the new spacers.
+fn update_editor(
+    editor: &Entity<Editor>,
+    old_spacers: HashSet<CustomBlockId>,
+    spacers: Vec<(u32, u32)>,
+    changed_rows: impl Iterator<Item = Range<u32>>,
+    changed_words: impl Iterator<Item = Range<usize>>,
+    side: Side,
+    cx: &mut App,
+) -> HashSet<CustomBlockId> {
+    let row_color = match side {
+        Side::Left => cx.theme().colors().version_control_deleted,
+        Side::Right => cx.theme().colors().version_control_added,
+    }
+    .opacity(0.15);
+
+    editor.update(cx, |editor, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let max_point = snapshot.max_point();
+        editor.remove_blocks(old_spacers, None, cx);
+        let blocks = spacers
+            .into_iter()
+            .map(|(row, height)| BlockProperties {
+                // Spacers after the last row, when it doesn't end in a newline, go below it.
+                placement: if row > max_point.row {
+                    BlockPlacement::Below(snapshot.anchor_after(max_point))
+                } else {
+                    BlockPlacement::Above(snapshot.anchor_before(Point::new(row, 0)))
+                },
+                height: Some(height),
+                style: BlockStyle::Fixed,
+                render: Arc::new(|_| div().into_any_element()),
+                priority: 0,
+            })
+            .collect();
+        let spacers = editor.insert_blocks(blocks, None, cx).into_iter().collect();
+
+        match side {
+            Side::Left => editor.clear_row_highlights::<DeletedRows>(),
+            Side::Right => editor.clear_row_highlights::<AddedRows>(),
+        }
+        for rows in changed_rows.filter(|rows| !rows.is_empty()) {
+            let start = snapshot.anchor_before(Point::new(rows.start, 0));
+            let end = snapshot.anchor_before(Point::new(rows.end - 1, 0));
+            match side {
+                Side::Left => editor.highlight_rows::<DeletedRows>(
+                    start..end,
+                    row_color,
+                    RowHighlightOptions::default(),
+                    cx,
+                ),
+                Side::Right => editor.highlight_rows::<AddedRows>(
+                    start..end,
+                    row_color,
+                    RowHighlightOptions::default(),
+                    cx,
+                ),
+            }
+        }
+
+        let word_ranges = changed_words
+            .map(|range| snapshot.anchor_after(range.start)..snapshot.anchor_before(range.end))
+            .collect::<Vec<_>>();
+        match side {
+            Side::Left => editor.highlight_background::<DeletedWords>(
+                &word_ranges,
+                |theme| theme.colors().version_control_deleted.opacity(0.4),
+                cx,
+            ),
+            Side::Right => editor.highlight_background::<AddedWords>(
+                &word_ranges,
+                |theme| theme.colors().version_control_added.opacity(0.4),
+                cx,
+            ),
+        }
+
+        spacers
+    })
+}
+
+impl EventEmitter<EditorEvent> for SplitDiffView {}
+
+impl Focusable for SplitDiffView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.right_editor.focus_handle(cx)
+    }
+}
+
+impl Item for SplitDiffView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Diff).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
+        format!(
+            "{} ↔ {}",
+            self.title(Side::Left, cx),
+            self.title(Side::Right, cx)
+        )
+        .into()
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        Some(
+            format!(
+                "{} ↔ {}",
+                self.path(Side::Left, cx),
+                self.path(Side::Right, cx)
+            )
+            .into(),
+        )
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Split Diff View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        for editor in [&self.left_editor, &self.right_editor] {
+            editor.update(cx, |editor, cx| editor.deactivated(window, cx));
+        }
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.right_editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.right_editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.left_editor.for_each_project_item(cx, f);
+        self.right_editor.for_each_project_item(cx, f);
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.right_editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.right_editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, theme: &theme::Theme, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.right_editor.breadcrumbs(theme, cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        for editor in [&self.left_editor, &self.right_editor] {
+            editor.update(cx, |editor, cx| {
+                editor.added_to_workspace(workspace, window, cx)
+            });
+        }
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        // Edits to the clipboard contents aren't worth prompting about when closing.
+        self.file_backed_editors(cx)
+            .iter()
+            .any(|editor| editor.read(cx).is_dirty(cx))
+    }
+
+    fn can_save(&self, cx: &App) -> bool {
+        !self.file_backed_editors(cx).is_empty()
+    }
+
+    fn save(
+        &mut self,
+        options: SaveOptions,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let saves = self
+            .file_backed_editors(cx)
+            .into_iter()
+            .map(|editor| {
+                editor.update(cx, |editor, cx| {
+                    editor.save(options, project.clone(), window, cx)
+                })
+            })
+            .collect::<Vec<_>>();
+        cx.background_spawn(async move {
+            try_join_all(saves).await?;
+            Ok(())
+        })
+    }
+}
+
+impl Render for SplitDiffView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .key_context("SplitDiffView")
+            .size_full()
+            .on_action(cx.listener(Self::copy_hunk_left))
+            .on_action(cx.listener(Self::copy_hunk_right))
+            .child(div().flex_1().h_full().child(self.left_editor.clone()))
+            .child(div().w_px().h_full().bg(cx.theme().colors().border))
+            .child(div().flex_1().h_full().child(self.right_editor.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::{FakeFs, Project};
+    use settings::SettingsStore;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init_settings(cx);
+            theme::init(theme::LoadThemes::JustBase, cx);
+        });
+    }
+
+    #[test]
+    fn test_compute_hunks() {
+        let old_text = "one\ntwo\nthree\nfour\nfive\n";
+        let new_text = "one\n2\nthree\nfive\nsix\nseven\n";
+        assert_eq!(
+            compute_hunks(old_text, new_text),
+            [
+                SplitDiffHunk {
+                    old_rows: 1..2,
+                    new_rows: 1..2,
+                    old_changes: vec![4..7],
+                    new_changes: vec![4..5],
+                },
+                SplitDiffHunk {
+                    old_rows: 3..4,
+                    new_rows: 3..3,
+                    old_changes: Vec::new(),
+                    new_changes: Vec::new(),
+                },
+                SplitDiffHunk {
+                    old_rows: 5..5,
+                    new_rows: 4..6,
+                    old_changes: Vec::new(),
+                    new_changes: Vec::new(),
+                },
+            ]
+        );
+
+        assert_eq!(compute_hunks("same\n", "same\n"), []);
+    }
+
+    #[gpui::test]
+    async fn test_split_diff_view(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "old_file.txt": "line 1\nold line 2\nline 3\nline 4\n",
+                "new_file.txt": "line 1\nnew line 2\nline 3\ninserted\nline 4\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let old_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/test/old_file.txt"), cx)
+            })
+            .await
+            .unwrap();
+        let new_buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/test/new_file.txt"), cx)
+            })
+            .await
+            .unwrap();
+
+        let diff_view = workspace.update_in(cx, |workspace, window, cx| {
+            SplitDiffView::deploy(
+                old_buffer.clone(),
+                new_buffer.clone(),
+                workspace,
+                window,
+                cx,
+            )
+        });
+
+        diff_view.read_with(cx, |diff_view, cx| {
+            assert_eq!(
+                diff_view.tab_content_text(0, cx),
+                "old_file.txt ↔ new_file.txt"
+            );
+            assert_eq!(
+                diff_view
+                    .hunks
+                    .iter()
+                    .map(|hunk| (hunk.old_rows.clone(), hunk.new_rows.clone()))
+                    .collect::<Vec<_>>(),
+                [(1..2, 1..2), (3..3, 3..4)]
+            );
+            // The inserted line is matched by a spacer on the left.
+            assert_eq!(diff_view.left_spacers.len(), 1);
+            assert!(diff_view.right_spacers.is_empty());
+        });
+
+        // Copying the insertion to the left side leaves only the changed line.
+        diff_view.update_in(cx, |diff_view, window, cx| {
+            diff_view.right_editor.update(cx, |editor, cx| {
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_ranges([Point::new(3, 0)..Point::new(3, 0)])
+                });
+            });
+            window.focus(&diff_view.right_editor.focus_handle(cx));
+            diff_view.copy_hunk(Side::Left, window, cx);
+        });
+        assert_eq!(
+            old_buffer.read_with(cx, |buffer, _| buffer.text()),
+            "line 1\nold line 2\nline 3\ninserted\nline 4\n"
+        );
+
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.run_until_parked();
+        diff_view.read_with(cx, |diff_view, _| {
+            assert_eq!(diff_view.hunks.len(), 1);
+            assert!(diff_view.left_spacers.is_empty());
+        });
+
+        // Copying the changed line to the right side makes both sides equal.
+        diff_view.update_in(cx, |diff_view, window, cx| {
+            window.focus(&diff_view.left_editor.focus_handle(cx));
+            diff_view.left_editor.update(cx, |editor, cx| {
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_ranges([Point::new(1, 0)..Point::new(1, 0)])
+                });
+            });
+            diff_view.copy_hunk(Side::Right, window, cx);
+        });
+        assert_eq!(
+            new_buffer.read_with(cx, |buffer, _| buffer.text()),
+            "line 1\nold line 2\nline 3\ninserted\nline 4\n"
+        );
+
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.run_until_parked();
+        diff_view.read_with(cx, |diff_view, _| assert!(diff_view.hunks.is_empty()));
+    }
+}
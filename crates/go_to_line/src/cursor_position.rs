@@ -15,6 +15,38 @@ pub(crate) struct SelectionStats {
     pub lines: usize,
     pub characters: usize,
     pub selections: usize,
+    /// The numbers that are selected, when every non-empty selection is a single number.
+    pub numbers: Option<NumberStats>,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialOrd, PartialEq)]
+pub(crate) struct NumberStats {
+    pub count: usize,
+    pub sum: f64,
+}
+
+impl NumberStats {
+    fn average(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+fn parse_number(text: &str) -> Option<f64> {
+    text.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+/// Formats `number` with at most four decimals, without trailing zeros.
+fn format_number(number: f64) -> String {
+    let text = format!("{number:.4}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
 }
 
 pub struct CursorPosition {
@@ -111,6 +143,9 @@ impl CursorPosition {
                             }
                             editor::EditorMode::Full { .. } => {
                                 let mut last_selection = None::<Selection<Point>>;
+                                let mut numbers = StatusBarSettings::get_global(cx)
+                                    .selection_number_stats
+                                    .then(NumberStats::default);
                                 let snapshot = editor.buffer().read(cx).snapshot(cx);
                                 if snapshot.excerpts().count() > 0 {
                                     for selection in
@@ -128,6 +163,18 @@ impl CursorPosition {
                                             if selection.end.column != 0 {
                                                 cursor_position.selected_count.lines += 1;
                                             }
+                                            if let Some(stats) = numbers.as_mut() {
+                                                let text = snapshot
+                                                    .text_for_range(selection.start..selection.end)
+                                                    .collect::<String>();
+                                                match parse_number(&text) {
+                                                    Some(number) => {
+                                                        stats.count += 1;
+                                                        stats.sum += number;
+                                                    }
+                                                    None => numbers = None,
+                                                }
+                                            }
                                         }
                                         if last_selection.as_ref().is_none_or(|last_selection| {
                                             selection.id > last_selection.id
@@ -136,6 +183,9 @@ impl CursorPosition {
                                         }
                                     }
                                 }
+                                // A sum is only worth showing for several numbers.
+                                cursor_position.selected_count.numbers =
+                                    numbers.filter(|numbers| numbers.count > 1);
                                 cursor_position.position = last_selection
                                     .map(|s| UserCaretPosition::at_selection_end(&s, &snapshot));
                                 cursor_position.context = Some(editor.focus_handle(cx));
@@ -166,6 +216,7 @@ impl CursorPosition {
             lines,
             characters,
             selections,
+            numbers,
         } = self.selected_count;
         let format = LineIndicatorFormat::get(None, cx);
         let is_short_format = format == &LineIndicatorFormat::Short;
@@ -191,6 +242,15 @@ impl CursorPosition {
             write!(text, "{count} {name}{plural_suffix}").unwrap();
             wrote_once = true;
         }
+        if let Some(numbers) = numbers {
+            write!(
+                text,
+                ", sum {}, avg {}",
+                format_number(numbers.sum),
+                format_number(numbers.average())
+            )
+            .unwrap();
+        }
         text.push(')');
     }
 
@@ -203,6 +263,13 @@ impl CursorPosition {
     pub(crate) fn position(&self) -> Option<UserCaretPosition> {
         self.position
     }
+
+    #[cfg(test)]
+    pub(crate) fn position_text(&self, cx: &App) -> String {
+        let mut text = String::new();
+        self.write_position(&mut text, cx);
+        text
+    }
 }
 
 impl Render for CursorPosition {
@@ -27,6 +27,8 @@ pub struct GoToLine {
     line_editor: Entity<Editor>,
     active_editor: Entity<Editor>,
     current_text: SharedString,
+    current_line: u32,
+    line_count: u32,
     prev_scroll_position: Option<gpui::Point<ScrollOffset>>,
     _subscriptions: Vec<Subscription>,
 }
@@ -133,6 +135,8 @@ impl GoToLine {
             line_editor,
             active_editor,
             current_text: current_text.into(),
+            current_line: line,
+            line_count: last_line + 1,
             prev_scroll_position: Some(scroll_position),
             _subscriptions: vec![line_editor_change, cx.on_release_in(window, Self::release)],
         }
@@ -234,13 +238,7 @@ impl GoToLine {
 
     fn line_and_char_from_query(&self, cx: &App) -> Option<(u32, Option<u32>)> {
         let input = self.line_editor.read(cx).text(cx);
-        let mut components = input
-            .splitn(2, FILE_ROW_COLUMN_DELIMITER)
-            .map(str::trim)
-            .fuse();
-        let row = components.next().and_then(|row| row.parse::<u32>().ok())?;
-        let column = components.next().and_then(|col| col.parse::<u32>().ok());
-        Some((row, column))
+        parse_query(&input, self.current_line, self.line_count)
     }
 
     fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
@@ -268,6 +266,32 @@ impl GoToLine {
     }
 }
 
+/// Parses a go to line query into a 1-based line and an optional 1-based column. The line can
+/// be a line number, a percentage through the file such as `50%`, or an offset from the
+/// current line such as `+20` or `-5`, and can be followed by `:column`.
+fn parse_query(query: &str, current_line: u32, line_count: u32) -> Option<(u32, Option<u32>)> {
+    let mut components = query
+        .splitn(2, FILE_ROW_COLUMN_DELIMITER)
+        .map(str::trim)
+        .fuse();
+    let line = components.next()?;
+    let line = if let Some(offset) = line.strip_prefix('+') {
+        current_line.saturating_add(offset.parse::<u32>().ok()?)
+    } else if let Some(offset) = line.strip_prefix('-') {
+        current_line
+            .saturating_sub(offset.parse::<u32>().ok()?)
+            .max(1)
+    } else if let Some(percentage) = line.strip_suffix('%') {
+        let percentage = percentage.trim().parse::<f64>().ok()?.clamp(0., 100.);
+        let last_row = line_count.saturating_sub(1) as f64;
+        (last_row * percentage / 100.).round() as u32 + 1
+    } else {
+        line.parse::<u32>().ok()?
+    };
+    let column = components.next().and_then(|col| col.parse::<u32>().ok());
+    Some((line, column))
+}
+
 impl Render for GoToLine {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let help_text = match self.line_and_char_from_query(cx) {
@@ -305,7 +329,7 @@ impl Render for GoToLine {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cursor_position::{CursorPosition, SelectionStats, UserCaretPosition};
+    use cursor_position::{CursorPosition, NumberStats, SelectionStats, UserCaretPosition};
     use editor::actions::{MoveRight, MoveToBeginning, SelectAll};
     use gpui::{TestAppContext, VisualTestContext};
     use indoc::indoc;
@@ -475,6 +499,7 @@ mod tests {
                     lines: 0,
                     characters: 0,
                     selections: 1,
+                    numbers: None,
                 },
                 workspace
                     .status_bar()
@@ -496,6 +521,7 @@ mod tests {
                     lines: 1,
                     characters: 3,
                     selections: 1,
+                    numbers: None,
                 },
                 workspace
                     .status_bar()
@@ -509,6 +535,108 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_selection_number_stats(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.update(|cx| {
+            cx.update_global::<settings::SettingsStore, _>(|settings, cx| {
+                settings.update_user_settings(cx, |settings| {
+                    settings
+                        .status_bar
+                        .get_or_insert_default()
+                        .selection_number_stats = Some(true);
+                });
+            });
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "a.rs": "10\n2.5\nx\n-4\n"
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        workspace.update_in(cx, |workspace, window, cx| {
+            let cursor_position = cx.new(|_| CursorPosition::new(workspace));
+            workspace.status_bar().update(cx, |status_bar, cx| {
+                status_bar.add_right_item(cursor_position, window, cx);
+            });
+        });
+
+        let worktree_id = workspace.update(cx, |workspace, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees(cx).next().unwrap().read(cx).id()
+            })
+        });
+        let _buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/dir/a.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, rel_path("a.rs")), None, true, window, cx)
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+
+        let selection_stats = |cx: &mut VisualTestContext| {
+            workspace.update(cx, |workspace, cx| {
+                let cursor_position = workspace
+                    .status_bar()
+                    .read(cx)
+                    .item_of_type::<CursorPosition>()
+                    .expect("missing cursor position item")
+                    .read(cx);
+                (
+                    *cursor_position.selection_stats(),
+                    cursor_position.position_text(cx),
+                )
+            })
+        };
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges([
+                    Point::new(0, 0)..Point::new(0, 2),
+                    Point::new(1, 0)..Point::new(1, 3),
+                    Point::new(3, 0)..Point::new(3, 2),
+                ])
+            });
+        });
+        cx.executor().advance_clock(Duration::from_millis(200));
+        let (stats, text) = selection_stats(cx);
+        assert_eq!(stats.numbers, Some(NumberStats { count: 3, sum: 8.5 }));
+        assert_eq!(
+            text,
+            " (3 selections, 3 lines, 7 characters, sum 8.5, avg 2.8333)"
+        );
+
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges([
+                    Point::new(0, 0)..Point::new(0, 2),
+                    Point::new(2, 0)..Point::new(2, 1),
+                ])
+            });
+        });
+        cx.executor().advance_clock(Duration::from_millis(200));
+        let (stats, text) = selection_stats(cx);
+        assert_eq!(
+            stats.numbers, None,
+            "Selections that aren't all numbers have no sum"
+        );
+        assert_eq!(text, " (2 selections, 2 lines, 3 characters)");
+    }
+
     #[gpui::test]
     async fn test_unicode_line_numbers(cx: &mut TestAppContext) {
         init_test(cx);
@@ -663,6 +791,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(parse_query("12", 5, 100), Some((12, None)));
+        assert_eq!(parse_query("12:3", 5, 100), Some((12, Some(3))));
+        assert_eq!(parse_query(" 12 : 3 ", 5, 100), Some((12, Some(3))));
+        assert_eq!(parse_query("+20", 5, 100), Some((25, None)));
+        assert_eq!(parse_query("-3:4", 5, 100), Some((2, Some(4))));
+        assert_eq!(parse_query("-30", 5, 100), Some((1, None)));
+        assert_eq!(parse_query("0%", 5, 101), Some((1, None)));
+        assert_eq!(parse_query("50%", 5, 101), Some((51, None)));
+        assert_eq!(parse_query("100%", 5, 101), Some((101, None)));
+        assert_eq!(parse_query("250%", 5, 101), Some((101, None)));
+        assert_eq!(parse_query("", 5, 100), None);
+        assert_eq!(parse_query("+", 5, 100), None);
+        assert_eq!(parse_query("abc", 5, 100), None);
+    }
+
+    #[gpui::test]
+    async fn test_go_to_relative_and_percentage_lines(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "a.rs": (0..21).map(|row| format!("line {row}\n")).collect::<String>()
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let worktree_id = workspace.update(cx, |workspace, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees(cx).next().unwrap().read(cx).id()
+            })
+        });
+        let editor = workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_path((worktree_id, rel_path("a.rs")), None, true, window, cx)
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+
+        let go_to_line_view = open_go_to_line_view(&workspace, cx);
+        cx.simulate_input("+4");
+        assert_eq!(highlighted_display_rows(&editor, cx), vec![4]);
+        cx.dispatch_action(menu::Confirm);
+        drop(go_to_line_view);
+        assert_single_caret_at_row(&editor, 4, cx);
+
+        let go_to_line_view = open_go_to_line_view(&workspace, cx);
+        cx.simulate_input("-2");
+        assert_eq!(highlighted_display_rows(&editor, cx), vec![2]);
+        cx.dispatch_action(menu::Confirm);
+        drop(go_to_line_view);
+        assert_single_caret_at_row(&editor, 2, cx);
+
+        let go_to_line_view = open_go_to_line_view(&workspace, cx);
+        cx.simulate_input("50%");
+        assert_eq!(
+            highlighted_display_rows(&editor, cx),
+            vec![11],
+            "Half way through the 22 lines of the file"
+        );
+        cx.dispatch_action(menu::Cancel);
+        drop(go_to_line_view);
+        editor.update(cx, |_, _| {});
+        assert_eq!(highlighted_display_rows(&editor, cx), Vec::<u32>::new());
+        assert_single_caret_at_row(&editor, 2, cx);
+    }
+
     fn current_position(
         workspace: &Entity<Workspace>,
         cx: &mut VisualTestContext,
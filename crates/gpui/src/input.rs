@@ -1,4 +1,6 @@
-use crate::{App, Bounds, Context, Entity, InputHandler, Pixels, UTF16Selection, Window};
+use crate::{
+    App, Bounds, CompositionClause, Context, Entity, InputHandler, Pixels, UTF16Selection, Window,
+};
 use std::ops::Range;
 
 /// Implement this trait to allow views to handle textual input when implementing an editor, field, etc.
@@ -54,6 +56,15 @@ pub trait EntityInputHandler: 'static + Sized {
         cx: &mut Context<Self>,
     );
 
+    /// See [`InputHandler::set_marked_text_clauses`] for details
+    fn set_marked_text_clauses(
+        &mut self,
+        _clauses: Vec<CompositionClause>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
     /// See [`InputHandler::bounds_for_range`] for details
     fn bounds_for_range(
         &mut self,
@@ -151,6 +162,17 @@ impl<V: EntityInputHandler> InputHandler for ElementInputHandler<V> {
         });
     }
 
+    fn set_marked_text_clauses(
+        &mut self,
+        clauses: Vec<CompositionClause>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.view.update(cx, |view, cx| {
+            view.set_marked_text_clauses(clauses, window, cx)
+        });
+    }
+
     fn unmark_text(&mut self, window: &mut Window, cx: &mut App) {
         self.view
             .update(cx, |view, cx| view.unmark_text(window, cx));
@@ -513,6 +513,7 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
     }
     fn set_edited(&mut self, _edited: bool) {}
     fn show_character_palette(&self) {}
+    fn announce(&self, _message: &str) {}
     fn titlebar_double_click(&self) {}
     fn on_move_tab_to_new_window(&self, _callback: Box<dyn FnMut()>) {}
     fn on_merge_all_windows(&self, _callback: Box<dyn FnMut()>) {}
@@ -933,6 +934,13 @@ impl PlatformInputHandler {
             .ok();
     }
 
+    #[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+    fn set_marked_text_clauses(&mut self, clauses: Vec<CompositionClause>) {
+        self.cx
+            .update(|window, cx| self.handler.set_marked_text_clauses(clauses, window, cx))
+            .ok();
+    }
+
     #[cfg_attr(target_os = "windows", allow(dead_code))]
     fn unmark_text(&mut self) {
         self.cx
@@ -990,6 +998,16 @@ pub struct UTF16Selection {
     pub reversed: bool,
 }
 
+/// A clause of the text being composed by an input method, such as a word being converted from
+/// kana to kanji. Input methods draw the clause being converted more prominently.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompositionClause {
+    /// The range of the clause in UTF16 characters, relative to the start of the marked text.
+    pub range_utf16: Range<usize>,
+    /// Whether this is the clause that the input method is currently converting.
+    pub is_target: bool,
+}
+
 /// Zed's interface for handling text input from the platform's IME system
 /// This is currently a 1:1 exposure of the NSTextInputClient API:
 ///
@@ -1051,6 +1069,18 @@ pub trait InputHandler: 'static {
         cx: &mut App,
     );
 
+    /// Set the clauses of the text marked by the last call to `replace_and_mark_text_in_range`,
+    /// on platforms whose input methods segment their composition.
+    /// Corresponds to the [markedClauseSegment](https://developer.apple.com/documentation/foundation/nsattributedstring/key/markedclausesegment)
+    /// attributes of the marked text
+    fn set_marked_text_clauses(
+        &mut self,
+        _clauses: Vec<CompositionClause>,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) {
+    }
+
     /// Remove the IME 'composing' state from the document
     /// Corresponds to [unmarkText()](https://developer.apple.com/documentation/appkit/nstextinputclient/1438239-unmarktext)
     fn unmark_text(&mut self, window: &mut Window, cx: &mut App);
@@ -1530,6 +1560,8 @@ impl ClipboardItem {
             entries: vec![ClipboardEntry::String(ClipboardString {
                 text,
                 metadata: Some(metadata),
+                html: None,
+                rtf: None,
             })],
         }
     }
@@ -1557,7 +1589,7 @@ impl ClipboardItem {
         let mut any_entries = false;
 
         for entry in self.entries.iter() {
-            if let ClipboardEntry::String(ClipboardString { text, metadata: _ }) = entry {
+            if let ClipboardEntry::String(ClipboardString { text, .. }) = entry {
                 answer.push_str(text);
                 any_entries = true;
             }
@@ -1808,6 +1840,8 @@ impl Image {
 pub struct ClipboardString {
     pub(crate) text: String,
     pub(crate) metadata: Option<String>,
+    pub(crate) html: Option<String>,
+    pub(crate) rtf: Option<String>,
 }
 
 impl ClipboardString {
@@ -1816,9 +1850,37 @@ impl ClipboardString {
         Self {
             text,
             metadata: None,
+            html: None,
+            rtf: None,
         }
     }
 
+    /// Return a new clipboard string that is also offered to other applications as HTML.
+    ///
+    /// This is written to the clipboard on macOS, Windows, X11 and Wayland.
+    pub fn with_html(mut self, html: String) -> Self {
+        self.html = Some(html);
+        self
+    }
+
+    /// Return a new clipboard string that is also offered to other applications as RTF.
+    ///
+    /// This is written to the clipboard on macOS, Windows, X11 and Wayland.
+    pub fn with_rtf(mut self, rtf: String) -> Self {
+        self.rtf = Some(rtf);
+        self
+    }
+
+    /// Get the HTML rendition of the clipboard string, if it has one
+    pub fn html(&self) -> Option<&str> {
+        self.html.as_deref()
+    }
+
+    /// Get the RTF rendition of the clipboard string, if it has one
+    pub fn rtf(&self) -> Option<&str> {
+        self.rtf.as_deref()
+    }
+
     /// Return a new clipboard item with the metadata replaced by the given metadata,
     /// after serializing it as JSON.
     pub fn with_json_metadata<T: Serialize>(mut self, metadata: T) -> Self {
@@ -1856,9 +1918,6 @@ impl ClipboardString {
 
 impl From<String> for ClipboardString {
     fn from(value: String) -> Self {
-        Self {
-            text: value,
-            metadata: None,
-        }
+        Self::new(value)
     }
 }
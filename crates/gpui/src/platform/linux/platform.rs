@@ -657,6 +657,19 @@ pub(super) fn reveal_path_internal(
         .detach();
 }
 
+/// Returns the HTML and RTF renditions of the first string in a clipboard item, which are
+/// offered to other applications alongside its plain text.
+#[cfg(any(feature = "wayland", feature = "x11"))]
+pub(super) fn clipboard_renditions(item: &ClipboardItem) -> (Option<&str>, Option<&str>) {
+    item.entries()
+        .iter()
+        .find_map(|entry| match entry {
+            crate::ClipboardEntry::String(string) => Some((string.html(), string.rtf())),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
 #[allow(unused)]
 pub(super) fn is_within_click_distance(a: Point<Pixels>, b: Point<Pixels>) -> bool {
     let diff = a - b;
@@ -82,10 +82,14 @@ use crate::{
 use crate::{
     SharedString,
     platform::linux::{
-        LinuxClient, get_xkb_compose_state, is_within_click_distance, open_uri_internal, read_fd,
-        reveal_path_internal,
+        LinuxClient, get_xkb_compose_state, is_within_click_distance, open_uri_internal,
+        platform::clipboard_renditions,
+        read_fd, reveal_path_internal,
         wayland::{
-            clipboard::{Clipboard, DataOffer, FILE_LIST_MIME_TYPE, TEXT_MIME_TYPES},
+            clipboard::{
+                Clipboard, DataOffer, FILE_LIST_MIME_TYPE, HTML_MIME_TYPE, RTF_MIME_TYPE,
+                TEXT_MIME_TYPES,
+            },
             cursor::Cursor,
             serial::{SerialKind, SerialTracker},
             window::WaylandWindow,
@@ -828,12 +832,20 @@ impl LinuxClient for WaylandClient {
             return;
         };
         if state.mouse_focused_window.is_some() || state.keyboard_focused_window.is_some() {
+            let (html, rtf) = clipboard_renditions(&item);
+            let (has_html, has_rtf) = (html.is_some(), rtf.is_some());
             state.clipboard.set(item);
             let serial = state.serial_tracker.get(SerialKind::KeyPress);
             let data_source = data_device_manager.create_data_source(&state.globals.qh, ());
             for mime_type in TEXT_MIME_TYPES {
                 data_source.offer(mime_type.to_string());
             }
+            if has_html {
+                data_source.offer(HTML_MIME_TYPE.to_string());
+            }
+            if has_rtf {
+                data_source.offer(RTF_MIME_TYPE.to_string());
+            }
             data_source.offer(state.clipboard.self_mime());
             data_device.set_selection(Some(&data_source), serial);
         }
@@ -12,13 +12,16 @@ use wayland_protocols::wp::primary_selection::zv1::client::zwp_primary_selection
 
 use crate::{
     ClipboardEntry, ClipboardItem, Image, ImageFormat, WaylandClientStatePtr, hash,
-    platform::linux::platform::read_fd,
+    platform::linux::platform::{clipboard_renditions, read_fd},
 };
 
 /// Text mime types that we'll offer to other programs.
 pub(crate) const TEXT_MIME_TYPES: [&str; 3] =
     ["text/plain;charset=utf-8", "UTF8_STRING", "text/plain"];
 pub(crate) const FILE_LIST_MIME_TYPE: &str = "text/uri-list";
+/// Rich text mime types that we'll offer to other programs when the copied text has them.
+pub(crate) const HTML_MIME_TYPE: &str = "text/html";
+pub(crate) const RTF_MIME_TYPE: &str = "text/rtf";
 
 /// Text mime types that we'll accept from other programs.
 pub(crate) const ALLOWED_TEXT_MIME_TYPES: [&str; 2] = ["text/plain;charset=utf-8", "UTF8_STRING"];
@@ -179,8 +182,19 @@ impl Clipboard {
         self.self_mime.clone()
     }
 
-    pub fn send(&self, _mime_type: String, fd: OwnedFd) {
-        if let Some(text) = self.contents.as_ref().and_then(|contents| contents.text()) {
+    pub fn send(&self, mime_type: String, fd: OwnedFd) {
+        let Some(contents) = self.contents.as_ref() else {
+            return;
+        };
+        let (html, rtf) = clipboard_renditions(contents);
+        let rendition = match mime_type.as_str() {
+            HTML_MIME_TYPE => html,
+            RTF_MIME_TYPE => rtf,
+            _ => None,
+        };
+        if let Some(rendition) = rendition {
+            self.send_internal(fd, rendition.as_bytes().to_owned());
+        } else if let Some(text) = contents.text() {
             self.send_internal(fd, text.as_bytes().to_owned());
         }
     }
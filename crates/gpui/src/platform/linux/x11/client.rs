@@ -54,7 +54,7 @@ use crate::platform::{
     linux::{
         DEFAULT_CURSOR_ICON_NAME, LinuxClient, get_xkb_compose_state, is_within_click_distance,
         log_cursor_icon_warning, open_uri_internal,
-        platform::{DOUBLE_CLICK_INTERVAL, SCROLL_LINES},
+        platform::{DOUBLE_CLICK_INTERVAL, SCROLL_LINES, clipboard_renditions},
         reveal_path_internal,
         xdg_desktop_portal::{Event as XDPEvent, XDPEventSource},
     },
@@ -1554,10 +1554,13 @@ impl LinuxClient for X11Client {
 
     fn write_to_clipboard(&self, item: crate::ClipboardItem) {
         let mut state = self.0.borrow_mut();
+        let (html, rtf) = clipboard_renditions(&item);
         state
             .clipboard
-            .set_text(
+            .set_rich_text(
                 std::borrow::Cow::Owned(item.text().unwrap_or_default()),
+                html,
+                rtf,
                 clipboard::ClipboardKind::Clipboard,
                 clipboard::WaitConfig::None,
             )
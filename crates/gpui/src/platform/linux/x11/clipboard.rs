@@ -76,7 +76,8 @@ x11rb::atom_manager! {
         TEXT,
         TEXT_MIME_UNKNOWN: b"text/plain",
 
-        // HTML: b"text/html",
+        HTML: b"text/html",
+        RTF: b"text/rtf",
         // URI_LIST: b"text/uri-list",
 
         PNG__MIME: ImageFormat::mime_type(ImageFormat::Png ).as_bytes(),
@@ -981,10 +982,34 @@ impl Clipboard {
         selection: ClipboardKind,
         wait: WaitConfig,
     ) -> Result<()> {
-        let data = vec![ClipboardData {
+        self.set_rich_text(message, None, None, selection, wait)
+    }
+
+    /// Like `set_text`, but also offers the given HTML and RTF renditions of the text.
+    pub(crate) fn set_rich_text(
+        &self,
+        message: Cow<'_, str>,
+        html: Option<&str>,
+        rtf: Option<&str>,
+        selection: ClipboardKind,
+        wait: WaitConfig,
+    ) -> Result<()> {
+        let mut data = vec![ClipboardData {
             bytes: message.into_owned().into_bytes(),
             format: self.inner.atoms.UTF8_STRING,
         }];
+        if let Some(html) = html {
+            data.push(ClipboardData {
+                bytes: html.as_bytes().to_vec(),
+                format: self.inner.atoms.HTML,
+            });
+        }
+        if let Some(rtf) = rtf {
+            data.push(ClipboardData {
+                bytes: rtf.as_bytes().to_vec(),
+                format: self.inner.atoms.RTF,
+            });
+        }
         self.inner.write(data, selection, wait)
     }
 
@@ -18,8 +18,8 @@ use cocoa::{
     appkit::{
         NSApplication, NSApplicationActivationPolicy::NSApplicationActivationPolicyRegular,
         NSEventModifierFlags, NSMenu, NSMenuItem, NSModalResponse, NSOpenPanel, NSPasteboard,
-        NSPasteboardTypePNG, NSPasteboardTypeRTF, NSPasteboardTypeRTFD, NSPasteboardTypeString,
-        NSPasteboardTypeTIFF, NSSavePanel, NSWindow,
+        NSPasteboardTypeHTML, NSPasteboardTypePNG, NSPasteboardTypeRTF, NSPasteboardTypeRTFD,
+        NSPasteboardTypeString, NSPasteboardTypeTIFF, NSSavePanel, NSWindow,
     },
     base::{BOOL, NO, YES, id, nil, selector},
     foundation::{
@@ -1053,8 +1053,7 @@ impl Platform for MacPlatform {
                         .init_attributed_string(NSString::alloc(nil).init_str(""));
 
                     for entry in item.entries {
-                        if let ClipboardEntry::String(ClipboardString { text, metadata: _ }) = entry
-                        {
+                        if let ClipboardEntry::String(ClipboardString { text, .. }) = entry {
                             let to_append = NSAttributedString::alloc(nil)
                                 .init_attributed_string(NSString::alloc(nil).init_str(&text));
 
@@ -1266,7 +1265,12 @@ impl MacPlatform {
                 });
 
             ClipboardItem {
-                entries: vec![ClipboardEntry::String(ClipboardString { text, metadata })],
+                entries: vec![ClipboardEntry::String(ClipboardString {
+                    text,
+                    metadata,
+                    html: None,
+                    rtf: None,
+                })],
             }
         }
     }
@@ -1285,6 +1289,22 @@ impl MacPlatform {
                 .pasteboard
                 .setData_forType(text_bytes, NSPasteboardTypeString);
 
+            for (rich_text, pasteboard_type) in [
+                (string.html.as_ref(), NSPasteboardTypeHTML),
+                (string.rtf.as_ref(), NSPasteboardTypeRTF),
+            ] {
+                if let Some(rich_text) = rich_text {
+                    let rich_text_bytes = NSData::dataWithBytes_length_(
+                        nil,
+                        rich_text.as_ptr() as *const c_void,
+                        rich_text.len() as u64,
+                    );
+                    state
+                        .pasteboard
+                        .setData_forType(rich_text_bytes, pasteboard_type);
+                }
+            }
+
             if let Some(metadata) = string.metadata.as_ref() {
                 let hash_bytes = ClipboardString::text_hash(&string.text).to_be_bytes();
                 let hash_bytes = NSData::dataWithBytes_length_(
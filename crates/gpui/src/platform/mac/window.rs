@@ -1,12 +1,13 @@
 use super::{BoolExt, MacDisplay, NSRange, NSStringExt, ns_string, renderer};
 use crate::{
-    AnyWindowHandle, Bounds, Capslock, DisplayLink, ExternalPaths, FileDropEvent,
-    ForegroundExecutor, KeyDownEvent, Keystroke, Modifiers, ModifiersChangedEvent, MouseButton,
-    MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, PlatformAtlas, PlatformDisplay,
-    PlatformInput, PlatformWindow, Point, PromptButton, PromptLevel, RequestFrameOptions,
-    SharedString, Size, SystemWindowTab, Timer, WindowAppearance, WindowBackgroundAppearance,
-    WindowBounds, WindowControlArea, WindowKind, WindowParams, dispatch_get_main_queue,
-    dispatch_sys::dispatch_async_f, platform::PlatformInputHandler, point, px, size,
+    AnyWindowHandle, Bounds, Capslock, CompositionClause, DisplayLink, ExternalPaths,
+    FileDropEvent, ForegroundExecutor, KeyDownEvent, Keystroke, Modifiers, ModifiersChangedEvent,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, PlatformAtlas,
+    PlatformDisplay, PlatformInput, PlatformWindow, Point, PromptButton, PromptLevel,
+    RequestFrameOptions, SharedString, Size, SystemWindowTab, Timer, WindowAppearance,
+    WindowBackgroundAppearance, WindowBounds, WindowControlArea, WindowKind, WindowParams,
+    dispatch_get_main_queue, dispatch_sys::dispatch_async_f, platform::PlatformInputHandler, point,
+    px, size,
 };
 use block::ConcreteBlock;
 use cocoa::{
@@ -84,6 +85,8 @@ type NSDragOperation = NSUInteger;
 const NSDragOperationNone: NSDragOperation = 0;
 #[allow(non_upper_case_globals)]
 const NSDragOperationCopy: NSDragOperation = 1;
+#[allow(non_upper_case_globals)]
+const NSAccessibilityPriorityHigh: NSInteger = 90;
 #[derive(PartialEq)]
 pub enum UserTabbingPreference {
     Never,
@@ -91,6 +94,14 @@ pub enum UserTabbingPreference {
     InFullScreen,
 }
 
+#[link(name = "AppKit", kind = "framework")]
+unsafe extern "C" {
+    static NSAccessibilityAnnouncementRequestedNotification: id;
+    static NSAccessibilityAnnouncementKey: id;
+    static NSAccessibilityPriorityKey: id;
+    fn NSAccessibilityPostNotificationWithUserInfo(element: id, notification: id, user_info: id);
+}
+
 #[link(name = "CoreGraphics", kind = "framework")]
 unsafe extern "C" {
     // Widely used private APIs; Apple uses them for their Terminal.app.
@@ -1334,6 +1345,27 @@ impl PlatformWindow for MacWindow {
             .detach();
     }
 
+    fn announce(&self, message: &str) {
+        let window = self.0.lock().native_window;
+        unsafe {
+            let priority: id =
+                msg_send![class!(NSNumber), numberWithInteger: NSAccessibilityPriorityHigh];
+            let user_info = NSDictionary::dictionaryWithObjects_forKeys_(
+                nil,
+                NSArray::arrayWithObjects(nil, &[ns_string(message), priority]),
+                NSArray::arrayWithObjects(
+                    nil,
+                    &[NSAccessibilityAnnouncementKey, NSAccessibilityPriorityKey],
+                ),
+            );
+            NSAccessibilityPostNotificationWithUserInfo(
+                window,
+                NSAccessibilityAnnouncementRequestedNotification,
+                user_info,
+            );
+        }
+    }
+
     fn minimize(&self) {
         let window = self.0.lock().native_window;
         unsafe {
@@ -2259,19 +2291,66 @@ extern "C" fn set_marked_text(
     unsafe {
         let is_attributed_string: BOOL =
             msg_send![text, isKindOfClass: [class!(NSAttributedString)]];
-        let text: id = if is_attributed_string == YES {
-            msg_send![text, string]
+        let (text, clauses): (id, _) = if is_attributed_string == YES {
+            (msg_send![text, string], marked_text_clauses(text))
         } else {
-            text
+            (text, Vec::new())
         };
         let selected_range = selected_range.to_range();
         let replacement_range = replacement_range.to_range();
         let text = text.to_str();
         with_input_handler(this, |input_handler| {
-            input_handler.replace_and_mark_text_in_range(replacement_range, text, selected_range)
+            input_handler.replace_and_mark_text_in_range(replacement_range, text, selected_range);
+            if !clauses.is_empty() {
+                input_handler.set_marked_text_clauses(clauses);
+            }
         });
     }
 }
+
+/// Reads the clauses of marked text from the attributes that input methods set on it, where the
+/// clause being converted has a thick underline.
+unsafe fn marked_text_clauses(text: id) -> Vec<CompositionClause> {
+    const NS_UNDERLINE_STYLE_THICK: NSInteger = 0x02;
+
+    unsafe {
+        let length: NSUInteger = msg_send![text, length];
+        let clause_key = ns_string("NSMarkedClauseSegment");
+        let underline_key = ns_string("NSUnderline");
+        let mut clauses = Vec::new();
+        let mut location: NSUInteger = 0;
+        while location < length {
+            let mut clause_range = NSRange::from(location as usize..length as usize);
+            let _: id = msg_send![
+                text,
+                attribute: clause_key
+                atIndex: location
+                effectiveRange: &mut clause_range
+            ];
+            let underline: id = msg_send![
+                text,
+                attribute: underline_key
+                atIndex: location
+                effectiveRange: std::ptr::null_mut::<NSRange>()
+            ];
+            let underline_style: NSInteger = if underline == nil {
+                0
+            } else {
+                msg_send![underline, integerValue]
+            };
+            let Some(range_utf16) = clause_range.to_range().filter(|range| !range.is_empty())
+            else {
+                break;
+            };
+            location = range_utf16.end as NSUInteger;
+            clauses.push(CompositionClause {
+                range_utf16,
+                is_target: underline_style & NS_UNDERLINE_STYLE_THICK != 0,
+            });
+        }
+        clauses
+    }
+}
 extern "C" fn unmark_text(this: &Object, _: Sel) {
     with_input_handler(this, |input_handler| input_handler.unmark_text());
 }
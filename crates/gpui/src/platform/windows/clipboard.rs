@@ -28,6 +28,10 @@ static CLIPBOARD_HASH_FORMAT: LazyLock<u32> =
     LazyLock::new(|| register_clipboard_format(windows::core::w!("GPUI internal text hash")));
 static CLIPBOARD_METADATA_FORMAT: LazyLock<u32> =
     LazyLock::new(|| register_clipboard_format(windows::core::w!("GPUI internal metadata")));
+static CLIPBOARD_HTML_FORMAT: LazyLock<u32> =
+    LazyLock::new(|| register_clipboard_format(windows::core::w!("HTML Format")));
+static CLIPBOARD_RTF_FORMAT: LazyLock<u32> =
+    LazyLock::new(|| register_clipboard_format(windows::core::w!("Rich Text Format")));
 static CLIPBOARD_SVG_FORMAT: LazyLock<u32> =
     LazyLock::new(|| register_clipboard_format(windows::core::w!("image/svg+xml")));
 static CLIPBOARD_GIF_FORMAT: LazyLock<u32> =
@@ -171,6 +175,14 @@ fn write_string_to_clipboard(item: &ClipboardString) -> Result<()> {
     let encode_wide = item.text.encode_utf16().chain(Some(0)).collect_vec();
     set_data_to_clipboard(&encode_wide, CF_UNICODETEXT.0 as u32)?;
 
+    if let Some(html) = item.html.as_ref() {
+        let cf_html = html_clipboard_data(html);
+        set_data_to_clipboard(cf_html.as_bytes(), *CLIPBOARD_HTML_FORMAT)?;
+    }
+    if let Some(rtf) = item.rtf.as_ref() {
+        set_data_to_clipboard(rtf.as_bytes(), *CLIPBOARD_RTF_FORMAT)?;
+    }
+
     if let Some(metadata) = item.metadata.as_ref() {
         let hash_result = {
             let hash = ClipboardString::text_hash(&item.text);
@@ -186,6 +198,26 @@ fn write_string_to_clipboard(item: &ClipboardString) -> Result<()> {
     Ok(())
 }
 
+// https://learn.microsoft.com/en-us/windows/win32/dataxchg/html-clipboard-format
+fn html_clipboard_data(html: &str) -> String {
+    // The header has a fixed length, as every offset in it is padded to ten digits.
+    const HEADER_LEN: usize = 105;
+    let prefix = "<html><body><!--StartFragment-->";
+    let suffix = "<!--EndFragment--></body></html>";
+    let start_fragment = HEADER_LEN + prefix.len();
+    let end_fragment = start_fragment + html.len();
+    let end_html = end_fragment + suffix.len();
+    let mut data = format!(
+        "Version:0.9\r\nStartHTML:{HEADER_LEN:010}\r\nEndHTML:{end_html:010}\r\nStartFragment:{start_fragment:010}\r\nEndFragment:{end_fragment:010}\r\n"
+    );
+    debug_assert_eq!(data.len(), HEADER_LEN);
+    data.push_str(prefix);
+    data.push_str(html);
+    data.push_str(suffix);
+    data.push('\0');
+    data
+}
+
 fn set_data_to_clipboard<T>(data: &[T], format: u32) -> Result<()> {
     unsafe {
         let global = GlobalAlloc(GMEM_MOVEABLE, std::mem::size_of_val(data))?;
@@ -293,8 +325,8 @@ fn read_string_from_clipboard() -> Option<ClipboardEntry> {
     };
     if hash == ClipboardString::text_hash(&text) {
         Some(ClipboardEntry::String(ClipboardString {
-            text,
             metadata: Some(metadata),
+            ..ClipboardString::new(text)
         }))
     } else {
         Some(ClipboardEntry::String(ClipboardString::new(text)))
@@ -354,10 +386,7 @@ fn read_files_from_clipboard() -> Option<ClipboardEntry> {
         });
         filenames
     })?;
-    Some(ClipboardEntry::String(ClipboardString {
-        text,
-        metadata: None,
-    }))
+    Some(ClipboardEntry::String(ClipboardString::new(text)))
 }
 
 fn with_clipboard_data<F, R>(format: u32, f: F) -> Option<R>
@@ -664,8 +664,16 @@ impl WindowsWindowInner {
                         let pos = retrieve_composition_cursor_position(ctx);
                         pos..pos
                     });
+                let clauses = if lparam & GCS_COMPATTR.0 > 0 {
+                    parse_ime_composition_clauses(ctx)
+                } else {
+                    Vec::new()
+                };
                 self.with_input_handler(|input_handler| {
                     input_handler.replace_and_mark_text_in_range(None, &comp_string, caret_pos);
+                    if !clauses.is_empty() {
+                        input_handler.set_marked_text_clauses(clauses);
+                    }
                 })?;
             }
             if lparam & GCS_RESULTSTR.0 > 0 {
@@ -1437,6 +1445,43 @@ fn parse_ime_composition_string(ctx: HIMC, comp_type: IME_COMPOSITION_STRING) ->
     }
 }
 
+/// Groups the attributes of the composition string's UTF-16 characters into clauses, where the
+/// clause being converted has one of the target attributes.
+fn parse_ime_composition_clauses(ctx: HIMC) -> Vec<CompositionClause> {
+    let attributes = unsafe {
+        let len = ImmGetCompositionStringW(ctx, GCS_COMPATTR, None, 0);
+        if len <= 0 {
+            return Vec::new();
+        }
+        let mut attributes = vec![0u8; len as usize];
+        ImmGetCompositionStringW(
+            ctx,
+            GCS_COMPATTR,
+            Some(attributes.as_mut_ptr() as _),
+            len as _,
+        );
+        attributes
+    };
+
+    let mut clauses = Vec::<CompositionClause>::new();
+    let mut clause_attribute = None;
+    for (ix, attribute) in attributes.into_iter().enumerate() {
+        let attribute = attribute as u32;
+        match clauses.last_mut() {
+            Some(clause) if clause_attribute == Some(attribute) => clause.range_utf16.end = ix + 1,
+            _ => {
+                clause_attribute = Some(attribute);
+                clauses.push(CompositionClause {
+                    range_utf16: ix..ix + 1,
+                    is_target: attribute == ATTR_TARGET_CONVERTED
+                        || attribute == ATTR_TARGET_NOTCONVERTED,
+                });
+            }
+        }
+    }
+    clauses
+}
+
 #[inline]
 fn retrieve_composition_cursor_position(ctx: HIMC) -> usize {
     unsafe { ImmGetCompositionStringW(ctx, GCS_CURSORPOS, None, 0) as usize }
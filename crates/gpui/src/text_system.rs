@@ -58,6 +58,7 @@ pub struct TextSystem {
     raster_bounds: RwLock<FxHashMap<RenderGlyphParams, Bounds<DevicePixels>>>,
     wrapper_pool: Mutex<FxHashMap<FontIdWithSize, Vec<LineWrapper>>>,
     font_runs_pool: Mutex<Vec<Vec<FontRun>>>,
+    glyph_presence: RwLock<FxHashMap<(FontId, char), bool>>,
     fallback_font_stack: SmallVec<[Font; 2]>,
 }
 
@@ -70,6 +71,7 @@ impl TextSystem {
             font_ids_by_font: RwLock::default(),
             wrapper_pool: Mutex::default(),
             font_runs_pool: Mutex::default(),
+            glyph_presence: RwLock::default(),
             fallback_font_stack: smallvec![
                 // TODO: Remove this when Linux have implemented setting fallbacks.
                 font(".ZedMono"),
@@ -165,6 +167,68 @@ impl TextSystem {
         );
     }
 
+    fn has_glyph(&self, font_id: FontId, c: char) -> bool {
+        if let Some(has_glyph) = self.glyph_presence.read().get(&(font_id, c)) {
+            return *has_glyph;
+        }
+        let has_glyph = self
+            .platform_text_system
+            .glyph_for_char(font_id, c)
+            .is_some();
+        self.glyph_presence.write().insert((font_id, c), has_glyph);
+        has_glyph
+    }
+
+    /// Splits `text`, to be shown in `font`, into runs of the fonts that have glyphs for its
+    /// characters: the font itself, or else the first of its fallbacks for the character's
+    /// script, or of its other fallbacks, that has a glyph for it. Characters without a glyph in
+    /// any of them are left to the platform's own fallback fonts.
+    ///
+    /// Returns `None` if the whole text can be shown in `font`.
+    fn fallback_font_runs(&self, text: &str, font: &Font) -> Option<SmallVec<[FontRun; 2]>> {
+        let fallbacks = font
+            .fallbacks
+            .as_ref()
+            .filter(|fallbacks| !fallbacks.is_empty())?;
+        if text.is_ascii() {
+            return None;
+        }
+
+        let font_id = self.resolve_font(font);
+        let mut runs = SmallVec::<[FontRun; 2]>::new();
+        for c in text.chars() {
+            let char_font_id = match runs.last() {
+                Some(run) if continues_grapheme(c) => run.font_id,
+                _ if c.is_ascii() || self.has_glyph(font_id, c) => font_id,
+                _ => fallbacks
+                    .fallbacks_for_char(c)
+                    .find_map(|family| {
+                        let fallback = Font {
+                            family: family.clone().into(),
+                            fallbacks: None,
+                            ..font.clone()
+                        };
+                        let fallback_id = self.font_id(&fallback).ok()?;
+                        self.has_glyph(fallback_id, c).then_some(fallback_id)
+                    })
+                    .unwrap_or(font_id),
+            };
+            match runs.last_mut() {
+                Some(run) if run.font_id == char_font_id => run.len += c.len_utf8(),
+                _ => runs.push(FontRun {
+                    len: c.len_utf8(),
+                    font_id: char_font_id,
+                }),
+            }
+        }
+
+        if runs.iter().all(|run| run.font_id == font_id) {
+            None
+        } else {
+            Some(runs)
+        }
+    }
+
     /// Get the bounding box for the given font and font size.
     /// A font's bounding box is the smallest rectangle that could enclose all glyphs
     /// in the font. superimposed over one another.
@@ -453,7 +517,23 @@ impl WindowTextSystem {
                     true
                 };
 
-                if let Some(font_run) = font_runs.last_mut()
+                let fallback_runs = line_text
+                    .get(run_start - line_start..run_start - line_start + run_len_within_line)
+                    .and_then(|run_text| self.fallback_font_runs(run_text, &run.font));
+                if let Some(fallback_runs) = fallback_runs {
+                    for (ix, fallback_run) in fallback_runs.into_iter().enumerate() {
+                        if let Some(font_run) = font_runs.last_mut()
+                            && font_run.font_id == fallback_run.font_id
+                            && (ix > 0 || !decoration_changed)
+                        {
+                            font_run.len += fallback_run.len;
+                        } else {
+                            font_runs.push(fallback_run);
+                        }
+                    }
+                    // The next run doesn't continue this run's fallback fonts.
+                    last_font = None;
+                } else if let Some(font_run) = font_runs.last_mut()
                     && Some(font_run.font_id) == last_font
                     && !decoration_changed
                 {
@@ -544,6 +624,7 @@ impl WindowTextSystem {
         let mut font_runs = self.font_runs_pool.lock().pop().unwrap_or_default();
         font_runs.clear();
 
+        let mut run_start = 0;
         for run in runs.iter() {
             let decoration_changed = if let Some(last_run) = last_run
                 && last_run.color == run.color
@@ -558,7 +639,23 @@ impl WindowTextSystem {
                 true
             };
 
-            if let Some(font_run) = font_runs.last_mut()
+            let fallback_runs = text
+                .get(run_start..run_start + run.len)
+                .and_then(|run_text| self.fallback_font_runs(run_text, &run.font));
+            if let Some(fallback_runs) = fallback_runs {
+                for (ix, fallback_run) in fallback_runs.into_iter().enumerate() {
+                    if let Some(font_run) = font_runs.last_mut()
+                        && font_run.font_id == fallback_run.font_id
+                        && (ix > 0 || !decoration_changed)
+                    {
+                        font_run.len += fallback_run.len;
+                    } else {
+                        font_runs.push(fallback_run);
+                    }
+                }
+                // The next run doesn't continue this run's fallback fonts.
+                last_font = None;
+            } else if let Some(font_run) = font_runs.last_mut()
                 && Some(font_run.font_id) == last_font
                 && !decoration_changed
             {
@@ -571,6 +668,7 @@ impl WindowTextSystem {
                     font_id,
                 });
             }
+            run_start += run.len;
         }
 
         let layout = self.line_layout_cache.layout_line(
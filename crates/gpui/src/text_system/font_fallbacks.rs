@@ -6,16 +6,169 @@ use serde::{Deserialize, Serialize};
 /// The fallback fonts that can be configured for a given font.
 /// Fallback fonts family names are stored here.
 #[derive(Default, Clone, Eq, PartialEq, Hash, Debug, Deserialize, Serialize, JsonSchema)]
-pub struct FontFallbacks(pub Arc<Vec<String>>);
+pub struct FontFallbacks {
+    fonts: Arc<Vec<String>>,
+    /// The fallback fonts for the characters of specific scripts, which are tried before `fonts`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    script_fonts: Vec<(FontScript, Arc<Vec<String>>)>,
+}
 
 impl FontFallbacks {
     /// Get the fallback fonts family names
     pub fn fallback_list(&self) -> &[String] {
-        self.0.as_slice()
+        self.fonts.as_slice()
     }
 
     /// Create a font fallback from a list of strings
     pub fn from_fonts(fonts: Vec<String>) -> Self {
-        FontFallbacks(Arc::new(fonts))
+        FontFallbacks {
+            fonts: Arc::new(fonts),
+            script_fonts: Vec::new(),
+        }
+    }
+
+    /// Use the given fonts for the characters of `script`, before the other fallback fonts.
+    pub fn with_script_fonts(mut self, script: FontScript, fonts: Vec<String>) -> Self {
+        self.script_fonts
+            .retain(|(existing, _)| *existing != script);
+        if !fonts.is_empty() {
+            self.script_fonts.push((script, Arc::new(fonts)));
+        }
+        self
+    }
+
+    /// Get the fallback fonts family names for the characters of `script`.
+    pub fn script_fallback_list(&self, script: FontScript) -> &[String] {
+        self.script_fonts
+            .iter()
+            .find(|(existing, _)| *existing == script)
+            .map_or(&[], |(_, fonts)| fonts.as_slice())
+    }
+
+    /// Whether there are no fallback fonts.
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty() && self.script_fonts.is_empty()
+    }
+
+    /// The fallback fonts to try, in order, for a character missing from the font.
+    pub fn fallbacks_for_char(&self, c: char) -> impl Iterator<Item = &String> {
+        FontScript::of(c)
+            .map(|script| self.script_fallback_list(script))
+            .unwrap_or_default()
+            .iter()
+            .chain(self.fonts.iter())
+    }
+}
+
+/// A group of characters that can be given their own fallback fonts, such as a writing system.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum FontScript {
+    /// Chinese characters, which are also used in Japanese and Korean.
+    Han,
+    /// Japanese hiragana and katakana.
+    Kana,
+    /// Korean hangul.
+    Hangul,
+    /// Arabic.
+    Arabic,
+    /// Hebrew.
+    Hebrew,
+    /// Cyrillic, as used in Russian and Ukrainian.
+    Cyrillic,
+    /// Greek.
+    Greek,
+    /// Devanagari, as used in Hindi.
+    Devanagari,
+    /// Thai.
+    Thai,
+    /// Emoji.
+    Emoji,
+    /// Symbols such as arrows, mathematical operators, box drawing characters, and the icons in
+    /// the private use area that are provided by fonts such as Nerd Fonts.
+    Symbols,
+}
+
+impl FontScript {
+    /// The script of `c`, if it has one that can be given its own fallback fonts.
+    pub fn of(c: char) -> Option<Self> {
+        Some(match c {
+            '\u{0370}'..='\u{03ff}' | '\u{1f00}'..='\u{1fff}' => Self::Greek,
+            '\u{0400}'..='\u{052f}' | '\u{2de0}'..='\u{2dff}' | '\u{a640}'..='\u{a69f}' => {
+                Self::Cyrillic
+            }
+            '\u{0590}'..='\u{05ff}' | '\u{fb1d}'..='\u{fb4f}' => Self::Hebrew,
+            '\u{0600}'..='\u{06ff}'
+            | '\u{0750}'..='\u{077f}'
+            | '\u{08a0}'..='\u{08ff}'
+            | '\u{fb50}'..='\u{fdff}'
+            | '\u{fe70}'..='\u{feff}' => Self::Arabic,
+            '\u{0900}'..='\u{097f}' | '\u{a8e0}'..='\u{a8ff}' => Self::Devanagari,
+            '\u{0e00}'..='\u{0e7f}' => Self::Thai,
+            '\u{1100}'..='\u{11ff}'
+            | '\u{3130}'..='\u{318f}'
+            | '\u{a960}'..='\u{a97f}'
+            | '\u{ac00}'..='\u{d7ff}' => Self::Hangul,
+            '\u{3040}'..='\u{30ff}' | '\u{31f0}'..='\u{31ff}' | '\u{ff66}'..='\u{ff9f}' => {
+                Self::Kana
+            }
+            '\u{2e80}'..='\u{2fdf}'
+            | '\u{3000}'..='\u{303f}'
+            | '\u{3400}'..='\u{4dbf}'
+            | '\u{4e00}'..='\u{9fff}'
+            | '\u{f900}'..='\u{faff}'
+            | '\u{ff00}'..='\u{ff65}'
+            | '\u{20000}'..='\u{3134f}' => Self::Han,
+            '\u{1f000}'..='\u{1faff}' | '\u{2600}'..='\u{27bf}' => Self::Emoji,
+            '\u{2190}'..='\u{23ff}'
+            | '\u{2500}'..='\u{25ff}'
+            | '\u{2800}'..='\u{28ff}'
+            | '\u{e000}'..='\u{f8ff}'
+            | '\u{f0000}'..='\u{10ffff}' => Self::Symbols,
+            _ => return None,
+        })
+    }
+}
+
+/// Whether `c` modifies the character before it, such as a combining accent, a variation
+/// selector or an emoji skin tone, and so has to use the same font.
+pub(crate) fn continues_grapheme(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036f}'
+            | '\u{200c}'
+            | '\u{200d}'
+            | '\u{20d0}'..='\u{20ff}'
+            | '\u{fe00}'..='\u{fe0f}'
+            | '\u{1f3fb}'..='\u{1f3ff}'
+            | '\u{e0020}'..='\u{e007f}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallbacks_for_char() {
+        let fallbacks = FontFallbacks::from_fonts(vec!["Symbols".into()])
+            .with_script_fonts(FontScript::Han, vec!["Noto Sans CJK SC".into()])
+            .with_script_fonts(FontScript::Emoji, vec!["Noto Color Emoji".into()]);
+        assert_eq!(
+            fallbacks.fallbacks_for_char('中').collect::<Vec<_>>(),
+            ["Noto Sans CJK SC", "Symbols"]
+        );
+        assert_eq!(
+            fallbacks.fallbacks_for_char('😀').collect::<Vec<_>>(),
+            ["Noto Color Emoji", "Symbols"]
+        );
+        assert_eq!(
+            fallbacks.fallbacks_for_char('→').collect::<Vec<_>>(),
+            ["Symbols"]
+        );
+        assert!(!fallbacks.is_empty());
+        assert!(FontFallbacks::default().is_empty());
     }
 }
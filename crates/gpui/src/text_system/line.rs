@@ -1,9 +1,11 @@
 use crate::{
-    App, Bounds, Half, Hsla, LineLayout, Pixels, Point, Result, SharedString, StrikethroughStyle,
-    TextAlign, UnderlineStyle, Window, WrapBoundary, WrappedLineLayout, black, fill, point, px,
-    size,
+    App, Bounds, DevicePixels, Half, Hsla, LineLayout, Pixels, Point, RenderGlyphParams, Result,
+    Rgba, SUBPIXEL_VARIANTS_X, SUBPIXEL_VARIANTS_Y, SharedString, Size, StrikethroughStyle,
+    TextAlign, TextSystem, UnderlineStyle, Window, WrapBoundary, WrappedLineLayout, black, fill,
+    point, px, size,
 };
 use derive_more::{Deref, DerefMut};
+use image::RgbaImage;
 use smallvec::SmallVec;
 use std::sync::Arc;
 
@@ -104,6 +106,137 @@ impl ShapedLine {
 
         Ok(())
     }
+
+    /// Rasterize the line's glyphs on the CPU and blend them over `image`, rather than painting
+    /// them to a window. `origin` is in logical pixels and is scaled by `scale_factor`.
+    ///
+    /// The renderers only draw to window surfaces and can't read frames back, so this asks the
+    /// platform text system for the same glyph rasters that `paint` uploads to the sprite atlas,
+    /// and composites them itself. Backgrounds and text decorations are not drawn.
+    pub fn rasterize(
+        &self,
+        origin: Point<Pixels>,
+        line_height: Pixels,
+        scale_factor: f32,
+        text_system: &TextSystem,
+        image: &mut RgbaImage,
+    ) -> Result<()> {
+        let padding_top = (line_height - self.layout.ascent - self.layout.descent) / 2.;
+        let baseline_y = origin.y + padding_top + self.layout.ascent;
+        let mut decoration_runs = self.decoration_runs.iter();
+        let mut run_end = 0;
+        let mut color = black();
+        for run in &self.layout.runs {
+            for glyph in &run.glyphs {
+                if glyph.index >= run_end {
+                    // ignore style runs that apply to a partial glyph
+                    for style_run in decoration_runs.by_ref() {
+                        run_end += style_run.len as usize;
+                        if glyph.index < run_end {
+                            color = style_run.color;
+                            break;
+                        }
+                    }
+                }
+
+                let glyph_origin =
+                    point(origin.x + glyph.position.x, baseline_y).scale(scale_factor);
+                let subpixel_variant = if glyph.is_emoji {
+                    Point::default()
+                } else {
+                    Point {
+                        x: (glyph_origin.x.0.fract() * SUBPIXEL_VARIANTS_X as f32).floor() as u8,
+                        y: (glyph_origin.y.0.fract() * SUBPIXEL_VARIANTS_Y as f32).floor() as u8,
+                    }
+                };
+                let params = RenderGlyphParams {
+                    font_id: run.font_id,
+                    glyph_id: glyph.id,
+                    font_size: self.layout.font_size,
+                    subpixel_variant,
+                    scale_factor,
+                    is_emoji: glyph.is_emoji,
+                };
+                let raster_bounds = text_system.raster_bounds(&params)?;
+                if raster_bounds.is_zero() {
+                    continue;
+                }
+                let (glyph_size, bytes) = text_system.rasterize_glyph(&params)?;
+                let left = glyph_origin.x.0.floor() as i32 + raster_bounds.origin.x.0;
+                let top = glyph_origin.y.0.floor() as i32 + raster_bounds.origin.y.0;
+                blend_glyph(
+                    image,
+                    point(left, top),
+                    glyph_size,
+                    &bytes,
+                    glyph.is_emoji,
+                    Rgba::from(color),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Blends a rasterized glyph over `image` with its top left corner at `origin`. Emoji are
+/// rasterized as BGRA, and other glyphs as an alpha mask that is filled with `color`.
+fn blend_glyph(
+    image: &mut RgbaImage,
+    origin: Point<i32>,
+    glyph_size: Size<DevicePixels>,
+    bytes: &[u8],
+    is_emoji: bool,
+    color: Rgba,
+) {
+    for row in 0..glyph_size.height.0 {
+        for column in 0..glyph_size.width.0 {
+            let ix = (row * glyph_size.width.0 + column) as usize;
+            let source = if is_emoji {
+                let Some(pixel) = bytes.get(ix * 4..ix * 4 + 4) else {
+                    continue;
+                };
+                Rgba {
+                    r: pixel[2] as f32 / 255.,
+                    g: pixel[1] as f32 / 255.,
+                    b: pixel[0] as f32 / 255.,
+                    a: pixel[3] as f32 / 255.,
+                }
+            } else {
+                let Some(coverage) = bytes.get(ix) else {
+                    continue;
+                };
+                Rgba {
+                    a: color.a * *coverage as f32 / 255.,
+                    ..color
+                }
+            };
+            blend_pixel(image, origin.x + column, origin.y + row, source);
+        }
+    }
+}
+
+/// Blends `source` over the pixel at `x`, `y`, ignoring coordinates outside of `image`.
+fn blend_pixel(image: &mut RgbaImage, x: i32, y: i32, source: Rgba) {
+    if source.a <= 0. || x < 0 || y < 0 {
+        return;
+    }
+    let Some(pixel) = image.get_pixel_mut_checked(x as u32, y as u32) else {
+        return;
+    };
+    let destination_alpha = pixel[3] as f32 / 255.;
+    let alpha = source.a + destination_alpha * (1. - source.a);
+    if alpha <= 0. {
+        return;
+    }
+    for (channel, source_channel) in [source.r, source.g, source.b].into_iter().enumerate() {
+        let destination_channel = pixel[channel] as f32 / 255.;
+        let blended = (source_channel * source.a
+            + destination_channel * destination_alpha * (1. - source.a))
+            / alpha;
+        pixel[channel] = (blended * 255.).round() as u8;
+    }
+    pixel[3] = (alpha * 255.).round() as u8;
 }
 
 /// A line of text that has been shaped, decorated, and wrapped by the text layout system.
@@ -589,3 +722,58 @@ fn aligned_origin_x(
         TextAlign::Right => origin.x + align_width - line_width,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blend_glyph() {
+        let background = image::Rgba([0, 0, 255, 255]);
+        let mut image = RgbaImage::from_pixel(3, 2, background);
+        let red = Rgba {
+            r: 1.,
+            g: 0.,
+            b: 0.,
+            a: 1.,
+        };
+
+        // Alpha masks are filled with the text color, and clipped to the image.
+        blend_glyph(
+            &mut image,
+            point(1, 0),
+            size(DevicePixels(3), DevicePixels(1)),
+            &[255, 0, 128],
+            false,
+            red,
+        );
+        assert_eq!(*image.get_pixel(0, 0), background);
+        assert_eq!(*image.get_pixel(1, 0), image::Rgba([255, 0, 0, 255]));
+        assert_eq!(*image.get_pixel(2, 0), background);
+
+        // Emoji keep their own colors, which are rasterized as BGRA.
+        blend_glyph(
+            &mut image,
+            point(-1, 1),
+            size(DevicePixels(2), DevicePixels(1)),
+            &[0, 0, 0, 255, 0, 255, 0, 255],
+            true,
+            red,
+        );
+        assert_eq!(*image.get_pixel(0, 1), image::Rgba([0, 255, 0, 255]));
+        assert_eq!(*image.get_pixel(1, 1), background);
+    }
+
+    #[test]
+    fn test_blend_pixel_over_transparent() {
+        let mut image = RgbaImage::from_pixel(1, 1, image::Rgba([0, 0, 0, 0]));
+        let half_white = Rgba {
+            r: 1.,
+            g: 1.,
+            b: 1.,
+            a: 0.5,
+        };
+        blend_pixel(&mut image, 0, 0, half_white);
+        assert_eq!(*image.get_pixel(0, 0), image::Rgba([255, 255, 255, 128]));
+    }
+}
@@ -34,6 +34,18 @@ impl LineWrapper {
         &'a mut self,
         fragments: &'a [LineFragment],
         wrap_width: Pixels,
+    ) -> impl Iterator<Item = Boundary> + 'a {
+        self.wrap_line_with_indent(fragments, wrap_width, |indent| indent)
+    }
+
+    /// Wrap a line of text to the given width, indenting the wrapped lines by
+    /// `continuation_indent` applied to the indentation of the line's first non-whitespace
+    /// character.
+    pub fn wrap_line_with_indent<'a>(
+        &'a mut self,
+        fragments: &'a [LineFragment],
+        wrap_width: Pixels,
+        continuation_indent: impl Fn(u32) -> u32 + 'a,
     ) -> impl Iterator<Item = Boundary> + 'a {
         let mut width = px(0.);
         let mut first_non_whitespace_ix = None;
@@ -100,9 +112,9 @@ impl LineWrapper {
                 if width > wrap_width && ix > last_wrap_ix {
                     if let (None, Some(first_non_whitespace_ix)) = (indent, first_non_whitespace_ix)
                     {
-                        indent = Some(
-                            Self::MAX_INDENT.min((first_non_whitespace_ix - last_wrap_ix) as u32),
-                        );
+                        indent = Some(Self::MAX_INDENT.min(continuation_indent(
+                            (first_non_whitespace_ix - last_wrap_ix) as u32,
+                        )));
                     }
 
                     if last_candidate_ix > 0 {
@@ -114,11 +126,11 @@ impl LineWrapper {
                         width = item_width;
                     }
 
-                    if let Some(indent) = indent {
-                        width += self.width_for_char(' ') * indent as f32;
-                    }
+                    let next_indent =
+                        indent.unwrap_or_else(|| Self::MAX_INDENT.min(continuation_indent(0)));
+                    width += self.width_for_char(' ') * next_indent as f32;
 
-                    return Some(Boundary::new(last_wrap_ix, indent.unwrap_or(0)));
+                    return Some(Boundary::new(last_wrap_ix, next_indent));
                 }
 
                 prev_c = new_prev_c;
@@ -128,6 +140,44 @@ impl LineWrapper {
         })
     }
 
+    /// Wrap an extremely long line of text to the given width, breaking it before the first
+    /// character that doesn't fit rather than at a word boundary. Wrapped lines are never
+    /// indented.
+    pub fn wrap_long_line<'a>(
+        &'a mut self,
+        fragments: &'a [LineFragment],
+        wrap_width: Pixels,
+    ) -> impl Iterator<Item = Boundary> + 'a {
+        let mut width = px(0.);
+        let mut last_wrap_ix = 0;
+        let mut index = 0;
+        let mut candidates = fragments
+            .iter()
+            .flat_map(move |fragment| fragment.wrap_boundary_candidates());
+        iter::from_fn(move || {
+            for candidate in candidates.by_ref() {
+                let ix = index;
+                index += candidate.len_utf8();
+                let item_width = match candidate {
+                    WrapBoundaryCandidate::Char { character: '\n' } => continue,
+                    WrapBoundaryCandidate::Char { character } => self.width_for_char(character),
+                    WrapBoundaryCandidate::Element {
+                        width: element_width,
+                        ..
+                    } => element_width,
+                };
+
+                width += item_width;
+                if width > wrap_width && ix > last_wrap_ix {
+                    last_wrap_ix = ix;
+                    width = item_width;
+                    return Some(Boundary::new(ix, 0));
+                }
+            }
+            None
+        })
+    }
+
     /// Truncate a line of text to the given width with this wrapper's font and font size.
     pub fn truncate_line(
         &mut self,
@@ -485,6 +535,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_wrap_long_line() {
+        let mut wrapper = build_wrapper();
+
+        assert_eq!(
+            wrapper
+                .wrap_long_line(&[LineFragment::text("aa bbb cccc ddddd eeee")], px(72.))
+                .collect::<Vec<_>>(),
+            &[
+                Boundary::new(7, 0),
+                Boundary::new(14, 0),
+                Boundary::new(21, 0)
+            ],
+        );
+        assert_eq!(
+            wrapper
+                .wrap_long_line(&[LineFragment::text("     aaaaaaaaa\n")], px(72.))
+                .collect::<Vec<_>>(),
+            &[Boundary::new(7, 0)],
+        );
+        assert_eq!(
+            wrapper
+                .wrap_long_line(&[LineFragment::text("αβγδεζηθι")], px(72.))
+                .collect::<Vec<_>>(),
+            &[Boundary::new(14, 0)],
+        );
+        assert_eq!(
+            wrapper
+                .wrap_long_line(
+                    &[
+                        LineFragment::text("aaaa"),
+                        LineFragment::element(px(30.), 1),
+                        LineFragment::text("aaaa"),
+                    ],
+                    px(72.)
+                )
+                .collect::<Vec<_>>(),
+            &[Boundary::new(5, 0)],
+        );
+        // Characters wider than the wrap width get a line of their own.
+        assert_eq!(
+            wrapper
+                .wrap_long_line(&[LineFragment::text("abc")], px(5.))
+                .collect::<Vec<_>>(),
+            &[Boundary::new(1, 0), Boundary::new(2, 0)],
+        );
+    }
+
     #[test]
     fn test_truncate_line() {
         let mut wrapper = build_wrapper();
@@ -1809,6 +1809,12 @@ impl Window {
         self.platform_window.show_character_palette();
     }
 
+    /// Asks the platform's screen reader to read `message` aloud, interrupting any earlier
+    /// announcement. Currently only supported on macOS, with VoiceOver.
+    pub fn announce(&self, message: &str) {
+        self.platform_window.announce(message);
+    }
+
     /// The scale factor of the display associated with the window. For example, it could
     /// return 2.0 for a "retina" display, indicating that each logical pixel should actually
     /// be rendered as two pixels on screen.
@@ -48,6 +48,7 @@ pub enum IconName {
     BoltFilled,
     Book,
     BookCopy,
+    Bookmark,
     CaseSensitive,
     Chat,
     Check,
@@ -174,6 +175,7 @@ pub enum IconName {
     PlayFilled,
     Plus,
     Power,
+    PreserveCase,
     Public,
     PullRequest,
     Quote,
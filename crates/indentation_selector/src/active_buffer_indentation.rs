@@ -0,0 +1,117 @@
+use editor::Editor;
+use gpui::{
+    Context, Entity, IntoElement, ParentElement, Render, Subscription, WeakEntity, Window, div,
+};
+use language::{Buffer, IndentKind, IndentSize};
+use settings::Settings as _;
+use ui::{Button, ButtonCommon, Clickable, FluentBuilder, LabelSize, Tooltip};
+use workspace::{StatusBarSettings, StatusItemView, Workspace, item::ItemHandle};
+
+use crate::{IndentationSelector, Toggle};
+
+pub struct ActiveBufferIndentation {
+    active_indentation: Option<(Entity<Buffer>, IndentSize)>,
+    workspace: WeakEntity<Workspace>,
+    _observe_active_editor: Option<Subscription>,
+    _observe_active_buffer: Option<Subscription>,
+}
+
+impl ActiveBufferIndentation {
+    pub fn new(workspace: &Workspace) -> Self {
+        Self {
+            active_indentation: None,
+            workspace: workspace.weak_handle(),
+            _observe_active_editor: None,
+            _observe_active_buffer: None,
+        }
+    }
+
+    fn update_indentation(
+        &mut self,
+        editor: Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let previous_buffer = self
+            .active_indentation
+            .take()
+            .map(|(buffer, _)| buffer.entity_id());
+
+        if let Some((_, buffer, _)) = editor.read(cx).active_excerpt(cx) {
+            // The indentation can be overridden on the buffer without the editor changing.
+            if previous_buffer != Some(buffer.entity_id()) {
+                let editor = editor.clone();
+                self._observe_active_buffer =
+                    Some(cx.observe_in(&buffer, window, move |this, _, window, cx| {
+                        this.update_indentation(editor.clone(), window, cx)
+                    }));
+            }
+            let settings = buffer.read(cx).settings_at(0, cx);
+            let indentation = if settings.hard_tabs {
+                IndentSize::tab()
+            } else {
+                IndentSize::spaces(settings.tab_size.get())
+            };
+            self.active_indentation = Some((buffer, indentation));
+        }
+
+        cx.notify();
+    }
+}
+
+impl Render for ActiveBufferIndentation {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if !StatusBarSettings::get_global(cx).indentation_button {
+            return div();
+        }
+
+        div().when_some(
+            self.active_indentation.clone(),
+            |el, (buffer, indentation)| {
+                let label = match indentation.kind {
+                    IndentKind::Space => format!("Spaces: {}", indentation.len),
+                    IndentKind::Tab => "Tabs".to_string(),
+                };
+
+                el.child(
+                    Button::new("change-indentation", label)
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            if let Some(workspace) = this.workspace.upgrade() {
+                                let buffer = buffer.clone();
+                                workspace.update(cx, |workspace, cx| {
+                                    IndentationSelector::toggle_for_buffer(
+                                        workspace, buffer, window, cx,
+                                    )
+                                });
+                            }
+                        }))
+                        .tooltip(|window, cx| {
+                            Tooltip::for_action("Select Indentation", &Toggle, window, cx)
+                        }),
+                )
+            },
+        )
+    }
+}
+
+impl StatusItemView for ActiveBufferIndentation {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(editor) = active_pane_item.and_then(|item| item.downcast::<Editor>()) {
+            self._observe_active_editor =
+                Some(cx.observe_in(&editor, window, Self::update_indentation));
+            self.update_indentation(editor, window, cx);
+        } else {
+            self.active_indentation = None;
+            self._observe_active_editor = None;
+            self._observe_active_buffer = None;
+        }
+
+        cx.notify();
+    }
+}
@@ -0,0 +1,221 @@
+mod active_buffer_indentation;
+
+use editor::Editor;
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity, actions};
+use language::{Buffer, IndentKind, IndentOverride, IndentSize};
+use picker::{Picker, PickerDelegate};
+use std::sync::Arc;
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{ModalView, Workspace};
+
+pub use active_buffer_indentation::ActiveBufferIndentation;
+
+actions!(
+    indentation_selector,
+    [
+        /// Toggles the indentation selector modal.
+        Toggle
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(IndentationSelector::register).detach();
+}
+
+pub struct IndentationSelector {
+    picker: Entity<Picker<IndentationSelectorDelegate>>,
+}
+
+impl IndentationSelector {
+    fn register(editor: &mut Editor, _window: Option<&mut Window>, cx: &mut Context<Editor>) {
+        let editor_handle = cx.weak_entity();
+        editor
+            .register_action(move |_: &Toggle, window, cx| {
+                Self::toggle(&editor_handle, window, cx);
+            })
+            .detach();
+    }
+
+    fn toggle(editor: &WeakEntity<Editor>, window: &mut Window, cx: &mut App) {
+        let Some((workspace, buffer)) = editor
+            .update(cx, |editor, cx| {
+                Some((editor.workspace()?, editor.active_excerpt(cx)?.1))
+            })
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+
+        workspace.update(cx, |workspace, cx| {
+            Self::toggle_for_buffer(workspace, buffer, window, cx);
+        })
+    }
+
+    pub(crate) fn toggle_for_buffer(
+        workspace: &mut Workspace,
+        buffer: Entity<Buffer>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            IndentationSelector::new(buffer, window, cx)
+        });
+    }
+
+    fn new(buffer: Entity<Buffer>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let delegate = IndentationSelectorDelegate::new(cx.entity().downgrade(), buffer, cx);
+        let picker = cx.new(|cx| Picker::nonsearchable_uniform_list(delegate, window, cx));
+        Self { picker }
+    }
+}
+
+impl Render for IndentationSelector {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl Focusable for IndentationSelector {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for IndentationSelector {}
+impl ModalView for IndentationSelector {}
+
+/// An indentation that can be chosen for a buffer, or `None` to use the detected or configured one.
+type IndentationChoice = Option<IndentSize>;
+
+struct IndentationSelectorDelegate {
+    indentation_selector: WeakEntity<IndentationSelector>,
+    buffer: Entity<Buffer>,
+    indent_override: IndentOverride,
+    matches: Vec<IndentationChoice>,
+    selected_index: usize,
+}
+
+impl IndentationSelectorDelegate {
+    fn new(
+        indentation_selector: WeakEntity<IndentationSelector>,
+        buffer: Entity<Buffer>,
+        cx: &App,
+    ) -> Self {
+        let indent_override = buffer.read(cx).indent_override();
+        let matches = vec![
+            Some(IndentSize::spaces(2)),
+            Some(IndentSize::spaces(4)),
+            Some(IndentSize::spaces(8)),
+            Some(IndentSize::tab()),
+            None,
+        ];
+        let selected_index = matches
+            .iter()
+            .position(|choice| *choice == indent_override.explicit)
+            .unwrap_or_default();
+        Self {
+            indentation_selector,
+            buffer,
+            indent_override,
+            matches,
+            selected_index,
+        }
+    }
+
+    fn label(&self, choice: IndentationChoice) -> String {
+        match choice {
+            Some(IndentSize {
+                kind: IndentKind::Tab,
+                ..
+            }) => "Indent Using Tabs".to_string(),
+            Some(IndentSize { len, .. }) => format!("Indent Using {len} Spaces"),
+            None => match self.indent_override.detected {
+                Some(detected) => format!("Use Detected Indentation ({})", indent_label(detected)),
+                None => "Use Configured Indentation".to_string(),
+            },
+        }
+    }
+}
+
+impl PickerDelegate for IndentationSelectorDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select an indentation…".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if let Some(choice) = self.matches.get(self.selected_index) {
+            let indent_override = IndentOverride {
+                explicit: *choice,
+                ..self.indent_override
+            };
+            self.buffer.update(cx, |buffer, cx| {
+                buffer.set_indent_override(indent_override, cx);
+            });
+        }
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.indentation_selector
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        _query: String,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        Task::ready(())
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let choice = *self.matches.get(ix)?;
+        let mut list_item = ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected)
+            .child(Label::new(self.label(choice)));
+
+        if self.indent_override.explicit == choice {
+            list_item = list_item.end_slot(Icon::new(IconName::Check).color(Color::Muted));
+        }
+
+        Some(list_item)
+    }
+}
+
+fn indent_label(indent: IndentSize) -> String {
+    match indent.kind {
+        IndentKind::Space => format!("Spaces: {}", indent.len),
+        IndentKind::Tab => "Tabs".to_string(),
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
         SyntaxMapMatches, SyntaxSnapshot, ToTreeSitterPoint,
     },
     task_context::RunnableRange,
-    text_diff::text_diff,
+    text_diff::{text_diff, three_way_merge},
 };
 pub use crate::{
     Grammar, Language, LanguageRegistry,
@@ -24,8 +24,8 @@ use collections::HashMap;
 use fs::MTime;
 use futures::channel::oneshot;
 use gpui::{
-    App, AppContext as _, Context, Entity, EventEmitter, HighlightStyle, SharedString, StyledText,
-    Task, TaskLabel, TextStyle,
+    App, AppContext as _, Context, Entity, EntityId, EventEmitter, HighlightStyle, SharedString,
+    StyledText, Task, TaskLabel, TextStyle,
 };
 
 use lsp::{LanguageServerId, NumberOrString};
@@ -111,9 +111,20 @@ pub struct Buffer {
     syntax_map: Mutex<SyntaxMap>,
     reparse: Option<Task<()>>,
     parse_status: (watch::Sender<ParseStatus>, watch::Receiver<ParseStatus>),
+    /// The ranges of the buffer shown by each editor, which are reparsed first after an edit.
+    visible_ranges: HashMap<EntityId, Range<Anchor>>,
     non_text_state_update_count: usize,
     diagnostics: SmallVec<[(LanguageServerId, DiagnosticSet); 2]>,
     remote_selections: TreeMap<ReplicaId, SelectionSet>,
+    indent_override: IndentOverride,
+    /// Whether expensive features, such as syntax parsing, are disabled because the buffer is very large.
+    large_file_mode: bool,
+    /// Whether the user locked the buffer against edits, in every editor that shows it.
+    edits_locked: bool,
+    /// The encoding of the file on disk, which the buffer is converted to when saved.
+    encoding: Encoding,
+    /// The lines whose line break differed from the buffer's line ending when it was loaded.
+    mixed_line_endings: Vec<Range<Anchor>>,
     diagnostics_timestamp: clock::Lamport,
     completion_triggers: BTreeSet<String>,
     completion_triggers_per_language_server: HashMap<LanguageServerId, BTreeSet<String>>,
@@ -148,6 +159,7 @@ pub struct BufferSnapshot {
     diagnostics: SmallVec<[(LanguageServerId, DiagnosticSet); 2]>,
     remote_selections: TreeMap<ReplicaId, SelectionSet>,
     language: Option<Arc<Language>>,
+    indent_override: IndentOverride,
     non_text_state_update_count: usize,
 }
 
@@ -171,6 +183,43 @@ pub enum IndentKind {
     Tab,
 }
 
+/// Indentation of a buffer that takes precedence over the `tab_size` and `hard_tabs` settings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct IndentOverride {
+    /// The indentation detected from the buffer's contents when it was opened. Only used when
+    /// the `detect_indentation` setting is enabled.
+    pub detected: Option<IndentSize>,
+    /// The indentation explicitly chosen for the buffer, which takes precedence over the detected
+    /// one.
+    pub explicit: Option<IndentSize>,
+}
+
+impl IndentOverride {
+    /// Returns the indentation to use in place of the given settings, if any.
+    pub fn resolve(&self, settings: &LanguageSettings) -> Option<IndentSize> {
+        self.explicit
+            .or(self.detected.filter(|_| settings.detect_indentation))
+    }
+
+    /// Returns the given settings, with their indentation replaced by this override.
+    pub fn apply<'a>(&self, settings: Cow<'a, LanguageSettings>) -> Cow<'a, LanguageSettings> {
+        let Some(indent) = self.resolve(&settings) else {
+            return settings;
+        };
+        let mut settings = settings.into_owned();
+        match indent.kind {
+            IndentKind::Tab => settings.hard_tabs = true,
+            IndentKind::Space => {
+                settings.hard_tabs = false;
+                if let Some(tab_size) = NonZeroU32::new(indent.len) {
+                    settings.tab_size = tab_size;
+                }
+            }
+        }
+        Cow::Owned(settings)
+    }
+}
+
 /// The shape of a selection cursor.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum CursorShape {
@@ -299,6 +348,14 @@ pub enum Operation {
         /// The buffer's lamport timestamp.
         lamport_timestamp: clock::Lamport,
     },
+
+    /// An update to the encoding of this buffer's file.
+    UpdateEncoding {
+        /// The encoding.
+        encoding: Encoding,
+        /// The buffer's lamport timestamp.
+        lamport_timestamp: clock::Lamport,
+    },
 }
 
 /// An event that occurs in a buffer.
@@ -330,6 +387,10 @@ pub enum BufferEvent {
     DiagnosticsUpdated,
     /// The buffer gained or lost editing capabilities.
     CapabilityChanged,
+    /// The buffer entered or left large file mode.
+    LargeFileModeChanged,
+    /// The buffer was locked against edits, or unlocked.
+    EditsLockChanged,
 }
 
 /// The file associated with a buffer.
@@ -880,6 +941,9 @@ impl Buffer {
         this.text.set_line_ending(proto::deserialize_line_ending(
             rpc::proto::LineEnding::from_i32(message.line_ending).context("missing line_ending")?,
         ));
+        this.encoding = proto::deserialize_encoding(
+            rpc::proto::Encoding::from_i32(message.encoding).context("missing encoding")?,
+        );
         this.saved_version = proto::deserialize_version(&message.saved_version);
         this.saved_mtime = message.saved_mtime.map(|time| time.into());
         Ok(this)
@@ -894,6 +958,7 @@ impl Buffer {
             line_ending: proto::serialize_line_ending(self.line_ending()) as i32,
             saved_version: proto::serialize_version(&self.saved_version),
             saved_mtime: self.saved_mtime.map(|time| time.into()),
+            encoding: proto::serialize_encoding(self.encoding) as i32,
         }
     }
 
@@ -982,6 +1047,7 @@ impl Buffer {
             capability,
             syntax_map,
             reparse: None,
+            visible_ranges: HashMap::default(),
             non_text_state_update_count: 0,
             sync_parse_timeout: Duration::from_millis(1),
             parse_status: watch::channel(ParseStatus::Idle),
@@ -990,6 +1056,11 @@ impl Buffer {
             pending_autoindent: Default::default(),
             language: None,
             remote_selections: Default::default(),
+            indent_override: Default::default(),
+            large_file_mode: false,
+            edits_locked: false,
+            encoding: Encoding::default(),
+            mixed_line_endings: Vec::new(),
             diagnostics: Default::default(),
             diagnostics_timestamp: Default::default(),
             completion_triggers: Default::default(),
@@ -1025,6 +1096,7 @@ impl Buffer {
                 diagnostics: Default::default(),
                 remote_selections: Default::default(),
                 language,
+                indent_override: Default::default(),
                 non_text_state_update_count: 0,
             }
         }
@@ -1043,6 +1115,7 @@ impl Buffer {
             diagnostics: Default::default(),
             remote_selections: Default::default(),
             language: None,
+            indent_override: Default::default(),
             non_text_state_update_count: 0,
         }
     }
@@ -1068,6 +1141,7 @@ impl Buffer {
             diagnostics: Default::default(),
             remote_selections: Default::default(),
             language,
+            indent_override: Default::default(),
             non_text_state_update_count: 0,
         }
     }
@@ -1087,6 +1161,7 @@ impl Buffer {
             remote_selections: self.remote_selections.clone(),
             diagnostics: self.diagnostics.clone(),
             language: self.language.clone(),
+            indent_override: self.indent_override,
             non_text_state_update_count: self.non_text_state_update_count,
         }
     }
@@ -1100,6 +1175,7 @@ impl Buffer {
                     merged_operations: Default::default(),
                 }),
                 language: self.language.clone(),
+                indent_override: self.indent_override,
                 has_conflict: self.has_conflict,
                 has_unsaved_edits: Cell::new(self.has_unsaved_edits.get_mut().clone()),
                 _subscriptions: vec![cx.subscribe(&this, Self::on_base_buffer_event)],
@@ -1286,6 +1362,7 @@ impl Buffer {
     /// Assign the line ending type to the buffer.
     pub fn set_line_ending(&mut self, line_ending: LineEnding, cx: &mut Context<Self>) {
         self.text.set_line_ending(line_ending);
+        cx.notify();
 
         let lamport_timestamp = self.text.lamport_clock.tick();
         self.send_operation(
@@ -1298,6 +1375,32 @@ impl Buffer {
         );
     }
 
+    /// Returns the indentation that takes precedence over the language settings for this buffer.
+    pub fn indent_override(&self) -> IndentOverride {
+        self.indent_override
+    }
+
+    /// Sets the indentation that takes precedence over the language settings for this buffer.
+    pub fn set_indent_override(&mut self, indent_override: IndentOverride, cx: &mut Context<Self>) {
+        if self.indent_override != indent_override {
+            self.indent_override = indent_override;
+            self.non_text_state_update_count += 1;
+            cx.notify();
+        }
+    }
+
+    /// Infers the indentation of the buffer from its current contents.
+    pub fn detect_indent(&mut self, cx: &mut Context<Self>) {
+        let detected = detect_indent(&self.text);
+        self.set_indent_override(
+            IndentOverride {
+                detected,
+                ..self.indent_override
+            },
+            cx,
+        );
+    }
+
     /// Assign the buffer a new [`Capability`].
     pub fn set_capability(&mut self, capability: Capability, cx: &mut Context<Self>) {
         if self.capability != capability {
@@ -1306,6 +1409,96 @@ impl Buffer {
         }
     }
 
+    /// Whether this buffer is too large for expensive features, such as syntax parsing,
+    /// language servers, inlay hints and soft wrapping, which are disabled for it.
+    pub fn large_file_mode(&self) -> bool {
+        self.large_file_mode
+    }
+
+    /// The encoding of the buffer's file on disk, which the buffer is converted to when saved.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Sets the encoding that the buffer is converted to when saved.
+    pub fn set_encoding(&mut self, encoding: Encoding, cx: &mut Context<Self>) {
+        if self.encoding != encoding {
+            self.encoding = encoding;
+            self.non_text_state_update_count += 1;
+            cx.notify();
+
+            let lamport_timestamp = self.text.lamport_clock.tick();
+            self.send_operation(
+                Operation::UpdateEncoding {
+                    encoding,
+                    lamport_timestamp,
+                },
+                true,
+                cx,
+            );
+        }
+    }
+
+    /// The lines whose line break differed from the buffer's line ending when the file was loaded.
+    /// Saving the buffer writes every line with the buffer's line ending.
+    pub fn mixed_line_endings(&self) -> &[Range<Anchor>] {
+        &self.mixed_line_endings
+    }
+
+    pub fn has_mixed_line_endings(&self) -> bool {
+        !self.mixed_line_endings.is_empty()
+    }
+
+    /// Records the rows whose line break differs from the buffer's line ending, as found by
+    /// [`LineEnding::mismatched_rows`] in the text that the buffer was loaded from.
+    pub fn set_mixed_line_ending_rows(&mut self, rows: &[u32], cx: &mut Context<Self>) {
+        let max_row = self.max_point().row;
+        let mixed_line_endings = rows
+            .iter()
+            .filter(|row| **row <= max_row)
+            .map(|row| {
+                self.anchor_before(Point::new(*row, 0))
+                    ..self.anchor_after(Point::new(*row, self.line_len(*row)))
+            })
+            .collect::<Vec<_>>();
+        if mixed_line_endings.is_empty() && self.mixed_line_endings.is_empty() {
+            return;
+        }
+        self.mixed_line_endings = mixed_line_endings;
+        self.non_text_state_update_count += 1;
+        cx.notify();
+    }
+
+    /// Enables or disables large file mode for this buffer.
+    pub fn set_large_file_mode(&mut self, large_file_mode: bool, cx: &mut Context<Self>) {
+        if self.large_file_mode != large_file_mode {
+            self.large_file_mode = large_file_mode;
+            self.non_text_state_update_count += 1;
+            if self.reparse.take().is_some() {
+                self.parse_status.0.send(ParseStatus::Idle).unwrap();
+            }
+            self.syntax_map.lock().clear(&self.text);
+            self.was_changed();
+            self.reparse(cx);
+            cx.emit(BufferEvent::LargeFileModeChanged);
+        }
+    }
+
+    /// Whether the user locked this buffer against edits. Unlike a read-only [`Capability`], the
+    /// lock only applies to this replica, and can be lifted by the user.
+    pub fn edits_locked(&self) -> bool {
+        self.edits_locked
+    }
+
+    /// Locks this buffer against edits made through editors, or unlocks it.
+    pub fn set_edits_locked(&mut self, edits_locked: bool, cx: &mut Context<Self>) {
+        if self.edits_locked != edits_locked {
+            self.edits_locked = edits_locked;
+            cx.emit(BufferEvent::EditsLockChanged);
+            cx.notify();
+        }
+    }
+
     /// This method is called to signal that the buffer has been saved.
     pub fn did_save(
         &mut self,
@@ -1317,6 +1510,7 @@ impl Buffer {
         self.has_unsaved_edits.set((version, false));
         self.has_conflict = false;
         self.saved_mtime = mtime;
+        self.set_mixed_line_ending_rows(&[], cx);
         self.was_changed();
         cx.emit(BufferEvent::Saved);
         cx.notify();
@@ -1327,16 +1521,18 @@ impl Buffer {
         let (tx, rx) = futures::channel::oneshot::channel();
         let prev_version = self.text.version();
         self.reload_task = Some(cx.spawn(async move |this, cx| {
-            let Some((new_mtime, new_text)) = this.update(cx, |this, cx| {
+            let Some((new_mtime, new_bytes)) = this.update(cx, |this, cx| {
                 let file = this.file.as_ref()?.as_local()?;
 
-                Some((file.disk_state().mtime(), file.load(cx)))
+                Some((file.disk_state().mtime(), file.load_bytes(cx)))
             })?
             else {
                 return Ok(());
             };
 
-            let new_text = new_text.await?;
+            let (new_text, encoding) = Encoding::detect_and_decode(new_bytes.await?)?;
+            let mixed_line_ending_rows =
+                LineEnding::mismatched_rows(&new_text, LineEnding::detect(&new_text));
             let diff = this
                 .update(cx, |this, cx| this.diff(new_text.clone(), cx))?
                 .await;
@@ -1346,6 +1542,8 @@ impl Buffer {
                     this.apply_diff(diff, cx);
                     tx.send(this.finalize_last_transaction().cloned()).ok();
                     this.has_conflict = false;
+                    this.set_encoding(encoding, cx);
+                    this.set_mixed_line_ending_rows(&mixed_line_ending_rows, cx);
                     this.did_reload(this.version(), this.line_ending(), new_mtime, cx);
                 } else {
                     if !diff.edits.is_empty()
@@ -1366,6 +1564,68 @@ impl Buffer {
         rx
     }
 
+    /// Reloads the contents of the buffer from disk while keeping its unsaved edits, by merging
+    /// the changes made on disk and in the buffer since it was last saved. Lines that were changed
+    /// differently on both sides are kept between conflict markers. Resolves to whether there
+    /// were any conflicts.
+    pub fn reload_with_merge(&mut self, cx: &Context<Self>) -> Task<Result<bool>> {
+        cx.spawn(async move |this, cx| {
+            let (new_mtime, new_bytes) = this
+                .update(cx, |this, cx| {
+                    let file = this.file.as_ref()?.as_local()?;
+                    Some((file.disk_state().mtime(), file.load_bytes(cx)))
+                })?
+                .context("buffer has no local file to merge with")?;
+
+            let (new_text, encoding) = Encoding::detect_and_decode(new_bytes.await?)?;
+            let mixed_line_ending_rows =
+                LineEnding::mismatched_rows(&new_text, LineEnding::detect(&new_text));
+            let mut disk_text = new_text.clone();
+            LineEnding::normalize(&mut disk_text);
+
+            let (saved_text, buffer_text, version) = this.update(cx, |this, _| {
+                (
+                    this.rope_for_version(&this.saved_version).to_string(),
+                    this.text(),
+                    this.version(),
+                )
+            })?;
+            let (merged_text, has_conflicts) = cx
+                .background_spawn({
+                    let disk_text = disk_text.clone();
+                    async move {
+                        three_way_merge(
+                            &saved_text,
+                            &buffer_text,
+                            &disk_text,
+                            ["Unsaved changes", "Last saved", "On disk"],
+                        )
+                    }
+                })
+                .await;
+            let diff = this.update(cx, |this, cx| this.diff(new_text, cx))?.await;
+
+            this.update(cx, |this, cx| {
+                anyhow::ensure!(
+                    this.version() == version && diff.base_version == version,
+                    "buffer was edited while merging"
+                );
+                // Reload the text on disk first, so that the merged changes become unsaved edits.
+                this.finalize_last_transaction();
+                this.apply_diff(diff, cx);
+                this.finalize_last_transaction();
+                this.has_conflict = false;
+                this.set_encoding(encoding, cx);
+                this.set_mixed_line_ending_rows(&mixed_line_ending_rows, cx);
+                this.did_reload(this.version(), this.line_ending(), new_mtime, cx);
+
+                this.edit(text_diff(&disk_text, &merged_text), None, cx);
+                this.finalize_last_transaction();
+                Ok(has_conflicts)
+            })?
+        })
+    }
+
     /// This method is called to signal that the buffer has been reloaded.
     pub fn did_reload(
         &mut self,
@@ -1458,6 +1718,21 @@ impl Buffer {
             .or_else(|| self.language.clone())
     }
 
+    /// Returns the settings for the language at the given location, accounting for the
+    /// buffer's [`IndentOverride`].
+    pub fn settings_at<'a, D: ToOffset>(
+        &'a self,
+        position: D,
+        cx: &'a App,
+    ) -> Cow<'a, LanguageSettings> {
+        let settings = language_settings(
+            self.language_at(position).map(|l| l.name()),
+            self.file.as_ref(),
+            cx,
+        );
+        self.indent_override.apply(settings)
+    }
+
     /// Returns each [`Language`] for the active syntax layers at the given location.
     pub fn languages_at<D: ToOffset>(&self, position: D) -> Vec<Arc<Language>> {
         let offset = position.to_offset(self);
@@ -1495,6 +1770,20 @@ impl Buffer {
         self.syntax_map.lock().contains_unknown_injections()
     }
 
+    /// Records the range of the buffer shown by `viewer`, or that it no longer shows the buffer.
+    /// After an edit, the syntax of the visible ranges is reparsed before the rest of the buffer,
+    /// so that they're highlighted first.
+    pub fn set_visible_range(&mut self, viewer: EntityId, range: Option<Range<Anchor>>) {
+        match range {
+            Some(range) => {
+                self.visible_ranges.insert(viewer, range);
+            }
+            None => {
+                self.visible_ranges.remove(&viewer);
+            }
+        }
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn set_sync_parse_timeout(&mut self, timeout: Duration) {
         self.sync_parse_timeout = timeout;
@@ -1523,8 +1812,12 @@ impl Buffer {
     /// initiate an additional reparse recursively. To avoid concurrent parses
     /// for the same buffer, we only initiate a new parse if we are not already
     /// parsing in the background.
+    ///
+    /// Injected layers outside of the ranges shown in editors are left out of
+    /// the parse of an edit, and reparsed right after it, so that the visible
+    /// ranges are highlighted first after large edits.
     pub fn reparse(&mut self, cx: &mut Context<Self>) {
-        if self.reparse.is_some() {
+        if self.reparse.is_some() || self.large_file_mode {
             return;
         }
         let language = if let Some(language) = self.language.clone() {
@@ -1535,6 +1828,11 @@ impl Buffer {
 
         let text = self.text_snapshot();
         let parsed_version = self.version();
+        let visible_ranges = self
+            .visible_ranges
+            .values()
+            .map(|range| range.to_offset(&text))
+            .collect::<Vec<_>>();
 
         let mut syntax_map = self.syntax_map.lock();
         syntax_map.interpolate(&text);
@@ -1546,7 +1844,12 @@ impl Buffer {
             let language = language.clone();
             let language_registry = language_registry.clone();
             async move {
-                syntax_snapshot.reparse(&text, language_registry, language);
+                syntax_snapshot.reparse_prioritized(
+                    &text,
+                    language_registry,
+                    language,
+                    &visible_ranges,
+                );
                 syntax_snapshot
             }
         });
@@ -1557,8 +1860,12 @@ impl Buffer {
             .block_with_timeout(self.sync_parse_timeout, parse_task)
         {
             Ok(new_syntax_snapshot) => {
+                let has_deferred_ranges = new_syntax_snapshot.has_deferred_ranges();
                 self.did_finish_parsing(new_syntax_snapshot, cx);
                 self.reparse = None;
+                if has_deferred_ranges {
+                    self.reparse(cx);
+                }
             }
             Err(parse_task) => {
                 self.reparse = Some(cx.spawn(async move |this, cx| {
@@ -1575,6 +1882,7 @@ impl Buffer {
                             });
                         let parse_again = language_registry_changed
                             || grammar_changed
+                            || new_syntax_map.has_deferred_ranges()
                             || this.version.changed_since(&parsed_version);
                         this.did_finish_parsing(new_syntax_map, cx);
                         this.reparse = None;
@@ -1918,11 +2226,44 @@ impl Buffer {
     /// Spawns a background task that searches the buffer for any whitespace
     /// at the ends of a lines, and returns a `Diff` that removes that whitespace.
     pub fn remove_trailing_whitespace(&self, cx: &App) -> Task<Diff> {
+        self.remove_trailing_whitespace_in_rows(None, cx)
+    }
+
+    /// Like [`Buffer::remove_trailing_whitespace`], but only removes whitespace
+    /// from lines that were edited since the buffer was last saved.
+    pub fn remove_trailing_whitespace_in_modified_lines(&self, cx: &App) -> Task<Diff> {
+        let modified_rows = self
+            .edits_since::<Point>(self.saved_version())
+            .map(|edit| {
+                // An edit ending at the start of a line, such as an inserted
+                // newline, doesn't modify that line.
+                let end_row = if edit.new.end.column == 0 && edit.new.end.row > edit.new.start.row {
+                    edit.new.end.row
+                } else {
+                    edit.new.end.row + 1
+                };
+                edit.new.start.row..end_row
+            })
+            .collect();
+        self.remove_trailing_whitespace_in_rows(Some(modified_rows), cx)
+    }
+
+    fn remove_trailing_whitespace_in_rows(
+        &self,
+        rows: Option<Vec<Range<u32>>>,
+        cx: &App,
+    ) -> Task<Diff> {
         let old_text = self.as_rope().clone();
         let line_ending = self.line_ending();
         let base_version = self.version();
         cx.background_spawn(async move {
-            let ranges = trailing_whitespace_ranges(&old_text);
+            let mut ranges = trailing_whitespace_ranges(&old_text);
+            if let Some(rows) = rows {
+                ranges.retain(|range| {
+                    let row = old_text.offset_to_point(range.start).row;
+                    rows.iter().any(|rows| rows.contains(&row))
+                });
+            }
             let empty = Arc::<str>::from("");
             Diff {
                 base_version,
@@ -2614,7 +2955,9 @@ impl Buffer {
             Operation::UpdateSelections { selections, .. } => selections
                 .iter()
                 .all(|s| self.can_resolve(&s.start) && self.can_resolve(&s.end)),
-            Operation::UpdateCompletionTriggers { .. } | Operation::UpdateLineEnding { .. } => true,
+            Operation::UpdateCompletionTriggers { .. }
+            | Operation::UpdateLineEnding { .. }
+            | Operation::UpdateEncoding { .. } => true,
         }
     }
 
@@ -2687,6 +3030,16 @@ impl Buffer {
                 self.text.set_line_ending(line_ending);
                 self.text.lamport_clock.observe(lamport_timestamp);
             }
+            Operation::UpdateEncoding {
+                encoding,
+                lamport_timestamp,
+            } => {
+                if self.encoding != encoding {
+                    self.encoding = encoding;
+                    self.non_text_state_update_count += 1;
+                }
+                self.text.lamport_clock.observe(lamport_timestamp);
+            }
         }
     }
 
@@ -2955,11 +3308,7 @@ impl BufferSnapshot {
     /// Returns [`IndentSize`] for a given position that respects user settings
     /// and language preferences.
     pub fn language_indent_size_at<T: ToOffset>(&self, position: T, cx: &App) -> IndentSize {
-        let settings = language_settings(
-            self.language_at(position).map(|l| l.name()),
-            self.file(),
-            cx,
-        );
+        let settings = self.settings_at(position, cx);
         if settings.hard_tabs {
             IndentSize::tab()
         } else {
@@ -3004,7 +3353,9 @@ impl BufferSnapshot {
         &self,
         row_range: Range<u32>,
     ) -> Option<impl Iterator<Item = Option<IndentSuggestion>> + '_> {
-        let config = &self.language.as_ref()?.config;
+        // The indent patterns of the rows come from the language at their start, which is the
+        // injected language in embedded code such as Markdown's fenced code blocks.
+        let config = &self.language_at(Point::new(row_range.start, 0))?.config;
         let prev_non_blank_row = self.prev_non_blank_row(row_range.start);
 
         #[derive(Debug, Clone)]
@@ -3391,17 +3742,24 @@ impl BufferSnapshot {
             .or(self.language.as_ref())
     }
 
-    /// Returns the settings for the language at the given location.
+    /// Returns the settings for the language at the given location, accounting for the
+    /// buffer's [`IndentOverride`].
     pub fn settings_at<'a, D: ToOffset>(
         &'a self,
         position: D,
         cx: &'a App,
     ) -> Cow<'a, LanguageSettings> {
-        language_settings(
+        let settings = language_settings(
             self.language_at(position).map(|l| l.name()),
             self.file.as_ref(),
             cx,
-        )
+        );
+        self.indent_override.apply(settings)
+    }
+
+    /// Returns the indentation that takes precedence over the language settings for this buffer.
+    pub fn indent_override(&self) -> IndentOverride {
+        self.indent_override
     }
 
     pub fn char_classifier_at<T: ToOffset>(&self, point: T) -> CharClassifier {
@@ -4048,6 +4406,62 @@ impl BufferSnapshot {
         self.syntax.matches(range, self, query)
     }
 
+    /// Returns the ranges of the import statements, such as `use` declarations, that intersect
+    /// the given range, sorted by their start.
+    pub fn import_ranges(&self, range: Range<usize>) -> Vec<Range<usize>> {
+        let mut matches = self.syntax.matches(range, &self.text, |grammar| {
+            grammar.imports_config.as_ref().map(|config| &config.query)
+        });
+        let configs = matches
+            .grammars()
+            .iter()
+            .map(|grammar| grammar.imports_config.as_ref().unwrap())
+            .collect::<Vec<_>>();
+
+        let mut ranges = Vec::new();
+        while let Some(mat) = matches.peek() {
+            let import_ix = configs[mat.grammar_index].import_ix;
+            ranges.extend(
+                mat.captures
+                    .iter()
+                    .filter(|capture| capture.index == import_ix)
+                    .map(|capture| capture.node.byte_range()),
+            );
+            matches.advance();
+        }
+        ranges.sort_unstable_by_key(|range| (range.start, range.end));
+        ranges.dedup();
+        ranges
+    }
+
+    /// Returns the ranges of the syntax errors that intersect the given range: the text that
+    /// the parser couldn't make sense of, and the empty ranges where it expected a token that
+    /// is missing, such as a closing quote or bracket.
+    pub fn syntax_error_ranges(&self, range: Range<usize>) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        'layers: for layer in self.syntax_layers_for_range(range.clone(), true) {
+            let mut cursor = layer.node().walk();
+            loop {
+                let node = cursor.node();
+                if node.start_byte() <= range.end && node.end_byte() >= range.start {
+                    if node.is_error() || node.is_missing() {
+                        ranges.push(node.byte_range());
+                    } else if node.has_error() && cursor.goto_first_child() {
+                        continue;
+                    }
+                }
+                while !cursor.goto_next_sibling() {
+                    if !cursor.goto_parent() {
+                        continue 'layers;
+                    }
+                }
+            }
+        }
+        ranges.sort_unstable_by_key(|range| (range.start, range.end));
+        ranges.dedup();
+        ranges
+    }
+
     pub fn all_bracket_ranges(
         &self,
         range: Range<usize>,
@@ -4326,6 +4740,64 @@ impl BufferSnapshot {
         })
     }
 
+    /// Returns whether folding on the given row is driven by the language's `folds.scm` query,
+    /// rather than by indentation.
+    pub fn uses_syntax_folds(&self, row: u32) -> bool {
+        self.language_at(Point::new(row, self.indent_size_for_line(row).len))
+            .and_then(|language| language.grammar())
+            .is_some_and(|grammar| grammar.folds_config.is_some())
+    }
+
+    /// Returns the range to fold for the outermost `@fold` syntax node that starts on the given row.
+    ///
+    /// Like indentation-based folds, the range starts at the end of the row, and the node's last
+    /// line stays visible when it only closes the node (e.g. a lone `}`).
+    pub fn syntax_fold_range(&self, row: u32) -> Option<Range<Point>> {
+        let row_range =
+            Point::new(row, 0).to_offset(self)..Point::new(row, self.line_len(row)).to_offset(self);
+        let mut syntax_matches = self.syntax.matches(row_range, self, |grammar| {
+            grammar.folds_config.as_ref().map(|config| &config.query)
+        });
+        let configs = syntax_matches
+            .grammars()
+            .iter()
+            .map(|grammar| grammar.folds_config.as_ref())
+            .collect::<Vec<_>>();
+
+        let mut fold_end: Option<Point> = None;
+        while let Some(mat) = syntax_matches.peek() {
+            if let Some(config) = configs[mat.grammar_index] {
+                for capture in mat.captures {
+                    if capture.index != config.fold_capture_ix {
+                        continue;
+                    }
+                    let start = self.offset_to_point(capture.node.start_byte());
+                    let mut end = self.offset_to_point(capture.node.end_byte());
+                    if start.row != row {
+                        continue;
+                    }
+                    if end.column == 0
+                        || self
+                            .text_for_range(Point::new(end.row, 0)..end)
+                            .flat_map(|chunk| chunk.chars())
+                            .all(|c| c.is_whitespace() || c.is_ascii_punctuation())
+                    {
+                        end = Point::new(
+                            end.row.saturating_sub(1),
+                            self.line_len(end.row.saturating_sub(1)),
+                        );
+                    }
+                    if end.row > row && fold_end.is_none_or(|fold_end| end > fold_end) {
+                        fold_end = Some(end);
+                    }
+                }
+            }
+            syntax_matches.advance();
+        }
+
+        Some(Point::new(row, self.line_len(row))..fold_end?)
+    }
+
     pub fn injections_intersecting_range<T: ToOffset>(
         &self,
         range: Range<T>,
@@ -4712,6 +5184,48 @@ fn indent_size_for_line(text: &text::BufferSnapshot, row: u32) -> IndentSize {
     indent_size_for_text(text.chars_at(Point::new(row, 0)))
 }
 
+/// Infers the indentation unit of a buffer from the leading whitespace of its first lines.
+///
+/// Returns `None` when there isn't enough indented text to tell.
+fn detect_indent(text: &text::BufferSnapshot) -> Option<IndentSize> {
+    const MAX_ROWS_TO_SAMPLE: u32 = 1000;
+    const MAX_INDENT_WIDTH: u32 = 8;
+
+    let mut tab_rows = 0;
+    let mut space_rows = 0;
+    let mut indent_width_counts = [0usize; MAX_INDENT_WIDTH as usize + 1];
+    let mut previous_indent = 0;
+    for row in 0..=text.max_point().row.min(MAX_ROWS_TO_SAMPLE) {
+        let indent = indent_size_for_line(text, row);
+        if indent.len == text.line_len(row) {
+            continue;
+        }
+        match indent.kind {
+            IndentKind::Tab => tab_rows += 1,
+            IndentKind::Space => {
+                if indent.len > 0 {
+                    space_rows += 1;
+                }
+                // Single-space steps are usually alignment, e.g. in block comments.
+                let step = indent.len.saturating_sub(previous_indent);
+                if (2..=MAX_INDENT_WIDTH).contains(&step) {
+                    indent_width_counts[step as usize] += 1;
+                }
+                previous_indent = indent.len;
+            }
+        }
+    }
+
+    if tab_rows > space_rows {
+        return Some(IndentSize::tab());
+    }
+    let (width, count) = indent_width_counts
+        .iter()
+        .enumerate()
+        .max_by_key(|(width, count)| (**count, Reverse(*width)))?;
+    (*count > 0).then(|| IndentSize::spaces(width as u32))
+}
+
 fn indent_size_for_text(text: impl Iterator<Item = char>) -> IndentSize {
     let mut result = IndentSize::spaces(0);
     for c in text {
@@ -4737,6 +5251,7 @@ impl Clone for BufferSnapshot {
             remote_selections: self.remote_selections.clone(),
             diagnostics: self.diagnostics.clone(),
             language: self.language.clone(),
+            indent_override: self.indent_override,
             non_text_state_update_count: self.non_text_state_update_count,
         }
     }
@@ -5034,6 +5549,9 @@ impl operation_queue::Operation for Operation {
             }
             | Operation::UpdateLineEnding {
                 lamport_timestamp, ..
+            }
+            | Operation::UpdateEncoding {
+                lamport_timestamp, ..
             } => *lamport_timestamp,
         }
     }
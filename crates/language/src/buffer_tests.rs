@@ -20,7 +20,7 @@ use std::{
 };
 use syntax_map::TreeSitterOptions;
 use text::network::Network;
-use text::{BufferId, LineEnding};
+use text::{BufferId, Encoding, LineEnding};
 use text::{Point, ToPoint};
 use theme::ActiveTheme;
 use unindent::Unindent as _;
@@ -66,6 +66,38 @@ fn test_line_endings(cx: &mut gpui::App) {
     });
 }
 
+#[gpui::test]
+fn test_mixed_line_endings(cx: &mut gpui::App) {
+    let text = "one\r\ntwo\nthree\r\nfour\n";
+    let mixed_rows = LineEnding::mismatched_rows(text, LineEnding::detect(text));
+    assert_eq!(mixed_rows, [1, 3]);
+
+    cx.new(|cx| {
+        let mut buffer = Buffer::local(text, cx);
+        buffer.set_mixed_line_ending_rows(&mixed_rows, cx);
+        assert!(buffer.has_mixed_line_endings());
+
+        buffer.edit([(0..0, "zero\n")], None, cx);
+        let mixed_lines = buffer
+            .mixed_line_endings()
+            .iter()
+            .map(|range| range.start.to_point(&buffer)..range.end.to_point(&buffer))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            mixed_lines,
+            [
+                Point::new(2, 0)..Point::new(2, 3),
+                Point::new(4, 0)..Point::new(4, 4)
+            ]
+        );
+
+        // Saving writes every line with the buffer's line ending.
+        buffer.did_save(buffer.version(), None, cx);
+        assert!(!buffer.has_mixed_line_endings());
+        buffer
+    });
+}
+
 #[gpui::test]
 fn test_set_line_ending(cx: &mut TestAppContext) {
     let base = cx.new(|cx| Buffer::local("one\ntwo\nthree\n", cx));
@@ -138,6 +170,40 @@ fn test_set_line_ending(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+fn test_set_encoding(cx: &mut TestAppContext) {
+    let base = cx.new(|cx| {
+        let mut buffer = Buffer::local("café\n", cx);
+        buffer.set_encoding(Encoding::Latin1, cx);
+        buffer
+    });
+    let base_replica = cx.new(|cx| {
+        Buffer::from_proto(1, Capability::ReadWrite, base.read(cx).to_proto(cx), None).unwrap()
+    });
+    base_replica.read_with(cx, |buffer, _| {
+        assert_eq!(buffer.encoding(), Encoding::Latin1);
+    });
+
+    base_replica.update(cx, |_buffer, cx| {
+        cx.subscribe(&base, |this, _, event, cx| {
+            if let BufferEvent::Operation {
+                operation,
+                is_local: true,
+            } = event
+            {
+                this.apply_ops([operation.clone()], cx);
+            }
+        })
+        .detach();
+    });
+    base.update(cx, |buffer, cx| {
+        buffer.set_encoding(Encoding::Windows1252, cx)
+    });
+    base_replica.read_with(cx, |buffer, _| {
+        assert_eq!(buffer.encoding(), Encoding::Windows1252);
+    });
+}
+
 #[gpui::test]
 fn test_select_language(cx: &mut App) {
     init_settings(cx, |_| {});
@@ -599,6 +665,47 @@ async fn test_normalize_whitespace(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_remove_trailing_whitespace_in_modified_lines(cx: &mut gpui::TestAppContext) {
+    let text = [
+        "zero  ",  // 2 trailing spaces
+        "one  ",   // 2 trailing spaces
+        "two\t",   // 1 trailing tab
+        "three  ", // 2 trailing spaces
+    ]
+    .join("\n");
+
+    let buffer = cx.new(|cx| Buffer::local(text, cx));
+    buffer.update(cx, |buffer, cx| {
+        buffer.edit(
+            [
+                (Point::new(1, 3)..Point::new(1, 3), "!"),
+                (Point::new(2, 3)..Point::new(3, 0), " and a half \n"),
+            ],
+            None,
+            cx,
+        );
+    });
+
+    let format = buffer.update(cx, |buffer, cx| {
+        buffer.remove_trailing_whitespace_in_modified_lines(cx)
+    });
+    let format_diff = format.await;
+    buffer.update(cx, |buffer, cx| {
+        buffer.apply_diff(format_diff, cx);
+        assert_eq!(
+            buffer.text(),
+            [
+                "zero  ",         //
+                "one!",           //
+                "two and a half", //
+                "three  ",        //
+            ]
+            .join("\n")
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_reparse(cx: &mut gpui::TestAppContext) {
     let text = "fn a() {}";
@@ -726,6 +833,37 @@ async fn test_reparse(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_large_file_mode(cx: &mut gpui::TestAppContext) {
+    let buffer = cx.new(|cx| {
+        let mut buffer = Buffer::local("fn a() {}", cx);
+        buffer.set_large_file_mode(true, cx);
+        buffer.with_language(Arc::new(rust_lang()), cx)
+    });
+
+    // Large file mode prevents the buffer from being parsed
+    cx.executor().run_until_parked();
+    buffer.update(cx, |buffer, _| {
+        assert!(!buffer.is_parsing());
+        assert_eq!(
+            buffer
+                .snapshot()
+                .syntax
+                .layers(buffer.as_text_snapshot())
+                .len(),
+            0
+        );
+    });
+
+    // Leaving large file mode parses the buffer
+    buffer.update(cx, |buffer, cx| buffer.set_large_file_mode(false, cx));
+    cx.executor().run_until_parked();
+    assert_eq!(
+        get_tree_sexp(&buffer, cx),
+        "(source_file (function_item name: (identifier) parameters: (parameters) body: (block)))"
+    );
+}
+
 #[gpui::test]
 async fn test_resetting_language(cx: &mut gpui::TestAppContext) {
     let buffer = cx.new(|cx| {
@@ -748,6 +886,27 @@ async fn test_resetting_language(cx: &mut gpui::TestAppContext) {
     assert_eq!(get_tree_sexp(&buffer, cx), "(document (object))");
 }
 
+#[gpui::test]
+fn test_syntax_error_ranges(cx: &mut App) {
+    let text = "fn a() {}\nfn b() { let c = ; }\nfn d() {}\n";
+    let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(Arc::new(rust_lang()), cx));
+    let snapshot = buffer.read(cx).snapshot();
+
+    let second_fn = text.find("fn b").unwrap()..text.find("fn d").unwrap();
+    let errors = snapshot.syntax_error_ranges(0..text.len());
+    assert!(!errors.is_empty());
+    assert!(
+        errors
+            .iter()
+            .all(|error| second_fn.start <= error.start && error.end <= second_fn.end)
+    );
+    assert_eq!(snapshot.syntax_error_ranges(0..second_fn.start - 1), []);
+
+    buffer.update(cx, |buffer, cx| buffer.edit([(second_fn, "")], None, cx));
+    let snapshot = buffer.read(cx).snapshot();
+    assert_eq!(snapshot.syntax_error_ranges(0..snapshot.len()), []);
+}
+
 #[gpui::test]
 async fn test_outline(cx: &mut gpui::TestAppContext) {
     let text = r#"
@@ -1103,6 +1262,54 @@ fn test_text_objects(cx: &mut App) {
     )
 }
 
+#[gpui::test]
+fn test_syntax_fold_ranges(cx: &mut App) {
+    let text = indoc! {"
+        fn a(
+            b: u32,
+        ) {
+            let c = [
+                1,
+                2];
+        }
+        fn d() {}
+    "};
+    let language = rust_lang()
+        .with_folds_query(
+            r#"
+            [
+                (block)
+                (parameters)
+                (array_expression)
+            ] @fold
+            "#,
+        )
+        .unwrap();
+    let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(Arc::new(language), cx));
+    let snapshot = buffer.read(cx).snapshot();
+
+    assert!(snapshot.uses_syntax_folds(0));
+    // The closing line of a node stays visible.
+    assert_eq!(
+        snapshot.syntax_fold_range(0),
+        Some(Point::new(0, 5)..Point::new(1, 11))
+    );
+    assert_eq!(
+        snapshot.syntax_fold_range(2),
+        Some(Point::new(2, 3)..Point::new(5, 11))
+    );
+    // Nodes ending on a line with other content are folded entirely.
+    assert_eq!(
+        snapshot.syntax_fold_range(3),
+        Some(Point::new(3, 13)..Point::new(5, 10))
+    );
+    assert_eq!(snapshot.syntax_fold_range(1), None);
+    assert_eq!(snapshot.syntax_fold_range(7), None);
+
+    let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(Arc::new(rust_lang()), cx));
+    assert!(!buffer.read(cx).snapshot().uses_syntax_folds(0));
+}
+
 #[gpui::test]
 fn test_enclosing_bracket_ranges(cx: &mut App) {
     let mut assert = |selection_text, range_markers| {
@@ -1383,6 +1590,64 @@ fn test_autoindent_with_hard_tabs(cx: &mut App) {
     });
 }
 
+#[gpui::test]
+fn test_detect_indent(cx: &mut App) {
+    init_settings(cx, |_| {});
+
+    cx.new(|cx| {
+        let text = "fn a() {\n  if b {\n    c();\n  }\n\n  /*\n   * d\n   */\n}\n";
+        let mut buffer = Buffer::local(text, cx).with_language(Arc::new(rust_lang()), cx);
+        buffer.detect_indent(cx);
+        assert_eq!(
+            buffer.indent_override().detected,
+            Some(IndentSize::spaces(2))
+        );
+        let settings = buffer.settings_at(0, cx);
+        assert_eq!((settings.tab_size.get(), settings.hard_tabs), (2, false));
+
+        buffer.edit([(8..8, "\n")], Some(AutoindentMode::EachLine), cx);
+        assert_eq!(
+            buffer.text(),
+            "fn a() {\n  \n  if b {\n    c();\n  }\n\n  /*\n   * d\n   */\n}\n"
+        );
+
+        // An explicitly chosen indentation takes precedence over the detected one.
+        buffer.set_indent_override(
+            IndentOverride {
+                explicit: Some(IndentSize::tab()),
+                ..buffer.indent_override()
+            },
+            cx,
+        );
+        let settings = buffer.settings_at(0, cx);
+        assert_eq!((settings.tab_size.get(), settings.hard_tabs), (4, true));
+        buffer
+    });
+
+    let buffer = cx.new(|cx| {
+        let mut buffer = Buffer::local("a\n\tb\n\t\tc\n", cx);
+        buffer.detect_indent(cx);
+        assert_eq!(buffer.indent_override().detected, Some(IndentSize::tab()));
+        assert!(buffer.settings_at(0, cx).hard_tabs);
+        buffer
+    });
+
+    // Detection can be disabled in the settings.
+    cx.update_global::<SettingsStore, _>(|settings, cx| {
+        settings.update_user_settings(cx, |content| {
+            content.project.all_languages.defaults.detect_indentation = Some(false);
+        });
+    });
+    assert!(!buffer.read(cx).settings_at(0, cx).hard_tabs);
+
+    cx.new(|cx| {
+        let mut buffer = Buffer::local("a\nb\n", cx);
+        buffer.detect_indent(cx);
+        assert_eq!(buffer.indent_override().detected, None);
+        buffer
+    });
+}
+
 #[gpui::test]
 fn test_autoindent_does_not_adjust_lines_with_unchanged_suggestion(cx: &mut App) {
     init_settings(cx, |_| {});
@@ -2156,6 +2421,71 @@ fn test_autoindent_with_injected_languages(cx: &mut App) {
     });
 }
 
+#[gpui::test]
+fn test_autoindent_with_injected_language_patterns(cx: &mut App) {
+    init_settings(cx, |_| {});
+
+    let python_language = Arc::new(Language::new(
+        LanguageConfig {
+            name: "Python".into(),
+            matcher: LanguageMatcher {
+                path_suffixes: vec!["py".to_string()],
+                ..Default::default()
+            },
+            increase_indent_pattern: Some(regex::Regex::new(r":\s*$").unwrap()),
+            ..Default::default()
+        },
+        Some(tree_sitter_python::LANGUAGE.into()),
+    ));
+    let language_registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
+    language_registry.add(Arc::new(markdown_lang()));
+    language_registry.add(Arc::new(markdown_inline_lang()));
+    language_registry.add(python_language);
+
+    cx.new(|cx| {
+        let (text, ranges) = marked_text_ranges(
+            &"
+                # Example
+
+                ```py
+                if ready:ˇ
+                ```
+            "
+            .unindent(),
+            false,
+        );
+
+        let mut buffer = Buffer::local(text, cx);
+        buffer.set_language_registry(language_registry.clone());
+        buffer.set_language(
+            language_registry
+                .language_for_name("Markdown")
+                .now_or_never()
+                .unwrap()
+                .ok(),
+            cx,
+        );
+        buffer.edit(
+            ranges.into_iter().map(|range| (range, "\nstart()")),
+            Some(AutoindentMode::EachLine),
+            cx,
+        );
+        assert_eq!(
+            buffer.text(),
+            "
+                # Example
+
+                ```py
+                if ready:
+                    start()
+                ```
+            "
+            .unindent()
+        );
+        buffer
+    });
+}
+
 #[gpui::test]
 fn test_autoindent_query_with_outdent_captures(cx: &mut App) {
     init_settings(cx, |settings| {
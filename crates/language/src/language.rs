@@ -26,7 +26,7 @@ use crate::language_settings::SoftWrap;
 pub use crate::language_settings::{EditPredictionsMode, IndentGuideSettings};
 use anyhow::{Context as _, Result};
 use async_trait::async_trait;
-use collections::{HashMap, HashSet, IndexSet};
+use collections::{BTreeMap, HashMap, HashSet, IndexSet};
 use futures::Future;
 use gpui::{App, AsyncApp, Entity, SharedString};
 pub use highlight_map::HighlightMap;
@@ -54,7 +54,7 @@ use std::{
     pin::Pin,
     str,
     sync::{
-        Arc, LazyLock,
+        Arc, LazyLock, OnceLock,
         atomic::{AtomicUsize, Ordering::SeqCst},
     },
 };
@@ -62,7 +62,8 @@ use syntax_map::{QueryCursorHandle, SyntaxSnapshot};
 use task::RunnableTag;
 pub use task_context::{ContextLocation, ContextProvider, RunnableRange};
 pub use text_diff::{
-    DiffOptions, apply_diff_patch, line_diff, text_diff, text_diff_with_options, unified_diff,
+    DiffOptions, apply_diff_patch, line_diff, text_diff, text_diff_with_options, three_way_merge,
+    unified_diff, word_diff,
 };
 use theme::SyntaxTheme;
 pub use toolchain::{
@@ -85,7 +86,7 @@ pub use outline::*;
 pub use syntax_map::{
     OwnedSyntaxLayer, SyntaxLayer, SyntaxMapMatches, ToTreeSitterPoint, TreeSitterOptions,
 };
-pub use text::{AnchorRangeExt, LineEnding};
+pub use text::{AnchorRangeExt, Encoding, LineEnding};
 pub use tree_sitter::{Node, Parser, Tree, TreeCursor};
 
 /// Initializes the `language` crate.
@@ -767,6 +768,10 @@ pub struct LanguageConfig {
     /// When set, selections can be wrapped using prefix/suffix pairs on both sides.
     #[serde(default)]
     pub wrap_characters: Option<WrapCharactersConfig>,
+    /// Markers delimiting foldable regions, such as `#pragma region` and `#pragma endregion`.
+    /// When empty, `#region` and `#endregion` are recognized after any of the `line_comments`.
+    #[serde(default)]
+    pub fold_region_markers: Vec<FoldRegionMarkers>,
     /// The name of a Prettier parser that will be used for this language when no file path is available.
     /// If there's a parser name in the language settings, that will be used instead.
     #[serde(default)]
@@ -796,6 +801,10 @@ pub struct LanguageConfig {
     #[serde(default, deserialize_with = "deserialize_regex")]
     #[schemars(schema_with = "regex_json_schema")]
     pub import_path_strip_regex: Option<Regex>,
+    /// Templates for postfix completions, keyed by the name typed after the dot following an expression.
+    /// `${receiver}` is replaced by the expression, the rest of the template uses snippet syntax.
+    #[serde(default)]
+    pub postfix_templates: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Default, JsonSchema)]
@@ -822,7 +831,8 @@ pub struct LanguageMatcher {
     pub first_line_pattern: Option<Regex>,
 }
 
-/// The configuration for JSX tag auto-closing.
+/// The configuration for JSX tag auto-closing, which is also used to rename the closing tag along
+/// with the opening tag when no language server provides linked editing ranges.
 #[derive(Clone, Deserialize, JsonSchema)]
 pub struct JsxTagAutoCloseConfig {
     /// The name of the node for a opening tag
@@ -853,6 +863,9 @@ pub struct JsxTagAutoCloseConfig {
     /// normal tag name node name
     #[serde(default)]
     pub erroneous_close_tag_name_node_name: Option<String>,
+    /// Names of tags that never have a closing tag, such as `br` in HTML
+    #[serde(default)]
+    pub void_tag_names: Vec<String>,
 }
 
 /// The configuration for block comments for this language.
@@ -986,6 +999,7 @@ impl Default for LanguageConfig {
             tab_size: None,
             soft_wrap: None,
             wrap_characters: None,
+            fold_region_markers: Default::default(),
             prettier_parser_name: None,
             hidden: false,
             jsx_tag_auto_close: None,
@@ -994,10 +1008,41 @@ impl Default for LanguageConfig {
             debuggers: Default::default(),
             ignored_import_segments: Default::default(),
             import_path_strip_regex: None,
+            postfix_templates: Default::default(),
         }
     }
 }
 
+/// Text that starts and ends a foldable region, matched at the start of a line after its
+/// indentation. The rest of the start line is the region's name.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, JsonSchema)]
+pub struct FoldRegionMarkers {
+    pub start: Arc<str>,
+    pub end: Arc<str>,
+}
+
+impl FoldRegionMarkers {
+    /// The default markers, `#region` and `#endregion` after any of the line comment prefixes.
+    fn for_line_comments(line_comments: &[Arc<str>]) -> Vec<Self> {
+        line_comments
+            .iter()
+            .flat_map(|prefix| {
+                let prefix = prefix.trim_end();
+                [
+                    FoldRegionMarkers {
+                        start: format!("{prefix} #region").into(),
+                        end: format!("{prefix} #endregion").into(),
+                    },
+                    FoldRegionMarkers {
+                        start: format!("{prefix}#region").into(),
+                        end: format!("{prefix}#endregion").into(),
+                    },
+                ]
+            })
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, JsonSchema)]
 pub struct WrapCharactersConfig {
     /// Opening token split into a prefix and suffix. The first caret goes
@@ -1157,6 +1202,9 @@ pub struct Language {
     pub(crate) context_provider: Option<Arc<dyn ContextProvider>>,
     pub(crate) toolchain: Option<Arc<dyn ToolchainLister>>,
     pub(crate) manifest_name: Option<ManifestName>,
+    /// The markers of foldable regions, built on first use from the configured markers or the
+    /// line comment prefixes.
+    fold_region_markers: OnceLock<Arc<[FoldRegionMarkers]>>,
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
@@ -1177,6 +1225,7 @@ pub struct Grammar {
     pub(crate) redactions_config: Option<RedactionConfig>,
     pub(crate) runnable_config: Option<RunnableConfig>,
     pub(crate) indents_config: Option<IndentConfig>,
+    pub(crate) folds_config: Option<FoldsConfig>,
     pub outline_config: Option<OutlineConfig>,
     pub text_object_config: Option<TextObjectConfig>,
     pub embedding_config: Option<EmbeddingConfig>,
@@ -1201,6 +1250,11 @@ struct IndentConfig {
     suffixed_start_captures: HashMap<u32, SharedString>,
 }
 
+struct FoldsConfig {
+    query: Query,
+    fold_capture_ix: u32,
+}
+
 pub struct OutlineConfig {
     pub query: Query,
     pub item_capture_ix: u32,
@@ -1373,6 +1427,7 @@ impl Language {
                     text_object_config: None,
                     embedding_config: None,
                     indents_config: None,
+                    folds_config: None,
                     injection_config: None,
                     override_config: None,
                     redactions_config: None,
@@ -1387,6 +1442,7 @@ impl Language {
             context_provider: None,
             toolchain: None,
             manifest_name: None,
+            fold_region_markers: OnceLock::new(),
         }
     }
 
@@ -1421,6 +1477,11 @@ impl Language {
                 .with_indents_query(query.as_ref())
                 .context("Error loading indents query")?;
         }
+        if let Some(query) = queries.folds {
+            self = self
+                .with_folds_query(query.as_ref())
+                .context("Error loading folds query")?;
+        }
         if let Some(query) = queries.outline {
             self = self
                 .with_outline_query(query.as_ref())
@@ -1745,6 +1806,24 @@ impl Language {
         Ok(self)
     }
 
+    pub fn with_folds_query(mut self, source: &str) -> Result<Self> {
+        let query = Query::new(&self.expect_grammar()?.ts_language, source)?;
+        let mut fold_capture_ix = 0;
+        if populate_capture_indices(
+            &query,
+            &self.config.name,
+            "folds",
+            &[],
+            &mut [Capture::Required("fold", &mut fold_capture_ix)],
+        ) {
+            self.grammar_mut()?.folds_config = Some(FoldsConfig {
+                query,
+                fold_capture_ix,
+            });
+        }
+        Ok(self)
+    }
+
     pub fn with_injection_query(mut self, source: &str) -> Result<Self> {
         let query = Query::new(&self.expect_grammar()?.ts_language, source)?;
         let mut language_capture_ix = None;
@@ -2044,6 +2123,28 @@ impl LanguageScope {
         .map_or([].as_slice(), |e| e.as_slice())
     }
 
+    /// Returns the markers delimiting foldable regions in this language.
+    pub fn fold_region_markers(&self) -> Arc<[FoldRegionMarkers]> {
+        let config = &self.language.config;
+        let line_comments = self.line_comment_prefixes();
+        // Scopes that override the line comments can't share the language's markers.
+        if config.fold_region_markers.is_empty()
+            && !std::ptr::eq(line_comments, config.line_comments.as_slice())
+        {
+            return FoldRegionMarkers::for_line_comments(line_comments).into();
+        }
+        self.language
+            .fold_region_markers
+            .get_or_init(|| {
+                if config.fold_region_markers.is_empty() {
+                    FoldRegionMarkers::for_line_comments(&config.line_comments).into()
+                } else {
+                    config.fold_region_markers.as_slice().into()
+                }
+            })
+            .clone()
+    }
+
     /// Config for block comments for this language.
     pub fn block_comment(&self) -> Option<&BlockCommentConfig> {
         Override::as_option(
@@ -222,6 +222,7 @@ pub const QUERY_FILENAME_PREFIXES: &[(
     ("brackets", |q| &mut q.brackets),
     ("outline", |q| &mut q.outline),
     ("indents", |q| &mut q.indents),
+    ("folds", |q| &mut q.folds),
     ("embedding", |q| &mut q.embedding),
     ("injections", |q| &mut q.injections),
     ("overrides", |q| &mut q.overrides),
@@ -238,6 +239,7 @@ pub struct LanguageQueries {
     pub highlights: Option<Cow<'static, str>>,
     pub brackets: Option<Cow<'static, str>>,
     pub indents: Option<Cow<'static, str>>,
+    pub folds: Option<Cow<'static, str>>,
     pub outline: Option<Cow<'static, str>>,
     pub embedding: Option<Cow<'static, str>>,
     pub injections: Option<Cow<'static, str>>,
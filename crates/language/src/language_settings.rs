@@ -1,23 +1,30 @@
 //! Provides `language`-related settings.
 
 use crate::{File, Language, LanguageName, LanguageServerName};
-use collections::{FxHashMap, HashMap, HashSet};
+use collections::{FxHashMap, HashMap, HashSet, IndexMap};
 use ec4rs::{
     Properties as EditorconfigProperties,
-    property::{FinalNewline, IndentSize, IndentStyle, MaxLineLen, TabWidth, TrimTrailingWs},
+    property::{
+        EndOfLine, FinalNewline, IndentSize, IndentStyle, MaxLineLen, TabWidth, TrimTrailingWs,
+    },
 };
 use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
-use gpui::{App, Modifiers, SharedString};
+use gpui::{App, FontFeatures, Modifiers, Pixels, SharedString, px};
 use itertools::{Either, Itertools};
 
+use regex::Regex;
 pub use settings::{
-    CompletionSettingsContent, EditPredictionProvider, EditPredictionsMode, FormatOnSave,
-    Formatter, FormatterList, InlayHintKind, LanguageSettingsContent, LspInsertMode,
+    AutoImportPlacement, CompletionSettingsContent, EditPredictionProvider, EditPredictionsMode,
+    FormatOnSave, Formatter, FormatterList, InlayHintKind, LanguageSettingsContent,
+    LineEndingSetting, LspInsertMode, LspSortTextMode, RemoveTrailingWhitespaceOnSave,
     RewrapBehavior, ShowWhitespaceSetting, SoftWrap, WordsCompletionMode,
 };
-use settings::{ExtendingVec, Settings, SettingsContent, SettingsLocation, SettingsStore};
+use settings::{
+    ExtendingVec, FontFamilyName, Settings, SettingsContent, SettingsLocation, SettingsStore,
+};
 use shellexpand;
-use std::{borrow::Cow, num::NonZeroU32, path::Path, sync::Arc};
+use std::{borrow::Cow, num::NonZeroU32, path::Path, sync::Arc, time::Duration};
+use util::ResultExt as _;
 
 /// Initializes the language settings.
 pub fn init(cx: &mut App) {
@@ -65,6 +72,41 @@ pub struct WhitespaceMap {
     pub tab: SharedString,
 }
 
+/// A rule highlighting the text that matches `regex` with a syntax theme style.
+#[derive(Debug, Clone)]
+pub struct HighlightRule {
+    pub regex: Regex,
+    /// The name of the syntax theme style to highlight matches with.
+    pub style: SharedString,
+    /// The paths, relative to their worktree, of the files that the rule applies to, or
+    /// `None` when it applies to all files.
+    pub paths: Option<GlobSet>,
+}
+
+impl HighlightRule {
+    fn from_content(content: settings::HighlightRuleContent) -> Option<Self> {
+        let paths = if content.paths.is_empty() {
+            None
+        } else {
+            let mut builder = GlobSetBuilder::new();
+            for path in &content.paths {
+                builder.add(Glob::new(path).log_err()?);
+            }
+            Some(builder.build().log_err()?)
+        };
+        Some(Self {
+            regex: Regex::new(&content.pattern).log_err()?,
+            style: content.style.into(),
+            paths,
+        })
+    }
+
+    /// Whether the rule applies to the file at `path`, relative to its worktree.
+    pub fn applies_to(&self, path: &Path) -> bool {
+        self.paths.as_ref().is_none_or(|paths| paths.is_match(path))
+    }
+}
+
 /// The settings for a particular language.
 #[derive(Debug, Clone)]
 pub struct LanguageSettings {
@@ -73,6 +115,11 @@ pub struct LanguageSettings {
     /// Whether to indent lines using tab characters, as opposed to multiple
     /// spaces.
     pub hard_tabs: bool,
+    /// Whether to lay out the cells that tabs terminate on adjacent lines to equal widths.
+    pub elastic_tabstops: bool,
+    /// Whether to infer `tab_size` and `hard_tabs` from the existing indentation
+    /// of a file when it's opened.
+    pub detect_indentation: bool,
     /// How to soft-wrap long lines of text.
     pub soft_wrap: settings::SoftWrap,
     /// The column at which to soft-wrap lines, for buffers where soft-wrap
@@ -85,16 +132,28 @@ pub struct LanguageSettings {
     pub show_wrap_guides: bool,
     /// Character counts at which to show wrap guides (vertical rulers) in the editor.
     pub wrap_guides: Vec<usize>,
+    /// Whether to highlight the text of lines that are longer than the
+    /// 'preferred_line_length', from that column to the end of the line.
+    pub highlight_line_length_overflow: bool,
+    /// Whether to insert a line break while typing past the 'preferred_line_length',
+    /// continuing the comment prefix or list bullet of the line on the new line.
+    pub auto_wrap_at_preferred_line_length: bool,
     /// Indent guide related settings.
     pub indent_guides: IndentGuideSettings,
     /// Whether or not to perform a buffer format before saving.
     pub format_on_save: FormatOnSave,
     /// Whether or not to remove any trailing whitespace from lines of a buffer
     /// before saving it.
-    pub remove_trailing_whitespace_on_save: bool,
+    pub remove_trailing_whitespace_on_save: RemoveTrailingWhitespaceOnSave,
     /// Whether or not to ensure there's a single newline at the end of a buffer
     /// when saving it.
     pub ensure_final_newline_on_save: bool,
+    /// The line ending that newly created files are saved with.
+    pub line_ending: LineEndingSetting,
+    /// Code action kinds to run through the language servers before saving, in order.
+    pub code_actions_on_save: IndexMap<String, bool>,
+    /// How long to wait for a language server to provide each of the `code_actions_on_save`.
+    pub code_actions_on_save_timeout: Duration,
     /// How to perform a buffer format.
     pub formatter: settings::FormatterList,
     /// Zed's Prettier integration settings.
@@ -121,10 +180,20 @@ pub struct LanguageSettings {
     /// Controls whether edit predictions are shown in the given language
     /// scopes.
     pub edit_predictions_disabled_in: Vec<String>,
+    /// The font family of buffers in this language, overriding the buffer font's family.
+    pub font_family: Option<FontFamilyName>,
+    /// The font features of buffers in this language, overriding the buffer font's features.
+    pub font_features: Option<FontFeatures>,
+    /// The font size of buffers in this language, overriding the buffer font size.
+    pub font_size: Option<Pixels>,
     /// Whether to show tabs and spaces in the editor.
     pub show_whitespaces: settings::ShowWhitespaceSetting,
     /// Visible characters used to render whitespace when show_whitespaces is enabled.
     pub whitespace_map: WhitespaceMap,
+    /// Whether to highlight whitespace at the end of lines.
+    pub highlight_trailing_whitespace: bool,
+    /// Rules highlighting the text that matches a regex with a syntax theme style.
+    pub highlight_rules: Vec<HighlightRule>,
     /// Whether to start a new line with a comment when a previous line is a comment as well.
     pub extend_comment_on_newline: bool,
     /// Inlay hint related settings.
@@ -140,6 +209,10 @@ pub struct LanguageSettings {
     pub auto_indent: bool,
     /// Whether indentation of pasted content should be adjusted based on the context.
     pub auto_indent_on_paste: bool,
+    /// Whether lines moved up or down should be reindented to match the block they are moved into.
+    pub auto_indent_on_move_lines: bool,
+    /// Whether pasted content should be formatted by the language server's range formatting.
+    pub format_on_paste: bool,
     /// Controls how the editor handles the autoclosed characters.
     pub always_treat_brackets_as_autoclosed: bool,
     /// Whether to perform linked edits
@@ -183,6 +256,38 @@ pub struct CompletionSettings {
     ///
     /// Default: "replace_suffix"
     pub lsp_insert_mode: LspInsertMode,
+    /// Controls how the `sortText` of LSP completions weighs against how well they match the query.
+    ///
+    /// Default: "after_match_score"
+    pub lsp_sort_text: LspSortTextMode,
+    /// Whether to rank deprecated completions below the others.
+    ///
+    /// Default: false
+    pub deprioritize_deprecated: bool,
+    /// Whether to rank completions that start with the query above fuzzy matches.
+    ///
+    /// Default: false
+    pub prefer_prefix_matches: bool,
+    /// Whether to apply the additional edits, mostly imports, that language servers attach to completions.
+    ///
+    /// Default: true
+    pub auto_imports: bool,
+    /// Where the import statements added by completions are placed.
+    ///
+    /// Default: "merge"
+    pub auto_import_placement: AutoImportPlacement,
+    /// Whether to show a notification, with an option to undo it, when a completion adds an import.
+    ///
+    /// Default: false
+    pub auto_import_notification: bool,
+    /// Whether to offer postfix completions, such as `expr.if`.
+    ///
+    /// Default: true
+    pub postfix: bool,
+    /// Postfix templates overriding the ones built into the language, an empty template disabling one.
+    ///
+    /// Default: {}
+    pub postfix_templates: HashMap<String, String>,
 }
 
 /// The settings for indent guides.
@@ -377,6 +482,8 @@ pub struct EditPredictionSettings {
     pub copilot: CopilotSettings,
     /// Settings specific to Codestral.
     pub codestral: CodestralSettings,
+    /// The name of the registered inline completion source used by the `custom` provider.
+    pub custom_provider: Option<String>,
     /// Whether edit predictions are enabled in the assistant panel.
     /// This setting has no effect if globally disabled.
     pub enabled_in_text_threads: bool,
@@ -486,9 +593,15 @@ fn merge_with_editorconfig(settings: &mut LanguageSettings, cfg: &EditorconfigPr
     let remove_trailing_whitespace_on_save = cfg
         .get::<TrimTrailingWs>()
         .map(|v| match v {
-            TrimTrailingWs::Value(b) => b,
+            TrimTrailingWs::Value(true) => RemoveTrailingWhitespaceOnSave::All,
+            TrimTrailingWs::Value(false) => RemoveTrailingWhitespaceOnSave::None,
         })
         .ok();
+    let line_ending = cfg.get::<EndOfLine>().ok().and_then(|v| match v {
+        EndOfLine::Lf => Some(LineEndingSetting::Lf),
+        EndOfLine::CrLf => Some(LineEndingSetting::Crlf),
+        EndOfLine::Cr => None,
+    });
     fn merge<T>(target: &mut T, value: Option<T>) {
         if let Some(value) = value {
             *target = value;
@@ -497,6 +610,10 @@ fn merge_with_editorconfig(settings: &mut LanguageSettings, cfg: &EditorconfigPr
     merge(&mut settings.preferred_line_length, preferred_line_length);
     merge(&mut settings.tab_size, tab_size);
     merge(&mut settings.hard_tabs, hard_tabs);
+    // Indentation configured in an `.editorconfig` file takes precedence over detection.
+    if tab_size.is_some() || hard_tabs.is_some() {
+        settings.detect_indentation = false;
+    }
     merge(
         &mut settings.remove_trailing_whitespace_on_save,
         remove_trailing_whitespace_on_save,
@@ -505,6 +622,7 @@ fn merge_with_editorconfig(settings: &mut LanguageSettings, cfg: &EditorconfigPr
         &mut settings.ensure_final_newline_on_save,
         ensure_final_newline_on_save,
     );
+    merge(&mut settings.line_ending, line_ending);
 }
 
 impl settings::Settings for AllLanguageSettings {
@@ -522,10 +640,16 @@ impl settings::Settings for AllLanguageSettings {
             LanguageSettings {
                 tab_size: settings.tab_size.unwrap(),
                 hard_tabs: settings.hard_tabs.unwrap(),
+                elastic_tabstops: settings.elastic_tabstops.unwrap(),
+                detect_indentation: settings.detect_indentation.unwrap(),
                 soft_wrap: settings.soft_wrap.unwrap(),
                 preferred_line_length: settings.preferred_line_length.unwrap(),
                 show_wrap_guides: settings.show_wrap_guides.unwrap(),
                 wrap_guides: settings.wrap_guides.unwrap(),
+                highlight_line_length_overflow: settings.highlight_line_length_overflow.unwrap(),
+                auto_wrap_at_preferred_line_length: settings
+                    .auto_wrap_at_preferred_line_length
+                    .unwrap(),
                 indent_guides: IndentGuideSettings {
                     enabled: indent_guides.enabled.unwrap(),
                     line_width: indent_guides.line_width.unwrap(),
@@ -538,6 +662,11 @@ impl settings::Settings for AllLanguageSettings {
                     .remove_trailing_whitespace_on_save
                     .unwrap(),
                 ensure_final_newline_on_save: settings.ensure_final_newline_on_save.unwrap(),
+                line_ending: settings.line_ending.unwrap(),
+                code_actions_on_save: settings.code_actions_on_save.unwrap(),
+                code_actions_on_save_timeout: Duration::from_millis(
+                    settings.code_actions_on_save_timeout_ms.unwrap(),
+                ),
                 formatter: settings.formatter.unwrap(),
                 prettier: PrettierSettings {
                     allowed: prettier.allowed.unwrap(),
@@ -551,11 +680,21 @@ impl settings::Settings for AllLanguageSettings {
                 allow_rewrap: settings.allow_rewrap.unwrap(),
                 show_edit_predictions: settings.show_edit_predictions.unwrap(),
                 edit_predictions_disabled_in: settings.edit_predictions_disabled_in.unwrap(),
+                font_family: settings.font_family,
+                font_features: settings.font_features,
+                font_size: settings.font_size.map(px),
                 show_whitespaces: settings.show_whitespaces.unwrap(),
                 whitespace_map: WhitespaceMap {
                     space: SharedString::new(whitespace_map.space.unwrap().to_string()),
                     tab: SharedString::new(whitespace_map.tab.unwrap().to_string()),
                 },
+                highlight_trailing_whitespace: settings.highlight_trailing_whitespace.unwrap(),
+                highlight_rules: settings
+                    .highlight_rules
+                    .unwrap()
+                    .into_iter()
+                    .filter_map(HighlightRule::from_content)
+                    .collect(),
                 extend_comment_on_newline: settings.extend_comment_on_newline.unwrap(),
                 inlay_hints: InlayHintSettings {
                     enabled: inlay_hints.enabled.unwrap(),
@@ -573,6 +712,8 @@ impl settings::Settings for AllLanguageSettings {
                 use_on_type_format: settings.use_on_type_format.unwrap(),
                 auto_indent: settings.auto_indent.unwrap(),
                 auto_indent_on_paste: settings.auto_indent_on_paste.unwrap(),
+                auto_indent_on_move_lines: settings.auto_indent_on_move_lines.unwrap(),
+                format_on_paste: settings.format_on_paste.unwrap(),
                 always_treat_brackets_as_autoclosed: settings
                     .always_treat_brackets_as_autoclosed
                     .unwrap(),
@@ -590,6 +731,14 @@ impl settings::Settings for AllLanguageSettings {
                     lsp: completions.lsp.unwrap(),
                     lsp_fetch_timeout_ms: completions.lsp_fetch_timeout_ms.unwrap(),
                     lsp_insert_mode: completions.lsp_insert_mode.unwrap(),
+                    lsp_sort_text: completions.lsp_sort_text.unwrap(),
+                    deprioritize_deprecated: completions.deprioritize_deprecated.unwrap(),
+                    prefer_prefix_matches: completions.prefer_prefix_matches.unwrap(),
+                    auto_imports: completions.auto_imports.unwrap(),
+                    auto_import_placement: completions.auto_import_placement.unwrap(),
+                    auto_import_notification: completions.auto_import_notification.unwrap(),
+                    postfix: completions.postfix.unwrap(),
+                    postfix_templates: completions.postfix_templates.unwrap(),
                 },
                 debuggers: settings.debuggers.unwrap(),
             }
@@ -669,6 +818,7 @@ impl settings::Settings for AllLanguageSettings {
                 mode: edit_predictions_mode,
                 copilot: copilot_settings,
                 codestral: codestral_settings,
+                custom_provider: edit_predictions.custom_provider,
                 enabled_in_text_threads,
             },
             defaults: default_language_settings,
@@ -689,6 +839,9 @@ impl settings::Settings for AllLanguageSettings {
         if let Some(v) = vscode.read_bool("editor.insertSpaces") {
             d.hard_tabs = Some(!v);
         }
+        if let Some(v) = vscode.read_bool("editor.detectIndentation") {
+            d.detect_indentation = Some(v);
+        }
 
         vscode.enum_setting("editor.wordWrap", &mut d.soft_wrap, |s| match s {
             "on" => Some(SoftWrap::EditorWidth),
@@ -717,14 +870,23 @@ impl settings::Settings for AllLanguageSettings {
                 FormatOnSave::Off
             });
         }
-        vscode.bool_setting(
-            "editor.trimAutoWhitespace",
-            &mut d.remove_trailing_whitespace_on_save,
-        );
+        if let Some(b) = vscode.read_bool("editor.trimAutoWhitespace") {
+            d.remove_trailing_whitespace_on_save = Some(if b {
+                RemoveTrailingWhitespaceOnSave::All
+            } else {
+                RemoveTrailingWhitespaceOnSave::None
+            });
+        }
         vscode.bool_setting(
             "files.insertFinalNewline",
             &mut d.ensure_final_newline_on_save,
         );
+        vscode.enum_setting("files.eol", &mut d.line_ending, |s| match s {
+            "\n" => Some(LineEndingSetting::Lf),
+            "\r\n" => Some(LineEndingSetting::Crlf),
+            "auto" => Some(LineEndingSetting::PlatformDefault),
+            _ => None,
+        });
         vscode.bool_setting("editor.inlineSuggest.enabled", &mut d.show_edit_predictions);
         vscode.enum_setting("editor.renderWhitespace", &mut d.show_whitespaces, |s| {
             Some(match s {
@@ -747,6 +909,7 @@ impl settings::Settings for AllLanguageSettings {
         vscode.bool_setting("editor.formatOnType", &mut d.use_on_type_format);
         vscode.bool_setting("editor.linkedEditing", &mut d.linked_edits);
         vscode.bool_setting("editor.formatOnPaste", &mut d.auto_indent_on_paste);
+        vscode.bool_setting("editor.formatOnPaste", &mut d.format_on_paste);
         vscode.bool_setting(
             "editor.suggestOnTriggerCharacters",
             &mut d.show_completions_on_input,
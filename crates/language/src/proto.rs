@@ -29,6 +29,34 @@ pub fn serialize_line_ending(message: text::LineEnding) -> proto::LineEnding {
     }
 }
 
+/// Deserializes a [`text::Encoding`] from the RPC representation.
+pub fn deserialize_encoding(message: proto::Encoding) -> text::Encoding {
+    match message {
+        proto::Encoding::Utf8 => text::Encoding::Utf8,
+        proto::Encoding::Utf16Le => text::Encoding::Utf16Le,
+        proto::Encoding::Utf16Be => text::Encoding::Utf16Be,
+        proto::Encoding::Utf16LeWithoutBom => text::Encoding::Utf16LeWithoutBom,
+        proto::Encoding::Utf16BeWithoutBom => text::Encoding::Utf16BeWithoutBom,
+        proto::Encoding::Latin1 => text::Encoding::Latin1,
+        proto::Encoding::Windows1252 => text::Encoding::Windows1252,
+        proto::Encoding::ShiftJis => text::Encoding::ShiftJis,
+    }
+}
+
+/// Serializes a [`text::Encoding`] to be sent over RPC.
+pub fn serialize_encoding(encoding: text::Encoding) -> proto::Encoding {
+    match encoding {
+        text::Encoding::Utf8 => proto::Encoding::Utf8,
+        text::Encoding::Utf16Le => proto::Encoding::Utf16Le,
+        text::Encoding::Utf16Be => proto::Encoding::Utf16Be,
+        text::Encoding::Utf16LeWithoutBom => proto::Encoding::Utf16LeWithoutBom,
+        text::Encoding::Utf16BeWithoutBom => proto::Encoding::Utf16BeWithoutBom,
+        text::Encoding::Latin1 => proto::Encoding::Latin1,
+        text::Encoding::Windows1252 => proto::Encoding::Windows1252,
+        text::Encoding::ShiftJis => proto::Encoding::ShiftJis,
+    }
+}
+
 /// Serializes a [`crate::Operation`] to be sent over RPC.
 pub fn serialize_operation(operation: &crate::Operation) -> proto::Operation {
     proto::Operation {
@@ -99,6 +127,15 @@ pub fn serialize_operation(operation: &crate::Operation) -> proto::Operation {
                 lamport_timestamp: lamport_timestamp.value,
                 line_ending: serialize_line_ending(*line_ending) as i32,
             }),
+
+            crate::Operation::UpdateEncoding {
+                encoding,
+                lamport_timestamp,
+            } => proto::operation::Variant::UpdateEncoding(proto::operation::UpdateEncoding {
+                replica_id: lamport_timestamp.replica_id as u32,
+                lamport_timestamp: lamport_timestamp.value,
+                encoding: serialize_encoding(*encoding) as i32,
+            }),
         }),
     }
 }
@@ -362,6 +399,17 @@ pub fn deserialize_operation(message: proto::Operation) -> Result<crate::Operati
                     ),
                 }
             }
+            proto::operation::Variant::UpdateEncoding(message) => {
+                crate::Operation::UpdateEncoding {
+                    lamport_timestamp: clock::Lamport {
+                        replica_id: message.replica_id as ReplicaId,
+                        value: message.lamport_timestamp,
+                    },
+                    encoding: deserialize_encoding(
+                        proto::Encoding::from_i32(message.encoding).context("missing encoding")?,
+                    ),
+                }
+            }
         },
     )
 }
@@ -521,6 +569,10 @@ pub fn lamport_timestamp_for_operation(operation: &proto::Operation) -> Option<c
             replica_id = op.replica_id;
             value = op.lamport_timestamp;
         }
+        proto::operation::Variant::UpdateEncoding(op) => {
+            replica_id = op.replica_id;
+            value = op.lamport_timestamp;
+        }
     }
 
     Some(clock::Lamport {
@@ -33,6 +33,9 @@ pub struct SyntaxSnapshot {
     interpolated_version: clock::Global,
     language_registry_version: usize,
     update_count: usize,
+    /// Injected layers that were edited but not reparsed, because they were outside of the
+    /// ranges prioritized by [`SyntaxSnapshot::reparse_prioritized`].
+    deferred_ranges: Vec<Range<Anchor>>,
 }
 
 #[derive(Default)]
@@ -272,6 +275,7 @@ impl SyntaxSnapshot {
             interpolated_version: clock::Global::default(),
             language_registry_version: 0,
             update_count: 0,
+            deferred_ranges: Vec::new(),
         }
     }
 
@@ -408,11 +412,41 @@ impl SyntaxSnapshot {
         registry: Option<Arc<LanguageRegistry>>,
         root_language: Arc<Language>,
     ) {
-        let edit_ranges = text
+        self.reparse_prioritized(text, registry, root_language, &[]);
+    }
+
+    /// Reparses the buffer after an edit, but only reparses the injected layers that intersect
+    /// `priority_ranges`, such as the ranges shown in editors. The other edited layers keep their
+    /// interpolated trees until the next reparse, which parses them regardless of priority once
+    /// there are no new edits, as reported by [`Self::has_deferred_ranges`].
+    pub fn reparse_prioritized(
+        &mut self,
+        text: &BufferSnapshot,
+        registry: Option<Arc<LanguageRegistry>>,
+        root_language: Arc<Language>,
+        priority_ranges: &[Range<usize>],
+    ) {
+        let mut edit_ranges = text
             .edits_since::<usize>(&self.parsed_version)
             .map(|edit| edit.new)
             .collect::<Vec<_>>();
-        self.reparse_with_ranges(text, root_language.clone(), edit_ranges, registry.as_ref());
+        let priority_ranges =
+            (!edit_ranges.is_empty() && !priority_ranges.is_empty()).then_some(priority_ranges);
+        if !self.deferred_ranges.is_empty() {
+            edit_ranges.extend(
+                self.deferred_ranges
+                    .drain(..)
+                    .map(|range| range.to_offset(text)),
+            );
+            edit_ranges.sort_unstable_by_key(|range| range.start);
+        }
+        self.reparse_with_ranges(
+            text,
+            root_language.clone(),
+            edit_ranges,
+            registry.as_ref(),
+            priority_ranges,
+        );
 
         if let Some(registry) = registry
             && registry.version() != self.language_registry_version
@@ -447,6 +481,7 @@ impl SyntaxSnapshot {
                     root_language,
                     resolved_injection_ranges,
                     Some(&registry),
+                    None,
                 );
             }
             self.language_registry_version = registry.version();
@@ -455,12 +490,17 @@ impl SyntaxSnapshot {
         self.update_count += 1;
     }
 
+    pub fn has_deferred_ranges(&self) -> bool {
+        !self.deferred_ranges.is_empty()
+    }
+
     fn reparse_with_ranges(
         &mut self,
         text: &BufferSnapshot,
         root_language: Arc<Language>,
         invalidated_ranges: Vec<Range<usize>>,
         registry: Option<&Arc<LanguageRegistry>>,
+        priority_ranges: Option<&[Range<usize>]>,
     ) {
         log::trace!(
             "reparse. invalidated ranges:{:?}",
@@ -578,6 +618,19 @@ impl SyntaxSnapshot {
                 }
             }
 
+            if step.depth > 0
+                && let Some(priority_ranges) = priority_ranges
+                && !priority_ranges
+                    .iter()
+                    .any(|range| range.start <= step_end_byte && range.end >= step_start_byte)
+            {
+                if let Some(old_layer) = old_layer {
+                    layers.push(old_layer.clone(), text);
+                }
+                self.deferred_ranges.push(step.range);
+                continue;
+            }
+
             let content = match step.language {
                 ParseStepLanguage::Loaded { language } => {
                     let Some(grammar) = language.grammar() else {
@@ -178,6 +178,83 @@ fn test_syntax_map_layers_for_range(cx: &mut App) {
     );
 }
 
+#[gpui::test]
+fn test_reparse_prioritized(cx: &mut App) {
+    let registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
+    let language = Arc::new(rust_lang());
+    registry.add(language.clone());
+
+    let mut buffer = Buffer::new(
+        0,
+        BufferId::new(1).unwrap(),
+        r#"
+            fn a() {
+                vec![b];
+            }
+
+            fn c() {
+                vec![d];
+            }
+        "#
+        .unindent(),
+    );
+
+    let mut syntax_map = SyntaxMap::new(&buffer);
+    syntax_map.set_language_registry(registry.clone());
+    syntax_map.reparse(language.clone(), &buffer);
+
+    let edits = [
+        (range_for_text(&buffer, "b"), "b.e"),
+        (range_for_text(&buffer, "d"), "d.f"),
+    ];
+    buffer.edit(edits);
+    syntax_map.interpolate(&buffer);
+
+    // Only the injection on the first line of `a` is reparsed.
+    let visible_range =
+        buffer.point_to_offset(Point::new(1, 0))..buffer.point_to_offset(Point::new(2, 0));
+    syntax_map.snapshot.reparse_prioritized(
+        &buffer,
+        Some(registry.clone()),
+        language.clone(),
+        &[visible_range.clone()],
+    );
+    assert!(syntax_map.snapshot.has_deferred_ranges());
+    assert_layers_for_range(
+        &syntax_map,
+        &buffer,
+        Point::new(1, 9)..Point::new(1, 9),
+        &[
+            "...(function_item ...",
+            "...(array_expression (field_expression ...",
+        ],
+    );
+    assert_layers_for_range(
+        &syntax_map,
+        &buffer,
+        Point::new(5, 9)..Point::new(5, 9),
+        &[
+            "...(function_item ...",
+            "...(array_expression (identifier))...",
+        ],
+    );
+
+    // Without new edits, the deferred injections are reparsed regardless of priority.
+    syntax_map
+        .snapshot
+        .reparse_prioritized(&buffer, Some(registry), language, &[visible_range]);
+    assert!(!syntax_map.snapshot.has_deferred_ranges());
+    assert_layers_for_range(
+        &syntax_map,
+        &buffer,
+        Point::new(5, 9)..Point::new(5, 9),
+        &[
+            "...(function_item ...",
+            "...(array_expression (field_expression ...",
+        ],
+    );
+}
+
 #[gpui::test]
 fn test_dynamic_language_injection(cx: &mut App) {
     let registry = Arc::new(LanguageRegistry::test(cx.background_executor().clone()));
@@ -120,12 +120,121 @@ pub fn text_diff_with_options(
     edits
 }
 
+/// Computes a word-level diff between two strings, returning the byte ranges of the words that
+/// were removed from `old_text` and of the words that were inserted into `new_text`.
+pub fn word_diff(old_text: &str, new_text: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let mut old_changes = Vec::new();
+    let mut new_changes = Vec::new();
+    let mut input = InternedInput::default();
+    input.update_before(tokenize(old_text, None));
+    input.update_after(tokenize(new_text, None));
+    diff_internal(&input, |old_byte_range, new_byte_range, _, _| {
+        if !old_byte_range.is_empty() {
+            old_changes.push(old_byte_range);
+        }
+        if !new_byte_range.is_empty() {
+            new_changes.push(new_byte_range);
+        }
+    });
+    (old_changes, new_changes)
+}
+
 pub fn apply_diff_patch(base_text: &str, patch: &str) -> Result<String, anyhow::Error> {
     let patch = diffy::Patch::from_str(patch).context("Failed to parse patch")?;
     let result = diffy::apply(base_text, &patch);
     result.map_err(|err| anyhow!(err))
 }
 
+/// Merges the changes that `ours` and `theirs` each made to `base`, line by line.
+///
+/// Where both sides changed the same lines differently, both versions are kept between git-style
+/// conflict markers, which are labelled with `labels` for ours, the base and theirs. Returns the
+/// merged text and whether it has any conflicts.
+pub fn three_way_merge(base: &str, ours: &str, theirs: &str, labels: [&str; 3]) -> (String, bool) {
+    let base_lines = base.split_inclusive('\n').collect::<Vec<_>>();
+    let our_lines = ours.split_inclusive('\n').collect::<Vec<_>>();
+    let their_lines = theirs.split_inclusive('\n').collect::<Vec<_>>();
+
+    let mut hunks = line_diff(base, ours)
+        .into_iter()
+        .map(|(base_rows, rows)| (base_rows, rows, true))
+        .chain(
+            line_diff(base, theirs)
+                .into_iter()
+                .map(|(base_rows, rows)| (base_rows, rows, false)),
+        )
+        .collect::<Vec<_>>();
+    hunks.sort_by_key(|(base_rows, _, _)| (base_rows.start, base_rows.end));
+
+    let mut merged = String::with_capacity(ours.len().max(theirs.len()));
+    let mut has_conflicts = false;
+    let mut base_row = 0;
+    // How many more rows each side has than the base, before `base_row`.
+    let mut our_delta = 0;
+    let mut their_delta = 0;
+    let mut hunks = hunks.into_iter().peekable();
+    while let Some((base_rows, rows, is_ours)) = hunks.next() {
+        // Hunks from both sides that overlap or touch are merged as one region.
+        let start = base_rows.start as usize;
+        let mut end = base_rows.end as usize;
+        let mut region_deltas = [0, 0];
+        let mut changed = [false, false];
+        let mut add_hunk = |base_rows: Range<u32>, rows: Range<u32>, is_ours: bool| {
+            let side = if is_ours { 0 } else { 1 };
+            region_deltas[side] += rows.len() as isize - base_rows.len() as isize;
+            changed[side] = true;
+        };
+        add_hunk(base_rows, rows, is_ours);
+        while let Some((base_rows, rows, is_ours)) =
+            hunks.next_if(|(base_rows, _, _)| base_rows.start as usize <= end)
+        {
+            end = end.max(base_rows.end as usize);
+            add_hunk(base_rows, rows, is_ours);
+        }
+
+        merged.extend(base_lines[base_row..start].iter().copied());
+        let our_rows = (start as isize + our_delta) as usize
+            ..(end as isize + our_delta + region_deltas[0]) as usize;
+        let their_rows = (start as isize + their_delta) as usize
+            ..(end as isize + their_delta + region_deltas[1]) as usize;
+        let our_region = &our_lines[our_rows];
+        let their_region = &their_lines[their_rows];
+        if !changed[1] || our_region == their_region {
+            merged.extend(our_region.iter().copied());
+        } else if !changed[0] {
+            merged.extend(their_region.iter().copied());
+        } else {
+            has_conflicts = true;
+            let sections = [
+                ("<<<<<<< ", labels[0], our_region),
+                ("||||||| ", labels[1], &base_lines[start..end]),
+                ("=======", "", their_region),
+            ];
+            for (marker, label, lines) in sections {
+                push_conflict_marker(&mut merged, marker, label);
+                merged.extend(lines.iter().copied());
+            }
+            push_conflict_marker(&mut merged, ">>>>>>> ", labels[2]);
+        }
+
+        our_delta += region_deltas[0];
+        their_delta += region_deltas[1];
+        base_row = end;
+    }
+    merged.extend(base_lines[base_row..].iter().copied());
+
+    (merged, has_conflicts)
+}
+
+fn push_conflict_marker(text: &mut String, marker: &str, label: &str) {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text.push_str(marker);
+    text.push_str(label);
+    text.push('\n');
+}
+
 fn should_perform_word_diff_within_hunk(
     old_row_range: &Range<u32>,
     old_byte_range: &Range<usize>,
@@ -279,6 +388,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_word_diff() {
+        assert_eq!(
+            word_diff("let x = foo(1);\n", "let y = foo(1, 2);\n"),
+            (vec![4..5], vec![4..5, 13..17])
+        );
+        assert_eq!(word_diff("same", "same"), (Vec::new(), Vec::new()));
+    }
+
     #[test]
     fn test_apply_diff_patch() {
         let old_text = "one two\nthree four five\nsix seven eight nine\nten\n";
@@ -286,4 +404,37 @@ mod tests {
         let patch = unified_diff(old_text, new_text);
         assert_eq!(apply_diff_patch(old_text, &patch).unwrap(), new_text);
     }
+
+    #[test]
+    fn test_three_way_merge() {
+        let labels = ["ours", "base", "theirs"];
+        let base = "one\ntwo\nthree\nfour\nfive\n";
+
+        // Changes to different lines are combined.
+        let ours = "ONE\ntwo\nthree\nfour\nfive\n";
+        let theirs = "one\ntwo\nthree\nFOUR\nfive\nsix\n";
+        assert_eq!(
+            three_way_merge(base, ours, theirs, labels),
+            ("ONE\ntwo\nthree\nFOUR\nfive\nsix\n".to_string(), false)
+        );
+
+        // The same change on both sides isn't a conflict.
+        let ours = "one\nTWO\nthree\nfour\nfive\n";
+        assert_eq!(
+            three_way_merge(base, ours, ours, labels),
+            (ours.to_string(), false)
+        );
+
+        let ours = "one\nTWO\nthree\nfour\nFIVE\n";
+        let theirs = "one\n2\nthree\nfour\nfive";
+        assert_eq!(
+            three_way_merge(base, ours, theirs, labels),
+            (
+                "one\n<<<<<<< ours\nTWO\n||||||| base\ntwo\n=======\n2\n>>>>>>> theirs\nthree\nfour\n\
+                 <<<<<<< ours\nFIVE\n||||||| base\nfive\n=======\nfive\n>>>>>>> theirs\n"
+                    .to_string(),
+                true
+            )
+        );
+    }
 }
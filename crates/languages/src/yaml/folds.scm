@@ -0,0 +1,7 @@
+[
+    (block_mapping_pair)
+    (block_sequence_item)
+    (flow_mapping)
+    (flow_sequence)
+    (block_scalar)
+] @fold
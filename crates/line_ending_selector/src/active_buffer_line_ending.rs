@@ -0,0 +1,122 @@
+use editor::Editor;
+use gpui::{
+    Context, Entity, IntoElement, ParentElement, Render, Subscription, WeakEntity, Window, div,
+};
+use language::{Buffer, LineEnding};
+use settings::Settings as _;
+use ui::{Button, ButtonCommon, Clickable, Color, FluentBuilder, LabelSize, Tooltip};
+use workspace::{StatusBarSettings, StatusItemView, Workspace, item::ItemHandle};
+
+use crate::{LineEndingSelector, Toggle};
+
+/// Shows the line ending of the active buffer in the status bar, and whether its file mixes line
+/// endings.
+pub struct ActiveBufferLineEnding {
+    active_line_ending: Option<(Entity<Buffer>, LineEnding, bool)>,
+    workspace: WeakEntity<Workspace>,
+    _observe_active_editor: Option<Subscription>,
+    _observe_active_buffer: Option<Subscription>,
+}
+
+impl ActiveBufferLineEnding {
+    pub fn new(workspace: &Workspace) -> Self {
+        Self {
+            active_line_ending: None,
+            workspace: workspace.weak_handle(),
+            _observe_active_editor: None,
+            _observe_active_buffer: None,
+        }
+    }
+
+    fn update_line_ending(
+        &mut self,
+        editor: Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let previous_buffer = self
+            .active_line_ending
+            .take()
+            .map(|(buffer, _, _)| buffer.entity_id());
+
+        if let Some((_, buffer, _)) = editor.read(cx).active_excerpt(cx) {
+            // The line ending changes on the buffer when it's converted, saved or reloaded.
+            if previous_buffer != Some(buffer.entity_id()) {
+                let editor = editor.clone();
+                self._observe_active_buffer =
+                    Some(cx.observe_in(&buffer, window, move |this, _, window, cx| {
+                        this.update_line_ending(editor.clone(), window, cx)
+                    }));
+            }
+            let line_ending = buffer.read(cx).line_ending();
+            let is_mixed = buffer.read(cx).has_mixed_line_endings();
+            self.active_line_ending = Some((buffer, line_ending, is_mixed));
+        }
+
+        cx.notify();
+    }
+}
+
+impl Render for ActiveBufferLineEnding {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if !StatusBarSettings::get_global(cx).line_ending_button {
+            return div();
+        }
+
+        div().when_some(
+            self.active_line_ending.clone(),
+            |el, (buffer, line_ending, is_mixed)| {
+                let (label, tooltip) = if is_mixed {
+                    (
+                        format!("Mixed ({})", line_ending.label()),
+                        format!("Mixed Line Endings, Saved as {}", line_ending.label()),
+                    )
+                } else {
+                    (
+                        line_ending.label().to_string(),
+                        "Select Line Ending".to_string(),
+                    )
+                };
+                el.child(
+                    Button::new("change-line-ending", label)
+                        .label_size(LabelSize::Small)
+                        .when(is_mixed, |button| button.color(Color::Warning))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            if let Some(workspace) = this.workspace.upgrade() {
+                                let buffer = buffer.clone();
+                                workspace.update(cx, |workspace, cx| {
+                                    LineEndingSelector::toggle_for_buffer(
+                                        workspace, buffer, window, cx,
+                                    )
+                                });
+                            }
+                        }))
+                        .tooltip(move |window, cx| {
+                            Tooltip::for_action(tooltip.clone(), &Toggle, window, cx)
+                        }),
+                )
+            },
+        )
+    }
+}
+
+impl StatusItemView for ActiveBufferLineEnding {
+    fn set_active_pane_item(
+        &mut self,
+        active_pane_item: Option<&dyn ItemHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(editor) = active_pane_item.and_then(|item| item.downcast::<Editor>()) {
+            self._observe_active_editor =
+                Some(cx.observe_in(&editor, window, Self::update_line_ending));
+            self.update_line_ending(editor, window, cx);
+        } else {
+            self.active_line_ending = None;
+            self._observe_active_editor = None;
+            self._observe_active_buffer = None;
+        }
+
+        cx.notify();
+    }
+}
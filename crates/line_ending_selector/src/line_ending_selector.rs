@@ -1,3 +1,5 @@
+mod active_buffer_line_ending;
+
 use editor::Editor;
 use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Task, WeakEntity, actions};
 use language::{Buffer, LineEnding};
@@ -6,13 +8,19 @@ use project::Project;
 use std::sync::Arc;
 use ui::{ListItem, ListItemSpacing, prelude::*};
 use util::ResultExt;
-use workspace::ModalView;
+use workspace::{ModalView, Workspace};
+
+pub use active_buffer_line_ending::ActiveBufferLineEnding;
 
 actions!(
     line_ending,
     [
         /// Toggles the line ending selector modal.
-        Toggle
+        Toggle,
+        /// Converts the active buffer's line endings to LF and saves it.
+        ConvertToLf,
+        /// Converts the active buffer's line endings to CRLF and saves it.
+        ConvertToCrlf
     ]
 );
 
@@ -28,8 +36,24 @@ impl LineEndingSelector {
     fn register(editor: &mut Editor, _window: Option<&mut Window>, cx: &mut Context<Editor>) {
         let editor_handle = cx.weak_entity();
         editor
-            .register_action(move |_: &Toggle, window, cx| {
-                Self::toggle(&editor_handle, window, cx);
+            .register_action({
+                let editor_handle = editor_handle.clone();
+                move |_: &Toggle, window, cx| {
+                    Self::toggle(&editor_handle, window, cx);
+                }
+            })
+            .detach();
+        editor
+            .register_action({
+                let editor_handle = editor_handle.clone();
+                move |_: &ConvertToLf, _, cx| {
+                    convert_line_endings(&editor_handle, LineEnding::Unix, cx);
+                }
+            })
+            .detach();
+        editor
+            .register_action(move |_: &ConvertToCrlf, _, cx| {
+                convert_line_endings(&editor_handle, LineEnding::Windows, cx);
             })
             .detach();
     }
@@ -46,13 +70,22 @@ impl LineEndingSelector {
         };
 
         workspace.update(cx, |workspace, cx| {
-            let project = workspace.project().clone();
-            workspace.toggle_modal(window, cx, move |window, cx| {
-                LineEndingSelector::new(buffer, project, window, cx)
-            });
+            Self::toggle_for_buffer(workspace, buffer, window, cx);
         })
     }
 
+    pub(crate) fn toggle_for_buffer(
+        workspace: &mut Workspace,
+        buffer: Entity<Buffer>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let project = workspace.project().clone();
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            LineEndingSelector::new(buffer, project, window, cx)
+        });
+    }
+
     fn new(
         buffer: Entity<Buffer>,
         project: Entity<Project>,
@@ -67,6 +100,39 @@ impl LineEndingSelector {
     }
 }
 
+fn convert_line_endings(editor: &WeakEntity<Editor>, line_ending: LineEnding, cx: &mut App) {
+    let Some((project, buffer)) = editor
+        .update(cx, |editor, cx| {
+            Some((editor.project()?.clone(), editor.active_excerpt(cx)?.1))
+        })
+        .ok()
+        .flatten()
+    else {
+        return;
+    };
+    set_line_ending(buffer, project, line_ending, cx);
+}
+
+/// Sets the buffer's line ending and saves its file, which writes every line with that line
+/// ending.
+fn set_line_ending(
+    buffer: Entity<Buffer>,
+    project: Entity<Project>,
+    line_ending: LineEnding,
+    cx: &mut App,
+) {
+    buffer.update(cx, |buffer, cx| {
+        buffer.set_line_ending(line_ending, cx);
+    });
+    if buffer.read(cx).file().is_some() {
+        cx.defer(move |cx| {
+            project.update(cx, |project, cx| {
+                project.save_buffer(buffer, cx).detach();
+            });
+        });
+    }
+}
+
 impl Render for LineEndingSelector {
     fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
         v_flex().w(rems(34.)).child(self.picker.clone())
@@ -122,16 +188,7 @@ impl PickerDelegate for LineEndingSelectorDelegate {
 
     fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
         if let Some(line_ending) = self.matches.get(self.selected_index) {
-            self.buffer.update(cx, |this, cx| {
-                this.set_line_ending(*line_ending, cx);
-            });
-            let buffer = self.buffer.clone();
-            let project = self.project.clone();
-            cx.defer(move |cx| {
-                project.update(cx, |this, cx| {
-                    this.save_buffer(buffer, cx).detach();
-                });
-            });
+            set_line_ending(self.buffer.clone(), self.project.clone(), *line_ending, cx);
         }
         self.dismissed(window, cx);
     }
@@ -172,16 +229,12 @@ impl PickerDelegate for LineEndingSelectorDelegate {
         _: &mut Context<Picker<Self>>,
     ) -> Option<Self::ListItem> {
         let line_ending = self.matches.get(ix)?;
-        let label = match line_ending {
-            LineEnding::Unix => "LF",
-            LineEnding::Windows => "CRLF",
-        };
 
         let mut list_item = ListItem::new(ix)
             .inset(true)
             .spacing(ListItemSpacing::Sparse)
             .toggle_state(selected)
-            .child(Label::new(label));
+            .child(Label::new(line_ending.label()));
 
         if &self.line_ending == line_ending {
             list_item = list_item.end_slot(Icon::new(IconName::Check).color(Color::Muted));
@@ -3,12 +3,13 @@ use std::time::Duration;
 use std::{ops::Range, path::PathBuf};
 
 use anyhow::Result;
-use editor::scroll::Autoscroll;
-use editor::{Editor, EditorEvent, SelectionEffects};
+use editor::display_map::{DisplayRow, DisplaySnapshot};
+use editor::scroll::{Autoscroll, ScrollAnchor};
+use editor::{Bias, DisplayPoint, Editor, EditorEvent, RowExt as _, SelectionEffects};
 use gpui::{
     App, ClickEvent, Context, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement,
-    IntoElement, IsZero, ListState, ParentElement, Render, RetainAllImageCache, Styled,
-    Subscription, Task, WeakEntity, Window, list,
+    IntoElement, IsZero, ListOffset, ListScrollEvent, ListState, ParentElement, Render,
+    RetainAllImageCache, Styled, Subscription, Task, WeakEntity, Window, list, point,
 };
 use language::LanguageRegistry;
 use settings::Settings;
@@ -36,6 +37,9 @@ pub struct MarkdownPreviewView {
     contents: Option<ParsedMarkdown>,
     selected_block: usize,
     list_state: ListState,
+    /// The scroll position that the editor was given to follow the preview, so that the editor
+    /// scrolling doesn't scroll the preview back.
+    synced_editor_scroll: Option<ScrollAnchor>,
     language_registry: Arc<LanguageRegistry>,
     parsing_markdown_task: Option<Task<Result<()>>>,
     mode: MarkdownPreviewMode,
@@ -203,6 +207,12 @@ impl MarkdownPreviewView {
     ) -> Entity<Self> {
         cx.new(|cx| {
             let list_state = ListState::new(0, gpui::ListAlignment::Top, px(1000.));
+            list_state.set_scroll_handler(cx.listener(|_, _: &ListScrollEvent, window, cx| {
+                // The list's state is borrowed while it calls this handler.
+                cx.defer_in(window, |this, window, cx| {
+                    this.sync_editor_scroll_to_preview(window, cx);
+                });
+            }));
 
             let mut this = Self {
                 selected_block: 0,
@@ -211,6 +221,7 @@ impl MarkdownPreviewView {
                 workspace: workspace.clone(),
                 contents: None,
                 list_state,
+                synced_editor_scroll: None,
                 language_registry,
                 parsing_markdown_task: None,
                 image_cache: RetainAllImageCache::new(cx),
@@ -284,6 +295,14 @@ impl MarkdownPreviewView {
                         this.list_state.scroll_to_reveal_item(this.selected_block);
                         cx.notify();
                     }
+                    // Scrolling caused by moving the cursor is handled above, by revealing the
+                    // block under the cursor.
+                    EditorEvent::ScrollPositionChanged {
+                        local: true,
+                        autoscroll: false,
+                    } => {
+                        this.sync_preview_scroll_to_editor(editor, cx);
+                    }
                     _ => {}
                 };
             },
@@ -369,6 +388,116 @@ impl MarkdownPreviewView {
         }
     }
 
+    /// Scrolls the preview to the block at the top of the editor, and as far into that block as
+    /// the editor is into the display rows of its source.
+    fn sync_preview_scroll_to_editor(&mut self, editor: &Entity<Editor>, cx: &mut Context<Self>) {
+        let scroll_anchor = editor.read(cx).scroll_manager.anchor();
+        if self.synced_editor_scroll.take() == Some(scroll_anchor) {
+            return;
+        }
+        let Some(contents) = &self.contents else {
+            return;
+        };
+
+        let display_snapshot = editor.update(cx, |editor, cx| {
+            editor.display_map.update(cx, |map, cx| map.snapshot(cx))
+        });
+        let scroll_top = scroll_anchor.scroll_position(&display_snapshot).y;
+        let top_offset = DisplayPoint::new(DisplayRow(scroll_top as u32), 0)
+            .to_offset(&display_snapshot, Bias::Left);
+        let block_ix = self.get_block_index_under_cursor(top_offset..top_offset);
+        let Some(source_range) = contents
+            .children
+            .get(block_ix)
+            .and_then(|block| block.source_range())
+        else {
+            return;
+        };
+        let progress = Self::source_scroll_progress(&display_snapshot, source_range, scroll_top);
+        let block_height = self
+            .list_state
+            .bounds_for_item(block_ix)
+            .map_or(px(0.), |bounds| bounds.size.height);
+
+        self.list_state.scroll_to(ListOffset {
+            item_ix: block_ix,
+            offset_in_item: block_height * progress as f32,
+        });
+        cx.notify();
+    }
+
+    /// Scrolls the editor to the source lines of the block at the top of the preview, and as far
+    /// into those lines as the preview is into the block.
+    fn sync_editor_scroll_to_preview(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(state), Some(contents)) = (&self.active_editor, &self.contents) else {
+            return;
+        };
+        let scroll_top = self.list_state.logical_scroll_top();
+        let Some(source_range) = contents
+            .children
+            .get(scroll_top.item_ix)
+            .and_then(|block| block.source_range())
+        else {
+            return;
+        };
+        let block_height = self
+            .list_state
+            .bounds_for_item(scroll_top.item_ix)
+            .map_or(px(0.), |bounds| bounds.size.height);
+        let progress = if block_height > px(0.) {
+            (scroll_top.offset_in_item / block_height).clamp(0., 1.)
+        } else {
+            0.
+        };
+
+        let scroll_anchor = state.editor.update(cx, |editor, cx| {
+            let display_snapshot = editor.display_map.update(cx, |map, cx| map.snapshot(cx));
+            let scroll_top =
+                Self::source_scroll_top(&display_snapshot, source_range, progress as f64);
+            let scroll_x = editor.scroll_position(cx).x;
+            editor.set_scroll_position(point(scroll_x, scroll_top), window, cx);
+            editor.scroll_manager.anchor()
+        });
+        self.synced_editor_scroll = Some(scroll_anchor);
+    }
+
+    /// How far the editor's scroll position is into the display rows of a block's source, from 0
+    /// to 1.
+    fn source_scroll_progress(
+        display_snapshot: &DisplaySnapshot,
+        source_range: Range<usize>,
+        scroll_top: f64,
+    ) -> f64 {
+        let rows = Self::source_display_rows(display_snapshot, source_range);
+        ((scroll_top - rows.start) / (rows.end - rows.start)).clamp(0., 1.)
+    }
+
+    /// The editor scroll position that is as far into the display rows of a block's source as
+    /// `progress`.
+    fn source_scroll_top(
+        display_snapshot: &DisplaySnapshot,
+        source_range: Range<usize>,
+        progress: f64,
+    ) -> f64 {
+        let rows = Self::source_display_rows(display_snapshot, source_range);
+        (rows.start + (rows.end - rows.start) * progress)
+            .min(display_snapshot.max_point().row().as_f64())
+    }
+
+    /// The display rows that the source of a block is laid out on, which soft wraps, folds and
+    /// blocks make differ from its buffer rows.
+    fn source_display_rows(
+        display_snapshot: &DisplaySnapshot,
+        source_range: Range<usize>,
+    ) -> Range<f64> {
+        let buffer = display_snapshot.buffer_snapshot();
+        let start = display_snapshot
+            .point_to_display_point(buffer.offset_to_point(source_range.start), Bias::Left);
+        let end = display_snapshot
+            .point_to_display_point(buffer.offset_to_point(source_range.end), Bias::Right);
+        start.row().as_f64()..end.row().as_f64() + 1.
+    }
+
     /// The absolute path of the file that is currently being previewed.
     fn get_folder_for_active_editor(editor: &Editor, cx: &App) -> Option<PathBuf> {
         if let Some(file) = editor.file_at(0, cx) {
@@ -601,3 +730,58 @@ impl Render for MarkdownPreviewView {
             .vertical_scrollbar_for(self.list_state.clone(), window, cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::{EditorMode, MultiBuffer};
+    use gpui::TestAppContext;
+    use language::Point;
+    use settings::SettingsStore;
+
+    #[gpui::test]
+    fn test_scroll_sync_in_display_rows(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            workspace::init_settings(cx);
+            editor::init(cx);
+        });
+
+        let text = "```\n1\n2\n3\n4\n5\n```\n\nfirst\nsecond\nthird\nfourth\n";
+        let window = cx.add_window(|window, cx| {
+            let buffer = MultiBuffer::build_simple(text, cx);
+            Editor::new(EditorMode::full(), buffer, None, window, cx)
+        });
+
+        // Folding the code block moves the paragraph below it up four display rows, to rows 4
+        // through 7, while it stays on buffer rows 8 through 11.
+        let paragraph = text.find("first").unwrap()..text.find("fourth\n").unwrap() + 6;
+        let display_snapshot = window
+            .update(cx, |editor, window, cx| {
+                editor.fold_ranges(vec![Point::new(1, 0)..Point::new(5, 1)], false, window, cx);
+                editor.display_map.update(cx, |map, cx| map.snapshot(cx))
+            })
+            .unwrap();
+
+        // The editor is half way into the paragraph at display row 6, which shows "third".
+        assert_eq!(
+            MarkdownPreviewView::source_scroll_progress(&display_snapshot, paragraph.clone(), 6.),
+            0.5
+        );
+        assert_eq!(
+            MarkdownPreviewView::source_scroll_progress(&display_snapshot, paragraph.clone(), 2.),
+            0.
+        );
+        assert_eq!(
+            MarkdownPreviewView::source_scroll_top(&display_snapshot, paragraph.clone(), 0.5),
+            6.
+        );
+        assert_eq!(
+            MarkdownPreviewView::source_scroll_top(&display_snapshot, paragraph, 0.),
+            4.
+        );
+    }
+}
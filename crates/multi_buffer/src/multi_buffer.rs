@@ -108,6 +108,10 @@ pub enum Event {
     Reloaded,
     LanguageChanged(BufferId),
     Reparsed(BufferId),
+    LargeFileModeChanged(BufferId),
+    EditsLockChanged(BufferId),
+    /// Edits to a buffer were dropped because the user locked it against edits.
+    LockedEditsDropped(BufferId),
     Saved,
     FileHandleChanged,
     DirtyChanged,
@@ -130,6 +134,10 @@ pub struct MultiBufferDiffHunk {
     pub diff_base_byte_range: Range<usize>,
     /// Whether or not this hunk also appears in the 'secondary diff'.
     pub secondary_status: DiffHunkSecondaryStatus,
+    /// The ranges of the words in the buffer that differ from the diff base.
+    pub buffer_word_diffs: Vec<Range<text::Anchor>>,
+    /// The ranges of the words in the diff base that differ from the buffer.
+    pub base_word_diffs: Vec<Range<usize>>,
 }
 
 impl MultiBufferDiffHunk {
@@ -829,9 +837,10 @@ impl MultiBuffer {
                 _ => Default::default(),
             };
 
-            let (buffer_edits, edited_excerpt_ids) =
+            let (mut buffer_edits, edited_excerpt_ids) =
                 this.convert_edits_to_buffer_edits(edits, &snapshot, &original_indent_columns);
             drop(snapshot);
+            this.retain_unlocked_buffer_edits(&mut buffer_edits, cx);
 
             let mut buffer_ids = Vec::with_capacity(buffer_edits.len());
             for (buffer_id, mut edits) in buffer_edits {
@@ -875,17 +884,9 @@ impl MultiBuffer {
 
                             if is_insertion {
                                 original_indent_columns.push(original_indent_column);
-                                insertions.push((
-                                    buffer.anchor_before(range.start)
-                                        ..buffer.anchor_before(range.end),
-                                    new_text.clone(),
-                                ));
+                                insertions.push((range, new_text.clone()));
                             } else if !range.is_empty() {
-                                deletions.push((
-                                    buffer.anchor_before(range.start)
-                                        ..buffer.anchor_before(range.end),
-                                    empty_str.clone(),
-                                ));
+                                deletions.push((range, empty_str.clone()));
                             }
                         }
 
@@ -906,8 +907,17 @@ impl MultiBuffer {
                                 autoindent_mode.clone()
                             };
 
-                        buffer.edit(deletions, deletion_autoindent_mode, cx);
-                        buffer.edit(insertions, insertion_autoindent_mode, cx);
+                        // Deletions only come from edits spanning several excerpts. Without
+                        // them, all of the insertions are applied in one pass at their offsets,
+                        // rather than anchoring each one and resolving it again.
+                        if deletions.is_empty() {
+                            buffer.edit(insertions, insertion_autoindent_mode, cx);
+                        } else {
+                            let deletions = anchor_buffer_edits(buffer, deletions);
+                            let insertions = anchor_buffer_edits(buffer, insertions);
+                            buffer.edit(deletions, deletion_autoindent_mode, cx);
+                            buffer.edit(insertions, insertion_autoindent_mode, cx);
+                        }
                     })
             }
 
@@ -916,6 +926,44 @@ impl MultiBuffer {
                 buffer_ids,
             });
         }
+
+        fn anchor_buffer_edits(
+            buffer: &Buffer,
+            edits: Vec<(Range<usize>, Arc<str>)>,
+        ) -> Vec<(Range<text::Anchor>, Arc<str>)> {
+            edits
+                .into_iter()
+                .map(|(range, new_text)| {
+                    (
+                        buffer.anchor_before(range.start)..buffer.anchor_before(range.end),
+                        new_text,
+                    )
+                })
+                .collect()
+        }
+    }
+
+    /// Drops the edits to buffers that the user locked against edits, emitting
+    /// [`Event::LockedEditsDropped`] for each of them so that the drop isn't silent.
+    fn retain_unlocked_buffer_edits(
+        &self,
+        buffer_edits: &mut HashMap<BufferId, Vec<BufferEdit>>,
+        cx: &mut Context<Self>,
+    ) {
+        let mut dropped_buffer_ids = Vec::new();
+        {
+            let buffers = self.buffers.borrow();
+            buffer_edits.retain(|buffer_id, _| {
+                let locked = buffers[buffer_id].buffer.read(cx).edits_locked();
+                if locked {
+                    dropped_buffer_ids.push(*buffer_id);
+                }
+                !locked
+            });
+        }
+        for buffer_id in dropped_buffer_ids {
+            cx.emit(Event::LockedEditsDropped(buffer_id));
+        }
     }
 
     fn convert_edits_to_buffer_edits(
@@ -1036,6 +1084,7 @@ impl MultiBuffer {
                 }
             }
         }
+        edited_excerpt_ids.dedup();
         (buffer_edits, edited_excerpt_ids)
     }
 
@@ -1069,9 +1118,10 @@ impl MultiBuffer {
                 return;
             }
 
-            let (buffer_edits, edited_excerpt_ids) =
+            let (mut buffer_edits, edited_excerpt_ids) =
                 this.convert_edits_to_buffer_edits(edits, &snapshot, &[]);
             drop(snapshot);
+            this.retain_unlocked_buffer_edits(&mut buffer_edits, cx);
 
             let mut buffer_ids = Vec::new();
             for (buffer_id, mut edits) in buffer_edits {
@@ -1507,8 +1557,8 @@ impl MultiBuffer {
     fn expand_excerpts_with_paths(
         &mut self,
         ids: impl IntoIterator<Item = ExcerptId>,
-        line_count: u32,
-        direction: ExpandExcerptDirection,
+        up_line_count: u32,
+        down_line_count: u32,
         cx: &mut Context<Self>,
     ) {
         let grouped = ids
@@ -1530,23 +1580,14 @@ impl MultiBuffer {
 
                 let mut context = excerpt.range.context.to_point(&excerpt.buffer);
                 if ids_to_expand.contains(excerpt_id) {
-                    match direction {
-                        ExpandExcerptDirection::Up => {
-                            context.start.row = context.start.row.saturating_sub(line_count);
-                            context.start.column = 0;
-                        }
-                        ExpandExcerptDirection::Down => {
-                            context.end.row =
-                                (context.end.row + line_count).min(excerpt.buffer.max_point().row);
-                            context.end.column = excerpt.buffer.line_len(context.end.row);
-                        }
-                        ExpandExcerptDirection::UpAndDown => {
-                            context.start.row = context.start.row.saturating_sub(line_count);
-                            context.start.column = 0;
-                            context.end.row =
-                                (context.end.row + line_count).min(excerpt.buffer.max_point().row);
-                            context.end.column = excerpt.buffer.line_len(context.end.row);
-                        }
+                    if up_line_count > 0 {
+                        context.start.row = context.start.row.saturating_sub(up_line_count);
+                        context.start.column = 0;
+                    }
+                    if down_line_count > 0 {
+                        context.end.row =
+                            (context.end.row + down_line_count).min(excerpt.buffer.max_point().row);
+                        context.end.column = excerpt.buffer.line_len(context.end.row);
                     }
                 }
 
@@ -2438,6 +2479,10 @@ impl MultiBuffer {
             BufferEvent::Reloaded => Event::Reloaded,
             BufferEvent::LanguageChanged => Event::LanguageChanged(buffer.read(cx).remote_id()),
             BufferEvent::Reparsed => Event::Reparsed(buffer.read(cx).remote_id()),
+            BufferEvent::LargeFileModeChanged => {
+                Event::LargeFileModeChanged(buffer.read(cx).remote_id())
+            }
+            BufferEvent::EditsLockChanged => Event::EditsLockChanged(buffer.read(cx).remote_id()),
             BufferEvent::DiagnosticsUpdated => Event::DiagnosticsUpdated,
             BufferEvent::CapabilityChanged => {
                 self.capability = buffer.read(cx).capability();
@@ -2568,7 +2613,9 @@ impl MultiBuffer {
             .and_then(|buffer_id| self.buffer(buffer_id))
             .map(|buffer| {
                 let buffer = buffer.read(cx);
-                language_settings(buffer.language().map(|l| l.name()), buffer.file(), cx)
+                let settings =
+                    language_settings(buffer.language().map(|l| l.name()), buffer.file(), cx);
+                buffer.indent_override().apply(settings)
             })
             .unwrap_or_else(move || self.language_settings_at(0, cx))
     }
@@ -2578,14 +2625,11 @@ impl MultiBuffer {
         point: T,
         cx: &'a App,
     ) -> Cow<'a, LanguageSettings> {
-        let mut language = None;
-        let mut file = None;
         if let Some((buffer, offset)) = self.point_to_buffer_offset(point, cx) {
-            let buffer = buffer.read(cx);
-            language = buffer.language_at(offset);
-            file = buffer.file();
+            buffer.read(cx).settings_at(offset, cx)
+        } else {
+            language_settings(None, None, cx)
         }
-        language_settings(language.map(|l| l.name()), file, cx)
     }
 
     pub fn for_each_buffer(&self, mut f: impl FnMut(&Entity<Buffer>)) {
@@ -2906,12 +2950,33 @@ impl MultiBuffer {
         direction: ExpandExcerptDirection,
         cx: &mut Context<Self>,
     ) {
-        if line_count == 0 {
+        let up_line_count = if direction.should_expand_up() {
+            line_count
+        } else {
+            0
+        };
+        let down_line_count = if direction.should_expand_down() {
+            line_count
+        } else {
+            0
+        };
+        self.expand_excerpts_by(ids, up_line_count, down_line_count, cx);
+    }
+
+    /// Expands the excerpts by a different number of lines above and below.
+    pub fn expand_excerpts_by(
+        &mut self,
+        ids: impl IntoIterator<Item = ExcerptId>,
+        up_line_count: u32,
+        down_line_count: u32,
+        cx: &mut Context<Self>,
+    ) {
+        if up_line_count == 0 && down_line_count == 0 {
             return;
         }
         self.sync(cx);
         if !self.excerpts_by_path.is_empty() {
-            self.expand_excerpts_with_paths(ids, line_count, direction, cx);
+            self.expand_excerpts_with_paths(ids, up_line_count, down_line_count, cx);
             return;
         }
         let mut snapshot = self.snapshot.borrow_mut();
@@ -2931,12 +2996,6 @@ impl MultiBuffer {
             let mut excerpt = cursor.item().unwrap().clone();
             let old_text_len = ExcerptOffset::new(excerpt.text_summary.len);
 
-            let up_line_count = if direction.should_expand_up() {
-                line_count
-            } else {
-                0
-            };
-
             let start_row = excerpt
                 .range
                 .context
@@ -2947,12 +3006,6 @@ impl MultiBuffer {
             let start_point = Point::new(start_row, 0);
             excerpt.range.context.start = excerpt.buffer.anchor_before(start_point);
 
-            let down_line_count = if direction.should_expand_down() {
-                line_count
-            } else {
-                0
-            };
-
             let mut end_point = excerpt.buffer.clip_point(
                 excerpt.range.context.end.to_point(&excerpt.buffer)
                     + Point::new(down_line_count, 0),
@@ -3861,6 +3914,8 @@ impl MultiBufferSnapshot {
                 buffer_range: hunk.buffer_range.clone(),
                 diff_base_byte_range: hunk.diff_base_byte_range.clone(),
                 secondary_status: hunk.secondary_status,
+                buffer_word_diffs: hunk.buffer_word_diffs.clone(),
+                base_word_diffs: hunk.base_word_diffs.clone(),
             })
         })
     }
@@ -5527,6 +5582,21 @@ impl MultiBufferSnapshot {
         )
     }
 
+    pub fn uses_syntax_folds(&self, row: MultiBufferRow) -> bool {
+        self.buffer_line_for_row(row)
+            .is_some_and(|(buffer, range)| buffer.uses_syntax_folds(range.start.row))
+    }
+
+    pub fn syntax_fold_range(&self, row: MultiBufferRow) -> Option<Range<Point>> {
+        let row_range = Point::new(row.0, 0)..Point::new(row.0, self.line_len(row));
+        self.lift_buffer_metadata(row_range, |buffer, range| {
+            let fold_range = buffer.syntax_fold_range(range.start.row)?;
+            Some(iter::once((fold_range, ())))
+        })
+        .map(|(range, _, _)| range)
+        .find(|range| range.start.row == row.0 && range.end.row > row.0)
+    }
+
     pub fn redacted_ranges<'a, T: ToOffset>(
         &'a self,
         range: Range<T>,
@@ -5811,10 +5881,9 @@ impl MultiBufferSnapshot {
             }
             let settings = &prev_settings
                 .get_or_insert_with(|| {
-                    (
-                        buffer.remote_id(),
-                        language_settings(buffer.language().map(|l| l.name()), buffer.file(), cx),
-                    )
+                    let settings =
+                        language_settings(buffer.language().map(|l| l.name()), buffer.file(), cx);
+                    (buffer.remote_id(), buffer.indent_override().apply(settings))
                 })
                 .1;
             let tab_size = settings.tab_size.get();
@@ -5910,11 +5979,12 @@ impl MultiBufferSnapshot {
             .first()
             .map(|excerpt| &excerpt.buffer)
             .map(|buffer| {
-                language_settings(
+                let settings = language_settings(
                     buffer.language().map(|language| language.name()),
                     buffer.file(),
                     cx,
-                )
+                );
+                buffer.indent_override().apply(settings)
             })
             .unwrap_or_else(move || self.language_settings_at(0, cx))
     }
@@ -5924,13 +5994,11 @@ impl MultiBufferSnapshot {
         point: T,
         cx: &'a App,
     ) -> Cow<'a, LanguageSettings> {
-        let mut language = None;
-        let mut file = None;
         if let Some((buffer, offset)) = self.point_to_buffer_offset(point) {
-            language = buffer.language_at(offset);
-            file = buffer.file();
+            buffer.settings_at(offset, cx)
+        } else {
+            language_settings(None, None, cx)
         }
-        language_settings(language.map(|l| l.name()), file, cx)
     }
 
     pub fn language_scope_at<T: ToOffset>(&self, point: T) -> Option<LanguageScope> {
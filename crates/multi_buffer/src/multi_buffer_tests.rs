@@ -704,6 +704,91 @@ fn test_excerpt_events(cx: &mut App) {
     assert_eq!(*follower_edit_event_count.read(), 4);
 }
 
+#[gpui::test]
+fn test_editing_many_ranges(cx: &mut App) {
+    let buffer = cx.new(|cx| Buffer::local("line\n".repeat(2000), cx));
+    let multibuffer = cx.new(|_| MultiBuffer::new(Capability::ReadWrite));
+    let edited_excerpts = Arc::new(RwLock::new(Vec::new()));
+    cx.subscribe(&multibuffer, {
+        let edited_excerpts = edited_excerpts.clone();
+        move |_, event, _| {
+            if let Event::ExcerptsEdited { excerpt_ids, .. } = event {
+                edited_excerpts.write().push(excerpt_ids.clone());
+            }
+        }
+    })
+    .detach();
+    let excerpt_ids = multibuffer.update(cx, |multibuffer, cx| {
+        multibuffer.push_excerpts(
+            buffer.clone(),
+            [
+                ExcerptRange::new(Point::new(0, 0)..Point::new(999, 4)),
+                ExcerptRange::new(Point::new(1000, 0)..Point::new(1999, 4)),
+            ],
+            cx,
+        )
+    });
+
+    // Insert at the start of every line, as when typing with a cursor on each of them.
+    multibuffer.update(cx, |multibuffer, cx| {
+        let snapshot = multibuffer.snapshot(cx);
+        let edits = (0..=snapshot.max_point().row)
+            .map(|row| {
+                let offset = Point::new(row, 0).to_offset(&snapshot);
+                (offset..offset, "> ")
+            })
+            .collect::<Vec<_>>();
+        multibuffer.edit(edits, None, cx);
+    });
+    assert_eq!(buffer.read(cx).text(), "> line\n".repeat(2000));
+    assert_eq!(edited_excerpts.read().as_slice(), [excerpt_ids.clone()]);
+
+    // Replace text spanning both excerpts, which leaves the newline between them.
+    multibuffer.update(cx, |multibuffer, cx| {
+        let snapshot = multibuffer.snapshot(cx);
+        let start = Point::new(999, 2).to_offset(&snapshot);
+        let end = Point::new(1000, 2).to_offset(&snapshot);
+        multibuffer.edit([(start..end, "joined ")], None, cx);
+    });
+    assert_eq!(
+        buffer.read(cx).text(),
+        format!(
+            "{}> joined \nline\n{}",
+            "> line\n".repeat(999),
+            "> line\n".repeat(999)
+        )
+    );
+}
+
+#[gpui::test]
+fn test_edits_to_locked_buffers_are_dropped(cx: &mut App) {
+    let buffer_1 = cx.new(|cx| Buffer::local("one", cx));
+    let buffer_2 = cx.new(|cx| Buffer::local("two", cx));
+    let multibuffer = cx.new(|_| MultiBuffer::new(Capability::ReadWrite));
+    let dropped = Arc::new(RwLock::new(Vec::new()));
+    cx.subscribe(&multibuffer, {
+        let dropped = dropped.clone();
+        move |_, event, _| {
+            if let Event::LockedEditsDropped(buffer_id) = event {
+                dropped.write().push(*buffer_id);
+            }
+        }
+    })
+    .detach();
+    multibuffer.update(cx, |multibuffer, cx| {
+        multibuffer.push_excerpts(buffer_1.clone(), [ExcerptRange::new(0..3)], cx);
+        multibuffer.push_excerpts(buffer_2.clone(), [ExcerptRange::new(0..3)], cx);
+    });
+    buffer_1.update(cx, |buffer, cx| buffer.set_edits_locked(true, cx));
+
+    multibuffer.update(cx, |multibuffer, cx| {
+        multibuffer.edit([(0..0, "1 "), (4..4, "2 ")], None, cx);
+    });
+    assert_eq!(buffer_1.read(cx).text(), "one");
+    assert_eq!(buffer_2.read(cx).text(), "2 two");
+    assert_eq!(dropped.read().as_slice(), [buffer_1.read(cx).remote_id()]);
+}
+
 #[gpui::test]
 fn test_expand_excerpts(cx: &mut App) {
     let buffer = cx.new(|cx| Buffer::local(sample_text(20, 3, 'a'), cx));
@@ -779,6 +864,36 @@ fn test_expand_excerpts(cx: &mut App) {
             "rrr",   // End of excerpt
         )
     );
+
+    multibuffer.update(cx, |multibuffer, cx| {
+        let first_excerpt = multibuffer.excerpt_ids()[0];
+        multibuffer.expand_excerpts_by([first_excerpt], 1, 0, cx);
+        let last_excerpt = *multibuffer.excerpt_ids().last().unwrap();
+        multibuffer.expand_excerpts_by([last_excerpt], 0, 2, cx);
+    });
+
+    assert_eq!(
+        multibuffer.read(cx).snapshot(cx).text(),
+        concat!(
+            "aaa\n", //
+            "bbb\n", //
+            "ccc\n", //
+            "ddd\n", //
+            "eee\n", //
+            "fff\n", //
+            "ggg\n", //
+            "hhh\n", //
+            "iii\n", //
+            "jjj\n", // End of excerpt
+            "nnn\n", //
+            "ooo\n", //
+            "ppp\n", //
+            "qqq\n", //
+            "rrr\n", //
+            "sss\n", //
+            "ttt",   // End of excerpt
+        )
+    );
 }
 
 #[gpui::test(iterations = 100)]
@@ -285,6 +285,23 @@ pub fn snippets_dir() -> &'static PathBuf {
     SNIPPETS_DIR.get_or_init(|| config_dir().join("snippets"))
 }
 
+/// Returns the path to the local file history directory.
+///
+/// This is where snapshots of edited files are kept.
+pub fn file_history_dir() -> &'static PathBuf {
+    static FILE_HISTORY_DIR: OnceLock<PathBuf> = OnceLock::new();
+    FILE_HISTORY_DIR.get_or_init(|| data_dir().join("file_history"))
+}
+
+/// Returns the path to the scratch directory.
+///
+/// Untitled buffers are opened in language servers as documents in this directory, which are
+/// never written to disk.
+pub fn scratch_dir() -> &'static PathBuf {
+    static SCRATCH_DIR: OnceLock<PathBuf> = OnceLock::new();
+    SCRATCH_DIR.get_or_init(|| temp_dir().join("scratch"))
+}
+
 /// Returns the path to the contexts directory.
 ///
 /// This is where the saved contexts from the Assistant are stored.
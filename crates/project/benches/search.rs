@@ -0,0 +1,62 @@
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use language::{Buffer, BufferSnapshot, Capability};
+use project::search::SearchQuery;
+use text::BufferId;
+use util::paths::PathMatcher;
+
+/// Lines of source code, with a match for the searched needle every hundred lines.
+fn generate_text(len: usize) -> String {
+    let mut text = String::with_capacity(len + 100);
+    let mut row = 0;
+    while text.len() < len {
+        if row % 100 == 0 {
+            text.push_str("    let Needle = haystack.find(needle_index)?;\n");
+        } else {
+            text.push_str("    let value = compute_value(&mut context, row, column + 1);\n");
+        }
+        row += 1;
+    }
+    text
+}
+
+fn buffer_snapshot(text: String) -> BufferSnapshot {
+    let text = text::Buffer::new(0, BufferId::new(1).unwrap(), text);
+    Buffer::build(text, None, Capability::ReadWrite).snapshot()
+}
+
+fn search_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("text search");
+    group.sample_size(10);
+    for size_mb in [64, 256] {
+        let len = size_mb * 1024 * 1024;
+        let snapshot = buffer_snapshot(generate_text(len));
+        group.throughput(Throughput::Bytes(len as u64));
+        for case_sensitive in [true, false] {
+            let query = SearchQuery::text(
+                "needle",
+                false,
+                case_sensitive,
+                false,
+                PathMatcher::default(),
+                PathMatcher::default(),
+                false,
+                None,
+            )
+            .unwrap();
+            let name = if case_sensitive {
+                "case sensitive"
+            } else {
+                "case insensitive"
+            };
+            group.bench_with_input(
+                BenchmarkId::new(name, format!("{size_mb}MB")),
+                &snapshot,
+                |b, snapshot| b.iter(|| smol::block_on(query.search(snapshot, None))),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, search_benchmark);
+criterion_main!(benches);
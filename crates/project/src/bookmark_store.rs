@@ -0,0 +1,569 @@
+//! Module for managing bookmarks in a project.
+//!
+//! Bookmarks are stored as buffer anchors, so they keep pointing at the same line while the buffer is edited.
+//! In a shared project the host owns the bookmarks: guests ask the host to toggle them, and the host sends
+//! every participant the bookmarks of each file, along with who added them.
+use anyhow::{Context as _, Result};
+use client::UserId;
+use collections::BTreeMap;
+use gpui::{App, AsyncApp, Context, Entity, EventEmitter, Subscription, Task};
+use language::{Buffer, BufferEvent, BufferSnapshot, DiskState};
+use rpc::{AnyProtoClient, TypedEnvelope, proto};
+use std::{ops::Range, path::Path, sync::Arc};
+use text::Point;
+use util::ResultExt as _;
+
+use crate::{Project, ProjectPath, buffer_store::BufferStore, worktree_store::WorktreeStore};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bookmark {
+    pub position: text::Anchor,
+    /// The user that added the bookmark while the project was shared.
+    pub author: Option<UserId>,
+}
+
+impl Bookmark {
+    fn to_proto(&self) -> proto::Bookmark {
+        proto::Bookmark {
+            position: Some(language::proto::serialize_anchor(&self.position)),
+            author_id: self.author,
+        }
+    }
+
+    fn from_proto(bookmark: proto::Bookmark) -> Option<Self> {
+        Some(Self {
+            position: language::proto::deserialize_anchor(bookmark.position?)?,
+            author: bookmark.author_id,
+        })
+    }
+}
+
+struct BookmarksInFile {
+    buffer: Entity<Buffer>,
+    /// Kept sorted by their position in the buffer.
+    bookmarks: Vec<Bookmark>,
+    _subscription: Subscription,
+}
+
+impl BookmarksInFile {
+    fn new(buffer: Entity<Buffer>, cx: &mut Context<BookmarkStore>) -> Self {
+        let subscription = cx.subscribe(&buffer, |bookmark_store, buffer, event, cx| {
+            if let BufferEvent::FileHandleChanged = event {
+                bookmark_store.handle_file_handle_changed(buffer, cx);
+            }
+        });
+
+        Self {
+            buffer,
+            bookmarks: Vec::new(),
+            _subscription: subscription,
+        }
+    }
+
+    fn row_of(&self, bookmark: &Bookmark, snapshot: &BufferSnapshot) -> u32 {
+        bookmark.position.summary::<Point>(snapshot).row
+    }
+}
+
+#[derive(Clone)]
+struct CollabBookmarkStore {
+    upstream_client: AnyProtoClient,
+    upstream_project_id: u64,
+}
+
+#[derive(Clone)]
+struct LocalBookmarkStore {
+    worktree_store: Entity<WorktreeStore>,
+    buffer_store: Entity<BufferStore>,
+}
+
+#[derive(Clone)]
+enum BookmarkStoreMode {
+    Local(LocalBookmarkStore),
+    Collab(CollabBookmarkStore),
+}
+
+pub struct BookmarkStore {
+    bookmarks: BTreeMap<Arc<Path>, BookmarksInFile>,
+    /// The project's guests and the host's user, while the project is shared.
+    downstream_client: Option<(AnyProtoClient, u64, Option<UserId>)>,
+    mode: BookmarkStoreMode,
+}
+
+impl BookmarkStore {
+    pub fn init(client: &AnyProtoClient) {
+        client.add_entity_request_handler(Self::handle_toggle_bookmark);
+        client.add_entity_message_handler(Self::handle_bookmarks_for_file);
+    }
+
+    pub fn local(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
+        Self {
+            bookmarks: BTreeMap::new(),
+            downstream_client: None,
+            mode: BookmarkStoreMode::Local(LocalBookmarkStore {
+                worktree_store,
+                buffer_store,
+            }),
+        }
+    }
+
+    /// Bookmarks of a remote project are kept on the client, which opens their buffers through the
+    /// project's own stores.
+    pub(crate) fn remote(
+        worktree_store: Entity<WorktreeStore>,
+        buffer_store: Entity<BufferStore>,
+    ) -> Self {
+        Self::local(worktree_store, buffer_store)
+    }
+
+    pub(crate) fn collab(upstream_project_id: u64, upstream_client: AnyProtoClient) -> Self {
+        Self {
+            bookmarks: BTreeMap::new(),
+            downstream_client: None,
+            mode: BookmarkStoreMode::Collab(CollabBookmarkStore {
+                upstream_client,
+                upstream_project_id,
+            }),
+        }
+    }
+
+    pub(crate) fn shared(
+        &mut self,
+        project_id: u64,
+        downstream_client: AnyProtoClient,
+        user_id: Option<UserId>,
+    ) {
+        self.downstream_client = Some((downstream_client, project_id, user_id));
+    }
+
+    pub(crate) fn unshared(&mut self, cx: &mut Context<Self>) {
+        self.downstream_client.take();
+        cx.notify();
+    }
+
+    /// Sends the bookmarks of every file to the project's guests.
+    pub(crate) fn broadcast(&self) {
+        for path in self.bookmarks.keys() {
+            self.send_bookmarks_for_file(path);
+        }
+    }
+
+    fn send_bookmarks_for_file(&self, path: &Path) {
+        let Some((client, project_id, _)) = &self.downstream_client else {
+            return;
+        };
+        let bookmarks = self
+            .bookmarks
+            .get(path)
+            .map(|bookmarks_in_file| {
+                bookmarks_in_file
+                    .bookmarks
+                    .iter()
+                    .map(Bookmark::to_proto)
+                    .collect()
+            })
+            .unwrap_or_default();
+        client
+            .send(proto::BookmarksForFile {
+                project_id: *project_id,
+                path: path.to_string_lossy().into_owned(),
+                bookmarks,
+            })
+            .log_err();
+    }
+
+    async fn handle_toggle_bookmark(
+        this: Entity<Project>,
+        envelope: TypedEnvelope<proto::ToggleBookmark>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let peer_id = envelope.original_sender_id()?;
+        let (bookmark_store, buffer, author) = this.update(&mut cx, |project, cx| {
+            let path = project
+                .project_path_for_absolute_path(envelope.payload.path.as_ref(), cx)
+                .context("Could not resolve provided abs path")?;
+            let buffer = project
+                .buffer_store()
+                .read(cx)
+                .get_by_path(&path)
+                .context("Could not find buffer for a given path")?;
+            let author = project
+                .collaborators()
+                .get(&peer_id)
+                .map(|collaborator| collaborator.user_id);
+            anyhow::Ok((project.bookmark_store(), buffer, author))
+        })??;
+        let position = envelope
+            .payload
+            .position
+            .and_then(language::proto::deserialize_anchor)
+            .context("Invalid bookmark position")?;
+
+        bookmark_store.update(&mut cx, |bookmark_store, cx| {
+            bookmark_store.toggle_bookmark_for_author(buffer, position, author, cx);
+        })?;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bookmarks_for_file(
+        this: Entity<Project>,
+        envelope: TypedEnvelope<proto::BookmarksForFile>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let bookmark_store = this.read_with(&cx, |project, _| project.bookmark_store())?;
+        let abs_path = Arc::<Path>::from(Path::new(&envelope.payload.path));
+        if envelope.payload.bookmarks.is_empty() {
+            bookmark_store.update(&mut cx, |bookmark_store, cx| {
+                if bookmark_store.bookmarks.remove(&abs_path).is_some() {
+                    cx.emit(BookmarkStoreEvent::BookmarksCleared(vec![abs_path]));
+                    cx.notify();
+                }
+            })?;
+            return Ok(());
+        }
+
+        let buffer = this
+            .update(&mut cx, |project, cx| {
+                let path = project.project_path_for_absolute_path(&abs_path, cx)?;
+                Some(project.open_buffer(path, cx))
+            })?
+            .context("Invalid project path")?
+            .await?;
+
+        bookmark_store.update(&mut cx, |bookmark_store, cx| {
+            let snapshot = buffer.read(cx).snapshot();
+            let bookmarks_in_file = bookmark_store
+                .bookmarks
+                .entry(abs_path.clone())
+                .or_insert_with(|| BookmarksInFile::new(buffer, cx));
+            bookmarks_in_file.bookmarks = envelope
+                .payload
+                .bookmarks
+                .into_iter()
+                .filter_map(Bookmark::from_proto)
+                .collect();
+            bookmarks_in_file
+                .bookmarks
+                .sort_by(|a, b| a.position.cmp(&b.position, &snapshot));
+            cx.emit(BookmarkStoreEvent::BookmarksUpdated(abs_path));
+            cx.notify();
+        })?;
+        Ok(())
+    }
+
+    pub fn abs_path_from_buffer(buffer: &Entity<Buffer>, cx: &App) -> Option<Arc<Path>> {
+        worktree::File::from_dyn(buffer.read(cx).file())
+            .map(|file| file.worktree.read(cx).absolutize(&file.path))
+            .map(Arc::<Path>::from)
+    }
+
+    fn handle_file_handle_changed(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
+        let entity_id = buffer.entity_id();
+        if buffer
+            .read(cx)
+            .file()
+            .is_none_or(|file| file.disk_state() == DiskState::Deleted)
+        {
+            let removed_paths = self
+                .bookmarks
+                .iter()
+                .filter(|(_, in_file)| in_file.buffer.entity_id() == entity_id)
+                .map(|(path, _)| path.clone())
+                .collect::<Vec<_>>();
+            for path in &removed_paths {
+                self.bookmarks.remove(path);
+                self.send_bookmarks_for_file(path);
+            }
+            if !removed_paths.is_empty() {
+                cx.emit(BookmarkStoreEvent::BookmarksCleared(removed_paths));
+                cx.notify();
+            }
+            return;
+        }
+
+        let Some(abs_path) = Self::abs_path_from_buffer(&buffer, cx) else {
+            return;
+        };
+        if self.bookmarks.contains_key(&abs_path) {
+            return;
+        }
+        let Some(old_path) = self
+            .bookmarks
+            .iter()
+            .find(|(_, in_file)| in_file.buffer.entity_id() == entity_id)
+            .map(|(path, _)| path.clone())
+        else {
+            return;
+        };
+        self.on_file_rename(old_path, abs_path, cx);
+    }
+
+    /// Adds a bookmark on the line containing `position`, or removes the bookmark that is already there.
+    pub fn toggle_bookmark(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: text::Anchor,
+        cx: &mut Context<Self>,
+    ) {
+        let author = self
+            .downstream_client
+            .as_ref()
+            .and_then(|(_, _, user_id)| *user_id);
+        self.toggle_bookmark_for_author(buffer, position, author, cx);
+    }
+
+    fn toggle_bookmark_for_author(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: text::Anchor,
+        author: Option<UserId>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(abs_path) = Self::abs_path_from_buffer(&buffer, cx) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).snapshot();
+        let row = position.summary::<Point>(&snapshot).row;
+        let position = snapshot.anchor_after(Point::new(row, 0));
+
+        let bookmarks_in_file = self
+            .bookmarks
+            .entry(abs_path.clone())
+            .or_insert_with(|| BookmarksInFile::new(buffer, cx));
+
+        let len_before = bookmarks_in_file.bookmarks.len();
+        bookmarks_in_file
+            .bookmarks
+            .retain(|bookmark| bookmark.position.summary::<Point>(&snapshot).row != row);
+        if len_before == bookmarks_in_file.bookmarks.len() {
+            let ix = bookmarks_in_file
+                .bookmarks
+                .partition_point(|bookmark| bookmark.position.cmp(&position, &snapshot).is_lt());
+            bookmarks_in_file
+                .bookmarks
+                .insert(ix, Bookmark { position, author });
+        }
+
+        if bookmarks_in_file.bookmarks.is_empty() {
+            self.bookmarks.remove(&abs_path);
+        }
+
+        if let BookmarkStoreMode::Collab(collab) = &self.mode {
+            cx.background_spawn(collab.upstream_client.request(proto::ToggleBookmark {
+                project_id: collab.upstream_project_id,
+                path: abs_path.to_string_lossy().into_owned(),
+                position: Some(language::proto::serialize_anchor(&position)),
+            }))
+            .detach_and_log_err(cx);
+        } else {
+            self.send_bookmarks_for_file(&abs_path);
+        }
+
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated(abs_path));
+        cx.notify();
+    }
+
+    pub fn on_file_rename(
+        &mut self,
+        old_path: Arc<Path>,
+        new_path: Arc<Path>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(bookmarks) = self.bookmarks.remove(&old_path) {
+            self.bookmarks.insert(new_path.clone(), bookmarks);
+            self.send_bookmarks_for_file(&old_path);
+            self.send_bookmarks_for_file(&new_path);
+            cx.emit(BookmarkStoreEvent::BookmarksCleared(vec![old_path]));
+            cx.emit(BookmarkStoreEvent::BookmarksUpdated(new_path));
+            cx.notify();
+        }
+    }
+
+    pub fn clear_bookmarks(&mut self, cx: &mut Context<Self>) {
+        let bookmark_paths: Vec<_> = self.bookmarks.keys().cloned().collect();
+        let bookmarks = std::mem::take(&mut self.bookmarks);
+        if let BookmarkStoreMode::Collab(collab) = &self.mode {
+            // The host toggles each bookmark off for every participant.
+            for (path, bookmarks_in_file) in &bookmarks {
+                for bookmark in &bookmarks_in_file.bookmarks {
+                    cx.background_spawn(collab.upstream_client.request(proto::ToggleBookmark {
+                        project_id: collab.upstream_project_id,
+                        path: path.to_string_lossy().into_owned(),
+                        position: Some(language::proto::serialize_anchor(&bookmark.position)),
+                    }))
+                    .detach_and_log_err(cx);
+                }
+            }
+        } else {
+            for path in &bookmark_paths {
+                self.send_bookmarks_for_file(path);
+            }
+        }
+        cx.emit(BookmarkStoreEvent::BookmarksCleared(bookmark_paths));
+        cx.notify();
+    }
+
+    /// Returns the bookmarks of `buffer` in buffer order, optionally restricted to `range`.
+    pub fn bookmarks<'a>(
+        &'a self,
+        buffer: &Entity<Buffer>,
+        range: Option<Range<text::Anchor>>,
+        buffer_snapshot: &'a BufferSnapshot,
+        cx: &App,
+    ) -> impl Iterator<Item = &'a text::Anchor> + 'a {
+        Self::abs_path_from_buffer(buffer, cx)
+            .and_then(|path| self.bookmarks.get(&path))
+            .into_iter()
+            .flat_map(move |bookmarks_in_file| {
+                let range = range.clone();
+                bookmarks_in_file
+                    .bookmarks
+                    .iter()
+                    .map(|bookmark| &bookmark.position)
+                    .filter(move |bookmark| {
+                        range.as_ref().is_none_or(|range| {
+                            bookmark.cmp(&range.start, buffer_snapshot).is_ge()
+                                && bookmark.cmp(&range.end, buffer_snapshot).is_le()
+                        })
+                    })
+            })
+    }
+
+    pub fn has_bookmarks(&self, buffer: &Entity<Buffer>, cx: &App) -> bool {
+        Self::abs_path_from_buffer(buffer, cx)
+            .is_some_and(|path| self.bookmarks.contains_key(&path))
+    }
+
+    pub fn bookmark_at_row(&self, path: &Path, row: u32, cx: &App) -> Option<Bookmark> {
+        let bookmarks_in_file = self.bookmarks.get(path)?;
+        let snapshot = bookmarks_in_file.buffer.read(cx).snapshot();
+        bookmarks_in_file
+            .bookmarks
+            .iter()
+            .find(|bookmark| bookmarks_in_file.row_of(bookmark, &snapshot) == row)
+            .copied()
+    }
+
+    pub fn all_bookmarks(&self) -> impl Iterator<Item = (&Entity<Buffer>, &[Bookmark])> {
+        self.bookmarks
+            .values()
+            .map(|bookmarks_in_file| (&bookmarks_in_file.buffer, &*bookmarks_in_file.bookmarks))
+    }
+
+    pub fn all_source_bookmarks(&self, cx: &App) -> BTreeMap<Arc<Path>, Vec<SourceBookmark>> {
+        self.bookmarks
+            .iter()
+            .map(|(path, bookmarks_in_file)| {
+                let snapshot = bookmarks_in_file.buffer.read(cx).snapshot();
+                let mut rows = bookmarks_in_file
+                    .bookmarks
+                    .iter()
+                    .map(|bookmark| bookmarks_in_file.row_of(bookmark, &snapshot))
+                    .collect::<Vec<_>>();
+                // Edits can collapse several bookmarked lines into one.
+                rows.dedup();
+                (
+                    path.clone(),
+                    rows.into_iter()
+                        .map(|row| SourceBookmark {
+                            row,
+                            path: path.clone(),
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn with_serialized_bookmarks(
+        &self,
+        bookmarks: BTreeMap<Arc<Path>, Vec<SourceBookmark>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let BookmarkStoreMode::Local(mode) = &self.mode else {
+            return Task::ready(Ok(()));
+        };
+        let mode = mode.clone();
+        cx.spawn(async move |this, cx| {
+            let mut new_bookmarks = BTreeMap::default();
+            for (path, source_bookmarks) in bookmarks {
+                if source_bookmarks.is_empty() {
+                    continue;
+                }
+                let worktree = mode
+                    .worktree_store
+                    .update(cx, |this, cx| {
+                        this.find_or_create_worktree(&path, false, cx)
+                    })?
+                    .await;
+                let (worktree, relative_path) = match worktree {
+                    Ok(worktree) => worktree,
+                    Err(error) => {
+                        log::error!(
+                            "Failed to find worktree for serialized bookmarks in {path:?}: {error:#}"
+                        );
+                        continue;
+                    }
+                };
+                let buffer = mode
+                    .buffer_store
+                    .update(cx, |this, cx| {
+                        let path = ProjectPath {
+                            worktree_id: worktree.read(cx).id(),
+                            path: relative_path,
+                        };
+                        this.open_buffer(path, cx)
+                    })?
+                    .await;
+                let buffer = match buffer {
+                    Ok(buffer) => buffer,
+                    Err(error) => {
+                        log::error!("Failed to open buffer for serialized bookmarks: {error:#}");
+                        continue;
+                    }
+                };
+                let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+
+                let mut bookmarks_in_file =
+                    this.update(cx, |_, cx| BookmarksInFile::new(buffer, cx))?;
+                let max_point = snapshot.max_point();
+                for source_bookmark in source_bookmarks {
+                    if source_bookmark.row > max_point.row {
+                        log::error!("skipping a deserialized bookmark that's out of range");
+                        continue;
+                    }
+                    bookmarks_in_file.bookmarks.push(Bookmark {
+                        position: snapshot.anchor_after(Point::new(source_bookmark.row, 0)),
+                        author: None,
+                    });
+                }
+                bookmarks_in_file
+                    .bookmarks
+                    .sort_by(|a, b| a.position.cmp(&b.position, &snapshot));
+                new_bookmarks.insert(path, bookmarks_in_file);
+            }
+
+            this.update(cx, |this, cx| {
+                log::debug!("Deserialized bookmarks for {} files", new_bookmarks.len());
+                this.bookmarks = new_bookmarks;
+                cx.notify();
+            })?;
+
+            Ok(())
+        })
+    }
+}
+
+pub enum BookmarkStoreEvent {
+    BookmarksUpdated(Arc<Path>),
+    BookmarksCleared(Vec<Arc<Path>>),
+}
+
+impl EventEmitter<BookmarkStoreEvent> for BookmarkStore {}
+
+/// Bookmark for a line within source code.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SourceBookmark {
+    pub row: u32,
+    pub path: Arc<Path>,
+}
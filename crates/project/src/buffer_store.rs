@@ -1,6 +1,7 @@
 use crate::{
     ProjectItem as _, ProjectPath,
     lsp_store::OpenLspBufferHandle,
+    project_settings::ProjectSettings,
     search::SearchQuery,
     worktree_store::{WorktreeStore, WorktreeStoreEvent},
 };
@@ -13,7 +14,8 @@ use gpui::{
     App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Subscription, Task, WeakEntity,
 };
 use language::{
-    Buffer, BufferEvent, Capability, DiskState, File as _, Language, Operation,
+    Buffer, BufferEvent, Capability, DiskState, File as _, Language, LineEnding, Operation,
+    language_settings::{LineEndingSetting, language_settings},
     proto::{
         deserialize_line_ending, deserialize_version, serialize_line_ending, serialize_version,
         split_operations,
@@ -23,6 +25,7 @@ use rpc::{
     AnyProtoClient, ErrorCode, ErrorExt as _, TypedEnvelope,
     proto::{self},
 };
+use settings::Settings as _;
 use smol::channel::Receiver;
 use std::{io, pin::pin, sync::Arc, time::Instant};
 use text::BufferId;
@@ -375,9 +378,31 @@ impl LocalBufferStore {
         cx: &mut Context<BufferStore>,
     ) -> Task<Result<()>> {
         let buffer = buffer_handle.read(cx);
+        // Newly created files are saved with the line ending configured for their language.
+        if buffer
+            .file()
+            .is_none_or(|file| file.disk_state() == DiskState::New)
+        {
+            let line_ending = match language_settings(
+                buffer.language().map(|language| language.name()),
+                buffer.file(),
+                cx,
+            )
+            .line_ending
+            {
+                LineEndingSetting::PlatformDefault => LineEnding::default(),
+                LineEndingSetting::Lf => LineEnding::Unix,
+                LineEndingSetting::Crlf => LineEnding::Windows,
+            };
+            if buffer.line_ending() != line_ending {
+                buffer_handle.update(cx, |buffer, cx| buffer.set_line_ending(line_ending, cx));
+            }
+        }
+        let buffer = buffer_handle.read(cx);
 
         let text = buffer.as_rope().clone();
         let line_ending = buffer.line_ending();
+        let encoding = buffer.encoding();
         let version = buffer.version();
         let buffer_id = buffer.remote_id();
         let file = buffer.file().cloned();
@@ -389,7 +414,7 @@ impl LocalBufferStore {
         }
 
         let save = worktree.update(cx, |worktree, cx| {
-            worktree.write_file(path, text, line_ending, cx)
+            worktree.write_file(path, text, line_ending, encoding, cx)
         });
 
         cx.spawn(async move |this, cx| {
@@ -623,13 +648,29 @@ impl LocalBufferStore {
             let load_file = worktree.load_file(path.as_ref(), cx);
             let reservation = cx.reserve_entity();
             let buffer_id = BufferId::from(reservation.entity_id().as_non_zero_u64());
+            let large_file_settings = ProjectSettings::get_global(cx).large_file;
             cx.spawn(async move |_, cx| {
                 let loaded = load_file.await?;
-                let text_buffer = cx
-                    .background_spawn(async move { text::Buffer::new(0, buffer_id, loaded.text) })
+                let (text_buffer, mixed_line_ending_rows) = cx
+                    .background_spawn(async move {
+                        let line_ending = LineEnding::detect(&loaded.text);
+                        let mixed_line_ending_rows =
+                            LineEnding::mismatched_rows(&loaded.text, line_ending);
+                        (
+                            text::Buffer::new(0, buffer_id, loaded.text),
+                            mixed_line_ending_rows,
+                        )
+                    })
                     .await;
-                cx.insert_entity(reservation, |_| {
-                    Buffer::build(text_buffer, Some(loaded.file), Capability::ReadWrite)
+                let is_large_file = large_file_settings
+                    .is_large_file(text_buffer.len(), text_buffer.max_point().row + 1);
+                cx.insert_entity(reservation, |cx| {
+                    let mut buffer =
+                        Buffer::build(text_buffer, Some(loaded.file), Capability::ReadWrite);
+                    buffer.set_large_file_mode(is_large_file, cx);
+                    buffer.set_encoding(loaded.encoding, cx);
+                    buffer.set_mixed_line_ending_rows(&mixed_line_ending_rows, cx);
+                    buffer
                 })
             })
         });
@@ -842,6 +883,7 @@ impl BufferStore {
                                 this.loading_buffers.remove(&project_path);
 
                                 let buffer = load_result.map_err(Arc::new)?;
+                                buffer.update(cx, |buffer, cx| buffer.detect_indent(cx));
                                 cx.emit(BufferStoreEvent::BufferOpened {
                                     buffer: buffer.clone(),
                                     project_path,
@@ -799,6 +799,21 @@ impl GitStore {
         conflict_set
     }
 
+    /// Shows the conflict markers that [`Buffer::reload_with_merge`] left in `buffer`, even though
+    /// git doesn't consider its file conflicted.
+    pub fn show_reload_conflicts(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
+        let conflict_set = self.open_conflict_set(buffer.clone(), cx);
+        conflict_set.update(cx, |conflict_set, _| {
+            conflict_set.has_reload_conflict = true
+        });
+        if let Some(git_state) = self.diffs.get(&buffer.read(cx).remote_id()) {
+            let buffer_snapshot = buffer.read(cx).text_snapshot();
+            git_state.update(cx, |state, cx| {
+                let _ = state.reparse_conflict_markers(buffer_snapshot, cx);
+            });
+        }
+    }
+
     pub fn project_path_git_status(
         &self,
         project_path: &ProjectPath,
@@ -2463,7 +2478,7 @@ impl BufferGitState {
         };
 
         let old_snapshot = conflict_set.read_with(cx, |conflict_set, _| {
-            if conflict_set.has_conflict {
+            if conflict_set.has_conflict || conflict_set.has_reload_conflict {
                 Some(conflict_set.snapshot())
             } else {
                 None
@@ -4,6 +4,9 @@ use text::{Anchor, BufferId, OffsetRangeExt as _};
 
 pub struct ConflictSet {
     pub has_conflict: bool,
+    /// Whether the buffer has conflict markers from merging the changes on disk into its unsaved
+    /// edits, which are shown even though git doesn't consider its file conflicted.
+    pub has_reload_conflict: bool,
     pub snapshot: ConflictSetSnapshot,
 }
 
@@ -129,6 +132,7 @@ impl ConflictSet {
     pub fn new(buffer_id: BufferId, has_conflict: bool, _: &mut Context<Self>) -> Self {
         Self {
             has_conflict,
+            has_reload_conflict: false,
             snapshot: ConflictSetSnapshot {
                 buffer_id,
                 conflicts: Default::default(),
@@ -139,7 +143,7 @@ impl ConflictSet {
     pub fn set_has_conflict(&mut self, has_conflict: bool, cx: &mut Context<Self>) -> bool {
         if has_conflict != self.has_conflict {
             self.has_conflict = has_conflict;
-            if !self.has_conflict {
+            if !self.has_conflict && !self.has_reload_conflict {
                 cx.emit(ConflictSetUpdate {
                     buffer_range: None,
                     old_range: 0..self.snapshot.conflicts.len(),
@@ -163,6 +167,10 @@ impl ConflictSet {
         update: ConflictSetUpdate,
         cx: &mut Context<Self>,
     ) {
+        // Once every conflict from a reload is resolved, the buffer is back to normal.
+        if snapshot.conflicts.is_empty() {
+            self.has_reload_conflict = false;
+        }
         self.snapshot = snapshot;
         cx.emit(update);
     }
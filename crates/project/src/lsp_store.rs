@@ -59,9 +59,12 @@ use language::{
     Bias, BinaryStatus, Buffer, BufferSnapshot, CachedLspAdapter, CodeLabel, Diagnostic,
     DiagnosticEntry, DiagnosticSet, DiagnosticSourceKind, Diff, File as _, Language, LanguageName,
     LanguageRegistry, LocalFile, LspAdapter, LspAdapterDelegate, LspInstaller, ManifestDelegate,
-    ManifestName, Patch, PointUtf16, TextBufferSnapshot, ToOffset, ToPointUtf16, Toolchain,
+    ManifestName, Patch, Point, PointUtf16, TextBufferSnapshot, ToOffset, ToPointUtf16, Toolchain,
     Transaction, Unclipped,
-    language_settings::{FormatOnSave, Formatter, LanguageSettings, language_settings},
+    language_settings::{
+        AutoImportPlacement, FormatOnSave, Formatter, LanguageSettings,
+        RemoveTrailingWhitespaceOnSave, language_settings,
+    },
     point_to_lsp,
     proto::{
         deserialize_anchor, deserialize_lsp_edit, deserialize_version, serialize_anchor,
@@ -129,6 +132,8 @@ pub use worktree::{
 
 const SERVER_LAUNCHING_BEFORE_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 pub const SERVER_PROGRESS_THROTTLE_TIMEOUT: Duration = Duration::from_millis(100);
+/// How many of the last workspace edits applied by language servers can be reverted.
+const MAX_WORKSPACE_EDIT_HISTORY: usize = 32;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FormatTrigger {
@@ -175,6 +180,29 @@ pub struct DocumentDiagnosticsUpdate<'a, D> {
     pub disk_based_sources: Cow<'a, [String]>,
 }
 
+/// A workspace edit that a language server applied, such as a rename, with the edits that
+/// revert it.
+struct AppliedWorkspaceEdit {
+    language_server_id: LanguageServerId,
+    buffer_edits: Vec<AppliedBufferEdit>,
+}
+
+struct AppliedBufferEdit {
+    uri: lsp::Uri,
+    /// The edits restoring the previous text of the file, with ranges in its edited text.
+    inverse_edits: Vec<(Range<usize>, Arc<str>)>,
+    /// The hash of the file's text right after the edit, to detect later changes to the file.
+    edited_text_hash: u64,
+}
+
+fn text_hash(text: &text::BufferSnapshot) -> u64 {
+    let mut hasher = std::hash::DefaultHasher::new();
+    for chunk in text.as_rope().chunks() {
+        std::hash::Hasher::write(&mut hasher, chunk.as_bytes());
+    }
+    std::hash::Hasher::finish(&hasher)
+}
+
 pub struct DocumentDiagnostics {
     diagnostics: Vec<DiagnosticEntry<Unclipped<PointUtf16>>>,
     document_abs_path: PathBuf,
@@ -194,6 +222,7 @@ pub struct LocalLspStore {
     pub language_servers: HashMap<LanguageServerId, LanguageServerState>,
     buffers_being_formatted: HashSet<BufferId>,
     last_workspace_edits_by_language_server: HashMap<LanguageServerId, ProjectTransaction>,
+    workspace_edit_history: Vec<Arc<AppliedWorkspaceEdit>>,
     language_server_watched_paths: HashMap<LanguageServerId, LanguageServerWatchedPaths>,
     watched_manifest_filenames: HashSet<ManifestName>,
     language_server_paths_watched_for_rename:
@@ -219,10 +248,145 @@ pub struct LocalLspStore {
     lsp_tree: LanguageServerTree,
     registered_buffers: HashMap<BufferId, usize>,
     buffers_opened_in_servers: HashMap<BufferId, HashSet<LanguageServerId>>,
-    buffer_pull_diagnostics_result_ids: HashMap<LanguageServerId, HashMap<PathBuf, Option<String>>>,
+    /// The result ids of the last pulled diagnostics of each document, including the ones that
+    /// are reported by workspace pulls without being open.
+    pull_diagnostics_result_ids: HashMap<LanguageServerId, HashMap<PathBuf, String>>,
+    /// The invisible worktree in which untitled buffers are opened in language servers, which
+    /// only address documents by path.
+    scratch_worktree: Option<Entity<Worktree>>,
+    scratch_worktree_task: Option<Task<()>>,
 }
 
+/// The file name prefix of the documents that untitled buffers are opened as in language
+/// servers, followed by the buffer id.
+const SCRATCH_DOCUMENT_PREFIX: &str = "untitled-";
+
 impl LocalLspStore {
+    /// Returns the worktree, path and absolute path of the document that a buffer is opened as in
+    /// its language servers.
+    ///
+    /// Untitled buffers with a language are opened as a document in the scratch worktree, named
+    /// after their buffer id and the language's first path suffix.
+    fn lsp_document(
+        &self,
+        buffer: &Buffer,
+        cx: &App,
+    ) -> Option<(WorktreeId, Arc<RelPath>, PathBuf)> {
+        if let Some(file) = buffer.file() {
+            let file = File::from_dyn(Some(file)).filter(|file| file.is_local())?;
+            return Some((file.worktree_id(cx), file.path().clone(), file.abs_path(cx)));
+        }
+        self.scratch_document(buffer, cx)
+    }
+
+    /// Returns the document that an untitled buffer is opened as in its language servers, or
+    /// was opened as before it was saved.
+    fn scratch_document(
+        &self,
+        buffer: &Buffer,
+        cx: &App,
+    ) -> Option<(WorktreeId, Arc<RelPath>, PathBuf)> {
+        let language = buffer.language()?;
+        let mut file_name = format!("{SCRATCH_DOCUMENT_PREFIX}{}", buffer.remote_id());
+        if let Some(suffix) = language.path_suffixes().first() {
+            file_name.push('.');
+            file_name.push_str(suffix);
+        }
+        let path = RelPath::unix(&file_name).ok()?.into_arc();
+        let worktree = self.scratch_worktree.as_ref()?.read(cx);
+        Some((worktree.id(), path.clone(), worktree.absolutize(&path)))
+    }
+
+    /// Returns the id of the untitled buffer that a document in the scratch worktree belongs to.
+    fn scratch_buffer_id(&self, project_path: &ProjectPath, cx: &App) -> Option<BufferId> {
+        let worktree = self.scratch_worktree.as_ref()?;
+        if worktree.read(cx).id() != project_path.worktree_id {
+            return None;
+        }
+        let id = project_path
+            .path
+            .file_stem()?
+            .strip_prefix(SCRATCH_DOCUMENT_PREFIX)?
+            .parse()
+            .ok()?;
+        BufferId::new(id).ok()
+    }
+
+    /// Creates the scratch worktree, then registers the untitled buffers that were waiting for
+    /// it with their language servers.
+    fn create_scratch_worktree(&mut self, cx: &mut Context<LspStore>) {
+        if self.scratch_worktree.is_some() || self.scratch_worktree_task.is_some() {
+            return;
+        }
+
+        let fs = self.fs.clone();
+        let worktree_store = self.worktree_store.clone();
+        self.scratch_worktree_task = Some(cx.spawn(async move |this, cx| {
+            let scratch_dir = paths::scratch_dir();
+            let worktree = async {
+                fs.create_dir(scratch_dir).await?;
+                let (worktree, _) = worktree_store
+                    .update(cx, |worktree_store, cx| {
+                        worktree_store.find_or_create_worktree(scratch_dir, false, cx)
+                    })?
+                    .await?;
+                anyhow::Ok(worktree)
+            }
+            .await
+            .context("creating the scratch worktree")
+            .log_err();
+            let Some(worktree) = worktree else {
+                return;
+            };
+
+            this.update(cx, |this, cx| {
+                let untitled_buffers = this
+                    .buffer_store
+                    .read(cx)
+                    .buffers()
+                    .filter(|buffer| buffer.read(cx).file().is_none())
+                    .collect::<Vec<_>>();
+                let Some(local) = this.as_local_mut() else {
+                    return;
+                };
+                local.scratch_worktree = Some(worktree);
+                for buffer in untitled_buffers {
+                    if local
+                        .registered_buffers
+                        .contains_key(&buffer.read(cx).remote_id())
+                    {
+                        local.register_buffer_with_language_servers(
+                            &buffer,
+                            HashSet::default(),
+                            cx,
+                        );
+                    }
+                }
+            })
+            .ok();
+        }));
+    }
+
+    fn set_pull_diagnostics_result_id(
+        &mut self,
+        server_id: LanguageServerId,
+        abs_path: PathBuf,
+        result_id: Option<String>,
+    ) {
+        let result_ids = self
+            .pull_diagnostics_result_ids
+            .entry(server_id)
+            .or_default();
+        match result_id {
+            Some(result_id) => {
+                result_ids.insert(abs_path, result_id);
+            }
+            None => {
+                result_ids.remove(&abs_path);
+            }
+        }
+    }
+
     /// Returns the running language server for the given ID. Note if the language server is starting, it will not be returned.
     pub fn running_language_server_for_id(
         &self,
@@ -1078,14 +1242,10 @@ impl LocalLspStore {
         buffer: &Buffer,
         cx: &mut App,
     ) -> Vec<LanguageServerId> {
-        if let Some((file, language)) = File::from_dyn(buffer.file()).zip(buffer.language()) {
-            let worktree_id = file.worktree_id(cx);
-
-            let path: Arc<RelPath> = file
-                .path()
-                .parent()
-                .map(Arc::from)
-                .unwrap_or_else(|| file.path().clone());
+        if let Some(((worktree_id, path, _), language)) =
+            self.lsp_document(buffer, cx).zip(buffer.language())
+        {
+            let path: Arc<RelPath> = path.parent().map(Arc::from).unwrap_or(path);
             let worktree_path = ProjectPath { worktree_id, path };
             self.language_server_ids_for_project_path(worktree_path, language, cx)
         } else {
@@ -1285,9 +1445,14 @@ impl LocalLspStore {
                     .language_servers_for_buffer(buffer, cx)
                     .map(|(adapter, lsp)| (adapter.clone(), lsp.clone()))
                     .collect::<Vec<_>>();
-                let settings =
-                    language_settings(buffer.language().map(|l| l.name()), buffer.file(), cx)
-                        .into_owned();
+                let settings = buffer
+                    .indent_override()
+                    .apply(language_settings(
+                        buffer.language().map(|l| l.name()),
+                        buffer.file(),
+                        cx,
+                    ))
+                    .into_owned();
                 (adapters_and_servers, settings)
             })
         })?;
@@ -1315,11 +1480,17 @@ impl LocalLspStore {
         }
 
         // handle whitespace formatting
-        if settings.remove_trailing_whitespace_on_save {
+        let remove_trailing_whitespace = settings.remove_trailing_whitespace_on_save;
+        if remove_trailing_whitespace != RemoveTrailingWhitespaceOnSave::None {
             zlog::trace!(logger => "removing trailing whitespace");
             let diff = buffer
                 .handle
-                .read_with(cx, |buffer, cx| buffer.remove_trailing_whitespace(cx))?
+                .read_with(cx, |buffer, cx| match remove_trailing_whitespace {
+                    RemoveTrailingWhitespaceOnSave::ModifiedLinesOnly => {
+                        buffer.remove_trailing_whitespace_in_modified_lines(cx)
+                    }
+                    _ => buffer.remove_trailing_whitespace(cx),
+                })?
                 .await;
             extend_formatting_transaction(buffer, formatting_transaction_id, cx, |buffer, cx| {
                 buffer.apply_diff(diff, cx);
@@ -1339,8 +1510,21 @@ impl LocalLspStore {
                 settings.formatter.as_ref()
             }
         };
+        // Code actions on save run before any other formatter, regardless of `format_on_save`,
+        // and give up on servers that don't respond in time.
+        let code_actions_on_save = match trigger {
+            FormatTrigger::Save => settings
+                .code_actions_on_save
+                .iter()
+                .filter(|(_, enabled)| **enabled)
+                .map(|(kind, _)| Formatter::CodeAction(kind.clone()))
+                .collect::<Vec<_>>(),
+            FormatTrigger::Manual => Vec::new(),
+        };
 
-        for formatter in formatters {
+        for (formatter_ix, formatter) in code_actions_on_save.iter().chain(formatters).enumerate() {
+            let code_actions_timeout = (formatter_ix < code_actions_on_save.len())
+                .then_some(settings.code_actions_on_save_timeout);
             let formatter = if formatter == &Formatter::Auto {
                 if settings.prettier.allowed {
                     zlog::trace!(logger => "Formatter set to auto: defaulting to prettier");
@@ -1504,14 +1688,22 @@ impl LocalLspStore {
                     let mut actions_and_servers = Vec::new();
 
                     for (index, (_, language_server)) in adapters_and_servers.iter().enumerate() {
-                        let actions_result = Self::get_server_code_actions_from_action_kinds(
+                        let timeout = code_actions_timeout
+                            .map(|timeout| cx.background_executor().timer(timeout));
+                        let request = Self::get_server_code_actions_from_action_kinds(
                             &lsp_store,
                             language_server.server_id(),
                             vec![code_action_kind.clone()],
                             &buffer.handle,
                             cx,
-                        )
-                        .await
+                        );
+                        let actions_result = match timeout {
+                            Some(timeout) => futures::select_biased! {
+                                actions = request.fuse() => actions,
+                                () = timeout.fuse() => Err(anyhow!("timed out")),
+                            },
+                            None => request.await,
+                        }
                         .with_context(|| {
                             format!(
                                 "Failed to resolve code action {:?} with language server {}",
@@ -2107,7 +2299,6 @@ impl LocalLspStore {
                     buffer_handle,
                     server_id,
                     None,
-                    None,
                     diagnostics,
                     Vec::new(),
                     cx,
@@ -2187,7 +2378,6 @@ impl LocalLspStore {
         &mut self,
         buffer: &Entity<Buffer>,
         server_id: LanguageServerId,
-        result_id: Option<String>,
         version: Option<i32>,
         new_diagnostics: Vec<DiagnosticEntry<Unclipped<PointUtf16>>>,
         reused_diagnostics: Vec<DiagnosticEntry<Unclipped<PointUtf16>>>,
@@ -2260,13 +2450,6 @@ impl LocalLspStore {
 
         let set = DiagnosticSet::new(sanitized_diagnostics, &snapshot);
         buffer.update(cx, |buffer, cx| {
-            if let Some(abs_path) = File::from_dyn(buffer.file()).map(|f| f.abs_path(cx)) {
-                self.buffer_pull_diagnostics_result_ids
-                    .entry(server_id)
-                    .or_default()
-                    .insert(abs_path, result_id);
-            }
-
             buffer.update_diagnostics(server_id, set, cx)
         });
 
@@ -2306,29 +2489,27 @@ impl LocalLspStore {
     ) {
         let buffer = buffer_handle.read(cx);
         let buffer_id = buffer.remote_id();
+        if buffer.large_file_mode() {
+            return;
+        }
 
-        let Some(file) = File::from_dyn(buffer.file()) else {
+        if buffer.file().is_none() && buffer.language().is_some() {
+            self.create_scratch_worktree(cx);
+        }
+        let buffer = buffer_handle.read(cx);
+        let Some((worktree_id, path, abs_path)) = self.lsp_document(buffer, cx) else {
             return;
         };
-        if !file.is_local() {
-            return;
-        }
 
-        let abs_path = file.abs_path(cx);
         let Some(uri) = file_path_to_lsp_url(&abs_path).log_err() else {
             return;
         };
         let initial_snapshot = buffer.text_snapshot();
-        let worktree_id = file.worktree_id(cx);
 
         let Some(language) = buffer.language().cloned() else {
             return;
         };
-        let path: Arc<RelPath> = file
-            .path()
-            .parent()
-            .map(Arc::from)
-            .unwrap_or_else(|| file.path().clone());
+        let path: Arc<RelPath> = path.parent().map(Arc::from).unwrap_or(path);
         let Some(worktree) = self
             .worktree_store
             .read(cx)
@@ -2849,6 +3030,12 @@ impl LocalLspStore {
             }));
         }
 
+        // Edits that create, rename or delete files aren't recorded, since reverting their text
+        // edits alone would leave the files inconsistent.
+        let mut buffer_edits = operations
+            .iter()
+            .all(|operation| matches!(operation, lsp::DocumentChangeOperation::Edit(_)))
+            .then(Vec::new);
         let mut project_transaction = ProjectTransaction::default();
         for operation in operations {
             match operation {
@@ -3016,21 +3203,42 @@ impl LocalLspStore {
                         })?
                         .await?;
 
+                    let uri = op.text_document.uri;
                     let transaction = buffer_to_edit.update(cx, |buffer, cx| {
+                        let old_snapshot = buffer.text_snapshot();
                         buffer.finalize_last_transaction();
                         buffer.start_transaction();
                         for (range, text) in edits {
                             buffer.edit([(range, text)], None, cx);
                         }
 
-                        buffer.end_transaction(cx).and_then(|transaction_id| {
+                        let transaction = buffer.end_transaction(cx).and_then(|transaction_id| {
                             if push_to_history {
                                 buffer.finalize_last_transaction();
                                 buffer.get_transaction(transaction_id).cloned()
                             } else {
                                 buffer.forget_transaction(transaction_id)
                             }
-                        })
+                        });
+                        if transaction.is_some()
+                            && push_to_history
+                            && let Some(buffer_edits) = &mut buffer_edits
+                        {
+                            buffer_edits.push(AppliedBufferEdit {
+                                uri,
+                                inverse_edits: buffer
+                                    .edits_since::<usize>(old_snapshot.version())
+                                    .map(|edit| {
+                                        let old_text = old_snapshot
+                                            .text_for_range(edit.old)
+                                            .collect::<String>();
+                                        (edit.new, Arc::from(old_text))
+                                    })
+                                    .collect(),
+                                edited_text_hash: text_hash(&buffer.text_snapshot()),
+                            });
+                        }
+                        transaction
                     })?;
                     if let Some(transaction) = transaction {
                         project_transaction.0.insert(buffer_to_edit, transaction);
@@ -3039,6 +3247,26 @@ impl LocalLspStore {
             }
         }
 
+        if let Some(buffer_edits) = buffer_edits
+            && !buffer_edits.is_empty()
+        {
+            this.update(cx, |this, _| {
+                if let Some(local) = this.as_local_mut() {
+                    local
+                        .workspace_edit_history
+                        .push(Arc::new(AppliedWorkspaceEdit {
+                            language_server_id: language_server.server_id(),
+                            buffer_edits,
+                        }));
+                    let excess = local
+                        .workspace_edit_history
+                        .len()
+                        .saturating_sub(MAX_WORKSPACE_EDIT_HISTORY);
+                    local.workspace_edit_history.drain(..excess);
+                }
+            })?;
+        }
+
         Ok(project_transaction)
     }
 
@@ -3106,8 +3334,7 @@ impl LocalLspStore {
             self.last_workspace_edits_by_language_server
                 .remove(server_id_to_remove);
             self.language_servers.remove(server_id_to_remove);
-            self.buffer_pull_diagnostics_result_ids
-                .remove(server_id_to_remove);
+            self.pull_diagnostics_result_ids.remove(server_id_to_remove);
             for buffer_servers in self.buffers_opened_in_servers.values_mut() {
                 buffer_servers.remove(server_id_to_remove);
             }
@@ -3604,6 +3831,12 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_command::<GetCompletions>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentHighlights>);
         client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentSymbols>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareCallHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetIncomingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetOutgoingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareTypeHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSupertypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSubtypes>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
@@ -3701,6 +3934,7 @@ impl LspStore {
                 language_server_ids: Default::default(),
                 language_servers: Default::default(),
                 last_workspace_edits_by_language_server: Default::default(),
+                workspace_edit_history: Default::default(),
                 language_server_watched_paths: Default::default(),
                 language_server_paths_watched_for_rename: Default::default(),
                 language_server_watcher_registrations: Default::default(),
@@ -3726,7 +3960,9 @@ impl LspStore {
                 toolchain_store,
                 registered_buffers: HashMap::default(),
                 buffers_opened_in_servers: HashMap::default(),
-                buffer_pull_diagnostics_result_ids: HashMap::default(),
+                pull_diagnostics_result_ids: HashMap::default(),
+                scratch_worktree: None,
+                scratch_worktree_task: None,
                 watched_manifest_filenames: ManifestProvidersStore::global(cx)
                     .manifest_file_names(),
             }),
@@ -3830,6 +4066,13 @@ impl LspStore {
                     if local.registered_buffers.contains_key(&buffer_id) {
                         local.unregister_old_buffer_from_language_servers(buffer, old_file, cx);
                     }
+                } else if old_file.is_none()
+                    && let Some(local) = self.as_local_mut()
+                    && local.registered_buffers.contains_key(&buffer_id)
+                    && let Some((_, _, abs_path)) = local.scratch_document(buffer.read(cx), cx)
+                    && let Some(file_url) = file_path_to_lsp_url(&abs_path).log_err()
+                {
+                    local.unregister_buffer_from_language_servers(buffer, &file_url, cx);
                 }
 
                 self.detect_language_for_buffer(buffer, cx);
@@ -3935,6 +4178,17 @@ impl LspStore {
                 self.on_buffer_saved(buffer, cx);
             }
 
+            language::BufferEvent::LargeFileModeChanged => {
+                if !buffer.read(cx).large_file_mode()
+                    && let Some(local) = self.as_local_mut()
+                    && local
+                        .registered_buffers
+                        .contains_key(&buffer.read(cx).remote_id())
+                {
+                    local.register_buffer_with_language_servers(&buffer, HashSet::default(), cx);
+                }
+            }
+
             _ => {}
         }
     }
@@ -3972,14 +4226,12 @@ impl LspStore {
                 *refcount += 1;
             }
 
-            // We run early exits on non-existing buffers AFTER we mark the buffer as registered in order to handle buffer saving.
+            // We run early exits on non-local buffers AFTER we mark the buffer as registered in order to handle buffer saving.
             // When a new unnamed buffer is created and saved, we will start loading it's language. Once the language is loaded, we go over all "language-less" buffers and try to fit that new language
-            // with them. However, we do that only for the buffers that we think are open in at least one editor; thus, we need to keep tab of unnamed buffers as well, even though they're not actually registered with any language
-            // servers in practice (we don't support non-file URI schemes in our LSP impl).
-            let Some(file) = File::from_dyn(buffer.read(cx).file()) else {
-                return handle;
-            };
-            if !file.is_local() {
+            // with them. However, we do that only for the buffers that we think are open in at least one editor; thus, we need to keep tab of unnamed buffers as well.
+            // Unnamed buffers are registered with language servers once they have a language, as documents in the scratch worktree (we don't support non-file URI schemes in our LSP impl).
+            let file = buffer.read(cx).file();
+            if file.is_some() && !File::from_dyn(file).is_some_and(|file| file.is_local()) {
                 return handle;
             }
 
@@ -4004,8 +4256,10 @@ impl LspStore {
                         let local = lsp_store.as_local_mut().unwrap();
                         local.registered_buffers.remove(&buffer_id);
                         local.buffers_opened_in_servers.remove(&buffer_id);
-                        if let Some(file) = File::from_dyn(buffer.read(cx).file()).cloned() {
-                            local.unregister_old_buffer_from_language_servers(buffer, &file, cx);
+                        if let Some((_, _, abs_path)) = local.lsp_document(buffer.read(cx), cx)
+                            && let Some(file_url) = file_path_to_lsp_url(&abs_path).log_err()
+                        {
+                            local.unregister_buffer_from_language_servers(buffer, &file_url, cx);
                         }
                     }
                 })
@@ -4180,8 +4434,7 @@ impl LspStore {
         let buffer_id = buffer.remote_id();
         if let Some(local_store) = self.as_local_mut()
             && local_store.registered_buffers.contains_key(&buffer_id)
-            && let Some(abs_path) =
-                File::from_dyn(buffer_file.as_ref()).map(|file| file.abs_path(cx))
+            && let Some((_, _, abs_path)) = local_store.lsp_document(buffer, cx)
             && let Some(file_url) = file_path_to_lsp_url(&abs_path).log_err()
         {
             local_store.unregister_buffer_from_language_servers(buffer_entity, &file_url, cx);
@@ -4199,18 +4452,13 @@ impl LspStore {
             language_settings(Some(new_language.name()), buffer_file.as_ref(), cx).into_owned();
         let buffer_file = File::from_dyn(buffer_file.as_ref());
 
-        let worktree_id = if let Some(file) = buffer_file {
-            let worktree = file.worktree.clone();
-
-            if let Some(local) = self.as_local_mut()
-                && local.registered_buffers.contains_key(&buffer_id)
-            {
-                local.register_buffer_with_language_servers(buffer_entity, HashSet::default(), cx);
-            }
-            Some(worktree.read(cx).id())
-        } else {
-            None
-        };
+        if let Some(local) = self.as_local_mut()
+            && local.registered_buffers.contains_key(&buffer_id)
+            && (buffer_file.is_some() || buffer_entity.read(cx).file().is_none())
+        {
+            local.register_buffer_with_language_servers(buffer_entity, HashSet::default(), cx);
+        }
+        let worktree_id = buffer_file.map(|file| file.worktree.read(cx).id());
 
         if settings.prettier.allowed
             && let Some(prettier_plugins) = prettier_store::prettier_plugins_for_language(&settings)
@@ -4355,14 +4603,16 @@ impl LspStore {
             return Task::ready(Ok(Default::default()));
         };
 
-        let file = File::from_dyn(buffer.read(cx).file()).and_then(File::as_local);
+        let abs_path = self
+            .as_local()
+            .and_then(|local| local.lsp_document(buffer.read(cx), cx));
 
-        let Some(file) = file else {
+        let Some((_, _, abs_path)) = abs_path else {
             return Task::ready(Ok(Default::default()));
         };
 
         let lsp_params = match request.to_lsp_params_or_response(
-            &file.abs_path(cx),
+            &abs_path,
             buffer.read(cx),
             &language_server,
             cx,
@@ -4774,6 +5024,86 @@ impl LspStore {
         }
     }
 
+    /// Reverts the last workspace edit that a language server applied, such as a rename, in all
+    /// the files it edited, reopening the ones that were closed since. Nothing is reverted when
+    /// any of the files changed after the edit.
+    pub fn revert_last_workspace_edit(
+        &mut self,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ProjectTransaction>> {
+        let Some(local) = self.as_local() else {
+            return Task::ready(Err(anyhow!(
+                "reverting workspace edits is only supported in local projects"
+            )));
+        };
+        let Some(workspace_edit) = local.workspace_edit_history.last().cloned() else {
+            return Task::ready(Err(anyhow!("no workspace edit to revert")));
+        };
+
+        cx.spawn(async move |this, cx| {
+            let mut buffers = Vec::new();
+            for buffer_edit in &workspace_edit.buffer_edits {
+                let buffer = this
+                    .update(cx, |this, cx| {
+                        this.open_local_buffer_via_lsp(
+                            buffer_edit.uri.clone(),
+                            workspace_edit.language_server_id,
+                            cx,
+                        )
+                    })?
+                    .await?;
+                buffers.push(buffer);
+            }
+
+            // A file edited more than once by the workspace edit only has to match the hash
+            // recorded after its last edit.
+            let mut checked_buffers = HashSet::default();
+            for (buffer, buffer_edit) in buffers.iter().zip(&workspace_edit.buffer_edits).rev() {
+                if !checked_buffers.insert(buffer.entity_id()) {
+                    continue;
+                }
+                let changed = buffer.read_with(cx, |buffer, _| {
+                    text_hash(&buffer.text_snapshot()) != buffer_edit.edited_text_hash
+                })?;
+                if changed {
+                    let path = buffer_edit
+                        .uri
+                        .to_file_path()
+                        .map_err(|()| anyhow!("can't convert URI to path"))?;
+                    anyhow::bail!(
+                        "{} changed since the workspace edit was applied",
+                        path.display()
+                    );
+                }
+            }
+
+            let mut project_transaction = ProjectTransaction::default();
+            for (buffer, buffer_edit) in buffers.into_iter().zip(&workspace_edit.buffer_edits).rev()
+            {
+                let transaction = buffer.update(cx, |buffer, cx| {
+                    buffer.finalize_last_transaction();
+                    buffer.start_transaction();
+                    buffer.edit(buffer_edit.inverse_edits.iter().cloned(), None, cx);
+                    let transaction_id = buffer.end_transaction(cx)?;
+                    buffer.finalize_last_transaction();
+                    buffer.get_transaction(transaction_id).cloned()
+                })?;
+                if let Some(transaction) = transaction {
+                    project_transaction.0.insert(buffer, transaction);
+                }
+            }
+
+            this.update(cx, |this, _| {
+                if let Some(local) = this.as_local_mut() {
+                    local
+                        .workspace_edit_history
+                        .retain(|edit| !Arc::ptr_eq(edit, &workspace_edit));
+                }
+            })?;
+            Ok(project_transaction)
+        })
+    }
+
     pub fn resolve_inlay_hint(
         &self,
         mut hint: InlayHint,
@@ -5075,14 +5405,7 @@ impl LspStore {
         cx: &mut Context<Self>,
     ) -> Task<Result<Option<Transaction>>> {
         let options = buffer.update(cx, |buffer, cx| {
-            lsp_command::lsp_formatting_options(
-                language_settings(
-                    buffer.language_at(position).map(|l| l.name()),
-                    buffer.file(),
-                    cx,
-                )
-                .as_ref(),
-            )
+            lsp_command::lsp_formatting_options(buffer.settings_at(position, cx).as_ref())
         });
 
         cx.spawn(async move |this, cx| {
@@ -6186,14 +6509,24 @@ impl LspStore {
                 }
             })
         } else {
-            let Some(server) = buffer_handle.update(cx, |buffer, cx| {
+            let Some((server, auto_import_placement)) = buffer_handle.update(cx, |buffer, cx| {
                 let completion = &completions.borrow()[completion_index];
                 let server_id = completion.source.server_id()?;
-                Some(
+                let completion_settings = &language_settings(
+                    buffer.language().map(|language| language.name()),
+                    buffer.file(),
+                    cx,
+                )
+                .completions;
+                if !completion_settings.auto_imports {
+                    return None;
+                }
+                Some((
                     self.language_server_for_local_buffer(buffer, server_id, cx)?
                         .1
                         .clone(),
-                )
+                    completion_settings.auto_import_placement,
+                ))
             }) else {
                 return Task::ready(Ok(None));
             };
@@ -6229,7 +6562,37 @@ impl LspStore {
                         buffer.finalize_last_transaction();
                         buffer.start_transaction();
 
-                        for (range, text) in edits {
+                        let snapshot = buffer.snapshot();
+                        let append_imports_after = match auto_import_placement {
+                            AutoImportPlacement::Merge => None,
+                            AutoImportPlacement::Append => {
+                                let completion_start =
+                                    completion.replace_range.start.to_offset(&snapshot);
+                                snapshot
+                                    .import_ranges(0..completion_start)
+                                    .into_iter()
+                                    .filter(|range| range.end <= completion_start)
+                                    .last()
+                                    .map(|range| {
+                                        let row = range.end.to_point(&snapshot).row;
+                                        snapshot
+                                            .anchor_after(Point::new(row, snapshot.line_len(row)))
+                                    })
+                            }
+                        };
+
+                        for (mut range, mut text) in edits {
+                            // Move edits inserting whole lines, which add new import statements,
+                            // after the last existing import.
+                            if let Some(anchor) = append_imports_after
+                                && range.to_offset(&snapshot).is_empty()
+                                && range.start.to_point(&snapshot).column == 0
+                                && text.ends_with('\n')
+                            {
+                                range = anchor..anchor;
+                                text = format!("\n{}", text.trim_end_matches('\n')).into();
+                            }
+
                             let primary = &completion.replace_range;
 
                             // Special case: if both ranges start at the very beginning of the file (line 0, column 0),
@@ -7097,8 +7460,7 @@ impl LspStore {
         })?;
 
         let buffer = buffer.read(cx);
-        let file = File::from_dyn(buffer.file())?;
-        let abs_path = file.as_local()?.abs_path(cx);
+        let (_, _, abs_path) = self.as_local()?.lsp_document(buffer, cx)?;
         let uri = lsp::Uri::from_file_path(abs_path).unwrap();
         let next_snapshot = buffer.text_snapshot();
         for language_server in language_servers {
@@ -7530,7 +7892,26 @@ impl LspStore {
                 path: relative_path,
             };
 
-            if let Some(buffer_handle) = self.buffer_store.read(cx).get_by_path(&project_path) {
+            if let Some(local) = self.as_local_mut() {
+                local.set_pull_diagnostics_result_id(
+                    server_id,
+                    update.diagnostics.document_abs_path.clone(),
+                    update.result_id.take(),
+                );
+            }
+
+            let buffer_handle = self
+                .buffer_store
+                .read(cx)
+                .get_by_path(&project_path)
+                .or_else(|| {
+                    let buffer_id = self.as_local()?.scratch_buffer_id(&project_path, cx)?;
+                    self.buffer_store
+                        .read(cx)
+                        .get(buffer_id)
+                        .filter(|buffer| buffer.read(cx).file().is_none())
+                });
+            if let Some(buffer_handle) = buffer_handle {
                 let snapshot = buffer_handle.read(cx).snapshot();
                 let buffer = buffer_handle.read(cx);
                 let reused_diagnostics = buffer
@@ -7552,7 +7933,6 @@ impl LspStore {
                     .update_buffer_diagnostics(
                         &buffer_handle,
                         server_id,
-                        update.result_id,
                         update.diagnostics.version,
                         update.diagnostics.diagnostics.clone(),
                         reused_diagnostics.clone(),
@@ -11042,7 +11422,7 @@ impl LspStore {
             buffer_lens.lens.remove(&for_server);
         }
         if let Some(local) = self.as_local_mut() {
-            local.buffer_pull_diagnostics_result_ids.remove(&for_server);
+            local.pull_diagnostics_result_ids.remove(&for_server);
             for buffer_servers in local.buffers_opened_in_servers.values_mut() {
                 buffer_servers.remove(&for_server);
             }
@@ -11062,10 +11442,10 @@ impl LspStore {
             .and_then(|b| File::from_dyn(b.read(cx).file()))
             .map(|f| f.abs_path(cx))?;
         self.as_local()?
-            .buffer_pull_diagnostics_result_ids
+            .pull_diagnostics_result_ids
             .get(&server_id)?
-            .get(&abs_path)?
-            .clone()
+            .get(&abs_path)
+            .cloned()
     }
 
     pub fn all_result_ids(&self, server_id: LanguageServerId) -> HashMap<PathBuf, String> {
@@ -11073,11 +11453,11 @@ impl LspStore {
             return HashMap::default();
         };
         local
-            .buffer_pull_diagnostics_result_ids
+            .pull_diagnostics_result_ids
             .get(&server_id)
             .into_iter()
             .flatten()
-            .filter_map(|(abs_path, result_id)| Some((abs_path.clone(), result_id.clone()?)))
+            .map(|(abs_path, result_id)| (abs_path.clone(), result_id.clone()))
             .collect()
     }
 
@@ -11122,7 +11502,7 @@ impl LspStore {
     ) {
         let workspace_diagnostics =
             GetDocumentDiagnostics::deserialize_workspace_diagnostics_report(report, server_id);
-        let mut unchanged_buffers = HashSet::default();
+        let mut unchanged_result_ids = Vec::new();
         let mut changed_buffers = HashSet::default();
         let workspace_diagnostics_updates = workspace_diagnostics
             .into_iter()
@@ -11131,27 +11511,33 @@ impl LspStore {
                     LspPullDiagnostics::Response {
                         server_id,
                         uri,
+                        diagnostics: PulledDiagnostics::Unchanged { result_id },
+                    } => {
+                        unchanged_result_ids.push((server_id, uri, result_id));
+                        None
+                    }
+                    LspPullDiagnostics::Response {
+                        server_id,
+                        uri,
+                        diagnostics:
+                            PulledDiagnostics::Changed {
+                                result_id,
+                                diagnostics,
+                            },
+                    } => Some((
+                        server_id,
+                        uri,
+                        result_id,
                         diagnostics,
-                    } => Some((server_id, uri, diagnostics, workspace_diagnostics.version)),
+                        workspace_diagnostics.version,
+                    )),
                     LspPullDiagnostics::Default => None,
                 },
             )
             .fold(
                 HashMap::default(),
-                |mut acc, (server_id, uri, diagnostics, version)| {
-                    let (result_id, diagnostics) = match diagnostics {
-                        PulledDiagnostics::Unchanged { result_id } => {
-                            unchanged_buffers.insert(uri.clone());
-                            (Some(result_id), Vec::new())
-                        }
-                        PulledDiagnostics::Changed {
-                            result_id,
-                            diagnostics,
-                        } => {
-                            changed_buffers.insert(uri.clone());
-                            (result_id, diagnostics)
-                        }
-                    };
+                |mut acc, (server_id, uri, result_id, diagnostics, version)| {
+                    changed_buffers.insert(uri.clone());
                     let disk_based_sources = Cow::Owned(
                         self.language_server_adapter_for_id(server_id)
                             .as_ref()
@@ -11185,22 +11571,25 @@ impl LspStore {
                             let abs_path = file.as_local()?.abs_path(cx);
                             lsp::Uri::from_file_path(abs_path).ok()
                         })
-                        .is_none_or(|buffer_uri| {
-                            unchanged_buffers.contains(&buffer_uri)
-                                || match old_diagnostic.source_kind {
-                                    DiagnosticSourceKind::Pulled => {
-                                        !changed_buffers.contains(&buffer_uri)
-                                    }
-                                    DiagnosticSourceKind::Other | DiagnosticSourceKind::Pushed => {
-                                        true
-                                    }
-                                }
+                        .is_none_or(|buffer_uri| match old_diagnostic.source_kind {
+                            DiagnosticSourceKind::Pulled => !changed_buffers.contains(&buffer_uri),
+                            DiagnosticSourceKind::Other | DiagnosticSourceKind::Pushed => true,
                         })
                 },
                 cx,
             )
             .log_err();
         }
+
+        // Unchanged reports don't carry any diagnostics, so the ones stored for their documents
+        // are kept, whether or not the documents are open.
+        if let Some(local) = self.as_local_mut() {
+            for (server_id, uri, result_id) in unchanged_result_ids {
+                if let Ok(abs_path) = uri.to_file_path() {
+                    local.set_pull_diagnostics_result_id(server_id, abs_path, Some(result_id));
+                }
+            }
+        }
     }
 
     fn register_server_capabilities(
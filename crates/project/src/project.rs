@@ -1,4 +1,5 @@
 pub mod agent_server_store;
+pub mod bookmark_store;
 pub mod buffer_store;
 mod color_extractor;
 pub mod connection_manager;
@@ -48,6 +49,7 @@ pub use git_store::{
 pub use manifest_tree::ManifestTree;
 
 use anyhow::{Context as _, Result, anyhow};
+use bookmark_store::BookmarkStore;
 use buffer_store::{BufferStore, BufferStoreEvent};
 use client::{Client, Collaborator, PendingEntitySubscription, TypedEnvelope, UserStore, proto};
 use clock::ReplicaId;
@@ -109,6 +111,7 @@ use snippet_provider::SnippetProvider;
 use std::{
     borrow::Cow,
     collections::BTreeMap,
+    mem,
     ops::Range,
     path::{Path, PathBuf},
     pin::pin,
@@ -186,6 +189,7 @@ pub struct Project {
     agent_server_store: Entity<AgentServerStore>,
 
     breakpoint_store: Entity<BreakpointStore>,
+    bookmark_store: Entity<BookmarkStore>,
     collab_client: Arc<client::Client>,
     join_project_response_message_id: u32,
     task_store: Entity<TaskStore>,
@@ -203,6 +207,8 @@ pub struct Project {
     lsp_store: Entity<LspStore>,
     _subscriptions: Vec<gpui::Subscription>,
     buffers_needing_diff: HashSet<WeakEntity<Buffer>>,
+    /// The timestamps of the local edits that the remote server hasn't acknowledged yet.
+    unconfirmed_operations: HashMap<BufferId, Vec<clock::Lamport>>,
     git_diff_debouncer: DebouncedDelay<Self>,
     remotely_created_models: Arc<Mutex<RemotelyCreatedModels>>,
     terminals: Terminals,
@@ -339,6 +345,9 @@ pub enum Event {
     Rejoined,
     RefreshInlayHints,
     RefreshCodeLens,
+    /// The edits to the buffer that the remote server hasn't acknowledged yet changed, because
+    /// it acknowledged some, or because they stopped being tracked on disconnect.
+    UnconfirmedEditsChanged(BufferId),
     RevealInProjectPanel(ProjectEntryId),
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
     ExpandedAllForEntry(WorktreeId, ProjectEntryId),
@@ -762,6 +771,38 @@ pub struct DocumentSymbol {
     pub children: Vec<DocumentSymbol>,
 }
 
+/// A function or method that can be the root of a call hierarchy.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub detail: Option<String>,
+    pub kind: lsp::SymbolKind,
+    /// The location of the item's name.
+    pub location: Location,
+    /// The item as returned by the language server, which is needed to query its calls.
+    pub(crate) lsp_item: lsp::CallHierarchyItem,
+}
+
+#[derive(Clone, Debug)]
+pub struct CallHierarchyCall {
+    /// The caller for incoming calls, or the callee for outgoing calls.
+    pub item: CallHierarchyItem,
+    /// The locations of the calls, which are in the caller's buffer in both directions.
+    pub call_sites: Vec<Location>,
+}
+
+/// A type that can be the root of a type hierarchy, or one of its supertypes or subtypes.
+#[derive(Clone, Debug)]
+pub struct TypeHierarchyItem {
+    pub name: String,
+    pub detail: Option<String>,
+    pub kind: lsp::SymbolKind,
+    /// The location of the type's name.
+    pub location: Location,
+    /// The item as returned by the language server, which is needed to query related types.
+    pub(crate) lsp_item: lsp::TypeHierarchyItem,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HoverBlock {
     pub text: String,
@@ -1031,6 +1072,7 @@ impl Project {
         ToolchainStore::init(&client);
         DapStore::init(&client, cx);
         BreakpointStore::init(&client);
+        BookmarkStore::init(&client);
         context_server_store::init(cx);
     }
 
@@ -1074,6 +1116,8 @@ impl Project {
 
             let breakpoint_store =
                 cx.new(|_| BreakpointStore::local(worktree_store.clone(), buffer_store.clone()));
+            let bookmark_store =
+                cx.new(|_| BookmarkStore::local(worktree_store.clone(), buffer_store.clone()));
 
             let dap_store = cx.new(|cx| {
                 DapStore::new_local(
@@ -1189,10 +1233,12 @@ impl Project {
                 fs,
                 remote_client: None,
                 breakpoint_store,
+                bookmark_store,
                 dap_store,
                 agent_server_store,
 
                 buffers_needing_diff: Default::default(),
+                unconfirmed_operations: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
                 terminals: Terminals {
                     local_handles: Vec::new(),
@@ -1306,6 +1352,8 @@ impl Project {
 
             let breakpoint_store =
                 cx.new(|_| BreakpointStore::remote(REMOTE_SERVER_PROJECT_ID, remote_proto.clone()));
+            let bookmark_store =
+                cx.new(|_| BookmarkStore::remote(worktree_store.clone(), buffer_store.clone()));
 
             let dap_store = cx.new(|cx| {
                 DapStore::new_remote(
@@ -1344,6 +1392,7 @@ impl Project {
                 lsp_store,
                 context_server_store,
                 breakpoint_store,
+                bookmark_store,
                 dap_store,
                 join_project_response_message_id: 0,
                 client_state: ProjectClientState::Local,
@@ -1379,6 +1428,7 @@ impl Project {
                 fs,
                 remote_client: Some(remote.clone()),
                 buffers_needing_diff: Default::default(),
+                unconfirmed_operations: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
                 terminals: Terminals {
                     local_handles: Vec::new(),
@@ -1507,6 +1557,7 @@ impl Project {
 
         let breakpoint_store =
             cx.new(|_| BreakpointStore::remote(remote_id, client.clone().into()))?;
+        let bookmark_store = cx.new(|_| BookmarkStore::collab(remote_id, client.clone().into()))?;
         let dap_store = cx.new(|cx| {
             DapStore::new_collab(
                 remote_id,
@@ -1630,10 +1681,12 @@ impl Project {
                     replica_id,
                 },
                 breakpoint_store,
+                bookmark_store,
                 dap_store: dap_store.clone(),
                 git_store: git_store.clone(),
                 agent_server_store,
                 buffers_needing_diff: Default::default(),
+                unconfirmed_operations: Default::default(),
                 git_diff_debouncer: DebouncedDelay::new(),
                 terminals: Terminals {
                     local_handles: Vec::new(),
@@ -1837,6 +1890,10 @@ impl Project {
         self.breakpoint_store.clone()
     }
 
+    pub fn bookmark_store(&self) -> Entity<BookmarkStore> {
+        self.bookmark_store.clone()
+    }
+
     pub fn active_debug_session(&self, cx: &App) -> Option<(Entity<Session>, ActiveStackFrame)> {
         let active_position = self.breakpoint_store.read(cx).active_position()?;
         let session = self
@@ -2320,6 +2377,10 @@ impl Project {
         self.breakpoint_store.update(cx, |breakpoint_store, _| {
             breakpoint_store.shared(project_id, self.collab_client.clone().into())
         });
+        let user_id = self.user_store.read(cx).current_user().map(|user| user.id);
+        self.bookmark_store.update(cx, |bookmark_store, _| {
+            bookmark_store.shared(project_id, self.collab_client.clone().into(), user_id)
+        });
         self.dap_store.update(cx, |dap_store, cx| {
             dap_store.shared(project_id, self.collab_client.clone().into(), cx);
         });
@@ -2426,6 +2487,9 @@ impl Project {
             self.breakpoint_store.update(cx, |breakpoint_store, cx| {
                 breakpoint_store.unshared(cx);
             });
+            self.bookmark_store.update(cx, |bookmark_store, cx| {
+                bookmark_store.unshared(cx);
+            });
             self.dap_store.update(cx, |dap_store, cx| {
                 dap_store.unshared(cx);
             });
@@ -3087,6 +3151,10 @@ impl Project {
                 self.lsp_store.update(cx, |lsp_store, _cx| {
                     lsp_store.disconnected_from_ssh_remote()
                 });
+                // The server won't acknowledge the pending edits anymore.
+                for buffer_id in mem::take(&mut self.unconfirmed_operations).into_keys() {
+                    cx.emit(Event::UnconfirmedEditsChanged(buffer_id));
+                }
                 cx.emit(Event::DisconnectedFromSshRemote);
             }
         }
@@ -3216,18 +3284,44 @@ impl Project {
                 operation,
                 is_local: true,
             } => {
+                let timestamp = match operation {
+                    language::Operation::Buffer(operation) => Some(operation.timestamp()),
+                    _ => None,
+                };
                 let operation = language::proto::serialize_operation(operation);
 
                 if let Some(remote) = &self.remote_client {
-                    remote
-                        .read(cx)
-                        .proto_client()
-                        .send(proto::UpdateBuffer {
-                            project_id: 0,
-                            buffer_id: buffer_id.to_proto(),
-                            operations: vec![operation.clone()],
+                    let message = proto::UpdateBuffer {
+                        project_id: 0,
+                        buffer_id: buffer_id.to_proto(),
+                        operations: vec![operation.clone()],
+                    };
+                    let client = remote.read(cx).proto_client();
+                    if let Some(timestamp) = timestamp
+                        && !remote.read(cx).is_disconnected()
+                    {
+                        // Track edits until the remote server applies them, so that editors can
+                        // mark text that was typed but isn't confirmed yet.
+                        self.unconfirmed_operations
+                            .entry(buffer_id)
+                            .or_default()
+                            .push(timestamp);
+                        // Requests are resent after a reconnect, so one that fails was lost with
+                        // the connection, and its edit stays unconfirmed until the disconnect
+                        // drops it.
+                        let request = client.request(message);
+                        cx.spawn(async move |this, cx| {
+                            if request.await.is_ok() {
+                                this.update(cx, |this, cx| {
+                                    this.confirm_operation(buffer_id, timestamp, cx)
+                                })
+                                .ok();
+                            }
                         })
-                        .ok();
+                        .detach();
+                    } else {
+                        client.send(message).ok();
+                    }
                 }
 
                 self.enqueue_buffer_ordered_message(BufferOrderedMessage::Operation {
@@ -3663,6 +3757,102 @@ impl Project {
         )
     }
 
+    /// Returns the items that can be the root of a call hierarchy at `position`.
+    pub fn prepare_call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareCallHierarchy { position },
+            cx,
+        )
+    }
+
+    pub fn incoming_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        let request = GetIncomingCalls {
+            item: item.lsp_item.clone(),
+        };
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            request,
+            cx,
+        )
+    }
+
+    pub fn outgoing_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        let request = GetOutgoingCalls {
+            item: item.lsp_item.clone(),
+        };
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            request,
+            cx,
+        )
+    }
+
+    /// Returns the items that can be the root of a type hierarchy at `position`.
+    pub fn prepare_type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareTypeHierarchy { position },
+            cx,
+        )
+    }
+
+    pub fn supertypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let request = GetSupertypes {
+            item: item.lsp_item.clone(),
+        };
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            request,
+            cx,
+        )
+    }
+
+    pub fn subtypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let request = GetSubtypes {
+            item: item.lsp_item.clone(),
+        };
+        self.request_lsp(
+            item.location.buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            request,
+            cx,
+        )
+    }
+
     pub fn symbols(&self, query: &str, cx: &mut Context<Self>) -> Task<Result<Vec<Symbol>>> {
         self.lsp_store
             .update(cx, |lsp_store, cx| lsp_store.symbols(query, cx))
@@ -3868,6 +4058,16 @@ impl Project {
         )
     }
 
+    /// Reverts the last workspace edit that a language server applied, such as a rename, unless
+    /// one of the files it edited changed since.
+    pub fn revert_last_workspace_edit(
+        &mut self,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<ProjectTransaction>> {
+        self.lsp_store
+            .update(cx, |lsp_store, cx| lsp_store.revert_last_workspace_edit(cx))
+    }
+
     pub fn on_type_format<T: ToPointUtf16>(
         &mut self,
         buffer: Entity<Buffer>,
@@ -4618,6 +4818,7 @@ impl Project {
                 buffer_store.forget_shared_buffers_for(&collaborator.peer_id);
             });
             this.breakpoint_store.read(cx).broadcast();
+            this.bookmark_store.read(cx).broadcast();
             cx.emit(Event::CollaboratorJoined(collaborator.peer_id));
             this.collaborators
                 .insert(collaborator.peer_id, collaborator);
@@ -5151,6 +5352,31 @@ impl Project {
     }
 
     pub fn any_language_server_supports_inlay_hints(&self, buffer: &Buffer, cx: &mut App) -> bool {
+        self.any_language_server_has_capability(buffer, cx, InlayHints::check_capabilities)
+    }
+
+    pub fn any_language_server_supports_document_highlights(
+        &self,
+        buffer: &Buffer,
+        cx: &mut App,
+    ) -> bool {
+        self.any_language_server_has_capability(buffer, cx, |capabilities| {
+            capabilities
+                .document_highlight_provider
+                .as_ref()
+                .is_some_and(|capability| match capability {
+                    lsp::OneOf::Left(supported) => *supported,
+                    lsp::OneOf::Right(_) => true,
+                })
+        })
+    }
+
+    fn any_language_server_has_capability(
+        &self,
+        buffer: &Buffer,
+        cx: &mut App,
+        check_capabilities: impl Fn(&lsp::ServerCapabilities) -> bool,
+    ) -> bool {
         let Some(language) = buffer.language().cloned() else {
             return false;
         };
@@ -5169,7 +5395,7 @@ impl Project {
                         .then_some(server_id)
                 })
                 .filter_map(|server_id| lsp_store.lsp_server_capabilities.get(&server_id))
-                .any(InlayHints::check_capabilities)
+                .any(check_capabilities)
         })
     }
 
@@ -5303,6 +5529,53 @@ impl Project {
         cx.emit(Event::AgentLocationChanged);
     }
 
+    fn confirm_operation(
+        &mut self,
+        buffer_id: BufferId,
+        timestamp: clock::Lamport,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(timestamps) = self.unconfirmed_operations.get_mut(&buffer_id) else {
+            return;
+        };
+        timestamps.retain(|pending| *pending != timestamp);
+        if timestamps.is_empty() {
+            self.unconfirmed_operations.remove(&buffer_id);
+        }
+        cx.emit(Event::UnconfirmedEditsChanged(buffer_id));
+    }
+
+    /// Returns the ranges of text inserted into `buffer` that the remote server hasn't
+    /// acknowledged yet.
+    pub fn unconfirmed_edits(&self, buffer: &Entity<Buffer>, cx: &App) -> Vec<Range<Anchor>> {
+        let buffer = buffer.read(cx);
+        let Some(oldest) = self
+            .unconfirmed_operations
+            .get(&buffer.remote_id())
+            .and_then(|timestamps| timestamps.iter().min_by_key(|timestamp| timestamp.value))
+        else {
+            return Vec::new();
+        };
+
+        // The version of the buffer that only includes the edits acknowledged so far.
+        let mut confirmed_version = clock::Global::new();
+        for timestamp in buffer.version().iter() {
+            if timestamp.replica_id == oldest.replica_id {
+                confirmed_version.observe(clock::Lamport {
+                    replica_id: oldest.replica_id,
+                    value: oldest.value.saturating_sub(1),
+                });
+            } else {
+                confirmed_version.observe(timestamp);
+            }
+        }
+        buffer
+            .anchored_edits_since::<usize>(&confirmed_version)
+            .filter(|(edit, _)| !edit.new.is_empty())
+            .map(|(_, range)| range)
+            .collect()
+    }
+
     pub fn agent_location(&self) -> Option<AgentLocation> {
         self.agent_location.clone()
     }
@@ -5346,7 +5619,13 @@ impl Project {
             worktree
                 .update(cx, |worktree, cx| {
                     let line_ending = text::LineEnding::detect(&new_text);
-                    worktree.write_file(rel_path.clone(), new_text.into(), line_ending, cx)
+                    worktree.write_file(
+                        rel_path.clone(),
+                        new_text.into(),
+                        line_ending,
+                        file.encoding,
+                        cx,
+                    )
                 })?
                 .await
                 .context("Failed to write settings file")?;
@@ -72,6 +72,9 @@ pub struct ProjectSettings {
 
     /// Configuration for session-related features
     pub session: SessionSettings,
+
+    /// Configuration for opening very large files
+    pub large_file: LargeFileSettings,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -85,6 +88,32 @@ pub struct SessionSettings {
     pub restore_unsaved_buffers: bool,
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct LargeFileSettings {
+    /// Whether to open very large files in large file mode, which disables
+    /// syntax highlighting, language servers, inlay hints and soft wrapping.
+    ///
+    /// Default: true
+    pub enabled: bool,
+    /// The file size, in megabytes, above which a file is opened in large file mode.
+    ///
+    /// Default: 50
+    pub max_file_size_mb: u64,
+    /// The number of lines above which a file is opened in large file mode.
+    ///
+    /// Default: 500000
+    pub max_line_count: u32,
+}
+
+impl LargeFileSettings {
+    /// Whether a file with the given size in bytes and number of lines should be opened in large file mode.
+    pub fn is_large_file(&self, len: usize, line_count: u32) -> bool {
+        self.enabled
+            && (len as u64 > self.max_file_size_mb.saturating_mul(1024 * 1024)
+                || line_count > self.max_line_count)
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct NodeBinarySettings {
     /// The path to the Node binary.
@@ -391,6 +420,10 @@ pub struct DiagnosticsSettings {
     /// Whether or not to include warning diagnostics.
     pub include_warnings: bool,
 
+    /// Whether going to the next or previous diagnostic skips warnings, information and hints
+    /// while there are errors in the file.
+    pub skip_warnings_when_errors_exist: bool,
+
     /// Settings for using LSP pull diagnostics mechanism in Zed.
     pub lsp_pull_diagnostics: LspPullDiagnosticsSettings,
 
@@ -502,6 +535,9 @@ impl Settings for ProjectSettings {
             diagnostics: DiagnosticsSettings {
                 button: diagnostics.button.unwrap(),
                 include_warnings: diagnostics.include_warnings.unwrap(),
+                skip_warnings_when_errors_exist: diagnostics
+                    .skip_warnings_when_errors_exist
+                    .unwrap(),
                 lsp_pull_diagnostics: LspPullDiagnosticsSettings {
                     enabled: lsp_pull_diagnostics.enabled.unwrap(),
                     debounce_ms: lsp_pull_diagnostics.debounce_ms.unwrap(),
@@ -520,6 +556,14 @@ impl Settings for ProjectSettings {
             session: SessionSettings {
                 restore_unsaved_buffers: content.session.unwrap().restore_unsaved_buffers.unwrap(),
             },
+            large_file: {
+                let large_file = content.large_file.unwrap();
+                LargeFileSettings {
+                    enabled: large_file.enabled.unwrap(),
+                    max_file_size_mb: large_file.max_file_size_mb.unwrap(),
+                    max_line_count: large_file.max_line_count.unwrap(),
+                }
+            },
         }
     }
 
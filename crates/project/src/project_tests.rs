@@ -35,7 +35,7 @@ use lsp::{
     Uri, WillRenameFiles, notification::DidRenameFiles,
 };
 use parking_lot::Mutex;
-use paths::{config_dir, global_gitignore_path, tasks_file};
+use paths::{config_dir, global_gitignore_path, scratch_dir, tasks_file};
 use postage::stream::Stream as _;
 use pretty_assertions::{assert_eq, assert_matches};
 use rand::{Rng as _, rngs::StdRng};
@@ -226,7 +226,10 @@ async fn test_editorconfig_support(cx: &mut gpui::TestAppContext) {
         assert_eq!(Some(settings_a.tab_size), NonZeroU32::new(3));
         assert_eq!(settings_a.hard_tabs, true);
         assert_eq!(settings_a.ensure_final_newline_on_save, true);
-        assert_eq!(settings_a.remove_trailing_whitespace_on_save, true);
+        assert_eq!(
+            settings_a.remove_trailing_whitespace_on_save,
+            settings::RemoveTrailingWhitespaceOnSave::All
+        );
         assert_eq!(settings_a.preferred_line_length, 120);
 
         // .editorconfig in b/ overrides .editorconfig in root
@@ -2732,6 +2735,158 @@ async fn test_diagnostics_from_multiple_language_servers(cx: &mut gpui::TestAppC
     });
 }
 
+#[gpui::test]
+async fn test_workspace_pull_diagnostics_result_ids(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "main.rs": "fn main() {}",
+            "lib.rs": "fn lib() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store());
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                diagnostic_provider: Some(lsp::DiagnosticServerCapabilities::Options(
+                    lsp::DiagnosticOptions {
+                        identifier: None,
+                        inter_file_dependencies: false,
+                        workspace_diagnostics: true,
+                        work_done_progress_options: Default::default(),
+                    },
+                )),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (_buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/main.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let fake_server = fake_servers.next().await.unwrap();
+    fake_server.set_request_handler::<lsp::request::DocumentDiagnosticRequest, _, _>(
+        |_, _| async move {
+            Ok(lsp::DocumentDiagnosticReportResult::Report(
+                lsp::DocumentDiagnosticReport::Full(lsp::RelatedFullDocumentDiagnosticReport {
+                    related_documents: None,
+                    full_document_diagnostic_report: lsp::FullDocumentDiagnosticReport {
+                        result_id: None,
+                        items: Vec::new(),
+                    },
+                }),
+            ))
+        },
+    );
+
+    // Only reports a full set of diagnostics for `lib.rs` if it wasn't reported before.
+    let lib_uri = lsp::Uri::from_file_path(path!("/dir/lib.rs")).unwrap();
+    let previous_result_ids = Arc::new(Mutex::new(Vec::new()));
+    fake_server.set_request_handler::<lsp::request::WorkspaceDiagnosticRequest, _, _>({
+        let previous_result_ids = previous_result_ids.clone();
+        move |params, _| {
+            let lib_uri = lib_uri.clone();
+            *previous_result_ids.lock() = params
+                .previous_result_ids
+                .iter()
+                .map(|id| (id.uri.clone(), id.value.clone()))
+                .collect::<Vec<_>>();
+            let reported_before = params
+                .previous_result_ids
+                .iter()
+                .any(|id| id.uri == lib_uri && id.value == "lib-1");
+            async move {
+                let report = if reported_before {
+                    lsp::WorkspaceDocumentDiagnosticReport::Unchanged(
+                        lsp::WorkspaceUnchangedDocumentDiagnosticReport {
+                            uri: lib_uri,
+                            version: None,
+                            unchanged_document_diagnostic_report:
+                                lsp::UnchangedDocumentDiagnosticReport {
+                                    result_id: "lib-1".to_string(),
+                                },
+                        },
+                    )
+                } else {
+                    lsp::WorkspaceDocumentDiagnosticReport::Full(
+                        lsp::WorkspaceFullDocumentDiagnosticReport {
+                            uri: lib_uri,
+                            version: None,
+                            full_document_diagnostic_report: lsp::FullDocumentDiagnosticReport {
+                                result_id: Some("lib-1".to_string()),
+                                items: vec![lsp::Diagnostic {
+                                    range: lsp::Range::new(
+                                        lsp::Position::new(0, 3),
+                                        lsp::Position::new(0, 6),
+                                    ),
+                                    severity: Some(lsp::DiagnosticSeverity::ERROR),
+                                    message: "unused function".to_string(),
+                                    ..lsp::Diagnostic::default()
+                                }],
+                            },
+                        },
+                    )
+                };
+                Ok(lsp::WorkspaceDiagnosticReportResult::Report(
+                    lsp::WorkspaceDiagnosticReport {
+                        items: vec![report],
+                    },
+                ))
+            }
+        }
+    });
+
+    cx.executor().advance_clock(Duration::from_secs(1));
+    cx.run_until_parked();
+    lsp_store.update(cx, |lsp_store, cx| {
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 1,
+                warning_count: 0,
+            }
+        );
+    });
+
+    // The result id of the unopened file is sent back, and its diagnostics are kept when the
+    // server reports them as unchanged.
+    lsp_store.update(cx, |lsp_store, _| {
+        lsp_store.pull_workspace_diagnostics(fake_server.server.server_id())
+    });
+    cx.executor().advance_clock(Duration::from_secs(1));
+    cx.run_until_parked();
+    assert_eq!(
+        *previous_result_ids.lock(),
+        [(
+            lsp::Uri::from_file_path(path!("/dir/lib.rs")).unwrap(),
+            "lib-1".to_string()
+        )]
+    );
+    lsp_store.update(cx, |lsp_store, cx| {
+        assert_eq!(
+            lsp_store.diagnostic_summary(false, cx),
+            DiagnosticSummary {
+                error_count: 1,
+                warning_count: 0,
+            }
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4037,6 +4192,84 @@ async fn test_save_file_spawns_language_server(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_untitled_buffer_language_server(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/dir"), json!({})).await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+
+    language_registry.add(rust_lang());
+    let mut fake_rust_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            name: "the-rust-language-server",
+            ..Default::default()
+        },
+    );
+
+    let buffer = project
+        .update(cx, |this, cx| this.create_buffer(false, cx))
+        .unwrap()
+        .await;
+    let _handle = project.update(cx, |this, cx| {
+        this.register_buffer_with_language_servers(&buffer, cx)
+    });
+    project.update(cx, |this, cx| {
+        this.set_language_for_buffer(&buffer, rust_lang(), cx);
+    });
+
+    // Once it has a language, the untitled buffer is opened as a document in the scratch directory.
+    let mut fake_rust_server = fake_rust_servers.next().await.unwrap();
+    let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+    let scratch_uri =
+        Uri::from_file_path(scratch_dir().join(format!("untitled-{buffer_id}.rs"))).unwrap();
+    assert_eq!(
+        fake_rust_server
+            .receive_notification::<lsp::notification::DidOpenTextDocument>()
+            .await
+            .text_document
+            .uri,
+        scratch_uri
+    );
+
+    buffer.update(cx, |buffer, cx| {
+        buffer.edit([(0..0, "fn main() {}")], None, cx)
+    });
+    assert_eq!(
+        fake_rust_server
+            .receive_notification::<lsp::notification::DidChangeTextDocument>()
+            .await
+            .text_document,
+        lsp::VersionedTextDocumentIdentifier::new(scratch_uri.clone(), 1)
+    );
+
+    fake_rust_server.notify::<lsp::notification::PublishDiagnostics>(
+        lsp::PublishDiagnosticsParams {
+            uri: scratch_uri,
+            version: None,
+            diagnostics: vec![lsp::Diagnostic {
+                range: lsp::Range::new(lsp::Position::new(0, 3), lsp::Position::new(0, 7)),
+                severity: Some(lsp::DiagnosticSeverity::WARNING),
+                message: "unused function".to_string(),
+                ..Default::default()
+            }],
+        },
+    );
+    cx.executor().run_until_parked();
+    buffer.update(cx, |buffer, _| {
+        let messages = buffer
+            .snapshot()
+            .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+            .map(|entry| entry.diagnostic.message.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, ["unused function"]);
+    });
+}
+
 #[gpui::test(iterations = 30)]
 async fn test_file_changes_multiple_times_on_disk(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4778,6 +5011,54 @@ async fn test_buffer_file_changes_on_disk(cx: &mut gpui::TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_buffer_reload_with_merge(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "the-file": "one\ntwo\nthree\nfour\nfive\n",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |p, cx| p.open_local_buffer(path!("/dir/the-file"), cx))
+        .await
+        .unwrap();
+
+    buffer.update(cx, |buffer, cx| {
+        buffer.edit([(0..3, "ONE"), (8..13, "3")], None, cx);
+    });
+    fs.save(
+        path!("/dir/the-file").as_ref(),
+        &"one\ntwo\nthree!\nfour\nFIVE\n".into(),
+        LineEnding::Unix,
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+    buffer.update(cx, |buffer, _| assert!(buffer.has_conflict()));
+
+    // Changes to different lines are combined, and the line changed on both sides is kept
+    // between conflict markers.
+    let has_conflicts = buffer
+        .update(cx, |buffer, cx| buffer.reload_with_merge(cx))
+        .await
+        .unwrap();
+    assert!(has_conflicts);
+    buffer.update(cx, |buffer, _| {
+        assert_eq!(
+            buffer.text(),
+            "ONE\ntwo\n<<<<<<< Unsaved changes\n3\n||||||| Last saved\nthree\n=======\nthree!\n>>>>>>> On disk\nfour\nFIVE\n"
+        );
+        assert!(buffer.is_dirty());
+        assert!(!buffer.has_conflict());
+    });
+}
+
 #[gpui::test]
 async fn test_buffer_line_endings(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -5376,6 +5657,132 @@ async fn test_rename(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_revert_last_workspace_edit(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "one.rs": "const ONE: usize = 1;",
+            "two.rs": "const TWO: usize = one::ONE + one::ONE;"
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                rename_provider: Some(lsp::OneOf::Left(true)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/one.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    let response = project.update(cx, |project, cx| {
+        project.perform_rename(buffer.clone(), 7, "THREE".to_string(), cx)
+    });
+    fake_server
+        .set_request_handler::<lsp::request::Rename, _, _>(|_, _| async move {
+            Ok(Some(lsp::WorkspaceEdit {
+                changes: Some(
+                    [
+                        (
+                            lsp::Uri::from_file_path(path!("/dir/one.rs")).unwrap(),
+                            vec![lsp::TextEdit::new(
+                                lsp::Range::new(lsp::Position::new(0, 6), lsp::Position::new(0, 9)),
+                                "THREE".to_string(),
+                            )],
+                        ),
+                        (
+                            lsp::Uri::from_file_path(path!("/dir/two.rs")).unwrap(),
+                            vec![
+                                lsp::TextEdit::new(
+                                    lsp::Range::new(
+                                        lsp::Position::new(0, 24),
+                                        lsp::Position::new(0, 27),
+                                    ),
+                                    "THREE".to_string(),
+                                ),
+                                lsp::TextEdit::new(
+                                    lsp::Range::new(
+                                        lsp::Position::new(0, 35),
+                                        lsp::Position::new(0, 38),
+                                    ),
+                                    "THREE".to_string(),
+                                ),
+                            ],
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+                ..Default::default()
+            }))
+        })
+        .next()
+        .await
+        .unwrap();
+    let mut transaction = response.await.unwrap().0;
+    transaction.remove(&buffer);
+    let other_buffer = transaction.into_keys().next().unwrap();
+    assert_eq!(
+        other_buffer.read_with(cx, |buffer, _| buffer.text()),
+        "const TWO: usize = one::THREE + one::THREE;"
+    );
+
+    // The edit isn't reverted while one of the files has changed since.
+    other_buffer.update(cx, |buffer, cx| {
+        buffer.edit([(0..0, "// ")], None, cx);
+    });
+    project
+        .update(cx, |project, cx| project.revert_last_workspace_edit(cx))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        buffer.read_with(cx, |buffer, _| buffer.text()),
+        "const THREE: usize = 1;"
+    );
+
+    other_buffer.update(cx, |buffer, cx| {
+        buffer.undo(cx);
+    });
+    let transaction = project
+        .update(cx, |project, cx| project.revert_last_workspace_edit(cx))
+        .await
+        .unwrap();
+    assert_eq!(transaction.0.len(), 2);
+    assert_eq!(
+        buffer.read_with(cx, |buffer, _| buffer.text()),
+        "const ONE: usize = 1;"
+    );
+    assert_eq!(
+        other_buffer.read_with(cx, |buffer, _| buffer.text()),
+        "const TWO: usize = one::ONE + one::ONE;"
+    );
+
+    // Each edit is only reverted once.
+    project
+        .update(cx, |project, cx| project.revert_last_workspace_edit(cx))
+        .await
+        .unwrap_err();
+}
+
 #[gpui::test]
 async fn test_search(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -6250,6 +6657,47 @@ async fn test_search_with_unicode(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_search_utf16_and_binary_files(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/dir"), json!({ "utf8.txt": "let needle = 1;" }))
+        .await;
+    let utf16 = [0xFF, 0xFE]
+        .into_iter()
+        .chain("let needle = 1;".encode_utf16().flat_map(u16::to_le_bytes))
+        .collect();
+    fs.insert_file(path!("/dir/utf16.txt"), utf16).await;
+    fs.insert_file(path!("/dir/binary.bin"), b"let needle = 1;\0\0\0".to_vec())
+        .await;
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+
+    assert_eq!(
+        search(
+            &project,
+            SearchQuery::text(
+                "needle",
+                false,
+                true,
+                false,
+                Default::default(),
+                Default::default(),
+                false,
+                None,
+            )
+            .unwrap(),
+            cx
+        )
+        .await
+        .unwrap(),
+        HashMap::from_iter([
+            (path!("dir/utf8.txt").to_string(), vec![4..10]),
+            (path!("dir/utf16.txt").to_string(), vec![4..10]),
+        ])
+    );
+}
+
 #[gpui::test]
 async fn test_create_entry(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -9983,3 +10431,118 @@ async fn test_find_project_path_abs(
         );
     });
 }
+
+#[gpui::test]
+async fn test_bookmarks(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "one\ntwo\nthree\nfour\n",
+            "b.rs": "five\n",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let bookmark_store = project.read_with(cx, |project, _| project.bookmark_store());
+
+    // Toggling the same line twice removes its bookmark, wherever the cursor is on that line.
+    bookmark_store.update(cx, |bookmark_store, cx| {
+        for (row, column) in [(2, 0), (0, 1), (2, 3), (3, 2)] {
+            let position = buffer.read(cx).anchor_before(Point::new(row, column));
+            bookmark_store.toggle_bookmark(buffer.clone(), position, cx);
+        }
+    });
+    let a_path: Arc<Path> = Arc::from(Path::new(path!("/dir/a.rs")));
+    assert_eq!(
+        bookmarked_rows(&bookmark_store, cx),
+        vec![(a_path.clone(), 0), (a_path.clone(), 3)]
+    );
+    bookmark_store.read_with(cx, |bookmark_store, cx| {
+        assert!(bookmark_store.has_bookmarks(&buffer, cx));
+        assert!(bookmark_store.bookmark_at_row(&a_path, 3, cx).is_some());
+        assert!(bookmark_store.bookmark_at_row(&a_path, 2, cx).is_none());
+    });
+
+    // Bookmarks follow their lines as the buffer is edited.
+    buffer.update(cx, |buffer, cx| {
+        buffer.edit([(Point::new(0, 0)..Point::new(0, 0), "zero\n")], None, cx)
+    });
+    assert_eq!(
+        bookmarked_rows(&bookmark_store, cx),
+        vec![(a_path.clone(), 1), (a_path.clone(), 4)]
+    );
+
+    // Bookmarks move with their file when it is renamed.
+    let (worktree_id, entry_id) = project.read_with(cx, |project, cx| {
+        let worktree = project.worktrees(cx).next().unwrap().read(cx);
+        (
+            worktree.id(),
+            worktree.entry_for_path(rel_path("a.rs")).unwrap().id,
+        )
+    });
+    project
+        .update(cx, |project, cx| {
+            project.rename_entry(entry_id, (worktree_id, rel_path("c.rs")).into(), cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    let c_path: Arc<Path> = Arc::from(Path::new(path!("/dir/c.rs")));
+    assert_eq!(
+        bookmarked_rows(&bookmark_store, cx),
+        vec![(c_path.clone(), 1), (c_path.clone(), 4)]
+    );
+
+    let serialized = bookmark_store.read_with(cx, |bookmark_store, cx| {
+        bookmark_store.all_source_bookmarks(cx)
+    });
+    bookmark_store.update(cx, |bookmark_store, cx| bookmark_store.clear_bookmarks(cx));
+    assert_eq!(bookmarked_rows(&bookmark_store, cx), Vec::new());
+    bookmark_store.read_with(cx, |bookmark_store, cx| {
+        assert!(!bookmark_store.has_bookmarks(&buffer, cx));
+    });
+
+    // A file that can no longer be opened doesn't prevent the others from being restored.
+    let missing_path: Arc<Path> = Arc::from(Path::new(path!("/missing/d.rs")));
+    let mut serialized_with_missing_file = serialized.clone();
+    serialized_with_missing_file.insert(
+        missing_path.clone(),
+        vec![bookmark_store::SourceBookmark {
+            row: 0,
+            path: missing_path,
+        }],
+    );
+    bookmark_store
+        .update(cx, |bookmark_store, cx| {
+            bookmark_store.with_serialized_bookmarks(serialized_with_missing_file, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        bookmarked_rows(&bookmark_store, cx),
+        vec![(c_path.clone(), 1), (c_path, 4)]
+    );
+}
+
+fn bookmarked_rows(
+    bookmark_store: &Entity<bookmark_store::BookmarkStore>,
+    cx: &mut gpui::TestAppContext,
+) -> Vec<(Arc<Path>, u32)> {
+    bookmark_store.read_with(cx, |bookmark_store, cx| {
+        bookmark_store
+            .all_source_bookmarks(cx)
+            .into_values()
+            .flatten()
+            .map(|bookmark| (bookmark.path, bookmark.row))
+            .collect()
+    })
+}
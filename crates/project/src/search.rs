@@ -1,4 +1,4 @@
-use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, Input};
 use anyhow::Result;
 use client::proto;
 use fancy_regex::{Captures, Regex, RegexBuilder};
@@ -59,6 +59,7 @@ pub enum SearchQuery {
     Text {
         search: AhoCorasick,
         replacement: Option<String>,
+        preserve_case: bool,
         whole_word: bool,
         case_sensitive: bool,
         include_ignored: bool,
@@ -67,6 +68,7 @@ pub enum SearchQuery {
     Regex {
         regex: Regex,
         replacement: Option<String>,
+        preserve_case: bool,
         multiline: bool,
         whole_word: bool,
         case_sensitive: bool,
@@ -76,6 +78,83 @@ pub enum SearchQuery {
     },
 }
 
+/// The number of bytes copied out of a rope before they're searched for a text query. Rope chunks
+/// are too short for the SIMD prefilters of [`AhoCorasick`] to pay off, so they're searched in
+/// windows of many chunks instead.
+const SEARCH_WINDOW_LEN: usize = 256 * 1024;
+
+/// The non-overlapping matches of a single-pattern text query in a sequence of chunks, found by
+/// searching the chunks in place, one window at a time. Bytes that could begin a match spanning
+/// the end of a window are kept at the start of the next one.
+struct WindowedMatches<'a, I> {
+    search: &'a AhoCorasick,
+    chunks: I,
+    pattern_len: usize,
+    window: Vec<u8>,
+    /// The offset of the start of `window` in the searched text.
+    window_offset: usize,
+    /// The index in `window` that the next match is searched from.
+    search_start: usize,
+}
+
+impl<'a, I: Iterator<Item = &'a str>> WindowedMatches<'a, I> {
+    fn new(search: &'a AhoCorasick, pattern_len: usize, chunks: I) -> Self {
+        Self {
+            search,
+            chunks,
+            pattern_len,
+            window: Vec::new(),
+            window_offset: 0,
+            search_start: 0,
+        }
+    }
+
+    /// Moves the window forward, returning false once there's nothing left to search.
+    fn advance(&mut self) -> bool {
+        // A match has as many bytes as the pattern, since case-insensitive text queries are
+        // ASCII-only, so one starting before the last `pattern_len - 1` bytes would've been found.
+        let retained_start = self.search_start.max(
+            self.window
+                .len()
+                .saturating_sub(self.pattern_len.saturating_sub(1)),
+        );
+        self.window.drain(..retained_start);
+        self.window_offset += retained_start;
+        self.search_start = 0;
+
+        let retained_len = self.window.len();
+        while self.window.len() < SEARCH_WINDOW_LEN {
+            let Some(chunk) = self.chunks.next() else {
+                break;
+            };
+            self.window.extend_from_slice(chunk.as_bytes());
+        }
+        self.window.len() > retained_len
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a str>> Iterator for WindowedMatches<'a, I> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // An empty pattern would match everywhere without moving forward, and empty queries
+        // aren't searched for anyway.
+        if self.pattern_len == 0 {
+            return None;
+        }
+        loop {
+            let input = Input::new(&self.window).span(self.search_start..self.window.len());
+            if let Some(mat) = self.search.find(input) {
+                self.search_start = mat.end();
+                return Some(self.window_offset + mat.start()..self.window_offset + mat.end());
+            }
+            if !self.advance() {
+                return None;
+            }
+        }
+    }
+}
+
 static WORD_MATCH_TEST: LazyLock<Regex> = LazyLock::new(|| {
     RegexBuilder::new(r"\B")
         .build()
@@ -127,6 +206,7 @@ impl SearchQuery {
         Ok(Self::Text {
             search,
             replacement: None,
+            preserve_case: false,
             whole_word,
             case_sensitive,
             include_ignored,
@@ -190,6 +270,7 @@ impl SearchQuery {
         Ok(Self::Regex {
             regex,
             replacement: None,
+            preserve_case: false,
             multiline,
             whole_word,
             case_sensitive,
@@ -239,6 +320,7 @@ impl SearchQuery {
     }
 
     pub fn from_proto(message: proto::SearchQuery, path_style: PathStyle) -> Result<Self> {
+        let preserve_case = message.preserve_case;
         let files_to_include = if message.files_to_include.is_empty() {
             message
                 .files_to_include_legacy
@@ -263,7 +345,7 @@ impl SearchQuery {
             message.files_to_exclude
         };
 
-        if message.regex {
+        let query = if message.regex {
             Self::regex(
                 message.query,
                 message.whole_word,
@@ -274,7 +356,7 @@ impl SearchQuery {
                 PathMatcher::new(files_to_exclude, path_style)?,
                 message.match_full_paths,
                 None, // search opened only don't need search remote
-            )
+            )?
         } else {
             Self::text(
                 message.query,
@@ -285,8 +367,9 @@ impl SearchQuery {
                 PathMatcher::new(files_to_exclude, path_style)?,
                 false,
                 None, // search opened only don't need search remote
-            )
-        }
+            )?
+        };
+        Ok(query.with_preserve_case(preserve_case))
     }
 
     pub fn with_replacement(mut self, new_replacement: String) -> Self {
@@ -305,6 +388,24 @@ impl SearchQuery {
         }
     }
 
+    /// Makes replacements follow the case of the text they replace, e.g. replacing `color` with
+    /// `colour` also turns `Color` into `Colour` and `COLOR` into `COLOUR`.
+    pub fn with_preserve_case(mut self, new_preserve_case: bool) -> Self {
+        match self {
+            Self::Text {
+                ref mut preserve_case,
+                ..
+            }
+            | Self::Regex {
+                ref mut preserve_case,
+                ..
+            } => {
+                *preserve_case = new_preserve_case;
+                self
+            }
+        }
+    }
+
     pub fn to_proto(&self) -> proto::SearchQuery {
         let files_to_include = self.files_to_include().sources().to_vec();
         let files_to_exclude = self.files_to_exclude().sources().to_vec();
@@ -317,6 +418,7 @@ impl SearchQuery {
             files_to_include: files_to_include.clone(),
             files_to_exclude: files_to_exclude.clone(),
             match_full_paths: self.match_full_paths(),
+            preserve_case: self.preserve_case(),
             // Populate legacy fields for backwards compatibility
             files_to_include_legacy: files_to_include.join(","),
             files_to_exclude_legacy: files_to_exclude.join(","),
@@ -372,27 +474,29 @@ impl SearchQuery {
     }
     /// Replaces search hits if replacement is set. `text` is assumed to be a string that matches this `SearchQuery` exactly, without any leftovers on either side.
     pub fn replacement_for<'a>(&self, text: &'a str) -> Option<Cow<'a, str>> {
+        let replacement = self.expand_replacement(text)?;
+        if self.preserve_case() {
+            Some(Cow::Owned(match_case(text, &replacement)))
+        } else {
+            Some(replacement)
+        }
+    }
+
+    fn expand_replacement<'a>(&self, text: &'a str) -> Option<Cow<'a, str>> {
         match self {
             SearchQuery::Text { replacement, .. } => replacement.clone().map(Cow::from),
             SearchQuery::Regex {
                 regex, replacement, ..
             } => {
-                if let Some(replacement) = replacement {
-                    static TEXT_REPLACEMENT_SPECIAL_CHARACTERS_REGEX: LazyLock<Regex> =
-                        LazyLock::new(|| Regex::new(r"\\\\|\\n|\\t").unwrap());
-                    let replacement = TEXT_REPLACEMENT_SPECIAL_CHARACTERS_REGEX.replace_all(
-                        replacement,
-                        |c: &Captures| match c.get(0).unwrap().as_str() {
-                            r"\\" => "\\",
-                            r"\n" => "\n",
-                            r"\t" => "\t",
-                            x => unreachable!("Unexpected escape sequence: {}", x),
-                        },
-                    );
-                    Some(regex.replace(text, replacement))
-                } else {
-                    None
-                }
+                let replacement = replacement.as_ref()?;
+                let Some(captures) = regex.captures(text).ok().flatten() else {
+                    return Some(Cow::Borrowed(text));
+                };
+                let mat = captures.get(0)?;
+                let mut result = text[..mat.start()].to_string();
+                result.push_str(&expand_regex_replacement(replacement, &captures));
+                result.push_str(&text[mat.end()..]);
+                Some(Cow::Owned(result))
             }
         }
     }
@@ -420,34 +524,32 @@ impl SearchQuery {
             Self::Text {
                 search, whole_word, ..
             } => {
-                for (ix, mat) in search
-                    .stream_find_iter(rope.bytes_in_range(0..rope.len()))
-                    .enumerate()
+                let pattern_len = self.as_str().len();
+                for (ix, mat) in
+                    WindowedMatches::new(search, pattern_len, rope.chunks()).enumerate()
                 {
                     if (ix + 1) % YIELD_INTERVAL == 0 {
                         yield_now().await;
                     }
 
-                    let mat = mat.unwrap();
                     if *whole_word {
-                        let classifier = buffer.char_classifier_at(range_offset + mat.start());
+                        let classifier = buffer.char_classifier_at(range_offset + mat.start);
 
                         let prev_kind = rope
-                            .reversed_chars_at(mat.start())
+                            .reversed_chars_at(mat.start)
                             .next()
                             .map(|c| classifier.kind(c));
-                        let start_kind =
-                            classifier.kind(rope.chars_at(mat.start()).next().unwrap());
+                        let start_kind = classifier.kind(rope.chars_at(mat.start).next().unwrap());
                         let end_kind =
-                            classifier.kind(rope.reversed_chars_at(mat.end()).next().unwrap());
-                        let next_kind = rope.chars_at(mat.end()).next().map(|c| classifier.kind(c));
+                            classifier.kind(rope.reversed_chars_at(mat.end).next().unwrap());
+                        let next_kind = rope.chars_at(mat.end).next().map(|c| classifier.kind(c));
                         if (Some(start_kind) == prev_kind && start_kind == CharKind::Word)
                             || (Some(end_kind) == next_kind && end_kind == CharKind::Word)
                         {
                             continue;
                         }
                     }
-                    matches.push(mat.start()..mat.end())
+                    matches.push(mat)
                 }
             }
 
@@ -512,6 +614,13 @@ impl SearchQuery {
         }
     }
 
+    pub fn preserve_case(&self) -> bool {
+        match self {
+            Self::Text { preserve_case, .. } => *preserve_case,
+            Self::Regex { preserve_case, .. } => *preserve_case,
+        }
+    }
+
     pub fn case_sensitive(&self) -> bool {
         match self {
             Self::Text { case_sensitive, .. } => *case_sensitive,
@@ -595,9 +704,173 @@ impl SearchQuery {
     }
 }
 
+#[derive(Clone, Copy)]
+enum CaseTransform {
+    Upper,
+    Lower,
+}
+
+#[derive(Default)]
+struct ReplacementBuilder {
+    text: String,
+    case: Option<CaseTransform>,
+    next_char_case: Option<CaseTransform>,
+}
+
+impl ReplacementBuilder {
+    fn push(&mut self, text: &str) {
+        let mut chars = text.chars();
+        if let Some(case) = self.next_char_case
+            && let Some(first) = chars.next()
+        {
+            self.next_char_case = None;
+            self.push_char(first, Some(case));
+        }
+        for c in chars {
+            self.push_char(c, self.case);
+        }
+    }
+
+    fn push_char(&mut self, c: char, case: Option<CaseTransform>) {
+        match case {
+            Some(CaseTransform::Upper) => self.text.extend(c.to_uppercase()),
+            Some(CaseTransform::Lower) => self.text.extend(c.to_lowercase()),
+            None => self.text.push(c),
+        }
+    }
+}
+
+/// Expands a regex replacement string for the given captures.
+///
+/// Besides `$1`, `${1}` and `${name}` group references, `\n`, `\t` and `\\` escapes, this supports
+/// case transformations:
+///
+/// - `\u` / `\l` uppercase or lowercase the next character
+/// - `\U` / `\L` uppercase or lowercase everything up to the next `\E`
+/// - `${1:/upcase}`, `${1:/downcase}`, `${1:/capitalize}`, `${1:/camelcase}` and
+///   `${1:/pascalcase}` transform a single group
+fn expand_regex_replacement(template: &str, captures: &Captures) -> String {
+    let mut builder = ReplacementBuilder::default();
+    let mut rest = template;
+    while let Some(ix) = rest.find(['\\', '$']) {
+        builder.push(&rest[..ix]);
+        let special = &rest[ix..];
+        let consumed = if let Some(escaped) = special.strip_prefix('\\') {
+            match escaped.chars().next() {
+                Some('\\') => builder.push("\\"),
+                Some('n') => builder.push("\n"),
+                Some('t') => builder.push("\t"),
+                Some('u') => builder.next_char_case = Some(CaseTransform::Upper),
+                Some('l') => builder.next_char_case = Some(CaseTransform::Lower),
+                Some('U') => builder.case = Some(CaseTransform::Upper),
+                Some('L') => builder.case = Some(CaseTransform::Lower),
+                Some('E') => builder.case = None,
+                _ => {
+                    builder.push("\\");
+                    rest = escaped;
+                    continue;
+                }
+            }
+            2
+        } else {
+            let reference = &special[1..];
+            if reference.starts_with('$') {
+                builder.push("$");
+                2
+            } else if let Some(braced) = reference.strip_prefix('{')
+                && let Some(end) = braced.find('}')
+            {
+                match braced[..end].split_once(":/") {
+                    Some((group, transform)) => {
+                        builder.push(&transform_case(capture_group(captures, group), transform))
+                    }
+                    None => builder.push(capture_group(captures, &braced[..end])),
+                }
+                end + 3
+            } else {
+                let len = reference
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(reference.len());
+                if len == 0 {
+                    builder.push("$");
+                } else {
+                    builder.push(capture_group(captures, &reference[..len]));
+                }
+                len + 1
+            }
+        };
+        rest = &special[consumed..];
+    }
+    builder.push(rest);
+    builder.text
+}
+
+fn capture_group<'a>(captures: &Captures<'a>, group: &str) -> &'a str {
+    let group = match group.parse::<usize>() {
+        Ok(ix) => captures.get(ix),
+        Err(_) => captures.name(group),
+    };
+    group.map_or("", |group| group.as_str())
+}
+
+fn transform_case(text: &str, transform: &str) -> String {
+    fn capitalize(word: &str) -> String {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+
+    let words = || {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+    };
+    match transform {
+        "upcase" => text.to_uppercase(),
+        "downcase" => text.to_lowercase(),
+        "capitalize" => capitalize(text),
+        "pascalcase" => words().map(capitalize).collect(),
+        "camelcase" => {
+            let pascal_case = words().map(capitalize).collect::<String>();
+            let mut chars = pascal_case.chars();
+            match chars.next() {
+                Some(first) => first.to_lowercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+        _ => text.to_string(),
+    }
+}
+
+/// Applies the case of `matched` to `replacement` when it is all uppercase, all lowercase or
+/// capitalized. Otherwise the replacement is used as is.
+fn match_case(matched: &str, replacement: &str) -> String {
+    let mut letters = matched.chars().filter(|c| c.is_alphabetic()).peekable();
+    let Some(first) = letters.next() else {
+        return replacement.to_string();
+    };
+    let rest_is_lowercase = letters.clone().all(|c| !c.is_uppercase());
+    let has_rest = letters.peek().is_some();
+    if first.is_uppercase() && has_rest && letters.all(|c| !c.is_lowercase()) {
+        replacement.to_uppercase()
+    } else if first.is_uppercase() && rest_is_lowercase {
+        let mut chars = replacement.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    } else if first.is_lowercase() && rest_is_lowercase {
+        replacement.to_lowercase()
+    } else {
+        replacement.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use gpui::AppContext as _;
 
     #[test]
     fn path_matcher_creation_for_valid_paths() {
@@ -726,4 +999,174 @@ mod tests {
             "Case sensitivity should not be enabled when \\C pattern item is preceded by a backslash."
         );
     }
+
+    #[test]
+    fn test_preserve_case_replacement() {
+        let text_query = SearchQuery::text(
+            "color",
+            false,
+            false,
+            false,
+            Default::default(),
+            Default::default(),
+            false,
+            None,
+        )
+        .unwrap()
+        .with_replacement("colour".to_string())
+        .with_preserve_case(true);
+        for (matched, expected) in [
+            ("color", "colour"),
+            ("Color", "Colour"),
+            ("COLOR", "COLOUR"),
+            ("cOLoR", "colour"),
+        ] {
+            assert_eq!(
+                text_query.replacement_for(matched).as_deref(),
+                Some(expected)
+            );
+        }
+
+        let regex_query = SearchQuery::regex(
+            "(\\w+)_id",
+            false,
+            false,
+            false,
+            false,
+            Default::default(),
+            Default::default(),
+            false,
+            None,
+        )
+        .unwrap()
+        .with_replacement("${1}Key".to_string())
+        .with_preserve_case(true);
+        assert_eq!(
+            regex_query.replacement_for("user_id").as_deref(),
+            Some("userkey")
+        );
+        assert_eq!(
+            regex_query.replacement_for("USER_ID").as_deref(),
+            Some("USERKEY")
+        );
+
+        let regex_query = regex_query.with_preserve_case(false);
+        assert_eq!(
+            regex_query.replacement_for("user_id").as_deref(),
+            Some("userKey")
+        );
+
+        let proto = text_query.to_proto();
+        assert!(proto.preserve_case);
+        let query = SearchQuery::from_proto(proto, PathStyle::local()).unwrap();
+        assert!(query.preserve_case());
+    }
+
+    #[test]
+    fn test_replacement_case_transforms() {
+        let query = |pattern: &str, replacement: &str| {
+            SearchQuery::regex(
+                pattern,
+                false,
+                true,
+                false,
+                false,
+                Default::default(),
+                Default::default(),
+                false,
+                None,
+            )
+            .unwrap()
+            .with_replacement(replacement.to_string())
+        };
+
+        for (pattern, replacement, text, expected) in [
+            (r"(\w+)_(\w+)", r"$1\u$2", "user_name", "userName"),
+            (r"(\w+)_(\w+)", r"\U$1\E_$2", "user_name", "USER_name"),
+            (r"(\w+)", r"\L$1\E!", "LOUD", "loud!"),
+            (r"(\w+)", r"\l$1", "Name", "name"),
+            (r"(\w+)", r"\u\L$1", "hELLO", "Hello"),
+            (r"(\w+)", r"${1:/upcase}", "value", "VALUE"),
+            (r"(\w+)", r"${1:/downcase}", "VALUE", "value"),
+            (r"(\w+)", r"${1:/capitalize}", "value", "Value"),
+            (
+                r"([\w-]+)",
+                r"${1:/camelcase}",
+                "user-name_id",
+                "userNameId",
+            ),
+            (
+                r"([\w-]+)",
+                r"${1:/pascalcase}",
+                "user-name_id",
+                "UserNameId",
+            ),
+            (r"(?<word>\w+)", r"${word:/upcase}", "named", "NAMED"),
+            (r"(\w+)", r"\\u$1 $$1", "text", r"\utext $1"),
+            (r"(\w+)", r"\x$1", "text", r"\xtext"),
+        ] {
+            assert_eq!(
+                query(pattern, replacement).replacement_for(text).as_deref(),
+                Some(expected),
+                "replacing {text:?} matched by {pattern:?} with {replacement:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_windowed_matches() {
+        // Three windows of text, searched in uneven chunks so that matches span chunk and window
+        // boundaries, with runs of the pattern's first letter to check matches don't overlap.
+        let text = "Needle in a haystack, neeeedle, NEEDLE needleneedle ee "
+            .repeat(3 * SEARCH_WINDOW_LEN / 56);
+        let chunks = text
+            .as_bytes()
+            .chunks(37)
+            .map(|chunk| std::str::from_utf8(chunk).unwrap())
+            .collect::<Vec<_>>();
+
+        for (pattern, case_sensitive) in [("needle", true), ("needle", false), ("ee", true)] {
+            let search = AhoCorasickBuilder::new()
+                .ascii_case_insensitive(!case_sensitive)
+                .build([pattern])
+                .unwrap();
+            let expected = search
+                .find_iter(&text)
+                .map(|mat| mat.start()..mat.end())
+                .collect::<Vec<_>>();
+            let actual = WindowedMatches::new(&search, pattern.len(), chunks.iter().copied())
+                .collect::<Vec<_>>();
+            assert!(expected.len() > SEARCH_WINDOW_LEN / 56);
+            assert_eq!(
+                actual, expected,
+                "matches of {pattern:?}, case sensitive: {case_sensitive}"
+            );
+        }
+
+        let search = AhoCorasickBuilder::new().build([""]).unwrap();
+        assert_eq!(
+            WindowedMatches::new(&search, 0, chunks.iter().copied()).next(),
+            None
+        );
+    }
+
+    #[gpui::test]
+    async fn test_empty_text_query(cx: &mut gpui::TestAppContext) {
+        let buffer = cx.new(|cx| Buffer::local("needle in a haystack", cx));
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+        for case_sensitive in [true, false] {
+            let query = SearchQuery::text(
+                "",
+                false,
+                case_sensitive,
+                false,
+                Default::default(),
+                Default::default(),
+                false,
+                None,
+            )
+            .unwrap();
+            assert!(query.search(&snapshot, None).await.is_empty());
+        }
+    }
 }
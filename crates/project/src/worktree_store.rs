@@ -1,5 +1,5 @@
 use std::{
-    io::{BufRead, BufReader},
+    io::{BufRead, BufReader, Cursor, Read},
     path::{Path, PathBuf},
     pin::pin,
     sync::{Arc, atomic::AtomicUsize},
@@ -24,7 +24,7 @@ use smol::{
     channel::{Receiver, Sender},
     stream::StreamExt,
 };
-use text::ReplicaId;
+use text::{Encoding, ReplicaId};
 use util::{
     ResultExt,
     paths::{PathStyle, RemotePathBuf, SanitizedPath},
@@ -1159,6 +1159,22 @@ impl WorktreeStore {
             let mut file = BufReader::new(file);
             let file_start = file.fill_buf()?;
 
+            // UTF-16 files aren't valid UTF-8, so they're decoded before being matched.
+            if let Some(encoding) = Encoding::detect(file_start)
+                && encoding.is_utf16()
+            {
+                let mut bytes = Vec::new();
+                file.read_to_end(&mut bytes)?;
+                let Some(text) = encoding.decode(&bytes).log_err() else {
+                    continue;
+                };
+                let text: Box<dyn Read + Send + Sync> = Box::new(Cursor::new(text.into_bytes()));
+                if query.detect(BufReader::new(text)).unwrap_or(false) {
+                    entry.respond.send(entry.path).await?
+                }
+                continue;
+            }
+
             if let Err(Some(starting_position)) =
                 std::str::from_utf8(file_start).map_err(|e| e.error_len())
             {
@@ -1169,6 +1185,11 @@ impl WorktreeStore {
                 );
                 continue;
             }
+            // NUL bytes are valid UTF-8, but don't occur in text files.
+            if file_start.contains(&0) {
+                log::debug!("Skipping binary file {abs_path:?}");
+                continue;
+            }
 
             if query.detect(file).unwrap_or(false) {
                 entry.respond.send(entry.path).await?
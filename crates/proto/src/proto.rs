@@ -95,6 +95,15 @@ messages!(
     (GetDocumentHighlightsResponse, Background),
     (GetDocumentSymbols, Background),
     (GetDocumentSymbolsResponse, Background),
+    (PrepareCallHierarchy, Background),
+    (PrepareCallHierarchyResponse, Background),
+    (GetIncomingCalls, Background),
+    (GetOutgoingCalls, Background),
+    (GetCallHierarchyCallsResponse, Background),
+    (PrepareTypeHierarchy, Background),
+    (GetSupertypes, Background),
+    (GetSubtypes, Background),
+    (TypeHierarchyResponse, Background),
     (GetHover, Background),
     (GetHoverResponse, Background),
     (GetNotifications, Foreground),
@@ -245,6 +254,8 @@ messages!(
     (SyncExtensionsResponse, Background),
     (BreakpointsForFile, Background),
     (ToggleBreakpoint, Foreground),
+    (BookmarksForFile, Background),
+    (ToggleBookmark, Foreground),
     (SynchronizeBuffers, Foreground),
     (SynchronizeBuffersResponse, Foreground),
     (SynchronizeContexts, Foreground),
@@ -361,6 +372,12 @@ request_messages!(
     (GetImplementation, GetImplementationResponse),
     (GetDocumentHighlights, GetDocumentHighlightsResponse),
     (GetDocumentSymbols, GetDocumentSymbolsResponse),
+    (PrepareCallHierarchy, PrepareCallHierarchyResponse),
+    (GetIncomingCalls, GetCallHierarchyCallsResponse),
+    (GetOutgoingCalls, GetCallHierarchyCallsResponse),
+    (PrepareTypeHierarchy, TypeHierarchyResponse),
+    (GetSupertypes, TypeHierarchyResponse),
+    (GetSubtypes, TypeHierarchyResponse),
     (GetHover, GetHoverResponse),
     (GetNotifications, GetNotificationsResponse),
     (GetProjectSymbols, GetProjectSymbolsResponse),
@@ -492,6 +509,7 @@ request_messages!(
     (GitDiff, GitDiffResponse),
     (GitInit, Ack),
     (ToggleBreakpoint, Ack),
+    (ToggleBookmark, Ack),
     (GetDebugAdapterBinary, DebugAdapterBinary),
     (RunDebugLocators, DebugRequest),
     (GetDocumentDiagnostics, GetDocumentDiagnosticsResponse),
@@ -549,6 +567,12 @@ entity_messages!(
     GetImplementation,
     GetDocumentHighlights,
     GetDocumentSymbols,
+    PrepareCallHierarchy,
+    GetIncomingCalls,
+    GetOutgoingCalls,
+    PrepareTypeHierarchy,
+    GetSupertypes,
+    GetSubtypes,
     GetHover,
     GetProjectSymbols,
     GetReferences,
@@ -652,6 +676,8 @@ entity_messages!(
     GitInit,
     BreakpointsForFile,
     ToggleBreakpoint,
+    BookmarksForFile,
+    ToggleBookmark,
     RunDebugLocators,
     GetDebugAdapterBinary,
     LogToDebugConsole,
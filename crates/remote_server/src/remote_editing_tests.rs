@@ -15,6 +15,7 @@ use gpui::{AppContext as _, Entity, SemanticVersion, TestAppContext};
 use http_client::{BlockedHttpClient, FakeHttpClient};
 use language::{
     Buffer, FakeLspAdapter, LanguageConfig, LanguageMatcher, LanguageRegistry, LineEnding,
+    OffsetRangeExt as _,
     language_settings::{AllLanguageSettings, language_settings},
 };
 use lsp::{CompletionContext, CompletionResponse, CompletionTriggerKind, LanguageServerName};
@@ -165,6 +166,67 @@ async fn test_basic_remote_editing(cx: &mut TestAppContext, server_cx: &mut Test
     });
 }
 
+#[gpui::test]
+async fn test_remote_unconfirmed_edits(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        path!("/code"),
+        json!({
+            "project1": {
+                "src": {
+                    "lib.rs": "fn one() -> usize { 1 }"
+                }
+            },
+        }),
+    )
+    .await;
+
+    let (project, _headless) = init_test(&fs, cx, server_cx).await;
+    let (worktree, _) = project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(path!("/code/project1"), true, cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    let worktree_id = worktree.read_with(cx, |worktree, _| worktree.id());
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, rel_path("src/lib.rs")), cx)
+        })
+        .await
+        .unwrap();
+
+    let unconfirmed_edits = |cx: &mut TestAppContext| {
+        project.read_with(cx, |project, cx| {
+            let snapshot = buffer.read(cx).snapshot();
+            project
+                .unconfirmed_edits(&buffer, cx)
+                .into_iter()
+                .map(|range| range.to_offset(&snapshot))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // Edits are applied locally right away, and are unconfirmed until the server applies them.
+    buffer.update(cx, |buffer, cx| {
+        let ix = buffer.text().find('1').unwrap();
+        buffer.edit([(ix..ix + 1, "100")], None, cx);
+        assert_eq!(buffer.text(), "fn one() -> usize { 100 }");
+    });
+    assert_eq!(unconfirmed_edits(cx), vec![20..23]);
+
+    cx.executor().run_until_parked();
+    assert_eq!(unconfirmed_edits(cx), Vec::new());
+
+    // Only the edits made since the last confirmed edit are unconfirmed.
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "pub ")], None, cx));
+    assert_eq!(unconfirmed_edits(cx), vec![0..4]);
+
+    cx.executor().run_until_parked();
+    assert_eq!(unconfirmed_edits(cx), Vec::new());
+}
+
 #[gpui::test]
 async fn test_remote_project_search(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
     let fs = FakeFs::new(server_cx.executor());
@@ -21,7 +21,8 @@ pub use crate::jupyter_settings::JupyterSettings;
 pub use crate::kernels::{Kernel, KernelSpecification, KernelStatus};
 pub use crate::repl_editor::*;
 pub use crate::repl_sessions_ui::{
-    ClearOutputs, Interrupt, ReplSessionsPage, Restart, Run, Sessions, Shutdown,
+    ClearOutputs, Interrupt, ReplSessionsPage, Restart, Run, RunAllAbove, RunCell, Sessions,
+    Shutdown,
 };
 pub use crate::repl_settings::ReplSettings;
 use crate::repl_store::ReplStore;
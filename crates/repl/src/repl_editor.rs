@@ -4,15 +4,22 @@ use std::ops::Range;
 use std::sync::Arc;
 
 use anyhow::{Context as _, Result};
-use editor::Editor;
-use gpui::{App, Entity, WeakEntity, Window, prelude::*};
+use editor::{
+    Editor, EditorEvent,
+    display_map::{Crease, CreaseId},
+};
+use gpui::{App, Context, Entity, Subscription, WeakEntity, Window, prelude::*};
 use language::{BufferSnapshot, Language, LanguageName, Point};
+use multi_buffer::MultiBufferRow;
 use project::{ProjectItem as _, WorktreeId};
+use ui::{ButtonSize, Disclosure, IconButton, IconName, IconSize, Tooltip, prelude::*};
+use util::ResultExt as _;
 
 use crate::repl_store::ReplStore;
 use crate::session::SessionEvent;
 use crate::{
-    ClearOutputs, Interrupt, JupyterSettings, KernelSpecification, Restart, Session, Shutdown,
+    ClearOutputs, Interrupt, JupyterSettings, KernelSpecification, Restart, RunCell, Session,
+    Shutdown,
 };
 
 pub fn assign_kernelspec(
@@ -87,6 +94,81 @@ pub fn run(
     let selected_range = editor
         .update(cx, |editor, cx| editor.selections.newest_adjusted(cx))
         .range();
+    let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+        return Ok(());
+    };
+
+    let (runnable_ranges, next_cell_point) =
+        runnable_ranges(&buffer.read(cx).snapshot(), selected_range, cx);
+    run_ranges(
+        &editor,
+        runnable_ranges,
+        next_cell_point,
+        move_down,
+        window,
+        cx,
+    )
+}
+
+/// Runs the cell at `row`, or the one containing the cursor, without moving the cursor.
+pub fn run_cell(
+    editor: WeakEntity<Editor>,
+    row: Option<u32>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Result<()> {
+    let store = ReplStore::global(cx);
+    if !store.read(cx).is_enabled() {
+        return Ok(());
+    }
+
+    let editor = editor.upgrade().context("editor was dropped")?;
+    let row = row.unwrap_or_else(|| {
+        editor
+            .update(cx, |editor, cx| editor.selections.newest_adjusted(cx))
+            .head()
+            .row
+    });
+    let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+        return Ok(());
+    };
+
+    let point = Point::new(row, 0);
+    let (runnable_ranges, _) = runnable_ranges(&buffer.read(cx).snapshot(), point..point, cx);
+    run_ranges(&editor, runnable_ranges, None, false, window, cx)
+}
+
+/// Runs every jupytext cell above the one containing the cursor, in order.
+pub fn run_all_above(editor: WeakEntity<Editor>, window: &mut Window, cx: &mut App) -> Result<()> {
+    let store = ReplStore::global(cx);
+    if !store.read(cx).is_enabled() {
+        return Ok(());
+    }
+
+    let editor = editor.upgrade().context("editor was dropped")?;
+    let cursor_row = editor
+        .update(cx, |editor, cx| editor.selections.newest_adjusted(cx))
+        .head()
+        .row;
+    let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+        return Ok(());
+    };
+
+    let mut cells = all_jupytext_cells(&buffer.read(cx).snapshot());
+    let cells_up_to_cursor = cells.partition_point(|cell| cell.start.row <= cursor_row);
+    cells.truncate(cells_up_to_cursor.saturating_sub(1));
+    run_ranges(&editor, cells, None, false, window, cx)
+}
+
+fn run_ranges(
+    editor: &Entity<Editor>,
+    runnable_ranges: Vec<Range<Point>>,
+    next_cell_point: Option<Point>,
+    move_down: bool,
+    window: &mut Window,
+    cx: &mut App,
+) -> Result<()> {
+    let store = ReplStore::global(cx);
     let multibuffer = editor.read(cx).buffer().clone();
     let Some(buffer) = multibuffer.read(cx).as_singleton() else {
         return Ok(());
@@ -96,9 +178,6 @@ pub fn run(
         return Ok(());
     };
 
-    let (runnable_ranges, next_cell_point) =
-        runnable_ranges(&buffer.read(cx).snapshot(), selected_range, cx);
-
     for runnable_range in runnable_ranges {
         let Some(language) = multibuffer.read(cx).language_at(runnable_range.start, cx) else {
             continue;
@@ -353,27 +432,14 @@ fn jupytext_cells(
 ) -> (Vec<Range<Point>>, Option<Point>) {
     let mut current_row = range.start.row;
 
-    let Some(language) = buffer.language() else {
-        return (Vec::new(), None);
-    };
-
-    let default_scope = language.default_scope();
-    let comment_prefixes = default_scope.line_comment_prefixes();
-    if comment_prefixes.is_empty() {
+    let jupytext_prefixes = jupytext_prefixes(buffer);
+    if jupytext_prefixes.is_empty() {
         return (Vec::new(), None);
     }
 
-    let jupytext_prefixes = comment_prefixes
-        .iter()
-        .map(|comment_prefix| format!("{comment_prefix}%%"))
-        .collect::<Vec<_>>();
-
     let mut snippet_start_row = None;
     loop {
-        if jupytext_prefixes
-            .iter()
-            .any(|prefix| buffer.contains_str_at(Point::new(current_row, 0), prefix))
-        {
+        if is_jupytext_marker(buffer, &jupytext_prefixes, current_row) {
             snippet_start_row = Some(current_row);
             break;
         } else if current_row > 0 {
@@ -386,10 +452,7 @@ fn jupytext_cells(
     let mut snippets = Vec::new();
     if let Some(mut snippet_start_row) = snippet_start_row {
         for current_row in range.start.row + 1..=buffer.max_point().row {
-            if jupytext_prefixes
-                .iter()
-                .any(|prefix| buffer.contains_str_at(Point::new(current_row, 0), prefix))
-            {
+            if is_jupytext_marker(buffer, &jupytext_prefixes, current_row) {
                 snippets.push(cell_range(buffer, snippet_start_row, current_row - 1));
 
                 if current_row <= range.end.row {
@@ -412,6 +475,165 @@ fn jupytext_cells(
     (snippets, None)
 }
 
+/// The prefixes of the lines that start jupytext cells, such as `# %%` or `// %%`.
+fn jupytext_prefixes(buffer: &BufferSnapshot) -> Vec<String> {
+    let Some(language) = buffer.language() else {
+        return Vec::new();
+    };
+
+    language
+        .default_scope()
+        .line_comment_prefixes()
+        .iter()
+        .map(|comment_prefix| format!("{comment_prefix}%%"))
+        .collect()
+}
+
+fn is_jupytext_marker(buffer: &BufferSnapshot, jupytext_prefixes: &[String], row: u32) -> bool {
+    jupytext_prefixes
+        .iter()
+        .any(|prefix| buffer.contains_str_at(Point::new(row, 0), prefix))
+}
+
+// Returns the ranges of all the jupytext cells in the buffer, each starting at its marker line
+fn all_jupytext_cells(buffer: &BufferSnapshot) -> Vec<Range<Point>> {
+    let jupytext_prefixes = jupytext_prefixes(buffer);
+    if jupytext_prefixes.is_empty() {
+        return Vec::new();
+    }
+
+    let max_row = buffer.max_point().row;
+    let marker_rows = (0..=max_row)
+        .filter(|row| is_jupytext_marker(buffer, &jupytext_prefixes, *row))
+        .collect::<Vec<_>>();
+    marker_rows
+        .iter()
+        .enumerate()
+        .map(|(ix, &start_row)| {
+            let end_row = marker_rows
+                .get(ix + 1)
+                .map_or(max_row, |next_start_row| next_start_row - 1);
+            cell_range(buffer, start_row, end_row)
+        })
+        .collect()
+}
+
+/// Shows a run button in the gutter next to every jupytext cell marker, and makes each cell
+/// foldable down to its marker line.
+struct JupytextCellsAddon {
+    cells: Vec<Range<Point>>,
+    crease_ids: Vec<CreaseId>,
+    _subscription: Subscription,
+}
+
+impl editor::Addon for JupytextCellsAddon {
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn to_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(self)
+    }
+}
+
+pub(crate) fn register_jupytext_cells(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let subscription = cx.subscribe(&cx.entity(), |editor, _, event, cx| {
+        if matches!(event, EditorEvent::BufferEdited | EditorEvent::Reparsed(_)) {
+            update_jupytext_cells(editor, cx);
+        }
+    });
+    editor.register_addon(JupytextCellsAddon {
+        cells: Vec::new(),
+        crease_ids: Vec::new(),
+        _subscription: subscription,
+    });
+    update_jupytext_cells(editor, cx);
+}
+
+fn update_jupytext_cells(editor: &mut Editor, cx: &mut Context<Editor>) {
+    let Some(buffer) = editor.buffer().read(cx).as_singleton() else {
+        return;
+    };
+    let buffer = buffer.read(cx).snapshot();
+    let cells = all_jupytext_cells(&buffer);
+    let Some(addon) = editor.addon_mut::<JupytextCellsAddon>() else {
+        return;
+    };
+    if addon.cells == cells {
+        return;
+    }
+    addon.cells = cells.clone();
+    let old_crease_ids = std::mem::take(&mut addon.crease_ids);
+
+    let snapshot = editor.buffer().read(cx).snapshot(cx);
+    let placeholder = editor.default_fold_placeholder(cx);
+    let editor_handle = cx.weak_entity();
+    let creases = cells
+        .into_iter()
+        .map(|cell| {
+            let marker_end = Point::new(cell.start.row, buffer.line_len(cell.start.row));
+            let range = snapshot.anchor_after(marker_end)..snapshot.anchor_before(cell.end);
+            Crease::inline(
+                range,
+                placeholder.clone(),
+                render_run_cell_button(editor_handle.clone()),
+                render_cell_fold_toggle(editor_handle.clone()),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    editor.remove_creases(old_crease_ids, cx);
+    let crease_ids = editor.insert_creases(creases, cx);
+    if let Some(addon) = editor.addon_mut::<JupytextCellsAddon>() {
+        addon.crease_ids = crease_ids;
+    }
+}
+
+fn render_run_cell_button(
+    editor: WeakEntity<Editor>,
+) -> impl Fn(
+    MultiBufferRow,
+    bool,
+    Arc<dyn Fn(bool, &mut Window, &mut App) + Send + Sync>,
+    &mut Window,
+    &mut App,
+) -> AnyElement {
+    move |row, _, _, _, _| {
+        let editor = editor.clone();
+        IconButton::new(("run-jupytext-cell", row.0), IconName::PlayOutlined)
+            .icon_size(IconSize::Small)
+            .icon_color(Color::Muted)
+            .size(ButtonSize::None)
+            .tooltip(|window, cx| Tooltip::for_action("Run Cell", &RunCell, window, cx))
+            .on_click(move |_, window, cx| {
+                crate::run_cell(editor.clone(), Some(row.0), window, cx).log_err();
+            })
+            .into_any_element()
+    }
+}
+
+fn render_cell_fold_toggle(
+    editor: WeakEntity<Editor>,
+) -> impl Fn(MultiBufferRow, bool, &mut Window, &mut App) -> AnyElement {
+    move |row, folded, _, _| {
+        let editor = editor.clone();
+        Disclosure::new(("jupytext-cell-fold", row.0 as u64), !folded)
+            .toggle_state(folded)
+            .on_click(move |_, window, cx| {
+                editor
+                    .update(cx, |editor, cx| {
+                        if folded {
+                            editor.unfold_at(row, window, cx);
+                        } else {
+                            editor.fold_at(row, window, cx);
+                        }
+                    })
+                    .ok();
+            })
+            .into_any_element()
+    }
+}
+
 fn runnable_ranges(
     buffer: &BufferSnapshot,
     range: Range<Point>,
@@ -632,6 +854,58 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_all_jupytext_cells(cx: &mut App) {
+        let test_language = Arc::new(Language::new(
+            LanguageConfig {
+                name: "TestLang".into(),
+                line_comments: vec!["# ".into()],
+                ..Default::default()
+            },
+            None,
+        ));
+
+        let buffer = cx.new(|cx| {
+            Buffer::local(
+                indoc! { r#"
+                    # Hello!
+                    # %% [markdown]
+                    # This is some arithmetic
+                    print(1 + 1)
+
+                    # %%
+                    # %%
+                    print(3 + 3)
+
+                "# },
+                cx,
+            )
+            .with_language(test_language, cx)
+        });
+        let snapshot = buffer.read(cx).snapshot();
+
+        // Text before the first marker isn't part of any cell.
+        let cells = all_jupytext_cells(&snapshot)
+            .into_iter()
+            .map(|range| snapshot.text_for_range(range).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            cells,
+            vec![
+                indoc! { r#"
+                    # %% [markdown]
+                    # This is some arithmetic
+                    print(1 + 1)"#
+                },
+                "# %%",
+                indoc! { r#"
+                    # %%
+                    print(3 + 3)"#
+                },
+            ]
+        );
+    }
+
     #[gpui::test]
     fn test_markdown_code_blocks(cx: &mut App) {
         use crate::kernels::LocalKernelSpecification;
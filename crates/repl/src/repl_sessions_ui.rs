@@ -19,6 +19,10 @@ actions!(
         Run,
         /// Runs the current cell without advancing.
         RunInPlace,
+        /// Runs the cell containing the cursor, ignoring the selection.
+        RunCell,
+        /// Runs every cell above the one containing the cursor.
+        RunAllAbove,
         /// Clears all outputs in the REPL.
         ClearOutputs,
         /// Opens the REPL sessions panel.
@@ -128,6 +132,7 @@ pub fn init(cx: &mut App) {
 
                 editor
                     .register_action({
+                        let editor_handle = editor_handle.clone();
                         move |_: &RunInPlace, window, cx| {
                             if !JupyterSettings::enabled(cx) {
                                 return;
@@ -137,6 +142,35 @@ pub fn init(cx: &mut App) {
                         }
                     })
                     .detach();
+
+                editor
+                    .register_action({
+                        let editor_handle = editor_handle.clone();
+                        move |_: &RunCell, window, cx| {
+                            if !JupyterSettings::enabled(cx) {
+                                return;
+                            }
+
+                            crate::run_cell(editor_handle.clone(), None, window, cx).log_err();
+                        }
+                    })
+                    .detach();
+
+                editor
+                    .register_action({
+                        move |_: &RunAllAbove, window, cx| {
+                            if !JupyterSettings::enabled(cx) {
+                                return;
+                            }
+
+                            crate::run_all_above(editor_handle.clone(), window, cx).log_err();
+                        }
+                    })
+                    .detach();
+
+                if JupyterSettings::enabled(cx) {
+                    crate::repl_editor::register_jupytext_cells(editor, cx);
+                }
             });
         },
     )
@@ -1,16 +1,17 @@
 mod registrar;
 
 use crate::{
-    FocusSearch, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll, ReplaceNext, SearchOption,
-    SearchOptions, SearchSource, SelectAllMatches, SelectNextMatch, SelectPreviousMatch,
-    ToggleCaseSensitive, ToggleRegex, ToggleReplace, ToggleSelection, ToggleWholeWord,
+    FocusSearch, NextHistoryQuery, PreviewReplaceAll, PreviousHistoryQuery, ReplaceAll,
+    ReplaceNext, SearchOption, SearchOptions, SearchSource, SelectAllMatches, SelectNextMatch,
+    SelectPreviousMatch, ToggleCaseSensitive, TogglePreserveCase, ToggleRegex, ToggleReplace,
+    ToggleSelection, ToggleWholeWord, replace_preview,
     search_bar::{ActionButtonState, input_base_styles, render_action_button, render_text_input},
 };
 use any_vec::AnyVec;
 use anyhow::Context as _;
 use collections::HashMap;
 use editor::{
-    DisplayPoint, Editor, EditorSettings,
+    Anchor, DisplayPoint, Editor, EditorSettings, SelectionEffects,
     actions::{Backtab, Tab},
 };
 use futures::channel::oneshot;
@@ -27,7 +28,7 @@ use project::{
 use schemars::JsonSchema;
 use serde::Deserialize;
 use settings::Settings;
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 use zed_actions::{outline::ToggleOutline, workspace::CopyPath, workspace::CopyRelativePath};
 
 use ui::{
@@ -66,10 +67,21 @@ actions!(
     [
         /// Deploys the search and replace interface.
         DeployReplace,
+        /// Deploys the search and replace interface, limiting matches and replacements to the
+        /// current selections.
+        ReplaceInSelection,
         /// Dismisses the search bar.
         Dismiss,
         /// Focuses back on the editor.
-        FocusEditor
+        FocusEditor,
+        /// Starts an incremental search forward from the cursor, or moves to the next match when
+        /// one is in progress.
+        IsearchForward,
+        /// Starts an incremental search backward from the cursor, or moves to the previous match
+        /// when one is in progress.
+        IsearchBackward,
+        /// Dismisses the search bar, keeping the match an incremental search moved to selected.
+        IsearchAccept
     ]
 );
 
@@ -89,12 +101,31 @@ impl Deploy {
             selection_search_enabled: false,
         }
     }
+
+    pub fn replace_in_selection() -> Self {
+        Self {
+            focus: true,
+            replace_enabled: true,
+            selection_search_enabled: true,
+        }
+    }
 }
 
 pub enum Event {
     UpdateLocation,
 }
 
+/// An incremental search, which jumps to the match nearest to the cursor as the query is typed.
+struct Isearch {
+    direction: Direction,
+    /// The editor's selections when the search started, restored when the search bar is dismissed
+    /// without [`IsearchAccept`].
+    origin: Vec<Range<Anchor>>,
+    /// Where the next query is searched from: the cursor when the search started, or the start
+    /// of the match moved to last.
+    position: Anchor,
+}
+
 pub fn init(cx: &mut App) {
     cx.observe_new(|workspace: &mut Workspace, _, _| BufferSearchBar::register(workspace))
         .detach();
@@ -120,6 +151,7 @@ pub struct BufferSearchBar {
     search_history_cursor: SearchHistoryCursor,
     replace_enabled: bool,
     selection_search_enabled: Option<FilteredSearchRange>,
+    isearch: Option<Isearch>,
     scroll_handle: ScrollHandle,
     editor_scroll_handle: ScrollHandle,
     editor_needed_width: Pixels,
@@ -356,6 +388,11 @@ impl Render for BufferSearchBar {
                 let replace_actions = h_flex()
                     .min_w_64()
                     .gap_1()
+                    .child(SearchOption::PreserveCase.as_button(
+                        self.search_options,
+                        SearchSource::Buffer,
+                        focus_handle.clone(),
+                    ))
                     .child(render_action_button(
                         "buffer-search-replace-button",
                         IconName::ReplaceNext,
@@ -370,6 +407,14 @@ impl Render for BufferSearchBar {
                         Default::default(),
                         "Replace All Matches",
                         &ReplaceAll,
+                        focus_handle.clone(),
+                    ))
+                    .child(render_action_button(
+                        "buffer-search-replace-button",
+                        IconName::Diff,
+                        Default::default(),
+                        "Preview Replace All",
+                        &PreviewReplaceAll,
                         focus_handle,
                     ));
                 h_flex()
@@ -442,6 +487,7 @@ impl Render for BufferSearchBar {
                     .when(in_replace, |this| {
                         this.on_action(cx.listener(Self::replace_next))
                             .on_action(cx.listener(Self::replace_all))
+                            .on_action(cx.listener(Self::preview_replace_all))
                     })
             })
             .when(case, |this| {
@@ -478,6 +524,7 @@ impl ToolbarItemView for BufferSearchBar {
         cx.notify();
         self.active_searchable_item_subscription.take();
         self.active_searchable_item.take();
+        self.isearch = None;
 
         self.pending_search.take();
 
@@ -537,6 +584,15 @@ impl BufferSearchBar {
                 this.toggle_regex(action, window, cx);
             }
         }));
+        registrar.register_handler(ForDeployed(
+            |this, action: &TogglePreserveCase, window, cx| {
+                if this.supported_options(cx).replacement {
+                    this.toggle_preserve_case(action, window, cx);
+                } else {
+                    cx.propagate();
+                }
+            },
+        ));
         registrar.register_handler(ForDeployed(|this, action: &ToggleSelection, window, cx| {
             if this.supported_options(cx).selection {
                 this.toggle_selection(action, window, cx);
@@ -607,6 +663,35 @@ impl BufferSearchBar {
                 this.deploy(&Deploy::replace(), window, cx);
             }
         }));
+        registrar.register_handler(ForDeployed(|this, _: &ReplaceInSelection, window, cx| {
+            if this.supported_options(cx).find_in_results {
+                cx.propagate();
+            } else {
+                this.deploy(&Deploy::replace_in_selection(), window, cx);
+            }
+        }));
+        registrar.register_handler(ForDismissed(|this, _: &ReplaceInSelection, window, cx| {
+            if this.supported_options(cx).find_in_results {
+                cx.propagate();
+            } else {
+                this.deploy(&Deploy::replace_in_selection(), window, cx);
+            }
+        }));
+        registrar.register_handler(ForDeployed(|this, _: &IsearchForward, window, cx| {
+            this.isearch(Direction::Next, window, cx);
+        }));
+        registrar.register_handler(ForDismissed(|this, _: &IsearchForward, window, cx| {
+            this.isearch(Direction::Next, window, cx);
+        }));
+        registrar.register_handler(ForDeployed(|this, _: &IsearchBackward, window, cx| {
+            this.isearch(Direction::Prev, window, cx);
+        }));
+        registrar.register_handler(ForDismissed(|this, _: &IsearchBackward, window, cx| {
+            this.isearch(Direction::Prev, window, cx);
+        }));
+        registrar.register_handler(ForDeployed(|this, action: &IsearchAccept, window, cx| {
+            this.isearch_accept(action, window, cx);
+        }));
     }
 
     pub fn new(
@@ -676,6 +761,7 @@ impl BufferSearchBar {
             active_search: None,
             replace_enabled: false,
             selection_search_enabled: None,
+            isearch: None,
             scroll_handle: ScrollHandle::new(),
             editor_scroll_handle: ScrollHandle::new(),
             editor_needed_width: px(0.),
@@ -688,6 +774,7 @@ impl BufferSearchBar {
     }
 
     pub fn dismiss(&mut self, _: &Dismiss, window: &mut Window, cx: &mut Context<Self>) {
+        self.cancel_isearch(window, cx);
         self.dismissed = true;
         self.query_error = None;
         for searchable_item in self.searchable_items_with_matches.keys() {
@@ -751,6 +838,80 @@ impl BufferSearchBar {
         false
     }
 
+    /// Starts an incremental search from the cursor, or moves to the next match in `direction`
+    /// if the search bar is already deployed.
+    ///
+    /// The cursor's position is pushed to the navigation history, so that it can be returned to
+    /// after the search, and dismissing the search bar without [`IsearchAccept`] restores the
+    /// selections.
+    fn isearch(&mut self, direction: Direction, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.dismissed {
+            if let Some(isearch) = self.isearch.as_mut() {
+                isearch.direction = direction;
+            }
+            self.select_match(direction, 1, window, cx);
+            if let Some(editor) = self.active_editor(cx)
+                && let Some(isearch) = self.isearch.as_mut()
+            {
+                isearch.position = editor.read(cx).selections.newest_anchor().start;
+            }
+            return;
+        }
+
+        let Some(editor) = self.active_editor(cx) else {
+            cx.propagate();
+            return;
+        };
+        if !self.show(window, cx) {
+            cx.propagate();
+            return;
+        }
+        let (origin, position) = editor.update(cx, |editor, cx| {
+            editor.create_nav_history_entry(cx);
+            let origin = editor
+                .selections
+                .disjoint_anchor_ranges()
+                .collect::<Vec<_>>();
+            (origin, editor.selections.newest_anchor().head())
+        });
+        self.isearch = Some(Isearch {
+            direction,
+            origin,
+            position,
+        });
+        self.replace_enabled = false;
+        self.selection_search_enabled = None;
+        if !self.query(cx).is_empty() {
+            drop(self.search("", None, false, window, cx));
+        }
+        window.focus(&self.query_editor.focus_handle(cx));
+    }
+
+    fn isearch_accept(&mut self, _: &IsearchAccept, window: &mut Window, cx: &mut Context<Self>) {
+        self.isearch = None;
+        self.dismiss(&Dismiss, window, cx);
+    }
+
+    /// Restores the selections from before the incremental search in progress, if any.
+    fn cancel_isearch(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(isearch) = self.isearch.take() else {
+            return;
+        };
+        if let Some(editor) = self.active_editor(cx) {
+            editor.update(cx, |editor, cx| {
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_anchor_ranges(isearch.origin);
+                });
+            });
+        }
+    }
+
+    fn active_editor(&self, cx: &App) -> Option<Entity<Editor>> {
+        self.active_searchable_item
+            .as_ref()
+            .and_then(|searchable_item| searchable_item.act_as::<Editor>(cx))
+    }
+
     pub fn toggle(&mut self, action: &Deploy, window: &mut Window, cx: &mut Context<Self>) {
         if self.is_dismissed() {
             self.deploy(action, window, cx);
@@ -1067,6 +1228,20 @@ impl BufferSearchBar {
             editor::EditorEvent::Edited { .. } => {
                 self.smartcase(window, cx);
                 self.clear_matches(window, cx);
+                // An incremental search looks for each query from the same position, so that
+                // typing more of it keeps the match nearest to where the search started.
+                if let Some(position) = self.isearch.as_ref().map(|isearch| isearch.position)
+                    && let Some(active_editor) = self.active_editor(cx)
+                {
+                    active_editor.update(cx, |active_editor, cx| {
+                        active_editor.change_selections(
+                            SelectionEffects::no_scroll(),
+                            window,
+                            cx,
+                            |s| s.select_anchor_ranges([position..position]),
+                        );
+                    });
+                }
                 let search = self.update_matches(false, true, window, cx);
 
                 let width = editor.update(cx, |editor, cx| {
@@ -1160,6 +1335,15 @@ impl BufferSearchBar {
         self.toggle_search_option(SearchOptions::REGEX, window, cx)
     }
 
+    fn toggle_preserve_case(
+        &mut self,
+        _: &TogglePreserveCase,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_search_option(SearchOptions::PRESERVE_CASE, window, cx)
+    }
+
     fn clear_active_searchable_item_matches(&mut self, window: &mut Window, cx: &mut App) {
         if let Some(active_searchable_item) = self.active_searchable_item.as_ref() {
             self.active_match_index = None;
@@ -1312,7 +1496,10 @@ impl BufferSearchBar {
     }
 
     pub fn update_match_index(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        let direction = self.reverse_direction_if_backwards(Direction::Next);
+        let direction = match &self.isearch {
+            Some(isearch) => isearch.direction,
+            None => self.reverse_direction_if_backwards(Direction::Next),
+        };
         let new_index = self
             .active_searchable_item
             .as_ref()
@@ -1433,7 +1620,8 @@ impl BufferSearchBar {
                 let query = query
                     .as_ref()
                     .clone()
-                    .with_replacement(self.replacement(cx));
+                    .with_replacement(self.replacement(cx))
+                    .with_preserve_case(self.search_options.contains(SearchOptions::PRESERVE_CASE));
                 searchable_item.replace(matches.at(active_index), &query, window, cx);
                 self.select_next_match(&SelectNextMatch, window, cx);
             }
@@ -1456,11 +1644,44 @@ impl BufferSearchBar {
             let query = query
                 .as_ref()
                 .clone()
-                .with_replacement(self.replacement(cx));
+                .with_replacement(self.replacement(cx))
+                .with_preserve_case(self.search_options.contains(SearchOptions::PRESERVE_CASE));
             searchable_item.replace_all(&mut matches.iter(), &query, window, cx);
         }
     }
 
+    fn preview_replace_all(
+        &mut self,
+        _: &PreviewReplaceAll,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.dismissed
+            && let Some(searchable_item) = self.active_searchable_item.as_ref()
+            && let Some(editor) = searchable_item.act_as::<Editor>(cx)
+            && let Some(workspace) = editor.read(cx).workspace()
+            && let Some(query) = self.active_search.as_ref()
+            && let Some(matches) = self
+                .searchable_items_with_matches
+                .get(&searchable_item.downgrade())
+            && let Some(matches) = matches.downcast_ref::<Range<Anchor>>()
+        {
+            let query = query
+                .as_ref()
+                .clone()
+                .with_replacement(self.replacement(cx))
+                .with_preserve_case(self.search_options.contains(SearchOptions::PRESERVE_CASE));
+            replace_preview::open_replace_preview(
+                workspace,
+                &editor,
+                matches.as_slice(),
+                &query,
+                window,
+                cx,
+            );
+        }
+    }
+
     pub fn match_exists(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
         self.update_match_index(window, cx);
         self.active_match_index.is_some()
@@ -2128,6 +2349,81 @@ mod tests {
             .unwrap();
     }
 
+    #[gpui::test]
+    async fn test_isearch(cx: &mut TestAppContext) {
+        let (editor, search_bar, cx) = init_test(cx);
+        let selections = |cx: &mut VisualTestContext| {
+            editor.update(cx, |editor, cx| editor.selections.display_ranges(cx))
+        };
+        let origin = DisplayPoint::new(DisplayRow(1), 0)..DisplayPoint::new(DisplayRow(1), 0);
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_display_ranges([origin.clone()])
+            });
+        });
+
+        // Typing the query jumps to the nearest match after the cursor.
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.dismiss(&Dismiss, window, cx);
+            search_bar.isearch(Direction::Next, window, cx);
+        });
+        cx.simulate_input("o");
+        cx.run_until_parked();
+        assert_eq!(
+            selections(cx),
+            [DisplayPoint::new(DisplayRow(1), 4)..DisplayPoint::new(DisplayRow(1), 5)]
+        );
+        cx.simulate_input("r");
+        cx.run_until_parked();
+        assert_eq!(
+            selections(cx),
+            [DisplayPoint::new(DisplayRow(2), 71)..DisplayPoint::new(DisplayRow(2), 73)]
+        );
+
+        // Repeating the search moves between matches.
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.isearch(Direction::Next, window, cx);
+        });
+        assert_eq!(
+            selections(cx),
+            [DisplayPoint::new(DisplayRow(3), 1)..DisplayPoint::new(DisplayRow(3), 3)]
+        );
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.isearch(Direction::Prev, window, cx);
+        });
+        assert_eq!(
+            selections(cx),
+            [DisplayPoint::new(DisplayRow(2), 71)..DisplayPoint::new(DisplayRow(2), 73)]
+        );
+
+        // Dismissing the search returns to where it started.
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.dismiss(&Dismiss, window, cx);
+        });
+        assert!(search_bar.read_with(cx, |search_bar, _| search_bar.is_dismissed()));
+        assert_eq!(selections(cx), [origin.clone()]);
+
+        // Searching backward jumps to the nearest match before the cursor, and accepting the
+        // search keeps it selected.
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.isearch(Direction::Prev, window, cx);
+        });
+        cx.simulate_input("or");
+        cx.run_until_parked();
+        assert_eq!(
+            selections(cx),
+            [DisplayPoint::new(DisplayRow(0), 41)..DisplayPoint::new(DisplayRow(0), 43)]
+        );
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.isearch_accept(&IsearchAccept, window, cx);
+        });
+        assert!(search_bar.read_with(cx, |search_bar, _| search_bar.is_dismissed()));
+        assert_eq!(
+            selections(cx),
+            [DisplayPoint::new(DisplayRow(0), 41)..DisplayPoint::new(DisplayRow(0), 43)]
+        );
+    }
+
     #[gpui::test]
     async fn test_search_query_with_match_whole_word(cx: &mut TestAppContext) {
         init_globals(cx);
@@ -2529,6 +2825,58 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_replace_in_selection(cx: &mut TestAppContext) {
+        let (editor, search_bar, cx) = init_test(cx);
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+                s.select_ranges([
+                    Point::new(0, 0)..Point::new(0, 20),
+                    Point::new(3, 0)..Point::new(3, 22),
+                ])
+            });
+        });
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.dismiss(&Dismiss, window, cx);
+            search_bar.deploy(&Deploy::replace_in_selection(), window, cx);
+        });
+        search_bar
+            .update_in(cx, |search_bar, window, cx| {
+                search_bar.search("r", Some(SearchOptions::CASE_SENSITIVE), true, window, cx)
+            })
+            .await
+            .unwrap();
+
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.replacement_editor.update(cx, |editor, cx| {
+                editor.set_text("R", window, cx);
+            });
+            search_bar.replace_next(&ReplaceNext, window, cx);
+        });
+        // The match nearest to the newest selection's head is replaced first.
+        assert_eq!(
+            editor.read_with(cx, |this, cx| this.text(cx).lines().nth(3).map(str::to_string)),
+            Some(
+                r#"for "find" oR "find and replace" operations on strings, or for input validation."#
+                    .to_string()
+            )
+        );
+
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.replace_all(&ReplaceAll, window, cx)
+        });
+        assert_eq!(
+            editor.read_with(cx, |this, cx| { this.text(cx) }),
+            r#"
+        A RegulaR expRession (shortened as regex or regexp;[1] also referred to as
+        rational expression[2][3]) is a sequence of characters that specifies a search
+        pattern in text. Usually such patterns are used by string-searching algorithms
+        foR "find" oR "find and replace" operations on strings, or for input validation.
+        "#
+            .unindent()
+        );
+    }
+
     #[gpui::test]
     async fn test_replace_special_characters(cx: &mut TestAppContext) {
         let (editor, search_bar, cx) = init_test(cx);
@@ -2592,6 +2940,47 @@ mod tests {
         .await;
     }
 
+    #[gpui::test]
+    async fn test_replace_preserving_case(cx: &mut TestAppContext) {
+        let (editor, search_bar, cx) = init_test(cx);
+
+        run_replacement_test(ReplacementTestParams {
+            editor: &editor,
+            search_bar: &search_bar,
+            cx,
+            search_text: "a regular",
+            search_options: Some(SearchOptions::PRESERVE_CASE),
+            replacement_text: "one typical",
+            replace_all: true,
+            expected_text: r#"
+            One typical expression (shortened as regex or regexp;[1] also referred to as
+            rational expression[2][3]) is a sequence of characters that specifies a search
+            pattern in text. Usually such patterns are used by string-searching algorithms
+            for "find" or "find and replace" operations on strings, or for input validation.
+            "#
+            .unindent(),
+        })
+        .await;
+
+        run_replacement_test(ReplacementTestParams {
+            editor: &editor,
+            search_bar: &search_bar,
+            cx,
+            search_text: "FIND",
+            search_options: Some(SearchOptions::WHOLE_WORD | SearchOptions::PRESERVE_CASE),
+            replacement_text: "Search",
+            replace_all: true,
+            expected_text: r#"
+            One typical expression (shortened as regex or regexp;[1] also referred to as
+            rational expression[2][3]) is a sequence of characters that specifies a search
+            pattern in text. Usually such patterns are used by string-searching algorithms
+            for "search" or "search and replace" operations on strings, or for input validation.
+            "#
+            .unindent(),
+        })
+        .await;
+    }
+
     #[gpui::test]
     async fn test_find_matches_in_selections_singleton_buffer_multiple_selections(
         cx: &mut TestAppContext,
@@ -2656,6 +3045,22 @@ mod tests {
                 ]
             );
         });
+        editor.update(cx, |editor, cx| {
+            assert_eq!(
+                editor.search_outside_range_highlights(cx),
+                &[
+                    Point::new(0, 0)..Point::new(1, 0),
+                    Point::new(2, 4)..Point::new(6, 0),
+                ]
+            );
+        });
+
+        search_bar.update_in(cx, |search_bar, window, cx| {
+            search_bar.toggle_selection(&ToggleSelection, window, cx);
+        });
+        editor.update(cx, |editor, cx| {
+            assert!(editor.search_outside_range_highlights(cx).is_empty());
+        });
     }
 
     #[gpui::test]
@@ -1,15 +1,18 @@
 use crate::{
-    BufferSearchBar, FocusSearch, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll, ReplaceNext,
-    SearchOption, SearchOptions, SearchSource, SelectNextMatch, SelectPreviousMatch,
-    ToggleCaseSensitive, ToggleIncludeIgnored, ToggleRegex, ToggleReplace, ToggleWholeWord,
+    BufferSearchBar, FocusSearch, NextHistoryQuery, PreviewReplaceAll, PreviousHistoryQuery,
+    ReplaceAll, ReplaceNext, SearchOption, SearchOptions, SearchSource, SelectNextMatch,
+    SelectPreviousMatch, ToggleCaseSensitive, ToggleIncludeIgnored, TogglePreserveCase,
+    ToggleRegex, ToggleReplace, ToggleWholeWord,
     buffer_search::Deploy,
+    replace_preview,
     search_bar::{ActionButtonState, input_base_styles, render_action_button, render_text_input},
 };
 use anyhow::Context as _;
-use collections::HashMap;
+use collections::{HashMap, VecDeque};
 use editor::{
     Anchor, Editor, EditorEvent, EditorSettings, MAX_TAB_TITLE_LEN, MultiBuffer, SelectionEffects,
     actions::{Backtab, SelectAll, Tab},
+    is_minified_buffer,
     items::active_match_index,
     multibuffer_context_lines,
 };
@@ -54,10 +57,21 @@ actions!(
         /// Moves to the next input field.
         NextField,
         /// Toggles the search filters panel.
-        ToggleFilters
+        ToggleFilters,
+        /// Shows the results of the next batch of files, when a search matched too many files to
+        /// show them all at once.
+        LoadMoreResults,
+        /// Shows the results in minified files, which are skipped by default.
+        IncludeMinifiedResults
     ]
 );
 
+/// How many files' results are shown at once. Showing the results of thousands of files at once
+/// freezes the UI, so the rest are shown in batches of this size when asked for.
+const RESULTS_BATCH_FILE_COUNT: usize = 200;
+
+type BufferSearchResults = (Entity<Buffer>, Vec<Range<language::Anchor>>);
+
 #[derive(Default)]
 struct ActiveSettings(HashMap<WeakEntity<Project>, ProjectSearchSettings>);
 
@@ -78,6 +92,15 @@ pub fn init(cx: &mut App) {
                 search_bar.toggle_filters(window, cx);
             },
         );
+        register_workspace_action(workspace, move |search_bar, _: &LoadMoreResults, _, cx| {
+            search_bar.load_more_results(cx);
+        });
+        register_workspace_action(
+            workspace,
+            move |search_bar, _: &IncludeMinifiedResults, _, cx| {
+                search_bar.include_minified_results(cx);
+            },
+        );
         register_workspace_action(
             workspace,
             move |search_bar, _: &ToggleCaseSensitive, window, cx| {
@@ -93,6 +116,12 @@ pub fn init(cx: &mut App) {
         register_workspace_action(workspace, move |search_bar, _: &ToggleRegex, window, cx| {
             search_bar.toggle_search_option(SearchOptions::REGEX, window, cx);
         });
+        register_workspace_action(
+            workspace,
+            move |search_bar, _: &TogglePreserveCase, _, cx| {
+                search_bar.toggle_preserve_case(cx);
+            },
+        );
         register_workspace_action(
             workspace,
             move |search_bar, action: &ToggleReplace, window, cx| {
@@ -186,6 +215,16 @@ pub struct ProjectSearch {
     search_id: usize,
     no_results: Option<bool>,
     limit_reached: bool,
+    /// How many files' results are shown, or being added to the results.
+    shown_file_count: usize,
+    /// How many files' results are shown before the rest are deferred.
+    shown_file_limit: usize,
+    /// The results that aren't shown yet, because too many files matched.
+    deferred_results: VecDeque<BufferSearchResults>,
+    /// The results in minified files, which aren't shown unless asked for.
+    minified_results: Vec<BufferSearchResults>,
+    include_minified: bool,
+    loading_deferred_results: Task<()>,
     search_history_cursor: SearchHistoryCursor,
     search_included_history_cursor: SearchHistoryCursor,
     search_excluded_history_cursor: SearchHistoryCursor,
@@ -244,6 +283,12 @@ impl ProjectSearch {
             search_id: 0,
             no_results: None,
             limit_reached: false,
+            shown_file_count: 0,
+            shown_file_limit: RESULTS_BATCH_FILE_COUNT,
+            deferred_results: VecDeque::new(),
+            minified_results: Vec::new(),
+            include_minified: false,
+            loading_deferred_results: Task::ready(()),
             search_history_cursor: Default::default(),
             search_included_history_cursor: Default::default(),
             search_excluded_history_cursor: Default::default(),
@@ -263,6 +308,12 @@ impl ProjectSearch {
             search_id: self.search_id,
             no_results: self.no_results,
             limit_reached: self.limit_reached,
+            shown_file_count: self.shown_file_count,
+            shown_file_limit: self.shown_file_limit,
+            deferred_results: self.deferred_results.clone(),
+            minified_results: self.minified_results.clone(),
+            include_minified: self.include_minified,
+            loading_deferred_results: Task::ready(()),
             search_history_cursor: self.search_history_cursor.clone(),
             search_included_history_cursor: self.search_included_history_cursor.clone(),
             search_excluded_history_cursor: self.search_excluded_history_cursor.clone(),
@@ -316,6 +367,12 @@ impl ProjectSearch {
                         .update(cx, |excerpts, cx| excerpts.clear(cx));
                     project_search.no_results = Some(true);
                     project_search.limit_reached = false;
+                    project_search.shown_file_count = 0;
+                    project_search.shown_file_limit = RESULTS_BATCH_FILE_COUNT;
+                    project_search.deferred_results.clear();
+                    project_search.minified_results.clear();
+                    project_search.include_minified = false;
+                    project_search.loading_deferred_results = Task::ready(());
                 })
                 .ok()?;
 
@@ -335,19 +392,8 @@ impl ProjectSearch {
 
                 let mut new_ranges = project_search
                     .update(cx, |project_search, cx| {
-                        project_search.excerpts.update(cx, |excerpts, cx| {
-                            buffers_with_ranges
-                                .into_iter()
-                                .map(|(buffer, ranges)| {
-                                    excerpts.set_anchored_excerpts_for_path(
-                                        buffer,
-                                        ranges,
-                                        multibuffer_context_lines(cx),
-                                        cx,
-                                    )
-                                })
-                                .collect::<FuturesOrdered<_>>()
-                        })
+                        let shown_results = project_search.defer_results(buffers_with_ranges, cx);
+                        project_search.show_results(shown_results, cx)
                     })
                     .ok()?;
 
@@ -363,7 +409,9 @@ impl ProjectSearch {
 
             project_search
                 .update(cx, |project_search, cx| {
-                    if !project_search.match_ranges.is_empty() {
+                    if !project_search.match_ranges.is_empty()
+                        || !project_search.deferred_results.is_empty()
+                    {
                         project_search.no_results = Some(false);
                     }
                     project_search.limit_reached = limit_reached;
@@ -376,6 +424,113 @@ impl ProjectSearch {
         }));
         cx.notify();
     }
+
+    /// Takes the results that should be shown right away, keeping back the results in minified
+    /// files and, once enough files are shown, the rest.
+    fn defer_results(
+        &mut self,
+        results: Vec<BufferSearchResults>,
+        cx: &App,
+    ) -> Vec<BufferSearchResults> {
+        let mut shown_results = Vec::new();
+        for (buffer, ranges) in results {
+            if !self.include_minified && is_minified_buffer(buffer.read(cx)) {
+                self.minified_results.push((buffer, ranges));
+            } else if self.shown_file_count < self.shown_file_limit
+                && self.deferred_results.is_empty()
+            {
+                self.shown_file_count += 1;
+                shown_results.push((buffer, ranges));
+            } else {
+                self.deferred_results.push_back((buffer, ranges));
+            }
+        }
+        shown_results
+    }
+
+    fn show_results(
+        &mut self,
+        results: Vec<BufferSearchResults>,
+        cx: &mut Context<Self>,
+    ) -> FuturesOrdered<Task<Vec<Range<Anchor>>>> {
+        self.excerpts.update(cx, |excerpts, cx| {
+            results
+                .into_iter()
+                .map(|(buffer, ranges)| {
+                    excerpts.set_anchored_excerpts_for_path(
+                        buffer,
+                        ranges,
+                        multibuffer_context_lines(cx),
+                        cx,
+                    )
+                })
+                .collect::<FuturesOrdered<_>>()
+        })
+    }
+
+    /// Shows the results of up to `file_count` of the files whose results were deferred.
+    fn load_deferred_results(&mut self, file_count: usize, cx: &mut Context<Self>) {
+        let file_count = file_count.min(self.deferred_results.len());
+        let results = self.deferred_results.drain(..file_count).collect();
+        self.shown_file_count += file_count;
+        let mut new_ranges = self.show_results(results, cx);
+        // Keep the earlier loads running, as dropping them would cancel them.
+        let previous_load = mem::replace(&mut self.loading_deferred_results, Task::ready(()));
+        self.loading_deferred_results = cx.spawn(async move |project_search, cx| {
+            previous_load.await;
+            while let Some(new_ranges) = new_ranges.next().await {
+                let updated = project_search.update(cx, |project_search, cx| {
+                    project_search.match_ranges.extend(new_ranges);
+                    cx.notify();
+                });
+                if updated.is_err() {
+                    return;
+                }
+            }
+        });
+        cx.notify();
+    }
+
+    pub fn load_more_results(&mut self, cx: &mut Context<Self>) {
+        self.shown_file_limit = self.shown_file_count + RESULTS_BATCH_FILE_COUNT;
+        self.load_deferred_results(RESULTS_BATCH_FILE_COUNT, cx);
+    }
+
+    /// Shows all the results, including those in minified files, and returns a task that resolves
+    /// once they're shown.
+    fn load_all_results(&mut self, cx: &mut Context<Self>) -> Task<()> {
+        self.include_minified_results(cx);
+        self.shown_file_limit = usize::MAX;
+        self.load_deferred_results(usize::MAX, cx);
+        mem::replace(&mut self.loading_deferred_results, Task::ready(()))
+    }
+
+    pub fn include_minified_results(&mut self, cx: &mut Context<Self>) {
+        self.include_minified = true;
+        let minified_results = mem::take(&mut self.minified_results);
+        let file_count = minified_results.len();
+        if file_count == 0 {
+            return;
+        }
+        for results in minified_results.into_iter().rev() {
+            self.deferred_results.push_front(results);
+        }
+        if self.no_results == Some(true) {
+            self.no_results = Some(false);
+        }
+        self.shown_file_limit = self.shown_file_limit.saturating_add(file_count);
+        self.load_deferred_results(file_count, cx);
+    }
+
+    /// How many files' results aren't shown yet.
+    pub fn deferred_file_count(&self) -> usize {
+        self.deferred_results.len()
+    }
+
+    /// How many minified files had results that were skipped.
+    pub fn minified_file_count(&self) -> usize {
+        self.minified_results.len()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -695,7 +850,9 @@ impl ProjectSearchView {
 
         let query = self.entity.read(cx).active_query.clone();
         if let Some(query) = query {
-            let query = query.with_replacement(self.replacement(cx));
+            let query = query
+                .with_replacement(self.replacement(cx))
+                .with_preserve_case(self.search_options.contains(SearchOptions::PRESERVE_CASE));
 
             // TODO: Do we need the clone here?
             let mat = self.entity.read(cx).match_ranges[active_index].clone();
@@ -713,13 +870,23 @@ impl ProjectSearchView {
             self.search(cx);
             return;
         }
+        if self.load_hidden_results_then(
+            |this, window, cx| this.replace_all(&ReplaceAll, window, cx),
+            window,
+            cx,
+        ) {
+            return;
+        }
         if self.active_match_index.is_none() {
             return;
         }
         let Some(query) = self.entity.read(cx).active_query.as_ref() else {
             return;
         };
-        let query = query.clone().with_replacement(self.replacement(cx));
+        let query = query
+            .clone()
+            .with_replacement(self.replacement(cx))
+            .with_preserve_case(self.search_options.contains(SearchOptions::PRESERVE_CASE));
 
         let match_ranges = self
             .entity
@@ -737,6 +904,72 @@ impl ProjectSearchView {
         });
     }
 
+    /// Shows the results that were deferred or skipped as minified, and calls `then` once they're
+    /// shown, so that replacing covers every result rather than only the shown ones. Returns
+    /// whether there were any such results.
+    fn load_hidden_results_then(
+        &mut self,
+        then: fn(&mut Self, &mut Window, &mut Context<Self>),
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let project_search = self.entity.read(cx);
+        if project_search.deferred_file_count() == 0 && project_search.minified_file_count() == 0 {
+            return false;
+        }
+        let load = self
+            .entity
+            .update(cx, |project_search, cx| project_search.load_all_results(cx));
+        cx.spawn_in(window, async move |this, cx| {
+            load.await;
+            this.update_in(cx, |this, window, cx| then(this, window, cx))
+                .ok();
+        })
+        .detach();
+        true
+    }
+
+    fn preview_replace_all(
+        &mut self,
+        _: &PreviewReplaceAll,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(last_search_query_text) = &self.entity.read(cx).last_search_query_text
+            && self.query_editor.read(cx).text(cx) != *last_search_query_text
+        {
+            // search query has changed, restart search and bail
+            self.search(cx);
+            return;
+        }
+        if self.load_hidden_results_then(
+            |this, window, cx| this.preview_replace_all(&PreviewReplaceAll, window, cx),
+            window,
+            cx,
+        ) {
+            return;
+        }
+        let Some(query) = self.entity.read(cx).active_query.as_ref() else {
+            return;
+        };
+        let query = query
+            .clone()
+            .with_replacement(self.replacement(cx))
+            .with_preserve_case(self.search_options.contains(SearchOptions::PRESERVE_CASE));
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let match_ranges = self.entity.read(cx).match_ranges.clone();
+        replace_preview::open_replace_preview(
+            workspace,
+            &self.results_editor,
+            &match_ranges,
+            &query,
+            window,
+            cx,
+        );
+    }
+
     pub fn new(
         workspace: WeakEntity<Workspace>,
         entity: Entity<ProjectSearch>,
@@ -1711,6 +1944,20 @@ impl ProjectSearchBar {
         true
     }
 
+    /// Unlike the other search options, preserving case only affects replacements, so toggling
+    /// it doesn't search again.
+    fn toggle_preserve_case(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(search_view) = self.active_project_search.as_ref() else {
+            return false;
+        };
+        search_view.update(cx, |search_view, cx| {
+            search_view.toggle_search_option(SearchOptions::PRESERVE_CASE, cx);
+            cx.notify();
+        });
+        cx.notify();
+        true
+    }
+
     fn toggle_replace(&mut self, _: &ToggleReplace, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(search) = &self.active_project_search {
             search.update(cx, |this, cx| {
@@ -1746,6 +1993,26 @@ impl ProjectSearchBar {
         }
     }
 
+    fn load_more_results(&mut self, cx: &mut Context<Self>) {
+        if let Some(search_view) = self.active_project_search.as_ref() {
+            let project_search = search_view.read(cx).entity.clone();
+            project_search.update(cx, |project_search, cx| {
+                project_search.load_more_results(cx)
+            });
+            cx.notify();
+        }
+    }
+
+    fn include_minified_results(&mut self, cx: &mut Context<Self>) {
+        if let Some(search_view) = self.active_project_search.as_ref() {
+            let project_search = search_view.read(cx).entity.clone();
+            project_search.update(cx, |project_search, cx| {
+                project_search.include_minified_results(cx)
+            });
+            cx.notify();
+        }
+    }
+
     fn toggle_opened_only(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
         if self.active_project_search.is_none() {
             return false;
@@ -1933,6 +2200,8 @@ impl Render for ProjectSearchBar {
         let theme_colors = cx.theme().colors();
         let project_search = search.entity.read(cx);
         let limit_reached = project_search.limit_reached;
+        let deferred_file_count = project_search.deferred_file_count();
+        let minified_file_count = project_search.minified_file_count();
 
         let color_override = match (
             &project_search.pending_search,
@@ -2037,7 +2306,48 @@ impl Render for ProjectSearchBar {
                             "Search limits reached.\nTry narrowing your search.",
                         ))
                     }),
-            );
+            )
+            .when(deferred_file_count > 0, |el| {
+                el.child(
+                    Button::new(
+                        "project-search-load-more",
+                        if deferred_file_count == 1 {
+                            "+1 file".to_string()
+                        } else {
+                            format!("+{deferred_file_count} files")
+                        },
+                    )
+                    .label_size(LabelSize::Small)
+                    .tooltip(|window, cx| {
+                        Tooltip::for_action(
+                            "Show the Results of More Files",
+                            &LoadMoreResults,
+                            window,
+                            cx,
+                        )
+                    })
+                    .on_click(cx.listener(|this, _, _, cx| this.load_more_results(cx))),
+                )
+            })
+            .when(minified_file_count > 0, |el| {
+                el.child(
+                    Button::new(
+                        "project-search-include-minified",
+                        format!("{minified_file_count} minified skipped"),
+                    )
+                    .label_size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .tooltip(|window, cx| {
+                        Tooltip::for_action(
+                            "Show the Results in Minified Files",
+                            &IncludeMinifiedResults,
+                            window,
+                            cx,
+                        )
+                    })
+                    .on_click(cx.listener(|this, _, _, cx| this.include_minified_results(cx))),
+                )
+            });
 
         let mode_column = h_flex()
             .gap_1()
@@ -2098,6 +2408,11 @@ impl Render for ProjectSearchBar {
             let replace_actions = h_flex()
                 .min_w_64()
                 .gap_1()
+                .child(SearchOption::PreserveCase.as_button(
+                    search.search_options,
+                    SearchSource::Project(cx),
+                    focus_handle.clone(),
+                ))
                 .child(render_action_button(
                     "project-search-replace-button",
                     IconName::ReplaceNext,
@@ -2112,6 +2427,14 @@ impl Render for ProjectSearchBar {
                     Default::default(),
                     "Replace All Matches",
                     &ReplaceAll,
+                    focus_handle.clone(),
+                ))
+                .child(render_action_button(
+                    "project-search-replace-button",
+                    IconName::Diff,
+                    Default::default(),
+                    "Preview Replace All",
+                    &PreviewReplaceAll,
                     focus_handle,
                 ));
 
@@ -2225,6 +2548,9 @@ impl Render for ProjectSearchBar {
             .on_action(cx.listener(|this, _: &ToggleCaseSensitive, window, cx| {
                 this.toggle_search_option(SearchOptions::CASE_SENSITIVE, window, cx);
             }))
+            .on_action(cx.listener(|this, _: &TogglePreserveCase, _, cx| {
+                this.toggle_preserve_case(cx);
+            }))
             .on_action(cx.listener(|this, action, window, cx| {
                 if let Some(search) = this.active_project_search.as_ref() {
                     search.update(cx, |this, cx| {
@@ -2239,6 +2565,13 @@ impl Render for ProjectSearchBar {
                     })
                 }
             }))
+            .on_action(cx.listener(|this, action, window, cx| {
+                if let Some(search) = this.active_project_search.as_ref() {
+                    search.update(cx, |this, cx| {
+                        this.preview_replace_all(action, window, cx);
+                    })
+                }
+            }))
             .when(search.filters_enabled, |this| {
                 this.on_action(cx.listener(|this, _: &ToggleIncludeIgnored, window, cx| {
                     this.toggle_search_option(SearchOptions::INCLUDE_IGNORED, window, cx);
@@ -4209,6 +4542,244 @@ pub mod tests {
             .unwrap();
     }
 
+    #[gpui::test]
+    async fn test_project_search_results_in_batches(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let file_count = RESULTS_BATCH_FILE_COUNT + 5;
+        let files = (0..file_count)
+            .map(|ix| (format!("file_{ix}.txt"), json!("let needle = 1;")))
+            .collect::<serde_json::Map<_, _>>();
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(path!("/dir"), serde_json::Value::Object(files))
+            .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.add_window(|window, cx| {
+            ProjectSearchView::new(workspace.downgrade(), search.clone(), window, cx, None)
+        });
+
+        perform_search(search_view, "needle", cx);
+        search.update(cx, |search, cx| {
+            assert_eq!(search.deferred_file_count(), 5);
+            assert_eq!(search.match_ranges.len(), RESULTS_BATCH_FILE_COUNT);
+            assert_eq!(
+                search.excerpts.read(cx).all_buffers().len(),
+                RESULTS_BATCH_FILE_COUNT
+            );
+            assert_eq!(search.no_results, Some(false));
+            search.load_more_results(cx);
+        });
+        cx.run_until_parked();
+        search.update(cx, |search, cx| {
+            assert_eq!(search.deferred_file_count(), 0);
+            assert_eq!(search.match_ranges.len(), file_count);
+            assert_eq!(search.excerpts.read(cx).all_buffers().len(), file_count);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_project_search_skips_minified_results(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let long_line = format!(
+            "let needle = \"{}\";",
+            "x".repeat(2 * editor::MINIFIED_LINE_LEN as usize)
+        );
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "main.rs": "let needle = 1;",
+                "vendor.min.js": "let needle = 1;",
+                "bundle.js": long_line,
+                "data.json": long_line,
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.add_window(|window, cx| {
+            ProjectSearchView::new(workspace.downgrade(), search.clone(), window, cx, None)
+        });
+
+        perform_search(search_view, "needle", cx);
+        search.update(cx, |search, cx| {
+            assert_eq!(search.minified_file_count(), 2);
+            assert_eq!(search.deferred_file_count(), 0);
+            assert_eq!(search.match_ranges.len(), 2, "data files aren't minified");
+            search.include_minified_results(cx);
+        });
+        cx.run_until_parked();
+        search.update(cx, |search, _| {
+            assert_eq!(search.minified_file_count(), 0);
+            assert_eq!(search.match_ranges.len(), 4);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_replace_all_replaces_hidden_results(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let mut files = (0..RESULTS_BATCH_FILE_COUNT + 1)
+            .map(|ix| (format!("file_{ix}.txt"), json!("let needle = 1;")))
+            .collect::<serde_json::Map<_, _>>();
+        files.insert("vendor.min.js".into(), json!("let needle = 1;"));
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(path!("/dir"), serde_json::Value::Object(files))
+            .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.add_window(|window, cx| {
+            ProjectSearchView::new(workspace.downgrade(), search.clone(), window, cx, None)
+        });
+
+        perform_search(search_view, "needle", cx);
+        search.update(cx, |search, _| {
+            assert_eq!(search.deferred_file_count(), 1);
+            assert_eq!(search.minified_file_count(), 1);
+        });
+        search_view
+            .update(cx, |search_view, window, cx| {
+                search_view
+                    .replacement_editor
+                    .update(cx, |editor, cx| editor.set_text("haystack", window, cx));
+                search_view.replace_all(&ReplaceAll, window, cx);
+            })
+            .unwrap();
+        cx.run_until_parked();
+        search.update(cx, |search, cx| {
+            assert_eq!(search.deferred_file_count(), 0);
+            assert_eq!(search.minified_file_count(), 0);
+            let buffers = search.excerpts.read(cx).all_buffers();
+            assert_eq!(buffers.len(), RESULTS_BATCH_FILE_COUNT + 2);
+            for buffer in buffers {
+                assert_eq!(buffer.read(cx).text(), "let haystack = 1;");
+            }
+        });
+    }
+
+    #[gpui::test]
+    async fn test_replace_all_preserving_case(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "one.rs": "let color = Color::new(COLOR);",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.add_window(|window, cx| {
+            ProjectSearchView::new(workspace.downgrade(), search.clone(), window, cx, None)
+        });
+
+        perform_search(search_view, "color", cx);
+        search_view
+            .update(cx, |search_view, window, cx| {
+                search_view.toggle_search_option(SearchOptions::PRESERVE_CASE, cx);
+                search_view
+                    .replacement_editor
+                    .update(cx, |editor, cx| editor.set_text("colour", window, cx));
+                search_view.replace_all(&ReplaceAll, window, cx);
+            })
+            .unwrap();
+        cx.run_until_parked();
+        search.update(cx, |search, cx| {
+            let buffers = search.excerpts.read(cx).all_buffers();
+            assert_eq!(buffers.len(), 1);
+            for buffer in buffers {
+                assert_eq!(buffer.read(cx).text(), "let colour = Colour::new(COLOUR);");
+            }
+        });
+    }
+
+    #[gpui::test]
+    async fn test_preview_replace_all_preserving_case(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "one.rs": "let color = Color::new(COLOR);",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project, window, cx));
+        window
+            .update(cx, |workspace, window, cx| {
+                ProjectSearchView::deploy_search(
+                    workspace,
+                    &workspace::DeploySearch::find(),
+                    window,
+                    cx,
+                )
+            })
+            .unwrap();
+        let search_view = window
+            .read_with(cx, |workspace, cx| {
+                workspace
+                    .active_item(cx)
+                    .and_then(|item| item.downcast::<ProjectSearchView>())
+            })
+            .unwrap()
+            .expect("the search view should be open");
+
+        window
+            .update(cx, |_, window, cx| {
+                search_view.update(cx, |search_view, cx| {
+                    search_view
+                        .query_editor
+                        .update(cx, |editor, cx| editor.set_text("color", window, cx));
+                    search_view.search(cx);
+                });
+            })
+            .unwrap();
+        cx.executor().advance_clock(
+            editor::SELECTION_HIGHLIGHT_DEBOUNCE_TIMEOUT + Duration::from_millis(100),
+        );
+        cx.background_executor.run_until_parked();
+
+        window
+            .update(cx, |_, window, cx| {
+                search_view.update(cx, |search_view, cx| {
+                    search_view.toggle_search_option(SearchOptions::PRESERVE_CASE, cx);
+                    search_view
+                        .replacement_editor
+                        .update(cx, |editor, cx| editor.set_text("colour", window, cx));
+                    search_view.preview_replace_all(&PreviewReplaceAll, window, cx);
+                });
+            })
+            .unwrap();
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+
+        let branch_text = window
+            .read_with(cx, |workspace, cx| {
+                let preview = workspace
+                    .active_item(cx)
+                    .and_then(|item| item.act_as::<Editor>(cx))
+                    .expect("the replace preview should be open");
+                let buffers = preview.read(cx).buffer().read(cx).all_buffers();
+                buffers.into_iter().next().unwrap().read(cx).text()
+            })
+            .unwrap();
+        assert_eq!(branch_text, "let colour = Colour::new(COLOUR);");
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings = SettingsStore::test(cx);
@@ -0,0 +1,468 @@
+//! Lists the tags configured by the `todo_highlights` setting, such as `TODO` and `FIXME`, that
+//! appear in comments across the project, as excerpts grouped by file.
+use std::{
+    any::{Any, TypeId},
+    ops::Range,
+    pin::pin,
+};
+
+use anyhow::Result;
+use editor::{
+    Anchor, Editor, EditorEvent, EditorSettings, MultiBuffer, multibuffer_context_lines,
+    todo_comments,
+};
+use futures::StreamExt as _;
+use gpui::{
+    AnyView, App, Context, Entity, EntityId, EventEmitter, FocusHandle, Focusable, IntoElement,
+    Render, SharedString, Subscription, Task, Window, actions,
+};
+use language::{ParseStatus, ToOffset as _};
+use project::{
+    Project, ProjectPath,
+    search::{SearchQuery, SearchResult},
+};
+use settings::Settings as _;
+use ui::prelude::*;
+use util::{ResultExt as _, paths::PathMatcher};
+use workspace::{
+    Item, ItemNavHistory, ToolbarItemLocation, Workspace,
+    item::{BreadcrumbText, ItemEvent, ItemHandle as _, SaveOptions},
+    searchable::SearchableItemHandle,
+};
+
+actions!(
+    project_todos,
+    [
+        /// Opens a multibuffer listing the TODO tags in comments across the project.
+        Deploy
+    ]
+);
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
+        workspace.register_action(ProjectTodosView::deploy);
+    })
+    .detach();
+}
+
+/// Builds a query matching any of `tags` as a whole, case-sensitive word.
+fn todo_search_query(tags: &[String]) -> Option<SearchQuery> {
+    let pattern = tags
+        .iter()
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| regex::escape(tag))
+        .collect::<Vec<_>>()
+        .join("|");
+    if pattern.is_empty() {
+        return None;
+    }
+    SearchQuery::regex(
+        format!("\\b(?:{pattern})\\b"),
+        false,
+        true,
+        false,
+        false,
+        PathMatcher::default(),
+        PathMatcher::default(),
+        false,
+        None,
+    )
+    .log_err()
+}
+
+pub struct ProjectTodosView {
+    project: Entity<Project>,
+    excerpts: Entity<MultiBuffer>,
+    editor: Entity<Editor>,
+    focus_handle: FocusHandle,
+    match_ranges: Vec<Range<Anchor>>,
+    pending_search: Option<Task<Option<()>>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ProjectTodosView {
+    fn deploy(
+        workspace: &mut Workspace,
+        _: &Deploy,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        if let Some(existing) = workspace.item_of_type::<ProjectTodosView>(cx) {
+            let is_active = workspace
+                .active_item(cx)
+                .is_some_and(|item| item.item_id() == existing.item_id());
+            existing.update(cx, |view, cx| view.search(cx));
+            workspace.activate_item(&existing, true, !is_active, window, cx);
+        } else {
+            let view = cx.new(|cx| ProjectTodosView::new(workspace.project().clone(), window, cx));
+            workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+        }
+    }
+
+    fn new(project: Entity<Project>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+        cx.on_focus_in(&focus_handle, window, |this, window, cx| {
+            if this.focus_handle.is_focused(window) && !this.match_ranges.is_empty() {
+                this.editor.focus_handle(cx).focus(window)
+            }
+        })
+        .detach();
+
+        let excerpts = cx.new(|cx| MultiBuffer::new(project.read(cx).capability()));
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(excerpts.clone(), Some(project.clone()), window, cx);
+            editor.set_vertical_scroll_margin(5, cx);
+            editor
+        });
+        let subscriptions = vec![cx.subscribe(&editor, |_, _, event: &EditorEvent, cx| {
+            // Reraise editor events for workspace item activation purposes
+            cx.emit(event.clone());
+        })];
+
+        let mut this = Self {
+            project,
+            excerpts,
+            editor,
+            focus_handle,
+            match_ranges: Vec::new(),
+            pending_search: None,
+            _subscriptions: subscriptions,
+        };
+        this.search(cx);
+        this
+    }
+
+    fn search(&mut self, cx: &mut Context<Self>) {
+        let tags = &EditorSettings::get_global(cx).todo_highlights.tags;
+        let Some(query) = todo_search_query(tags) else {
+            self.match_ranges.clear();
+            self.excerpts.update(cx, |excerpts, cx| excerpts.clear(cx));
+            self.pending_search = None;
+            cx.notify();
+            return;
+        };
+        let search = self
+            .project
+            .update(cx, |project, cx| project.search(query, cx));
+        self.pending_search = Some(cx.spawn(async move |this, cx| {
+            let mut results = pin!(search.ready_chunks(1024));
+            this.update(cx, |this, cx| {
+                this.match_ranges.clear();
+                this.excerpts.update(cx, |excerpts, cx| excerpts.clear(cx));
+            })
+            .ok()?;
+
+            while let Some(results) = results.next().await {
+                for result in results {
+                    let SearchResult::Buffer { buffer, ranges } = result else {
+                        continue;
+                    };
+
+                    // Comments can only be told apart once the buffer has been parsed.
+                    let mut parse_status = buffer
+                        .read_with(cx, |buffer, _| buffer.parse_status())
+                        .ok()?;
+                    while *parse_status.borrow() != ParseStatus::Idle {
+                        parse_status.changed().await.ok()?;
+                    }
+                    let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot()).ok()?;
+                    let ranges = ranges
+                        .into_iter()
+                        .filter(|range| {
+                            todo_comments::is_todo_position(
+                                &snapshot,
+                                range.start.to_offset(&snapshot),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    if ranges.is_empty() {
+                        continue;
+                    }
+
+                    let new_ranges = this
+                        .update(cx, |this, cx| {
+                            this.excerpts.update(cx, |excerpts, cx| {
+                                excerpts.set_anchored_excerpts_for_path(
+                                    buffer,
+                                    ranges,
+                                    multibuffer_context_lines(cx),
+                                    cx,
+                                )
+                            })
+                        })
+                        .ok()?
+                        .await;
+                    this.update(cx, |this, cx| {
+                        this.match_ranges.extend(new_ranges);
+                        cx.notify();
+                    })
+                    .ok()?;
+                }
+            }
+
+            this.update(cx, |this, cx| {
+                this.pending_search.take();
+                cx.emit(EditorEvent::TitleChanged);
+                cx.notify();
+            })
+            .ok()
+        }));
+        cx.notify();
+    }
+}
+
+impl EventEmitter<EditorEvent> for ProjectTodosView {}
+
+impl Focusable for ProjectTodosView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ProjectTodosView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let child = if self.match_ranges.is_empty() {
+            let label = if self.pending_search.is_some() {
+                "Searching…"
+            } else {
+                "No TODOs in this project"
+            };
+            v_flex()
+                .key_context("EmptyPane")
+                .size_full()
+                .justify_center()
+                .items_center()
+                .bg(cx.theme().colors().editor_background)
+                .child(Label::new(label).color(Color::Muted))
+        } else {
+            div().size_full().child(self.editor.clone())
+        };
+
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(child)
+    }
+}
+
+impl Item for ProjectTodosView {
+    type Event = EditorEvent;
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some("Project TODOs".into())
+    }
+
+    fn tab_content_text(&self, _detail: usize, _: &App) -> SharedString {
+        if self.pending_search.is_none() && !self.match_ranges.is_empty() {
+            format!("Project TODOs ({})", self.match_ranges.len()).into()
+        } else {
+            "Project TODOs".into()
+        }
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::ListTodo))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Project TODOs Opened")
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(EntityId, &dyn project::ProjectItem),
+    ) {
+        self.editor.for_each_project_item(cx, f)
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<workspace::WorkspaceId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Entity<Self>>
+    where
+        Self: Sized,
+    {
+        Some(cx.new(|cx| ProjectTodosView::new(self.project.clone(), window, cx)))
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.excerpts.read(cx).is_dirty(cx)
+    }
+
+    fn has_deleted_file(&self, cx: &App) -> bool {
+        self.excerpts.read(cx).has_deleted_file(cx)
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.excerpts.read(cx).has_conflict(cx)
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        true
+    }
+
+    fn save(
+        &mut self,
+        options: SaveOptions,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.editor.save(options, project, window, cx)
+    }
+
+    fn save_as(
+        &mut self,
+        _: Entity<Project>,
+        _: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        unreachable!()
+    }
+
+    fn reload(
+        &mut self,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.editor.reload(project, window, cx)
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, theme: &theme::Theme, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.editor.breadcrumbs(theme, cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_project_todos(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "one.txt": "first\nTODO: second\nTODOS and MY_FIXME",
+                "two.txt": "FIXME third",
+                "three.txt": "nothing to do",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        window
+            .update(cx, |workspace, window, cx| {
+                ProjectTodosView::deploy(workspace, &Deploy, window, cx)
+            })
+            .unwrap();
+        cx.background_executor.run_until_parked();
+
+        let view = window
+            .update(cx, |workspace, _, cx| {
+                workspace.item_of_type::<ProjectTodosView>(cx).unwrap()
+            })
+            .unwrap();
+        view.update(cx, |view, cx| {
+            assert_eq!(view.match_ranges.len(), 2);
+            assert_eq!(
+                view.editor.update(cx, |editor, cx| editor.display_text(cx)),
+                "\n\nfirst\nTODO: second\nTODOS and MY_FIXME\n\n\nFIXME third"
+            );
+        });
+    }
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+
+            theme::init(theme::LoadThemes::JustBase, cx);
+
+            language::init(cx);
+            client::init_settings(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            crate::init(cx);
+        });
+    }
+}
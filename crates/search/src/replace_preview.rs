@@ -0,0 +1,196 @@
+use editor::{Anchor, Editor, ProposedChangeLocation, ProposedChangesEditor};
+use gpui::{App, AppContext as _, Entity, Window};
+use language::{Buffer, Point, ToPoint as _};
+use project::search::SearchQuery;
+use std::ops::Range;
+use workspace::Workspace;
+
+/// Opens a tab previewing the replacement of `matches` in `editor`.
+///
+/// Every replacement is shown as a diff hunk that can be restored to exclude it. Applying the
+/// preview merges the remaining replacements with one transaction per buffer.
+pub(crate) fn open_replace_preview(
+    workspace: Entity<Workspace>,
+    editor: &Entity<Editor>,
+    matches: &[Range<Anchor>],
+    query: &SearchQuery,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let project = editor.read(cx).project().cloned();
+    let multi_buffer = editor.read(cx).buffer().clone();
+    let snapshot = multi_buffer.read(cx).snapshot(cx);
+
+    let mut locations = Vec::<ProposedChangeLocation<Point>>::new();
+    let mut edits_by_buffer = Vec::<(Entity<Buffer>, Vec<(Range<usize>, String)>)>::new();
+    for range in matches {
+        for (buffer_snapshot, range, _) in snapshot.range_to_buffer_ranges(range.clone()) {
+            let Some(buffer) = multi_buffer.read(cx).buffer(buffer_snapshot.remote_id()) else {
+                continue;
+            };
+            let text = buffer_snapshot
+                .text_for_range(range.clone())
+                .collect::<String>();
+            let Some(replacement) = query.replacement_for(&text) else {
+                continue;
+            };
+
+            let mut line_range =
+                range.start.to_point(buffer_snapshot)..range.end.to_point(buffer_snapshot);
+            line_range.start.column = 0;
+            line_range.end.column = buffer_snapshot.line_len(line_range.end.row);
+
+            let ix = match locations
+                .iter()
+                .position(|location| location.buffer == buffer)
+            {
+                Some(ix) => ix,
+                None => {
+                    locations.push(ProposedChangeLocation {
+                        buffer: buffer.clone(),
+                        ranges: Vec::new(),
+                    });
+                    edits_by_buffer.push((buffer, Vec::new()));
+                    locations.len() - 1
+                }
+            };
+            let ranges = &mut locations[ix].ranges;
+            match ranges.last_mut() {
+                Some(last) if last.end.row + 1 >= line_range.start.row => {
+                    last.end = last.end.max(line_range.end);
+                }
+                _ => ranges.push(line_range),
+            }
+            edits_by_buffer[ix]
+                .1
+                .push((range, replacement.into_owned()));
+        }
+    }
+    if locations.is_empty() {
+        return;
+    }
+
+    let title = format!("Replace “{}”", query.as_str());
+    let preview = cx.new(|cx| ProposedChangesEditor::new(title, locations, project, window, cx));
+    preview.update(cx, |preview, cx| {
+        for (buffer, edits) in edits_by_buffer {
+            if let Some(branch) = preview.branch_buffer_for_base(&buffer) {
+                branch.update(cx, |branch, cx| branch.edit(edits, None, cx));
+            }
+        }
+    });
+
+    window.defer(cx, move |window, cx| {
+        workspace.update(cx, |workspace, cx| {
+            workspace.add_item_to_active_pane(Box::new(preview), None, true, window, cx);
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::actions::ApplyAllDiffHunks;
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::time::Duration;
+    use util::{path, paths::PathMatcher};
+
+    #[gpui::test]
+    async fn test_replace_preview_applies_unrestored_replacements(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            client::init_settings(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            crate::init(cx);
+        });
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({ "one.txt": "needle 1\n\n\nneedle 2\n\n\nneedle 3\n" }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/dir/one.txt"), cx)
+            })
+            .await
+            .unwrap();
+        let editor = cx.update(|window, cx| {
+            cx.new(|cx| Editor::for_buffer(buffer.clone(), Some(project.clone()), window, cx))
+        });
+
+        let query = SearchQuery::text(
+            "needle",
+            false,
+            false,
+            false,
+            PathMatcher::default(),
+            PathMatcher::default(),
+            false,
+            None,
+        )
+        .unwrap()
+        .with_replacement("haystack".into());
+        cx.update(|window, cx| {
+            let snapshot = editor.read(cx).buffer().read(cx).snapshot(cx);
+            let matches = snapshot
+                .text()
+                .match_indices("needle")
+                .map(|(ix, needle)| {
+                    snapshot.anchor_after(ix)..snapshot.anchor_before(ix + needle.len())
+                })
+                .collect::<Vec<_>>();
+            open_replace_preview(workspace.clone(), &editor, &matches, &query, window, cx);
+        });
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+
+        // Every replacement is proposed in the preview, and none of them is made yet.
+        let preview = workspace.update(cx, |workspace, cx| {
+            workspace
+                .active_item(cx)
+                .and_then(|item| item.act_as::<Editor>(cx))
+                .expect("the replace preview should be open")
+        });
+        let branch_text = preview.update(cx, |preview, cx| {
+            let buffers = preview.buffer().read(cx).all_buffers();
+            buffers.into_iter().next().unwrap().read(cx).text()
+        });
+        assert_eq!(branch_text, "haystack 1\n\n\nhaystack 2\n\n\nhaystack 3\n");
+        assert_eq!(
+            buffer.read_with(cx, |buffer, _| buffer.text()),
+            "needle 1\n\n\nneedle 2\n\n\nneedle 3\n"
+        );
+
+        // Restoring the second hunk excludes its replacement, and applying the preview makes the
+        // others.
+        preview.update_in(cx, |preview, window, cx| {
+            let snapshot = preview.buffer().read(cx).snapshot(cx);
+            let offset = snapshot.text().find("haystack 2").unwrap();
+            let point = snapshot.offset_to_point(offset);
+            preview.restore_hunks_in_ranges(vec![point..point], window, cx);
+        });
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+        cx.dispatch_action(ApplyAllDiffHunks);
+        cx.run_until_parked();
+
+        assert_eq!(
+            buffer.read_with(cx, |buffer, _| buffer.text()),
+            "haystack 1\n\n\nneedle 2\n\n\nhaystack 3\n"
+        );
+    }
+}
@@ -15,6 +15,8 @@ use crate::project_search::ProjectSearchBar;
 
 pub mod buffer_search;
 pub mod project_search;
+pub mod project_todos;
+mod replace_preview;
 pub(crate) mod search_bar;
 pub mod search_status_button;
 
@@ -22,6 +24,7 @@ pub fn init(cx: &mut App) {
     menu::init();
     buffer_search::init(cx);
     project_search::init(cx);
+    project_todos::init(cx);
 }
 
 actions!(
@@ -37,6 +40,8 @@ actions!(
         ToggleIncludeIgnored,
         /// Toggles regular expression mode.
         ToggleRegex,
+        /// Toggles preserving the case of replaced matches.
+        TogglePreserveCase,
         /// Toggles the replace interface.
         ToggleReplace,
         /// Toggles searching within selection only.
@@ -55,6 +60,9 @@ actions!(
         PreviousHistoryQuery,
         /// Replaces all matches.
         ReplaceAll,
+        /// Opens a preview of replacing all matches, in which individual replacements can be
+        /// excluded before applying the rest.
+        PreviewReplaceAll,
         /// Replaces the next match.
         ReplaceNext,
     ]
@@ -69,6 +77,7 @@ bitflags! {
         const INCLUDE_IGNORED = 1 << SearchOption::IncludeIgnored as u8;
         const REGEX = 1 << SearchOption::Regex as u8;
         const ONE_MATCH_PER_LINE = 1 << SearchOption::OneMatchPerLine as u8;
+        const PRESERVE_CASE = 1 << SearchOption::PreserveCase as u8;
         /// If set, reverse direction when finding the active match
         const BACKWARDS = 1 << SearchOption::Backwards as u8;
     }
@@ -83,6 +92,7 @@ pub enum SearchOption {
     Regex,
     OneMatchPerLine,
     Backwards,
+    PreserveCase,
 }
 
 pub(crate) enum SearchSource<'a, 'b> {
@@ -103,6 +113,7 @@ impl SearchOption {
             SearchOption::Regex => "Use Regular Expressions",
             SearchOption::OneMatchPerLine => "One Match Per Line",
             SearchOption::Backwards => "Search Backwards",
+            SearchOption::PreserveCase => "Preserve Case",
         }
     }
 
@@ -112,6 +123,7 @@ impl SearchOption {
             SearchOption::CaseSensitive => ui::IconName::CaseSensitive,
             SearchOption::IncludeIgnored => ui::IconName::Sliders,
             SearchOption::Regex => ui::IconName::Regex,
+            SearchOption::PreserveCase => ui::IconName::PreserveCase,
             _ => panic!("{self:?} is not a named SearchOption"),
         }
     }
@@ -122,6 +134,7 @@ impl SearchOption {
             SearchOption::CaseSensitive => &ToggleCaseSensitive,
             SearchOption::IncludeIgnored => &ToggleIncludeIgnored,
             SearchOption::Regex => &ToggleRegex,
+            SearchOption::PreserveCase => &TogglePreserveCase,
             _ => panic!("{self:?} is not a toggle action"),
         }
     }
@@ -175,6 +188,7 @@ impl SearchOptions {
         options.set(SearchOptions::CASE_SENSITIVE, query.case_sensitive());
         options.set(SearchOptions::INCLUDE_IGNORED, query.include_ignored());
         options.set(SearchOptions::REGEX, query.is_regex());
+        options.set(SearchOptions::PRESERVE_CASE, query.preserve_case());
         options
     }
 
@@ -138,6 +138,13 @@ pub struct SettingsContent {
 
     /// Configuration for session-related features
     pub session: Option<SessionSettingsContent>,
+
+    /// Configuration for opening very large files
+    pub large_file: Option<LargeFileSettingsContent>,
+
+    /// Configuration for spell checking comments, strings and other prose.
+    pub spellcheck: Option<SpellcheckSettingsContent>,
+
     /// Control what info is collected by Zed.
     pub telemetry: Option<TelemetrySettingsContent>,
 
@@ -710,6 +717,20 @@ pub struct JournalSettingsContent {
     pub hour_format: Option<HourFormat>,
 }
 
+/// Settings specific to spell checking
+#[skip_serializing_none]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct SpellcheckSettingsContent {
+    /// Whether to check the spelling of comments, strings and other prose.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// Additional words to accept as correctly spelled.
+    ///
+    /// Default: []
+    pub words: Option<Vec<String>>,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum HourFormat {
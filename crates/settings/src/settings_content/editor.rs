@@ -41,6 +41,32 @@ pub struct EditorSettingsContent {
     ///
     /// Default: true
     pub rounded_selection: Option<bool>,
+    /// Rulers that follow the newest cursor, to help with column alignment.
+    pub cursor_ruler: Option<CursorRulerContent>,
+    /// Animates the cursor between its old and new positions when it moves.
+    pub smooth_caret: Option<SmoothCaretContent>,
+    /// Highlighting of tags such as `TODO` and `FIXME` in comments.
+    pub todo_highlights: Option<TodoHighlightsContent>,
+    /// Rules that turn text matching a regex in comments, such as issue references, into links
+    /// that can be opened with a secondary click.
+    ///
+    /// Default: []
+    pub link_rules: Option<Vec<LinkRuleContent>>,
+    /// Whether to render ANSI escape sequences in read-only editors as the colors and text styles
+    /// they describe, instead of showing the raw sequences.
+    ///
+    /// Default: true
+    pub render_ansi_escapes: Option<bool>,
+    /// Log file mode, which colors lines by level and follows files that are being appended to.
+    pub log_files: Option<LogFilesContent>,
+    /// Coloring and alignment of the columns of CSV and TSV files.
+    pub rainbow_csv: Option<RainbowCsvContent>,
+    /// Editing of Markdown tables.
+    pub markdown_tables: Option<MarkdownTablesContent>,
+    /// Highlighting of invisible, confusable and bidirectional control characters.
+    pub unicode_highlights: Option<UnicodeHighlightsContent>,
+    /// How the occurrences of the symbol under the cursor are highlighted.
+    pub document_highlights: Option<DocumentHighlightsContent>,
     /// The debounce delay before querying highlights from the language
     /// server based on the current cursor location.
     ///
@@ -63,10 +89,34 @@ pub struct EditorSettingsContent {
     pub minimap: Option<MinimapContent>,
     /// Gutter related settings
     pub gutter: Option<GutterContent>,
+    /// Whether to fold the imports at the top of a file, such as Rust's `use` declarations, when
+    /// it's opened.
+    ///
+    /// Default: false
+    pub auto_fold_imports: Option<bool>,
+    /// Whether to fold a region again when the cursor leaves it, after the region was unfolded
+    /// to reveal a search match, a diagnostic, or the target of a go-to action.
+    ///
+    /// Default: false
+    pub refold_revealed_regions: Option<bool>,
+    /// Whether to underline the syntax errors that the parser finds in a file, such as unclosed
+    /// strings and stray tokens, including in languages without a language server.
+    ///
+    /// Default: false
+    pub show_syntax_errors: Option<bool>,
     /// Whether the editor will scroll beyond the last line.
     ///
     /// Default: one_page
     pub scroll_beyond_last_line: Option<ScrollBeyondLastLine>,
+    /// The text shown at the start of the continuation lines of soft-wrapped lines, such as "↪".
+    /// Only its first character is used, and an empty string shows nothing.
+    ///
+    /// Default: ""
+    pub wrap_indicator: Option<String>,
+    /// How the continuation lines of soft-wrapped lines are indented.
+    ///
+    /// Default: same
+    pub wrap_indent: Option<WrapIndent>,
     /// The number of lines to keep above/below the cursor when auto-scrolling.
     ///
     /// Default: 3.
@@ -75,6 +125,12 @@ pub struct EditorSettingsContent {
     ///
     /// Default: false
     pub autoscroll_on_clicks: Option<bool>,
+    /// Keeps the cursor line at a fixed row of the viewport while typing and
+    /// moving, scrolling the buffer underneath it instead.
+    pub typewriter_scrolling: Option<TypewriterScrollingContent>,
+    /// Animates large programmatic scrolls, such as paging or jumping to a
+    /// definition or search result, instead of moving there instantly.
+    pub smooth_scroll: Option<SmoothScrollContent>,
     /// The number of characters to keep on either side when scrolling with the mouse.
     ///
     /// Default: 5.
@@ -92,8 +148,14 @@ pub struct EditorSettingsContent {
     pub fast_scroll_sensitivity: Option<f32>,
     /// Whether the line numbers on editors gutter are relative or not.
     ///
+    /// Deprecated: `true` is equivalent to setting `line_numbers` to `both`.
+    ///
     /// Default: false
     pub relative_line_numbers: Option<bool>,
+    /// How to number the lines in the editor's gutter.
+    ///
+    /// Default: absolute
+    pub line_numbers: Option<LineNumbers>,
     /// When to populate a new search's query based on the text under the cursor.
     ///
     /// Default: always
@@ -103,6 +165,16 @@ pub struct EditorSettingsContent {
     ///
     /// Default: alt
     pub multi_cursor_modifier: Option<MultiCursorModifier>,
+    /// How the left and right arrow keys move the cursor through lines that mix left-to-right
+    /// and right-to-left text.
+    ///
+    /// Default: logical
+    pub bidi_cursor_movement: Option<BidiCursorMovement>,
+    /// Whether moving the cursor up or down after moving it to the end of a line keeps it at the
+    /// end of each line it moves to, rather than at the column it was in.
+    ///
+    /// Default: false
+    pub sticky_end_of_line: Option<bool>,
     /// Hide the values of variables in `private` files, as defined by the
     /// private_files setting. This only changes the visual representation,
     /// the values are still present in the file and can be selected / copied / pasted
@@ -125,6 +197,11 @@ pub struct EditorSettingsContent {
     /// Default: true
     pub middle_click_paste: Option<bool>,
 
+    /// How text copied from several cursors is pasted at a single cursor.
+    ///
+    /// Default: verbatim
+    pub multi_cursor_paste: Option<MultiCursorPaste>,
+
     /// What to do when multibuffer is double clicked in some of its excerpts
     /// (parts of singleton buffers).
     ///
@@ -156,6 +233,11 @@ pub struct EditorSettingsContent {
     /// Default: 45
     #[schemars(range(min = 0, max = 106))]
     pub minimum_contrast_for_highlights: Option<MinimumContrast>,
+    /// Whether to announce the cursor position, the selected text, the highlighted completion
+    /// and the diagnostic under the cursor to screen readers.
+    ///
+    /// Default: true
+    pub screen_reader_announcements: Option<bool>,
 
     /// Whether to follow-up empty go to definition responses from the language server or not.
     /// `FindAllReferences` allows to look up references of the same symbol instead.
@@ -252,6 +334,11 @@ pub struct ScrollbarContent {
     ///
     /// Default: true
     pub cursors: Option<bool>,
+    /// Whether to fade the left and right edges of the text when it extends
+    /// beyond the viewport horizontally.
+    ///
+    /// Default: false
+    pub overflow_indicators: Option<bool>,
     /// Forcefully enable or disable the scrollbar for each axis
     pub axes: Option<ScrollbarAxesContent>,
 }
@@ -314,6 +401,10 @@ pub struct GutterContent {
     ///
     /// Default: true
     pub line_numbers: Option<bool>,
+    /// Whether to show the line numbers of the lines with cursors in bold.
+    ///
+    /// Default: false
+    pub bold_active_line_numbers: Option<bool>,
     /// Minimum number of characters to reserve space for in the gutter.
     ///
     /// Default: 4
@@ -326,6 +417,10 @@ pub struct GutterContent {
     ///
     /// Default: true
     pub breakpoints: Option<bool>,
+    /// Whether to show bookmarks in the gutter.
+    ///
+    /// Default: true
+    pub bookmarks: Option<bool>,
     /// Whether to show fold buttons in the gutter.
     ///
     /// Default: true
@@ -385,6 +480,33 @@ pub enum CurrentLineHighlight {
     All,
 }
 
+/// How to number the lines in the editor's gutter.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LineNumbers {
+    /// Show the line number of every line.
+    #[default]
+    Absolute,
+    /// Show the distance of every line from the cursor's line, which is numbered 0.
+    Relative,
+    /// Show the distance of every line from the cursor's line, and the line
+    /// number of the cursor's line.
+    Both,
+}
+
 /// When to populate a new search's query based on the text under the cursor.
 #[derive(
     Copy,
@@ -434,6 +556,33 @@ pub enum DoubleClickInMultibuffer {
     Open,
 }
 
+/// How text copied from several cursors is pasted at a single cursor.
+///
+/// Default: verbatim
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiCursorPaste {
+    /// Paste the copied text as it was copied, one cursor's text per line.
+    #[default]
+    Verbatim,
+    /// Paste each cursor's text at the cursor's column on consecutive lines, with a cursor after
+    /// each of them.
+    Split,
+}
+
 /// When to show the minimap thumb.
 ///
 /// Default: always
@@ -545,6 +694,33 @@ pub enum MultiCursorModifier {
     CmdOrCtrl,
 }
 
+/// How the arrow keys move the cursor through bidirectional text.
+///
+/// Default: logical
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum BidiCursorMovement {
+    /// Move through the text in the order it's stored, so the cursor moves right to left
+    /// through right-to-left text.
+    #[default]
+    Logical,
+    /// Move the cursor in the direction of the arrow key on screen.
+    Visual,
+}
+
 /// Whether the editor will scroll beyond the last line.
 ///
 /// Default: one_page
@@ -573,6 +749,33 @@ pub enum ScrollBeyondLastLine {
     VerticalScrollMargin,
 }
 
+/// How the continuation lines of soft-wrapped lines are indented.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WrapIndent {
+    /// Aligned with the first non-whitespace character of the line.
+    #[default]
+    Same,
+    /// Indented two columns further than the first non-whitespace character of the line, so
+    /// that they stand out from the lines that follow.
+    Extra,
+    /// Not indented.
+    None,
+}
+
 /// The shape of a selection cursor.
 #[derive(
     Copy,
@@ -725,6 +928,195 @@ pub struct DragAndDropSelectionContent {
     pub delay: Option<u64>,
 }
 
+/// Rulers that follow the newest cursor in the editor.
+#[skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+pub struct CursorRulerContent {
+    /// Whether to show a vertical ruler at the cursor's column.
+    ///
+    /// Default: false
+    pub column: Option<bool>,
+    /// Whether to show a horizontal ruler on the cursor's row.
+    ///
+    /// Default: false
+    pub row: Option<bool>,
+}
+
+/// Highlighting of tags such as `TODO` and `FIXME` in comments.
+#[skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+pub struct TodoHighlightsContent {
+    /// Whether to highlight the tags in comments.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// The tags to highlight, and to list in the project TODOs view.
+    ///
+    /// Default: ["TODO", "FIXME", "HACK", "XXX"]
+    pub tags: Option<Vec<String>>,
+}
+
+/// A rule turning text that matches `pattern` in comments into a link to `url`.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct LinkRuleContent {
+    /// The regex to match, such as `JIRA-\d+`.
+    pub pattern: String,
+    /// The URL to open. `$0` is replaced with the whole match, and `${1}`, `${2}`, and so on
+    /// with its capture groups.
+    pub url: String,
+}
+
+/// Log file mode, which colors lines by level and follows files that are being appended to.
+#[skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+pub struct LogFilesContent {
+    /// Whether to recognize log files, either by a `.log` file name or by their contents, and
+    /// color their lines by level.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// Whether to keep the cursor at the end of a log file that is being appended to, when it
+    /// was on the last line.
+    ///
+    /// Default: true
+    pub follow: Option<bool>,
+}
+
+/// Coloring and alignment of the columns of CSV and TSV files.
+#[skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+pub struct RainbowCsvContent {
+    /// Whether to color every column of CSV and TSV files with its own accent color.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// Whether to pad the columns so that they line up, without editing the file.
+    ///
+    /// Default: false
+    pub align_columns: Option<bool>,
+}
+
+/// Editing of Markdown tables.
+#[skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+pub struct MarkdownTablesContent {
+    /// Whether tab and shift-tab move between the cells of Markdown tables, and whether tables
+    /// are realigned when moving between cells or typing a `|`.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+}
+
+/// Highlighting of invisible, confusable and bidirectional control characters.
+#[skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+pub struct UnicodeHighlightsContent {
+    /// Whether to show zero-width characters, non-breaking spaces and bidirectional control
+    /// characters as placeholder glyphs, instead of as blank space.
+    ///
+    /// Default: true
+    pub invisible_characters: Option<bool>,
+    /// Whether to highlight characters in identifiers that look like ASCII letters, such as the
+    /// Cyrillic "а", when they are mixed with ASCII text.
+    ///
+    /// Default: false
+    pub confusables: Option<bool>,
+    /// Whether to warn about bidirectional control characters that reorder how the code is
+    /// displayed, which can make it read differently than it compiles.
+    ///
+    /// Default: true
+    pub bidi_controls: Option<bool>,
+}
+
+/// How the occurrences of the symbol under the cursor are highlighted.
+#[skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+pub struct DocumentHighlightsContent {
+    /// How the occurrences where the symbol is read are highlighted.
+    ///
+    /// Default: background
+    pub read: Option<DocumentHighlightStyle>,
+    /// How the occurrences where the symbol is written, such as assignments to a variable, are
+    /// highlighted.
+    ///
+    /// Default: background
+    pub write: Option<DocumentHighlightStyle>,
+}
+
+/// How an occurrence of the symbol under the cursor is highlighted.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    PartialEq,
+    Eq,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentHighlightStyle {
+    /// With the `editor.document_highlight.read_background` or
+    /// `editor.document_highlight.write_background` theme color behind the text.
+    #[default]
+    Background,
+    /// With a line in the `editor.document_highlight.read_underline` or
+    /// `editor.document_highlight.write_underline` theme color under the text.
+    Underline,
+}
+
+/// Smooth caret related settings.
+#[skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+pub struct SmoothCaretContent {
+    /// Whether to animate the cursor between its old and new positions.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// The duration of the cursor animation, in milliseconds.
+    ///
+    /// Default: 80
+    pub duration: Option<u64>,
+    /// The number of cursors above which the animation is disabled.
+    ///
+    /// Default: 10
+    pub max_cursors: Option<u32>,
+}
+
+/// Typewriter scrolling related settings.
+#[skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct TypewriterScrollingContent {
+    /// Whether to keep the cursor line at a fixed row of the viewport.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// Where in the viewport to keep the cursor line, as a fraction of its
+    /// height: 0.0 is the top, 0.5 the center and 1.0 the bottom.
+    ///
+    /// Default: 0.5
+    pub position: Option<f32>,
+}
+
+/// Smooth scrolling related settings.
+#[skip_serializing_none]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq, Eq)]
+pub struct SmoothScrollContent {
+    /// Whether to animate programmatic and keyboard scrolling.
+    /// Scrolling with the mouse wheel or trackpad is never animated.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// The duration of the scroll animation, in milliseconds.
+    ///
+    /// Default: 150
+    pub duration: Option<u64>,
+}
+
 /// When to show the minimap in the editor.
 ///
 /// Default: never
@@ -1,14 +1,14 @@
 use std::num::NonZeroU32;
 
-use collections::{HashMap, HashSet};
-use gpui::{Modifiers, SharedString};
+use collections::{HashMap, HashSet, IndexMap};
+use gpui::{FontFeatures, Modifiers, SharedString};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use settings_macros::MergeFrom;
 use std::sync::Arc;
 
-use crate::{ExtendingVec, merge_from};
+use crate::{ExtendingVec, FontFamilyName, merge_from};
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
@@ -79,6 +79,9 @@ pub enum EditPredictionProvider {
     Supermaven,
     Zed,
     Codestral,
+    /// An inline completion source registered by another crate or extension, selected with
+    /// `edit_predictions.custom_provider`.
+    Custom,
 }
 
 impl EditPredictionProvider {
@@ -88,7 +91,8 @@ impl EditPredictionProvider {
             EditPredictionProvider::None
             | EditPredictionProvider::Copilot
             | EditPredictionProvider::Supermaven
-            | EditPredictionProvider::Codestral => false,
+            | EditPredictionProvider::Codestral
+            | EditPredictionProvider::Custom => false,
         }
     }
 }
@@ -108,6 +112,11 @@ pub struct EditPredictionSettingsContent {
     pub copilot: Option<CopilotSettingsContent>,
     /// Settings specific to Codestral.
     pub codestral: Option<CodestralSettingsContent>,
+    /// The name of the registered inline completion source to use when the edit prediction
+    /// provider is `custom`.
+    ///
+    /// Default: null
+    pub custom_provider: Option<String>,
     /// Whether edit predictions are enabled in the assistant prompt editor.
     /// This has no effect if globally disabled.
     pub enabled_in_text_threads: Option<bool>,
@@ -203,6 +212,16 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: false
     pub hard_tabs: Option<bool>,
+    /// Whether to lay out the cells that tabs terminate on adjacent lines to equal widths,
+    /// so that columns separated by tabs stay aligned as they're edited.
+    ///
+    /// Default: false
+    pub elastic_tabstops: Option<bool>,
+    /// Whether to infer `tab_size` and `hard_tabs` from the existing indentation
+    /// of a file when it's opened.
+    ///
+    /// Default: true
+    pub detect_indentation: Option<bool>,
     /// How to soft-wrap long lines of text.
     ///
     /// Default: none
@@ -223,6 +242,16 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: []
     pub wrap_guides: Option<Vec<usize>>,
+    /// Whether to highlight the text of lines that are longer than the
+    /// 'preferred_line_length', from that column to the end of the line.
+    ///
+    /// Default: false
+    pub highlight_line_length_overflow: Option<bool>,
+    /// Whether to insert a line break while typing past the 'preferred_line_length',
+    /// continuing the comment prefix or list bullet of the line on the new line.
+    ///
+    /// Default: false
+    pub auto_wrap_at_preferred_line_length: Option<bool>,
     /// Indent guide related settings.
     pub indent_guides: Option<IndentGuideSettingsContent>,
     /// Whether or not to perform a buffer format before saving.
@@ -232,13 +261,28 @@ pub struct LanguageSettingsContent {
     /// Whether or not to remove any trailing whitespace from lines of a buffer
     /// before saving it.
     ///
-    /// Default: true
-    pub remove_trailing_whitespace_on_save: Option<bool>,
+    /// Default: all
+    pub remove_trailing_whitespace_on_save: Option<RemoveTrailingWhitespaceOnSave>,
     /// Whether or not to ensure there's a single newline at the end of a buffer
     /// when saving it.
     ///
     /// Default: true
     pub ensure_final_newline_on_save: Option<bool>,
+    /// The line ending that newly created files are saved with. Existing files keep the
+    /// line ending that they're detected to use.
+    ///
+    /// Default: platform_default
+    pub line_ending: Option<LineEndingSetting>,
+    /// Code action kinds to run through the language servers before saving, in order,
+    /// such as `{"source.organizeImports": true}`. Kinds mapped to `false` are skipped.
+    ///
+    /// Default: {}
+    pub code_actions_on_save: Option<IndexMap<String, bool>>,
+    /// How long to wait for a language server to provide each of the
+    /// `code_actions_on_save`, in milliseconds.
+    ///
+    /// Default: 2000
+    pub code_actions_on_save_timeout_ms: Option<u64>,
     /// How to perform a buffer format.
     ///
     /// Default: auto
@@ -283,12 +327,36 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: []
     pub edit_predictions_disabled_in: Option<Vec<String>>,
+    /// The font family to use for buffers of this language, instead of `buffer_font_family`.
+    ///
+    /// Default: null
+    pub font_family: Option<FontFamilyName>,
+    /// The OpenType features to enable for buffers of this language, instead of
+    /// `buffer_font_features`. For example, `{"calt": false}` disables ligatures.
+    ///
+    /// Default: null
+    pub font_features: Option<FontFeatures>,
+    /// The font size to use for buffers of this language, instead of `buffer_font_size`.
+    ///
+    /// Default: null
+    pub font_size: Option<f32>,
     /// Whether to show tabs and spaces in the editor.
     pub show_whitespaces: Option<ShowWhitespaceSetting>,
     /// Visible characters used to render whitespace when show_whitespaces is enabled.
     ///
     /// Default: "•" for spaces, "→" for tabs.
     pub whitespace_map: Option<WhitespaceMapContent>,
+    /// Whether to highlight whitespace at the end of lines with the
+    /// `editor.trailing_whitespace.background` theme color.
+    ///
+    /// Default: false
+    pub highlight_trailing_whitespace: Option<bool>,
+    /// Rules highlighting the text that matches a regex with a syntax theme style, such as
+    /// domain keywords, deprecated APIs, or internal markers. Rules set in a project's
+    /// `.zed/settings.json` only apply to that project.
+    ///
+    /// Default: []
+    pub highlight_rules: Option<Vec<HighlightRuleContent>>,
     /// Whether to start a new line with a comment when a previous line is a comment as well.
     ///
     /// Default: true
@@ -331,6 +399,16 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: true
     pub auto_indent_on_paste: Option<bool>,
+    /// Whether lines moved up or down should be reindented to match the block they
+    /// are moved into.
+    ///
+    /// Default: true
+    pub auto_indent_on_move_lines: Option<bool>,
+    /// Whether pasted content should be formatted by the language server's range
+    /// formatting after it is pasted.
+    ///
+    /// Default: false
+    pub format_on_paste: Option<bool>,
     /// Task configuration for this language.
     ///
     /// Default: {}
@@ -393,6 +471,20 @@ pub struct WhitespaceMapContent {
     pub tab: Option<char>,
 }
 
+/// A rule highlighting the text that matches `pattern` with the syntax theme's `style`.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct HighlightRuleContent {
+    /// The regex to match, such as `\bdeprecated_\w+`.
+    pub pattern: String,
+    /// The name of the syntax theme style to highlight matches with, such as `keyword` or
+    /// `emphasis.strong`.
+    pub style: String,
+    /// Globs that the paths of files, relative to their worktree, must match for the rule
+    /// to apply. The rule applies to all files when this is empty.
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
 /// The behavior of `editor::Rewrap`.
 #[derive(
     Debug,
@@ -535,6 +627,43 @@ pub struct CompletionSettingsContent {
     ///
     /// Default: "replace_suffix"
     pub lsp_insert_mode: Option<LspInsertMode>,
+    /// Controls how the `sortText` of LSP completions weighs against how well they match the query.
+    ///
+    /// Default: "after_match_score"
+    pub lsp_sort_text: Option<LspSortTextMode>,
+    /// Whether to rank completions that are marked as deprecated below the others.
+    ///
+    /// Default: false
+    pub deprioritize_deprecated: Option<bool>,
+    /// Whether to rank completions that start with the query above the ones that only match it fuzzily.
+    ///
+    /// Default: false
+    pub prefer_prefix_matches: Option<bool>,
+    /// Whether to apply the additional edits that language servers attach to completions, which are
+    /// mostly used to import the completed item.
+    ///
+    /// Default: true
+    pub auto_imports: Option<bool>,
+    /// Where the import statements added by completions are placed.
+    ///
+    /// Default: "merge"
+    pub auto_import_placement: Option<AutoImportPlacement>,
+    /// Whether to show a notification, with an option to undo it, when accepting a completion also
+    /// adds an import.
+    ///
+    /// Default: false
+    pub auto_import_notification: Option<bool>,
+    /// Whether to offer postfix completions, such as `expr.if`, that rewrite the expression
+    /// before the cursor using the language's postfix templates.
+    ///
+    /// Default: true
+    pub postfix: Option<bool>,
+    /// Additional postfix templates, keyed by the name typed after the dot, that override the ones
+    /// built into the language. `${receiver}` is replaced by the expression and the rest of the
+    /// template uses snippet syntax. An empty template disables the built-in one of the same name.
+    ///
+    /// Default: {}
+    pub postfix_templates: Option<HashMap<String, String>>,
 }
 
 #[derive(
@@ -564,6 +693,57 @@ pub enum LspInsertMode {
     ReplaceSuffix,
 }
 
+/// Controls where the import statements added by completions are placed.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoImportPlacement {
+    /// Uses the placement chosen by the language server, which usually merges imports into the
+    /// existing import statements and keeps them sorted.
+    #[default]
+    Merge,
+    /// Appends new import statements after the last import of the file.
+    Append,
+}
+
+/// Controls how the `sortText` of LSP completions is used to rank them.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LspSortTextMode {
+    /// Rank completions by how well they match the query, using `sortText` to break ties.
+    #[default]
+    AfterMatchScore,
+    /// Rank completions by `sortText`, using how well they match the query to break ties.
+    BeforeMatchScore,
+    /// Ignore `sortText`.
+    Ignore,
+}
+
 /// Controls how document's words are completed.
 #[derive(
     Copy,
@@ -635,6 +815,95 @@ pub enum FormatOnSave {
     Off,
 }
 
+/// The line ending that newly created files are saved with.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LineEndingSetting {
+    /// LF on macOS and Linux, CRLF on Windows.
+    PlatformDefault,
+    /// Unix line endings (`\n`).
+    Lf,
+    /// Windows line endings (`\r\n`).
+    Crlf,
+}
+
+/// Controls which trailing whitespace is removed when files are saved.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    MergeFrom,
+    strum::VariantArray,
+    strum::VariantNames,
+)]
+#[serde(
+    rename_all = "snake_case",
+    // allow specifying the mode as a boolean, which was the only option before modes were added
+    from = "RemoveTrailingWhitespaceOnSaveContent"
+)]
+pub enum RemoveTrailingWhitespaceOnSave {
+    /// Remove trailing whitespace from every line.
+    All,
+    /// Only remove trailing whitespace from lines that were modified since the file was last saved.
+    ModifiedLinesOnly,
+    /// Do not remove trailing whitespace.
+    None,
+}
+
+impl From<RemoveTrailingWhitespaceOnSaveContent> for RemoveTrailingWhitespaceOnSave {
+    fn from(value: RemoveTrailingWhitespaceOnSaveContent) -> Self {
+        match value {
+            RemoveTrailingWhitespaceOnSaveContent::Bool(true)
+            | RemoveTrailingWhitespaceOnSaveContent::Mode(
+                RemoveTrailingWhitespaceOnSaveMode::All,
+            ) => Self::All,
+            RemoveTrailingWhitespaceOnSaveContent::Mode(
+                RemoveTrailingWhitespaceOnSaveMode::ModifiedLinesOnly,
+            ) => Self::ModifiedLinesOnly,
+            RemoveTrailingWhitespaceOnSaveContent::Bool(false)
+            | RemoveTrailingWhitespaceOnSaveContent::Mode(
+                RemoveTrailingWhitespaceOnSaveMode::None,
+            ) => Self::None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(untagged)]
+enum RemoveTrailingWhitespaceOnSaveContent {
+    /// Remove trailing whitespace from every line (true), or not at all (false).
+    Bool(bool),
+    Mode(RemoveTrailingWhitespaceOnSaveMode),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum RemoveTrailingWhitespaceOnSaveMode {
+    /// Remove trailing whitespace from every line.
+    All,
+    /// Only remove trailing whitespace from lines that were modified since the file was last saved.
+    ModifiedLinesOnly,
+    /// Do not remove trailing whitespace.
+    None,
+}
+
 /// Controls which formatters should be used when formatting code.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema, MergeFrom)]
 #[serde(untagged)]
@@ -170,6 +170,26 @@ pub struct SessionSettingsContent {
     pub restore_unsaved_buffers: Option<bool>,
 }
 
+#[skip_serializing_none]
+#[derive(
+    Default, Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, JsonSchema, MergeFrom,
+)]
+pub struct LargeFileSettingsContent {
+    /// Whether to open very large files in large file mode, which disables
+    /// syntax highlighting, language servers, inlay hints and soft wrapping.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// The file size, in megabytes, above which a file is opened in large file mode.
+    ///
+    /// Default: 50
+    pub max_file_size_mb: Option<u64>,
+    /// The number of lines above which a file is opened in large file mode.
+    ///
+    /// Default: 500000
+    pub max_line_count: Option<u32>,
+}
+
 #[derive(Deserialize, Serialize, Clone, PartialEq, Eq, JsonSchema, MergeFrom, Debug)]
 #[serde(tag = "source", rename_all = "snake_case")]
 pub enum ContextServerSettingsContent {
@@ -375,6 +395,12 @@ pub struct DiagnosticsSettingsContent {
     /// Whether or not to include warning diagnostics.
     pub include_warnings: Option<bool>,
 
+    /// Whether going to the next or previous diagnostic skips warnings, information and hints
+    /// while there are errors in the file.
+    ///
+    /// Default: false
+    pub skip_warnings_when_errors_exist: Option<bool>,
+
     /// Settings for using LSP pull diagnostics mechanism in Zed.
     pub lsp_pull_diagnostics: Option<LspPullDiagnosticsSettingsContent>,
 
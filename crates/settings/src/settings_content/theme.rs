@@ -1,5 +1,5 @@
-use collections::{HashMap, IndexMap};
-use gpui::{FontFallbacks, FontFeatures, FontStyle, FontWeight, SharedString};
+use collections::{BTreeMap, HashMap, IndexMap};
+use gpui::{FontFeatures, FontScript, FontStyle, FontWeight, SharedString};
 use schemars::{JsonSchema, JsonSchema_repr};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
@@ -40,6 +40,10 @@ pub struct ThemeSettingsContent {
     #[serde(default)]
     #[schemars(extend("uniqueItems" = true))]
     pub buffer_font_fallbacks: Option<Vec<FontFamilyName>>,
+    /// The font fallbacks to use for the characters of specific scripts in text buffers, such
+    /// as `han`, `hangul`, `emoji` or `symbols`, which are tried before `buffer_font_fallbacks`.
+    #[serde(default)]
+    pub buffer_font_script_fallbacks: Option<BTreeMap<FontScript, Vec<FontFamilyName>>>,
     /// The default font size for rendering in text buffers.
     #[serde(default)]
     pub buffer_font_size: Option<f32>,
@@ -116,8 +120,8 @@ fn default_font_features() -> Option<FontFeatures> {
     Some(FontFeatures::default())
 }
 
-fn default_font_fallbacks() -> Option<FontFallbacks> {
-    Some(FontFallbacks::default())
+fn default_font_fallbacks() -> Option<Vec<FontFamilyName>> {
+    Some(Vec::new())
 }
 
 fn default_buffer_font_weight() -> Option<FontWeight> {
@@ -621,6 +625,10 @@ pub struct ThemeColorsContent {
     #[serde(rename = "editor.active_line.background")]
     pub editor_active_line_background: Option<String>,
 
+    /// Background Color. Used for the gutter next to the lines with cursors.
+    #[serde(rename = "editor.active_line.gutter_background")]
+    pub editor_active_line_gutter_background: Option<String>,
+
     #[serde(rename = "editor.highlighted_line.background")]
     pub editor_highlighted_line_background: Option<String>,
 
@@ -674,12 +682,37 @@ pub struct ThemeColorsContent {
     #[serde(rename = "editor.document_highlight.write_background")]
     pub editor_document_highlight_write_background: Option<String>,
 
+    /// Underline color of the occurrences where the symbol under the cursor is read.
+    ///
+    /// Only shown when the `document_highlights.read` setting is `underline`.
+    #[serde(rename = "editor.document_highlight.read_underline")]
+    pub editor_document_highlight_read_underline: Option<String>,
+
+    /// Underline color of the occurrences where the symbol under the cursor is written, such as
+    /// assignments to a variable.
+    ///
+    /// Only shown when the `document_highlights.write` setting is `underline`.
+    #[serde(rename = "editor.document_highlight.write_underline")]
+    pub editor_document_highlight_write_underline: Option<String>,
+
     /// Highlighted brackets background color.
     ///
     /// Matching brackets in the cursor scope are highlighted with this background color.
     #[serde(rename = "editor.document_highlight.bracket_background")]
     pub editor_document_highlight_bracket_background: Option<String>,
 
+    /// Background color of whitespace at the end of lines.
+    ///
+    /// Only shown when the `highlight_trailing_whitespace` setting is enabled.
+    #[serde(rename = "editor.trailing_whitespace.background")]
+    pub editor_trailing_whitespace_background: Option<String>,
+
+    /// Background color of TODO-style tags, such as `TODO` and `FIXME`, in comments.
+    ///
+    /// Only shown when the `todo_highlights` setting is enabled.
+    #[serde(rename = "editor.todo_highlight.background")]
+    pub editor_todo_highlight_background: Option<String>,
+
     /// Terminal background color.
     #[serde(rename = "terminal.background")]
     pub terminal_background: Option<String>,
@@ -373,10 +373,27 @@ pub struct StatusBarSettingsContent {
     ///
     /// Default: true
     pub active_language_button: Option<bool>,
+    /// Whether to show the indentation of the active buffer in the status bar.
+    ///
+    /// Default: true
+    pub indentation_button: Option<bool>,
+    /// Whether to show the encoding of the active buffer in the status bar, when it isn't UTF-8.
+    ///
+    /// Default: true
+    pub encoding_button: Option<bool>,
+    /// Whether to show the line ending of the active buffer in the status bar.
+    ///
+    /// Default: true
+    pub line_ending_button: Option<bool>,
     /// Whether to show the cursor position button in the status bar.
     ///
     /// Default: true
     pub cursor_position_button: Option<bool>,
+    /// Whether to show the sum and average of the selected numbers next to the cursor position,
+    /// when every selection contains a single number.
+    ///
+    /// Default: false
+    pub selection_number_stats: Option<bool>,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema, MergeFrom)]
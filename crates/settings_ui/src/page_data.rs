@@ -136,6 +136,58 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                     metadata: None,
                     files: USER,
                 }),
+                SettingsPageItem::SectionHeader("Large Files"),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Large File Mode",
+                    description: "Whether to disable expensive features, such as syntax highlighting and language servers, for very large files",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| match settings_content.large_file.as_ref() {
+                            Some(large_file) => &large_file.enabled,
+                            None => &None,
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content.large_file.get_or_insert_default().enabled
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Max File Size",
+                    description: "The file size, in megabytes, above which a file is opened in large file mode",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| match settings_content.large_file.as_ref() {
+                            Some(large_file) => &large_file.max_file_size_mb,
+                            None => &None,
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .large_file
+                                .get_or_insert_default()
+                                .max_file_size_mb
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Max Line Count",
+                    description: "The number of lines above which a file is opened in large file mode",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| match settings_content.large_file.as_ref() {
+                            Some(large_file) => &large_file.max_line_count,
+                            None => &None,
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .large_file
+                                .get_or_insert_default()
+                                .max_line_count
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
                 SettingsPageItem::SectionHeader("Scoped Settings"),
                 SettingsPageItem::SettingItem(SettingItem {
                     // todo(settings_ui): Implement another setting item type that just shows an edit in settings.json
@@ -331,6 +383,23 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                     ),
                     metadata: None,
                 }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    files: USER,
+                    title: "Script Font Fallbacks",
+                    description: "The font fallbacks to use for the characters of specific scripts in text buffers.",
+                    field: Box::new(
+                        SettingField {
+                            pick: |settings_content| {
+                                &settings_content.theme.buffer_font_script_fallbacks
+                            },
+                            pick_mut: |settings_content| {
+                                &mut settings_content.theme.buffer_font_script_fallbacks
+                            },
+                        }
+                        .unimplemented(),
+                    ),
+                    metadata: None,
+                }),
                 SettingsPageItem::SectionHeader("UI Font"),
                 SettingsPageItem::SettingItem(SettingItem {
                     title: "Font Family",
@@ -434,6 +503,30 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                     metadata: None,
                     files: USER,
                 }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Bidi Cursor Movement",
+                    description: "How the arrow keys move the cursor through right-to-left text",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| &settings_content.editor.bidi_cursor_movement,
+                        pick_mut: |settings_content| {
+                            &mut settings_content.editor.bidi_cursor_movement
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Sticky End Of Line",
+                    description: "Keep the cursor at the end of lines when moving up or down from the end of a line",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| &settings_content.editor.sticky_end_of_line,
+                        pick_mut: |settings_content| {
+                            &mut settings_content.editor.sticky_end_of_line
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
                 SettingsPageItem::SettingItem(SettingItem {
                     title: "Cursor Blink",
                     description: "Whether the cursor blinks in the editor",
@@ -511,6 +604,211 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                     metadata: None,
                     files: USER,
                 }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Cursor Column Ruler",
+                    description: "Show a vertical ruler at the cursor's column",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(cursor_ruler) = &settings_content.editor.cursor_ruler {
+                                &cursor_ruler.column
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .editor
+                                .cursor_ruler
+                                .get_or_insert_default()
+                                .column
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Cursor Row Ruler",
+                    description: "Show a horizontal ruler on the cursor's row",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(cursor_ruler) = &settings_content.editor.cursor_ruler {
+                                &cursor_ruler.row
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .editor
+                                .cursor_ruler
+                                .get_or_insert_default()
+                                .row
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Smooth Caret",
+                    description: "Whether to animate the cursor between its old and new positions",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(smooth_caret) = &settings_content.editor.smooth_caret {
+                                &smooth_caret.enabled
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .editor
+                                .smooth_caret
+                                .get_or_insert_default()
+                                .enabled
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Smooth Caret Duration",
+                    description: "The duration of the cursor animation, in milliseconds",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(smooth_caret) = &settings_content.editor.smooth_caret {
+                                &smooth_caret.duration
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .editor
+                                .smooth_caret
+                                .get_or_insert_default()
+                                .duration
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Smooth Caret Max Cursors",
+                    description: "The number of cursors above which the cursor animation is disabled",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(smooth_caret) = &settings_content.editor.smooth_caret {
+                                &smooth_caret.max_cursors
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .editor
+                                .smooth_caret
+                                .get_or_insert_default()
+                                .max_cursors
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "TODO Highlights",
+                    description: "Whether to highlight tags such as TODO and FIXME in comments",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(todo_highlights) = &settings_content.editor.todo_highlights
+                            {
+                                &todo_highlights.enabled
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .editor
+                                .todo_highlights
+                                .get_or_insert_default()
+                                .enabled
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Invisible Character Placeholders",
+                    description: "Whether to show zero-width characters, non-breaking spaces and bidirectional control characters as placeholder glyphs",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(unicode_highlights) =
+                                &settings_content.editor.unicode_highlights
+                            {
+                                &unicode_highlights.invisible_characters
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .editor
+                                .unicode_highlights
+                                .get_or_insert_default()
+                                .invisible_characters
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Highlight Confusable Characters",
+                    description: "Whether to highlight characters that look like ASCII letters in ASCII identifiers",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(unicode_highlights) =
+                                &settings_content.editor.unicode_highlights
+                            {
+                                &unicode_highlights.confusables
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .editor
+                                .unicode_highlights
+                                .get_or_insert_default()
+                                .confusables
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Warn About Bidirectional Controls",
+                    description: "Whether to warn about bidirectional control characters that reorder how code is displayed",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(unicode_highlights) =
+                                &settings_content.editor.unicode_highlights
+                            {
+                                &unicode_highlights.bidi_controls
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .editor
+                                .unicode_highlights
+                                .get_or_insert_default()
+                                .bidi_controls
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
                 SettingsPageItem::SettingItem(SettingItem {
                     title: "Minimum Contrast For Highlights",
                     description: "The minimum APCA perceptual contrast to maintain when rendering text over highlight backgrounds",
@@ -525,6 +823,20 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                     metadata: None,
                     files: USER,
                 }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Screen Reader Announcements",
+                    description: "Read the cursor position, selection, completions and diagnostics aloud with a screen reader",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            &settings_content.editor.screen_reader_announcements
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content.editor.screen_reader_announcements
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
                 SettingsPageItem::SectionHeader("Guides"),
                 SettingsPageItem::SettingItem(SettingItem {
                     title: "Show Wrap Guides",
@@ -690,6 +1002,29 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                         metadata: None,
                         files: USER,
                     }),
+                    SettingsPageItem::SectionHeader("Soft Wrap"),
+                    SettingsPageItem::SettingItem(SettingItem {
+                        title: "Wrap Indicator",
+                        description: "The text shown at the start of the continuation lines of soft-wrapped lines",
+                        field: Box::new(SettingField {
+                            pick: |settings_content| &settings_content.editor.wrap_indicator,
+                            pick_mut: |settings_content| {
+                                &mut settings_content.editor.wrap_indicator
+                            },
+                        }),
+                        metadata: None,
+                        files: USER,
+                    }),
+                    SettingsPageItem::SettingItem(SettingItem {
+                        title: "Wrap Indent",
+                        description: "How the continuation lines of soft-wrapped lines are indented",
+                        field: Box::new(SettingField {
+                            pick: |settings_content| &settings_content.editor.wrap_indent,
+                            pick_mut: |settings_content| &mut settings_content.editor.wrap_indent,
+                        }),
+                        metadata: None,
+                        files: USER,
+                    }),
                     SettingsPageItem::SectionHeader("Scrolling"),
                     SettingsPageItem::SettingItem(SettingItem {
                         title: "Scroll Beyond Last Line",
@@ -719,6 +1054,100 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                         metadata: None,
                         files: USER,
                     }),
+                    SettingsPageItem::SettingItem(SettingItem {
+                        title: "Typewriter Scrolling",
+                        description: "Whether to keep the cursor line at a fixed row of the viewport while typing and moving",
+                        field: Box::new(SettingField {
+                            pick: |settings_content| {
+                                if let Some(typewriter_scrolling) =
+                                    &settings_content.editor.typewriter_scrolling
+                                {
+                                    &typewriter_scrolling.enabled
+                                } else {
+                                    &None
+                                }
+                            },
+                            pick_mut: |settings_content| {
+                                &mut settings_content
+                                    .editor
+                                    .typewriter_scrolling
+                                    .get_or_insert_default()
+                                    .enabled
+                            },
+                        }),
+                        metadata: None,
+                        files: USER,
+                    }),
+                    SettingsPageItem::SettingItem(SettingItem {
+                        title: "Typewriter Scrolling Position",
+                        description: "Where in the viewport to keep the cursor line, from 0.0 (top) to 1.0 (bottom)",
+                        field: Box::new(SettingField {
+                            pick: |settings_content| {
+                                if let Some(typewriter_scrolling) =
+                                    &settings_content.editor.typewriter_scrolling
+                                {
+                                    &typewriter_scrolling.position
+                                } else {
+                                    &None
+                                }
+                            },
+                            pick_mut: |settings_content| {
+                                &mut settings_content
+                                    .editor
+                                    .typewriter_scrolling
+                                    .get_or_insert_default()
+                                    .position
+                            },
+                        }),
+                        metadata: None,
+                        files: USER,
+                    }),
+                    SettingsPageItem::SettingItem(SettingItem {
+                        title: "Smooth Scroll",
+                        description: "Whether to animate programmatic and keyboard scrolling",
+                        field: Box::new(SettingField {
+                            pick: |settings_content| {
+                                if let Some(smooth_scroll) = &settings_content.editor.smooth_scroll
+                                {
+                                    &smooth_scroll.enabled
+                                } else {
+                                    &None
+                                }
+                            },
+                            pick_mut: |settings_content| {
+                                &mut settings_content
+                                    .editor
+                                    .smooth_scroll
+                                    .get_or_insert_default()
+                                    .enabled
+                            },
+                        }),
+                        metadata: None,
+                        files: USER,
+                    }),
+                    SettingsPageItem::SettingItem(SettingItem {
+                        title: "Smooth Scroll Duration",
+                        description: "The duration of the scroll animation, in milliseconds",
+                        field: Box::new(SettingField {
+                            pick: |settings_content| {
+                                if let Some(smooth_scroll) = &settings_content.editor.smooth_scroll
+                                {
+                                    &smooth_scroll.duration
+                                } else {
+                                    &None
+                                }
+                            },
+                            pick_mut: |settings_content| {
+                                &mut settings_content
+                                    .editor
+                                    .smooth_scroll
+                                    .get_or_insert_default()
+                                    .duration
+                            },
+                        }),
+                        metadata: None,
+                        files: USER,
+                    }),
                     SettingsPageItem::SettingItem(SettingItem {
                         title: "Horizontal Scroll Margin",
                         description: "The number of characters to keep on either side when scrolling with the mouse",
@@ -909,13 +1338,11 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                         files: USER,
                     }),
                     SettingsPageItem::SettingItem(SettingItem {
-                        title: "Relative Line Numbers",
-                        description: "Whether the line numbers in the editor's gutter are relative or not",
+                        title: "Line Numbers Mode",
+                        description: "Whether the line numbers in the editor's gutter are absolute, relative to the cursor, or both",
                         field: Box::new(SettingField {
-                            pick: |settings_content| &settings_content.editor.relative_line_numbers,
-                            pick_mut: |settings_content| {
-                                &mut settings_content.editor.relative_line_numbers
-                            },
+                            pick: |settings_content| &settings_content.editor.line_numbers,
+                            pick_mut: |settings_content| &mut settings_content.editor.line_numbers,
                         }),
                         metadata: None,
                         files: USER,
@@ -964,6 +1391,28 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                         metadata: None,
                         files: USER,
                     }),
+                    SettingsPageItem::SettingItem(SettingItem {
+                        title: "Show Bookmarks",
+                        description: "Show bookmarks in the gutter",
+                        field: Box::new(SettingField {
+                            pick: |settings_content| {
+                                if let Some(gutter) = &settings_content.editor.gutter {
+                                    &gutter.bookmarks
+                                } else {
+                                    &None
+                                }
+                            },
+                            pick_mut: |settings_content| {
+                                &mut settings_content
+                                    .editor
+                                    .gutter
+                                    .get_or_insert_default()
+                                    .bookmarks
+                            },
+                        }),
+                        metadata: None,
+                        files: USER,
+                    }),
                     SettingsPageItem::SettingItem(SettingItem {
                         title: "Show Folds",
                         description: "Show code folding controls in the gutter",
@@ -982,6 +1431,66 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                         metadata: None,
                         files: USER,
                     }),
+                    SettingsPageItem::SettingItem(SettingItem {
+                        title: "Auto Fold Imports",
+                        description: "Fold the imports at the top of a file when it's opened",
+                        field: Box::new(SettingField {
+                            pick: |settings_content| &settings_content.editor.auto_fold_imports,
+                            pick_mut: |settings_content| {
+                                &mut settings_content.editor.auto_fold_imports
+                            },
+                        }),
+                        metadata: None,
+                        files: USER,
+                    }),
+                    SettingsPageItem::SettingItem(SettingItem {
+                        title: "Refold Revealed Regions",
+                        description: "Fold regions again when leaving them, after they were unfolded to reveal a search match, diagnostic or go-to target",
+                        field: Box::new(SettingField {
+                            pick: |settings_content| {
+                                &settings_content.editor.refold_revealed_regions
+                            },
+                            pick_mut: |settings_content| {
+                                &mut settings_content.editor.refold_revealed_regions
+                            },
+                        }),
+                        metadata: None,
+                        files: USER,
+                    }),
+                    SettingsPageItem::SettingItem(SettingItem {
+                        title: "Show Syntax Errors",
+                        description: "Underline the syntax errors that the parser finds in a file",
+                        field: Box::new(SettingField {
+                            pick: |settings_content| &settings_content.editor.show_syntax_errors,
+                            pick_mut: |settings_content| {
+                                &mut settings_content.editor.show_syntax_errors
+                            },
+                        }),
+                        metadata: None,
+                        files: USER,
+                    }),
+                    SettingsPageItem::SettingItem(SettingItem {
+                        title: "Bold Active Line Numbers",
+                        description: "Show the line numbers of the lines with cursors in bold",
+                        field: Box::new(SettingField {
+                            pick: |settings_content| {
+                                if let Some(gutter) = &settings_content.editor.gutter {
+                                    &gutter.bold_active_line_numbers
+                                } else {
+                                    &None
+                                }
+                            },
+                            pick_mut: |settings_content| {
+                                &mut settings_content
+                                    .editor
+                                    .gutter
+                                    .get_or_insert_default()
+                                    .bold_active_line_numbers
+                            },
+                        }),
+                        metadata: None,
+                        files: USER,
+                    }),
                     SettingsPageItem::SettingItem(SettingItem {
                         title: "Min Line Number Digits",
                         description: "Minimum number of characters to reserve space for in the gutter",
@@ -1171,6 +1680,28 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                         metadata: None,
                         files: USER,
                     }),
+                    SettingsPageItem::SettingItem(SettingItem {
+                        title: "Overflow Indicators",
+                        description: "Fade the edges of the text when it extends beyond the viewport horizontally",
+                        field: Box::new(SettingField {
+                            pick: |settings_content| {
+                                if let Some(scrollbar) = &settings_content.editor.scrollbar {
+                                    &scrollbar.overflow_indicators
+                                } else {
+                                    &None
+                                }
+                            },
+                            pick_mut: |settings_content| {
+                                &mut settings_content
+                                    .editor
+                                    .scrollbar
+                                    .get_or_insert_default()
+                                    .overflow_indicators
+                            },
+                        }),
+                        metadata: None,
+                        files: USER,
+                    }),
                     SettingsPageItem::SettingItem(SettingItem {
                         title: "Horizontal Scrollbar",
                         description: "When false, forcefully disables the horizontal scrollbar",
@@ -1535,6 +2066,27 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                                         metadata: None,
                                         files: USER,
                                     }),
+                                    SettingsPageItem::SettingItem(SettingItem {
+                                        title: "Skip Warnings When Errors Exist",
+                                        description: "Whether going to the next or previous diagnostic skips warnings, information and hints while there are errors in the file",
+                                        field: Box::new(SettingField {
+                                            pick: |settings_content| {
+                                                if let Some(diagnostics) = &settings_content.diagnostics {
+                                                    &diagnostics.skip_warnings_when_errors_exist
+                                                } else {
+                                                    &None
+                                                }
+                                            },
+                                            pick_mut: |settings_content| {
+                                                &mut settings_content
+                                                    .diagnostics
+                                                    .get_or_insert_default()
+                                                    .skip_warnings_when_errors_exist
+                                            },
+                                        }),
+                                        metadata: None,
+                                        files: USER,
+                                    }),
                                     SettingsPageItem::SectionHeader("Inline Diagnostics"),
                                     SettingsPageItem::SettingItem(SettingItem {
                                         title: "Enabled",
@@ -1701,6 +2253,54 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                                         files: USER,
                                     }),
                                     SettingsPageItem::SectionHeader("LSP Highlights"),
+                                    SettingsPageItem::SettingItem(SettingItem {
+                                        title: "Read Highlight Style",
+                                        description: "How the occurrences where the symbol under the cursor is read are highlighted",
+                                        field: Box::new(SettingField {
+                                            pick: |settings_content| {
+                                                if let Some(document_highlights) =
+                                                    &settings_content.editor.document_highlights
+                                                {
+                                                    &document_highlights.read
+                                                } else {
+                                                    &None
+                                                }
+                                            },
+                                            pick_mut: |settings_content| {
+                                                &mut settings_content
+                                                    .editor
+                                                    .document_highlights
+                                                    .get_or_insert_default()
+                                                    .read
+                                            },
+                                        }),
+                                        metadata: None,
+                                        files: USER,
+                                    }),
+                                    SettingsPageItem::SettingItem(SettingItem {
+                                        title: "Write Highlight Style",
+                                        description: "How the occurrences where the symbol under the cursor is written are highlighted",
+                                        field: Box::new(SettingField {
+                                            pick: |settings_content| {
+                                                if let Some(document_highlights) =
+                                                    &settings_content.editor.document_highlights
+                                                {
+                                                    &document_highlights.write
+                                                } else {
+                                                    &None
+                                                }
+                                            },
+                                            pick_mut: |settings_content| {
+                                                &mut settings_content
+                                                    .editor
+                                                    .document_highlights
+                                                    .get_or_insert_default()
+                                                    .write
+                                            },
+                                        }),
+                                        metadata: None,
+                                        files: USER,
+                                    }),
                                     SettingsPageItem::SettingItem(SettingItem {
                                         title: "Debounce",
                                         description: "The debounce delay before querying highlights from the language",
@@ -2051,21 +2651,84 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                         },
                         pick_mut: |settings_content| {
                             &mut settings_content
-                                .project_panel
+                                .project_panel
+                                .get_or_insert_default()
+                                .button
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Active Language Button",
+                    description: "Show the active language button in the status bar",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(status_bar) = &settings_content.status_bar {
+                                &status_bar.active_language_button
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .status_bar
+                                .get_or_insert_default()
+                                .active_language_button
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Indentation Button",
+                    description: "Show the indentation of the active buffer in the status bar",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(status_bar) = &settings_content.status_bar {
+                                &status_bar.indentation_button
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .status_bar
+                                .get_or_insert_default()
+                                .indentation_button
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Encoding Button",
+                    description: "Show the encoding of the active buffer in the status bar, when it isn't UTF-8",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(status_bar) = &settings_content.status_bar {
+                                &status_bar.encoding_button
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .status_bar
                                 .get_or_insert_default()
-                                .button
+                                .encoding_button
                         },
                     }),
                     metadata: None,
                     files: USER,
                 }),
                 SettingsPageItem::SettingItem(SettingItem {
-                    title: "Active Language Button",
-                    description: "Show the active language button in the status bar",
+                    title: "Line Ending Button",
+                    description: "Show the line ending of the active buffer in the status bar",
                     field: Box::new(SettingField {
                         pick: |settings_content| {
                             if let Some(status_bar) = &settings_content.status_bar {
-                                &status_bar.active_language_button
+                                &status_bar.line_ending_button
                             } else {
                                 &None
                             }
@@ -2074,7 +2737,7 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                             &mut settings_content
                                 .status_bar
                                 .get_or_insert_default()
-                                .active_language_button
+                                .line_ending_button
                         },
                     }),
                     metadata: None,
@@ -2101,6 +2764,27 @@ pub(crate) fn settings_data(cx: &App) -> Vec<SettingsPage> {
                     metadata: None,
                     files: USER,
                 }),
+                SettingsPageItem::SettingItem(SettingItem {
+                    title: "Selection Number Stats",
+                    description: "Show the sum and average of the selected numbers in the status bar",
+                    field: Box::new(SettingField {
+                        pick: |settings_content| {
+                            if let Some(status_bar) = &settings_content.status_bar {
+                                &status_bar.selection_number_stats
+                            } else {
+                                &None
+                            }
+                        },
+                        pick_mut: |settings_content| {
+                            &mut settings_content
+                                .status_bar
+                                .get_or_insert_default()
+                                .selection_number_stats
+                        },
+                    }),
+                    metadata: None,
+                    files: USER,
+                }),
                 SettingsPageItem::SettingItem(SettingItem {
                     title: "Terminal Button",
                     description: "Show the terminal button in the status bar",
@@ -5084,6 +5768,40 @@ fn language_settings_data() -> Vec<SettingsPageItem> {
             metadata: None,
             files: USER | LOCAL,
         }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Elastic Tabstops",
+            description: "Whether to lay out the cells that tabs terminate on adjacent lines to equal widths",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| &language.elastic_tabstops)
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language.elastic_tabstops
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Detect Indentation",
+            description: "Whether to infer the tab size and hard tabs from the existing indentation of a file when it's opened",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        &language.detect_indentation
+                    })
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language.detect_indentation
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
         SettingsPageItem::SettingItem(SettingItem {
             title: "Auto Indent",
             description: "Whether indentation should be adjusted based on the context whilst typing",
@@ -5118,6 +5836,40 @@ fn language_settings_data() -> Vec<SettingsPageItem> {
             metadata: None,
             files: USER | LOCAL,
         }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Auto Indent On Move Lines",
+            description: "Whether lines moved up or down should be reindented to match the block they are moved into",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        &language.auto_indent_on_move_lines
+                    })
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language.auto_indent_on_move_lines
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Format On Paste",
+            description: "Whether pasted content should be formatted by the language server",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| &language.format_on_paste)
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language.format_on_paste
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
         SettingsPageItem::SectionHeader("Wrapping"),
         SettingsPageItem::SettingItem(SettingItem {
             title: "Soft Wrap",
@@ -5151,6 +5903,42 @@ fn language_settings_data() -> Vec<SettingsPageItem> {
             metadata: None,
             files: USER | LOCAL,
         }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Highlight Line Length Overflow",
+            description: "Highlight the text of lines that are longer than the preferred line length",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        &language.highlight_line_length_overflow
+                    })
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language.highlight_line_length_overflow
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Auto Wrap At Preferred Line Length",
+            description: "Insert a line break while typing past the preferred line length",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        &language.auto_wrap_at_preferred_line_length
+                    })
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language.auto_wrap_at_preferred_line_length
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
         SettingsPageItem::SettingItem(SettingItem {
             title: "Preferred Line Length",
             description: "The column at which to soft-wrap lines, for buffers where soft-wrap is enabled",
@@ -5379,6 +6167,22 @@ fn language_settings_data() -> Vec<SettingsPageItem> {
             metadata: None,
             files: USER | LOCAL,
         }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Line Ending",
+            description: "The line ending that newly created files are saved with",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| &language.line_ending)
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language.line_ending
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
         SettingsPageItem::SettingItem(SettingItem {
             title: "Formatter",
             description: "How to perform a buffer format",
@@ -5548,6 +6352,24 @@ fn language_settings_data() -> Vec<SettingsPageItem> {
             metadata: None,
             files: USER | LOCAL,
         }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Highlight Trailing Whitespace",
+            description: "Whether to highlight whitespace at the end of lines",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        &language.highlight_trailing_whitespace
+                    })
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language.highlight_trailing_whitespace
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
         SettingsPageItem::SettingItem(SettingItem {
             title: "Space Whitespace Indicator",
             description: "Visible character used to render space characters when show_whitespaces is enabled (default: \"•\")",
@@ -6016,6 +6838,16 @@ fn language_settings_data() -> Vec<SettingsPageItem> {
             metadata: None,
             files: USER,
         }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Multi Cursor Paste",
+            description: "How text copied from several cursors is pasted at a single cursor",
+            field: Box::new(SettingField {
+                pick: |settings_content| &settings_content.editor.multi_cursor_paste,
+                pick_mut: |settings_content| &mut settings_content.editor.multi_cursor_paste,
+            }),
+            metadata: None,
+            files: USER,
+        }),
         SettingsPageItem::SettingItem(SettingItem {
             title: "Extend Comment On Newline",
             description: "Whether to start a new line with a comment when a previous line is a comment as well",
@@ -6233,6 +7065,150 @@ fn non_editor_language_settings_data() -> Vec<SettingsPageItem> {
             metadata: None,
             files: USER | LOCAL,
         }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Sort Text",
+            description: "Controls how the sortText of LSP completions weighs against how well they match the query",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        if let Some(completions) = &language.completions {
+                            &completions.lsp_sort_text
+                        } else {
+                            &None
+                        }
+                    })
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language.completions.get_or_insert_default().lsp_sort_text
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Deprioritize Deprecated",
+            description: "Whether to rank completions that are marked as deprecated below the others",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        if let Some(completions) = &language.completions {
+                            &completions.deprioritize_deprecated
+                        } else {
+                            &None
+                        }
+                    })
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language
+                            .completions
+                            .get_or_insert_default()
+                            .deprioritize_deprecated
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Prefer Prefix Matches",
+            description: "Whether to rank completions that start with the query above the ones that only match it fuzzily",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        if let Some(completions) = &language.completions {
+                            &completions.prefer_prefix_matches
+                        } else {
+                            &None
+                        }
+                    })
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language
+                            .completions
+                            .get_or_insert_default()
+                            .prefer_prefix_matches
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Auto Imports",
+            description: "Whether to apply the additional edits, mostly imports, that language servers attach to completions",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        if let Some(completions) = &language.completions {
+                            &completions.auto_imports
+                        } else {
+                            &None
+                        }
+                    })
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language.completions.get_or_insert_default().auto_imports
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Auto Import Placement",
+            description: "Where the import statements added by completions are placed",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        if let Some(completions) = &language.completions {
+                            &completions.auto_import_placement
+                        } else {
+                            &None
+                        }
+                    })
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language
+                            .completions
+                            .get_or_insert_default()
+                            .auto_import_placement
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
+        SettingsPageItem::SettingItem(SettingItem {
+            title: "Auto Import Notification",
+            description: "Whether to show a notification, with an option to undo it, when a completion adds an import",
+            field: Box::new(SettingField {
+                pick: |settings_content| {
+                    language_settings_field(settings_content, |language| {
+                        if let Some(completions) = &language.completions {
+                            &completions.auto_import_notification
+                        } else {
+                            &None
+                        }
+                    })
+                },
+                pick_mut: |settings_content| {
+                    language_settings_field_mut(settings_content, |language| {
+                        &mut language
+                            .completions
+                            .get_or_insert_default()
+                            .auto_import_notification
+                    })
+                },
+            }),
+            metadata: None,
+            files: USER | LOCAL,
+        }),
         SettingsPageItem::SectionHeader("Debuggers"),
         SettingsPageItem::SettingItem(SettingItem {
             title: "Debuggers",
@@ -1,4 +1,5 @@
 use anyhow::{Context as _, Result};
+use regex::{Captures, Regex, RegexBuilder};
 use smallvec::SmallVec;
 use std::{collections::BTreeMap, ops::Range};
 
@@ -16,9 +17,19 @@ pub struct TabStop {
 
 impl Snippet {
     pub fn parse(source: &str) -> Result<Self> {
+        Self::parse_with_variables(source, &|_| None)
+    }
+
+    /// Parses a snippet, using `resolve_variable` to look up the value of variables such as
+    /// `$TM_FILENAME`. Variables that can't be resolved are replaced by their default value, or by
+    /// their name when they have none.
+    pub fn parse_with_variables(
+        source: &str,
+        resolve_variable: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<Self> {
         let mut text = String::with_capacity(source.len());
         let mut tabstops = BTreeMap::new();
-        parse_snippet(source, false, &mut text, &mut tabstops)
+        parse_snippet(source, false, resolve_variable, &mut text, &mut tabstops)
             .context("failed to parse snippet")?;
 
         let len = text.len() as isize;
@@ -45,6 +56,7 @@ impl Snippet {
 fn parse_snippet<'a>(
     mut source: &'a str,
     nested: bool,
+    resolve_variable: &dyn Fn(&str) -> Option<String>,
     text: &mut String,
     tabstops: &mut BTreeMap<usize, TabStop>,
 ) -> Result<&'a str> {
@@ -52,7 +64,16 @@ fn parse_snippet<'a>(
         match source.chars().next() {
             None => return Ok(""),
             Some('$') => {
-                source = parse_tabstop(&source[1..], text, tabstops)?;
+                let rest = &source[1..];
+                source = if rest
+                    .strip_prefix('{')
+                    .unwrap_or(rest)
+                    .starts_with(|c: char| c == '_' || c.is_ascii_alphabetic())
+                {
+                    parse_variable(rest, resolve_variable, text, tabstops)?
+                } else {
+                    parse_tabstop(rest, resolve_variable, text, tabstops)?
+                };
             }
             Some('\\') => {
                 // As specified in the LSP spec (`Grammar` section),
@@ -91,6 +112,7 @@ fn parse_snippet<'a>(
 
 fn parse_tabstop<'a>(
     mut source: &'a str,
+    resolve_variable: &dyn Fn(&str) -> Option<String>,
     text: &mut String,
     tabstops: &mut BTreeMap<usize, TabStop>,
 ) -> Result<&'a str> {
@@ -108,7 +130,7 @@ fn parse_tabstop<'a>(
         }
 
         if source.starts_with(':') {
-            source = parse_snippet(&source[1..], true, text, tabstops)?;
+            source = parse_snippet(&source[1..], true, resolve_variable, text, tabstops)?;
         }
 
         if source.starts_with('}') {
@@ -133,6 +155,319 @@ fn parse_tabstop<'a>(
     Ok(source)
 }
 
+/// Parses a variable such as `$name`, `${name:default}` or `${name/regex/format/options}`.
+fn parse_variable<'a>(
+    mut source: &'a str,
+    resolve_variable: &dyn Fn(&str) -> Option<String>,
+    text: &mut String,
+    tabstops: &mut BTreeMap<usize, TabStop>,
+) -> Result<&'a str> {
+    let braced = source.starts_with('{');
+    if braced {
+        source = &source[1..];
+    }
+    let name_len = source
+        .find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+        .unwrap_or(source.len());
+    let (name, rest) = source.split_at(name_len);
+    source = rest;
+    let value = resolve_variable(name);
+    if !braced {
+        text.push_str(value.as_deref().unwrap_or(name));
+        return Ok(source);
+    }
+
+    if source.starts_with(':') {
+        // The default value may contain tabstops, so it's parsed separately and only kept when
+        // the variable can't be resolved.
+        let mut default_text = String::new();
+        let mut default_tabstops = BTreeMap::new();
+        source = parse_snippet(
+            &source[1..],
+            true,
+            resolve_variable,
+            &mut default_text,
+            &mut default_tabstops,
+        )?;
+        match value {
+            Some(value) => text.push_str(&value),
+            None => {
+                let offset = text.len() as isize;
+                for (index, tabstop) in default_tabstops {
+                    let entry = tabstops.entry(index).or_insert_with(|| TabStop {
+                        ranges: Default::default(),
+                        choices: tabstop.choices,
+                    });
+                    entry.ranges.extend(
+                        tabstop
+                            .ranges
+                            .into_iter()
+                            .map(|range| range.start + offset..range.end + offset),
+                    );
+                }
+                text.push_str(&default_text);
+            }
+        }
+    } else if source.starts_with('/') {
+        let (rest, transform) = parse_transform(&source[1..])?;
+        source = rest;
+        text.push_str(&transform.apply(value.as_deref().unwrap_or_default()));
+    } else {
+        text.push_str(value.as_deref().unwrap_or(name));
+    }
+
+    if source.starts_with('}') {
+        Ok(&source[1..])
+    } else {
+        anyhow::bail!("expected a closing brace")
+    }
+}
+
+/// A regular expression replacement applied to the value of a variable.
+struct Transform {
+    regex: Regex,
+    format: Vec<FormatItem>,
+    global: bool,
+}
+
+enum FormatItem {
+    Text(String),
+    Group {
+        index: usize,
+        modifier: Option<CaseModifier>,
+        if_text: Option<String>,
+        else_text: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum CaseModifier {
+    Upcase,
+    Downcase,
+    Capitalize,
+    Camelcase,
+    Pascalcase,
+}
+
+impl Transform {
+    fn apply(&self, value: &str) -> String {
+        let replacer = |captures: &Captures| {
+            let mut replacement = String::new();
+            for item in &self.format {
+                match item {
+                    FormatItem::Text(text) => replacement.push_str(text),
+                    FormatItem::Group {
+                        index,
+                        modifier,
+                        if_text,
+                        else_text,
+                    } => match captures.get(*index).filter(|group| !group.is_empty()) {
+                        Some(_) if if_text.is_some() => {
+                            replacement.push_str(if_text.as_deref().unwrap_or_default())
+                        }
+                        Some(group) => match modifier {
+                            Some(modifier) => replacement.push_str(&modifier.apply(group.as_str())),
+                            None => replacement.push_str(group.as_str()),
+                        },
+                        None => replacement.push_str(else_text.as_deref().unwrap_or_default()),
+                    },
+                }
+            }
+            replacement
+        };
+        if self.global {
+            self.regex.replace_all(value, replacer).into_owned()
+        } else {
+            self.regex.replace(value, replacer).into_owned()
+        }
+    }
+}
+
+impl CaseModifier {
+    fn apply(self, text: &str) -> String {
+        match self {
+            CaseModifier::Upcase => text.to_uppercase(),
+            CaseModifier::Downcase => text.to_lowercase(),
+            CaseModifier::Capitalize => capitalize(text),
+            CaseModifier::Pascalcase => text
+                .split(|c: char| c == '_' || c == '-' || c.is_whitespace())
+                .map(capitalize)
+                .collect(),
+            CaseModifier::Camelcase => {
+                let pascal = CaseModifier::Pascalcase.apply(text);
+                let mut chars = pascal.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_lowercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+        }
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Parses `regex/format/options` up to, but excluding, the closing brace.
+fn parse_transform(source: &str) -> Result<(&str, Transform)> {
+    let (regex, source) = parse_regex(source)?;
+    let (format, source) = parse_format(source)?;
+    let options_len = source.find('}').unwrap_or(source.len());
+    let (options, source) = source.split_at(options_len);
+    let regex = RegexBuilder::new(&regex)
+        .case_insensitive(options.contains('i'))
+        .multi_line(options.contains('m'))
+        .dot_matches_new_line(options.contains('s'))
+        .build()?;
+    let transform = Transform {
+        regex,
+        format,
+        global: options.contains('g'),
+    };
+    Ok((source, transform))
+}
+
+/// Reads a regular expression up to the first unescaped `/`, keeping any other escapes intact.
+fn parse_regex(source: &str) -> Result<(String, &str)> {
+    let mut text = String::new();
+    let mut chars = source.char_indices();
+    while let Some((ix, c)) = chars.next() {
+        match c {
+            '/' => return Ok((text, &source[ix + 1..])),
+            '\\' => match chars.next() {
+                Some((_, '/')) => text.push('/'),
+                Some((_, escaped)) => {
+                    text.push('\\');
+                    text.push(escaped);
+                }
+                None => text.push('\\'),
+            },
+            c => text.push(c),
+        }
+    }
+    anyhow::bail!("expected a closing slash")
+}
+
+fn parse_format(mut source: &str) -> Result<(Vec<FormatItem>, &str)> {
+    let mut items = Vec::new();
+    let mut text = String::new();
+    loop {
+        match source.chars().next() {
+            None => anyhow::bail!("expected a closing slash"),
+            Some('/') => {
+                if !text.is_empty() {
+                    items.push(FormatItem::Text(text));
+                }
+                return Ok((items, &source[1..]));
+            }
+            Some('\\') => {
+                source = &source[1..];
+                match source.chars().next() {
+                    Some(c @ ('$' | '\\' | '/')) => {
+                        text.push(c);
+                        source = &source[1..];
+                    }
+                    _ => text.push('\\'),
+                }
+            }
+            Some('$') if source[1..].starts_with(|c: char| c == '{' || c.is_ascii_digit()) => {
+                if !text.is_empty() {
+                    items.push(FormatItem::Text(std::mem::take(&mut text)));
+                }
+                let (item, rest) = parse_format_group(&source[1..])?;
+                items.push(item);
+                source = rest;
+            }
+            Some(c) => {
+                text.push(c);
+                source = &source[c.len_utf8()..];
+            }
+        }
+    }
+}
+
+/// Parses `n`, `{n}`, `{n:/upcase}`, `{n:+if}`, `{n:-else}`, `{n:else}` or `{n:?if:else}`.
+fn parse_format_group(source: &str) -> Result<(FormatItem, &str)> {
+    let Some(source) = source.strip_prefix('{') else {
+        let (index, rest) = parse_int(source)?;
+        let item = FormatItem::Group {
+            index,
+            modifier: None,
+            if_text: None,
+            else_text: None,
+        };
+        return Ok((item, rest));
+    };
+
+    let (index, mut source) = parse_int(source)?;
+    let mut modifier = None;
+    let mut if_text = None;
+    let mut else_text = None;
+    if let Some(rest) = source.strip_prefix(':') {
+        source = if let Some(rest) = rest.strip_prefix('/') {
+            let name_len = rest.find('}').unwrap_or(rest.len());
+            modifier = Some(match &rest[..name_len] {
+                "upcase" => CaseModifier::Upcase,
+                "downcase" => CaseModifier::Downcase,
+                "capitalize" => CaseModifier::Capitalize,
+                "camelcase" => CaseModifier::Camelcase,
+                "pascalcase" => CaseModifier::Pascalcase,
+                name => anyhow::bail!("unknown case modifier {name:?}"),
+            });
+            &rest[name_len..]
+        } else if let Some(rest) = rest.strip_prefix('+') {
+            let (text, rest) = parse_format_text(rest, '}');
+            if_text = Some(text);
+            rest
+        } else if let Some(rest) = rest.strip_prefix('?') {
+            let (text, rest) = parse_format_text(rest, ':');
+            if_text = Some(text);
+            let rest = rest.strip_prefix(':').context("expected a colon")?;
+            let (text, rest) = parse_format_text(rest, '}');
+            else_text = Some(text);
+            rest
+        } else {
+            let rest = rest.strip_prefix('-').unwrap_or(rest);
+            let (text, rest) = parse_format_text(rest, '}');
+            else_text = Some(text);
+            rest
+        };
+    }
+    let source = source
+        .strip_prefix('}')
+        .context("expected a closing brace")?;
+    let item = FormatItem::Group {
+        index,
+        modifier,
+        if_text,
+        else_text,
+    };
+    Ok((item, source))
+}
+
+/// Reads text up to, but excluding, the first unescaped `terminator`.
+fn parse_format_text(source: &str, terminator: char) -> (String, &str) {
+    let mut text = String::new();
+    let mut chars = source.char_indices();
+    while let Some((ix, c)) = chars.next() {
+        if c == terminator {
+            return (text, &source[ix..]);
+        } else if c == '\\' {
+            if let Some((_, escaped)) = chars.next() {
+                text.push(escaped);
+            }
+        } else {
+            text.push(c);
+        }
+    }
+    (text, "")
+}
+
 fn parse_int(source: &str) -> Result<(usize, &str)> {
     let len = source
         .find(|c: char| !c.is_ascii_digit())
@@ -324,6 +659,63 @@ mod tests {
         assert_eq!(tabstops(&snippet), &[vec![4..4], vec![7..7]]);
     }
 
+    #[test]
+    fn test_snippet_with_variables() {
+        let resolve = |name: &str| match name {
+            "TM_FILENAME" => Some("main.rs".to_string()),
+            "CURRENT_YEAR" => Some("2024".to_string()),
+            _ => None,
+        };
+
+        let snippet =
+            Snippet::parse_with_variables("// $TM_FILENAME, ${CURRENT_YEAR}$0", &resolve).unwrap();
+        assert_eq!(snippet.text, "// main.rs, 2024");
+        assert_eq!(tabstops(&snippet), &[vec![16..16]]);
+
+        // Defaults are only used for unknown variables, and may contain tabstops.
+        let snippet = Snippet::parse_with_variables(
+            "${TM_FILENAME:untitled} ${TM_SELECTED_TEXT:${1:text}} $UNKNOWN",
+            &resolve,
+        )
+        .unwrap();
+        assert_eq!(snippet.text, "main.rs text UNKNOWN");
+        assert_eq!(tabstops(&snippet), &[vec![8..12], vec![20..20]]);
+
+        // Without a resolver, variables are replaced by their name.
+        let snippet = Snippet::parse("$TM_FILENAME").unwrap();
+        assert_eq!(snippet.text, "TM_FILENAME");
+    }
+
+    #[test]
+    fn test_snippet_with_variable_transforms() {
+        let resolve = |name: &str| match name {
+            "TM_FILENAME" => Some("my_module.rs".to_string()),
+            _ => None,
+        };
+        let parse = |source| {
+            Snippet::parse_with_variables(source, &resolve)
+                .unwrap()
+                .text
+        };
+
+        assert_eq!(parse("${TM_FILENAME/(.*)\\..+$/$1/}"), "my_module");
+        assert_eq!(
+            parse("${TM_FILENAME/(.*)\\..+$/${1:/pascalcase}/}"),
+            "MyModule"
+        );
+        assert_eq!(
+            parse("${TM_FILENAME/(.*)\\..+$/${1:/camelcase}/}"),
+            "myModule"
+        );
+        assert_eq!(parse("${TM_FILENAME/_/-/g}"), "my-module.rs");
+        assert_eq!(parse("${TM_FILENAME/MY/${0:/upcase}/i}"), "MY_module.rs");
+        assert_eq!(
+            parse("${TM_FILENAME/(test)?.*/${1:?test:source}/}"),
+            "source"
+        );
+        assert_eq!(parse("${TM_FILENAME/(rs)?$/${1:+\\/}/}"), "my_module./");
+    }
+
     fn tabstops(snippet: &Snippet) -> Vec<Vec<Range<isize>>> {
         snippet.tabstops.iter().map(|t| t.ranges.to_vec()).collect()
     }
@@ -0,0 +1,220 @@
+use std::{ops::Range, sync::Arc};
+
+use collections::HashSet;
+
+/// Words shorter than this are never reported as misspelled.
+const MIN_WORD_LEN: usize = 3;
+const MAX_EDIT_DISTANCE: usize = 2;
+
+/// A case-insensitive set of correctly spelled words.
+#[derive(Clone, Default)]
+pub struct Dictionary {
+    words: Arc<WordList>,
+    user_words: Arc<HashSet<String>>,
+}
+
+#[derive(Default)]
+struct WordList {
+    words: HashSet<String>,
+    /// The words indexed by their length in chars, so that suggestions are only looked for among
+    /// words that are close enough in length.
+    words_by_len: Vec<Vec<String>>,
+}
+
+impl WordList {
+    fn new(words: HashSet<String>) -> Self {
+        let mut words_by_len = Vec::<Vec<String>>::new();
+        for word in &words {
+            let len = word.chars().count();
+            if words_by_len.len() <= len {
+                words_by_len.resize_with(len + 1, Vec::new);
+            }
+            words_by_len[len].push(word.clone());
+        }
+        Self {
+            words,
+            words_by_len,
+        }
+    }
+
+    fn words_with_len(&self, len: Range<usize>) -> impl Iterator<Item = &String> {
+        len.filter_map(|len| self.words_by_len.get(len)).flatten()
+    }
+}
+
+impl Dictionary {
+    /// Builds a dictionary from a newline-separated word list.
+    pub fn new(word_list: &str) -> Self {
+        Self {
+            words: Arc::new(WordList::new(parse_words(word_list))),
+            user_words: Arc::default(),
+        }
+    }
+
+    /// Returns a copy of this dictionary that also accepts the given words.
+    pub fn with_user_words<'a>(&self, words: impl IntoIterator<Item = &'a String>) -> Self {
+        Self {
+            words: self.words.clone(),
+            user_words: Arc::new(words.into_iter().map(|word| word.to_lowercase()).collect()),
+        }
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        let contains =
+            |word: &str| self.words.words.contains(word) || self.user_words.contains(word);
+        contains(&word)
+            || word
+                .strip_suffix("'s")
+                .or_else(|| word.strip_suffix('\''))
+                .is_some_and(contains)
+    }
+
+    /// Returns the words closest to `word`, best match first, in the same case as `word`.
+    pub fn suggestions(&self, word: &str, limit: usize) -> Vec<String> {
+        let lowercase = word.to_lowercase();
+        let len = lowercase.chars().count();
+        let lengths = len.saturating_sub(MAX_EDIT_DISTANCE)..len + MAX_EDIT_DISTANCE + 1;
+        let mut candidates =
+            self.words
+                .words_with_len(lengths)
+                .chain(self.user_words.iter().filter(|candidate| {
+                    candidate.chars().count().abs_diff(len) <= MAX_EDIT_DISTANCE
+                }))
+                .filter_map(|candidate| {
+                    let distance = edit_distance(&lowercase, candidate);
+                    (distance <= MAX_EDIT_DISTANCE).then_some((distance, candidate))
+                })
+                .collect::<Vec<_>>();
+        candidates.sort();
+        candidates.dedup_by(|a, b| a.1 == b.1);
+
+        let capitalized = word.chars().next().is_some_and(char::is_uppercase);
+        candidates
+            .into_iter()
+            .take(limit)
+            .map(|(_, candidate)| {
+                if capitalized {
+                    let mut chars = candidate.chars();
+                    chars
+                        .next()
+                        .map(|first| first.to_uppercase().chain(chars).collect())
+                        .unwrap_or_default()
+                } else {
+                    candidate.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the byte ranges of the words in `text` that should be spell checked.
+    ///
+    /// Anything that looks like code rather than prose is skipped: identifiers containing digits,
+    /// underscores or inner capitals, paths, URLs, and very short words.
+    pub fn words(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+        text.split_whitespace()
+            .filter(|chunk| !looks_like_code(chunk))
+            .flat_map(move |chunk| {
+                let chunk_start = chunk.as_ptr() as usize - text.as_ptr() as usize;
+                chunk
+                    .split(|c: char| !c.is_alphanumeric() && c != '_' && c != '\'')
+                    .filter_map(move |token| {
+                        let trimmed = token.trim_matches('\'');
+                        let start = trimmed.as_ptr() as usize - chunk.as_ptr() as usize;
+                        is_word(trimmed)
+                            .then(|| chunk_start + start..chunk_start + start + trimmed.len())
+                    })
+            })
+    }
+
+    /// Returns the byte ranges of the misspelled words in `text`.
+    pub fn misspellings<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Range<usize>> + 'a {
+        Self::words(text).filter(|range| !self.contains(&text[range.clone()]))
+    }
+}
+
+fn parse_words(word_list: &str) -> HashSet<String> {
+    word_list
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn looks_like_code(chunk: &str) -> bool {
+    const CODE_PATTERNS: &[&str] = &[
+        "://", "::", "/", "\\", "@", "=", "<", ">", "{", "}", "$", "#",
+    ];
+    if CODE_PATTERNS.iter().any(|pattern| chunk.contains(pattern)) {
+        return true;
+    }
+    // Method calls, file names and qualified names, like `foo.bar`.
+    let mut chars = chunk.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '.' && chars.peek().is_some_and(|next| next.is_alphanumeric()) {
+            return true;
+        }
+    }
+    false
+}
+
+fn is_word(token: &str) -> bool {
+    token.chars().count() >= MIN_WORD_LEN
+        && token.chars().all(|c| c.is_alphabetic() || c == '\'')
+        && !token.chars().skip(1).any(char::is_uppercase)
+}
+
+/// The optimal string alignment distance between `a` and `b`: the number of insertions,
+/// deletions, substitutions and transpositions of adjacent characters needed to turn one into
+/// the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_misspellings_and_suggestions() {
+        let dictionary = Dictionary::new("the\nquick\nbrown\nfox\njumps\nover\nlazy\ndog\n");
+        let text = "The quikc brown fox's jumps ovr the lazzy dog. See foo.bar, snake_case, \
+                    camelCase, https://example.com/fox and well-known.";
+        let misspellings = dictionary
+            .misspellings(text)
+            .map(|range| &text[range])
+            .collect::<Vec<_>>();
+        assert_eq!(
+            misspellings,
+            ["quikc", "ovr", "lazzy", "See", "and", "well", "known"]
+        );
+
+        let dictionary = dictionary.with_user_words(&["see".to_string()]);
+        assert!(dictionary.contains("See"));
+        assert_eq!(dictionary.suggestions("quikc", 3), ["quick"]);
+        assert_eq!(dictionary.suggestions("Lazzy", 3), ["Lazy"]);
+        assert_eq!(dictionary.suggestions("ovr", 3), ["over"]);
+    }
+}
@@ -0,0 +1,631 @@
+//! Spell checking for prose: comments, strings, Markdown text and commit messages.
+//!
+//! Which text counts as prose is decided by the syntax scope of each word, so languages injected
+//! into others (e.g. code blocks in Markdown) are checked according to their own rules.
+//! Misspellings are underlined in the editor, and corrections are offered as code actions.
+mod dictionary;
+
+use std::{ops::Range, rc::Rc, sync::Arc, time::Duration};
+
+use anyhow::Result;
+use collections::{HashMap, HashSet};
+use editor::{
+    Anchor, CodeActionProvider, Editor, EditorEvent, EditorMode, ExcerptId, MultiBufferSnapshot,
+};
+use fs::Fs;
+use gpui::{
+    App, AppContext as _, Context, Entity, Global, HighlightStyle, Subscription, Task,
+    UnderlineStyle, WeakEntity, Window, px,
+};
+use language::{
+    Buffer, BufferId, BufferSnapshot, LanguageScope, LanguageServerId, Point, ToOffset as _,
+};
+use project::{CodeAction, LspAction, ProjectTransaction};
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsStore, update_settings_file};
+use theme::ActiveTheme as _;
+use util::ResultExt as _;
+
+pub use dictionary::Dictionary;
+
+/// The bundled word list, whose source and license are described in `assets/dictionaries/LICENSES`.
+const DICTIONARY_PATH: &str = "dictionaries/en_US.txt";
+const CHECK_DEBOUNCE: Duration = Duration::from_millis(300);
+const MAX_SUGGESTIONS: usize = 5;
+const SPELLCHECK_CODE_ACTION_PROVIDER_ID: &str = "spellcheck";
+/// Spellcheck code actions don't come from a language server, so they are attributed to an id
+/// that is never given to one.
+const SPELLCHECK_SERVER_ID: LanguageServerId = LanguageServerId(usize::MAX);
+
+/// Languages whose text is prose, unless it is part of an override scope (e.g. inline code).
+const PROSE_LANGUAGES: &[&str] = &["Markdown", "Markdown-Inline", "Git Commit"];
+
+/// Settings specific to spell checking
+#[derive(Clone, Debug)]
+pub struct SpellcheckSettings {
+    /// Whether to check the spelling of comments, strings and other prose.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// Additional words to accept as correctly spelled.
+    ///
+    /// Default: []
+    pub words: Vec<String>,
+}
+
+impl Settings for SpellcheckSettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let spellcheck = content.spellcheck.clone().unwrap();
+
+        Self {
+            enabled: spellcheck.enabled.unwrap(),
+            words: spellcheck.words.unwrap(),
+        }
+    }
+}
+
+struct GlobalDictionary(Dictionary);
+
+impl Global for GlobalDictionary {}
+
+pub fn init(fs: Arc<dyn Fs>, cx: &mut App) {
+    SpellcheckSettings::register(cx);
+
+    let dictionary = cx
+        .asset_source()
+        .load(DICTIONARY_PATH)
+        .log_err()
+        .flatten()
+        .map(|word_list| Dictionary::new(&String::from_utf8_lossy(&word_list)))
+        .unwrap_or_default();
+    cx.set_global(GlobalDictionary(dictionary));
+
+    cx.observe_new(move |editor: &mut Editor, window, cx| {
+        if let Some(window) = window
+            && matches!(
+                editor.mode(),
+                EditorMode::Full { .. } | EditorMode::AutoHeight { .. }
+            )
+        {
+            Spellchecker::register(editor, fs.clone(), window, cx);
+        }
+    })
+    .detach();
+}
+
+/// The bundled dictionary, extended with the words from the user's settings.
+fn dictionary(cx: &App) -> Dictionary {
+    cx.try_global::<GlobalDictionary>()
+        .map(|dictionary| dictionary.0.clone())
+        .unwrap_or_default()
+        .with_user_words(&SpellcheckSettings::get_global(cx).words)
+}
+
+/// Returns the ranges of the misspelled words in the prose within `range`.
+pub fn misspellings(
+    buffer: &BufferSnapshot,
+    range: Range<usize>,
+    dictionary: &Dictionary,
+) -> Vec<Range<usize>> {
+    let text = buffer.text_for_range(range.clone()).collect::<String>();
+    dictionary
+        .misspellings(&text)
+        .map(|word| range.start + word.start..range.start + word.end)
+        .filter(|word| {
+            buffer
+                .language_scope_at(word.start)
+                .is_some_and(|scope| is_prose(&scope))
+        })
+        .collect()
+}
+
+fn is_prose(scope: &LanguageScope) -> bool {
+    match scope.override_name() {
+        Some(name) => matches!(name, "comment" | "string"),
+        None => PROSE_LANGUAGES.contains(&scope.language_name().as_ref()),
+    }
+}
+
+/// A marker type for the misspelling highlights.
+enum Misspelling {}
+
+/// Keeps the misspelling highlights of an editor up to date, by checking the text that is
+/// scrolled into view and the text that was edited since the last check.
+struct Spellchecker {
+    editor: WeakEntity<Editor>,
+    /// The words from the user's settings, or `None` when spell checking is disabled.
+    user_words: Option<Vec<String>>,
+    /// The misspellings found so far, sorted, which are highlighted in the editor.
+    misspellings: Vec<Range<Anchor>>,
+    /// The version of each buffer when it was last checked.
+    checked_versions: HashMap<BufferId, clock::Global>,
+    check_task: Task<()>,
+    editor_subscription: Option<Subscription>,
+    _settings_subscription: Subscription,
+}
+
+impl Spellchecker {
+    fn register(
+        editor: &mut Editor,
+        fs: Arc<dyn Fs>,
+        window: &mut Window,
+        cx: &mut Context<Editor>,
+    ) {
+        let editor_handle = cx.entity().downgrade();
+        let spellchecker = cx.new(|cx| {
+            let mut this = Self {
+                editor: editor_handle,
+                user_words: None,
+                misspellings: Vec::new(),
+                checked_versions: HashMap::default(),
+                check_task: Task::ready(()),
+                editor_subscription: None,
+                _settings_subscription: cx
+                    .observe_global::<SettingsStore>(|this, cx| this.settings_changed(cx)),
+            };
+            this.settings_changed(cx);
+            this
+        });
+        editor.add_code_action_provider(
+            Rc::new(SpellcheckCodeActionProvider {
+                fs,
+                _spellchecker: spellchecker,
+            }),
+            window,
+            cx,
+        );
+    }
+
+    /// Starts or stops following the editor when spell checking is toggled, and checks the text
+    /// again when the user's words change.
+    fn settings_changed(&mut self, cx: &mut Context<Self>) {
+        let settings = SpellcheckSettings::get_global(cx);
+        let user_words = settings.enabled.then(|| settings.words.clone());
+        if user_words == self.user_words {
+            return;
+        }
+        self.user_words = user_words;
+        self.misspellings.clear();
+        self.checked_versions.clear();
+
+        if self.user_words.is_none() {
+            self.editor_subscription = None;
+            self.check_task = Task::ready(());
+            self.editor
+                .update(cx, |editor, cx| editor.clear_highlights::<Misspelling>(cx))
+                .ok();
+            return;
+        }
+        if self.editor_subscription.is_none()
+            && let Some(editor) = self.editor.upgrade()
+        {
+            self.editor_subscription = Some(cx.subscribe(
+                &editor,
+                |this, _, event: &EditorEvent, cx| match event {
+                    EditorEvent::BufferEdited
+                    | EditorEvent::Reparsed(_)
+                    | EditorEvent::ExcerptsAdded { .. }
+                    | EditorEvent::ExcerptsExpanded { .. }
+                    | EditorEvent::ScrollPositionChanged { .. } => this.schedule_check(cx),
+                    _ => {}
+                },
+            ));
+        }
+        self.schedule_check(cx);
+    }
+
+    fn schedule_check(&mut self, cx: &mut Context<Self>) {
+        let editor = self.editor.clone();
+        self.check_task = cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(CHECK_DEBOUNCE).await;
+            let Some((snapshot, visible_range, large_buffers)) = editor
+                .update(cx, |editor, cx| {
+                    // Large files are skipped, like the other features that scan the whole buffer.
+                    let large_buffers = editor
+                        .buffer()
+                        .read(cx)
+                        .all_buffers()
+                        .into_iter()
+                        .filter(|buffer| buffer.read(cx).large_file_mode())
+                        .map(|buffer| buffer.read(cx).remote_id())
+                        .collect::<HashSet<_>>();
+                    (
+                        editor.buffer().read(cx).snapshot(cx),
+                        editor.multi_buffer_visible_range(cx),
+                        large_buffers,
+                    )
+                })
+                .ok()
+            else {
+                return;
+            };
+            let Some((misspellings, checked_versions, dictionary)) = this
+                .update(cx, |this, cx| {
+                    (
+                        this.misspellings.clone(),
+                        this.checked_versions.clone(),
+                        dictionary(cx),
+                    )
+                })
+                .ok()
+            else {
+                return;
+            };
+
+            let (misspellings, checked_versions) = cx
+                .background_spawn(async move {
+                    let ranges = ranges_to_check(
+                        &snapshot,
+                        visible_range,
+                        &large_buffers,
+                        &checked_versions,
+                    );
+                    let misspellings = update_misspellings(
+                        &snapshot,
+                        misspellings,
+                        &ranges,
+                        &large_buffers,
+                        &dictionary,
+                    );
+                    let checked_versions = snapshot
+                        .excerpts()
+                        .map(|(_, buffer, _)| (buffer.remote_id(), buffer.version().clone()))
+                        .collect::<HashMap<_, _>>();
+                    (misspellings, checked_versions)
+                })
+                .await;
+            this.update(cx, |this, cx| {
+                this.misspellings = misspellings;
+                this.checked_versions = checked_versions;
+                editor
+                    .update(cx, |editor, cx| {
+                        let style = HighlightStyle {
+                            underline: Some(UnderlineStyle {
+                                thickness: px(1.),
+                                color: Some(cx.theme().status().info),
+                                wavy: true,
+                            }),
+                            ..HighlightStyle::default()
+                        };
+                        editor.highlight_text::<Misspelling>(this.misspellings.clone(), style, cx);
+                    })
+                    .ok();
+            })
+            .ok();
+        });
+    }
+}
+
+/// Returns the ranges of each excerpt to check: the part that is visible, and the text that was
+/// edited since the excerpt's buffer was last checked, both extended to whole lines.
+fn ranges_to_check(
+    snapshot: &MultiBufferSnapshot,
+    visible_range: Range<Point>,
+    skipped_buffers: &HashSet<BufferId>,
+    checked_versions: &HashMap<BufferId, clock::Global>,
+) -> Vec<(ExcerptId, Range<usize>)> {
+    let visible_ranges = snapshot
+        .range_to_buffer_ranges(visible_range)
+        .into_iter()
+        .map(|(_, range, excerpt_id)| (excerpt_id, range))
+        .collect::<HashMap<_, _>>();
+
+    let mut ranges_to_check = Vec::new();
+    for (excerpt_id, buffer, excerpt_range) in snapshot.excerpts() {
+        if skipped_buffers.contains(&buffer.remote_id()) {
+            continue;
+        }
+        let context = excerpt_range.context.to_offset(buffer);
+        let mut ranges = visible_ranges
+            .get(&excerpt_id)
+            .cloned()
+            .into_iter()
+            .collect::<Vec<_>>();
+        if let Some(version) = checked_versions.get(&buffer.remote_id()) {
+            ranges.extend(
+                buffer
+                    .edits_since::<usize>(version)
+                    .map(|edit| edit.new.start.max(context.start)..edit.new.end.min(context.end))
+                    .filter(|range| range.start <= range.end),
+            );
+        }
+
+        let mut ranges = ranges
+            .into_iter()
+            .map(|range| {
+                let start = buffer.offset_to_point(range.start);
+                let end = buffer.offset_to_point(range.end);
+                let start = buffer.point_to_offset(Point::new(start.row, 0));
+                let end = buffer.point_to_offset(Point::new(end.row, buffer.line_len(end.row)));
+                start.max(context.start)..end.min(context.end)
+            })
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|range| range.start);
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        ranges_to_check.extend(merged.into_iter().map(|range| (excerpt_id, range)));
+    }
+    ranges_to_check
+}
+
+/// Replaces the misspellings within the checked ranges with the ones found there now, keeping the
+/// misspellings elsewhere.
+fn update_misspellings(
+    snapshot: &MultiBufferSnapshot,
+    misspellings: Vec<Range<Anchor>>,
+    checked_ranges: &[(ExcerptId, Range<usize>)],
+    skipped_buffers: &HashSet<BufferId>,
+    dictionary: &Dictionary,
+) -> Vec<Range<Anchor>> {
+    let mut misspellings = misspellings
+        .into_iter()
+        .filter(|misspelling| {
+            let excerpt_id = misspelling.start.excerpt_id;
+            let Some(buffer) = snapshot.buffer_for_excerpt(excerpt_id) else {
+                return false;
+            };
+            let start = misspelling.start.text_anchor.to_offset(buffer);
+            let end = misspelling.end.text_anchor.to_offset(buffer);
+            start < end
+                && !skipped_buffers.contains(&buffer.remote_id())
+                && !checked_ranges.iter().any(|(checked_excerpt_id, range)| {
+                    *checked_excerpt_id == excerpt_id && range.start <= end && start <= range.end
+                })
+        })
+        .collect::<Vec<_>>();
+    for (excerpt_id, range) in checked_ranges {
+        if let Some(buffer) = snapshot.buffer_for_excerpt(*excerpt_id) {
+            misspellings.extend(excerpt_misspellings(
+                snapshot,
+                *excerpt_id,
+                buffer,
+                range.clone(),
+                dictionary,
+            ));
+        }
+    }
+    misspellings.sort_by(|a, b| a.start.cmp(&b.start, snapshot));
+    misspellings
+}
+
+fn excerpt_misspellings(
+    snapshot: &MultiBufferSnapshot,
+    excerpt_id: ExcerptId,
+    buffer: &BufferSnapshot,
+    range: Range<usize>,
+    dictionary: &Dictionary,
+) -> Vec<Range<Anchor>> {
+    misspellings(buffer, range, dictionary)
+        .into_iter()
+        .filter_map(|word| {
+            let start = snapshot.anchor_in_excerpt(excerpt_id, buffer.anchor_after(word.start))?;
+            let end = snapshot.anchor_in_excerpt(excerpt_id, buffer.anchor_before(word.end))?;
+            Some(start..end)
+        })
+        .collect()
+}
+
+/// What a spellcheck code action does, stored in the action's data.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SpellcheckAction {
+    Replace { text: String },
+    AddToDictionary { word: String },
+}
+
+struct SpellcheckCodeActionProvider {
+    fs: Arc<dyn Fs>,
+    /// Kept here so the spellchecker lives as long as the editor.
+    _spellchecker: Entity<Spellchecker>,
+}
+
+impl CodeActionProvider for SpellcheckCodeActionProvider {
+    fn id(&self) -> Arc<str> {
+        SPELLCHECK_CODE_ACTION_PROVIDER_ID.into()
+    }
+
+    fn code_actions(
+        &self,
+        buffer: &Entity<Buffer>,
+        range: Range<text::Anchor>,
+        _: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Vec<CodeAction>>> {
+        if !SpellcheckSettings::get_global(cx).enabled || buffer.read(cx).large_file_mode() {
+            return Task::ready(Ok(Vec::new()));
+        }
+
+        let snapshot = buffer.read(cx).snapshot();
+        let dictionary = dictionary(cx);
+        let range = range.start.to_offset(&snapshot)..range.end.to_offset(&snapshot);
+        let start_row = snapshot.offset_to_point(range.start).row;
+        let end_row = snapshot.offset_to_point(range.end).row;
+        let lines = snapshot.point_to_offset(Point::new(start_row, 0))
+            ..snapshot.point_to_offset(Point::new(end_row, snapshot.line_len(end_row)));
+
+        let mut actions = Vec::new();
+        for word_range in misspellings(&snapshot, lines, &dictionary) {
+            if word_range.end < range.start || word_range.start > range.end {
+                continue;
+            }
+            let word = snapshot
+                .text_for_range(word_range.clone())
+                .collect::<String>();
+            let anchor_range =
+                snapshot.anchor_before(word_range.start)..snapshot.anchor_after(word_range.end);
+            for suggestion in dictionary.suggestions(&word, MAX_SUGGESTIONS) {
+                actions.push(code_action(
+                    format!("Change to “{suggestion}”"),
+                    anchor_range.clone(),
+                    SpellcheckAction::Replace { text: suggestion },
+                ));
+            }
+            actions.push(code_action(
+                format!("Add “{word}” to dictionary"),
+                anchor_range,
+                SpellcheckAction::AddToDictionary { word },
+            ));
+        }
+        Task::ready(Ok(actions))
+    }
+
+    fn apply_code_action(
+        &self,
+        buffer: Entity<Buffer>,
+        action: CodeAction,
+        _excerpt_id: ExcerptId,
+        _push_to_history: bool,
+        _window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<ProjectTransaction>> {
+        let LspAction::Action(lsp_action) = action.lsp_action else {
+            return Task::ready(Ok(ProjectTransaction::default()));
+        };
+        let spellcheck_action = match lsp_action
+            .data
+            .map(serde_json::from_value::<SpellcheckAction>)
+        {
+            Some(Ok(spellcheck_action)) => spellcheck_action,
+            Some(Err(error)) => return Task::ready(Err(error.into())),
+            None => return Task::ready(Ok(ProjectTransaction::default())),
+        };
+
+        match spellcheck_action {
+            SpellcheckAction::Replace { text } => {
+                buffer.update(cx, |buffer, cx| {
+                    buffer.edit([(action.range, text)], None, cx);
+                });
+            }
+            SpellcheckAction::AddToDictionary { word } => {
+                update_settings_file(self.fs.clone(), cx, move |settings, _| {
+                    let words = settings
+                        .spellcheck
+                        .get_or_insert_default()
+                        .words
+                        .get_or_insert_default();
+                    if !words.contains(&word) {
+                        words.push(word);
+                    }
+                });
+            }
+        }
+        Task::ready(Ok(ProjectTransaction::default()))
+    }
+}
+
+fn code_action(
+    title: String,
+    range: Range<text::Anchor>,
+    spellcheck_action: SpellcheckAction,
+) -> CodeAction {
+    CodeAction {
+        server_id: SPELLCHECK_SERVER_ID,
+        range,
+        lsp_action: LspAction::Action(Box::new(lsp::CodeAction {
+            title,
+            kind: Some(lsp::CodeActionKind::QUICKFIX),
+            data: serde_json::to_value(spellcheck_action).log_err(),
+            ..Default::default()
+        })),
+        resolved: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::MultiBuffer;
+    use gpui::TestAppContext;
+    use language::{Language, LanguageConfig};
+
+    #[gpui::test]
+    async fn test_misspellings_in_prose(cx: &mut TestAppContext) {
+        let language = Arc::new(
+            Language::new(
+                LanguageConfig {
+                    name: "Rust".into(),
+                    ..LanguageConfig::default()
+                },
+                Some(tree_sitter_rust::LANGUAGE.into()),
+            )
+            .with_override_query(
+                "[(line_comment) (block_comment)] @comment.inclusive
+                [(string_literal) (raw_string_literal)] @string",
+            )
+            .unwrap(),
+        );
+        let text = "// Returns the frist item.\nfn frist() -> &'static str {\n    \"an itme\"\n}\n";
+        let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(language, cx));
+        cx.run_until_parked();
+
+        let dictionary = Dictionary::new("returns\nthe\nfirst\nitem\n");
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+        let misspelled_words = misspellings(&snapshot, 0..text.len(), &dictionary)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect::<Vec<_>>();
+        // Identifiers aren't prose, and neither are words too short to be checked.
+        assert_eq!(misspelled_words, ["frist", "itme"]);
+    }
+
+    #[gpui::test]
+    fn test_ranges_to_check(cx: &mut TestAppContext) {
+        let text = (0..10)
+            .map(|row| format!("line {row}\n"))
+            .collect::<String>();
+        let buffer = cx.new(|cx| Buffer::local(text, cx));
+        let multibuffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
+        let checked_ranges = |checked_versions: &HashMap<BufferId, clock::Global>,
+                              cx: &mut TestAppContext| {
+            let snapshot = multibuffer.read_with(cx, |multibuffer, cx| multibuffer.snapshot(cx));
+            let buffer = snapshot.as_singleton().unwrap().2;
+            ranges_to_check(
+                &snapshot,
+                Point::new(2, 3)..Point::new(4, 0),
+                &HashSet::default(),
+                checked_versions,
+            )
+            .into_iter()
+            .map(|(_, range)| buffer.text_for_range(range).collect::<String>())
+            .collect::<Vec<_>>()
+        };
+
+        // A buffer that wasn't checked yet is only checked where it's visible, in whole lines.
+        assert_eq!(
+            checked_ranges(&HashMap::default(), cx),
+            ["line 2\nline 3\nline 4"]
+        );
+
+        // After that, the lines edited since the last check are checked too.
+        let checked_versions = buffer.read_with(cx, |buffer, _| {
+            HashMap::from_iter([(buffer.remote_id(), buffer.version())])
+        });
+        buffer.update(cx, |buffer, cx| {
+            let offset = buffer.point_to_offset(Point::new(8, 4));
+            buffer.edit([(offset..offset, "s")], None, cx);
+        });
+        assert_eq!(
+            checked_ranges(&checked_versions, cx),
+            ["line 2\nline 3\nline 4", "lines 8"]
+        );
+
+        // Large files aren't checked at all.
+        let snapshot = multibuffer.read_with(cx, |multibuffer, cx| multibuffer.snapshot(cx));
+        let large_buffers =
+            HashSet::from_iter([buffer.read_with(cx, |buffer, _| buffer.remote_id())]);
+        assert!(
+            ranges_to_check(
+                &snapshot,
+                Point::new(0, 0)..Point::new(10, 0),
+                &large_buffers,
+                &checked_versions,
+            )
+            .is_empty()
+        );
+    }
+}
@@ -1606,6 +1606,29 @@ impl workspace::TerminalProvider for TerminalProvider {
             }
         })
     }
+
+    fn reveal(&self, task: &SpawnInTerminal, window: &mut Window, cx: &mut App) -> bool {
+        self.0.update(cx, |terminal_panel, cx| {
+            let Some((item_index, pane, _)) = terminal_panel
+                .terminals_for_task(&task.full_label, cx)
+                .pop()
+            else {
+                return false;
+            };
+            terminal_panel.activate_terminal_view(&pane, item_index, true, window, cx);
+            if terminal_panel.center.panes().contains(&&pane) {
+                let workspace = terminal_panel.workspace.clone();
+                window.defer(cx, move |window, cx| {
+                    workspace
+                        .update(cx, |workspace, cx| {
+                            workspace.focus_panel::<TerminalPanel>(window, cx)
+                        })
+                        .ok();
+                });
+            }
+            true
+        })
+    }
 }
 
 struct InlineAssistTabBarButton {
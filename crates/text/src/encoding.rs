@@ -0,0 +1,324 @@
+use anyhow::{Result, anyhow};
+use encoding_rs::{SHIFT_JIS, WINDOWS_1252};
+use std::{borrow::Cow, fmt, str};
+
+/// How many bytes at the start of a file are inspected to recognize UTF-16 without a byte order
+/// mark.
+const UTF16_SAMPLE_LEN: usize = 1024;
+/// How many bytes at the start of a file are inspected to recognize binary content, as in Git.
+const BINARY_SAMPLE_LEN: usize = 8000;
+
+/// The character encoding of a file on disk. Buffers are always UTF-8 in memory, and are converted
+/// from and to their file's encoding when loading and saving.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// UTF-16 LE in a file that doesn't start with a byte order mark, which is kept that way.
+    Utf16LeWithoutBom,
+    /// UTF-16 BE in a file that doesn't start with a byte order mark, which is kept that way.
+    Utf16BeWithoutBom,
+    /// ISO-8859-1, in which every byte is the code point of the same value.
+    Latin1,
+    Windows1252,
+    ShiftJis,
+}
+
+impl Encoding {
+    pub const ALL: [Self; 8] = [
+        Self::Utf8,
+        Self::Utf16Le,
+        Self::Utf16Be,
+        Self::Utf16LeWithoutBom,
+        Self::Utf16BeWithoutBom,
+        Self::Latin1,
+        Self::Windows1252,
+        Self::ShiftJis,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le => "UTF-16 LE",
+            Self::Utf16Be => "UTF-16 BE",
+            Self::Utf16LeWithoutBom => "UTF-16 LE without BOM",
+            Self::Utf16BeWithoutBom => "UTF-16 BE without BOM",
+            Self::Latin1 => "ISO-8859-1",
+            Self::Windows1252 => "Windows-1252",
+            Self::ShiftJis => "Shift-JIS",
+        }
+    }
+
+    /// Guesses the encoding of a file's contents: a UTF-16 byte order mark, or ASCII text
+    /// interleaved with zero bytes, means UTF-16; then valid UTF-8 is preferred, then Shift-JIS
+    /// when it decodes into mostly Japanese text, and finally Windows-1252 when any of its
+    /// printable characters in `0x80..0xA0` are used, or Latin-1.
+    ///
+    /// Returns `None` for binary content, which the single-byte encodings would otherwise accept.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if let Some(encoding) = Self::detect_utf16(bytes) {
+            return Some(encoding);
+        }
+        if str::from_utf8(bytes).is_ok() {
+            return Some(Self::Utf8);
+        }
+        Self::detect_non_utf8(bytes)
+    }
+
+    /// Guesses the encoding of contents that are neither UTF-16 nor valid UTF-8.
+    fn detect_non_utf8(bytes: &[u8]) -> Option<Self> {
+        if Self::is_binary(bytes) {
+            return None;
+        }
+        if Self::is_shift_jis(bytes) {
+            return Some(Self::ShiftJis);
+        }
+        if bytes.iter().any(|byte| (0x80..0xA0).contains(byte)) {
+            Some(Self::Windows1252)
+        } else {
+            Some(Self::Latin1)
+        }
+    }
+
+    /// Whether this is one of the UTF-16 encodings, which can't be searched as ASCII-compatible
+    /// bytes.
+    pub fn is_utf16(self) -> bool {
+        matches!(
+            self,
+            Self::Utf16Le | Self::Utf16Be | Self::Utf16LeWithoutBom | Self::Utf16BeWithoutBom
+        )
+    }
+
+    /// Whether `bytes` start with a zero byte or with more than 10% of control characters other
+    /// than whitespace, which text in a single-byte encoding doesn't have.
+    fn is_binary(bytes: &[u8]) -> bool {
+        let sample = &bytes[..bytes.len().min(BINARY_SAMPLE_LEN)];
+        let control_chars = sample
+            .iter()
+            .filter(|&&byte| byte < 0x20 && !matches!(byte, b'\t' | b'\n' | b'\r' | 0x0C))
+            .count();
+        sample.contains(&0) || control_chars * 10 > sample.len()
+    }
+
+    fn detect_utf16(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0xFF, 0xFE, ..] => return Some(Self::Utf16Le),
+            [0xFE, 0xFF, ..] => return Some(Self::Utf16Be),
+            _ => {}
+        }
+
+        let sample = &bytes[..bytes.len().min(UTF16_SAMPLE_LEN) & !1];
+        if sample.is_empty() || bytes.len() % 2 != 0 {
+            return None;
+        }
+        let pairs = sample.len() / 2;
+        let (mut low_zeros, mut high_zeros) = (0, 0);
+        for pair in sample.chunks_exact(2) {
+            match pair {
+                [0, 0] => {}
+                [_, 0] => low_zeros += 1,
+                [0, _] => high_zeros += 1,
+                _ => {}
+            }
+        }
+        // Mostly ASCII text, with every other byte zero.
+        if low_zeros * 10 >= pairs * 9 {
+            Some(Self::Utf16LeWithoutBom)
+        } else if high_zeros * 10 >= pairs * 9 {
+            Some(Self::Utf16BeWithoutBom)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `bytes` decode as Shift-JIS into mostly Japanese text. Text in the single-byte
+    /// encodings often decodes too, with accented letters read as half-width katakana or as parts
+    /// of user-defined double-byte characters, but seldom into kana and kanji.
+    fn is_shift_jis(bytes: &[u8]) -> bool {
+        let Some(text) = SHIFT_JIS.decode_without_bom_handling_and_without_replacement(bytes)
+        else {
+            return false;
+        };
+        let (mut japanese_chars, mut other_chars) = (0, 0);
+        for ch in text.chars().filter(|ch| !ch.is_ascii()) {
+            // CJK punctuation, kana, kanji, and full-width forms.
+            if matches!(ch, '\u{3000}'..='\u{30FF}' | '\u{4E00}'..='\u{9FFF}' | '\u{FF01}'..='\u{FF5E}')
+            {
+                japanese_chars += 1;
+            } else {
+                other_chars += 1;
+            }
+        }
+        japanese_chars > 0 && other_chars * 10 <= japanese_chars
+    }
+
+    /// Detects the encoding of `bytes` and decodes them, validating and keeping valid UTF-8
+    /// without copying it. Binary content fails to decode as invalid UTF-8.
+    pub fn detect_and_decode(bytes: Vec<u8>) -> Result<(String, Self)> {
+        if let Some(encoding) = Self::detect_utf16(&bytes) {
+            return Ok((encoding.decode(&bytes)?, encoding));
+        }
+        let error = match String::from_utf8(bytes) {
+            Ok(text) => return Ok((text, Self::Utf8)),
+            Err(error) => error,
+        };
+        match Self::detect_non_utf8(error.as_bytes()) {
+            Some(encoding) => Ok((encoding.decode(error.as_bytes())?, encoding)),
+            None => Err(error.into()),
+        }
+    }
+
+    /// Decodes `bytes`, skipping a UTF-16 byte order mark. Fails on malformed input.
+    pub fn decode(self, bytes: &[u8]) -> Result<String> {
+        let decoded = match self {
+            Self::Utf8 => str::from_utf8(bytes).map(Cow::Borrowed).ok(),
+            Self::Utf16Le | Self::Utf16Be | Self::Utf16LeWithoutBom | Self::Utf16BeWithoutBom => {
+                self.decode_utf16(bytes).map(Cow::Owned)
+            }
+            Self::Latin1 => Some(bytes.iter().map(|&byte| byte as char).collect()),
+            Self::Windows1252 => {
+                WINDOWS_1252.decode_without_bom_handling_and_without_replacement(bytes)
+            }
+            Self::ShiftJis => SHIFT_JIS.decode_without_bom_handling_and_without_replacement(bytes),
+        };
+        decoded
+            .map(Cow::into_owned)
+            .ok_or_else(|| anyhow!("file is not valid {}", self.name()))
+    }
+
+    fn decode_utf16(self, bytes: &[u8]) -> Option<String> {
+        let little_endian = matches!(self, Self::Utf16Le | Self::Utf16LeWithoutBom);
+        let bytes = match (little_endian, bytes) {
+            (true, [0xFF, 0xFE, rest @ ..]) | (false, [0xFE, 0xFF, rest @ ..]) => rest,
+            _ => bytes,
+        };
+        if bytes.len() % 2 != 0 {
+            return None;
+        }
+        let units = bytes.chunks_exact(2).map(|pair| {
+            if little_endian {
+                u16::from_le_bytes([pair[0], pair[1]])
+            } else {
+                u16::from_be_bytes([pair[0], pair[1]])
+            }
+        });
+        char::decode_utf16(units).collect::<Result<_, _>>().ok()
+    }
+
+    /// Encodes `text`, starting UTF-16 with a byte order mark unless the encoding is one without.
+    /// Fails when `text` has characters that the encoding can't represent.
+    pub fn encode(self, text: &str) -> Result<Vec<u8>> {
+        let unrepresentable = || {
+            anyhow!(
+                "text contains characters that {} can't represent",
+                self.name()
+            )
+        };
+        match self {
+            Self::Utf8 => Ok(text.as_bytes().to_vec()),
+            Self::Utf16Le => Ok([0xFF, 0xFE]
+                .into_iter()
+                .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+                .collect()),
+            Self::Utf16Be => Ok([0xFE, 0xFF]
+                .into_iter()
+                .chain(text.encode_utf16().flat_map(u16::to_be_bytes))
+                .collect()),
+            Self::Utf16LeWithoutBom => Ok(text.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+            Self::Utf16BeWithoutBom => Ok(text.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+            Self::Latin1 => text
+                .chars()
+                .map(|ch| u8::try_from(u32::from(ch)).ok())
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(unrepresentable),
+            Self::Windows1252 | Self::ShiftJis => {
+                let encoding = if self == Self::Windows1252 {
+                    WINDOWS_1252
+                } else {
+                    SHIFT_JIS
+                };
+                let (bytes, _, had_unmappable_characters) = encoding.encode(text);
+                if had_unmappable_characters {
+                    Err(unrepresentable())
+                } else {
+                    Ok(bytes.into_owned())
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_convert_encodings() {
+        let cases: [(&str, Encoding); 6] = [
+            ("plain ascii", Encoding::Utf8),
+            ("naïve café", Encoding::Utf8),
+            ("naïve café", Encoding::Latin1),
+            ("“quoted” café", Encoding::Windows1252),
+            ("日本語のテキスト", Encoding::ShiftJis),
+            ("utf-16 テキスト", Encoding::Utf16Le),
+        ];
+        for (text, encoding) in cases {
+            let bytes = encoding.encode(text).unwrap();
+            assert_eq!(Encoding::detect(&bytes), Some(encoding), "{text:?}");
+            assert_eq!(
+                Encoding::detect_and_decode(bytes).unwrap(),
+                (text.to_string(), encoding)
+            );
+        }
+
+        // UTF-16 without a byte order mark is saved without one.
+        let utf16_be = Encoding::Utf16Be.encode("big endian").unwrap();
+        let (text, encoding) = Encoding::detect_and_decode(utf16_be[2..].to_vec()).unwrap();
+        assert_eq!(
+            (text.as_str(), encoding),
+            ("big endian", Encoding::Utf16BeWithoutBom)
+        );
+        assert_eq!(encoding.encode(&text).unwrap(), utf16_be[2..]);
+
+        assert!(Encoding::Latin1.encode("“quoted”").is_err());
+        assert!(Encoding::ShiftJis.encode("emoji 🎉").is_err());
+        assert!(Encoding::Utf16Le.decode(&[0xFF, 0xFE, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_detect_latin1_that_decodes_as_shift_jis() {
+        // Accented letters are valid Shift-JIS bytes, but not Japanese text.
+        for text in ["Größe", "Ça coûte cher", "Señor Müller"] {
+            let bytes = Encoding::Latin1.encode(text).unwrap();
+            assert_eq!(Encoding::detect(&bytes), Some(Encoding::Latin1), "{text:?}");
+        }
+        let mixed = Encoding::ShiftJis.encode("サイズ: 大きい (size)").unwrap();
+        assert_eq!(Encoding::detect(&mixed), Some(Encoding::ShiftJis));
+    }
+
+    #[test]
+    fn test_detect_binary_content() {
+        // Invalid UTF-8 with zero bytes, or with mostly control characters, isn't text.
+        let with_zero_bytes = b"\x7FELF\x02\x01\x01\x00\x00\x00\xE9\xFF";
+        let with_control_chars = b"\x01\x02\x03\x04\x05caf\xE9\x06\x07";
+        for bytes in [&with_zero_bytes[..], &with_control_chars[..]] {
+            assert_eq!(Encoding::detect(bytes), None);
+            let error = Encoding::detect_and_decode(bytes.to_vec()).unwrap_err();
+            assert!(error.to_string().contains("invalid utf-8"), "{error}");
+        }
+
+        // Occasional control characters are still text.
+        let form_feed = Encoding::Latin1
+            .encode("caf\u{e9}\x0C\u{1b}[0m page two")
+            .unwrap();
+        assert_eq!(Encoding::detect(&form_feed), Some(Encoding::Latin1));
+    }
+}
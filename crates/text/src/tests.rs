@@ -186,6 +186,17 @@ fn test_line_endings() {
     assert_eq!(buffer.text(), "zero\none\ntwo\nthree\nfour");
     assert_eq!(buffer.line_ending(), LineEnding::Windows);
     buffer.check_invariants();
+
+    let mixed = "one\r\ntwo\nthree\rfour\r\nfive";
+    assert_eq!(
+        LineEnding::mismatched_rows(mixed, LineEnding::Windows),
+        [1, 2]
+    );
+    assert_eq!(
+        LineEnding::mismatched_rows(mixed, LineEnding::Unix),
+        [0, 2, 3]
+    );
+    assert!(LineEnding::mismatched_rows("one\ntwo\n", LineEnding::Unix).is_empty());
 }
 
 #[test]
@@ -696,6 +707,30 @@ fn test_edited_ranges_for_transaction() {
     );
 }
 
+#[test]
+fn test_base_text_version() {
+    let mut buffer = Buffer::new(0, BufferId::new(1).unwrap(), "abc\ndef\nghi");
+    buffer.edit([(1..2, "B")]);
+    buffer.edit([(8..11, "")]);
+    assert_eq!(
+        buffer
+            .edits_since::<usize>(&buffer.base_text_version())
+            .map(|edit| edit.new)
+            .collect::<Vec<_>>(),
+        [1..2, 8..8]
+    );
+
+    let mut buffer = Buffer::new(0, BufferId::new(1).unwrap(), "");
+    buffer.edit([(0..0, "abc")]);
+    assert_eq!(
+        buffer
+            .edits_since::<usize>(&buffer.base_text_version())
+            .map(|edit| edit.new)
+            .collect::<Vec<_>>(),
+        [0..3]
+    );
+}
+
 #[test]
 fn test_concurrent_edits() {
     let text = "abcdef";
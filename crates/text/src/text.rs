@@ -1,4 +1,5 @@
 mod anchor;
+mod encoding;
 pub mod locator;
 #[cfg(any(test, feature = "test-support"))]
 pub mod network;
@@ -15,6 +16,7 @@ use anyhow::{Context as _, Result};
 use clock::LOCAL_BRANCH_REPLICA_ID;
 pub use clock::ReplicaId;
 use collections::{HashMap, HashSet};
+pub use encoding::Encoding;
 use locator::Locator;
 use operation_queue::OperationQueue;
 pub use patch::Patch;
@@ -48,6 +50,12 @@ static LINE_SEPARATORS_REGEX: LazyLock<Regex> =
 
 pub type TransactionId = clock::Lamport;
 
+/// The timestamp of the insertion of a buffer's base text.
+const BASE_TEXT_TIMESTAMP: clock::Lamport = clock::Lamport {
+    replica_id: 0,
+    value: 1,
+};
+
 pub struct Buffer {
     snapshot: BufferSnapshot,
     history: History,
@@ -731,10 +739,7 @@ impl Buffer {
 
         let visible_text = history.base_text.clone();
         if !visible_text.is_empty() {
-            let insertion_timestamp = clock::Lamport {
-                replica_id: 0,
-                value: 1,
-            };
+            let insertion_timestamp = BASE_TEXT_TIMESTAMP;
             lamport_clock.observe(insertion_timestamp);
             version.observe(insertion_timestamp);
             let fragment_id = Locator::between(&Locator::min(), &Locator::max());
@@ -1423,6 +1428,16 @@ impl Buffer {
         &self.history.base_text
     }
 
+    /// The version of the buffer when it only contained its base text, so that the edits since
+    /// this version are all the edits that were made to the buffer.
+    pub fn base_text_version(&self) -> clock::Global {
+        let mut version = clock::Global::new();
+        if !self.history.base_text.is_empty() {
+            version.observe(BASE_TEXT_TIMESTAMP);
+        }
+        version
+    }
+
     pub fn operations(&self) -> &TreeMap<clock::Lamport, Operation> {
         &self.history.operations
     }
@@ -3256,6 +3271,13 @@ impl Default for LineEnding {
 }
 
 impl LineEnding {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LineEnding::Unix => "LF",
+            LineEnding::Windows => "CRLF",
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             LineEnding::Unix => "\n",
@@ -3280,6 +3302,29 @@ impl LineEnding {
         }
     }
 
+    /// Returns the rows of `text` whose line break differs from `line_ending`, which shows that the
+    /// text has mixed line endings.
+    pub fn mismatched_rows(text: &str, line_ending: LineEnding) -> Vec<u32> {
+        let bytes = text.as_bytes();
+        let mut rows = Vec::new();
+        let mut row = 0;
+        let mut ix = 0;
+        while let Some(offset) = bytes[ix..].iter().position(|b| *b == b'\n' || *b == b'\r') {
+            ix += offset;
+            let is_crlf = bytes[ix] == b'\r' && bytes.get(ix + 1) == Some(&b'\n');
+            let matches = match line_ending {
+                LineEnding::Unix => bytes[ix] == b'\n',
+                LineEnding::Windows => is_crlf,
+            };
+            if !matches {
+                rows.push(row);
+            }
+            row += 1;
+            ix += if is_crlf { 2 } else { 1 };
+        }
+        rows
+    }
+
     pub fn normalize(text: &mut String) {
         if let Cow::Owned(replaced) = LINE_SEPARATORS_REGEX.replace_all(text, "\n") {
             *text = replaced;
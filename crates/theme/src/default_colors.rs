@@ -103,6 +103,7 @@ impl ThemeColors {
             editor_gutter_background: neutral().light().step_1(),
             editor_subheader_background: neutral().light().step_2(),
             editor_active_line_background: neutral().light_alpha().step_3(),
+            editor_active_line_gutter_background: neutral().light_alpha().step_3(),
             editor_highlighted_line_background: neutral().light_alpha().step_3(),
             editor_debugger_active_line_background: yellow().dark_alpha().step_3(),
             editor_line_number: neutral().light().step_10(),
@@ -115,7 +116,11 @@ impl ThemeColors {
             editor_indent_guide_active: neutral().light_alpha().step_6(),
             editor_document_highlight_read_background: neutral().light_alpha().step_3(),
             editor_document_highlight_write_background: neutral().light_alpha().step_4(),
+            editor_document_highlight_read_underline: neutral().light().step_9(),
+            editor_document_highlight_write_underline: orange().light().step_9(),
             editor_document_highlight_bracket_background: green().light_alpha().step_5(),
+            editor_trailing_whitespace_background: red().light_alpha().step_4(),
+            editor_todo_highlight_background: yellow().light_alpha().step_5(),
             terminal_background: neutral().light().step_1(),
             terminal_foreground: black().light().step_12(),
             terminal_bright_foreground: black().light().step_11(),
@@ -229,6 +234,7 @@ impl ThemeColors {
             editor_gutter_background: neutral().dark().step_1(),
             editor_subheader_background: neutral().dark().step_3(),
             editor_active_line_background: neutral().dark_alpha().step_3(),
+            editor_active_line_gutter_background: neutral().dark_alpha().step_3(),
             editor_highlighted_line_background: yellow().dark_alpha().step_4(),
             editor_debugger_active_line_background: yellow().dark_alpha().step_3(),
             editor_line_number: neutral().dark_alpha().step_10(),
@@ -241,7 +247,11 @@ impl ThemeColors {
             editor_indent_guide_active: neutral().dark_alpha().step_6(),
             editor_document_highlight_read_background: neutral().dark_alpha().step_4(),
             editor_document_highlight_write_background: neutral().dark_alpha().step_4(),
+            editor_document_highlight_read_underline: neutral().dark().step_9(),
+            editor_document_highlight_write_underline: orange().dark().step_9(),
             editor_document_highlight_bracket_background: green().dark_alpha().step_6(),
+            editor_trailing_whitespace_background: red().dark_alpha().step_5(),
+            editor_todo_highlight_background: yellow().dark_alpha().step_5(),
             terminal_background: neutral().dark().step_1(),
             terminal_ansi_background: neutral().dark().step_1(),
             terminal_foreground: white().dark().step_12(),
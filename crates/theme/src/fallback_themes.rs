@@ -145,6 +145,12 @@ pub(crate) fn zed_default_dark() -> Theme {
                 editor_gutter_background: editor,
                 editor_subheader_background: bg,
                 editor_active_line_background: hsla(222.9 / 360., 13.5 / 100., 20.4 / 100., 1.0),
+                editor_active_line_gutter_background: hsla(
+                    222.9 / 360.,
+                    13.5 / 100.,
+                    20.4 / 100.,
+                    1.0,
+                ),
                 editor_highlighted_line_background: hsla(207.8 / 360., 81. / 100., 66. / 100., 0.1),
                 editor_debugger_active_line_background: hsla(
                     207.8 / 360.,
@@ -167,7 +173,11 @@ pub(crate) fn zed_default_dark() -> Theme {
                     0.2,
                 ),
                 editor_document_highlight_write_background: gpui::red(),
+                editor_document_highlight_read_underline: gpui::blue(),
+                editor_document_highlight_write_underline: gpui::red(),
                 editor_document_highlight_bracket_background: gpui::green(),
+                editor_trailing_whitespace_background: gpui::red(),
+                editor_todo_highlight_background: gpui::yellow(),
 
                 terminal_background: bg,
                 // todo("Use one colors for terminal")
@@ -253,6 +253,10 @@ pub fn theme_colors_refinement(
         .border
         .as_ref()
         .and_then(|color| try_parse_color(color).ok());
+    let editor_active_line_background = this
+        .editor_active_line_background
+        .as_ref()
+        .and_then(|color| try_parse_color(color).ok());
     let editor_document_highlight_read_background = this
         .editor_document_highlight_read_background
         .as_ref()
@@ -533,10 +537,13 @@ pub fn theme_colors_refinement(
             .editor_subheader_background
             .as_ref()
             .and_then(|color| try_parse_color(color).ok()),
-        editor_active_line_background: this
-            .editor_active_line_background
+        editor_active_line_background,
+        editor_active_line_gutter_background: this
+            .editor_active_line_gutter_background
             .as_ref()
-            .and_then(|color| try_parse_color(color).ok()),
+            .and_then(|color| try_parse_color(color).ok())
+            // Fall back to `editor.active_line.background`, for backwards compatibility.
+            .or(editor_active_line_background),
         editor_highlighted_line_background: this
             .editor_highlighted_line_background
             .as_ref()
@@ -582,12 +589,28 @@ pub fn theme_colors_refinement(
             .editor_document_highlight_write_background
             .as_ref()
             .and_then(|color| try_parse_color(color).ok()),
+        editor_document_highlight_read_underline: this
+            .editor_document_highlight_read_underline
+            .as_ref()
+            .and_then(|color| try_parse_color(color).ok()),
+        editor_document_highlight_write_underline: this
+            .editor_document_highlight_write_underline
+            .as_ref()
+            .and_then(|color| try_parse_color(color).ok()),
         editor_document_highlight_bracket_background: this
             .editor_document_highlight_bracket_background
             .as_ref()
             .and_then(|color| try_parse_color(color).ok())
             // Fall back to `editor.document_highlight.read_background`, for backwards compatibility.
             .or(editor_document_highlight_read_background),
+        editor_trailing_whitespace_background: this
+            .editor_trailing_whitespace_background
+            .as_ref()
+            .and_then(|color| try_parse_color(color).ok()),
+        editor_todo_highlight_background: this
+            .editor_todo_highlight_background
+            .as_ref()
+            .and_then(|color| try_parse_color(color).ok()),
         terminal_background: this
             .terminal_background
             .as_ref()
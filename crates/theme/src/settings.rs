@@ -2,11 +2,11 @@ use crate::{
     Appearance, DEFAULT_ICON_THEME_NAME, SyntaxTheme, Theme, status_colors_refinement,
     syntax_overrides, theme_colors_refinement,
 };
-use collections::HashMap;
+use collections::{BTreeMap, HashMap};
 use derive_more::{Deref, DerefMut};
 use gpui::{
-    App, Context, Font, FontFallbacks, FontStyle, FontWeight, Global, Pixels, Subscription, Window,
-    px,
+    App, Context, Font, FontFallbacks, FontScript, FontStyle, FontWeight, Global, Pixels,
+    Subscription, Window, px,
 };
 use refineable::Refineable;
 use schemars::JsonSchema;
@@ -688,6 +688,30 @@ pub fn font_fallbacks_from_settings(
     })
 }
 
+/// Font fallbacks from settings, with the fallbacks for the characters of specific scripts.
+fn script_font_fallbacks_from_settings(
+    fallbacks: Option<FontFallbacks>,
+    script_fallbacks: Option<&BTreeMap<FontScript, Vec<settings::FontFamilyName>>>,
+) -> Option<FontFallbacks> {
+    let Some(script_fallbacks) =
+        script_fallbacks.filter(|script_fallbacks| !script_fallbacks.is_empty())
+    else {
+        return fallbacks;
+    };
+    Some(script_fallbacks.iter().fold(
+        fallbacks.unwrap_or_default(),
+        |fallbacks, (script, fonts)| {
+            fallbacks.with_script_fonts(
+                *script,
+                fonts
+                    .iter()
+                    .map(|font_family| font_family.0.to_string())
+                    .collect(),
+            )
+        },
+    ))
+}
+
 impl settings::Settings for ThemeSettings {
     fn from_settings(content: &settings::SettingsContent) -> Self {
         let content = &content.theme;
@@ -711,7 +735,10 @@ impl settings::Settings for ThemeSettings {
                     .clone()
                     .into(),
                 features: content.buffer_font_features.clone().unwrap(),
-                fallbacks: font_fallbacks_from_settings(content.buffer_font_fallbacks.clone()),
+                fallbacks: script_font_fallbacks_from_settings(
+                    font_fallbacks_from_settings(content.buffer_font_fallbacks.clone()),
+                    content.buffer_font_script_fallbacks.as_ref(),
+                ),
                 weight: clamp_font_weight(content.buffer_font_weight.unwrap().0),
                 style: FontStyle::default(),
             },
@@ -170,6 +170,8 @@ pub struct ThemeColors {
     pub editor_gutter_background: Hsla,
     pub editor_subheader_background: Hsla,
     pub editor_active_line_background: Hsla,
+    /// Background color of the gutter next to the lines with cursors.
+    pub editor_active_line_gutter_background: Hsla,
     pub editor_highlighted_line_background: Hsla,
     /// Line color of the line a debugger is currently stopped at
     pub editor_debugger_active_line_background: Hsla,
@@ -199,10 +201,20 @@ pub struct ThemeColors {
     /// special attention. Usually a document highlight is visualized by changing
     /// the background color of its range.
     pub editor_document_highlight_write_background: Hsla,
+    /// Underline color of the occurrences where the symbol under the cursor is read, when the
+    /// `document_highlights.read` setting is `underline`.
+    pub editor_document_highlight_read_underline: Hsla,
+    /// Underline color of the occurrences where the symbol under the cursor is written, when the
+    /// `document_highlights.write` setting is `underline`.
+    pub editor_document_highlight_write_underline: Hsla,
     /// Highlighted brackets background color.
     ///
     /// Matching brackets in the cursor scope are highlighted with this background color.
     pub editor_document_highlight_bracket_background: Hsla,
+    /// Background color of whitespace at the end of lines, when `highlight_trailing_whitespace` is enabled.
+    pub editor_trailing_whitespace_background: Hsla,
+    /// Background color of TODO-style tags in comments, when `todo_highlights` is enabled.
+    pub editor_todo_highlight_background: Hsla,
 
     // ===
     // Terminal
@@ -354,6 +366,7 @@ pub enum ThemeColorField {
     EditorGutterBackground,
     EditorSubheaderBackground,
     EditorActiveLineBackground,
+    EditorActiveLineGutterBackground,
     EditorHighlightedLineBackground,
     EditorLineNumber,
     EditorActiveLineNumber,
@@ -364,7 +377,11 @@ pub enum ThemeColorField {
     EditorIndentGuideActive,
     EditorDocumentHighlightReadBackground,
     EditorDocumentHighlightWriteBackground,
+    EditorDocumentHighlightReadUnderline,
+    EditorDocumentHighlightWriteUnderline,
     EditorDocumentHighlightBracketBackground,
+    EditorTrailingWhitespaceBackground,
+    EditorTodoHighlightBackground,
     TerminalBackground,
     TerminalForeground,
     TerminalBrightForeground,
@@ -471,6 +488,9 @@ impl ThemeColors {
             ThemeColorField::EditorGutterBackground => self.editor_gutter_background,
             ThemeColorField::EditorSubheaderBackground => self.editor_subheader_background,
             ThemeColorField::EditorActiveLineBackground => self.editor_active_line_background,
+            ThemeColorField::EditorActiveLineGutterBackground => {
+                self.editor_active_line_gutter_background
+            }
             ThemeColorField::EditorHighlightedLineBackground => {
                 self.editor_highlighted_line_background
             }
@@ -487,9 +507,19 @@ impl ThemeColors {
             ThemeColorField::EditorDocumentHighlightWriteBackground => {
                 self.editor_document_highlight_write_background
             }
+            ThemeColorField::EditorDocumentHighlightReadUnderline => {
+                self.editor_document_highlight_read_underline
+            }
+            ThemeColorField::EditorDocumentHighlightWriteUnderline => {
+                self.editor_document_highlight_write_underline
+            }
             ThemeColorField::EditorDocumentHighlightBracketBackground => {
                 self.editor_document_highlight_bracket_background
             }
+            ThemeColorField::EditorTrailingWhitespaceBackground => {
+                self.editor_trailing_whitespace_background
+            }
+            ThemeColorField::EditorTodoHighlightBackground => self.editor_todo_highlight_background,
             ThemeColorField::TerminalBackground => self.terminal_background,
             ThemeColorField::TerminalForeground => self.terminal_foreground,
             ThemeColorField::TerminalBrightForeground => self.terminal_bright_foreground,
@@ -3,7 +3,7 @@ use collections::{HashMap, HashSet};
 use command_palette_hooks::CommandInterceptResult;
 use editor::{
     Bias, Editor, EditorSettings, SelectionEffects, ToPoint,
-    actions::{SortLinesCaseInsensitive, SortLinesCaseSensitive},
+    actions::{SortLinesCaseInsensitive, SortLinesCaseSensitive, SortLinesNumeric},
     display_map::ToDisplayPoint,
 };
 use futures::AsyncWriteExt as _;
@@ -469,11 +469,13 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
                 for c in to_delete.chars().filter(|c| !c.is_whitespace()) {
                     vim.delete_mark(c.to_string(), editor, window, cx);
                 }
+                vim.refresh_mark_badges(editor, window, cx);
             }
             DeleteMarks::AllLocal => {
                 for s in 'a'..='z' {
                     vim.delete_mark(s.to_string(), editor, window, cx);
                 }
+                vim.refresh_mark_badges(editor, window, cx);
             }
         });
     });
@@ -1311,6 +1313,7 @@ fn generate_commands(_: &App) -> Vec<VimCommand> {
             .args(|_, args| Some(DeleteMarks::Marks(args).boxed_clone())),
         VimCommand::new(("sor", "t"), SortLinesCaseSensitive).range(select_range),
         VimCommand::new(("sort i", ""), SortLinesCaseInsensitive).range(select_range),
+        VimCommand::new(("sort n", ""), SortLinesNumeric).range(select_range),
         VimCommand::str(("E", "xplore"), "project_panel::ToggleFocus"),
         VimCommand::str(("H", "explore"), "project_panel::ToggleFocus"),
         VimCommand::str(("L", "explore"), "project_panel::ToggleFocus"),
@@ -2,6 +2,7 @@ mod boundary;
 mod object;
 mod paste;
 mod select;
+mod selections;
 
 use editor::display_map::DisplaySnapshot;
 use editor::{
@@ -40,6 +41,18 @@ actions!(
         HelixSelectLine,
         /// Select all matches of a given pattern within the current selection.
         HelixSelectRegex,
+        /// Removes every selection except the primary one.
+        HelixKeepPrimarySelection,
+        /// Removes the primary selection.
+        HelixRemovePrimarySelection,
+        /// Makes the next selection the primary one.
+        HelixRotateSelectionsForward,
+        /// Makes the previous selection the primary one.
+        HelixRotateSelectionsBackward,
+        /// Trims whitespace from the start and end of each selection.
+        HelixTrimSelections,
+        /// Merges all selections into one.
+        HelixMergeSelections,
     ]
 );
 
@@ -51,6 +64,12 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     Vim::action(editor, cx, Vim::helix_goto_last_modification);
     Vim::action(editor, cx, Vim::helix_paste);
     Vim::action(editor, cx, Vim::helix_select_regex);
+    Vim::action(editor, cx, Vim::helix_keep_primary_selection);
+    Vim::action(editor, cx, Vim::helix_remove_primary_selection);
+    Vim::action(editor, cx, Vim::helix_rotate_selections_forward);
+    Vim::action(editor, cx, Vim::helix_rotate_selections_backward);
+    Vim::action(editor, cx, Vim::helix_trim_selections);
+    Vim::action(editor, cx, Vim::helix_merge_selections);
 }
 
 impl Vim {
@@ -0,0 +1,218 @@
+use editor::Editor;
+use gpui::{Context, Window};
+use text::{Selection, SelectionGoal};
+
+use crate::{
+    Vim,
+    helix::{
+        HelixKeepPrimarySelection, HelixMergeSelections, HelixRemovePrimarySelection,
+        HelixRotateSelectionsBackward, HelixRotateSelectionsForward, HelixTrimSelections,
+    },
+};
+
+impl Vim {
+    pub(super) fn helix_keep_primary_selection(
+        &mut self,
+        _: &HelixKeepPrimarySelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            editor.change_selections(Default::default(), window, cx, |s| {
+                let newest = s.newest_anchor().clone();
+                s.select_anchors(vec![newest]);
+            });
+        });
+    }
+
+    pub(super) fn helix_remove_primary_selection(
+        &mut self,
+        _: &HelixRemovePrimarySelection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            let mut selections = editor.selections.all::<usize>(cx);
+            if selections.len() < 2 {
+                return;
+            }
+            let newest_id = editor.selections.newest_anchor().id;
+            let Some(ix) = selections
+                .iter()
+                .position(|selection| selection.id == newest_id)
+            else {
+                return;
+            };
+            selections.remove(ix);
+            // The selection after the removed one becomes the primary selection.
+            let next = ix % selections.len();
+            editor.change_selections(Default::default(), window, cx, |s| {
+                selections[next].id = s.new_selection_id();
+                s.select(selections);
+            });
+        });
+    }
+
+    pub(super) fn helix_rotate_selections_forward(
+        &mut self,
+        _: &HelixRotateSelectionsForward,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.rotate_primary_selection(true, window, cx);
+    }
+
+    pub(super) fn helix_rotate_selections_backward(
+        &mut self,
+        _: &HelixRotateSelectionsBackward,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.rotate_primary_selection(false, window, cx);
+    }
+
+    /// Makes the selection after (or before) the primary one primary, wrapping around at the end
+    /// of the buffer.
+    fn rotate_primary_selection(
+        &mut self,
+        forward: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            let mut selections = editor.selections.all::<usize>(cx);
+            if selections.len() < 2 {
+                return;
+            }
+            let newest_id = editor.selections.newest_anchor().id;
+            let Some(ix) = selections
+                .iter()
+                .position(|selection| selection.id == newest_id)
+            else {
+                return;
+            };
+            let next = if forward {
+                (ix + 1) % selections.len()
+            } else {
+                (ix + selections.len() - 1) % selections.len()
+            };
+            editor.change_selections(Default::default(), window, cx, |s| {
+                selections[next].id = s.new_selection_id();
+                s.select(selections);
+            });
+        });
+    }
+
+    /// Removes leading and trailing whitespace from each selection, dropping selections that
+    /// only contain whitespace.
+    pub(super) fn helix_trim_selections(
+        &mut self,
+        _: &HelixTrimSelections,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let selections = editor.selections.all::<usize>(cx);
+            let mut trimmed = selections
+                .iter()
+                .filter_map(|selection| {
+                    if selection.is_empty() {
+                        return Some(selection.clone());
+                    }
+                    let text = snapshot
+                        .text_for_range(selection.range())
+                        .collect::<String>();
+                    let trimmed_start = text.trim_start();
+                    if trimmed_start.is_empty() {
+                        return None;
+                    }
+                    let mut selection = selection.clone();
+                    selection.start += text.len() - trimmed_start.len();
+                    selection.end -= trimmed_start.len() - trimmed_start.trim_end().len();
+                    Some(selection)
+                })
+                .collect::<Vec<_>>();
+            if trimmed.is_empty() {
+                let newest = editor.selections.newest::<usize>(cx);
+                trimmed.push(Selection {
+                    start: newest.head(),
+                    end: newest.head(),
+                    ..newest
+                });
+            }
+            editor.change_selections(Default::default(), window, cx, |s| {
+                s.select(trimmed);
+            });
+        });
+    }
+
+    /// Replaces the selections with one selection spanning all of them.
+    pub(super) fn helix_merge_selections(
+        &mut self,
+        _: &HelixMergeSelections,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            let selections = editor.selections.all::<usize>(cx);
+            let (Some(first), Some(last)) = (selections.first(), selections.last()) else {
+                return;
+            };
+            let newest = editor.selections.newest::<usize>(cx);
+            let merged = Selection {
+                id: newest.id,
+                start: first.start,
+                end: last.end,
+                reversed: newest.reversed,
+                goal: SelectionGoal::None,
+            };
+            editor.change_selections(Default::default(), window, cx, |s| {
+                s.select(vec![merged]);
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{state::Mode, test::VimTestContext};
+
+    #[gpui::test]
+    async fn test_primary_selection(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("«oneˇ» «twoˇ» «threeˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes(")");
+        cx.update_editor(|editor, _, cx| {
+            assert_eq!(editor.selections.newest::<usize>(cx).range(), 0..3);
+        });
+        cx.simulate_keystrokes("( (");
+        cx.update_editor(|editor, _, cx| {
+            assert_eq!(editor.selections.newest::<usize>(cx).range(), 4..7);
+        });
+
+        cx.simulate_keystrokes("alt-,");
+        cx.assert_state("«oneˇ» two «threeˇ»", Mode::HelixNormal);
+        cx.update_editor(|editor, _, cx| {
+            assert_eq!(editor.selections.newest::<usize>(cx).range(), 8..13);
+        });
+
+        cx.simulate_keystrokes(",");
+        cx.assert_state("one two «threeˇ»", Mode::HelixNormal);
+    }
+
+    #[gpui::test]
+    async fn test_trim_and_merge_selections(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.enable_helix();
+
+        cx.set_state("«  one ˇ»two«   ˇ»three«\n fourˇ»", Mode::HelixNormal);
+        cx.simulate_keystrokes("_");
+        cx.assert_state("  «oneˇ» two   three\n «fourˇ»", Mode::HelixNormal);
+
+        cx.simulate_keystrokes("alt-minus");
+        cx.assert_state("  «one two   three\n fourˇ»", Mode::HelixNormal);
+    }
+}
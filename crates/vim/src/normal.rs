@@ -827,7 +827,7 @@ impl Vim {
         self.update_editor(cx, |_, editor, cx| {
             editor.transact(window, cx, |editor, window, cx| {
                 for _ in 0..times {
-                    editor.join_lines_impl(insert_whitespace, window, cx)
+                    editor.join_lines_impl(if insert_whitespace { " " } else { "" }, window, cx)
                 }
             })
         });
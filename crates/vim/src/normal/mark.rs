@@ -5,7 +5,7 @@ use editor::{
     display_map::{DisplaySnapshot, ToDisplayPoint},
     movement,
 };
-use gpui::{Context, Entity, EntityId, UpdateGlobal, Window};
+use gpui::{Context, Entity, EntityId, SharedString, UpdateGlobal, Window};
 use language::SelectionGoal;
 use text::Point;
 use ui::App;
@@ -17,6 +17,9 @@ use crate::{
     state::{Mark, Mode, VimGlobals},
 };
 
+/// The gutter badges for the marks in an editor.
+pub(crate) enum MarkBadges {}
+
 impl Vim {
     pub fn create_mark(&mut self, text: Arc<str>, window: &mut Window, cx: &mut Context<Self>) {
         self.update_editor(cx, |vim, editor, cx| {
@@ -27,10 +30,35 @@ impl Vim {
                 .map(|s| s.head())
                 .collect::<Vec<_>>();
             vim.set_mark(text.to_string(), anchors, editor.buffer(), window, cx);
+            vim.refresh_mark_badges(editor, window, cx);
         });
         self.clear_operator(window, cx);
     }
 
+    /// Shows the letter of each lowercase and uppercase mark in the editor's buffer in the gutter.
+    pub(crate) fn refresh_mark_badges(
+        &self,
+        editor: &mut Editor,
+        window: &mut Window,
+        cx: &mut Context<Editor>,
+    ) {
+        if !editor.mode().is_full() {
+            return;
+        }
+        let buffer_id = editor.buffer().entity_id();
+        let mut badges = Vec::new();
+        for name in ('a'..='z').chain('A'..='Z') {
+            let anchors = match self.get_mark(&name.to_string(), editor, window, cx) {
+                Some(Mark::Local(anchors)) => anchors,
+                Some(Mark::Buffer(entity_id, anchors)) if entity_id == buffer_id => anchors,
+                _ => continue,
+            };
+            let label = SharedString::from(name.to_string());
+            badges.extend(anchors.into_iter().map(|anchor| (anchor, label.clone())));
+        }
+        editor.set_gutter_badges::<MarkBadges>(badges, cx);
+    }
+
     // When handling an action, you must create visual marks if you will switch to normal
     // mode without the default selection behavior.
     pub(crate) fn store_visual_marks(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -367,9 +395,10 @@ pub fn jump_motion(
 
 #[cfg(test)]
 mod test {
+    use editor::display_map::DisplayRow;
     use gpui::TestAppContext;
 
-    use crate::test::NeovimBackedTestContext;
+    use crate::test::{NeovimBackedTestContext, VimTestContext};
 
     #[gpui::test]
     async fn test_quote_mark(cx: &mut TestAppContext) {
@@ -389,4 +418,26 @@ mod test {
         cx.simulate_shared_keystrokes("^ ` `").await;
         cx.shared_state().await.assert_eq("Hello, worldˇ!");
     }
+
+    #[gpui::test]
+    async fn test_mark_gutter_badges(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇone\ntwo\nthree", crate::state::Mode::Normal);
+        cx.simulate_keystrokes("m a j m b m C");
+        cx.update_editor(|editor, window, cx| {
+            assert_eq!(
+                editor.all_gutter_badges(window, cx),
+                [(DisplayRow(0), "a".into()), (DisplayRow(1), "Cb".into())]
+            );
+        });
+
+        cx.simulate_keystrokes(": d e l m a r k s space b enter");
+        cx.update_editor(|editor, window, cx| {
+            assert_eq!(
+                editor.all_gutter_badges(window, cx),
+                [(DisplayRow(0), "a".into()), (DisplayRow(1), "C".into())]
+            );
+        });
+    }
 }
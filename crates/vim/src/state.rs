@@ -1336,8 +1336,17 @@ impl RegistersView {
 }
 
 enum MarksMatchInfo {
-    Path(Arc<Path>),
-    Title(String),
+    /// A mark in a file that isn't open, with the marked line once it's been read from disk.
+    Path {
+        path: Arc<Path>,
+        line: Option<String>,
+    },
+    /// A mark in another open buffer, with the marked line.
+    Title {
+        title: String,
+        line: String,
+        highlights: Vec<(Range<usize>, HighlightStyle)>,
+    },
     Content {
         line: String,
         highlights: Vec<(Range<usize>, HighlightStyle)>,
@@ -1346,6 +1355,14 @@ enum MarksMatchInfo {
 
 impl MarksMatchInfo {
     fn from_chunks<'a>(chunks: impl Iterator<Item = Chunk<'a>>, cx: &App) -> Self {
+        let (line, highlights) = Self::highlighted_line(chunks, cx);
+        MarksMatchInfo::Content { line, highlights }
+    }
+
+    fn highlighted_line<'a>(
+        chunks: impl Iterator<Item = Chunk<'a>>,
+        cx: &App,
+    ) -> (String, Vec<(Range<usize>, HighlightStyle)>) {
         let mut line = String::new();
         let mut highlights = Vec::new();
         let mut offset = 0;
@@ -1358,7 +1375,32 @@ impl MarksMatchInfo {
             }
             offset += chunk.text.len();
         }
-        MarksMatchInfo::Content { line, highlights }
+        (line, highlights)
+    }
+
+    /// Shows the marked line after the file it's in.
+    fn with_location(
+        location: String,
+        line: Option<&str>,
+        highlights: &[(Range<usize>, HighlightStyle)],
+        cx: &App,
+    ) -> (String, Vec<(Range<usize>, HighlightStyle)>) {
+        let mut output = location;
+        let mut runs = vec![(
+            0..output.len(),
+            HighlightStyle::color(cx.theme().colors().text_muted),
+        )];
+        if let Some(line) = line {
+            output.push_str("  ");
+            let offset = output.len();
+            output.push_str(line);
+            runs.extend(
+                highlights.iter().map(|(range, highlight)| {
+                    (range.start + offset..range.end + offset, *highlight)
+                }),
+            );
+        }
+        (output, runs)
     }
 }
 
@@ -1404,6 +1446,7 @@ impl PickerDelegate for MarksViewDelegate {
         let Some(workspace) = self.workspace.upgrade() else {
             return Task::ready(());
         };
+        let fs = workspace.read(cx).app_state().fs.clone();
         cx.spawn(async move |picker, cx| {
             let mut matches = Vec::new();
             let _ = workspace.update(cx, |workspace, cx| {
@@ -1495,26 +1538,35 @@ impl PickerDelegate for MarksViewDelegate {
                                 .and_then(|map| map.get(name))
                                 .and_then(|anchors| anchors.first())
                             {
-                                let Some((info, snapshot)) = workspace
+                                let Some(buffer) = workspace
                                     .items(cx)
                                     .filter_map(|item| item.act_as::<Editor>(cx))
-                                    .map(|entity| entity.read(cx).buffer())
+                                    .map(|entity| entity.read(cx).buffer().clone())
                                     .find(|buffer| buffer.entity_id().eq(entity_id))
-                                    .map(|buffer| {
-                                        (
-                                            MarksMatchInfo::Title(
-                                                buffer.read(cx).title(cx).to_string(),
-                                            ),
-                                            buffer.read(cx).snapshot(cx),
-                                        )
-                                    })
                                 else {
                                     continue;
                                 };
+                                let snapshot = buffer.read(cx).snapshot(cx);
+                                let position = anchor.to_point(&snapshot);
+                                let (line, highlights) = MarksMatchInfo::highlighted_line(
+                                    snapshot.chunks(
+                                        Point::new(position.row, 0)
+                                            ..Point::new(
+                                                position.row,
+                                                snapshot.line_len(MultiBufferRow(position.row)),
+                                            ),
+                                        true,
+                                    ),
+                                    cx,
+                                );
                                 matches.push(MarksMatch {
                                     name: name.clone(),
-                                    position: anchor.to_point(&snapshot),
-                                    info,
+                                    position,
+                                    info: MarksMatchInfo::Title {
+                                        title: buffer.read(cx).title(cx).to_string(),
+                                        line,
+                                        highlights,
+                                    },
                                 });
                             }
                         }
@@ -1525,17 +1577,29 @@ impl PickerDelegate for MarksViewDelegate {
                                 .and_then(|map| map.get(name))
                                 .and_then(|points| points.first())
                             {
-                                let info = MarksMatchInfo::Path(path.clone());
                                 matches.push(MarksMatch {
                                     name: name.clone(),
                                     position,
-                                    info,
+                                    info: MarksMatchInfo::Path {
+                                        path: path.clone(),
+                                        line: None,
+                                    },
                                 });
                             }
                         }
                     }
                 }
             });
+            for mark_match in &mut matches {
+                if let MarksMatchInfo::Path { path, line } = &mut mark_match.info
+                    && let Ok(text) = fs.load(path.as_ref()).await
+                {
+                    *line = text
+                        .lines()
+                        .nth(mark_match.position.row as usize)
+                        .map(|line| line.to_string());
+                }
+            }
             let _ = picker.update(cx, |picker, cx| {
                 matches.sort_by_key(|a| {
                     (
@@ -1613,20 +1677,17 @@ impl PickerDelegate for MarksViewDelegate {
         }
 
         let (right_output, right_runs): (String, Vec<_>) = match &mark_match.info {
-            MarksMatchInfo::Path(path) => {
-                let s = path.to_string_lossy().into_owned();
-                (
-                    s.clone(),
-                    vec![(0..s.len(), HighlightStyle::color(cx.theme().colors().text))],
-                )
-            }
-            MarksMatchInfo::Title(title) => (
-                title.clone(),
-                vec![(
-                    0..title.len(),
-                    HighlightStyle::color(cx.theme().colors().text),
-                )],
+            MarksMatchInfo::Path { path, line } => MarksMatchInfo::with_location(
+                path.to_string_lossy().into_owned(),
+                line.as_deref(),
+                &[],
+                cx,
             ),
+            MarksMatchInfo::Title {
+                title,
+                line,
+                highlights,
+            } => MarksMatchInfo::with_location(title.clone(), Some(line), highlights, cx),
             MarksMatchInfo::Content { line, highlights } => (line.clone(), highlights.clone()),
         };
 
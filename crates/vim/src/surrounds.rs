@@ -4,11 +4,15 @@ use crate::{
     object::{Object, surrounding_markers},
     state::Mode,
 };
-use editor::{Bias, movement};
+use editor::{Bias, DisplayPoint, display_map::DisplaySnapshot, movement};
 use gpui::{Context, Window};
-use language::BracketPair;
+use language::{BracketPair, Selection};
 
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
+
+/// How far from the cursor the ends of a pair without a text object, like a block comment, are
+/// looked for when deleting them.
+const MAX_SURROUND_DISTANCE: usize = 10_000;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SurroundsType {
@@ -34,18 +38,12 @@ impl Vim {
             editor.transact(window, cx, |editor, window, cx| {
                 editor.set_clip_at_line_ends(false, cx);
 
-                let pair = match find_surround_pair(&all_support_surround_pair(), &text) {
-                    Some(pair) => pair.clone(),
-                    None => BracketPair {
-                        start: text.to_string(),
-                        end: text.to_string(),
-                        close: true,
-                        surround: true,
-                        newline: false,
-                    },
-                };
-                let surround = pair.end != surround_alias((*text).as_ref());
                 let (display_map, display_selections) = editor.selections.all_adjusted_display(cx);
+                let pair = surround_pair_at(&text, &display_map, &display_selections)
+                    .unwrap_or_else(|| literal_pair(&text));
+                // Spaces are added inside the pair when it's given by its opening bracket.
+                let alias = surround_alias(&text);
+                let surround = pair.start == alias && pair.end != alias;
                 let mut edits = Vec::new();
                 let mut anchors = Vec::new();
 
@@ -129,14 +127,22 @@ impl Vim {
     ) {
         self.stop_recording(cx);
 
+        if &*text == "t" {
+            self.delete_surrounding_tags(window, cx);
+            return;
+        }
+
         // only legitimate surrounds can be removed
-        let pair = match find_surround_pair(&all_support_surround_pair(), &text) {
-            Some(pair) => pair.clone(),
+        let pair = match self.surround_pair(&text, cx) {
+            Some(pair) => pair,
             None => return,
         };
         let pair_object = match pair_to_object(&pair) {
             Some(pair_object) => pair_object,
-            None => return,
+            None => {
+                self.delete_surrounding_strings(pair, window, cx);
+                return;
+            }
         };
         let surround = pair.end != *text;
 
@@ -225,22 +231,19 @@ impl Vim {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if target == Object::Tag {
+            self.change_surrounding_tags(text, window, cx);
+            return;
+        }
         if let Some(will_replace_pair) = self.object_to_bracket_pair(target, cx) {
             self.stop_recording(cx);
             self.update_editor(cx, |_, editor, cx| {
                 editor.transact(window, cx, |editor, window, cx| {
                     editor.set_clip_at_line_ends(false, cx);
 
-                    let pair = match find_surround_pair(&all_support_surround_pair(), &text) {
-                        Some(pair) => pair.clone(),
-                        None => BracketPair {
-                            start: text.to_string(),
-                            end: text.to_string(),
-                            close: true,
-                            surround: true,
-                            newline: false,
-                        },
-                    };
+                    let (display_map, selections) = editor.selections.all_adjusted_display(cx);
+                    let pair = surround_pair_at(&text, &display_map, &selections)
+                        .unwrap_or_else(|| literal_pair(&text));
 
                     // A single space should be added if the new surround is a
                     // bracket and not a quote (pair.start != pair.end) and if
@@ -256,7 +259,6 @@ impl Vim {
                     let preserve_space =
                         will_replace_pair.start == will_replace_pair.end || !opening;
 
-                    let (display_map, selections) = editor.selections.all_adjusted_display(cx);
                     let mut edits = Vec::new();
                     let mut anchors = Vec::new();
 
@@ -377,6 +379,9 @@ impl Vim {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> bool {
+        if object == Object::Tag {
+            return self.move_to_surrounding_tags(window, cx);
+        }
         let mut valid = false;
         if let Some(pair) = self.object_to_bracket_pair(object, cx) {
             self.update_editor(cx, |_, editor, cx| {
@@ -426,6 +431,129 @@ impl Vim {
         valid
     }
 
+    /// Moves each cursor to the start of the element around it, returning whether there are any.
+    fn move_to_surrounding_tags(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let mut valid = false;
+        self.update_editor(cx, |_, editor, cx| {
+            let (display_map, selections) = editor.selections.all_display(cx);
+            let anchors = selections
+                .into_iter()
+                .map(|selection| {
+                    let start = selection.start.to_offset(&display_map, Bias::Left);
+                    match surrounding_tag_ranges(&display_map, selection) {
+                        Some((open, _)) => {
+                            valid = true;
+                            open.start..open.start
+                        }
+                        None => start..start,
+                    }
+                })
+                .collect::<Vec<_>>();
+            editor.change_selections(Default::default(), window, cx, |s| {
+                s.select_ranges(anchors);
+            });
+        });
+        valid
+    }
+
+    fn delete_surrounding_tags(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.replace_surrounding_tags(None, window, cx);
+    }
+
+    fn change_surrounding_tags(
+        &mut self,
+        text: Arc<str>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.stop_recording(cx);
+        let pair = self
+            .surround_pair(&text, cx)
+            .unwrap_or_else(|| literal_pair(&text));
+        self.replace_surrounding_tags(Some(pair), window, cx);
+    }
+
+    /// Replaces the opening and closing tags of the elements around the cursors with the ends of
+    /// `pair`, or deletes them.
+    fn replace_surrounding_tags(
+        &mut self,
+        pair: Option<BracketPair>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            editor.transact(window, cx, |editor, window, cx| {
+                let (display_map, selections) = editor.selections.all_display(cx);
+                let (open_text, close_text) = pair
+                    .as_ref()
+                    .map_or(("", ""), |pair| (pair.start.as_str(), pair.end.as_str()));
+                let mut edits = Vec::new();
+                let mut anchors = Vec::new();
+                for selection in selections {
+                    let start = selection.start.to_offset(&display_map, Bias::Left);
+                    if let Some((open, close)) = surrounding_tag_ranges(&display_map, selection) {
+                        anchors.push(open.start..open.start);
+                        edits.push((open, open_text));
+                        edits.push((close, close_text));
+                    } else {
+                        anchors.push(start..start);
+                    }
+                }
+
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_ranges(anchors);
+                });
+                edits.sort_by_key(|(range, _)| range.start);
+                edits.dedup_by(|(a, _), (b, _)| a == b);
+                editor.edit(edits, cx);
+            });
+        });
+    }
+
+    /// Deletes the ends of a pair that no text object matches, like a block comment.
+    fn delete_surrounding_strings(
+        &mut self,
+        pair: BracketPair,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_editor(cx, |_, editor, cx| {
+            editor.transact(window, cx, |editor, window, cx| {
+                let (display_map, selections) = editor.selections.all_display(cx);
+                let mut edits = Vec::new();
+                let mut anchors = Vec::new();
+                for selection in &selections {
+                    let offset = selection.head().to_offset(&display_map, Bias::Left);
+                    if let Some((open, close)) =
+                        surrounding_string_ranges(&display_map, offset, &pair)
+                    {
+                        anchors.push(open.start..open.start);
+                        edits.push((open, ""));
+                        edits.push((close, ""));
+                    } else {
+                        anchors.push(offset..offset);
+                    }
+                }
+
+                editor.change_selections(Default::default(), window, cx, |s| {
+                    s.select_ranges(anchors);
+                });
+                edits.sort_by_key(|(range, _)| range.start);
+                edits.dedup_by(|(a, _), (b, _)| a == b);
+                editor.edit(edits, cx);
+            });
+        });
+    }
+
+    /// The pair that `text` stands for at the cursors, see [`surround_pair_at`].
+    fn surround_pair(&mut self, text: &str, cx: &mut Context<Self>) -> Option<BracketPair> {
+        self.update_editor(cx, |_, editor, cx| {
+            let (display_map, selections) = editor.selections.all_display(cx);
+            surround_pair_at(text, &display_map, &selections)
+        })
+        .flatten()
+    }
+
     fn object_to_bracket_pair(
         &self,
         object: Object,
@@ -561,10 +689,108 @@ impl Vim {
     }
 }
 
+/// Finds the pair that starts or ends with `ch`, where only single-character pairs are found by
+/// their end, so that `"` isn't taken for Python's `f"` strings.
 fn find_surround_pair<'a>(pairs: &'a [BracketPair], ch: &str) -> Option<&'a BracketPair> {
+    let ch = surround_alias(ch);
     pairs
         .iter()
-        .find(|pair| pair.start == surround_alias(ch) || pair.end == surround_alias(ch))
+        .find(|pair| pair.start == ch || (pair.end == ch && pair.start.chars().count() == 1))
+}
+
+/// The pair that `text`, typed after `ys`, `cs` or `ds`, stands for at the first cursor. The
+/// bracket pairs of the language there come first, and a character that begins just one of its
+/// longer pairs stands for that pair, like `/` for a block comment in Rust.
+fn surround_pair_at(
+    text: &str,
+    map: &DisplaySnapshot,
+    selections: &[Selection<DisplayPoint>],
+) -> Option<BracketPair> {
+    let offset = selections
+        .first()
+        .map_or(0, |selection| selection.head().to_offset(map, Bias::Left));
+    let language_pairs = map
+        .buffer_snapshot()
+        .language_scope_at(offset)
+        .map(|scope| {
+            scope
+                .brackets()
+                .filter(|(pair, enabled)| *enabled && pair.surround)
+                .map(|(pair, _)| pair.clone())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    if let Some(pair) = find_surround_pair(&language_pairs, text) {
+        return Some(pair.clone());
+    }
+    if let Some(pair) = find_surround_pair(&all_support_surround_pair(), text) {
+        return Some(pair.clone());
+    }
+    let ch = surround_alias(text);
+    let mut longer_pairs = language_pairs
+        .into_iter()
+        .filter(|pair| pair.start.len() > ch.len() && pair.start.starts_with(ch));
+    let pair = longer_pairs.next()?;
+    longer_pairs.next().is_none().then_some(pair)
+}
+
+fn literal_pair(text: &str) -> BracketPair {
+    BracketPair {
+        start: text.to_string(),
+        end: text.to_string(),
+        close: true,
+        surround: true,
+        newline: false,
+    }
+}
+
+/// The ranges of the opening and closing tags of the element around `selection`.
+fn surrounding_tag_ranges(
+    map: &DisplaySnapshot,
+    selection: Selection<DisplayPoint>,
+) -> Option<(Range<usize>, Range<usize>)> {
+    let outer = Object::Tag.range(map, selection.clone(), true, None)?;
+    let inner = Object::Tag.range(map, selection, false, None)?;
+    Some((
+        outer.start.to_offset(map, Bias::Left)..inner.start.to_offset(map, Bias::Left),
+        inner.end.to_offset(map, Bias::Left)..outer.end.to_offset(map, Bias::Left),
+    ))
+}
+
+/// The ranges of the closest `pair.start` at or before `offset` and of the first `pair.end`
+/// after it, when that ends at or after `offset`.
+fn surrounding_string_ranges(
+    map: &DisplaySnapshot,
+    offset: usize,
+    pair: &BracketPair,
+) -> Option<(Range<usize>, Range<usize>)> {
+    let snapshot = map.buffer_snapshot();
+    let search_start =
+        snapshot.clip_offset(offset.saturating_sub(MAX_SURROUND_DISTANCE), Bias::Left);
+    let search_end = snapshot.clip_offset(
+        (offset + MAX_SURROUND_DISTANCE).min(snapshot.len()),
+        Bias::Right,
+    );
+    let text = snapshot
+        .text_for_range(search_start..search_end)
+        .collect::<String>();
+    let cursor = offset - search_start;
+
+    let open_start = text
+        .match_indices(pair.start.as_str())
+        .map(|(ix, _)| ix)
+        .take_while(|ix| *ix <= cursor)
+        .last()?;
+    let open_end = open_start + pair.start.len();
+    let close_start = open_end + text[open_end..].find(pair.end.as_str())?;
+    let close_end = close_start + pair.end.len();
+    (close_end >= cursor).then(|| {
+        (
+            search_start + open_start..search_start + open_end,
+            search_start + close_start..search_start + close_end,
+        )
+    })
 }
 
 fn surround_alias(ch: &str) -> &str {
@@ -654,8 +880,11 @@ fn pair_to_object(pair: &BracketPair) -> Option<Object> {
 
 #[cfg(test)]
 mod test {
+    use std::sync::Arc;
+
     use gpui::KeyBinding;
     use indoc::indoc;
+    use language::{BracketPair, BracketPairConfig, Language, LanguageConfig};
 
     use crate::{PushAddSurrounds, object::AnyBrackets, state::Mode, test::VimTestContext};
 
@@ -1678,4 +1907,53 @@ mod test {
             Mode::Normal,
         );
     }
+
+    #[gpui::test]
+    async fn test_surrounds_with_language_pairs(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        let pair = |start: &str, end: &str| BracketPair {
+            start: start.into(),
+            end: end.into(),
+            close: true,
+            surround: true,
+            newline: false,
+        };
+        let language = Language::new(
+            LanguageConfig {
+                name: "Test".into(),
+                brackets: BracketPairConfig {
+                    pairs: vec![pair("f\"", "\""), pair("\"", "\""), pair("/*", "*/")],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            None,
+        );
+        cx.update_buffer(|buffer, cx| buffer.set_language(Some(Arc::new(language)), cx));
+
+        // A character beginning one of the language's pairs stands for it.
+        cx.set_state("The quˇick brown", Mode::Normal);
+        cx.simulate_keystrokes("y s i w /");
+        cx.assert_state("The ˇ/*quick*/ brown", Mode::Normal);
+
+        cx.simulate_keystrokes("l l l d s /");
+        cx.assert_state("The ˇquick brown", Mode::Normal);
+
+        // Pairs aren't found by the end of a longer start.
+        cx.simulate_keystrokes("y s i w \"");
+        cx.assert_state("The ˇ\"quick\" brown", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_surrounding_tags(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new_html(cx).await;
+
+        cx.set_state("<div><a>heˇllo</a></div>", Mode::Normal);
+        cx.simulate_keystrokes("d s t");
+        cx.assert_state("<div>ˇhello</div>", Mode::Normal);
+
+        cx.set_state("<div><a>heˇllo</a></div>", Mode::Normal);
+        cx.simulate_keystrokes("c s t [");
+        cx.assert_state("<div>ˇ[hello]</div>", Mode::Normal);
+    }
 }
@@ -35,6 +35,7 @@ use language::{
 };
 pub use mode_indicator::ModeIndicator;
 use motion::Motion;
+use normal::mark::MarkBadges;
 use normal::search::SearchSubmit;
 use object::Object;
 use schemars::JsonSchema;
@@ -888,6 +889,7 @@ impl Vim {
         editor.selections.set_line_mode(false);
         editor.unregister_addon::<VimAddon>();
         editor.set_relative_line_number(None, cx);
+        editor.clear_gutter_badges::<MarkBadges>(cx);
         if let Some(vim) = Vim::globals(cx).focused_vim()
             && vim.entity_id() == cx.entity().entity_id()
         {
@@ -1343,6 +1345,9 @@ impl Vim {
 
         cx.emit(VimEvent::Focused);
         self.sync_vim_settings(window, cx);
+        self.update_editor(cx, |vim, editor, cx| {
+            vim.refresh_mark_badges(editor, window, cx)
+        });
 
         if VimSettings::get_global(cx).toggle_relative_line_numbers {
             if let Some(old_vim) = Vim::globals(cx).focused_vim() {
@@ -256,6 +256,9 @@ pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
         false
     }
 
+    /// Records the item's current location in the workspace's jump list.
+    fn push_jump_location(&mut self, _: &mut Context<Self>) {}
+
     fn telemetry_event_text(&self) -> Option<&'static str> {
         None
     }
@@ -323,6 +326,20 @@ pub trait Item: Focusable + EventEmitter<Self::Event> + Render + Sized {
     ) -> Task<Result<()>> {
         unimplemented!("reload() must be implemented if can_save() returns true")
     }
+    fn can_reload_with_merge(&self, _: &App) -> bool {
+        false
+    }
+    /// Reloads the item from disk, merging the changes on disk into its unsaved edits.
+    fn reload_with_merge(
+        &mut self,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        unimplemented!(
+            "reload_with_merge() must be implemented if can_reload_with_merge() returns true"
+        )
+    }
 
     fn act_as_type<'a>(
         &'a self,
@@ -493,6 +510,7 @@ pub trait ItemHandle: 'static + Send {
     fn on_removed(&self, cx: &App);
     fn workspace_deactivated(&self, window: &mut Window, cx: &mut App);
     fn navigate(&self, data: Box<dyn Any>, window: &mut Window, cx: &mut App) -> bool;
+    fn push_jump_location(&self, cx: &mut App);
     fn item_id(&self) -> EntityId;
     fn to_any(&self) -> AnyView;
     fn is_dirty(&self, cx: &App) -> bool;
@@ -520,6 +538,13 @@ pub trait ItemHandle: 'static + Send {
         window: &mut Window,
         cx: &mut App,
     ) -> Task<Result<()>>;
+    fn can_reload_with_merge(&self, cx: &App) -> bool;
+    fn reload_with_merge(
+        &self,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<()>>;
     fn act_as_type(&self, type_id: TypeId, cx: &App) -> Option<AnyView>;
     fn to_followable_item_handle(&self, cx: &App) -> Option<Box<dyn FollowableItemHandle>>;
     fn to_serializable_item_handle(&self, cx: &App) -> Option<Box<dyn SerializableItemHandle>>;
@@ -939,6 +964,10 @@ impl<T: Item> ItemHandle for Entity<T> {
         self.update(cx, |this, cx| this.navigate(data, window, cx))
     }
 
+    fn push_jump_location(&self, cx: &mut App) {
+        self.update(cx, |this, cx| this.push_jump_location(cx))
+    }
+
     fn item_id(&self) -> EntityId {
         self.entity_id()
     }
@@ -996,6 +1025,19 @@ impl<T: Item> ItemHandle for Entity<T> {
         self.update(cx, |item, cx| item.reload(project, window, cx))
     }
 
+    fn can_reload_with_merge(&self, cx: &App) -> bool {
+        self.read(cx).can_reload_with_merge(cx)
+    }
+
+    fn reload_with_merge(
+        &self,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<()>> {
+        self.update(cx, |item, cx| item.reload_with_merge(project, window, cx))
+    }
+
     fn act_as_type<'a>(&'a self, type_id: TypeId, cx: &'a App) -> Option<AnyView> {
         self.read(cx).act_as_type(type_id, self, cx)
     }
@@ -211,6 +211,10 @@ actions!(
         GoBack,
         /// Navigates forward in history.
         GoForward,
+        /// Navigates back in the workspace's jump list, which spans all files.
+        NavigateBackwardInJumpList,
+        /// Navigates forward in the workspace's jump list, which spans all files.
+        NavigateForwardInJumpList,
         /// Joins this pane into the next pane.
         JoinIntoNext,
         /// Joins all panes into one.
@@ -419,6 +423,7 @@ struct NavHistoryState {
     paths_by_item: HashMap<EntityId, (ProjectPath, Option<PathBuf>)>,
     pane: WeakEntity<Pane>,
     next_timestamp: Arc<AtomicUsize>,
+    jump_list: Option<JumpList>,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -444,6 +449,28 @@ pub struct NavigationEntry {
     pub is_preview: bool,
 }
 
+/// Locations recorded before large cursor motions, shared by the center panes of a workspace.
+///
+/// Unlike a pane's navigation history, the jump list is a single list spanning every file,
+/// with a cursor that moves through it as it is navigated.
+#[derive(Clone, Default)]
+pub struct JumpList(Arc<Mutex<JumpListState>>);
+
+#[derive(Default)]
+struct JumpListState {
+    entries: VecDeque<JumpListEntry>,
+    index: usize,
+    navigating: bool,
+}
+
+#[derive(Clone)]
+pub struct JumpListEntry {
+    pub pane: WeakEntity<Pane>,
+    pub item: Arc<dyn WeakItemHandle>,
+    pub is_preview: bool,
+    data: Arc<dyn Fn() -> Box<dyn Any> + Send + Sync>,
+}
+
 #[derive(Clone)]
 pub struct DraggedTab {
     pub pane: Entity<Pane>,
@@ -508,6 +535,7 @@ impl Pane {
                 paths_by_item: Default::default(),
                 pane: handle,
                 next_timestamp,
+                jump_list: None,
             }))),
             toolbar: cx.new(|_| Toolbar::new()),
             tab_bar_scroll_handle: ScrollHandle::new(),
@@ -842,6 +870,40 @@ impl Pane {
         }
     }
 
+    fn navigate_backward_in_jump_list(
+        &mut self,
+        _: &NavigateBackwardInJumpList,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(workspace) = self.workspace.upgrade() {
+            window.defer(cx, move |window, cx| {
+                workspace.update(cx, |workspace, cx| {
+                    workspace
+                        .navigate_jump_list(NavigationMode::GoingBack, window, cx)
+                        .detach_and_log_err(cx)
+                })
+            })
+        }
+    }
+
+    fn navigate_forward_in_jump_list(
+        &mut self,
+        _: &NavigateForwardInJumpList,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(workspace) = self.workspace.upgrade() {
+            window.defer(cx, move |window, cx| {
+                workspace.update(cx, |workspace, cx| {
+                    workspace
+                        .navigate_jump_list(NavigationMode::GoingForward, window, cx)
+                        .detach_and_log_err(cx)
+                })
+            })
+        }
+    }
+
     fn history_updated(&mut self, cx: &mut Context<Self>) {
         self.toolbar.update(cx, |_, cx| cx.notify());
     }
@@ -2097,18 +2159,18 @@ impl Pane {
                 }
                 return Ok(true);
             } else {
+                let can_merge = cx.update(|_window, cx| item.can_reload_with_merge(cx))?;
+                let answers: &[&str] = if can_merge {
+                    &["Overwrite", "Merge", "Discard", "Cancel"]
+                } else {
+                    &["Overwrite", "Discard", "Cancel"]
+                };
                 let answer = pane.update_in(cx, |pane, window, cx| {
                     pane.activate_item(item_ix, true, true, window, cx);
-                    window.prompt(
-                        PromptLevel::Warning,
-                        CONFLICT_MESSAGE,
-                        None,
-                        &["Overwrite", "Discard", "Cancel"],
-                        cx,
-                    )
+                    window.prompt(PromptLevel::Warning, CONFLICT_MESSAGE, None, answers, cx)
                 })?;
-                match answer.await {
-                    Ok(0) => {
+                match answer.await.ok().and_then(|ix| answers.get(ix)).copied() {
+                    Some("Overwrite") => {
                         pane.update_in(cx, |_, window, cx| {
                             item.save(
                                 SaveOptions {
@@ -2122,7 +2184,15 @@ impl Pane {
                         })?
                         .await?
                     }
-                    Ok(1) => {
+                    // The merged item may have conflicts left to resolve, so it's kept open.
+                    Some("Merge") => {
+                        pane.update_in(cx, |_, window, cx| {
+                            item.reload_with_merge(project, window, cx)
+                        })?
+                        .await?;
+                        return Ok(false);
+                    }
+                    Some("Discard") => {
                         pane.update_in(cx, |_, window, cx| item.reload(project, window, cx))?
                             .await?
                     }
@@ -3714,6 +3784,8 @@ impl Render for Pane {
             .on_action(cx.listener(Pane::toggle_zoom))
             .on_action(cx.listener(Self::navigate_backward))
             .on_action(cx.listener(Self::navigate_forward))
+            .on_action(cx.listener(Self::navigate_backward_in_jump_list))
+            .on_action(cx.listener(Self::navigate_forward_in_jump_list))
             .on_action(
                 cx.listener(|pane: &mut Pane, action: &ActivateItem, window, cx| {
                     pane.activate_item(
@@ -3950,6 +4022,31 @@ impl ItemNavHistory {
         }
     }
 
+    /// Records a location in the workspace's jump list, if this item's pane has one.
+    pub fn push_jump<D: 'static + Send + Sync + Clone>(&mut self, data: D) {
+        let state = self.history.0.lock();
+        if !matches!(state.mode, NavigationMode::Normal)
+            || !self
+                .item
+                .upgrade()
+                .is_some_and(|item| item.include_in_nav_history())
+        {
+            return;
+        }
+        let Some(jump_list) = state.jump_list.clone() else {
+            return;
+        };
+        let pane = state.pane.clone();
+        drop(state);
+
+        jump_list.push(JumpListEntry {
+            pane,
+            item: self.item.clone(),
+            is_preview: self.is_preview,
+            data: Arc::new(move || Box::new(data.clone()) as Box<dyn Any>),
+        });
+    }
+
     pub fn pop_backward(&mut self, cx: &mut App) -> Option<NavigationEntry> {
         self.history.pop(NavigationMode::GoingBack, cx)
     }
@@ -3996,6 +4093,10 @@ impl NavHistory {
         self.0.lock().mode = NavigationMode::Disabled;
     }
 
+    pub fn set_jump_list(&mut self, jump_list: JumpList) {
+        self.0.lock().jump_list = Some(jump_list);
+    }
+
     pub fn enable(&mut self) {
         self.0.lock().mode = NavigationMode::Normal;
     }
@@ -4095,6 +4196,71 @@ impl NavHistory {
     }
 }
 
+impl JumpList {
+    fn push(&self, entry: JumpListEntry) {
+        let mut state = self.0.lock();
+        if state.navigating {
+            return;
+        }
+        let index = state.index;
+        state.entries.truncate(index);
+        if state.entries.len() >= MAX_NAVIGATION_HISTORY_LEN {
+            state.entries.pop_front();
+        }
+        state.entries.push_back(entry);
+        state.index = state.entries.len();
+    }
+
+    /// Moves the cursor one entry in the direction of `mode`, returning the entry to restore.
+    ///
+    /// When going back from the end of the list, `record_current_location` is called first so
+    /// that navigating forward again returns to where the cursor was.
+    pub fn step(
+        &self,
+        mode: NavigationMode,
+        record_current_location: impl FnOnce(),
+    ) -> Option<JumpListEntry> {
+        match mode {
+            NavigationMode::GoingBack => {
+                let (index, len) = {
+                    let state = self.0.lock();
+                    (state.index, state.entries.len())
+                };
+                if index >= len {
+                    record_current_location();
+                }
+                let mut state = self.0.lock();
+                if state.entries.len() > len {
+                    // Step over the location that was just recorded.
+                    state.index -= 1;
+                }
+                state.index = state.index.checked_sub(1)?;
+                state.entries.get(state.index).cloned()
+            }
+            NavigationMode::GoingForward => {
+                let mut state = self.0.lock();
+                if state.index + 1 >= state.entries.len() {
+                    return None;
+                }
+                state.index += 1;
+                state.entries.get(state.index).cloned()
+            }
+            _ => None,
+        }
+    }
+
+    /// Prevents locations from being recorded while an entry is being restored.
+    pub fn set_navigating(&self, navigating: bool) {
+        self.0.lock().navigating = navigating;
+    }
+}
+
+impl JumpListEntry {
+    pub fn data(&self) -> Box<dyn Any> {
+        (self.data)()
+    }
+}
+
 impl NavHistoryState {
     pub fn did_update(&self, cx: &mut App) {
         if let Some(pane) = self.pane.upgrade() {
@@ -16,7 +16,10 @@ use db::{
     sqlez_macros::sql,
 };
 use gpui::{Axis, Bounds, Task, WindowBounds, WindowId, point, size};
-use project::debugger::breakpoint_store::{BreakpointState, SourceBreakpoint};
+use project::{
+    bookmark_store::SourceBookmark,
+    debugger::breakpoint_store::{BreakpointState, SourceBreakpoint},
+};
 
 use language::{LanguageName, Toolchain, ToolchainScope};
 use project::WorktreeId;
@@ -702,6 +705,17 @@ impl Domain for WorkspaceDb {
         sql!(
             DROP TABLE ssh_connections;
         ),
+        sql!(
+            CREATE TABLE bookmarks (
+                workspace_id INTEGER NOT NULL,
+                path TEXT NOT NULL,
+                row INTEGER NOT NULL,
+                PRIMARY KEY (workspace_id, path, row),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            ) STRICT;
+        ),
     ];
 
     // Allow recovering from bad migration that was initially shipped to nightly
@@ -714,6 +728,18 @@ impl Domain for WorkspaceDb {
 
 db::static_connection!(DB, WorkspaceDb, []);
 
+/// A row of the `workspaces` table, as needed to deserialize a local workspace.
+type WorkspaceRow = (
+    WorkspaceId,
+    String,
+    String,
+    Option<SerializedWindowBounds>,
+    Option<Uuid>,
+    Option<bool>,
+    DockStructure,
+    Option<u64>,
+);
+
 impl WorkspaceDb {
     /// Returns a serialized workspace for the given worktree_roots. If the passed array
     /// is empty, the most recent workspace is returned instead. If no workspace for the
@@ -744,25 +770,7 @@ impl WorkspaceDb {
 
         // Note that we re-assign the workspace_id here in case it's empty
         // and we've grabbed the most recent workspace
-        let (
-            workspace_id,
-            paths,
-            paths_order,
-            window_bounds,
-            display,
-            centered_layout,
-            docks,
-            window_id,
-        ): (
-            WorkspaceId,
-            String,
-            String,
-            Option<SerializedWindowBounds>,
-            Option<Uuid>,
-            Option<bool>,
-            DockStructure,
-            Option<u64>,
-        ) = self
+        let row: WorkspaceRow = self
             .select_row_bound(sql! {
                 SELECT
                     workspace_id,
@@ -802,6 +810,65 @@ impl WorkspaceDb {
             .warn_on_err()
             .flatten()?;
 
+        self.serialized_workspace(row, remote_connection_id)
+    }
+
+    /// Returns the serialized workspace with the given id. Windows without folders are only
+    /// found this way, as each of them has its own serialized workspace.
+    pub(crate) fn workspace_for_id(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> Option<SerializedWorkspace> {
+        let row: WorkspaceRow = self
+            .select_row_bound(sql! {
+                SELECT
+                    workspace_id,
+                    paths,
+                    paths_order,
+                    window_state,
+                    window_x,
+                    window_y,
+                    window_width,
+                    window_height,
+                    display,
+                    centered_layout,
+                    left_dock_visible,
+                    left_dock_active_panel,
+                    left_dock_zoom,
+                    right_dock_visible,
+                    right_dock_active_panel,
+                    right_dock_zoom,
+                    bottom_dock_visible,
+                    bottom_dock_active_panel,
+                    bottom_dock_zoom,
+                    window_id
+                FROM workspaces
+                WHERE
+                    workspace_id = ? AND
+                    remote_connection_id IS NULL
+            })
+            .map(|mut prepared_statement| (prepared_statement)(workspace_id).unwrap())
+            .context("No workspaces found")
+            .warn_on_err()
+            .flatten()?;
+
+        self.serialized_workspace(row, None)
+    }
+
+    fn serialized_workspace(
+        &self,
+        (
+            workspace_id,
+            paths,
+            paths_order,
+            window_bounds,
+            display,
+            centered_layout,
+            docks,
+            window_id,
+        ): WorkspaceRow,
+        remote_connection_id: Option<RemoteConnectionId>,
+    ) -> Option<SerializedWorkspace> {
         let paths = PathList::deserialize(&SerializedPathList {
             paths,
             order: paths_order,
@@ -821,6 +888,7 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            bookmarks: self.bookmarks(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
         })
@@ -872,6 +940,34 @@ impl WorkspaceDb {
         }
     }
 
+    fn bookmarks(&self, workspace_id: WorkspaceId) -> BTreeMap<Arc<Path>, Vec<SourceBookmark>> {
+        let bookmarks: Result<Vec<(PathBuf, u32)>> = self
+            .select_bound(sql! {
+                SELECT path, row
+                FROM bookmarks
+                WHERE workspace_id = ?
+                ORDER BY path, row
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match bookmarks {
+            Ok(bookmarks) => {
+                let mut map: BTreeMap<Arc<Path>, Vec<SourceBookmark>> = Default::default();
+                for (path, row) in bookmarks {
+                    let path: Arc<Path> = path.into();
+                    map.entry(path.clone())
+                        .or_default()
+                        .push(SourceBookmark { row, path });
+                }
+                map
+            }
+            Err(msg) => {
+                log::error!("Bookmarks query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     fn user_toolchains(
         &self,
         workspace_id: WorkspaceId,
@@ -998,6 +1094,23 @@ impl WorkspaceDb {
                     }
                 }
 
+                conn.exec_bound(
+                    sql!(
+                        DELETE FROM bookmarks WHERE workspace_id = ?1;
+                    )
+                )?(workspace.id).context("Clearing old bookmarks")?;
+
+                for (path, bookmarks) in workspace.bookmarks {
+                    for bookmark in bookmarks {
+                        if let Err(err) = conn.exec_bound(sql!(
+                            INSERT OR IGNORE INTO bookmarks (workspace_id, path, row)
+                            VALUES (?1, ?2, ?3);))?
+                        ((workspace.id, path.as_ref(), bookmark.row)) {
+                            log::error!("{err}");
+                        }
+                    }
+                }
+
                 conn.exec_bound(
                     sql!(
                         DELETE FROM user_toolchains WHERE workspace_id = ?1;
@@ -1021,19 +1134,24 @@ impl WorkspaceDb {
                     }
                 }
 
-                conn.exec_bound(sql!(
-                    DELETE
-                    FROM workspaces
-                    WHERE
-                        workspace_id != ?1 AND
-                        paths IS ?2 AND
-                        remote_connection_id IS ?3
-                ))?((
-                    workspace.id,
-                    paths.paths.clone(),
-                    remote_connection_id,
-                ))
-                .context("clearing out old locations")?;
+                // Each window without folders keeps its own workspace, so that the scratch
+                // buffers of every such window are restored.
+                let is_scratch = paths.paths.is_empty() && remote_connection_id.is_none();
+                if !is_scratch {
+                    conn.exec_bound(sql!(
+                        DELETE
+                        FROM workspaces
+                        WHERE
+                            workspace_id != ?1 AND
+                            paths IS ?2 AND
+                            remote_connection_id IS ?3
+                    ))?((
+                        workspace.id,
+                        paths.paths.clone(),
+                        remote_connection_id,
+                    ))
+                    .context("clearing out old locations")?;
+                }
 
                 // Upsert
                 let query = sql!(
@@ -1209,12 +1327,20 @@ impl WorkspaceDb {
     fn session_workspaces(
         &self,
         session_id: String,
-    ) -> Result<Vec<(PathList, Option<u64>, Option<RemoteConnectionId>)>> {
+    ) -> Result<
+        Vec<(
+            WorkspaceId,
+            PathList,
+            Option<u64>,
+            Option<RemoteConnectionId>,
+        )>,
+    > {
         Ok(self
             .session_workspaces_query(session_id)?
             .into_iter()
-            .map(|(paths, order, window_id, remote_connection_id)| {
+            .map(|(id, paths, order, window_id, remote_connection_id)| {
                 (
+                    id,
                     PathList::deserialize(&SerializedPathList { paths, order }),
                     window_id,
                     remote_connection_id.map(RemoteConnectionId),
@@ -1224,8 +1350,8 @@ impl WorkspaceDb {
     }
 
     query! {
-        fn session_workspaces_query(session_id: String) -> Result<Vec<(String, String, Option<u64>, Option<u64>)>> {
-            SELECT paths, paths_order, window_id, remote_connection_id
+        fn session_workspaces_query(session_id: String) -> Result<Vec<(WorkspaceId, String, String, Option<u64>, Option<u64>)>> {
+            SELECT workspace_id, paths, paths_order, window_id, remote_connection_id
             FROM workspaces
             WHERE session_id = ?1
             ORDER BY timestamp DESC
@@ -1347,6 +1473,12 @@ impl WorkspaceDb {
                 continue;
             }
 
+            // The workspace of a window without folders only holds unsaved scratch buffers,
+            // which are restored with the session rather than reopened from the recent list.
+            if paths.is_empty() {
+                continue;
+            }
+
             let has_wsl_path = if cfg!(windows) {
                 fn is_wsl_path(path: &PathBuf) -> bool {
                     use std::path::{Component, Prefix};
@@ -1392,13 +1524,10 @@ impl WorkspaceDb {
         Ok(result)
     }
 
-    pub async fn last_workspace(&self) -> Result<Option<(SerializedWorkspaceLocation, PathList)>> {
-        Ok(self
-            .recent_workspaces_on_disk()
-            .await?
-            .into_iter()
-            .next()
-            .map(|(_, location, paths)| (location, paths)))
+    pub async fn last_workspace(
+        &self,
+    ) -> Result<Option<(WorkspaceId, SerializedWorkspaceLocation, PathList)>> {
+        Ok(self.recent_workspaces_on_disk().await?.into_iter().next())
     }
 
     // Returns the locations of the workspaces that were still opened when the last
@@ -1409,24 +1538,27 @@ impl WorkspaceDb {
         &self,
         last_session_id: &str,
         last_session_window_stack: Option<Vec<WindowId>>,
-    ) -> Result<Vec<(SerializedWorkspaceLocation, PathList)>> {
+    ) -> Result<Vec<(WorkspaceId, SerializedWorkspaceLocation, PathList)>> {
         let mut workspaces = Vec::new();
 
-        for (paths, window_id, remote_connection_id) in
+        for (id, paths, window_id, remote_connection_id) in
             self.session_workspaces(last_session_id.to_owned())?
         {
             if let Some(remote_connection_id) = remote_connection_id {
                 workspaces.push((
+                    id,
                     SerializedWorkspaceLocation::Remote(
                         self.remote_connection(remote_connection_id)?,
                     ),
                     paths,
                     window_id.map(WindowId::from),
                 ));
-            } else if paths.paths().iter().all(|path| path.exists())
-                && paths.paths().iter().any(|path| path.is_dir())
+            } else if paths.is_empty()
+                || (paths.paths().iter().all(|path| path.exists())
+                    && paths.paths().iter().any(|path| path.is_dir()))
             {
                 workspaces.push((
+                    id,
                     SerializedWorkspaceLocation::Local,
                     paths,
                     window_id.map(WindowId::from),
@@ -1435,7 +1567,7 @@ impl WorkspaceDb {
         }
 
         if let Some(stack) = last_session_window_stack {
-            workspaces.sort_by_key(|(_, _, window_id)| {
+            workspaces.sort_by_key(|(_, _, _, window_id)| {
                 window_id
                     .and_then(|id| stack.iter().position(|&order_id| order_id == id))
                     .unwrap_or(usize::MAX)
@@ -1444,7 +1576,7 @@ impl WorkspaceDb {
 
         Ok(workspaces
             .into_iter()
-            .map(|(location, paths, _)| (location, paths))
+            .map(|(id, location, paths, _)| (id, location, paths))
             .collect::<Vec<_>>())
     }
 
@@ -1675,6 +1807,16 @@ impl WorkspaceDb {
         }
     }
 
+    query! {
+        pub(crate) async fn delete_detached_scratch_workspaces() -> Result<()> {
+            DELETE FROM workspaces
+            WHERE
+                length(paths) = 0 AND
+                remote_connection_id IS NULL AND
+                session_id IS NULL
+        }
+    }
+
     pub async fn toolchain(
         &self,
         workspace_id: WorkspaceId,
@@ -1836,6 +1978,52 @@ mod tests {
     use remote::SshConnectionOptions;
     use std::{thread, time::Duration};
 
+    #[gpui::test]
+    async fn test_bookmarks() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_bookmarks").await;
+        let id = db.next_id().await.unwrap();
+
+        let first_path: Arc<Path> = Arc::from(Path::new("/tmp/a.rs"));
+        let second_path: Arc<Path> = Arc::from(Path::new("/tmp/b.rs"));
+        let bookmark = |path: &Arc<Path>, row| SourceBookmark {
+            row,
+            path: path.clone(),
+        };
+
+        let mut workspace = SerializedWorkspace {
+            id,
+            paths: PathList::new(&["/tmp"]),
+            location: SerializedWorkspaceLocation::Local,
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            bookmarks: collections::BTreeMap::from_iter([
+                (
+                    first_path.clone(),
+                    vec![bookmark(&first_path, 3), bookmark(&first_path, 17)],
+                ),
+                (second_path.clone(), vec![bookmark(&second_path, 0)]),
+            ]),
+            session_id: None,
+            window_id: None,
+            user_toolchains: Default::default(),
+        };
+
+        db.save_workspace(workspace.clone()).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.bookmarks, workspace.bookmarks);
+
+        workspace.bookmarks.remove(&first_path);
+        db.save_workspace(workspace.clone()).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.bookmarks, workspace.bookmarks);
+    }
+
     #[gpui::test]
     async fn test_breakpoints() {
         zlog::init_test();
@@ -1945,6 +2133,7 @@ mod tests {
             },
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2066,6 +2255,7 @@ mod tests {
             },
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2100,6 +2290,7 @@ mod tests {
             breakpoints: collections::BTreeMap::default(),
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2198,6 +2389,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2213,6 +2405,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2320,6 +2513,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(999),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2354,6 +2548,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(1),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2369,6 +2564,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2411,6 +2607,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(3),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2449,6 +2646,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2464,6 +2662,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2479,6 +2678,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2494,6 +2694,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2520,6 +2721,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2535,6 +2737,7 @@ mod tests {
             centered_layout: false,
             session_id: Some("session-id-3".to_owned()),
             window_id: Some(60),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2587,6 +2790,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         }
     }
@@ -2622,6 +2826,7 @@ mod tests {
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .collect::<Vec<_>>();
@@ -2641,7 +2846,10 @@ mod tests {
 
         let locations = db
             .last_session_workspace_locations("one-session", stack)
-            .unwrap();
+            .unwrap()
+            .into_iter()
+            .map(|(_, location, paths)| (location, paths))
+            .collect::<Vec<_>>();
         assert_eq!(
             locations,
             [
@@ -2673,6 +2881,63 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_last_session_scratch_workspaces() {
+        let db =
+            WorkspaceDb::open_test_db("test_serializing_workspaces_last_session_scratch").await;
+
+        // Two windows without folders, each with its own scratch buffer.
+        let scratch_pane = |item_id| {
+            SerializedPaneGroup::Pane(SerializedPane::new(
+                vec![SerializedItem::new("Editor", item_id, true, false)],
+                true,
+                0,
+            ))
+        };
+        for (workspace_id, window_id, item_id) in [(1, 10, 100), (2, 20, 200)] {
+            db.save_workspace(SerializedWorkspace {
+                id: WorkspaceId(workspace_id),
+                session_id: Some("one-session".to_owned()),
+                window_id: Some(window_id),
+                ..default_workspace(&[] as &[&str], &scratch_pane(item_id))
+            })
+            .await;
+        }
+        // Saving one window again doesn't replace the other.
+        db.save_workspace(SerializedWorkspace {
+            id: WorkspaceId(1),
+            session_id: Some("one-session".to_owned()),
+            window_id: Some(10),
+            ..default_workspace(&[] as &[&str], &scratch_pane(100))
+        })
+        .await;
+
+        let mut locations = db
+            .last_session_workspace_locations("one-session", None)
+            .unwrap();
+        locations.sort_by_key(|(id, _, _)| *id);
+        assert_eq!(
+            locations,
+            [
+                (
+                    WorkspaceId(1),
+                    SerializedWorkspaceLocation::Local,
+                    PathList::default()
+                ),
+                (
+                    WorkspaceId(2),
+                    SerializedWorkspaceLocation::Local,
+                    PathList::default()
+                ),
+            ]
+        );
+        for (workspace_id, item_id) in [(1, 100), (2, 200)] {
+            let workspace = db.workspace_for_id(WorkspaceId(workspace_id)).unwrap();
+            assert_eq!(workspace.center_group, scratch_pane(item_id));
+        }
+        assert!(db.recent_workspaces_on_disk().await.unwrap().is_empty());
+    }
+
     #[gpui::test]
     async fn test_last_session_workspace_locations_remote() {
         let db =
@@ -2720,6 +2985,7 @@ mod tests {
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .collect::<Vec<_>>();
@@ -2737,7 +3003,10 @@ mod tests {
 
         let have = db
             .last_session_workspace_locations("one-session", stack)
-            .unwrap();
+            .unwrap()
+            .into_iter()
+            .map(|(_, location, paths)| (location, paths))
+            .collect::<Vec<_>>();
         assert_eq!(have.len(), 4);
         assert_eq!(
             have[0],
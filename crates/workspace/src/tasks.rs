@@ -1,7 +1,7 @@
 use std::process::ExitStatus;
 
 use anyhow::Result;
-use gpui::{AppContext, Context, Entity, Task};
+use gpui::{App, AppContext, Context, Entity, Task};
 use language::Buffer;
 use project::{TaskSourceKind, WorktreeId};
 use remote::ConnectionState;
@@ -54,6 +54,28 @@ impl Workspace {
         window: &mut Window,
         cx: &mut Context<Workspace>,
     ) {
+        let task = self.schedule_resolved_task_with_status(
+            task_source_kind,
+            resolved_task,
+            omit_history,
+            window,
+            cx,
+        );
+        self.scheduled_tasks.push(cx.spawn(async move |_, _| {
+            task.await;
+        }));
+    }
+
+    /// Like [`Workspace::schedule_resolved_task`], but returns whether the task succeeded once
+    /// it finishes, or `None` if it could not be spawned or got cancelled.
+    pub fn schedule_resolved_task_with_status(
+        self: &mut Workspace,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        omit_history: bool,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Option<bool>> {
         let spawn_in_terminal = resolved_task.resolved.clone();
         if !omit_history {
             if let Some(debugger_provider) = self.debugger_provider.as_ref() {
@@ -71,31 +93,48 @@ impl Workspace {
             });
         }
 
-        if let Some(terminal_provider) = self.terminal_provider.as_ref() {
-            let task_status = terminal_provider.spawn(spawn_in_terminal, window, cx);
+        let Some(terminal_provider) = self.terminal_provider.as_ref() else {
+            return Task::ready(None);
+        };
+        let task_status = terminal_provider.spawn(spawn_in_terminal, window, cx);
 
-            let task = cx.spawn(async |w, cx| {
-                let res = cx.background_spawn(task_status).await;
-                match res {
-                    Some(Ok(status)) => {
-                        if status.success() {
-                            log::debug!("Task spawn succeeded");
-                        } else {
-                            log::debug!("Task spawn failed, code: {:?}", status.code());
-                        }
-                    }
-                    Some(Err(e)) => {
-                        log::error!("Task spawn failed: {e:#}");
-                        _ = w.update(cx, |w, cx| {
-                            let id = NotificationId::unique::<ResolvedTask>();
-                            w.show_toast(Toast::new(id, format!("Task spawn failed: {e}")), cx);
-                        })
+        cx.spawn(async |w, cx| {
+            let res = cx.background_spawn(task_status).await;
+            match res {
+                Some(Ok(status)) => {
+                    if status.success() {
+                        log::debug!("Task spawn succeeded");
+                    } else {
+                        log::debug!("Task spawn failed, code: {:?}", status.code());
                     }
-                    None => log::debug!("Task spawn got cancelled"),
-                };
-            });
-            self.scheduled_tasks.push(task);
-        }
+                    Some(status.success())
+                }
+                Some(Err(e)) => {
+                    log::error!("Task spawn failed: {e:#}");
+                    _ = w.update(cx, |w, cx| {
+                        let id = NotificationId::unique::<ResolvedTask>();
+                        w.show_toast(Toast::new(id, format!("Task spawn failed: {e}")), cx);
+                    });
+                    None
+                }
+                None => {
+                    log::debug!("Task spawn got cancelled");
+                    None
+                }
+            }
+        })
+    }
+
+    /// Shows the terminal running or last ran `task`, returning whether one was found.
+    pub fn reveal_task_output(
+        &self,
+        task: &SpawnInTerminal,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> bool {
+        self.terminal_provider
+            .as_ref()
+            .is_some_and(|terminal_provider| terminal_provider.reveal(task, window, cx))
     }
 
     pub fn start_debug_session(
@@ -73,6 +73,7 @@ use postage::stream::Stream;
 use project::{
     DirectoryLister, Project, ProjectEntryId, ProjectPath, ResolvedPath, Worktree, WorktreeId,
     WorktreeSettings,
+    bookmark_store::BookmarkStoreEvent,
     debugger::{breakpoint_store::BreakpointStoreEvent, session::ThreadStatus},
     toolchain_store::ToolchainStoreEvent,
 };
@@ -149,6 +150,9 @@ pub trait TerminalProvider {
         window: &mut Window,
         cx: &mut App,
     ) -> Task<Option<Result<ExitStatus>>>;
+
+    /// Shows the terminal running or last ran `task`, returning whether one was found.
+    fn reveal(&self, task: &SpawnInTerminal, window: &mut Window, cx: &mut App) -> bool;
 }
 
 pub trait DebuggerProvider {
@@ -1145,6 +1149,7 @@ pub struct Workspace {
     _schedule_serialize_workspace: Option<Task<()>>,
     _schedule_serialize_ssh_paths: Option<Task<()>>,
     pane_history_timestamp: Arc<AtomicUsize>,
+    jump_list: JumpList,
     bounds: Bounds<Pixels>,
     pub centered_layout: bool,
     bounds_save_task_queued: Option<Task<()>>,
@@ -1284,6 +1289,17 @@ impl Workspace {
             },
         )
         .detach();
+        cx.subscribe_in(
+            &project.read(cx).bookmark_store(),
+            window,
+            |workspace, _, event, window, cx| match event {
+                BookmarkStoreEvent::BookmarksUpdated(_)
+                | BookmarkStoreEvent::BookmarksCleared(_) => {
+                    workspace.serialize_workspace(window, cx);
+                }
+            },
+        )
+        .detach();
         if let Some(toolchain_store) = project.read(cx).toolchain_store() {
             cx.subscribe_in(
                 &toolchain_store,
@@ -1306,6 +1322,7 @@ impl Workspace {
 
         let weak_handle = cx.entity().downgrade();
         let pane_history_timestamp = Arc::new(AtomicUsize::new(0));
+        let jump_list = JumpList::default();
 
         let center_pane = cx.new(|cx| {
             let mut center_pane = Pane::new(
@@ -1319,6 +1336,9 @@ impl Workspace {
             );
             center_pane.set_can_split(Some(Arc::new(|_, _, _, _| true)));
             center_pane
+                .nav_history_mut()
+                .set_jump_list(jump_list.clone());
+            center_pane
         });
         cx.subscribe_in(&center_pane, window, Self::handle_pane_event)
             .detach();
@@ -1486,6 +1506,7 @@ impl Workspace {
             leader_updates_tx,
             _subscriptions: subscriptions,
             pane_history_timestamp,
+            jump_list,
             workspace_actions: Default::default(),
             // This data will be incorrect, but it will be overwritten by the time it needs to be used.
             bounds: Default::default(),
@@ -1514,6 +1535,37 @@ impl Workspace {
             WindowHandle<Workspace>,
             Vec<Option<anyhow::Result<Box<dyn ItemHandle>>>>,
         )>,
+    > {
+        Self::open_local(abs_paths, None, app_state, requesting_window, env, cx)
+    }
+
+    /// Opens a window without folders, restoring the scratch buffers of the serialized workspace
+    /// with the given id, which belonged to such a window in the previous session.
+    pub fn restore_scratch(
+        workspace_id: WorkspaceId,
+        app_state: Arc<AppState>,
+        cx: &mut App,
+    ) -> Task<
+        anyhow::Result<(
+            WindowHandle<Workspace>,
+            Vec<Option<anyhow::Result<Box<dyn ItemHandle>>>>,
+        )>,
+    > {
+        Self::open_local(Vec::new(), Some(workspace_id), app_state, None, None, cx)
+    }
+
+    fn open_local(
+        abs_paths: Vec<PathBuf>,
+        scratch_workspace_id: Option<WorkspaceId>,
+        app_state: Arc<AppState>,
+        requesting_window: Option<WindowHandle<Workspace>>,
+        env: Option<HashMap<String, String>>,
+        cx: &mut App,
+    ) -> Task<
+        anyhow::Result<(
+            WindowHandle<Workspace>,
+            Vec<Option<anyhow::Result<Box<dyn ItemHandle>>>>,
+        )>,
     > {
         let project_handle = Project::local(
             app_state.client.clone(),
@@ -1535,8 +1587,15 @@ impl Workspace {
                 }
             }
 
-            let serialized_workspace =
-                persistence::DB.workspace_for_roots(paths_to_open.as_slice());
+            // Each window without folders has its own serialized workspace, holding its scratch
+            // buffers, which is only restored with the session.
+            let serialized_workspace = if let Some(workspace_id) = scratch_workspace_id {
+                persistence::DB.workspace_for_id(workspace_id)
+            } else if paths_to_open.is_empty() {
+                None
+            } else {
+                persistence::DB.workspace_for_roots(paths_to_open.as_slice())
+            };
 
             if let Some(paths) = serialized_workspace.as_ref().map(|ws| &ws.paths) {
                 paths_to_open = paths.ordered_paths().cloned().collect();
@@ -2049,6 +2108,70 @@ impl Workspace {
         self.navigate_history(pane, NavigationMode::GoingForward, window, cx)
     }
 
+    /// Restores the file and cursor position of the next entry in the jump list in the
+    /// direction of `mode`.
+    pub fn navigate_jump_list(
+        &mut self,
+        mode: NavigationMode,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Task<Result<()>> {
+        let active_item = self.active_item(cx);
+        let (entry, pane) = loop {
+            let Some(entry) = self.jump_list.step(mode, || {
+                if let Some(item) = active_item.as_ref() {
+                    item.push_jump_location(cx);
+                }
+            }) else {
+                return Task::ready(Ok(()));
+            };
+            if let Some(pane) = entry.pane.upgrade() {
+                break (entry, pane);
+            }
+        };
+
+        let data = entry.data();
+        self.jump_list.set_navigating(true);
+        if let Some(item) = entry.item.upgrade()
+            && let Some(index) = pane.read(cx).index_for_item(item.as_ref())
+        {
+            window.focus(&pane.focus_handle(cx));
+            pane.update(cx, |pane, cx| {
+                pane.activate_item(index, true, true, window, cx)
+            });
+            item.navigate(data, window, cx);
+            self.jump_list.set_navigating(false);
+            return Task::ready(Ok(()));
+        }
+
+        // If the item is no longer present in its pane, then reopen it from its previous path.
+        let Some((project_path, _)) = pane.read(cx).nav_history().path_for_item(entry.item.id())
+        else {
+            self.jump_list.set_navigating(false);
+            return self.navigate_jump_list(mode, window, cx);
+        };
+        let open_item = self.open_path_preview(
+            project_path,
+            Some(pane.downgrade()),
+            true,
+            entry.is_preview,
+            true,
+            window,
+            cx,
+        );
+        let jump_list = self.jump_list.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let result = match open_item.await {
+                Ok(item) => cx.update(|window, cx| {
+                    item.navigate(data, window, cx);
+                }),
+                Err(error) => Err(error),
+            };
+            jump_list.set_navigating(false);
+            result
+        })
+    }
+
     pub fn reopen_closed_item(
         &mut self,
         window: &mut Window,
@@ -3222,6 +3345,7 @@ impl Workspace {
                 cx,
             );
             pane.set_can_split(Some(Arc::new(|_, _, _, _| true)));
+            pane.nav_history_mut().set_jump_list(self.jump_list.clone());
             pane
         });
         cx.subscribe_in(&pane, window, Self::handle_pane_event)
@@ -5340,6 +5464,12 @@ impl Workspace {
                         .read(cx)
                         .all_source_breakpoints(cx)
                 });
+                let bookmarks = self
+                    .project
+                    .read(cx)
+                    .bookmark_store()
+                    .read(cx)
+                    .all_source_bookmarks(cx);
                 let user_toolchains = self
                     .project
                     .read(cx)
@@ -5361,6 +5491,7 @@ impl Workspace {
                     centered_layout: self.centered_layout,
                     session_id: self.session_id.clone(),
                     breakpoints,
+                    bookmarks,
                     window_id: Some(window.window_handle().window_id().as_u64()),
                     user_toolchains,
                 };
@@ -5374,6 +5505,12 @@ impl Workspace {
                     .set_session_id(database_id, None)
                     .await
                     .log_err();
+                // Windows without folders are only serialized for their scratch buffers, which
+                // aren't restored outside of a session.
+                persistence::DB
+                    .delete_detached_scratch_workspaces()
+                    .await
+                    .log_err();
             }),
             WorkspaceLocation::None => Task::ready(()),
         }
@@ -5384,7 +5521,9 @@ impl Workspace {
         if let Some(connection) = self.project.read(cx).remote_connection_options(cx) {
             WorkspaceLocation::Location(SerializedWorkspaceLocation::Remote(connection), paths)
         } else if self.project.read(cx).is_local() {
-            if !paths.is_empty() {
+            // A window without folders is only serialized to restore its scratch buffers with
+            // the session, under its own workspace id.
+            if !paths.is_empty() || (self.session_id.is_some() && self.has_scratch_items(cx)) {
                 WorkspaceLocation::Location(SerializedWorkspaceLocation::Local, paths)
             } else {
                 WorkspaceLocation::DetachFromSession
@@ -5394,6 +5533,15 @@ impl Workspace {
         }
     }
 
+    /// Whether any pane holds an unsaved buffer that was never saved to a file.
+    fn has_scratch_items(&self, cx: &App) -> bool {
+        self.panes.iter().any(|pane| {
+            pane.read(cx)
+                .items()
+                .any(|item| item.project_path(cx).is_none() && item.is_dirty(cx))
+        })
+    }
+
     fn update_history(&self, cx: &mut App) {
         let Some(id) = self.database_id() else {
             return;
@@ -5548,6 +5696,14 @@ impl Workspace {
                 })?
                 .await;
 
+            let _ = project
+                .update(cx, |project, cx| {
+                    project.bookmark_store().update(cx, |bookmark_store, cx| {
+                        bookmark_store.with_serialized_bookmarks(serialized_workspace.bookmarks, cx)
+                    })
+                })?
+                .await;
+
             // Clean up all the items that have _not_ been loaded. Our ItemIds aren't stable. That means
             // after loading the items, we might have different items and in order to avoid
             // the database filling up, we delete items that haven't been loaded now.
@@ -6908,14 +7064,15 @@ impl WorkspaceHandle for Entity<Workspace> {
     }
 }
 
-pub async fn last_opened_workspace_location() -> Option<(SerializedWorkspaceLocation, PathList)> {
+pub async fn last_opened_workspace_location()
+-> Option<(WorkspaceId, SerializedWorkspaceLocation, PathList)> {
     DB.last_workspace().await.log_err().flatten()
 }
 
 pub fn last_session_workspace_locations(
     last_session_id: &str,
     last_session_window_stack: Option<Vec<WindowId>>,
-) -> Option<Vec<(SerializedWorkspaceLocation, PathList)>> {
+) -> Option<Vec<(WorkspaceId, SerializedWorkspaceLocation, PathList)>> {
     DB.last_session_workspace_locations(last_session_id, last_session_window_stack)
         .log_err()
 }
@@ -226,7 +226,11 @@ impl Settings for TabBarSettings {
 pub struct StatusBarSettings {
     pub show: bool,
     pub active_language_button: bool,
+    pub indentation_button: bool,
+    pub encoding_button: bool,
+    pub line_ending_button: bool,
     pub cursor_position_button: bool,
+    pub selection_number_stats: bool,
 }
 
 impl Settings for StatusBarSettings {
@@ -235,7 +239,11 @@ impl Settings for StatusBarSettings {
         StatusBarSettings {
             show: status_bar.show.unwrap(),
             active_language_button: status_bar.active_language_button.unwrap(),
+            indentation_button: status_bar.indentation_button.unwrap(),
+            encoding_button: status_bar.encoding_button.unwrap(),
+            line_ending_button: status_bar.line_ending_button.unwrap(),
             cursor_position_button: status_bar.cursor_position_button.unwrap(),
+            selection_number_stats: status_bar.selection_number_stats.unwrap(),
         }
     }
 
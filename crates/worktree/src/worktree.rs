@@ -62,7 +62,7 @@ use std::{
     time::{Duration, Instant},
 };
 use sum_tree::{Bias, Dimensions, Edit, KeyedItem, SeekTarget, SumTree, Summary, TreeMap, TreeSet};
-use text::{LineEnding, Rope};
+use text::{Encoding, LineEnding, Rope};
 use util::{
     ResultExt, debug_panic,
     paths::{PathMatcher, PathStyle, SanitizedPath, home_dir},
@@ -100,6 +100,8 @@ pub enum CreatedEntry {
 pub struct LoadedFile {
     pub file: Arc<File>,
     pub text: String,
+    /// The encoding that the file's contents were detected to have, and decoded from.
+    pub encoding: Encoding,
 }
 
 pub struct LoadedBinaryFile {
@@ -729,10 +731,11 @@ impl Worktree {
         path: Arc<RelPath>,
         text: Rope,
         line_ending: LineEnding,
+        encoding: Encoding,
         cx: &Context<Worktree>,
     ) -> Task<Result<Arc<File>>> {
         match self {
-            Worktree::Local(this) => this.write_file(path, text, line_ending, cx),
+            Worktree::Local(this) => this.write_file(path, text, line_ending, encoding, cx),
             Worktree::Remote(_) => {
                 Task::ready(Err(anyhow!("remote worktree can't yet write files")))
             }
@@ -1332,7 +1335,7 @@ impl LocalWorktree {
                     anyhow::bail!("File is too large to load");
                 }
             }
-            let text = fs.load(&abs_path).await?;
+            let (text, encoding) = Encoding::detect_and_decode(fs.load_bytes(&abs_path).await?)?;
 
             let worktree = this.upgrade().context("worktree was dropped")?;
             let file = match entry.await? {
@@ -1360,7 +1363,11 @@ impl LocalWorktree {
                 }
             };
 
-            Ok(LoadedFile { file, text })
+            Ok(LoadedFile {
+                file,
+                text,
+                encoding,
+            })
         })
     }
 
@@ -1443,6 +1450,7 @@ impl LocalWorktree {
         path: Arc<RelPath>,
         text: Rope,
         line_ending: LineEnding,
+        encoding: Encoding,
         cx: &Context<Worktree>,
     ) -> Task<Result<Arc<File>>> {
         let fs = self.fs.clone();
@@ -1452,7 +1460,14 @@ impl LocalWorktree {
         let write = cx.background_spawn({
             let fs = fs.clone();
             let abs_path = abs_path.clone();
-            async move { fs.save(&abs_path, &text, line_ending).await }
+            async move {
+                if encoding == Encoding::Utf8 {
+                    fs.save(&abs_path, &text, line_ending).await
+                } else {
+                    let text = text.to_string().replace('\n', line_ending.as_str());
+                    fs.write(&abs_path, &encoding.encode(&text)?).await
+                }
+            }
         });
 
         cx.spawn(async move |this, cx| {
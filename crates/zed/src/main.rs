@@ -43,8 +43,8 @@ use theme::{ActiveTheme, GlobalTheme, ThemeRegistry};
 use util::{ResultExt, TryFutureExt, maybe};
 use uuid::Uuid;
 use workspace::{
-    AppState, PathList, SerializedWorkspaceLocation, Toast, Workspace, WorkspaceSettings,
-    WorkspaceStore, notifications::NotificationId,
+    AppState, PathList, SerializedWorkspaceLocation, Toast, Workspace, WorkspaceId,
+    WorkspaceSettings, WorkspaceStore, notifications::NotificationId,
 };
 use zed::{
     OpenListener, OpenRequest, RawOpenRequest, app_menus, build_window_options,
@@ -599,18 +599,23 @@ pub fn main() {
 
         go_to_line::init(cx);
         file_finder::init(cx);
+        file_history::init(app_state.fs.clone(), cx);
         tab_switcher::init(cx);
         outline::init(cx);
+        clipboard_history::init(cx);
         project_symbols::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
         tasks_ui::init(cx);
         snippets_ui::init(cx);
+        spellcheck::init(app_state.fs.clone(), cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
         search::init(cx);
         vim::init(cx);
         terminal_view::init(cx);
         journal::init(app_state.clone(), cx);
+        indentation_selector::init(cx);
+        encoding_selector::init(cx);
         language_selector::init(cx);
         line_ending_selector::init(cx);
         toolchain_selector::init(cx);
@@ -1015,11 +1020,17 @@ async fn restore_or_create_workspace(app_state: Arc<AppState>, cx: &mut AsyncApp
         let mut results: Vec<Result<(), Error>> = Vec::new();
         let mut tasks = Vec::new();
 
-        for (index, (location, paths)) in locations.into_iter().enumerate() {
+        for (index, (workspace_id, location, paths)) in locations.into_iter().enumerate() {
             match location {
                 SerializedWorkspaceLocation::Local => {
                     let app_state = app_state.clone();
                     let task = cx.spawn(async move |cx| {
+                        if paths.is_empty() {
+                            let open_task = cx.update(|cx| {
+                                Workspace::restore_scratch(workspace_id, app_state, cx)
+                            })?;
+                            return open_task.await.map(|_| ());
+                        }
                         let open_task = cx.update(|cx| {
                             workspace::open_paths(
                                 &paths.paths(),
@@ -1135,7 +1146,7 @@ async fn restore_or_create_workspace(app_state: Arc<AppState>, cx: &mut AsyncApp
 pub(crate) async fn restorable_workspace_locations(
     cx: &mut AsyncApp,
     app_state: &Arc<AppState>,
-) -> Option<Vec<(SerializedWorkspaceLocation, PathList)>> {
+) -> Option<Vec<(WorkspaceId, SerializedWorkspaceLocation, PathList)>> {
     let mut restore_behavior = cx
         .update(|cx| WorkspaceSettings::get(None, cx).restore_on_startup)
         .ok()?;
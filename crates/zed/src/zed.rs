@@ -18,8 +18,8 @@ use breadcrumbs::Breadcrumbs;
 use client::zed_urls;
 use collections::VecDeque;
 use debugger_ui::debugger_panel::DebugPanel;
-use editor::ProposedChangesEditorToolbar;
 use editor::{Editor, MultiBuffer};
+use editor::{LargeFileBanner, ProposedChangesEditorToolbar};
 use extension_host::ExtensionStore;
 use feature_flags::{FeatureFlagAppExt, PanicFeatureFlag};
 use fs::Fs;
@@ -398,6 +398,12 @@ pub fn initialize_workspace(
             window,
             cx,
         );
+        let active_buffer_indentation =
+            cx.new(|_| indentation_selector::ActiveBufferIndentation::new(workspace));
+        let active_buffer_encoding =
+            cx.new(|_| encoding_selector::ActiveBufferEncoding::new(workspace));
+        let active_buffer_line_ending =
+            cx.new(|_| line_ending_selector::ActiveBufferLineEnding::new(workspace));
         let active_buffer_language =
             cx.new(|_| language_selector::ActiveBufferLanguage::new(workspace));
         let active_toolchain_language =
@@ -416,15 +422,22 @@ pub fn initialize_workspace(
 
         let cursor_position =
             cx.new(|_| go_to_line::cursor_position::CursorPosition::new(workspace));
+        let csv_column_indicator = cx.new(|_| editor::CsvColumnIndicator::default());
+        let read_only_indicator = cx.new(|_| editor::ReadOnlyIndicator::default());
         workspace.status_bar().update(cx, |status_bar, cx| {
             status_bar.add_left_item(search_button, window, cx);
             status_bar.add_left_item(lsp_button, window, cx);
             status_bar.add_left_item(diagnostic_summary, window, cx);
             status_bar.add_left_item(activity_indicator, window, cx);
             status_bar.add_right_item(edit_prediction_button, window, cx);
+            status_bar.add_right_item(read_only_indicator, window, cx);
+            status_bar.add_right_item(active_buffer_indentation, window, cx);
+            status_bar.add_right_item(active_buffer_encoding, window, cx);
+            status_bar.add_right_item(active_buffer_line_ending, window, cx);
             status_bar.add_right_item(active_buffer_language, window, cx);
             status_bar.add_right_item(active_toolchain_language, window, cx);
             status_bar.add_right_item(vim_mode_indicator, window, cx);
+            status_bar.add_right_item(csv_column_indicator, window, cx);
             status_bar.add_right_item(cursor_position, window, cx);
             status_bar.add_right_item(image_info, window, cx);
         });
@@ -1028,6 +1041,8 @@ fn initialize_pane(
                 )
             });
             toolbar.add_item(buffer_search_bar.clone(), window, cx);
+            let large_file_banner = cx.new(|_| LargeFileBanner::new());
+            toolbar.add_item(large_file_banner, window, cx);
             let proposed_change_bar = cx.new(|_| ProposedChangesEditorToolbar::new());
             toolbar.add_item(proposed_change_bar, window, cx);
             let quick_action_bar =
@@ -2,6 +2,7 @@ use client::{Client, UserStore};
 use codestral::CodestralCompletionProvider;
 use collections::HashMap;
 use copilot::{Copilot, CopilotCompletionProvider};
+use edit_prediction::{InlineCompletionRegistry, InlineCompletionSourceProvider};
 use editor::Editor;
 use gpui::{AnyWindowHandle, App, AppContext as _, Context, Entity, WeakEntity};
 use language::language_settings::{EditPredictionProvider, all_language_settings};
@@ -58,6 +59,10 @@ pub fn init(client: Arc<Client>, user_store: Entity<UserStore>, cx: &mut App) {
     cx.on_action(clear_zeta_edit_history);
 
     let mut provider = all_language_settings(None, cx).edit_predictions.provider;
+    let mut custom_provider = all_language_settings(None, cx)
+        .edit_predictions
+        .custom_provider
+        .clone();
     cx.subscribe(&user_store, {
         let editors = editors.clone();
         let client = client.clone();
@@ -70,10 +75,31 @@ pub fn init(client: Arc<Client>, user_store: Entity<UserStore>, cx: &mut App) {
     })
     .detach();
 
+    cx.observe_global::<InlineCompletionRegistry>({
+        let editors = editors.clone();
+        let client = client.clone();
+        let user_store = user_store.clone();
+        move |cx| {
+            let provider = all_language_settings(None, cx).edit_predictions.provider;
+            if provider == EditPredictionProvider::Custom {
+                assign_edit_prediction_providers(
+                    &editors,
+                    provider,
+                    &client,
+                    user_store.clone(),
+                    cx,
+                );
+            }
+        }
+    })
+    .detach();
+
     cx.observe_global::<SettingsStore>({
         let user_store = user_store.clone();
         move |cx| {
-            let new_provider = all_language_settings(None, cx).edit_predictions.provider;
+            let settings = &all_language_settings(None, cx).edit_predictions;
+            let new_provider = settings.provider;
+            let new_custom_provider = settings.custom_provider.clone();
 
             if new_provider != provider {
                 telemetry::event!(
@@ -81,8 +107,11 @@ pub fn init(client: Arc<Client>, user_store: Entity<UserStore>, cx: &mut App) {
                     from = provider,
                     to = new_provider,
                 );
+            }
 
+            if new_provider != provider || new_custom_provider != custom_provider {
                 provider = new_provider;
+                custom_provider = new_custom_provider;
                 assign_edit_prediction_providers(
                     &editors,
                     provider,
@@ -200,6 +229,15 @@ fn assign_edit_prediction_provider(
             let provider = cx.new(|_| CodestralCompletionProvider::new(http_client));
             editor.set_edit_prediction_provider(Some(provider), window, cx);
         }
+        EditPredictionProvider::Custom => {
+            let provider = all_language_settings(None, cx)
+                .edit_predictions
+                .custom_provider
+                .as_deref()
+                .and_then(|name| InlineCompletionRegistry::source(name, cx))
+                .map(|source| cx.new(|_| InlineCompletionSourceProvider::new(source)));
+            editor.set_edit_prediction_provider(provider, window, cx);
+        }
         EditPredictionProvider::Zed => {
             if user_store.read(cx).current_user().is_some() {
                 let mut worktree = None;
@@ -396,6 +396,9 @@ async fn open_workspaces(
             restorable_workspace_locations(cx, &app_state)
                 .await
                 .unwrap_or_default()
+                .into_iter()
+                .map(|(_, location, paths)| (location, paths))
+                .collect()
         }
     } else {
         vec![(
@@ -120,6 +120,7 @@ impl Render for QuickActionBar {
         let inline_diagnostics_enabled = editor_value.show_inline_diagnostics();
         let git_blame_inline_enabled = editor_value.git_blame_inline_enabled();
         let show_git_blame_gutter = editor_value.show_git_blame_gutter();
+        let inline_diff_enabled = editor_value.inline_diff_enabled();
         let auto_signature_help_enabled = editor_value.auto_signature_help_enabled(cx);
         let show_line_numbers = editor_value.line_numbers_enabled(cx);
         let has_edit_prediction_provider = editor_value.edit_prediction_provider().is_some();
@@ -562,6 +563,27 @@ impl Render for QuickActionBar {
                                 },
                             );
 
+                            menu = menu.toggleable_entry(
+                                "Inline Diff",
+                                inline_diff_enabled,
+                                IconPosition::Start,
+                                Some(editor::actions::ToggleInlineDiff.boxed_clone()),
+                                {
+                                    let editor = editor.clone();
+                                    move |window, cx| {
+                                        editor
+                                            .update(cx, |editor, cx| {
+                                                editor.toggle_inline_diff(
+                                                    &editor::actions::ToggleInlineDiff,
+                                                    window,
+                                                    cx,
+                                                )
+                                            })
+                                            .ok();
+                                    }
+                                },
+                            );
+
                             menu = menu.separator();
 
                             menu = menu.toggleable_entry(
@@ -150,7 +150,10 @@ pub mod dev {
         dev,
         [
             /// Toggles the developer inspector for debugging UI elements.
-            ToggleInspector
+            ToggleInspector,
+            /// Toggles an overlay in the editor that measures keystroke latency, frame
+            /// timings and the cost of editor subsystems.
+            ToggleEditorProfiler
         ]
     );
 }